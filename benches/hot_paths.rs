@@ -0,0 +1,117 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// benches/hot_paths.rs
+//! Microbenchmarks of functions every ingested event passes through
+//!
+//! ```text
+//! cargo bench --bench hot_paths
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use serde_json::{json, Value};
+
+use xzepr::domain::canonical_json::canonical_bytes;
+use xzepr::domain::entities::event_receiver::EventReceiver;
+use xzepr::{EventId, EventReceiverId, Ulid};
+
+/// Receiver schema shaped like the demo receivers' with a few more fields
+fn receiver_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "severity": {"type": "string", "enum": ["info", "warning", "error", "critical"]},
+            "duration_ms": {"type": "integer", "minimum": 0},
+            "commit": {"type": "string"},
+            "labels": {"type": "object"}
+        },
+        "required": ["severity"]
+    })
+}
+
+/// Payloads of growing size with keys in non-sorted order
+fn payloads() -> Vec<(&'static str, Value)> {
+    let labels: serde_json::Map<String, Value> = (0..64)
+        .rev()
+        .map(|i| (format!("label-{:02}", i), json!(format!("value-{}", i))))
+        .collect();
+    let steps: Vec<Value> = (0..200)
+        .map(|i| json!({"step": i, "status": "ok", "duration_ms": i * 17, "retries": 0}))
+        .collect();
+
+    vec![
+        ("small", json!({"severity": "info", "duration_ms": 1234})),
+        (
+            "medium",
+            json!({
+                "severity": "warning",
+                "duration_ms": 98_765,
+                "commit": "4f2a9c1e8b7d6a5f4e3d2c1b0a9f8e7d6c5b4a39",
+                "labels": labels,
+            }),
+        ),
+        (
+            "large",
+            json!({"severity": "error", "build": {"steps": steps, "cache": {"hit": false}}}),
+        ),
+    ]
+}
+
+fn bench_fingerprint(c: &mut Criterion) {
+    let schema = receiver_schema();
+    c.bench_function("receiver_fingerprint", |b| {
+        b.iter(|| {
+            EventReceiver::generate_fingerprint(
+                black_box("ci-builds"),
+                black_box("webhook"),
+                black_box("1.0.0"),
+                black_box(&schema),
+            )
+        })
+    });
+}
+
+fn bench_canonical_json(c: &mut Criterion) {
+    let mut group = c.benchmark_group("canonical_json");
+    for (name, payload) in payloads() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &payload, |b, payload| {
+            b.iter(|| canonical_bytes(black_box(payload)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_ulid_parsing(c: &mut Criterion) {
+    let ids: Vec<String> = (0..64).map(|_| Ulid::new().to_string()).collect();
+    let mut group = c.benchmark_group("ulid_parse");
+    group.bench_function("ulid", |b| {
+        b.iter(|| {
+            for id in &ids {
+                black_box(Ulid::from_string(black_box(id)).unwrap());
+            }
+        })
+    });
+    group.bench_function("event_id", |b| {
+        b.iter(|| {
+            for id in &ids {
+                black_box(black_box(id).parse::<EventId>().unwrap());
+            }
+        })
+    });
+    group.bench_function("event_receiver_id", |b| {
+        b.iter(|| {
+            for id in &ids {
+                black_box(black_box(id).parse::<EventReceiverId>().unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    hot_paths,
+    bench_fingerprint,
+    bench_canonical_json,
+    bench_ulid_parsing
+);
+criterion_main!(hot_paths);
@@ -0,0 +1,138 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// benches/ingestion_load.rs
+//! Ingestion load harness
+//!
+//! Runs every ingestion scenario, prints throughput and latency
+//! percentiles, writes a JSON report, and exits non-zero when throughput
+//! regressed against the committed baseline or a scenario has no baseline.
+//!
+//! ```text
+//! cargo bench --features bench --bench ingestion_load
+//! ```
+//!
+//! Load size comes from the `XZEPR_BENCH_*` variables read by
+//! `LoadConfig::from_env`. The runner itself reads:
+//!
+//! * `XZEPR_BENCH_DATABASE_URL` - run against PostgreSQL instead of memory
+//! * `XZEPR_BENCH_BASELINE` - baseline file, `benches/baseline.json` by default
+//! * `XZEPR_BENCH_OUTPUT` - report file, `target/bench/ingestion_load.json`
+//!   by default
+//! * `XZEPR_BENCH_MAX_REGRESSION_PERCENT` - tolerated throughput drop, 10 by
+//!   default
+//! * `XZEPR_BENCH_UPDATE_BASELINE` - set to `1` to write the results to the
+//!   baseline file instead of comparing
+//! * `XZEPR_BENCH_RECORDED_ON` - label stored with an updated baseline
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use xzepr::bench::{
+    Backend, Baseline, HarnessError, LoadConfig, LoadHarness, RunReport, Scenario,
+    DEFAULT_MAX_REGRESSION_PERCENT,
+};
+
+const DEFAULT_BASELINE_PATH: &str = "benches/baseline.json";
+const DEFAULT_OUTPUT_PATH: &str = "target/bench/ingestion_load.json";
+
+fn main() -> ExitCode {
+    // `cargo bench` passes `--bench` and any filter; scenarios are not filtered
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build the Tokio runtime");
+
+    match runtime.block_on(run()) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("ingestion_load: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run() -> Result<ExitCode, HarnessError> {
+    let config = LoadConfig::from_env()?;
+    let backend = match std::env::var("XZEPR_BENCH_DATABASE_URL") {
+        Ok(url) if !url.trim().is_empty() => Backend::postgres(&url).await?,
+        _ => Backend::Memory,
+    };
+    let baseline_path = env_path("XZEPR_BENCH_BASELINE", DEFAULT_BASELINE_PATH);
+    let output_path = env_path("XZEPR_BENCH_OUTPUT", DEFAULT_OUTPUT_PATH);
+    let max_regression_percent = match std::env::var("XZEPR_BENCH_MAX_REGRESSION_PERCENT") {
+        Ok(value) => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|percent| *percent >= 0.0)
+            .ok_or_else(|| HarnessError::InvalidSetting {
+                name: "XZEPR_BENCH_MAX_REGRESSION_PERCENT",
+                message: format!("'{}' is not a non-negative number", value),
+            })?,
+        Err(_) => DEFAULT_MAX_REGRESSION_PERCENT,
+    };
+    let update_baseline = std::env::var("XZEPR_BENCH_UPDATE_BASELINE").is_ok_and(|v| v == "1");
+
+    let harness = LoadHarness::new(config.clone(), backend)?;
+    let results = harness.run_all(&Scenario::matrix()).await?;
+
+    let comparison = if update_baseline {
+        let recorded_on = std::env::var("XZEPR_BENCH_RECORDED_ON").ok();
+        Baseline::from_results(&results, recorded_on).save(&baseline_path)?;
+        println!("Updated baseline {}", baseline_path.display());
+        None
+    } else if baseline_path.exists() {
+        Some(Baseline::load(&baseline_path)?.compare(&results, max_regression_percent))
+    } else {
+        println!(
+            "No baseline at {}; every scenario is unrecorded",
+            baseline_path.display()
+        );
+        Some(Baseline::default().compare(&results, max_regression_percent))
+    };
+
+    let report = RunReport {
+        generated_at: chrono::Utc::now(),
+        backend: harness.backend().name().to_string(),
+        config,
+        results,
+        comparison,
+    };
+    report.save(&output_path)?;
+    print!("{}", report);
+    println!("Wrote {}", output_path.display());
+
+    if report.results.iter().any(|result| result.errors > 0) {
+        eprintln!("Some submissions failed; throughput numbers are not comparable");
+        return Ok(ExitCode::FAILURE);
+    }
+    if let Some(comparison) = &report.comparison {
+        for regression in comparison.regressions() {
+            eprintln!(
+                "{} regressed: {:.0} events/sec against {:.0} in the baseline",
+                regression.name,
+                regression.events_per_sec,
+                regression.baseline_events_per_sec.unwrap_or_default()
+            );
+        }
+        for scenario in comparison.unrecorded() {
+            eprintln!(
+                "{} has no baseline; record one with XZEPR_BENCH_UPDATE_BASELINE=1",
+                scenario.name
+            );
+        }
+        if !comparison.passed() {
+            return Ok(ExitCode::FAILURE);
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn env_path(name: &str, default: &str) -> PathBuf {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| default.to_string())
+        .into()
+}
@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// build.rs
+
+//! Embeds build metadata read by `infrastructure::build_info`
+//!
+//! The git SHA comes from `XZEPR_GIT_SHA` when set (container builds copy
+//! the sources without `.git`) and from `git rev-parse HEAD` otherwise. The
+//! build time honors `SOURCE_DATE_EPOCH` so reproducible builds stay
+//! reproducible.
+
+use std::env;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const UNKNOWN: &str = "unknown";
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    println!("cargo:rerun-if-env-changed=XZEPR_GIT_SHA");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let git_sha = non_empty_env("XZEPR_GIT_SHA")
+        .or_else(|| command_output("git", &["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| UNKNOWN.to_string());
+
+    let build_epoch = non_empty_env("SOURCE_DATE_EPOCH")
+        .and_then(|value| value.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default()
+        });
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version =
+        command_output(&rustc, &["--version"]).unwrap_or_else(|| UNKNOWN.to_string());
+
+    println!("cargo:rustc-env=XZEPR_GIT_SHA={}", git_sha);
+    println!("cargo:rustc-env=XZEPR_BUILD_EPOCH={}", build_epoch);
+    println!("cargo:rustc-env=XZEPR_RUSTC_VERSION={}", rustc_version);
+}
+
+fn non_empty_env(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let trimmed = stdout.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
@@ -0,0 +1,66 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Embedded XZepr Example
+//!
+//! This example mounts XZepr under `/xzepr` in a host service that has
+//! routes of its own, and logs every event XZepr stores.
+//!
+//! # Running the Example
+//!
+//! XZepr reads its settings from `config/` and `XZEPR__` environment
+//! variables like the server does. Data is kept in memory.
+//!
+//! ```bash
+//! export XZEPR__AUTH__JWT__ALGORITHM="HS256"
+//! export XZEPR__AUTH__JWT__SECRET_KEY="a-secret-of-at-least-32-characters!"
+//!
+//! cargo run --example embedded
+//! ```
+//!
+//! Then try:
+//!
+//! ```bash
+//! curl http://localhost:8080/status
+//! curl http://localhost:8080/xzepr/health
+//! open http://localhost:8080/xzepr/graphql/playground
+//! ```
+
+use axum::{routing::get, Router};
+use tokio::signal;
+use xzepr::application::domain_events::DomainEvent;
+use xzepr::{Settings, XzeprBuilder};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt().with_target(false).init();
+
+    let settings = Settings::new()?;
+    let (xzepr, handle) = XzeprBuilder::new(settings).build().await?;
+
+    // React to stored events without going through Kafka
+    let mut domain_events = handle.domain_events().subscribe();
+    tokio::spawn(async move {
+        while let Ok(notification) = domain_events.recv().await {
+            if let DomainEvent::EventCreated { event } = &*notification {
+                println!("XZepr stored event {} ({})", event.id(), event.name());
+            }
+        }
+    });
+
+    let app = Router::new()
+        .route("/status", get(|| async { "host service is up" }))
+        .nest("/xzepr", xzepr);
+
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+    println!("Host listening on http://localhost:8080; XZepr mounted at /xzepr");
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = signal::ctrl_c().await;
+        })
+        .await?;
+
+    // Stop XZepr's background work once requests are drained
+    handle.shutdown().await;
+    Ok(())
+}
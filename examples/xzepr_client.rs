@@ -132,6 +132,14 @@ enum EventCommands {
         /// Event ID (ULID)
         id: String,
     },
+    /// Create the events listed in a JSON file in one request
+    Batch {
+        /// File holding a JSON array of events
+        file: String,
+        /// Quota mode: best_effort or atomic
+        #[arg(long)]
+        quota_mode: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -179,7 +187,7 @@ struct CreateEventReceiverResponse {
     data: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 struct CreateEventRequest {
     name: String,
     version: String,
@@ -197,6 +205,40 @@ struct CreateEventResponse {
     data: String,
 }
 
+#[derive(Debug, Serialize)]
+struct CreateEventBatchRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quota_mode: Option<String>,
+    events: Vec<CreateEventRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchItemResult {
+    index: usize,
+    outcome: String,
+    event_id: Option<String>,
+    duplicate_of: Option<usize>,
+    error: Option<ErrorResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchSummary {
+    total: usize,
+    created: usize,
+    deduplicated: usize,
+    sampled_out: usize,
+    quota_exceeded: usize,
+    validation_failed: usize,
+    publish_deferred: usize,
+    failed: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateEventBatchResponse {
+    results: Vec<BatchItemResult>,
+    summary: BatchSummary,
+}
+
 #[derive(Debug, Serialize)]
 struct CreateEventReceiverGroupRequest {
     name: String,
@@ -447,6 +489,22 @@ impl XzeprClient {
         Ok(result.data)
     }
 
+    async fn create_event_batch(
+        &self,
+        events: Vec<CreateEventRequest>,
+        quota_mode: Option<String>,
+    ) -> Result<CreateEventBatchResponse, Box<dyn Error>> {
+        let request = CreateEventBatchRequest { quota_mode, events };
+
+        let response = self
+            .build_request(reqwest::Method::POST, "/api/v1/events/batch")
+            .json(&request)
+            .send()
+            .await?;
+
+        self.handle_response(response).await
+    }
+
     async fn get_event(&self, id: &str) -> Result<EventResponse, Box<dyn Error>> {
         let response = self
             .build_request(reqwest::Method::GET, &format!("/api/v1/events/{}", id))
@@ -561,6 +619,33 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 let event = client.get_event(&id).await?;
                 println!("{}", serde_json::to_string_pretty(&event)?);
             }
+            EventCommands::Batch { file, quota_mode } => {
+                let events: Vec<CreateEventRequest> =
+                    serde_json::from_str(&std::fs::read_to_string(&file)?)?;
+                let batch = client.create_event_batch(events, quota_mode).await?;
+                for result in &batch.results {
+                    let detail = match (&result.event_id, result.duplicate_of, &result.error) {
+                        (_, _, Some(error)) => error.message.clone(),
+                        (Some(id), Some(first), _) => format!("{} (duplicate of #{})", id, first),
+                        (Some(id), None, _) => id.clone(),
+                        (None, _, None) => String::new(),
+                    };
+                    println!("#{} {} {}", result.index, result.outcome, detail);
+                }
+                let summary = &batch.summary;
+                println!(
+                    "{} events: {} created, {} deduplicated, {} sampled out, {} over quota, \
+                     {} invalid, {} publish deferred, {} failed",
+                    summary.total,
+                    summary.created,
+                    summary.deduplicated,
+                    summary.sampled_out,
+                    summary.quota_exceeded,
+                    summary.validation_failed,
+                    summary.publish_deferred,
+                    summary.failed
+                );
+            }
         },
         Commands::Group { action } => match action {
             GroupCommands::Create {
@@ -0,0 +1,40 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add change feed tracking for receivers and groups
+-- Adds updated_at to event_receivers and tombstone tables that record
+-- deletions so clients can poll for incremental changes.
+
+-- ============================================================================
+-- Step 1: Track modification time on event_receivers
+-- ============================================================================
+ALTER TABLE event_receivers
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW();
+
+-- Existing rows have not changed since they were created
+UPDATE event_receivers SET updated_at = created_at;
+
+-- Change feed ordering is (updated_at, id) with bytewise id comparison
+CREATE INDEX IF NOT EXISTS idx_event_receivers_updated_id
+    ON event_receivers(updated_at, id COLLATE "C");
+CREATE INDEX IF NOT EXISTS idx_event_receiver_groups_updated_id
+    ON event_receiver_groups(updated_at, id COLLATE "C");
+
+-- ============================================================================
+-- Step 2: Tombstones for deleted receivers and groups
+-- ============================================================================
+CREATE TABLE IF NOT EXISTS event_receiver_tombstones (
+    id VARCHAR(26) PRIMARY KEY,
+    deleted_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_receiver_tombstones_deleted_id
+    ON event_receiver_tombstones(deleted_at, id COLLATE "C");
+
+CREATE TABLE IF NOT EXISTS event_receiver_group_tombstones (
+    id VARCHAR(26) PRIMARY KEY,
+    deleted_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_receiver_group_tombstones_deleted_id
+    ON event_receiver_group_tombstones(deleted_at, id COLLATE "C");
@@ -0,0 +1,23 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add event ingestion metadata
+-- Records who submitted each event, through which channel, and from where.
+-- Kept separate from events so it can be restricted to privileged readers.
+
+CREATE TABLE IF NOT EXISTS event_ingestion_meta (
+    event_id TEXT PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    principal_type VARCHAR(32) NOT NULL,
+    principal_id VARCHAR(255) NOT NULL,
+    source VARCHAR(32) NOT NULL,
+    client_ip VARCHAR(45),
+    user_agent VARCHAR(512),
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_ingestion_meta_principal
+    ON event_ingestion_meta(principal_type, principal_id, recorded_at DESC);
+CREATE INDEX IF NOT EXISTS idx_event_ingestion_meta_source
+    ON event_ingestion_meta(source, recorded_at DESC);
+CREATE INDEX IF NOT EXISTS idx_event_ingestion_meta_client_ip
+    ON event_ingestion_meta(client_ip);
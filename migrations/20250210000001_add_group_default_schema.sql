@@ -0,0 +1,9 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add default schema to event receiver groups
+-- Member receivers with an empty schema inherit the default schema of the
+-- single enabled group they belong to.
+
+ALTER TABLE event_receiver_groups
+    ADD COLUMN IF NOT EXISTS default_schema JSONB;
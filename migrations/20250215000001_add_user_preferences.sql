@@ -0,0 +1,15 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add user preferences
+-- Server-stored per-user defaults (page size, time range, export time zone,
+-- notification opt-outs). Keys are validated against an allowlist in the
+-- application before they are written.
+
+CREATE TABLE IF NOT EXISTS user_preferences (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    key VARCHAR(64) NOT NULL,
+    value JSONB NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, key)
+);
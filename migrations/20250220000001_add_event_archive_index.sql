@@ -0,0 +1,18 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add event archive index
+-- Maps events moved to cold storage by the retention job to the archive
+-- segment that holds them. No foreign key to events: the row outlives the
+-- event it points to.
+
+CREATE TABLE IF NOT EXISTS event_archive_index (
+    event_id TEXT PRIMARY KEY,
+    event_receiver_id VARCHAR(255) NOT NULL,
+    segment TEXT NOT NULL,
+    event_created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    archived_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_archive_index_segment
+    ON event_archive_index(segment);
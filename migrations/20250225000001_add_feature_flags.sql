@@ -0,0 +1,14 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add feature flags
+-- Runtime feature flag state set through the admin API. Flags without a row
+-- use the defaults from configuration.
+
+CREATE TABLE IF NOT EXISTS feature_flags (
+    name VARCHAR(64) PRIMARY KEY,
+    enabled BOOLEAN NOT NULL,
+    role_overrides JSONB NOT NULL DEFAULT '{}',
+    updated_by TEXT,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
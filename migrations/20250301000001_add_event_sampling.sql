@@ -0,0 +1,19 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add event sampling
+-- Receivers may store only a fraction of their events. Hourly counters
+-- record how many events each receiver saw versus stored. No foreign key:
+-- counters are kept after a receiver is deleted.
+
+ALTER TABLE event_receivers
+    ADD COLUMN IF NOT EXISTS sample_rate DOUBLE PRECISION
+    CHECK (sample_rate >= 0.0 AND sample_rate <= 1.0);
+
+CREATE TABLE IF NOT EXISTS event_sampling_counters (
+    event_receiver_id VARCHAR(26) NOT NULL,
+    bucket_start TIMESTAMP WITH TIME ZONE NOT NULL,
+    total_seen BIGINT NOT NULL DEFAULT 0,
+    total_stored BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_receiver_id, bucket_start)
+);
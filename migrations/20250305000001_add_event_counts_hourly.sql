@@ -0,0 +1,34 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add hourly event count rollup
+-- Stats queries read settled hours from this table instead of scanning
+-- events. Ingestion increments the current bucket in the same transaction
+-- as the event insert, and a reconciliation job recomputes recent buckets
+-- from events to heal drift. Severity is the lowercased payload `severity`
+-- string, or '' when absent. No foreign key: counts are kept after events
+-- are archived or a receiver is deleted.
+
+CREATE TABLE IF NOT EXISTS event_counts_hourly (
+    event_receiver_id VARCHAR(26) NOT NULL,
+    bucket_start TIMESTAMP WITH TIME ZONE NOT NULL,
+    severity TEXT NOT NULL DEFAULT '',
+    success BOOLEAN NOT NULL,
+    count BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_receiver_id, bucket_start, severity, success)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_counts_hourly_bucket_start
+    ON event_counts_hourly(bucket_start);
+
+-- Backfill from events already stored
+INSERT INTO event_counts_hourly (event_receiver_id, bucket_start, severity, success, count)
+SELECT event_receiver_id,
+       date_trunc('hour', created_at, 'UTC'),
+       COALESCE(LOWER(CASE WHEN jsonb_typeof(payload->'severity') = 'string'
+                           THEN payload->>'severity' END), ''),
+       success,
+       COUNT(*)
+FROM events
+GROUP BY 1, 2, 3, 4
+ON CONFLICT (event_receiver_id, bucket_start, severity, success) DO NOTHING;
@@ -0,0 +1,12 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Make receiver fingerprints unique
+-- Receivers provisioned implicitly by their first event are resolved by
+-- fingerprint. A unique index lets concurrent first events insert with
+-- ON CONFLICT so exactly one receiver is created.
+
+DROP INDEX IF EXISTS idx_event_receivers_fingerprint;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_event_receivers_fingerprint
+    ON event_receivers(fingerprint);
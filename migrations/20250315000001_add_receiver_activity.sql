@@ -0,0 +1,35 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add receiver activity tracking
+-- Ingestion buffers the latest event time and principal per receiver in
+-- memory and flushes them here in batches. Distinct principals are kept
+-- per day so the last 30 days can be counted without scanning events.
+-- No foreign keys: a batch may be flushed after its receiver was deleted.
+
+CREATE TABLE IF NOT EXISTS event_receiver_activity (
+    event_receiver_id VARCHAR(26) PRIMARY KEY,
+    last_event_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_principal TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_receiver_activity_last_event_at
+    ON event_receiver_activity(last_event_at);
+
+CREATE TABLE IF NOT EXISTS event_receiver_daily_principals (
+    event_receiver_id VARCHAR(26) NOT NULL,
+    day DATE NOT NULL,
+    principal TEXT NOT NULL,
+    PRIMARY KEY (event_receiver_id, day, principal)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_receiver_daily_principals_day
+    ON event_receiver_daily_principals(day);
+
+-- Backfill the latest event per receiver, attributed to the event owner
+INSERT INTO event_receiver_activity (event_receiver_id, last_event_at, last_principal)
+SELECT DISTINCT ON (event_receiver_id)
+       event_receiver_id, created_at, 'user:' || owner_id
+FROM events
+ORDER BY event_receiver_id, created_at DESC
+ON CONFLICT (event_receiver_id) DO NOTHING;
@@ -0,0 +1,16 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add receiver heartbeats
+-- Long-running producers report that they are alive without sending
+-- events. Only the latest heartbeat and its status are kept per receiver.
+-- No foreign key, matching event_receiver_activity.
+
+CREATE TABLE IF NOT EXISTS event_receiver_heartbeats (
+    event_receiver_id VARCHAR(26) PRIMARY KEY,
+    last_heartbeat_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    status JSONB
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_receiver_heartbeats_last_heartbeat_at
+    ON event_receiver_heartbeats(last_heartbeat_at);
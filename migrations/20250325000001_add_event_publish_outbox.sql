@@ -0,0 +1,20 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add event publish outbox
+-- Events stored under the best-effort publish policy whose Kafka publish
+-- failed wait here until a retry succeeds. Entries that exhaust their
+-- retries are dead-lettered and kept for operators.
+
+CREATE TABLE IF NOT EXISTS event_publish_outbox (
+    event_id TEXT PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    last_error TEXT NOT NULL,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    dead_lettered_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_publish_outbox_due
+    ON event_publish_outbox(next_attempt_at)
+    WHERE dead_lettered_at IS NULL;
@@ -0,0 +1,26 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add dedicated topics to event receiver groups
+-- Events of member receivers are also published to the dedicated topic of
+-- every enabled group. Fan-out publishes that fail wait in their own
+-- outbox, one entry per event and topic, so they are retried without
+-- touching the primary publish.
+
+ALTER TABLE event_receiver_groups
+    ADD COLUMN IF NOT EXISTS dedicated_topic TEXT;
+
+CREATE TABLE IF NOT EXISTS event_group_topic_outbox (
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    topic TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    last_error TEXT NOT NULL,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    dead_lettered_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (event_id, topic)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_group_topic_outbox_due
+    ON event_group_topic_outbox(next_attempt_at)
+    WHERE dead_lettered_at IS NULL;
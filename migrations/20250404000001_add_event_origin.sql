@@ -0,0 +1,31 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add event origin
+-- Events are either ingested from users or emitted by xzepr itself, such as
+-- xzepr.event.receiver.group.created. System events are left out of the
+-- default lists and stats. Existing xzepr.* events were all emitted
+-- internally, so they are marked as system events, and the hourly rollup
+-- is rebuilt from user events only.
+
+ALTER TABLE events
+    ADD COLUMN IF NOT EXISTS origin TEXT NOT NULL DEFAULT 'user';
+
+UPDATE events SET origin = 'system' WHERE name LIKE 'xzepr.%';
+
+CREATE INDEX IF NOT EXISTS idx_events_origin_created_at
+    ON events(origin, created_at DESC);
+
+DELETE FROM event_counts_hourly;
+
+INSERT INTO event_counts_hourly (event_receiver_id, bucket_start, severity, success, count)
+SELECT event_receiver_id,
+       date_trunc('hour', created_at, 'UTC'),
+       COALESCE(LOWER(CASE WHEN jsonb_typeof(payload->'severity') = 'string'
+                           THEN payload->>'severity' END), ''),
+       success,
+       COUNT(*)
+FROM events
+WHERE origin = 'user'
+GROUP BY 1, 2, 3, 4
+ON CONFLICT (event_receiver_id, bucket_start, severity, success) DO NOTHING;
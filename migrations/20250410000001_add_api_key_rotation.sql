@@ -0,0 +1,27 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add API key rotation
+-- Rotating a key issues a new secret for the same record. The replaced
+-- secret keeps authenticating until previous_expires_at so producers can
+-- switch over, and last_used_with shows which secret they still send.
+-- last_expiry_warning_at limits expiry warnings to one per key per day.
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS previous_hash TEXT,
+    ADD COLUMN IF NOT EXISTS previous_expires_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS rotated_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS last_used_with TEXT,
+    ADD COLUMN IF NOT EXISTS last_expiry_warning_at TIMESTAMPTZ;
+
+ALTER TABLE api_keys
+    ADD CONSTRAINT api_keys_last_used_with_check
+    CHECK (last_used_with IN ('current', 'previous'));
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_previous_hash
+    ON api_keys(previous_hash)
+    WHERE previous_hash IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_expires_at
+    ON api_keys(expires_at)
+    WHERE enabled AND expires_at IS NOT NULL;
@@ -0,0 +1,11 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add event feed index
+-- Long-poll consumers read user events after the last id they saw. Ids are
+-- ULID text, which sorts in creation order only under byte-wise comparison,
+-- so the feed compares and orders them with the "C" collation.
+
+CREATE INDEX IF NOT EXISTS idx_events_feed
+    ON events((id COLLATE "C"))
+    WHERE origin = 'user';
@@ -0,0 +1,19 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add API key scope
+-- A key may be limited to a single receiver or to the receivers of one
+-- group, never both. Group membership is checked on every request, so the
+-- group id is stored rather than a copy of its receivers.
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS receiver_id TEXT,
+    ADD COLUMN IF NOT EXISTS group_id TEXT;
+
+ALTER TABLE api_keys
+    ADD CONSTRAINT api_keys_single_scope_check
+    CHECK (receiver_id IS NULL OR group_id IS NULL);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_group_id
+    ON api_keys(group_id)
+    WHERE group_id IS NOT NULL;
@@ -0,0 +1,10 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add allowed event names
+-- A receiver may limit the event names it accepts to a list of names, a
+-- glob, or a regular expression, stored as {"names": [...]},
+-- {"glob": "..."}, or {"regex": "..."}. NULL accepts any name.
+
+ALTER TABLE event_receivers
+    ADD COLUMN IF NOT EXISTS allowed_event_names JSONB;
@@ -0,0 +1,14 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add group membership expiry
+-- A membership may end at a set time. Expired rows are treated as absent
+-- until the membership expiry job deletes them. NULL never expires.
+
+ALTER TABLE event_receiver_group_members
+    ADD COLUMN IF NOT EXISTS expires_at TIMESTAMP WITH TIME ZONE;
+
+-- Lets the expiry job find expired rows without scanning every membership
+CREATE INDEX IF NOT EXISTS idx_group_members_expires_at
+    ON event_receiver_group_members(expires_at)
+    WHERE expires_at IS NOT NULL;
@@ -0,0 +1,33 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add shared storage for large event payloads
+-- Payloads above ingestion.intern_threshold_bytes are stored once, keyed by
+-- the SHA-256 of their canonical JSON, and events reference them by hash
+-- instead of holding a copy. ref_count counts the referencing events; saving
+-- and deleting events maintain it, and a background job deletes payloads
+-- left at zero. The body is kept as canonical JSON text so that it decodes
+-- to exactly the payload that was submitted.
+
+CREATE TABLE IF NOT EXISTS event_payloads (
+    hash TEXT PRIMARY KEY,
+    body TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    ref_count BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_payloads_unreferenced
+    ON event_payloads(hash) WHERE ref_count <= 0;
+
+-- An event holds either its own payload or a reference to a shared one
+ALTER TABLE events ALTER COLUMN payload DROP NOT NULL;
+ALTER TABLE events ADD COLUMN IF NOT EXISTS payload_hash TEXT REFERENCES event_payloads(hash);
+ALTER TABLE events ADD CONSTRAINT events_payload_xor_hash
+    CHECK ((payload IS NULL) <> (payload_hash IS NULL));
+
+CREATE INDEX IF NOT EXISTS idx_events_payload_hash
+    ON events(payload_hash) WHERE payload_hash IS NOT NULL;
+
+COMMENT ON TABLE event_payloads IS 'Large event payloads stored once and referenced by content hash';
+COMMENT ON COLUMN events.payload_hash IS 'Hash of the shared payload when the payload is interned; payload is then NULL';
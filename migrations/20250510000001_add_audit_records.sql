@@ -0,0 +1,49 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add tamper-evident audit record storage
+-- Audit events are kept as canonical JSON in one hash chain per UTC day:
+-- each record stores the SHA-256 over the previous record's hash, its day,
+-- its position, and its content. Writers take a transaction-level advisory
+-- lock on the day before appending, so concurrent writers extend one
+-- unbroken chain. Records are append-only; retention deletes the oldest
+-- records of a day and keeps the hash of the last deleted one in
+-- audit_chain_anchors so the remaining records still verify.
+
+CREATE TABLE IF NOT EXISTS audit_records (
+    id BIGSERIAL PRIMARY KEY,
+    chain_day DATE NOT NULL,
+    position BIGINT NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    content TEXT NOT NULL,
+    prev_hash TEXT NOT NULL,
+    hash TEXT NOT NULL,
+    CONSTRAINT audit_records_chain_position UNIQUE (chain_day, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_records_recorded_at ON audit_records(recorded_at);
+
+CREATE TABLE IF NOT EXISTS audit_chain_anchors (
+    chain_day DATE NOT NULL,
+    position BIGINT NOT NULL,
+    hash TEXT NOT NULL,
+    anchored_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain_day, position)
+);
+
+CREATE OR REPLACE FUNCTION reject_audit_update() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION '% is append-only', TG_TABLE_NAME;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_records_append_only
+    BEFORE UPDATE ON audit_records
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_update();
+
+CREATE TRIGGER audit_chain_anchors_append_only
+    BEFORE UPDATE ON audit_chain_anchors
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_update();
+
+COMMENT ON TABLE audit_records IS 'Audit events in one SHA-256 hash chain per UTC day';
+COMMENT ON TABLE audit_chain_anchors IS 'Hash of the last record retention deleted from a day''s chain';
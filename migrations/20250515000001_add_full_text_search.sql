@@ -0,0 +1,42 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add full-text search over receivers, events, and groups
+-- Each table gets a generated tsvector column, kept current by PostgreSQL on
+-- every insert and update, and a GIN index over it. Names weigh most, so a
+-- match in the name ranks above one in the description. The english
+-- configuration stems words and drops stop words; search queries must use
+-- the same configuration. Adding a stored column rewrites the table, so
+-- this migration takes a while on large events tables.
+
+ALTER TABLE event_receivers ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_event_receivers_search
+    ON event_receivers USING GIN (search_vector);
+
+ALTER TABLE event_receiver_groups ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_event_receiver_groups_search
+    ON event_receiver_groups USING GIN (search_vector);
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(package, '')), 'B') ||
+        setweight(to_tsvector('english', coalesce(description, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_events_search
+    ON events USING GIN (search_vector);
+
+COMMENT ON COLUMN event_receivers.search_vector IS 'Full-text index of name and description';
+COMMENT ON COLUMN event_receiver_groups.search_vector IS 'Full-text index of name and description';
+COMMENT ON COLUMN events.search_vector IS 'Full-text index of name, package, and description';
@@ -0,0 +1,29 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add event attachments
+-- Files attached to events are stored in the blob store under storage_key;
+-- this table holds their metadata. event_receiver_id is copied from the
+-- event so that the per-receiver storage quota can be summed without a
+-- join. Rows go with their event, but blobs are only removed by the
+-- attachment endpoints and the retention job.
+
+CREATE TABLE IF NOT EXISTS event_attachments (
+    id TEXT PRIMARY KEY,
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    event_receiver_id VARCHAR(255) NOT NULL,
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    sha256 TEXT NOT NULL,
+    storage_key TEXT NOT NULL,
+    uploaded_by VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_attachments_event_id
+    ON event_attachments(event_id);
+CREATE INDEX IF NOT EXISTS idx_event_attachments_event_receiver_id
+    ON event_attachments(event_receiver_id);
+
+COMMENT ON TABLE event_attachments IS 'Metadata of files attached to events; content lives in the blob store';
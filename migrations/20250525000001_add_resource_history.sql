@@ -0,0 +1,32 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add receiver and group history
+-- Every write to a receiver or group appends its complete state after the
+-- write, so the state at any past instant is the latest row at or before
+-- it. A NULL state records a deletion. Group states include the group's
+-- memberships. Rows are never updated and have no foreign keys, so history
+-- outlives the resources it describes.
+
+CREATE TABLE IF NOT EXISTS event_receiver_history (
+    id BIGSERIAL PRIMARY KEY,
+    event_receiver_id VARCHAR(26) NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    state JSONB
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_receiver_history_receiver_recorded
+    ON event_receiver_history(event_receiver_id, recorded_at, id);
+
+CREATE TABLE IF NOT EXISTS event_receiver_group_history (
+    id BIGSERIAL PRIMARY KEY,
+    event_receiver_group_id VARCHAR(26) NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    state JSONB
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_receiver_group_history_group_recorded
+    ON event_receiver_group_history(event_receiver_group_id, recorded_at, id);
+
+COMMENT ON TABLE event_receiver_history IS 'Append-only receiver states; NULL state marks a deletion';
+COMMENT ON TABLE event_receiver_group_history IS 'Append-only group and membership states; NULL state marks a deletion';
@@ -0,0 +1,10 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Record impersonating administrators on ingestion metadata
+-- Events submitted with an impersonation token belong to the impersonated
+-- user, who stays the principal; actor_id names the administrator holding
+-- the token. NULL for every other submission.
+
+ALTER TABLE event_ingestion_meta
+    ADD COLUMN IF NOT EXISTS actor_id VARCHAR(255);
@@ -0,0 +1,31 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add fields extracted from attestation events
+-- Events of attestation receivers carry a CycloneDX SBOM or SLSA provenance
+-- document in their payload. The fields needed to find them again are
+-- extracted at ingest time; subject_digests holds the normalized
+-- algorithm:hex digests of the attested artifacts and is GIN indexed for
+-- lookups by digest.
+
+CREATE TABLE IF NOT EXISTS event_attestations (
+    event_id TEXT PRIMARY KEY REFERENCES events(id) ON DELETE CASCADE,
+    event_receiver_id VARCHAR(255) NOT NULL,
+    format VARCHAR(32) NOT NULL,
+    spec_version VARCHAR(64) NOT NULL,
+    subject_digests TEXT[] NOT NULL DEFAULT '{}',
+    builder_id TEXT,
+    component_count BIGINT,
+    component_name TEXT,
+    component_version TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_attestations_subject_digests
+    ON event_attestations USING GIN (subject_digests);
+CREATE INDEX IF NOT EXISTS idx_event_attestations_builder_id
+    ON event_attestations(builder_id);
+CREATE INDEX IF NOT EXISTS idx_event_attestations_created_at
+    ON event_attestations(created_at DESC);
+
+COMMENT ON TABLE event_attestations IS 'Fields extracted from attestation events; the document stays in the event payload';
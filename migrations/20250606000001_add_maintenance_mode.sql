@@ -0,0 +1,15 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add maintenance mode
+-- Read-only maintenance mode set through the admin API. A single row shared
+-- by every instance; without a row the API accepts writes.
+
+CREATE TABLE IF NOT EXISTS maintenance_mode (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    enabled BOOLEAN NOT NULL,
+    message TEXT NOT NULL DEFAULT '',
+    ends_at TIMESTAMP WITH TIME ZONE,
+    updated_by TEXT,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
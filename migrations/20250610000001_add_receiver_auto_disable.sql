@@ -0,0 +1,16 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add receiver auto-disable
+-- A receiver may be disabled automatically once the failure rate of its
+-- events stays above a threshold. auto_disable_policy holds
+-- {"failure_rate_threshold", "observation_window_minutes",
+-- "minimum_event_count"}; NULL never disables the receiver.
+-- auto_disabled holds the statistics of the window that disabled it and
+-- is NULL while the receiver accepts events. auto_disable_window_start is
+-- set when the receiver is re-enabled; earlier events are not evaluated.
+
+ALTER TABLE event_receivers
+    ADD COLUMN IF NOT EXISTS auto_disable_policy JSONB,
+    ADD COLUMN IF NOT EXISTS auto_disabled JSONB,
+    ADD COLUMN IF NOT EXISTS auto_disable_window_start TIMESTAMPTZ;
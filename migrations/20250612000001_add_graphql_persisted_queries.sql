@@ -0,0 +1,19 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add GraphQL persisted queries
+-- Query text keyed by its SHA-256 hash. Rows are either cached from clients
+-- through automatic persisted queries or registered by an administrator;
+-- only registered rows run when graphql.persisted_only is set.
+
+CREATE TABLE IF NOT EXISTS graphql_persisted_queries (
+    hash VARCHAR(64) PRIMARY KEY,
+    query TEXT NOT NULL,
+    registered BOOLEAN NOT NULL DEFAULT FALSE,
+    registered_by TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_graphql_persisted_queries_registered
+    ON graphql_persisted_queries (created_at)
+    WHERE registered;
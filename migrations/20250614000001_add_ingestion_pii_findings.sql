@@ -0,0 +1,15 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Record PII detection findings on ingestion metadata
+-- pii_findings holds [{"detector", "path"}] for payload values that looked
+-- like personal data when the event was ingested. The values themselves
+-- are never stored here. The partial index serves the per-receiver report,
+-- which only reads rows with findings.
+
+ALTER TABLE event_ingestion_meta
+    ADD COLUMN IF NOT EXISTS pii_findings JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+CREATE INDEX IF NOT EXISTS idx_event_ingestion_meta_pii_findings
+    ON event_ingestion_meta(recorded_at DESC)
+    WHERE pii_findings <> '[]'::jsonb;
@@ -0,0 +1,21 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add group release completeness
+-- Optional member receivers do not block a group's release completeness.
+-- The first time every required receiver of a group reports a successful
+-- event for a release, the pair is recorded so the completion system event
+-- is emitted once.
+
+ALTER TABLE event_receiver_group_receivers
+    ADD COLUMN IF NOT EXISTS required BOOLEAN NOT NULL DEFAULT TRUE;
+
+CREATE INDEX IF NOT EXISTS idx_events_receiver_release
+    ON events (event_receiver_id, release, created_at);
+
+CREATE TABLE IF NOT EXISTS group_release_completions (
+    group_id VARCHAR(26) NOT NULL REFERENCES event_receiver_groups(id) ON DELETE CASCADE,
+    release VARCHAR(100) NOT NULL,
+    completed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (group_id, release)
+);
@@ -0,0 +1,29 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add composite indexes for the repository query patterns
+-- Receiver, status, and platform event listings filter on one column and
+-- order by created_at, so each gets an index that serves both. Group
+-- lookups by receiver join on group_id, which the receiver index now
+-- carries. The single-column indexes these replace are prefixes of the new
+-- ones and only cost writes. Group members already have (user_id,
+-- group_id); audit records keep no user column, so they need no new index.
+-- tests/query_plan_tests.rs checks the plans of these queries.
+
+CREATE INDEX IF NOT EXISTS idx_events_receiver_created
+    ON events(event_receiver_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_events_success_created
+    ON events(success, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_events_platform_created
+    ON events(platform_id, created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_group_receivers_receiver_group
+    ON event_receiver_group_receivers(receiver_id, group_id);
+
+DROP INDEX IF EXISTS idx_events_event_receiver_id;
+DROP INDEX IF EXISTS idx_events_success;
+DROP INDEX IF EXISTS idx_events_platform_id;
+DROP INDEX IF EXISTS idx_group_receivers_receiver_id;
+DROP INDEX IF EXISTS idx_group_members_user_id;
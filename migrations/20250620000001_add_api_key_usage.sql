@@ -0,0 +1,32 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add API key usage tracking
+-- Key owners see how often a key is used and why its requests fail.
+-- Authentication buffers usage in memory, so last_used_at and
+-- last_used_ip are written at most once a minute per key and the daily
+-- counters once per flush. Only the latest failures of each key are kept.
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS last_used_ip TEXT;
+
+CREATE TABLE IF NOT EXISTS api_key_daily_usage (
+    key_id TEXT NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    successes BIGINT NOT NULL DEFAULT 0,
+    auth_failures BIGINT NOT NULL DEFAULT 0,
+    authz_denials BIGINT NOT NULL DEFAULT 0,
+    rate_limited BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (key_id, day)
+);
+
+CREATE TABLE IF NOT EXISTS api_key_failures (
+    id BIGSERIAL PRIMARY KEY,
+    key_id TEXT NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    reason TEXT NOT NULL,
+    route TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_key_failures_key_occurred
+    ON api_key_failures (key_id, occurred_at DESC);
@@ -0,0 +1,10 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Index API keys by the receiver they are scoped to
+-- Deleting a receiver looks up the keys scoped to it to block the deletion
+-- or revoke them.
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_receiver_id
+    ON api_keys(receiver_id)
+    WHERE receiver_id IS NOT NULL;
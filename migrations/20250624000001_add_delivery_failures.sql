@@ -0,0 +1,26 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add the delivery failure log
+-- Deliveries given up on after their last retry, whichever mechanism made
+-- them, so on-call finds them in one place. A delivery is given up on once
+-- per mechanism, event, and target; recording it again changes nothing.
+-- Rows are kept after their event is deleted.
+
+CREATE TABLE IF NOT EXISTS delivery_failures (
+    mechanism TEXT NOT NULL,
+    target TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    receiver_id TEXT NOT NULL,
+    error_class TEXT NOT NULL,
+    last_error TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    failed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (mechanism, event_id, target)
+);
+
+CREATE INDEX IF NOT EXISTS idx_delivery_failures_failed_at
+    ON delivery_failures (failed_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_delivery_failures_receiver_failed_at
+    ON delivery_failures (receiver_id, failed_at DESC);
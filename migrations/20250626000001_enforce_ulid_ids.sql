@@ -0,0 +1,352 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Enforce canonical ULID ids and the references between them
+-- Ids are compared as strings, so a lowercase ULID never matches its
+-- uppercase twin. Every ULID id column gets a CHECK constraint named
+-- <table>_<column>_ulid accepting only the canonical 26 character uppercase
+-- Crockford form.
+--
+-- Existing rows are repaired first: ids that are valid ULIDs in another
+-- case are uppercased. Foreign keys between ULID ids are recreated with ON
+-- UPDATE CASCADE so uppercasing a parent id carries its children along.
+-- Nothing is deleted. Ids that are not ULIDs at all, and references to
+-- rows that do not exist, are copied to id_quarantine for operator review;
+-- the constraints they violate are left NOT VALID, so new rows are still
+-- checked. Once the quarantined rows are dealt with, run
+-- ALTER TABLE ... VALIDATE CONSTRAINT for each.
+--
+-- Per-receiver state (activity, daily principals, heartbeats, sampling
+-- counters) now goes with its receiver. Events and scoped API keys outlive
+-- a deleted receiver or group, so a foreign key would either delete them
+-- or block the deletion; triggers check their references when they are
+-- written instead. Group member user ids stay unchecked, since members may
+-- come from a federated identity provider before they ever sign in.
+
+CREATE OR REPLACE FUNCTION is_canonical_ulid(value TEXT) RETURNS BOOLEAN AS $$
+    SELECT value ~ '^[0-7][0-9A-HJKMNP-TV-Z]{25}$'
+$$ LANGUAGE sql IMMUTABLE PARALLEL SAFE;
+
+CREATE TABLE IF NOT EXISTS id_quarantine (
+    id BIGSERIAL PRIMARY KEY,
+    table_name TEXT NOT NULL,
+    column_name TEXT NOT NULL,
+    value TEXT NOT NULL,
+    reason TEXT NOT NULL CHECK (reason IN ('malformed', 'orphan')),
+    row_data JSONB NOT NULL,
+    detected_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_id_quarantine_table_column
+    ON id_quarantine (table_name, column_name);
+
+-- ============================================================================
+-- Step 1: Let id repairs cascade through the existing foreign keys
+-- ============================================================================
+
+ALTER TABLE user_roles
+    DROP CONSTRAINT user_roles_user_id_fkey,
+    ADD CONSTRAINT user_roles_user_id_fkey FOREIGN KEY (user_id)
+        REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE user_preferences
+    DROP CONSTRAINT user_preferences_user_id_fkey,
+    ADD CONSTRAINT user_preferences_user_id_fkey FOREIGN KEY (user_id)
+        REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE api_keys
+    DROP CONSTRAINT api_keys_user_id_fkey,
+    ADD CONSTRAINT api_keys_user_id_fkey FOREIGN KEY (user_id)
+        REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE api_key_daily_usage
+    DROP CONSTRAINT api_key_daily_usage_key_id_fkey,
+    ADD CONSTRAINT api_key_daily_usage_key_id_fkey FOREIGN KEY (key_id)
+        REFERENCES api_keys(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE api_key_failures
+    DROP CONSTRAINT api_key_failures_key_id_fkey,
+    ADD CONSTRAINT api_key_failures_key_id_fkey FOREIGN KEY (key_id)
+        REFERENCES api_keys(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE event_receiver_group_receivers
+    DROP CONSTRAINT event_receiver_group_receivers_group_id_fkey,
+    DROP CONSTRAINT event_receiver_group_receivers_receiver_id_fkey,
+    ADD CONSTRAINT event_receiver_group_receivers_group_id_fkey FOREIGN KEY (group_id)
+        REFERENCES event_receiver_groups(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    ADD CONSTRAINT event_receiver_group_receivers_receiver_id_fkey FOREIGN KEY (receiver_id)
+        REFERENCES event_receivers(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE event_receiver_group_members
+    DROP CONSTRAINT event_receiver_group_members_group_id_fkey,
+    ADD CONSTRAINT event_receiver_group_members_group_id_fkey FOREIGN KEY (group_id)
+        REFERENCES event_receiver_groups(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE group_release_completions
+    DROP CONSTRAINT group_release_completions_group_id_fkey,
+    ADD CONSTRAINT group_release_completions_group_id_fkey FOREIGN KEY (group_id)
+        REFERENCES event_receiver_groups(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE event_ingestion_meta
+    DROP CONSTRAINT event_ingestion_meta_event_id_fkey,
+    ADD CONSTRAINT event_ingestion_meta_event_id_fkey FOREIGN KEY (event_id)
+        REFERENCES events(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE event_publish_outbox
+    DROP CONSTRAINT event_publish_outbox_event_id_fkey,
+    ADD CONSTRAINT event_publish_outbox_event_id_fkey FOREIGN KEY (event_id)
+        REFERENCES events(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE event_group_topic_outbox
+    DROP CONSTRAINT event_group_topic_outbox_event_id_fkey,
+    ADD CONSTRAINT event_group_topic_outbox_event_id_fkey FOREIGN KEY (event_id)
+        REFERENCES events(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE event_attachments
+    DROP CONSTRAINT event_attachments_event_id_fkey,
+    ADD CONSTRAINT event_attachments_event_id_fkey FOREIGN KEY (event_id)
+        REFERENCES events(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE event_attestations
+    DROP CONSTRAINT event_attestations_event_id_fkey,
+    ADD CONSTRAINT event_attestations_event_id_fkey FOREIGN KEY (event_id)
+        REFERENCES events(id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+-- ============================================================================
+-- Step 2: Uppercase ULID ids, parents before the tables referencing them
+-- ============================================================================
+
+CREATE TEMPORARY TABLE ulid_columns (
+    position SERIAL,
+    table_name TEXT NOT NULL,
+    column_name TEXT NOT NULL
+) ON COMMIT DROP;
+
+INSERT INTO ulid_columns (table_name, column_name) VALUES
+    ('users', 'id'),
+    ('user_roles', 'user_id'),
+    ('user_preferences', 'user_id'),
+    ('event_receivers', 'id'),
+    ('event_receivers', 'owner_id'),
+    ('event_receiver_groups', 'id'),
+    ('event_receiver_groups', 'owner_id'),
+    ('event_receiver_group_receivers', 'group_id'),
+    ('event_receiver_group_receivers', 'receiver_id'),
+    ('event_receiver_group_members', 'group_id'),
+    ('event_receiver_group_members', 'user_id'),
+    ('event_receiver_group_members', 'added_by'),
+    ('api_keys', 'id'),
+    ('api_keys', 'user_id'),
+    ('api_keys', 'receiver_id'),
+    ('api_keys', 'group_id'),
+    ('api_key_daily_usage', 'key_id'),
+    ('api_key_failures', 'key_id'),
+    ('events', 'id'),
+    ('events', 'event_receiver_id'),
+    ('events', 'owner_id'),
+    ('event_attachments', 'id'),
+    ('event_attachments', 'event_id'),
+    ('event_attachments', 'event_receiver_id'),
+    ('event_attachments', 'uploaded_by'),
+    ('event_attestations', 'event_id'),
+    ('event_attestations', 'event_receiver_id'),
+    ('event_ingestion_meta', 'event_id'),
+    ('event_publish_outbox', 'event_id'),
+    ('event_group_topic_outbox', 'event_id'),
+    ('event_archive_index', 'event_id'),
+    ('event_archive_index', 'event_receiver_id'),
+    ('event_counts_hourly', 'event_receiver_id'),
+    ('event_receiver_activity', 'event_receiver_id'),
+    ('event_receiver_daily_principals', 'event_receiver_id'),
+    ('event_receiver_heartbeats', 'event_receiver_id'),
+    ('event_sampling_counters', 'event_receiver_id'),
+    ('event_receiver_history', 'event_receiver_id'),
+    ('event_receiver_tombstones', 'id'),
+    ('event_receiver_group_history', 'event_receiver_group_id'),
+    ('event_receiver_group_tombstones', 'id'),
+    ('group_release_completions', 'group_id'),
+    ('delivery_failures', 'event_id'),
+    ('delivery_failures', 'receiver_id');
+
+DO $$
+DECLARE
+    c RECORD;
+    target TID;
+BEGIN
+    FOR c IN SELECT table_name, column_name FROM ulid_columns ORDER BY position LOOP
+        FOR target IN EXECUTE format(
+            'SELECT ctid FROM %I WHERE %I <> upper(%I) AND is_canonical_ulid(upper(%I))',
+            c.table_name, c.column_name, c.column_name, c.column_name
+        ) LOOP
+            BEGIN
+                EXECUTE format(
+                    'UPDATE %I SET %I = upper(%I) WHERE ctid = $1',
+                    c.table_name, c.column_name, c.column_name
+                ) USING target;
+            EXCEPTION WHEN unique_violation OR foreign_key_violation THEN
+                -- The uppercase id is taken, so the row is quarantined below
+                NULL;
+            END;
+        END LOOP;
+    END LOOP;
+END $$;
+
+-- ============================================================================
+-- Step 3: Quarantine ids that are still not canonical
+-- ============================================================================
+
+DO $$
+DECLARE
+    c RECORD;
+BEGIN
+    FOR c IN SELECT table_name, column_name FROM ulid_columns ORDER BY position LOOP
+        EXECUTE format(
+            'INSERT INTO id_quarantine (table_name, column_name, value, reason, row_data)
+             SELECT %L, %L, t.%I, ''malformed'', to_jsonb(t)
+             FROM %I t
+             WHERE NOT is_canonical_ulid(t.%I)',
+            c.table_name, c.column_name, c.column_name, c.table_name, c.column_name
+        );
+    END LOOP;
+END $$;
+
+-- ============================================================================
+-- Step 4: Quarantine references to rows that do not exist
+-- ============================================================================
+-- References to a deleted receiver or group are expected where its
+-- tombstone remains.
+
+CREATE TEMPORARY TABLE ulid_references (
+    table_name TEXT NOT NULL,
+    column_name TEXT NOT NULL,
+    parent_table TEXT NOT NULL,
+    tombstone_table TEXT
+) ON COMMIT DROP;
+
+INSERT INTO ulid_references VALUES
+    ('events', 'event_receiver_id', 'event_receivers', 'event_receiver_tombstones'),
+    ('api_keys', 'receiver_id', 'event_receivers', 'event_receiver_tombstones'),
+    ('api_keys', 'group_id', 'event_receiver_groups', 'event_receiver_group_tombstones'),
+    ('event_receiver_activity', 'event_receiver_id', 'event_receivers', NULL),
+    ('event_receiver_daily_principals', 'event_receiver_id', 'event_receivers', NULL),
+    ('event_receiver_heartbeats', 'event_receiver_id', 'event_receivers', NULL),
+    ('event_sampling_counters', 'event_receiver_id', 'event_receivers', NULL);
+
+DO $$
+DECLARE
+    r RECORD;
+BEGIN
+    FOR r IN SELECT * FROM ulid_references LOOP
+        EXECUTE format(
+            'INSERT INTO id_quarantine (table_name, column_name, value, reason, row_data)
+             SELECT %L, %L, t.%I, ''orphan'', to_jsonb(t)
+             FROM %I t
+             WHERE is_canonical_ulid(t.%I)
+               AND NOT EXISTS (SELECT 1 FROM %I p WHERE p.id = t.%I)',
+            r.table_name, r.column_name, r.column_name, r.table_name,
+            r.column_name, r.parent_table, r.column_name
+        ) || CASE
+            WHEN r.tombstone_table IS NULL THEN ''
+            ELSE format(
+                ' AND NOT EXISTS (SELECT 1 FROM %I d WHERE d.id = t.%I)',
+                r.tombstone_table, r.column_name
+            )
+        END;
+    END LOOP;
+END $$;
+
+-- ============================================================================
+-- Step 5: Add the constraints, validating those the data already meets
+-- ============================================================================
+
+ALTER TABLE events ALTER COLUMN owner_id DROP DEFAULT;
+
+ALTER TABLE event_receiver_activity
+    ADD CONSTRAINT event_receiver_activity_event_receiver_id_fkey
+        FOREIGN KEY (event_receiver_id) REFERENCES event_receivers(id)
+        ON DELETE CASCADE ON UPDATE CASCADE NOT VALID;
+
+ALTER TABLE event_receiver_daily_principals
+    ADD CONSTRAINT event_receiver_daily_principals_event_receiver_id_fkey
+        FOREIGN KEY (event_receiver_id) REFERENCES event_receivers(id)
+        ON DELETE CASCADE ON UPDATE CASCADE NOT VALID;
+
+ALTER TABLE event_receiver_heartbeats
+    ADD CONSTRAINT event_receiver_heartbeats_event_receiver_id_fkey
+        FOREIGN KEY (event_receiver_id) REFERENCES event_receivers(id)
+        ON DELETE CASCADE ON UPDATE CASCADE NOT VALID;
+
+ALTER TABLE event_sampling_counters
+    ADD CONSTRAINT event_sampling_counters_event_receiver_id_fkey
+        FOREIGN KEY (event_receiver_id) REFERENCES event_receivers(id)
+        ON DELETE CASCADE ON UPDATE CASCADE NOT VALID;
+
+DO $$
+DECLARE
+    c RECORD;
+BEGIN
+    FOR c IN SELECT table_name, column_name FROM ulid_columns ORDER BY position LOOP
+        EXECUTE format(
+            'ALTER TABLE %I ADD CONSTRAINT %I CHECK (is_canonical_ulid(%I)) NOT VALID',
+            c.table_name, c.table_name || '_' || c.column_name || '_ulid', c.column_name
+        );
+    END LOOP;
+
+    FOR c IN
+        SELECT conrelid::regclass AS table_name, conname
+        FROM pg_constraint
+        WHERE connamespace = 'public'::regnamespace AND NOT convalidated
+    LOOP
+        BEGIN
+            EXECUTE format('ALTER TABLE %s VALIDATE CONSTRAINT %I', c.table_name, c.conname);
+        EXCEPTION WHEN check_violation OR foreign_key_violation THEN
+            RAISE NOTICE '% left NOT VALID; see id_quarantine', c.conname;
+        END;
+    END LOOP;
+END $$;
+
+-- ============================================================================
+-- Step 6: Check references of rows that outlive what they reference
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION require_event_receiver() RETURNS trigger AS $$
+BEGIN
+    PERFORM 1 FROM event_receivers WHERE id = NEW.event_receiver_id FOR KEY SHARE;
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'event receiver % does not exist', NEW.event_receiver_id
+            USING ERRCODE = 'foreign_key_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER events_event_receiver_exists
+    BEFORE INSERT OR UPDATE OF event_receiver_id ON events
+    FOR EACH ROW EXECUTE FUNCTION require_event_receiver();
+
+CREATE OR REPLACE FUNCTION require_api_key_scope() RETURNS trigger AS $$
+BEGIN
+    IF NEW.receiver_id IS NOT NULL THEN
+        PERFORM 1 FROM event_receivers WHERE id = NEW.receiver_id FOR KEY SHARE;
+        IF NOT FOUND THEN
+            RAISE EXCEPTION 'event receiver % does not exist', NEW.receiver_id
+                USING ERRCODE = 'foreign_key_violation';
+        END IF;
+    END IF;
+    IF NEW.group_id IS NOT NULL THEN
+        PERFORM 1 FROM event_receiver_groups WHERE id = NEW.group_id FOR KEY SHARE;
+        IF NOT FOUND THEN
+            RAISE EXCEPTION 'event receiver group % does not exist', NEW.group_id
+                USING ERRCODE = 'foreign_key_violation';
+        END IF;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER api_keys_scope_exists
+    BEFORE INSERT OR UPDATE OF receiver_id, group_id ON api_keys
+    FOR EACH ROW EXECUTE FUNCTION require_api_key_scope();
+
+COMMENT ON TABLE id_quarantine IS 'Rows with malformed or dangling ULID ids, kept for operator review';
+COMMENT ON COLUMN event_receiver_group_members.user_id IS 'User ID (ULID) of the group member - may not have signed in yet, so not a foreign key';
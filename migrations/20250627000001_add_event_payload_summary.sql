@@ -0,0 +1,13 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add offloaded payload summaries to events
+-- Payloads over ingestion.offload_threshold_bytes are stored whole in the
+-- blob store. The event keeps an empty payload object and this summary:
+-- the blob key, size, content hash, top-level keys, and the values of the
+-- configured summary paths.
+
+ALTER TABLE events ADD COLUMN IF NOT EXISTS payload_summary JSONB;
+
+COMMENT ON COLUMN events.payload_summary IS
+    'Summary of a payload held in the blob store; NULL when the payload is stored with the event';
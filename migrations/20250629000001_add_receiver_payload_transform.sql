@@ -0,0 +1,10 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add receiver payload transforms
+-- A receiver may rewrite event payloads before schema validation. transform
+-- holds the ordered list of steps, such as
+-- [{"op": "rename", "from": "a", "to": "b", "on_failure": "skip"}].
+-- NULL stores payloads as they arrive.
+
+ALTER TABLE event_receivers ADD COLUMN IF NOT EXISTS transform JSONB;
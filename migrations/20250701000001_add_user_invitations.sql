@@ -0,0 +1,17 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add user invitations
+-- An invitation lets the user of an imported local account choose its
+-- password. Only the SHA-256 hash of the token is stored; the row is deleted
+-- when the invitation is accepted. A user has at most one invitation.
+
+CREATE TABLE IF NOT EXISTS user_invitations (
+    user_id TEXT PRIMARY KEY
+        REFERENCES users(id) ON DELETE CASCADE ON UPDATE CASCADE
+        CONSTRAINT user_invitations_user_id_ulid CHECK (is_canonical_ulid(user_id)),
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
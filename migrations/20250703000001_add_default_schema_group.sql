@@ -0,0 +1,14 @@
+-- SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+-- SPDX-License-Identifier: Apache-2.0
+
+-- Migration: Add a default schema group per receiver
+-- A receiver in several enabled groups whose default schemas differ
+-- inherits the schema of the membership marked as its default. The partial
+-- unique index allows at most one default membership per receiver.
+
+ALTER TABLE event_receiver_group_receivers
+    ADD COLUMN IF NOT EXISTS is_default BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_group_receivers_one_default
+    ON event_receiver_group_receivers(receiver_id)
+    WHERE is_default;
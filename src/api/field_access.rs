@@ -0,0 +1,167 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/field_access.rs
+
+//! Caller-dependent visibility of sensitive response fields
+//!
+//! Event payloads and receiver schemas can carry internal details, so they
+//! are only returned to callers holding `event:read_payload` or
+//! `receiver:read_schema`. Admins and the owner of a resource always see
+//! the full data. Receiver liveness metadata is only returned to admins and
+//! callers holding `receiver:read_meta`, and receiver configuration changes
+//! in timelines only to admins and the receiver's owner. Every API surface builds its responses through a
+//! [`FieldAccess`], so REST, GraphQL, and exports filter identically.
+
+use serde_json::{json, Value as JsonValue};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::domain::repositories::receiver_timeline_repo::TimelineKind;
+use crate::domain::value_objects::UserId;
+
+/// Permission required to see event payloads
+pub const READ_PAYLOAD_PERMISSION: &str = "event:read_payload";
+
+/// Permission required to see event receiver schemas
+pub const READ_SCHEMA_PERMISSION: &str = "receiver:read_schema";
+
+/// Permission required to see receiver liveness metadata
+pub const READ_RECEIVER_META_PERMISSION: &str = "receiver:read_meta";
+
+/// Marker returned in place of a payload the caller may not see
+pub fn redacted_payload() -> JsonValue {
+    json!({"_redacted": true})
+}
+
+/// Sensitive fields a caller is allowed to read
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldAccess {
+    user_id: Option<UserId>,
+    admin: bool,
+    read_payload: bool,
+    read_schema: bool,
+    read_receiver_meta: bool,
+}
+
+impl FieldAccess {
+    /// Access for the given caller; anonymous callers see no sensitive fields
+    pub fn for_user(user: Option<&AuthenticatedUser>) -> Self {
+        match user {
+            Some(user) => Self {
+                user_id: user.user_id().parse().ok(),
+                admin: user.has_role("admin"),
+                read_payload: user.has_permission(READ_PAYLOAD_PERMISSION),
+                read_schema: user.has_permission(READ_SCHEMA_PERMISSION),
+                read_receiver_meta: user.has_permission(READ_RECEIVER_META_PERMISSION),
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// Returns true if the caller may read the payload of an event owned by `owner_id`
+    pub fn can_read_payload(&self, owner_id: UserId) -> bool {
+        self.read_payload || self.is_admin_or_owner(owner_id)
+    }
+
+    /// Returns true if the caller may read the schema of a receiver owned by `owner_id`
+    pub fn can_read_schema(&self, owner_id: UserId) -> bool {
+        self.read_schema || self.is_admin_or_owner(owner_id)
+    }
+
+    /// Returns true if the caller may read receiver liveness metadata
+    pub fn can_read_receiver_meta(&self) -> bool {
+        self.read_receiver_meta || self.admin
+    }
+
+    /// Returns the timeline entry kinds the caller may see for a receiver
+    /// owned by `owner_id`
+    pub fn timeline_kinds(&self, owner_id: UserId) -> Vec<TimelineKind> {
+        TimelineKind::ALL
+            .into_iter()
+            .filter(|kind| *kind != TimelineKind::ConfigChange || self.is_admin_or_owner(owner_id))
+            .collect()
+    }
+
+    fn is_admin_or_owner(&self, owner_id: UserId) -> bool {
+        self.admin || self.user_id == Some(owner_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::jwt::claims::Claims;
+
+    fn user(sub: &str, roles: &[&str], permissions: &[&str]) -> AuthenticatedUser {
+        AuthenticatedUser::new(Claims::new_access_token(
+            sub.to_string(),
+            roles.iter().map(|r| r.to_string()).collect(),
+            permissions.iter().map(|p| p.to_string()).collect(),
+            "xzepr".to_string(),
+            "xzepr-api".to_string(),
+            chrono::Duration::minutes(15),
+        ))
+    }
+
+    #[test]
+    fn test_viewer_without_permissions_is_filtered() {
+        let viewer = user(
+            &UserId::new().to_string(),
+            &["event_viewer"],
+            &["event:read"],
+        );
+        let access = FieldAccess::for_user(Some(&viewer));
+
+        assert!(!access.can_read_payload(UserId::new()));
+        assert!(!access.can_read_schema(UserId::new()));
+        assert!(!FieldAccess::for_user(None).can_read_payload(UserId::new()));
+        assert!(!access.can_read_receiver_meta());
+    }
+
+    #[test]
+    fn test_permissions_grant_access() {
+        let reader = user(
+            &UserId::new().to_string(),
+            &["event_viewer"],
+            &[READ_PAYLOAD_PERMISSION, READ_SCHEMA_PERMISSION],
+        );
+        let access = FieldAccess::for_user(Some(&reader));
+
+        assert!(access.can_read_payload(UserId::new()));
+        assert!(access.can_read_schema(UserId::new()));
+    }
+
+    #[test]
+    fn test_admins_and_owners_see_everything() {
+        let owner_id = UserId::new();
+        let owner = FieldAccess::for_user(Some(&user(&owner_id.to_string(), &["user"], &[])));
+        assert!(owner.can_read_payload(owner_id));
+        assert!(owner.can_read_schema(owner_id));
+        assert!(!owner.can_read_payload(UserId::new()));
+
+        let admin = FieldAccess::for_user(Some(&user("admin-1", &["admin"], &[])));
+        assert!(admin.can_read_payload(UserId::new()));
+        assert!(admin.can_read_schema(UserId::new()));
+        assert!(admin.can_read_receiver_meta());
+        assert!(!owner.can_read_receiver_meta());
+    }
+
+    #[test]
+    fn test_config_changes_only_in_owner_and_admin_timelines() {
+        let owner_id = UserId::new();
+        let owner = FieldAccess::for_user(Some(&user(&owner_id.to_string(), &["user"], &[])));
+        let admin = FieldAccess::for_user(Some(&user("admin-1", &["admin"], &[])));
+        let reader = FieldAccess::for_user(Some(&user(
+            &UserId::new().to_string(),
+            &["event_viewer"],
+            &[READ_SCHEMA_PERMISSION],
+        )));
+
+        assert_eq!(owner.timeline_kinds(owner_id), TimelineKind::ALL.to_vec());
+        assert_eq!(admin.timeline_kinds(owner_id), TimelineKind::ALL.to_vec());
+        assert_eq!(
+            reader.timeline_kinds(owner_id),
+            vec![TimelineKind::Event, TimelineKind::GroupMembership]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/graphql/errors.rs
+
+use async_graphql::{Context, Error, ErrorExtensions, Value};
+use axum::http::{header, HeaderMap};
+
+use crate::error::DomainError;
+use crate::i18n::{self, Message, DEFAULT_LOCALE};
+
+/// Locale negotiated from the request's `Accept-Language` header
+///
+/// Added to the request data by the GraphQL handler; resolvers without it
+/// render messages in English.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestLocale(pub String);
+
+impl RequestLocale {
+    /// Negotiates the locale against the installed message catalogs
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let accept_language = headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok());
+        Self(i18n::catalogs().negotiate(accept_language).to_string())
+    }
+}
+
+/// Converts a domain error to a GraphQL error
+///
+/// Validation and business-rule errors are rendered in the request locale
+/// and carry `code`, `message_key`, `params`, and, for validation errors,
+/// `field` extensions. Other errors keep their English text.
+pub fn domain_error(ctx: &Context<'_>, e: &DomainError) -> Error {
+    localized(ctx, e.localizable()).unwrap_or_else(|| Error::new(e.to_string()))
+}
+
+/// Converts an application error to a GraphQL error
+///
+/// Errors without a message key are prefixed with `context`, such as
+/// "Failed to create event receiver".
+pub fn app_error(ctx: &Context<'_>, context: &str, e: &crate::error::Error) -> Error {
+    localized(ctx, e.localizable()).unwrap_or_else(|| Error::new(format!("{}: {}", context, e)))
+}
+
+/// Builds an error with a `code` extension, such as `forbidden` or
+/// `conflict`, so clients can branch without matching message text
+pub fn coded_error(code: &str, message: impl Into<String>) -> Error {
+    Error::new(message.into()).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+fn localized(ctx: &Context<'_>, localizable: Option<(Option<&str>, &Message)>) -> Option<Error> {
+    let (field, message) = localizable?;
+    let locale = ctx
+        .data_opt::<RequestLocale>()
+        .map_or(DEFAULT_LOCALE, |locale| locale.0.as_str());
+    let code = match field {
+        Some(_) => "validation_error",
+        None => "business_rule_violation",
+    };
+    let params = serde_json::to_value(message.params())
+        .ok()
+        .and_then(|params| Value::from_json(params).ok())
+        .unwrap_or_default();
+
+    Some(
+        Error::new(i18n::catalogs().render(locale, message)).extend_with(|_, extensions| {
+            extensions.set("code", code);
+            extensions.set("message_key", message.key());
+            extensions.set("params", params);
+            if let Some(field) = field {
+                extensions.set("field", field);
+            }
+        }),
+    )
+}
@@ -285,6 +285,7 @@ mod tests {
             iss: "xzepr".to_string(),
             aud: "xzepr-api".to_string(),
             token_type: TokenType::Access,
+            act: None,
             roles,
             permissions,
         }
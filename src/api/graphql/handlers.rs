@@ -5,26 +5,30 @@
 
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{OriginalUri, State},
+    http::{header, HeaderMap, StatusCode},
     response::{Html, IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::api::graphql::Schema;
+use crate::api::graphql::{RequestLocale, Schema};
 use crate::api::middleware::jwt::AuthenticatedUser;
 
 /// GraphQL request structure
 #[derive(Debug, Deserialize)]
 pub struct GraphQLRequest {
-    /// The GraphQL query string
+    /// The GraphQL query string; empty when a persisted query is sent
+    /// by hash
+    #[serde(default)]
     pub query: String,
     /// Optional operation name
     #[serde(rename = "operationName")]
     pub operation_name: Option<String>,
     /// Optional variables
     pub variables: Option<serde_json::Value>,
+    /// Optional protocol extensions, such as `persistedQuery`
+    pub extensions: Option<serde_json::Value>,
 }
 
 /// GraphQL response structure
@@ -46,6 +50,8 @@ pub struct GraphQLResponse {
 /// # Arguments
 ///
 /// * `schema` - The GraphQL schema containing queries and mutations
+/// * `headers` - Request headers; `Accept-Language` selects the language
+///   of validation error messages
 /// * `req` - The incoming GraphQL request
 ///
 /// # Returns
@@ -59,19 +65,22 @@ pub struct GraphQLResponse {
 /// Content-Type: application/json
 ///
 /// {
-///   "query": "{ eventReceivers(eventReceiver: {}) { id name type } }"
+///   "query": "{ eventReceivers(eventReceiver: {}) { nodes { id name type } } }"
 /// }
 /// ```
 pub async fn graphql_handler(
     State(schema): State<Schema>,
     user: AuthenticatedUser,
+    headers: HeaderMap,
     Json(req): Json<GraphQLRequest>,
 ) -> Response {
     // Build the GraphQL request
     let mut request = async_graphql::Request::new(req.query);
 
-    // Add authenticated user to GraphQL context
-    request = request.data(user);
+    // Add authenticated user and message locale to GraphQL context
+    request = request
+        .data(user)
+        .data(RequestLocale::from_headers(&headers));
 
     // Add operation name if provided
     if let Some(operation_name) = req.operation_name {
@@ -85,6 +94,13 @@ pub async fn graphql_handler(
         }
     }
 
+    // Add extensions if provided
+    if let Some(extensions) = req.extensions {
+        if let Ok(extensions) = serde_json::from_value(extensions) {
+            request.extensions = extensions;
+        }
+    }
+
     // Execute the query
     let response = schema.execute(request).await;
 
@@ -117,9 +133,35 @@ pub async fn graphql_handler(
 /// # Examples
 ///
 /// Access the playground by navigating to `/graphql/playground` in your browser.
-/// The playground will be configured to send queries to `/graphql`.
-pub async fn graphql_playground(State(_schema): State<Schema>) -> impl IntoResponse {
-    Html(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+/// The playground will be configured to send queries to `/graphql`. When the
+/// API is nested under a path prefix, queries go to the prefixed endpoint.
+///
+/// When the page is requested with a valid bearer token, the playground
+/// sends that token with its queries, so schema documentation still loads
+/// when introspection is limited to authenticated callers.
+pub async fn graphql_playground(
+    State(_schema): State<Schema>,
+    OriginalUri(uri): OriginalUri,
+    user: Option<AuthenticatedUser>,
+    headers: HeaderMap,
+) -> Response {
+    let endpoint = uri.path().strip_suffix("/playground").unwrap_or("/graphql");
+    let mut config = GraphQLPlaygroundConfig::new(endpoint);
+    let authorization = user
+        .and_then(|_| headers.get(header::AUTHORIZATION))
+        .and_then(|value| value.to_str().ok());
+
+    match authorization {
+        Some(authorization) => {
+            config = config.with_header("Authorization", authorization);
+            (
+                [(header::CACHE_CONTROL, "no-store")],
+                Html(playground_source(config)),
+            )
+                .into_response()
+        }
+        None => Html(playground_source(config)).into_response(),
+    }
 }
 
 /// Health check endpoint for GraphQL
@@ -139,14 +181,29 @@ pub async fn graphql_health() -> (StatusCode, Json<serde_json::Value>) {
 mod tests {
     use super::*;
     use crate::api::graphql::create_schema;
-    use crate::application::handlers::{EventReceiverGroupHandler, EventReceiverHandler};
+    use crate::application::authorization::AuthorizationService;
+    use crate::application::handlers::{
+        EventHandler, EventReceiverGroupHandler, EventReceiverHandler,
+    };
+    use crate::auth::api_key::UserRepository;
+    use crate::auth::rbac::roles::Role;
+    use crate::domain::entities::event::Event;
     use crate::domain::entities::event_receiver::EventReceiver;
+    use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+    use crate::domain::entities::user::User;
+    use crate::domain::repositories::pagination::{ListOrder, SortField};
     use crate::domain::repositories::{
-        event_receiver_group_repo::{EventReceiverGroupRepository, FindEventReceiverGroupCriteria},
+        event_receiver_group_repo::{
+            EventReceiverGroupRepository, ExpiredMembership, FindEventReceiverGroupCriteria,
+            GroupMembership,
+        },
         event_receiver_repo::{EventReceiverRepository, FindEventReceiverCriteria},
+        event_repo::{EventRepository, FindEventCriteria},
     };
-    use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
-    use crate::error::Result;
+    use crate::domain::value_objects::{EventId, EventReceiverGroupId, EventReceiverId, UserId};
+    use crate::error::{AuthError, Result};
+    use crate::fixtures::{GroupFixture, ReceiverFixture};
+    use chrono::{DateTime, Utc};
 
     use async_trait::async_trait;
     use std::collections::HashMap;
@@ -219,9 +276,38 @@ mod tests {
 
         async fn find_by_criteria(
             &self,
-            _criteria: FindEventReceiverCriteria,
+            criteria: FindEventReceiverCriteria,
         ) -> Result<Vec<EventReceiver>> {
-            Ok(vec![])
+            let receivers: Vec<_> = self
+                .receivers
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|r| criteria.id.is_none_or(|id| r.id() == id))
+                .filter(|r| {
+                    criteria
+                        .name
+                        .as_ref()
+                        .is_none_or(|n| contains_ci(r.name(), n))
+                })
+                .filter(|r| {
+                    criteria
+                        .receiver_type
+                        .as_ref()
+                        .is_none_or(|t| r.receiver_type() == t)
+                })
+                .filter(|r| criteria.version.as_ref().is_none_or(|v| r.version() == v))
+                .filter(|r| criteria.owner_id.is_none_or(|o| r.owner_id() == o))
+                .cloned()
+                .collect();
+
+            Ok(page(
+                receivers,
+                criteria.order,
+                criteria.limit,
+                criteria.offset,
+                |r| (r.created_at(), r.name().to_string(), r.id().to_string()),
+            ))
         }
 
         async fn find_by_owner(
@@ -253,24 +339,61 @@ mod tests {
         }
     }
 
+    fn contains_ci(value: &str, needle: &str) -> bool {
+        value.to_lowercase().contains(&needle.to_lowercase())
+    }
+
+    /// Sorts and slices matches the way the Postgres repositories do
+    fn page<T, F: SortField>(
+        mut items: Vec<T>,
+        order: ListOrder<F>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+        key: impl Fn(&T) -> (DateTime<Utc>, String, String),
+    ) -> Vec<T> {
+        items.sort_by(|a, b| {
+            let (a_created, a_name, a_id) = key(a);
+            let (b_created, b_name, b_id) = key(b);
+            let ordering = if order.field == F::NAME {
+                (a_name, a_id).cmp(&(b_name, b_id))
+            } else {
+                (a_created, a_id).cmp(&(b_created, b_id))
+            };
+            order.direction.apply(ordering)
+        });
+        items
+            .into_iter()
+            .skip(offset.unwrap_or(0))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+
     // Mock group repository for testing
-    struct MockEventReceiverGroupRepository;
+    #[derive(Default)]
+    struct MockEventReceiverGroupRepository {
+        groups: Mutex<Vec<EventReceiverGroup>>,
+        members: Mutex<Vec<(EventReceiverGroupId, GroupMembership)>>,
+        /// Updates that include this group fail without changing any group
+        failing_update: Mutex<Option<EventReceiverGroupId>>,
+    }
 
     #[async_trait]
     impl EventReceiverGroupRepository for MockEventReceiverGroupRepository {
         async fn save(
             &self,
-            _group: &crate::domain::entities::event_receiver_group::EventReceiverGroup,
+            group: &crate::domain::entities::event_receiver_group::EventReceiverGroup,
         ) -> Result<()> {
+            self.groups.lock().unwrap().push(group.clone());
             Ok(())
         }
 
         async fn find_by_id(
             &self,
-            _id: EventReceiverGroupId,
+            id: EventReceiverGroupId,
         ) -> Result<Option<crate::domain::entities::event_receiver_group::EventReceiverGroup>>
         {
-            Ok(None)
+            let groups = self.groups.lock().unwrap();
+            Ok(groups.iter().find(|g| g.id() == id).cloned())
         }
 
         async fn find_by_name(
@@ -343,8 +466,24 @@ mod tests {
 
         async fn update(
             &self,
-            _group: &crate::domain::entities::event_receiver_group::EventReceiverGroup,
+            group: &crate::domain::entities::event_receiver_group::EventReceiverGroup,
         ) -> Result<()> {
+            self.update_many(std::slice::from_ref(group)).await
+        }
+
+        async fn update_many(&self, updated: &[EventReceiverGroup]) -> Result<()> {
+            let failing = *self.failing_update.lock().unwrap();
+            if updated.iter().any(|group| Some(group.id()) == failing) {
+                return Err(crate::error::Error::Internal {
+                    message: "update failed".to_string(),
+                });
+            }
+            let mut groups = self.groups.lock().unwrap();
+            for group in updated {
+                if let Some(existing) = groups.iter_mut().find(|g| g.id() == group.id()) {
+                    *existing = group.clone();
+                }
+            }
             Ok(())
         }
 
@@ -387,12 +526,57 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn find_default_schema_group(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<EventReceiverGroupId>> {
+            Ok(None)
+        }
+
+        async fn set_default_schema_group(
+            &self,
+            _receiver_id: EventReceiverId,
+            _group_id: EventReceiverGroupId,
+        ) -> Result<()> {
+            Ok(())
+        }
+
         async fn find_by_criteria(
             &self,
-            _criteria: FindEventReceiverGroupCriteria,
+            criteria: FindEventReceiverGroupCriteria,
         ) -> Result<Vec<crate::domain::entities::event_receiver_group::EventReceiverGroup>>
         {
-            Ok(vec![])
+            let groups: Vec<_> = self
+                .groups
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|g| criteria.id.is_none_or(|id| g.id() == id))
+                .filter(|g| {
+                    criteria
+                        .name
+                        .as_ref()
+                        .is_none_or(|n| contains_ci(g.name(), n))
+                })
+                .filter(|g| {
+                    criteria
+                        .group_type
+                        .as_ref()
+                        .is_none_or(|t| g.group_type() == t)
+                })
+                .filter(|g| criteria.version.as_ref().is_none_or(|v| g.version() == v))
+                .filter(|g| criteria.enabled.is_none_or(|e| g.enabled() == e))
+                .filter(|g| criteria.owner_id.is_none_or(|o| g.owner_id() == o))
+                .cloned()
+                .collect();
+
+            Ok(page(
+                groups,
+                criteria.order,
+                criteria.limit,
+                criteria.offset,
+                |g| (g.created_at(), g.name().to_string(), g.id().to_string()),
+            ))
         }
 
         async fn find_by_owner(
@@ -430,53 +614,320 @@ mod tests {
 
         async fn is_member(
             &self,
-            _group_id: EventReceiverGroupId,
-            _user_id: crate::domain::value_objects::UserId,
+            group_id: EventReceiverGroupId,
+            user_id: crate::domain::value_objects::UserId,
         ) -> Result<bool> {
-            Ok(false)
+            let members = self.members.lock().unwrap();
+            Ok(members.iter().any(|(g, m)| {
+                *g == group_id && m.user_id == user_id && !m.is_expired_at(Utc::now())
+            }))
         }
 
         async fn get_group_members(
             &self,
-            _group_id: EventReceiverGroupId,
+            group_id: EventReceiverGroupId,
         ) -> Result<Vec<crate::domain::value_objects::UserId>> {
-            Ok(vec![])
+            let memberships = self.get_group_memberships(group_id).await?;
+            Ok(memberships.into_iter().map(|m| m.user_id).collect())
+        }
+
+        async fn get_group_memberships(
+            &self,
+            group_id: EventReceiverGroupId,
+        ) -> Result<Vec<GroupMembership>> {
+            let members = self.members.lock().unwrap();
+            let mut memberships: Vec<_> = members
+                .iter()
+                .filter(|(g, m)| *g == group_id && !m.is_expired_at(Utc::now()))
+                .map(|(_, m)| m.clone())
+                .collect();
+            memberships.sort_by_key(GroupMembership::cursor);
+            Ok(memberships)
         }
 
         async fn add_member(
             &self,
-            _group_id: EventReceiverGroupId,
-            _user_id: crate::domain::value_objects::UserId,
-            _added_by: crate::domain::value_objects::UserId,
+            group_id: EventReceiverGroupId,
+            user_id: crate::domain::value_objects::UserId,
+            added_by: crate::domain::value_objects::UserId,
+            expires_at: Option<DateTime<Utc>>,
         ) -> Result<()> {
+            let mut members = self.members.lock().unwrap();
+            members.retain(|(g, m)| !(*g == group_id && m.user_id == user_id));
+            members.push((
+                group_id,
+                GroupMembership {
+                    user_id,
+                    added_by,
+                    added_at: Utc::now(),
+                    expires_at,
+                },
+            ));
             Ok(())
         }
 
+        async fn set_member_expiry(
+            &self,
+            group_id: EventReceiverGroupId,
+            user_id: crate::domain::value_objects::UserId,
+            expires_at: Option<DateTime<Utc>>,
+        ) -> Result<()> {
+            let mut members = self.members.lock().unwrap();
+            let membership = members
+                .iter_mut()
+                .find(|(g, m)| {
+                    *g == group_id && m.user_id == user_id && !m.is_expired_at(Utc::now())
+                })
+                .ok_or_else(|| crate::error::Error::NotFound {
+                    resource: format!("User {} is not a member of group {}", user_id, group_id),
+                })?;
+            membership.1.expires_at = expires_at;
+            Ok(())
+        }
+
+        async fn remove_expired_members(
+            &self,
+            now: DateTime<Utc>,
+            limit: usize,
+        ) -> Result<Vec<ExpiredMembership>> {
+            let mut members = self.members.lock().unwrap();
+            let mut expired: Vec<_> = members
+                .iter()
+                .filter(|(_, m)| m.is_expired_at(now))
+                .map(|(group_id, m)| ExpiredMembership {
+                    group_id: *group_id,
+                    membership: m.clone(),
+                })
+                .collect();
+            expired.sort_by_key(|e| e.membership.expires_at);
+            expired.truncate(limit);
+            members.retain(|(g, m)| {
+                !expired
+                    .iter()
+                    .any(|e| e.group_id == *g && e.membership.user_id == m.user_id)
+            });
+            Ok(expired)
+        }
+
         async fn remove_member(
             &self,
-            _group_id: EventReceiverGroupId,
-            _user_id: crate::domain::value_objects::UserId,
+            group_id: EventReceiverGroupId,
+            user_id: crate::domain::value_objects::UserId,
         ) -> Result<()> {
+            let mut members = self.members.lock().unwrap();
+            let before = members.len();
+            members.retain(|(g, m)| !(*g == group_id && m.user_id == user_id));
+            if members.len() == before {
+                return Err(crate::error::Error::NotFound {
+                    resource: format!("User {} is not a member of group {}", user_id, group_id),
+                });
+            }
             Ok(())
         }
 
         async fn find_groups_for_user(
             &self,
-            _user_id: crate::domain::value_objects::UserId,
+            user_id: crate::domain::value_objects::UserId,
         ) -> Result<Vec<crate::domain::entities::event_receiver_group::EventReceiverGroup>>
         {
+            let members = self.members.lock().unwrap();
+            let groups = self.groups.lock().unwrap();
+            Ok(groups
+                .iter()
+                .filter(|g| {
+                    members.iter().any(|(id, m)| {
+                        *id == g.id() && m.user_id == user_id && !m.is_expired_at(Utc::now())
+                    })
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    // User repository holding a fixed set of users; counts batched lookups
+    #[derive(Default)]
+    struct MockUserRepository {
+        users: Vec<User>,
+        batches: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl UserRepository for MockUserRepository {
+        async fn find_by_id(&self, id: UserId) -> std::result::Result<Option<User>, AuthError> {
+            Ok(self.users.iter().find(|u| *u.id() == id).cloned())
+        }
+
+        async fn find_by_ids(&self, ids: &[UserId]) -> std::result::Result<Vec<User>, AuthError> {
+            *self.batches.lock().unwrap() += 1;
+            Ok(self
+                .users
+                .iter()
+                .filter(|u| ids.contains(u.id()))
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_username(
+            &self,
+            _username: &str,
+        ) -> std::result::Result<Option<User>, AuthError> {
+            Ok(None)
+        }
+
+        async fn save(&self, _user: &User) -> std::result::Result<(), AuthError> {
+            Ok(())
+        }
+
+        async fn find_all(&self) -> std::result::Result<Vec<User>, AuthError> {
+            Ok(self.users.clone())
+        }
+
+        async fn add_role(
+            &self,
+            _user_id: &UserId,
+            _role: Role,
+        ) -> std::result::Result<(), AuthError> {
+            Ok(())
+        }
+
+        async fn remove_role(
+            &self,
+            _user_id: &UserId,
+            _role: Role,
+        ) -> std::result::Result<(), AuthError> {
+            Ok(())
+        }
+    }
+
+    // Event repository without events
+    struct EmptyEventRepository;
+
+    #[async_trait]
+    impl EventRepository for EmptyEventRepository {
+        async fn save(&self, _event: &Event) -> Result<()> {
+            Ok(())
+        }
+
+        async fn find_by_id(&self, _id: EventId) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_success(&self, _success: bool) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_name(&self, _name: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_platform_id(&self, _platform_id: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_package(&self, _package: &str) -> Result<Vec<Event>> {
             Ok(vec![])
         }
+
+        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn count_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn count_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn delete(&self, _id: EventId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn find_latest_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_latest_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_by_time_range(
+            &self,
+            _start: DateTime<Utc>,
+            _end: DateTime<Utc>,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_criteria(&self, _criteria: FindEventCriteria) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner(&self, _owner_id: UserId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner_paginated(
+            &self,
+            _owner_id: UserId,
+            _limit: usize,
+            _offset: usize,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn is_owner(&self, _event_id: EventId, _user_id: UserId) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn get_resource_version(&self, _event_id: EventId) -> Result<Option<i64>> {
+            Ok(None)
+        }
     }
 
     fn create_test_schema() -> Schema {
-        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
-        let group_repo = Arc::new(MockEventReceiverGroupRepository);
+        create_seeded_schema(
+            Arc::new(MockEventReceiverRepository::new()),
+            Arc::new(MockEventReceiverGroupRepository::default()),
+        )
+    }
 
+    fn create_seeded_schema(
+        receiver_repo: Arc<MockEventReceiverRepository>,
+        group_repo: Arc<MockEventReceiverGroupRepository>,
+    ) -> Schema {
+        let event_handler = Arc::new(EventHandler::new(
+            Arc::new(EmptyEventRepository),
+            receiver_repo.clone(),
+        ));
         let receiver_handler = Arc::new(EventReceiverHandler::new(receiver_repo.clone()));
+        let authorization = AuthorizationService::new(receiver_repo.clone(), group_repo.clone());
         let group_handler = Arc::new(EventReceiverGroupHandler::new(group_repo, receiver_repo));
 
-        create_schema(receiver_handler, group_handler)
+        create_schema(
+            event_handler,
+            receiver_handler,
+            group_handler,
+            authorization,
+        )
     }
 
     fn create_test_authenticated_user() -> AuthenticatedUser {
@@ -503,18 +954,21 @@ mod tests {
             query: r#"
                 {
                     eventReceivers(eventReceiver: {}) {
-                        id
-                        name
+                        nodes {
+                            id
+                            name
+                        }
                     }
                 }
             "#
             .to_string(),
             operation_name: None,
             variables: None,
+            extensions: None,
         };
 
         let user = create_test_authenticated_user();
-        let response = graphql_handler(State(schema), user, Json(request)).await;
+        let response = graphql_handler(State(schema), user, HeaderMap::new(), Json(request)).await;
 
         // Verify we get a response (status check)
         assert_eq!(response.status(), StatusCode::OK);
@@ -528,28 +982,35 @@ mod tests {
             query: r#"
                 query GetReceivers {
                     eventReceivers(eventReceiver: {}) {
-                        id
-                        name
+                        nodes {
+                            id
+                            name
+                        }
                     }
                 }
             "#
             .to_string(),
             operation_name: Some("GetReceivers".to_string()),
             variables: None,
+            extensions: None,
         };
 
         let user = create_test_authenticated_user();
-        let response = graphql_handler(State(schema), user, Json(request)).await;
+        let response = graphql_handler(State(schema), user, HeaderMap::new(), Json(request)).await;
 
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    fn playground_uri() -> OriginalUri {
+        OriginalUri("/graphql/playground".parse().unwrap())
+    }
+
     #[tokio::test]
     async fn test_graphql_playground_returns_html() {
         let schema = create_test_schema();
 
-        let response = graphql_playground(State(schema)).await;
-        let html_response = response.into_response();
+        let html_response =
+            graphql_playground(State(schema), playground_uri(), None, HeaderMap::new()).await;
 
         // Verify the response has HTML content type
         assert_eq!(
@@ -561,6 +1022,36 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_graphql_playground_forwards_token_for_authenticated_user() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer token-123".parse().unwrap());
+
+        let response = graphql_playground(
+            State(create_test_schema()),
+            playground_uri(),
+            Some(create_test_authenticated_user()),
+            headers.clone(),
+        )
+        .await;
+        assert_eq!(
+            response.headers().get(header::CACHE_CONTROL).unwrap(),
+            "no-store"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("Bearer token-123"));
+
+        // Without a validated user the header is not echoed into the page
+        let response =
+            graphql_playground(State(create_test_schema()), playground_uri(), None, headers).await;
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(!String::from_utf8_lossy(&body).contains("Bearer token-123"));
+    }
+
     #[tokio::test]
     async fn test_graphql_health_endpoint() {
         let (status, json) = graphql_health().await;
@@ -581,20 +1072,794 @@ mod tests {
         let request = GraphQLRequest {
             query: r#"
                 query GetReceivers($limit: Int) {
-                    eventReceivers(eventReceiver: {}) {
-                        id
-                        name
+                    eventReceivers(eventReceiver: { limit: $limit }) {
+                        nodes {
+                            id
+                            name
+                        }
                     }
                 }
             "#
             .to_string(),
             operation_name: Some("GetReceivers".to_string()),
             variables: Some(variables),
+            extensions: None,
         };
 
         let user = create_test_authenticated_user();
-        let response = graphql_handler(State(schema), user, Json(request)).await;
+        let response = graphql_handler(State(schema), user, HeaderMap::new(), Json(request)).await;
 
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    async fn execute(schema: &Schema, query: &str) -> serde_json::Value {
+        let response = schema
+            .execute(async_graphql::Request::new(query).data(create_test_authenticated_user()))
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        response.data.into_json().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_event_receivers_filter_and_total_count() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let owner_id = UserId::new();
+        for (name, receiver_type, owner) in [
+            ("alpha-hook", "webhook", owner_id),
+            ("beta-hook", "webhook", owner_id),
+            ("gamma-hook", "webhook", UserId::new()),
+            ("delta-queue", "kafka", owner_id),
+            ("epsilon-hook", "webhook", owner_id),
+        ] {
+            let receiver = EventReceiver::new(
+                name.to_string(),
+                receiver_type.to_string(),
+                "1.0.0".to_string(),
+                "Seeded receiver".to_string(),
+                serde_json::json!({}),
+                owner,
+            )
+            .unwrap();
+            receiver_repo.save(&receiver).await.unwrap();
+        }
+        let schema = create_seeded_schema(
+            receiver_repo,
+            Arc::new(MockEventReceiverGroupRepository::default()),
+        );
+
+        let query = format!(
+            r#"{{ eventReceivers(eventReceiver: {{ type: "webhook", ownerId: "{}", limit: 2, sort: NAME_ASC }}) {{
+                nodes {{ name }} totalCount limit offset hasMore
+            }} }}"#,
+            owner_id
+        );
+        let data = execute(&schema, &query).await;
+        let connection = &data["eventReceivers"];
+        assert_eq!(connection["totalCount"], 3);
+        assert_eq!(connection["limit"], 2);
+        assert_eq!(connection["hasMore"], true);
+        assert_eq!(
+            connection["nodes"],
+            serde_json::json!([{"name": "alpha-hook"}, {"name": "beta-hook"}])
+        );
+
+        let query = format!(
+            r#"{{ eventReceivers(eventReceiver: {{ type: "webhook", ownerId: "{}", limit: 2, offset: 2, sort: NAME_ASC }}) {{
+                nodes {{ name }} totalCount hasMore
+            }} }}"#,
+            owner_id
+        );
+        let data = execute(&schema, &query).await;
+        assert_eq!(data["eventReceivers"]["totalCount"], 3);
+        assert_eq!(data["eventReceivers"]["hasMore"], false);
+        assert_eq!(
+            data["eventReceivers"]["nodes"],
+            serde_json::json!([{"name": "epsilon-hook"}])
+        );
+
+        // Without a filter every receiver is counted
+        let data = execute(&schema, "{ eventReceivers { totalCount limit } }").await;
+        assert_eq!(data["eventReceivers"]["totalCount"], 5);
+        assert_eq!(data["eventReceivers"]["limit"], 50);
+    }
+
+    #[tokio::test]
+    async fn test_event_receiver_groups_filter_and_total_count() {
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::default());
+        for (name, enabled) in [("nightly", true), ("release", false), ("canary", true)] {
+            let group = EventReceiverGroup::new(
+                name.to_string(),
+                "pipeline".to_string(),
+                "1.0.0".to_string(),
+                "Seeded group".to_string(),
+                enabled,
+                vec![],
+                UserId::new(),
+            )
+            .unwrap();
+            group_repo.save(&group).await.unwrap();
+        }
+        let schema = create_seeded_schema(Arc::new(MockEventReceiverRepository::new()), group_repo);
+
+        let data = execute(
+            &schema,
+            "{ eventReceiverGroups(eventReceiverGroup: { enabled: true, sort: NAME_ASC }) { nodes { name } totalCount hasMore } }",
+        )
+        .await;
+        assert_eq!(data["eventReceiverGroups"]["totalCount"], 2);
+        assert_eq!(data["eventReceiverGroups"]["hasMore"], false);
+        assert_eq!(
+            data["eventReceiverGroups"]["nodes"],
+            serde_json::json!([{"name": "canary"}, {"name": "nightly"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_event_receivers_rejects_out_of_range_limit() {
+        let schema = create_test_schema();
+        for limit in [0, 1001] {
+            let response = schema
+                .execute(
+                    async_graphql::Request::new(format!(
+                        "{{ eventReceivers(eventReceiver: {{ limit: {} }}) {{ totalCount }} }}",
+                        limit
+                    ))
+                    .data(create_test_authenticated_user()),
+                )
+                .await;
+            assert_eq!(response.errors.len(), 1);
+            assert!(response.errors[0].message.contains("Limit must be between"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validation_errors_follow_accept_language() {
+        let schema = create_test_schema();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_LANGUAGE, "de-DE, en;q=0.8".parse().unwrap());
+
+        let request = GraphQLRequest {
+            query: "{ eventReceivers(eventReceiver: { limit: 0 }) { totalCount } }".to_string(),
+            operation_name: None,
+            variables: None,
+            extensions: None,
+        };
+        let response = graphql_handler(
+            State(schema),
+            create_test_authenticated_user(),
+            headers,
+            Json(request),
+        )
+        .await;
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let error = &json["errors"][0];
+        assert_eq!(
+            error["message"],
+            "Das Limit muss zwischen 1 und 1000 liegen"
+        );
+        assert_eq!(error["extensions"]["code"], "validation_error");
+        assert_eq!(error["extensions"]["message_key"], "validation.limit_range");
+        assert_eq!(error["extensions"]["params"]["max"], "1000");
+        assert_eq!(error["extensions"]["field"], "limit");
+    }
+
+    struct MembershipFixture {
+        schema: Schema,
+        users: Arc<MockUserRepository>,
+        group_repo: Arc<MockEventReceiverGroupRepository>,
+        group_id: EventReceiverGroupId,
+        owner: UserId,
+        members: Vec<UserId>,
+    }
+
+    /// A group with `member_count` members added a second apart, oldest
+    /// first, all known to the user repository
+    async fn membership_fixture(member_count: usize) -> MembershipFixture {
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::default());
+        let owner = UserId::new();
+        let group = EventReceiverGroup::new(
+            "pipeline".to_string(),
+            "ci".to_string(),
+            "1.0.0".to_string(),
+            "Seeded group".to_string(),
+            true,
+            vec![],
+            owner,
+        )
+        .unwrap();
+        group_repo.save(&group).await.unwrap();
+
+        let users: Vec<User> = (0..member_count)
+            .map(|i| {
+                User::new_oidc(
+                    format!("member-{}", i),
+                    format!("member-{}@example.com", i),
+                    format!("subject-{}", i),
+                )
+            })
+            .collect();
+        let start = Utc::now() - chrono::Duration::hours(1);
+        for (i, user) in users.iter().enumerate() {
+            group_repo.members.lock().unwrap().push((
+                group.id(),
+                GroupMembership {
+                    user_id: *user.id(),
+                    added_by: owner,
+                    added_at: start + chrono::Duration::seconds(i as i64),
+                    expires_at: None,
+                },
+            ));
+        }
+        let members = users.iter().map(|u| *u.id()).collect();
+        let users = Arc::new(MockUserRepository {
+            users,
+            ..Default::default()
+        });
+
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let schema = crate::api::graphql::create_schema_with_users(
+            Arc::new(EventHandler::new(
+                Arc::new(EmptyEventRepository),
+                receiver_repo.clone(),
+            )),
+            Arc::new(EventReceiverHandler::new(receiver_repo.clone())),
+            Arc::new(EventReceiverGroupHandler::new(
+                group_repo.clone(),
+                receiver_repo.clone(),
+            )),
+            AuthorizationService::new(receiver_repo, group_repo.clone()),
+            users.clone(),
+            &crate::infrastructure::config::GraphQLConfig::default(),
+        );
+
+        MembershipFixture {
+            schema,
+            users,
+            group_repo,
+            group_id: group.id(),
+            owner,
+            members,
+        }
+    }
+
+    /// Executes `query` as `user_id`, returning the serialized response
+    async fn execute_as(schema: &Schema, user_id: UserId, query: &str) -> serde_json::Value {
+        use crate::auth::jwt::claims::Claims;
+
+        let user = AuthenticatedUser::new(Claims::new_access_token(
+            user_id.to_string(),
+            vec!["user".to_string()],
+            vec![],
+            "xzepr".to_string(),
+            "xzepr-api".to_string(),
+            chrono::Duration::hours(1),
+        ));
+        let response = schema
+            .execute(async_graphql::Request::new(query).data(user))
+            .await;
+        serde_json::to_value(&response).unwrap()
+    }
+
+    fn members_query(group_id: EventReceiverGroupId, arguments: &str) -> String {
+        format!(
+            r#"{{ group(id: "{}") {{ members{} {{
+                nodes {{ userId username email addedBy }} totalCount endCursor hasNextPage
+            }} }} }}"#,
+            group_id, arguments
+        )
+    }
+
+    #[tokio::test]
+    async fn test_group_members_visible_to_owner_and_members_only() {
+        let fixture = membership_fixture(3).await;
+        let query = members_query(fixture.group_id, "");
+
+        let json = execute_as(&fixture.schema, fixture.members[1], &query).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        let members = &json["data"]["group"]["members"];
+        assert_eq!(members["totalCount"], 3);
+        assert_eq!(members["hasNextPage"], false);
+        let nodes = members["nodes"].as_array().unwrap();
+        assert_eq!(nodes.len(), 3);
+        for (i, node) in nodes.iter().enumerate() {
+            assert_eq!(node["userId"], fixture.members[i].to_string());
+            assert_eq!(node["username"], format!("member-{}", i));
+            assert_eq!(node["email"], format!("member-{}@example.com", i));
+            assert_eq!(node["addedBy"], fixture.owner.to_string());
+        }
+        // Usernames and emails of every member came from one lookup
+        assert_eq!(*fixture.users.batches.lock().unwrap(), 1);
+
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        assert_eq!(json["data"]["group"]["members"]["totalCount"], 3);
+
+        let json = execute_as(&fixture.schema, UserId::new(), &query).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "forbidden");
+        assert_eq!(json["data"]["group"], serde_json::Value::Null);
+
+        // Members see the group from `me`; outsiders see no groups
+        let me = "{ me { groups { id name } } }";
+        let json = execute_as(&fixture.schema, fixture.members[0], me).await;
+        assert_eq!(
+            json["data"]["me"]["groups"],
+            serde_json::json!([{"id": fixture.group_id.to_string(), "name": "pipeline"}])
+        );
+        let json = execute_as(&fixture.schema, UserId::new(), me).await;
+        assert_eq!(json["data"]["me"]["groups"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_group_members_cursor_pagination() {
+        let fixture = membership_fixture(5).await;
+        let page = |json: &serde_json::Value| {
+            let members = &json["data"]["group"]["members"];
+            let ids: Vec<String> = members["nodes"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|n| n["userId"].as_str().unwrap().to_string())
+                .collect();
+            (
+                ids,
+                members["endCursor"].as_str().map(str::to_string),
+                members["hasNextPage"].as_bool().unwrap(),
+            )
+        };
+        let ids = |range: std::ops::Range<usize>| -> Vec<String> {
+            fixture.members[range]
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        };
+
+        let query = members_query(fixture.group_id, "(first: 2)");
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        let (first_page, cursor, has_next) = page(&json);
+        assert_eq!(first_page, ids(0..2));
+        assert!(has_next);
+        let cursor = cursor.unwrap();
+
+        let query = members_query(
+            fixture.group_id,
+            &format!(r#"(first: 2, after: "{}")"#, cursor),
+        );
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        let (second_page, _, has_next) = page(&json);
+        assert_eq!(second_page, ids(2..4));
+        assert!(has_next);
+
+        // Removing a member already paged past does not shift later pages
+        let remove = format!(
+            r#"mutation {{ removeGroupMember(groupId: "{}", userId: "{}") }}"#,
+            fixture.group_id, fixture.members[0]
+        );
+        let json = execute_as(&fixture.schema, fixture.owner, &remove).await;
+        assert_eq!(json["data"]["removeGroupMember"], true);
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        assert_eq!(page(&json).0, second_page);
+        assert_eq!(json["data"]["group"]["members"]["totalCount"], 4);
+
+        let query = members_query(
+            fixture.group_id,
+            &format!(r#"(first: 2, after: "{}")"#, page(&json).1.unwrap()),
+        );
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        let (last_page, _, has_next) = page(&json);
+        assert_eq!(last_page, ids(4..5));
+        assert!(!has_next);
+
+        let query = members_query(fixture.group_id, r#"(after: "not-a-cursor")"#);
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "validation_error");
+        assert_eq!(json["errors"][0]["extensions"]["field"], "after");
+    }
+
+    #[tokio::test]
+    async fn test_add_group_member_owner_only_and_conflict() {
+        let fixture = membership_fixture(1).await;
+        let new_member = UserId::new();
+        let add = format!(
+            r#"mutation {{ addGroupMember(groupId: "{}", userId: "{}") {{ userId addedBy }} }}"#,
+            fixture.group_id, new_member
+        );
+
+        let json = execute_as(&fixture.schema, fixture.members[0], &add).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "forbidden");
+
+        let json = execute_as(&fixture.schema, fixture.owner, &add).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        assert_eq!(
+            json["data"]["addGroupMember"],
+            serde_json::json!({
+                "userId": new_member.to_string(),
+                "addedBy": fixture.owner.to_string()
+            })
+        );
+
+        let json = execute_as(&fixture.schema, fixture.owner, &add).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "conflict");
+        assert_eq!(
+            json["errors"][0]["message"],
+            "User is already a member of this group"
+        );
+
+        let remove = format!(
+            r#"mutation {{ removeGroupMember(groupId: "{}", userId: "{}") }}"#,
+            fixture.group_id,
+            UserId::new()
+        );
+        let json = execute_as(&fixture.schema, fixture.owner, &remove).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "not_found");
+
+        let add_to_unknown_group = format!(
+            r#"mutation {{ addGroupMember(groupId: "{}", userId: "{}") {{ userId }} }}"#,
+            EventReceiverGroupId::new(),
+            new_member
+        );
+        let json = execute_as(&fixture.schema, fixture.owner, &add_to_unknown_group).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "not_found");
+    }
+
+    struct ReceiverGroupFixture {
+        schema: Schema,
+        group_repo: Arc<MockEventReceiverGroupRepository>,
+        owner: UserId,
+        source: EventReceiverGroupId,
+        destination: EventReceiverGroupId,
+        receiver: EventReceiverId,
+        spare: EventReceiverId,
+    }
+
+    /// Two groups with the same owner, the source holding `receiver`, and
+    /// a `spare` receiver in neither
+    async fn receiver_group_fixture() -> ReceiverGroupFixture {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let receiver = ReceiverFixture::new().name("build").build();
+        let spare = ReceiverFixture::new().name("deploy").build();
+        receiver_repo.save(&receiver).await.unwrap();
+        receiver_repo.save(&spare).await.unwrap();
+
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::default());
+        let owner = UserId::new();
+        let source = GroupFixture::new()
+            .name("source")
+            .owner(owner)
+            .receiver(&receiver)
+            .build();
+        let destination = GroupFixture::new().name("destination").owner(owner).build();
+        group_repo.save(&source).await.unwrap();
+        group_repo.save(&destination).await.unwrap();
+
+        ReceiverGroupFixture {
+            schema: create_seeded_schema(receiver_repo, group_repo.clone()),
+            group_repo,
+            owner,
+            source: source.id(),
+            destination: destination.id(),
+            receiver: receiver.id(),
+            spare: spare.id(),
+        }
+    }
+
+    impl ReceiverGroupFixture {
+        fn receivers_of(&self, group_id: EventReceiverGroupId) -> Vec<EventReceiverId> {
+            self.group_repo
+                .groups
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|g| g.id() == group_id)
+                .unwrap()
+                .event_receiver_ids()
+                .to_vec()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_receiver_to_group_returns_updated_group() {
+        let fixture = receiver_group_fixture().await;
+        let add = |receiver: EventReceiverId| {
+            format!(
+                r#"mutation {{ addReceiverToGroup(groupId: "{}", receiverId: "{}") {{
+                    id eventReceiverIds resourceVersion
+                }} }}"#,
+                fixture.destination, receiver
+            )
+        };
+
+        let json = execute_as(&fixture.schema, UserId::new(), &add(fixture.spare)).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "forbidden");
+        assert!(fixture.receivers_of(fixture.destination).is_empty());
+
+        let json = execute_as(&fixture.schema, fixture.owner, &add(fixture.spare)).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        let group = &json["data"]["addReceiverToGroup"];
+        assert_eq!(group["id"], fixture.destination.to_string());
+        assert_eq!(
+            group["eventReceiverIds"],
+            serde_json::json!([fixture.spare.to_string()])
+        );
+        assert_eq!(group["resourceVersion"], 2);
+
+        let json = execute_as(&fixture.schema, fixture.owner, &add(fixture.spare)).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "conflict");
+
+        let json = execute_as(&fixture.schema, fixture.owner, &add(EventReceiverId::new())).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "not_found");
+        assert_eq!(
+            fixture.receivers_of(fixture.destination),
+            vec![fixture.spare]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_receiver_from_group_returns_updated_group() {
+        let fixture = receiver_group_fixture().await;
+        let remove = format!(
+            r#"mutation {{ removeReceiverFromGroup(groupId: "{}", receiverId: "{}") {{
+                eventReceiverIds resourceVersion
+            }} }}"#,
+            fixture.source, fixture.receiver
+        );
+
+        let json = execute_as(&fixture.schema, UserId::new(), &remove).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "forbidden");
+
+        let json = execute_as(&fixture.schema, fixture.owner, &remove).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        assert_eq!(
+            json["data"]["removeReceiverFromGroup"],
+            serde_json::json!({"eventReceiverIds": [], "resourceVersion": 2})
+        );
+
+        let json = execute_as(&fixture.schema, fixture.owner, &remove).await;
+        assert_eq!(
+            json["errors"][0]["extensions"]["code"],
+            "business_rule_violation"
+        );
+        assert_eq!(
+            json["errors"][0]["extensions"]["message_key"],
+            "rule.group_member_missing"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_enable_and_disable_group_return_the_group() {
+        let fixture = receiver_group_fixture().await;
+        let toggle = |mutation: &str| {
+            format!(
+                r#"mutation {{ {}(id: "{}") {{ id enabled resourceVersion }} }}"#,
+                mutation, fixture.source
+            )
+        };
+
+        let json = execute_as(&fixture.schema, UserId::new(), &toggle("disableGroup")).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "forbidden");
+
+        let json = execute_as(&fixture.schema, fixture.owner, &toggle("disableGroup")).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        assert_eq!(
+            json["data"]["disableGroup"],
+            serde_json::json!({
+                "id": fixture.source.to_string(),
+                "enabled": false,
+                "resourceVersion": 2
+            })
+        );
+
+        let json = execute_as(&fixture.schema, fixture.owner, &toggle("enableGroup")).await;
+        assert_eq!(json["data"]["enableGroup"]["enabled"], true);
+        assert_eq!(json["data"]["enableGroup"]["resourceVersion"], 3);
+
+        // Enabling an enabled group leaves it unchanged
+        let json = execute_as(&fixture.schema, fixture.owner, &toggle("enableGroup")).await;
+        assert_eq!(json["data"]["enableGroup"]["resourceVersion"], 3);
+    }
+
+    fn move_query(
+        from: EventReceiverGroupId,
+        to: EventReceiverGroupId,
+        receiver: EventReceiverId,
+    ) -> String {
+        format!(
+            r#"mutation {{ moveReceiver(fromGroup: "{}", toGroup: "{}", receiverId: "{}") {{
+                fromGroup {{ id eventReceiverIds resourceVersion }}
+                toGroup {{ id eventReceiverIds resourceVersion }}
+            }} }}"#,
+            from, to, receiver
+        )
+    }
+
+    #[tokio::test]
+    async fn test_move_receiver_between_groups() {
+        let fixture = receiver_group_fixture().await;
+        let query = move_query(fixture.source, fixture.destination, fixture.receiver);
+
+        let json = execute_as(&fixture.schema, UserId::new(), &query).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "forbidden");
+
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        assert_eq!(
+            json["data"]["moveReceiver"],
+            serde_json::json!({
+                "fromGroup": {
+                    "id": fixture.source.to_string(),
+                    "eventReceiverIds": [],
+                    "resourceVersion": 2
+                },
+                "toGroup": {
+                    "id": fixture.destination.to_string(),
+                    "eventReceiverIds": [fixture.receiver.to_string()],
+                    "resourceVersion": 2
+                }
+            })
+        );
+
+        // The receiver is no longer in the source group
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        assert_eq!(
+            json["errors"][0]["extensions"]["message_key"],
+            "rule.group_member_missing"
+        );
+
+        let same_group = move_query(fixture.destination, fixture.destination, fixture.receiver);
+        let json = execute_as(&fixture.schema, fixture.owner, &same_group).await;
+        assert_eq!(
+            json["errors"][0]["extensions"]["message_key"],
+            "rule.group_move_same_group"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_move_receiver_into_a_group_already_holding_it_is_a_conflict() {
+        let fixture = receiver_group_fixture().await;
+        let add = format!(
+            r#"mutation {{ addReceiverToGroup(groupId: "{}", receiverId: "{}") {{ id }} }}"#,
+            fixture.destination, fixture.receiver
+        );
+        let json = execute_as(&fixture.schema, fixture.owner, &add).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+
+        let query = move_query(fixture.source, fixture.destination, fixture.receiver);
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "conflict");
+        assert_eq!(fixture.receivers_of(fixture.source), vec![fixture.receiver]);
+    }
+
+    #[tokio::test]
+    async fn test_move_receiver_keeps_source_membership_when_destination_fails() {
+        let fixture = receiver_group_fixture().await;
+        *fixture.group_repo.failing_update.lock().unwrap() = Some(fixture.destination);
+
+        let query = move_query(fixture.source, fixture.destination, fixture.receiver);
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        assert!(json.get("errors").is_some(), "{}", json);
+        assert_eq!(json["data"], serde_json::Value::Null);
+
+        assert_eq!(fixture.receivers_of(fixture.source), vec![fixture.receiver]);
+        assert!(fixture.receivers_of(fixture.destination).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expired_members_hidden_from_listing() {
+        let fixture = membership_fixture(3).await;
+        let expired = fixture.members[1];
+        for (_, membership) in fixture.group_repo.members.lock().unwrap().iter_mut() {
+            if membership.user_id == expired {
+                membership.expires_at = Some(Utc::now() - chrono::Duration::minutes(1));
+            }
+        }
+        let query = members_query(fixture.group_id, "");
+
+        let json = execute_as(&fixture.schema, fixture.owner, &query).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        let members = &json["data"]["group"]["members"];
+        assert_eq!(members["totalCount"], 2);
+        let ids: Vec<&str> = members["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["userId"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                fixture.members[0].to_string(),
+                fixture.members[2].to_string()
+            ]
+        );
+
+        // The lapsed member is treated as an outsider
+        let json = execute_as(&fixture.schema, expired, &query).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "forbidden");
+    }
+
+    #[tokio::test]
+    async fn test_update_group_member_extends_expiry() {
+        let fixture = membership_fixture(0).await;
+        let contractor = UserId::new();
+        let at = |days: i64| {
+            (Utc::now() + chrono::Duration::days(days))
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        };
+        let stored_expiry = || {
+            fixture
+                .group_repo
+                .members
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(_, m)| m.user_id == contractor)
+                .and_then(|(_, m)| m.expires_at)
+        };
+
+        let add = format!(
+            r#"mutation {{ addGroupMember(groupId: "{}", userId: "{}", expiresAt: "{}") {{ expiresAt }} }}"#,
+            fixture.group_id,
+            contractor,
+            at(7)
+        );
+        let json = execute_as(&fixture.schema, fixture.owner, &add).await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        assert!(json["data"]["addGroupMember"]["expiresAt"].is_string());
+
+        let extended = at(30);
+        let update = |expires_at: &str| {
+            format!(
+                r#"mutation {{ updateGroupMember(groupId: "{}", userId: "{}", expiresAt: {}) {{ userId expiresAt }} }}"#,
+                fixture.group_id, contractor, expires_at
+            )
+        };
+        let json = execute_as(
+            &fixture.schema,
+            fixture.owner,
+            &update(&format!("\"{}\"", extended)),
+        )
+        .await;
+        assert!(json.get("errors").is_none(), "{}", json);
+        let expected = DateTime::parse_from_rfc3339(&extended)
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(stored_expiry(), Some(expected));
+        assert_eq!(json["data"]["updateGroupMember"]["expiresAt"], extended);
+
+        for (expires_at, key) in [
+            (at(-1), "validation.membership_expiry_past"),
+            (at(400), "validation.membership_expiry_too_far"),
+        ] {
+            let json = execute_as(
+                &fixture.schema,
+                fixture.owner,
+                &update(&format!("\"{}\"", expires_at)),
+            )
+            .await;
+            assert_eq!(json["errors"][0]["extensions"]["code"], "validation_error");
+            assert_eq!(json["errors"][0]["extensions"]["message_key"], key);
+            assert_eq!(stored_expiry(), Some(expected));
+        }
+
+        // Only the owner may extend, and a null expiry makes it permanent
+        let json = execute_as(&fixture.schema, contractor, &update("null")).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "forbidden");
+        let json = execute_as(&fixture.schema, fixture.owner, &update("null")).await;
+        assert_eq!(
+            json["data"]["updateGroupMember"]["expiresAt"],
+            serde_json::Value::Null
+        );
+        assert_eq!(stored_expiry(), None);
+
+        let outsider = format!(
+            r#"mutation {{ updateGroupMember(groupId: "{}", userId: "{}", expiresAt: "{}") {{ userId }} }}"#,
+            fixture.group_id,
+            UserId::new(),
+            at(1)
+        );
+        let json = execute_as(&fixture.schema, fixture.owner, &outsider).await;
+        assert_eq!(json["errors"][0]["extensions"]["code"], "not_found");
+    }
 }
@@ -0,0 +1,126 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/graphql/introspection.rs
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo,
+};
+use async_graphql::{ServerError, ServerResult, Value};
+use std::sync::Arc;
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::infrastructure::config::IntrospectionMode;
+
+/// Schema extension rejecting introspection fields the caller may not use
+///
+/// Only the root `__schema` and `__type` fields are checked; `__typename`
+/// and ordinary fields always resolve.
+pub struct IntrospectionGuard {
+    mode: IntrospectionMode,
+}
+
+impl IntrospectionGuard {
+    /// Creates a guard enforcing `mode`
+    pub fn new(mode: IntrospectionMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl ExtensionFactory for IntrospectionGuard {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(IntrospectionGuardExtension { mode: self.mode })
+    }
+}
+
+struct IntrospectionGuardExtension {
+    mode: IntrospectionMode,
+}
+
+#[async_trait::async_trait]
+impl Extension for IntrospectionGuardExtension {
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let introspection = matches!(info.name, "__schema" | "__type");
+        let authenticated = ctx.data_opt::<AuthenticatedUser>().is_some();
+        if introspection && !self.mode.allows(authenticated) {
+            return Err(ServerError::new(
+                "Introspection is not allowed",
+                Some(info.field.name.pos),
+            ));
+        }
+
+        next.run(ctx, info).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Request, Schema};
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn status(&self) -> String {
+            "ok".to_string()
+        }
+    }
+
+    fn user() -> AuthenticatedUser {
+        use crate::auth::jwt::claims::Claims;
+
+        AuthenticatedUser::new(Claims::new_access_token(
+            "user-1".to_string(),
+            vec!["user".to_string()],
+            vec![],
+            "xzepr-dev".to_string(),
+            "xzepr-api-dev".to_string(),
+            chrono::Duration::minutes(15),
+        ))
+    }
+
+    async fn run(mode: IntrospectionMode, query: &str, authenticated: bool) -> bool {
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .extension(IntrospectionGuard::new(mode))
+            .finish();
+        let mut request = Request::new(query);
+        if authenticated {
+            request = request.data(user());
+        }
+        schema.execute(request).await.errors.is_empty()
+    }
+
+    #[tokio::test]
+    async fn test_introspection_follows_mode() {
+        let schema_query = "{ __schema { queryType { name } } }";
+        let type_query = r#"{ __type(name: "QueryRoot") { name } }"#;
+
+        for query in [schema_query, type_query] {
+            assert!(run(IntrospectionMode::Enabled, query, false).await);
+            assert!(run(IntrospectionMode::Enabled, query, true).await);
+            assert!(!run(IntrospectionMode::AuthenticatedOnly, query, false).await);
+            assert!(run(IntrospectionMode::AuthenticatedOnly, query, true).await);
+            assert!(!run(IntrospectionMode::Disabled, query, false).await);
+            assert!(!run(IntrospectionMode::Disabled, query, true).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_normal_queries_are_never_affected() {
+        for mode in [
+            IntrospectionMode::Enabled,
+            IntrospectionMode::AuthenticatedOnly,
+            IntrospectionMode::Disabled,
+        ] {
+            for authenticated in [false, true] {
+                assert!(run(mode, "{ status __typename }", authenticated).await);
+            }
+        }
+    }
+}
@@ -0,0 +1,553 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/graphql/limits.rs
+
+//! Resource bounds applied to GraphQL requests before they are parsed
+//!
+//! The complexity analyzer prices a query once it has been parsed, which is
+//! too late for a document that is expensive to parse in the first place.
+//! [`query_limits_middleware`] stops reading the body at
+//! `graphql.max_request_bytes`, refuses batched requests, and scans the
+//! query text for its token count, its number of operations and fragments,
+//! and repeated aliases of one field before async-graphql sees it. The scan
+//! keeps no tokens, so it runs in constant memory besides the alias counts
+//! of the open selection sets.
+
+use async_graphql::ServerError;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::api::graphql::coded_error;
+use crate::infrastructure::config::GraphQLConfig;
+
+/// Error code for a request body or query document over a limit
+pub const QUERY_TOO_LARGE: &str = "QUERY_TOO_LARGE";
+
+/// Error code for a selection set aliasing one field too often
+pub const TOO_MANY_ALIASES: &str = "TOO_MANY_ALIASES";
+
+/// Keywords that start an operation or fragment definition
+const DEFINITION_KEYWORDS: [&str; 4] = ["query", "mutation", "subscription", "fragment"];
+
+/// Bounds on a GraphQL request, from the `graphql` settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryLimits {
+    /// Largest request body read, in bytes
+    pub max_request_bytes: usize,
+    /// Most lexical tokens in the query document
+    pub max_tokens: usize,
+    /// Most operations and fragments in the query document
+    pub max_definitions: usize,
+    /// Most aliases of one field in a selection set
+    pub max_aliases_per_field: usize,
+}
+
+impl From<&GraphQLConfig> for QueryLimits {
+    fn from(config: &GraphQLConfig) -> Self {
+        Self {
+            max_request_bytes: config.max_request_bytes,
+            max_tokens: config.max_tokens,
+            max_definitions: config.max_definitions,
+            max_aliases_per_field: config.max_aliases_per_field,
+        }
+    }
+}
+
+impl Default for QueryLimits {
+    fn default() -> Self {
+        Self::from(&GraphQLConfig::default())
+    }
+}
+
+/// A request refused by [`QueryLimits`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitViolation {
+    /// [`QUERY_TOO_LARGE`] or [`TOO_MANY_ALIASES`]
+    pub code: &'static str,
+    /// Human-readable reason
+    pub message: String,
+}
+
+impl LimitViolation {
+    fn too_large(message: impl Into<String>) -> Self {
+        Self {
+            code: QUERY_TOO_LARGE,
+            message: message.into(),
+        }
+    }
+
+    /// Renders the violation as a GraphQL response with a `code` extension
+    ///
+    /// Like parse errors and refused persisted queries, it is sent with
+    /// `200 OK`, so the code reaches the client whatever the error format.
+    fn into_response(self) -> Response {
+        tracing::debug!(code = self.code, reason = %self.message, "Rejected GraphQL request");
+        let mut error = ServerError::new(self.message.clone(), None);
+        error.extensions = coded_error(self.code, self.message).extensions;
+        Json(async_graphql::Response::from_errors(vec![error])).into_response()
+    }
+}
+
+/// Lexical token kinds the scan tells apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Name,
+    Colon,
+    Other,
+}
+
+impl QueryLimits {
+    /// Checks a query document against the token, definition, and alias
+    /// limits without parsing it
+    ///
+    /// The scan stops at the first limit exceeded. Text that is not valid
+    /// GraphQL is left for the parser to reject.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xzepr::api::graphql::limits::{QueryLimits, TOO_MANY_ALIASES};
+    ///
+    /// let limits = QueryLimits {
+    ///     max_aliases_per_field: 2,
+    ///     ..QueryLimits::default()
+    /// };
+    /// assert!(limits.check_document("{ a: id b: id }").is_ok());
+    ///
+    /// let error = limits.check_document("{ a: id b: id c: id }").unwrap_err();
+    /// assert_eq!(error.code, TOO_MANY_ALIASES);
+    /// ```
+    pub fn check_document(&self, query: &str) -> Result<(), LimitViolation> {
+        let bytes = query.as_bytes();
+        let mut pos = 0;
+        let mut tokens = 0;
+        let mut definitions = 0;
+        let mut in_definition_header = false;
+        let mut paren_depth = 0usize;
+        // Alias counts per field of each open selection set
+        let mut selection_sets: Vec<HashMap<&str, usize>> = Vec::new();
+        let mut previous = [Token::Other, Token::Other];
+
+        while pos < bytes.len() {
+            let start = pos;
+            let byte = bytes[pos];
+            let token = match byte {
+                b' ' | b'\t' | b'\n' | b'\r' | b',' => {
+                    pos += 1;
+                    continue;
+                }
+                b'#' => {
+                    while pos < bytes.len() && bytes[pos] != b'\n' && bytes[pos] != b'\r' {
+                        pos += 1;
+                    }
+                    continue;
+                }
+                b'"' => {
+                    pos = skip_string(bytes, pos);
+                    Token::Other
+                }
+                b'.' if bytes[pos..].starts_with(b"...") => {
+                    pos += 3;
+                    Token::Other
+                }
+                b':' => {
+                    pos += 1;
+                    Token::Colon
+                }
+                b'_' | b'a'..=b'z' | b'A'..=b'Z' => {
+                    while pos < bytes.len()
+                        && (bytes[pos] == b'_' || bytes[pos].is_ascii_alphanumeric())
+                    {
+                        pos += 1;
+                    }
+                    Token::Name
+                }
+                b'-' | b'0'..=b'9' => {
+                    pos += 1;
+                    while pos < bytes.len()
+                        && (bytes[pos].is_ascii_alphanumeric()
+                            || matches!(bytes[pos], b'.' | b'+' | b'-'))
+                    {
+                        pos += 1;
+                    }
+                    Token::Other
+                }
+                _ => {
+                    // Punctuators, and stray bytes the parser will reject;
+                    // step over a whole character so the position stays on
+                    // a character boundary
+                    pos += query[pos..].chars().next().map_or(1, char::len_utf8);
+                    Token::Other
+                }
+            };
+
+            tokens += 1;
+            if tokens > self.max_tokens {
+                return Err(LimitViolation::too_large(format!(
+                    "Query has more than {} tokens",
+                    self.max_tokens
+                )));
+            }
+
+            let text = &query[start..pos];
+            match (byte, token) {
+                (b'(', _) => paren_depth += 1,
+                (b')', _) => paren_depth = paren_depth.saturating_sub(1),
+                // Braces inside arguments are object values, not selections
+                (b'{', _) if paren_depth == 0 => {
+                    if selection_sets.is_empty() {
+                        if !in_definition_header {
+                            // A shorthand query without a keyword
+                            definitions += 1;
+                        }
+                        in_definition_header = false;
+                    }
+                    selection_sets.push(HashMap::new());
+                }
+                (b'}', _) if paren_depth == 0 => {
+                    selection_sets.pop();
+                }
+                (_, Token::Name)
+                    if selection_sets.is_empty()
+                        && paren_depth == 0
+                        && !in_definition_header
+                        && DEFINITION_KEYWORDS.contains(&text) =>
+                {
+                    definitions += 1;
+                    in_definition_header = true;
+                }
+                (_, Token::Name) if paren_depth == 0 && previous == [Token::Name, Token::Colon] => {
+                    // `alias: field` in a selection set
+                    if let Some(aliases) = selection_sets.last_mut() {
+                        let count = aliases.entry(text).or_insert(0);
+                        *count += 1;
+                        if *count > self.max_aliases_per_field {
+                            return Err(LimitViolation {
+                                code: TOO_MANY_ALIASES,
+                                message: format!(
+                                    "Field '{}' is aliased more than {} times in one selection set",
+                                    text, self.max_aliases_per_field
+                                ),
+                            });
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if definitions > self.max_definitions {
+                return Err(LimitViolation::too_large(format!(
+                    "Query has more than {} operations and fragments",
+                    self.max_definitions
+                )));
+            }
+            previous = [previous[1], token];
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the position after the string or block string at `start`
+fn skip_string(bytes: &[u8], start: usize) -> usize {
+    if bytes[start..].starts_with(b"\"\"\"") {
+        let mut pos = start + 3;
+        while pos < bytes.len() {
+            if bytes[pos..].starts_with(b"\\\"\"\"") {
+                pos += 4;
+            } else if bytes[pos..].starts_with(b"\"\"\"") {
+                return pos + 3;
+            } else {
+                pos += 1;
+            }
+        }
+        return pos;
+    }
+
+    let mut pos = start + 1;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' => pos += 2,
+            b'"' => return pos + 1,
+            b'\n' | b'\r' => return pos,
+            _ => pos += 1,
+        }
+    }
+    pos.min(bytes.len())
+}
+
+/// The part of a GraphQL request body the limits look at
+#[derive(Deserialize)]
+struct QueryDocument {
+    #[serde(default)]
+    query: Option<String>,
+}
+
+/// Middleware applying [`QueryLimits`] to the `/graphql` endpoint
+///
+/// Reading stops once the body passes `max_request_bytes`, so an oversized
+/// document costs at most that much memory. Batched requests, a JSON array
+/// of operations, are not supported and are rejected up front, as are
+/// queries over the token, definition, or alias limits. Every rejection is
+/// a GraphQL response whose error carries the [`QUERY_TOO_LARGE`] or
+/// [`TOO_MANY_ALIASES`] code.
+pub async fn query_limits_middleware(
+    State(limits): State<QueryLimits>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let too_large = || {
+        LimitViolation::too_large(format!(
+            "Request body exceeds {} bytes",
+            limits.max_request_bytes
+        ))
+        .into_response()
+    };
+
+    let declared_length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if declared_length.is_some_and(|length| length > limits.max_request_bytes) {
+        return too_large();
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = match to_bytes(body, limits.max_request_bytes).await {
+        Ok(bytes) => bytes,
+        Err(_) => return too_large(),
+    };
+
+    if bytes.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'[') {
+        return LimitViolation::too_large("Batched GraphQL requests are not supported")
+            .into_response();
+    }
+
+    // Bodies that are not a request object are left to the handler to reject
+    if let Ok(QueryDocument { query: Some(query) }) = serde_json::from_slice(&bytes) {
+        if let Err(violation) = limits.check_document(&query) {
+            return violation.into_response();
+        }
+    }
+
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Bytes;
+    use axum::{http::StatusCode, middleware, routing::post, Router};
+    use http_body::Frame;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use tower::ServiceExt;
+
+    const CHUNK_SIZE: usize = 16 * 1024;
+
+    /// Streams `remaining` bytes of query text, counting what was read
+    struct CountingBody {
+        remaining: usize,
+        read: Arc<AtomicUsize>,
+    }
+
+    impl http_body::Body for CountingBody {
+        type Data = Bytes;
+        type Error = std::convert::Infallible;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Bytes>, Self::Error>>> {
+            if self.remaining == 0 {
+                return Poll::Ready(None);
+            }
+            let size = self.remaining.min(CHUNK_SIZE);
+            self.remaining -= size;
+            self.read.fetch_add(size, Ordering::SeqCst);
+            Poll::Ready(Some(Ok(Frame::data(Bytes::from(vec![b' '; size])))))
+        }
+    }
+
+    fn limits() -> QueryLimits {
+        QueryLimits {
+            max_request_bytes: 64 * 1024,
+            max_tokens: 200,
+            max_definitions: 3,
+            max_aliases_per_field: 3,
+        }
+    }
+
+    fn app(limits: QueryLimits) -> Router {
+        Router::new()
+            .route(
+                "/graphql",
+                post(|| async { Json(serde_json::json!({ "data": "executed" })) }),
+            )
+            .layer(middleware::from_fn_with_state(
+                limits,
+                query_limits_middleware,
+            ))
+    }
+
+    fn request(body: Body) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri("/graphql")
+            .header("content-type", "application/json")
+            .body(body)
+            .unwrap()
+    }
+
+    fn query(query: &str) -> Body {
+        Body::from(serde_json::json!({ "query": query }).to_string())
+    }
+
+    /// Sends `request`, returning the error code or the data
+    async fn send(limits: QueryLimits, request: Request) -> serde_json::Value {
+        let response = app(limits).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        match json.get("errors") {
+            Some(errors) => errors[0]["extensions"]["code"].clone(),
+            None => json["data"].clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_is_rejected_without_reading_it_all() {
+        let read = Arc::new(AtomicUsize::new(0));
+        let body = Body::new(CountingBody {
+            remaining: 5 * 1024 * 1024,
+            read: read.clone(),
+        });
+
+        assert_eq!(send(limits(), request(body)).await, QUERY_TOO_LARGE);
+        assert!(read.load(Ordering::SeqCst) <= limits().max_request_bytes + CHUNK_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_declared_length_over_the_limit_is_rejected_unread() {
+        let read = Arc::new(AtomicUsize::new(0));
+        let body = Body::new(CountingBody {
+            remaining: 5 * 1024 * 1024,
+            read: read.clone(),
+        });
+        let mut request = request(body);
+        request
+            .headers_mut()
+            .insert(header::CONTENT_LENGTH, (5 * 1024 * 1024).into());
+
+        assert_eq!(send(limits(), request).await, QUERY_TOO_LARGE);
+        assert_eq!(read.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_batched_requests_are_rejected() {
+        let body = Body::from(r#" [{"query":"{ a }"},{"query":"{ b }"}]"#);
+
+        assert_eq!(send(limits(), request(body)).await, QUERY_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_alias_bomb_is_rejected_and_small_queries_pass() {
+        let bomb: String = (0..50)
+            .map(|i| format!("a{}: eventsById(id: \"x\") {{ id }} ", i))
+            .collect();
+        let bomb = query(&format!("{{ {} }}", bomb));
+        assert_eq!(
+            send(QueryLimits::default(), request(bomb)).await,
+            TOO_MANY_ALIASES
+        );
+
+        let aliased =
+            query(r#"{ first: eventsById(id: "a") { id } second: eventsById(id: "b") { id } }"#);
+        assert_eq!(send(limits(), request(aliased)).await, "executed");
+    }
+
+    #[test]
+    fn test_aliases_are_counted_per_field_and_selection_set() {
+        let limits = limits();
+
+        // Three aliases each of two fields, and of one field in two sets
+        assert!(limits
+            .check_document("{ a: x b: x c: x d: y e: y f: y }")
+            .is_ok());
+        assert!(limits
+            .check_document("{ p { a: x b: x c: x } q { a: x b: x c: x } }")
+            .is_ok());
+        assert_eq!(
+            limits
+                .check_document("{ p { a: x b: x c: x d: x } }")
+                .unwrap_err()
+                .code,
+            TOO_MANY_ALIASES
+        );
+    }
+
+    #[test]
+    fn test_arguments_and_variables_are_not_aliases() {
+        let limits = limits();
+        let query = r#"
+            query Q($a: ID!, $b: ID!, $c: ID!, $d: ID!) {
+                x(a: $a, b: $b, c: $c, d: $d, filter: { a: x, b: x, c: x, d: x }) { id }
+            }
+        "#;
+
+        assert!(limits.check_document(query).is_ok());
+    }
+
+    #[test]
+    fn test_token_and_definition_limits() {
+        let limits = limits();
+
+        let tokens = format!("{{ {} }}", "id ".repeat(limits.max_tokens));
+        assert_eq!(
+            limits.check_document(&tokens).unwrap_err().code,
+            QUERY_TOO_LARGE
+        );
+
+        let fragments: String = (0..4)
+            .map(|i| format!("fragment F{} on Event {{ id }} ", i))
+            .collect();
+        assert_eq!(
+            limits.check_document(&fragments).unwrap_err().code,
+            QUERY_TOO_LARGE
+        );
+        assert!(limits
+            .check_document("query A { id } mutation B { id } fragment query on Event { id }")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_strings_and_comments_are_single_tokens() {
+        let limits = QueryLimits {
+            max_tokens: 8,
+            ..limits()
+        };
+
+        // { x ( a : "..." ) } is eight tokens
+        let query = format!(
+            "# {}\n{{ x(a: \"{}\") }}",
+            "ignored ".repeat(100),
+            "a: b ".repeat(100)
+        );
+        assert!(limits.check_document(&query).is_ok());
+        assert!(limits
+            .check_document("{ x(a: \"\"\"block \\\"\"\" text\"\"\") }")
+            .is_ok());
+    }
+}
@@ -0,0 +1,34 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/graphql/loaders.rs
+
+use async_graphql::dataloader::Loader;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::auth::api_key::UserRepository;
+use crate::domain::entities::user::User;
+use crate::domain::value_objects::UserId;
+use crate::error::AuthError;
+
+/// Loads users by ID, batching the lookups made while resolving a query
+pub struct UserLoader {
+    users: Arc<dyn UserRepository>,
+}
+
+impl UserLoader {
+    pub fn new(users: Arc<dyn UserRepository>) -> Self {
+        Self { users }
+    }
+}
+
+impl Loader<UserId> for UserLoader {
+    type Value = User;
+    type Error = Arc<AuthError>;
+
+    async fn load(&self, keys: &[UserId]) -> Result<HashMap<UserId, User>, Self::Error> {
+        let users = self.users.find_by_ids(keys).await.map_err(Arc::new)?;
+        Ok(users.into_iter().map(|user| (*user.id(), user)).collect())
+    }
+}
@@ -3,15 +3,30 @@
 
 // Generated mod file
 
+pub mod errors;
 pub mod guards;
 pub mod handlers;
+pub mod introspection;
+pub mod limits;
+pub mod loaders;
+pub mod persisted_queries;
+pub mod resolver_spans;
 pub mod schema;
 pub mod types;
 
+pub use errors::{app_error, coded_error, domain_error, RequestLocale};
 pub use guards::{
     helpers, require_auth, require_permissions, require_roles, require_roles_and_permissions,
     ComplexityConfig, QueryComplexityAnalyzer, QueryComplexityExtension,
 };
 pub use handlers::{graphql_handler, graphql_health, graphql_playground};
-pub use schema::{create_schema, Mutation, Query, Schema};
+pub use introspection::IntrospectionGuard;
+pub use limits::{query_limits_middleware, QueryLimits, QUERY_TOO_LARGE, TOO_MANY_ALIASES};
+pub use loaders::UserLoader;
+pub use persisted_queries::{PersistedQueries, PersistedQueryStore};
+pub use resolver_spans::ResolverTracing;
+pub use schema::{
+    create_schema, create_schema_with_config, create_schema_with_introspection,
+    create_schema_with_search, create_schema_with_users, Mutation, Query, Schema,
+};
 pub use types::*;
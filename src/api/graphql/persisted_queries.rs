@@ -0,0 +1,641 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/graphql/persisted_queries.rs
+//! Persisted GraphQL queries
+//!
+//! Clients may send the SHA-256 hash of a query in the `persistedQuery`
+//! request extension instead of its text, following the automatic
+//! persisted query protocol used by Apollo clients. An unknown hash is
+//! answered with `PERSISTED_QUERY_NOT_FOUND`, after which the client sends
+//! the text with its hash once. Text is only stored under the hash computed
+//! from it, so a client cannot bind a hash to a different query.
+//!
+//! In persisted-only mode, callers other than administrators may only run
+//! queries an administrator registered.
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+};
+use async_graphql::{Request, ServerError, ServerResult};
+use chrono::Utc;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+use crate::api::graphql::coded_error;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::domain::repositories::persisted_query_repo::{
+    PersistedQueryRepository, StoredPersistedQuery,
+};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+/// Default number of client queries kept in memory
+pub const DEFAULT_PERSISTED_QUERY_CACHE_SIZE: usize = 1_000;
+
+/// Error code for a hash the server does not know; clients resend the text
+pub const PERSISTED_QUERY_NOT_FOUND: &str = "PERSISTED_QUERY_NOT_FOUND";
+
+/// Error code for query text that does not hash to the hash sent with it
+pub const PERSISTED_QUERY_HASH_MISMATCH: &str = "PERSISTED_QUERY_HASH_MISMATCH";
+
+/// Error code for a query refused in persisted-only mode
+pub const PERSISTED_QUERY_NOT_ALLOWED: &str = "PERSISTED_QUERY_NOT_ALLOWED";
+
+/// Role that may run any query in persisted-only mode
+const PERSISTED_ONLY_EXEMPT_ROLE: &str = "admin";
+
+/// Version of the `persistedQuery` extension this server speaks
+const PERSISTED_QUERY_VERSION: u32 = 1;
+
+/// Returns the lowercase hex SHA-256 of `query`, the key it is stored under
+pub fn query_hash(query: &str) -> String {
+    hex::encode(Sha256::digest(query.as_bytes()))
+}
+
+/// A query found in the store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersistedLookup {
+    pub query: String,
+    /// Registered by an administrator rather than cached from a client
+    pub registered: bool,
+}
+
+/// Queries stored by hash: registered ones in full, client ones in a
+/// bounded cache, and both in an optional repository
+///
+/// The client cache is cleared when it is full. With a repository, lookups
+/// that miss in memory fall through to it, so queries cached or registered
+/// on another instance are found too. Queries unregistered on another
+/// instance stay runnable here until restart.
+#[derive(Clone)]
+pub struct PersistedQueryStore {
+    registered: Arc<RwLock<HashMap<String, StoredPersistedQuery>>>,
+    cached: Arc<RwLock<HashMap<String, String>>>,
+    capacity: usize,
+    repository: Option<Arc<dyn PersistedQueryRepository>>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+}
+
+impl Default for PersistedQueryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PersistedQueryStore {
+    /// Creates an in-memory store with the default cache size
+    pub fn new() -> Self {
+        Self {
+            registered: Arc::new(RwLock::new(HashMap::new())),
+            cached: Arc::new(RwLock::new(HashMap::new())),
+            capacity: DEFAULT_PERSISTED_QUERY_CACHE_SIZE,
+            repository: None,
+            metrics: None,
+        }
+    }
+
+    /// Sets how many client queries are kept in memory
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Stores queries in `repository` as well; call [`Self::load`] to read
+    /// the registered ones
+    pub fn with_repository(mut self, repository: Arc<dyn PersistedQueryRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Records hits, misses, unknown hashes, and refusals in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Loads the registered queries from the repository, returning how many
+    /// were loaded
+    pub async fn load(&self) -> Result<usize> {
+        let Some(repository) = &self.repository else {
+            return Ok(0);
+        };
+
+        let queries = repository.list_registered().await?;
+        let count = queries.len();
+        let mut registered = self.registered.write().unwrap();
+        for query in queries {
+            registered.insert(query.hash.clone(), query);
+        }
+        Ok(count)
+    }
+
+    /// Finds the query stored under `hash`
+    pub async fn lookup(&self, hash: &str) -> Result<Option<PersistedLookup>> {
+        if let Some(query) = self.registered.read().unwrap().get(hash) {
+            return Ok(Some(PersistedLookup {
+                query: query.query.clone(),
+                registered: true,
+            }));
+        }
+        if let Some(query) = self.cached.read().unwrap().get(hash) {
+            return Ok(Some(PersistedLookup {
+                query: query.clone(),
+                registered: false,
+            }));
+        }
+
+        let Some(repository) = &self.repository else {
+            return Ok(None);
+        };
+        let Some(stored) = repository.find_query(hash).await? else {
+            return Ok(None);
+        };
+        let lookup = PersistedLookup {
+            query: stored.query.clone(),
+            registered: stored.registered,
+        };
+        if stored.registered {
+            self.registered
+                .write()
+                .unwrap()
+                .insert(stored.hash.clone(), stored);
+        } else {
+            self.cache(stored.hash, stored.query);
+        }
+        Ok(Some(lookup))
+    }
+
+    /// Stores query text a client sent with its hash
+    ///
+    /// The caller checks that `query` hashes to `hash`. A failure to write
+    /// to the repository is logged; the query stays cached in memory.
+    pub async fn remember(&self, hash: String, query: String) {
+        if self.registered.read().unwrap().contains_key(&hash)
+            || self.cached.read().unwrap().contains_key(&hash)
+        {
+            return;
+        }
+
+        if let Some(repository) = &self.repository {
+            let stored = StoredPersistedQuery {
+                hash: hash.clone(),
+                query: query.clone(),
+                registered: false,
+                registered_by: None,
+                created_at: Utc::now(),
+            };
+            if let Err(e) = repository.save_query(&stored).await {
+                warn!(hash = %hash, error = %e, "Failed to store persisted query");
+            }
+        }
+        self.cache(hash, query);
+    }
+
+    /// Registers `query` so it runs in persisted-only mode
+    ///
+    /// The text must parse as a GraphQL document. Registering a query twice
+    /// keeps the first registration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error on `query` if the text does not parse.
+    pub async fn register(
+        &self,
+        query: String,
+        registered_by: Option<String>,
+    ) -> Result<StoredPersistedQuery> {
+        if let Err(e) = async_graphql::parser::parse_query(&query) {
+            return Err(DomainError::ValidationError {
+                field: "query".to_string(),
+                message: Message::new("validation.graphql_query_invalid")
+                    .with("reason", e.to_string()),
+            }
+            .into());
+        }
+
+        let hash = query_hash(&query);
+        if let Some(existing) = self.registered.read().unwrap().get(&hash) {
+            return Ok(existing.clone());
+        }
+
+        let stored = StoredPersistedQuery {
+            hash: hash.clone(),
+            query,
+            registered: true,
+            registered_by,
+            created_at: Utc::now(),
+        };
+        if let Some(repository) = &self.repository {
+            repository.save_query(&stored).await?;
+        }
+        self.cached.write().unwrap().remove(&hash);
+        self.registered
+            .write()
+            .unwrap()
+            .insert(hash, stored.clone());
+        Ok(stored)
+    }
+
+    /// Lists registered queries, oldest first
+    pub async fn list_registered(&self) -> Result<Vec<StoredPersistedQuery>> {
+        if let Some(repository) = &self.repository {
+            return repository.list_registered().await;
+        }
+
+        let mut queries: Vec<_> = self.registered.read().unwrap().values().cloned().collect();
+        queries.sort_by(|a, b| (a.created_at, &a.hash).cmp(&(b.created_at, &b.hash)));
+        Ok(queries)
+    }
+
+    /// Removes the query stored under `hash`, returning whether it existed
+    pub async fn unregister(&self, hash: &str) -> Result<bool> {
+        let mut removed = self.registered.write().unwrap().remove(hash).is_some();
+        removed |= self.cached.write().unwrap().remove(hash).is_some();
+        if let Some(repository) = &self.repository {
+            removed |= repository.delete_query(hash).await?;
+        }
+        Ok(removed)
+    }
+
+    fn cache(&self, hash: String, query: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut cached = self.cached.write().unwrap();
+        if cached.len() >= self.capacity {
+            cached.clear();
+        }
+        cached.insert(hash, query);
+    }
+
+    fn record(&self, result: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_persisted_query(result);
+        }
+    }
+}
+
+/// The `persistedQuery` request extension
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQueryRequest {
+    version: u32,
+    sha256_hash: String,
+}
+
+/// Schema extension resolving persisted queries before a request is parsed
+pub struct PersistedQueries {
+    store: PersistedQueryStore,
+    persisted_only: bool,
+}
+
+impl PersistedQueries {
+    /// Creates the extension; `persisted_only` refuses unregistered queries
+    /// from callers other than administrators
+    pub fn new(store: PersistedQueryStore, persisted_only: bool) -> Self {
+        Self {
+            store,
+            persisted_only,
+        }
+    }
+}
+
+impl ExtensionFactory for PersistedQueries {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PersistedQueriesExtension {
+            store: self.store.clone(),
+            persisted_only: self.persisted_only,
+        })
+    }
+}
+
+struct PersistedQueriesExtension {
+    store: PersistedQueryStore,
+    persisted_only: bool,
+}
+
+impl PersistedQueriesExtension {
+    fn refuse(&self, code: &str, message: &str) -> ServerError {
+        self.store.record(match code {
+            PERSISTED_QUERY_NOT_FOUND => "not_found",
+            _ => "rejected",
+        });
+        let mut error = ServerError::new(message, None);
+        error.extensions = coded_error(code, message).extensions;
+        error
+    }
+
+    fn not_allowed(&self) -> ServerError {
+        self.refuse(
+            PERSISTED_QUERY_NOT_ALLOWED,
+            "Only registered persisted queries may run on this server",
+        )
+    }
+
+    async fn lookup(&self, hash: &str) -> ServerResult<Option<PersistedLookup>> {
+        self.store.lookup(hash).await.map_err(|e| {
+            warn!(hash = %hash, error = %e, "Persisted query lookup failed");
+            ServerError::new("Failed to load persisted query", None)
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for PersistedQueriesExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        mut request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        // The request data is not yet visible through the context here
+        let exempt = request
+            .data
+            .get(&TypeId::of::<AuthenticatedUser>())
+            .and_then(|user| user.downcast_ref::<AuthenticatedUser>())
+            .is_some_and(|user| user.has_role(PERSISTED_ONLY_EXEMPT_ROLE));
+        let restricted = self.persisted_only && !exempt;
+
+        let Some(value) = request.extensions.remove("persistedQuery") else {
+            if restricted {
+                return Err(self.not_allowed());
+            }
+            return next.run(ctx, request).await;
+        };
+        let persisted: PersistedQueryRequest = async_graphql::from_value(value)
+            .map_err(|_| ServerError::new("Invalid \"persistedQuery\" extension", None))?;
+        if persisted.version != PERSISTED_QUERY_VERSION {
+            return Err(ServerError::new(
+                format!(
+                    "Unsupported \"persistedQuery\" version {}; only version {} is supported",
+                    persisted.version, PERSISTED_QUERY_VERSION
+                ),
+                None,
+            ));
+        }
+        let hash = persisted.sha256_hash.to_ascii_lowercase();
+
+        if request.query.is_empty() {
+            let Some(found) = self.lookup(&hash).await? else {
+                return Err(self.refuse(PERSISTED_QUERY_NOT_FOUND, "PersistedQueryNotFound"));
+            };
+            if restricted && !found.registered {
+                return Err(self.not_allowed());
+            }
+            self.store.record("hit");
+            request.query = found.query;
+        } else if query_hash(&request.query) != hash {
+            return Err(self.refuse(
+                PERSISTED_QUERY_HASH_MISMATCH,
+                "provided sha does not match query",
+            ));
+        } else if restricted {
+            match self.lookup(&hash).await? {
+                Some(found) if found.registered => self.store.record("hit"),
+                _ => return Err(self.not_allowed()),
+            }
+        } else {
+            self.store.record("miss");
+            self.store.remember(hash, request.query.clone()).await;
+        }
+
+        next.run(ctx, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema, Value};
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn status(&self) -> String {
+            "ok".to_string()
+        }
+    }
+
+    const QUERY: &str = "{ status }";
+
+    fn user(role: &str) -> AuthenticatedUser {
+        use crate::auth::jwt::claims::Claims;
+
+        AuthenticatedUser::new(Claims::new_access_token(
+            "user-1".to_string(),
+            vec![role.to_string()],
+            vec![],
+            "xzepr-dev".to_string(),
+            "xzepr-api-dev".to_string(),
+            chrono::Duration::minutes(15),
+        ))
+    }
+
+    fn schema(
+        store: &PersistedQueryStore,
+        persisted_only: bool,
+    ) -> Schema<QueryRoot, EmptyMutation, EmptySubscription> {
+        Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .extension(PersistedQueries::new(store.clone(), persisted_only))
+            .finish()
+    }
+
+    fn request(query: &str, hash: Option<&str>, role: &str) -> Request {
+        let mut request = Request::new(query).data(user(role));
+        if let Some(hash) = hash {
+            request.extensions.insert(
+                "persistedQuery".to_string(),
+                Value::from_json(serde_json::json!({"version": 1, "sha256Hash": hash})).unwrap(),
+            );
+        }
+        request
+    }
+
+    /// Runs `request`, returning the `status` field or the error code
+    async fn run(
+        schema: &Schema<QueryRoot, EmptyMutation, EmptySubscription>,
+        request: Request,
+    ) -> std::result::Result<String, String> {
+        let response = schema.execute(request).await;
+        match response.errors.first() {
+            Some(error) => Err(error
+                .extensions
+                .as_ref()
+                .and_then(|extensions| extensions.get("code"))
+                .map_or_else(|| error.message.clone(), |code| code.to_string())
+                .trim_matches('"')
+                .to_string()),
+            None => Ok(response.data.into_json().unwrap()["status"]
+                .as_str()
+                .unwrap()
+                .to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_automatic_persisted_query_handshake() {
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let store = PersistedQueryStore::new().with_metrics(metrics.clone());
+        let schema = schema(&store, false);
+        let hash = query_hash(QUERY);
+
+        // The hash alone is unknown until the client sends the text once
+        assert_eq!(
+            run(&schema, request("", Some(&hash), "user")).await,
+            Err(PERSISTED_QUERY_NOT_FOUND.to_string())
+        );
+        assert_eq!(
+            run(&schema, request(QUERY, Some(&hash), "user")).await,
+            Ok("ok".to_string())
+        );
+        assert_eq!(
+            run(&schema, request("", Some(&hash), "user")).await,
+            Ok("ok".to_string())
+        );
+        assert_eq!(
+            run(&schema, request("", Some(&hash.to_uppercase()), "user")).await,
+            Ok("ok".to_string())
+        );
+
+        // Ordinary requests are unaffected
+        assert_eq!(
+            run(&schema, request(QUERY, None, "user")).await,
+            Ok("ok".to_string())
+        );
+
+        let output = metrics.gather().unwrap();
+        for (result, count) in [("not_found", 1), ("miss", 1), ("hit", 2)] {
+            assert!(
+                output.contains(&format!(
+                    "xzepr_graphql_persisted_queries_total{{result=\"{}\"}} {}",
+                    result, count
+                )),
+                "{}",
+                output
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hash_mismatch_is_not_stored() {
+        let store = PersistedQueryStore::new();
+        let schema = schema(&store, false);
+        let hash = query_hash(QUERY);
+
+        // Binding another query to a known hash would poison the cache
+        assert_eq!(
+            run(&schema, request("{ __typename }", Some(&hash), "user")).await,
+            Err(PERSISTED_QUERY_HASH_MISMATCH.to_string())
+        );
+        assert_eq!(store.lookup(&hash).await.unwrap(), None);
+        assert_eq!(
+            store.lookup(&query_hash("{ __typename }")).await.unwrap(),
+            None
+        );
+
+        // The right text is still accepted afterwards
+        assert_eq!(
+            run(&schema, request(QUERY, Some(&hash), "user")).await,
+            Ok("ok".to_string())
+        );
+        assert_eq!(
+            store.lookup(&hash).await.unwrap(),
+            Some(PersistedLookup {
+                query: QUERY.to_string(),
+                registered: false
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_persisted_only_runs_registered_queries() {
+        let store = PersistedQueryStore::new();
+        let schema = schema(&store, true);
+        let hash = query_hash(QUERY);
+        let not_allowed = Err(PERSISTED_QUERY_NOT_ALLOWED.to_string());
+
+        // Ad-hoc text and client-persisted queries are refused
+        assert_eq!(
+            run(&schema, request(QUERY, None, "user")).await,
+            not_allowed
+        );
+        assert_eq!(
+            run(&schema, request(QUERY, Some(&hash), "user")).await,
+            not_allowed
+        );
+        store.remember(hash.clone(), QUERY.to_string()).await;
+        assert_eq!(
+            run(&schema, request("", Some(&hash), "user")).await,
+            not_allowed
+        );
+
+        // Administrators may run anything
+        assert_eq!(
+            run(&schema, request("{ __typename status }", None, "admin")).await,
+            Ok("ok".to_string())
+        );
+
+        store
+            .register(QUERY.to_string(), Some("admin-1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            run(&schema, request("", Some(&hash), "user")).await,
+            Ok("ok".to_string())
+        );
+        assert_eq!(
+            run(&schema, request(QUERY, Some(&hash), "user")).await,
+            Ok("ok".to_string())
+        );
+        assert_eq!(
+            run(&schema, request(QUERY, None, "user")).await,
+            not_allowed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_register_validates_and_survives_cache_clearing() {
+        let store = PersistedQueryStore::new().with_capacity(1);
+
+        let error = store
+            .register("{ status".to_string(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::Error::Domain(DomainError::ValidationError { ref field, .. })
+                if field == "query"
+        ));
+
+        let registered = store.register(QUERY.to_string(), None).await.unwrap();
+        assert_eq!(registered.hash, query_hash(QUERY));
+        for query in ["{ a }", "{ b }", "{ c }"] {
+            store.remember(query_hash(query), query.to_string()).await;
+        }
+        assert!(store.lookup(&query_hash("{ a }")).await.unwrap().is_none());
+        assert!(
+            store
+                .lookup(&registered.hash)
+                .await
+                .unwrap()
+                .unwrap()
+                .registered
+        );
+        assert_eq!(
+            store.list_registered().await.unwrap(),
+            vec![registered.clone()]
+        );
+
+        assert!(store.unregister(&registered.hash).await.unwrap());
+        assert!(!store.unregister(&registered.hash).await.unwrap());
+        assert!(store.list_registered().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/graphql/resolver_spans.rs
+
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextResolve, ResolveInfo,
+};
+use async_graphql::{Response, ServerResult, Value};
+use std::sync::Arc;
+use tracing::{info_span, Instrument};
+
+use crate::infrastructure::config::ResolverSpanLevel;
+
+/// Schema extension tracing the operation and, depending on `level`, its
+/// resolvers
+///
+/// Spans nest under the span of the HTTP request, so a slow query shows
+/// which field spent the time. Introspection fields are never traced.
+pub struct ResolverTracing {
+    level: ResolverSpanLevel,
+}
+
+impl ResolverTracing {
+    /// Creates an extension tracing resolvers at `level`
+    pub fn new(level: ResolverSpanLevel) -> Self {
+        Self { level }
+    }
+}
+
+impl ExtensionFactory for ResolverTracing {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ResolverTracingExtension { level: self.level })
+    }
+}
+
+struct ResolverTracingExtension {
+    level: ResolverSpanLevel,
+}
+
+impl ResolverTracingExtension {
+    fn traces(&self, info: &ResolveInfo<'_>) -> bool {
+        // The root `__schema` and `__type` fields are not flagged as
+        // introspection themselves, only the fields below them
+        if info.is_for_introspection || info.name.starts_with("__") {
+            return false;
+        }
+        match self.level {
+            ResolverSpanLevel::Off => false,
+            ResolverSpanLevel::Root => info.path_node.parent.is_none(),
+            ResolverSpanLevel::All => true,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for ResolverTracingExtension {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let span = info_span!(
+            "graphql.execute",
+            otel.name = %operation_name.unwrap_or("graphql.execute"),
+            graphql.operation.name = operation_name.unwrap_or_default(),
+        );
+        next.run(ctx, operation_name).instrument(span).await
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if !self.traces(&info) {
+            return next.run(ctx, info).await;
+        }
+
+        let span = info_span!(
+            "graphql.resolve",
+            otel.name = %format!("{}.{}", info.parent_type, info.name),
+            graphql.parent_type = info.parent_type,
+            graphql.field.name = info.name,
+            graphql.field.path = %info.path_node,
+        );
+        next.run(ctx, info).instrument(span).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema, SimpleObject};
+    use std::sync::Mutex;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    #[derive(SimpleObject)]
+    struct Nested {
+        value: i32,
+    }
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        async fn status(&self) -> String {
+            "ok".to_string()
+        }
+
+        async fn nested(&self) -> Nested {
+            Nested { value: 1 }
+        }
+    }
+
+    /// Records the names of the spans that were opened
+    #[derive(Clone, Default)]
+    struct SpanNames(Arc<Mutex<Vec<String>>>);
+
+    impl<S: tracing::Subscriber> Layer<S> for SpanNames {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: Context<'_, S>,
+        ) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(attrs.metadata().name().to_string());
+        }
+    }
+
+    async fn resolver_spans(level: ResolverSpanLevel) -> usize {
+        let names = SpanNames::default();
+        let _guard =
+            tracing::subscriber::set_default(tracing_subscriber::registry().with(names.clone()));
+
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .extension(ResolverTracing::new(level))
+            .finish();
+        let response = schema
+            .execute("{ status nested { value } __schema { queryType { name } } }")
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+        let names = names.0.lock().unwrap();
+        assert_eq!(names.iter().filter(|n| *n == "graphql.execute").count(), 1);
+        names.iter().filter(|n| *n == "graphql.resolve").count()
+    }
+
+    #[tokio::test]
+    async fn test_resolver_spans_follow_level() {
+        assert_eq!(resolver_spans(ResolverSpanLevel::Off).await, 0);
+        assert_eq!(resolver_spans(ResolverSpanLevel::Root).await, 2);
+        assert_eq!(resolver_spans(ResolverSpanLevel::All).await, 3);
+    }
+}
@@ -3,37 +3,260 @@
 
 // src/api/graphql/schema.rs
 
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
+use axum::http::StatusCode;
 use std::sync::Arc;
 
+use crate::api::field_access::FieldAccess;
+use crate::api::graphql::errors::{app_error, coded_error, domain_error};
+use crate::api::graphql::introspection::IntrospectionGuard;
+use crate::api::graphql::loaders::UserLoader;
+use crate::api::graphql::persisted_queries::{PersistedQueries, PersistedQueryStore};
+use crate::api::graphql::resolver_spans::ResolverTracing;
 use crate::api::graphql::types::*;
 use crate::api::middleware::jwt::AuthenticatedUser;
-use crate::application::handlers::{EventReceiverGroupHandler, EventReceiverHandler};
-use crate::domain::repositories::event_receiver_group_repo::FindEventReceiverGroupCriteria;
-use crate::domain::repositories::event_receiver_repo::FindEventReceiverCriteria;
-use crate::domain::value_objects::UserId;
+use crate::api::rest::search::search_viewer;
+use crate::application::authorization::{AuthorizationService, ProtectedResource, ResourceAction};
+use crate::application::handlers::event_receiver_group_handler::UpdateEventReceiverGroupParams;
+use crate::application::handlers::{
+    EventHandler, EventReceiverGroupHandler, EventReceiverHandler, SearchHandler, TimelineQuery,
+};
+use crate::auth::api_key::UserRepository;
+use crate::domain::entities::event::EventOrigin;
+use crate::domain::entities::receiver_deletion::ReceiverDeletionOutcome;
+use crate::domain::entities::search::SearchResourceType;
+use crate::domain::entities::user::User;
+use crate::domain::repositories::event_receiver_group_repo::MemberCursor;
+use crate::domain::repositories::event_repo::FindEventCriteria;
+use crate::domain::repositories::pagination::PaginationParams;
+use crate::domain::repositories::receiver_timeline_repo::TimelineCursor;
+use crate::domain::value_objects::{EventId, EventReceiverGroupId, EventReceiverId, UserId};
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::config::{GraphQLConfig, IntrospectionMode};
+
+/// Returns the sensitive fields the requesting user may read
+fn field_access(ctx: &Context<'_>) -> FieldAccess {
+    FieldAccess::for_user(ctx.data_opt::<AuthenticatedUser>())
+}
+
+/// Returns the ID of the requesting user
+fn caller_id(ctx: &Context<'_>) -> Result<UserId> {
+    ctx.data::<AuthenticatedUser>()?
+        .user_id()
+        .parse::<UserId>()
+        .map_err(|e| Error::new(format!("Invalid user ID in token: {}", e)))
+}
+
+/// Loads a user through the request's batching loader
+///
+/// Returns `None` when the schema was built without a user repository.
+async fn load_user(ctx: &Context<'_>, user_id: UserId) -> Result<Option<User>> {
+    let Some(loader) = ctx.data_opt::<DataLoader<UserLoader>>() else {
+        return Ok(None);
+    };
+
+    loader
+        .load_one(user_id)
+        .await
+        .map_err(|e| Error::new(format!("Failed to load user: {}", e)))
+}
+
+/// Records a change to a group, or an attempt the caller was denied
+fn audit_group_change(
+    ctx: &Context<'_>,
+    action: AuditAction,
+    outcome: AuditOutcome,
+    actor: UserId,
+    resource: String,
+) {
+    if let Some(audit_logger) = ctx.data_opt::<Arc<AuditLogger>>() {
+        audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(actor.to_string())
+                .action(action)
+                .resource(resource)
+                .outcome(outcome)
+                .build(),
+        );
+    }
+}
+
+/// Records a membership change, or an attempt the caller was denied
+fn audit_membership(
+    ctx: &Context<'_>,
+    action: AuditAction,
+    outcome: AuditOutcome,
+    actor: UserId,
+    group_id: EventReceiverGroupId,
+    user_id: UserId,
+) {
+    audit_group_change(
+        ctx,
+        action,
+        outcome,
+        actor,
+        format!("group:{}/member:{}", group_id, user_id),
+    );
+}
+
+/// Requires the caller to be allowed `action` on `resource`
+///
+/// Applies the same owner, member, and admin rules as the REST API.
+/// Denied callers get a `forbidden` error and unknown resources a
+/// `not_found` error.
+async fn authorize(
+    ctx: &Context<'_>,
+    action: ResourceAction,
+    resource: ProtectedResource,
+) -> Result<()> {
+    let user = ctx.data::<AuthenticatedUser>()?;
+    ctx.data::<AuthorizationService>()?
+        .authorize(&user.claims, action, resource)
+        .await
+        .map_err(|e| match e.status_code() {
+            StatusCode::FORBIDDEN => coded_error("forbidden", e.to_string()),
+            StatusCode::NOT_FOUND => coded_error("not_found", e.to_string()),
+            _ => app_error(ctx, "Failed to check permissions", &e),
+        })
+}
+
+/// Requires the caller to be allowed to change the members of a group
+///
+/// Denied attempts are also recorded as membership changes.
+async fn authorize_membership_change(
+    ctx: &Context<'_>,
+    action: ResourceAction,
+    audit_action: AuditAction,
+    caller: UserId,
+    group_id: EventReceiverGroupId,
+    user_id: UserId,
+) -> Result<()> {
+    let result = authorize(ctx, action, ProtectedResource::Group(group_id)).await;
+    if result.is_err() {
+        audit_membership(
+            ctx,
+            audit_action,
+            AuditOutcome::Denied,
+            caller,
+            group_id,
+            user_id,
+        );
+    }
+    result
+}
+
+/// Requires the caller to be allowed to update a group
+///
+/// Denied attempts are recorded against `resource`, such as the group or
+/// one of its receivers.
+async fn authorize_group_change(
+    ctx: &Context<'_>,
+    audit_action: AuditAction,
+    caller: UserId,
+    group_id: EventReceiverGroupId,
+    resource: &str,
+) -> Result<()> {
+    let result = authorize(
+        ctx,
+        ResourceAction::Update,
+        ProtectedResource::Group(group_id),
+    )
+    .await;
+    if result.is_err() {
+        audit_group_change(
+            ctx,
+            audit_action,
+            AuditOutcome::Denied,
+            caller,
+            resource.to_string(),
+        );
+    }
+    result
+}
+
+/// Maps errors from changing the receivers of a group
+///
+/// Unknown receivers get a `not_found` code and receivers already in the
+/// destination group a `conflict` code.
+fn group_receiver_error(ctx: &Context<'_>, context: &str, e: &crate::error::Error) -> Error {
+    match e.status_code() {
+        StatusCode::NOT_FOUND => coded_error("not_found", e.to_string()),
+        StatusCode::CONFLICT => coded_error("conflict", "Receiver is already in this group"),
+        _ => app_error(ctx, context, e),
+    }
+}
+
+/// Enables or disables a group and returns it, auditing the change
+async fn set_group_enabled(
+    ctx: &Context<'_>,
+    id: EventReceiverGroupId,
+    enabled: bool,
+) -> Result<EventReceiverGroupType> {
+    let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+    let caller = caller_id(ctx)?;
+    let action = AuditAction::ResourceUpdate;
+    let resource = format!("group:{}", id);
+    authorize_group_change(ctx, action.clone(), caller, id, &resource).await?;
+
+    let (result, context) = if enabled {
+        (
+            handler.enable_event_receiver_group(id).await,
+            "Failed to enable event receiver group",
+        )
+    } else {
+        (
+            handler.disable_event_receiver_group(id).await,
+            "Failed to disable event receiver group",
+        )
+    };
+    result.map_err(|e| app_error(ctx, context, &e))?;
+    audit_group_change(ctx, action, AuditOutcome::Success, caller, resource);
+
+    let group = handler
+        .get_event_receiver_group_or_error(id)
+        .await
+        .map_err(|e| app_error(ctx, context, &e))?;
+    Ok(group.into())
+}
 
 pub struct Query;
 
 #[Object]
 impl Query {
     /// Get events by ID
-    async fn events_by_id(&self, _ctx: &Context<'_>, _id: ID) -> Result<Vec<EventType>> {
-        // TODO: Implement event queries once Event entity is complete
-        Ok(vec![])
+    async fn events_by_id(&self, ctx: &Context<'_>, id: EventId) -> Result<Vec<EventType>> {
+        let handler = ctx.data::<Arc<EventHandler>>()?;
+
+        match handler.get_event(id).await {
+            Ok(Some(mut event)) => {
+                let access = field_access(ctx);
+                if access.can_read_payload(event.owner_id()) {
+                    handler
+                        .load_full_payload(&mut event)
+                        .await
+                        .map_err(|e| Error::new(format!("Failed to get event: {}", e)))?;
+                }
+                Ok(vec![EventType::for_caller(event, &access)])
+            }
+            Ok(None) => Ok(vec![]),
+            Err(e) => Err(Error::new(format!("Failed to get event: {}", e))),
+        }
     }
 
     /// Get event receivers by ID
     async fn event_receivers_by_id(
         &self,
         ctx: &Context<'_>,
-        id: ID,
+        id: EventReceiverId,
     ) -> Result<Vec<EventReceiverType>> {
         let handler = ctx.data::<Arc<EventReceiverHandler>>()?;
-        let receiver_id = parse_event_receiver_id(&id)?;
 
-        match handler.get_event_receiver(receiver_id).await {
-            Ok(Some(receiver)) => Ok(vec![receiver.into()]),
+        match handler.get_event_receiver(id).await {
+            Ok(Some(receiver)) => Ok(vec![EventReceiverType::for_caller(
+                receiver,
+                &field_access(ctx),
+            )]),
             Ok(None) => Ok(vec![]),
             Err(e) => Err(Error::new(format!("Failed to get event receiver: {}", e))),
         }
@@ -43,12 +266,11 @@ impl Query {
     async fn event_receiver_groups_by_id(
         &self,
         ctx: &Context<'_>,
-        id: ID,
+        id: EventReceiverGroupId,
     ) -> Result<Vec<EventReceiverGroupType>> {
         let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
-        let group_id = parse_event_receiver_group_id(&id)?;
 
-        match handler.get_event_receiver_group(group_id).await {
+        match handler.get_event_receiver_group(id).await {
             Ok(Some(group)) => Ok(vec![group.into()]),
             Ok(None) => Ok(vec![]),
             Err(e) => Err(Error::new(format!(
@@ -58,90 +280,191 @@ impl Query {
         }
     }
 
-    /// Find events with criteria
-    async fn events(&self, _ctx: &Context<'_>, _event: FindEventInput) -> Result<Vec<EventType>> {
-        // TODO: Implement event queries once Event entity is complete
-        Ok(vec![])
-    }
-
-    /// Find event receivers with criteria
-    async fn event_receivers(
+    /// Get an event receiver group by ID
+    async fn group(
         &self,
         ctx: &Context<'_>,
-        event_receiver: FindEventReceiverInput,
-    ) -> Result<Vec<EventReceiverType>> {
-        let handler = ctx.data::<Arc<EventReceiverHandler>>()?;
+        id: EventReceiverGroupId,
+    ) -> Result<Option<EventReceiverGroupType>> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
 
-        let mut criteria = FindEventReceiverCriteria::new();
+        handler
+            .get_event_receiver_group(id)
+            .await
+            .map(|group| group.map(Into::into))
+            .map_err(|e| Error::new(format!("Failed to get event receiver group: {}", e)))
+    }
+
+    /// The requesting user
+    async fn me(&self, ctx: &Context<'_>) -> Result<MeType> {
+        Ok(MeType {
+            user_id: caller_id(ctx)?,
+        })
+    }
+
+    /// Find events with criteria
+    async fn events(&self, ctx: &Context<'_>, event: FindEventInput) -> Result<Vec<EventType>> {
+        let handler = ctx.data::<Arc<EventHandler>>()?;
 
-        if let Some(id) = event_receiver.id {
-            let receiver_id = parse_event_receiver_id(&id)?;
-            criteria = criteria.with_id(receiver_id);
+        // System events are only listed through the admin REST API
+        let mut criteria = FindEventCriteria::new().with_origin(EventOrigin::User);
+
+        if let Some(event_id) = event.id {
+            criteria = criteria.with_id(event_id);
         }
 
-        if let Some(name) = event_receiver.name {
+        if let Some(name) = event.name {
             criteria = criteria.with_name(name);
         }
 
-        if let Some(receiver_type) = event_receiver.receiver_type {
-            criteria = criteria.with_type(receiver_type);
+        if let Some(version) = event.version {
+            criteria = criteria.with_version(version);
         }
 
-        if let Some(version) = event_receiver.version {
-            criteria = criteria.with_version(version);
+        if let Some(release) = event.release {
+            criteria = criteria.with_release(release);
+        }
+
+        if let Some(platform_id) = event.platform_id {
+            criteria = criteria.with_platform_id(platform_id);
+        }
+
+        if let Some(package) = event.package {
+            criteria = criteria.with_package(package);
         }
 
-        // Set default pagination if no specific criteria
-        if criteria.is_empty() {
-            criteria = criteria.with_limit(50).with_offset(0);
+        if let Some(success) = event.success {
+            criteria = criteria.with_success(success);
+        }
+
+        if let Some(receiver_id) = event.event_receiver_id {
+            criteria = criteria.with_event_receiver_id(receiver_id);
         }
 
         match handler.find_by_criteria(criteria).await {
-            Ok(receivers) => Ok(receivers.into_iter().map(|r| r.into()).collect()),
-            Err(e) => Err(Error::new(format!("Failed to find event receivers: {}", e))),
+            Ok(mut events) => {
+                let access = field_access(ctx);
+                for event in &mut events {
+                    if access.can_read_payload(event.owner_id()) {
+                        handler
+                            .load_full_payload(event)
+                            .await
+                            .map_err(|e| Error::new(format!("Failed to find events: {}", e)))?;
+                    }
+                }
+                Ok(events
+                    .into_iter()
+                    .map(|e| EventType::for_caller(e, &access))
+                    .collect())
+            }
+            Err(e) => Err(Error::new(format!("Failed to find events: {}", e))),
         }
     }
 
-    /// Find event receiver groups with criteria
-    async fn event_receiver_groups(
+    /// Find event receivers with criteria, one page at a time
+    async fn event_receivers(
         &self,
         ctx: &Context<'_>,
-        event_receiver_group: FindEventReceiverGroupInput,
-    ) -> Result<Vec<EventReceiverGroupType>> {
-        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
-
-        let mut criteria = FindEventReceiverGroupCriteria::new();
-
-        if let Some(id) = event_receiver_group.id {
-            let group_id = parse_event_receiver_group_id(&id)?;
-            criteria = criteria.with_id(group_id);
-        }
+        #[graphql(default)] event_receiver: FindEventReceiverInput,
+    ) -> Result<EventReceiverConnection> {
+        let handler = ctx.data::<Arc<EventReceiverHandler>>()?;
+        let (criteria, page) = event_receiver
+            .into_criteria()
+            .map_err(|e| domain_error(ctx, &e))?;
+        let limit = page.effective_limit(None);
 
-        if let Some(name) = event_receiver_group.name {
-            criteria = criteria.with_name(name);
-        }
+        let total_count = handler
+            .count_by_criteria(criteria.clone())
+            .await
+            .map_err(|e| Error::new(format!("Failed to count event receivers: {}", e)))?;
 
-        if let Some(group_type) = event_receiver_group.group_type {
-            criteria = criteria.with_type(group_type);
+        match handler
+            .find_by_criteria(criteria.with_limit(limit).with_offset(page.offset))
+            .await
+        {
+            Ok(receivers) => {
+                let access = field_access(ctx);
+                Ok(EventReceiverConnection {
+                    nodes: receivers
+                        .into_iter()
+                        .map(|r| EventReceiverType::for_caller(r, &access))
+                        .collect(),
+                    total_count,
+                    limit,
+                    offset: page.offset,
+                    has_more: page.offset + limit < total_count,
+                })
+            }
+            Err(e) => Err(Error::new(format!("Failed to find event receivers: {}", e))),
         }
+    }
 
-        if let Some(version) = event_receiver_group.version {
-            criteria = criteria.with_version(version);
-        }
+    /// Find event receiver groups with criteria, one page at a time
+    async fn event_receiver_groups(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] event_receiver_group: FindEventReceiverGroupInput,
+    ) -> Result<EventReceiverGroupConnection> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+        let (criteria, page) = event_receiver_group
+            .into_criteria()
+            .map_err(|e| domain_error(ctx, &e))?;
+        let limit = page.effective_limit(None);
 
-        // Set default pagination if no specific criteria
-        if criteria.is_empty() {
-            criteria = criteria.with_limit(50).with_offset(0);
-        }
+        let total_count = handler
+            .count_by_criteria(criteria.clone())
+            .await
+            .map_err(|e| Error::new(format!("Failed to count event receiver groups: {}", e)))?;
 
-        match handler.find_by_criteria(criteria).await {
-            Ok(groups) => Ok(groups.into_iter().map(|g| g.into()).collect()),
+        match handler
+            .find_by_criteria(criteria.with_limit(limit).with_offset(page.offset))
+            .await
+        {
+            Ok(groups) => Ok(EventReceiverGroupConnection {
+                nodes: groups.into_iter().map(|g| g.into()).collect(),
+                total_count,
+                limit,
+                offset: page.offset,
+                has_more: page.offset + limit < total_count,
+            }),
             Err(e) => Err(Error::new(format!(
                 "Failed to find event receiver groups: {}",
                 e
             ))),
         }
     }
+
+    /// Search receivers, events, and groups by their text
+    ///
+    /// `query` takes words, `"quoted phrases"`, and `prefix*` terms, all of
+    /// which must match. Hits are ranked across types, exact name matches
+    /// first, and only include resources the caller may read. `types`
+    /// defaults to all types; `limit` defaults to 20 and may be at most 50.
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        types: Option<Vec<SearchTypeInput>>,
+        limit: Option<usize>,
+    ) -> Result<SearchResultsType> {
+        let handler = ctx
+            .data_opt::<Arc<SearchHandler>>()
+            .ok_or_else(|| coded_error("search_unavailable", "Search is not configured"))?;
+        let user = ctx.data::<AuthenticatedUser>()?;
+        let viewer = search_viewer(user)
+            .map_err(|e| Error::new(format!("Invalid user ID in token: {}", e)))?;
+        let types: Vec<SearchResourceType> = types
+            .unwrap_or_default()
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        handler
+            .search(&query, &types, &viewer, limit)
+            .await
+            .map(Into::into)
+            .map_err(|e| app_error(ctx, "Failed to search", &e))
+    }
 }
 
 pub struct Mutation;
@@ -149,7 +472,7 @@ pub struct Mutation;
 #[Object]
 impl Mutation {
     /// Create a new event
-    async fn create_event(&self, _ctx: &Context<'_>, _event: CreateEventInput) -> Result<ID> {
+    async fn create_event(&self, _ctx: &Context<'_>, _event: CreateEventInput) -> Result<EventId> {
         // TODO: Implement event creation once Event entity is complete
         Err(Error::new("Event creation not implemented yet"))
     }
@@ -159,12 +482,14 @@ impl Mutation {
         &self,
         ctx: &Context<'_>,
         event_receiver: CreateEventReceiverInput,
-    ) -> Result<ID> {
+    ) -> Result<EventReceiverId> {
         let handler = ctx.data::<Arc<EventReceiverHandler>>()?;
         let user = ctx.data::<AuthenticatedUser>()?;
 
         // Parse user ID from authenticated user
-        let owner_id = UserId::parse(user.user_id())
+        let owner_id = user
+            .user_id()
+            .parse::<UserId>()
             .map_err(|e| Error::new(format!("Invalid user ID: {}", e)))?;
 
         match handler
@@ -178,11 +503,82 @@ impl Mutation {
             )
             .await
         {
-            Ok(receiver_id) => Ok(ID(receiver_id.to_string())),
-            Err(e) => Err(Error::new(format!(
-                "Failed to create event receiver: {}",
-                e
-            ))),
+            Ok(receiver_id) => Ok(receiver_id),
+            Err(e) => Err(app_error(ctx, "Failed to create event receiver", &e)),
+        }
+    }
+
+    /// Update an event receiver
+    ///
+    /// Only the receiver's owner, an admin, or a caller with the
+    /// `event_receiver:update` permission may update it. Errors carry a
+    /// `code` extension: `not_found` for an unknown receiver and
+    /// `forbidden` for other callers.
+    async fn update_event_receiver(
+        &self,
+        ctx: &Context<'_>,
+        id: EventReceiverId,
+        event_receiver: UpdateEventReceiverInput,
+    ) -> Result<EventReceiverId> {
+        authorize(ctx, ResourceAction::Update, ProtectedResource::Receiver(id)).await?;
+        let handler = ctx.data::<Arc<EventReceiverHandler>>()?;
+
+        match handler
+            .update_event_receiver(
+                id,
+                event_receiver.name,
+                event_receiver.receiver_type,
+                event_receiver.version,
+                event_receiver.description,
+                event_receiver.schema.map(|schema| schema.0),
+            )
+            .await
+        {
+            Ok(()) => Ok(id),
+            Err(e) => Err(app_error(ctx, "Failed to update event receiver", &e)),
+        }
+    }
+
+    /// Delete an event receiver
+    ///
+    /// Authorized and reported like `updateEventReceiver`, with the
+    /// `event_receiver:delete` permission. Recent events or active scoped
+    /// API keys fail the deletion with a `conflict` code unless an
+    /// administrator sets `force`.
+    async fn delete_event_receiver(
+        &self,
+        ctx: &Context<'_>,
+        id: EventReceiverId,
+        #[graphql(default)] force: bool,
+    ) -> Result<bool> {
+        authorize(ctx, ResourceAction::Delete, ProtectedResource::Receiver(id)).await?;
+        let handler = ctx.data::<Arc<EventReceiverHandler>>()?;
+        let user = ctx.data::<AuthenticatedUser>()?;
+        if force && !user.has_role("admin") {
+            return Err(coded_error(
+                "forbidden",
+                "Admin role required to force a deletion",
+            ));
+        }
+
+        match handler
+            .delete_event_receiver(id, force, user.user_id())
+            .await
+        {
+            Ok(ReceiverDeletionOutcome::Deleted(_)) => {
+                ctx.data::<AuthorizationService>()?
+                    .forget_receiver(id)
+                    .await;
+                Ok(true)
+            }
+            Ok(ReceiverDeletionOutcome::Blocked(blockers)) => Err(coded_error(
+                "conflict",
+                format!(
+                    "Receiver deletion blocked by {} condition(s); an administrator may force it",
+                    blockers.len()
+                ),
+            )),
+            Err(e) => Err(app_error(ctx, "Failed to delete event receiver", &e)),
         }
     }
 
@@ -191,16 +587,16 @@ impl Mutation {
         &self,
         ctx: &Context<'_>,
         event_receiver_group: CreateEventReceiverGroupInput,
-    ) -> Result<ID> {
+    ) -> Result<EventReceiverGroupId> {
         let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
         let user = ctx.data::<AuthenticatedUser>()?;
 
         // Parse user ID from authenticated user
-        let owner_id = UserId::parse(user.user_id())
+        let owner_id = user
+            .user_id()
+            .parse::<UserId>()
             .map_err(|e| Error::new(format!("Invalid user ID: {}", e)))?;
 
-        let receiver_ids = parse_event_receiver_ids(&event_receiver_group.event_receiver_ids)?;
-
         match handler
             .create_event_receiver_group(
                 event_receiver_group.name,
@@ -208,178 +604,727 @@ impl Mutation {
                 event_receiver_group.version,
                 event_receiver_group.description,
                 event_receiver_group.enabled,
-                receiver_ids,
+                event_receiver_group.event_receiver_ids,
+                event_receiver_group.optional_receiver_ids,
+                None,
+                event_receiver_group.dedicated_topic,
                 owner_id,
             )
             .await
         {
-            Ok(group_id) => Ok(ID(group_id.to_string())),
-            Err(e) => Err(Error::new(format!(
-                "Failed to create event receiver group: {}",
-                e
-            ))),
+            Ok(group_id) => Ok(group_id),
+            Err(e) => Err(app_error(ctx, "Failed to create event receiver group", &e)),
         }
     }
 
-    /// Enable an event receiver group
-    async fn set_event_receiver_group_enabled(&self, ctx: &Context<'_>, id: ID) -> Result<ID> {
+    /// Update an event receiver group
+    ///
+    /// Only the group's owner, an admin, or a caller with the
+    /// `event_receiver_group:update` permission may update it. Errors carry
+    /// a `code` extension: `not_found` for an unknown group and `forbidden`
+    /// for other callers.
+    async fn update_event_receiver_group(
+        &self,
+        ctx: &Context<'_>,
+        id: EventReceiverGroupId,
+        event_receiver_group: UpdateEventReceiverGroupInput,
+    ) -> Result<EventReceiverGroupId> {
+        authorize(ctx, ResourceAction::Update, ProtectedResource::Group(id)).await?;
         let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
-        let group_id = parse_event_receiver_group_id(&id)?;
 
-        match handler.enable_event_receiver_group(group_id).await {
-            Ok(enabled_group_id) => Ok(ID(enabled_group_id.to_string())),
-            Err(e) => Err(Error::new(format!(
-                "Failed to enable event receiver group: {}",
-                e
-            ))),
+        let params = UpdateEventReceiverGroupParams {
+            name: event_receiver_group.name,
+            group_type: event_receiver_group.group_type,
+            version: event_receiver_group.version,
+            description: event_receiver_group.description,
+            enabled: event_receiver_group.enabled,
+            event_receiver_ids: event_receiver_group.event_receiver_ids,
+            optional_receiver_ids: event_receiver_group.optional_receiver_ids,
+            default_schema: None,
+            dedicated_topic: event_receiver_group.dedicated_topic,
+        };
+        match handler.update_event_receiver_group(id, params).await {
+            Ok(()) => Ok(id),
+            Err(e) => Err(app_error(ctx, "Failed to update event receiver group", &e)),
         }
     }
 
-    /// Disable an event receiver group
-    async fn set_event_receiver_group_disabled(&self, ctx: &Context<'_>, id: ID) -> Result<ID> {
+    /// Delete an event receiver group
+    ///
+    /// Authorized and reported like `updateEventReceiverGroup`, with the
+    /// `event_receiver_group:delete` permission.
+    async fn delete_event_receiver_group(
+        &self,
+        ctx: &Context<'_>,
+        id: EventReceiverGroupId,
+    ) -> Result<bool> {
+        authorize(ctx, ResourceAction::Delete, ProtectedResource::Group(id)).await?;
         let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
-        let group_id = parse_event_receiver_group_id(&id)?;
 
-        match handler.disable_event_receiver_group(group_id).await {
-            Ok(disabled_group_id) => Ok(ID(disabled_group_id.to_string())),
-            Err(e) => Err(Error::new(format!(
-                "Failed to disable event receiver group: {}",
-                e
-            ))),
+        match handler.delete_event_receiver_group(id).await {
+            Ok(()) => Ok(true),
+            Err(e) => Err(app_error(ctx, "Failed to delete event receiver group", &e)),
         }
     }
 
-    /// Add a member to an event receiver group
-    ///
-    /// # Arguments
+    /// Enable an event receiver group
     ///
-    /// * `group_id` - The ID of the group to add the member to
-    /// * `user_id` - The ID of the user to add as a member
+    /// Authorized like `updateEventReceiverGroup`.
+    async fn set_event_receiver_group_enabled(
+        &self,
+        ctx: &Context<'_>,
+        id: EventReceiverGroupId,
+    ) -> Result<EventReceiverGroupId> {
+        authorize(ctx, ResourceAction::Update, ProtectedResource::Group(id)).await?;
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+
+        match handler.enable_event_receiver_group(id).await {
+            Ok(enabled_group_id) => Ok(enabled_group_id),
+            Err(e) => Err(app_error(ctx, "Failed to enable event receiver group", &e)),
+        }
+    }
+
+    /// Disable an event receiver group
     ///
-    /// # Returns
+    /// Authorized like `updateEventReceiverGroup`.
+    async fn set_event_receiver_group_disabled(
+        &self,
+        ctx: &Context<'_>,
+        id: EventReceiverGroupId,
+    ) -> Result<EventReceiverGroupId> {
+        authorize(ctx, ResourceAction::Update, ProtectedResource::Group(id)).await?;
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+
+        match handler.disable_event_receiver_group(id).await {
+            Ok(disabled_group_id) => Ok(disabled_group_id),
+            Err(e) => Err(app_error(ctx, "Failed to disable event receiver group", &e)),
+        }
+    }
+
+    /// Enable an event receiver group and return it
     ///
-    /// Returns the group ID on success
+    /// Authorized like `updateEventReceiverGroup`. Enabling an enabled
+    /// group returns it unchanged.
+    async fn enable_group(
+        &self,
+        ctx: &Context<'_>,
+        id: EventReceiverGroupId,
+    ) -> Result<EventReceiverGroupType> {
+        set_group_enabled(ctx, id, true).await
+    }
+
+    /// Disable an event receiver group and return it
     ///
-    /// # Errors
+    /// Authorized like `updateEventReceiverGroup`. Disabling a disabled
+    /// group returns it unchanged.
+    async fn disable_group(
+        &self,
+        ctx: &Context<'_>,
+        id: EventReceiverGroupId,
+    ) -> Result<EventReceiverGroupType> {
+        set_group_enabled(ctx, id, false).await
+    }
+
+    /// Add an event receiver to a group and return the updated group
     ///
-    /// Returns error if:
-    /// - Group not found
-    /// - User is not the group owner
-    /// - User is already a member
-    /// - Invalid ID format
-    async fn add_group_member(
+    /// Authorized like `updateEventReceiverGroup`. Errors carry a `code`
+    /// extension: `not_found` for an unknown group or receiver, `forbidden`
+    /// for callers who may not update the group, and `conflict` when the
+    /// receiver is already in the group.
+    async fn add_receiver_to_group(
         &self,
         ctx: &Context<'_>,
-        group_id: ID,
-        user_id: ID,
-    ) -> Result<GroupMemberType> {
+        group_id: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<EventReceiverGroupType> {
         let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
-        let user = ctx.data::<AuthenticatedUser>()?;
+        let caller = caller_id(ctx)?;
+        let action = AuditAction::ResourceCreate;
+        let resource = format!("group:{}/receiver:{}", group_id, receiver_id);
+        authorize_group_change(ctx, action.clone(), caller, group_id, &resource).await?;
 
-        // Parse authenticated user ID (who is adding the member)
-        let added_by = UserId::parse(user.user_id())
-            .map_err(|e| Error::new(format!("Invalid user ID in token: {}", e)))?;
+        let group = handler
+            .add_event_receiver_to_group(group_id, receiver_id)
+            .await
+            .map_err(|e| group_receiver_error(ctx, "Failed to add receiver", &e))?;
+        audit_group_change(ctx, action, AuditOutcome::Success, caller, resource);
 
-        // Parse group ID
-        let group_id = parse_event_receiver_group_id(&group_id)?;
+        Ok(group.into())
+    }
 
-        // Parse user ID to add
-        let member_user_id = parse_user_id(&user_id)?;
+    /// Remove an event receiver from a group and return the updated group
+    ///
+    /// Authorized like `updateEventReceiverGroup`. Errors carry a `code`
+    /// extension: `not_found` for an unknown group, `forbidden` for callers
+    /// who may not update the group, and `business_rule_violation` when the
+    /// receiver is not in the group.
+    async fn remove_receiver_from_group(
+        &self,
+        ctx: &Context<'_>,
+        group_id: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<EventReceiverGroupType> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+        let caller = caller_id(ctx)?;
+        let action = AuditAction::ResourceDelete;
+        let resource = format!("group:{}/receiver:{}", group_id, receiver_id);
+        authorize_group_change(ctx, action.clone(), caller, group_id, &resource).await?;
 
-        // Verify the group exists and user is owner
         let group = handler
-            .find_group_by_id(group_id)
+            .remove_event_receiver_from_group(group_id, receiver_id)
             .await
-            .map_err(|e| Error::new(format!("Failed to fetch group: {}", e)))?
-            .ok_or_else(|| Error::new("Group not found"))?;
+            .map_err(|e| group_receiver_error(ctx, "Failed to remove receiver", &e))?;
+        audit_group_change(ctx, action, AuditOutcome::Success, caller, resource);
 
-        if group.owner_id() != added_by {
-            return Err(Error::new("Only the group owner can add members"));
-        }
+        Ok(group.into())
+    }
 
-        // Add the member
+    /// Move an event receiver from one group to another
+    ///
+    /// The receiver leaves `fromGroup` and joins `toGroup` together: if
+    /// either change fails, neither group changes. The caller must be
+    /// allowed to update both groups. Errors are reported like
+    /// `addReceiverToGroup` and `removeReceiverFromGroup`.
+    async fn move_receiver(
+        &self,
+        ctx: &Context<'_>,
+        from_group: EventReceiverGroupId,
+        to_group: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<ReceiverMoveType> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+        let caller = caller_id(ctx)?;
+        let action = AuditAction::ResourceUpdate;
+        let removed = format!("group:{}/receiver:{}", from_group, receiver_id);
+        let added = format!("group:{}/receiver:{}", to_group, receiver_id);
+        authorize_group_change(ctx, action.clone(), caller, from_group, &removed).await?;
+        authorize_group_change(ctx, action.clone(), caller, to_group, &added).await?;
+
+        let (from, to) = handler
+            .move_event_receiver(from_group, to_group, receiver_id)
+            .await
+            .map_err(|e| group_receiver_error(ctx, "Failed to move receiver", &e))?;
+        audit_group_change(
+            ctx,
+            AuditAction::ResourceDelete,
+            AuditOutcome::Success,
+            caller,
+            removed,
+        );
+        audit_group_change(
+            ctx,
+            AuditAction::ResourceCreate,
+            AuditOutcome::Success,
+            caller,
+            added,
+        );
+
+        Ok(ReceiverMoveType {
+            from_group: from.into(),
+            to_group: to.into(),
+        })
+    }
+
+    /// Add a member to an event receiver group
+    ///
+    /// Only the group owner or an admin may add members. With `expiresAt`
+    /// the member loses access at that time; it must be in the future and
+    /// within the configured maximum membership duration. Errors carry a
+    /// `code` extension: `not_found` for an unknown group, `forbidden` for
+    /// other callers, `conflict` when the user is already a member, and
+    /// `validation_error` for an invalid `expiresAt`.
+    async fn add_group_member(
+        &self,
+        ctx: &Context<'_>,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        expires_at: Option<Time>,
+    ) -> Result<MemberType> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+        let added_by = caller_id(ctx)?;
+        let action = AuditAction::ResourceCreate;
+        authorize_membership_change(
+            ctx,
+            ResourceAction::AddMember,
+            action.clone(),
+            added_by,
+            group_id,
+            user_id,
+        )
+        .await?;
+
+        let expires_at = expires_at.map(|t| t.0);
         handler
-            .add_group_member(group_id, member_user_id, added_by)
+            .add_group_member(group_id, user_id, added_by, expires_at)
             .await
-            .map_err(|e| Error::new(format!("Failed to add member: {}", e)))?;
+            .map_err(|e| match e.status_code() {
+                StatusCode::CONFLICT => {
+                    coded_error("conflict", "User is already a member of this group")
+                }
+                _ => app_error(ctx, "Failed to add member", &e),
+            })?;
+        audit_membership(
+            ctx,
+            action,
+            AuditOutcome::Success,
+            added_by,
+            group_id,
+            user_id,
+        );
 
-        // Return member info
-        Ok(GroupMemberType {
-            user_id: user_id.clone(),
-            username: format!("user_{}", member_user_id),
-            email: format!("{}@example.com", member_user_id),
+        Ok(MemberType {
+            user_id,
             added_at: Time(chrono::Utc::now()),
-            added_by: ID(added_by.to_string()),
+            added_by,
+            expires_at: expires_at.map(Time),
         })
     }
 
-    /// Remove a member from an event receiver group
-    ///
-    /// # Arguments
+    /// Change when a member of an event receiver group loses access
     ///
-    /// * `group_id` - The ID of the group to remove the member from
-    /// * `user_id` - The ID of the user to remove
-    ///
-    /// # Returns
-    ///
-    /// Returns true on success
-    ///
-    /// # Errors
+    /// Only the group owner or an admin may change memberships. A null
+    /// `expiresAt` keeps the member until removed. Errors carry a `code`
+    /// extension: `not_found` for an unknown group or a user who is not a
+    /// current member, `forbidden` for other callers, and `validation_error`
+    /// for an invalid `expiresAt`.
+    async fn update_group_member(
+        &self,
+        ctx: &Context<'_>,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        expires_at: Option<Time>,
+    ) -> Result<MemberType> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+        let updated_by = caller_id(ctx)?;
+        let action = AuditAction::ResourceUpdate;
+        authorize_membership_change(
+            ctx,
+            ResourceAction::UpdateMember,
+            action.clone(),
+            updated_by,
+            group_id,
+            user_id,
+        )
+        .await?;
+
+        let membership = handler
+            .set_group_member_expiry(group_id, user_id, expires_at.map(|t| t.0))
+            .await
+            .map_err(|e| match e.status_code() {
+                StatusCode::NOT_FOUND => {
+                    coded_error("not_found", "User is not a member of this group")
+                }
+                _ => app_error(ctx, "Failed to update member", &e),
+            })?;
+        audit_membership(
+            ctx,
+            action,
+            AuditOutcome::Success,
+            updated_by,
+            group_id,
+            user_id,
+        );
+
+        Ok(membership.into())
+    }
+
+    /// Remove a member from an event receiver group
     ///
-    /// Returns error if:
-    /// - Group not found
-    /// - User is not the group owner
-    /// - User is not a member
-    /// - Invalid ID format
+    /// Only the group owner or an admin may remove members. Errors carry a
+    /// `code` extension: `not_found` for an unknown group or a user who is
+    /// not a member, and `forbidden` for other callers.
     async fn remove_group_member(
         &self,
         ctx: &Context<'_>,
-        group_id: ID,
-        user_id: ID,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
     ) -> Result<bool> {
         let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
-        let user = ctx.data::<AuthenticatedUser>()?;
+        let removed_by = caller_id(ctx)?;
+        let action = AuditAction::ResourceDelete;
+        authorize_membership_change(
+            ctx,
+            ResourceAction::RemoveMember,
+            action.clone(),
+            removed_by,
+            group_id,
+            user_id,
+        )
+        .await?;
 
-        // Parse authenticated user ID (who is removing the member)
-        let removed_by = UserId::parse(user.user_id())
-            .map_err(|e| Error::new(format!("Invalid user ID in token: {}", e)))?;
+        handler
+            .remove_group_member(group_id, user_id)
+            .await
+            .map_err(|e| match e.status_code() {
+                StatusCode::NOT_FOUND => {
+                    coded_error("not_found", "User is not a member of this group")
+                }
+                _ => app_error(ctx, "Failed to remove member", &e),
+            })?;
+        audit_membership(
+            ctx,
+            action,
+            AuditOutcome::Success,
+            removed_by,
+            group_id,
+            user_id,
+        );
 
-        // Parse group ID
-        let group_id = parse_event_receiver_group_id(&group_id)?;
+        Ok(true)
+    }
+}
 
-        // Parse user ID to remove
-        let member_user_id = parse_user_id(&user_id)?;
+#[ComplexObject]
+impl EventReceiverType {
+    /// Events, configuration changes, and group membership changes of the
+    /// receiver, oldest first
+    ///
+    /// Matches `GET /api/v1/receivers/:id/timeline`: `start` is inclusive,
+    /// `end` exclusive, and configuration changes are only included for the
+    /// receiver owner and admins. `first` defaults to 50 and may be at most
+    /// 1000; `after` takes the `endCursor` of the previous page.
+    async fn timeline(
+        &self,
+        ctx: &Context<'_>,
+        start: Option<Time>,
+        end: Option<Time>,
+        first: Option<usize>,
+        after: Option<String>,
+    ) -> Result<TimelineConnection> {
+        let handler = ctx.data::<Arc<EventReceiverHandler>>()?;
+        let Some(timeline) = handler.timeline() else {
+            return Err(coded_error(
+                "timeline_unavailable",
+                "Receiver timelines are not configured",
+            ));
+        };
+        authorize(
+            ctx,
+            ResourceAction::Read,
+            ProtectedResource::Receiver(self.id),
+        )
+        .await?;
 
-        // Verify the group exists and user is owner
-        let group = handler
-            .find_group_by_id(group_id)
+        let page = PaginationParams::new(first, 0);
+        page.validate().map_err(|e| domain_error(ctx, &e))?;
+        let after = after
+            .as_deref()
+            .map(TimelineCursor::parse)
+            .transpose()
+            .map_err(|e| domain_error(ctx, &e))?;
+        let query = TimelineQuery {
+            start: start.map(|start| start.0),
+            end: end.map(|end| end.0),
+            after,
+            limit: page.effective_limit(None),
+            kinds: field_access(ctx).timeline_kinds(self.owner_id),
+        };
+
+        let page = timeline
+            .timeline(self.id, query)
             .await
-            .map_err(|e| Error::new(format!("Failed to fetch group: {}", e)))?
-            .ok_or_else(|| Error::new("Group not found"))?;
+            .map_err(|e| app_error(ctx, "Failed to read timeline", &e))?;
+        Ok(TimelineConnection {
+            has_next_page: page.next_cursor.is_some(),
+            end_cursor: page.next_cursor.map(|cursor| cursor.to_string()),
+            nodes: page.entries.into_iter().map(Into::into).collect(),
+        })
+    }
+}
+
+#[ComplexObject]
+impl EventReceiverGroupType {
+    /// Whether every required member receiver stored a successful event
+    /// with `release`
+    ///
+    /// Matches `GET /api/v1/groups/:id/completeness`: only events at or
+    /// after `since` count, and optional receivers are reported but never
+    /// block completeness.
+    async fn completeness(
+        &self,
+        ctx: &Context<'_>,
+        release: String,
+        since: Option<Time>,
+    ) -> Result<GroupCompletenessType> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+        let Some(completeness) = handler.completeness() else {
+            return Err(coded_error(
+                "completeness_unavailable",
+                "Release completeness is not configured",
+            ));
+        };
+        authorize(ctx, ResourceAction::Read, ProtectedResource::Group(self.id)).await?;
+
+        completeness
+            .completeness(self.id, &release, since.map(|since| since.0))
+            .await
+            .map(Into::into)
+            .map_err(|e| app_error(ctx, "Failed to read release completeness", &e))
+    }
+
+    /// Members of the group in the order they were added
+    ///
+    /// Only the group owner and members may list them. `first` defaults to
+    /// 50 and may be at most 1000; `after` takes the `endCursor` of the
+    /// previous page.
+    async fn members(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<usize>,
+        after: Option<String>,
+    ) -> Result<MemberConnection> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+        let page = PaginationParams::new(first, 0);
+        page.validate().map_err(|e| domain_error(ctx, &e))?;
+        let first = page.effective_limit(None);
+        let after = after
+            .as_deref()
+            .map(MemberCursor::parse)
+            .transpose()
+            .map_err(|e| domain_error(ctx, &e))?;
 
-        if group.owner_id() != removed_by {
-            return Err(Error::new("Only the group owner can remove members"));
+        let caller = caller_id(ctx)?;
+        let is_member = handler
+            .is_group_member(self.id, caller)
+            .await
+            .map_err(|e| Error::new(format!("Failed to check group membership: {}", e)))?;
+        if self.owner_id != caller && !is_member {
+            return Err(coded_error(
+                "forbidden",
+                "Only group owners and members can view the member list",
+            ));
         }
 
-        // Remove the member
-        handler
-            .remove_group_member(group_id, member_user_id)
+        let memberships = handler
+            .get_group_memberships(self.id)
             .await
-            .map_err(|e| Error::new(format!("Failed to remove member: {}", e)))?;
+            .map_err(|e| Error::new(format!("Failed to fetch group members: {}", e)))?;
+        let total_count = memberships.len();
+        let mut nodes: Vec<_> = memberships
+            .into_iter()
+            .filter(|m| after.as_ref().is_none_or(|after| m.cursor() > *after))
+            .take(first + 1)
+            .collect();
+        let has_next_page = nodes.len() > first;
+        nodes.truncate(first);
 
-        Ok(true)
+        Ok(MemberConnection {
+            end_cursor: nodes.last().map(|m| m.cursor().to_string()),
+            nodes: nodes.into_iter().map(Into::into).collect(),
+            total_count,
+            has_next_page,
+        })
+    }
+}
+
+#[ComplexObject]
+impl MemberType {
+    async fn username(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        Ok(load_user(ctx, self.user_id)
+            .await?
+            .map(|user| user.username().to_string()))
+    }
+
+    async fn email(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        Ok(load_user(ctx, self.user_id)
+            .await?
+            .map(|user| user.email().to_string()))
+    }
+}
+
+#[ComplexObject]
+impl MeType {
+    /// Groups the user is a member of
+    async fn groups(&self, ctx: &Context<'_>) -> Result<Vec<EventReceiverGroupType>> {
+        let handler = ctx.data::<Arc<EventReceiverGroupHandler>>()?;
+
+        match handler.find_groups_for_user(self.user_id).await {
+            Ok(groups) => Ok(groups.into_iter().map(Into::into).collect()),
+            Err(e) => Err(Error::new(format!("Failed to find groups: {}", e))),
+        }
     }
 }
 
 pub type Schema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
 
 /// Creates a new GraphQL schema with the provided handlers
+///
+/// Mutations of receivers and groups are checked by `authorization`, the
+/// same service the REST API uses.
 pub fn create_schema(
+    event_handler: Arc<EventHandler>,
+    event_receiver_handler: Arc<EventReceiverHandler>,
+    event_receiver_group_handler: Arc<EventReceiverGroupHandler>,
+    authorization: AuthorizationService,
+) -> Schema {
+    create_schema_with_introspection(
+        event_handler,
+        event_receiver_handler,
+        event_receiver_group_handler,
+        authorization,
+        IntrospectionMode::Enabled,
+    )
+}
+
+/// Creates a new GraphQL schema that limits introspection to `introspection`
+pub fn create_schema_with_introspection(
+    event_handler: Arc<EventHandler>,
+    event_receiver_handler: Arc<EventReceiverHandler>,
+    event_receiver_group_handler: Arc<EventReceiverGroupHandler>,
+    authorization: AuthorizationService,
+    introspection: IntrospectionMode,
+) -> Schema {
+    create_schema_with_config(
+        event_handler,
+        event_receiver_handler,
+        event_receiver_group_handler,
+        authorization,
+        None,
+        &GraphQLConfig {
+            introspection,
+            ..GraphQLConfig::default()
+        },
+    )
+}
+
+/// Creates a new GraphQL schema with the introspection mode and resolver
+/// tracing of `config`
+///
+/// With `persisted_queries`, clients may send queries by hash and
+/// `config.persisted_only` limits callers to registered queries. Member
+/// usernames and emails resolve to null; use [`create_schema_with_users`]
+/// to look them up.
+pub fn create_schema_with_config(
+    event_handler: Arc<EventHandler>,
     event_receiver_handler: Arc<EventReceiverHandler>,
     event_receiver_group_handler: Arc<EventReceiverGroupHandler>,
+    authorization: AuthorizationService,
+    persisted_queries: Option<PersistedQueryStore>,
+    config: &GraphQLConfig,
 ) -> Schema {
-    Schema::build(Query, Mutation, EmptySubscription)
+    build_schema(
+        event_handler,
+        event_receiver_handler,
+        event_receiver_group_handler,
+        authorization,
+        None,
+        None,
+        persisted_queries,
+        config,
+    )
+}
+
+/// Creates a new GraphQL schema like [`create_schema_with_config`] that
+/// resolves member details through `users`
+pub fn create_schema_with_users(
+    event_handler: Arc<EventHandler>,
+    event_receiver_handler: Arc<EventReceiverHandler>,
+    event_receiver_group_handler: Arc<EventReceiverGroupHandler>,
+    authorization: AuthorizationService,
+    users: Arc<dyn UserRepository>,
+    config: &GraphQLConfig,
+) -> Schema {
+    build_schema(
+        event_handler,
+        event_receiver_handler,
+        event_receiver_group_handler,
+        authorization,
+        Some(users),
+        None,
+        None,
+        config,
+    )
+}
+
+/// Creates a new GraphQL schema like [`create_schema_with_users`] that
+/// also answers `search` through `search` and serves persisted queries
+/// like [`create_schema_with_config`]
+#[allow(clippy::too_many_arguments)]
+pub fn create_schema_with_search(
+    event_handler: Arc<EventHandler>,
+    event_receiver_handler: Arc<EventReceiverHandler>,
+    event_receiver_group_handler: Arc<EventReceiverGroupHandler>,
+    authorization: AuthorizationService,
+    users: Arc<dyn UserRepository>,
+    search: Arc<SearchHandler>,
+    persisted_queries: Option<PersistedQueryStore>,
+    config: &GraphQLConfig,
+) -> Schema {
+    build_schema(
+        event_handler,
+        event_receiver_handler,
+        event_receiver_group_handler,
+        authorization,
+        Some(users),
+        Some(search),
+        persisted_queries,
+        config,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_schema(
+    event_handler: Arc<EventHandler>,
+    event_receiver_handler: Arc<EventReceiverHandler>,
+    event_receiver_group_handler: Arc<EventReceiverGroupHandler>,
+    authorization: AuthorizationService,
+    users: Option<Arc<dyn UserRepository>>,
+    search: Option<Arc<SearchHandler>>,
+    persisted_queries: Option<PersistedQueryStore>,
+    config: &GraphQLConfig,
+) -> Schema {
+    let mut builder = Schema::build(Query, Mutation, EmptySubscription)
+        .data(event_handler)
         .data(event_receiver_handler)
         .data(event_receiver_group_handler)
-        .finish()
+        .data(authorization)
+        .data(Arc::new(AuditLogger::new()))
+        .extension(ResolverTracing::new(config.resolver_spans));
+
+    // Without a cache the loader only batches lookups made concurrently,
+    // so users are never served stale across requests
+    if let Some(users) = users {
+        builder = builder.data(DataLoader::new(UserLoader::new(users), tokio::spawn));
+    }
+
+    if let Some(search) = search {
+        builder = builder.data(search);
+    }
+
+    if let Some(store) = persisted_queries {
+        builder = builder.extension(PersistedQueries::new(store, config.persisted_only));
+    }
+
+    match config.introspection {
+        IntrospectionMode::Enabled => builder.finish(),
+        mode => builder.extension(IntrospectionGuard::new(mode)).finish(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fails when the SDL changes; regenerate the snapshot with
+    /// `UPDATE_GRAPHQL_SDL=1 cargo test test_sdl_snapshot`
+    #[test]
+    fn test_sdl_snapshot() {
+        let sdl = Schema::build(Query, Mutation, EmptySubscription)
+            .finish()
+            .sdl();
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/api/graphql/schema.graphql"
+        );
+
+        if std::env::var_os("UPDATE_GRAPHQL_SDL").is_some() {
+            std::fs::write(path, &sdl).unwrap();
+        }
+
+        let snapshot = std::fs::read_to_string(path).unwrap();
+        assert!(
+            sdl == snapshot,
+            "GraphQL SDL changed; review the diff and rerun with UPDATE_GRAPHQL_SDL=1"
+        );
+    }
 }
@@ -7,10 +7,25 @@ use async_graphql::*;
 use chrono::{DateTime, Utc};
 use serde_json::Value as JsonValue;
 
+use crate::api::field_access::{redacted_payload, FieldAccess};
+use crate::api::rest::timestamp::{format_utc, Timestamp};
 use crate::domain::entities::{
-    event_receiver::EventReceiver, event_receiver_group::EventReceiverGroup,
+    event::Event,
+    event_receiver::EventReceiver,
+    event_receiver_group::EventReceiverGroup,
+    release_completeness::{GroupCompleteness, ReceiverCompleteness, ReceiverReleaseStatus},
+    search::{SearchHit, SearchResourceType, SearchResults},
 };
-use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId, UserId};
+use crate::domain::repositories::event_receiver_group_repo::{
+    FindEventReceiverGroupCriteria, GroupMembership,
+};
+use crate::domain::repositories::event_receiver_repo::FindEventReceiverCriteria;
+use crate::domain::repositories::pagination::{ListOrder, PaginationParams, SortField};
+use crate::domain::repositories::receiver_timeline_repo::{
+    ConfigChangeAction, MembershipAction, TimelineDetail, TimelineEntry,
+};
+use crate::domain::value_objects::{EventId, EventReceiverGroupId, EventReceiverId, UserId};
+use crate::error::DomainError;
 
 /// Wrapper for JSON values to implement custom scalar
 #[derive(Debug, Clone, PartialEq)]
@@ -33,6 +48,9 @@ impl ScalarType for JSON {
 }
 
 /// Wrapper for DateTime<Utc> to implement custom scalar
+///
+/// Inputs follow the REST rules in [`crate::api::rest::timestamp`]: an
+/// explicit offset is required. Outputs are UTC with a `Z` suffix.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Time(pub DateTime<Utc>);
 
@@ -40,84 +58,169 @@ pub struct Time(pub DateTime<Utc>);
 impl ScalarType for Time {
     fn parse(value: Value) -> InputValueResult<Self> {
         match value {
-            Value::String(s) => DateTime::parse_from_rfc3339(&s)
-                .map(|dt| Time(dt.with_timezone(&Utc)))
-                .map_err(|e| InputValueError::custom(format!("Invalid datetime: {}", e))),
+            Value::String(s) => s
+                .parse::<Timestamp>()
+                .map(|timestamp| Time(timestamp.utc()))
+                .map_err(|message| InputValueError::custom(message.to_string())),
             _ => Err(InputValueError::expected_type(value)),
         }
     }
 
     fn to_value(&self) -> Value {
-        Value::String(self.0.to_rfc3339())
+        Value::String(format_utc(&self.0))
     }
 }
 
+/// Exposes ULID value objects as the GraphQL `ID` scalar
+///
+/// The schema keeps the built-in `ID` type, but inputs are validated as
+/// canonical ULIDs when the request is parsed.
+macro_rules! ulid_id_scalar {
+    ($($id:ty),+ $(,)?) => {$(
+        impl InputType for $id {
+            type RawValueType = Self;
+
+            fn type_name() -> std::borrow::Cow<'static, str> {
+                <ID as InputType>::type_name()
+            }
+
+            fn create_type_info(registry: &mut registry::Registry) -> String {
+                <ID as InputType>::create_type_info(registry)
+            }
+
+            fn parse(value: Option<Value>) -> InputValueResult<Self> {
+                let id = <ID as InputType>::parse(value).map_err(InputValueError::propagate)?;
+                id.parse().map_err(|e| {
+                    InputValueError::custom(format!("Invalid {}: {}", stringify!($id), e))
+                })
+            }
+
+            fn to_value(&self) -> Value {
+                Value::String(self.to_string())
+            }
+
+            fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+                Some(self)
+            }
+        }
+
+        impl OutputType for $id {
+            fn type_name() -> std::borrow::Cow<'static, str> {
+                <ID as OutputType>::type_name()
+            }
+
+            fn create_type_info(registry: &mut registry::Registry) -> String {
+                <ID as OutputType>::create_type_info(registry)
+            }
+
+            async fn resolve(
+                &self,
+                _ctx: &ContextSelectionSet<'_>,
+                _field: &Positioned<parser::types::Field>,
+            ) -> ServerResult<Value> {
+                Ok(Value::String(self.to_string()))
+            }
+        }
+    )+};
+}
+
+ulid_id_scalar!(EventId, EventReceiverId, EventReceiverGroupId, UserId);
+
 /// GraphQL type for EventReceiver
 #[derive(SimpleObject)]
-#[graphql(name = "EventReceiver")]
+#[graphql(name = "EventReceiver", complex)]
 pub struct EventReceiverType {
-    pub id: ID,
+    pub id: EventReceiverId,
     pub name: String,
     #[graphql(name = "type")]
     pub receiver_type: String,
     pub version: String,
     pub description: String,
-    pub schema: JSON,
+    /// Payload schema, null for callers not allowed to read it
+    pub schema: Option<JSON>,
     pub fingerprint: String,
     pub created_at: Time,
+    /// Decides who sees configuration changes in the timeline
+    #[graphql(skip)]
+    pub owner_id: UserId,
 }
 
-impl From<EventReceiver> for EventReceiverType {
-    fn from(receiver: EventReceiver) -> Self {
+impl EventReceiverType {
+    /// Builds the type, omitting the schema unless `access` allows it
+    pub fn for_caller(receiver: EventReceiver, access: &FieldAccess) -> Self {
+        let schema = access
+            .can_read_schema(receiver.owner_id())
+            .then(|| JSON(receiver.schema().clone()));
+
         Self {
-            id: ID(receiver.id().to_string()),
+            id: receiver.id(),
             name: receiver.name().to_string(),
             receiver_type: receiver.receiver_type().to_string(),
             version: receiver.version().to_string(),
             description: receiver.description().to_string(),
-            schema: JSON(receiver.schema().clone()),
+            schema,
             fingerprint: receiver.fingerprint().to_string(),
             created_at: Time(receiver.created_at()),
+            owner_id: receiver.owner_id(),
         }
     }
 }
 
 /// GraphQL type for EventReceiverGroup
 #[derive(SimpleObject)]
-#[graphql(name = "EventReceiverGroup")]
+#[graphql(name = "EventReceiverGroup", complex)]
 pub struct EventReceiverGroupType {
-    pub id: ID,
+    pub id: EventReceiverGroupId,
     pub name: String,
     #[graphql(name = "type")]
     pub group_type: String,
     pub version: String,
     pub description: String,
     pub enabled: bool,
-    pub event_receiver_ids: Vec<ID>,
+    pub event_receiver_ids: Vec<EventReceiverId>,
+    /// Members that never block release completeness
+    pub optional_receiver_ids: Vec<EventReceiverId>,
+    /// Kafka topic that also receives the events of member receivers
+    pub dedicated_topic: Option<String>,
+    /// Incremented on every change to the group
+    pub resource_version: i64,
     pub created_at: Time,
     pub updated_at: Time,
+    /// Decides who may list the members
+    #[graphql(skip)]
+    pub owner_id: UserId,
 }
 
 impl From<EventReceiverGroup> for EventReceiverGroupType {
     fn from(group: EventReceiverGroup) -> Self {
         Self {
-            id: ID(group.id().to_string()),
+            id: group.id(),
             name: group.name().to_string(),
             group_type: group.group_type().to_string(),
             version: group.version().to_string(),
             description: group.description().to_string(),
             enabled: group.enabled(),
-            event_receiver_ids: group
-                .event_receiver_ids()
-                .iter()
-                .map(|id| ID(id.to_string()))
-                .collect(),
+            event_receiver_ids: group.event_receiver_ids().to_vec(),
+            optional_receiver_ids: group.optional_receiver_ids().to_vec(),
+            dedicated_topic: group.dedicated_topic().map(str::to_string),
+            resource_version: group.resource_version(),
             created_at: Time(group.created_at()),
             updated_at: Time(group.updated_at()),
+            owner_id: group.owner_id(),
         }
     }
 }
 
+/// GraphQL type for the groups changed by moving a receiver between them
+#[derive(SimpleObject)]
+#[graphql(name = "ReceiverMove")]
+pub struct ReceiverMoveType {
+    /// Group the receiver was moved out of
+    pub from_group: EventReceiverGroupType,
+    /// Group the receiver was moved into
+    pub to_group: EventReceiverGroupType,
+}
+
 /// Input type for creating an event receiver
 #[derive(InputObject)]
 #[graphql(name = "CreateEventReceiverInput")]
@@ -142,15 +245,200 @@ impl From<CreateEventReceiverInput> for (String, String, String, String, JsonVal
     }
 }
 
+/// Input type for updating an event receiver
+///
+/// Omitted fields keep their current values.
+#[derive(Default, InputObject)]
+#[graphql(name = "UpdateEventReceiverInput")]
+pub struct UpdateEventReceiverInput {
+    pub name: Option<String>,
+    #[graphql(name = "type")]
+    pub receiver_type: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub schema: Option<JSON>,
+}
+
 /// Input type for finding event receivers
-#[derive(InputObject)]
+///
+/// Filters and paging match `GET /api/v1/receivers`.
+#[derive(Default, InputObject)]
 #[graphql(name = "FindEventReceiverInput")]
 pub struct FindEventReceiverInput {
-    pub id: Option<ID>,
+    pub id: Option<EventReceiverId>,
+    /// Partial, case-insensitive name match
     pub name: Option<String>,
     #[graphql(name = "type")]
     pub receiver_type: Option<String>,
     pub version: Option<String>,
+    pub owner_id: Option<UserId>,
+    /// Page size, 1 to 1000; defaults to 50
+    pub limit: Option<usize>,
+    #[graphql(default)]
+    pub offset: usize,
+    #[graphql(default)]
+    pub sort: ListOrderInput,
+}
+
+impl FindEventReceiverInput {
+    /// Splits the input into repository criteria and the requested page
+    pub fn into_criteria(
+        self,
+    ) -> Result<(FindEventReceiverCriteria, PaginationParams), DomainError> {
+        let page = PaginationParams::new(self.limit, self.offset);
+        page.validate()?;
+
+        let mut criteria = FindEventReceiverCriteria::new().with_order(self.sort.into());
+        if let Some(id) = self.id {
+            criteria = criteria.with_id(id);
+        }
+        if let Some(name) = self.name {
+            criteria = criteria.with_name(name);
+        }
+        if let Some(receiver_type) = self.receiver_type {
+            criteria = criteria.with_type(receiver_type);
+        }
+        if let Some(version) = self.version {
+            criteria = criteria.with_version(version);
+        }
+        if let Some(owner_id) = self.owner_id {
+            criteria = criteria.with_owner(owner_id);
+        }
+
+        Ok((criteria, page))
+    }
+}
+
+/// Sort order for receiver and group queries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Enum)]
+#[graphql(name = "ListOrder")]
+pub enum ListOrderInput {
+    /// Newest first
+    #[default]
+    CreatedAtDesc,
+    /// Oldest first
+    CreatedAtAsc,
+    /// Alphabetical by name
+    NameAsc,
+}
+
+impl<F: SortField> From<ListOrderInput> for ListOrder<F> {
+    fn from(order: ListOrderInput) -> Self {
+        match order {
+            ListOrderInput::CreatedAtDesc => ListOrder::desc(F::CREATED_AT),
+            ListOrderInput::CreatedAtAsc => ListOrder::asc(F::CREATED_AT),
+            ListOrderInput::NameAsc => ListOrder::asc(F::NAME),
+        }
+    }
+}
+
+/// Page of event receivers with the total number of matches
+#[derive(SimpleObject)]
+pub struct EventReceiverConnection {
+    pub nodes: Vec<EventReceiverType>,
+    /// Receivers matching the filter across all pages
+    pub total_count: usize,
+    pub limit: usize,
+    pub offset: usize,
+    pub has_more: bool,
+}
+
+/// Page of event receiver groups with the total number of matches
+#[derive(SimpleObject)]
+pub struct EventReceiverGroupConnection {
+    pub nodes: Vec<EventReceiverGroupType>,
+    /// Groups matching the filter across all pages
+    pub total_count: usize,
+    pub limit: usize,
+    pub offset: usize,
+    pub has_more: bool,
+}
+
+/// Resource types that `search` can return
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[graphql(name = "SearchType")]
+pub enum SearchTypeInput {
+    Receivers,
+    Events,
+    Groups,
+}
+
+impl From<SearchTypeInput> for SearchResourceType {
+    fn from(input: SearchTypeInput) -> Self {
+        match input {
+            SearchTypeInput::Receivers => SearchResourceType::Receiver,
+            SearchTypeInput::Events => SearchResourceType::Event,
+            SearchTypeInput::Groups => SearchResourceType::Group,
+        }
+    }
+}
+
+impl From<SearchResourceType> for SearchTypeInput {
+    fn from(resource_type: SearchResourceType) -> Self {
+        match resource_type {
+            SearchResourceType::Receiver => SearchTypeInput::Receivers,
+            SearchResourceType::Event => SearchTypeInput::Events,
+            SearchResourceType::Group => SearchTypeInput::Groups,
+        }
+    }
+}
+
+/// A resource matching a search
+#[derive(SimpleObject)]
+#[graphql(name = "SearchHit")]
+pub struct SearchHitType {
+    #[graphql(name = "type")]
+    pub resource_type: SearchTypeInput,
+    pub id: String,
+    pub name: String,
+    /// HTML-escaped excerpt with the matches wrapped in `<mark>` tags
+    pub snippet: String,
+    pub rank: f32,
+    /// Whether the name equals the query
+    pub exact_match: bool,
+}
+
+impl From<SearchHit> for SearchHitType {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            resource_type: hit.resource_type.into(),
+            id: hit.id,
+            name: hit.name,
+            snippet: hit.snippet,
+            rank: hit.rank,
+            exact_match: hit.exact_name,
+        }
+    }
+}
+
+/// Number of visible matches per resource type, before the limit
+#[derive(SimpleObject)]
+#[graphql(name = "SearchCounts")]
+pub struct SearchCountsType {
+    pub receivers: u64,
+    pub events: u64,
+    pub groups: u64,
+}
+
+/// Ranked search hits across resource types
+#[derive(SimpleObject)]
+#[graphql(name = "SearchResults")]
+pub struct SearchResultsType {
+    pub hits: Vec<SearchHitType>,
+    pub counts: SearchCountsType,
+}
+
+impl From<SearchResults> for SearchResultsType {
+    fn from(results: SearchResults) -> Self {
+        Self {
+            hits: results.hits.into_iter().map(Into::into).collect(),
+            counts: SearchCountsType {
+                receivers: results.counts.receivers,
+                events: results.counts.events,
+                groups: results.counts.groups,
+            },
+        }
+    }
 }
 
 /// Input type for creating an event receiver group
@@ -163,18 +451,85 @@ pub struct CreateEventReceiverGroupInput {
     pub version: String,
     pub description: String,
     pub enabled: bool,
-    pub event_receiver_ids: Vec<ID>,
+    pub event_receiver_ids: Vec<EventReceiverId>,
+    /// Members that never block release completeness
+    #[graphql(default)]
+    pub optional_receiver_ids: Vec<EventReceiverId>,
+    /// Kafka topic that also receives the events of member receivers
+    pub dedicated_topic: Option<String>,
+}
+
+/// Input type for updating an event receiver group
+///
+/// Omitted fields keep their current values.
+#[derive(Default, InputObject)]
+#[graphql(name = "UpdateEventReceiverGroupInput")]
+pub struct UpdateEventReceiverGroupInput {
+    pub name: Option<String>,
+    #[graphql(name = "type")]
+    pub group_type: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub enabled: Option<bool>,
+    pub event_receiver_ids: Option<Vec<EventReceiverId>>,
+    /// Members that never block release completeness; ids that are not
+    /// members are rejected
+    pub optional_receiver_ids: Option<Vec<EventReceiverId>>,
+    /// New topic that also receives the events of member receivers; an
+    /// empty name clears it
+    pub dedicated_topic: Option<String>,
 }
 
 /// Input type for finding event receiver groups
-#[derive(InputObject)]
+#[derive(Default, InputObject)]
 #[graphql(name = "FindEventReceiverGroupInput")]
 pub struct FindEventReceiverGroupInput {
-    pub id: Option<ID>,
+    pub id: Option<EventReceiverGroupId>,
+    /// Partial, case-insensitive name match
     pub name: Option<String>,
     #[graphql(name = "type")]
     pub group_type: Option<String>,
     pub version: Option<String>,
+    pub enabled: Option<bool>,
+    pub owner_id: Option<UserId>,
+    /// Page size, 1 to 1000; defaults to 50
+    pub limit: Option<usize>,
+    #[graphql(default)]
+    pub offset: usize,
+    #[graphql(default)]
+    pub sort: ListOrderInput,
+}
+
+impl FindEventReceiverGroupInput {
+    /// Splits the input into repository criteria and the requested page
+    pub fn into_criteria(
+        self,
+    ) -> Result<(FindEventReceiverGroupCriteria, PaginationParams), DomainError> {
+        let page = PaginationParams::new(self.limit, self.offset);
+        page.validate()?;
+
+        let mut criteria = FindEventReceiverGroupCriteria::new().with_order(self.sort.into());
+        if let Some(id) = self.id {
+            criteria = criteria.with_id(id);
+        }
+        if let Some(name) = self.name {
+            criteria = criteria.with_name(name);
+        }
+        if let Some(group_type) = self.group_type {
+            criteria = criteria.with_type(group_type);
+        }
+        if let Some(version) = self.version {
+            criteria = criteria.with_version(version);
+        }
+        if let Some(enabled) = self.enabled {
+            criteria = criteria.with_enabled(enabled);
+        }
+        if let Some(owner_id) = self.owner_id {
+            criteria = criteria.with_owner(owner_id);
+        }
+
+        Ok((criteria, page))
+    }
 }
 
 impl From<CreateEventInput>
@@ -199,7 +554,7 @@ impl From<CreateEventInput>
             input.package,
             input.description,
             input.payload.0,
-            input.event_receiver_id.0,
+            input.event_receiver_id.to_string(),
             input.success,
         )
     }
@@ -216,7 +571,7 @@ pub struct CreateEventInput {
     pub package: String,
     pub description: String,
     pub payload: JSON,
-    pub event_receiver_id: ID,
+    pub event_receiver_id: EventReceiverId,
     pub success: bool,
 }
 
@@ -224,69 +579,304 @@ pub struct CreateEventInput {
 #[derive(InputObject)]
 #[graphql(name = "FindEventInput")]
 pub struct FindEventInput {
-    pub id: Option<ID>,
+    pub id: Option<EventId>,
     pub name: Option<String>,
     pub version: Option<String>,
     pub release: Option<String>,
     pub platform_id: Option<String>,
     pub package: Option<String>,
     pub success: Option<bool>,
-    pub event_receiver_id: Option<ID>,
+    pub event_receiver_id: Option<EventReceiverId>,
 }
 
 /// GraphQL type for Event
 #[derive(SimpleObject)]
 #[graphql(name = "Event")]
 pub struct EventType {
-    pub id: ID,
+    pub id: EventId,
     pub name: String,
     pub version: String,
     pub release: String,
     pub platform_id: String,
     pub package: String,
     pub description: String,
+    /// Event payload, or `{"_redacted": true}` for callers not allowed to read it
     pub payload: JSON,
-    pub event_receiver_id: ID,
+    /// Serialized size of a redacted payload
+    pub payload_size_bytes: Option<u64>,
+    pub event_receiver_id: EventReceiverId,
     pub success: bool,
     pub created_at: Time,
+    /// `user` for ingested events, `system` for events xzepr emits itself
+    pub origin: String,
+}
+
+impl EventType {
+    /// Builds the type, redacting the payload unless `access` allows it
+    pub fn for_caller(event: Event, access: &FieldAccess) -> Self {
+        let (payload, payload_size_bytes) = if access.can_read_payload(event.owner_id()) {
+            (event.payload().clone(), None)
+        } else {
+            let size = match event.payload_summary() {
+                Some(summary) => summary.size_bytes,
+                None => event.payload().to_string().len() as u64,
+            };
+            (redacted_payload(), Some(size))
+        };
+
+        Self {
+            id: event.id(),
+            name: event.name().to_string(),
+            version: event.version().to_string(),
+            release: event.release().to_string(),
+            platform_id: event.platform_id().to_string(),
+            package: event.package().to_string(),
+            description: event.description().to_string(),
+            payload: JSON(payload),
+            payload_size_bytes,
+            event_receiver_id: event.event_receiver_id(),
+            success: event.success(),
+            created_at: Time(event.created_at()),
+            origin: event.origin().to_string(),
+        }
+    }
 }
 
-/// Helper functions for ID parsing
-pub fn parse_event_receiver_id(id: &ID) -> Result<EventReceiverId, Error> {
-    EventReceiverId::parse(&id.0).map_err(|e| Error::new(format!("Invalid EventReceiverId: {}", e)))
+/// GraphQL type for a group member
+///
+/// `username` and `email` are resolved through the user repository and are
+/// null for users it does not know.
+#[derive(Debug, Clone, SimpleObject)]
+#[graphql(name = "Member", complex)]
+pub struct MemberType {
+    pub user_id: UserId,
+    pub added_at: Time,
+    pub added_by: UserId,
+    /// When the membership ends; null if it lasts until removed
+    pub expires_at: Option<Time>,
 }
 
-pub fn parse_event_receiver_group_id(id: &ID) -> Result<EventReceiverGroupId, Error> {
-    EventReceiverGroupId::parse(&id.0)
-        .map_err(|e| Error::new(format!("Invalid EventReceiverGroupId: {}", e)))
+impl From<GroupMembership> for MemberType {
+    fn from(membership: GroupMembership) -> Self {
+        Self {
+            user_id: membership.user_id,
+            added_at: Time(membership.added_at),
+            added_by: membership.added_by,
+            expires_at: membership.expires_at.map(Time),
+        }
+    }
 }
 
-pub fn parse_event_receiver_ids(ids: &[ID]) -> Result<Vec<EventReceiverId>, Error> {
-    ids.iter()
-        .map(parse_event_receiver_id)
-        .collect::<Result<Vec<_>, _>>()
+/// Page of group members in the order they were added
+#[derive(SimpleObject)]
+pub struct MemberConnection {
+    pub nodes: Vec<MemberType>,
+    /// Members of the group across all pages
+    pub total_count: usize,
+    /// Cursor of the last member on the page; pass it as `after` for the
+    /// next page
+    pub end_cursor: Option<String>,
+    pub has_next_page: bool,
 }
 
-pub fn parse_user_id(id: &ID) -> Result<UserId, Error> {
-    UserId::parse(&id.0).map_err(|e| Error::new(format!("Invalid UserId: {}", e)))
+/// Kind of a receiver timeline entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[graphql(name = "TimelineEntryKind")]
+pub enum TimelineEntryKindType {
+    Event,
+    ConfigChange,
+    GroupMembership,
 }
 
-/// GraphQL type for a group member
-#[derive(Debug, Clone, SimpleObject)]
-pub struct GroupMemberType {
-    pub user_id: ID,
-    pub username: String,
-    pub email: String,
-    pub added_at: Time,
-    pub added_by: ID,
+/// Entry of a receiver timeline
+///
+/// Fields that do not apply to the entry's `kind` are null.
+#[derive(SimpleObject)]
+#[graphql(name = "TimelineEntry")]
+pub struct TimelineEntryType {
+    pub kind: TimelineEntryKindType,
+    pub at: Time,
+    pub event_id: Option<EventId>,
+    pub name: Option<String>,
+    pub success: Option<bool>,
+    /// Payload severity of an event, null when unset
+    pub severity: Option<String>,
+    /// `created`, `updated`, or `deleted` for configuration changes;
+    /// `added` or `removed` for group membership changes
+    pub action: Option<String>,
+    /// Fields a configuration update changed
+    pub changed_fields: Option<Vec<String>>,
+    pub group_id: Option<EventReceiverGroupId>,
+    pub group_name: Option<String>,
+}
+
+impl From<TimelineEntry> for TimelineEntryType {
+    fn from(entry: TimelineEntry) -> Self {
+        let at = entry.at();
+        let blank = |kind| Self {
+            kind,
+            at: Time(at),
+            event_id: None,
+            name: None,
+            success: None,
+            severity: None,
+            action: None,
+            changed_fields: None,
+            group_id: None,
+            group_name: None,
+        };
+        match entry.detail {
+            TimelineDetail::Event {
+                id,
+                name,
+                success,
+                severity,
+            } => Self {
+                event_id: Some(id),
+                name: Some(name),
+                success: Some(success),
+                severity: (!severity.is_empty()).then_some(severity),
+                ..blank(TimelineEntryKindType::Event)
+            },
+            TimelineDetail::ConfigChange {
+                action,
+                changed_fields,
+            } => Self {
+                action: Some(
+                    match action {
+                        ConfigChangeAction::Created => "created",
+                        ConfigChangeAction::Updated => "updated",
+                        ConfigChangeAction::Deleted => "deleted",
+                    }
+                    .to_string(),
+                ),
+                changed_fields: Some(changed_fields),
+                ..blank(TimelineEntryKindType::ConfigChange)
+            },
+            TimelineDetail::GroupMembership {
+                group_id,
+                group_name,
+                action,
+            } => Self {
+                action: Some(
+                    match action {
+                        MembershipAction::Added => "added",
+                        MembershipAction::Removed => "removed",
+                    }
+                    .to_string(),
+                ),
+                group_id: Some(group_id),
+                group_name: Some(group_name),
+                ..blank(TimelineEntryKindType::GroupMembership)
+            },
+        }
+    }
+}
+
+/// Page of a receiver timeline, oldest first
+#[derive(SimpleObject)]
+pub struct TimelineConnection {
+    pub nodes: Vec<TimelineEntryType>,
+    /// Cursor of the last entry on the page; pass it as `after` for the
+    /// next page
+    pub end_cursor: Option<String>,
+    pub has_next_page: bool,
+}
+
+/// Whether a receiver reported a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[graphql(name = "ReceiverReleaseStatus")]
+pub enum ReceiverReleaseStatusType {
+    /// At least one successful event
+    Complete,
+    /// Only failed events
+    FailedOnly,
+    /// No events
+    Missing,
+}
+
+impl From<ReceiverReleaseStatus> for ReceiverReleaseStatusType {
+    fn from(status: ReceiverReleaseStatus) -> Self {
+        match status {
+            ReceiverReleaseStatus::Complete => Self::Complete,
+            ReceiverReleaseStatus::FailedOnly => Self::FailedOnly,
+            ReceiverReleaseStatus::Missing => Self::Missing,
+        }
+    }
+}
+
+/// Release status of one member receiver
+#[derive(SimpleObject)]
+#[graphql(name = "ReceiverCompleteness")]
+pub struct ReceiverCompletenessType {
+    pub receiver_id: EventReceiverId,
+    /// Optional receivers never block completeness
+    pub required: bool,
+    pub status: ReceiverReleaseStatusType,
+}
+
+impl From<ReceiverCompleteness> for ReceiverCompletenessType {
+    fn from(receiver: ReceiverCompleteness) -> Self {
+        Self {
+            receiver_id: receiver.receiver_id,
+            required: receiver.required,
+            status: receiver.status.into(),
+        }
+    }
+}
+
+/// Whether every required receiver of a group reported a release
+#[derive(SimpleObject)]
+#[graphql(name = "GroupCompleteness")]
+pub struct GroupCompletenessType {
+    pub release: String,
+    pub since: Option<Time>,
+    pub complete: bool,
+    /// Member receivers in group order
+    pub receivers: Vec<ReceiverCompletenessType>,
+}
+
+impl From<GroupCompleteness> for GroupCompletenessType {
+    fn from(completeness: GroupCompleteness) -> Self {
+        Self {
+            release: completeness.release,
+            since: completeness.since.map(Time),
+            complete: completeness.complete,
+            receivers: completeness.receivers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// The calling user
+#[derive(SimpleObject)]
+#[graphql(name = "Me", complex)]
+pub struct MeType {
+    pub user_id: UserId,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain::entities::event_receiver::EventReceiver;
+    use crate::domain::repositories::pagination::GroupSortField;
     use serde_json::json;
 
+    #[test]
+    fn test_time_scalar_requires_offset_and_outputs_utc() {
+        let time =
+            <Time as ScalarType>::parse(Value::String("2024-01-05T12:00:00+02:00".to_string()))
+                .unwrap();
+        assert_eq!(
+            <Time as ScalarType>::to_value(&time),
+            Value::String("2024-01-05T10:00:00Z".to_string())
+        );
+
+        let error = <Time as ScalarType>::parse(Value::String("2024-01-05 10:00:00".to_string()))
+            .unwrap_err()
+            .into_server_error(Default::default());
+        assert!(error.message.contains("offset"), "{}", error.message);
+    }
+
     #[test]
     fn test_event_receiver_type_conversion() {
         let schema = json!({
@@ -306,38 +896,75 @@ mod tests {
         )
         .unwrap();
 
-        let graphql_type: EventReceiverType = receiver.into();
+        let owner = crate::api::middleware::jwt::AuthenticatedUser::new(
+            crate::auth::jwt::claims::Claims::new_access_token(
+                receiver.owner_id().to_string(),
+                vec!["user".to_string()],
+                vec![],
+                "xzepr".to_string(),
+                "xzepr-api".to_string(),
+                chrono::Duration::minutes(15),
+            ),
+        );
+        let graphql_type =
+            EventReceiverType::for_caller(receiver, &FieldAccess::for_user(Some(&owner)));
 
         assert_eq!(graphql_type.name, "Test Receiver");
         assert_eq!(graphql_type.receiver_type, "webhook");
         assert_eq!(graphql_type.version, "1.0.0");
         assert_eq!(graphql_type.description, "A test receiver");
-        assert_eq!(graphql_type.schema.0, schema);
+        assert_eq!(graphql_type.schema.unwrap().0, schema);
     }
 
     #[test]
-    fn test_id_parsing() {
-        let receiver_id = EventReceiverId::new();
-        let id = ID(receiver_id.to_string());
+    fn test_find_input_into_criteria() {
+        let owner_id = UserId::new();
+        let (criteria, page) = FindEventReceiverGroupInput {
+            name: Some("ci".to_string()),
+            enabled: Some(false),
+            owner_id: Some(owner_id),
+            limit: Some(20),
+            offset: 40,
+            sort: ListOrderInput::NameAsc,
+            ..Default::default()
+        }
+        .into_criteria()
+        .unwrap();
 
-        let parsed = parse_event_receiver_id(&id).unwrap();
-        assert_eq!(parsed, receiver_id);
+        assert_eq!(criteria.name.as_deref(), Some("ci"));
+        assert_eq!(criteria.enabled, Some(false));
+        assert_eq!(criteria.owner_id, Some(owner_id));
+        assert_eq!(criteria.order, ListOrder::asc(GroupSortField::Name));
+        assert_eq!(criteria.limit, None);
+        assert_eq!(page, PaginationParams::new(Some(20), 40));
+
+        let too_large = FindEventReceiverInput {
+            limit: Some(1001),
+            ..Default::default()
+        };
+        assert!(too_large.into_criteria().is_err());
     }
 
     #[test]
-    fn test_invalid_id_parsing() {
-        let invalid_id = ID("invalid-uuid".to_string());
-        let result = parse_event_receiver_id(&invalid_id);
-        assert!(result.is_err());
+    fn test_id_input_parsing() {
+        let receiver_id = EventReceiverId::new();
+        let value = Value::String(receiver_id.to_string());
+
+        let parsed = <EventReceiverId as InputType>::parse(Some(value)).unwrap();
+        assert_eq!(parsed, receiver_id);
+        assert_eq!(
+            <EventReceiverId as InputType>::type_name(),
+            <ID as InputType>::type_name()
+        );
     }
 
     #[test]
-    fn test_multiple_id_parsing() {
-        let receiver_ids = vec![EventReceiverId::new(), EventReceiverId::new()];
-        let graphql_ids: Vec<ID> = receiver_ids.iter().map(|id| ID(id.to_string())).collect();
+    fn test_invalid_id_input_parsing() {
+        let lowercase = EventReceiverId::new().to_string().to_lowercase();
 
-        let parsed = parse_event_receiver_ids(&graphql_ids).unwrap();
-        assert_eq!(parsed.len(), 2);
-        assert_eq!(parsed, receiver_ids);
+        for invalid in ["invalid-uuid".to_string(), lowercase] {
+            let result = <EventReceiverId as InputType>::parse(Some(Value::String(invalid)));
+            assert!(result.is_err());
+        }
     }
 }
@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! API Key Authentication Middleware
+//!
+//! Authenticates requests that carry an `X-API-Key` header. The key's user
+//! is inserted as an [`AuthenticatedUser`] so downstream RBAC and handlers
+//! treat it like a bearer token, and the key's scope is inserted as an
+//! [`ApiKeyPrincipal`] for handlers that must enforce it.
+//!
+//! When the service tracks usage, the outcome of every request made with a
+//! key is counted once the response is known.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::api::middleware::client_ip::ClientIp;
+use crate::api::middleware::jwt::{AuthError, AuthenticatedUser};
+use crate::auth::api_key::{ApiKey, ApiKeyRequest, ApiKeyScope, ApiKeyService};
+use crate::auth::api_key_usage::ApiKeyOutcome;
+use crate::auth::jwt::Claims;
+use crate::auth::rbac::Permission;
+use crate::domain::entities::user::User;
+use crate::domain::value_objects::ApiKeyId;
+
+/// Header carrying the API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// The API key that authenticated a request
+#[derive(Debug, Clone)]
+pub struct ApiKeyPrincipal {
+    /// Key that authenticated the request
+    pub key_id: ApiKeyId,
+    /// Receivers the key may post events to
+    pub scope: ApiKeyScope,
+}
+
+impl From<&ApiKey> for ApiKeyPrincipal {
+    fn from(api_key: &ApiKey) -> Self {
+        Self {
+            key_id: *api_key.id(),
+            scope: api_key.scope(),
+        }
+    }
+}
+
+/// API key authentication middleware
+///
+/// Requests without an `X-API-Key` header pass through untouched so a
+/// later JWT layer can authenticate them. A key that fails to verify is
+/// rejected with `401 Unauthorized`.
+///
+/// Scoped keys only carry the `EventCreate` permission and no roles, and
+/// the RBAC middleware refuses them every route but ingestion, so they
+/// cannot reach anything beyond posting events.
+pub async fn api_key_auth_middleware(
+    State(api_key_service): State<Arc<ApiKeyService>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AuthError> {
+    let Some(value) = request.headers().get(API_KEY_HEADER) else {
+        return Ok(next.run(request).await);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| AuthError::InvalidToken("API key is not valid text".to_string()))?;
+
+    let key_request = api_key_request(&request);
+    let (user, api_key) = api_key_service
+        .authenticate_request(key, &key_request)
+        .await
+        .map_err(|e| {
+            warn!("API key authentication failed: {}", e);
+            AuthError::InvalidToken("API key rejected".to_string())
+        })?;
+
+    request
+        .extensions_mut()
+        .insert(AuthenticatedUser::new(api_key_claims(&user, &api_key)));
+    request
+        .extensions_mut()
+        .insert(ApiKeyPrincipal::from(&api_key));
+
+    let response = next.run(request).await;
+    if let Some(usage) = api_key_service.usage_tracker() {
+        let outcome = ApiKeyOutcome::from_status(response.status().as_u16());
+        let reason = (outcome == ApiKeyOutcome::AuthzDenied).then_some("forbidden");
+        usage.record_outcome(api_key.id, outcome, reason, &key_request.route, Utc::now());
+    }
+
+    Ok(response)
+}
+
+/// Describes where a key is presented: the matched route, or the path when
+/// no route matched, and the client address
+fn api_key_request(request: &Request) -> ApiKeyRequest {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let ip = request
+        .extensions()
+        .get::<ClientIp>()
+        .map(|ClientIp(ip)| ip.to_string());
+
+    ApiKeyRequest { route, ip }
+}
+
+/// Builds request-scoped claims for a key's user
+fn api_key_claims(user: &User, api_key: &ApiKey) -> Claims {
+    let scoped = api_key.scope() != ApiKeyScope::Unscoped;
+
+    let roles = if scoped {
+        Vec::new()
+    } else {
+        user.roles().iter().map(ToString::to_string).collect()
+    };
+
+    let permissions = user
+        .roles()
+        .iter()
+        .flat_map(|role| role.permissions())
+        .filter(|permission| !scoped || *permission == Permission::EventCreate)
+        .map(|permission| format!("{:?}", permission))
+        .collect();
+
+    Claims::new_access_token(
+        user.id().to_string(),
+        roles,
+        permissions,
+        "xzepr".to_string(),
+        "xzepr-api".to_string(),
+        Duration::minutes(5),
+    )
+}
@@ -0,0 +1,369 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/middleware/body_encoding.rs
+
+//! MessagePack and CBOR bodies for event ingestion
+//!
+//! Producers may post events as `application/msgpack` or `application/cbor`
+//! instead of JSON. The body is decoded and handed to the handler as the
+//! equivalent JSON document, so it runs through the same extraction and
+//! validation and its payload is stored exactly as if it had been posted as
+//! JSON. The size limit applies to that JSON document. Successful responses
+//! are encoded as MessagePack or CBOR when the `Accept` header prefers it;
+//! errors stay JSON so they are localized and formatted like every other
+//! error.
+//!
+//! Each codec sits behind a cargo feature, `msgpack` and `cbor`, both on by
+//! default. Without them the middleware passes every request through.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde_json::Value;
+
+use crate::api::middleware::validation::DEFAULT_MAX_BODY_SIZE;
+use crate::api::rest::dtos::ErrorResponse;
+
+/// Error code of binary bodies that cannot be decoded
+pub const MALFORMED_BODY_CODE: &str = "MALFORMED_BODY";
+
+/// Media type of MessagePack bodies
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Media type of CBOR bodies
+pub const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+/// Largest successful response re-encoded for the client
+const MAX_ENCODED_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Encoding of a request or response body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    Json,
+    #[cfg(feature = "msgpack")]
+    MessagePack,
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl BodyFormat {
+    /// Returns the binary format of a `Content-Type`, if it names one
+    ///
+    /// Parameters are ignored. Formats whose feature is disabled are not
+    /// recognized.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match media_type.as_str() {
+            #[cfg(feature = "msgpack")]
+            MSGPACK_CONTENT_TYPE => Some(BodyFormat::MessagePack),
+            #[cfg(feature = "cbor")]
+            CBOR_CONTENT_TYPE => Some(BodyFormat::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Returns the format a client prefers in its `Accept` header
+    ///
+    /// The range with the highest quality wins, the earlier one on a tie.
+    /// Anything other than an exact binary media type, such as `*/*`, means
+    /// JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xzepr::api::middleware::body_encoding::BodyFormat;
+    ///
+    /// assert_eq!(BodyFormat::preferred("application/json"), BodyFormat::Json);
+    /// assert_eq!(BodyFormat::preferred("*/*"), BodyFormat::Json);
+    /// ```
+    pub fn preferred(accept: &str) -> Self {
+        let mut best: Option<(f32, &str)> = None;
+        for range in accept.split(',') {
+            let mut params = range.split(';');
+            let media_type = params.next().unwrap_or_default().trim();
+            if media_type.is_empty() {
+                continue;
+            }
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality > 0.0 && best.is_none_or(|(best, _)| quality > best) {
+                best = Some((quality, media_type));
+            }
+        }
+
+        best.and_then(|(_, media_type)| Self::from_content_type(media_type))
+            .unwrap_or(BodyFormat::Json)
+    }
+
+    /// Returns the media type of the format
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            BodyFormat::Json => "application/json",
+            #[cfg(feature = "msgpack")]
+            BodyFormat::MessagePack => MSGPACK_CONTENT_TYPE,
+            #[cfg(feature = "cbor")]
+            BodyFormat::Cbor => CBOR_CONTENT_TYPE,
+        }
+    }
+
+    /// Returns the name of the format used in error messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            BodyFormat::Json => "JSON",
+            #[cfg(feature = "msgpack")]
+            BodyFormat::MessagePack => "MessagePack",
+            #[cfg(feature = "cbor")]
+            BodyFormat::Cbor => "CBOR",
+        }
+    }
+
+    /// Decodes a body into a JSON document
+    ///
+    /// Values without a JSON equivalent, such as byte strings or maps with
+    /// non-string keys, and trailing bytes after the first value are errors.
+    pub fn decode(&self, bytes: &[u8]) -> Result<Value, String> {
+        match self {
+            BodyFormat::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            #[cfg(feature = "msgpack")]
+            BodyFormat::MessagePack => {
+                let mut reader = bytes;
+                let value = rmp_serde::from_read(&mut reader).map_err(|e| e.to_string())?;
+                whole_body(value, reader)
+            }
+            #[cfg(feature = "cbor")]
+            BodyFormat::Cbor => {
+                let mut reader = bytes;
+                let value = ciborium::from_reader(&mut reader).map_err(|e| e.to_string())?;
+                whole_body(value, reader)
+            }
+        }
+    }
+
+    /// Encodes a JSON document in the format
+    pub fn encode(&self, value: &Value) -> Result<Vec<u8>, String> {
+        match self {
+            BodyFormat::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            #[cfg(feature = "msgpack")]
+            BodyFormat::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+            #[cfg(feature = "cbor")]
+            BodyFormat::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|e| e.to_string())?;
+                Ok(bytes)
+            }
+        }
+    }
+}
+
+/// Returns `value` if nothing follows it in the body
+#[cfg(any(feature = "msgpack", feature = "cbor"))]
+fn whole_body(value: Value, rest: &[u8]) -> Result<Value, String> {
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(format!("{} trailing bytes", rest.len()))
+    }
+}
+
+/// Middleware accepting MessagePack and CBOR bodies on a route
+///
+/// Layer it on the routes that accept binary bodies, inside
+/// authentication, so unauthenticated bodies are never decoded.
+pub async fn binary_body_middleware(request: Request, next: Next) -> Response {
+    let response_format = request
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map_or(BodyFormat::Json, BodyFormat::preferred);
+    let request_format = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(BodyFormat::from_content_type);
+
+    let request = match request_format {
+        Some(format) => match transcode_request(request, format).await {
+            Ok(request) => request,
+            Err(response) => return response,
+        },
+        None => request,
+    };
+
+    let response = next.run(request).await;
+    if response_format == BodyFormat::Json || !response.status().is_success() {
+        return response;
+    }
+    encode_response(response, response_format).await
+}
+
+/// Replaces a binary body with the equivalent JSON body
+async fn transcode_request(request: Request, format: BodyFormat) -> Result<Request, Response> {
+    let (mut parts, body) = request.into_parts();
+    let bytes = to_bytes(body, DEFAULT_MAX_BODY_SIZE)
+        .await
+        .map_err(|_| payload_too_large())?;
+
+    let json = format
+        .decode(&bytes)
+        .and_then(|value| BodyFormat::Json.encode(&value))
+        .map_err(|e| {
+            tracing::debug!(format = format.name(), error = %e, "Rejected malformed body");
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(
+                    MALFORMED_BODY_CODE.to_string(),
+                    format!("Malformed {} body: {}", format.name(), e),
+                )),
+            )
+                .into_response()
+        })?;
+    if json.len() > DEFAULT_MAX_BODY_SIZE {
+        return Err(payload_too_large());
+    }
+
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    parts
+        .headers
+        .insert(header::CONTENT_LENGTH, json.len().into());
+    Ok(Request::from_parts(parts, Body::from(json)))
+}
+
+/// Re-encodes a successful JSON response in `format`
+async fn encode_response(response: Response, format: BodyFormat) -> Response {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, MAX_ENCODED_RESPONSE_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to buffer response for encoding: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let encoded = match serde_json::from_slice::<Value>(&bytes)
+        .map_err(|e| e.to_string())
+        .and_then(|value| format.encode(&value))
+    {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            tracing::warn!(format = format.name(), "Failed to encode response: {}", e);
+            return Response::from_parts(parts, Body::from(bytes));
+        }
+    };
+
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    parts
+        .headers
+        .insert(header::CONTENT_LENGTH, encoded.len().into());
+    parts
+        .headers
+        .append(header::VARY, HeaderValue::from_static("accept"));
+    Response::from_parts(parts, Body::from(encoded))
+}
+
+fn payload_too_large() -> Response {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(ErrorResponse::new(
+            "PAYLOAD_TOO_LARGE".to_string(),
+            format!(
+                "Decoded request body exceeds {} bytes",
+                DEFAULT_MAX_BODY_SIZE
+            ),
+        )),
+    )
+        .into_response()
+}
+
+#[cfg(all(test, feature = "msgpack", feature = "cbor"))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_preferred_format_follows_accept_quality() {
+        assert_eq!(
+            BodyFormat::preferred("application/msgpack"),
+            BodyFormat::MessagePack
+        );
+        assert_eq!(
+            BodyFormat::preferred("application/json;q=0.5, application/cbor"),
+            BodyFormat::Cbor
+        );
+        assert_eq!(
+            BodyFormat::preferred("application/json, application/msgpack"),
+            BodyFormat::Json
+        );
+        assert_eq!(
+            BodyFormat::preferred("application/msgpack;q=0, */*"),
+            BodyFormat::Json
+        );
+        assert_eq!(BodyFormat::preferred(""), BodyFormat::Json);
+    }
+
+    #[test]
+    fn test_binary_formats_round_trip_json_documents() {
+        let document = json!({
+            "name": "build",
+            "success": true,
+            "payload": {"count": 3, "ratio": 0.5, "tags": ["a", "b"], "none": null}
+        });
+
+        for format in [BodyFormat::MessagePack, BodyFormat::Cbor] {
+            let bytes = format.encode(&document).unwrap();
+            assert_eq!(
+                format.decode(&bytes).unwrap(),
+                document,
+                "{}",
+                format.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_values_without_json_equivalent_are_malformed() {
+        // A byte string, a map with an integer key, trailing bytes, and a
+        // truncated value
+        assert!(BodyFormat::MessagePack
+            .decode(&[0xc4, 0x03, 1, 2, 3])
+            .is_err());
+        assert!(BodyFormat::Cbor.decode(&[0xa1, 0x01, 0x61, b'a']).is_err());
+
+        let mut bytes = BodyFormat::MessagePack.encode(&json!({"a": 1})).unwrap();
+        bytes.push(0xc0);
+        assert!(BodyFormat::MessagePack
+            .decode(&bytes)
+            .unwrap_err()
+            .contains("trailing"));
+
+        let bytes = BodyFormat::Cbor
+            .encode(&json!({"a": "long value"}))
+            .unwrap();
+        assert!(BodyFormat::Cbor.decode(&bytes[..bytes.len() - 3]).is_err());
+    }
+}
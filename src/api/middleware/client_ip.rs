@@ -0,0 +1,337 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/middleware/client_ip.rs
+
+//! Client IP resolution behind trusted reverse proxies
+//!
+//! `X-Forwarded-For` and `X-Real-IP` are client-controlled headers. They are
+//! only honored when proxy trust is enabled and the TCP peer is inside one of
+//! the configured trusted CIDR ranges; otherwise the peer address is used.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+
+/// An IP network in CIDR notation (e.g. `10.0.0.0/8` or `fd00::/8`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpCidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpCidr {
+    /// Creates a CIDR block, masking any host bits in `addr`
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Self, String> {
+        let max = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_len > max {
+            return Err(format!(
+                "Prefix length {} exceeds maximum of {} for {}",
+                prefix_len, max, addr
+            ));
+        }
+
+        let network = match addr {
+            IpAddr::V4(v4) => IpAddr::V4(Ipv4Addr::from(u32::from(v4) & v4_mask(prefix_len))),
+            IpAddr::V6(v6) => IpAddr::V6(Ipv6Addr::from(u128::from(v6) & v6_mask(prefix_len))),
+        };
+
+        Ok(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    /// Returns true if `ip` is inside this block
+    ///
+    /// IPv4-mapped IPv6 addresses are compared as IPv4.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, canonical(ip)) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                u32::from(ip) & v4_mask(self.prefix_len) == u32::from(net)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                u128::from(ip) & v6_mask(self.prefix_len) == u128::from(net)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpCidr {
+    type Err = String;
+
+    /// Parses `addr/prefix`; a bare address is treated as a single host
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+
+        let addr: IpAddr = addr
+            .parse()
+            .map_err(|_| format!("Invalid IP address in CIDR '{}'", s))?;
+        let prefix_len = match prefix {
+            Some(p) => p
+                .parse::<u8>()
+                .map_err(|_| format!("Invalid prefix length in CIDR '{}'", s))?,
+            None => match addr {
+                IpAddr::V4(_) => 32,
+                IpAddr::V6(_) => 128,
+            },
+        };
+
+        Self::new(addr, prefix_len)
+    }
+}
+
+impl fmt::Display for IpCidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+fn v4_mask(prefix_len: u8) -> u32 {
+    u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0)
+}
+
+fn v6_mask(prefix_len: u8) -> u128 {
+    u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0)
+}
+
+fn canonical(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6
+            .to_ipv4_mapped()
+            .map(IpAddr::V4)
+            .unwrap_or(IpAddr::V6(v6)),
+        v4 => v4,
+    }
+}
+
+/// Trusted proxy policy used to resolve the originating client address
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    enabled: bool,
+    cidrs: Vec<IpCidr>,
+}
+
+impl TrustedProxies {
+    /// Creates a policy from settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any entry in `cidrs` is not a valid CIDR block
+    pub fn new(trust_proxy: bool, cidrs: &[String]) -> Result<Self, String> {
+        let cidrs = cidrs
+            .iter()
+            .map(|c| c.parse())
+            .collect::<Result<Vec<IpCidr>, String>>()?;
+
+        Ok(Self {
+            enabled: trust_proxy,
+            cidrs,
+        })
+    }
+
+    /// Creates a policy that never trusts forwarding headers
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if forwarding headers from `peer` should be honored
+    pub fn is_trusted(&self, peer: IpAddr) -> bool {
+        self.enabled && self.cidrs.iter().any(|c| c.contains(peer))
+    }
+
+    /// Resolves the client address for a request received from `peer`
+    ///
+    /// When the peer is a trusted proxy, `X-Forwarded-For` is walked from
+    /// right to left and the first hop that is not itself a trusted proxy is
+    /// returned. Entries to the left of that hop are client-supplied and are
+    /// never used. `X-Real-IP` is consulted only when `X-Forwarded-For` is
+    /// absent.
+    pub fn resolve(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> Option<IpAddr> {
+        let peer = peer.map(canonical)?;
+        if !self.is_trusted(peer) {
+            return Some(peer);
+        }
+
+        let hops: Vec<IpAddr> = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(|hop| hop.trim().parse::<IpAddr>().ok())
+            .map(canonical)
+            .collect();
+
+        if hops.is_empty() {
+            return headers
+                .get("x-real-ip")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<IpAddr>().ok())
+                .map(canonical)
+                .or(Some(peer));
+        }
+
+        hops.iter()
+            .rev()
+            .find(|hop| !self.is_trusted(**hop))
+            .or_else(|| hops.first())
+            .copied()
+    }
+
+    /// Resolves the client address for an axum request
+    ///
+    /// The peer address comes from `ConnectInfo<SocketAddr>`, so the server
+    /// must be started with `into_make_service_with_connect_info`.
+    pub fn resolve_request(&self, request: &Request) -> Option<IpAddr> {
+        self.resolve(request.headers(), peer_addr(request))
+    }
+}
+
+/// Resolved client IP stored in request extensions
+///
+/// Inserted by [`client_ip_middleware`] so handlers can record where a
+/// request came from without re-parsing forwarding headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+/// Client IP middleware
+///
+/// Resolves the client address using the trusted proxy policy and stores
+/// it as a [`ClientIp`] extension for downstream handlers.
+pub async fn client_ip_middleware(
+    State(trusted_proxies): State<TrustedProxies>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if let Some(ip) = trusted_proxies.resolve_request(&request) {
+        request.extensions_mut().insert(ClientIp(ip));
+    }
+
+    next.run(request).await
+}
+
+/// Returns the TCP peer address recorded by axum, if available
+pub fn peer_addr(request: &Request) -> Option<IpAddr> {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn xff(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn trusted() -> TrustedProxies {
+        TrustedProxies::new(true, &["10.0.0.0/8".to_string(), "fd00::/8".to_string()]).unwrap()
+    }
+
+    #[test]
+    fn test_cidr_parse_and_contains() {
+        let cidr: IpCidr = "192.168.1.77/24".parse().unwrap();
+        assert_eq!(cidr.to_string(), "192.168.1.0/24");
+        assert!(cidr.contains(ip("192.168.1.1")));
+        assert!(!cidr.contains(ip("192.168.2.1")));
+        assert!(cidr.contains(ip("::ffff:192.168.1.9")));
+
+        let host: IpCidr = "203.0.113.5".parse().unwrap();
+        assert!(host.contains(ip("203.0.113.5")));
+        assert!(!host.contains(ip("203.0.113.6")));
+
+        let any: IpCidr = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains(ip("8.8.8.8")));
+        assert!(!any.contains(ip("2001:db8::1")));
+
+        let v6: IpCidr = "fd00::/8".parse().unwrap();
+        assert!(v6.contains(ip("fd12::1")));
+    }
+
+    #[test]
+    fn test_cidr_parse_errors() {
+        assert!("10.0.0.0/33".parse::<IpCidr>().is_err());
+        assert!("not-an-ip/8".parse::<IpCidr>().is_err());
+        assert!("10.0.0.0/abc".parse::<IpCidr>().is_err());
+        assert!(TrustedProxies::new(true, &["bogus".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_spoofed_xff_ignored_when_untrusted() {
+        let headers = xff("1.2.3.4");
+
+        // Trust disabled entirely
+        let policy = TrustedProxies::new(false, &["10.0.0.0/8".to_string()]).unwrap();
+        assert_eq!(
+            policy.resolve(&headers, Some(ip("10.1.1.1"))),
+            Some(ip("10.1.1.1"))
+        );
+
+        // Trust enabled but peer is not a trusted proxy
+        assert_eq!(
+            trusted().resolve(&headers, Some(ip("198.51.100.7"))),
+            Some(ip("198.51.100.7"))
+        );
+    }
+
+    #[test]
+    fn test_xff_respected_when_trusted() {
+        let policy = trusted();
+        assert_eq!(
+            policy.resolve(&xff("203.0.113.9"), Some(ip("10.0.0.2"))),
+            Some(ip("203.0.113.9"))
+        );
+
+        // Client-supplied hops left of the first untrusted hop are ignored
+        assert_eq!(
+            policy.resolve(&xff("1.2.3.4, 203.0.113.9, 10.0.0.3"), Some(ip("10.0.0.2"))),
+            Some(ip("203.0.113.9"))
+        );
+
+        // Garbage entries are skipped
+        assert_eq!(
+            policy.resolve(&xff("203.0.113.9, unknown"), Some(ip("fd00::1"))),
+            Some(ip("203.0.113.9"))
+        );
+    }
+
+    #[test]
+    fn test_real_ip_fallback_and_missing_peer() {
+        let policy = trusted();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", HeaderValue::from_static("203.0.113.10"));
+
+        assert_eq!(
+            policy.resolve(&headers, Some(ip("10.0.0.2"))),
+            Some(ip("203.0.113.10"))
+        );
+        assert_eq!(
+            policy.resolve(&HeaderMap::new(), Some(ip("10.0.0.2"))),
+            Some(ip("10.0.0.2"))
+        );
+        assert_eq!(policy.resolve(&headers, None), None);
+    }
+}
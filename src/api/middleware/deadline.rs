@@ -0,0 +1,404 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/middleware/deadline.rs
+
+//! Request deadlines from the route budget and `X-Request-Deadline`
+//!
+//! Every request gets a deadline: its route's budget from arrival, or the
+//! client's `X-Request-Deadline` when that is earlier. The header holds an
+//! RFC 3339 timestamp or a number of milliseconds from now. The deadline is
+//! stored in the request extensions and set for the handler with
+//! [`with_deadline`], so repository, Kafka, and outbound HTTP calls stop at
+//! it. A request whose deadline has already passed is answered at once,
+//! and one cut short by its deadline is answered with `504 Gateway Timeout`
+//! and the code `TIMEOUT`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::api::rest::dtos::ErrorResponse;
+use crate::infrastructure::config::DeadlineConfig;
+use crate::infrastructure::deadline::{with_deadline, DeadlineSource, RequestDeadline};
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+/// Request header carrying the client's deadline
+pub const REQUEST_DEADLINE_HEADER: &str = "x-request-deadline";
+
+/// Error code of requests cut short by their deadline
+pub const TIMEOUT_CODE: &str = "TIMEOUT";
+
+/// Error code of requests with an unreadable `X-Request-Deadline`
+pub const INVALID_DEADLINE_CODE: &str = "INVALID_DEADLINE";
+
+/// Route budgets that bound each request's deadline
+#[derive(Clone)]
+pub struct RequestDeadlines {
+    default_budget: Duration,
+    /// Budgets by path prefix, longest prefix first
+    route_budgets: Vec<(String, Duration)>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+}
+
+impl Default for RequestDeadlines {
+    fn default() -> Self {
+        Self::from_config(&DeadlineConfig::default())
+    }
+}
+
+impl RequestDeadlines {
+    /// Gives every route `default_budget`
+    pub fn new(default_budget: Duration) -> Self {
+        Self {
+            default_budget,
+            route_budgets: Vec::new(),
+            metrics: None,
+        }
+    }
+
+    /// Builds the budgets from the `deadlines` settings
+    pub fn from_config(config: &DeadlineConfig) -> Self {
+        let mut deadlines = Self::new(Duration::from_secs(config.default_budget_seconds));
+        for (prefix, seconds) in &config.route_budgets {
+            deadlines = deadlines.with_route_budget(prefix.clone(), Duration::from_secs(*seconds));
+        }
+        deadlines
+    }
+
+    /// Gives paths starting with `prefix` their own budget
+    pub fn with_route_budget(mut self, prefix: impl Into<String>, budget: Duration) -> Self {
+        let prefix = prefix.into();
+        self.route_budgets
+            .retain(|(existing, _)| *existing != prefix);
+        self.route_budgets.push((prefix, budget));
+        self.route_budgets
+            .sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        self
+    }
+
+    /// Counts aborted requests in `xzepr_request_timeouts_total`
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the budget of `path`
+    pub fn budget_for(&self, path: &str) -> Duration {
+        self.route_budgets
+            .iter()
+            .find(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .map_or(self.default_budget, |(_, budget)| *budget)
+    }
+
+    /// Returns the deadline of a request for `path` arriving now
+    ///
+    /// # Errors
+    ///
+    /// Returns a message for the client if `header` is neither an RFC 3339
+    /// timestamp nor a number of milliseconds.
+    pub fn deadline_for(
+        &self,
+        path: &str,
+        header: Option<&HeaderValue>,
+    ) -> Result<RequestDeadline, String> {
+        let now = Instant::now();
+        let budget_at = now + self.budget_for(path);
+        let client_at = header.map(|value| parse_deadline(value, now)).transpose()?;
+
+        Ok(match client_at {
+            Some(at) if at < budget_at => RequestDeadline::new(at, DeadlineSource::Client),
+            _ => RequestDeadline::new(budget_at, DeadlineSource::Server),
+        })
+    }
+
+    fn timeout_response(&self, deadline: &RequestDeadline, path: &str) -> Response {
+        let reason = deadline.source().timeout_reason();
+        warn!(
+            path,
+            reason,
+            operation = deadline.exceeded_during().unwrap_or("request"),
+            "Request aborted at its deadline"
+        );
+        if let Some(metrics) = &self.metrics {
+            metrics.record_request_timeout(reason);
+        }
+
+        let message = match deadline.source() {
+            DeadlineSource::Client => "The request deadline passed before the request completed",
+            DeadlineSource::Server => "The request did not complete within its time budget",
+        };
+        (
+            StatusCode::GATEWAY_TIMEOUT,
+            Json(ErrorResponse::new(
+                TIMEOUT_CODE.to_string(),
+                message.to_string(),
+            )),
+        )
+            .into_response()
+    }
+}
+
+/// Reads `X-Request-Deadline` as an instant
+///
+/// Timestamps in the past yield `now`, so the request is answered at once.
+fn parse_deadline(value: &HeaderValue, now: Instant) -> Result<Instant, String> {
+    let invalid = || {
+        format!(
+            "{} must be an RFC 3339 timestamp or a number of milliseconds",
+            REQUEST_DEADLINE_HEADER
+        )
+    };
+    let value = value.to_str().map_err(|_| invalid())?.trim();
+
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        let millis: u64 = value.parse().map_err(|_| invalid())?;
+        return Ok(now + Duration::from_millis(millis));
+    }
+
+    let at = DateTime::parse_from_rfc3339(value).map_err(|_| invalid())?;
+    let remaining = (at.with_timezone(&Utc) - Utc::now())
+        .to_std()
+        .unwrap_or(Duration::ZERO);
+    Ok(now + remaining)
+}
+
+/// Middleware setting each request's deadline
+///
+/// Layer it inside the error localization and problem details middleware
+/// so timeouts are formatted like every other error, and outside
+/// authentication so its lookups are bounded too.
+pub async fn deadline_middleware(
+    State(deadlines): State<RequestDeadlines>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let deadline =
+        match deadlines.deadline_for(&path, request.headers().get(REQUEST_DEADLINE_HEADER)) {
+            Ok(deadline) => deadline,
+            Err(message) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::new(
+                        INVALID_DEADLINE_CODE.to_string(),
+                        message,
+                    )),
+                )
+                    .into_response()
+            }
+        };
+    if deadline.has_passed() {
+        return deadlines.timeout_response(&deadline, &path);
+    }
+
+    request.extensions_mut().insert(deadline.clone());
+    let response = with_deadline(Some(deadline.clone()), next.run(request)).await;
+
+    // A server error after the deadline is taken to be caused by it, as
+    // outbound HTTP calls fail with their own timeout errors
+    let cut_short = deadline.exceeded_during().is_some()
+        || (response.status().is_server_error() && deadline.has_passed());
+    if cut_short {
+        return deadlines.timeout_response(&deadline, &path);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::deadline::guard;
+    use axum::{body::Body, middleware, routing::get, Extension, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    /// Router whose handler counts the queries it starts
+    fn router(deadlines: RequestDeadlines, queries: Arc<AtomicUsize>) -> Router {
+        let query = move || {
+            let queries = queries.clone();
+            async move {
+                guard("test.query", async {
+                    queries.fetch_add(1, Ordering::SeqCst);
+                })
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        };
+        Router::new()
+            .route("/api/v1/events", get(query))
+            .route(
+                "/api/v1/events/poll",
+                get(
+                    |Extension(deadline): Extension<RequestDeadline>| async move {
+                        deadline.remaining().as_secs().to_string()
+                    },
+                ),
+            )
+            .route(
+                "/api/v1/slow",
+                get(|| async {
+                    guard("test.sleep", tokio::time::sleep(Duration::from_secs(10)))
+                        .await
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                deadlines,
+                deadline_middleware,
+            ))
+    }
+
+    async fn send(router: Router, uri: &str, deadline: Option<&str>) -> Response {
+        let mut request = Request::builder().uri(uri);
+        if let Some(deadline) = deadline {
+            request = request.header(REQUEST_DEADLINE_HEADER, deadline);
+        }
+        router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn body(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    fn deadlines() -> RequestDeadlines {
+        RequestDeadlines::new(Duration::from_secs(30))
+            .with_route_budget("/api/v1/events/poll", Duration::from_secs(60))
+    }
+
+    #[tokio::test]
+    async fn test_expired_deadline_short_circuits_before_any_query() {
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let queries = Arc::new(AtomicUsize::new(0));
+        let router = router(deadlines().with_metrics(metrics.clone()), queries.clone());
+
+        let expired = (Utc::now() - chrono::Duration::seconds(1)).to_rfc3339();
+        let response = send(router.clone(), "/api/v1/events", Some(&expired)).await;
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(body(response).await["error"], TIMEOUT_CODE);
+
+        let response = send(router, "/api/v1/events", Some("0")).await;
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        assert_eq!(queries.load(Ordering::SeqCst), 0);
+        assert!(metrics
+            .gather()
+            .unwrap()
+            .contains("xzepr_request_timeouts_total{reason=\"deadline_exceeded\"} 2"));
+    }
+
+    #[tokio::test]
+    async fn test_tight_deadline_cancels_a_running_operation() {
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let router = router(
+            deadlines().with_metrics(metrics.clone()),
+            Arc::new(AtomicUsize::new(0)),
+        );
+        let started = std::time::Instant::now();
+
+        let response = send(router, "/api/v1/slow", Some("100")).await;
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(metrics
+            .gather()
+            .unwrap()
+            .contains("xzepr_request_timeouts_total{reason=\"deadline_exceeded\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn test_route_budget_is_a_server_timeout() {
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let router = router(
+            RequestDeadlines::new(Duration::from_millis(100)).with_metrics(metrics.clone()),
+            Arc::new(AtomicUsize::new(0)),
+        );
+
+        // A later client deadline does not extend the budget
+        let response = send(router, "/api/v1/slow", Some("60000")).await;
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert!(metrics
+            .gather()
+            .unwrap()
+            .contains("xzepr_request_timeouts_total{reason=\"server_timeout\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn test_absent_header_falls_back_to_route_budgets() {
+        let queries = Arc::new(AtomicUsize::new(0));
+        let router = router(deadlines(), queries.clone());
+
+        let response = send(router.clone(), "/api/v1/events", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+
+        let response = send(router, "/api/v1/events/poll", None).await;
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let remaining: u64 = std::str::from_utf8(&bytes).unwrap().parse().unwrap();
+        assert!((58..=60).contains(&remaining), "{}", remaining);
+    }
+
+    #[test]
+    fn test_deadline_for_caps_the_client_deadline() {
+        let deadlines = deadlines();
+        assert_eq!(
+            deadlines.budget_for("/api/v1/events/poll?cursor=1"),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            deadlines.budget_for("/api/v1/receivers"),
+            Duration::from_secs(30)
+        );
+
+        let header = HeaderValue::from_static("1500");
+        let deadline = deadlines
+            .deadline_for("/api/v1/events", Some(&header))
+            .unwrap();
+        assert_eq!(deadline.source(), DeadlineSource::Client);
+        assert!(deadline.remaining() <= Duration::from_millis(1500));
+
+        let later = (Utc::now() + chrono::Duration::minutes(5)).to_rfc3339();
+        let header = HeaderValue::from_str(&later).unwrap();
+        let deadline = deadlines
+            .deadline_for("/api/v1/events", Some(&header))
+            .unwrap();
+        assert_eq!(deadline.source(), DeadlineSource::Server);
+        assert!(deadline.remaining() <= Duration::from_secs(30));
+
+        let header = HeaderValue::from_static("soon");
+        assert!(deadlines
+            .deadline_for("/api/v1/events", Some(&header))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_header_is_rejected() {
+        let response = send(
+            router(deadlines(), Arc::new(AtomicUsize::new(0))),
+            "/api/v1/events",
+            Some("tomorrow"),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body(response).await["error"], INVALID_DEADLINE_CODE);
+    }
+}
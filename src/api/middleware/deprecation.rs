@@ -0,0 +1,652 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/middleware/deprecation.rs
+
+//! Deprecated API behavior and its sunset
+//!
+//! Deprecations are declared as constants next to the handlers or
+//! middleware that serve the deprecated behavior and listed in
+//! [`DEPRECATIONS`]. Responses that rely on one carry `Deprecation`,
+//! `Sunset`, and `Link` headers, and every use is counted per principal so
+//! administrators can see who still depends on it. After its sunset a
+//! deprecated route or parameter is answered with `410 Gone` and the code
+//! `DEPRECATED_REMOVED`, unless re-enabled in `api.deprecations`.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::api::middleware::api_key::ApiKeyPrincipal;
+use crate::api::middleware::client_ip::ClientIp;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::middleware::problem::LEGACY_ERROR_FORMAT;
+use crate::api::rest::dtos::{ErrorResponse, OFFSET_PAGINATION};
+use crate::infrastructure::config::{ApiConfig, DeprecationSettings, ErrorFormat};
+
+/// Error code of requests for deprecated behavior past its sunset
+pub const DEPRECATED_REMOVED_CODE: &str = "DEPRECATED_REMOVED";
+
+/// Every registered deprecation
+pub static DEPRECATIONS: &[&Deprecation] = &[&LEGACY_ERROR_FORMAT, &OFFSET_PAGINATION];
+
+/// Most principals counted per deprecation; later principals are counted
+/// under [`OTHER_PRINCIPALS`]
+const MAX_PRINCIPALS: usize = 10_000;
+
+/// Principal that uses past [`MAX_PRINCIPALS`] are counted under
+const OTHER_PRINCIPALS: &str = "other";
+
+/// A deprecated route, query parameter, or response mode
+#[derive(Debug)]
+pub struct Deprecation {
+    /// Stable identifier, also the key of its `api.deprecations` settings
+    pub id: &'static str,
+    /// What is deprecated and what replaces it
+    pub summary: &'static str,
+    /// Requests or responses that rely on the deprecated behavior
+    pub usage: DeprecatedUsage,
+    /// Day the deprecation was announced, as `YYYY-MM-DD`
+    pub deprecated_on: &'static str,
+    /// Last day the behavior is served, as `YYYY-MM-DD`
+    pub sunset: &'static str,
+    /// Migration guide
+    pub link: &'static str,
+}
+
+/// How requests or responses rely on a deprecated behavior
+#[derive(Debug)]
+pub enum DeprecatedUsage {
+    /// Any request to one of the routes
+    Route { paths: &'static [&'static str] },
+    /// Requests to one of `paths` that send the query parameter `name`
+    QueryParameter {
+        name: &'static str,
+        paths: &'static [&'static str],
+    },
+    /// Error responses while the error format is configured
+    ErrorFormat(ErrorFormat),
+}
+
+impl DeprecatedUsage {
+    /// Returns true if a request to `path` with `query` relies on the
+    /// deprecated behavior
+    fn matches_request(&self, path: &str, query: Option<&str>) -> bool {
+        match self {
+            Self::Route { paths } => paths.contains(&path),
+            Self::QueryParameter { name, paths } => {
+                paths.contains(&path)
+                    && query.is_some_and(|query| {
+                        query
+                            .split('&')
+                            .any(|pair| pair.split('=').next() == Some(*name))
+                    })
+            }
+            Self::ErrorFormat(_) => false,
+        }
+    }
+}
+
+/// Uses of a deprecation by one principal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeprecationUsage {
+    pub count: u64,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// State of a deprecation and its heaviest users
+#[derive(Debug, Clone)]
+pub struct DeprecationStatus {
+    pub id: &'static str,
+    pub summary: &'static str,
+    pub deprecated_on: NaiveDate,
+    pub sunset: NaiveDate,
+    pub link: &'static str,
+    /// The sunset has passed and the behavior is no longer served
+    pub removed: bool,
+    /// Served past its sunset by the `reenabled` setting
+    pub reenabled: bool,
+    pub total_uses: u64,
+    /// Principals with the most uses, most first
+    pub top_consumers: Vec<(String, DeprecationUsage)>,
+}
+
+/// A registered deprecation with its configured dates
+#[derive(Debug)]
+struct Entry {
+    deprecation: &'static Deprecation,
+    deprecated_on: NaiveDate,
+    sunset: NaiveDate,
+    reenabled: bool,
+}
+
+impl Entry {
+    fn new(deprecation: &'static Deprecation, settings: Option<&DeprecationSettings>) -> Self {
+        Self {
+            deprecation,
+            deprecated_on: parse_date(deprecation.deprecated_on),
+            sunset: settings
+                .and_then(|settings| settings.sunset)
+                .unwrap_or_else(|| parse_date(deprecation.sunset)),
+            reenabled: settings.is_some_and(|settings| settings.reenabled),
+        }
+    }
+
+    /// Returns true if the behavior is past its sunset on `today`
+    fn is_past_sunset(&self, today: NaiveDate) -> bool {
+        today > self.sunset
+    }
+
+    /// Returns true if the behavior is no longer served on `today`
+    fn is_removed(&self, today: NaiveDate) -> bool {
+        self.is_past_sunset(today) && !self.reenabled
+    }
+
+    /// Adds the `Deprecation`, `Sunset`, and `Link` headers
+    fn append_headers(&self, headers: &mut HeaderMap) {
+        let deprecated_at = self
+            .deprecated_on
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc()
+            .timestamp();
+        let sunset = self
+            .sunset
+            .and_hms_opt(23, 59, 59)
+            .unwrap_or_default()
+            .and_utc()
+            .format("%a, %d %b %Y %H:%M:%S GMT");
+        let link = format!(
+            "<{}>; rel=\"deprecation\"; type=\"text/html\"",
+            self.deprecation.link
+        );
+
+        for (name, value) in [
+            (DEPRECATION_HEADER, format!("@{}", deprecated_at)),
+            (SUNSET_HEADER, sunset.to_string()),
+            (header::LINK.as_str(), link),
+        ] {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.append(name, value);
+            }
+        }
+    }
+}
+
+/// Response header of deprecated behavior (RFC 9745)
+const DEPRECATION_HEADER: &str = "deprecation";
+
+/// Response header of the last day a behavior is served (RFC 8594)
+const SUNSET_HEADER: &str = "sunset";
+
+/// Parses a registered `YYYY-MM-DD` date
+///
+/// Registered dates are checked by the tests of this module.
+fn parse_date(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap_or(NaiveDate::MAX)
+}
+
+/// Registered deprecations with their usage counters
+///
+/// Counters are kept in memory and start over when the server restarts.
+#[derive(Debug)]
+pub struct DeprecationRegistry {
+    entries: Vec<Entry>,
+    error_format: ErrorFormat,
+    usage: Mutex<HashMap<&'static str, HashMap<String, DeprecationUsage>>>,
+}
+
+impl Default for DeprecationRegistry {
+    fn default() -> Self {
+        Self::new(&ApiConfig::default())
+    }
+}
+
+impl DeprecationRegistry {
+    /// Creates the registry of [`DEPRECATIONS`] with the overrides and
+    /// error format of `config`
+    pub fn new(config: &ApiConfig) -> Self {
+        Self::with_deprecations(DEPRECATIONS, config)
+    }
+
+    /// Creates a registry of `deprecations` with the overrides and error
+    /// format of `config`
+    ///
+    /// A configured error format that is past its sunset and not
+    /// re-enabled falls back to problem details.
+    pub fn with_deprecations(deprecations: &[&'static Deprecation], config: &ApiConfig) -> Self {
+        for id in config.deprecations.keys() {
+            if !deprecations.iter().any(|deprecation| deprecation.id == id) {
+                tracing::warn!("Ignoring settings of unknown deprecation '{}'", id);
+            }
+        }
+
+        let entries: Vec<Entry> = deprecations
+            .iter()
+            .map(|deprecation| Entry::new(deprecation, config.deprecations.get(deprecation.id)))
+            .collect();
+
+        let today = Utc::now().date_naive();
+        let mut error_format = config.error_format;
+        for entry in &entries {
+            if matches!(entry.deprecation.usage, DeprecatedUsage::ErrorFormat(format) if format == error_format)
+                && entry.is_removed(today)
+            {
+                tracing::warn!(
+                    "Error format {:?} was removed on {}; serving problem details",
+                    error_format,
+                    entry.sunset
+                );
+                error_format = ErrorFormat::Problem;
+            }
+        }
+
+        Self {
+            entries,
+            error_format,
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the error format to serve
+    pub fn error_format(&self) -> ErrorFormat {
+        self.error_format
+    }
+
+    /// Counts a use of deprecation `id` by `principal`
+    pub fn record(&self, id: &'static str, principal: &str) {
+        let mut usage = self
+            .usage
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let principals = usage.entry(id).or_default();
+        let key = if principals.contains_key(principal) || principals.len() < MAX_PRINCIPALS {
+            principal
+        } else {
+            OTHER_PRINCIPALS
+        };
+        let now = Utc::now();
+        principals
+            .entry(key.to_string())
+            .and_modify(|usage| {
+                usage.count += 1;
+                usage.last_seen = now;
+            })
+            .or_insert(DeprecationUsage {
+                count: 1,
+                last_seen: now,
+            });
+    }
+
+    /// Returns every deprecation with its `top` heaviest users
+    pub fn statuses(&self, top: usize) -> Vec<DeprecationStatus> {
+        let today = Utc::now().date_naive();
+        let usage = self
+            .usage
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        self.entries
+            .iter()
+            .map(|entry| {
+                let mut consumers: Vec<(String, DeprecationUsage)> = usage
+                    .get(entry.deprecation.id)
+                    .map(|principals| {
+                        principals
+                            .iter()
+                            .map(|(principal, usage)| (principal.clone(), *usage))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let total_uses = consumers.iter().map(|(_, usage)| usage.count).sum();
+                consumers.sort_by(|(a_principal, a), (b_principal, b)| {
+                    b.count
+                        .cmp(&a.count)
+                        .then_with(|| a_principal.cmp(b_principal))
+                });
+                consumers.truncate(top);
+
+                DeprecationStatus {
+                    id: entry.deprecation.id,
+                    summary: entry.deprecation.summary,
+                    deprecated_on: entry.deprecated_on,
+                    sunset: entry.sunset,
+                    link: entry.deprecation.link,
+                    removed: entry.is_removed(today),
+                    reenabled: entry.reenabled && entry.is_past_sunset(today),
+                    total_uses,
+                    top_consumers: consumers,
+                }
+            })
+            .collect()
+    }
+
+    /// Deprecations a request to `path` with `query` relies on
+    fn request_entries<'a>(
+        &'a self,
+        path: &'a str,
+        query: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.deprecation.usage.matches_request(path, query))
+    }
+
+    /// The deprecation of the served error format, if any
+    fn error_format_entry(&self) -> Option<&Entry> {
+        self.entries.iter().find(|entry| {
+            matches!(entry.deprecation.usage, DeprecatedUsage::ErrorFormat(format) if format == self.error_format)
+        })
+    }
+}
+
+/// Marks responses that rely on deprecated behavior and counts their use
+///
+/// Requests for a deprecated route or parameter past its sunset are
+/// answered with `410 Gone`. Error responses served in a deprecated error
+/// format carry the headers of that deprecation. Layer it inside the
+/// authentication layers so uses are counted per user or API key; other
+/// requests are counted per client IP.
+pub async fn deprecation_middleware(
+    State(registry): State<Arc<DeprecationRegistry>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let today = Utc::now().date_naive();
+    let principal = principal(&request);
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let query = request.uri().query().map(str::to_string);
+    let entries: Vec<&Entry> = registry.request_entries(&path, query.as_deref()).collect();
+
+    for entry in &entries {
+        registry.record(entry.deprecation.id, &principal);
+    }
+
+    if let Some(entry) = entries.iter().find(|entry| entry.is_removed(today)) {
+        tracing::warn!(
+            principal = %principal,
+            deprecation = entry.deprecation.id,
+            "Rejected request for behavior removed on {}",
+            entry.sunset
+        );
+        let mut response = (
+            StatusCode::GONE,
+            Json(ErrorResponse::new(
+                DEPRECATED_REMOVED_CODE.to_string(),
+                format!(
+                    "{} was removed on {}; see {}",
+                    entry.deprecation.summary, entry.sunset, entry.deprecation.link
+                ),
+            )),
+        )
+            .into_response();
+        entry.append_headers(response.headers_mut());
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+
+    let status = response.status();
+    if status.is_client_error() || status.is_server_error() {
+        if let Some(entry) = registry.error_format_entry() {
+            registry.record(entry.deprecation.id, &principal);
+            entry.append_headers(response.headers_mut());
+        }
+    }
+    for entry in entries {
+        entry.append_headers(response.headers_mut());
+    }
+
+    response
+}
+
+/// Names the caller of `request` for usage counting
+///
+/// API keys are named by key rather than by the key's user.
+fn principal(request: &Request) -> String {
+    let extensions = request.extensions();
+    if let Some(api_key) = extensions.get::<ApiKeyPrincipal>() {
+        format!("api_key:{}", api_key.key_id)
+    } else if let Some(user) = extensions.get::<AuthenticatedUser>() {
+        format!("user:{}", user.user_id())
+    } else if let Some(ClientIp(ip)) = extensions.get::<ClientIp>() {
+        format!("ip:{}", ip)
+    } else {
+        "anonymous".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::config::DeprecationSettings;
+    use axum::{body::Body, middleware, routing::get, Router};
+    use std::net::{IpAddr, Ipv4Addr};
+    use tower::ServiceExt;
+
+    const OLD_ROUTE: Deprecation = Deprecation {
+        id: "old_route",
+        summary: "The /old route",
+        usage: DeprecatedUsage::Route { paths: &["/old"] },
+        deprecated_on: "2025-01-01",
+        sunset: "2999-12-31",
+        link: "https://xzepr.dev/deprecations/old-route",
+    };
+
+    fn config(settings: &[(&str, DeprecationSettings)]) -> ApiConfig {
+        ApiConfig {
+            deprecations: settings
+                .iter()
+                .map(|(id, settings)| (id.to_string(), settings.clone()))
+                .collect(),
+            ..ApiConfig::default()
+        }
+    }
+
+    fn past_sunset(reenabled: bool) -> DeprecationSettings {
+        DeprecationSettings {
+            sunset: NaiveDate::from_ymd_opt(2020, 1, 1),
+            reenabled,
+        }
+    }
+
+    fn router(registry: Arc<DeprecationRegistry>, ip: [u8; 4]) -> Router {
+        Router::new()
+            .route("/old", get(|| async { "old" }))
+            .route("/new", get(|| async { "new" }))
+            .route(
+                "/missing",
+                get(|| async { (StatusCode::NOT_FOUND, "missing") }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                registry,
+                deprecation_middleware,
+            ))
+            .layer(middleware::from_fn(
+                move |mut request: Request, next: Next| {
+                    request
+                        .extensions_mut()
+                        .insert(ClientIp(IpAddr::V4(Ipv4Addr::from(ip))));
+                    next.run(request)
+                },
+            ))
+    }
+
+    async fn get_response(app: &Router, uri: &str) -> Response {
+        app.clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_registered_deprecations_are_well_formed() {
+        for deprecation in DEPRECATIONS {
+            for date in [deprecation.deprecated_on, deprecation.sunset] {
+                assert!(
+                    NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok(),
+                    "{}: {}",
+                    deprecation.id,
+                    date
+                );
+            }
+            assert!(deprecation.link.starts_with("https://"));
+            assert_eq!(
+                DEPRECATIONS
+                    .iter()
+                    .filter(|other| other.id == deprecation.id)
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn test_query_parameter_matches_by_name() {
+        let usage = DeprecatedUsage::QueryParameter {
+            name: "offset",
+            paths: &["/items"],
+        };
+
+        assert!(usage.matches_request("/items", Some("offset=10")));
+        assert!(usage.matches_request("/items", Some("limit=5&offset")));
+        assert!(!usage.matches_request("/items", Some("limit=5")));
+        assert!(!usage.matches_request("/items", Some("page_offset=5")));
+        assert!(!usage.matches_request("/items", None));
+        assert!(!usage.matches_request("/other", Some("offset=10")));
+    }
+
+    #[tokio::test]
+    async fn test_headers_only_on_deprecated_routes() {
+        let registry = Arc::new(DeprecationRegistry::with_deprecations(
+            &[&OLD_ROUTE],
+            &ApiConfig::default(),
+        ));
+        let app = router(registry, [10, 0, 0, 1]);
+
+        let response = get_response(&app, "/old").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[DEPRECATION_HEADER], "@1735689600");
+        assert_eq!(
+            response.headers()[SUNSET_HEADER],
+            "Tue, 31 Dec 2999 23:59:59 GMT"
+        );
+        assert_eq!(
+            response.headers()[header::LINK],
+            "<https://xzepr.dev/deprecations/old-route>; rel=\"deprecation\"; type=\"text/html\""
+        );
+
+        let response = get_response(&app, "/new").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(DEPRECATION_HEADER).is_none());
+        assert!(response.headers().get(SUNSET_HEADER).is_none());
+        assert!(response.headers().get(header::LINK).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_usage_counted_per_principal() {
+        let registry = Arc::new(DeprecationRegistry::with_deprecations(
+            &[&OLD_ROUTE],
+            &ApiConfig::default(),
+        ));
+        let first = router(registry.clone(), [10, 0, 0, 1]);
+        let second = router(registry.clone(), [10, 0, 0, 2]);
+
+        for _ in 0..3 {
+            get_response(&first, "/old").await;
+        }
+        get_response(&second, "/old").await;
+        get_response(&second, "/new").await;
+
+        let statuses = registry.statuses(10);
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].total_uses, 4);
+        let consumers: Vec<(&str, u64)> = statuses[0]
+            .top_consumers
+            .iter()
+            .map(|(principal, usage)| (principal.as_str(), usage.count))
+            .collect();
+        assert_eq!(consumers, vec![("ip:10.0.0.1", 3), ("ip:10.0.0.2", 1)]);
+
+        assert_eq!(registry.statuses(1)[0].top_consumers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_removed_after_sunset_unless_reenabled() {
+        let registry = Arc::new(DeprecationRegistry::with_deprecations(
+            &[&OLD_ROUTE],
+            &config(&[("old_route", past_sunset(false))]),
+        ));
+        let app = router(registry.clone(), [10, 0, 0, 1]);
+
+        let response = get_response(&app, "/old").await;
+        assert_eq!(response.status(), StatusCode::GONE);
+        assert!(response.headers().contains_key(SUNSET_HEADER));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], DEPRECATED_REMOVED_CODE);
+        assert_eq!(get_response(&app, "/new").await.status(), StatusCode::OK);
+        assert!(registry.statuses(10)[0].removed);
+
+        let registry = Arc::new(DeprecationRegistry::with_deprecations(
+            &[&OLD_ROUTE],
+            &config(&[("old_route", past_sunset(true))]),
+        ));
+        let app = router(registry.clone(), [10, 0, 0, 1]);
+
+        let response = get_response(&app, "/old").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key(DEPRECATION_HEADER));
+        let status = &registry.statuses(10)[0];
+        assert!(!status.removed);
+        assert!(status.reenabled);
+    }
+
+    #[tokio::test]
+    async fn test_legacy_error_format_marks_error_responses() {
+        let legacy = ApiConfig {
+            error_format: ErrorFormat::Legacy,
+            ..ApiConfig::default()
+        };
+        let registry = Arc::new(DeprecationRegistry::with_deprecations(
+            &[&LEGACY_ERROR_FORMAT],
+            &legacy,
+        ));
+        assert_eq!(registry.error_format(), ErrorFormat::Legacy);
+        let app = router(registry.clone(), [10, 0, 0, 1]);
+
+        let response = get_response(&app, "/new").await;
+        assert!(response.headers().get(DEPRECATION_HEADER).is_none());
+        let response = get_response(&app, "/missing").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().contains_key(DEPRECATION_HEADER));
+        assert_eq!(registry.statuses(10)[0].total_uses, 1);
+
+        // Past its sunset the legacy format falls back to problem details
+        let removed = ApiConfig {
+            deprecations: config(&[("legacy_error_format", past_sunset(false))]).deprecations,
+            ..legacy.clone()
+        };
+        let registry = DeprecationRegistry::with_deprecations(&[&LEGACY_ERROR_FORMAT], &removed);
+        assert_eq!(registry.error_format(), ErrorFormat::Problem);
+
+        let reenabled = ApiConfig {
+            deprecations: config(&[("legacy_error_format", past_sunset(true))]).deprecations,
+            ..legacy
+        };
+        let registry = DeprecationRegistry::with_deprecations(&[&LEGACY_ERROR_FORMAT], &reenabled);
+        assert_eq!(registry.error_format(), ErrorFormat::Legacy);
+    }
+}
@@ -18,7 +18,9 @@ use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, warn};
 
+use crate::api::middleware::client_ip::TrustedProxies;
 use crate::auth::jwt::{Claims, JwtService};
+use crate::infrastructure::audit;
 use crate::infrastructure::{AuditAction, AuditLogger, AuditOutcome, PrometheusMetrics};
 
 /// Extension type for authenticated user claims
@@ -48,6 +50,11 @@ impl AuthenticatedUser {
     pub fn has_permission(&self, permission: &str) -> bool {
         self.claims.has_permission(permission)
     }
+
+    /// Get the administrator impersonating this user, if any
+    pub fn actor_id(&self) -> Option<&str> {
+        self.claims.actor_id()
+    }
 }
 
 /// Shared JWT service state for middleware
@@ -56,6 +63,7 @@ pub struct JwtMiddlewareState {
     jwt_service: Arc<JwtService>,
     audit_logger: Option<Arc<AuditLogger>>,
     metrics: Option<Arc<PrometheusMetrics>>,
+    trusted_proxies: Arc<TrustedProxies>,
 }
 
 impl JwtMiddlewareState {
@@ -65,6 +73,7 @@ impl JwtMiddlewareState {
             jwt_service: Arc::new(jwt_service),
             audit_logger: None,
             metrics: None,
+            trusted_proxies: Arc::new(TrustedProxies::disabled()),
         }
     }
 
@@ -79,12 +88,21 @@ impl JwtMiddlewareState {
         self.metrics = Some(metrics);
         self
     }
+
+    /// Create new middleware state with a trusted proxy policy for audit IPs
+    pub fn with_trusted_proxies(mut self, trusted_proxies: TrustedProxies) -> Self {
+        self.trusted_proxies = Arc::new(trusted_proxies);
+        self
+    }
 }
 
 /// JWT authentication middleware
 ///
 /// This middleware extracts the JWT token from the Authorization header,
-/// validates it, and adds the claims to the request extensions.
+/// validates it, and adds the claims to the request extensions. Requests
+/// already authenticated by an API key are passed through. Requests made
+/// with an impersonation token are handled inside
+/// [`audit::with_actor`], so their audit events name the administrator.
 ///
 /// # Example
 ///
@@ -111,21 +129,19 @@ pub async fn jwt_auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, AuthError> {
+    // An earlier layer, such as API key authentication, already did the work
+    if request.extensions().get::<AuthenticatedUser>().is_some() {
+        return Ok(next.run(request).await);
+    }
+
     let start = Instant::now();
     let path = request.uri().path().to_string();
 
-    // Extract IP address from headers if available
-    let ip_address = request
-        .headers()
-        .get("x-forwarded-for")
-        .and_then(|h| h.to_str().ok())
-        .or_else(|| {
-            request
-                .headers()
-                .get("x-real-ip")
-                .and_then(|h| h.to_str().ok())
-        })
-        .map(|s| s.to_string());
+    // Resolve the client IP, honoring forwarding headers only from trusted proxies
+    let ip_address = state
+        .trusted_proxies
+        .resolve_request(&request)
+        .map(|ip| ip.to_string());
 
     // Extract token from Authorization header
     let token = match extract_token_from_header(&request) {
@@ -180,12 +196,14 @@ pub async fn jwt_auth_middleware(
     };
 
     let user_id = claims.sub.clone();
-    debug!(user_id = %user_id, "User authenticated");
+    let actor_id = claims.actor_id().map(str::to_string);
+    debug!(user_id = %user_id, actor_id = ?actor_id, "User authenticated");
 
     // Log successful authentication
     if let Some(audit_logger) = &state.audit_logger {
         let event = crate::infrastructure::AuditEvent::builder()
             .user_id(&user_id)
+            .actor_id_opt(actor_id.as_deref())
             .action(AuditAction::TokenValidation)
             .resource(&path)
             .outcome(AuditOutcome::Success)
@@ -206,7 +224,7 @@ pub async fn jwt_auth_middleware(
         .extensions_mut()
         .insert(AuthenticatedUser::new(claims));
 
-    Ok(next.run(request).await)
+    Ok(audit::with_actor(actor_id, next.run(request)).await)
 }
 
 /// Optional JWT authentication middleware
@@ -218,18 +236,21 @@ pub async fn optional_jwt_auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Response {
+    let mut actor_id = None;
+
     // Try to extract token
     if let Ok(token) = extract_token_from_header(&request) {
         // Try to validate token
         if let Ok(claims) = state.jwt_service.validate_token(token).await {
             debug!(user_id = %claims.sub, "User authenticated (optional)");
+            actor_id = claims.actor_id().map(str::to_string);
             request
                 .extensions_mut()
                 .insert(AuthenticatedUser::new(claims));
         }
     }
 
-    next.run(request).await
+    audit::with_actor(actor_id, next.run(request)).await
 }
 
 /// Extract JWT token from Authorization header
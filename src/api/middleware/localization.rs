@@ -0,0 +1,190 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/middleware/localization.rs
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::Request,
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+
+use crate::i18n::{self, MessageParams};
+
+/// Largest error body that is rewritten; bigger bodies pass through
+const MAX_LOCALIZED_BODY_BYTES: u64 = 64 * 1024;
+
+/// Translates the `message` of JSON error responses
+///
+/// Error bodies that carry a `message_key` get their `message` re-rendered
+/// from the catalog of the locale negotiated from `Accept-Language`, with
+/// the body's `params` interpolated. `error`, `message_key`, and `params`
+/// are left untouched so clients can still match on them. Localized
+/// responses carry `Content-Language`; all error responses vary on
+/// `Accept-Language`.
+pub async fn localize_errors_middleware(request: Request, next: Next) -> Response {
+    let accept_language = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let response = next.run(request).await;
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) || !is_json(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    parts
+        .headers
+        .append(header::VARY, HeaderValue::from_static("accept-language"));
+    let fits = body
+        .size_hint()
+        .upper()
+        .is_some_and(|size| size <= MAX_LOCALIZED_BODY_BYTES);
+    if !fits {
+        return Response::from_parts(parts, body);
+    }
+
+    let bytes = match axum::body::to_bytes(body, MAX_LOCALIZED_BODY_BYTES as usize).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to buffer error response for localization: {}", e);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+
+    let mut document = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(document) => document,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+    let Some(key) = document
+        .get("message_key")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+    else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let params: MessageParams = document
+        .get("params")
+        .cloned()
+        .and_then(|params| serde_json::from_value(params).ok())
+        .unwrap_or_default();
+
+    let catalogs = i18n::catalogs();
+    let locale = catalogs.negotiate(accept_language.as_deref());
+    document["message"] = Value::String(catalogs.resolve(locale, &key, &params));
+
+    let body = match serde_json::to_vec(&document) {
+        Ok(body) => body,
+        Err(_) => return Response::from_parts(parts, Body::from(bytes)),
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    if let Ok(language) = HeaderValue::from_str(locale) {
+        parts.headers.insert(header::CONTENT_LANGUAGE, language);
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn is_json(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::rest::dtos::ErrorResponse;
+    use crate::error::DomainError;
+    use crate::i18n::Message;
+    use axum::{http::StatusCode, middleware, routing::get, Json, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/invalid",
+                get(|| async {
+                    let error = DomainError::ValidationError {
+                        field: "limit".to_string(),
+                        message: Message::new("validation.limit_range").with("max", 1000),
+                    };
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(ErrorResponse::from_domain(
+                            "validation_error".to_string(),
+                            &error,
+                        )),
+                    )
+                }),
+            )
+            .route(
+                "/plain",
+                get(|| async {
+                    (
+                        StatusCode::NOT_FOUND,
+                        Json(ErrorResponse::new(
+                            "not_found".to_string(),
+                            "Event not found".to_string(),
+                        )),
+                    )
+                }),
+            )
+            .layer(middleware::from_fn(localize_errors_middleware))
+    }
+
+    async fn get_json(uri: &str, accept_language: Option<&str>) -> (Response, Value) {
+        let mut request = Request::builder().uri(uri);
+        if let Some(accept_language) = accept_language {
+            request = request.header(header::ACCEPT_LANGUAGE, accept_language);
+        }
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let (parts, body) = response.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (
+            Response::from_parts(parts, Body::empty()),
+            serde_json::from_slice(&bytes).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_accept_language_translates_message() {
+        let (response, body) = get_json("/invalid", Some("de-CH, en;q=0.5")).await;
+
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "de");
+        assert_eq!(body["error"], "validation_error");
+        assert_eq!(body["field"], "limit");
+        assert_eq!(body["message_key"], "validation.limit_range");
+        assert_eq!(body["params"]["max"], "1000");
+        assert_eq!(body["message"], "Das Limit muss zwischen 1 und 1000 liegen");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_language_falls_back_to_english() {
+        let (response, body) = get_json("/invalid", Some("ja")).await;
+
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "en");
+        assert_eq!(body["message"], "Limit must be between 1 and 1000");
+    }
+
+    #[tokio::test]
+    async fn test_errors_without_key_are_unchanged() {
+        let (response, body) = get_json("/plain", Some("de")).await;
+
+        assert!(response.headers().get(header::CONTENT_LANGUAGE).is_none());
+        assert_eq!(response.headers()[header::VARY], "accept-language");
+        assert_eq!(body["message"], "Event not found");
+        assert!(body.get("message_key").is_none());
+    }
+}
@@ -0,0 +1,437 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/middleware/maintenance.rs
+
+//! Rejects writes while maintenance mode is on
+//!
+//! Mutating requests are answered with `503 Service Unavailable`, the code
+//! `MAINTENANCE_MODE`, the operator's message, and a `Retry-After` header
+//! derived from the scheduled end. Reads, health, metrics, and a short
+//! allowlist of requests that must keep working, such as logging in and
+//! turning maintenance mode off again, pass through. GraphQL requests are
+//! only rejected when they run a mutation; persisted queries sent by hash
+//! alone are looked up first so that they are classified by their text.
+
+use async_graphql::parser::{
+    parse_query,
+    types::{DocumentOperations, OperationType},
+};
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::Utc;
+
+use crate::api::graphql::handlers::GraphQLRequest;
+use crate::api::graphql::persisted_queries::PersistedQueryStore;
+use crate::api::middleware::rate_limit::LOGIN_PATH;
+use crate::api::middleware::validation::DEFAULT_MAX_BODY_SIZE;
+use crate::api::rest::dtos::ErrorResponse;
+use crate::infrastructure::maintenance::{MaintenanceMode, MaintenanceWindow};
+
+/// Error code of writes rejected during maintenance
+pub const MAINTENANCE_MODE_CODE: &str = "MAINTENANCE_MODE";
+
+/// Path of the GraphQL endpoint
+const GRAPHQL_PATH: &str = "/graphql";
+
+/// Paths of writes served during maintenance
+///
+/// Logging in and refreshing a token do not touch the tables being
+/// migrated; other authentication requests, such as accepting an
+/// invitation, write users and are rejected.
+const ALLOWED_PATHS: &[&str] = &[LOGIN_PATH, "/api/v1/auth/refresh"];
+
+/// Path prefixes of writes served during maintenance
+///
+/// Session revocation and the maintenance toggle itself do not touch the
+/// tables being migrated.
+const ALLOWED_PREFIXES: &[&str] = &[
+    "/api/v1/admin/maintenance",
+    "/api/v1/admin/impersonate",
+    "/api/v1/admin/sessions/",
+    "/api/v1/me/sessions/",
+];
+
+/// Path suffixes of POST requests that only compute a result
+const READ_ONLY_SUFFIXES: &[&str] = &["/diagnose", "/schema/preview"];
+
+/// State of [`maintenance_middleware`]
+#[derive(Clone)]
+pub struct MaintenanceGuard {
+    mode: MaintenanceMode,
+    persisted_queries: Option<PersistedQueryStore>,
+}
+
+impl MaintenanceGuard {
+    /// Creates a guard rejecting writes while `mode` is on
+    pub fn new(mode: MaintenanceMode) -> Self {
+        Self {
+            mode,
+            persisted_queries: None,
+        }
+    }
+
+    /// Resolves GraphQL queries sent by hash alone in `store`
+    ///
+    /// Without a store such requests are rejected during maintenance,
+    /// since there is no way to tell whether they run a mutation.
+    pub fn with_persisted_queries(mut self, store: Option<PersistedQueryStore>) -> Self {
+        self.persisted_queries = store;
+        self
+    }
+}
+
+impl From<MaintenanceMode> for MaintenanceGuard {
+    fn from(mode: MaintenanceMode) -> Self {
+        Self::new(mode)
+    }
+}
+
+/// Middleware rejecting writes while maintenance mode is on
+///
+/// Layer it inside the error localization and problem details middleware
+/// so rejections are formatted like every other error.
+pub async fn maintenance_middleware(
+    State(guard): State<MaintenanceGuard>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(window) = guard.mode.current() else {
+        return next.run(request).await;
+    };
+    if !is_write(request.method()) || is_allowed(request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    if request.uri().path() != GRAPHQL_PATH {
+        return maintenance_response(&window);
+    }
+
+    // Only mutations write; queries keep working
+    let (parts, body) = request.into_parts();
+    let Ok(bytes) = to_bytes(body, DEFAULT_MAX_BODY_SIZE).await else {
+        return maintenance_response(&window);
+    };
+    if is_graphql_mutation(&bytes, guard.persisted_queries.as_ref()).await {
+        return maintenance_response(&window);
+    }
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
+fn is_write(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+fn is_allowed(path: &str) -> bool {
+    ALLOWED_PATHS.contains(&path)
+        || ALLOWED_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        || READ_ONLY_SUFFIXES
+            .iter()
+            .any(|suffix| path.ends_with(suffix))
+}
+
+/// Returns true if the GraphQL request in `body` runs a mutation
+///
+/// Requests that cannot be classified count as mutations: bodies that fail
+/// to parse, and persisted queries sent by hash alone whose text is not
+/// found in `persisted_queries`.
+async fn is_graphql_mutation(body: &[u8], persisted_queries: Option<&PersistedQueryStore>) -> bool {
+    let Ok(mut request) = serde_json::from_slice::<GraphQLRequest>(body) else {
+        return true;
+    };
+    if request.query.is_empty() {
+        match persisted_query(&request, persisted_queries).await {
+            Some(query) => request.query = query,
+            None => return true,
+        }
+    }
+    let Ok(document) = parse_query(&request.query) else {
+        return true;
+    };
+
+    match document.operations {
+        DocumentOperations::Single(operation) => operation.node.ty == OperationType::Mutation,
+        DocumentOperations::Multiple(operations) => match request.operation_name {
+            Some(name) => operations
+                .get(name.as_str())
+                .is_some_and(|operation| operation.node.ty == OperationType::Mutation),
+            None => operations
+                .values()
+                .any(|operation| operation.node.ty == OperationType::Mutation),
+        },
+    }
+}
+
+/// Looks up the text of a persisted query sent by hash alone
+///
+/// The text stored under a hash always hashes to it, so GraphQL runs the
+/// same text once it resolves the hash itself.
+async fn persisted_query(
+    request: &GraphQLRequest,
+    persisted_queries: Option<&PersistedQueryStore>,
+) -> Option<String> {
+    let hash = request
+        .extensions
+        .as_ref()?
+        .get("persistedQuery")?
+        .get("sha256Hash")?
+        .as_str()?
+        .to_ascii_lowercase();
+    match persisted_queries?.lookup(&hash).await {
+        Ok(found) => found.map(|found| found.query),
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to look up persisted query");
+            None
+        }
+    }
+}
+
+fn maintenance_response(window: &MaintenanceWindow) -> Response {
+    let mut response = (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(ErrorResponse::new(
+            MAINTENANCE_MODE_CODE.to_string(),
+            window.message.clone(),
+        )),
+    )
+        .into_response();
+
+    if let Some(retry_after) = window.retry_after(Utc::now()) {
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(retry_after.as_secs()),
+        );
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        middleware,
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    fn router(maintenance: impl Into<MaintenanceGuard>) -> Router {
+        Router::new()
+            .route(
+                "/api/v1/events",
+                get(|| async { "read" }).post(|| async { "write" }),
+            )
+            .route("/api/v1/auth/login", post(|| async { "login" }))
+            .route(
+                "/api/v1/auth/invitations/accept",
+                post(|| async { "accepted" }),
+            )
+            .route(
+                "/api/v1/receivers/:id/diagnose",
+                post(|| async { "diagnose" }),
+            )
+            .route(GRAPHQL_PATH, post(|body: String| async move { body }))
+            .layer(middleware::from_fn_with_state(
+                maintenance.into(),
+                maintenance_middleware,
+            ))
+    }
+
+    async fn send(router: Router, method: Method, uri: &str, body: &str) -> Response {
+        router
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn active() -> MaintenanceMode {
+        let maintenance = MaintenanceMode::new();
+        maintenance
+            .enter(
+                "Database migration".to_string(),
+                Some(Utc::now() + chrono::Duration::minutes(10)),
+                "admin",
+            )
+            .await
+            .unwrap();
+        maintenance
+    }
+
+    #[tokio::test]
+    async fn test_writes_rejected_with_retry_after() {
+        let response = send(router(active().await), Method::POST, "/api/v1/events", "{}").await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((590..=600).contains(&retry_after));
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], MAINTENANCE_MODE_CODE);
+        assert_eq!(body["message"], "Database migration");
+    }
+
+    #[tokio::test]
+    async fn test_reads_and_allowlisted_writes_pass() {
+        let maintenance = active().await;
+
+        for (method, uri) in [
+            (Method::GET, "/api/v1/events"),
+            (Method::POST, "/api/v1/auth/login"),
+            (Method::POST, "/api/v1/receivers/01H/diagnose"),
+        ] {
+            let response = send(router(maintenance.clone()), method, uri, "").await;
+            assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+        }
+
+        maintenance.exit("admin").await.unwrap();
+        let response = send(router(maintenance), Method::POST, "/api/v1/events", "{}").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_only_graphql_mutations_rejected() {
+        let maintenance = active().await;
+        let query = r#"{"query":"query { eventReceivers { id } }"}"#;
+        let mutation = r#"{"query":"mutation { deleteEventReceiver(id: \"x\") }"}"#;
+        let named = r#"{"query":"query Q { a } mutation M { b }","operationName":"Q"}"#;
+
+        let response = send(
+            router(maintenance.clone()),
+            Method::POST,
+            GRAPHQL_PATH,
+            query,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        // The buffered body still reaches the handler
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, query.as_bytes());
+
+        let response = send(
+            router(maintenance.clone()),
+            Method::POST,
+            GRAPHQL_PATH,
+            named,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(router(maintenance), Method::POST, GRAPHQL_PATH, mutation).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_persisted_mutation_sent_by_hash_rejected() {
+        use crate::api::graphql::persisted_queries::query_hash;
+
+        let maintenance = active().await;
+        let store = PersistedQueryStore::new();
+        let query = "query { eventReceivers { id } }";
+        let mutation = r#"mutation { deleteEventReceiver(id: "x") }"#;
+        for text in [query, mutation] {
+            store.register(text.to_string(), None).await.unwrap();
+        }
+        let by_hash = |text: &str| {
+            serde_json::json!({
+                "extensions": {
+                    "persistedQuery": {"version": 1, "sha256Hash": query_hash(text)}
+                }
+            })
+            .to_string()
+        };
+        let guard = || {
+            MaintenanceGuard::new(maintenance.clone()).with_persisted_queries(Some(store.clone()))
+        };
+
+        let response = send(router(guard()), Method::POST, GRAPHQL_PATH, &by_hash(query)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = send(
+            router(guard()),
+            Method::POST,
+            GRAPHQL_PATH,
+            &by_hash(mutation),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], MAINTENANCE_MODE_CODE);
+
+        // Unknown hashes, and any hash without a store, cannot be classified
+        let response = send(
+            router(guard()),
+            Method::POST,
+            GRAPHQL_PATH,
+            &by_hash("query { unknown }"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let response = send(
+            router(maintenance),
+            Method::POST,
+            GRAPHQL_PATH,
+            &by_hash(query),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_unclassifiable_writes_rejected() {
+        let maintenance = active().await;
+
+        // Accepting an invitation sets a password
+        let response = send(
+            router(maintenance.clone()),
+            Method::POST,
+            "/api/v1/auth/invitations/accept",
+            "{}",
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        for body in [
+            "not json",
+            r#"[{"query":"query { a }"}]"#,
+            r#"{"query":"mutation {"}"#,
+        ] {
+            let response = send(
+                router(maintenance.clone()),
+                Method::POST,
+                GRAPHQL_PATH,
+                body,
+            )
+            .await;
+            assert_eq!(
+                response.status(),
+                StatusCode::SERVICE_UNAVAILABLE,
+                "{}",
+                body
+            );
+        }
+    }
+}
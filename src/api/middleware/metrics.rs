@@ -23,6 +23,7 @@ use axum::{
 use std::sync::Arc;
 use std::time::Instant;
 
+use crate::api::middleware::tracing_middleware::RequestTraceId;
 use crate::infrastructure::PrometheusMetrics;
 
 /// Metrics middleware state
@@ -47,7 +48,9 @@ impl MetricsMiddlewareState {
 ///
 /// This middleware automatically records:
 /// - Request counts by method, path, and status code
-/// - Request duration in seconds
+/// - Request duration in seconds, with the trace id that an inner
+///   [`tracing_middleware`](super::tracing_middleware::tracing_middleware)
+///   put on the response as a candidate exemplar
 /// - Active connection tracking
 ///
 /// # Example
@@ -97,10 +100,14 @@ pub async fn metrics_middleware(
     // Extract status code
     let status = response.status().as_u16();
 
-    // Record metrics
+    // Record metrics, linked to the request's trace when it was sampled
+    let trace_id = response
+        .extensions()
+        .get::<RequestTraceId>()
+        .map(|trace_id| trace_id.0.as_str());
     state
         .metrics
-        .record_http_request(&method, &path, status, duration_secs);
+        .record_http_request_with_trace(&method, &path, status, duration_secs, trace_id);
 
     response
 }
@@ -8,14 +8,29 @@
 //! This module provides reusable middleware for the XZepr API, including:
 //! - CORS configuration and validation
 //! - Rate limiting with token bucket algorithm
-//! - JWT authentication and validation
+//! - Client IP resolution behind trusted proxies
+//! - API key and JWT authentication
 //! - Input validation and sanitization
+//! - MessagePack and CBOR event bodies
+//! - Localized error messages
+//! - Read-only maintenance mode
+//! - Request deadlines from route budgets and X-Request-Deadline
+//! - Deprecation and sunset headers for deprecated behavior
+//! - RFC 7807 problem details for error responses
 //! - Security headers (CSP, HSTS, etc.)
 
+pub mod api_key;
+pub mod body_encoding;
+pub mod client_ip;
 pub mod cors;
+pub mod deadline;
+pub mod deprecation;
 pub mod jwt;
+pub mod localization;
+pub mod maintenance;
 pub mod metrics;
 pub mod opa;
+pub mod problem;
 pub mod rate_limit;
 pub mod rbac;
 pub mod rbac_helpers;
@@ -28,11 +43,21 @@ pub mod validation;
 // pub mod logging;
 // pub mod request_id;
 
+pub use api_key::{api_key_auth_middleware, ApiKeyPrincipal, API_KEY_HEADER};
+pub use body_encoding::{binary_body_middleware, BodyFormat, MALFORMED_BODY_CODE};
+pub use client_ip::{client_ip_middleware, peer_addr, ClientIp, IpCidr, TrustedProxies};
 pub use cors::{cors_layer, development_cors_layer, production_cors_layer, CorsConfig};
+pub use deadline::{deadline_middleware, RequestDeadlines, REQUEST_DEADLINE_HEADER, TIMEOUT_CODE};
+pub use deprecation::{
+    deprecation_middleware, DeprecatedUsage, Deprecation, DeprecationRegistry,
+    DEPRECATED_REMOVED_CODE, DEPRECATIONS,
+};
 pub use jwt::{
     jwt_auth_middleware, optional_jwt_auth_middleware, require_permissions, require_roles,
     AuthError, AuthenticatedUser, JwtMiddlewareState,
 };
+pub use localization::localize_errors_middleware;
+pub use maintenance::{maintenance_middleware, MaintenanceGuard, MAINTENANCE_MODE_CODE};
 pub use metrics::{
     extract_path_for_metrics, metrics_middleware, metrics_middleware_simple, record_error_metric,
     MetricsError, MetricsMiddlewareState,
@@ -40,16 +65,22 @@ pub use metrics::{
 pub use opa::{
     opa_authorize_middleware, AuthorizationDecision, AuthorizationError, OpaMiddlewareState,
 };
+pub use problem::{
+    problem_details_middleware, Problem, ProblemFieldError, PROBLEM_CONTENT_TYPE, PROBLEM_TYPE_BASE,
+};
 pub use rate_limit::{
-    rate_limit_middleware, InMemoryRateLimitStore, RateLimitConfig, RateLimitStore,
+    auth_rate_limit_middleware, rate_limit_middleware, AuthAttemptStatus, AuthRateLimitConfig,
+    AuthRateLimiterState, InMemoryRateLimitStore, RateLimitConfig, RateLimitStore,
     RateLimiterState,
 };
 pub use rbac::{
-    rbac_enforcement_middleware, rbac_enforcement_middleware_with_state, RbacError,
-    RbacMiddlewareState,
+    authorize_permission, authorize_rule, rbac_enforcement_middleware,
+    rbac_enforcement_middleware_with_state, RbacError, RbacMiddlewareState,
 };
 pub use rbac_helpers::{
-    extract_resource_id, get_resource_permissions, is_public_route, route_to_permission,
+    extract_resource_id, get_resource_permissions, is_public_route, restricted_under_impersonation,
+    restricted_under_scoped_key, route_rule, route_to_permission, GraphQLOperationKind,
+    GraphQLOperationRule, RouteAccess, RouteRule, ADMIN_ROLE, GRAPHQL_OPERATION_RULES, ROUTE_RULES,
 };
 pub use resource_context::{
     EventContextBuilder, EventReceiverContextBuilder, EventReceiverGroupContextBuilder,
@@ -60,11 +91,13 @@ pub use security_headers::{
     ReferrerPolicy, SecurityHeadersConfig,
 };
 pub use tracing_middleware::{
-    enhanced_tracing_middleware, request_id_middleware, tracing_middleware, RequestId, TracedError,
+    enhanced_tracing_middleware, request_id_middleware, tracing_middleware, RequestId,
+    RequestTraceId, TracedError,
 };
 pub use validation::{
-    body_size_limit_middleware, sanitize, validate_request, FieldError, ValidationConfig,
-    ValidationErrorResponse, ValidationState, DEFAULT_MAX_BODY_SIZE, MAX_UPLOAD_SIZE,
+    body_size_limit_middleware, content_negotiation_middleware, sanitize, validate_request,
+    ContentNegotiationConfig, FieldError, ValidationConfig, ValidationErrorResponse,
+    ValidationState, DEFAULT_MAX_BODY_SIZE, MAX_UPLOAD_SIZE,
 };
 
 // Re-export for convenience
@@ -6,9 +6,9 @@
 //! OPA-based authorization middleware
 //!
 //! This module provides middleware for enforcing authorization policies using
-//! Open Policy Agent (OPA). It integrates with the OPA client to evaluate
-//! policies and make authorization decisions based on user context, resource
-//! context, and requested actions.
+//! Open Policy Agent (OPA). Decisions are made by the
+//! [`AuthorizationService`] shared with the GraphQL API, based on user
+//! context, resource context, and requested actions.
 
 use axum::{
     extract::{Request, State},
@@ -17,40 +17,24 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use serde::Serialize;
-use std::sync::Arc;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, info, warn};
 
 use crate::api::middleware::jwt::AuthenticatedUser;
-use crate::infrastructure::audit::AuditLogger;
-use crate::infrastructure::metrics::PrometheusMetrics;
-use crate::opa::client::OpaClient;
-use crate::opa::types::{
-    AuthorizationDecision as OpaDecision, OpaInput, ResourceContext, UserContext,
-};
+use crate::application::authorization::AuthorizationService;
+use crate::opa::types::ResourceContext;
 
 /// State for OPA authorization middleware
 #[derive(Clone)]
 pub struct OpaMiddlewareState {
-    /// OPA client for policy evaluation
-    pub opa_client: Arc<OpaClient>,
-    /// Audit logger for authorization decisions
-    pub audit_logger: Arc<AuditLogger>,
-    /// Metrics collector
-    pub metrics: Arc<PrometheusMetrics>,
+    /// Service that evaluates policies and records decisions; attach an
+    /// OPA client and feature flags to it with its builders
+    pub authorization: AuthorizationService,
 }
 
 impl OpaMiddlewareState {
     /// Create new OPA middleware state
-    pub fn new(
-        opa_client: Arc<OpaClient>,
-        audit_logger: Arc<AuditLogger>,
-        metrics: Arc<PrometheusMetrics>,
-    ) -> Self {
-        Self {
-            opa_client,
-            audit_logger,
-            metrics,
-        }
+    pub fn new(authorization: AuthorizationService) -> Self {
+        Self { authorization }
     }
 }
 
@@ -82,14 +66,13 @@ pub struct AuthorizationDecision {
 /// 1. Extract authenticated user from request extensions
 /// 2. Extract resource context from request path and method
 /// 3. Build OPA input with user context, action, and resource context
-/// 4. Evaluate policy using OPA client (with caching and circuit breaker)
-/// 5. Log authorization decision to audit log
-/// 6. Record metrics
-/// 7. Allow or deny request
+/// 4. Evaluate policy through the [`AuthorizationService`], which records
+///    metrics and the audit log entry
+/// 5. Allow or deny request
 ///
 /// # Fallback
 ///
-/// If OPA is unavailable or returns an error, the middleware falls back to
+/// If OPA is unavailable or returns an error, the service falls back to
 /// legacy RBAC checks using the user's roles and permissions.
 pub async fn opa_authorize_middleware(
     State(state): State<OpaMiddlewareState>,
@@ -125,51 +108,10 @@ pub async fn opa_authorize_middleware(
     // Determine action from HTTP method and path
     let action = determine_action(&method, &path);
 
-    // Build OPA input
-    let user_context = UserContext {
-        user_id: user_id.clone(),
-        username: user.claims.sub.clone(),
-        roles: user.claims.roles.clone(),
-        groups: vec![],
-    };
-
-    let opa_input = OpaInput {
-        user: user_context,
-        action: action.clone(),
-        resource: resource_context.clone(),
-    };
-
-    // Evaluate policy
-    let start = std::time::Instant::now();
-    let decision = match state.opa_client.evaluate(opa_input).await {
-        Ok(result) => {
-            let duration = start.elapsed();
-            state
-                .metrics
-                .record_auth_duration(&format!("opa_{}", action), duration.as_secs_f64());
-
-            result
-        }
-        Err(e) => {
-            error!(
-                user_id = %user_id,
-                error = %e,
-                "OPA policy evaluation failed, falling back to legacy RBAC"
-            );
-
-            // Fallback to legacy RBAC
-            let allowed = legacy_rbac_check(&user, &action, &resource_context);
-
-            OpaDecision {
-                allow: allowed,
-                reason: Some("OPA unavailable, used legacy RBAC".to_string()),
-                metadata: None,
-            }
-        }
-    };
-
-    // Log authorization decision
-    log_authorization_decision(&state, &user_id, &action, &resource_context, &decision).await;
+    let decision = state
+        .authorization
+        .decide(&user.claims, &action, &resource_context)
+        .await;
 
     // Check if access is allowed
     if !decision.allow {
@@ -281,80 +223,13 @@ fn determine_action(method: &str, path: &str) -> String {
 /// Legacy RBAC fallback check
 ///
 /// When OPA is unavailable, fall back to simple role-based checks.
-fn legacy_rbac_check(user: &AuthenticatedUser, action: &str, resource: &ResourceContext) -> bool {
-    // Admin can do anything
-    if user.has_role("admin") {
-        return true;
-    }
-
-    // Owner can do anything with their own resources
-    if let Some(owner_id) = &resource.owner_id {
-        if owner_id == user.user_id() {
-            return true;
-        }
-    }
-
-    // Group members can read resources in their group
-    if action == "read" {
-        if let Some(_group_id) = &resource.group_id {
-            // Check if user is a member of the group
-            if resource.members.contains(&user.user_id().to_string()) {
-                return true;
-            }
-        }
-    }
-
-    // Check specific permissions
-    let permission = format!("{}:{}", resource.resource_type, action);
-    user.has_permission(&permission)
-}
-
-/// Log authorization decision to audit log
-async fn log_authorization_decision(
-    state: &OpaMiddlewareState,
-    user_id: &str,
+#[cfg(test)]
+pub(crate) fn legacy_rbac_check(
+    user: &AuthenticatedUser,
     action: &str,
     resource: &ResourceContext,
-    decision: &OpaDecision,
-) {
-    use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditOutcome};
-
-    let outcome = if decision.allow {
-        AuditOutcome::Success
-    } else {
-        AuditOutcome::Denied
-    };
-
-    let mut metadata = std::collections::HashMap::new();
-    metadata.insert("action".to_string(), action.to_string());
-    metadata.insert("resource_type".to_string(), resource.resource_type.clone());
-    if let Some(rid) = &resource.resource_id {
-        metadata.insert("resource_id".to_string(), rid.clone());
-    }
-    if let Some(oid) = &resource.owner_id {
-        metadata.insert("owner_id".to_string(), oid.clone());
-    }
-    if let Some(gid) = &resource.group_id {
-        metadata.insert("group_id".to_string(), gid.clone());
-    }
-    metadata.insert("decision_allowed".to_string(), decision.allow.to_string());
-    if let Some(reason) = &decision.reason {
-        metadata.insert("decision_reason".to_string(), reason.clone());
-    }
-
-    let audit_event = AuditEvent::builder()
-        .user_id(user_id)
-        .action(AuditAction::PermissionCheck)
-        .resource(format!(
-            "{}:{}",
-            resource.resource_type,
-            resource.resource_id.as_deref().unwrap_or("*")
-        ))
-        .outcome(outcome)
-        .metadata(metadata)
-        .build();
-
-    state.audit_logger.log_event(audit_event);
+) -> bool {
+    crate::application::authorization::legacy_decision(&user.claims, action, resource)
 }
 
 /// Authorization error
@@ -463,6 +338,7 @@ mod tests {
             iss: "xzepr".to_string(),
             aud: "xzepr-api".to_string(),
             token_type: TokenType::Access,
+            act: None,
         };
         let user = AuthenticatedUser::new(claims);
 
@@ -494,6 +370,7 @@ mod tests {
             iss: "xzepr".to_string(),
             aud: "xzepr-api".to_string(),
             token_type: TokenType::Access,
+            act: None,
         };
         let user = AuthenticatedUser::new(claims);
 
@@ -525,6 +402,7 @@ mod tests {
             iss: "xzepr".to_string(),
             aud: "xzepr-api".to_string(),
             token_type: TokenType::Access,
+            act: None,
         };
         let user = AuthenticatedUser::new(claims);
 
@@ -538,6 +416,7 @@ mod tests {
         };
 
         assert!(legacy_rbac_check(&user, "read", &resource));
+        assert!(legacy_rbac_check(&user, "create", &resource));
         assert!(!legacy_rbac_check(&user, "update", &resource));
     }
 
@@ -557,6 +436,7 @@ mod tests {
             iss: "xzepr".to_string(),
             aud: "xzepr-api".to_string(),
             token_type: TokenType::Access,
+            act: None,
         };
         let user = AuthenticatedUser::new(claims);
 
@@ -0,0 +1,586 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/middleware/problem.rs
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::api::middleware::deprecation::{DeprecatedUsage, Deprecation};
+use crate::api::middleware::tracing_middleware::RequestId;
+use crate::infrastructure::config::ErrorFormat;
+
+/// Base URI of the `type` of every problem; the error code is appended
+pub const PROBLEM_TYPE_BASE: &str = "https://xzepr.dev/problems/";
+
+/// Media type of problem documents
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Largest error body that is converted; bigger bodies pass through
+const MAX_PROBLEM_BODY_BYTES: u64 = 64 * 1024;
+
+/// Request header carrying the request ID
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// The error bodies served before problem details, kept while
+/// `api.error_format` is `legacy`
+pub const LEGACY_ERROR_FORMAT: Deprecation = Deprecation {
+    id: "legacy_error_format",
+    summary: "Legacy error bodies (api.error_format: legacy)",
+    usage: DeprecatedUsage::ErrorFormat(ErrorFormat::Legacy),
+    deprecated_on: "2025-06-06",
+    sunset: "2027-06-30",
+    link: "https://xzepr.dev/deprecations/legacy-error-format",
+};
+
+/// RFC 7807 problem details of a failed request
+///
+/// `code` is the machine-readable error code that `type` ends in, such as
+/// `validation_error` or `not_found`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub field_errors: Vec<ProblemFieldError>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Structured context carried over from the error body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+/// A rejected field of a problem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProblemFieldError {
+    pub field: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<String>,
+}
+
+impl Problem {
+    /// Creates a problem titled after `status`
+    pub fn new(status: StatusCode, code: impl Into<String>, detail: impl Into<String>) -> Self {
+        let code = code.into();
+        Self {
+            problem_type: format!("{}{}", PROBLEM_TYPE_BASE, code),
+            title: title(status),
+            status: status.as_u16(),
+            detail: detail.into(),
+            instance: None,
+            code,
+            field_errors: Vec::new(),
+            request_id: None,
+            details: None,
+        }
+    }
+
+    /// Sets the path of the request that failed
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Sets the ID of the request that failed
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Adds a rejected field
+    pub fn with_field_error(
+        mut self,
+        field: impl Into<String>,
+        message: impl Into<String>,
+        message_key: Option<String>,
+    ) -> Self {
+        self.field_errors.push(ProblemFieldError {
+            field: field.into(),
+            message: message.into(),
+            message_key,
+        });
+        self
+    }
+
+    /// Builds a problem from an error body in one of the legacy shapes
+    ///
+    /// JSON bodies keep their `error` code, `message`, `field`,
+    /// `message_key`, `field_errors`, `request_id`, and `details`. Other bodies become
+    /// the `detail`, and the code is derived from `status`.
+    pub fn from_legacy_body(status: StatusCode, body: &[u8]) -> Self {
+        let document = match serde_json::from_slice::<Value>(body) {
+            Ok(Value::Object(document)) => document,
+            _ => {
+                let text = String::from_utf8_lossy(body).trim().to_string();
+                let detail = if text.is_empty() { title(status) } else { text };
+                return Self::new(status, status_code(status), detail);
+            }
+        };
+
+        let text = |document: &Map<String, Value>, key: &str| {
+            document
+                .get(key)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        let error = text(&document, "error");
+        let message = text(&document, "message");
+        let field_errors = match document.get("field_errors") {
+            Some(Value::Array(errors)) => errors
+                .iter()
+                .filter_map(Value::as_object)
+                .filter_map(|error| {
+                    Some(ProblemFieldError {
+                        field: text(error, "field")?,
+                        message: text(error, "message")?,
+                        message_key: text(error, "message_key"),
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        // Some middleware put a sentence rather than a code in `error`
+        let code = match error.as_deref() {
+            Some(error) if is_code(error) => error.to_lowercase(),
+            _ if !field_errors.is_empty() => "validation_error".to_string(),
+            _ => status_code(status),
+        };
+        let detail = message
+            .or_else(|| error.filter(|error| !is_code(error)))
+            .unwrap_or_else(|| title(status));
+
+        let mut problem = Self::new(status, code, detail);
+        problem.field_errors = field_errors;
+        if let Some(field) = text(&document, "field") {
+            let detail = problem.detail.clone();
+            problem = problem.with_field_error(field, detail, text(&document, "message_key"));
+        }
+        problem.request_id = text(&document, "request_id");
+        problem.details = document.get("details").cloned();
+        problem
+    }
+}
+
+impl IntoResponse for Problem {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = serde_json::to_vec(&self).unwrap_or_default();
+
+        (
+            status,
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(PROBLEM_CONTENT_TYPE),
+            )],
+            body,
+        )
+            .into_response()
+    }
+}
+
+/// Rewrites error responses as RFC 7807 problem documents
+///
+/// Every 4xx and 5xx response of the layers and handlers inside this one
+/// is converted with [`Problem::from_legacy_body`], with the request path
+/// as `instance` and the `X-Request-ID` as `request_id`. Other headers,
+/// such as `Retry-After` or `Content-Language`, are kept. Layer it outside
+/// [`localize_errors_middleware`](super::localize_errors_middleware) so
+/// `detail` is localized. With [`ErrorFormat::Legacy`] responses pass
+/// through untouched.
+pub async fn problem_details_middleware(
+    State(format): State<ErrorFormat>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if format == ErrorFormat::Legacy {
+        return next.run(request).await;
+    }
+
+    let instance = request.uri().path().to_string();
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone())
+        .or_else(|| header_value(request.headers().get(REQUEST_ID_HEADER)));
+
+    let response = next.run(request).await;
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) || is_problem(&response) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let fits = body
+        .size_hint()
+        .upper()
+        .is_some_and(|size| size <= MAX_PROBLEM_BODY_BYTES);
+    if !fits {
+        return Response::from_parts(parts, body);
+    }
+
+    let bytes = match axum::body::to_bytes(body, MAX_PROBLEM_BODY_BYTES as usize).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("Failed to buffer error response for problem details: {}", e);
+            Bytes::new()
+        }
+    };
+
+    let mut problem = Problem::from_legacy_body(status, &bytes).with_instance(instance);
+    if problem.request_id.is_none() {
+        problem.request_id = header_value(parts.headers.get(REQUEST_ID_HEADER)).or(request_id);
+    }
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(PROBLEM_CONTENT_TYPE),
+    );
+    let body = serde_json::to_vec(&problem).unwrap_or_default();
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Returns the reason phrase of `status`, such as "Not Found"
+fn title(status: StatusCode) -> String {
+    status.canonical_reason().unwrap_or("Error").to_string()
+}
+
+/// Returns the error code of responses that do not name one
+fn status_code(status: StatusCode) -> String {
+    if status == StatusCode::INTERNAL_SERVER_ERROR {
+        return "internal_error".to_string();
+    }
+
+    title(status)
+        .to_lowercase()
+        .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+}
+
+/// Returns true if `value` reads like an error code rather than a sentence
+fn is_code(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+fn is_problem(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(PROBLEM_CONTENT_TYPE))
+}
+
+fn header_value(value: Option<&HeaderValue>) -> Option<String> {
+    value
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::middleware::jwt::AuthError;
+    use crate::api::middleware::localize_errors_middleware;
+    use crate::api::middleware::rbac::RbacError;
+    use crate::api::middleware::validation::{
+        content_negotiation_middleware, ContentNegotiationConfig, FieldError,
+        ValidationErrorResponse,
+    };
+    use crate::api::rest::dtos::ErrorResponse;
+    use crate::error::DomainError;
+    use crate::i18n::Message;
+    use axum::{
+        extract::DefaultBodyLimit,
+        middleware,
+        routing::{get, post},
+        Json, Router,
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn error(status: StatusCode, code: &str, message: &str) -> Response {
+        (
+            status,
+            Json(ErrorResponse::new(code.to_string(), message.to_string())),
+        )
+            .into_response()
+    }
+
+    fn routes() -> Router {
+        Router::new()
+            .route(
+                "/validation",
+                get(|| async {
+                    let error = DomainError::ValidationError {
+                        field: "limit".to_string(),
+                        message: Message::new("validation.limit_range").with("max", 1000),
+                    };
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(ErrorResponse::from_domain(
+                            "validation_error".to_string(),
+                            &error,
+                        )),
+                    )
+                }),
+            )
+            .route(
+                "/fields",
+                get(|| async {
+                    ValidationErrorResponse {
+                        error: "Validation failed".to_string(),
+                        field_errors: vec![FieldError {
+                            field: "name".to_string(),
+                            message: "Name is required".to_string(),
+                            code: "required".to_string(),
+                        }],
+                    }
+                }),
+            )
+            .route("/unauthorized", get(|| async { AuthError::MissingToken }))
+            .route(
+                "/forbidden",
+                get(|| async {
+                    RbacError::Forbidden {
+                        required_permission: "event:read".to_string(),
+                        user_permissions: vec![],
+                    }
+                }),
+            )
+            .route(
+                "/missing",
+                get(|| async { error(StatusCode::NOT_FOUND, "not_found", "Event not found") }),
+            )
+            .route(
+                "/conflict",
+                get(|| async {
+                    error(
+                        StatusCode::CONFLICT,
+                        "conflict",
+                        "Receiver was modified concurrently",
+                    )
+                }),
+            )
+            .route(
+                "/upload",
+                post(|_: Bytes| async { StatusCode::OK }).layer(DefaultBodyLimit::max(8)),
+            )
+            .route(
+                "/throttled",
+                get(|| async {
+                    (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        [(header::RETRY_AFTER, "30")],
+                        "Rate limit exceeded",
+                    )
+                }),
+            )
+            .route(
+                "/broken",
+                get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+            )
+            .route(
+                "/unavailable",
+                get(|| async {
+                    error(
+                        StatusCode::SERVICE_UNAVAILABLE,
+                        "search_unavailable",
+                        "Search is not configured",
+                    )
+                }),
+            )
+            .route(
+                "/slow",
+                get(|| async { (StatusCode::GATEWAY_TIMEOUT, "Upstream timed out") }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Arc::new(ContentNegotiationConfig::default()),
+                content_negotiation_middleware,
+            ))
+            .layer(middleware::from_fn(localize_errors_middleware))
+    }
+
+    fn app(format: ErrorFormat) -> Router {
+        routes().layer(middleware::from_fn_with_state(
+            format,
+            problem_details_middleware,
+        ))
+    }
+
+    fn get_request(uri: &str) -> Request {
+        Request::builder()
+            .uri(uri)
+            .header(REQUEST_ID_HEADER, "req-1")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn post_request(uri: &str, content_type: &str, body: &'static str) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(header::CONTENT_TYPE, content_type)
+            .header(REQUEST_ID_HEADER, "req-1")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn fetch_problem(request: Request) -> (Response, Problem) {
+        let response = app(ErrorFormat::Problem).oneshot(request).await.unwrap();
+        let (parts, body) = response.into_parts();
+        assert_eq!(parts.headers[header::CONTENT_TYPE], PROBLEM_CONTENT_TYPE);
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        (
+            Response::from_parts(parts, Body::empty()),
+            serde_json::from_slice(&bytes).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_every_error_class_is_a_problem_document() {
+        let cases = [
+            (get_request("/validation"), 400, "validation_error"),
+            (get_request("/unauthorized"), 401, "unauthorized"),
+            (get_request("/forbidden"), 403, "forbidden"),
+            (get_request("/missing"), 404, "not_found"),
+            (get_request("/conflict"), 409, "conflict"),
+            (
+                post_request("/upload", "application/json", "0123456789"),
+                413,
+                "payload_too_large",
+            ),
+            (
+                post_request("/upload", "text/plain", "x"),
+                415,
+                "unsupported_media_type",
+            ),
+            (get_request("/throttled"), 429, "too_many_requests"),
+            (get_request("/broken"), 500, "internal_error"),
+            (get_request("/unavailable"), 503, "search_unavailable"),
+            (get_request("/slow"), 504, "gateway_timeout"),
+        ];
+
+        for (request, status, code) in cases {
+            let path = request.uri().path().to_string();
+            let (response, problem) = fetch_problem(request).await;
+
+            assert_eq!(response.status().as_u16(), status, "{}", path);
+            assert_eq!(problem.status, status, "{}", path);
+            assert_eq!(problem.code, code, "{}", path);
+            assert_eq!(
+                problem.problem_type,
+                format!("{}{}", PROBLEM_TYPE_BASE, code)
+            );
+            assert_eq!(
+                problem.title,
+                response.status().canonical_reason().unwrap(),
+                "{}",
+                path
+            );
+            assert!(!problem.detail.is_empty(), "{}", path);
+            assert_eq!(problem.instance.as_deref(), Some(path.as_str()));
+            assert_eq!(problem.request_id.as_deref(), Some("req-1"), "{}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_field_errors_keep_their_message_keys() {
+        let (response, problem) = fetch_problem(get_request("/validation")).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(problem.detail, "Limit must be between 1 and 1000");
+        assert_eq!(
+            problem.field_errors,
+            [ProblemFieldError {
+                field: "limit".to_string(),
+                message: "Limit must be between 1 and 1000".to_string(),
+                message_key: Some("validation.limit_range".to_string()),
+            }]
+        );
+
+        let (_, problem) = fetch_problem(get_request("/fields")).await;
+        assert_eq!(problem.code, "validation_error");
+        assert_eq!(problem.detail, "Validation failed");
+        assert_eq!(problem.field_errors[0].field, "name");
+        assert_eq!(problem.field_errors[0].message_key, None);
+    }
+
+    #[tokio::test]
+    async fn test_detail_is_localized_and_headers_are_kept() {
+        let mut request = get_request("/validation");
+        request
+            .headers_mut()
+            .insert(header::ACCEPT_LANGUAGE, HeaderValue::from_static("de"));
+        let (response, problem) = fetch_problem(request).await;
+        assert_eq!(response.headers()[header::CONTENT_LANGUAGE], "de");
+        assert_eq!(problem.detail, "Das Limit muss zwischen 1 und 1000 liegen");
+
+        let (response, problem) = fetch_problem(get_request("/throttled")).await;
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+        assert_eq!(problem.detail, "Rate limit exceeded");
+    }
+
+    #[tokio::test]
+    async fn test_legacy_format_keeps_bodies_byte_for_byte() {
+        for uri in [
+            "/validation",
+            "/fields",
+            "/unauthorized",
+            "/forbidden",
+            "/throttled",
+        ] {
+            let legacy = app(ErrorFormat::Legacy)
+                .oneshot(get_request(uri))
+                .await
+                .unwrap();
+            let original = routes().oneshot(get_request(uri)).await.unwrap();
+
+            assert_eq!(legacy.status(), original.status());
+            assert_eq!(
+                legacy.headers().get(header::CONTENT_TYPE),
+                original.headers().get(header::CONTENT_TYPE)
+            );
+            let legacy = axum::body::to_bytes(legacy.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let original = axum::body::to_bytes(original.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(legacy, original, "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_successful_responses_are_unchanged() {
+        let app = Router::new()
+            .route(
+                "/ok",
+                get(|| async { Json(serde_json::json!({"ok": true})) }),
+            )
+            .layer(middleware::from_fn_with_state(
+                ErrorFormat::Problem,
+                problem_details_middleware,
+            ));
+
+        let response = app.oneshot(get_request("/ok")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    }
+}
@@ -7,7 +7,7 @@
 //! enforcement on REST API routes based on HTTP method and path.
 
 use axum::{
-    extract::{Request, State},
+    extract::{MatchedPath, Request, State},
     http::StatusCode,
     middleware::Next,
     response::{IntoResponse, Response},
@@ -18,8 +18,13 @@ use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, warn};
 
+use super::api_key::ApiKeyPrincipal;
 use super::jwt::AuthenticatedUser;
-use super::rbac_helpers::route_to_permission;
+use super::rbac_helpers::{
+    restricted_under_impersonation, restricted_under_scoped_key, route_rule, RouteAccess, RouteRule,
+};
+use crate::auth::api_key::ApiKeyScope;
+use crate::auth::rbac::Permission;
 use crate::infrastructure::{AuditLogger, PrometheusMetrics};
 
 /// State for RBAC middleware with audit logging and metrics
@@ -59,20 +64,22 @@ impl Default for RbacMiddlewareState {
 
 /// RBAC enforcement middleware
 ///
-/// This middleware automatically determines the required permission based on
-/// the HTTP method and route path, then checks if the authenticated user
-/// has that permission.
+/// This middleware looks up the route's rule in
+/// [`ROUTE_RULES`](super::rbac_helpers::ROUTE_RULES), then checks that the
+/// authenticated user holds the permission and role it declares.
 ///
 /// **Prerequisites**: This middleware must be applied AFTER jwt_auth_middleware,
 /// as it depends on AuthenticatedUser being present in request extensions.
 ///
 /// # How It Works
 ///
-/// 1. Checks if the route is public (health checks, etc.) - if so, allows access
-/// 2. Extracts the AuthenticatedUser from request extensions
-/// 3. Determines required permission based on method + path
-/// 4. Checks if user has the required permission via their roles
-/// 5. Returns 403 Forbidden if permission check fails
+/// 1. Refuses token, password, and API key operations to impersonation
+///    tokens, and everything but ingestion to scoped API keys
+/// 2. Looks up the rule of the matched route; public routes are allowed
+///    through and routes without a rule are refused
+/// 3. Extracts the AuthenticatedUser from request extensions
+/// 4. Checks the role and permission the rule declares
+/// 5. Returns 403 Forbidden if either check fails
 ///
 /// # Examples
 ///
@@ -93,18 +100,13 @@ pub async fn rbac_enforcement_middleware(
     let method = request.method().clone();
     let path = request.uri().path();
 
-    // Determine required permission
-    let required_permission = match route_to_permission(&method, path) {
-        Some(perm) => perm,
-        None => {
-            // Public route or unmapped route - allow through
-            debug!(
-                method = %method,
-                path = %path,
-                "Route is public or unmapped, allowing access"
-            );
-            return Ok(next.run(request).await);
-        }
+    check_impersonation(&request, &method, path)?;
+    check_scoped_key(&request, &method, path)?;
+
+    // Determine the declared rule
+    let rule = match declared_rule(&request)? {
+        Some(rule) => rule,
+        None => return Ok(next.run(request).await),
     };
 
     // Extract authenticated user
@@ -120,28 +122,23 @@ pub async fn rbac_enforcement_middleware(
             RbacError::Unauthorized
         })?;
 
-    // Check permission
-    let permission_str = format!("{:?}", required_permission);
-    let has_permission = user.has_permission(&permission_str);
-
-    if !has_permission {
+    // Check role and permission
+    if let Err(e) = authorize_rule(user, rule) {
         warn!(
             user_id = %user.user_id(),
-            required_permission = ?required_permission,
+            required_permission = ?rule.access.permission(),
+            required_role = ?rule.role,
             user_permissions = ?user.claims.permissions,
             method = %method,
             path = %path,
-            "Access denied: user lacks required permission"
+            "Access denied: user lacks required permission or role"
         );
-        return Err(RbacError::Forbidden {
-            required_permission: permission_str,
-            user_permissions: user.claims.permissions.clone(),
-        });
+        return Err(e);
     }
 
     debug!(
         user_id = %user.user_id(),
-        permission = ?required_permission,
+        permission = ?rule.access.permission(),
         method = %method,
         path = %path,
         "Access granted"
@@ -150,6 +147,129 @@ pub async fn rbac_enforcement_middleware(
     Ok(next.run(request).await)
 }
 
+/// Finds the rule the caller must satisfy for `request`
+///
+/// Returns `None` for public routes, which are allowed through, and
+/// refuses routes missing from `ROUTE_RULES` rather than leaving them
+/// unguarded.
+fn declared_rule(request: &Request) -> Result<Option<&'static RouteRule>, RbacError> {
+    match matched_rule(request) {
+        Some(rule) if rule.access == RouteAccess::Public => {
+            debug!(
+                method = %request.method(),
+                path = %request.uri().path(),
+                "Route is public, allowing access"
+            );
+            Ok(None)
+        }
+        Some(rule) => Ok(Some(rule)),
+        None => {
+            warn!(
+                method = %request.method(),
+                path = %request.uri().path(),
+                "Access denied: route has no declared access rule"
+            );
+            Err(RbacError::UndeclaredRoute)
+        }
+    }
+}
+
+/// Finds the declared rule of the route serving `request`
+///
+/// Uses the matched route pattern when the router recorded it, and the
+/// request path otherwise, e.g. when the router is nested under a prefix.
+fn matched_rule(request: &Request) -> Option<&'static RouteRule> {
+    let method = request.method();
+    request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|matched| route_rule(method, matched.as_str()))
+        .or_else(|| route_rule(method, request.uri().path()))
+}
+
+/// Checks that `user` holds the role and permission `rule` declares
+pub fn authorize_rule(user: &AuthenticatedUser, rule: &RouteRule) -> Result<(), RbacError> {
+    if let Some(role) = rule.role {
+        if !user.has_role(role) {
+            return Err(RbacError::MissingRole {
+                required_role: role.to_string(),
+            });
+        }
+    }
+    match rule.access.permission() {
+        Some(permission) => authorize_permission(user, permission),
+        None => Ok(()),
+    }
+}
+
+/// Checks that `user` holds `permission`
+///
+/// This is the check [`rbac_enforcement_middleware`] applies. Handlers
+/// that report on access, such as receiver diagnosis, call it directly so
+/// the report matches enforcement.
+pub fn authorize_permission(
+    user: &AuthenticatedUser,
+    permission: Permission,
+) -> Result<(), RbacError> {
+    let permission_str = format!("{:?}", permission);
+    if user.has_permission(&permission_str) {
+        Ok(())
+    } else {
+        Err(RbacError::Forbidden {
+            required_permission: permission_str,
+            user_permissions: user.claims.permissions.clone(),
+        })
+    }
+}
+
+/// Refuses restricted operations to impersonation tokens
+///
+/// Runs before the public route check, since the restricted routes are not
+/// mapped to permissions.
+fn check_impersonation(
+    request: &Request,
+    method: &axum::http::Method,
+    path: &str,
+) -> Result<(), RbacError> {
+    let Some(user) = request.extensions().get::<AuthenticatedUser>() else {
+        return Ok(());
+    };
+    match user.actor_id() {
+        Some(actor_id) if restricted_under_impersonation(method, path) => {
+            warn!(
+                user_id = %user.user_id(),
+                actor_id = %actor_id,
+                method = %method,
+                path = %path,
+                "Access denied: operation not allowed while impersonating"
+            );
+            Err(RbacError::Impersonating)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Refuses everything but ingestion to receiver- and group-scoped API keys
+fn check_scoped_key(
+    request: &Request,
+    method: &axum::http::Method,
+    path: &str,
+) -> Result<(), RbacError> {
+    let Some(principal) = request.extensions().get::<ApiKeyPrincipal>() else {
+        return Ok(());
+    };
+    if principal.scope == ApiKeyScope::Unscoped || !restricted_under_scoped_key(method, path) {
+        return Ok(());
+    }
+    warn!(
+        key_id = %principal.key_id,
+        method = %method,
+        path = %path,
+        "Access denied: operation not allowed with a scoped API key"
+    );
+    Err(RbacError::ScopedApiKey)
+}
+
 /// RBAC enforcement middleware with audit logging and metrics
 ///
 /// Enhanced version that logs permission checks and records metrics.
@@ -162,17 +282,41 @@ pub async fn rbac_enforcement_middleware_with_state(
     let method = request.method().clone();
     let path = request.uri().path().to_string();
 
-    // Determine required permission
-    let required_permission = match route_to_permission(&method, &path) {
-        Some(perm) => perm,
-        None => {
-            // Public route or unmapped route - allow through
-            debug!(
-                method = %method,
-                path = %path,
-                "Route is public or unmapped, allowing access"
-            );
-            return Ok(next.run(request).await);
+    let restricted = check_impersonation(&request, &method, &path)
+        .map_err(|e| (e, "impersonation"))
+        .and_then(|()| {
+            check_scoped_key(&request, &method, &path).map_err(|e| (e, "scoped_api_key"))
+        });
+    if let Err((e, reason)) = restricted {
+        if let (Some(audit_logger), Some(user)) = (
+            &state.audit_logger,
+            request.extensions().get::<AuthenticatedUser>(),
+        ) {
+            audit_logger.log_event(crate::infrastructure::AuditEvent::permission_denied(
+                user.user_id(),
+                &path,
+                reason,
+            ));
+        }
+        return Err(e);
+    }
+
+    // Determine the declared rule
+    let rule = match declared_rule(&request) {
+        Ok(Some(rule)) => rule,
+        Ok(None) => return Ok(next.run(request).await),
+        Err(e) => {
+            if let (Some(audit_logger), Some(user)) = (
+                &state.audit_logger,
+                request.extensions().get::<AuthenticatedUser>(),
+            ) {
+                audit_logger.log_event(crate::infrastructure::AuditEvent::permission_denied(
+                    user.user_id(),
+                    &path,
+                    "undeclared_route",
+                ));
+            }
+            return Err(e);
         }
     };
 
@@ -190,18 +334,22 @@ pub async fn rbac_enforcement_middleware_with_state(
         })?;
 
     let user_id = user.user_id().to_string();
-    let permission_str = format!("{:?}", required_permission);
-    let has_permission = user.has_permission(&permission_str);
+    let permission_str = match (rule.access.permission(), rule.role) {
+        (Some(permission), _) => format!("{:?}", permission),
+        (None, Some(role)) => format!("role:{}", role),
+        (None, None) => "authenticated".to_string(),
+    };
 
-    // Check permission
-    if !has_permission {
+    // Check role and permission
+    if let Err(e) = authorize_rule(user, rule) {
         warn!(
             user_id = %user_id,
-            required_permission = ?required_permission,
+            required_permission = ?rule.access.permission(),
+            required_role = ?rule.role,
             user_permissions = ?user.claims.permissions,
             method = %method,
             path = %path,
-            "Access denied: user lacks required permission"
+            "Access denied: user lacks required permission or role"
         );
 
         // Log permission denial
@@ -219,15 +367,12 @@ pub async fn rbac_enforcement_middleware_with_state(
             metrics.record_permission_check(false, &permission_str);
         }
 
-        return Err(RbacError::Forbidden {
-            required_permission: permission_str,
-            user_permissions: user.claims.permissions.clone(),
-        });
+        return Err(e);
     }
 
     debug!(
         user_id = %user_id,
-        permission = ?required_permission,
+        permission = ?rule.access.permission(),
         method = %method,
         path = %path,
         "Access granted"
@@ -259,6 +404,14 @@ pub enum RbacError {
         required_permission: String,
         user_permissions: Vec<String>,
     },
+    /// User is authenticated but lacks the role the route is gated on
+    MissingRole { required_role: String },
+    /// Operation is not allowed with an impersonation token
+    Impersonating,
+    /// Operation is not allowed with a receiver- or group-scoped API key
+    ScopedApiKey,
+    /// Route has no rule in `ROUTE_RULES`
+    UndeclaredRoute,
 }
 
 impl IntoResponse for RbacError {
@@ -283,6 +436,26 @@ impl IntoResponse for RbacError {
                     "user_permissions": user_permissions,
                 })),
             ),
+            RbacError::MissingRole { required_role } => (
+                StatusCode::FORBIDDEN,
+                format!("Access denied: missing required role '{}'", required_role),
+                Some(json!({ "required_role": required_role })),
+            ),
+            RbacError::Impersonating => (
+                StatusCode::FORBIDDEN,
+                "Access denied: not allowed while impersonating a user".to_string(),
+                None,
+            ),
+            RbacError::ScopedApiKey => (
+                StatusCode::FORBIDDEN,
+                "Access denied: scoped API keys may only submit events".to_string(),
+                None,
+            ),
+            RbacError::UndeclaredRoute => (
+                StatusCode::FORBIDDEN,
+                "Access denied: route has no declared access rule".to_string(),
+                None,
+            ),
         };
 
         let mut body = json!({
@@ -310,6 +483,22 @@ impl std::fmt::Display for RbacError {
                 "Access denied: missing required permission '{}'",
                 required_permission
             ),
+            RbacError::MissingRole { required_role } => {
+                write!(
+                    f,
+                    "Access denied: missing required role '{}'",
+                    required_role
+                )
+            }
+            RbacError::Impersonating => {
+                write!(f, "Access denied: not allowed while impersonating a user")
+            }
+            RbacError::ScopedApiKey => {
+                write!(f, "Access denied: scoped API keys may only submit events")
+            }
+            RbacError::UndeclaredRoute => {
+                write!(f, "Access denied: route has no declared access rule")
+            }
         }
     }
 }
@@ -468,14 +657,86 @@ mod tests {
     #[tokio::test]
     async fn test_rbac_delete_requires_delete_permission() {
         let app = Router::new()
-            .route("/api/v1/events/:id", delete(test_delete_handler))
+            .route("/api/v1/receivers/:id", delete(test_delete_handler))
             .layer(middleware::from_fn(rbac_enforcement_middleware));
 
-        let claims = create_claims_with_permissions(vec!["EventDelete".to_string()]);
-        let request = create_test_request(Method::DELETE, "/api/v1/events/123", Some(claims)).await;
+        let claims = create_claims_with_permissions(vec!["ReceiverDelete".to_string()]);
+        let request =
+            create_test_request(Method::DELETE, "/api/v1/receivers/123", Some(claims)).await;
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rbac_denies_without_declared_role() {
+        let app = Router::new()
+            .route("/api/v1/admin/about", get(test_handler))
+            .route_layer(middleware::from_fn(rbac_enforcement_middleware));
+
+        let claims = create_claims_with_permissions(vec!["UserManage".to_string()]);
+        let request = create_test_request(Method::GET, "/api/v1/admin/about", Some(claims)).await;
 
         let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_rbac_denies_undeclared_routes() {
+        let app = Router::new()
+            .route("/api/v1/receivers/:id", axum::routing::patch(test_handler))
+            .route_layer(middleware::from_fn(rbac_enforcement_middleware));
+
+        let claims = create_claims_with_permissions(vec!["ReceiverUpdate".to_string()]);
+        let request =
+            create_test_request(Method::PATCH, "/api/v1/receivers/123", Some(claims)).await;
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_rbac_limits_scoped_api_keys_to_ingestion() {
+        use crate::domain::value_objects::{ApiKeyId, EventReceiverId};
+
+        let app = Router::new()
+            .route("/api/v1/events", post(test_post_handler))
+            .route("/api/v1/api-keys/:id/rotate", post(test_post_handler))
+            .route("/api/v1/me/preferences", get(test_handler))
+            .route("/api/v1/me/sessions", get(test_handler))
+            .route_layer(middleware::from_fn(rbac_enforcement_middleware));
+        let send = |method: Method, path: &str, scope: ApiKeyScope| {
+            let claims = create_claims_with_permissions(vec!["EventCreate".to_string()]);
+            let mut request = Request::builder()
+                .method(method)
+                .uri(path)
+                .body(Body::empty())
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(AuthenticatedUser::new(claims));
+            request.extensions_mut().insert(ApiKeyPrincipal {
+                key_id: ApiKeyId::new(),
+                scope,
+            });
+            app.clone().oneshot(request)
+        };
+        let scoped = ApiKeyScope::Receiver(EventReceiverId::new());
+
+        let response = send(Method::POST, "/api/v1/events", scoped).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
+        for (method, path) in [
+            (Method::POST, "/api/v1/api-keys/01J/rotate"),
+            (Method::GET, "/api/v1/me/preferences"),
+            (Method::GET, "/api/v1/me/sessions"),
+        ] {
+            let response = send(method.clone(), path, scoped).await.unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{}", path);
+
+            // The same owner's unscoped key is not restricted
+            let response = send(method, path, ApiKeyScope::Unscoped).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", path);
+        }
     }
 
     #[test]
@@ -3,16 +3,434 @@
 
 //! RBAC Helper Functions for REST API
 //!
-//! This module provides helper functions for mapping HTTP methods and routes
-//! to required permissions for RBAC enforcement in REST endpoints.
+//! This module declares the access rule of every mounted route in
+//! [`ROUTE_RULES`] and of every GraphQL operation in
+//! [`GRAPHQL_OPERATION_RULES`]. The router mounts REST routes through the
+//! table, the RBAC middleware enforces it, and the RBAC matrix export
+//! renders it, so the three cannot drift apart.
 
 use crate::auth::rbac::permissions::Permission;
 use axum::http::Method;
 
+/// Role every administrative route is gated on
+pub const ADMIN_ROLE: &str = "admin";
+
+/// What a caller needs to reach a route or operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteAccess {
+    /// Anyone, with or without credentials
+    Public,
+    /// Any authenticated caller; handlers may narrow access further
+    Authenticated,
+    /// An authenticated caller holding the permission
+    Permission(Permission),
+}
+
+impl RouteAccess {
+    /// Returns the permission the access requires, if any
+    pub fn permission(&self) -> Option<Permission> {
+        match self {
+            RouteAccess::Permission(permission) => Some(*permission),
+            _ => None,
+        }
+    }
+}
+
+/// Declared access rule of a mounted REST route
+#[derive(Debug, Clone)]
+pub struct RouteRule {
+    /// HTTP method the rule applies to
+    pub method: Method,
+    /// Route pattern as mounted, e.g. `/api/v1/receivers/:id`
+    pub path: &'static str,
+    /// Authentication and permission the route requires
+    pub access: RouteAccess,
+    /// Role the caller must also hold, if any
+    pub role: Option<&'static str>,
+}
+
+const fn public(method: Method, path: &'static str) -> RouteRule {
+    RouteRule {
+        method,
+        path,
+        access: RouteAccess::Public,
+        role: None,
+    }
+}
+
+const fn authenticated(method: Method, path: &'static str) -> RouteRule {
+    RouteRule {
+        method,
+        path,
+        access: RouteAccess::Authenticated,
+        role: None,
+    }
+}
+
+const fn admin(method: Method, path: &'static str) -> RouteRule {
+    RouteRule {
+        method,
+        path,
+        access: RouteAccess::Authenticated,
+        role: Some(ADMIN_ROLE),
+    }
+}
+
+const fn permission(method: Method, path: &'static str, permission: Permission) -> RouteRule {
+    RouteRule {
+        method,
+        path,
+        access: RouteAccess::Permission(permission),
+        role: None,
+    }
+}
+
+/// Access rules of every route the API mounts
+///
+/// The REST router refuses to mount a route missing from this table, and
+/// the RBAC middleware refuses requests to one. Routes only reachable by
+/// authenticated callers list the permission or role the RBAC middleware
+/// checks; handlers may apply ownership checks on top, such as API keys
+/// readable by their owner. Event updates and deletes and group `PATCH`
+/// are declared although no bundled router mounts them.
+pub static ROUTE_RULES: &[RouteRule] = &[
+    public(Method::GET, "/health"),
+    public(Method::POST, "/graphql"),
+    public(Method::GET, "/graphql/health"),
+    public(Method::GET, "/graphql/playground"),
+    public(Method::POST, "/api/v1/auth/invitations/accept"),
+    // Events
+    permission(Method::POST, "/api/v1/events", Permission::EventCreate),
+    permission(
+        Method::POST,
+        "/api/v1/events/batch",
+        Permission::EventCreate,
+    ),
+    permission(Method::GET, "/api/v1/events/export", Permission::EventRead),
+    permission(Method::GET, "/api/v1/events/poll", Permission::EventRead),
+    permission(Method::GET, "/api/v1/events/:id", Permission::EventRead),
+    // Events are immutable, so the bundled routers mount neither of these
+    permission(Method::PUT, "/api/v1/events/:id", Permission::EventUpdate),
+    permission(
+        Method::DELETE,
+        "/api/v1/events/:id",
+        Permission::EventDelete,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/events/:id/diff/:other_id",
+        Permission::EventRead,
+    ),
+    // Adding or removing attachments changes the event
+    permission(
+        Method::POST,
+        "/api/v1/events/:id/attachments",
+        Permission::EventUpdate,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/events/:id/attachments/:attachment_id",
+        Permission::EventRead,
+    ),
+    permission(
+        Method::DELETE,
+        "/api/v1/events/:id/attachments/:attachment_id",
+        Permission::EventUpdate,
+    ),
+    // Attestation lookups return the attesting events
+    permission(
+        Method::GET,
+        "/api/v1/attestations/by-digest/:digest",
+        Permission::EventRead,
+    ),
+    // Administration
+    permission(
+        Method::GET,
+        "/api/v1/admin/events",
+        Permission::EventReadMeta,
+    ),
+    admin(Method::GET, "/api/v1/admin/debug/kafka"),
+    admin(Method::GET, "/api/v1/admin/deprecations"),
+    admin(Method::GET, "/api/v1/admin/maintenance"),
+    admin(Method::PUT, "/api/v1/admin/maintenance"),
+    admin(Method::POST, "/api/v1/admin/config/reload"),
+    admin(Method::PUT, "/api/v1/admin/flags/:name"),
+    admin(Method::GET, "/api/v1/admin/graphql/persisted-queries"),
+    admin(Method::POST, "/api/v1/admin/graphql/persisted-queries"),
+    admin(
+        Method::DELETE,
+        "/api/v1/admin/graphql/persisted-queries/:hash",
+    ),
+    admin(Method::GET, "/api/v1/admin/summary"),
+    admin(Method::POST, "/api/v1/admin/bulk-delete"),
+    admin(Method::GET, "/api/v1/admin/about"),
+    admin(Method::GET, "/api/v1/admin/jobs"),
+    admin(Method::POST, "/api/v1/admin/jobs/:name/run"),
+    admin(Method::GET, "/api/v1/admin/delivery-failures"),
+    admin(Method::GET, "/api/v1/admin/audit/verify"),
+    admin(Method::POST, "/api/v1/admin/impersonate"),
+    admin(Method::GET, "/api/v1/admin/sessions"),
+    admin(Method::DELETE, "/api/v1/admin/sessions/:id"),
+    admin(Method::GET, "/api/v1/admin/rbac/matrix"),
+    admin(Method::POST, "/api/v1/admin/users/import"),
+    admin(Method::GET, "/api/v1/admin/users/import/:job_id"),
+    admin(Method::POST, "/api/v1/admin/users/:id/invitation"),
+    // The caller's own data
+    authenticated(Method::GET, "/api/v1/search"),
+    authenticated(Method::GET, "/api/v1/flags"),
+    authenticated(Method::GET, "/api/v1/me/preferences"),
+    authenticated(Method::PUT, "/api/v1/me/preferences"),
+    authenticated(Method::GET, "/api/v1/me/sessions"),
+    authenticated(Method::DELETE, "/api/v1/me/sessions/:id"),
+    authenticated(Method::GET, "/api/v1/api-keys/:id"),
+    authenticated(Method::POST, "/api/v1/api-keys/:id/rotate"),
+    authenticated(Method::GET, "/api/v1/api-keys/:id/usage"),
+    // Event receivers
+    permission(
+        Method::POST,
+        "/api/v1/receivers",
+        Permission::ReceiverCreate,
+    ),
+    permission(Method::GET, "/api/v1/receivers", Permission::ReceiverRead),
+    permission(
+        Method::GET,
+        "/api/v1/receivers/changes",
+        Permission::ReceiverRead,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/receivers/:id",
+        Permission::ReceiverRead,
+    ),
+    permission(
+        Method::PUT,
+        "/api/v1/receivers/:id",
+        Permission::ReceiverUpdate,
+    ),
+    permission(
+        Method::DELETE,
+        "/api/v1/receivers/:id",
+        Permission::ReceiverDelete,
+    ),
+    // Heartbeats come from the same producers that send events
+    permission(
+        Method::POST,
+        "/api/v1/receivers/:id/heartbeat",
+        Permission::EventCreate,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/receivers/:id/description/html",
+        Permission::ReceiverRead,
+    ),
+    permission(
+        Method::POST,
+        "/api/v1/receivers/:id/clone",
+        Permission::ReceiverCreate,
+    ),
+    // Diagnosis reports on the caller's own access, so a caller missing a
+    // permission still gets the report
+    authenticated(Method::POST, "/api/v1/receivers/:id/diagnose"),
+    permission(
+        Method::GET,
+        "/api/v1/receivers/:id/timeline",
+        Permission::ReceiverRead,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/receivers/:id/pii-report",
+        Permission::ReceiverRead,
+    ),
+    // Schema previews read stored events and never change the receiver
+    permission(
+        Method::POST,
+        "/api/v1/receivers/:id/schema/preview",
+        Permission::ReceiverRead,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/receivers/:id/schema/preview/:job_id",
+        Permission::ReceiverRead,
+    ),
+    // Event receiver groups
+    permission(Method::POST, "/api/v1/groups", Permission::GroupCreate),
+    permission(Method::GET, "/api/v1/groups", Permission::GroupRead),
+    permission(Method::GET, "/api/v1/groups/changes", Permission::GroupRead),
+    permission(Method::GET, "/api/v1/groups/:id", Permission::GroupRead),
+    permission(Method::PUT, "/api/v1/groups/:id", Permission::GroupUpdate),
+    permission(Method::PATCH, "/api/v1/groups/:id", Permission::GroupUpdate),
+    permission(
+        Method::DELETE,
+        "/api/v1/groups/:id",
+        Permission::GroupDelete,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/groups/:id/description/html",
+        Permission::GroupRead,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/groups/:id/completeness",
+        Permission::GroupRead,
+    ),
+    permission(
+        Method::PUT,
+        "/api/v1/groups/:id/receivers/:receiver_id/default-schema",
+        Permission::GroupUpdate,
+    ),
+    // Group keys are credentials, so they have their own permission rather
+    // than riding on group updates
+    permission(
+        Method::POST,
+        "/api/v1/groups/:id/keys",
+        Permission::GroupManageKeys,
+    ),
+    permission(
+        Method::GET,
+        "/api/v1/groups/:id/keys",
+        Permission::GroupManageKeys,
+    ),
+    permission(
+        Method::DELETE,
+        "/api/v1/groups/:id/keys/:key_id",
+        Permission::GroupManageKeys,
+    ),
+];
+
+/// Finds the rule of the route serving `method` and `path`
+///
+/// `path` may be a mounted pattern, as reported by axum's `MatchedPath`,
+/// or a concrete request path. Literal segments win over parameters, so
+/// `/api/v1/receivers/changes` is not taken for `/api/v1/receivers/:id`.
+///
+/// # Examples
+///
+/// ```
+/// use axum::http::Method;
+/// use xzepr::api::middleware::rbac_helpers::route_rule;
+///
+/// let rule = route_rule(&Method::PUT, "/api/v1/receivers/01J").unwrap();
+/// assert_eq!(rule.path, "/api/v1/receivers/:id");
+/// assert!(route_rule(&Method::PATCH, "/api/v1/receivers/01J").is_none());
+/// ```
+pub fn route_rule(method: &Method, path: &str) -> Option<&'static RouteRule> {
+    let rules = ROUTE_RULES.iter().filter(|rule| rule.method == *method);
+    if let Some(rule) = rules.clone().find(|rule| rule.path == path) {
+        return Some(rule);
+    }
+    rules
+        .filter_map(|rule| pattern_match(rule.path, path).map(|literals| (literals, rule)))
+        .max_by_key(|(literals, _)| *literals)
+        .map(|(_, rule)| rule)
+}
+
+/// Returns the number of literal segments if `path` matches `pattern`
+fn pattern_match(pattern: &str, path: &str) -> Option<usize> {
+    let mut pattern_segments = pattern.split('/');
+    let mut path_segments = path.split('/');
+    let mut literals = 0;
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return Some(literals),
+            (Some(expected), Some(actual)) if expected.starts_with(':') => {
+                if actual.is_empty() {
+                    return None;
+                }
+            }
+            (Some(expected), Some(actual)) if expected == actual => literals += 1,
+            _ => return None,
+        }
+    }
+}
+
+/// Kind of GraphQL root operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphQLOperationKind {
+    Query,
+    Mutation,
+}
+
+impl std::fmt::Display for GraphQLOperationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphQLOperationKind::Query => write!(f, "query"),
+            GraphQLOperationKind::Mutation => write!(f, "mutation"),
+        }
+    }
+}
+
+/// Declared access rule of a GraphQL root field
+#[derive(Debug, Clone, Copy)]
+pub struct GraphQLOperationRule {
+    /// Root type the field belongs to
+    pub kind: GraphQLOperationKind,
+    /// Field name as exposed in the schema
+    pub field: &'static str,
+    /// What the resolver requires before running
+    pub access: RouteAccess,
+    /// Resource-level check the resolver applies on top, e.g. `update group`
+    pub resource_check: Option<&'static str>,
+}
+
+const fn query(field: &'static str, access: RouteAccess) -> GraphQLOperationRule {
+    GraphQLOperationRule {
+        kind: GraphQLOperationKind::Query,
+        field,
+        access,
+        resource_check: None,
+    }
+}
+
+const fn mutation(
+    field: &'static str,
+    resource_check: Option<&'static str>,
+) -> GraphQLOperationRule {
+    GraphQLOperationRule {
+        kind: GraphQLOperationKind::Mutation,
+        field,
+        access: RouteAccess::Authenticated,
+        resource_check,
+    }
+}
+
+/// Access rules of every GraphQL query and mutation
+///
+/// `/graphql` itself is public; resolvers require a caller where listed.
+/// Resource checks go through the authorization service, which consults
+/// ownership, group membership, and OPA when configured.
+pub static GRAPHQL_OPERATION_RULES: &[GraphQLOperationRule] = &[
+    query("eventsById", RouteAccess::Public),
+    query("eventReceiversById", RouteAccess::Public),
+    query("eventReceiverGroupsById", RouteAccess::Public),
+    query("group", RouteAccess::Public),
+    query("me", RouteAccess::Authenticated),
+    query("events", RouteAccess::Public),
+    query("eventReceivers", RouteAccess::Public),
+    query("eventReceiverGroups", RouteAccess::Public),
+    query("search", RouteAccess::Authenticated),
+    mutation("createEvent", None),
+    mutation("createEventReceiver", None),
+    mutation("updateEventReceiver", Some("update receiver")),
+    mutation("deleteEventReceiver", Some("delete receiver")),
+    mutation("createEventReceiverGroup", None),
+    mutation("updateEventReceiverGroup", Some("update group")),
+    mutation("deleteEventReceiverGroup", Some("delete group")),
+    mutation("setEventReceiverGroupEnabled", Some("update group")),
+    mutation("setEventReceiverGroupDisabled", Some("update group")),
+    mutation("enableGroup", Some("update group")),
+    mutation("disableGroup", Some("update group")),
+    mutation("addReceiverToGroup", Some("update group")),
+    mutation("removeReceiverFromGroup", Some("update group")),
+    mutation("moveReceiver", Some("update group")),
+    mutation("addGroupMember", Some("update group")),
+    mutation("updateGroupMember", Some("update group")),
+    mutation("removeGroupMember", Some("update group")),
+];
+
 /// Maps HTTP method and route path to required permission
 ///
-/// This function determines which permission is required based on the
-/// HTTP method and the resource being accessed.
+/// The permission is read from the route's entry in [`ROUTE_RULES`].
 ///
 /// # Arguments
 ///
@@ -22,7 +440,8 @@ use axum::http::Method;
 /// # Returns
 ///
 /// Returns `Some(Permission)` if the route requires a specific permission,
-/// or `None` if the route is public or doesn't map to a known permission.
+/// or `None` if the route is public, needs only authentication, or is not
+/// mounted.
 ///
 /// # Examples
 ///
@@ -38,32 +457,7 @@ use axum::http::Method;
 /// assert_eq!(perm, Some(Permission::ReceiverRead));
 /// ```
 pub fn route_to_permission(method: &Method, path: &str) -> Option<Permission> {
-    // Health check is always public
-    if path == "/health" || path.starts_with("/graphql") {
-        return None;
-    }
-
-    // Determine resource type from path
-    let resource = if path.contains("/events") {
-        "event"
-    } else if path.contains("/receivers") {
-        "receiver"
-    } else if path.contains("/groups") {
-        "group"
-    } else {
-        return None;
-    };
-
-    // Map HTTP method to action
-    let action = match method {
-        &Method::GET => "read",
-        &Method::POST => "create",
-        &Method::PUT | &Method::PATCH => "update",
-        &Method::DELETE => "delete",
-        _ => return None,
-    };
-
-    Permission::from_action(resource, action)
+    route_rule(method, path).and_then(|rule| rule.access.permission())
 }
 
 /// Helper to get all permissions for a specific resource
@@ -115,6 +509,9 @@ pub fn get_resource_permissions(resource: &str) -> Vec<Permission> {
 
 /// Check if a route is public (doesn't require authentication)
 ///
+/// A route is public when its entry in [`ROUTE_RULES`] is
+/// [`RouteAccess::Public`].
+///
 /// # Arguments
 ///
 /// * `path` - The API route path
@@ -133,7 +530,74 @@ pub fn get_resource_permissions(resource: &str) -> Vec<Permission> {
 /// assert!(!is_public_route("/api/v1/events"));
 /// ```
 pub fn is_public_route(path: &str) -> bool {
-    matches!(path, "/health" | "/graphql/health" | "/graphql/playground")
+    ROUTE_RULES
+        .iter()
+        .any(|rule| rule.access == RouteAccess::Public && pattern_match(rule.path, path).is_some())
+}
+
+/// Checks whether a request is refused to impersonation tokens
+///
+/// An administrator impersonating a user may not issue tokens (including
+/// starting another impersonation), change passwords, or write API keys.
+/// Reading key metadata stays allowed, as it is for the impersonated user.
+///
+/// # Examples
+///
+/// ```
+/// use axum::http::Method;
+/// use xzepr::api::middleware::rbac_helpers::restricted_under_impersonation;
+///
+/// assert!(restricted_under_impersonation(&Method::POST, "/api/v1/admin/impersonate"));
+/// assert!(restricted_under_impersonation(&Method::POST, "/api/v1/groups/1/keys"));
+/// assert!(!restricted_under_impersonation(&Method::GET, "/api/v1/groups/1/keys"));
+/// ```
+pub fn restricted_under_impersonation(method: &Method, path: &str) -> bool {
+    if path.starts_with("/api/v1/auth/")
+        || path == "/api/v1/admin/impersonate"
+        || path.ends_with("/password")
+    {
+        return true;
+    }
+
+    let manages_keys = path.starts_with("/api/v1/api-keys")
+        || (path.starts_with("/api/v1/groups/") && path.contains("/keys"));
+    manages_keys && *method != Method::GET
+}
+
+/// Routes a receiver- or group-scoped API key may call
+///
+/// Scoped keys exist to post events, so they reach the ingestion routes,
+/// heartbeats, and the diagnosis that reports on their own scope.
+const SCOPED_KEY_ROUTES: &[(Method, &str)] = &[
+    (Method::POST, "/api/v1/events"),
+    (Method::POST, "/api/v1/events/batch"),
+    (Method::POST, "/api/v1/receivers/:id/heartbeat"),
+    (Method::POST, "/api/v1/receivers/:id/diagnose"),
+];
+
+/// Checks whether a request is refused to receiver- or group-scoped API keys
+///
+/// A scoped key authenticates as its owner, so without this check it could
+/// read the owner's data or rotate itself into an unscoped key. Only
+/// ingestion routes and public reads stay allowed. GraphQL is refused too,
+/// since its mutations do not enforce key scope.
+///
+/// # Examples
+///
+/// ```
+/// use axum::http::Method;
+/// use xzepr::api::middleware::rbac_helpers::restricted_under_scoped_key;
+///
+/// assert!(restricted_under_scoped_key(&Method::POST, "/api/v1/api-keys/1/rotate"));
+/// assert!(restricted_under_scoped_key(&Method::GET, "/api/v1/me/preferences"));
+/// assert!(!restricted_under_scoped_key(&Method::POST, "/api/v1/events"));
+/// ```
+pub fn restricted_under_scoped_key(method: &Method, path: &str) -> bool {
+    let ingests = SCOPED_KEY_ROUTES
+        .iter()
+        .any(|(allowed, pattern)| allowed == method && pattern_match(pattern, path).is_some());
+    let public_read = *method == Method::GET && is_public_route(path);
+    !(ingests || public_read)
 }
 
 /// Extract resource ID from path if present
@@ -189,6 +653,40 @@ mod tests {
         assert_eq!(perm, Some(Permission::EventRead));
     }
 
+    #[test]
+    fn test_route_to_permission_admin_events() {
+        let perm = route_to_permission(&Method::GET, "/api/v1/admin/events");
+        assert_eq!(perm, Some(Permission::EventReadMeta));
+    }
+
+    #[test]
+    fn test_route_to_permission_schema_preview() {
+        let perm = route_to_permission(&Method::POST, "/api/v1/receivers/123/schema/preview");
+        assert_eq!(perm, Some(Permission::ReceiverRead));
+    }
+
+    #[test]
+    fn test_route_to_permission_receiver_heartbeat() {
+        let perm = route_to_permission(&Method::POST, "/api/v1/receivers/123/heartbeat");
+        assert_eq!(perm, Some(Permission::EventCreate));
+    }
+
+    #[test]
+    fn test_route_to_permission_group_api_keys() {
+        for method in [Method::GET, Method::POST] {
+            let perm = route_to_permission(&method, "/api/v1/groups/123/keys");
+            assert_eq!(perm, Some(Permission::GroupManageKeys));
+        }
+        let perm = route_to_permission(&Method::DELETE, "/api/v1/groups/123/keys/456");
+        assert_eq!(perm, Some(Permission::GroupManageKeys));
+    }
+
+    #[test]
+    fn test_route_to_permission_receiver_diagnose() {
+        let perm = route_to_permission(&Method::POST, "/api/v1/receivers/123/diagnose");
+        assert_eq!(perm, None);
+    }
+
     #[test]
     fn test_route_to_permission_event_update() {
         let perm = route_to_permission(&Method::PUT, "/api/v1/events/123");
@@ -201,6 +699,29 @@ mod tests {
         assert_eq!(perm, Some(Permission::EventDelete));
     }
 
+    #[test]
+    fn test_route_to_permission_event_attachments() {
+        let path = "/api/v1/events/123/attachments";
+        assert_eq!(
+            route_to_permission(&Method::POST, path),
+            Some(Permission::EventUpdate)
+        );
+        assert_eq!(
+            route_to_permission(&Method::GET, &format!("{}/456", path)),
+            Some(Permission::EventRead)
+        );
+        assert_eq!(
+            route_to_permission(&Method::DELETE, &format!("{}/456", path)),
+            Some(Permission::EventUpdate)
+        );
+    }
+
+    #[test]
+    fn test_route_to_permission_attestation_lookup() {
+        let perm = route_to_permission(&Method::GET, "/api/v1/attestations/by-digest/sha256:ab");
+        assert_eq!(perm, Some(Permission::EventRead));
+    }
+
     #[test]
     fn test_route_to_permission_receiver_create() {
         let perm = route_to_permission(&Method::POST, "/api/v1/receivers");
@@ -239,8 +760,10 @@ mod tests {
 
     #[test]
     fn test_route_to_permission_group_update() {
-        let perm = route_to_permission(&Method::PATCH, "/api/v1/groups/789");
-        assert_eq!(perm, Some(Permission::GroupUpdate));
+        for method in [Method::PUT, Method::PATCH] {
+            let perm = route_to_permission(&method, "/api/v1/groups/789");
+            assert_eq!(perm, Some(Permission::GroupUpdate));
+        }
     }
 
     #[test]
@@ -330,6 +853,90 @@ mod tests {
         assert!(is_public_route("/graphql/playground"));
     }
 
+    #[test]
+    fn test_route_rule_prefers_literal_segments() {
+        let rule = route_rule(&Method::GET, "/api/v1/receivers/changes").unwrap();
+        assert_eq!(rule.path, "/api/v1/receivers/changes");
+        let rule = route_rule(&Method::GET, "/api/v1/receivers/01J").unwrap();
+        assert_eq!(rule.path, "/api/v1/receivers/:id");
+        let rule = route_rule(&Method::GET, "/api/v1/events/export").unwrap();
+        assert_eq!(rule.path, "/api/v1/events/export");
+    }
+
+    #[test]
+    fn test_route_rule_accepts_mounted_pattern() {
+        let rule = route_rule(&Method::DELETE, "/api/v1/groups/:id/keys/:key_id").unwrap();
+        assert_eq!(
+            rule.access,
+            RouteAccess::Permission(Permission::GroupManageKeys)
+        );
+    }
+
+    #[test]
+    fn test_route_rule_rejects_empty_parameter() {
+        assert!(route_rule(&Method::GET, "/api/v1/receivers//timeline").is_none());
+    }
+
+    #[test]
+    fn test_route_rules_are_unique() {
+        for (i, rule) in ROUTE_RULES.iter().enumerate() {
+            assert!(
+                !ROUTE_RULES[i + 1..]
+                    .iter()
+                    .any(|other| other.method == rule.method && other.path == rule.path),
+                "{} {} is declared twice",
+                rule.method,
+                rule.path
+            );
+        }
+    }
+
+    #[test]
+    fn test_admin_routes_are_role_gated() {
+        let rule = route_rule(&Method::GET, "/api/v1/admin/about").unwrap();
+        assert_eq!(rule.role, Some(ADMIN_ROLE));
+        assert_eq!(rule.access, RouteAccess::Authenticated);
+    }
+
+    #[test]
+    fn test_session_routes_are_declared() {
+        for (method, path, role) in [
+            (Method::POST, "/api/v1/admin/impersonate", Some(ADMIN_ROLE)),
+            (Method::GET, "/api/v1/admin/sessions", Some(ADMIN_ROLE)),
+            (
+                Method::DELETE,
+                "/api/v1/admin/sessions/01H",
+                Some(ADMIN_ROLE),
+            ),
+            (Method::GET, "/api/v1/me/sessions", None),
+            (Method::DELETE, "/api/v1/me/sessions/01H", None),
+        ] {
+            let rule = route_rule(&method, path).unwrap();
+            assert_eq!(
+                rule.access,
+                RouteAccess::Authenticated,
+                "{} {}",
+                method,
+                path
+            );
+            assert_eq!(rule.role, role, "{} {}", method, path);
+        }
+    }
+
+    #[test]
+    fn test_graphql_operation_rules_are_unique() {
+        for (i, rule) in GRAPHQL_OPERATION_RULES.iter().enumerate() {
+            assert!(
+                !GRAPHQL_OPERATION_RULES[i + 1..]
+                    .iter()
+                    .any(|other| other.kind == rule.kind && other.field == rule.field),
+                "{} {} is declared twice",
+                rule.kind,
+                rule.field
+            );
+        }
+    }
+
     #[test]
     fn test_is_public_route_api_is_protected() {
         assert!(!is_public_route("/api/v1/events"));
@@ -373,4 +980,53 @@ mod tests {
         let id = extract_resource_id("/health");
         assert_eq!(id, None);
     }
+
+    #[test]
+    fn test_restricted_under_impersonation() {
+        for (method, path) in [
+            (Method::POST, "/api/v1/admin/impersonate"),
+            (Method::POST, "/api/v1/auth/refresh"),
+            (Method::PUT, "/api/v1/me/password"),
+            (Method::POST, "/api/v1/api-keys/01J/rotate"),
+            (Method::POST, "/api/v1/groups/01J/keys"),
+            (Method::DELETE, "/api/v1/groups/01J/keys/01K"),
+        ] {
+            assert!(restricted_under_impersonation(&method, path), "{}", path);
+        }
+        for (method, path) in [
+            (Method::GET, "/api/v1/receivers"),
+            (Method::POST, "/api/v1/events"),
+            (Method::GET, "/api/v1/api-keys/01J"),
+            (Method::GET, "/api/v1/groups/01J/keys"),
+            (Method::GET, "/api/v1/me/sessions"),
+        ] {
+            assert!(!restricted_under_impersonation(&method, path), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_restricted_under_scoped_key() {
+        for (method, path) in [
+            (Method::POST, "/api/v1/api-keys/01J/rotate"),
+            (Method::GET, "/api/v1/api-keys/01J"),
+            (Method::GET, "/api/v1/api-keys/01J/usage"),
+            (Method::GET, "/api/v1/me/preferences"),
+            (Method::PUT, "/api/v1/me/preferences"),
+            (Method::GET, "/api/v1/me/sessions"),
+            (Method::GET, "/api/v1/receivers"),
+            (Method::GET, "/api/v1/events/poll"),
+            (Method::POST, "/graphql"),
+        ] {
+            assert!(restricted_under_scoped_key(&method, path), "{}", path);
+        }
+        for (method, path) in [
+            (Method::POST, "/api/v1/events"),
+            (Method::POST, "/api/v1/events/batch"),
+            (Method::POST, "/api/v1/receivers/01J/heartbeat"),
+            (Method::POST, "/api/v1/receivers/01J/diagnose"),
+            (Method::GET, "/health"),
+        ] {
+            assert!(!restricted_under_scoped_key(&method, path), "{}", path);
+        }
+    }
 }
@@ -13,6 +13,7 @@ use async_trait::async_trait;
 use std::sync::Arc;
 use tracing::debug;
 
+use crate::application::authorization::{group_context, receiver_context, receiver_group_context};
 use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
 use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
 use crate::domain::repositories::event_repo::EventRepository;
@@ -53,7 +54,6 @@ pub struct EventReceiverContextBuilder {
     /// Repository for querying event receivers
     receiver_repo: Arc<dyn EventReceiverRepository>,
     /// Repository for querying event receiver groups
-    #[allow(dead_code)]
     group_repo: Arc<dyn EventReceiverGroupRepository>,
 }
 
@@ -79,7 +79,8 @@ impl ResourceContextBuilder for EventReceiverContextBuilder {
         );
 
         // Parse receiver ID
-        let receiver_id = EventReceiverId::parse(resource_id)
+        let receiver_id = resource_id
+            .parse::<EventReceiverId>()
             .map_err(|e| format!("Invalid receiver ID: {}", e))?;
 
         // Load receiver from repository
@@ -90,23 +91,9 @@ impl ResourceContextBuilder for EventReceiverContextBuilder {
             .map_err(|e| format!("Failed to load receiver: {}", e))?
             .ok_or_else(|| format!("Receiver not found: {}", resource_id))?;
 
-        let owner_id = Some(receiver.owner_id().to_string());
-        let resource_version = receiver.resource_version();
-
-        // TODO: Implement group membership lookup
-        // EventReceiver doesn't have a direct group_id field
-        // Need to query which groups contain this receiver
-        let group_id = None;
-        let group_members = Vec::new();
-
-        Ok(ResourceContext {
-            resource_type: "event_receiver".to_string(),
-            resource_id: Some(resource_id.to_string()),
-            owner_id,
-            group_id,
-            members: group_members,
-            resource_version,
-        })
+        receiver_context(&receiver, &*self.group_repo)
+            .await
+            .map_err(|e| format!("Failed to load receiver groups: {}", e))
     }
 }
 
@@ -117,7 +104,6 @@ pub struct EventContextBuilder {
     /// Repository for querying event receivers
     receiver_repo: Arc<dyn EventReceiverRepository>,
     /// Repository for querying event receiver groups
-    #[allow(dead_code)]
     group_repo: Arc<dyn EventReceiverGroupRepository>,
 }
 
@@ -145,8 +131,9 @@ impl ResourceContextBuilder for EventContextBuilder {
         );
 
         // Parse event ID
-        let event_id =
-            EventId::parse(resource_id).map_err(|e| format!("Invalid event ID: {}", e))?;
+        let event_id = resource_id
+            .parse::<EventId>()
+            .map_err(|e| format!("Invalid event ID: {}", e))?;
 
         // Load event from repository
         let event = self
@@ -168,11 +155,9 @@ impl ResourceContextBuilder for EventContextBuilder {
         let owner_id = Some(receiver.owner_id().to_string());
         let resource_version = event.resource_version();
 
-        // TODO: Implement group membership lookup
-        // EventReceiver doesn't have a direct group_id field
-        // Need to query which groups contain this receiver
-        let group_id = None;
-        let group_members = Vec::new();
+        let (group_id, group_members) = receiver_group_context(&*self.group_repo, receiver_id)
+            .await
+            .map_err(|e| format!("Failed to load receiver groups: {}", e))?;
 
         Ok(ResourceContext {
             resource_type: "event".to_string(),
@@ -207,7 +192,8 @@ impl ResourceContextBuilder for EventReceiverGroupContextBuilder {
         );
 
         // Parse group ID
-        let group_id = EventReceiverGroupId::parse(resource_id)
+        let group_id = resource_id
+            .parse::<EventReceiverGroupId>()
             .map_err(|e| format!("Invalid group ID: {}", e))?;
 
         // Load group from repository
@@ -218,26 +204,91 @@ impl ResourceContextBuilder for EventReceiverGroupContextBuilder {
             .map_err(|e| format!("Failed to load group: {}", e))?
             .ok_or_else(|| format!("Group not found: {}", resource_id))?;
 
-        let owner_id = Some(group.owner_id().to_string());
-        let resource_version = group.resource_version();
+        group_context(&group, &*self.group_repo)
+            .await
+            .map_err(|e| format!("Failed to load group members: {}", e))
+    }
+}
 
-        // TODO: Query group members from membership table
-        let group_members = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::middleware::jwt::AuthenticatedUser;
+    use crate::api::middleware::opa::legacy_rbac_check;
+    use crate::auth::jwt::claims::TokenType;
+    use crate::auth::jwt::Claims;
+    use crate::domain::value_objects::UserId;
+    use crate::fixtures::{GroupFixture, ReceiverFixture};
+    use crate::infrastructure::memory::{
+        InMemoryEventReceiverGroupRepository, InMemoryEventReceiverRepository,
+    };
+    use chrono::{Duration, Utc};
 
-        Ok(ResourceContext {
-            resource_type: "event_receiver_group".to_string(),
-            resource_id: Some(resource_id.to_string()),
-            owner_id,
-            group_id: Some(resource_id.to_string()),
-            members: group_members,
-            resource_version,
+    fn authenticated(user_id: UserId) -> AuthenticatedUser {
+        AuthenticatedUser::new(Claims {
+            sub: user_id.to_string(),
+            roles: vec!["user".to_string()],
+            permissions: vec![],
+            exp: 9999999999,
+            iat: 0,
+            nbf: 0,
+            jti: "test-jti".to_string(),
+            iss: "xzepr".to_string(),
+            aud: "xzepr-api".to_string(),
+            token_type: TokenType::Access,
+            act: None,
         })
     }
-}
 
-// TODO: Add tests once mock repositories are available
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     // Tests will be added in Phase 3 testing task
-// }
+    #[tokio::test]
+    async fn test_expired_member_cannot_post_events_to_group_receiver() {
+        let owner = UserId::new();
+        let receiver_repo = Arc::new(InMemoryEventReceiverRepository::new());
+        let group_repo = Arc::new(InMemoryEventReceiverGroupRepository::new());
+        let receiver = ReceiverFixture::new()
+            .owner(owner)
+            .persist(&*receiver_repo)
+            .await;
+        let receiver_id = receiver.id();
+        let group_id = GroupFixture::new()
+            .receiver(&receiver)
+            .owner(owner)
+            .persist(&*group_repo)
+            .await
+            .id();
+
+        let (contractor, employee) = (UserId::new(), UserId::new());
+        group_repo
+            .add_member(
+                group_id,
+                contractor,
+                owner,
+                Some(Utc::now() - Duration::minutes(1)),
+            )
+            .await
+            .unwrap();
+        group_repo
+            .add_member(group_id, employee, owner, None)
+            .await
+            .unwrap();
+
+        let builder = EventReceiverContextBuilder::new(receiver_repo, group_repo);
+        let context = builder
+            .build_context(&receiver_id.to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(context.group_id, Some(group_id.to_string()));
+        assert_eq!(context.members, vec![employee.to_string()]);
+        assert!(legacy_rbac_check(
+            &authenticated(employee),
+            "create",
+            &context
+        ));
+        assert!(!legacy_rbac_check(
+            &authenticated(contractor),
+            "create",
+            &context
+        ));
+    }
+}
@@ -16,6 +16,9 @@ use axum::{
 };
 use std::time::Instant;
 use tracing::{info_span, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::infrastructure::tracing::{current_trace_id, extract_parent_context};
 
 /// Tracing middleware that creates spans for HTTP requests
 ///
@@ -23,7 +26,10 @@ use tracing::{info_span, Instrument};
 /// - Creates a span for each request
 /// - Captures request method, path, and headers
 /// - Records response status and duration
-/// - Propagates trace context across service boundaries
+/// - Continues the caller's trace when the request carries a W3C
+///   `traceparent` header
+/// - Marks the response with the [`RequestTraceId`] of a sampled trace, so
+///   outer layers such as the metrics middleware can refer to it
 ///
 /// # Example
 ///
@@ -61,6 +67,8 @@ pub async fn tracing_middleware(request: Request, next: Next) -> Response {
     // Create span with request metadata
     let span = info_span!(
         "http_request",
+        otel.name = %format!("{} {}", method, path),
+        otel.kind = "server",
         http.method = %method,
         http.route = %path,
         http.target = %uri,
@@ -69,6 +77,7 @@ pub async fn tracing_middleware(request: Request, next: Next) -> Response {
         http.status_code = tracing::field::Empty,
         http.duration_ms = tracing::field::Empty,
     );
+    span.set_parent(extract_parent_context(request.headers()));
 
     // Process request with span
     process_request_with_span(request, next, start, span).await
@@ -83,7 +92,10 @@ async fn process_request_with_span(
 ) -> Response {
     async move {
         // Process the request
-        let response = next.run(request).await;
+        let mut response = next.run(request).await;
+        if let Some(trace_id) = current_trace_id() {
+            response.extensions_mut().insert(RequestTraceId(trace_id));
+        }
 
         // Record response metadata
         let status = response.status();
@@ -168,6 +180,8 @@ pub async fn enhanced_tracing_middleware(mut request: Request, next: Next) -> Re
     // Create comprehensive span
     let span = info_span!(
         "http_request",
+        otel.name = %format!("{} {}", method, path),
+        otel.kind = "server",
         http.method = %method,
         http.route = %path,
         http.target = %uri,
@@ -178,6 +192,7 @@ pub async fn enhanced_tracing_middleware(mut request: Request, next: Next) -> Re
         http.duration_ms = tracing::field::Empty,
         http.response_size = tracing::field::Empty,
     );
+    span.set_parent(extract_parent_context(request.headers()));
 
     // Add request ID to extensions for downstream use
     request
@@ -216,6 +231,10 @@ fn finalize_span(span: &tracing::Span, start: Instant, response: &Response) {
 #[derive(Debug, Clone)]
 pub struct RequestId(pub String);
 
+/// Trace id of a sampled request, stored in response extensions
+#[derive(Debug, Clone)]
+pub struct RequestTraceId(pub String);
+
 /// Generate a unique request ID
 fn generate_request_id() -> String {
     use std::sync::atomic::{AtomicU64, Ordering};
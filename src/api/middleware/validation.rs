@@ -4,16 +4,19 @@
 // src/api/middleware/validation.rs
 
 use axum::{
-    body::Body,
-    extract::Request,
-    http::StatusCode,
+    body::{Body, HttpBody},
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
+    Json,
 };
 use serde_json::json;
 use std::sync::Arc;
 use validator::{Validate, ValidationErrors};
 
+use crate::infrastructure::MediaTypeSecurityConfig;
+
 /// Maximum request body size in bytes (default: 1MB)
 pub const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
 
@@ -236,6 +239,221 @@ pub async fn body_size_limit_middleware(
     Ok(next.run(request).await)
 }
 
+/// Request and response media types allowed by content negotiation
+#[derive(Debug, Clone)]
+pub struct ContentNegotiationConfig {
+    content_types: Vec<String>,
+    /// Route prefixes with their own content types, longest first
+    routes: Vec<(String, Vec<String>)>,
+    produces: Vec<String>,
+}
+
+impl ContentNegotiationConfig {
+    /// Creates a config accepting `content_types` on every route
+    pub fn new(content_types: Vec<String>, produces: Vec<String>) -> Self {
+        Self {
+            content_types: normalize_media_types(content_types),
+            routes: Vec::new(),
+            produces: normalize_media_types(produces),
+        }
+    }
+
+    /// Replaces the accepted content types for paths under `path_prefix`
+    ///
+    /// A `:name` segment in the prefix matches any single path segment.
+    pub fn with_route(
+        mut self,
+        path_prefix: impl Into<String>,
+        content_types: Vec<String>,
+    ) -> Self {
+        let path_prefix = path_prefix.into().trim_end_matches('/').to_string();
+        self.routes.retain(|(prefix, _)| *prefix != path_prefix);
+        self.routes
+            .push((path_prefix, normalize_media_types(content_types)));
+        self.routes
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        self
+    }
+
+    /// Returns the request body types accepted on `path`
+    pub fn content_types_for(&self, path: &str) -> &[String] {
+        self.routes
+            .iter()
+            .find(|(prefix, _)| is_under(path, prefix))
+            .map(|(_, content_types)| content_types.as_slice())
+            .unwrap_or(&self.content_types)
+    }
+
+    /// Returns true if a body of `content_type` is accepted on `path`
+    ///
+    /// Parameters such as `charset=utf-8` are ignored.
+    pub fn accepts_content_type(&self, path: &str, content_type: &str) -> bool {
+        let media_type = essence(content_type);
+        self.content_types_for(path).contains(&media_type)
+    }
+
+    /// Returns true if an `Accept` header allows a type the API produces
+    ///
+    /// Ranges with `q=0` are ignored; an empty header accepts anything.
+    pub fn is_acceptable(&self, accept: &str) -> bool {
+        let mut ranges = accept
+            .split(',')
+            .filter(|range| !range.trim().is_empty())
+            .peekable();
+        if ranges.peek().is_none() {
+            return true;
+        }
+
+        ranges
+            .filter(|range| !is_refused(range))
+            .map(essence)
+            .any(|range| {
+                self.produces
+                    .iter()
+                    .any(|produced| range_matches(&range, produced))
+            })
+    }
+}
+
+impl Default for ContentNegotiationConfig {
+    fn default() -> Self {
+        Self::from(&MediaTypeSecurityConfig::default())
+    }
+}
+
+impl From<&MediaTypeSecurityConfig> for ContentNegotiationConfig {
+    fn from(config: &MediaTypeSecurityConfig) -> Self {
+        config.routes.iter().fold(
+            Self::new(config.content_types.clone(), config.produces.clone()),
+            |negotiation, (prefix, content_types)| {
+                negotiation.with_route(prefix.clone(), content_types.clone())
+            },
+        )
+    }
+}
+
+/// Returns true if `path` is `prefix` or lies below it
+fn is_under(path: &str, prefix: &str) -> bool {
+    let mut segments = path.split('/');
+    prefix.split('/').all(|expected| {
+        segments.next().is_some_and(|segment| {
+            segment == expected || (expected.starts_with(':') && !segment.is_empty())
+        })
+    })
+}
+
+/// Returns the lowercase media type without parameters
+fn essence(media_type: &str) -> String {
+    media_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Returns true if the `Accept` media range covers `media_type`
+fn range_matches(range: &str, media_type: &str) -> bool {
+    match range.strip_suffix("/*") {
+        Some("*") => true,
+        Some(kind) => media_type
+            .split_once('/')
+            .is_some_and(|(media_kind, _)| media_kind == kind),
+        None => range == media_type,
+    }
+}
+
+/// Returns true if an `Accept` range carries `q=0`
+fn is_refused(range: &str) -> bool {
+    range.split(';').skip(1).any(|param| {
+        param
+            .trim()
+            .strip_prefix("q=")
+            .and_then(|q| q.trim().parse::<f32>().ok())
+            .is_some_and(|q| q <= 0.0)
+    })
+}
+
+fn normalize_media_types(media_types: Vec<String>) -> Vec<String> {
+    media_types
+        .iter()
+        .map(|media_type| essence(media_type))
+        .collect()
+}
+
+/// Builds a structured media type error response
+fn media_type_error(status: StatusCode, code: &str, message: String) -> Response {
+    (
+        status,
+        Json(json!({
+            "error": code,
+            "message": message,
+        })),
+    )
+        .into_response()
+}
+
+/// Content negotiation middleware
+///
+/// Rejects mutating requests whose body is not one of the content types
+/// allowed on the route with `415 UNSUPPORTED_MEDIA_TYPE`, and requests whose
+/// `Accept` header excludes every type the API produces with
+/// `406 NOT_ACCEPTABLE`. Both checks only read headers, so rejected bodies
+/// are never buffered.
+pub async fn content_negotiation_middleware(
+    State(config): State<Arc<ContentNegotiationConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    let mutating = matches!(
+        *request.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    let has_body = request.body().size_hint().exact() != Some(0);
+
+    if mutating && has_body {
+        let content_type = request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        if !config.accepts_content_type(path, content_type) {
+            tracing::debug!(
+                path = %path,
+                content_type = %content_type,
+                "Rejected unsupported request media type"
+            );
+
+            return media_type_error(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "UNSUPPORTED_MEDIA_TYPE",
+                format!(
+                    "Content-Type must be one of: {}",
+                    config.content_types_for(path).join(", ")
+                ),
+            );
+        }
+    }
+
+    if let Some(accept) = request
+        .headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+    {
+        if !config.is_acceptable(accept) {
+            return media_type_error(
+                StatusCode::NOT_ACCEPTABLE,
+                "NOT_ACCEPTABLE",
+                format!("Accept must allow one of: {}", config.produces.join(", ")),
+            );
+        }
+    }
+
+    next.run(request).await
+}
+
 /// String sanitization helpers
 pub mod sanitize {
     use regex::Regex;
@@ -484,4 +702,165 @@ mod tests {
         let state = ValidationState::new(config);
         assert_eq!(state.config().max_body_size, DEFAULT_MAX_BODY_SIZE);
     }
+
+    mod content_negotiation {
+        use super::*;
+        use axum::{
+            middleware,
+            routing::{get, post},
+            Router,
+        };
+        use tower::ServiceExt;
+
+        fn app(config: ContentNegotiationConfig) -> Router {
+            Router::new()
+                .route("/api/v1/events", post(|| async { "ok" }))
+                .route(
+                    "/api/v1/receivers",
+                    get(|| async { "ok" }).post(|| async { "ok" }),
+                )
+                .layer(middleware::from_fn_with_state(
+                    Arc::new(config),
+                    content_negotiation_middleware,
+                ))
+        }
+
+        fn post_request(path: &str, content_type: Option<&str>, body: &str) -> Request {
+            let mut builder = Request::builder().method("POST").uri(path);
+            if let Some(content_type) = content_type {
+                builder = builder.header(header::CONTENT_TYPE, content_type);
+            }
+            builder.body(Body::from(body.to_string())).unwrap()
+        }
+
+        fn get_request(accept: &str) -> Request {
+            Request::builder()
+                .uri("/api/v1/receivers")
+                .header(header::ACCEPT, accept)
+                .body(Body::empty())
+                .unwrap()
+        }
+
+        async fn status(request: Request) -> StatusCode {
+            app(ContentNegotiationConfig::default())
+                .oneshot(request)
+                .await
+                .unwrap()
+                .status()
+        }
+
+        #[tokio::test]
+        async fn test_form_post_is_unsupported() {
+            let response = app(ContentNegotiationConfig::default())
+                .oneshot(post_request(
+                    "/api/v1/events",
+                    Some("application/x-www-form-urlencoded"),
+                    "name=build",
+                ))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["error"], "UNSUPPORTED_MEDIA_TYPE");
+
+            assert_eq!(
+                status(post_request("/api/v1/receivers", Some("text/plain"), "x")).await,
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            );
+            assert_eq!(
+                status(post_request("/api/v1/receivers", None, "{}")).await,
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            );
+        }
+
+        #[tokio::test]
+        async fn test_json_with_parameters_and_empty_bodies_pass() {
+            assert_eq!(
+                status(post_request(
+                    "/api/v1/receivers",
+                    Some("Application/JSON; charset=utf-8"),
+                    "{}"
+                ))
+                .await,
+                StatusCode::OK
+            );
+            assert_eq!(
+                status(post_request("/api/v1/receivers", None, "")).await,
+                StatusCode::OK
+            );
+        }
+
+        #[tokio::test]
+        async fn test_cloudevents_allowed_only_on_events_route() {
+            let cloudevent = Some("application/cloudevents+json; charset=utf-8");
+            assert_eq!(
+                status(post_request("/api/v1/events", cloudevent, "{}")).await,
+                StatusCode::OK
+            );
+            assert_eq!(
+                status(post_request("/api/v1/receivers", cloudevent, "{}")).await,
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            );
+        }
+
+        #[tokio::test]
+        async fn test_accept_negotiation() {
+            for accept in [
+                "*/*",
+                "application/*",
+                "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+                "application/json; charset=utf-8",
+            ] {
+                assert_eq!(
+                    status(get_request(accept)).await,
+                    StatusCode::OK,
+                    "{}",
+                    accept
+                );
+            }
+
+            for accept in ["application/xml", "image/*", "application/json;q=0"] {
+                assert_eq!(
+                    status(get_request(accept)).await,
+                    StatusCode::NOT_ACCEPTABLE,
+                    "{}",
+                    accept
+                );
+            }
+        }
+
+        #[test]
+        fn test_config_route_classes() {
+            let mut media_types = MediaTypeSecurityConfig::default();
+            media_types
+                .routes
+                .insert("/api/v1/imports/".to_string(), vec!["text/csv".to_string()]);
+            let config = ContentNegotiationConfig::from(&media_types);
+
+            assert!(config.accepts_content_type("/api/v1/imports/events", "text/csv"));
+            assert!(!config.accepts_content_type("/api/v1/imports", "application/json"));
+            assert!(config.accepts_content_type("/api/v1/importsx", "application/json"));
+            assert!(
+                config.accepts_content_type("/api/v1/events", "application/cloudevents-batch+json")
+            );
+        }
+
+        #[test]
+        fn test_route_prefix_parameters() {
+            let config = ContentNegotiationConfig::default();
+            let upload = "multipart/form-data; boundary=xyz";
+
+            assert!(config.accepts_content_type("/api/v1/events/01J0EVENT/attachments", upload));
+            assert!(config
+                .accepts_content_type("/api/v1/events/01J0EVENT/attachments/01J0FILE", upload));
+            assert!(!config.accepts_content_type("/api/v1/events/01J0EVENT", upload));
+            assert!(!config.accepts_content_type("/api/v1/events//attachments", upload));
+            assert!(config
+                .accepts_content_type("/api/v1/events/01J0EVENT", "application/cloudevents+json"));
+        }
+    }
 }
@@ -3,6 +3,7 @@
 
 // Generated mod file
 
+pub mod field_access;
 pub mod graphql;
 pub mod middleware;
 pub mod rest;
@@ -0,0 +1,402 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/api_keys.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::Utc;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    ApiKeyResponse, ApiKeyUsageResponse, CreateApiKeyResponse, CreateGroupApiKeyRequest,
+    ErrorResponse, RotateApiKeyRequest, RotateApiKeyResponse,
+};
+use crate::api::rest::events::AppState;
+use crate::api::rest::timestamp::Timestamp;
+use crate::auth::api_key::{ApiKey, ApiKeyService};
+use crate::domain::value_objects::{ApiKeyId, EventReceiverGroupId, UserId};
+use crate::error::AuthError;
+
+/// Role that may manage any user's API keys
+const API_KEY_ADMIN_ROLE: &str = "admin";
+
+type ApiKeyError = (StatusCode, Json<ErrorResponse>);
+
+fn api_key_error(status: StatusCode, error: &str, message: String) -> ApiKeyError {
+    (status, Json(ErrorResponse::new(error.to_string(), message)))
+}
+
+fn auth_error_response(e: AuthError) -> ApiKeyError {
+    match e {
+        AuthError::ApiKeyNotFound => api_key_error(
+            StatusCode::NOT_FOUND,
+            "not_found",
+            "API key not found".to_string(),
+        ),
+        AuthError::ApiKeyDisabled | AuthError::ApiKeyExpired => {
+            api_key_error(StatusCode::CONFLICT, "api_key_inactive", e.to_string())
+        }
+        e => {
+            error!("API key operation failed: {}", e);
+            api_key_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                "API key operation failed".to_string(),
+            )
+        }
+    }
+}
+
+fn api_key_service(state: &AppState) -> Result<Arc<ApiKeyService>, ApiKeyError> {
+    state.api_key_service.clone().ok_or_else(|| {
+        api_key_error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "api_keys_unavailable",
+            "API key management is not configured".to_string(),
+        )
+    })
+}
+
+fn parse_key_id(id_str: &str) -> Result<ApiKeyId, ApiKeyError> {
+    id_str.parse::<ApiKeyId>().map_err(|_| {
+        warn!("Invalid API key ID format: {}", id_str);
+        api_key_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_id",
+            "Invalid API key ID format".to_string(),
+        )
+    })
+}
+
+fn parse_group_id(id_str: &str) -> Result<EventReceiverGroupId, ApiKeyError> {
+    id_str.parse::<EventReceiverGroupId>().map_err(|_| {
+        warn!("Invalid group ID format: {}", id_str);
+        api_key_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_id",
+            "Invalid group ID format".to_string(),
+        )
+    })
+}
+
+/// Loads the key at `id_str` if the caller owns it or is an administrator
+async fn load_owned_key(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    id_str: &str,
+) -> Result<(Arc<ApiKeyService>, ApiKey), ApiKeyError> {
+    let service = api_key_service(state)?;
+    let key_id = parse_key_id(id_str)?;
+
+    let api_key = service.get_key(key_id).await.map_err(auth_error_response)?;
+    if api_key.user_id.to_string() != user.user_id() && !user.has_role(API_KEY_ADMIN_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            key_id = %key_id,
+            "API key request denied: caller does not own the key"
+        );
+        return Err(api_key_error(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Only the key owner or an administrator may manage this key".to_string(),
+        ));
+    }
+
+    Ok((service, api_key))
+}
+
+/// Returns the metadata of an API key
+///
+/// `last_used_with` tells whether the last request used the `current` or
+/// the `previous` secret, so owners can see when producers have switched
+/// after a rotation.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid key id
+/// * `403 FORBIDDEN` - Caller neither owns the key nor is an administrator
+/// * `404 NOT_FOUND` - Key does not exist
+pub async fn get_api_key(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+) -> Result<Json<ApiKeyResponse>, ApiKeyError> {
+    let (service, api_key) = load_owned_key(&state, &user, &id_str).await?;
+    let now = Utc::now();
+    Ok(Json(
+        ApiKeyResponse::at(&api_key, now).with_stale(service.is_stale(&api_key, now)),
+    ))
+}
+
+/// Returns how an API key has been used over the last 30 days
+///
+/// Counts requests per UTC day by outcome: successes, authentication
+/// failures of a disabled, expired, or superseded key, authorization
+/// denials, and rate limiting. The latest 20 failures are listed with
+/// their reason and route. Requests still buffered are included.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid key id
+/// * `403 FORBIDDEN` - Caller neither owns the key nor is an administrator
+/// * `404 NOT_FOUND` - Key does not exist
+/// * `503 SERVICE_UNAVAILABLE` - Usage tracking is not configured
+pub async fn get_api_key_usage(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+) -> Result<Json<ApiKeyUsageResponse>, ApiKeyError> {
+    let (service, api_key) = load_owned_key(&state, &user, &id_str).await?;
+    let usage = service.usage_tracker().ok_or_else(|| {
+        api_key_error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "api_key_usage_unavailable",
+            "API key usage tracking is not configured".to_string(),
+        )
+    })?;
+
+    let now = Utc::now();
+    let report = usage
+        .report(api_key.id, now.date_naive())
+        .await
+        .map_err(auth_error_response)?;
+    let stale = report.last_use.is_none() && service.is_stale(&api_key, now);
+    Ok(Json(ApiKeyUsageResponse::new(&api_key, &report, stale)))
+}
+
+/// Issues a new secret for an API key
+///
+/// The key keeps its id, name, and expiry. The replaced secret keeps
+/// working for `grace_period_seconds`, or the configured default when the
+/// body is omitted, and stops working after that. The new secret is only
+/// returned in this response.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid key id or grace period
+/// * `403 FORBIDDEN` - Caller neither owns the key nor is an administrator
+/// * `404 NOT_FOUND` - Key does not exist
+/// * `409 CONFLICT` - Key is disabled or expired
+pub async fn rotate_api_key(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+    body: Bytes,
+) -> Result<Json<RotateApiKeyResponse>, ApiKeyError> {
+    let request = if body.iter().all(u8::is_ascii_whitespace) {
+        RotateApiKeyRequest::default()
+    } else {
+        serde_json::from_slice::<RotateApiKeyRequest>(&body).map_err(|e| {
+            api_key_error(
+                StatusCode::BAD_REQUEST,
+                "invalid_json",
+                format!("Invalid JSON: {}", e),
+            )
+        })?
+    };
+    let grace = request.grace_period().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )
+    })?;
+
+    let (service, api_key) = load_owned_key(&state, &user, &id_str).await?;
+    let (key, rotated) = service
+        .rotate_key(api_key.id, grace)
+        .await
+        .map_err(auth_error_response)?;
+
+    info!(
+        user_id = %user.user_id(),
+        key_id = %rotated.id,
+        "API key rotated"
+    );
+    let now = Utc::now();
+    Ok(Json(RotateApiKeyResponse {
+        key,
+        api_key: ApiKeyResponse::at(&rotated, now).with_stale(service.is_stale(&rotated, now)),
+    }))
+}
+
+/// Checks that the caller owns `group_id`
+async fn check_group_owner(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    group_id: EventReceiverGroupId,
+) -> Result<(), ApiKeyError> {
+    let group = match state
+        .event_receiver_group_handler
+        .get_event_receiver_group(group_id)
+        .await
+    {
+        Ok(Some(group)) => group,
+        Ok(None) => {
+            return Err(api_key_error(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "Group not found".to_string(),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to fetch group: {}", e);
+            return Err(api_key_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal_error",
+                "Failed to fetch group information".to_string(),
+            ));
+        }
+    };
+
+    if group.owner_id().to_string() != user.user_id() {
+        warn!(
+            user_id = %user.user_id(),
+            group_id = %group_id,
+            "Group API key request denied: caller does not own the group"
+        );
+        return Err(api_key_error(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "Only the group owner may manage the group's API keys".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Issues an API key that may only post events to the group's receivers
+///
+/// Membership is checked on every request, so receivers added to the group
+/// later are covered and removed ones stop accepting the key immediately.
+/// The secret is only returned in this response.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid group id, empty name, or a `receiver_id`
+///   alongside the group scope
+/// * `403 FORBIDDEN` - Caller does not own the group
+/// * `404 NOT_FOUND` - Group does not exist
+pub async fn create_group_api_key(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id_str): Path<String>,
+    Json(request): Json<CreateGroupApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreateApiKeyResponse>), ApiKeyError> {
+    let group_id = parse_group_id(&group_id_str)?;
+    let scope = request.scope(group_id).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )
+    })?;
+
+    let service = api_key_service(&state)?;
+    check_group_owner(&state, &user, group_id).await?;
+
+    let user_id = user.user_id().parse::<UserId>().map_err(|_| {
+        api_key_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal_error",
+            "Invalid user ID in authentication token".to_string(),
+        )
+    })?;
+    let (key, api_key) = service
+        .generate_scoped_api_key(
+            user_id,
+            request.name,
+            request.expires_at.map(Timestamp::utc),
+            scope,
+        )
+        .await
+        .map_err(auth_error_response)?;
+
+    info!(
+        user_id = %user.user_id(),
+        group_id = %group_id,
+        key_id = %api_key.id,
+        "Group API key created"
+    );
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateApiKeyResponse {
+            key,
+            api_key: ApiKeyResponse::at(&api_key, Utc::now()),
+        }),
+    ))
+}
+
+/// Lists the API keys scoped to a group
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid group id
+/// * `403 FORBIDDEN` - Caller does not own the group
+/// * `404 NOT_FOUND` - Group does not exist
+pub async fn list_group_api_keys(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id_str): Path<String>,
+) -> Result<Json<Vec<ApiKeyResponse>>, ApiKeyError> {
+    let group_id = parse_group_id(&group_id_str)?;
+    let service = api_key_service(&state)?;
+    check_group_owner(&state, &user, group_id).await?;
+
+    let now = Utc::now();
+    let keys = service
+        .list_group_keys(group_id)
+        .await
+        .map_err(auth_error_response)?;
+    Ok(Json(
+        keys.iter()
+            .map(|key| ApiKeyResponse::at(key, now).with_stale(service.is_stale(key, now)))
+            .collect(),
+    ))
+}
+
+/// Revokes an API key scoped to a group
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid group or key id
+/// * `403 FORBIDDEN` - Caller does not own the group
+/// * `404 NOT_FOUND` - Group does not exist or the key is not scoped to it
+pub async fn revoke_group_api_key(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((group_id_str, key_id_str)): Path<(String, String)>,
+) -> Result<StatusCode, ApiKeyError> {
+    let group_id = parse_group_id(&group_id_str)?;
+    let key_id = parse_key_id(&key_id_str)?;
+    let service = api_key_service(&state)?;
+    check_group_owner(&state, &user, group_id).await?;
+
+    let api_key = service.get_key(key_id).await.map_err(auth_error_response)?;
+    if api_key.group_id != Some(group_id) {
+        return Err(auth_error_response(AuthError::ApiKeyNotFound));
+    }
+    service
+        .revoke_key(key_id)
+        .await
+        .map_err(auth_error_response)?;
+
+    info!(
+        user_id = %user.user_id(),
+        group_id = %group_id,
+        key_id = %key_id,
+        "Group API key revoked"
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,623 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/attachments.rs
+
+//! Files attached to events
+//!
+//! Uploads are `multipart/form-data` requests whose `file` part is streamed
+//! to the blob store without being buffered. Downloads stream the blob back
+//! and honour single `Range` requests.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use axum::{
+    body::{Body, Bytes},
+    extract::{multipart::Field, Multipart, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use http_body::Frame;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+use crate::api::field_access::{FieldAccess, READ_PAYLOAD_PERMISSION};
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{AttachmentResponse, ErrorResponse};
+use crate::api::rest::events::AppState;
+use crate::application::handlers::{
+    AttachmentUpload, EventAttachmentHandler, NewAttachment, UploadRejection,
+};
+use crate::domain::entities::event::Event;
+use crate::domain::entities::event_attachment::EventAttachment;
+use crate::domain::repositories::event_attachment_repo::{ByteRange, ByteStream};
+use crate::domain::value_objects::{AttachmentId, EventId, UserId};
+use crate::error::{DomainError, Error};
+use crate::i18n::Message;
+
+/// Name of the multipart part carrying the file
+pub const FILE_PART: &str = "file";
+
+/// Content type assumed for a file part without one
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Chunks buffered between the blob store and a slow client
+const DOWNLOAD_BUFFER_CHUNKS: usize = 4;
+
+type ApiError = (StatusCode, Json<ErrorResponse>);
+
+/// Uploads a file to an event
+///
+/// The request is a `multipart/form-data` form with a `file` part; other
+/// parts are ignored. Only the event's owner and admins may upload.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - The form is malformed, has no `file` part, or the
+///   filename is unusable
+/// * `403 FORBIDDEN` - The caller does not own the event
+/// * `404 NOT_FOUND` - The event does not exist
+/// * `413 PAYLOAD_TOO_LARGE` - The file exceeds the size limit or the
+///   receiver's remaining quota
+/// * `415 UNSUPPORTED_MEDIA_TYPE` - The file's content type is not allowed
+/// * `503 SERVICE_UNAVAILABLE` - Attachments are not enabled
+pub async fn upload_attachment(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<AttachmentResponse>), ApiError> {
+    let handler = attachment_handler(&state)?;
+    let event = find_event(&state, &id_str).await?;
+    if !can_modify(&user, &event) {
+        warn!(
+            user_id = %user.user_id(),
+            event_id = %event.id(),
+            "Attachment upload denied: not the event owner"
+        );
+        return Err(forbidden(
+            "Only the event owner or an admin may add attachments",
+        ));
+    }
+    let uploaded_by = user.user_id().parse::<UserId>().map_err(|e| {
+        error!("Invalid user ID in JWT token: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(
+                "internal_error".to_string(),
+                "Invalid user ID in authentication token".to_string(),
+            )),
+        )
+    })?;
+
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some(FILE_PART) {
+            continue;
+        }
+
+        let file = NewAttachment {
+            filename: field.file_name().unwrap_or_default().to_string(),
+            content_type: field
+                .content_type()
+                .unwrap_or(DEFAULT_CONTENT_TYPE)
+                .to_string(),
+            uploaded_by,
+        };
+        let outcome = handler
+            .upload(&event, file, &mut MultipartChunks(field))
+            .await
+            .map_err(|e| {
+                error!("Failed to store attachment of {}: {}", event.id(), e);
+                (
+                    e.status_code(),
+                    Json(ErrorResponse::from_error(
+                        "attachment_upload_failed".to_string(),
+                        &e,
+                    )),
+                )
+            })?;
+
+        return match outcome {
+            AttachmentUpload::Stored(attachment) => Ok((
+                StatusCode::CREATED,
+                Json(AttachmentResponse::from(attachment)),
+            )),
+            AttachmentUpload::Rejected(rejection) => {
+                info!(
+                    event_id = %event.id(),
+                    rejection = ?rejection,
+                    "Attachment upload rejected"
+                );
+                let (status, code) = match rejection {
+                    UploadRejection::UnsupportedContentType { .. } => (
+                        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                        "unsupported_attachment_type",
+                    ),
+                    UploadRejection::TooLarge { .. } => {
+                        (StatusCode::PAYLOAD_TOO_LARGE, "attachment_too_large")
+                    }
+                    UploadRejection::QuotaExceeded { .. } => {
+                        (StatusCode::PAYLOAD_TOO_LARGE, "attachment_quota_exceeded")
+                    }
+                };
+                Err((
+                    status,
+                    Json(ErrorResponse::from_domain(
+                        code.to_string(),
+                        &rejection.error(),
+                    )),
+                ))
+            }
+        };
+    }
+
+    Err((
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse::from_domain(
+            "validation_error".to_string(),
+            &DomainError::ValidationError {
+                field: FILE_PART.to_string(),
+                message: Message::new("validation.attachment_file_missing"),
+            },
+        )),
+    ))
+}
+
+/// Downloads an attachment
+///
+/// A single `Range: bytes=...` request is answered with `206 PARTIAL_CONTENT`;
+/// malformed and multi-range headers get the whole file. Downloads need
+/// the same access as the event payload.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - The caller may not read the event payload
+/// * `404 NOT_FOUND` - The event or attachment does not exist
+/// * `416 RANGE_NOT_SATISFIABLE` - The range starts past the end of the file
+/// * `503 SERVICE_UNAVAILABLE` - Attachments are not enabled
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((id_str, attachment_id_str)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let handler = attachment_handler(&state)?;
+    let event = find_event(&state, &id_str).await?;
+    if !FieldAccess::for_user(Some(&user)).can_read_payload(event.owner_id()) {
+        return Err(forbidden(&format!(
+            "{} permission required",
+            READ_PAYLOAD_PERMISSION
+        )));
+    }
+    let attachment = find_attachment(handler, event.id(), &attachment_id_str).await?;
+
+    let size = attachment.size_bytes;
+    let range_header = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+    let range = match parse_range(range_header, size) {
+        RangeRequest::Full => None,
+        RangeRequest::Partial(range) => Some(range),
+        RangeRequest::Unsatisfiable => {
+            return Ok((
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [(header::CONTENT_RANGE, format!("bytes */{}", size))],
+                Json(ErrorResponse::new(
+                    "range_not_satisfiable".to_string(),
+                    format!("Range is outside the {} byte attachment", size),
+                )),
+            )
+                .into_response());
+        }
+    };
+
+    let content = handler.open(&attachment, range).await.map_err(|e| {
+        error!("Failed to open attachment {}: {}", attachment.id, e);
+        (
+            e.status_code(),
+            Json(ErrorResponse::from_error(
+                "attachment_download_failed".to_string(),
+                &e,
+            )),
+        )
+    })?;
+    let Some(content) = content else {
+        error!(
+            attachment_id = %attachment.id,
+            key = %attachment.storage_key,
+            "Attachment content is missing from the blob store"
+        );
+        return Err(not_found("Attachment content not found"));
+    };
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_str(&attachment.content_type)
+            .unwrap_or(HeaderValue::from_static(DEFAULT_CONTENT_TYPE)),
+    );
+    response_headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&content_disposition(&attachment.filename))
+            .unwrap_or(HeaderValue::from_static("attachment")),
+    );
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", attachment.sha256)) {
+        response_headers.insert(header::ETAG, etag);
+    }
+
+    let status = match range {
+        Some(range) => {
+            response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(range.length()));
+            if let Ok(content_range) =
+                HeaderValue::from_str(&format!("bytes {}-{}/{}", range.start, range.end, size))
+            {
+                response_headers.insert(header::CONTENT_RANGE, content_range);
+            }
+            StatusCode::PARTIAL_CONTENT
+        }
+        None => {
+            response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(size));
+            StatusCode::OK
+        }
+    };
+
+    Ok((status, response_headers, stream_body(content)).into_response())
+}
+
+/// Deletes an attachment
+///
+/// Only the event's owner and admins may delete attachments.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - The caller does not own the event
+/// * `404 NOT_FOUND` - The event or attachment does not exist
+/// * `503 SERVICE_UNAVAILABLE` - Attachments are not enabled
+pub async fn delete_attachment(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((id_str, attachment_id_str)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    let handler = attachment_handler(&state)?;
+    let event = find_event(&state, &id_str).await?;
+    if !can_modify(&user, &event) {
+        warn!(
+            user_id = %user.user_id(),
+            event_id = %event.id(),
+            "Attachment delete denied: not the event owner"
+        );
+        return Err(forbidden(
+            "Only the event owner or an admin may delete attachments",
+        ));
+    }
+    let attachment = find_attachment(handler, event.id(), &attachment_id_str).await?;
+
+    handler.delete(&attachment).await.map_err(|e| {
+        error!("Failed to delete attachment {}: {}", attachment.id, e);
+        (
+            e.status_code(),
+            Json(ErrorResponse::from_error(
+                "attachment_deletion_failed".to_string(),
+                &e,
+            )),
+        )
+    })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Returns true if `user` owns `event` or is an admin
+fn can_modify(user: &AuthenticatedUser, event: &Event) -> bool {
+    user.has_role("admin") || user.user_id().parse::<UserId>().ok() == Some(event.owner_id())
+}
+
+fn attachment_handler(state: &AppState) -> Result<&EventAttachmentHandler, ApiError> {
+    state.attachment_handler.as_ref().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "attachments_unavailable".to_string(),
+                "Event attachments are not enabled".to_string(),
+            )),
+        )
+    })
+}
+
+async fn find_event(state: &AppState, id_str: &str) -> Result<Event, ApiError> {
+    let event_id = id_str.parse::<EventId>().map_err(|_| {
+        warn!("Invalid event ID format: {}", id_str);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid event ID format".to_string(),
+            )),
+        )
+    })?;
+
+    match state.event_handler.get_event(event_id).await {
+        Ok(Some(event)) => Ok(event),
+        Ok(None) => Err(not_found("Event not found")),
+        Err(e) => {
+            error!("Failed to get event {}: {}", event_id, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "event_retrieval_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+async fn find_attachment(
+    handler: &EventAttachmentHandler,
+    event_id: EventId,
+    id_str: &str,
+) -> Result<EventAttachment, ApiError> {
+    let Ok(id) = id_str.parse::<AttachmentId>() else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid attachment ID format".to_string(),
+            )),
+        ));
+    };
+
+    match handler.get(event_id, id).await {
+        Ok(Some(attachment)) => Ok(attachment),
+        Ok(None) => Err(not_found("Attachment not found")),
+        Err(e) => {
+            error!("Failed to get attachment {}: {}", id, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "attachment_retrieval_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+fn forbidden(message: &str) -> ApiError {
+    (
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse::new(
+            "forbidden".to_string(),
+            message.to_string(),
+        )),
+    )
+}
+
+fn not_found(message: &str) -> ApiError {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new(
+            "not_found".to_string(),
+            message.to_string(),
+        )),
+    )
+}
+
+fn multipart_error(e: axum::extract::multipart::MultipartError) -> ApiError {
+    (
+        e.status(),
+        Json(ErrorResponse::new(
+            "invalid_multipart".to_string(),
+            e.body_text(),
+        )),
+    )
+}
+
+/// Reads the content of a multipart part chunk by chunk
+struct MultipartChunks<'a>(Field<'a>);
+
+#[async_trait]
+impl ByteStream for MultipartChunks<'_> {
+    async fn next_chunk(&mut self) -> crate::error::Result<Option<Vec<u8>>> {
+        self.0
+            .chunk()
+            .await
+            .map(|chunk| chunk.map(|bytes| bytes.to_vec()))
+            .map_err(|e| Error::BadRequest {
+                message: e.body_text(),
+            })
+    }
+}
+
+/// Response body fed by a task reading from the blob store
+struct BlobBody(mpsc::Receiver<crate::error::Result<Bytes>>);
+
+impl http_body::Body for BlobBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Error>>> {
+        self.0
+            .poll_recv(cx)
+            .map(|chunk| chunk.map(|chunk| chunk.map(Frame::data)))
+    }
+}
+
+/// Streams `content` as a response body
+///
+/// The reader stops as soon as the client goes away.
+fn stream_body(mut content: Box<dyn ByteStream>) -> Body {
+    let (tx, rx) = mpsc::channel(DOWNLOAD_BUFFER_CHUNKS);
+    tokio::spawn(async move {
+        loop {
+            let chunk = match content.next_chunk().await {
+                Ok(Some(chunk)) => Ok(Bytes::from(chunk)),
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Failed to read attachment content: {}", e);
+                    Err(e)
+                }
+            };
+            let failed = chunk.is_err();
+            if tx.send(chunk).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+    Body::new(BlobBody(rx))
+}
+
+/// What a `Range` header asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeRequest {
+    Full,
+    Partial(ByteRange),
+    Unsatisfiable,
+}
+
+/// Interprets a `Range` header against content of `size` bytes
+///
+/// Only single `bytes` ranges are served. Malformed headers and multiple
+/// ranges fall back to the full content, as RFC 9110 allows.
+fn parse_range(header: Option<&str>, size: u64) -> RangeRequest {
+    let Some(spec) = header.and_then(|value| value.trim().strip_prefix("bytes=")) else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((start, end)) = spec.split_once('-') else {
+        return RangeRequest::Full;
+    };
+
+    let (start, end) = (start.trim(), end.trim());
+    if start.is_empty() {
+        // `bytes=-N` asks for the last N bytes
+        return match end.parse::<u64>() {
+            Ok(0) => RangeRequest::Unsatisfiable,
+            Ok(_) if size == 0 => RangeRequest::Unsatisfiable,
+            Ok(suffix) => RangeRequest::Partial(ByteRange {
+                start: size.saturating_sub(suffix),
+                end: size - 1,
+            }),
+            Err(_) => RangeRequest::Full,
+        };
+    }
+
+    let Ok(start) = start.parse::<u64>() else {
+        return RangeRequest::Full;
+    };
+    let end = if end.is_empty() {
+        u64::MAX
+    } else {
+        match end.parse::<u64>() {
+            Ok(end) if end >= start => end,
+            _ => return RangeRequest::Full,
+        }
+    };
+    if start >= size {
+        return RangeRequest::Unsatisfiable;
+    }
+    RangeRequest::Partial(ByteRange {
+        start,
+        end: end.min(size - 1),
+    })
+}
+
+/// Builds a `Content-Disposition` header that always downloads the file
+///
+/// Names outside printable ASCII get an RFC 6266 `filename*` with an ASCII
+/// fallback.
+fn content_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| {
+            if c.is_ascii() && !c.is_ascii_control() && c != '"' && c != '\\' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if fallback == filename {
+        return format!("attachment; filename=\"{}\"", filename);
+    }
+
+    let encoded: String = filename
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect();
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partial(start: u64, end: u64) -> RangeRequest {
+        RangeRequest::Partial(ByteRange { start, end })
+    }
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range(None, 100), RangeRequest::Full);
+        assert_eq!(parse_range(Some("bytes=0-9"), 100), partial(0, 9));
+        assert_eq!(parse_range(Some("bytes=90-"), 100), partial(90, 99));
+        assert_eq!(parse_range(Some("bytes=90-500"), 100), partial(90, 99));
+        assert_eq!(parse_range(Some("bytes=-10"), 100), partial(90, 99));
+        assert_eq!(parse_range(Some("bytes=-500"), 100), partial(0, 99));
+
+        assert_eq!(
+            parse_range(Some("bytes=100-"), 100),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(
+            parse_range(Some("bytes=-0"), 100),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(
+            parse_range(Some("bytes=0-"), 0),
+            RangeRequest::Unsatisfiable
+        );
+
+        for ignored in [
+            "items=0-9",
+            "bytes=9-0",
+            "bytes=0-1,5-6",
+            "bytes=a-b",
+            "bytes",
+        ] {
+            assert_eq!(
+                parse_range(Some(ignored), 100),
+                RangeRequest::Full,
+                "{}",
+                ignored
+            );
+        }
+    }
+
+    #[test]
+    fn test_content_disposition() {
+        assert_eq!(
+            content_disposition("sbom.json"),
+            "attachment; filename=\"sbom.json\""
+        );
+        assert_eq!(
+            content_disposition("résumé.pdf"),
+            "attachment; filename=\"r_sum_.pdf\"; filename*=UTF-8''r%C3%A9sum%C3%A9.pdf"
+        );
+    }
+}
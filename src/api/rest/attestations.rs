@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/attestations.rs
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::collections::HashMap;
+use tracing::{error, info, warn};
+
+use crate::api::field_access::FieldAccess;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    AttestationLookupResponse, AttestationQueryParams, AttestedEventResponse, ErrorResponse,
+    EventResponse,
+};
+use crate::api::rest::events::AppState;
+use crate::application::authorization::{ProtectedResource, ResourceAction};
+use crate::domain::value_objects::EventReceiverId;
+
+type AttestationError = (StatusCode, Json<ErrorResponse>);
+
+/// Attestations read per page while skipping unreadable receivers
+const SCAN_PAGE_SIZE: usize = 200;
+
+/// Most attestations read for a single lookup
+const MAX_SCANNED_ATTESTATIONS: usize = 2_000;
+
+/// Finds events attesting to an artifact digest
+///
+/// `digest` is `algorithm:hex`, such as `sha256:abc...`, and matches the
+/// subjects of SLSA provenance and the top-level component hashes of
+/// CycloneDX SBOMs. Events are returned newest first, across every
+/// receiver the caller may read; events of other receivers are skipped.
+/// Payloads are redacted as for the event endpoints.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Malformed digest, unknown format, or `limit` out
+///   of range
+pub async fn find_attestations_by_digest(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(digest): Path<String>,
+    Query(params): Query<AttestationQueryParams>,
+) -> Result<Json<AttestationLookupResponse>, AttestationError> {
+    let filter = params.to_filter(&digest).map_err(|e| {
+        warn!("Attestation lookup validation failed: {}", e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )
+    })?;
+    let limit = filter.limit.unwrap_or_default();
+    let digest = filter.digest.clone().unwrap_or_default();
+
+    info!(user_id = %user.user_id(), digest = %digest, "Looking up attestations by digest");
+
+    let access = FieldAccess::for_user(Some(&user));
+    let mut readable: HashMap<EventReceiverId, bool> = HashMap::new();
+    let mut events = Vec::new();
+    let mut offset = 0;
+    while events.len() < limit && offset < MAX_SCANNED_ATTESTATIONS {
+        let page = state
+            .event_handler
+            .list_events_by_attestation(
+                filter
+                    .clone()
+                    .with_offset(offset)
+                    .with_limit(SCAN_PAGE_SIZE),
+            )
+            .await
+            .map_err(|e| {
+                error!("Failed to look up attestations for {}: {}", digest, e);
+                (
+                    e.status_code(),
+                    Json(ErrorResponse::from_error(
+                        "attestation_lookup_failed".to_string(),
+                        &e,
+                    )),
+                )
+            })?;
+        let scanned = page.len();
+
+        for (event, attestation) in page {
+            let receiver_id = event.event_receiver_id();
+            let allowed = match readable.get(&receiver_id) {
+                Some(allowed) => *allowed,
+                None => {
+                    let allowed = can_read_receiver(&state, &user, receiver_id).await?;
+                    readable.insert(receiver_id, allowed);
+                    allowed
+                }
+            };
+            if allowed && events.len() < limit {
+                events.push(AttestedEventResponse {
+                    attestation: attestation.attestation.into(),
+                    event: EventResponse::for_caller(event, &access),
+                });
+            }
+        }
+
+        if scanned < SCAN_PAGE_SIZE {
+            break;
+        }
+        offset += SCAN_PAGE_SIZE;
+    }
+
+    Ok(Json(AttestationLookupResponse { digest, events }))
+}
+
+/// Returns true if `user` may read the events of `receiver_id`
+///
+/// Denied and since deleted receivers are skipped rather than failing the
+/// lookup.
+async fn can_read_receiver(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    receiver_id: EventReceiverId,
+) -> Result<bool, AttestationError> {
+    match state
+        .authorization
+        .authorize(
+            &user.claims,
+            ResourceAction::Read,
+            ProtectedResource::Receiver(receiver_id),
+        )
+        .await
+    {
+        Ok(()) => Ok(true),
+        Err(e)
+            if matches!(
+                e.status_code(),
+                StatusCode::FORBIDDEN | StatusCode::NOT_FOUND
+            ) =>
+        {
+            Ok(false)
+        }
+        Err(e) => {
+            error!(
+                "Failed to authorize reading receiver {}: {}",
+                receiver_id, e
+            );
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "authorization_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/audit.rs
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{AuditVerifyQuery, AuditVerifyResponse, ErrorResponse};
+use crate::api::rest::events::AppState;
+
+/// Role required to verify the audit records
+const AUDIT_ROLE: &str = "admin";
+
+/// Verifies the hash chain of the stored audit records
+///
+/// Checks the days `from` through `to` (UTC, inclusive; both default to
+/// today) and reports the first record whose hash does not match. A broken
+/// chain is reported in the body, not as an error status. Requires the
+/// admin role.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - `from` is after `to`
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `503 SERVICE_UNAVAILABLE` - Audit records are not persisted
+pub async fn verify_audit_chain(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<AuditVerifyQuery>,
+) -> Result<Json<AuditVerifyResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if !user.has_role(AUDIT_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "Audit verification denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    let Some(handler) = state.audit_chain.as_ref() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "audit_unavailable".to_string(),
+                "Audit records are not persisted".to_string(),
+            )),
+        ));
+    };
+
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query.from.unwrap_or(to);
+    info!(user_id = %user.user_id(), from = %from, to = %to, "Verifying audit chain");
+
+    match handler.verify(from, to).await {
+        Ok(report) => Ok(Json(AuditVerifyResponse::new(from, to, report))),
+        Err(e) => {
+            error!("Failed to verify audit chain: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "audit_verify_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
@@ -389,14 +389,19 @@ fn permission_to_string(permission: &crate::auth::rbac::Permission) -> String {
         Permission::EventRead => "event:read".to_string(),
         Permission::EventUpdate => "event:update".to_string(),
         Permission::EventDelete => "event:delete".to_string(),
+        Permission::EventReadMeta => "event:read_meta".to_string(),
+        Permission::EventReadPayload => "event:read_payload".to_string(),
         Permission::ReceiverCreate => "receiver:create".to_string(),
         Permission::ReceiverRead => "receiver:read".to_string(),
         Permission::ReceiverUpdate => "receiver:update".to_string(),
         Permission::ReceiverDelete => "receiver:delete".to_string(),
+        Permission::ReceiverReadSchema => "receiver:read_schema".to_string(),
+        Permission::ReceiverReadMeta => "receiver:read_meta".to_string(),
         Permission::GroupCreate => "group:create".to_string(),
         Permission::GroupRead => "group:read".to_string(),
         Permission::GroupUpdate => "group:update".to_string(),
         Permission::GroupDelete => "group:delete".to_string(),
+        Permission::GroupManageKeys => "group:manage_keys".to_string(),
         Permission::UserManage => "user:manage".to_string(),
         Permission::RoleManage => "role:manage".to_string(),
     }
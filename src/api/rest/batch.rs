@@ -0,0 +1,245 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/batch.rs
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    Extension,
+};
+use std::collections::HashMap;
+use tracing::{error, info, warn};
+
+use crate::api::middleware::api_key::ApiKeyPrincipal;
+use crate::api::middleware::client_ip::ClientIp;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    BatchItemResponse, BatchSummaryResponse, CreateEventBatchRequest, CreateEventBatchResponse,
+    CreateEventRequest, ErrorResponse,
+};
+use crate::api::rest::events::{
+    api_key_scope_allows, ingestion_context, requested_publish_policy, AppState,
+    QUOTA_EXCEEDED_CODE, RECEIVER_AUTO_DISABLED_CODE, RESERVED_EVENT_NAME_CODE,
+};
+use crate::application::handlers::{BatchItem, BatchItemResult};
+use crate::domain::entities::event::CreateEventParams;
+use crate::domain::value_objects::{EventReceiverId, UserId};
+use crate::error::{AuthorizationError, DomainError, Error, InfrastructureError};
+use crate::i18n::Message;
+
+/// Message of items an API key may not post
+const API_KEY_SCOPE_MESSAGE: &str = "API key may not post events to this receiver";
+
+/// Creates many events in one request
+///
+/// Each item is checked and stored like a single `POST /api/v1/events` and
+/// gets its own result, so a bad item does not fail the batch. Items must
+/// name an existing receiver; `receiver_spec` is not supported here. The
+/// optional `quota_mode` decides what happens when a receiver's daily quota
+/// runs out: `best_effort` stores items until it does, `atomic` rejects the
+/// whole batch up front.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Empty batch, too many events, or an unknown
+///   `quota_mode` or publish policy
+/// * `429 TOO_MANY_REQUESTS` - An atomic batch exceeds a receiver's quota;
+///   nothing was stored
+pub async fn create_event_batch(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    headers: HeaderMap,
+    client_ip: Option<Extension<ClientIp>>,
+    api_key: Option<Extension<ApiKeyPrincipal>>,
+    Json(request): Json<CreateEventBatchRequest>,
+) -> Result<(StatusCode, Json<CreateEventBatchResponse>), (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        user_id = %user.user_id(),
+        events = request.events.len(),
+        "Creating event batch"
+    );
+
+    let owner_id = match user.user_id().parse::<UserId>() {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Invalid user ID in JWT token: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(
+                    "internal_error".to_string(),
+                    "Invalid user ID in authentication token".to_string(),
+                )),
+            ));
+        }
+    };
+
+    let (mode, publish_policy) = match request
+        .quota_mode()
+        .and_then(|mode| requested_publish_policy(&headers).map(|policy| (mode, policy)))
+    {
+        Ok(options) => options,
+        Err(e) => {
+            warn!("Event batch validation failed: {}", e);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_domain(
+                    "validation_error".to_string(),
+                    &e,
+                )),
+            ));
+        }
+    };
+
+    let principal = api_key.as_ref().map(|Extension(principal)| principal);
+    let mut scope_allows: HashMap<EventReceiverId, bool> = HashMap::new();
+    let mut items = Vec::with_capacity(request.events.len());
+    for (index, value) in request.events.into_iter().enumerate() {
+        let event = match parse_item(index, value) {
+            Ok(event) => event,
+            Err(e) => {
+                items.push(BatchItem::Rejected(e.into()));
+                continue;
+            }
+        };
+        let receiver_id = event.event_receiver_id.expect("validated by parse_item");
+
+        if let Some(principal) = principal {
+            let allowed = match scope_allows.get(&receiver_id) {
+                Some(allowed) => *allowed,
+                None => {
+                    match api_key_scope_allows(&state, principal.scope, Some(receiver_id)).await {
+                        Ok(allowed) => *scope_allows.entry(receiver_id).or_insert(allowed),
+                        Err(e) => {
+                            error!("Failed to load API key scope group: {}", e);
+                            items.push(BatchItem::Rejected(e));
+                            continue;
+                        }
+                    }
+                }
+            };
+            if !allowed {
+                warn!(
+                    scope = ?principal.scope,
+                    receiver_id = %receiver_id,
+                    "API key scope denied batch item"
+                );
+                items.push(BatchItem::Rejected(
+                    AuthorizationError::PermissionDenied.into(),
+                ));
+                continue;
+            }
+        }
+
+        items.push(BatchItem::Event(CreateEventParams {
+            name: event.name,
+            version: event.version,
+            release: event.release,
+            platform_id: event.platform_id,
+            package: event.package,
+            description: event.description,
+            payload: event.payload,
+            success: event.success,
+            receiver_id,
+            owner_id,
+        }));
+    }
+
+    let context = ingestion_context(
+        &user,
+        principal,
+        client_ip.as_ref().map(|Extension(client_ip)| client_ip),
+        &headers,
+    );
+
+    match state
+        .event_batch_handler
+        .submit(items, context, publish_policy, mode)
+        .await
+    {
+        Ok(report) => {
+            let summary = BatchSummaryResponse::from(&report);
+            Ok((
+                StatusCode::MULTI_STATUS,
+                Json(CreateEventBatchResponse {
+                    results: report.items.into_iter().map(item_response).collect(),
+                    summary,
+                }),
+            ))
+        }
+        Err(e @ Error::Domain(DomainError::QuotaExceeded { .. })) => {
+            warn!("Event batch rejected: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::new(
+                    QUOTA_EXCEEDED_CODE.to_string(),
+                    e.message(),
+                )),
+            ))
+        }
+        Err(e) => {
+            warn!("Event batch rejected: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    if e.status_code().is_client_error() {
+                        "validation_error"
+                    } else {
+                        "event_creation_failed"
+                    }
+                    .to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Reads and validates item `index` of a batch
+fn parse_item(index: usize, value: serde_json::Value) -> Result<CreateEventRequest, DomainError> {
+    let event: CreateEventRequest =
+        serde_json::from_value(value).map_err(|e| DomainError::ValidationError {
+            field: format!("events[{}]", index),
+            message: Message::new("validation.batch_item_malformed").with("detail", e),
+        })?;
+    if event.receiver_spec.is_some() {
+        return Err(DomainError::ValidationError {
+            field: "receiver_spec".to_string(),
+            message: Message::new("validation.batch_receiver_spec"),
+        });
+    }
+    event.validate()?;
+    Ok(event)
+}
+
+/// Builds the response entry of one batch item
+fn item_response(item: BatchItemResult) -> BatchItemResponse {
+    BatchItemResponse {
+        index: item.index,
+        outcome: item.outcome.to_string(),
+        event_id: item.event_id,
+        duplicate_of: item.duplicate_of,
+        error: item.error.map(|e| {
+            let code = match &e {
+                Error::Domain(DomainError::ReservedEventName { .. }) => RESERVED_EVENT_NAME_CODE,
+                Error::Domain(DomainError::QuotaExceeded { .. }) => QUOTA_EXCEEDED_CODE,
+                Error::Domain(DomainError::ReceiverAutoDisabled { .. }) => {
+                    RECEIVER_AUTO_DISABLED_CODE
+                }
+                Error::Authorization(_) => {
+                    return ErrorResponse::new(
+                        "api_key_scope".to_string(),
+                        API_KEY_SCOPE_MESSAGE.to_string(),
+                    )
+                }
+                Error::Infrastructure(InfrastructureError::PublishUnavailable { .. }) => {
+                    "PUBLISH_UNAVAILABLE"
+                }
+                _ if e.status_code().is_client_error() => "validation_error",
+                _ => "event_creation_failed",
+            };
+            ErrorResponse::from_error(code.to_string(), &e)
+        }),
+    }
+}
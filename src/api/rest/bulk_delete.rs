@@ -0,0 +1,69 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/bulk_delete.rs
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{BulkDeleteRequest, BulkDeleteResponse, ErrorResponse};
+use crate::api::rest::events::AppState;
+
+/// Role required to bulk delete receivers and groups
+const BULK_DELETE_ROLE: &str = "admin";
+
+/// Deletes many receivers and groups selected by id or name prefix
+///
+/// Receivers still referenced by events, or belonging to a group outside
+/// the selection, are reported as blocked and left in place. Without
+/// `"dry_run": false` nothing is deleted and the report shows what would
+/// be. Requires the admin role.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Empty selector, or more resources than the cap
+/// * `403 FORBIDDEN` - Caller is not an administrator
+pub async fn bulk_delete(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<BulkDeleteRequest>,
+) -> Result<Json<BulkDeleteResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if !user.has_role(BULK_DELETE_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "Bulk delete denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    info!(
+        user_id = %user.user_id(),
+        dry_run = request.dry_run,
+        "Bulk deleting receivers and groups"
+    );
+
+    match state
+        .bulk_delete_handler
+        .execute(&(&request).into(), request.dry_run, user.user_id())
+        .await
+    {
+        Ok(report) => Ok(Json(report.into())),
+        Err(e) => {
+            error!("Bulk delete failed: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "bulk_delete_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
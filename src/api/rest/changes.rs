@@ -0,0 +1,130 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/changes.rs
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info, warn};
+
+use crate::api::field_access::FieldAccess;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    ChangesQueryParams, ChangesResponse, ErrorResponse, EventReceiverGroupResponse,
+    EventReceiverResponse,
+};
+use crate::api::rest::events::AppState;
+use crate::domain::repositories::change_feed_repo::ChangeCursor;
+
+/// Lists event receivers changed since a point in time
+///
+/// Returns receivers created or updated after `since` as full objects and
+/// deleted receivers as a list of ids. Omitting `since` returns every
+/// receiver, which is how a client performs its initial sync. Schemas are
+/// included only when the caller may read them.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid `since` or `limit`
+/// * `500 INTERNAL_SERVER_ERROR` - Unexpected server error
+pub async fn list_event_receiver_changes(
+    State(state): State<AppState>,
+    user: Option<AuthenticatedUser>,
+    Query(params): Query<ChangesQueryParams>,
+) -> Result<Json<ChangesResponse<EventReceiverResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        since = ?params.since,
+        limit = %params.limit,
+        "Listing event receiver changes"
+    );
+
+    let since = validate_params(&params)?;
+
+    match state
+        .change_feed_handler
+        .receiver_changes(since, params.limit)
+        .await
+    {
+        Ok(changes) => {
+            let access = FieldAccess::for_user(user.as_ref());
+            Ok(Json(ChangesResponse::from_change_set(
+                changes,
+                |receiver| EventReceiverResponse::for_caller(receiver, &access),
+            )))
+        }
+        Err(e) => {
+            error!("Failed to list event receiver changes: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "list_changes_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Lists event receiver groups changed since a point in time
+///
+/// Same contract as [`list_event_receiver_changes`], for groups.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid `since` or `limit`
+/// * `500 INTERNAL_SERVER_ERROR` - Unexpected server error
+pub async fn list_event_receiver_group_changes(
+    State(state): State<AppState>,
+    Query(params): Query<ChangesQueryParams>,
+) -> Result<Json<ChangesResponse<EventReceiverGroupResponse>>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        since = ?params.since,
+        limit = %params.limit,
+        "Listing event receiver group changes"
+    );
+
+    let since = validate_params(&params)?;
+
+    match state
+        .change_feed_handler
+        .group_changes(since, params.limit)
+        .await
+    {
+        Ok(changes) => Ok(Json(ChangesResponse::from_change_set(
+            changes,
+            EventReceiverGroupResponse::from,
+        ))),
+        Err(e) => {
+            error!("Failed to list event receiver group changes: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "list_changes_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Validates change feed parameters and returns the parsed cursor
+fn validate_params(
+    params: &ChangesQueryParams,
+) -> Result<Option<ChangeCursor>, (StatusCode, Json<ErrorResponse>)> {
+    params
+        .validate()
+        .and_then(|_| params.cursor())
+        .map_err(|e| {
+            warn!("Change feed query validation failed: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_domain(
+                    "validation_error".to_string(),
+                    &e,
+                )),
+            )
+        })
+}
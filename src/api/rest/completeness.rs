@@ -0,0 +1,101 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/completeness.rs
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    ErrorResponse, GroupCompletenessQueryParams, GroupCompletenessResponse,
+};
+use crate::api::rest::events::{authorize, AppState};
+use crate::application::authorization::{ProtectedResource, ResourceAction};
+use crate::domain::value_objects::EventReceiverGroupId;
+
+type CompletenessError = (StatusCode, Json<ErrorResponse>);
+
+/// Reports whether every required receiver of a group reported a release
+///
+/// For each member receiver, says whether it stored a successful event
+/// with `release` (`complete`), only failed ones (`failed_only`), or none
+/// (`missing`), counting events stored from `since` on when it is set.
+/// Optional receivers are listed but never block `complete`. The first
+/// complete report of a release records an
+/// `xzepr.event.receiver.group.release_completed` system event.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid group id, missing release, or malformed
+///   `since`
+/// * `403 FORBIDDEN` - The caller may not read the group
+/// * `404 NOT_FOUND` - The group does not exist
+/// * `503 SERVICE_UNAVAILABLE` - Completeness reports are not configured
+pub async fn get_group_completeness(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+    Query(query): Query<GroupCompletenessQueryParams>,
+) -> Result<Json<GroupCompletenessResponse>, CompletenessError> {
+    let group_id = id_str.parse::<EventReceiverGroupId>().map_err(|_| {
+        warn!("Invalid event receiver group ID format: {}", id_str);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid event receiver group ID format".to_string(),
+            )),
+        )
+    })?;
+    let since = query.since().map_err(|e| {
+        warn!("Invalid completeness window: {}", e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )
+    })?;
+
+    let completeness = state
+        .event_receiver_group_handler
+        .completeness()
+        .ok_or_else(|| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ErrorResponse::new(
+                    "completeness_unavailable".to_string(),
+                    "Group completeness reports are not configured".to_string(),
+                )),
+            )
+        })?;
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Read,
+        ProtectedResource::Group(group_id),
+    )
+    .await?;
+
+    let release = query.release.unwrap_or_default();
+    info!(group_id = %group_id, release = %release, "Reading group release completeness");
+    match completeness.completeness(group_id, &release, since).await {
+        Ok(report) => Ok(Json(report.into())),
+        Err(e) => {
+            error!("Failed to read completeness of group {}: {}", group_id, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "completeness_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
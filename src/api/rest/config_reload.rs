@@ -0,0 +1,95 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/config_reload.rs
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::ErrorResponse;
+use crate::api::rest::events::AppState;
+use crate::error::Error;
+use crate::infrastructure::ReloadReport;
+
+/// Role required to reload the configuration
+const CONFIG_RELOAD_ROLE: &str = "admin";
+
+type ConfigReloadError = (StatusCode, Json<ErrorResponse>);
+
+/// Re-reads the configuration and applies the reloadable settings
+///
+/// Same as sending `SIGHUP` to the process. Returns the settings that
+/// changed with their old and new values; changed startup-only settings
+/// are listed under `ignored` with a warning and take effect on the next
+/// restart. Secret values are never returned. Each applied change is
+/// recorded in the audit log. Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `422 UNPROCESSABLE_ENTITY` - The configuration cannot be read or a
+///   reloadable setting is invalid; the running settings are unchanged
+/// * `503 SERVICE_UNAVAILABLE` - Runtime reload is not enabled
+pub async fn reload_config(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<ReloadReport>, ConfigReloadError> {
+    if !user.has_role(CONFIG_RELOAD_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "Configuration reload denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    let Some(settings) = state.runtime_settings.clone() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "config_reload_unavailable".to_string(),
+                "Configuration reload is not enabled".to_string(),
+            )),
+        ));
+    };
+
+    info!(user_id = %user.user_id(), "Reloading configuration");
+    let actor = user.user_id().to_string();
+    let result = tokio::task::spawn_blocking(move || settings.reload(&actor))
+        .await
+        .unwrap_or_else(|e| {
+            Err(Error::Internal {
+                message: e.to_string(),
+            })
+        });
+
+    match result {
+        Ok(report) => Ok(Json(report)),
+        Err(e @ Error::Config(_)) => {
+            warn!("Configuration reload rejected: {}", e);
+            Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ErrorResponse::new(
+                    "config_invalid".to_string(),
+                    e.to_string(),
+                )),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to reload configuration: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "config_reload_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/debug.rs
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, KafkaProducerDebugResponse};
+use crate::api::rest::events::AppState;
+
+/// Role required to read debug endpoints
+const DEBUG_ROLE: &str = "admin";
+
+/// Returns the effective Kafka producer configuration
+///
+/// Passwords and other secrets are redacted. Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+pub async fn get_kafka_producer_config(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<KafkaProducerDebugResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if !user.has_role(DEBUG_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "Kafka producer debug request denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    info!(user_id = %user.user_id(), "Reading Kafka producer configuration");
+
+    let response = match state.event_handler.event_publisher() {
+        Some(publisher) => KafkaProducerDebugResponse {
+            enabled: true,
+            topic: Some(publisher.topic().to_string()),
+            config: publisher.effective_config().clone(),
+        },
+        None => KafkaProducerDebugResponse {
+            enabled: false,
+            topic: None,
+            config: Default::default(),
+        },
+    };
+
+    Ok(Json(response))
+}
@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/delivery_failures.rs
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::warn;
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    DeliveryFailureQueryParams, DeliveryFailureResponse, DeliveryFailuresResponse, ErrorResponse,
+};
+use crate::api::rest::events::AppState;
+use crate::domain::entities::delivery_failure::DeliveryErrorClass;
+use crate::domain::repositories::delivery_failure_repo::FindDeliveryFailureCriteria;
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Error;
+
+/// Role required to list delivery failures
+const DELIVERY_FAILURES_ROLE: &str = "admin";
+
+type DeliveryFailuresError = (StatusCode, Json<ErrorResponse>);
+
+/// Lists the latest deliveries given up on, across every mechanism
+///
+/// Newest first, optionally only those of one receiver or error class.
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid receiver id or error class
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `503 SERVICE_UNAVAILABLE` - No delivery failure log is configured
+pub async fn list_delivery_failures(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(params): Query<DeliveryFailureQueryParams>,
+) -> Result<Json<DeliveryFailuresResponse>, DeliveryFailuresError> {
+    if !user.has_role(DELIVERY_FAILURES_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "Delivery failure listing denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    let handler = state.delivery_failures.as_ref().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "delivery_failures_unavailable".to_string(),
+                "Delivery failures are not recorded".to_string(),
+            )),
+        )
+    })?;
+
+    let receiver_id = params
+        .receiver_id
+        .as_deref()
+        .map(|id| {
+            id.parse::<EventReceiverId>().map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::new(
+                        "invalid_id".to_string(),
+                        "Invalid event receiver ID format".to_string(),
+                    )),
+                )
+            })
+        })
+        .transpose()?;
+    let error_class = params
+        .error_class
+        .as_deref()
+        .map(|class| {
+            class.parse::<DeliveryErrorClass>().map_err(|e| {
+                let e = Error::from(e);
+                (
+                    e.status_code(),
+                    Json(ErrorResponse::from_error(
+                        "invalid_error_class".to_string(),
+                        &e,
+                    )),
+                )
+            })
+        })
+        .transpose()?;
+
+    let failures = handler
+        .recent(FindDeliveryFailureCriteria {
+            receiver_id,
+            error_class,
+            limit: params.limit.unwrap_or_default(),
+        })
+        .await
+        .map_err(|e| {
+            (
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "delivery_failures_failed".to_string(),
+                    &e,
+                )),
+            )
+        })?;
+
+    Ok(Json(DeliveryFailuresResponse {
+        failures: failures
+            .into_iter()
+            .map(DeliveryFailureResponse::from)
+            .collect(),
+    }))
+}
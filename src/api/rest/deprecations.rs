@@ -0,0 +1,69 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/deprecations.rs
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{DeprecationQueryParams, DeprecationsResponse, ErrorResponse};
+use crate::api::rest::events::AppState;
+
+/// Role required to read the deprecation report
+const DEPRECATIONS_ROLE: &str = "admin";
+
+/// Lists the registered deprecations with their heaviest users
+///
+/// Reports each deprecation's dates, whether it is past its sunset, and
+/// the `top` principals that relied on it since the server started.
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - `top` out of range
+/// * `403 FORBIDDEN` - Caller is not an administrator
+pub async fn list_deprecations(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(params): Query<DeprecationQueryParams>,
+) -> Result<Json<DeprecationsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if !user.has_role(DEPRECATIONS_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "Deprecation report denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    let top = params.top().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )
+    })?;
+
+    info!(user_id = %user.user_id(), "Reading deprecation report");
+
+    Ok(Json(DeprecationsResponse {
+        deprecations: state
+            .deprecations
+            .statuses(top)
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    }))
+}
@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/descriptions.rs
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::sync::Arc;
+use tracing::{error, warn};
+
+use crate::api::rest::dtos::ErrorResponse;
+use crate::api::rest::events::AppState;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::Result;
+
+type DescriptionError = (StatusCode, Json<ErrorResponse>);
+
+/// Returns a receiver's Markdown description rendered as sanitized HTML
+///
+/// The body is an HTML fragment served as `text/html`. It is sent with a
+/// `Content-Security-Policy` that blocks scripts, styles, and embedded
+/// content, so the fragment is inert even when opened directly. The raw
+/// description stays in the receiver's JSON representation.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid receiver id
+/// * `404 NOT_FOUND` - Receiver does not exist
+pub async fn get_receiver_description_html(
+    State(state): State<AppState>,
+    Path(id_str): Path<String>,
+) -> std::result::Result<Response, DescriptionError> {
+    let receiver_id = id_str.parse::<EventReceiverId>().map_err(|_| {
+        warn!("Invalid event receiver ID format: {}", id_str);
+        invalid_id("Invalid event receiver ID format")
+    })?;
+
+    let html = state
+        .event_receiver_handler
+        .description_html(receiver_id)
+        .await;
+    html_response(html, "Event receiver", &id_str)
+}
+
+/// Returns a group's Markdown description rendered as sanitized HTML
+///
+/// Rendered and served like receiver descriptions.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid group id
+/// * `404 NOT_FOUND` - Group does not exist
+pub async fn get_group_description_html(
+    State(state): State<AppState>,
+    Path(id_str): Path<String>,
+) -> std::result::Result<Response, DescriptionError> {
+    let group_id = id_str.parse::<EventReceiverGroupId>().map_err(|_| {
+        warn!("Invalid event receiver group ID format: {}", id_str);
+        invalid_id("Invalid event receiver group ID format")
+    })?;
+
+    let html = state
+        .event_receiver_group_handler
+        .description_html(group_id)
+        .await;
+    html_response(html, "Event receiver group", &id_str)
+}
+
+fn invalid_id(message: &str) -> DescriptionError {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ErrorResponse::new(
+            "invalid_id".to_string(),
+            message.to_string(),
+        )),
+    )
+}
+
+fn html_response(
+    html: Result<Option<Arc<str>>>,
+    resource: &str,
+    id: &str,
+) -> std::result::Result<Response, DescriptionError> {
+    match html {
+        Ok(Some(html)) => Ok((
+            [
+                (
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("text/html; charset=utf-8"),
+                ),
+                (
+                    header::CONTENT_SECURITY_POLICY,
+                    HeaderValue::from_static("default-src 'none'; frame-ancestors 'none'"),
+                ),
+                (
+                    header::X_CONTENT_TYPE_OPTIONS,
+                    HeaderValue::from_static("nosniff"),
+                ),
+            ],
+            html.to_string(),
+        )
+            .into_response()),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                "not_found".to_string(),
+                format!("{} {} not found", resource, id),
+            )),
+        )),
+        Err(e) => {
+            error!("Failed to render description of {}: {}", id, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "description_render_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
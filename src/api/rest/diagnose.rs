@@ -0,0 +1,409 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/diagnose.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{Method, StatusCode},
+    response::Json,
+    Extension,
+};
+use tracing::{error, info, warn};
+
+use crate::api::middleware::api_key::ApiKeyPrincipal;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::middleware::rbac::authorize_permission;
+use crate::api::middleware::rbac_helpers::route_to_permission;
+use crate::api::rest::dtos::{
+    DiagnoseReceiverRequest, DiagnoseReceiverResponse, DiagnosticCheck, ErrorResponse,
+};
+use crate::api::rest::events::{api_key_scope_allows, AppState};
+use crate::application::handlers::PublishHealth;
+use crate::auth::api_key::ApiKeyScope;
+use crate::domain::entities::event_publication::PublishPolicy;
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::payload_transform::TransformPreview;
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::{DomainError, Error};
+
+/// Path events are posted to; the permission check asks RBAC about it
+const EVENTS_PATH: &str = "/api/v1/events";
+
+type DiagnoseError = (StatusCode, Json<ErrorResponse>);
+
+fn diagnose_error(status: StatusCode, error: &str, message: String) -> DiagnoseError {
+    (status, Json(ErrorResponse::new(error.to_string(), message)))
+}
+
+fn storage_error(e: Error) -> DiagnoseError {
+    error!("Receiver diagnosis failed: {}", e);
+    diagnose_error(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "internal_error",
+        "Receiver diagnosis failed".to_string(),
+    )
+}
+
+/// Reports whether the caller's events would reach the receiver
+///
+/// Runs the checks event creation applies, for the calling principal, and
+/// returns one entry per check with an explanation and, on failure, a
+/// remediation hint. Nothing is stored or published. The body is optional;
+/// its `name` is checked against the receiver's allowed event names and its
+/// `payload` is rewritten by the receiver's transform, shown before and
+/// after, and validated against the receiver's effective schema.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid receiver id or body
+pub async fn diagnose_receiver(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    api_key: Option<Extension<ApiKeyPrincipal>>,
+    Path(id_str): Path<String>,
+    body: Bytes,
+) -> Result<Json<DiagnoseReceiverResponse>, DiagnoseError> {
+    let receiver_id = id_str.parse::<EventReceiverId>().map_err(|_| {
+        warn!("Invalid event receiver ID format: {}", id_str);
+        diagnose_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_id",
+            "Invalid event receiver ID format".to_string(),
+        )
+    })?;
+
+    let request = if body.iter().all(u8::is_ascii_whitespace) {
+        DiagnoseReceiverRequest::default()
+    } else {
+        serde_json::from_slice::<DiagnoseReceiverRequest>(&body).map_err(|e| {
+            diagnose_error(
+                StatusCode::BAD_REQUEST,
+                "invalid_json",
+                format!("Invalid JSON: {}", e),
+            )
+        })?
+    };
+
+    let receiver = state
+        .event_receiver_handler
+        .get_event_receiver(receiver_id)
+        .await
+        .map_err(storage_error)?;
+
+    let transform = match (&receiver, &request.payload) {
+        (Some(receiver), Some(payload)) => receiver
+            .transform()
+            .map(|transform| TransformPreview::new(transform, payload)),
+        _ => None,
+    };
+    let payload = match &transform {
+        Some(preview) => preview.after.as_ref(),
+        None => request.payload.as_ref(),
+    };
+
+    let checks = vec![
+        receiver_check(receiver.as_ref()),
+        state_check(receiver.as_ref()),
+        permission_check(&user),
+        api_key_scope_check(&state, api_key.map(|Extension(p)| p.scope), receiver_id).await?,
+        groups_check(&state, receiver.as_ref()).await?,
+        event_name_check(&state, receiver.as_ref(), request.name.as_deref()),
+        transform_check(receiver.as_ref(), request.payload.as_ref()),
+        schema_check(&state, receiver.as_ref(), payload).await,
+        publishing_check(&state),
+        DiagnosticCheck::skip("quota", "No ingestion quota applies to receivers"),
+    ];
+
+    let report = DiagnoseReceiverResponse::new(receiver_id, checks).with_transform(transform);
+    info!(
+        user_id = %user.user_id(),
+        receiver_id = %receiver_id,
+        ready = report.ready,
+        "Receiver diagnosed"
+    );
+    Ok(Json(report))
+}
+
+fn receiver_check(receiver: Option<&EventReceiver>) -> DiagnosticCheck {
+    match receiver {
+        Some(receiver) => {
+            DiagnosticCheck::pass("receiver", format!("Receiver '{}' exists", receiver.name()))
+        }
+        None => DiagnosticCheck::fail(
+            "receiver",
+            "No receiver has this id",
+            "Check the id, or create the receiver with POST /api/v1/receivers",
+        ),
+    }
+}
+
+/// Reports whether the receiver was disabled by its auto-disable policy
+fn state_check(receiver: Option<&EventReceiver>) -> DiagnosticCheck {
+    let Some(receiver) = receiver else {
+        return DiagnosticCheck::skip("state", "The receiver does not exist");
+    };
+
+    match (receiver.auto_disabled(), receiver.auto_disable_policy()) {
+        (Some(trigger), _) => {
+            DiagnosticCheck::fail(
+                "state",
+                format!(
+                "The receiver was auto-disabled at {}: {} of {} events failed between {} and {}",
+                trigger.disabled_at, trigger.failed, trigger.total, trigger.window_start,
+                trigger.window_end
+            ),
+                "Fix the failing integration, then re-enable the receiver with \
+             PUT /api/v1/receivers/{id} and {\"enabled\": true}",
+            )
+        }
+        (None, Some(policy)) => DiagnosticCheck::pass(
+            "state",
+            format!(
+                "The receiver is active; it is disabled when {:.0}% or more of at least {} \
+                 events fail within {} minutes",
+                policy.failure_rate_threshold * 100.0,
+                policy.minimum_event_count,
+                policy.observation_window_minutes
+            ),
+        ),
+        (None, None) => DiagnosticCheck::pass(
+            "state",
+            "The receiver is active and has no auto-disable policy",
+        ),
+    }
+}
+
+/// Asks RBAC whether the caller may post events
+fn permission_check(user: &AuthenticatedUser) -> DiagnosticCheck {
+    let Some(permission) = route_to_permission(&Method::POST, EVENTS_PATH) else {
+        return DiagnosticCheck::pass("permission", "Posting events needs no permission");
+    };
+
+    match authorize_permission(user, permission) {
+        Ok(()) => DiagnosticCheck::pass("permission", format!("Caller holds {:?}", permission)),
+        Err(e) => DiagnosticCheck::fail(
+            "permission",
+            e.to_string(),
+            format!(
+                "Ask an administrator for a role that grants {:?}, such as event_manager",
+                permission
+            ),
+        ),
+    }
+}
+
+/// Applies the scope of the caller's API key, if it used one
+async fn api_key_scope_check(
+    state: &AppState,
+    scope: Option<ApiKeyScope>,
+    receiver_id: EventReceiverId,
+) -> Result<DiagnosticCheck, DiagnoseError> {
+    let Some(scope) = scope else {
+        return Ok(DiagnosticCheck::skip(
+            "api_key_scope",
+            "The request was not authenticated with an API key",
+        ));
+    };
+
+    let allowed = api_key_scope_allows(state, scope, Some(receiver_id))
+        .await
+        .map_err(storage_error)?;
+    Ok(match (allowed, scope) {
+        (true, _) => DiagnosticCheck::pass("api_key_scope", "The API key covers this receiver"),
+        (false, ApiKeyScope::Group(group_id)) => DiagnosticCheck::fail(
+            "api_key_scope",
+            format!(
+                "The API key is scoped to group {}, which does not contain this receiver",
+                group_id
+            ),
+            "Add the receiver to the key's group, or use a key scoped to this receiver",
+        ),
+        (false, _) => DiagnosticCheck::fail(
+            "api_key_scope",
+            "The API key is scoped to a different receiver",
+            "Use a key scoped to this receiver or to a group containing it",
+        ),
+    })
+}
+
+async fn groups_check(
+    state: &AppState,
+    receiver: Option<&EventReceiver>,
+) -> Result<DiagnosticCheck, DiagnoseError> {
+    let Some(receiver) = receiver else {
+        return Ok(DiagnosticCheck::skip(
+            "groups",
+            "The receiver does not exist",
+        ));
+    };
+
+    let groups = state
+        .event_receiver_group_handler
+        .find_by_event_receiver_id(receiver.id())
+        .await
+        .map_err(storage_error)?;
+    let disabled: Vec<&str> = groups
+        .iter()
+        .filter(|g| !g.enabled())
+        .map(|g| g.name())
+        .collect();
+
+    Ok(if groups.is_empty() {
+        DiagnosticCheck::pass("groups", "The receiver belongs to no group")
+    } else if disabled.is_empty() {
+        DiagnosticCheck::pass(
+            "groups",
+            format!(
+                "All {} groups containing the receiver are enabled",
+                groups.len()
+            ),
+        )
+    } else {
+        DiagnosticCheck::fail(
+            "groups",
+            format!(
+                "Disabled groups: {}. Disabled groups neither fan events out to their \
+                 dedicated topics nor pass on their default schema",
+                disabled.join(", ")
+            ),
+            "Enable the groups or remove the receiver from them",
+        )
+    })
+}
+
+/// Checks the sample event name the way event creation does
+fn event_name_check(
+    state: &AppState,
+    receiver: Option<&EventReceiver>,
+    name: Option<&str>,
+) -> DiagnosticCheck {
+    let Some(receiver) = receiver else {
+        return DiagnosticCheck::skip("event_name", "The receiver does not exist");
+    };
+
+    match (receiver.allowed_event_names(), name) {
+        (_, Some(name)) => match state.event_handler.check_event_name(receiver, name) {
+            Ok(()) => DiagnosticCheck::pass(
+                "event_name",
+                format!("The receiver accepts events named '{}'", name),
+            ),
+            Err(e @ DomainError::ReservedEventName { .. }) => DiagnosticCheck::fail(
+                "event_name",
+                e.to_string(),
+                "Choose a name outside the xzepr. prefix",
+            ),
+            Err(e) => DiagnosticCheck::fail(
+                "event_name",
+                e.to_string(),
+                "Rename the event or widen the receiver's allowed_event_names",
+            ),
+        },
+        (Some(allowed), None) => DiagnosticCheck::skip(
+            "event_name",
+            format!(
+                "The receiver only accepts event names matching {}; send a sample name to check it",
+                allowed.patterns().join(", ")
+            ),
+        ),
+        (None, None) => DiagnosticCheck::pass(
+            "event_name",
+            "The receiver accepts any event name outside the reserved xzepr. prefix",
+        ),
+    }
+}
+
+/// Applies the receiver's transform to the sample payload
+fn transform_check(
+    receiver: Option<&EventReceiver>,
+    payload: Option<&serde_json::Value>,
+) -> DiagnosticCheck {
+    let Some(transform) = receiver.and_then(EventReceiver::transform) else {
+        return DiagnosticCheck::pass("transform", "The receiver stores payloads as sent");
+    };
+    let Some(payload) = payload else {
+        return DiagnosticCheck::skip(
+            "transform",
+            format!(
+                "The receiver rewrites payloads in {} steps; send a sample payload to check it",
+                transform.steps().len()
+            ),
+        );
+    };
+
+    match transform.apply(payload.clone()) {
+        Ok(_) => DiagnosticCheck::pass(
+            "transform",
+            "The receiver's transform rewrites the payload; see transform.after",
+        ),
+        Err(e) => DiagnosticCheck::fail(
+            "transform",
+            e.to_string(),
+            "Send the fields the failing step reads, or let the step skip on failure",
+        ),
+    }
+}
+
+/// Validates the sample payload the way event creation does
+///
+/// `payload` is the sample as the receiver's transform leaves it; `None`
+/// after a transform that rejects it.
+async fn schema_check(
+    state: &AppState,
+    receiver: Option<&EventReceiver>,
+    payload: Option<&serde_json::Value>,
+) -> DiagnosticCheck {
+    let (Some(receiver), Some(payload)) = (receiver, payload) else {
+        return DiagnosticCheck::skip(
+            "schema",
+            "Send a sample payload for an existing receiver to check it",
+        );
+    };
+
+    match state
+        .event_handler
+        .validate_payload(receiver, payload)
+        .await
+    {
+        Ok(()) => DiagnosticCheck::pass(
+            "schema",
+            "The payload matches the receiver's effective schema",
+        ),
+        Err(e) => DiagnosticCheck::fail(
+            "schema",
+            e.to_string(),
+            "Change the payload or the receiver's schema so they agree",
+        ),
+    }
+}
+
+fn publishing_check(state: &AppState) -> DiagnosticCheck {
+    let policy = state.event_handler.publish_policy();
+    match state.event_handler.publish_health() {
+        PublishHealth::Healthy => {
+            DiagnosticCheck::pass("publishing", "The stream accepted the last event")
+        }
+        PublishHealth::Failing => DiagnosticCheck::fail(
+            "publishing",
+            format!(
+                "The last publish to the stream failed; under the {} policy events are {}",
+                policy.as_str(),
+                match policy {
+                    PublishPolicy::BestEffort => "stored and retried from the outbox",
+                    PublishPolicy::Require => "rejected",
+                }
+            ),
+            "Check the Kafka brokers and the producer configuration",
+        ),
+        PublishHealth::Disabled if policy == PublishPolicy::Require => DiagnosticCheck::fail(
+            "publishing",
+            "Publishing is not configured and the require policy rejects every event",
+            "Configure Kafka or switch the publish policy to best-effort",
+        ),
+        PublishHealth::Disabled => DiagnosticCheck::pass(
+            "publishing",
+            "Publishing is not configured; events are stored but not streamed",
+        ),
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/diff.rs
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info, warn};
+
+use crate::api::field_access::FieldAccess;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, EventDiffResponse};
+use crate::api::rest::events::{authorize, load_full_payloads, AppState};
+use crate::application::authorization::{ProtectedResource, ResourceAction};
+use crate::application::handlers::ArchivedEventLookup;
+use crate::domain::entities::event::Event;
+use crate::domain::value_objects::EventId;
+
+type DiffError = (StatusCode, Json<ErrorResponse>);
+
+/// Compares two events
+///
+/// Returns the metadata fields that differ and the payload differences as
+/// a JSON Patch and as a list of changed paths, with large values
+/// truncated. The caller must be allowed to read the receivers of both
+/// events. Events from different receivers can be compared and are marked
+/// `cross_receiver`, and archived events are compared like live ones.
+/// Unless the caller may read both payloads, payload differences are only
+/// counted.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid event ID
+/// * `403 FORBIDDEN` - The caller may not read one of the receivers
+/// * `404 NOT_FOUND` - One of the events does not exist
+pub async fn diff_events(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((id, other_id)): Path<(String, String)>,
+) -> Result<Json<EventDiffResponse>, DiffError> {
+    let mut event = load_event(&state, &id).await?;
+    let mut other = load_event(&state, &other_id).await?;
+
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Read,
+        ProtectedResource::Receiver(event.event_receiver_id()),
+    )
+    .await?;
+    if other.event_receiver_id() != event.event_receiver_id() {
+        authorize(
+            &state,
+            &user,
+            ResourceAction::Read,
+            ProtectedResource::Receiver(other.event_receiver_id()),
+        )
+        .await?;
+        info!(
+            event_id = %event.id(),
+            other_event_id = %other.id(),
+            "Comparing events from different receivers"
+        );
+    }
+
+    // Payloads kept in blob storage are compared in full
+    let access = FieldAccess::for_user(Some(&user));
+    load_full_payloads(&state, std::slice::from_mut(&mut event), &access).await?;
+    load_full_payloads(&state, std::slice::from_mut(&mut other), &access).await?;
+    Ok(Json(EventDiffResponse::for_caller(&event, &other, &access)))
+}
+
+async fn load_event(state: &AppState, raw: &str) -> Result<Event, DiffError> {
+    let event_id = raw.parse::<EventId>().map_err(|_| {
+        warn!("Invalid event ID format: {}", raw);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid event ID format".to_string(),
+            )),
+        )
+    })?;
+
+    let lookup = match state.event_handler.get_event(event_id).await {
+        Ok(Some(event)) => return Ok(event),
+        Ok(None) => state.event_handler.find_archived_event(event_id).await,
+        Err(e) => Err(e),
+    };
+
+    match lookup {
+        Ok(ArchivedEventLookup::Found(event)) => Ok(*event),
+        Ok(ArchivedEventLookup::Unavailable { segment }) => {
+            error!(
+                "Archive segment {} for event {} is unavailable",
+                segment, event_id
+            );
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::new(
+                    "EVENT_ARCHIVED_UNAVAILABLE".to_string(),
+                    "Event was archived but its archive segment is unavailable".to_string(),
+                )),
+            ))
+        }
+        Ok(ArchivedEventLookup::NotArchived) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                "not_found".to_string(),
+                format!("Event {} not found", event_id),
+            )),
+        )),
+        Err(e) => {
+            error!("Failed to load event {} for comparison: {}", event_id, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "event_retrieval_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
@@ -3,15 +3,74 @@
 
 // src/api/rest/dtos.rs
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, Offset, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
+use crate::api::field_access::{redacted_payload, FieldAccess};
+use crate::api::middleware::deprecation::{DeprecatedUsage, Deprecation, DeprecationStatus};
+use crate::api::rest::fields::Fieldset;
+use crate::api::rest::timestamp::{is_naive, Timestamp};
+use crate::application::handlers::change_feed_handler::MAX_CHANGE_PAGE_SIZE;
+use crate::application::handlers::{
+    BatchItemOutcome, BatchReport, BulkDeleteReport, BulkDeleteSelector, QuotaMode,
+    SchemaPreviewJob, SchemaPreviewJobStatus, SchemaPreviewReport, SchemaPreviewRequest,
+    SystemSummary, TimelinePage, TimelineQuery, UserImportJob, UserImportJobStatus,
+    UserImportReport,
+};
+use crate::auth::api_key::{ApiKey, ApiKeyScope, ApiKeySecret};
+use crate::auth::api_key_usage::{ApiKeyDailyUsage, ApiKeyFailure, ApiKeyUsageReport};
+use crate::auth::jwt::Session;
 use crate::domain::entities::{
-    event::Event, event_receiver::EventReceiver, event_receiver_group::EventReceiverGroup,
+    attestation::{normalize_digest, Attestation, AttestationFormat},
+    audit_chain::AuditChainReport,
+    delivery_failure::DeliveryFailure,
+    event::{Event, EventOrigin},
+    event_attachment::EventAttachment,
+    event_diff::{
+        diff_metadata, diff_payloads, FieldChange, PatchOperation, PathChange,
+        DEFAULT_MAX_VALUE_BYTES,
+    },
+    event_name_constraint::AllowedEventNames,
+    event_publication::PublishStatus,
+    event_receiver::EventReceiver,
+    event_receiver_group::EventReceiverGroup,
+    event_sampling::ALWAYS_KEEP_RULES,
+    ingestion_meta::IngestionMeta,
+    payload_transform::{PayloadTransform, TransformPreview},
+    pii_detection::{PiiFinding, PiiReport},
+    receiver_activity::ReceiverActivity,
+    receiver_auto_disable::{AutoDisablePolicy, AutoDisableTrigger, ReceiverState},
+    receiver_clone::{ReceiverCloneManifest, ReceiverCloneOptions, ReceiverCloneOverrides},
+    receiver_heartbeat::ReceiverHeartbeat,
+    receiver_provisioning::ReceiverSpec,
+    release_completeness::{GroupCompleteness, ReceiverReleaseStatus},
+    schema_inheritance::SchemaSource,
+    search::{SearchCounts, SearchHit, SearchResults},
+    user_preferences::{parse_utc_offset, UserPreferences},
+};
+use crate::domain::repositories::attestation_repo::AttestationFilter;
+use crate::domain::repositories::change_feed_repo::{ChangeCursor, ChangeSet};
+use crate::domain::repositories::event_repo::FindEventCriteria;
+use crate::domain::repositories::ingestion_meta_repo::IngestionMetaFilter;
+use crate::domain::repositories::pagination::{
+    EventSortField, GroupSortField, ListOrder, PaginationParams, ReceiverSortField,
+    DEFAULT_PAGE_SIZE,
+};
+use crate::domain::repositories::persisted_query_repo::StoredPersistedQuery;
+use crate::domain::repositories::receiver_timeline_repo::{
+    ConfigChangeAction, MembershipAction, TimelineCursor, TimelineDetail, TimelineEntry,
+    TimelineKind,
+};
+use crate::domain::repositories::system_summary_repo::EventOutcomeCounts;
+use crate::domain::value_objects::{
+    ApiKeyId, AttachmentId, EventId, EventReceiverGroupId, EventReceiverId, UserId,
 };
-use crate::domain::value_objects::EventReceiverId;
 use crate::error::DomainError;
+use crate::i18n::{Message, MessageParams};
+use crate::infrastructure::feature_flags::FeatureFlagState;
+use crate::infrastructure::jobs::JobStatus;
+use crate::infrastructure::maintenance::MaintenanceWindow;
 
 /// Request DTO for creating an event receiver
 #[derive(Debug, Deserialize, Serialize)]
@@ -22,6 +81,18 @@ pub struct CreateEventReceiverRequest {
     pub version: String,
     pub description: String,
     pub schema: JsonValue,
+    /// Fraction of events to store, between 0.0 and 1.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<f64>,
+    /// Event names the receiver accepts; any name when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_event_names: Option<AllowedEventNames>,
+    /// Failure rate at which the receiver is disabled; never when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_disable: Option<AutoDisablePolicy>,
+    /// Rewrites applied to payloads before validation; none when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<PayloadTransform>,
 }
 
 impl CreateEventReceiverRequest {
@@ -30,39 +101,158 @@ impl CreateEventReceiverRequest {
         if self.name.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "name".to_string(),
-                message: "Name cannot be empty".to_string(),
+                message: Message::new("validation.name_empty"),
             });
         }
 
         if self.receiver_type.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "type".to_string(),
-                message: "Type cannot be empty".to_string(),
+                message: Message::new("validation.type_empty"),
             });
         }
 
         if self.version.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "version".to_string(),
-                message: "Version cannot be empty".to_string(),
+                message: Message::new("validation.version_empty"),
             });
         }
 
         if !self.schema.is_object() {
             return Err(DomainError::ValidationError {
                 field: "schema".to_string(),
-                message: "Schema must be a JSON object".to_string(),
+                message: Message::new("validation.schema_not_object"),
             });
         }
 
-        Ok(())
+        validate_sample_rate(self.sample_rate)?;
+        validate_allowed_event_names(self.allowed_event_names.as_ref())?;
+        validate_auto_disable(self.auto_disable.as_ref())?;
+        validate_transform(self.transform.as_ref())
+    }
+}
+
+/// Validates an optional receiver sample rate
+fn validate_sample_rate(sample_rate: Option<f64>) -> Result<(), DomainError> {
+    match sample_rate {
+        Some(rate) if !(0.0..=1.0).contains(&rate) => Err(DomainError::ValidationError {
+            field: "sample_rate".to_string(),
+            message: Message::new("validation.sample_rate_range"),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Validates an optional event name constraint
+fn validate_allowed_event_names(
+    allowed_event_names: Option<&AllowedEventNames>,
+) -> Result<(), DomainError> {
+    match allowed_event_names {
+        Some(allowed) => allowed.validate(),
+        None => Ok(()),
+    }
+}
+
+/// Validates an optional auto-disable policy
+fn validate_auto_disable(policy: Option<&AutoDisablePolicy>) -> Result<(), DomainError> {
+    match policy {
+        Some(policy) => policy.validate(),
+        None => Ok(()),
+    }
+}
+
+/// Validates an optional payload transform
+fn validate_transform(transform: Option<&PayloadTransform>) -> Result<(), DomainError> {
+    match transform {
+        Some(transform) => transform.validate(),
+        None => Ok(()),
     }
 }
 
 /// Response DTO for event receiver creation
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateEventReceiverResponse {
-    pub data: String, // ULID as string
+    pub data: EventReceiverId,
+}
+
+/// Request DTO for cloning an event receiver
+///
+/// Omitted fields keep the source's value; `copy` selects the
+/// configuration taken over and copies everything by default.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CloneEventReceiverRequest {
+    pub name: String,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub receiver_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Accepted for forward compatibility; receivers carry no labels yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<serde_json::Map<String, JsonValue>>,
+    #[serde(default)]
+    pub copy: ReceiverCloneOptions,
+}
+
+impl CloneEventReceiverRequest {
+    /// Validates the request data
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "name".to_string(),
+                message: Message::new("validation.name_empty"),
+            });
+        }
+
+        if self
+            .receiver_type
+            .as_ref()
+            .is_some_and(|receiver_type| receiver_type.trim().is_empty())
+        {
+            return Err(DomainError::ValidationError {
+                field: "type".to_string(),
+                message: Message::new("validation.type_empty"),
+            });
+        }
+
+        if self
+            .version
+            .as_ref()
+            .is_some_and(|version| version.trim().is_empty())
+        {
+            return Err(DomainError::ValidationError {
+                field: "version".to_string(),
+                message: Message::new("validation.version_empty"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Splits the request into the overrides and copy options
+    pub fn into_parts(self) -> (ReceiverCloneOverrides, ReceiverCloneOptions) {
+        (
+            ReceiverCloneOverrides {
+                name: self.name,
+                receiver_type: self.receiver_type,
+                version: self.version,
+                description: self.description,
+                labels: self.labels,
+            },
+            self.copy,
+        )
+    }
+}
+
+/// Response DTO for a cloned event receiver
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CloneEventReceiverResponse {
+    /// The new receiver
+    pub data: EventReceiverResponse,
+    /// What was copied from the source and what was left out
+    pub manifest: ReceiverCloneManifest,
 }
 
 /// Request DTO for creating an event
@@ -76,77 +266,263 @@ pub struct CreateEventRequest {
     pub description: String,
     pub payload: JsonValue,
     pub success: bool,
-    pub event_receiver_id: String, // ULID as string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_receiver_id: Option<EventReceiverId>,
+    /// Receiver to resolve or provision in place of `event_receiver_id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiver_spec: Option<ReceiverSpecRequest>,
+    /// Captured only so it can be rejected; the server sets the origin
+    #[serde(default, skip_serializing)]
+    pub origin: Option<JsonValue>,
 }
 
 impl CreateEventRequest {
     /// Validates the request data
     pub fn validate(&self) -> Result<(), DomainError> {
+        if self.origin.is_some() {
+            return Err(DomainError::ValidationError {
+                field: "origin".to_string(),
+                message: Message::new("validation.origin_server_assigned"),
+            });
+        }
+
+        match (&self.event_receiver_id, &self.receiver_spec) {
+            (Some(_), Some(_)) | (None, None) => {
+                return Err(DomainError::ValidationError {
+                    field: "event_receiver_id".to_string(),
+                    message: Message::new("validation.receiver_selection_exclusive"),
+                });
+            }
+            (None, Some(spec)) => spec.validate()?,
+            (Some(_), None) => {}
+        }
+
         if self.name.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "name".to_string(),
-                message: "Name cannot be empty".to_string(),
+                message: Message::new("validation.name_empty"),
             });
         }
 
         if self.version.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "version".to_string(),
-                message: "Version cannot be empty".to_string(),
+                message: Message::new("validation.version_empty"),
             });
         }
 
         if self.release.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "release".to_string(),
-                message: "Release cannot be empty".to_string(),
+                message: Message::new("validation.release_empty"),
             });
         }
 
         if self.platform_id.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "platform_id".to_string(),
-                message: "Platform ID cannot be empty".to_string(),
+                message: Message::new("validation.platform_id_empty"),
             });
         }
 
         if self.package.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "package".to_string(),
-                message: "Package cannot be empty".to_string(),
+                message: Message::new("validation.package_empty"),
             });
         }
 
-        if self.event_receiver_id.trim().is_empty() {
+        if !self.payload.is_object() {
             return Err(DomainError::ValidationError {
-                field: "event_receiver_id".to_string(),
-                message: "Event receiver ID cannot be empty".to_string(),
+                field: "payload".to_string(),
+                message: Message::new("validation.payload_not_object"),
             });
         }
 
-        if !self.payload.is_object() {
+        Ok(())
+    }
+}
+
+/// Receiver named by an event request instead of a receiver id
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReceiverSpecRequest {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub receiver_type: String,
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<JsonValue>,
+}
+
+impl ReceiverSpecRequest {
+    /// Validates the spec fields
+    pub fn validate(&self) -> Result<(), DomainError> {
+        for (field, value) in [
+            ("receiver_spec.name", &self.name),
+            ("receiver_spec.type", &self.receiver_type),
+            ("receiver_spec.version", &self.version),
+        ] {
+            if value.trim().is_empty() {
+                return Err(DomainError::ValidationError {
+                    field: field.to_string(),
+                    message: Message::new("validation.value_empty"),
+                });
+            }
+        }
+
+        if self
+            .schema
+            .as_ref()
+            .is_some_and(|schema| !schema.is_object())
+        {
             return Err(DomainError::ValidationError {
-                field: "payload".to_string(),
-                message: "Payload must be a JSON object".to_string(),
+                field: "receiver_spec.schema".to_string(),
+                message: Message::new("validation.schema_not_object"),
             });
         }
 
         Ok(())
     }
 
-    /// Converts to event receiver ID
-    pub fn parse_event_receiver_id(&self) -> Result<EventReceiverId, DomainError> {
-        EventReceiverId::parse(&self.event_receiver_id).map_err(|_| DomainError::ValidationError {
-            field: "event_receiver_id".to_string(),
-            message: "Invalid event receiver ID format".to_string(),
-        })
+    /// Converts the request into the domain spec
+    pub fn into_spec(self) -> ReceiverSpec {
+        ReceiverSpec {
+            name: self.name,
+            receiver_type: self.receiver_type,
+            version: self.version,
+            schema: self.schema,
+        }
     }
 }
 
+/// Query parameters for event creation
+#[derive(Debug, Default, Deserialize)]
+pub struct CreateEventQueryParams {
+    /// Run the ingestion checks without storing or publishing the event
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Query parameters for deleting an event receiver
+#[derive(Debug, Default, Deserialize)]
+pub struct DeleteEventReceiverQueryParams {
+    /// Delete despite recent events or active scoped keys; admins only
+    #[serde(default)]
+    pub force: bool,
+}
+
 /// Response DTO for event creation
+///
+/// Events dropped by the receiver's sample rate carry no id and set
+/// `sampled`. Requests that named their receiver by `receiver_spec` also
+/// get the resolved `event_receiver_id`. Dry runs carry no id and report
+/// their findings in `dry_run`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateEventResponse {
-    pub data: String, // ULID as string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<EventId>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sampled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_receiver_id: Option<EventReceiverId>,
+    /// Whether the stored event reached the stream; omitted when event
+    /// publishing is not configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_status: Option<PublishStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<DryRunResponse>,
+}
+
+/// What a dry run of event creation found; nothing was stored
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DryRunResponse {
+    /// Whether the event would be stored
+    pub would_create: bool,
+    /// Every reason the event would be rejected
+    pub validation_errors: Vec<ErrorResponse>,
+    /// Whether `receiver_spec` names a receiver that would be provisioned
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub would_provision_receiver: bool,
+    /// The payload before and after the receiver's transform, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<TransformPreview>,
+}
+
+/// Request DTO for submitting several events at once
+///
+/// Items are kept as raw JSON so a malformed item is reported in its own
+/// result instead of failing the whole batch.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CreateEventBatchRequest {
+    /// `atomic` or `best_effort`; defaults to `best_effort`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_mode: Option<String>,
+    /// Events in the shape `POST /api/v1/events` accepts
+    pub events: Vec<JsonValue>,
+}
+
+impl CreateEventBatchRequest {
+    /// Returns the requested quota mode
+    pub fn quota_mode(&self) -> Result<QuotaMode, DomainError> {
+        self.quota_mode
+            .as_deref()
+            .map_or(Ok(QuotaMode::default()), str::parse)
+    }
+}
+
+/// Outcome of one item of a batch submission
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchItemResponse {
+    /// Position of the item in the submitted `events`
+    pub index: usize,
+    /// One of `created`, `deduplicated`, `sampled_out`, `quota_exceeded`,
+    /// `validation_failed`, `publish_deferred`, or `failed`
+    pub outcome: String,
+    /// The stored event; for a duplicate, the event of the item it repeats
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<EventId>,
+    /// Index of the earlier item a duplicate repeats
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<usize>,
+    /// Why the item was not stored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorResponse>,
+}
+
+/// Number of batch items per outcome
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchSummaryResponse {
+    pub total: usize,
+    pub created: usize,
+    pub deduplicated: usize,
+    pub sampled_out: usize,
+    pub quota_exceeded: usize,
+    pub validation_failed: usize,
+    pub publish_deferred: usize,
+    pub failed: usize,
+}
+
+impl From<&BatchReport> for BatchSummaryResponse {
+    fn from(report: &BatchReport) -> Self {
+        Self {
+            total: report.items.len(),
+            created: report.count(BatchItemOutcome::Created),
+            deduplicated: report.count(BatchItemOutcome::Deduplicated),
+            sampled_out: report.count(BatchItemOutcome::SampledOut),
+            quota_exceeded: report.count(BatchItemOutcome::QuotaExceeded),
+            validation_failed: report.count(BatchItemOutcome::ValidationFailed),
+            publish_deferred: report.count(BatchItemOutcome::PublishDeferred),
+            failed: report.count(BatchItemOutcome::Failed),
+        }
+    }
+}
+
+/// Response DTO for a batch submission, served as `207 Multi-Status`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateEventBatchResponse {
+    /// One result per submitted item, in submission order
+    pub results: Vec<BatchItemResponse>,
+    pub summary: BatchSummaryResponse,
 }
 
 /// Request DTO for creating an event receiver group
@@ -158,7 +534,17 @@ pub struct CreateEventReceiverGroupRequest {
     pub version: String,
     pub description: String,
     pub enabled: bool,
-    pub event_receiver_ids: Vec<String>, // ULIDs as strings
+    pub event_receiver_ids: Vec<EventReceiverId>,
+    /// Members that do not block release completeness; every other member
+    /// is required
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub optional_receiver_ids: Vec<EventReceiverId>,
+    /// Schema inherited by member receivers that have no schema of their own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_schema: Option<JsonValue>,
+    /// Kafka topic that also receives the events of member receivers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedicated_topic: Option<String>,
 }
 
 impl CreateEventReceiverGroupRequest {
@@ -167,220 +553,2132 @@ impl CreateEventReceiverGroupRequest {
         if self.name.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "name".to_string(),
-                message: "Name cannot be empty".to_string(),
+                message: Message::new("validation.name_empty"),
             });
         }
 
         if self.group_type.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "type".to_string(),
-                message: "Type cannot be empty".to_string(),
+                message: Message::new("validation.type_empty"),
             });
         }
 
         if self.version.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "version".to_string(),
-                message: "Version cannot be empty".to_string(),
+                message: Message::new("validation.version_empty"),
             });
         }
 
         if self.event_receiver_ids.is_empty() {
             return Err(DomainError::ValidationError {
                 field: "event_receiver_ids".to_string(),
-                message: "At least one event receiver ID is required".to_string(),
+                message: Message::new("validation.receivers_required"),
             });
         }
 
         Ok(())
     }
-
-    /// Converts event receiver ID strings to EventReceiverId values
-    pub fn parse_event_receiver_ids(&self) -> Result<Vec<EventReceiverId>, DomainError> {
-        self.event_receiver_ids
-            .iter()
-            .map(|id_str| {
-                EventReceiverId::parse(id_str).map_err(|_| DomainError::ValidationError {
-                    field: "event_receiver_ids".to_string(),
-                    message: format!("Invalid event receiver ID format: {}", id_str),
-                })
-            })
-            .collect()
-    }
 }
 
 /// Response DTO for event receiver group creation
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateEventReceiverGroupResponse {
-    pub data: String, // ULID as string
+    pub data: EventReceiverGroupId,
 }
 
 /// Response DTO for event receiver details
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EventReceiverResponse {
-    pub id: String,
+    pub id: EventReceiverId,
     pub name: String,
     #[serde(rename = "type")]
     pub receiver_type: String,
     pub version: String,
     pub description: String,
-    pub schema: JsonValue,
+    /// Payload schema, only present for callers allowed to read it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<JsonValue>,
     pub fingerprint: String,
+    pub sampling: SamplingResponse,
+    /// Event names the receiver accepts; absent when any name is accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_event_names: Option<AllowedEventNames>,
+    /// `active`, or `auto_disabled` once the auto-disable policy tripped
+    pub state: ReceiverState,
+    /// Failure rate at which the receiver is disabled; absent without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_disable: Option<AutoDisablePolicy>,
+    /// Statistics of the window that disabled the receiver
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_disabled: Option<AutoDisableTrigger>,
+    /// Rewrites applied to payloads before validation; absent without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<PayloadTransform>,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Origin of the effective payload schema, set on single-receiver reads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_source: Option<SchemaSource>,
+    /// Liveness metadata, only present for callers allowed to read it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activity: Option<ReceiverActivityResponse>,
+    /// Latest producer heartbeat, only present for callers allowed to read it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat: Option<ReceiverHeartbeatResponse>,
+    /// Instant a historical read reconstructed; absent on current reads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+impl Fieldset for EventReceiverResponse {
+    const FIELDS: &'static [&'static str] = &[
+        "name",
+        "type",
+        "version",
+        "description",
+        "schema",
+        "fingerprint",
+        "sampling",
+        "allowed_event_names",
+        "state",
+        "auto_disable",
+        "auto_disabled",
+        "transform",
+        "created_at",
+        "updated_at",
+        "schema_source",
+        "activity",
+        "heartbeat",
+    ];
 }
 
-impl From<EventReceiver> for EventReceiverResponse {
-    fn from(receiver: EventReceiver) -> Self {
+impl EventReceiverResponse {
+    /// Builds the response, omitting the schema unless `access` allows it
+    pub fn for_caller(receiver: EventReceiver, access: &FieldAccess) -> Self {
+        let schema = access
+            .can_read_schema(receiver.owner_id())
+            .then(|| receiver.schema().clone());
+
         Self {
-            id: receiver.id().to_string(),
+            id: receiver.id(),
             name: receiver.name().to_string(),
             receiver_type: receiver.receiver_type().to_string(),
             version: receiver.version().to_string(),
             description: receiver.description().to_string(),
-            schema: receiver.schema().clone(),
+            schema,
             fingerprint: receiver.fingerprint().to_string(),
+            sampling: SamplingResponse::from(&receiver),
+            allowed_event_names: receiver.allowed_event_names().cloned(),
+            state: receiver.state(),
+            auto_disable: receiver.auto_disable_policy().copied(),
+            auto_disabled: receiver.auto_disabled().cloned(),
+            transform: receiver.transform().cloned(),
             created_at: receiver.created_at(),
+            updated_at: receiver.updated_at(),
+            schema_source: None,
+            activity: None,
+            heartbeat: None,
+            as_of: None,
+        }
+    }
+
+    /// Marks the response as the receiver's state at `instant`
+    pub fn with_as_of(mut self, instant: DateTime<Utc>) -> Self {
+        self.as_of = Some(instant);
+        self
+    }
+
+    /// Attaches liveness metadata if `access` allows it
+    ///
+    /// A receiver that never received an event reports null fields and a
+    /// zero principal count.
+    pub fn with_activity(
+        mut self,
+        activity: Option<&ReceiverActivity>,
+        access: &FieldAccess,
+    ) -> Self {
+        if access.can_read_receiver_meta() {
+            self.activity = Some(ReceiverActivityResponse {
+                last_event_at: activity.map(|a| a.last_event_at),
+                last_principal: activity.map(|a| a.last_principal.clone()),
+                distinct_principals_30d: activity.map_or(0, |a| a.distinct_principals),
+            });
         }
+        self
+    }
+
+    /// Attaches the latest heartbeat if `access` allows it
+    ///
+    /// A receiver that never sent a heartbeat reports null fields.
+    pub fn with_heartbeat(
+        mut self,
+        heartbeat: Option<ReceiverHeartbeat>,
+        access: &FieldAccess,
+    ) -> Self {
+        if access.can_read_receiver_meta() {
+            self.heartbeat = Some(heartbeat.map_or_else(
+                ReceiverHeartbeatResponse::default,
+                ReceiverHeartbeatResponse::from,
+            ));
+        }
+        self
+    }
+
+    /// Attaches the origin of the receiver's effective schema
+    pub fn with_schema_source(mut self, schema_source: SchemaSource) -> Self {
+        self.schema_source = Some(schema_source);
+        self
     }
 }
 
-/// Response DTO for event details
+/// Liveness metadata of an event receiver
 #[derive(Debug, Serialize, Deserialize)]
-pub struct EventResponse {
-    pub id: String,
-    pub name: String,
-    pub version: String,
-    pub release: String,
-    pub platform_id: String,
-    pub package: String,
-    pub description: String,
-    pub payload: JsonValue,
-    pub success: bool,
-    pub event_receiver_id: String,
-    pub created_at: DateTime<Utc>,
+pub struct ReceiverActivityResponse {
+    /// Time of the latest accepted event
+    pub last_event_at: Option<DateTime<Utc>>,
+    /// Principal of the latest event, e.g. `api_key:01J...`
+    pub last_principal: Option<String>,
+    /// Distinct principals that sent events in the last 30 days
+    pub distinct_principals_30d: u64,
+}
+
+/// Latest heartbeat sent by the producers of an event receiver
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReceiverHeartbeatResponse {
+    /// Time of the latest stored heartbeat
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+    /// Status reported with the latest heartbeat, e.g. version and hostname
+    pub status: Option<JsonValue>,
 }
 
-impl From<Event> for EventResponse {
-    fn from(event: Event) -> Self {
+impl From<ReceiverHeartbeat> for ReceiverHeartbeatResponse {
+    fn from(heartbeat: ReceiverHeartbeat) -> Self {
         Self {
-            id: event.id().to_string(),
-            name: event.name().to_string(),
-            version: event.version().to_string(),
-            release: event.release().to_string(),
-            platform_id: event.platform_id().to_string(),
-            package: event.package().to_string(),
-            description: event.description().to_string(),
-            payload: event.payload().clone(),
-            success: event.success(),
-            event_receiver_id: event.event_receiver_id().to_string(),
-            created_at: event.created_at(),
+            last_heartbeat_at: Some(heartbeat.last_heartbeat_at),
+            status: heartbeat.status,
         }
     }
 }
 
-/// Response DTO for event receiver group details
+/// Request DTO for a receiver heartbeat
+///
+/// The body may be omitted entirely; a heartbeat without status clears the
+/// stored one.
+#[derive(Debug, Default, Deserialize)]
+pub struct ReceiverHeartbeatRequest {
+    /// Small JSON object describing the producer, at most 1 KiB
+    #[serde(default)]
+    pub status: Option<JsonValue>,
+}
+
+/// Request DTO for diagnosing a receiver
+///
+/// The body may be omitted; without `payload` the schema check is skipped.
+#[derive(Debug, Default, Deserialize)]
+pub struct DiagnoseReceiverRequest {
+    /// Sample event name to check against the receiver's allowed names
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Sample event payload to validate against the effective schema
+    #[serde(default)]
+    pub payload: Option<JsonValue>,
+}
+
+/// Outcome of one diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// The check does not apply or could not run
+    Skip,
+}
+
+/// One check of a receiver diagnosis
 #[derive(Debug, Serialize, Deserialize)]
-pub struct EventReceiverGroupResponse {
-    pub id: String,
+pub struct DiagnosticCheck {
     pub name: String,
-    #[serde(rename = "type")]
-    pub group_type: String,
-    pub version: String,
-    pub description: String,
-    pub enabled: bool,
-    pub event_receiver_ids: Vec<String>,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
+    pub status: CheckStatus,
+    pub explanation: String,
+    /// What to change to make the check pass; only set on failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
 }
 
-impl From<EventReceiverGroup> for EventReceiverGroupResponse {
-    fn from(group: EventReceiverGroup) -> Self {
+impl DiagnosticCheck {
+    pub fn pass(name: &str, explanation: impl Into<String>) -> Self {
         Self {
-            id: group.id().to_string(),
-            name: group.name().to_string(),
-            group_type: group.group_type().to_string(),
-            version: group.version().to_string(),
-            description: group.description().to_string(),
-            enabled: group.enabled(),
-            event_receiver_ids: group
-                .event_receiver_ids()
-                .iter()
-                .map(|id| id.to_string())
-                .collect(),
-            created_at: group.created_at(),
-            updated_at: group.updated_at(),
+            name: name.to_string(),
+            status: CheckStatus::Pass,
+            explanation: explanation.into(),
+            remediation: None,
+        }
+    }
+
+    pub fn fail(
+        name: &str,
+        explanation: impl Into<String>,
+        remediation: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Fail,
+            explanation: explanation.into(),
+            remediation: Some(remediation.into()),
+        }
+    }
+
+    pub fn skip(name: &str, explanation: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Skip,
+            explanation: explanation.into(),
+            remediation: None,
         }
     }
 }
 
-/// Generic error response DTO
+/// Response DTO for a receiver diagnosis
 #[derive(Debug, Serialize, Deserialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub field: Option<String>,
+pub struct DiagnoseReceiverResponse {
+    pub receiver_id: EventReceiverId,
+    /// True when no check failed
+    pub ready: bool,
+    pub checks: Vec<DiagnosticCheck>,
+    /// The sample payload before and after the receiver's transform
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<TransformPreview>,
 }
 
-impl ErrorResponse {
-    pub fn new(error: String, message: String) -> Self {
+impl DiagnoseReceiverResponse {
+    pub fn new(receiver_id: EventReceiverId, checks: Vec<DiagnosticCheck>) -> Self {
         Self {
-            error,
-            message,
-            field: None,
+            receiver_id,
+            ready: checks.iter().all(|c| c.status != CheckStatus::Fail),
+            checks,
+            transform: None,
         }
     }
 
-    pub fn with_field(error: String, message: String, field: String) -> Self {
-        Self {
-            error,
-            message,
-            field: Some(field),
-        }
+    /// Adds the sample payload as the receiver's transform leaves it
+    pub fn with_transform(mut self, transform: Option<TransformPreview>) -> Self {
+        self.transform = transform;
+        self
     }
 }
 
-/// Request DTO for updating an event receiver
-#[derive(Debug, Deserialize, Serialize)]
-pub struct UpdateEventReceiverRequest {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
-    pub receiver_type: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub version: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub schema: Option<JsonValue>,
+/// Longest grace period a rotation may keep the previous secret valid
+pub const MAX_ROTATION_GRACE_SECONDS: u64 = 30 * 86_400;
+
+/// Request DTO for rotating an API key
+///
+/// The body may be omitted; the configured grace period is used then.
+#[derive(Debug, Default, Deserialize)]
+pub struct RotateApiKeyRequest {
+    /// Seconds the replaced secret keeps working; `0` ends it immediately
+    #[serde(default)]
+    pub grace_period_seconds: Option<u64>,
 }
 
-impl UpdateEventReceiverRequest {
-    /// Validates the request data
-    pub fn validate(&self) -> Result<(), DomainError> {
-        if let Some(ref name) = self.name {
-            if name.trim().is_empty() {
-                return Err(DomainError::ValidationError {
-                    field: "name".to_string(),
-                    message: "Name cannot be empty".to_string(),
-                });
+impl RotateApiKeyRequest {
+    /// Returns the requested grace period, if any
+    pub fn grace_period(&self) -> Result<Option<Duration>, DomainError> {
+        match self.grace_period_seconds {
+            Some(seconds) if seconds > MAX_ROTATION_GRACE_SECONDS => {
+                Err(DomainError::ValidationError {
+                    field: "grace_period_seconds".to_string(),
+                    message: Message::new("validation.grace_period_too_long")
+                        .with("max", MAX_ROTATION_GRACE_SECONDS),
+                })
             }
+            Some(seconds) => Ok(Some(Duration::seconds(seconds as i64))),
+            None => Ok(None),
         }
+    }
+}
 
-        if let Some(ref receiver_type) = self.receiver_type {
-            if receiver_type.trim().is_empty() {
-                return Err(DomainError::ValidationError {
-                    field: "type".to_string(),
-                    message: "Type cannot be empty".to_string(),
-                });
-            }
-        }
+/// Response DTO for API key metadata; secrets and hashes are never included
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyResponse {
+    pub id: ApiKeyId,
+    pub user_id: UserId,
+    pub name: String,
+    pub enabled: bool,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Secret presented by the last authenticated request
+    pub last_used_with: Option<ApiKeySecret>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    /// End of the previous secret's grace period, while it lasts
+    pub previous_expires_at: Option<DateTime<Utc>>,
+    /// Only receiver the key may post events to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receiver_id: Option<EventReceiverId>,
+    /// Group whose receivers the key may post events to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<EventReceiverGroupId>,
+    /// Enabled but unused for longer than `hygiene.key_stale_days`
+    #[serde(default)]
+    pub stale: bool,
+}
+
+impl ApiKeyResponse {
+    /// Builds the response for `api_key` as of `now`
+    pub fn at(api_key: &ApiKey, now: DateTime<Utc>) -> Self {
+        Self {
+            id: api_key.id,
+            user_id: api_key.user_id,
+            name: api_key.name.clone(),
+            enabled: api_key.enabled,
+            expires_at: api_key.expires_at,
+            created_at: api_key.created_at,
+            last_used_at: api_key.last_used_at,
+            last_used_with: api_key.last_used_with,
+            rotated_at: api_key.rotated_at,
+            previous_expires_at: api_key.previous_expires_at.filter(|until| *until > now),
+            receiver_id: api_key.receiver_id,
+            group_id: api_key.group_id,
+            stale: false,
+        }
+    }
+
+    /// Marks the key as stale
+    pub fn with_stale(mut self, stale: bool) -> Self {
+        self.stale = stale;
+        self
+    }
+}
+
+/// Requests made with an API key on one UTC day
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyDailyUsageResponse {
+    pub day: NaiveDate,
+    pub successes: u64,
+    pub auth_failures: u64,
+    pub authz_denials: u64,
+    pub rate_limited: u64,
+}
+
+impl From<&ApiKeyDailyUsage> for ApiKeyDailyUsageResponse {
+    fn from(usage: &ApiKeyDailyUsage) -> Self {
+        Self {
+            day: usage.day,
+            successes: usage.successes,
+            auth_failures: usage.auth_failures,
+            authz_denials: usage.authz_denials,
+            rate_limited: usage.rate_limited,
+        }
+    }
+}
+
+/// A failed request made with an API key
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyFailureResponse {
+    pub occurred_at: DateTime<Utc>,
+    pub reason: String,
+    pub route: String,
+}
+
+impl From<&ApiKeyFailure> for ApiKeyFailureResponse {
+    fn from(failure: &ApiKeyFailure) -> Self {
+        Self {
+            occurred_at: failure.occurred_at,
+            reason: failure.reason.clone(),
+            route: failure.route.clone(),
+        }
+    }
+}
+
+/// Response DTO for the usage of an API key
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyUsageResponse {
+    pub key_id: ApiKeyId,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub last_used_ip: Option<String>,
+    pub last_used_with: Option<ApiKeySecret>,
+    pub stale: bool,
+    /// First day covered by `days`
+    pub since: NaiveDate,
+    /// Days with at least one request, oldest first
+    pub days: Vec<ApiKeyDailyUsageResponse>,
+    /// Latest failed requests, newest first
+    pub recent_failures: Vec<ApiKeyFailureResponse>,
+}
+
+impl ApiKeyUsageResponse {
+    /// Builds the response from a key and its usage report
+    ///
+    /// A buffered last use takes precedence over the stored one.
+    pub fn new(api_key: &ApiKey, report: &ApiKeyUsageReport, stale: bool) -> Self {
+        let (last_used_at, last_used_ip, last_used_with) = match &report.last_use {
+            Some(last_use) => (
+                Some(last_use.at),
+                last_use.ip.clone(),
+                Some(last_use.secret),
+            ),
+            None => (
+                api_key.last_used_at,
+                api_key.last_used_ip.clone(),
+                api_key.last_used_with,
+            ),
+        };
+        Self {
+            key_id: api_key.id,
+            last_used_at,
+            last_used_ip,
+            last_used_with,
+            stale,
+            since: report.since,
+            days: report.days.iter().map(Into::into).collect(),
+            recent_failures: report.recent_failures.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Request DTO for creating an API key scoped to a group
+#[derive(Debug, Deserialize)]
+pub struct CreateGroupApiKeyRequest {
+    pub name: String,
+    #[serde(default)]
+    pub expires_at: Option<Timestamp>,
+    /// Not allowed: a key is scoped to a receiver or a group, never both
+    #[serde(default)]
+    pub receiver_id: Option<EventReceiverId>,
+}
+
+impl CreateGroupApiKeyRequest {
+    /// Validates the request and returns the scope of the new key
+    pub fn scope(&self, group_id: EventReceiverGroupId) -> Result<ApiKeyScope, DomainError> {
+        if self.name.trim().is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "name".to_string(),
+                message: Message::new("validation.name_empty"),
+            });
+        }
+
+        ApiKeyScope::from_parts(self.receiver_id, Some(group_id))
+    }
+}
+
+/// Response DTO for a newly created API key
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateApiKeyResponse {
+    /// The secret; it is only returned once
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+}
+
+/// Response DTO for a rotated API key
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RotateApiKeyResponse {
+    /// The new secret; it is only returned once
+    pub key: String,
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+}
+
+/// Effective sampling configuration of an event receiver
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SamplingResponse {
+    /// Fraction of events stored; 1.0 when sampling is disabled
+    pub sample_rate: f64,
+    /// Rules that store an event regardless of the sample rate
+    pub always_keep: Vec<String>,
+}
+
+impl From<&EventReceiver> for SamplingResponse {
+    fn from(receiver: &EventReceiver) -> Self {
+        Self {
+            sample_rate: receiver.effective_sample_rate(),
+            always_keep: ALWAYS_KEEP_RULES.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+}
+
+/// Response DTO for event details
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventResponse {
+    pub id: EventId,
+    pub name: String,
+    pub version: String,
+    pub release: String,
+    pub platform_id: String,
+    pub package: String,
+    pub description: String,
+    /// Event payload, or `{"_redacted": true}` for callers not allowed to read it
+    pub payload: JsonValue,
+    /// Serialized size of a redacted payload
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_size_bytes: Option<usize>,
+    /// True when `payload` is the summary of a payload kept in blob storage
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub payload_truncated: bool,
+    /// Where the full payload of a truncated response can be read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_url: Option<String>,
+    pub success: bool,
+    pub event_receiver_id: EventReceiverId,
+    pub created_at: DateTime<Utc>,
+    /// `user` for ingested events, `system` for events xzepr emits itself
+    #[serde(default)]
+    pub origin: EventOrigin,
+    /// Ingestion metadata, only present for callers with `event:read_meta`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<IngestionMetaResponse>,
+    /// True when the event was served from cold storage
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// Whether the event reached the stream; omitted when event publishing
+    /// or the publish outbox is not configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_status: Option<PublishStatus>,
+    /// Metadata of files attached to the event; content is downloaded
+    /// separately
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<AttachmentResponse>,
+}
+
+impl Fieldset for EventResponse {
+    const FIELDS: &'static [&'static str] = &[
+        "name",
+        "version",
+        "release",
+        "platform_id",
+        "package",
+        "description",
+        "payload",
+        "success",
+        "event_receiver_id",
+        "created_at",
+        "origin",
+        "meta",
+        "archived",
+        "publish_status",
+        "attachments",
+    ];
+    // A redacted payload keeps its size hint, a summary its link
+    const COMPANIONS: &'static [(&'static str, &'static str)] = &[
+        ("payload", "payload_size_bytes"),
+        ("payload", "payload_truncated"),
+        ("payload", "payload_url"),
+    ];
+}
+
+impl EventResponse {
+    /// Builds the response, redacting the payload unless `access` allows it
+    ///
+    /// An event whose payload is still offloaded is returned with its
+    /// summary as the payload and a link to the full payload.
+    pub fn for_caller(event: Event, access: &FieldAccess) -> Self {
+        let summary = event.payload_summary();
+        let (payload, payload_size_bytes) = if !access.can_read_payload(event.owner_id()) {
+            let size = match summary {
+                Some(summary) => summary.size_bytes as usize,
+                None => event.payload().to_string().len(),
+            };
+            (redacted_payload(), Some(size))
+        } else if let Some(summary) = summary {
+            (serde_json::to_value(summary).unwrap_or_default(), None)
+        } else {
+            (event.payload().clone(), None)
+        };
+        let payload_truncated = summary.is_some() && payload_size_bytes.is_none();
+        let payload_url =
+            payload_truncated.then(|| format!("/api/v1/events/{}?payload=full", event.id()));
+
+        Self {
+            id: event.id(),
+            name: event.name().to_string(),
+            version: event.version().to_string(),
+            release: event.release().to_string(),
+            platform_id: event.platform_id().to_string(),
+            package: event.package().to_string(),
+            description: event.description().to_string(),
+            payload,
+            payload_size_bytes,
+            payload_truncated,
+            payload_url,
+            success: event.success(),
+            event_receiver_id: event.event_receiver_id(),
+            created_at: event.created_at(),
+            origin: event.origin(),
+            meta: None,
+            archived: false,
+            publish_status: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    /// Attaches ingestion metadata to the response
+    pub fn with_meta(mut self, meta: Option<IngestionMeta>) -> Self {
+        self.meta = meta.map(IngestionMetaResponse::from);
+        self
+    }
+
+    /// Attaches whether the event reached the stream
+    pub fn with_publish_status(mut self, publish_status: Option<PublishStatus>) -> Self {
+        self.publish_status = publish_status;
+        self
+    }
+
+    /// Marks the response as served from the event archive
+    pub fn archived(mut self) -> Self {
+        self.archived = true;
+        self
+    }
+
+    /// Lists the files attached to the event
+    pub fn with_attachments(mut self, attachments: Vec<EventAttachment>) -> Self {
+        self.attachments = attachments
+            .into_iter()
+            .map(AttachmentResponse::from)
+            .collect();
+        self
+    }
+}
+
+/// Most attestation events a digest lookup returns
+pub const MAX_ATTESTATION_LOOKUP_LIMIT: usize = 100;
+
+/// Query parameters for looking up attestations by subject digest
+#[derive(Debug, Default, Deserialize)]
+pub struct AttestationQueryParams {
+    /// `cyclonedx` or `slsa_provenance`; both when absent
+    pub format: Option<String>,
+    /// Only provenance produced by this builder
+    pub builder_id: Option<String>,
+    /// Most events returned, 1 to 100; defaults to 20
+    pub limit: Option<usize>,
+}
+
+impl AttestationQueryParams {
+    /// Builds the repository filter for `digest`
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for a malformed digest, an unknown
+    /// format, or a limit out of range.
+    pub fn to_filter(&self, digest: &str) -> Result<AttestationFilter, DomainError> {
+        let limit = self.limit.unwrap_or(20);
+        if limit == 0 || limit > MAX_ATTESTATION_LOOKUP_LIMIT {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range")
+                    .with("max", MAX_ATTESTATION_LOOKUP_LIMIT),
+            });
+        }
+
+        let mut filter = AttestationFilter::new()
+            .with_digest(normalize_digest(digest)?)
+            .with_limit(limit);
+        if let Some(format) = &self.format {
+            filter = filter.with_format(format.parse::<AttestationFormat>()?);
+        }
+        if let Some(builder_id) = &self.builder_id {
+            filter = filter.with_builder_id(builder_id.clone());
+        }
+        Ok(filter)
+    }
+}
+
+/// Response DTO for the fields extracted from an attestation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttestationResponse {
+    pub format: AttestationFormat,
+    pub spec_version: String,
+    /// Digests of the attested artifacts as `algorithm:hex`
+    pub subject_digests: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builder_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_version: Option<String>,
+}
+
+impl From<Attestation> for AttestationResponse {
+    fn from(attestation: Attestation) -> Self {
+        Self {
+            format: attestation.format,
+            spec_version: attestation.spec_version,
+            subject_digests: attestation.subject_digests,
+            builder_id: attestation.builder_id,
+            component_count: attestation.component_count,
+            component_name: attestation.component_name,
+            component_version: attestation.component_version,
+        }
+    }
+}
+
+/// Response DTO for an event carrying an attestation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttestedEventResponse {
+    pub attestation: AttestationResponse,
+    pub event: EventResponse,
+}
+
+/// Response DTO for a lookup of attestations by subject digest
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttestationLookupResponse {
+    /// Normalized digest that was looked up
+    pub digest: String,
+    /// Matching events the caller may read, newest first
+    pub events: Vec<AttestedEventResponse>,
+}
+
+/// Response DTO for the differences between two events
+///
+/// Payload differences are only listed when the caller may read both
+/// payloads; otherwise only their number is given.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventDiffResponse {
+    pub event_id: EventId,
+    pub other_event_id: EventId,
+    /// True when the events were posted to different receivers
+    pub cross_receiver: bool,
+    pub metadata: Vec<FieldChange>,
+    /// Number of payload differences, also given when they are redacted
+    pub payload_change_count: usize,
+    /// True when the caller may not read one of the payloads
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub payload_redacted: bool,
+    /// JSON Patch (RFC 6902) turning the first payload into the second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_patch: Option<Vec<PatchOperation>>,
+    /// Changed payload paths with the values on either side
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_changes: Option<Vec<PathChange>>,
+}
+
+impl EventDiffResponse {
+    /// Compares `event` with `other`, listing payload differences only if
+    /// `access` allows reading both payloads
+    pub fn for_caller(event: &Event, other: &Event, access: &FieldAccess) -> Self {
+        let payload = diff_payloads(event.payload(), other.payload(), DEFAULT_MAX_VALUE_BYTES);
+        let payload_change_count = payload.len();
+        let payload_redacted = !(access.can_read_payload(event.owner_id())
+            && access.can_read_payload(other.owner_id()));
+        let (payload_patch, payload_changes) = if payload_redacted {
+            (None, None)
+        } else {
+            (Some(payload.patch), Some(payload.changes))
+        };
+
+        Self {
+            event_id: event.id(),
+            other_event_id: other.id(),
+            cross_receiver: event.event_receiver_id() != other.event_receiver_id(),
+            metadata: diff_metadata(event, other),
+            payload_change_count,
+            payload_redacted,
+            payload_patch,
+            payload_changes,
+        }
+    }
+}
+
+/// Response DTO for event attachment metadata
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttachmentResponse {
+    pub id: AttachmentId,
+    pub event_id: EventId,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    /// Hex encoded SHA-256 of the content
+    pub sha256: String,
+    pub uploaded_by: UserId,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<EventAttachment> for AttachmentResponse {
+    fn from(attachment: EventAttachment) -> Self {
+        Self {
+            id: attachment.id,
+            event_id: attachment.event_id,
+            filename: attachment.filename,
+            content_type: attachment.content_type,
+            size_bytes: attachment.size_bytes,
+            sha256: attachment.sha256,
+            uploaded_by: attachment.uploaded_by,
+            created_at: attachment.created_at,
+        }
+    }
+}
+
+/// Response DTO for event ingestion metadata
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngestionMetaResponse {
+    pub principal_type: String,
+    pub principal_id: String,
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Administrator who submitted the event while impersonating the
+    /// principal
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+    /// Payload values that looked like personal data, by detector and path
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pii_findings: Vec<PiiFinding>,
+}
+
+impl From<IngestionMeta> for IngestionMetaResponse {
+    fn from(meta: IngestionMeta) -> Self {
+        Self {
+            principal_type: meta.principal_type().to_string(),
+            principal_id: meta.principal_id().to_string(),
+            source: meta.source().to_string(),
+            client_ip: meta.client_ip().map(str::to_string),
+            user_agent: meta.user_agent().map(str::to_string),
+            actor_id: meta.actor_id().map(str::to_string),
+            recorded_at: meta.recorded_at(),
+            pii_findings: meta.pii_findings().to_vec(),
+        }
+    }
+}
+
+/// Response DTO for event receiver group details
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventReceiverGroupResponse {
+    pub id: EventReceiverGroupId,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub group_type: String,
+    pub version: String,
+    pub description: String,
+    pub enabled: bool,
+    pub event_receiver_ids: Vec<EventReceiverId>,
+    /// Members that do not block release completeness
+    #[serde(default)]
+    pub optional_receiver_ids: Vec<EventReceiverId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_schema: Option<JsonValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedicated_topic: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Instant a historical read reconstructed; absent on current reads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+impl Fieldset for EventReceiverGroupResponse {
+    const FIELDS: &'static [&'static str] = &[
+        "name",
+        "type",
+        "version",
+        "description",
+        "enabled",
+        "event_receiver_ids",
+        "optional_receiver_ids",
+        "default_schema",
+        "dedicated_topic",
+        "created_at",
+        "updated_at",
+    ];
+}
+
+impl From<EventReceiverGroup> for EventReceiverGroupResponse {
+    fn from(group: EventReceiverGroup) -> Self {
+        Self {
+            id: group.id(),
+            name: group.name().to_string(),
+            group_type: group.group_type().to_string(),
+            version: group.version().to_string(),
+            description: group.description().to_string(),
+            enabled: group.enabled(),
+            event_receiver_ids: group.event_receiver_ids().to_vec(),
+            optional_receiver_ids: group.optional_receiver_ids().to_vec(),
+            default_schema: group.default_schema().cloned(),
+            dedicated_topic: group.dedicated_topic().map(str::to_string),
+            created_at: group.created_at(),
+            updated_at: group.updated_at(),
+            as_of: None,
+        }
+    }
+}
+
+impl EventReceiverGroupResponse {
+    /// Marks the response as the group's state at `instant`
+    pub fn with_as_of(mut self, instant: DateTime<Utc>) -> Self {
+        self.as_of = Some(instant);
+        self
+    }
+}
+
+/// Response DTO for the effective Kafka producer configuration
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KafkaProducerDebugResponse {
+    /// Whether event publication to Kafka is enabled
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// librdkafka client settings with secrets redacted
+    pub config: std::collections::BTreeMap<String, String>,
+}
+
+/// Response DTO describing the running instance for support requests
+///
+/// Holds no secrets or connection URLs.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AboutResponse {
+    pub build: BuildInfoResponse,
+    /// Optional Cargo features compiled into the binary
+    pub cargo_features: Vec<String>,
+    /// Feature flags for callers without a role override
+    pub feature_flags: std::collections::BTreeMap<String, bool>,
+    pub auth_providers: AuthProvidersResponse,
+    /// Latest database migration applied at startup
+    pub migration_version: Option<i64>,
+    /// Applied and pending migrations as of startup
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub migrations: Option<MigrationsResponse>,
+    pub kafka_topics: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub uptime_seconds: u64,
+}
+
+/// Most consumers listed per deprecation
+pub const MAX_DEPRECATION_CONSUMERS: usize = 100;
+
+/// Query parameters for the deprecation report
+#[derive(Debug, Default, Deserialize)]
+pub struct DeprecationQueryParams {
+    /// Consumers listed per deprecation, 1 to 100; defaults to 10
+    pub top: Option<usize>,
+}
+
+impl DeprecationQueryParams {
+    /// Returns the number of consumers to list
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `top` is out of range.
+    pub fn top(&self) -> Result<usize, DomainError> {
+        let top = self.top.unwrap_or(10);
+        if top == 0 || top > MAX_DEPRECATION_CONSUMERS {
+            return Err(DomainError::ValidationError {
+                field: "top".to_string(),
+                message: Message::new("validation.limit_range")
+                    .with("max", MAX_DEPRECATION_CONSUMERS),
+            });
+        }
+        Ok(top)
+    }
+}
+
+/// Response DTO listing the registered deprecations
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeprecationsResponse {
+    pub deprecations: Vec<DeprecationResponse>,
+}
+
+/// Response DTO for a deprecation and its heaviest users
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeprecationResponse {
+    pub id: String,
+    pub summary: String,
+    pub deprecated_on: NaiveDate,
+    /// Last day the behavior is served
+    pub sunset: NaiveDate,
+    pub link: String,
+    /// Past its sunset and answered with `410 Gone`
+    pub removed: bool,
+    /// Past its sunset but still served by the `reenabled` setting
+    pub reenabled: bool,
+    /// Uses since the server started
+    pub total_uses: u64,
+    pub top_consumers: Vec<DeprecationConsumerResponse>,
+}
+
+/// Response DTO for the uses of a deprecation by one principal
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeprecationConsumerResponse {
+    /// `user:<id>`, `api_key:<id>`, or `ip:<address>`
+    pub principal: String,
+    pub uses: u64,
+    pub last_seen: DateTime<Utc>,
+}
+
+impl From<DeprecationStatus> for DeprecationResponse {
+    fn from(status: DeprecationStatus) -> Self {
+        Self {
+            id: status.id.to_string(),
+            summary: status.summary.to_string(),
+            deprecated_on: status.deprecated_on,
+            sunset: status.sunset,
+            link: status.link.to_string(),
+            removed: status.removed,
+            reenabled: status.reenabled,
+            total_uses: status.total_uses,
+            top_consumers: status
+                .top_consumers
+                .into_iter()
+                .map(|(principal, usage)| DeprecationConsumerResponse {
+                    principal,
+                    uses: usage.count,
+                    last_seen: usage.last_seen,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Response DTO for the build metadata of the running binary
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildInfoResponse {
+    pub version: String,
+    pub git_sha: String,
+    pub build_timestamp: Option<DateTime<Utc>>,
+    pub rustc_version: String,
+}
+
+/// Response DTO summarizing applied and pending database migrations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationsResponse {
+    /// Number of applied migrations
+    pub applied: usize,
+    pub pending: Vec<PendingMigrationResponse>,
+}
+
+/// Response DTO for a migration the database has not applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingMigrationResponse {
+    pub version: i64,
+    pub description: String,
+}
+
+/// Response DTO listing which authentication providers are enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthProvidersResponse {
+    pub local: bool,
+    pub oidc: bool,
+    pub api_key: bool,
+}
+
+/// Request DTO registering a GraphQL persisted query
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisterPersistedQueryRequest {
+    /// Query text; it is stored exactly as sent and hashed as is
+    pub query: String,
+}
+
+/// Response DTO listing registered GraphQL persisted queries
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PersistedQueriesResponse {
+    pub queries: Vec<PersistedQueryResponse>,
+}
+
+/// Response DTO describing a registered GraphQL persisted query
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PersistedQueryResponse {
+    /// SHA-256 of `query`, sent by clients as `persistedQuery.sha256Hash`
+    pub sha256_hash: String,
+    pub query: String,
+    pub registered_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<StoredPersistedQuery> for PersistedQueryResponse {
+    fn from(query: StoredPersistedQuery) -> Self {
+        Self {
+            sha256_hash: query.hash,
+            query: query.query,
+            registered_by: query.registered_by,
+            created_at: query.created_at,
+        }
+    }
+}
+
+/// Response DTO listing background jobs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobsResponse {
+    pub jobs: Vec<JobStatusResponse>,
+}
+
+/// Response DTO describing one background job and its last run
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobStatusResponse {
+    pub name: String,
+    pub schedule: String,
+    pub enabled: bool,
+    /// A run is in progress
+    pub running: bool,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_finished_at: Option<DateTime<Utc>>,
+    pub last_duration_ms: Option<u64>,
+    /// `success`, `failure`, or `panic`
+    pub last_outcome: Option<String>,
+    pub last_error: Option<String>,
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub consecutive_failures: u32,
+    /// Next scheduled run; `None` for disabled or stopped jobs
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl From<JobStatus> for JobStatusResponse {
+    fn from(status: JobStatus) -> Self {
+        Self {
+            name: status.name,
+            schedule: status.schedule,
+            enabled: status.enabled,
+            running: status.running,
+            last_started_at: status.last_started_at,
+            last_finished_at: status.last_finished_at,
+            last_duration_ms: status.last_duration_ms,
+            last_outcome: status.last_outcome.map(|o| o.as_str().to_string()),
+            last_error: status.last_error,
+            success_count: status.success_count,
+            failure_count: status.failure_count,
+            consecutive_failures: status.consecutive_failures,
+            next_run_at: status.next_run_at,
+        }
+    }
+}
+
+/// Query parameters for listing delivery failures
+#[derive(Debug, Default, Deserialize)]
+pub struct DeliveryFailureQueryParams {
+    /// Only failures of events of this receiver
+    pub receiver_id: Option<String>,
+    /// `timeout`, `unavailable`, `rejected`, or `other`
+    pub error_class: Option<String>,
+    /// Most failures returned, up to 500; defaults to 50
+    pub limit: Option<usize>,
+}
+
+/// Response DTO listing deliveries given up on
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeliveryFailuresResponse {
+    pub failures: Vec<DeliveryFailureResponse>,
+}
+
+/// Response DTO describing one delivery given up on
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeliveryFailureResponse {
+    /// `event_publish` or `group_topic_fanout`
+    pub mechanism: String,
+    /// Topic the event was being delivered to
+    pub target: String,
+    pub event_id: String,
+    pub receiver_id: String,
+    /// `timeout`, `unavailable`, `rejected`, or `other`
+    pub error_class: String,
+    pub last_error: String,
+    pub attempts: u32,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl From<DeliveryFailure> for DeliveryFailureResponse {
+    fn from(failure: DeliveryFailure) -> Self {
+        Self {
+            mechanism: failure.mechanism.as_str().to_string(),
+            target: failure.target,
+            event_id: failure.event_id.to_string(),
+            receiver_id: failure.receiver_id.to_string(),
+            error_class: failure.error_class.as_str().to_string(),
+            last_error: failure.last_error,
+            attempts: failure.attempts,
+            failed_at: failure.failed_at,
+        }
+    }
+}
+
+/// Request DTO for impersonating a user
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImpersonateRequest {
+    pub user_id: String,
+    /// Why the user is impersonated; recorded in the audit log
+    pub reason: String,
+    /// Token lifetime; defaults to 15 minutes
+    pub duration_seconds: Option<i64>,
+}
+
+/// Response DTO carrying an impersonation token
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImpersonationResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub session: SessionResponse,
+}
+
+/// Query parameters for listing sessions
+#[derive(Debug, Default, Deserialize)]
+pub struct ListSessionsQuery {
+    /// Only sessions authorizing as this user
+    pub user_id: Option<String>,
+}
+
+/// Response DTO listing sessions, newest first
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionsResponse {
+    pub sessions: Vec<SessionResponse>,
+}
+
+/// Response DTO describing an issued access token
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SessionResponse {
+    pub id: String,
+    pub user_id: String,
+    /// An administrator holds the token as the user
+    pub impersonation: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impersonated_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Not revoked and not expired
+    pub active: bool,
+}
+
+impl From<Session> for SessionResponse {
+    fn from(session: Session) -> Self {
+        Self {
+            impersonation: session.is_impersonation(),
+            active: session.is_active_at(Utc::now()),
+            id: session.id,
+            user_id: session.user_id,
+            impersonated_by: session.actor_id,
+            reason: session.reason,
+            issued_at: session.issued_at,
+            expires_at: session.expires_at,
+            revoked_at: session.revoked_at,
+        }
+    }
+}
+
+/// Query parameters for verifying the audit record chain
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditVerifyQuery {
+    /// First day verified; defaults to `to`
+    pub from: Option<NaiveDate>,
+    /// Last day verified; defaults to the current UTC day
+    pub to: Option<NaiveDate>,
+}
+
+/// Response DTO for a verification of the audit record chain
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditVerifyResponse {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Every checked record verified
+    pub intact: bool,
+    pub records_checked: u64,
+    pub days_checked: u64,
+    pub first_broken: Option<BrokenAuditLinkResponse>,
+}
+
+/// Response DTO locating the first audit record that failed verification
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BrokenAuditLinkResponse {
+    pub record_id: i64,
+    pub chain_day: NaiveDate,
+    /// Position of the record in its day's chain
+    pub position: i64,
+    pub recorded_at: DateTime<Utc>,
+    /// `content_mismatch`, `previous_hash_mismatch`, or
+    /// `missing_predecessor`
+    pub fault: String,
+}
+
+impl AuditVerifyResponse {
+    /// Creates the response for a verification of `from` through `to`
+    pub fn new(from: NaiveDate, to: NaiveDate, report: AuditChainReport) -> Self {
+        Self {
+            from,
+            to,
+            intact: report.is_intact(),
+            records_checked: report.records_checked,
+            days_checked: report.days_checked,
+            first_broken: report.first_broken.map(|broken| BrokenAuditLinkResponse {
+                record_id: broken.record_id,
+                chain_day: broken.chain_day,
+                position: broken.position,
+                recorded_at: broken.recorded_at,
+                fault: broken.fault.as_str().to_string(),
+            }),
+        }
+    }
+}
+
+/// Query parameters for full-text search
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchQueryParams {
+    /// Words, `"quoted phrases"`, and `prefix*` terms that must all match
+    pub q: Option<String>,
+    /// Comma-separated `receivers`, `events`, and `groups`; all when absent
+    pub types: Option<String>,
+    /// Most results returned, 1 to 50; defaults to 20
+    pub limit: Option<usize>,
+}
+
+/// Response DTO for a full-text search
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchResponse {
+    pub query: String,
+    /// Best matches across the searched types
+    pub results: Vec<SearchResultResponse>,
+    /// Matches per type before `limit` was applied
+    pub counts: SearchCountsResponse,
+}
+
+/// Response DTO for one search match
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchResultResponse {
+    /// `receiver`, `event`, or `group`
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub id: String,
+    pub name: String,
+    /// HTML-escaped text around the match, with matches in `<mark>` tags
+    pub snippet: String,
+    pub rank: f32,
+    /// The name equals the query, ignoring case
+    pub exact_match: bool,
+}
+
+/// Response DTO for the matches per searched type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchCountsResponse {
+    pub receivers: u64,
+    pub events: u64,
+    pub groups: u64,
+}
+
+impl SearchResponse {
+    /// Creates the response for the results of `query`
+    pub fn new(query: String, results: SearchResults) -> Self {
+        Self {
+            query,
+            results: results.hits.into_iter().map(Into::into).collect(),
+            counts: results.counts.into(),
+        }
+    }
+}
+
+impl From<SearchHit> for SearchResultResponse {
+    fn from(hit: SearchHit) -> Self {
+        Self {
+            resource_type: hit.resource_type.kind().to_string(),
+            id: hit.id,
+            name: hit.name,
+            snippet: hit.snippet,
+            rank: hit.rank,
+            exact_match: hit.exact_name,
+        }
+    }
+}
+
+impl From<SearchCounts> for SearchCountsResponse {
+    fn from(counts: SearchCounts) -> Self {
+        Self {
+            receivers: counts.receivers,
+            events: counts.events,
+            groups: counts.groups,
+        }
+    }
+}
+
+/// Response DTO for the feature flags evaluated for the caller
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeatureFlagsResponse {
+    pub flags: std::collections::BTreeMap<String, bool>,
+}
+
+/// Request DTO for changing a feature flag at runtime
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateFeatureFlagRequest {
+    /// State for callers without a matching role override
+    pub enabled: bool,
+    /// Replaces the role overrides; omit to keep the current ones
+    #[serde(default)]
+    pub roles: Option<std::collections::BTreeMap<String, bool>>,
+}
+
+/// Response DTO for the state of a single feature flag
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeatureFlagResponse {
+    pub name: String,
+    pub enabled: bool,
+    pub roles: std::collections::BTreeMap<String, bool>,
+}
+
+impl From<FeatureFlagState> for FeatureFlagResponse {
+    fn from(state: FeatureFlagState) -> Self {
+        Self {
+            name: state.name,
+            enabled: state.enabled,
+            roles: state.roles,
+        }
+    }
+}
+
+/// Longest maintenance message accepted
+pub const MAX_MAINTENANCE_MESSAGE_LENGTH: usize = 500;
+
+/// Message of maintenance windows entered without one
+pub const DEFAULT_MAINTENANCE_MESSAGE: &str =
+    "The API is in read-only maintenance mode; writes are temporarily rejected";
+
+/// Request DTO for turning maintenance mode on or off
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateMaintenanceRequest {
+    pub enabled: bool,
+    /// Returned with rejected writes; defaults to a generic message
+    #[serde(default)]
+    pub message: Option<String>,
+    /// When maintenance is scheduled to end; sets `Retry-After`
+    #[serde(default)]
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl UpdateMaintenanceRequest {
+    /// Validates the request against the current time
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the message is too long or the
+    /// scheduled end has passed. Neither is checked when turning
+    /// maintenance mode off.
+    pub fn validate(&self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self
+            .message
+            .as_ref()
+            .is_some_and(|message| message.chars().count() > MAX_MAINTENANCE_MESSAGE_LENGTH)
+        {
+            return Err(DomainError::ValidationError {
+                field: "message".to_string(),
+                message: Message::new("validation.maintenance_message_too_long")
+                    .with("max", MAX_MAINTENANCE_MESSAGE_LENGTH),
+            });
+        }
+        if self.ends_at.is_some_and(|ends_at| ends_at <= now) {
+            return Err(DomainError::ValidationError {
+                field: "ends_at".to_string(),
+                message: Message::new("validation.maintenance_end_past"),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the message to reject writes with
+    pub fn message(&self) -> String {
+        self.message
+            .as_deref()
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+            .unwrap_or(DEFAULT_MAINTENANCE_MESSAGE)
+            .to_string()
+    }
+}
+
+/// Response DTO for the maintenance mode state
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaintenanceResponse {
+    pub enabled: bool,
+    pub message: Option<String>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub started_by: Option<String>,
+}
+
+impl From<Option<MaintenanceWindow>> for MaintenanceResponse {
+    fn from(window: Option<MaintenanceWindow>) -> Self {
+        match window {
+            Some(window) => Self {
+                enabled: true,
+                message: Some(window.message),
+                ends_at: window.ends_at,
+                started_at: Some(window.started_at),
+                started_by: window.started_by,
+            },
+            None => Self {
+                enabled: false,
+                message: None,
+                ends_at: None,
+                started_at: None,
+                started_by: None,
+            },
+        }
+    }
+}
+
+/// Notice sent to streaming clients while maintenance mode is on
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaintenanceNoticeResponse {
+    pub message: String,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+impl From<MaintenanceWindow> for MaintenanceNoticeResponse {
+    fn from(window: MaintenanceWindow) -> Self {
+        Self {
+            message: window.message,
+            ends_at: window.ends_at,
+        }
+    }
+}
+
+/// Response DTO for the admin overview
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummaryResponse {
+    pub generated_at: DateTime<Utc>,
+    pub receivers: ReceiverCountsResponse,
+    pub groups: GroupCountsResponse,
+    pub events: EventWindowsResponse,
+    /// Busiest receivers over the last 24 hours
+    pub top_receivers: Vec<ReceiverVolumeResponse>,
+    /// Failed Kafka sends since start; null when publication is disabled
+    pub kafka_publish_failures: Option<u64>,
+    /// Null: no dead-letter queue is configured
+    pub dead_letter_backlog: Option<u64>,
+    /// Null: no transactional outbox is configured
+    pub outbox_backlog: Option<u64>,
+    pub active_users: u64,
+    /// Share of requests rejected by rate limits over the last few minutes
+    pub rate_limit_rejection_rate: Option<f64>,
+    pub maintenance: MaintenanceResponse,
+}
+
+/// Receiver counts; active receivers created events in the last 24 hours
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReceiverCountsResponse {
+    pub total: u64,
+    pub active: u64,
+    pub idle: u64,
+}
+
+/// Event receiver group counts by enabled state
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupCountsResponse {
+    pub total: u64,
+    pub enabled: u64,
+    pub disabled: u64,
+}
+
+/// Event counts for the last hour, day, and week
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventWindowsResponse {
+    pub last_1h: EventCountsResponse,
+    pub last_24h: EventCountsResponse,
+    pub last_7d: EventCountsResponse,
+}
+
+/// Events in one time window split by outcome
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventCountsResponse {
+    pub total: u64,
+    pub successful: u64,
+    pub failed: u64,
+}
+
+/// Event volume of a single receiver
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReceiverVolumeResponse {
+    pub id: EventReceiverId,
+    pub name: String,
+    pub event_count: u64,
+}
+
+impl From<EventOutcomeCounts> for EventCountsResponse {
+    fn from(counts: EventOutcomeCounts) -> Self {
+        Self {
+            total: counts.total(),
+            successful: counts.successful,
+            failed: counts.failed,
+        }
+    }
+}
+
+impl SummaryResponse {
+    /// Builds the overview from the system summary and the maintenance
+    /// window active on this instance
+    pub fn new(summary: SystemSummary, maintenance: Option<MaintenanceWindow>) -> Self {
+        Self {
+            generated_at: summary.generated_at,
+            receivers: ReceiverCountsResponse {
+                total: summary.receivers.total,
+                active: summary.receivers.active,
+                idle: summary.receivers.idle,
+            },
+            groups: GroupCountsResponse {
+                total: summary.groups.enabled + summary.groups.disabled,
+                enabled: summary.groups.enabled,
+                disabled: summary.groups.disabled,
+            },
+            events: EventWindowsResponse {
+                last_1h: summary.events_last_hour.into(),
+                last_24h: summary.events_last_day.into(),
+                last_7d: summary.events_last_week.into(),
+            },
+            top_receivers: summary
+                .top_receivers
+                .into_iter()
+                .map(|volume| ReceiverVolumeResponse {
+                    id: volume.receiver_id,
+                    name: volume.name,
+                    event_count: volume.event_count,
+                })
+                .collect(),
+            kafka_publish_failures: summary.kafka_publish_failures,
+            dead_letter_backlog: summary.dead_letter_backlog,
+            outbox_backlog: summary.outbox_backlog,
+            active_users: summary.active_users,
+            rate_limit_rejection_rate: summary.rate_limit_rejection_rate,
+            maintenance: maintenance.into(),
+        }
+    }
+}
+
+/// Request DTO for checking a candidate schema against stored events
+///
+/// The schema is never saved. Without a range, every stored event is a
+/// candidate, newest first, up to the server's scan cap.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaPreviewRequestBody {
+    pub schema: JsonValue,
+    pub start_time: Option<Timestamp>,
+    pub end_time: Option<Timestamp>,
+}
+
+impl From<SchemaPreviewRequestBody> for SchemaPreviewRequest {
+    fn from(body: SchemaPreviewRequestBody) -> Self {
+        Self {
+            schema: body.schema,
+            start_time: body.start_time.map(Timestamp::utc),
+            end_time: body.end_time.map(Timestamp::utc),
+        }
+    }
+}
+
+/// Response DTO for a schema preview, inline or polled by job id
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaPreviewResponse {
+    /// Set when the preview runs in the background
+    pub job_id: Option<String>,
+    /// `running`, `completed`, or `failed`
+    pub status: String,
+    /// Events scanned so far
+    pub scanned: usize,
+    /// Present once the preview has completed
+    pub report: Option<SchemaPreviewReportResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of validating stored events against a candidate schema
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SchemaPreviewReportResponse {
+    pub scanned: usize,
+    pub passing: usize,
+    pub failing: usize,
+    /// Most frequent violation messages, most common first
+    pub top_violations: Vec<ViolationCountResponse>,
+    /// Sample of failing event ids, newest first
+    pub failing_event_ids: Vec<EventId>,
+    /// True if the scan cap was reached before the oldest event in range
+    pub truncated: bool,
+}
+
+/// A violation message and the number of events that produced it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ViolationCountResponse {
+    pub message: String,
+    pub count: usize,
+}
+
+impl From<SchemaPreviewReport> for SchemaPreviewReportResponse {
+    fn from(report: SchemaPreviewReport) -> Self {
+        Self {
+            scanned: report.scanned,
+            passing: report.passing,
+            failing: report.failing,
+            top_violations: report
+                .top_violations
+                .into_iter()
+                .map(|violation| ViolationCountResponse {
+                    message: violation.message,
+                    count: violation.count,
+                })
+                .collect(),
+            failing_event_ids: report.failing_event_ids,
+            truncated: report.truncated,
+        }
+    }
+}
+
+impl From<SchemaPreviewReport> for SchemaPreviewResponse {
+    fn from(report: SchemaPreviewReport) -> Self {
+        Self {
+            job_id: None,
+            status: SchemaPreviewJobStatus::Completed.as_str().to_string(),
+            scanned: report.scanned,
+            report: Some(report.into()),
+            error: None,
+        }
+    }
+}
+
+impl From<SchemaPreviewJob> for SchemaPreviewResponse {
+    fn from(job: SchemaPreviewJob) -> Self {
+        Self {
+            job_id: Some(job.id),
+            status: job.status.as_str().to_string(),
+            scanned: job.scanned,
+            report: job.report.map(Into::into),
+            error: job.error,
+        }
+    }
+}
+
+/// Request DTO for deleting many receivers and groups in one call
+///
+/// Ids and name prefixes are combined. `dry_run` defaults to `true`, so a
+/// caller has to ask for deletion explicitly.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkDeleteRequest {
+    #[serde(default)]
+    pub receiver_ids: Vec<EventReceiverId>,
+    #[serde(default)]
+    pub receiver_name_prefixes: Vec<String>,
+    #[serde(default)]
+    pub group_ids: Vec<EventReceiverGroupId>,
+    #[serde(default)]
+    pub group_name_prefixes: Vec<String>,
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+impl From<&BulkDeleteRequest> for BulkDeleteSelector {
+    fn from(request: &BulkDeleteRequest) -> Self {
+        Self {
+            receiver_ids: request.receiver_ids.clone(),
+            receiver_name_prefixes: request.receiver_name_prefixes.clone(),
+            group_ids: request.group_ids.clone(),
+            group_name_prefixes: request.group_name_prefixes.clone(),
+        }
+    }
+}
+
+/// Response DTO describing what a bulk delete removed or would remove
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BulkDeleteResponse {
+    pub dry_run: bool,
+    /// Groups deleted, or that would be on a real run
+    pub groups: Vec<EventReceiverGroupId>,
+    /// Receivers deleted, or that would be on a real run
+    pub receivers: Vec<EventReceiverId>,
+    /// Selected resources that were left in place
+    pub blocked: Vec<BlockedResourceResponse>,
+}
+
+/// A selected resource a bulk delete left in place, and why
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlockedResourceResponse {
+    pub resource_type: String,
+    pub id: String,
+    pub reasons: Vec<String>,
+}
+
+/// Query parameters for importing users from CSV
+///
+/// `dry_run` defaults to `true`, so a caller has to ask for users to be
+/// created explicitly.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserImportQuery {
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+/// Response DTO for a user import, inline or polled by job id
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserImportResponse {
+    /// Set when the import runs in the background
+    pub job_id: Option<String>,
+    /// `running`, `completed`, or `failed`
+    pub status: String,
+    pub dry_run: bool,
+    /// Rows processed so far
+    pub processed: usize,
+    /// Rows in the file
+    pub total: usize,
+    /// Present once the import has completed
+    pub report: Option<UserImportReportResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of a user import
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserImportReportResponse {
+    pub dry_run: bool,
+    pub total: usize,
+    /// Users created, or that would be created on a dry run
+    pub created: usize,
+    pub skipped: usize,
+    pub invalid: usize,
+    pub failed: usize,
+    /// One entry per row, in file order
+    pub rows: Vec<UserImportRowResponse>,
+}
+
+/// Outcome of one row of a user import
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserImportRowResponse {
+    pub line: usize,
+    pub username: String,
+    pub email: String,
+    /// `created`, `would_create`, `skipped`, `invalid`, or `failed`
+    pub status: String,
+    pub roles: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<UserId>,
+    /// Invitation token of a created local account; only returned by
+    /// imports answered inline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitation_token: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub messages: Vec<String>,
+}
+
+/// Response DTO for an issued user invitation
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserInvitationResponse {
+    pub user_id: UserId,
+    /// Token the user accepts to choose a password; it is not shown again
+    pub invitation_token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Request DTO for accepting a user invitation
+#[derive(Debug, Deserialize)]
+pub struct AcceptInvitationRequest {
+    /// Invitation token handed out by an admin
+    pub token: String,
+    /// Password to set on the account
+    pub password: String,
+}
+
+impl From<UserImportReport> for UserImportReportResponse {
+    fn from(report: UserImportReport) -> Self {
+        Self {
+            dry_run: report.dry_run,
+            total: report.total,
+            created: report.created,
+            skipped: report.skipped,
+            invalid: report.invalid,
+            failed: report.failed,
+            rows: report
+                .rows
+                .into_iter()
+                .map(|row| UserImportRowResponse {
+                    line: row.line,
+                    username: row.username,
+                    email: row.email,
+                    status: row.status.as_str().to_string(),
+                    roles: row.roles.iter().map(ToString::to_string).collect(),
+                    user_id: row.user_id,
+                    invitation_token: row.invitation_token,
+                    messages: row.messages,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<UserImportReport> for UserImportResponse {
+    fn from(report: UserImportReport) -> Self {
+        Self {
+            job_id: None,
+            status: UserImportJobStatus::Completed.as_str().to_string(),
+            dry_run: report.dry_run,
+            processed: report.rows.len(),
+            total: report.total,
+            report: Some(report.into()),
+            error: None,
+        }
+    }
+}
+
+impl From<UserImportJob> for UserImportResponse {
+    fn from(job: UserImportJob) -> Self {
+        Self {
+            job_id: Some(job.id),
+            status: job.status.as_str().to_string(),
+            dry_run: job.dry_run,
+            processed: job.processed,
+            total: job.total,
+            report: job.report.map(Into::into),
+            error: job.error,
+        }
+    }
+}
+
+impl From<BulkDeleteReport> for BulkDeleteResponse {
+    fn from(report: BulkDeleteReport) -> Self {
+        Self {
+            dry_run: report.dry_run,
+            groups: report.groups,
+            receivers: report.receivers,
+            blocked: report
+                .blocked
+                .into_iter()
+                .map(|resource| BlockedResourceResponse {
+                    resource_type: resource.resource_type.to_string(),
+                    id: resource.id,
+                    reasons: resource.reasons,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Generic error response DTO
+///
+/// Validation and business-rule errors also carry the catalog key and
+/// parameters of their message, so clients can localize or match on them
+/// without parsing `message`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_key: Option<String>,
+    /// Boxed so that `(StatusCode, Json<ErrorResponse>)` error results stay
+    /// small
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Box<MessageParams>>,
+    /// Structured context of the error, such as what blocked a deletion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Box<JsonValue>>,
+}
+
+impl ErrorResponse {
+    pub fn new(error: String, message: String) -> Self {
+        Self {
+            error,
+            message,
+            field: None,
+            message_key: None,
+            params: None,
+            details: None,
+        }
+    }
+
+    /// Attaches structured context to the error
+    pub fn with_details(mut self, details: JsonValue) -> Self {
+        self.details = Some(Box::new(details));
+        self
+    }
+
+    pub fn with_field(error: String, message: String, field: String) -> Self {
+        Self {
+            field: Some(field),
+            ..Self::new(error, message)
+        }
+    }
+
+    /// Builds a response from a domain error, keeping its message key
+    pub fn from_domain(error: String, e: &DomainError) -> Self {
+        Self::new(error, e.to_string()).localizable(e.localizable())
+    }
+
+    /// Builds a response from an application error, keeping the message
+    /// key of validation and business-rule errors
+    pub fn from_error(error: String, e: &crate::error::Error) -> Self {
+        Self::new(error, e.message()).localizable(e.localizable())
+    }
+
+    fn localizable(mut self, localizable: Option<(Option<&str>, &Message)>) -> Self {
+        if let Some((field, message)) = localizable {
+            self.message = message.to_string();
+            self.field = field.map(str::to_string);
+            self.message_key = Some(message.key().to_string());
+            self.params = Some(Box::new(message.params().clone()));
+        }
+        self
+    }
+}
+
+/// Request DTO for updating an event receiver
+#[derive(Debug, Deserialize, Serialize)]
+pub struct UpdateEventReceiverRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub receiver_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<JsonValue>,
+    /// Fraction of events to store; 1.0 stores every event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<f64>,
+    /// Event names the receiver accepts; `{"glob": "*"}` accepts any name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_event_names: Option<AllowedEventNames>,
+    /// Failure rate at which the receiver is disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_disable: Option<AutoDisablePolicy>,
+    /// Rewrites applied to payloads before validation; `[]` removes them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<PayloadTransform>,
+    /// `true` re-enables a receiver its auto-disable policy disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+impl UpdateEventReceiverRequest {
+    /// Validates the request data
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if let Some(ref name) = self.name {
+            if name.trim().is_empty() {
+                return Err(DomainError::ValidationError {
+                    field: "name".to_string(),
+                    message: Message::new("validation.name_empty"),
+                });
+            }
+        }
+
+        if let Some(ref receiver_type) = self.receiver_type {
+            if receiver_type.trim().is_empty() {
+                return Err(DomainError::ValidationError {
+                    field: "type".to_string(),
+                    message: Message::new("validation.type_empty"),
+                });
+            }
+        }
 
         if let Some(ref version) = self.version {
             if version.trim().is_empty() {
                 return Err(DomainError::ValidationError {
                     field: "version".to_string(),
-                    message: "Version cannot be empty".to_string(),
+                    message: Message::new("validation.version_empty"),
                 });
             }
         }
@@ -389,12 +2687,22 @@ impl UpdateEventReceiverRequest {
             if !schema.is_object() {
                 return Err(DomainError::ValidationError {
                     field: "schema".to_string(),
-                    message: "Schema must be a JSON object".to_string(),
+                    message: Message::new("validation.schema_not_object"),
                 });
             }
         }
 
-        Ok(())
+        if self.enabled == Some(false) {
+            return Err(DomainError::ValidationError {
+                field: "enabled".to_string(),
+                message: Message::new("validation.receiver_enabled_false"),
+            });
+        }
+
+        validate_sample_rate(self.sample_rate)?;
+        validate_allowed_event_names(self.allowed_event_names.as_ref())?;
+        validate_auto_disable(self.auto_disable.as_ref())?;
+        validate_transform(self.transform.as_ref())
     }
 }
 
@@ -412,7 +2720,17 @@ pub struct UpdateEventReceiverGroupRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub event_receiver_ids: Option<Vec<String>>,
+    pub event_receiver_ids: Option<Vec<EventReceiverId>>,
+    /// New members that do not block release completeness; every other
+    /// member becomes required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional_receiver_ids: Option<Vec<EventReceiverId>>,
+    /// New default schema; an empty object clears it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_schema: Option<JsonValue>,
+    /// New dedicated topic; an empty name clears it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedicated_topic: Option<String>,
 }
 
 impl UpdateEventReceiverGroupRequest {
@@ -422,7 +2740,7 @@ impl UpdateEventReceiverGroupRequest {
             if name.trim().is_empty() {
                 return Err(DomainError::ValidationError {
                     field: "name".to_string(),
-                    message: "Name cannot be empty".to_string(),
+                    message: Message::new("validation.name_empty"),
                 });
             }
         }
@@ -431,7 +2749,7 @@ impl UpdateEventReceiverGroupRequest {
             if group_type.trim().is_empty() {
                 return Err(DomainError::ValidationError {
                     field: "type".to_string(),
-                    message: "Type cannot be empty".to_string(),
+                    message: Message::new("validation.type_empty"),
                 });
             }
         }
@@ -440,7 +2758,7 @@ impl UpdateEventReceiverGroupRequest {
             if version.trim().is_empty() {
                 return Err(DomainError::ValidationError {
                     field: "version".to_string(),
-                    message: "Version cannot be empty".to_string(),
+                    message: Message::new("validation.version_empty"),
                 });
             }
         }
@@ -449,62 +2767,332 @@ impl UpdateEventReceiverGroupRequest {
             if receiver_ids.is_empty() {
                 return Err(DomainError::ValidationError {
                     field: "event_receiver_ids".to_string(),
-                    message: "At least one event receiver ID is required".to_string(),
+                    message: Message::new("validation.receivers_required"),
                 });
             }
         }
 
         Ok(())
     }
+}
 
-    /// Converts event receiver ID strings to EventReceiverId values
-    pub fn parse_event_receiver_ids(&self) -> Result<Option<Vec<EventReceiverId>>, DomainError> {
-        if let Some(ref ids) = self.event_receiver_ids {
-            let parsed_ids: Result<Vec<_>, _> = ids
-                .iter()
-                .map(|id_str| {
-                    EventReceiverId::parse(id_str).map_err(|_| DomainError::ValidationError {
-                        field: "event_receiver_ids".to_string(),
-                        message: format!("Invalid event receiver ID format: {}", id_str),
-                    })
-                })
-                .collect();
-            Ok(Some(parsed_ids?))
-        } else {
-            Ok(None)
+/// Offset pagination of the receiver, group, and admin event listings,
+/// which skips or repeats items when the listing changes between pages
+pub const OFFSET_PAGINATION: Deprecation = Deprecation {
+    id: "offset_pagination",
+    summary: "The offset query parameter of list endpoints",
+    usage: DeprecatedUsage::QueryParameter {
+        name: "offset",
+        paths: &[
+            "/api/v1/receivers",
+            "/api/v1/groups",
+            "/api/v1/admin/events",
+        ],
+    },
+    deprecated_on: "2025-06-06",
+    sunset: "2027-06-30",
+    link: "https://xzepr.dev/deprecations/offset-pagination",
+};
+
+/// List query parameters for event receivers
+#[derive(Debug, Deserialize)]
+pub struct EventReceiverQueryParams {
+    /// Page size; falls back to the user's `default_page_size`, then 50
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: usize,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub receiver_type: Option<String>,
+    pub version: Option<String>,
+    /// Only receivers with no events or heartbeats since this RFC 3339 time
+    pub stale_since: Option<DateTime<Utc>>,
+    /// One of `created_at`, `updated_at`, `name`, `event_volume`, or
+    /// `success_rate`; defaults to `created_at`
+    pub sort: Option<String>,
+    /// `asc` or `desc`; defaults to `asc` for `name` and `desc` otherwise
+    pub order: Option<String>,
+    /// Comma-separated response fields; all fields when absent
+    pub fields: Option<String>,
+}
+
+fn default_limit() -> usize {
+    DEFAULT_PAGE_SIZE
+}
+
+impl EventReceiverQueryParams {
+    /// Returns the requested page
+    pub fn pagination(&self) -> PaginationParams {
+        PaginationParams::new(self.limit, self.offset)
+    }
+
+    /// Returns the requested sort order
+    pub fn list_order(&self) -> Result<ListOrder<ReceiverSortField>, DomainError> {
+        ListOrder::parse(self.sort.as_deref(), self.order.as_deref())
+    }
+
+    /// Validates query parameters
+    pub fn validate(&self) -> Result<(), DomainError> {
+        self.pagination().validate()?;
+        self.list_order()?;
+        Ok(())
+    }
+
+    /// Returns the requested limit, or the user's default page size
+    pub fn effective_limit(&self, preferences: &UserPreferences) -> usize {
+        self.pagination()
+            .effective_limit(preferences.default_page_size())
+    }
+}
+
+/// List query parameters for event receiver groups
+#[derive(Debug, Deserialize)]
+pub struct EventReceiverGroupQueryParams {
+    /// Page size; falls back to the user's `default_page_size`, then 50
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub offset: usize,
+    /// One of `created_at`, `updated_at`, or `name`; defaults to `created_at`
+    pub sort: Option<String>,
+    /// `asc` or `desc`; defaults to `asc` for `name` and `desc` otherwise
+    pub order: Option<String>,
+    /// Comma-separated response fields; all fields when absent
+    pub fields: Option<String>,
+}
+
+impl EventReceiverGroupQueryParams {
+    /// Returns the requested page
+    pub fn pagination(&self) -> PaginationParams {
+        PaginationParams::new(self.limit, self.offset)
+    }
+
+    /// Returns the requested sort order
+    pub fn list_order(&self) -> Result<ListOrder<GroupSortField>, DomainError> {
+        ListOrder::parse(self.sort.as_deref(), self.order.as_deref())
+    }
+
+    /// Validates query parameters
+    pub fn validate(&self) -> Result<(), DomainError> {
+        self.pagination().validate()?;
+        self.list_order()?;
+        Ok(())
+    }
+
+    /// Returns the requested limit, or the user's default page size
+    pub fn effective_limit(&self, preferences: &UserPreferences) -> usize {
+        self.pagination()
+            .effective_limit(preferences.default_page_size())
+    }
+}
+
+/// Time range used by exports when neither the request nor the user's
+/// preferences specify one
+pub const DEFAULT_EXPORT_TIME_RANGE_HOURS: i64 = 24;
+
+/// Query parameters for the event CSV export
+#[derive(Debug, Default, Deserialize)]
+pub struct EventExportQueryParams {
+    /// RFC 3339 start; defaults to `end` minus the user's `default_time_range`
+    pub start: Option<String>,
+    /// RFC 3339 end; defaults to now
+    pub end: Option<String>,
+    /// `UTC` or a `+HH:MM` offset; defaults to the user's `export_timezone`
+    pub timezone: Option<String>,
+}
+
+/// Resolved time window and output offset for an export
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventExportWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub timezone: FixedOffset,
+}
+
+impl EventExportQueryParams {
+    /// Resolves the export window, filling gaps from the user's preferences
+    pub fn resolve(
+        &self,
+        preferences: &UserPreferences,
+        now: DateTime<Utc>,
+    ) -> Result<EventExportWindow, DomainError> {
+        let end = match &self.end {
+            Some(end) => Timestamp::parse("end", end)?.utc(),
+            None => now,
+        };
+        let start = match &self.start {
+            Some(start) => Timestamp::parse("start", start)?.utc(),
+            None => {
+                end - preferences
+                    .default_time_range()
+                    .unwrap_or_else(|| Duration::hours(DEFAULT_EXPORT_TIME_RANGE_HOURS))
+            }
+        };
+        if start >= end {
+            return Err(DomainError::ValidationError {
+                field: "start".to_string(),
+                message: Message::new("validation.time_range_order"),
+            });
+        }
+
+        let timezone = match &self.timezone {
+            Some(tz) => parse_utc_offset(tz).ok_or_else(|| DomainError::ValidationError {
+                field: "timezone".to_string(),
+                message: Message::new("validation.timezone"),
+            })?,
+            None => preferences.export_timezone().unwrap_or(Utc.fix()),
+        };
+
+        Ok(EventExportWindow {
+            start,
+            end,
+            timezone,
+        })
+    }
+}
+
+/// Query parameters for the admin event list
+#[derive(Debug, Deserialize)]
+pub struct AdminEventQueryParams {
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub offset: usize,
+    pub principal_type: Option<String>,
+    pub principal_id: Option<String>,
+    pub source: Option<String>,
+    pub client_ip: Option<String>,
+    /// `user` or `system`; defaults to `user`
+    pub origin: Option<String>,
+    /// One of `created_at`, `name`, or `success`; defaults to `created_at`
+    pub sort: Option<String>,
+    /// `asc` or `desc`; defaults to `asc` for `name` and `desc` otherwise
+    pub order: Option<String>,
+    /// Comma-separated response fields; all fields when absent
+    pub fields: Option<String>,
+    /// `full` or `summary` for payloads kept in blob storage; defaults to
+    /// `full`
+    #[serde(default)]
+    pub payload: PayloadMode,
+}
+
+impl AdminEventQueryParams {
+    /// Validates query parameters
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.limit == 0 || self.limit > 1000 {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range").with("max", 1000),
+            });
+        }
+
+        // System events are not ingested, so they carry no metadata to match
+        let has_meta_filter = self.principal_type.is_some()
+            || self.principal_id.is_some()
+            || self.source.is_some()
+            || self.client_ip.is_some();
+        if has_meta_filter && self.origin()? == EventOrigin::System {
+            return Err(DomainError::ValidationError {
+                field: "origin".to_string(),
+                message: Message::new("validation.system_event_filters"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the requested event origin, defaulting to user events
+    pub fn origin(&self) -> Result<EventOrigin, DomainError> {
+        self.origin
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Converts the query parameters into event criteria, used for system
+    /// events which have no ingestion metadata
+    pub fn to_criteria(&self) -> Result<FindEventCriteria, DomainError> {
+        let order: ListOrder<EventSortField> =
+            ListOrder::parse(self.sort.as_deref(), self.order.as_deref())?;
+        Ok(FindEventCriteria::new()
+            .with_origin(self.origin()?)
+            .with_order(order)
+            .with_limit(self.limit)
+            .with_offset(self.offset))
+    }
+
+    /// Converts the query parameters into a repository filter
+    pub fn to_filter(&self) -> Result<IngestionMetaFilter, DomainError> {
+        let order: ListOrder<EventSortField> =
+            ListOrder::parse(self.sort.as_deref(), self.order.as_deref())?;
+        let mut filter = IngestionMetaFilter::new()
+            .with_origin(self.origin()?)
+            .with_order(order)
+            .with_limit(self.limit)
+            .with_offset(self.offset);
+
+        if let Some(principal_type) = &self.principal_type {
+            filter = filter.with_principal_type(principal_type.parse()?);
+        }
+        if let Some(principal_id) = &self.principal_id {
+            filter = filter.with_principal_id(principal_id.clone());
+        }
+        if let Some(source) = &self.source {
+            filter = filter.with_source(source.parse()?);
         }
+        if let Some(client_ip) = &self.client_ip {
+            filter = filter.with_client_ip(client_ip.clone());
+        }
+
+        Ok(filter)
     }
 }
 
-/// List query parameters for event receivers
-#[derive(Debug, Deserialize)]
-pub struct EventReceiverQueryParams {
-    #[serde(default = "default_limit")]
-    pub limit: usize,
-    #[serde(default)]
-    pub offset: usize,
-    pub name: Option<String>,
-    #[serde(rename = "type")]
-    pub receiver_type: Option<String>,
-    pub version: Option<String>,
+/// Query parameters of single-resource reads
+#[derive(Debug, Default, Deserialize)]
+pub struct FieldsQueryParams {
+    /// Comma-separated response fields; all fields when absent
+    pub fields: Option<String>,
+}
+
+/// How event reads return payloads kept in blob storage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadMode {
+    /// The full payload, read back from the blob store
+    #[default]
+    Full,
+    /// The stored summary, marked `payload_truncated`
+    Summary,
 }
 
-fn default_limit() -> usize {
-    50
+/// Query parameters of single event reads
+#[derive(Debug, Default, Deserialize)]
+pub struct EventQueryParams {
+    /// Comma-separated response fields; all fields when absent
+    pub fields: Option<String>,
+    /// `full` or `summary` for payloads kept in blob storage; defaults to
+    /// `full`
+    #[serde(default)]
+    pub payload: PayloadMode,
 }
 
-impl EventReceiverQueryParams {
-    /// Validates query parameters
-    pub fn validate(&self) -> Result<(), DomainError> {
-        if self.limit == 0 || self.limit > 1000 {
-            return Err(DomainError::ValidationError {
-                field: "limit".to_string(),
-                message: "Limit must be between 1 and 1000".to_string(),
-            });
-        }
+/// Query parameters of receiver and group reads that can look into the past
+#[derive(Debug, Default, Deserialize)]
+pub struct HistoricalQueryParams {
+    /// Comma-separated response fields; all fields when absent
+    pub fields: Option<String>,
+    /// RFC 3339 instant to read the resource at; current state when absent
+    pub as_of: Option<String>,
+}
 
-        Ok(())
-    }
+/// Query parameters of group member lists
+#[derive(Debug, Default, Deserialize)]
+pub struct AsOfQueryParams {
+    /// RFC 3339 instant to read the members at; current members when absent
+    pub as_of: Option<String>,
 }
 
 /// Paginated response wrapper
@@ -534,110 +3122,444 @@ impl PaginationMeta {
     }
 }
 
-/// Request body for adding a member to a group
+/// Query parameters for change feed polling
+#[derive(Debug, Deserialize)]
+pub struct ChangesQueryParams {
+    /// RFC 3339 timestamp or the `next_since` cursor from a previous poll
+    pub since: Option<String>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+impl ChangesQueryParams {
+    /// Validates query parameters
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.limit == 0 || self.limit > MAX_CHANGE_PAGE_SIZE {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range").with("max", MAX_CHANGE_PAGE_SIZE),
+            });
+        }
+
+        self.cursor().map(|_| ())
+    }
+
+    /// Parses the since parameter into a change cursor
+    pub fn cursor(&self) -> Result<Option<ChangeCursor>, DomainError> {
+        self.since
+            .as_deref()
+            .map(|since| {
+                // Timestamps without an offset get the same hint as elsewhere
+                if is_naive(since.trim()) {
+                    return Timestamp::parse("since", since).map(|ts| ChangeCursor::at(ts.utc()));
+                }
+                ChangeCursor::parse(since)
+            })
+            .transpose()
+    }
+}
+
+/// Response DTO for change feed polling
 ///
-/// This DTO is used when adding a user to an event receiver group,
-/// granting them permission to POST events to receivers in that group.
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct AddMemberRequest {
-    /// The ID of the user to add as a member
-    pub user_id: String,
+/// Clients apply `upserts` and `deleted` in order and pass `next_since`
+/// on the following poll. When `has_more` is true the client should poll
+/// again immediately.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangesResponse<T> {
+    pub upserts: Vec<T>,
+    pub deleted: Vec<String>,
+    pub next_since: String,
+    pub has_more: bool,
 }
 
-impl AddMemberRequest {
-    /// Validates the add member request
-    ///
-    /// # Returns
-    ///
-    /// Returns Ok(()) if validation passes, or an ErrorResponse if validation fails
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use xzepr::api::rest::dtos::AddMemberRequest;
-    ///
-    /// let request = AddMemberRequest {
-    ///     user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB0".to_string(),
-    /// };
-    /// assert!(request.validate().is_ok());
-    /// ```
-    pub fn validate(&self) -> Result<(), ErrorResponse> {
-        if self.user_id.trim().is_empty() {
-            return Err(ErrorResponse::with_field(
-                "ValidationError".to_string(),
-                "user_id cannot be empty".to_string(),
-                "user_id".to_string(),
-            ));
+impl<T> ChangesResponse<T> {
+    /// Builds a response from a domain change set, converting upserts with `convert`
+    pub fn from_change_set<E, I>(changes: ChangeSet<E, I>, convert: impl FnMut(E) -> T) -> Self
+    where
+        I: std::fmt::Display,
+    {
+        Self {
+            upserts: changes.upserts.into_iter().map(convert).collect(),
+            deleted: changes.deleted.iter().map(|id| id.to_string()).collect(),
+            next_since: changes.next_since.to_string(),
+            has_more: changes.has_more,
         }
+    }
+}
 
-        Ok(())
+/// Query parameters for long-polling new events
+#[derive(Debug, Default, Deserialize)]
+pub struct EventPollQueryParams {
+    /// Id of the last event seen, as returned in `next_cursor`
+    pub cursor: Option<String>,
+    /// Only return events of this receiver
+    pub receiver_id: Option<String>,
+    /// Seconds to wait for new events; defaults to, and is capped at, the
+    /// configured maximum
+    pub timeout_seconds: Option<u64>,
+}
+
+impl EventPollQueryParams {
+    /// Parses the cursor into an event id
+    pub fn cursor(&self) -> Result<Option<EventId>, DomainError> {
+        self.cursor
+            .as_deref()
+            .map(|cursor| {
+                cursor
+                    .parse::<EventId>()
+                    .map_err(|_| DomainError::ValidationError {
+                        field: "cursor".to_string(),
+                        message: Message::new("validation.cursor"),
+                    })
+            })
+            .transpose()
     }
 
-    /// Parses the user_id string into a UserId value object
-    ///
-    /// # Returns
-    ///
-    /// Returns the parsed UserId or an ErrorResponse if parsing fails
-    pub fn parse_user_id(&self) -> Result<crate::domain::value_objects::UserId, ErrorResponse> {
-        self.user_id.parse().map_err(|_| {
-            ErrorResponse::new(
-                "ValidationError".to_string(),
-                "Invalid user_id format".to_string(),
-            )
+    /// Parses the receiver filter
+    pub fn receiver_id(&self) -> Result<Option<EventReceiverId>, DomainError> {
+        self.receiver_id
+            .as_deref()
+            .map(|id| {
+                id.parse::<EventReceiverId>()
+                    .map_err(|_| DomainError::ValidationError {
+                        field: "receiver_id".to_string(),
+                        message: Message::new("validation.receiver_id"),
+                    })
+            })
+            .transpose()
+    }
+}
+
+/// Response DTO for long-polling new events
+///
+/// `next_cursor` is always present: the id of the last event returned, or
+/// the requested cursor when the poll timed out. It is `null` only when no
+/// cursor was given and no event exists yet.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EventPollResponse {
+    pub events: Vec<EventResponse>,
+    pub next_cursor: Option<EventId>,
+    /// Present while maintenance mode is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance: Option<MaintenanceNoticeResponse>,
+}
+
+/// Query parameters for reading a receiver timeline
+#[derive(Debug, Default, Deserialize)]
+pub struct ReceiverTimelineQueryParams {
+    /// Earliest entry time, inclusive, as an RFC 3339 timestamp
+    pub start: Option<String>,
+    /// Latest entry time, exclusive, as an RFC 3339 timestamp
+    pub end: Option<String>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Page size, 1 to 1000; defaults to 50
+    pub limit: Option<usize>,
+}
+
+impl ReceiverTimelineQueryParams {
+    /// Validates the parameters into a query over `kinds`
+    pub fn into_query(self, kinds: Vec<TimelineKind>) -> Result<TimelineQuery, DomainError> {
+        let page = PaginationParams::new(self.limit, 0);
+        page.validate()?;
+
+        Ok(TimelineQuery {
+            start: self
+                .start
+                .map(|start| Timestamp::parse("start", &start).map(Timestamp::utc))
+                .transpose()?,
+            end: self
+                .end
+                .map(|end| Timestamp::parse("end", &end).map(Timestamp::utc))
+                .transpose()?,
+            after: self
+                .cursor
+                .as_deref()
+                .map(TimelineCursor::parse)
+                .transpose()?,
+            limit: page.effective_limit(None),
+            kinds,
         })
     }
 }
 
-/// Request body for removing a member from a group
+/// A receiver timeline entry, tagged with its `kind`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimelineEntryResponse {
+    Event {
+        at: DateTime<Utc>,
+        event_id: EventId,
+        name: String,
+        success: bool,
+        /// Payload severity; omitted when the payload has none
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        severity: Option<String>,
+    },
+    ConfigChange {
+        at: DateTime<Utc>,
+        /// `created`, `updated`, or `deleted`
+        action: String,
+        /// Fields an update changed
+        changed_fields: Vec<String>,
+    },
+    GroupMembership {
+        at: DateTime<Utc>,
+        group_id: EventReceiverGroupId,
+        group_name: String,
+        /// `added` or `removed`
+        action: String,
+    },
+}
+
+impl From<TimelineEntry> for TimelineEntryResponse {
+    fn from(entry: TimelineEntry) -> Self {
+        let at = entry.at();
+        match entry.detail {
+            TimelineDetail::Event {
+                id,
+                name,
+                success,
+                severity,
+            } => TimelineEntryResponse::Event {
+                at,
+                event_id: id,
+                name,
+                success,
+                severity: (!severity.is_empty()).then_some(severity),
+            },
+            TimelineDetail::ConfigChange {
+                action,
+                changed_fields,
+            } => TimelineEntryResponse::ConfigChange {
+                at,
+                action: match action {
+                    ConfigChangeAction::Created => "created",
+                    ConfigChangeAction::Updated => "updated",
+                    ConfigChangeAction::Deleted => "deleted",
+                }
+                .to_string(),
+                changed_fields,
+            },
+            TimelineDetail::GroupMembership {
+                group_id,
+                group_name,
+                action,
+            } => TimelineEntryResponse::GroupMembership {
+                at,
+                group_id,
+                group_name,
+                action: match action {
+                    MembershipAction::Added => "added",
+                    MembershipAction::Removed => "removed",
+                }
+                .to_string(),
+            },
+        }
+    }
+}
+
+/// Response DTO for a page of a receiver timeline
 ///
-/// This DTO is used when removing a user from an event receiver group,
-/// revoking their permission to POST events to receivers in that group.
-#[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct RemoveMemberRequest {
-    /// The ID of the user to remove from the group
-    pub user_id: String,
+/// `next_cursor` is set when more entries follow; pass it back as `cursor`
+/// to read the next page.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReceiverTimelineResponse {
+    pub receiver_id: EventReceiverId,
+    pub entries: Vec<TimelineEntryResponse>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
 }
 
-impl RemoveMemberRequest {
-    /// Validates the remove member request
-    ///
-    /// # Returns
-    ///
-    /// Returns Ok(()) if validation passes, or an ErrorResponse if validation fails
-    pub fn validate(&self) -> Result<(), ErrorResponse> {
-        if self.user_id.trim().is_empty() {
-            return Err(ErrorResponse::with_field(
-                "ValidationError".to_string(),
-                "user_id cannot be empty".to_string(),
-                "user_id".to_string(),
-            ));
+impl ReceiverTimelineResponse {
+    /// Builds the response for a page of the timeline of `receiver_id`
+    pub fn new(receiver_id: EventReceiverId, page: TimelinePage) -> Self {
+        Self {
+            receiver_id,
+            entries: page.entries.into_iter().map(Into::into).collect(),
+            has_more: page.next_cursor.is_some(),
+            next_cursor: page.next_cursor.map(|cursor| cursor.to_string()),
         }
+    }
+}
 
-        Ok(())
+/// Response DTO summarizing the PII findings of a receiver's events
+///
+/// Covers events ingested from `since` on. Only detector names and JSON
+/// paths are reported, never the detected values.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PiiReportResponse {
+    pub receiver_id: EventReceiverId,
+    pub since: DateTime<Utc>,
+    pub events_with_findings: u64,
+    /// Most frequent detector and path pairs, most frequent first
+    pub paths: Vec<PiiPathCountResponse>,
+}
+
+/// One detector and path of a [`PiiReportResponse`]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PiiPathCountResponse {
+    pub detector: String,
+    pub path: String,
+    pub count: u64,
+}
+
+impl PiiReportResponse {
+    /// Builds the response for the report of `receiver_id` since `since`
+    pub fn new(receiver_id: EventReceiverId, since: DateTime<Utc>, report: PiiReport) -> Self {
+        Self {
+            receiver_id,
+            since,
+            events_with_findings: report.events_with_findings,
+            paths: report
+                .paths
+                .into_iter()
+                .map(|path| PiiPathCountResponse {
+                    detector: path.detector.to_string(),
+                    path: path.path,
+                    count: path.count,
+                })
+                .collect(),
+        }
     }
+}
 
-    /// Parses the user_id string into a UserId value object
-    ///
-    /// # Returns
-    ///
-    /// Returns the parsed UserId or an ErrorResponse if parsing fails
-    pub fn parse_user_id(&self) -> Result<crate::domain::value_objects::UserId, ErrorResponse> {
-        self.user_id.parse().map_err(|_| {
-            ErrorResponse::new(
-                "ValidationError".to_string(),
-                "Invalid user_id format".to_string(),
-            )
-        })
+/// Query parameters of the group completeness report
+#[derive(Debug, Default, Deserialize)]
+pub struct GroupCompletenessQueryParams {
+    /// Release value events must carry
+    pub release: Option<String>,
+    /// RFC 3339 instant events must be stored at or after; every stored
+    /// event counts when absent
+    pub since: Option<String>,
+}
+
+impl GroupCompletenessQueryParams {
+    /// Parses `since`
+    pub fn since(&self) -> Result<Option<DateTime<Utc>>, DomainError> {
+        self.since
+            .as_deref()
+            .map(|since| Timestamp::parse("since", since).map(Timestamp::utc))
+            .transpose()
+    }
+}
+
+/// Response DTO reporting whether every required receiver of a group
+/// reported a successful event for a release
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupCompletenessResponse {
+    pub group_id: EventReceiverGroupId,
+    pub release: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    /// True once every required receiver is `complete`
+    pub complete: bool,
+    pub counts: GroupCompletenessCountsResponse,
+    /// Member receivers in group order
+    pub receivers: Vec<ReceiverCompletenessResponse>,
+}
+
+/// Member receivers of a [`GroupCompletenessResponse`] by status, required
+/// or not
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupCompletenessCountsResponse {
+    pub complete: usize,
+    pub failed_only: usize,
+    pub missing: usize,
+    /// Required receivers that are not `complete`
+    pub blocking: usize,
+}
+
+/// One member receiver of a [`GroupCompletenessResponse`]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReceiverCompletenessResponse {
+    pub receiver_id: EventReceiverId,
+    pub required: bool,
+    /// `complete`, `failed_only`, or `missing`
+    pub status: ReceiverReleaseStatus,
+}
+
+impl From<GroupCompleteness> for GroupCompletenessResponse {
+    fn from(completeness: GroupCompleteness) -> Self {
+        let counts = GroupCompletenessCountsResponse {
+            complete: completeness.count(ReceiverReleaseStatus::Complete),
+            failed_only: completeness.count(ReceiverReleaseStatus::FailedOnly),
+            missing: completeness.count(ReceiverReleaseStatus::Missing),
+            blocking: completeness.blocking_receivers().count(),
+        };
+        Self {
+            group_id: completeness.group_id,
+            release: completeness.release,
+            since: completeness.since,
+            complete: completeness.complete,
+            counts,
+            receivers: completeness
+                .receivers
+                .into_iter()
+                .map(|receiver| ReceiverCompletenessResponse {
+                    receiver_id: receiver.receiver_id,
+                    required: receiver.required,
+                    status: receiver.status,
+                })
+                .collect(),
+        }
     }
 }
 
+/// Request body for adding a member to a group
+///
+/// This DTO is used when adding a user to an event receiver group,
+/// granting them permission to POST events to receivers in that group.
+///
+/// # Examples
+///
+/// ```
+/// use xzepr::api::rest::dtos::AddMemberRequest;
+///
+/// let request: AddMemberRequest =
+///     serde_json::from_str(r#"{"user_id": "01HN6Z5K8XQZJQY7WZXR5VQMB0"}"#).unwrap();
+/// assert_eq!(request.user_id.to_string(), "01HN6Z5K8XQZJQY7WZXR5VQMB0");
+///
+/// assert!(request.expires_at.is_none());
+///
+/// assert!(serde_json::from_str::<AddMemberRequest>(r#"{"user_id": ""}"#).is_err());
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AddMemberRequest {
+    /// The ID of the user to add as a member
+    pub user_id: UserId,
+    /// When the membership ends; omitted or null keeps it until removed
+    #[serde(default)]
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Request body for changing a group membership
+///
+/// A null or omitted `expires_at` keeps the member until removed.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct UpdateMemberRequest {
+    /// When the membership ends
+    #[serde(default)]
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Request body for removing a member from a group
+///
+/// This DTO is used when removing a user from an event receiver group,
+/// revoking their permission to POST events to receivers in that group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RemoveMemberRequest {
+    /// The ID of the user to remove from the group
+    pub user_id: UserId,
+}
+
 /// Response body for a single group member
 ///
 /// Contains information about a user who is a member of an event receiver group.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupMemberResponse {
     /// The unique identifier of the user
-    pub user_id: String,
+    pub user_id: UserId,
     /// The username of the member
     pub username: String,
     /// The email address of the member
@@ -645,7 +3567,10 @@ pub struct GroupMemberResponse {
     /// Timestamp when this user was added to the group
     pub added_at: DateTime<Utc>,
     /// The user ID of who added this member to the group
-    pub added_by: String,
+    pub added_by: UserId,
+    /// When the membership ends; null if it lasts until removed
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 /// Response body for listing all members of a group
@@ -654,9 +3579,12 @@ pub struct GroupMemberResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupMembersResponse {
     /// The unique identifier of the group
-    pub group_id: String,
+    pub group_id: EventReceiverGroupId,
     /// List of all members in the group
     pub members: Vec<GroupMemberResponse>,
+    /// Instant a historical read reconstructed; absent on current reads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<DateTime<Utc>>,
 }
 
 #[cfg(test)]
@@ -672,6 +3600,10 @@ mod tests {
             version: "1.0.0".to_string(),
             description: "A test receiver".to_string(),
             schema: json!({"type": "object"}),
+            sample_rate: None,
+            allowed_event_names: None,
+            auto_disable: None,
+            transform: None,
         };
         assert!(valid_request.validate().is_ok());
 
@@ -682,6 +3614,10 @@ mod tests {
             version: "1.0.0".to_string(),
             description: "A test receiver with no schema constraints".to_string(),
             schema: json!({}),
+            sample_rate: None,
+            allowed_event_names: None,
+            auto_disable: None,
+            transform: None,
         };
         assert!(empty_schema_request.validate().is_ok());
 
@@ -691,8 +3627,25 @@ mod tests {
             version: "1.0.0".to_string(),
             description: "A test receiver".to_string(),
             schema: json!({"type": "object"}),
+            sample_rate: None,
+            allowed_event_names: None,
+            auto_disable: None,
+            transform: None,
         };
         assert!(invalid_request.validate().is_err());
+
+        let invalid_sample_rate = CreateEventReceiverRequest {
+            name: "Test Receiver".to_string(),
+            receiver_type: "webhook".to_string(),
+            version: "1.0.0".to_string(),
+            description: "A test receiver".to_string(),
+            schema: json!({}),
+            sample_rate: Some(1.5),
+            allowed_event_names: None,
+            auto_disable: None,
+            transform: None,
+        };
+        assert!(invalid_sample_rate.validate().is_err());
     }
 
     #[test]
@@ -706,7 +3659,9 @@ mod tests {
             description: "Test event".to_string(),
             payload: json!({"test": "data"}),
             success: true,
-            event_receiver_id: EventReceiverId::new().to_string(),
+            event_receiver_id: Some(EventReceiverId::new()),
+            receiver_spec: None,
+            origin: None,
         };
         assert!(valid_request.validate().is_ok());
 
@@ -719,169 +3674,273 @@ mod tests {
             description: "Test event".to_string(),
             payload: json!({"test": "data"}),
             success: true,
-            event_receiver_id: EventReceiverId::new().to_string(),
+            event_receiver_id: Some(EventReceiverId::new()),
+            receiver_spec: None,
+            origin: None,
         };
         assert!(invalid_request.validate().is_err());
     }
 
+    #[test]
+    fn test_create_event_request_receiver_spec() {
+        let body = json!({
+            "name": "test",
+            "version": "1.0.0",
+            "release": "2023.11.16",
+            "platform_id": "linux",
+            "package": "docker",
+            "description": "Test",
+            "payload": {},
+            "success": true,
+            "receiver_spec": {"name": "ci", "type": "webhook", "version": "1.0.0"},
+        });
+        let request: CreateEventRequest = serde_json::from_value(body.clone()).unwrap();
+        assert!(request.validate().is_ok());
+        let spec = request.receiver_spec.unwrap().into_spec();
+        assert_eq!(spec.receiver_type, "webhook");
+        assert_eq!(spec.schema, None);
+
+        let mut both = body.clone();
+        both["event_receiver_id"] = json!(EventReceiverId::new().to_string());
+        let both: CreateEventRequest = serde_json::from_value(both).unwrap();
+        assert!(both.validate().is_err());
+
+        let mut neither = body.clone();
+        neither.as_object_mut().unwrap().remove("receiver_spec");
+        let neither: CreateEventRequest = serde_json::from_value(neither).unwrap();
+        assert!(neither.validate().is_err());
+
+        let mut blank = body;
+        blank["receiver_spec"]["name"] = json!(" ");
+        let blank: CreateEventRequest = serde_json::from_value(blank).unwrap();
+        assert!(blank.validate().is_err());
+    }
+
     #[test]
     fn test_query_params_validation() {
         let valid_params = EventReceiverQueryParams {
-            limit: 10,
+            limit: Some(10),
             offset: 0,
             name: None,
             receiver_type: None,
             version: None,
+            stale_since: None,
+            sort: None,
+            order: None,
+            fields: None,
         };
         assert!(valid_params.validate().is_ok());
 
         let invalid_params = EventReceiverQueryParams {
-            limit: 0, // Invalid limit
+            limit: Some(0), // Invalid limit
             offset: 0,
             name: None,
             receiver_type: None,
             version: None,
+            stale_since: None,
+            sort: None,
+            order: None,
+            fields: None,
         };
         assert!(invalid_params.validate().is_err());
-    }
-
-    #[test]
-    fn test_pagination_meta() {
-        let meta = PaginationMeta::new(10, 0, 100);
-        assert_eq!(meta.limit, 10);
-        assert_eq!(meta.offset, 0);
-        assert_eq!(meta.total, 100);
-        assert!(meta.has_more);
 
-        let meta_last_page = PaginationMeta::new(10, 90, 100);
-        assert!(!meta_last_page.has_more);
+        let invalid_sort = EventReceiverQueryParams {
+            limit: Some(10),
+            sort: Some("owner_id".to_string()),
+            ..invalid_params
+        };
+        assert!(invalid_sort.validate().is_err());
     }
 
     #[test]
-    fn test_event_receiver_id_parsing() {
-        let receiver_id = EventReceiverId::new();
-        let request = CreateEventRequest {
-            name: "test".to_string(),
-            version: "1.0.0".to_string(),
-            release: "2023.11.16".to_string(),
-            platform_id: "linux".to_string(),
-            package: "docker".to_string(),
-            description: "Test".to_string(),
-            payload: json!({}),
-            success: true,
-            event_receiver_id: receiver_id.to_string(),
+    fn test_query_params_default_page_size_preference() {
+        let mut preferences = UserPreferences::new();
+        let params = EventReceiverQueryParams {
+            limit: None,
+            offset: 0,
+            name: None,
+            receiver_type: None,
+            version: None,
+            stale_since: None,
+            sort: None,
+            order: None,
+            fields: None,
         };
+        assert_eq!(params.effective_limit(&preferences), 50);
 
-        let parsed_id = request.parse_event_receiver_id().unwrap();
-        assert_eq!(parsed_id, receiver_id);
+        preferences
+            .merge(&json!({"default_page_size": 15}))
+            .unwrap();
+        assert_eq!(params.effective_limit(&preferences), 15);
 
-        let invalid_request = CreateEventRequest {
-            name: "test".to_string(),
-            version: "1.0.0".to_string(),
-            release: "2023.11.16".to_string(),
-            platform_id: "linux".to_string(),
-            package: "docker".to_string(),
-            description: "Test".to_string(),
-            payload: json!({}),
-            success: true,
-            event_receiver_id: "invalid-id".to_string(),
+        // An explicit limit always wins
+        let params = EventReceiverQueryParams {
+            limit: Some(5),
+            ..params
         };
-
-        assert!(invalid_request.parse_event_receiver_id().is_err());
+        assert_eq!(params.effective_limit(&preferences), 5);
     }
 
     #[test]
-    fn test_add_member_request_validation() {
-        let valid_request = AddMemberRequest {
-            user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB0".to_string(),
-        };
-        assert!(valid_request.validate().is_ok());
-
-        let empty_request = AddMemberRequest {
-            user_id: "".to_string(),
+    fn test_export_params_resolve_from_preferences() {
+        let now = DateTime::parse_from_rfc3339("2025-02-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut preferences = UserPreferences::new();
+
+        let window = EventExportQueryParams::default()
+            .resolve(&preferences, now)
+            .unwrap();
+        assert_eq!(window.end, now);
+        assert_eq!(window.start, now - Duration::hours(24));
+        assert_eq!(window.timezone, Utc.fix());
+
+        preferences
+            .merge(&json!({"default_time_range": "2h", "export_timezone": "+01:00"}))
+            .unwrap();
+        let window = EventExportQueryParams::default()
+            .resolve(&preferences, now)
+            .unwrap();
+        assert_eq!(window.start, now - Duration::hours(2));
+        assert_eq!(window.timezone, FixedOffset::east_opt(3600).unwrap());
+
+        // Request parameters override preferences
+        let params = EventExportQueryParams {
+            start: Some("2025-02-01T00:00:00Z".to_string()),
+            end: None,
+            timezone: Some("UTC".to_string()),
         };
-        assert!(empty_request.validate().is_err());
+        let window = params.resolve(&preferences, now).unwrap();
+        assert_eq!(window.start, now - Duration::hours(12));
+        assert_eq!(window.timezone, Utc.fix());
 
-        let whitespace_request = AddMemberRequest {
-            user_id: "   ".to_string(),
+        let params = EventExportQueryParams {
+            timezone: Some("Mars/Olympus".to_string()),
+            ..Default::default()
         };
-        assert!(whitespace_request.validate().is_err());
+        assert!(params.resolve(&preferences, now).is_err());
     }
 
     #[test]
-    fn test_add_member_request_parse_user_id() {
-        use crate::domain::value_objects::UserId;
-
-        let user_id = UserId::new();
-        let request = AddMemberRequest {
-            user_id: user_id.to_string(),
+    fn test_export_params_convert_offsets_across_dst() {
+        let now = DateTime::parse_from_rfc3339("2025-02-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let preferences = UserPreferences::new();
+
+        // 01:30 CEST to 02:30 CET on the night clocks go back is two hours
+        let params = EventExportQueryParams {
+            start: Some("2024-10-27T01:30:00+02:00".to_string()),
+            end: Some("2024-10-27T02:30:00+01:00".to_string()),
+            timezone: None,
         };
+        let window = params.resolve(&preferences, now).unwrap();
+        assert_eq!(window.start.to_rfc3339(), "2024-10-26T23:30:00+00:00");
+        assert_eq!(window.end - window.start, Duration::hours(2));
 
-        let parsed = request.parse_user_id();
-        assert!(parsed.is_ok());
-        assert_eq!(parsed.unwrap(), user_id);
+        let params = EventExportQueryParams {
+            start: Some("2024-10-27 01:30:00".to_string()),
+            ..Default::default()
+        };
+        match params.resolve(&preferences, now) {
+            Err(DomainError::ValidationError { field, message }) => {
+                assert_eq!(field, "start");
+                assert_eq!(message.key(), "validation.timestamp_offset");
+                assert!(message.to_string().contains("2024-01-05T10:00:00Z"));
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
     }
 
     #[test]
-    fn test_add_member_request_parse_invalid_user_id() {
-        let request = AddMemberRequest {
-            user_id: "invalid-id".to_string(),
-        };
+    fn test_pagination_meta() {
+        let meta = PaginationMeta::new(10, 0, 100);
+        assert_eq!(meta.limit, 10);
+        assert_eq!(meta.offset, 0);
+        assert_eq!(meta.total, 100);
+        assert!(meta.has_more);
 
-        let parsed = request.parse_user_id();
-        assert!(parsed.is_err());
+        let meta_last_page = PaginationMeta::new(10, 90, 100);
+        assert!(!meta_last_page.has_more);
     }
 
     #[test]
-    fn test_remove_member_request_validation() {
-        let valid_request = RemoveMemberRequest {
-            user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB0".to_string(),
-        };
-        assert!(valid_request.validate().is_ok());
-
-        let empty_request = RemoveMemberRequest {
-            user_id: "".to_string(),
-        };
-        assert!(empty_request.validate().is_err());
-
-        let whitespace_request = RemoveMemberRequest {
-            user_id: "   ".to_string(),
-        };
-        assert!(whitespace_request.validate().is_err());
+    fn test_create_event_request_deserializes_typed_receiver_id() {
+        let receiver_id = EventReceiverId::new();
+        let body = json!({
+            "name": "test",
+            "version": "1.0.0",
+            "release": "2023.11.16",
+            "platform_id": "linux",
+            "package": "docker",
+            "description": "Test",
+            "payload": {},
+            "success": true,
+            "event_receiver_id": receiver_id.to_string(),
+        });
+
+        let request: CreateEventRequest = serde_json::from_value(body.clone()).unwrap();
+        assert_eq!(request.event_receiver_id, Some(receiver_id));
+        // The wire format is unchanged
+        assert_eq!(serde_json::to_value(&request).unwrap(), body);
+
+        for invalid in [
+            json!("invalid-id"),
+            json!(""),
+            json!(receiver_id.to_string().to_lowercase()),
+        ] {
+            let mut body = body.clone();
+            body["event_receiver_id"] = invalid;
+            assert!(serde_json::from_value::<CreateEventRequest>(body).is_err());
+        }
     }
 
     #[test]
-    fn test_remove_member_request_parse_user_id() {
-        use crate::domain::value_objects::UserId;
-
+    fn test_member_requests_deserialize_user_id() {
         let user_id = UserId::new();
-        let request = RemoveMemberRequest {
-            user_id: user_id.to_string(),
-        };
+        let body = json!({"user_id": user_id.to_string()});
 
-        let parsed = request.parse_user_id();
-        assert!(parsed.is_ok());
-        assert_eq!(parsed.unwrap(), user_id);
+        let add: AddMemberRequest = serde_json::from_value(body.clone()).unwrap();
+        assert_eq!(add.user_id, user_id);
+        let remove: RemoveMemberRequest = serde_json::from_value(body).unwrap();
+        assert_eq!(remove.user_id, user_id);
+
+        for invalid in ["", "   ", "invalid-id"] {
+            let body = json!({ "user_id": invalid });
+            assert!(serde_json::from_value::<AddMemberRequest>(body.clone()).is_err());
+            assert!(serde_json::from_value::<RemoveMemberRequest>(body).is_err());
+        }
     }
 
     #[test]
-    fn test_remove_member_request_parse_invalid_user_id() {
-        let request = RemoveMemberRequest {
-            user_id: "invalid-id".to_string(),
-        };
-
-        let parsed = request.parse_user_id();
-        assert!(parsed.is_err());
+    fn test_group_response_serializes_ids_as_strings() {
+        let receiver_id = EventReceiverId::new();
+        let group = EventReceiverGroup::new(
+            "group".to_string(),
+            "ci".to_string(),
+            "1.0.0".to_string(),
+            "Test group".to_string(),
+            true,
+            vec![receiver_id],
+            UserId::new(),
+        )
+        .unwrap();
+        let group_id = group.id();
+
+        let json = serde_json::to_value(EventReceiverGroupResponse::from(group)).unwrap();
+        assert_eq!(json["id"], json!(group_id.to_string()));
+        assert_eq!(json["event_receiver_ids"], json!([receiver_id.to_string()]));
     }
 
     #[test]
     fn test_group_member_response_serialization() {
         let response = GroupMemberResponse {
-            user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB0".to_string(),
+            user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB0".parse().unwrap(),
             username: "testuser".to_string(),
             email: "test@example.com".to_string(),
             added_at: Utc::now(),
-            added_by: "01HN6Z5K8XQZJQY7WZXR5VQMB1".to_string(),
+            added_by: "01HN6Z5K8XQZJQY7WZXR5VQMB1".parse().unwrap(),
+            expires_at: None,
         };
 
         let serialized = serde_json::to_string(&response);
@@ -898,24 +3957,27 @@ mod tests {
     #[test]
     fn test_group_members_response_serialization() {
         let member1 = GroupMemberResponse {
-            user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB0".to_string(),
+            user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB0".parse().unwrap(),
             username: "user1".to_string(),
             email: "user1@example.com".to_string(),
             added_at: Utc::now(),
-            added_by: "01HN6Z5K8XQZJQY7WZXR5VQMB2".to_string(),
+            added_by: "01HN6Z5K8XQZJQY7WZXR5VQMB2".parse().unwrap(),
+            expires_at: None,
         };
 
         let member2 = GroupMemberResponse {
-            user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB1".to_string(),
+            user_id: "01HN6Z5K8XQZJQY7WZXR5VQMB1".parse().unwrap(),
             username: "user2".to_string(),
             email: "user2@example.com".to_string(),
             added_at: Utc::now(),
-            added_by: "01HN6Z5K8XQZJQY7WZXR5VQMB2".to_string(),
+            added_by: "01HN6Z5K8XQZJQY7WZXR5VQMB2".parse().unwrap(),
+            expires_at: None,
         };
 
         let response = GroupMembersResponse {
-            group_id: "01HN6Z5K8XQZJQY7WZXR5VQMB3".to_string(),
+            group_id: "01HN6Z5K8XQZJQY7WZXR5VQMB3".parse().unwrap(),
             members: vec![member1, member2],
+            as_of: None,
         };
 
         let serialized = serde_json::to_string(&response);
@@ -926,5 +3988,72 @@ mod tests {
         assert!(json_str.contains("members"));
         assert!(json_str.contains("user1"));
         assert!(json_str.contains("user2"));
+        assert!(!json_str.contains("as_of"));
+    }
+
+    #[test]
+    fn test_event_poll_query_params_parse() {
+        let params = EventPollQueryParams::default();
+        assert!(params.cursor().unwrap().is_none());
+        assert!(params.receiver_id().unwrap().is_none());
+
+        let cursor = EventId::new();
+        let params = EventPollQueryParams {
+            cursor: Some(cursor.to_string()),
+            receiver_id: Some("not-a-ulid".to_string()),
+            timeout_seconds: Some(5),
+        };
+        assert_eq!(params.cursor().unwrap(), Some(cursor));
+        assert!(matches!(
+            params.receiver_id(),
+            Err(DomainError::ValidationError { field, .. }) if field == "receiver_id"
+        ));
+
+        let params = EventPollQueryParams {
+            cursor: Some("2025-01-15T10:00:00Z".to_string()),
+            ..Default::default()
+        };
+        assert!(params.cursor().is_err());
+    }
+
+    #[test]
+    fn test_changes_query_params_validation() {
+        let params = ChangesQueryParams {
+            since: None,
+            limit: 50,
+        };
+        assert!(params.validate().is_ok());
+        assert!(params.cursor().unwrap().is_none());
+
+        let params = ChangesQueryParams {
+            since: Some("2025-01-15T10:00:00Z".to_string()),
+            limit: 50,
+        };
+        assert!(params.validate().is_ok());
+        assert!(params.cursor().unwrap().is_some());
+
+        let params = ChangesQueryParams {
+            since: Some("last tuesday".to_string()),
+            limit: 50,
+        };
+        assert!(params.validate().is_err());
+
+        let params = ChangesQueryParams {
+            since: Some("2025-01-15 10:00:00".to_string()),
+            limit: 50,
+        };
+        match params.cursor() {
+            Err(DomainError::ValidationError { field, message }) => {
+                assert_eq!(field, "since");
+                assert_eq!(message.key(), "validation.timestamp_offset");
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+
+        let params = ChangesQueryParams {
+            since: None,
+            limit: 1001,
+        };
+        assert!(params.validate().is_err());
     }
 }
@@ -4,38 +4,460 @@
 // src/api/rest/events.rs
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{FromRef, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::Json,
+    Extension,
 };
+use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{error, info, warn};
 
+use crate::api::field_access::FieldAccess;
+use crate::api::graphql::PersistedQueryStore;
+use crate::api::middleware::api_key::ApiKeyPrincipal;
+use crate::api::middleware::client_ip::ClientIp;
+use crate::api::middleware::deadline::RequestDeadlines;
+use crate::api::middleware::deprecation::DeprecationRegistry;
 use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::about::AboutInfo;
 use crate::api::rest::dtos::{
-    CreateEventReceiverGroupRequest, CreateEventReceiverGroupResponse, CreateEventReceiverRequest,
-    CreateEventReceiverResponse, CreateEventRequest, CreateEventResponse, ErrorResponse,
-    EventReceiverGroupResponse, EventReceiverQueryParams, EventReceiverResponse, EventResponse,
-    PaginatedResponse, PaginationMeta, UpdateEventReceiverGroupRequest, UpdateEventReceiverRequest,
+    AdminEventQueryParams, CloneEventReceiverRequest, CloneEventReceiverResponse,
+    CreateEventQueryParams, CreateEventReceiverGroupRequest, CreateEventReceiverGroupResponse,
+    CreateEventReceiverRequest, CreateEventReceiverResponse, CreateEventRequest,
+    CreateEventResponse, DeleteEventReceiverQueryParams, DryRunResponse, ErrorResponse,
+    EventQueryParams, EventReceiverGroupQueryParams, EventReceiverGroupResponse,
+    EventReceiverQueryParams, EventReceiverResponse, EventResponse, HistoricalQueryParams,
+    PaginatedResponse, PaginationMeta, PayloadMode, UpdateEventReceiverGroupRequest,
+    UpdateEventReceiverRequest,
 };
+use crate::api::rest::fields::{self, Sparse};
+use crate::api::rest::history;
+use crate::api::rest::preferences::RequestPreferences;
+use crate::api::rest::timestamp::format_utc;
+use crate::application::authorization::{AuthorizationService, ProtectedResource, ResourceAction};
 use crate::application::handlers::event_receiver_group_handler::UpdateEventReceiverGroupParams;
-use crate::application::handlers::{EventHandler, EventReceiverGroupHandler, EventReceiverHandler};
-use crate::domain::entities::event::CreateEventParams;
+use crate::application::handlers::{
+    AdminSummaryHandler, ArchivedEventLookup, AuditChainHandler, BulkDeleteHandler,
+    ChangeFeedHandler, CreateEventOutcome, DeliveryFailureHandler, DryRunOutcome,
+    EventAttachmentHandler, EventBatchHandler, EventHandler, EventPollHandler,
+    EventReceiverGroupHandler, EventReceiverHandler, SchemaPreviewHandler, SearchHandler,
+    UserImportHandler, UserPreferencesHandler,
+};
+use crate::auth::api_key::{ApiKeyScope, ApiKeyService};
+use crate::auth::sessions::SessionService;
+use crate::domain::entities::event::{CreateEventParams, Event, EventOrigin};
+use crate::domain::entities::event_publication::{PublishPolicy, PUBLISH_POLICY_HEADER};
+use crate::domain::entities::ingestion_meta::{
+    IngestionContext, IngestionMeta, IngestionSource, PrincipalType,
+};
+use crate::domain::entities::receiver_deletion::{
+    DeletionCache, ReceiverDeletionOutcome, ReceiverDeletionReport,
+};
+use crate::domain::repositories::event_receiver_group_repo::FindEventReceiverGroupCriteria;
+use crate::domain::repositories::event_receiver_repo::FindEventReceiverCriteria;
+use crate::domain::repositories::event_repo::FindEventCriteria;
+use crate::domain::repositories::ingestion_meta_repo::IngestionMetaFilter;
+use crate::domain::repositories::pagination::ListOrder;
 use crate::domain::value_objects::{EventId, EventReceiverGroupId, EventReceiverId, UserId};
+use crate::error::{DomainError, Error, InfrastructureError};
+use crate::i18n::Message;
+use crate::infrastructure::config::{ErrorFormat, GraphQLConfig};
+use crate::infrastructure::{FeatureFlags, JobRunner, MaintenanceMode, ReloadableSettings};
 
 /// Application state containing handlers
 #[derive(Clone)]
 pub struct AppState {
     pub event_handler: EventHandler,
+    /// Batch submission; shares the event handler's checks and quotas
+    pub event_batch_handler: EventBatchHandler,
     pub event_receiver_handler: EventReceiverHandler,
     pub event_receiver_group_handler: EventReceiverGroupHandler,
+    /// Owner, member, and admin checks shared with the GraphQL API
+    pub authorization: AuthorizationService,
+    pub change_feed_handler: ChangeFeedHandler,
+    pub user_preferences_handler: UserPreferencesHandler,
+    pub feature_flags: FeatureFlags,
+    /// Source of the admin overview; `None` disables the summary endpoint
+    pub admin_summary_handler: Option<AdminSummaryHandler>,
+    pub schema_preview_handler: SchemaPreviewHandler,
+    pub bulk_delete_handler: BulkDeleteHandler,
+    /// API key rotation and metadata; `None` disables the key endpoints
+    pub api_key_service: Option<Arc<ApiKeyService>>,
+    /// Long-polling of new events; `None` disables the poll endpoint
+    pub event_poll_handler: Option<EventPollHandler>,
+    /// Playground and introspection settings for the GraphQL routes
+    pub graphql: GraphQLConfig,
+    /// Instance description captured at startup; `None` disables the
+    /// about endpoint
+    pub about: Option<Arc<AboutInfo>>,
+    /// Background job runner; `None` disables the job endpoints
+    pub jobs: Option<Arc<JobRunner>>,
+    /// Log of deliveries given up on; `None` disables the delivery failure
+    /// endpoint
+    pub delivery_failures: Option<DeliveryFailureHandler>,
+    /// Audit record verification; `None` when audit records are not
+    /// persisted
+    pub audit_chain: Option<AuditChainHandler>,
+    /// Full-text search; `None` disables the search endpoint
+    pub search_handler: Option<SearchHandler>,
+    /// Event attachments; `None` disables the attachment endpoints
+    pub attachment_handler: Option<EventAttachmentHandler>,
+    /// User import from CSV; `None` disables the user import endpoints
+    pub user_import_handler: Option<UserImportHandler>,
+    /// Session listing, revocation, and impersonation; `None` disables the
+    /// session endpoints
+    pub session_service: Option<Arc<SessionService>>,
+    /// Shape of error response bodies
+    pub error_format: ErrorFormat,
+    /// Registered deprecations and their usage; its error format should
+    /// match `error_format`
+    pub deprecations: Arc<DeprecationRegistry>,
+    /// Read-only maintenance mode, checked before every write
+    pub maintenance: MaintenanceMode,
+    /// GraphQL queries sent by hash; `None` disables persisted queries and
+    /// their admin endpoints
+    pub persisted_queries: Option<PersistedQueryStore>,
+    /// Settings applied without a restart; `None` disables the config
+    /// reload endpoint
+    pub runtime_settings: Option<ReloadableSettings>,
+    /// Route budgets bounding each request's deadline
+    pub request_deadlines: RequestDeadlines,
+}
+
+impl FromRef<AppState> for UserPreferencesHandler {
+    fn from_ref(state: &AppState) -> Self {
+        state.user_preferences_handler.clone()
+    }
+}
+
+/// Requires `user` to be allowed `action` on `resource`
+///
+/// Unknown resources are `404 NOT_FOUND` and denied callers
+/// `403 FORBIDDEN`.
+pub(crate) async fn authorize(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    action: ResourceAction,
+    resource: ProtectedResource,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    state
+        .authorization
+        .authorize(&user.claims, action, resource)
+        .await
+        .map_err(|e| {
+            let error = match e.status_code() {
+                StatusCode::FORBIDDEN => "forbidden",
+                StatusCode::NOT_FOUND => "not_found",
+                _ => "authorization_failed",
+            };
+            (
+                e.status_code(),
+                Json(ErrorResponse::from_error(error.to_string(), &e)),
+            )
+        })
+}
+
+/// Permission required to see event ingestion metadata
+pub const READ_META_PERMISSION: &str = "event:read_meta";
+
+/// Permission that allows provisioning receivers under the `allow_with_permission` policy
+pub const RECEIVER_CREATE_PERMISSION: &str = "receiver:create";
+
+/// Role required to force the deletion of a receiver
+const FORCE_DELETE_ROLE: &str = "admin";
+
+/// Error code of events named under the prefix reserved for system events
+pub(crate) const RESERVED_EVENT_NAME_CODE: &str = "RESERVED_EVENT_NAME";
+
+/// Error code of events sent to a receiver its auto-disable policy disabled
+pub(crate) const RECEIVER_AUTO_DISABLED_CODE: &str = "RECEIVER_AUTO_DISABLED";
+
+/// Error code of events a receiver's daily quota has no room for
+pub(crate) const QUOTA_EXCEEDED_CODE: &str = "quota_exceeded";
+
+/// Reads the publish policy requested with `X-Publish-Policy`, if any
+pub(crate) fn requested_publish_policy(
+    headers: &HeaderMap,
+) -> Result<Option<PublishPolicy>, DomainError> {
+    headers
+        .get(PUBLISH_POLICY_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|_| DomainError::ValidationError {
+                    field: "X-Publish-Policy".to_string(),
+                    message: Message::new("validation.header_not_text"),
+                })
+                .and_then(str::parse::<PublishPolicy>)
+        })
+        .transpose()
+}
+
+/// Describes who submitted events and from where
+pub(crate) fn ingestion_context(
+    user: &AuthenticatedUser,
+    api_key: Option<&ApiKeyPrincipal>,
+    client_ip: Option<&ClientIp>,
+    headers: &HeaderMap,
+) -> IngestionContext {
+    match api_key {
+        Some(principal) => IngestionContext::new(
+            PrincipalType::ApiKey,
+            principal.key_id.to_string(),
+            IngestionSource::Rest,
+        ),
+        None => IngestionContext::new(PrincipalType::User, user.user_id(), IngestionSource::Rest),
+    }
+    .with_actor(user.actor_id().map(str::to_string))
+    .with_client_ip(client_ip.map(|ClientIp(ip)| ip.to_string()))
+    .with_user_agent(
+        headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+    )
+}
+
+/// Returns true if an API key with `scope` may post to `receiver_id`
+///
+/// Group membership is read on every request, so removing a receiver from
+/// the group stops the key immediately. Scoped keys must name an existing
+/// receiver; they cannot provision one.
+pub(crate) async fn api_key_scope_allows(
+    state: &AppState,
+    scope: ApiKeyScope,
+    receiver_id: Option<EventReceiverId>,
+) -> Result<bool, Error> {
+    Ok(match (scope, receiver_id) {
+        (ApiKeyScope::Unscoped, _) => true,
+        (_, None) => false,
+        (ApiKeyScope::Receiver(scoped), Some(receiver_id)) => scoped == receiver_id,
+        (ApiKeyScope::Group(group_id), Some(receiver_id)) => match state
+            .event_receiver_group_handler
+            .get_group_event_receivers(group_id)
+            .await
+        {
+            Ok(receivers) => receivers.contains(&receiver_id),
+            Err(Error::Domain(DomainError::GroupNotFound)) => false,
+            Err(e) => return Err(e),
+        },
+    })
+}
+
+/// Rejects an event an API key with `scope` may not post
+pub(crate) async fn check_api_key_scope(
+    state: &AppState,
+    scope: ApiKeyScope,
+    receiver_id: Option<EventReceiverId>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    match api_key_scope_allows(state, scope, receiver_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            warn!(scope = ?scope, receiver_id = ?receiver_id, "API key scope denied event");
+            Err((
+                StatusCode::FORBIDDEN,
+                Json(ErrorResponse::new(
+                    "api_key_scope".to_string(),
+                    "API key may not post events to this receiver".to_string(),
+                )),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to load API key scope group: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "event_creation_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Reports what creating the event in `request` would do
+///
+/// Every rejection the real submission would answer with an error is
+/// listed in the `200 OK` response instead, including an unknown receiver
+/// and every schema violation. API key scope is still enforced. A
+/// `receiver_spec` is resolved but never provisioned. Dry runs are
+/// audit-logged with the `dry_run` flag.
+async fn dry_run_event(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    owner_id: UserId,
+    api_key_scope: Option<ApiKeyScope>,
+    request: CreateEventRequest,
+) -> Result<(StatusCode, Json<CreateEventResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let handler = &state.event_handler;
+    let mut event_receiver_id = None;
+    let mut would_provision_receiver = false;
+    let mut transform = None;
+
+    let outcome = match request.validate() {
+        Err(e) => Ok(DryRunOutcome::Rejected(vec![e])),
+        Ok(()) => {
+            if let Some(scope) = api_key_scope {
+                check_api_key_scope(state, scope, request.event_receiver_id).await?;
+            }
+
+            let receiver = match (request.event_receiver_id, &request.receiver_spec) {
+                (Some(_), _) => Ok(None),
+                (None, Some(spec)) => handler
+                    .preview_receiver(
+                        &spec.clone().into_spec(),
+                        owner_id,
+                        user.has_permission(RECEIVER_CREATE_PERMISSION),
+                    )
+                    .await
+                    .map(Some),
+                (None, None) => unreachable!("validated above"),
+            };
+
+            match receiver {
+                Err(Error::Domain(e)) => Ok(DryRunOutcome::Rejected(vec![e])),
+                Err(e) => Err(e),
+                Ok(preview) => {
+                    let receiver = preview.map(|preview| {
+                        would_provision_receiver = preview.would_create;
+                        preview.receiver
+                    });
+                    let receiver_id = match &receiver {
+                        Some(receiver) => receiver.id(),
+                        None => request.event_receiver_id.expect("validated above"),
+                    };
+                    if receiver.is_some() && !would_provision_receiver {
+                        event_receiver_id = Some(receiver_id);
+                    }
+
+                    match handler
+                        .preview_transform(receiver_id, receiver.as_ref(), &request.payload)
+                        .await
+                    {
+                        Err(e) => Err(e),
+                        Ok(preview) => {
+                            transform = preview;
+                            handler
+                                .dry_run_event(
+                                    CreateEventParams {
+                                        name: request.name,
+                                        version: request.version,
+                                        release: request.release,
+                                        platform_id: request.platform_id,
+                                        package: request.package,
+                                        description: request.description,
+                                        payload: request.payload,
+                                        success: request.success,
+                                        receiver_id,
+                                        owner_id,
+                                    },
+                                    receiver,
+                                )
+                                .await
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    let outcome = match outcome {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            error!("Failed to dry-run event: {}", e);
+            return Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "event_creation_failed".to_string(),
+                    &e,
+                )),
+            ));
+        }
+    };
+
+    let audited_receiver = event_receiver_id.or(request.event_receiver_id);
+    handler.record_dry_run(owner_id, audited_receiver, &outcome);
+    info!(
+        receiver_id = ?audited_receiver,
+        would_create = outcome.would_create(),
+        "Event dry run completed"
+    );
+
+    let validation_errors = match &outcome {
+        DryRunOutcome::Rejected(errors) => errors
+            .iter()
+            .map(|e| {
+                let error = match e {
+                    DomainError::ValidationError { .. } => "validation_error",
+                    DomainError::ReservedEventName { .. } => RESERVED_EVENT_NAME_CODE,
+                    DomainError::ReceiverAutoDisabled { .. } => RECEIVER_AUTO_DISABLED_CODE,
+                    DomainError::ReceiverProvisioningDisabled { .. } => {
+                        "receiver_provisioning_disabled"
+                    }
+                    _ => "event_creation_failed",
+                };
+                ErrorResponse::from_domain(error.to_string(), e)
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(CreateEventResponse {
+            data: None,
+            sampled: matches!(outcome, DryRunOutcome::SampledOut),
+            event_receiver_id,
+            publish_status: None,
+            dry_run: Some(DryRunResponse {
+                would_create: outcome.would_create(),
+                validation_errors,
+                would_provision_receiver,
+                transform,
+            }),
+        }),
+    ))
+}
+
+/// Returns true if the caller may see ingestion metadata
+fn can_read_ingestion_meta(user: Option<&AuthenticatedUser>) -> bool {
+    user.is_some_and(|u| u.has_permission(READ_META_PERMISSION))
 }
 
 /// Creates a new event
+///
+/// Returns `202 Accepted` with `sampled: true` when the receiver's sample
+/// rate drops the event; such events are neither stored nor published.
+///
+/// The `X-Publish-Policy` header (`require` or `best-effort`) overrides the
+/// configured policy for a failed Kafka publish. Under `require` the event
+/// is not kept and the response is `503 Service Unavailable` with
+/// `PUBLISH_UNAVAILABLE`; under `best-effort` it is kept with
+/// `publish_status: deferred` and retried from the outbox.
+///
+/// A scoped API key may only post to an existing receiver inside its
+/// scope; anything else is `403 Forbidden` with `api_key_scope`.
+///
+/// Names under the `xzepr.` prefix are reserved for system events and are
+/// `422 Unprocessable Entity` with `RESERVED_EVENT_NAME`. A receiver with
+/// `allowed_event_names` rejects other names with a validation error
+/// listing what it accepts.
+///
+/// A receiver its auto-disable policy disabled rejects every event with
+/// `409 Conflict` and `RECEIVER_AUTO_DISABLED` until it is re-enabled.
+///
+/// With `?dry_run=true` the event runs through the same checks but nothing
+/// is stored, published, or counted; see [`dry_run_event`].
 pub async fn create_event(
     State(state): State<AppState>,
     user: AuthenticatedUser,
+    headers: HeaderMap,
+    client_ip: Option<Extension<ClientIp>>,
+    api_key: Option<Extension<ApiKeyPrincipal>>,
+    Query(options): Query<CreateEventQueryParams>,
     Json(request): Json<CreateEventRequest>,
-) -> Result<Json<CreateEventResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(StatusCode, Json<CreateEventResponse>), (StatusCode, Json<ErrorResponse>)> {
     let user_id_str = user.user_id();
     info!(
         user_id = %user_id_str,
@@ -44,7 +466,7 @@ pub async fn create_event(
     );
 
     // Parse user ID
-    let owner_id = match UserId::parse(user_id_str) {
+    let owner_id = match user_id_str.parse::<UserId>() {
         Ok(id) => id,
         Err(e) => {
             error!("Invalid user ID in JWT token: {}", e);
@@ -58,65 +480,190 @@ pub async fn create_event(
         }
     };
 
+    if options.dry_run {
+        let scope = api_key.map(|Extension(principal)| principal.scope);
+        return dry_run_event(&state, &user, owner_id, scope, request).await;
+    }
+
     // Validate request
     if let Err(e) = request.validate() {
         warn!("Event creation validation failed: {}", e);
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new(
+            Json(ErrorResponse::from_domain(
                 "validation_error".to_string(),
-                e.to_string(),
+                &e,
             )),
         ));
     }
 
-    // Parse event receiver ID
-    let receiver_id = match request.parse_event_receiver_id() {
-        Ok(id) => id,
+    if let Some(Extension(principal)) = &api_key {
+        check_api_key_scope(&state, principal.scope, request.event_receiver_id).await?;
+    }
+
+    // An explicit publish policy overrides the configured default
+    let publish_policy = match requested_publish_policy(&headers) {
+        Ok(policy) => policy,
         Err(e) => {
-            warn!("Invalid event receiver ID: {}", e);
+            warn!("Invalid publish policy: {}", e);
             return Err((
                 StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::with_field(
+                Json(ErrorResponse::from_domain(
                     "validation_error".to_string(),
-                    e.to_string(),
-                    "event_receiver_id".to_string(),
+                    &e,
                 )),
             ));
         }
     };
 
+    // Resolve the receiver, provisioning it from the spec if allowed
+    let (receiver_id, provisioned) = match (request.event_receiver_id, request.receiver_spec) {
+        (Some(receiver_id), _) => (receiver_id, false),
+        (None, Some(spec)) => {
+            match state
+                .event_handler
+                .provision_receiver(
+                    &spec.into_spec(),
+                    owner_id,
+                    user.has_permission(RECEIVER_CREATE_PERMISSION),
+                )
+                .await
+            {
+                Ok(provisioned) => (provisioned.receiver_id, true),
+                Err(Error::Domain(e @ DomainError::ReceiverProvisioningDisabled { .. })) => {
+                    return Err((
+                        StatusCode::UNPROCESSABLE_ENTITY,
+                        Json(ErrorResponse::new(
+                            "receiver_provisioning_disabled".to_string(),
+                            e.to_string(),
+                        )),
+                    ));
+                }
+                Err(e) => {
+                    error!("Failed to resolve event receiver: {}", e);
+                    return Err((
+                        e.status_code(),
+                        Json(ErrorResponse::from_error(
+                            "event_creation_failed".to_string(),
+                            &e,
+                        )),
+                    ));
+                }
+            }
+        }
+        (None, None) => unreachable!("validated above"),
+    };
+    let event_receiver_id = provisioned.then_some(receiver_id);
+
+    // Record who submitted the event and from where
+    let context = ingestion_context(
+        &user,
+        api_key.as_ref().map(|Extension(principal)| principal),
+        client_ip.as_ref().map(|Extension(client_ip)| client_ip),
+        &headers,
+    );
+
     // Create event
     match state
         .event_handler
-        .create_event(CreateEventParams {
-            name: request.name,
-            version: request.version,
-            release: request.release,
-            platform_id: request.platform_id,
-            package: request.package,
-            description: request.description,
-            payload: request.payload,
-            success: request.success,
-            receiver_id,
-            owner_id,
-        })
+        .create_event_with_policy(
+            CreateEventParams {
+                name: request.name,
+                version: request.version,
+                release: request.release,
+                platform_id: request.platform_id,
+                package: request.package,
+                description: request.description,
+                payload: request.payload,
+                success: request.success,
+                receiver_id,
+                owner_id,
+            },
+            context,
+            publish_policy,
+        )
         .await
     {
-        Ok(event_id) => {
+        Ok(CreateEventOutcome::Stored {
+            event_id,
+            publish_status,
+        }) => {
             info!("Event created successfully with ID: {}", event_id);
-            Ok(Json(CreateEventResponse {
-                data: event_id.to_string(),
-            }))
+            Ok((
+                StatusCode::OK,
+                Json(CreateEventResponse {
+                    data: Some(event_id),
+                    sampled: false,
+                    event_receiver_id,
+                    publish_status,
+                    dry_run: None,
+                }),
+            ))
+        }
+        Ok(CreateEventOutcome::SampledOut) => {
+            info!(
+                "Event accepted but sampled out for receiver {}",
+                receiver_id
+            );
+            Ok((
+                StatusCode::ACCEPTED,
+                Json(CreateEventResponse {
+                    data: None,
+                    sampled: true,
+                    event_receiver_id,
+                    publish_status: None,
+                    dry_run: None,
+                }),
+            ))
+        }
+        Err(e @ Error::Domain(DomainError::ReservedEventName { .. })) => {
+            warn!("Event rejected: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::new(
+                    RESERVED_EVENT_NAME_CODE.to_string(),
+                    e.message(),
+                )),
+            ))
+        }
+        Err(e @ Error::Domain(DomainError::ReceiverAutoDisabled { .. })) => {
+            warn!("Event rejected: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::new(
+                    RECEIVER_AUTO_DISABLED_CODE.to_string(),
+                    e.message(),
+                )),
+            ))
+        }
+        Err(e @ Error::Domain(DomainError::QuotaExceeded { .. })) => {
+            warn!("Event rejected: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::new(
+                    QUOTA_EXCEEDED_CODE.to_string(),
+                    e.message(),
+                )),
+            ))
+        }
+        Err(e @ Error::Infrastructure(InfrastructureError::PublishUnavailable { .. })) => {
+            warn!("Event rejected by the require publish policy: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::new(
+                    "PUBLISH_UNAVAILABLE".to_string(),
+                    e.message(),
+                )),
+            ))
         }
         Err(e) => {
             error!("Failed to create event: {}", e);
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new(
+                Json(ErrorResponse::from_error(
                     "event_creation_failed".to_string(),
-                    e.message(),
+                    &e,
                 )),
             ))
         }
@@ -124,14 +671,33 @@ pub async fn create_event(
 }
 
 /// Gets an event by ID
+///
+/// The payload is redacted unless the caller may read it. Ingestion
+/// metadata is included only when the caller has the `event:read_meta`
+/// permission. Events no longer in the database are
+/// looked up in the event archive and returned with `archived: true`.
+/// `fields` limits the response to the listed fields; a selected payload
+/// the caller may not read is still redacted. A payload kept in blob storage
+/// is read back unless `payload=summary` asks for its summary, which is
+/// returned with `payload_truncated: true` and a `payload_url`.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - `fields` names an unknown field
+/// * `404 NOT_FOUND` - `EVENT_ARCHIVED_UNAVAILABLE` when the event was
+///   archived but its segment can no longer be read
 pub async fn get_event(
     State(state): State<AppState>,
+    user: Option<AuthenticatedUser>,
     Path(id_str): Path<String>,
-) -> Result<Json<EventResponse>, (StatusCode, Json<ErrorResponse>)> {
+    Query(query): Query<EventQueryParams>,
+) -> Result<Json<Sparse<EventResponse>>, (StatusCode, Json<ErrorResponse>)> {
     info!("Getting event: {}", id_str);
+    let access = FieldAccess::for_user(user.as_ref());
+    let fields = fields::select::<EventResponse>(query.fields.as_deref())?;
 
     // Parse event ID
-    let event_id = match EventId::parse(&id_str) {
+    let event_id = match id_str.parse::<EventId>() {
         Ok(id) => id,
         Err(_) => {
             warn!("Invalid event ID format: {}", id_str);
@@ -147,32 +713,257 @@ pub async fn get_event(
 
     // Get event
     match state.event_handler.get_event(event_id).await {
-        Ok(Some(event)) => {
+        Ok(Some(mut event)) => {
             info!("Event found: {}", event_id);
-            Ok(Json(EventResponse::from(event)))
+            if query.payload == PayloadMode::Full {
+                load_full_payloads(&state, std::slice::from_mut(&mut event), &access).await?;
+            }
+            let mut response = EventResponse::for_caller(event, &access);
+            match state.event_handler.publish_statuses(&[event_id]).await {
+                Ok(mut statuses) => {
+                    response = response.with_publish_status(statuses.remove(&event_id))
+                }
+                Err(e) => {
+                    error!("Failed to load publish status for {}: {}", event_id, e);
+                }
+            }
+            if can_read_ingestion_meta(user.as_ref()) {
+                match state.event_handler.get_ingestion_meta(event_id).await {
+                    Ok(meta) => response = response.with_meta(meta),
+                    Err(e) => {
+                        error!("Failed to load ingestion metadata for {}: {}", event_id, e);
+                    }
+                }
+            }
+            if let Some(attachments) = &state.attachment_handler {
+                match attachments.list(event_id).await {
+                    Ok(attachments) => response = response.with_attachments(attachments),
+                    Err(e) => {
+                        error!("Failed to load attachments for {}: {}", event_id, e);
+                    }
+                }
+            }
+            Ok(Json(fields.apply(response)))
         }
-        Ok(None) => {
-            info!("Event not found: {}", event_id);
+        Ok(None) => match state.event_handler.find_archived_event(event_id).await {
+            Ok(ArchivedEventLookup::Found(event)) => {
+                info!("Event served from archive: {}", event_id);
+                Ok(Json(fields.apply(
+                    EventResponse::for_caller(*event, &access).archived(),
+                )))
+            }
+            Ok(ArchivedEventLookup::Unavailable { segment }) => {
+                error!(
+                    "Archive segment {} for event {} is unavailable",
+                    segment, event_id
+                );
+                Err((
+                    StatusCode::NOT_FOUND,
+                    Json(ErrorResponse::new(
+                        "EVENT_ARCHIVED_UNAVAILABLE".to_string(),
+                        "Event was archived but its archive segment is unavailable".to_string(),
+                    )),
+                ))
+            }
+            Ok(ArchivedEventLookup::NotArchived) => {
+                info!("Event not found: {}", event_id);
+                Err((
+                    StatusCode::NOT_FOUND,
+                    Json(ErrorResponse::new(
+                        "not_found".to_string(),
+                        "Event not found".to_string(),
+                    )),
+                ))
+            }
+            Err(e) => {
+                error!("Failed to read archived event {}: {}", event_id, e);
+                Err((
+                    e.status_code(),
+                    Json(ErrorResponse::from_error(
+                        "event_retrieval_failed".to_string(),
+                        &e,
+                    )),
+                ))
+            }
+        },
+        Err(e) => {
+            error!("Failed to get event {}: {}", event_id, e);
+            let status = e.status_code();
             Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse::new(
-                    "not_found".to_string(),
-                    "Event not found".to_string(),
+                status,
+                Json(ErrorResponse::from_error(
+                    "event_retrieval_failed".to_string(),
+                    &e,
                 )),
             ))
         }
+    }
+}
+
+/// Lists events with ingestion metadata for administrators
+///
+/// Supports filtering by principal, ingestion source, and client IP.
+/// `origin=system` lists the events xzepr emits itself instead. `fields`
+/// limits each event to the listed fields. Requires the `event:read_meta`
+/// permission.
+pub async fn list_admin_events(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(params): Query<AdminEventQueryParams>,
+) -> Result<Json<PaginatedResponse<Sparse<EventResponse>>>, (StatusCode, Json<ErrorResponse>)> {
+    if !can_read_ingestion_meta(Some(&user)) {
+        warn!(
+            user_id = %user.user_id(),
+            "Admin event list denied: missing event:read_meta"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Missing required permission: event:read_meta".to_string(),
+            )),
+        ));
+    }
+
+    info!(
+        "Listing admin events with limit: {}, offset: {}",
+        params.limit, params.offset
+    );
+
+    // Validate query parameters
+    let filter = match params.validate().and_then(|_| params.to_filter()) {
+        Ok(filter) => filter,
         Err(e) => {
-            error!("Failed to get event {}: {}", event_id, e);
+            warn!("Admin event list validation failed: {}", e);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_domain(
+                    "validation_error".to_string(),
+                    &e,
+                )),
+            ));
+        }
+    };
+    let fields = fields::select::<EventResponse>(params.fields.as_deref())?;
+
+    // System events are never ingested, so they are listed without metadata
+    let events = match params.origin() {
+        Ok(EventOrigin::System) => match params.to_criteria() {
+            Ok(criteria) => list_system_events(&state, criteria).await,
+            Err(e) => Err(e.into()),
+        },
+        _ => list_ingested_events(&state, filter).await,
+    };
+
+    let access = FieldAccess::for_user(Some(&user));
+    match events {
+        Ok((mut events, total)) => {
+            if params.payload == PayloadMode::Full {
+                for (event, _) in &mut events {
+                    load_full_payloads(&state, std::slice::from_mut(event), &access).await?;
+                }
+            }
+            let ids: Vec<EventId> = events.iter().map(|(event, _)| event.id()).collect();
+            let mut statuses = match state.event_handler.publish_statuses(&ids).await {
+                Ok(statuses) => statuses,
+                Err(e) => {
+                    error!("Failed to load publish statuses: {}", e);
+                    HashMap::new()
+                }
+            };
+            let responses: Vec<Sparse<EventResponse>> = events
+                .into_iter()
+                .map(|(event, meta)| {
+                    let publish_status = statuses.remove(&event.id());
+                    fields.apply(
+                        EventResponse::for_caller(event, &access)
+                            .with_meta(meta)
+                            .with_publish_status(publish_status),
+                    )
+                })
+                .collect();
+
+            let pagination = PaginationMeta::new(params.limit, params.offset, total);
+
+            Ok(Json(PaginatedResponse {
+                data: responses,
+                pagination,
+            }))
+        }
+        Err(e) => {
+            error!("Failed to list admin events: {}", e);
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new(
+                Json(ErrorResponse::from_error("list_failed".to_string(), &e)),
+            ))
+        }
+    }
+}
+
+/// An admin event page with each event's ingestion metadata, if any, and
+/// the number of events matching the query
+type AdminEventPage = (Vec<(Event, Option<IngestionMeta>)>, usize);
+
+/// Lists a page of system events and counts all that match
+async fn list_system_events(
+    state: &AppState,
+    criteria: FindEventCriteria,
+) -> crate::error::Result<AdminEventPage> {
+    let total = state
+        .event_handler
+        .count_by_criteria(criteria.clone())
+        .await?;
+    let events = state.event_handler.find_by_criteria(criteria).await?;
+    Ok((
+        events.into_iter().map(|event| (event, None)).collect(),
+        total,
+    ))
+}
+
+/// Lists a page of events by ingestion metadata and counts all that match
+async fn list_ingested_events(
+    state: &AppState,
+    filter: IngestionMetaFilter,
+) -> crate::error::Result<AdminEventPage> {
+    let total = state
+        .event_handler
+        .count_events_by_ingestion(&filter)
+        .await?;
+    let events = state.event_handler.list_events_by_ingestion(filter).await?;
+    Ok((
+        events
+            .into_iter()
+            .map(|(event, meta)| (event, Some(meta)))
+            .collect(),
+        total,
+    ))
+}
+
+/// Reads back the offloaded payloads of the events `access` may read
+///
+/// Redacted payloads are left as summaries, which only cost their size.
+pub(crate) async fn load_full_payloads(
+    state: &AppState,
+    events: &mut [Event],
+    access: &FieldAccess,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    for event in events {
+        if !access.can_read_payload(event.owner_id()) {
+            continue;
+        }
+        if let Err(e) = state.event_handler.load_full_payload(event).await {
+            error!("Failed to read offloaded payload of {}: {}", event.id(), e);
+            return Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
                     "event_retrieval_failed".to_string(),
-                    e.message(),
+                    &e,
                 )),
-            ))
+            ));
         }
     }
+    Ok(())
 }
 
 /// Creates a new event receiver
@@ -189,7 +980,7 @@ pub async fn create_event_receiver(
     );
 
     // Parse user ID
-    let owner_id = match UserId::parse(user_id_str) {
+    let owner_id = match user_id_str.parse::<UserId>() {
         Ok(id) => id,
         Err(e) => {
             error!("Invalid user ID in JWT token: {}", e);
@@ -208,16 +999,17 @@ pub async fn create_event_receiver(
         warn!("Event receiver creation validation failed: {}", e);
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new(
+            Json(ErrorResponse::from_domain(
                 "validation_error".to_string(),
-                e.to_string(),
+                &e,
             )),
         ));
     }
 
-    // Create event receiver
-    match state
-        .event_receiver_handler
+    // Create event receiver, then apply sampling, name constraints, the
+    // auto-disable policy, and the payload transform if requested
+    let handler = &state.event_receiver_handler;
+    let result = match handler
         .create_event_receiver(
             request.name,
             request.receiver_type,
@@ -228,38 +1020,190 @@ pub async fn create_event_receiver(
         )
         .await
     {
+        Ok(receiver_id) => match request.sample_rate {
+            Some(sample_rate) => handler
+                .update_sample_rate(receiver_id, Some(sample_rate))
+                .await
+                .map(|()| receiver_id),
+            None => Ok(receiver_id),
+        },
+        Err(e) => Err(e),
+    };
+    let result = match (result, request.allowed_event_names) {
+        (Ok(receiver_id), Some(allowed)) => handler
+            .update_allowed_event_names(receiver_id, Some(allowed))
+            .await
+            .map(|()| receiver_id),
+        (result, _) => result,
+    };
+    let result = match (result, request.auto_disable) {
+        (Ok(receiver_id), Some(policy)) => handler
+            .update_auto_disable_policy(receiver_id, Some(policy))
+            .await
+            .map(|()| receiver_id),
+        (result, _) => result,
+    };
+    let result = match (result, request.transform) {
+        (Ok(receiver_id), Some(transform)) => handler
+            .update_transform(receiver_id, Some(transform))
+            .await
+            .map(|()| receiver_id),
+        (result, _) => result,
+    };
+
+    match result {
         Ok(receiver_id) => {
             info!(
                 "Event receiver created successfully with ID: {}",
                 receiver_id
             );
-            Ok(Json(CreateEventReceiverResponse {
-                data: receiver_id.to_string(),
-            }))
+            Ok(Json(CreateEventReceiverResponse { data: receiver_id }))
         }
         Err(e) => {
             error!("Failed to create event receiver: {}", e);
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new(
+                Json(ErrorResponse::from_error(
                     "receiver_creation_failed".to_string(),
-                    e.message(),
+                    &e,
                 )),
             ))
         }
     }
 }
 
+/// Creates a new event receiver from the configuration of another
+///
+/// The caller needs read access to the source, and copying its schema
+/// additionally requires being allowed to read the schema. Group
+/// memberships and secrets are never copied; the manifest lists what was.
+pub async fn clone_event_receiver(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+    Json(request): Json<CloneEventReceiverRequest>,
+) -> Result<Json<CloneEventReceiverResponse>, (StatusCode, Json<ErrorResponse>)> {
+    info!(
+        user_id = %user.user_id(),
+        source_id = %id_str,
+        receiver_name = %request.name,
+        "Cloning event receiver"
+    );
+
+    let owner_id = match user.user_id().parse::<UserId>() {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Invalid user ID in JWT token: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(
+                    "internal_error".to_string(),
+                    "Invalid user ID in authentication token".to_string(),
+                )),
+            ));
+        }
+    };
+
+    let source_id = match id_str.parse::<EventReceiverId>() {
+        Ok(id) => id,
+        Err(_) => {
+            warn!("Invalid event receiver ID format: {}", id_str);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(
+                    "invalid_id".to_string(),
+                    "Invalid event receiver ID format".to_string(),
+                )),
+            ));
+        }
+    };
+
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Read,
+        ProtectedResource::Receiver(source_id),
+    )
+    .await?;
+
+    if let Err(e) = request.validate() {
+        warn!("Event receiver clone validation failed: {}", e);
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        ));
+    }
+
+    let handler = &state.event_receiver_handler;
+    let clone_failed = |e: crate::error::Error| {
+        error!("Failed to clone event receiver {}: {}", source_id, e);
+        (
+            e.status_code(),
+            Json(ErrorResponse::from_error(
+                "receiver_clone_failed".to_string(),
+                &e,
+            )),
+        )
+    };
+    let source = handler
+        .get_event_receiver_or_error(source_id)
+        .await
+        .map_err(clone_failed)?;
+
+    let access = FieldAccess::for_user(Some(&user));
+    let (overrides, options) = request.into_parts();
+    if options.schema && source.has_own_schema() && !access.can_read_schema(source.owner_id()) {
+        warn!(
+            user_id = %user.user_id(),
+            source_id = %source_id,
+            "Caller may not read the schema it asked to clone"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Copying the schema requires permission to read it".to_string(),
+            )),
+        ));
+    }
+
+    let (receiver, manifest) = handler
+        .clone_event_receiver(&source, overrides, options, owner_id)
+        .await
+        .map_err(clone_failed)?;
+
+    info!(
+        source_id = %source_id,
+        receiver_id = %receiver.id(),
+        "Event receiver cloned successfully"
+    );
+    Ok(Json(CloneEventReceiverResponse {
+        data: EventReceiverResponse::for_caller(receiver, &access),
+        manifest,
+    }))
+}
+
 /// Gets an event receiver by ID
+///
+/// The schema is included only when the caller may read it, even if
+/// `fields` selects it. With `as_of`, the receiver is returned as it was
+/// at that instant and the response carries the instant in `as_of`.
 pub async fn get_event_receiver(
     State(state): State<AppState>,
+    user: Option<AuthenticatedUser>,
     Path(id_str): Path<String>,
-) -> Result<Json<EventReceiverResponse>, (StatusCode, Json<ErrorResponse>)> {
+    Query(query): Query<HistoricalQueryParams>,
+) -> Result<Json<Sparse<EventReceiverResponse>>, (StatusCode, Json<ErrorResponse>)> {
     info!("Getting event receiver: {}", id_str);
+    let fields = fields::select::<EventReceiverResponse>(query.fields.as_deref())?;
+    let as_of = history::parse_as_of(query.as_of.as_deref())?;
 
     // Parse receiver ID
-    let receiver_id = match EventReceiverId::parse(&id_str) {
+    let receiver_id = match id_str.parse::<EventReceiverId>() {
         Ok(id) => id,
         Err(_) => {
             warn!("Invalid event receiver ID format: {}", id_str);
@@ -273,6 +1217,18 @@ pub async fn get_event_receiver(
         }
     };
 
+    if let Some(instant) = as_of {
+        let receiver_history = history::require_history(state.event_receiver_handler.history())?;
+        return history::receiver_as_of(
+            receiver_history,
+            user.as_ref(),
+            receiver_id,
+            instant,
+            &fields,
+        )
+        .await;
+    }
+
     // Get event receiver
     match state
         .event_receiver_handler
@@ -281,7 +1237,51 @@ pub async fn get_event_receiver(
     {
         Ok(Some(receiver)) => {
             info!("Event receiver found: {}", receiver_id);
-            Ok(Json(EventReceiverResponse::from(receiver)))
+            let schema_source = match state.event_receiver_handler.resolve_schema(&receiver).await {
+                Ok(resolved) => resolved.source(),
+                Err(e) => {
+                    error!("Failed to resolve schema for {}: {}", receiver_id, e);
+                    let status = e.status_code();
+                    return Err((
+                        status,
+                        Json(ErrorResponse::from_error(
+                            "receiver_retrieval_failed".to_string(),
+                            &e,
+                        )),
+                    ));
+                }
+            };
+            let access = FieldAccess::for_user(user.as_ref());
+            let (activity, heartbeat) = if access.can_read_receiver_meta() {
+                let handler = &state.event_receiver_handler;
+                let ids = [receiver_id];
+                match tokio::try_join!(handler.find_activity(&ids), handler.find_heartbeats(&ids)) {
+                    Ok((mut activity, mut heartbeats)) => (
+                        activity.remove(&receiver_id),
+                        heartbeats.remove(&receiver_id),
+                    ),
+                    Err(e) => {
+                        error!("Failed to load activity for {}: {}", receiver_id, e);
+                        return Err((
+                            e.status_code(),
+                            Json(ErrorResponse::from_error(
+                                "receiver_retrieval_failed".to_string(),
+                                &e,
+                            )),
+                        ));
+                    }
+                }
+            } else {
+                (None, None)
+            };
+            Ok(Json(
+                fields.apply(
+                    EventReceiverResponse::for_caller(receiver, &access)
+                        .with_schema_source(schema_source)
+                        .with_activity(activity.as_ref(), &access)
+                        .with_heartbeat(heartbeat, &access),
+                ),
+            ))
         }
         Ok(None) => {
             info!("Event receiver not found: {}", receiver_id);
@@ -298,9 +1298,9 @@ pub async fn get_event_receiver(
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new(
+                Json(ErrorResponse::from_error(
                     "receiver_retrieval_failed".to_string(),
-                    e.message(),
+                    &e,
                 )),
             ))
         }
@@ -308,52 +1308,115 @@ pub async fn get_event_receiver(
 }
 
 /// Lists event receivers with optional filtering and pagination
+///
+/// Without an explicit `limit`, the caller's `default_page_size`
+/// preference applies. Schemas are included only when the caller may read
+/// them. `fields` limits each receiver to the listed fields.
 pub async fn list_event_receivers(
     State(state): State<AppState>,
+    user: Option<AuthenticatedUser>,
+    preferences: RequestPreferences,
     Query(params): Query<EventReceiverQueryParams>,
-) -> Result<Json<PaginatedResponse<EventReceiverResponse>>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<PaginatedResponse<Sparse<EventReceiverResponse>>>, (StatusCode, Json<ErrorResponse>)>
+{
+    let limit = params.effective_limit(preferences.preferences());
     info!(
         "Listing event receivers with limit: {}, offset: {}",
-        params.limit, params.offset
+        limit, params.offset
     );
 
     // Validate query parameters
-    if let Err(e) = params.validate() {
-        warn!("Event receiver list validation failed: {}", e);
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new(
-                "validation_error".to_string(),
-                e.to_string(),
-            )),
-        ));
-    }
+    let order = match params.validate().and_then(|_| params.list_order()) {
+        Ok(order) => order,
+        Err(e) => {
+            warn!("Event receiver list validation failed: {}", e);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_domain(
+                    "validation_error".to_string(),
+                    &e,
+                )),
+            ));
+        }
+    };
+    let fields = fields::select::<EventReceiverResponse>(params.fields.as_deref())?;
+
+    // Filtering and sorting go through criteria; plain listing keeps the cheap path
+    let criteria = (params.stale_since.is_some() || order != ListOrder::default()).then(|| {
+        let criteria = FindEventReceiverCriteria::new().with_order(order);
+        match params.stale_since {
+            Some(since) => criteria.with_stale_since(since),
+            None => criteria,
+        }
+    });
 
     // Get total count for pagination
-    let total = match state.event_receiver_handler.count_event_receivers().await {
+    let total = match &criteria {
+        Some(criteria) => {
+            state
+                .event_receiver_handler
+                .count_by_criteria(criteria.clone())
+                .await
+        }
+        None => state.event_receiver_handler.count_event_receivers().await,
+    };
+    let total = match total {
         Ok(count) => count,
         Err(e) => {
             error!("Failed to count event receivers: {}", e);
             return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new("count_failed".to_string(), e.message())),
+                Json(ErrorResponse::from_error("count_failed".to_string(), &e)),
             ));
         }
     };
 
     // List event receivers
-    match state
-        .event_receiver_handler
-        .list_event_receivers(params.limit, params.offset)
-        .await
-    {
+    let receivers = match criteria {
+        Some(criteria) => {
+            state
+                .event_receiver_handler
+                .find_by_criteria(criteria.with_limit(limit).with_offset(params.offset))
+                .await
+        }
+        None => {
+            state
+                .event_receiver_handler
+                .list_event_receivers(limit, params.offset)
+                .await
+        }
+    };
+
+    match receivers {
         Ok(receivers) => {
-            let responses: Vec<EventReceiverResponse> = receivers
+            let access = FieldAccess::for_user(user.as_ref());
+            let activity = if access.can_read_receiver_meta() {
+                let ids: Vec<EventReceiverId> = receivers.iter().map(|r| r.id()).collect();
+                match state.event_receiver_handler.find_activity(&ids).await {
+                    Ok(activity) => activity,
+                    Err(e) => {
+                        error!("Failed to load receiver activity: {}", e);
+                        return Err((
+                            e.status_code(),
+                            Json(ErrorResponse::from_error("list_failed".to_string(), &e)),
+                        ));
+                    }
+                }
+            } else {
+                HashMap::new()
+            };
+            let responses: Vec<Sparse<EventReceiverResponse>> = receivers
                 .into_iter()
-                .map(EventReceiverResponse::from)
+                .map(|receiver| {
+                    let receiver_activity = activity.get(&receiver.id());
+                    fields.apply(
+                        EventReceiverResponse::for_caller(receiver, &access)
+                            .with_activity(receiver_activity, &access),
+                    )
+                })
                 .collect();
 
-            let pagination = PaginationMeta::new(params.limit, params.offset, total);
+            let pagination = PaginationMeta::new(limit, params.offset, total);
 
             Ok(Json(PaginatedResponse {
                 data: responses,
@@ -365,22 +1428,26 @@ pub async fn list_event_receivers(
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new("list_failed".to_string(), e.message())),
+                Json(ErrorResponse::from_error("list_failed".to_string(), &e)),
             ))
         }
     }
 }
 
 /// Updates an event receiver
+///
+/// Only the receiver's owner, an admin, or a caller with the
+/// `event_receiver:update` permission may update it.
 pub async fn update_event_receiver(
     State(state): State<AppState>,
+    user: AuthenticatedUser,
     Path(id_str): Path<String>,
     Json(request): Json<UpdateEventReceiverRequest>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
     info!("Updating event receiver: {}", id_str);
 
     // Parse receiver ID
-    let receiver_id = match EventReceiverId::parse(&id_str) {
+    let receiver_id = match id_str.parse::<EventReceiverId>() {
         Ok(id) => id,
         Err(_) => {
             warn!("Invalid event receiver ID format: {}", id_str);
@@ -394,21 +1461,31 @@ pub async fn update_event_receiver(
         }
     };
 
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Update,
+        ProtectedResource::Receiver(receiver_id),
+    )
+    .await?;
+
     // Validate request
     if let Err(e) = request.validate() {
         warn!("Event receiver update validation failed: {}", e);
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new(
+            Json(ErrorResponse::from_domain(
                 "validation_error".to_string(),
-                e.to_string(),
+                &e,
             )),
         ));
     }
 
-    // Update event receiver
-    match state
-        .event_receiver_handler
+    // Update event receiver, then its sampling, name constraint,
+    // auto-disable policy, and payload transform if requested;
+    // `enabled: true` re-enables it last
+    let handler = &state.event_receiver_handler;
+    let result = match handler
         .update_event_receiver(
             receiver_id,
             request.name,
@@ -419,6 +1496,42 @@ pub async fn update_event_receiver(
         )
         .await
     {
+        Ok(()) => match request.sample_rate {
+            Some(sample_rate) => {
+                handler
+                    .update_sample_rate(receiver_id, Some(sample_rate))
+                    .await
+            }
+            None => Ok(()),
+        },
+        Err(e) => Err(e),
+    };
+    let result = match (result, request.allowed_event_names) {
+        (Ok(()), Some(allowed)) => {
+            handler
+                .update_allowed_event_names(receiver_id, Some(allowed))
+                .await
+        }
+        (result, _) => result,
+    };
+    let result = match (result, request.auto_disable) {
+        (Ok(()), Some(policy)) => {
+            handler
+                .update_auto_disable_policy(receiver_id, Some(policy))
+                .await
+        }
+        (result, _) => result,
+    };
+    let result = match (result, request.transform) {
+        (Ok(()), Some(transform)) => handler.update_transform(receiver_id, Some(transform)).await,
+        (result, _) => result,
+    };
+    let result = match (result, request.enabled) {
+        (Ok(()), Some(true)) => handler.enable_event_receiver(receiver_id).await,
+        (result, _) => result,
+    };
+
+    match result {
         Ok(()) => {
             info!("Event receiver updated successfully: {}", receiver_id);
             Ok(StatusCode::NO_CONTENT)
@@ -428,21 +1541,34 @@ pub async fn update_event_receiver(
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new("update_failed".to_string(), e.message())),
+                Json(ErrorResponse::from_error("update_failed".to_string(), &e)),
             ))
         }
     }
 }
 
 /// Deletes an event receiver
+///
+/// Authorized like updates, with the `event_receiver:delete` permission.
+/// Recent events or active scoped API keys block the deletion with a
+/// `409` whose `details` list the blockers, unless an administrator sets
+/// `force=true`. The response lists every side effect of the deletion.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - `force` set by a caller who is not an administrator
+/// * `404 NOT_FOUND` - No such receiver
+/// * `409 CONFLICT` - The deletion is blocked
 pub async fn delete_event_receiver(
     State(state): State<AppState>,
+    user: AuthenticatedUser,
     Path(id_str): Path<String>,
-) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    Query(params): Query<DeleteEventReceiverQueryParams>,
+) -> Result<Json<ReceiverDeletionReport>, (StatusCode, Json<ErrorResponse>)> {
     info!("Deleting event receiver: {}", id_str);
 
     // Parse receiver ID
-    let receiver_id = match EventReceiverId::parse(&id_str) {
+    let receiver_id = match id_str.parse::<EventReceiverId>() {
         Ok(id) => id,
         Err(_) => {
             warn!("Invalid event receiver ID format: {}", id_str);
@@ -456,33 +1582,79 @@ pub async fn delete_event_receiver(
         }
     };
 
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Delete,
+        ProtectedResource::Receiver(receiver_id),
+    )
+    .await?;
+
+    if params.force && !user.has_role(FORCE_DELETE_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            receiver_id = %receiver_id,
+            "Forced receiver deletion denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required to force a deletion".to_string(),
+            )),
+        ));
+    }
+
     // Delete event receiver
     match state
         .event_receiver_handler
-        .delete_event_receiver(receiver_id)
+        .delete_event_receiver(receiver_id, params.force, user.user_id())
         .await
     {
-        Ok(()) => {
+        Ok(ReceiverDeletionOutcome::Deleted(mut report)) => {
+            if state.authorization.forget_receiver(receiver_id).await {
+                report
+                    .invalidated_caches
+                    .push(DeletionCache::AuthorizationDecisions);
+            }
             info!("Event receiver deleted successfully: {}", receiver_id);
-            Ok(StatusCode::NO_CONTENT)
+            Ok(Json(report))
+        }
+        Ok(ReceiverDeletionOutcome::Blocked(blockers)) => {
+            info!("Event receiver deletion blocked: {}", receiver_id);
+            Err((
+                StatusCode::CONFLICT,
+                Json(
+                    ErrorResponse::new(
+                        "receiver_delete_blocked".to_string(),
+                        "Receiver has recent events or active API keys; an administrator may force the deletion".to_string(),
+                    )
+                    .with_details(serde_json::json!({ "blockers": blockers })),
+                ),
+            ))
         }
         Err(e) => {
             error!("Failed to delete event receiver {}: {}", receiver_id, e);
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new("delete_failed".to_string(), e.message())),
+                Json(ErrorResponse::from_error("delete_failed".to_string(), &e)),
             ))
         }
     }
 }
 
 /// Creates a new event receiver group
+///
+/// Responds `201 CREATED` once the group is saved. A `group.created` system
+/// event that cannot be built is logged and counted without failing the
+/// request.
 pub async fn create_event_receiver_group(
     State(state): State<AppState>,
     user: AuthenticatedUser,
     Json(request): Json<CreateEventReceiverGroupRequest>,
-) -> Result<Json<CreateEventReceiverGroupResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(StatusCode, Json<CreateEventReceiverGroupResponse>), (StatusCode, Json<ErrorResponse>)>
+{
     let user_id_str = user.user_id();
     info!(
         user_id = %user_id_str,
@@ -491,7 +1663,7 @@ pub async fn create_event_receiver_group(
     );
 
     // Parse user ID
-    let owner_id = match UserId::parse(user_id_str) {
+    let owner_id = match user_id_str.parse::<UserId>() {
         Ok(id) => id,
         Err(e) => {
             error!("Invalid user ID in JWT token: {}", e);
@@ -510,28 +1682,14 @@ pub async fn create_event_receiver_group(
         warn!("Event receiver group creation validation failed: {}", e);
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new(
+            Json(ErrorResponse::from_domain(
                 "validation_error".to_string(),
-                e.to_string(),
+                &e,
             )),
         ));
     }
 
-    // Parse event receiver IDs
-    let receiver_ids = match request.parse_event_receiver_ids() {
-        Ok(ids) => ids,
-        Err(e) => {
-            warn!("Invalid event receiver IDs: {}", e);
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::with_field(
-                    "validation_error".to_string(),
-                    e.to_string(),
-                    "event_receiver_ids".to_string(),
-                )),
-            ));
-        }
-    };
+    let receiver_ids = request.event_receiver_ids.clone();
 
     // Create event receiver group
     match state
@@ -543,6 +1701,9 @@ pub async fn create_event_receiver_group(
             request.description,
             request.enabled,
             receiver_ids,
+            request.optional_receiver_ids,
+            request.default_schema,
+            request.dedicated_topic,
             owner_id,
         )
         .await
@@ -552,33 +1713,115 @@ pub async fn create_event_receiver_group(
                 "Event receiver group created successfully with ID: {}",
                 group_id
             );
-            Ok(Json(CreateEventReceiverGroupResponse {
-                data: group_id.to_string(),
-            }))
+            Ok((
+                StatusCode::CREATED,
+                Json(CreateEventReceiverGroupResponse { data: group_id }),
+            ))
         }
         Err(e) => {
             error!("Failed to create event receiver group: {}", e);
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new(
+                Json(ErrorResponse::from_error(
                     "group_creation_failed".to_string(),
-                    e.message(),
+                    &e,
                 )),
             ))
         }
     }
 }
 
+/// Lists event receiver groups with sorting and pagination
+///
+/// Without an explicit `limit`, the caller's `default_page_size`
+/// preference applies. `fields` limits each group to the listed fields.
+pub async fn list_event_receiver_groups(
+    State(state): State<AppState>,
+    preferences: RequestPreferences,
+    Query(params): Query<EventReceiverGroupQueryParams>,
+) -> Result<
+    Json<PaginatedResponse<Sparse<EventReceiverGroupResponse>>>,
+    (StatusCode, Json<ErrorResponse>),
+> {
+    let limit = params.effective_limit(preferences.preferences());
+    info!(
+        "Listing event receiver groups with limit: {}, offset: {}",
+        limit, params.offset
+    );
+
+    // Validate query parameters
+    let order = match params.validate().and_then(|_| params.list_order()) {
+        Ok(order) => order,
+        Err(e) => {
+            warn!("Event receiver group list validation failed: {}", e);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_domain(
+                    "validation_error".to_string(),
+                    &e,
+                )),
+            ));
+        }
+    };
+    let fields = fields::select::<EventReceiverGroupResponse>(params.fields.as_deref())?;
+    let criteria = FindEventReceiverGroupCriteria::new().with_order(order);
+
+    // Get total count for pagination
+    let total = match state
+        .event_receiver_group_handler
+        .count_by_criteria(criteria.clone())
+        .await
+    {
+        Ok(count) => count,
+        Err(e) => {
+            error!("Failed to count event receiver groups: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::from_error("count_failed".to_string(), &e)),
+            ));
+        }
+    };
+
+    match state
+        .event_receiver_group_handler
+        .find_by_criteria(criteria.with_limit(limit).with_offset(params.offset))
+        .await
+    {
+        Ok(groups) => Ok(Json(PaginatedResponse {
+            data: groups
+                .into_iter()
+                .map(|group| fields.apply(EventReceiverGroupResponse::from(group)))
+                .collect(),
+            pagination: PaginationMeta::new(limit, params.offset, total),
+        })),
+        Err(e) => {
+            error!("Failed to list event receiver groups: {}", e);
+            let status = e.status_code();
+            Err((
+                status,
+                Json(ErrorResponse::from_error("list_failed".to_string(), &e)),
+            ))
+        }
+    }
+}
+
 /// Gets an event receiver group by ID
+///
+/// `fields` limits the response to the listed fields. With `as_of`, the
+/// group is returned as it was at that instant and the response carries
+/// the instant in `as_of`.
 pub async fn get_event_receiver_group(
     State(state): State<AppState>,
     Path(id_str): Path<String>,
-) -> Result<Json<EventReceiverGroupResponse>, (StatusCode, Json<ErrorResponse>)> {
+    Query(query): Query<HistoricalQueryParams>,
+) -> Result<Json<Sparse<EventReceiverGroupResponse>>, (StatusCode, Json<ErrorResponse>)> {
     info!("Getting event receiver group: {}", id_str);
+    let fields = fields::select::<EventReceiverGroupResponse>(query.fields.as_deref())?;
+    let as_of = history::parse_as_of(query.as_of.as_deref())?;
 
     // Parse group ID
-    let group_id = match EventReceiverGroupId::parse(&id_str) {
+    let group_id = match id_str.parse::<EventReceiverGroupId>() {
         Ok(id) => id,
         Err(_) => {
             warn!("Invalid event receiver group ID format: {}", id_str);
@@ -592,6 +1835,11 @@ pub async fn get_event_receiver_group(
         }
     };
 
+    if let Some(instant) = as_of {
+        let group_history = history::require_history(state.event_receiver_group_handler.history())?;
+        return history::group_response_as_of(group_history, group_id, instant, &fields).await;
+    }
+
     // Get event receiver group
     match state
         .event_receiver_group_handler
@@ -600,7 +1848,7 @@ pub async fn get_event_receiver_group(
     {
         Ok(Some(group)) => {
             info!("Event receiver group found: {}", group_id);
-            Ok(Json(EventReceiverGroupResponse::from(group)))
+            Ok(Json(fields.apply(EventReceiverGroupResponse::from(group))))
         }
         Ok(None) => {
             info!("Event receiver group not found: {}", group_id);
@@ -617,9 +1865,9 @@ pub async fn get_event_receiver_group(
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new(
+                Json(ErrorResponse::from_error(
                     "group_retrieval_failed".to_string(),
-                    e.message(),
+                    &e,
                 )),
             ))
         }
@@ -627,15 +1875,19 @@ pub async fn get_event_receiver_group(
 }
 
 /// Updates an event receiver group
+///
+/// Only the group's owner, an admin, or a caller with the
+/// `event_receiver_group:update` permission may update it.
 pub async fn update_event_receiver_group(
     State(state): State<AppState>,
+    user: AuthenticatedUser,
     Path(id_str): Path<String>,
     Json(request): Json<UpdateEventReceiverGroupRequest>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
     info!("Updating event receiver group: {}", id_str);
 
     // Parse group ID
-    let group_id = match EventReceiverGroupId::parse(&id_str) {
+    let group_id = match id_str.parse::<EventReceiverGroupId>() {
         Ok(id) => id,
         Err(_) => {
             warn!("Invalid event receiver group ID format: {}", id_str);
@@ -649,33 +1901,27 @@ pub async fn update_event_receiver_group(
         }
     };
 
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Update,
+        ProtectedResource::Group(group_id),
+    )
+    .await?;
+
     // Validate request
     if let Err(e) = request.validate() {
         warn!("Event receiver group update validation failed: {}", e);
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ErrorResponse::new(
+            Json(ErrorResponse::from_domain(
                 "validation_error".to_string(),
-                e.to_string(),
+                &e,
             )),
         ));
     }
 
-    // Parse event receiver IDs if provided
-    let receiver_ids = match request.parse_event_receiver_ids() {
-        Ok(ids) => ids,
-        Err(e) => {
-            warn!("Invalid event receiver IDs: {}", e);
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::with_field(
-                    "validation_error".to_string(),
-                    e.to_string(),
-                    "event_receiver_ids".to_string(),
-                )),
-            ));
-        }
-    };
+    let receiver_ids = request.event_receiver_ids.clone();
 
     // Update event receiver group
     match state
@@ -689,6 +1935,9 @@ pub async fn update_event_receiver_group(
                 description: request.description,
                 enabled: request.enabled,
                 event_receiver_ids: receiver_ids,
+                optional_receiver_ids: request.optional_receiver_ids,
+                default_schema: request.default_schema,
+                dedicated_topic: request.dedicated_topic,
             },
         )
         .await
@@ -702,21 +1951,25 @@ pub async fn update_event_receiver_group(
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new("update_failed".to_string(), e.message())),
+                Json(ErrorResponse::from_error("update_failed".to_string(), &e)),
             ))
         }
     }
 }
 
 /// Deletes an event receiver group
+///
+/// Authorized like updates, with the `event_receiver_group:delete`
+/// permission.
 pub async fn delete_event_receiver_group(
     State(state): State<AppState>,
+    user: AuthenticatedUser,
     Path(id_str): Path<String>,
 ) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
     info!("Deleting event receiver group: {}", id_str);
 
     // Parse group ID
-    let group_id = match EventReceiverGroupId::parse(&id_str) {
+    let group_id = match id_str.parse::<EventReceiverGroupId>() {
         Ok(id) => id,
         Err(_) => {
             warn!("Invalid event receiver group ID format: {}", id_str);
@@ -730,6 +1983,14 @@ pub async fn delete_event_receiver_group(
         }
     };
 
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Delete,
+        ProtectedResource::Group(group_id),
+    )
+    .await?;
+
     // Delete event receiver group
     match state
         .event_receiver_group_handler
@@ -745,7 +2006,64 @@ pub async fn delete_event_receiver_group(
             let status = e.status_code();
             Err((
                 status,
-                Json(ErrorResponse::new("delete_failed".to_string(), e.message())),
+                Json(ErrorResponse::from_error("delete_failed".to_string(), &e)),
+            ))
+        }
+    }
+}
+
+/// Makes a group the default schema group of one of its receivers
+///
+/// The receiver inherits this group's default schema when its enabled
+/// groups define conflicting ones; any previous default is replaced.
+/// Authorized like group updates.
+pub async fn set_default_schema_group(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((group_id_str, receiver_id_str)): Path<(String, String)>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let group_id = group_id_str.parse::<EventReceiverGroupId>().map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid event receiver group ID format".to_string(),
+            )),
+        )
+    })?;
+    let receiver_id = receiver_id_str.parse::<EventReceiverId>().map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid event receiver ID format".to_string(),
+            )),
+        )
+    })?;
+
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Update,
+        ProtectedResource::Group(group_id),
+    )
+    .await?;
+
+    match state
+        .event_receiver_group_handler
+        .set_default_schema_group(group_id, receiver_id)
+        .await
+    {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            error!(
+                "Failed to set default schema group {} for receiver {}: {}",
+                group_id, receiver_id, e
+            );
+            let status = e.status_code();
+            Err((
+                status,
+                Json(ErrorResponse::from_error("update_failed".to_string(), &e)),
             ))
         }
     }
@@ -756,7 +2074,7 @@ pub async fn health_check() -> Result<Json<serde_json::Value>, (StatusCode, Json
     Ok(Json(serde_json::json!({
         "status": "healthy",
         "service": "xzepr",
-        "timestamp": chrono::Utc::now().to_rfc3339()
+        "timestamp": format_utc(&chrono::Utc::now())
     })))
 }
 
@@ -0,0 +1,211 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/export.rs
+
+use axum::{
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{FixedOffset, SecondsFormat, Utc};
+use tracing::{error, info, warn};
+
+use crate::api::field_access::{redacted_payload, FieldAccess};
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, EventExportQueryParams};
+use crate::api::rest::events::{load_full_payloads, AppState};
+use crate::api::rest::preferences::RequestPreferences;
+use crate::api::rest::timestamp::format_utc;
+use crate::domain::entities::event::Event;
+
+/// Column header for event CSV exports
+const EVENT_CSV_HEADER: &str = "id,name,version,release,platform_id,package,success,\
+     event_receiver_id,payload,created_at,created_at_local";
+
+/// Most events one export may contain; larger ranges must be split
+pub const MAX_EXPORT_ROWS: usize = 100_000;
+
+/// Exports events in a time range as CSV
+///
+/// Missing `start` and `timezone` parameters fall back to the caller's
+/// `default_time_range` and `export_timezone` preferences. Payloads are
+/// redacted unless the caller may read them.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid timestamps, range, or time zone
+/// * `413 PAYLOAD_TOO_LARGE` - More than [`MAX_EXPORT_ROWS`] events match
+/// * `500 INTERNAL_SERVER_ERROR` - Unexpected server error
+pub async fn export_events(
+    State(state): State<AppState>,
+    user: Option<AuthenticatedUser>,
+    preferences: RequestPreferences,
+    Query(params): Query<EventExportQueryParams>,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    let window = match params.resolve(preferences.preferences(), Utc::now()) {
+        Ok(window) => window,
+        Err(e) => {
+            warn!("Event export validation failed: {}", e);
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_domain(
+                    "validation_error".to_string(),
+                    &e,
+                )),
+            ));
+        }
+    };
+
+    info!(
+        start = %window.start,
+        end = %window.end,
+        timezone = %window.timezone,
+        "Exporting events"
+    );
+
+    let matched = state
+        .event_handler
+        .count_events_in_time_range(window.start, window.end)
+        .await
+        .map_err(|e| {
+            error!("Failed to count events for export: {}", e);
+            (
+                e.status_code(),
+                Json(ErrorResponse::from_error("export_failed".to_string(), &e)),
+            )
+        })?;
+    check_export_size(matched, MAX_EXPORT_ROWS)?;
+
+    match state
+        .event_handler
+        .find_events_in_time_range(window.start, window.end)
+        .await
+    {
+        Ok(mut events) => {
+            events.sort_by_key(|e| (e.created_at(), e.id().to_string()));
+            let access = FieldAccess::for_user(user.as_ref());
+            load_full_payloads(&state, &mut events, &access).await?;
+            let body = events_to_csv(&events, &window.timezone, &access);
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        "attachment; filename=\"events.csv\"",
+                    ),
+                ],
+                body,
+            )
+                .into_response())
+        }
+        Err(e) => {
+            error!("Failed to export events: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error("export_failed".to_string(), &e)),
+            ))
+        }
+    }
+}
+
+/// Rejects an export of `matched` events when it exceeds `limit`
+fn check_export_size(
+    matched: usize,
+    limit: usize,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    if matched <= limit {
+        return Ok(());
+    }
+
+    warn!(matched, limit, "Event export exceeds the row limit");
+    Err((
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(
+            ErrorResponse::new(
+                "export_too_large".to_string(),
+                format!(
+                    "{} events match; narrow the time range to at most {} events",
+                    matched, limit
+                ),
+            )
+            .with_details(serde_json::json!({ "matched": matched, "limit": limit })),
+        ),
+    ))
+}
+
+/// Renders events as CSV
+///
+/// `created_at` is always UTC so exports can be compared and re-imported;
+/// `created_at_local` repeats it in `timezone` for display. The payload
+/// column holds the payload as JSON, or the redaction marker when `access`
+/// does not allow reading it.
+pub fn events_to_csv(events: &[Event], timezone: &FixedOffset, access: &FieldAccess) -> String {
+    let mut csv = String::from(EVENT_CSV_HEADER);
+    csv.push('\n');
+
+    for event in events {
+        let created_at = format_utc(&event.created_at());
+        let created_at_local = event
+            .created_at()
+            .with_timezone(timezone)
+            .to_rfc3339_opts(SecondsFormat::AutoSi, true);
+        let payload = if access.can_read_payload(event.owner_id()) {
+            event.payload().to_string()
+        } else {
+            redacted_payload().to_string()
+        };
+        let fields = [
+            event.id().to_string(),
+            event.name().to_string(),
+            event.version().to_string(),
+            event.release().to_string(),
+            event.platform_id().to_string(),
+            event.package().to_string(),
+            event.success().to_string(),
+            event.event_receiver_id().to_string(),
+            payload,
+            created_at,
+            created_at_local,
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote, or newline
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_export_over_row_limit_rejected() {
+        assert!(check_export_size(10, 10).is_ok());
+
+        let (status, Json(body)) = check_export_size(11, 10).unwrap_err();
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body.error, "export_too_large");
+        assert_eq!(
+            body.details.as_deref(),
+            Some(&serde_json::json!({ "matched": 11, "limit": 10 }))
+        );
+    }
+}
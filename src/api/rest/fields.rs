@@ -0,0 +1,240 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/fields.rs
+
+//! Sparse fieldsets selected with the `fields` query parameter
+//!
+//! `?fields=name,type` trims each resource in a response to the listed
+//! top-level fields plus `id`, and `as_of` on historical reads. Unselected fields are omitted rather than
+//! nulled. Selection happens after the DTO is built, so fields the caller
+//! may not read are still redacted or omitted exactly as without `fields`.
+
+use axum::{http::StatusCode, response::Json};
+use serde::ser::{Error as _, SerializeMap};
+use serde::{Serialize, Serializer};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::api::rest::dtos::ErrorResponse;
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Field that is returned whatever the selection
+pub const ALWAYS_SELECTED: &str = "id";
+
+/// Marker of historical reads, returned whatever the selection so that a
+/// trimmed response cannot pass for current data
+pub const HISTORICAL_MARKER: &str = "as_of";
+
+/// Response DTO whose top-level fields callers may select
+pub trait Fieldset: Serialize {
+    /// Selectable field names as they appear in the response
+    const FIELDS: &'static [&'static str];
+
+    /// Pairs of a selectable field and a field returned along with it
+    const COMPANIONS: &'static [(&'static str, &'static str)] = &[];
+}
+
+/// Fields requested with `?fields=`; all fields when none were requested
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldSelection {
+    fields: Option<Arc<BTreeSet<String>>>,
+}
+
+impl FieldSelection {
+    /// Selects every field
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Parses a comma-separated field list for `T`
+    ///
+    /// A missing or blank list selects every field.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error on `fields` naming the unknown fields and
+    /// the fields `T` allows.
+    pub fn parse<T: Fieldset>(raw: Option<&str>) -> Result<Self, DomainError> {
+        let requested: BTreeSet<String> = raw
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .map(str::to_string)
+            .collect();
+        if requested.is_empty() {
+            return Ok(Self::all());
+        }
+
+        let unknown: Vec<&str> = requested
+            .iter()
+            .map(String::as_str)
+            .filter(|field| *field != ALWAYS_SELECTED && !T::FIELDS.contains(field))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "fields".to_string(),
+                message: Message::new("validation.unknown_fields")
+                    .with("fields", unknown.join(", "))
+                    .with("allowed", T::FIELDS.join(", ")),
+            });
+        }
+
+        let mut fields = requested;
+        fields.insert(ALWAYS_SELECTED.to_string());
+        fields.insert(HISTORICAL_MARKER.to_string());
+        for (field, companion) in T::COMPANIONS {
+            if fields.contains(*field) {
+                fields.insert(companion.to_string());
+            }
+        }
+
+        Ok(Self {
+            fields: Some(Arc::new(fields)),
+        })
+    }
+
+    /// Returns true if only some fields were selected
+    pub fn is_sparse(&self) -> bool {
+        self.fields.is_some()
+    }
+
+    /// Wraps `value` so that it serializes with the selected fields only
+    pub fn apply<T: Fieldset>(&self, value: T) -> Sparse<T> {
+        Sparse {
+            value,
+            fields: self.fields.clone(),
+        }
+    }
+}
+
+/// Parses the `fields` parameter of a request for `T`
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - A requested field does not exist on `T`
+pub fn select<T: Fieldset>(
+    raw: Option<&str>,
+) -> Result<FieldSelection, (StatusCode, Json<ErrorResponse>)> {
+    FieldSelection::parse::<T>(raw).map_err(|e| {
+        warn!("Field selection rejected: {}", e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )
+    })
+}
+
+/// A response DTO trimmed to a [`FieldSelection`]
+#[derive(Debug, Clone)]
+pub struct Sparse<T> {
+    value: T,
+    fields: Option<Arc<BTreeSet<String>>>,
+}
+
+impl<T> Sparse<T> {
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Fieldset> Serialize for Sparse<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let fields = match &self.fields {
+            Some(fields) => fields,
+            None => return self.value.serialize(serializer),
+        };
+
+        match serde_json::to_value(&self.value).map_err(S::Error::custom)? {
+            JsonValue::Object(object) => {
+                let selected: Vec<_> = object
+                    .iter()
+                    .filter(|(key, _)| fields.contains(key.as_str()))
+                    .collect();
+                let mut map = serializer.serialize_map(Some(selected.len()))?;
+                for (key, value) in selected {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            other => other.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Widget {
+        id: u32,
+        name: String,
+        secret: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        secret_size: Option<usize>,
+    }
+
+    impl Fieldset for Widget {
+        const FIELDS: &'static [&'static str] = &["name", "secret"];
+        const COMPANIONS: &'static [(&'static str, &'static str)] = &[("secret", "secret_size")];
+    }
+
+    fn widget() -> Widget {
+        Widget {
+            id: 7,
+            name: "gear".to_string(),
+            secret: None,
+            secret_size: Some(12),
+        }
+    }
+
+    #[test]
+    fn test_selection_keeps_id_and_companions() {
+        let selection = FieldSelection::parse::<Widget>(Some(" name ,")).unwrap();
+        assert_eq!(
+            serde_json::to_value(selection.apply(widget())).unwrap(),
+            json!({"id": 7, "name": "gear"})
+        );
+
+        let selection = FieldSelection::parse::<Widget>(Some("secret")).unwrap();
+        assert_eq!(
+            serde_json::to_value(selection.apply(widget())).unwrap(),
+            json!({"id": 7, "secret": null, "secret_size": 12})
+        );
+    }
+
+    #[test]
+    fn test_missing_selection_returns_everything() {
+        for raw in [None, Some(""), Some(" , ")] {
+            let selection = FieldSelection::parse::<Widget>(raw).unwrap();
+            assert!(!selection.is_sparse());
+            assert_eq!(
+                serde_json::to_value(selection.apply(widget())).unwrap(),
+                json!({"id": 7, "name": "gear", "secret": null, "secret_size": 12})
+            );
+        }
+    }
+
+    #[test]
+    fn test_unknown_fields_name_valid_options() {
+        match FieldSelection::parse::<Widget>(Some("name,colour,secret_size")) {
+            Err(DomainError::ValidationError { field, message }) => {
+                assert_eq!(field, "fields");
+                assert_eq!(message.key(), "validation.unknown_fields");
+                let text = message.to_string();
+                assert!(text.contains("colour, secret_size"), "{}", text);
+                assert!(text.contains("name, secret"), "{}", text);
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/flags.rs
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    ErrorResponse, FeatureFlagResponse, FeatureFlagsResponse, UpdateFeatureFlagRequest,
+};
+use crate::api::rest::events::AppState;
+
+/// Role required to change feature flags
+const FLAG_ADMIN_ROLE: &str = "admin";
+
+/// Returns every feature flag evaluated for the caller's roles
+pub async fn list_my_flags(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Json<FeatureFlagsResponse> {
+    Json(FeatureFlagsResponse {
+        flags: state.feature_flags.evaluate_for(&user.claims.roles),
+    })
+}
+
+/// Changes a feature flag at runtime
+///
+/// The change applies immediately, is persisted so that it survives
+/// restarts, and is recorded in the audit log. Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `404 NOT_FOUND` - No flag with this name is defined
+pub async fn update_flag(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(name): Path<String>,
+    Json(request): Json<UpdateFeatureFlagRequest>,
+) -> Result<Json<FeatureFlagResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if !user.has_role(FLAG_ADMIN_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            flag = %name,
+            "Feature flag update denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    info!(user_id = %user.user_id(), flag = %name, "Updating feature flag");
+
+    match state
+        .feature_flags
+        .set_flag(&name, request.enabled, request.roles, user.user_id())
+        .await
+    {
+        Ok(flag) => Ok(Json(FeatureFlagResponse::from(flag))),
+        Err(e) => {
+            let status = e.status_code();
+            if status == StatusCode::NOT_FOUND {
+                warn!("Unknown feature flag: {}", name);
+                Err((
+                    status,
+                    Json(ErrorResponse::new(
+                        "not_found".to_string(),
+                        format!("Unknown feature flag: {}", name),
+                    )),
+                ))
+            } else {
+                error!("Failed to update feature flag {}: {}", name, e);
+                Err((
+                    status,
+                    Json(ErrorResponse::from_error(
+                        "flag_update_failed".to_string(),
+                        &e,
+                    )),
+                ))
+            }
+        }
+    }
+}
@@ -4,7 +4,7 @@
 // src/api/rest/group_membership.rs
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
@@ -12,15 +12,74 @@ use tracing::{error, info, warn};
 
 use crate::api::middleware::jwt::AuthenticatedUser;
 use crate::api::rest::dtos::{
-    AddMemberRequest, ErrorResponse, GroupMemberResponse, GroupMembersResponse, RemoveMemberRequest,
+    AddMemberRequest, AsOfQueryParams, ErrorResponse, GroupMemberResponse, GroupMembersResponse,
+    RemoveMemberRequest, UpdateMemberRequest,
 };
+use crate::api::rest::history;
+use crate::api::rest::timestamp::Timestamp;
+use crate::application::authorization::{AuthorizationService, ProtectedResource, ResourceAction};
 use crate::application::handlers::EventReceiverGroupHandler;
+use crate::domain::repositories::event_receiver_group_repo::GroupMembership;
 use crate::domain::value_objects::{EventReceiverGroupId, UserId};
 
 /// Application state containing the event receiver group handler
 #[derive(Clone)]
 pub struct GroupMembershipState {
     pub group_handler: EventReceiverGroupHandler,
+    /// Owner and admin checks shared with the GraphQL API
+    pub authorization: AuthorizationService,
+}
+
+/// Requires `user` to be allowed to change the members of a group
+async fn authorize_membership_change(
+    state: &GroupMembershipState,
+    user: &AuthenticatedUser,
+    action: ResourceAction,
+    group_id: EventReceiverGroupId,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let result = state
+        .authorization
+        .authorize(&user.claims, action, ProtectedResource::Group(group_id))
+        .await;
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) if e.status_code() == StatusCode::FORBIDDEN => {
+            warn!(
+                "User {} is not authorized to {} of group {}",
+                user.user_id(),
+                action,
+                group_id
+            );
+            Err((
+                StatusCode::FORBIDDEN,
+                Json(ErrorResponse::new(
+                    "Forbidden".to_string(),
+                    "Only the group owner or an admin can change its members".to_string(),
+                )),
+            ))
+        }
+        Err(e) if e.status_code() == StatusCode::NOT_FOUND => {
+            warn!("Group not found: {}", group_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::new(
+                    "NotFound".to_string(),
+                    "Group not found".to_string(),
+                )),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to check group authorization: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(
+                    "InternalError".to_string(),
+                    "Failed to check authorization".to_string(),
+                )),
+            ))
+        }
+    }
 }
 
 /// Adds a member to an event receiver group
@@ -28,8 +87,10 @@ pub struct GroupMembershipState {
 /// # Arguments
 ///
 /// * `group_id` - The ID of the group to add the member to
-/// * `user` - The authenticated user making the request (must be group owner)
-/// * `request` - The request body containing the user_id to add
+/// * `user` - The authenticated user making the request (must be the group
+///   owner or an admin)
+/// * `request` - The request body containing the user_id to add and an
+///   optional `expires_at`
 ///
 /// # Returns
 ///
@@ -38,7 +99,8 @@ pub struct GroupMembershipState {
 ///
 /// # Errors
 ///
-/// * `400 BAD_REQUEST` - Invalid request data or validation failure
+/// * `400 BAD_REQUEST` - Invalid request data, or `expires_at` is not in
+///   the future or exceeds the maximum membership duration
 /// * `401 UNAUTHORIZED` - Invalid authentication token
 /// * `403 FORBIDDEN` - User is not authorized to add members to this group
 /// * `404 NOT_FOUND` - Group not found
@@ -58,12 +120,6 @@ pub async fn add_group_member(
         "Adding member to group"
     );
 
-    // Validate request
-    if let Err(err) = request.validate() {
-        warn!("Add member validation failed: {:?}", err);
-        return Err((StatusCode::BAD_REQUEST, Json(err)));
-    }
-
     // Parse group ID
     let group_id = match group_id.parse::<EventReceiverGroupId>() {
         Ok(id) => id,
@@ -80,7 +136,7 @@ pub async fn add_group_member(
     };
 
     // Parse authenticated user ID (who is adding the member)
-    let added_by = match UserId::parse(user_id_str) {
+    let added_by = match user_id_str.parse::<UserId>() {
         Ok(id) => id,
         Err(e) => {
             error!("Invalid user ID in JWT token: {}", e);
@@ -94,59 +150,17 @@ pub async fn add_group_member(
         }
     };
 
-    // Parse user ID to add
-    let user_id = match request.parse_user_id() {
-        Ok(id) => id,
-        Err(err) => {
-            warn!("Invalid user ID to add: {:?}", err);
-            return Err((StatusCode::BAD_REQUEST, Json(err)));
-        }
-    };
+    let user_id = request.user_id;
 
-    // Check if group exists and user is owner
-    let group = match state.group_handler.find_group_by_id(group_id).await {
-        Ok(Some(g)) => g,
-        Ok(None) => {
-            warn!("Group not found: {}", group_id);
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse::new(
-                    "NotFound".to_string(),
-                    "Group not found".to_string(),
-                )),
-            ));
-        }
-        Err(e) => {
-            error!("Failed to fetch group: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(
-                    "InternalError".to_string(),
-                    "Failed to fetch group information".to_string(),
-                )),
-            ));
-        }
-    };
+    // Verify the group exists and the user owns it or is an admin
+    authorize_membership_change(&state, &user, ResourceAction::AddMember, group_id).await?;
 
-    // Verify the authenticated user owns the group
-    if group.owner_id() != added_by {
-        warn!(
-            "User {} is not authorized to add members to group {}",
-            added_by, group_id
-        );
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(ErrorResponse::new(
-                "Forbidden".to_string(),
-                "Only the group owner can add members".to_string(),
-            )),
-        ));
-    }
+    let expires_at = request.expires_at.map(Timestamp::utc);
 
     // Add the member
     match state
         .group_handler
-        .add_group_member(group_id, user_id, added_by)
+        .add_group_member(group_id, user_id, added_by, expires_at)
         .await
     {
         Ok(_) => {
@@ -159,19 +173,30 @@ pub async fn add_group_member(
             // Note: In a real implementation, we would fetch user details
             // from a user service or repository. For now, we return basic info.
             Ok(Json(GroupMemberResponse {
-                user_id: user_id.to_string(),
+                user_id,
                 username: format!("user_{}", user_id), // Placeholder
                 email: format!("{}@example.com", user_id), // Placeholder
                 added_at: chrono::Utc::now(),
-                added_by: added_by.to_string(),
+                added_by,
+                expires_at,
             }))
         }
+        Err(e) if e.status_code() == StatusCode::BAD_REQUEST => {
+            warn!("Invalid group membership: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_error("ValidationError".to_string(), &e)),
+            ))
+        }
         Err(e) => {
             error!("Failed to add member to group: {}", e);
 
             // Check for specific error types
             let error_msg = e.to_string();
-            if error_msg.contains("already a member") || error_msg.contains("duplicate") {
+            if e.status_code() == StatusCode::CONFLICT
+                || error_msg.contains("already a member")
+                || error_msg.contains("duplicate")
+            {
                 return Err((
                     StatusCode::CONFLICT,
                     Json(ErrorResponse::new(
@@ -197,7 +222,8 @@ pub async fn add_group_member(
 /// # Arguments
 ///
 /// * `group_id` - The ID of the group to remove the member from
-/// * `user` - The authenticated user making the request (must be group owner)
+/// * `user` - The authenticated user making the request (must be the group
+///   owner or an admin)
 /// * `request` - The request body containing the user_id to remove
 ///
 /// # Returns
@@ -225,12 +251,6 @@ pub async fn remove_group_member(
         "Removing member from group"
     );
 
-    // Validate request
-    if let Err(err) = request.validate() {
-        warn!("Remove member validation failed: {:?}", err);
-        return Err((StatusCode::BAD_REQUEST, Json(err)));
-    }
-
     // Parse group ID
     let group_id = match group_id.parse::<EventReceiverGroupId>() {
         Ok(id) => id,
@@ -247,7 +267,7 @@ pub async fn remove_group_member(
     };
 
     // Parse authenticated user ID (who is removing the member)
-    let removed_by = match UserId::parse(user_id_str) {
+    let removed_by = match user_id_str.parse::<UserId>() {
         Ok(id) => id,
         Err(e) => {
             error!("Invalid user ID in JWT token: {}", e);
@@ -261,54 +281,10 @@ pub async fn remove_group_member(
         }
     };
 
-    // Parse user ID to remove
-    let user_id = match request.parse_user_id() {
-        Ok(id) => id,
-        Err(err) => {
-            warn!("Invalid user ID to remove: {:?}", err);
-            return Err((StatusCode::BAD_REQUEST, Json(err)));
-        }
-    };
-
-    // Check if group exists and user is owner
-    let group = match state.group_handler.find_group_by_id(group_id).await {
-        Ok(Some(g)) => g,
-        Ok(None) => {
-            warn!("Group not found: {}", group_id);
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse::new(
-                    "NotFound".to_string(),
-                    "Group not found".to_string(),
-                )),
-            ));
-        }
-        Err(e) => {
-            error!("Failed to fetch group: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(
-                    "InternalError".to_string(),
-                    "Failed to fetch group information".to_string(),
-                )),
-            ));
-        }
-    };
+    let user_id = request.user_id;
 
-    // Verify the authenticated user owns the group
-    if group.owner_id() != removed_by {
-        warn!(
-            "User {} is not authorized to remove members from group {}",
-            removed_by, group_id
-        );
-        return Err((
-            StatusCode::FORBIDDEN,
-            Json(ErrorResponse::new(
-                "Forbidden".to_string(),
-                "Only the group owner can remove members".to_string(),
-            )),
-        ));
-    }
+    // Verify the group exists and the user owns it or is an admin
+    authorize_membership_change(&state, &user, ResourceAction::RemoveMember, group_id).await?;
 
     // Remove the member
     match state
@@ -350,9 +326,14 @@ pub async fn remove_group_member(
 
 /// Lists all members of an event receiver group
 ///
+/// With `as_of`, the members are listed as they were at that instant and
+/// the response carries the instant in `as_of`. Access is still decided by
+/// the group's current owner and members.
+///
 /// # Arguments
 ///
 /// * `group_id` - The ID of the group to list members for
+/// * `query` - Optional `as_of` instant
 /// * `user` - The authenticated user making the request
 ///
 /// # Returns
@@ -362,16 +343,20 @@ pub async fn remove_group_member(
 ///
 /// # Errors
 ///
-/// * `400 BAD_REQUEST` - Invalid group ID format
+/// * `400 BAD_REQUEST` - Invalid group ID format or `as_of` timestamp
 /// * `401 UNAUTHORIZED` - Invalid authentication token
 /// * `403 FORBIDDEN` - User is not authorized to view members (not owner or member)
-/// * `404 NOT_FOUND` - Group not found
+/// * `404 NOT_FOUND` - Group not found, or did not exist at `as_of`
 /// * `500 INTERNAL_SERVER_ERROR` - Unexpected server error
+/// * `503 SERVICE_UNAVAILABLE` - `as_of` was given but history is not
+///   configured
 pub async fn list_group_members(
     State(state): State<GroupMembershipState>,
     Path(group_id): Path<String>,
+    Query(query): Query<AsOfQueryParams>,
     user: AuthenticatedUser,
 ) -> Result<Json<GroupMembersResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let as_of = history::parse_as_of(query.as_of.as_deref())?;
     let user_id_str = user.user_id();
     info!(
         group_id = %group_id,
@@ -395,7 +380,7 @@ pub async fn list_group_members(
     };
 
     // Parse authenticated user ID
-    let requesting_user_id = match UserId::parse(user_id_str) {
+    let requesting_user_id = match user_id_str.parse::<UserId>() {
         Ok(id) => id,
         Err(e) => {
             error!("Invalid user ID in JWT token: {}", e);
@@ -468,28 +453,28 @@ pub async fn list_group_members(
         ));
     }
 
-    // Get all members
-    match state.group_handler.get_group_members(group_id).await {
-        Ok(member_ids) => {
-            info!("Found {} members in group {}", member_ids.len(), group_id);
+    if let Some(instant) = as_of {
+        let group_history = history::require_history(state.group_handler.history())?;
+        let past = history::group_as_of(group_history, group_id, instant).await?;
+        return Ok(Json(GroupMembersResponse {
+            group_id,
+            members: past.members.into_iter().map(member_response).collect(),
+            as_of: Some(instant),
+        }));
+    }
 
-            // Convert user IDs to member responses
-            // Note: In a real implementation, we would fetch full user details
-            // from a user service or repository. For now, we return basic info.
-            let members: Vec<GroupMemberResponse> = member_ids
-                .iter()
-                .map(|uid| GroupMemberResponse {
-                    user_id: uid.to_string(),
-                    username: format!("user_{}", uid), // Placeholder
-                    email: format!("{}@example.com", uid), // Placeholder
-                    added_at: chrono::Utc::now(),      // Placeholder
-                    added_by: group.owner_id().to_string(), // Placeholder
-                })
-                .collect();
+    // Get all current members; expired memberships are left out
+    match state.group_handler.get_group_memberships(group_id).await {
+        Ok(memberships) => {
+            info!("Found {} members in group {}", memberships.len(), group_id);
+
+            let members: Vec<GroupMemberResponse> =
+                memberships.into_iter().map(member_response).collect();
 
             Ok(Json(GroupMembersResponse {
-                group_id: group_id.to_string(),
+                group_id,
                 members,
+                as_of: None,
             }))
         }
         Err(e) => {
@@ -505,6 +490,121 @@ pub async fn list_group_members(
     }
 }
 
+/// Changes when a member of an event receiver group loses access
+///
+/// A null or omitted `expires_at` keeps the member until removed; owners
+/// use this to extend time-boxed access before it runs out.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid ids, or `expires_at` is not in the future
+///   or exceeds the maximum membership duration
+/// * `403 FORBIDDEN` - User is neither the group owner nor an admin
+/// * `404 NOT_FOUND` - Group not found or user is not a current member
+/// * `500 INTERNAL_SERVER_ERROR` - Unexpected server error
+pub async fn update_group_member(
+    State(state): State<GroupMembershipState>,
+    Path((group_id, member_id)): Path<(String, String)>,
+    user: AuthenticatedUser,
+    Json(request): Json<UpdateMemberRequest>,
+) -> Result<Json<GroupMemberResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let user_id_str = user.user_id();
+    info!(
+        group_id = %group_id,
+        member = %member_id,
+        updated_by = %user_id_str,
+        expires_at = ?request.expires_at,
+        "Updating group member"
+    );
+
+    let (group_id, member_id) = match (
+        group_id.parse::<EventReceiverGroupId>(),
+        member_id.parse::<UserId>(),
+    ) {
+        (Ok(group_id), Ok(member_id)) => (group_id, member_id),
+        _ => {
+            warn!("Invalid group or user ID format");
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(
+                    "ValidationError".to_string(),
+                    "Invalid group or user ID format".to_string(),
+                )),
+            ));
+        }
+    };
+
+    let updated_by = match user_id_str.parse::<UserId>() {
+        Ok(id) => id,
+        Err(e) => {
+            error!("Invalid user ID in JWT token: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(
+                    "InternalError".to_string(),
+                    "Invalid user ID in authentication token".to_string(),
+                )),
+            ));
+        }
+    };
+
+    authorize_membership_change(&state, &user, ResourceAction::UpdateMember, group_id).await?;
+
+    match state
+        .group_handler
+        .set_group_member_expiry(group_id, member_id, request.expires_at.map(Timestamp::utc))
+        .await
+    {
+        Ok(membership) => {
+            info!(
+                "Updated membership of user {} in group {} by {}",
+                member_id, group_id, updated_by
+            );
+            Ok(Json(member_response(membership)))
+        }
+        Err(e) if e.status_code() == StatusCode::BAD_REQUEST => {
+            warn!("Invalid group membership: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_error("ValidationError".to_string(), &e)),
+            ))
+        }
+        Err(e) if e.status_code() == StatusCode::NOT_FOUND => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                "NotFound".to_string(),
+                "User is not a member of this group".to_string(),
+            )),
+        )),
+        Err(e) => {
+            error!("Failed to update group member: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new(
+                    "InternalError".to_string(),
+                    "Failed to update member".to_string(),
+                )),
+            ))
+        }
+    }
+}
+
+/// Builds the response for a membership
+///
+/// Note: In a real implementation, we would fetch user details from a user
+/// service or repository. For now, username and email are placeholders.
+fn member_response(membership: GroupMembership) -> GroupMemberResponse {
+    let user_id = membership.user_id;
+    GroupMemberResponse {
+        user_id,
+        username: format!("user_{}", user_id),
+        email: format!("{}@example.com", user_id),
+        added_at: membership.added_at,
+        added_by: membership.added_by,
+        expires_at: membership.expires_at,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -678,6 +778,21 @@ mod tests {
                 unimplemented!()
             }
 
+            async fn find_default_schema_group(
+                &self,
+                _receiver_id: EventReceiverId,
+            ) -> crate::error::Result<Option<EventReceiverGroupId>> {
+                unimplemented!()
+            }
+
+            async fn set_default_schema_group(
+                &self,
+                _receiver_id: EventReceiverId,
+                _group_id: EventReceiverGroupId,
+            ) -> crate::error::Result<()> {
+                unimplemented!()
+            }
+
             async fn find_by_owner(
                 &self,
                 _owner_id: crate::domain::value_objects::UserId,
@@ -728,15 +843,44 @@ mod tests {
                 unimplemented!()
             }
 
+            async fn get_group_memberships(
+                &self,
+                _group_id: EventReceiverGroupId,
+            ) -> crate::error::Result<
+                Vec<crate::domain::repositories::event_receiver_group_repo::GroupMembership>,
+            > {
+                unimplemented!()
+            }
+
             async fn add_member(
                 &self,
                 _group_id: EventReceiverGroupId,
                 _user_id: crate::domain::value_objects::UserId,
                 _added_by: crate::domain::value_objects::UserId,
+                _expires_at: Option<chrono::DateTime<chrono::Utc>>,
+            ) -> crate::error::Result<()> {
+                unimplemented!()
+            }
+
+            async fn set_member_expiry(
+                &self,
+                _group_id: EventReceiverGroupId,
+                _user_id: crate::domain::value_objects::UserId,
+                _expires_at: Option<chrono::DateTime<chrono::Utc>>,
             ) -> crate::error::Result<()> {
                 unimplemented!()
             }
 
+            async fn remove_expired_members(
+                &self,
+                _now: chrono::DateTime<chrono::Utc>,
+                _limit: usize,
+            ) -> crate::error::Result<
+                Vec<crate::domain::repositories::event_receiver_group_repo::ExpiredMembership>,
+            > {
+                unimplemented!()
+            }
+
             async fn remove_member(
                 &self,
                 _group_id: EventReceiverGroupId,
@@ -881,9 +1025,12 @@ mod tests {
 
         let handler =
             EventReceiverGroupHandler::new(Arc::new(MockGroupRepo), Arc::new(MockReceiverRepo));
+        let authorization =
+            AuthorizationService::new(Arc::new(MockReceiverRepo), Arc::new(MockGroupRepo));
 
         let state = GroupMembershipState {
             group_handler: handler,
+            authorization,
         };
 
         let _cloned_state = state.clone();
@@ -0,0 +1,330 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/health.rs
+
+use std::sync::{Arc, OnceLock, RwLock};
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    routing::get,
+    Router,
+};
+use serde_json::json;
+use tower::ServiceExt;
+use tracing::warn;
+
+use crate::infrastructure::database::SchemaCheckReport;
+use crate::infrastructure::{MaintenanceMode, MigrationStatus, StartupReadiness};
+
+/// Front router that serves probes while startup dependencies are pending
+///
+/// The HTTP listener is bound before the database, Kafka, and OPA are
+/// reachable. Until [`StartupGate::install`] hands over the application
+/// router, `/health/live` answers 200, `/health/ready` answers 503, and
+/// every other request gets 503. Afterwards requests are forwarded to the
+/// installed router and `/health/ready` reports 200 once all dependencies
+/// have been marked ready. Once recorded, the migration status, the schema
+/// check result, and the maintenance mode state are included in the
+/// `/health/ready` body under `details.migrations`, `details.schema`, and
+/// `details.maintenance`.
+#[derive(Clone)]
+pub struct StartupGate {
+    readiness: StartupReadiness,
+    app: Arc<OnceLock<Router>>,
+    // Writers only assign and readers only clone out of it, so the lock
+    // is never held across code that can panic and cannot be poisoned
+    migrations: Arc<RwLock<Option<MigrationStatus>>>,
+    schema: Arc<OnceLock<SchemaCheckReport>>,
+    maintenance: Arc<OnceLock<MaintenanceMode>>,
+}
+
+impl StartupGate {
+    /// Creates a gate that reports readiness from `readiness`
+    pub fn new(readiness: StartupReadiness) -> Self {
+        Self {
+            readiness,
+            app: Arc::new(OnceLock::new()),
+            migrations: Arc::new(RwLock::new(None)),
+            schema: Arc::new(OnceLock::new()),
+            maintenance: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Records which migrations are applied; replaces the previous status
+    pub fn record_migrations(&self, status: MigrationStatus) {
+        // Never poisoned; see the field
+        *self.migrations.write().unwrap() = Some(status);
+    }
+
+    /// Records the startup schema check result; later calls are ignored
+    pub fn record_schema_check(&self, report: SchemaCheckReport) {
+        if self.schema.set(report).is_err() {
+            warn!("Schema check result already recorded; ignoring the new one");
+        }
+    }
+
+    /// Reports `maintenance` in readiness details; later calls are ignored
+    pub fn record_maintenance(&self, maintenance: MaintenanceMode) {
+        if self.maintenance.set(maintenance).is_err() {
+            warn!("Maintenance mode already recorded; ignoring the new one");
+        }
+    }
+
+    /// Installs the application router; later calls are ignored
+    ///
+    /// A second install is logged, since requests keep going to the first
+    /// router.
+    pub fn install(&self, app: Router) {
+        if self.app.set(app).is_err() {
+            warn!("Application router already installed; ignoring the new one");
+        }
+    }
+
+    /// Returns true when dependencies are ready and the app is installed
+    pub fn is_ready(&self) -> bool {
+        self.app.get().is_some() && self.readiness.is_ready()
+    }
+
+    /// Builds the router to serve on the listener
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/health/live", get(liveness))
+            .route("/health/ready", get(readiness))
+            .fallback(forward)
+            .with_state(self.clone())
+    }
+}
+
+/// Liveness probe; OK whenever the process is serving requests
+pub async fn liveness() -> impl IntoResponse {
+    Json(json!({"status": "alive"}))
+}
+
+/// Readiness probe; OK only after every startup dependency has passed
+pub async fn readiness(State(gate): State<StartupGate>) -> impl IntoResponse {
+    let (status, mut body) = if gate.is_ready() {
+        (StatusCode::OK, json!({"status": "ready"}))
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            json!({
+                "status": "starting",
+                "pending": gate.readiness.pending(),
+            }),
+        )
+    };
+    // Never poisoned; see StartupGate::migrations
+    if let Some(migrations) = gate.migrations.read().unwrap().as_ref() {
+        let pending: Vec<_> = migrations
+            .pending
+            .iter()
+            .map(|m| json!({"version": m.version, "description": m.description}))
+            .collect();
+        body["details"]["migrations"] = json!({
+            "status": if migrations.is_current() { "current" } else { "pending" },
+            "schema_version": migrations.schema_version,
+            "applied": migrations.applied.len(),
+            "pending": pending,
+        });
+    }
+    if let Some(schema) = gate.schema.get() {
+        body["details"]["schema"] = json!({
+            "status": if schema.is_clean() { "ok" } else { "drift" },
+            "mode": schema.mode,
+            "drift": schema.drift,
+        });
+    }
+    // Reads are still served during maintenance, so the instance stays ready
+    if let Some(maintenance) = gate.maintenance.get() {
+        body["details"]["maintenance"] = match maintenance.current() {
+            Some(window) => json!({
+                "enabled": true,
+                "message": window.message,
+                "ends_at": window.ends_at,
+            }),
+            None => json!({"enabled": false}),
+        };
+    }
+    (status, Json(body))
+}
+
+async fn forward(State(gate): State<StartupGate>, request: Request) -> Response {
+    match gate.app.get() {
+        Some(app) => match app.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        },
+        None => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "error": "service_starting",
+                "message": "Server is waiting for its dependencies",
+            })),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::database::{SchemaCheckMode, SchemaDrift};
+    use crate::infrastructure::startup::{DEPENDENCY_DATABASE, DEPENDENCY_KAFKA};
+    use axum::body::Body;
+    use axum::http::Method;
+
+    async fn status(router: &Router, uri: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_readiness_flips_only_after_all_dependencies() {
+        let readiness = StartupReadiness::new([DEPENDENCY_DATABASE, DEPENDENCY_KAFKA]);
+        let gate = StartupGate::new(readiness.clone());
+        let router = gate.router();
+
+        assert_eq!(status(&router, "/health/live").await, StatusCode::OK);
+        assert_eq!(
+            status(&router, "/health/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(&router, "/api/v1/status").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        readiness.mark_ready(DEPENDENCY_DATABASE);
+        assert_eq!(
+            status(&router, "/health/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        readiness.mark_ready(DEPENDENCY_KAFKA);
+        assert_eq!(
+            status(&router, "/health/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        gate.install(Router::new().route("/api/v1/status", get(|| async { "ok" })));
+        assert_eq!(status(&router, "/health/ready").await, StatusCode::OK);
+        assert_eq!(status(&router, "/health/live").await, StatusCode::OK);
+        assert_eq!(status(&router, "/api/v1/status").await, StatusCode::OK);
+        assert_eq!(status(&router, "/missing").await, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_reports_schema_drift() {
+        let readiness = StartupReadiness::new([DEPENDENCY_DATABASE]);
+        let gate = StartupGate::new(readiness.clone());
+        let router = gate.router();
+        readiness.mark_ready(DEPENDENCY_DATABASE);
+        gate.install(Router::new());
+        gate.record_schema_check(SchemaCheckReport {
+            mode: SchemaCheckMode::Warn,
+            drift: vec![SchemaDrift::MissingIndex {
+                table: "events".to_string(),
+                index: "idx_events_feed".to_string(),
+            }],
+        });
+
+        let request = Request::builder()
+            .uri("/health/ready")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["status"], "ready");
+        assert_eq!(body["details"]["schema"]["status"], "drift");
+        assert_eq!(body["details"]["schema"]["mode"], "warn");
+        assert_eq!(
+            body["details"]["schema"]["drift"][0]["index"],
+            "idx_events_feed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_readiness_reports_maintenance() {
+        let readiness = StartupReadiness::new([DEPENDENCY_DATABASE]);
+        let gate = StartupGate::new(readiness.clone());
+        let router = gate.router();
+        readiness.mark_ready(DEPENDENCY_DATABASE);
+        gate.install(Router::new());
+        let maintenance = MaintenanceMode::new();
+        gate.record_maintenance(maintenance.clone());
+
+        let body = |router: Router| async move {
+            let request = Request::builder()
+                .uri("/health/ready")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        };
+
+        let ready = body(router.clone()).await;
+        assert_eq!(ready["details"]["maintenance"]["enabled"], false);
+
+        maintenance
+            .enter("Migrating".to_string(), None, "admin")
+            .await
+            .unwrap();
+        let ready = body(router).await;
+        assert_eq!(ready["status"], "ready");
+        assert_eq!(ready["details"]["maintenance"]["enabled"], true);
+        assert_eq!(ready["details"]["maintenance"]["message"], "Migrating");
+    }
+
+    #[tokio::test]
+    async fn test_readiness_reports_pending_migrations() {
+        use crate::infrastructure::migrations::MigrationInfo;
+
+        let readiness = StartupReadiness::new([DEPENDENCY_DATABASE]);
+        let gate = StartupGate::new(readiness.clone());
+        let router = gate.router();
+        readiness.mark_ready(DEPENDENCY_DATABASE);
+        gate.install(Router::new());
+        let migration = |version: i64, description: &str| MigrationInfo {
+            version,
+            description: description.to_string(),
+            transactional: true,
+            safe_to_rerun: false,
+        };
+        gate.record_migrations(MigrationStatus {
+            schema_version: Some(1),
+            applied: vec![migration(1, "initial")],
+            pending: vec![migration(2, "add receivers")],
+        });
+
+        let request = Request::builder()
+            .uri("/health/ready")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let migrations = &body["details"]["migrations"];
+        assert_eq!(migrations["status"], "pending");
+        assert_eq!(migrations["schema_version"], 1);
+        assert_eq!(migrations["applied"], 1);
+        assert_eq!(migrations["pending"][0]["version"], 2);
+        assert_eq!(migrations["pending"][0]["description"], "add receivers");
+    }
+}
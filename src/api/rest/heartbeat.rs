@@ -0,0 +1,126 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/heartbeat.rs
+
+use axum::{
+    body::Bytes,
+    extract::{Extension, Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::Json,
+};
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+use crate::api::middleware::api_key::ApiKeyPrincipal;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, ReceiverHeartbeatRequest, ReceiverHeartbeatResponse};
+use crate::api::rest::events::{check_api_key_scope, AppState};
+use crate::domain::repositories::receiver_heartbeat_repo::HeartbeatWrite;
+use crate::domain::value_objects::EventReceiverId;
+
+type HeartbeatError = (StatusCode, HeaderMap, Json<ErrorResponse>);
+
+fn heartbeat_error(status: StatusCode, error: &str, message: String) -> HeartbeatError {
+    (
+        status,
+        HeaderMap::new(),
+        Json(ErrorResponse::new(error.to_string(), message)),
+    )
+}
+
+/// Records that a producer of the receiver is alive
+///
+/// No event is created. The body is optional; when present its `status`
+/// object replaces the stored one. A receiver accepts one heartbeat per
+/// configured interval; earlier ones are not stored.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid receiver id or status
+/// * `403 FORBIDDEN` - API key is scoped to other receivers
+/// * `404 NOT_FOUND` - Receiver does not exist
+/// * `429 TOO_MANY_REQUESTS` - Previous heartbeat is too recent; the
+///   `Retry-After` header gives the seconds to wait
+pub async fn record_receiver_heartbeat(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    api_key: Option<Extension<ApiKeyPrincipal>>,
+    Path(id_str): Path<String>,
+    body: Bytes,
+) -> Result<Json<ReceiverHeartbeatResponse>, HeartbeatError> {
+    let receiver_id = id_str.parse::<EventReceiverId>().map_err(|_| {
+        warn!("Invalid event receiver ID format: {}", id_str);
+        heartbeat_error(
+            StatusCode::BAD_REQUEST,
+            "invalid_id",
+            "Invalid event receiver ID format".to_string(),
+        )
+    })?;
+
+    if let Some(Extension(principal)) = api_key {
+        check_api_key_scope(&state, principal.scope, Some(receiver_id))
+            .await
+            .map_err(|(status, body)| (status, HeaderMap::new(), body))?;
+    }
+
+    let request = if body.iter().all(u8::is_ascii_whitespace) {
+        ReceiverHeartbeatRequest::default()
+    } else {
+        serde_json::from_slice::<ReceiverHeartbeatRequest>(&body).map_err(|e| {
+            heartbeat_error(
+                StatusCode::BAD_REQUEST,
+                "invalid_json",
+                format!("Invalid JSON: {}", e),
+            )
+        })?
+    };
+
+    let now = Utc::now();
+    let handler = &state.event_receiver_handler;
+    match handler
+        .record_heartbeat(receiver_id, request.status.clone(), now)
+        .await
+    {
+        Ok(HeartbeatWrite::Recorded) => {
+            info!(
+                user_id = %user.user_id(),
+                receiver_id = %receiver_id,
+                "Receiver heartbeat recorded"
+            );
+            Ok(Json(ReceiverHeartbeatResponse {
+                last_heartbeat_at: Some(now),
+                status: request.status,
+            }))
+        }
+        Ok(HeartbeatWrite::Throttled { last_heartbeat_at }) => {
+            let wait = handler.heartbeat_retry_after(last_heartbeat_at, now);
+            // Round up so a client honouring the header is never early
+            let seconds = (wait.num_milliseconds() + 999) / 1000;
+            let mut headers = HeaderMap::new();
+            headers.insert(header::RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+            Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                headers,
+                Json(ErrorResponse::new(
+                    "rate_limited".to_string(),
+                    format!(
+                        "Receiver {} already sent a heartbeat at {}",
+                        receiver_id, last_heartbeat_at
+                    ),
+                )),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to record heartbeat for {}: {}", receiver_id, e);
+            Err((
+                e.status_code(),
+                HeaderMap::new(),
+                Json(ErrorResponse::from_error(
+                    "heartbeat_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/history.rs
+
+//! Receivers and groups as they were at a past instant
+//!
+//! Reads with `?as_of=<RFC 3339 timestamp>` return the state recorded by
+//! the latest write at or before that instant instead of the current
+//! state. Every such response carries an `as_of` field holding the
+//! instant, so historical data cannot be mistaken for current data. A
+//! resource that did not exist yet, or had been deleted, at the instant is
+//! `404 NOT_FOUND`.
+
+use axum::{http::StatusCode, response::Json};
+use chrono::{DateTime, Utc};
+use tracing::{error, info, warn};
+
+use crate::api::field_access::FieldAccess;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, EventReceiverGroupResponse, EventReceiverResponse};
+use crate::api::rest::fields::{FieldSelection, Sparse};
+use crate::api::rest::timestamp::{format_utc, Timestamp};
+use crate::application::handlers::ResourceHistoryHandler;
+use crate::domain::repositories::resource_history_repo::GroupState;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::Error;
+
+type HistoryError = (StatusCode, Json<ErrorResponse>);
+
+/// Parses the `as_of` query parameter
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - The value is not an RFC 3339 timestamp with an
+///   offset
+pub fn parse_as_of(raw: Option<&str>) -> Result<Option<DateTime<Utc>>, HistoryError> {
+    raw.map(|raw| {
+        Timestamp::parse("as_of", raw)
+            .map(Timestamp::utc)
+            .map_err(|e| {
+                warn!("Invalid as_of timestamp: {}", e);
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse::from_domain(
+                        "validation_error".to_string(),
+                        &e,
+                    )),
+                )
+            })
+    })
+    .transpose()
+}
+
+/// Returns `history`, or `503 SERVICE_UNAVAILABLE` if none is attached
+pub fn require_history(
+    history: Option<&ResourceHistoryHandler>,
+) -> Result<&ResourceHistoryHandler, HistoryError> {
+    history.ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "history_unavailable".to_string(),
+                "Resource history is not configured".to_string(),
+            )),
+        )
+    })
+}
+
+/// Returns a receiver as it was at `instant`
+///
+/// The schema is redacted as on current reads. Activity, heartbeats, and
+/// the schema source describe the present and are left out.
+pub async fn receiver_as_of(
+    history: &ResourceHistoryHandler,
+    user: Option<&AuthenticatedUser>,
+    receiver_id: EventReceiverId,
+    instant: DateTime<Utc>,
+    fields: &FieldSelection,
+) -> Result<Json<Sparse<EventReceiverResponse>>, HistoryError> {
+    info!(receiver_id = %receiver_id, as_of = %instant, "Reconstructing event receiver");
+
+    match history.receiver_as_of(receiver_id, instant).await {
+        Ok(Some(receiver)) => {
+            let access = FieldAccess::for_user(user);
+            Ok(Json(fields.apply(
+                EventReceiverResponse::for_caller(receiver, &access).with_as_of(instant),
+            )))
+        }
+        Ok(None) => Err(not_found_at("Event receiver", instant)),
+        Err(e) => Err(retrieval_failed("receiver_retrieval_failed", &e)),
+    }
+}
+
+/// Returns a group and its unexpired members as they were at `instant`
+pub async fn group_as_of(
+    history: &ResourceHistoryHandler,
+    group_id: EventReceiverGroupId,
+    instant: DateTime<Utc>,
+) -> Result<GroupState, HistoryError> {
+    info!(group_id = %group_id, as_of = %instant, "Reconstructing event receiver group");
+
+    match history.group_as_of(group_id, instant).await {
+        Ok(Some(state)) => Ok(state),
+        Ok(None) => Err(not_found_at("Event receiver group", instant)),
+        Err(e) => Err(retrieval_failed("group_retrieval_failed", &e)),
+    }
+}
+
+/// Returns a group as it was at `instant`, trimmed to `fields`
+pub async fn group_response_as_of(
+    history: &ResourceHistoryHandler,
+    group_id: EventReceiverGroupId,
+    instant: DateTime<Utc>,
+    fields: &FieldSelection,
+) -> Result<Json<Sparse<EventReceiverGroupResponse>>, HistoryError> {
+    let state = group_as_of(history, group_id, instant).await?;
+    Ok(Json(fields.apply(
+        EventReceiverGroupResponse::from(state.group).with_as_of(instant),
+    )))
+}
+
+fn not_found_at(resource: &str, instant: DateTime<Utc>) -> HistoryError {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse::new(
+            "not_found".to_string(),
+            format!("{} did not exist at {}", resource, format_utc(&instant)),
+        )),
+    )
+}
+
+fn retrieval_failed(code: &str, e: &Error) -> HistoryError {
+    error!("Failed to reconstruct resource history: {}", e);
+    (
+        e.status_code(),
+        Json(ErrorResponse::from_error(code.to_string(), e)),
+    )
+}
@@ -0,0 +1,113 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/jobs.rs
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, JobStatusResponse, JobsResponse};
+use crate::api::rest::events::AppState;
+use crate::infrastructure::jobs::{JobRunner, JobTriggerError};
+
+/// Role required to inspect and run background jobs
+const JOBS_ROLE: &str = "admin";
+
+type JobsError = (StatusCode, Json<ErrorResponse>);
+
+/// Lists background jobs with their schedule and last run
+///
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `503 SERVICE_UNAVAILABLE` - No job runner is configured
+pub async fn list_jobs(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<JobsResponse>, JobsError> {
+    let runner = authorize(&state, &user, "list")?;
+
+    Ok(Json(JobsResponse {
+        jobs: runner
+            .statuses()
+            .into_iter()
+            .map(JobStatusResponse::from)
+            .collect(),
+    }))
+}
+
+/// Runs a background job now
+///
+/// Responds once the run has finished, with the job's status. A failed run
+/// is reported in `last_outcome` and `last_error`, not as an error status.
+/// Every call is recorded in the audit log. Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `404 NOT_FOUND` - No job with this name is registered
+/// * `409 CONFLICT` - The job is disabled in the settings
+/// * `503 SERVICE_UNAVAILABLE` - No job runner is configured
+pub async fn run_job(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(name): Path<String>,
+) -> Result<Json<JobStatusResponse>, JobsError> {
+    let runner = authorize(&state, &user, "run")?;
+
+    info!(user_id = %user.user_id(), job = %name, "Running background job on demand");
+
+    match runner.trigger(&name, user.user_id()).await {
+        Ok(status) => Ok(Json(JobStatusResponse::from(status))),
+        Err(e @ JobTriggerError::UnknownJob(_)) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new("not_found".to_string(), e.to_string())),
+        )),
+        Err(e @ JobTriggerError::Disabled(_)) => Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse::new(
+                "job_disabled".to_string(),
+                e.to_string(),
+            )),
+        )),
+    }
+}
+
+fn authorize(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    action: &str,
+) -> Result<Arc<JobRunner>, JobsError> {
+    if !user.has_role(JOBS_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            action,
+            "Job request denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    state.jobs.clone().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "jobs_unavailable".to_string(),
+                "Background jobs are not configured".to_string(),
+            )),
+        )
+    })
+}
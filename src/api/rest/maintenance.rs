@@ -0,0 +1,111 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/maintenance.rs
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, MaintenanceResponse, UpdateMaintenanceRequest};
+use crate::api::rest::events::AppState;
+
+/// Role required to read and change maintenance mode
+const MAINTENANCE_ROLE: &str = "admin";
+
+type MaintenanceError = (StatusCode, Json<ErrorResponse>);
+
+/// Returns the maintenance mode state
+///
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+pub async fn get_maintenance(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<MaintenanceResponse>, MaintenanceError> {
+    require_admin(&user)?;
+    Ok(Json(state.maintenance.current().into()))
+}
+
+/// Turns read-only maintenance mode on or off
+///
+/// While on, writes are rejected with `503 Service Unavailable` and the
+/// code `MAINTENANCE_MODE`. Sending `enabled: true` again updates the
+/// message and scheduled end. The change is persisted, reaches the other
+/// instances on their next refresh, and is recorded in the audit log.
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Message too long or `ends_at` not in the future
+/// * `403 FORBIDDEN` - Caller is not an administrator
+pub async fn update_maintenance(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<UpdateMaintenanceRequest>,
+) -> Result<Json<MaintenanceResponse>, MaintenanceError> {
+    require_admin(&user)?;
+
+    request.validate(Utc::now()).map_err(|e| {
+        warn!("Maintenance mode validation failed: {}", e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )
+    })?;
+
+    info!(
+        user_id = %user.user_id(),
+        enabled = request.enabled,
+        "Updating maintenance mode"
+    );
+
+    let result = if request.enabled {
+        state
+            .maintenance
+            .enter(request.message(), request.ends_at, user.user_id())
+            .await
+            .map(Some)
+    } else {
+        state.maintenance.exit(user.user_id()).await.map(|()| None)
+    };
+
+    match result {
+        Ok(window) => Ok(Json(window.into())),
+        Err(e) => {
+            error!("Failed to update maintenance mode: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "maintenance_update_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+fn require_admin(user: &AuthenticatedUser) -> Result<(), MaintenanceError> {
+    if user.has_role(MAINTENANCE_ROLE) {
+        return Ok(());
+    }
+
+    warn!(
+        user_id = %user.user_id(),
+        "Maintenance mode request denied: admin role required"
+    );
+    Err((
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse::new(
+            "forbidden".to_string(),
+            "Admin role required".to_string(),
+        )),
+    ))
+}
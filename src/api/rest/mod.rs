@@ -3,19 +3,58 @@
 
 // src/api/rest/mod.rs
 
+pub mod about;
+pub mod api_keys;
+pub mod attachments;
+pub mod attestations;
+pub mod audit;
 pub mod auth;
+pub mod batch;
+pub mod bulk_delete;
+pub mod changes;
+pub mod completeness;
+pub mod config_reload;
+pub mod debug;
+pub mod delivery_failures;
+pub mod deprecations;
+pub mod descriptions;
+pub mod diagnose;
+pub mod diff;
 pub mod dtos;
 pub mod events;
+pub mod export;
+pub mod fields;
+pub mod flags;
 pub mod group_membership;
+pub mod health;
+pub mod heartbeat;
+pub mod history;
+pub mod jobs;
+pub mod maintenance;
+pub mod persisted_queries;
+pub mod pii_report;
+pub mod poll;
+pub mod preferences;
+pub mod rbac_matrix;
 pub mod routes;
+pub mod schema_preview;
+pub mod search;
+pub mod sessions;
+pub mod summary;
+pub mod timeline;
+pub mod timestamp;
+pub mod user_import;
 
 pub use auth::{AuthState, LoginRequest, LoginResponse, RefreshRequest};
 pub use dtos::*;
 pub use events::AppState;
 pub use group_membership::{
-    add_group_member, list_group_members, remove_group_member, GroupMembershipState,
+    add_group_member, list_group_members, remove_group_member, update_group_member,
+    GroupMembershipState,
+};
+pub use routes::{
+    build_protected_router, build_protected_router_with, build_router, ApiSubsystems,
 };
-pub use routes::{build_protected_router, build_router};
 
 /// Re-export common types for convenience
 pub use axum::{
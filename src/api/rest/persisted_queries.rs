@@ -0,0 +1,162 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/persisted_queries.rs
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info, warn};
+
+use crate::api::graphql::PersistedQueryStore;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    ErrorResponse, PersistedQueriesResponse, PersistedQueryResponse, RegisterPersistedQueryRequest,
+};
+use crate::api::rest::events::AppState;
+use crate::error::{DomainError, Error};
+
+/// Role required to manage persisted queries
+const PERSISTED_QUERIES_ROLE: &str = "admin";
+
+type PersistedQueryError = (StatusCode, Json<ErrorResponse>);
+
+/// Lists the registered GraphQL persisted queries
+///
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `503 SERVICE_UNAVAILABLE` - Persisted queries are not configured
+pub async fn list_persisted_queries(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<PersistedQueriesResponse>, PersistedQueryError> {
+    let store = authorize(&state, &user, "list")?;
+
+    let queries = store.list_registered().await.map_err(storage_error)?;
+    Ok(Json(PersistedQueriesResponse {
+        queries: queries
+            .into_iter()
+            .map(PersistedQueryResponse::from)
+            .collect(),
+    }))
+}
+
+/// Registers a GraphQL persisted query
+///
+/// The query text must parse. It is stored under its SHA-256 hash, which
+/// the response returns; in persisted-only mode it is then executable by
+/// every caller. Registering the same text again returns the existing
+/// registration. Requires the admin role.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - The query is not valid GraphQL
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `503 SERVICE_UNAVAILABLE` - Persisted queries are not configured
+pub async fn register_persisted_query(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<RegisterPersistedQueryRequest>,
+) -> Result<(StatusCode, Json<PersistedQueryResponse>), PersistedQueryError> {
+    let store = authorize(&state, &user, "register")?;
+
+    match store
+        .register(request.query, Some(user.user_id().to_string()))
+        .await
+    {
+        Ok(query) => {
+            info!(
+                user_id = %user.user_id(),
+                hash = %query.hash,
+                "Registered GraphQL persisted query"
+            );
+            Ok((StatusCode::CREATED, Json(query.into())))
+        }
+        Err(e @ Error::Domain(DomainError::ValidationError { .. })) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_error(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )),
+        Err(e) => Err(storage_error(e)),
+    }
+}
+
+/// Removes a GraphQL persisted query
+///
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `404 NOT_FOUND` - No query is stored under the hash
+/// * `503 SERVICE_UNAVAILABLE` - Persisted queries are not configured
+pub async fn delete_persisted_query(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(hash): Path<String>,
+) -> Result<StatusCode, PersistedQueryError> {
+    let store = authorize(&state, &user, "delete")?;
+
+    let hash = hash.to_ascii_lowercase();
+    if !store.unregister(&hash).await.map_err(storage_error)? {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                "not_found".to_string(),
+                format!("No persisted query has hash {}", hash),
+            )),
+        ));
+    }
+
+    info!(user_id = %user.user_id(), hash = %hash, "Removed GraphQL persisted query");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn authorize(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    action: &str,
+) -> Result<PersistedQueryStore, PersistedQueryError> {
+    if !user.has_role(PERSISTED_QUERIES_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            action,
+            "Persisted query request denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    state.persisted_queries.clone().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "persisted_queries_unavailable".to_string(),
+                "GraphQL persisted queries are not configured".to_string(),
+            )),
+        )
+    })
+}
+
+fn storage_error(e: Error) -> PersistedQueryError {
+    error!("Persisted query request failed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ErrorResponse::new(
+            "internal_error".to_string(),
+            "Persisted query request failed".to_string(),
+        )),
+    )
+}
@@ -0,0 +1,87 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/pii_report.rs
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, PiiReportResponse};
+use crate::api::rest::events::{authorize, AppState};
+use crate::application::authorization::{ProtectedResource, ResourceAction};
+use crate::application::handlers::event_handler::PII_REPORT_WINDOW_DAYS;
+use crate::domain::value_objects::EventReceiverId;
+
+type PiiReportError = (StatusCode, Json<ErrorResponse>);
+
+/// Returns the PII findings of a receiver's events over the last week
+///
+/// Findings are grouped by detector and JSON path, most frequent first, so
+/// teams can see which producer fields carry personal data. Detected values
+/// are never returned. The report is empty while `ingestion.pii_detection`
+/// is `off`.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid receiver id
+/// * `403 FORBIDDEN` - The caller may not read the receiver
+/// * `404 NOT_FOUND` - The receiver does not exist
+pub async fn get_receiver_pii_report(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+) -> Result<Json<PiiReportResponse>, PiiReportError> {
+    let receiver_id = id_str.parse::<EventReceiverId>().map_err(|_| {
+        warn!("Invalid event receiver ID format: {}", id_str);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid event receiver ID format".to_string(),
+            )),
+        )
+    })?;
+
+    state
+        .event_receiver_handler
+        .get_event_receiver_or_error(receiver_id)
+        .await
+        .map_err(|e| {
+            (
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "receiver_retrieval_failed".to_string(),
+                    &e,
+                )),
+            )
+        })?;
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Read,
+        ProtectedResource::Receiver(receiver_id),
+    )
+    .await?;
+
+    info!(receiver_id = %receiver_id, "Reading receiver PII report");
+    let since = Utc::now() - chrono::Duration::days(PII_REPORT_WINDOW_DAYS);
+    match state.event_handler.pii_report(receiver_id, since).await {
+        Ok(report) => Ok(Json(PiiReportResponse::new(receiver_id, since, report))),
+        Err(e) => {
+            error!("Failed to read PII report of {}: {}", receiver_id, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "pii_report_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/poll.rs
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+use crate::api::field_access::FieldAccess;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    ErrorResponse, EventPollQueryParams, EventPollResponse, EventResponse,
+    MaintenanceNoticeResponse,
+};
+use crate::api::rest::events::AppState;
+use crate::domain::entities::ingestion_meta::PrincipalType;
+
+/// Long-polls for events stored after a cursor
+///
+/// Returns at once with up to one batch of events newer than `cursor`.
+/// When there are none, the request is held open until a matching event is
+/// stored or `timeout_seconds` pass, capped by the configured maximum, and
+/// then returns an empty batch with `next_cursor` unchanged. Omitting
+/// `cursor` starts from the first event. Closing the connection ends the
+/// wait and frees the caller's slot. While maintenance mode is on, responses
+/// carry a `maintenance` notice; events are not ingested until it ends.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid `cursor` or `receiver_id`
+/// * `429 TOO_MANY_REQUESTS` - Caller already holds the maximum number of
+///   open polls
+/// * `503 SERVICE_UNAVAILABLE` - Long-polling is not configured
+pub async fn poll_events(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(params): Query<EventPollQueryParams>,
+) -> Result<Json<EventPollResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(handler) = &state.event_poll_handler else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "poll_unavailable".to_string(),
+                "Event long-polling is not configured".to_string(),
+            )),
+        ));
+    };
+
+    let (cursor, receiver_id) = params
+        .cursor()
+        .and_then(|cursor| {
+            params
+                .receiver_id()
+                .map(|receiver_id| (cursor, receiver_id))
+        })
+        .map_err(|e| {
+            warn!("Event poll validation failed: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_domain(
+                    "validation_error".to_string(),
+                    &e,
+                )),
+            )
+        })?;
+
+    let principal = format!("{}:{}", PrincipalType::User, user.user_id());
+    // Dropped with the request future, including on client disconnect
+    let Some(_permit) = handler.try_acquire(&principal) else {
+        warn!(principal = %principal, "Event poll rejected: too many open polls");
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ErrorResponse::new(
+                "too_many_polls".to_string(),
+                "Too many open polls for this caller".to_string(),
+            )),
+        ));
+    };
+
+    let timeout = params
+        .timeout_seconds
+        .map_or(handler.max_timeout(), Duration::from_secs);
+    debug!(
+        principal = %principal,
+        cursor = ?cursor,
+        receiver_id = ?receiver_id,
+        timeout_ms = timeout.as_millis() as u64,
+        "Polling for new events"
+    );
+
+    match handler.poll(cursor, receiver_id, timeout).await {
+        Ok(batch) => {
+            let access = FieldAccess::for_user(Some(&user));
+            Ok(Json(EventPollResponse {
+                events: batch
+                    .events
+                    .into_iter()
+                    .map(|event| EventResponse::for_caller(event, &access))
+                    .collect(),
+                next_cursor: batch.next_cursor,
+                maintenance: state
+                    .maintenance
+                    .current()
+                    .map(MaintenanceNoticeResponse::from),
+            }))
+        }
+        Err(e) => {
+            error!("Failed to poll for events: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error("poll_failed".to_string(), &e)),
+            ))
+        }
+    }
+}
@@ -0,0 +1,151 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/preferences.rs
+
+use axum::{
+    extract::{FromRef, FromRequestParts, State},
+    http::{request::Parts, StatusCode},
+    response::Json,
+};
+use serde_json::Value as JsonValue;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::ErrorResponse;
+use crate::api::rest::events::AppState;
+use crate::application::handlers::UserPreferencesHandler;
+use crate::domain::entities::user_preferences::UserPreferences;
+
+/// The calling user's preferences, loaded once per request
+///
+/// The first extraction reads from the repository and stores the result in
+/// the request extensions; later extractions in the same request reuse it.
+/// Nothing outlives the request, so preference updates apply immediately.
+/// Anonymous requests, and requests whose preferences cannot be loaded, get
+/// empty preferences so that a storage problem never fails the request.
+#[derive(Debug, Clone, Default)]
+pub struct RequestPreferences(pub Arc<UserPreferences>);
+
+impl RequestPreferences {
+    /// Loads preferences for `user` without going through an extractor
+    pub async fn load(handler: &UserPreferencesHandler, user: Option<&AuthenticatedUser>) -> Self {
+        let user = match user {
+            Some(user) => user,
+            None => return Self::default(),
+        };
+
+        match handler.get_preferences(user.user_id()).await {
+            Ok(preferences) => Self(Arc::new(preferences)),
+            Err(e) => {
+                error!(
+                    user_id = %user.user_id(),
+                    "Failed to load user preferences, using defaults: {}", e
+                );
+                Self::default()
+            }
+        }
+    }
+
+    pub fn preferences(&self) -> &UserPreferences {
+        &self.0
+    }
+}
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for RequestPreferences
+where
+    UserPreferencesHandler: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(cached) = parts.extensions.get::<RequestPreferences>() {
+            return Ok(cached.clone());
+        }
+
+        let handler = UserPreferencesHandler::from_ref(state);
+        let user = parts.extensions.get::<AuthenticatedUser>().cloned();
+        let preferences = Self::load(&handler, user.as_ref()).await;
+
+        parts.extensions.insert(preferences.clone());
+        Ok(preferences)
+    }
+}
+
+/// Returns the caller's full preference document
+///
+/// Every known key is present; unset keys are `null`.
+pub async fn get_my_preferences(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<JsonValue>, (StatusCode, Json<ErrorResponse>)> {
+    info!(user_id = %user.user_id(), "Getting user preferences");
+
+    match state
+        .user_preferences_handler
+        .get_preferences(user.user_id())
+        .await
+    {
+        Ok(preferences) => Ok(Json(preferences.to_document())),
+        Err(e) => {
+            error!("Failed to get user preferences: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "preferences_retrieval_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Merges a preference document into the caller's preferences
+///
+/// Keys in the body replace stored values and `null` clears a key; keys not
+/// in the body are kept. Returns the full document after the merge.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Unknown key or invalid value; nothing is saved
+pub async fn update_my_preferences(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(document): Json<JsonValue>,
+) -> Result<Json<JsonValue>, (StatusCode, Json<ErrorResponse>)> {
+    info!(user_id = %user.user_id(), "Updating user preferences");
+
+    match state
+        .user_preferences_handler
+        .update_preferences(user.user_id(), &document)
+        .await
+    {
+        Ok(preferences) => Ok(Json(preferences.to_document())),
+        Err(e) => {
+            let status = e.status_code();
+            if status == StatusCode::BAD_REQUEST {
+                warn!("User preference validation failed: {}", e);
+                Err((
+                    status,
+                    Json(ErrorResponse::from_error(
+                        "validation_error".to_string(),
+                        &e,
+                    )),
+                ))
+            } else {
+                error!("Failed to update user preferences: {}", e);
+                Err((
+                    status,
+                    Json(ErrorResponse::from_error(
+                        "preferences_update_failed".to_string(),
+                        &e,
+                    )),
+                ))
+            }
+        }
+    }
+}
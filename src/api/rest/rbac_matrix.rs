@@ -0,0 +1,321 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/rbac_matrix.rs
+
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::middleware::rbac_helpers::{
+    GraphQLOperationRule, RouteAccess, RouteRule, GRAPHQL_OPERATION_RULES, ROUTE_RULES,
+};
+use crate::api::rest::dtos::ErrorResponse;
+use crate::api::rest::export::csv_field;
+use crate::auth::rbac::Role;
+
+/// Role required to read the RBAC matrix
+const RBAC_MATRIX_ROLE: &str = "admin";
+
+/// Media type selecting the CSV rendering
+const CSV_MEDIA_TYPE: &str = "text/csv";
+
+/// Effective access of every route and GraphQL operation for every role
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RbacMatrix {
+    /// Roles the matrix is evaluated for, in column order
+    pub roles: Vec<String>,
+    /// One entry per REST route and GraphQL operation
+    pub entries: Vec<RbacMatrixEntry>,
+}
+
+/// Declared access of one route or operation and the roles it admits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RbacMatrixEntry {
+    /// `rest` or `graphql`
+    pub interface: &'static str,
+    /// HTTP method, or `query` or `mutation` for GraphQL
+    pub operation: String,
+    /// Route pattern, or GraphQL field name
+    pub target: String,
+    /// Whether a caller must be authenticated
+    pub authenticated: bool,
+    /// Permission the caller must hold, if any
+    pub required_permission: Option<String>,
+    /// Role the caller must hold, if any
+    pub required_role: Option<String>,
+    /// Resource-level check applied on top, if any
+    pub resource_check: Option<String>,
+    /// Roles admitted by the declaration, in the order of `roles`
+    pub allowed_roles: Vec<String>,
+}
+
+impl RbacMatrix {
+    /// Builds the matrix from the declared route and operation rules,
+    /// cross-joined with every built-in role
+    pub fn build() -> Self {
+        let entries = ROUTE_RULES
+            .iter()
+            .map(rest_entry)
+            .chain(GRAPHQL_OPERATION_RULES.iter().map(graphql_entry))
+            .collect();
+        Self {
+            roles: Role::ALL.iter().map(Role::to_string).collect(),
+            entries,
+        }
+    }
+
+    /// Renders the matrix as CSV with one `true`/`false` column per role
+    pub fn to_csv(&self) -> String {
+        let mut header = vec![
+            "interface",
+            "operation",
+            "target",
+            "authenticated",
+            "required_permission",
+            "required_role",
+            "resource_check",
+        ];
+        header.extend(self.roles.iter().map(String::as_str));
+        let mut csv = header.join(",");
+        csv.push('\n');
+
+        for entry in &self.entries {
+            let mut row = vec![
+                entry.interface.to_string(),
+                csv_field(&entry.operation),
+                csv_field(&entry.target),
+                entry.authenticated.to_string(),
+                csv_field(entry.required_permission.as_deref().unwrap_or_default()),
+                csv_field(entry.required_role.as_deref().unwrap_or_default()),
+                csv_field(entry.resource_check.as_deref().unwrap_or_default()),
+            ];
+            row.extend(
+                self.roles
+                    .iter()
+                    .map(|role| entry.allowed_roles.contains(role).to_string()),
+            );
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
+
+fn rest_entry(rule: &RouteRule) -> RbacMatrixEntry {
+    RbacMatrixEntry {
+        interface: "rest",
+        operation: rule.method.to_string(),
+        target: rule.path.to_string(),
+        authenticated: rule.access != RouteAccess::Public,
+        required_permission: rule.access.permission().map(|p| format!("{:?}", p)),
+        required_role: rule.role.map(String::from),
+        resource_check: None,
+        allowed_roles: allowed_roles(rule.access, rule.role),
+    }
+}
+
+fn graphql_entry(rule: &GraphQLOperationRule) -> RbacMatrixEntry {
+    RbacMatrixEntry {
+        interface: "graphql",
+        operation: rule.kind.to_string(),
+        target: rule.field.to_string(),
+        authenticated: rule.access != RouteAccess::Public,
+        required_permission: rule.access.permission().map(|p| format!("{:?}", p)),
+        required_role: None,
+        resource_check: rule.resource_check.map(String::from),
+        allowed_roles: allowed_roles(rule.access, None),
+    }
+}
+
+/// Returns the built-in roles that satisfy `access` and `role`
+fn allowed_roles(access: RouteAccess, role: Option<&str>) -> Vec<String> {
+    Role::ALL
+        .iter()
+        .filter(|candidate| role.is_none_or(|role| candidate.to_string() == role))
+        .filter(|candidate| match access {
+            RouteAccess::Permission(permission) => candidate.has_permission(&permission),
+            RouteAccess::Public | RouteAccess::Authenticated => true,
+        })
+        .map(Role::to_string)
+        .collect()
+}
+
+/// Returns true if the `Accept` header prefers CSV over other formats
+fn prefers_csv(accept: &str) -> bool {
+    let mut best: Option<(f32, &str)> = None;
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let media_type = params.next().unwrap_or_default().trim();
+        if media_type.is_empty() {
+            continue;
+        }
+        let quality = params
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if quality > 0.0 && best.is_none_or(|(best, _)| quality > best) {
+            best = Some((quality, media_type));
+        }
+    }
+
+    best.is_some_and(|(_, media_type)| media_type.eq_ignore_ascii_case(CSV_MEDIA_TYPE))
+}
+
+/// Exports the effective RBAC matrix
+///
+/// Lists every REST route and GraphQL operation with the permission and
+/// role it declares and the built-in roles that satisfy them. Returns CSV
+/// when the `Accept` header prefers `text/csv`, and JSON otherwise.
+/// Requires the admin role.
+///
+/// The matrix describes [`build_protected_router`](super::build_protected_router),
+/// which enforces every rule in it. The `xzepr` server binary enforces it
+/// only on its administration and API key routes.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+pub async fn get_rbac_matrix(
+    headers: HeaderMap,
+    user: AuthenticatedUser,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    if !user.has_role(RBAC_MATRIX_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "RBAC matrix request denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    info!(user_id = %user.user_id(), "Exporting RBAC matrix");
+
+    let matrix = RbacMatrix::build();
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if prefers_csv(accept) {
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"rbac-matrix.csv\"",
+                ),
+            ],
+            matrix.to_csv(),
+        )
+            .into_response());
+    }
+
+    Ok(Json(matrix).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry<'a>(matrix: &'a RbacMatrix, operation: &str, target: &str) -> &'a RbacMatrixEntry {
+        matrix
+            .entries
+            .iter()
+            .find(|e| e.operation == operation && e.target == target)
+            .unwrap_or_else(|| panic!("{} {} missing from the matrix", operation, target))
+    }
+
+    #[test]
+    fn test_matrix_covers_every_declared_rule() {
+        let matrix = RbacMatrix::build();
+        assert_eq!(
+            matrix.entries.len(),
+            ROUTE_RULES.len() + GRAPHQL_OPERATION_RULES.len()
+        );
+        assert_eq!(
+            matrix.roles,
+            vec!["admin", "event_manager", "event_viewer", "user"]
+        );
+    }
+
+    #[test]
+    fn test_matrix_cross_joins_permissions_with_roles() {
+        let matrix = RbacMatrix::build();
+
+        let delete = entry(&matrix, "DELETE", "/api/v1/receivers/:id");
+        assert_eq!(
+            delete.required_permission.as_deref(),
+            Some("ReceiverDelete")
+        );
+        assert_eq!(delete.allowed_roles, vec!["admin"]);
+
+        let read = entry(&matrix, "GET", "/api/v1/events/:id");
+        assert_eq!(read.allowed_roles.len(), Role::ALL.len());
+
+        let about = entry(&matrix, "GET", "/api/v1/admin/about");
+        assert_eq!(about.required_role.as_deref(), Some("admin"));
+        assert_eq!(about.allowed_roles, vec!["admin"]);
+
+        let health = entry(&matrix, "GET", "/health");
+        assert!(!health.authenticated);
+    }
+
+    #[test]
+    fn test_matrix_group_keys_need_their_own_permission() {
+        let matrix = RbacMatrix::build();
+        for (operation, target) in [
+            ("POST", "/api/v1/groups/:id/keys"),
+            ("GET", "/api/v1/groups/:id/keys"),
+            ("DELETE", "/api/v1/groups/:id/keys/:key_id"),
+        ] {
+            let keys = entry(&matrix, operation, target);
+            assert_eq!(keys.required_permission.as_deref(), Some("GroupManageKeys"));
+            assert_eq!(keys.allowed_roles, vec!["admin", "event_manager"]);
+        }
+    }
+
+    #[test]
+    fn test_matrix_lists_graphql_resource_checks() {
+        let matrix = RbacMatrix::build();
+        let update = entry(&matrix, "mutation", "updateEventReceiverGroup");
+        assert_eq!(update.interface, "graphql");
+        assert!(update.authenticated);
+        assert_eq!(update.resource_check.as_deref(), Some("update group"));
+        assert!(!entry(&matrix, "query", "events").authenticated);
+    }
+
+    #[test]
+    fn test_matrix_csv_has_a_column_per_role() {
+        let matrix = RbacMatrix::build();
+        let csv = matrix.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "interface,operation,target,authenticated,required_permission,required_role,\
+             resource_check,admin,event_manager,event_viewer,user"
+        );
+        assert!(csv.contains(
+            "rest,DELETE,/api/v1/receivers/:id,true,ReceiverDelete,,,true,false,false,false\n"
+        ));
+        assert_eq!(lines.count(), matrix.entries.len());
+    }
+
+    #[test]
+    fn test_prefers_csv() {
+        assert!(prefers_csv("text/csv"));
+        assert!(prefers_csv("application/json;q=0.5, text/csv"));
+        assert!(!prefers_csv("application/json"));
+        assert!(!prefers_csv("*/*"));
+        assert!(!prefers_csv(""));
+    }
+}
@@ -0,0 +1,157 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/schema_preview.rs
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, SchemaPreviewRequestBody, SchemaPreviewResponse};
+use crate::api::rest::events::AppState;
+use crate::application::handlers::SchemaPreviewOutcome;
+use crate::domain::value_objects::{EventReceiverId, UserId};
+
+/// Role allowed to preview schemas on receivers it does not own
+const PREVIEW_ADMIN_ROLE: &str = "admin";
+
+/// Checks a candidate schema against events already stored for a receiver
+///
+/// The schema is not saved. Small ranges are scanned inline and answered
+/// with `200 OK` and a report; larger ranges return `202 ACCEPTED` with a
+/// job id to poll. Only the receiver owner or an admin may preview.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid receiver id, schema, or time range
+/// * `403 FORBIDDEN` - Caller neither owns the receiver nor is an admin
+/// * `404 NOT_FOUND` - Receiver does not exist
+pub async fn preview_receiver_schema(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+    Json(request): Json<SchemaPreviewRequestBody>,
+) -> Result<(StatusCode, Json<SchemaPreviewResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let receiver_id = authorize_preview(&state, &user, &id_str).await?;
+
+    info!(
+        user_id = %user.user_id(),
+        receiver_id = %receiver_id,
+        "Previewing receiver schema"
+    );
+
+    match state
+        .schema_preview_handler
+        .preview(receiver_id, request.into())
+        .await
+    {
+        Ok(SchemaPreviewOutcome::Completed(report)) => Ok((StatusCode::OK, Json(report.into()))),
+        Ok(SchemaPreviewOutcome::Started(job)) => Ok((StatusCode::ACCEPTED, Json(job.into()))),
+        Err(e) => {
+            error!("Failed to preview schema for {}: {}", receiver_id, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "schema_preview_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Returns the progress or result of a background schema preview
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid receiver id
+/// * `403 FORBIDDEN` - Caller neither owns the receiver nor is an admin
+/// * `404 NOT_FOUND` - Receiver or job does not exist, or the job expired
+pub async fn get_schema_preview_job(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((id_str, job_id)): Path<(String, String)>,
+) -> Result<Json<SchemaPreviewResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let receiver_id = authorize_preview(&state, &user, &id_str).await?;
+
+    match state.schema_preview_handler.job(&job_id) {
+        Some(job) if job.event_receiver_id == receiver_id => Ok(Json(job.into())),
+        _ => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                "preview_job_not_found".to_string(),
+                format!("Schema preview job {} not found", job_id),
+            )),
+        )),
+    }
+}
+
+/// Resolves the receiver and checks the caller owns it or is an admin
+async fn authorize_preview(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    id_str: &str,
+) -> Result<EventReceiverId, (StatusCode, Json<ErrorResponse>)> {
+    let receiver_id = id_str.parse::<EventReceiverId>().map_err(|_| {
+        warn!("Invalid event receiver ID format: {}", id_str);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid event receiver ID format".to_string(),
+            )),
+        )
+    })?;
+
+    let receiver = match state
+        .event_receiver_handler
+        .get_event_receiver(receiver_id)
+        .await
+    {
+        Ok(Some(receiver)) => receiver,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::new(
+                    "not_found".to_string(),
+                    "Event receiver not found".to_string(),
+                )),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to load event receiver {}: {}", receiver_id, e);
+            return Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "receiver_retrieval_failed".to_string(),
+                    &e,
+                )),
+            ));
+        }
+    };
+
+    let is_owner = user
+        .user_id()
+        .parse::<UserId>()
+        .is_ok_and(|user_id| receiver.owner_id() == user_id);
+    if !is_owner && !user.has_role(PREVIEW_ADMIN_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            receiver_id = %receiver_id,
+            "Schema preview denied: caller is not the owner or an admin"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Only the receiver owner or an admin may preview its schema".to_string(),
+            )),
+        ));
+    }
+
+    Ok(receiver_id)
+}
@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/search.rs
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, SearchQueryParams, SearchResponse};
+use crate::api::rest::events::AppState;
+use crate::domain::entities::search::SearchResourceType;
+use crate::domain::repositories::search_repo::SearchViewer;
+use crate::domain::value_objects::UserId;
+
+/// Returns what `user` may find, matching the read checks of the
+/// resource endpoints
+///
+/// Admins and callers holding a type's `read` permission find every
+/// resource of that type; everyone else finds the resources they own or
+/// reach through a current group membership.
+///
+/// # Errors
+///
+/// Returns an error if a non-admin user ID is not a valid ULID.
+pub fn search_viewer(user: &AuthenticatedUser) -> Result<SearchViewer, ulid::DecodeError> {
+    if user.has_role("admin") {
+        return Ok(SearchViewer::admin());
+    }
+
+    let user_id = user.user_id().parse::<UserId>()?;
+    Ok(SearchResourceType::ALL
+        .into_iter()
+        .filter(|resource_type| user.has_permission(&format!("{}:read", resource_type.kind())))
+        .fold(SearchViewer::new(user_id), SearchViewer::with_unrestricted))
+}
+
+/// Searches receivers, events, and groups by their text
+///
+/// `q` takes words, `"quoted phrases"`, and `prefix*` terms, all of which
+/// must match. Results are ranked across types, exact name matches first,
+/// and only include resources the caller could read through the resource
+/// endpoints.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - The query is too short, too long, or only has
+///   stop words, a type is unknown, or `limit` is out of range
+/// * `503 SERVICE_UNAVAILABLE` - Search is not configured
+pub async fn search(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(params): Query<SearchQueryParams>,
+) -> Result<Json<SearchResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let Some(handler) = state.search_handler.as_ref() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "search_unavailable".to_string(),
+                "Search is not configured".to_string(),
+            )),
+        ));
+    };
+
+    let types = SearchResourceType::parse_list(params.types.as_deref().unwrap_or_default())
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::from_domain(
+                    "validation_error".to_string(),
+                    &e,
+                )),
+            )
+        })?;
+    let viewer = search_viewer(&user).map_err(|e| {
+        error!("Invalid user ID in JWT token: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::new(
+                "internal_error".to_string(),
+                "Invalid user ID in authentication token".to_string(),
+            )),
+        )
+    })?;
+    let text = params.q.unwrap_or_default();
+
+    info!(user_id = %user.user_id(), query = %text, "Searching resources");
+
+    match handler.search(&text, &types, &viewer, params.limit).await {
+        Ok(results) => Ok(Json(SearchResponse::new(text.trim().to_string(), results))),
+        Err(e) => {
+            let status = e.status_code();
+            let code = if status == StatusCode::BAD_REQUEST {
+                "validation_error"
+            } else {
+                error!("Failed to search for {:?}: {}", text, e);
+                "search_failed"
+            };
+            Err((
+                status,
+                Json(ErrorResponse::from_error(code.to_string(), &e)),
+            ))
+        }
+    }
+}
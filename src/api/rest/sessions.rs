@@ -0,0 +1,214 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/sessions.rs
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    ErrorResponse, ImpersonateRequest, ImpersonationResponse, ListSessionsQuery, SessionResponse,
+    SessionsResponse,
+};
+use crate::api::rest::events::AppState;
+use crate::auth::jwt::Session;
+use crate::auth::sessions::{SessionError, SessionService, IMPERSONATION_ROLE};
+use crate::domain::value_objects::UserId;
+
+type SessionsError = (StatusCode, Json<ErrorResponse>);
+
+/// Issues a token authorizing as another user
+///
+/// The token carries the user's roles and permissions and names the caller
+/// as actor, so everything done with it is attributed to both. It cannot
+/// issue tokens, change passwords, or manage API keys. Requires the admin
+/// role; every impersonation is recorded in the audit log.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid user ID, missing reason, or duration out
+///   of range
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `404 NOT_FOUND` - No such user
+/// * `409 CONFLICT` - The user is disabled
+/// * `503 SERVICE_UNAVAILABLE` - Sessions are not configured
+pub async fn impersonate_user(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<ImpersonateRequest>,
+) -> Result<(StatusCode, Json<ImpersonationResponse>), SessionsError> {
+    let service = service(&state)?;
+    let target = request.user_id.parse::<UserId>().map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "validation_error".to_string(),
+                "Invalid user ID".to_string(),
+            )),
+        )
+    })?;
+    let duration = request.duration_seconds.map(chrono::Duration::seconds);
+
+    let issued = service
+        .impersonate(&user.claims, target, &request.reason, duration)
+        .await
+        .map_err(|e| session_error(&user, e))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ImpersonationResponse {
+            access_token: issued.token,
+            token_type: "Bearer".to_string(),
+            expires_in: (issued.session.expires_at - issued.session.issued_at).num_seconds(),
+            session: SessionResponse::from(issued.session),
+        }),
+    ))
+}
+
+/// Lists every session, or those of one user, newest first
+///
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `503 SERVICE_UNAVAILABLE` - Sessions are not configured
+pub async fn list_sessions(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListSessionsQuery>,
+) -> Result<Json<SessionsResponse>, SessionsError> {
+    let service = service(&state)?;
+    let sessions = service
+        .all_sessions(&user.claims)
+        .map_err(|e| session_error(&user, e))?;
+
+    Ok(match query.user_id {
+        Some(user_id) => sessions_response(
+            sessions
+                .into_iter()
+                .filter(|session| session.user_id == user_id),
+        ),
+        None => sessions_response(sessions),
+    })
+}
+
+/// Revokes any session
+///
+/// Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `404 NOT_FOUND` - No such session
+/// * `503 SERVICE_UNAVAILABLE` - Sessions are not configured
+pub async fn revoke_session(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<String>,
+) -> Result<Json<SessionResponse>, SessionsError> {
+    let service = service(&state)?;
+    if !user.has_role(IMPERSONATION_ROLE) {
+        return Err(session_error(&user, SessionError::NotAdmin));
+    }
+
+    let session = service
+        .revoke(&user.claims, &id)
+        .await
+        .map_err(|e| session_error(&user, e))?;
+    Ok(Json(SessionResponse::from(session)))
+}
+
+/// Lists the caller's sessions, newest first
+///
+/// Includes sessions in which an administrator impersonated the caller,
+/// with the administrator and the stated reason.
+///
+/// # Errors
+///
+/// * `503 SERVICE_UNAVAILABLE` - Sessions are not configured
+pub async fn list_my_sessions(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<SessionsResponse>, SessionsError> {
+    let service = service(&state)?;
+    Ok(sessions_response(service.sessions_for(user.user_id())))
+}
+
+/// Revokes one of the caller's sessions
+///
+/// The caller may end an impersonation of them this way.
+///
+/// # Errors
+///
+/// * `404 NOT_FOUND` - No such session among the caller's
+/// * `503 SERVICE_UNAVAILABLE` - Sessions are not configured
+pub async fn revoke_my_session(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<String>,
+) -> Result<Json<SessionResponse>, SessionsError> {
+    let service = service(&state)?;
+    let owned = service
+        .sessions_for(user.user_id())
+        .iter()
+        .any(|session| session.id == id);
+    if !owned {
+        return Err(session_error(&user, SessionError::SessionNotFound));
+    }
+
+    let session = service
+        .revoke(&user.claims, &id)
+        .await
+        .map_err(|e| session_error(&user, e))?;
+    Ok(Json(SessionResponse::from(session)))
+}
+
+fn sessions_response(sessions: impl IntoIterator<Item = Session>) -> Json<SessionsResponse> {
+    Json(SessionsResponse {
+        sessions: sessions.into_iter().map(SessionResponse::from).collect(),
+    })
+}
+
+fn service(state: &AppState) -> Result<Arc<SessionService>, SessionsError> {
+    state.session_service.clone().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "sessions_unavailable".to_string(),
+                "Sessions are not configured".to_string(),
+            )),
+        )
+    })
+}
+
+fn session_error(user: &AuthenticatedUser, error: SessionError) -> SessionsError {
+    let (status, code) = match &error {
+        SessionError::NotAdmin | SessionError::Impersonating => {
+            (StatusCode::FORBIDDEN, "forbidden")
+        }
+        SessionError::ReasonRequired
+        | SessionError::InvalidDuration { .. }
+        | SessionError::SelfImpersonation => (StatusCode::BAD_REQUEST, "validation_error"),
+        SessionError::UserNotFound | SessionError::SessionNotFound => {
+            (StatusCode::NOT_FOUND, "not_found")
+        }
+        SessionError::UserDisabled => (StatusCode::CONFLICT, "user_disabled"),
+        SessionError::Repository(_) | SessionError::Token(_) => {
+            (StatusCode::INTERNAL_SERVER_ERROR, "internal_error")
+        }
+    };
+    if status == StatusCode::FORBIDDEN || status == StatusCode::INTERNAL_SERVER_ERROR {
+        warn!(user_id = %user.user_id(), error = %error, "Session request failed");
+    }
+    (
+        status,
+        Json(ErrorResponse::new(code.to_string(), error.to_string())),
+    )
+}
@@ -0,0 +1,72 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/summary.rs
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{ErrorResponse, SummaryResponse};
+use crate::api::rest::events::AppState;
+
+/// Role required to read the admin summary
+const SUMMARY_ROLE: &str = "admin";
+
+/// Returns system-wide statistics for the admin overview
+///
+/// The summary is cached for a few seconds, so repeated calls within that
+/// window return the same `generated_at`. The maintenance mode state is
+/// always current. Requires the admin role.
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `503 SERVICE_UNAVAILABLE` - No summary source is configured
+pub async fn get_admin_summary(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> Result<Json<SummaryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if !user.has_role(SUMMARY_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "Admin summary request denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    let handler = match &state.admin_summary_handler {
+        Some(handler) => handler,
+        None => {
+            return Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ErrorResponse::new(
+                    "summary_unavailable".to_string(),
+                    "Admin summary is not configured".to_string(),
+                )),
+            ));
+        }
+    };
+
+    info!(user_id = %user.user_id(), "Reading admin summary");
+
+    match handler.summary().await {
+        Ok(summary) => Ok(Json(SummaryResponse::new(
+            summary,
+            state.maintenance.current(),
+        ))),
+        Err(e) => {
+            error!("Failed to load admin summary: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error("summary_failed".to_string(), &e)),
+            ))
+        }
+    }
+}
@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/timeline.rs
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info, warn};
+
+use crate::api::field_access::FieldAccess;
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    ErrorResponse, ReceiverTimelineQueryParams, ReceiverTimelineResponse,
+};
+use crate::api::rest::events::{authorize, AppState};
+use crate::application::authorization::{ProtectedResource, ResourceAction};
+use crate::domain::value_objects::EventReceiverId;
+
+type TimelineError = (StatusCode, Json<ErrorResponse>);
+
+/// Returns a page of a receiver's timeline
+///
+/// Events, configuration changes, and group membership changes of the
+/// receiver are merged into one stream, oldest first, each entry tagged
+/// with its `kind`. Configuration changes are only included for the
+/// receiver's owner and admins. Pass `next_cursor` back as `cursor` to read
+/// the next page; a cursor stays valid while new entries are added.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - Invalid receiver id, time range, cursor, or limit
+/// * `403 FORBIDDEN` - The caller may not read the receiver
+/// * `404 NOT_FOUND` - The receiver does not exist
+/// * `503 SERVICE_UNAVAILABLE` - Timelines are not configured
+pub async fn get_receiver_timeline(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id_str): Path<String>,
+    Query(params): Query<ReceiverTimelineQueryParams>,
+) -> Result<Json<ReceiverTimelineResponse>, TimelineError> {
+    let receiver_id = id_str.parse::<EventReceiverId>().map_err(|_| {
+        warn!("Invalid event receiver ID format: {}", id_str);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                "Invalid event receiver ID format".to_string(),
+            )),
+        )
+    })?;
+
+    let Some(timeline) = state.event_receiver_handler.timeline() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "timeline_unavailable".to_string(),
+                "Receiver timelines are not configured".to_string(),
+            )),
+        ));
+    };
+
+    let receiver = state
+        .event_receiver_handler
+        .get_event_receiver_or_error(receiver_id)
+        .await
+        .map_err(|e| {
+            (
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "receiver_retrieval_failed".to_string(),
+                    &e,
+                )),
+            )
+        })?;
+    authorize(
+        &state,
+        &user,
+        ResourceAction::Read,
+        ProtectedResource::Receiver(receiver_id),
+    )
+    .await?;
+
+    let kinds = FieldAccess::for_user(Some(&user)).timeline_kinds(receiver.owner_id());
+    let query = params.into_query(kinds).map_err(|e| {
+        warn!("Receiver timeline validation failed: {}", e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::from_domain(
+                "validation_error".to_string(),
+                &e,
+            )),
+        )
+    })?;
+
+    info!(receiver_id = %receiver_id, "Reading receiver timeline");
+    match timeline.timeline(receiver_id, query).await {
+        Ok(page) => Ok(Json(ReceiverTimelineResponse::new(receiver_id, page))),
+        Err(e) => {
+            error!("Failed to read timeline of {}: {}", receiver_id, e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error("timeline_failed".to_string(), &e)),
+            ))
+        }
+    }
+}
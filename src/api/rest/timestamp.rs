@@ -0,0 +1,221 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/timestamp.rs
+
+//! Timestamps at the API boundary
+//!
+//! Every timestamp the API accepts must be RFC 3339 with an explicit offset.
+//! Naive values such as `2024-01-05 10:00:00` are rejected rather than read
+//! as UTC, with an error that shows the expected format. Every timestamp the
+//! API returns is RFC 3339 in UTC with a `Z` suffix.
+//!
+//! Request DTOs take [`Timestamp`] instead of `DateTime<Utc>` so that new
+//! endpoints get the same parsing without extra attributes.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Example shown in timestamp validation errors
+pub const TIMESTAMP_EXAMPLE: &str = "2024-01-05T10:00:00Z";
+
+/// Layouts of timestamps that carry no offset
+const NAIVE_LAYOUTS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+
+/// Formats `timestamp` the way every API response does
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use xzepr::api::rest::timestamp::format_utc;
+///
+/// let timestamp = Utc.with_ymd_and_hms(2024, 1, 5, 10, 0, 0).unwrap();
+/// assert_eq!(format_utc(&timestamp), "2024-01-05T10:00:00Z");
+/// ```
+pub fn format_utc(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// A timestamp read from a request
+///
+/// Parsing requires RFC 3339 with `Z` or a numeric offset and converts the
+/// value to UTC. Serializing writes the UTC form returned by
+/// [`format_utc`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(DateTime<Utc>);
+
+impl Timestamp {
+    /// Parses the value of request field `field`
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error of `field` naming the expected format;
+    /// values without an offset get a message saying so.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xzepr::api::rest::timestamp::Timestamp;
+    ///
+    /// let timestamp = Timestamp::parse("start", "2024-01-05T12:00:00+02:00").unwrap();
+    /// assert_eq!(timestamp.to_string(), "2024-01-05T10:00:00Z");
+    ///
+    /// assert!(Timestamp::parse("start", "2024-01-05 10:00:00").is_err());
+    /// ```
+    pub fn parse(field: &str, value: &str) -> Result<Self, DomainError> {
+        value
+            .parse()
+            .map_err(|message| DomainError::ValidationError {
+                field: field.to_string(),
+                message,
+            })
+    }
+
+    /// Returns the timestamp in UTC
+    pub fn utc(self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// Returns the validation message for a value that is not RFC 3339
+fn invalid_message(value: &str) -> Message {
+    let key = if is_naive(value) {
+        "validation.timestamp_offset"
+    } else {
+        "validation.timestamp"
+    };
+    Message::new(key).with("example", TIMESTAMP_EXAMPLE)
+}
+
+/// Returns true if `value` is a date or date and time without an offset
+pub fn is_naive(value: &str) -> bool {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+        || NAIVE_LAYOUTS
+            .iter()
+            .any(|layout| NaiveDateTime::parse_from_str(value, layout).is_ok())
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(timestamp: DateTime<Utc>) -> Self {
+        Self(timestamp)
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Message;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        DateTime::parse_from_rfc3339(value)
+            .map(|timestamp| Self(timestamp.with_timezone(&Utc)))
+            .map_err(|_| invalid_message(value))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_utc(&self.0))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[derive(Debug, Deserialize)]
+    struct Window {
+        start: Timestamp,
+        end: Option<Timestamp>,
+    }
+
+    #[test]
+    fn test_naive_timestamps_are_rejected_with_example() {
+        for naive in [
+            "2024-01-05 10:00:00",
+            "2024-01-05T10:00:00",
+            "2024-01-05T10:00:00.123",
+            "2024-01-05T10:00",
+            "2024-01-05",
+        ] {
+            let error = Timestamp::parse("start", naive).unwrap_err();
+            let DomainError::ValidationError { field, message } = error else {
+                panic!("expected a validation error for {}", naive);
+            };
+            assert_eq!(field, "start");
+            assert_eq!(message.key(), "validation.timestamp_offset", "{}", naive);
+            assert!(message.to_string().contains(TIMESTAMP_EXAMPLE));
+        }
+
+        let error = Timestamp::parse("start", "yesterday").unwrap_err();
+        assert!(error.to_string().contains(TIMESTAMP_EXAMPLE));
+
+        let error = serde_json::from_str::<Window>(r#"{"start": "2024-01-05 10:00:00"}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("offset"), "{}", error);
+        assert!(error.contains(TIMESTAMP_EXAMPLE), "{}", error);
+    }
+
+    #[test]
+    fn test_offsets_are_converted_to_utc() {
+        let window: Window = serde_json::from_str(
+            r#"{"start": "2024-03-31T01:30:00+01:00", "end": "2024-03-31T03:30:00+02:00"}"#,
+        )
+        .unwrap();
+
+        // Both sides of the CET to CEST switch are an hour apart in UTC
+        assert_eq!(
+            window.start.utc(),
+            Utc.with_ymd_and_hms(2024, 3, 31, 0, 30, 0).unwrap()
+        );
+        assert_eq!(
+            window.end.unwrap().utc() - window.start.utc(),
+            chrono::Duration::hours(1)
+        );
+    }
+
+    #[test]
+    fn test_serializes_as_utc_with_z() {
+        let timestamp = Timestamp::parse("at", "2024-01-05T12:00:00.250+02:00").unwrap();
+        assert_eq!(
+            serde_json::to_value(timestamp).unwrap(),
+            "2024-01-05T10:00:00.250Z"
+        );
+
+        // Plain DateTime<Utc> response fields serialize identically
+        let utc = timestamp.utc();
+        assert_eq!(serde_json::to_value(utc).unwrap(), format_utc(&utc));
+    }
+}
@@ -0,0 +1,217 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/api/rest/user_import.rs
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+};
+use tracing::{error, info, warn};
+
+use crate::api::middleware::jwt::AuthenticatedUser;
+use crate::api::rest::dtos::{
+    AcceptInvitationRequest, ErrorResponse, UserImportQuery, UserImportResponse,
+    UserInvitationResponse,
+};
+use crate::api::rest::events::AppState;
+use crate::application::handlers::{UserImportHandler, UserImportOutcome};
+use crate::domain::value_objects::UserId;
+
+/// Role required to import users
+const USER_IMPORT_ROLE: &str = "admin";
+
+/// Imports users and their roles from a CSV body
+///
+/// The header names the columns: `username` and `email` are required,
+/// `roles` (separated by `;`), `enabled`, and `external_id` optional.
+/// Without `dry_run=false` nothing is created and every row reports what
+/// it would do. Rows matching an existing user are skipped, so an import
+/// can be re-run after a partial failure. Small files are answered with
+/// `200 OK` and a report carrying the invitation tokens of created local
+/// accounts; larger ones return `202 ACCEPTED` with a job id to poll, and
+/// their accounts are invited separately. Requires the admin role.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - The body is not a readable import file
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `503 SERVICE_UNAVAILABLE` - User import is not configured
+pub async fn import_users(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<UserImportQuery>,
+    body: String,
+) -> Result<(StatusCode, Json<UserImportResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let handler = authorize_import(&state, &user)?;
+
+    info!(
+        user_id = %user.user_id(),
+        dry_run = query.dry_run,
+        "Importing users"
+    );
+
+    match handler.import(&body, query.dry_run, user.user_id()).await {
+        Ok(UserImportOutcome::Completed(report)) => Ok((StatusCode::OK, Json(report.into()))),
+        Ok(UserImportOutcome::Started(job)) => Ok((StatusCode::ACCEPTED, Json(job.into()))),
+        Err(e) => {
+            error!("User import failed: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "user_import_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Returns the progress or result of a background user import
+///
+/// # Errors
+///
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `404 NOT_FOUND` - Job does not exist, or expired
+/// * `503 SERVICE_UNAVAILABLE` - User import is not configured
+pub async fn get_user_import_job(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(job_id): Path<String>,
+) -> Result<Json<UserImportResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let handler = authorize_import(&state, &user)?;
+
+    match handler.job(&job_id) {
+        Some(job) => Ok(Json(job.into())),
+        None => Err((
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new(
+                "import_job_not_found".to_string(),
+                format!("User import job {} not found", job_id),
+            )),
+        )),
+    }
+}
+
+/// Issues a new invitation for a local account, replacing any earlier one
+///
+/// The token is returned once and only its hash is stored. Use this for
+/// accounts created by a background import, or when a token was lost or
+/// expired. Requires the admin role.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - The id is malformed, or the account is linked to
+///   an OIDC subject
+/// * `403 FORBIDDEN` - Caller is not an administrator
+/// * `404 NOT_FOUND` - User does not exist
+/// * `503 SERVICE_UNAVAILABLE` - User import is not configured
+pub async fn issue_user_invitation(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(user_id): Path<String>,
+) -> Result<(StatusCode, Json<UserInvitationResponse>), (StatusCode, Json<ErrorResponse>)> {
+    let handler = authorize_import(&state, &user)?;
+    let user_id = user_id.parse::<UserId>().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::new(
+                "invalid_id".to_string(),
+                format!("Invalid user ID: {}", e),
+            )),
+        )
+    })?;
+
+    match handler.invite(&user_id, user.user_id()).await {
+        Ok((invitation, token)) => Ok((
+            StatusCode::CREATED,
+            Json(UserInvitationResponse {
+                user_id: invitation.user_id,
+                invitation_token: token,
+                expires_at: invitation.expires_at,
+            }),
+        )),
+        Err(e) => {
+            warn!(user_id = %user_id, "User invitation failed: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "user_invitation_failed".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Sets the password of an invited account
+///
+/// Public: the invitation token is the credential. A token works once and
+/// expires after 72 hours.
+///
+/// # Errors
+///
+/// * `400 BAD_REQUEST` - The password is too short
+/// * `401 UNAUTHORIZED` - The token is unknown, used, or expired
+/// * `503 SERVICE_UNAVAILABLE` - User import is not configured
+pub async fn accept_user_invitation(
+    State(state): State<AppState>,
+    Json(request): Json<AcceptInvitationRequest>,
+) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
+    let handler = import_handler(&state)?;
+
+    match handler
+        .accept_invitation(&request.token, &request.password)
+        .await
+    {
+        Ok(_) => Ok(StatusCode::NO_CONTENT),
+        Err(e) => {
+            warn!("User invitation not accepted: {}", e);
+            Err((
+                e.status_code(),
+                Json(ErrorResponse::from_error(
+                    "invitation_not_accepted".to_string(),
+                    &e,
+                )),
+            ))
+        }
+    }
+}
+
+/// Checks the caller is an admin and user import is configured
+fn authorize_import<'a>(
+    state: &'a AppState,
+    user: &AuthenticatedUser,
+) -> Result<&'a UserImportHandler, (StatusCode, Json<ErrorResponse>)> {
+    if !user.has_role(USER_IMPORT_ROLE) {
+        warn!(
+            user_id = %user.user_id(),
+            "User import denied: admin role required"
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse::new(
+                "forbidden".to_string(),
+                "Admin role required".to_string(),
+            )),
+        ));
+    }
+
+    import_handler(state)
+}
+
+/// Returns the import handler, if user import is configured
+fn import_handler(
+    state: &AppState,
+) -> Result<&UserImportHandler, (StatusCode, Json<ErrorResponse>)> {
+    state.user_import_handler.as_ref().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ErrorResponse::new(
+                "user_import_unavailable".to_string(),
+                "User import is not configured".to_string(),
+            )),
+        )
+    })
+}
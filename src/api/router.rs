@@ -4,25 +4,42 @@
 // src/api/router.rs
 
 use axum::{
+    http::{header, HeaderMap},
     middleware,
-    routing::{get, post},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
     Router,
 };
 use std::sync::Arc;
-use tower_http::trace::TraceLayer;
 
-use crate::api::graphql::{graphql_handler, graphql_health, graphql_playground};
+use crate::api::graphql::{
+    graphql_handler, graphql_health, graphql_playground, query_limits_middleware, QueryLimits,
+};
 use crate::api::middleware::rate_limit::RedisRateLimitStore;
 use crate::api::middleware::{
+    body_encoding::binary_body_middleware,
+    client_ip::TrustedProxies,
     cors::CorsConfig,
     metrics::MetricsMiddlewareState,
-    rate_limit::{RateLimitConfig, RateLimiterState},
+    rate_limit::{AuthRateLimitConfig, AuthRateLimiterState, RateLimitConfig, RateLimiterState},
     security_headers::{security_headers_middleware_with_config, SecurityHeadersConfig},
-    validation::body_size_limit_middleware,
+    tracing_middleware,
+    validation::{
+        body_size_limit_middleware, content_negotiation_middleware, ContentNegotiationConfig,
+    },
 };
+use crate::api::rest::debug::get_kafka_producer_config;
 use crate::api::rest::events::AppState;
 use crate::api::rest::events::*;
-use crate::infrastructure::{PrometheusMetrics, SecurityConfig, SecurityMonitor};
+use crate::api::rest::export::export_events;
+use crate::api::rest::flags::{list_my_flags, update_flag};
+use crate::api::rest::poll::poll_events;
+use crate::api::rest::preferences::{get_my_preferences, update_my_preferences};
+use crate::api::rest::summary::get_admin_summary;
+use crate::auth::jwt::JwtService;
+use crate::infrastructure::config::mask_password;
+use crate::infrastructure::metrics::openmetrics::{prefers_openmetrics, OPENMETRICS_CONTENT_TYPE};
+use crate::infrastructure::{AuditLogger, PrometheusMetrics, SecurityConfig, SecurityMonitor};
 
 /// Router configuration
 pub struct RouterConfig {
@@ -32,6 +49,11 @@ pub struct RouterConfig {
     pub monitor: Arc<SecurityMonitor>,
     /// Prometheus metrics
     pub metrics: Option<Arc<PrometheusMetrics>>,
+    /// Trusted proxy policy for client IP resolution
+    pub trusted_proxies: TrustedProxies,
+    /// Validates bearer tokens so rate limits follow the caller's class;
+    /// without it, token holders are limited as anonymous
+    pub jwt_service: Option<Arc<JwtService>>,
 }
 
 impl RouterConfig {
@@ -41,6 +63,8 @@ impl RouterConfig {
             security,
             monitor,
             metrics: None,
+            trusted_proxies: TrustedProxies::disabled(),
+            jwt_service: None,
         }
     }
 
@@ -50,6 +74,18 @@ impl RouterConfig {
         self
     }
 
+    /// Sets the trusted proxy policy used to resolve client IPs
+    pub fn with_trusted_proxies(mut self, trusted_proxies: TrustedProxies) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    /// Sets the JWT service used to classify callers for rate limiting
+    pub fn with_jwt_service(mut self, jwt_service: Arc<JwtService>) -> Self {
+        self.jwt_service = Some(jwt_service);
+        self
+    }
+
     /// Creates a production router configuration
     pub fn production() -> Result<Self, prometheus::Error> {
         let security = SecurityConfig::production();
@@ -59,6 +95,8 @@ impl RouterConfig {
             security,
             monitor,
             metrics: Some(metrics),
+            trusted_proxies: TrustedProxies::disabled(),
+            jwt_service: None,
         })
     }
 
@@ -71,6 +109,8 @@ impl RouterConfig {
             security,
             monitor,
             metrics: Some(metrics),
+            trusted_proxies: TrustedProxies::disabled(),
+            jwt_service: None,
         })
     }
 }
@@ -81,11 +121,15 @@ impl RouterConfig {
 ///
 /// 1. Security Headers - CSP, HSTS, X-Frame-Options, etc.
 /// 2. CORS - Origin validation
-/// 3. Metrics - Request instrumentation (Prometheus)
-/// 4. Rate Limiting - Abuse prevention
-/// 5. Body Size Limits - Request size validation
-/// 6. Tracing - Request logging
-/// 7. Authentication - JWT validation (per-route)
+/// 3. Problem Details - RFC 7807 bodies for every error response below
+/// 4. Metrics - Request instrumentation (Prometheus)
+/// 5. Rate Limiting - Abuse prevention
+/// 6. Auth Rate Limiting - Per-IP limits on login and OIDC callback
+/// 7. Body Size Limits - Request size validation
+/// 8. Content Negotiation - Content-Type and Accept checks
+/// 9. Tracing - Request logging
+/// 10. Authentication - JWT validation (per-route)
+/// 11. Maintenance Mode - Rejects writes while maintenance mode is on
 ///
 /// # Arguments
 ///
@@ -109,35 +153,39 @@ impl RouterConfig {
 /// ```
 pub async fn build_router(state: AppState, config: RouterConfig) -> Router {
     tracing::info!("Building router with security middleware");
+    let error_format = state.error_format;
+    let deprecations = state.deprecations.clone();
+    let maintenance = crate::api::middleware::MaintenanceGuard::new(state.maintenance.clone())
+        .with_persisted_queries(state.persisted_queries.clone());
 
     // Create GraphQL schema
     let schema = crate::api::graphql::create_schema(
+        Arc::new(state.event_handler.clone()),
         Arc::new(state.event_receiver_handler.clone()),
         Arc::new(state.event_receiver_group_handler.clone()),
+        state.authorization.clone(),
     );
 
     // Initialize rate limiter
-    let rate_limit_config = RateLimitConfig {
-        anonymous_rpm: config.security.rate_limit.anonymous_rpm,
-        authenticated_rpm: config.security.rate_limit.authenticated_rpm,
-        admin_rpm: config.security.rate_limit.admin_rpm,
-        per_endpoint: config.security.rate_limit.per_endpoint.clone(),
-        window_size: std::time::Duration::from_secs(60),
-    };
+    let rate_limit_config = RateLimitConfig::from(&config.security.rate_limit);
 
-    let rate_limiter = if config.security.rate_limit.use_redis {
+    let mut rate_limiter = if config.security.rate_limit.use_redis {
         // Use Redis-backed rate limiting for distributed deployments
         let redis_url = std::env::var("XZEPR__REDIS_URL")
             .unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
 
         match RedisRateLimitStore::new(&redis_url).await {
             Ok(redis_store) => {
-                tracing::info!("Using Redis-backed rate limiting at {}", redis_url);
+                tracing::info!(
+                    "Using Redis-backed rate limiting at {}",
+                    mask_password(&redis_url)
+                );
                 RateLimiterState::new_with_monitor(
                     rate_limit_config.clone(),
                     Arc::new(redis_store),
                     config.monitor.clone(),
                 )
+                .with_trusted_proxies(config.trusted_proxies.clone())
             }
             Err(e) => {
                 tracing::error!(
@@ -146,13 +194,39 @@ pub async fn build_router(state: AppState, config: RouterConfig) -> Router {
                 );
                 RateLimiterState::default_with_config(rate_limit_config.clone())
                     .with_monitor(config.monitor.clone())
+                    .with_trusted_proxies(config.trusted_proxies.clone())
             }
         }
     } else {
         RateLimiterState::default_with_config(rate_limit_config.clone())
             .with_monitor(config.monitor.clone())
+            .with_trusted_proxies(config.trusted_proxies.clone())
     };
 
+    if let Some(jwt_service) = &config.jwt_service {
+        rate_limiter = rate_limiter.with_jwt_service(jwt_service.clone());
+    }
+    if let Some(usage) = state
+        .api_key_service
+        .as_ref()
+        .and_then(|service| service.usage_tracker())
+    {
+        rate_limiter = rate_limiter.with_api_key_usage(usage.clone());
+    }
+
+    // Stricter per-IP limiter for authentication endpoints; with runtime
+    // settings it follows reloads of the same keys
+    let mut auth_rate_limiter = AuthRateLimiterState::new(AuthRateLimitConfig::new(
+        config.security.rate_limit.auth_burst,
+        config.security.rate_limit.auth_sustained_per_minute,
+    ))
+    .with_trusted_proxies(config.trusted_proxies.clone())
+    .with_audit(Arc::new(AuditLogger::new()))
+    .with_monitor(config.monitor.clone());
+    if let Some(runtime_settings) = &state.runtime_settings {
+        auth_rate_limiter = auth_rate_limiter.with_runtime_settings(runtime_settings.clone());
+    }
+
     // Build CORS layer
     let cors_config = CorsConfig {
         allowed_origins: config.security.cors.allowed_origins.clone(),
@@ -188,11 +262,16 @@ pub async fn build_router(state: AppState, config: RouterConfig) -> Router {
 
     tracing::info!(
         cors_origins = ?cors_config.allowed_origins,
-        anonymous_rpm = rate_limit_config.anonymous_rpm,
-        authenticated_rpm = rate_limit_config.authenticated_rpm,
+        rate_limit_classes = ?rate_limit_config.classes,
+        rate_limit_routes = rate_limit_config.routes.len(),
         "Security configuration loaded"
     );
 
+    // Content-Type and Accept allow lists
+    let content_negotiation = Arc::new(ContentNegotiationConfig::from(
+        &config.security.validation.media_types,
+    ));
+
     // Create a metrics state for the /metrics endpoint
     let metrics_state = config.metrics.clone().unwrap_or_else(|| {
         tracing::warn!("No Prometheus metrics configured, using default");
@@ -210,13 +289,33 @@ pub async fn build_router(state: AppState, config: RouterConfig) -> Router {
         .route("/metrics", get(metrics_handler))
         .with_state(metrics_state)
         // GraphQL endpoints
-        .route("/graphql", post(graphql_handler))
+        .route(
+            "/graphql",
+            post(graphql_handler).layer(middleware::from_fn_with_state(
+                QueryLimits::from(&state.graphql),
+                query_limits_middleware,
+            )),
+        )
         .route("/graphql/playground", get(graphql_playground))
         .route("/graphql/health", get(graphql_health))
         .with_state(schema.clone())
         // REST API v1 routes
-        .route("/api/v1/events", post(create_event))
+        .route(
+            "/api/v1/events",
+            post(create_event).layer(middleware::from_fn(binary_body_middleware)),
+        )
+        .route("/api/v1/events/export", get(export_events))
+        .route("/api/v1/events/poll", get(poll_events))
         .route("/api/v1/events/:id", get(get_event))
+        .route("/api/v1/admin/events", get(list_admin_events))
+        .route("/api/v1/admin/debug/kafka", get(get_kafka_producer_config))
+        .route("/api/v1/admin/flags/:name", put(update_flag))
+        .route("/api/v1/admin/summary", get(get_admin_summary))
+        .route("/api/v1/flags", get(list_my_flags))
+        .route(
+            "/api/v1/me/preferences",
+            get(get_my_preferences).put(update_my_preferences),
+        )
         // Event receiver routes
         .route("/api/v1/receivers", post(create_event_receiver))
         .route("/api/v1/receivers", get(list_event_receivers))
@@ -228,12 +327,41 @@ pub async fn build_router(state: AppState, config: RouterConfig) -> Router {
         .route("/api/v1/groups/:id", get(get_event_receiver_group))
         .route("/api/v1/groups/:id", post(update_event_receiver_group))
         .route("/api/v1/groups/:id", get(delete_event_receiver_group))
+        // Deprecation headers and sunset of the routes above
+        .route_layer(middleware::from_fn_with_state(
+            deprecations,
+            crate::api::middleware::deprecation::deprecation_middleware,
+        ))
         .with_state(state)
         // Apply middleware layers (innermost to outermost)
-        // Layer 7: Tracing (request logging)
-        .layer(TraceLayer::new_for_http())
-        // Layer 6: Body size limits
+        // Layer 12: Maintenance mode (rejects writes, allows reads)
+        .layer(middleware::from_fn_with_state(
+            maintenance,
+            crate::api::middleware::maintenance::maintenance_middleware,
+        ))
+        // Layer 11: Error message localization (Accept-Language)
+        .layer(middleware::from_fn(
+            crate::api::middleware::localization::localize_errors_middleware,
+        ))
+        // Layer 10: Client IP resolution (recorded with ingested events)
+        .layer(middleware::from_fn_with_state(
+            config.trusted_proxies.clone(),
+            crate::api::middleware::client_ip::client_ip_middleware,
+        ))
+        // Layer 9: Tracing (request spans, continuing the caller's trace)
+        .layer(middleware::from_fn(tracing_middleware))
+        // Layer 8: Content negotiation (before the body is read)
+        .layer(middleware::from_fn_with_state(
+            content_negotiation,
+            content_negotiation_middleware,
+        ))
+        // Layer 7: Body size limits
         .layer(middleware::from_fn(body_size_limit_middleware))
+        // Layer 6: Authentication rate limiting
+        .layer(middleware::from_fn_with_state(
+            auth_rate_limiter,
+            crate::api::middleware::rate_limit::auth_rate_limit_middleware,
+        ))
         // Layer 5: Rate limiting
         .layer(middleware::from_fn_with_state(
             rate_limiter,
@@ -244,9 +372,14 @@ pub async fn build_router(state: AppState, config: RouterConfig) -> Router {
             metrics_middleware_state,
             crate::api::middleware::metrics::metrics_middleware,
         ))
-        // Layer 3: CORS
+        // Layer 3: Problem details for errors of all inner layers
+        .layer(middleware::from_fn_with_state(
+            error_format,
+            crate::api::middleware::problem::problem_details_middleware,
+        ))
+        // Layer 2: CORS
         .layer(cors_layer)
-        // Layer 2: Security headers (outermost)
+        // Layer 1: Security headers (outermost)
         .layer(middleware::from_fn(move |req, next| {
             let config = headers_config.clone();
             async move { security_headers_middleware_with_config(config, req, next).await }
@@ -254,7 +387,25 @@ pub async fn build_router(state: AppState, config: RouterConfig) -> Router {
 }
 
 /// Metrics handler for Prometheus scraping
-async fn metrics_handler(config: axum::extract::State<Arc<PrometheusMetrics>>) -> String {
+///
+/// Scrapers preferring `application/openmetrics-text` get the OpenMetrics
+/// format with exemplars; everyone else gets the classic text format.
+pub async fn metrics_handler(
+    config: axum::extract::State<Arc<PrometheusMetrics>>,
+    headers: HeaderMap,
+) -> Response {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if prefers_openmetrics(accept) {
+        return (
+            [(header::CONTENT_TYPE, OPENMETRICS_CONTENT_TYPE)],
+            config.gather_openmetrics(),
+        )
+            .into_response();
+    }
+
     match config.gather() {
         Ok(metrics) => metrics,
         Err(e) => {
@@ -262,12 +413,96 @@ async fn metrics_handler(config: axum::extract::State<Arc<PrometheusMetrics>>) -
             format!("# Error gathering metrics: {}\n", e)
         }
     }
+    .into_response()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    async fn scrape(app: &Router, accept: &str) -> (String, String) {
+        use tower::ServiceExt;
+
+        let request = axum::http::Request::builder()
+            .uri("/metrics")
+            .header(header::ACCEPT, accept)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (content_type, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_metrics_scrape_negotiates_openmetrics_exemplars() {
+        use crate::api::middleware::metrics::metrics_middleware;
+        use opentelemetry::trace::TracerProvider as _;
+        use tower::ServiceExt;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let provider = opentelemetry_sdk::trace::TracerProvider::builder().build();
+        let _guard = tracing::subscriber::set_default(
+            tracing_subscriber::registry()
+                .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("xzepr-test"))),
+        );
+
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let app = Router::new()
+            .route("/metrics", get(metrics_handler))
+            .route("/traced", get(|| async { "ok" }))
+            .with_state(metrics.clone())
+            .layer(middleware::from_fn(tracing_middleware))
+            .layer(middleware::from_fn_with_state(
+                MetricsMiddlewareState::new(metrics.clone()),
+                metrics_middleware,
+            ));
+
+        let request = axum::http::Request::builder()
+            .uri("/traced")
+            .header(
+                "traceparent",
+                "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            )
+            .body(axum::body::Body::empty())
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap();
+
+        // OpenMetrics scrapes link the latency bucket to the trace
+        let (content_type, body) = scrape(
+            &app,
+            "application/openmetrics-text;version=1.0.0,text/plain;version=0.0.4;q=0.5",
+        )
+        .await;
+        assert_eq!(content_type, OPENMETRICS_CONTENT_TYPE);
+        let exemplar = body
+            .lines()
+            .find(|line| {
+                line.starts_with("xzepr_http_request_duration_seconds_bucket{")
+                    && line.contains("path=\"/traced\"")
+                    && line.contains(" # {")
+            })
+            .expect("no exemplar for the traced request");
+        assert!(exemplar.contains(" # {trace_id=\"0af7651916cd43dd8448eb211c80319c\"} "));
+        assert!(body.contains("# TYPE xzepr_http_requests counter"));
+        assert!(body.ends_with("# EOF\n"));
+
+        // Classic scrapes are unchanged and carry no exemplars
+        let (content_type, body) = scrape(&app, "text/plain;version=0.0.4").await;
+        assert_eq!(content_type, "text/plain; charset=utf-8");
+        assert!(body.contains("xzepr_http_request_duration_seconds_bucket{"));
+        assert!(body.contains("# TYPE xzepr_http_requests_total counter"));
+        assert!(!body.contains("trace_id"));
+        assert!(!body.contains("# EOF"));
+        let (_, default_body) = scrape(&app, "*/*").await;
+        assert!(!default_body.contains("trace_id"));
+    }
+
     #[test]
     fn test_router_config_production() {
         let config = RouterConfig::production().unwrap();
@@ -0,0 +1,463 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/authorization.rs
+
+//! Resource authorization shared by the REST and GraphQL APIs
+//!
+//! [`AuthorizationService`] decides whether a caller may act on a receiver
+//! or group. It loads the resource's owner and group members, asks OPA
+//! when a client is attached and enforcement is on for the caller, and
+//! otherwise applies the built-in rules:
+//!
+//! - Admins may do anything
+//! - Owners may do anything with their own resources
+//! - Group members may read resources of the group and post events to its
+//!   receivers
+//! - Anyone else needs a `<resource_type>:<action>` permission
+//!
+//! Every decision is written to the audit log as a permission check.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, error, warn};
+
+use crate::auth::jwt::Claims;
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::{AuthorizationError, DomainError, Result};
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::feature_flags::{FeatureFlags, FLAG_OPA_ENFORCEMENT};
+use crate::infrastructure::PrometheusMetrics;
+use crate::opa::client::OpaClient;
+use crate::opa::types::{AuthorizationDecision, OpaInput, ResourceContext, UserContext};
+
+/// Something a caller asks to do with a receiver or group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceAction {
+    Read,
+    /// Includes enabling and disabling groups
+    Update,
+    Delete,
+    AddMember,
+    UpdateMember,
+    RemoveMember,
+}
+
+impl ResourceAction {
+    /// Returns the action name used in policies and audit records
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceAction::Read => "read",
+            ResourceAction::Update => "update",
+            ResourceAction::Delete => "delete",
+            ResourceAction::AddMember => "add_member",
+            ResourceAction::UpdateMember => "update_member",
+            ResourceAction::RemoveMember => "remove_member",
+        }
+    }
+}
+
+impl fmt::Display for ResourceAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A receiver or group access is checked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtectedResource {
+    Receiver(EventReceiverId),
+    Group(EventReceiverGroupId),
+}
+
+/// Decides whether callers may act on receivers and groups
+///
+/// Cloning is cheap; clones share the repositories, OPA client, and audit
+/// logger.
+#[derive(Clone)]
+pub struct AuthorizationService {
+    receiver_repository: Arc<dyn EventReceiverRepository>,
+    group_repository: Arc<dyn EventReceiverGroupRepository>,
+    opa_client: Option<Arc<OpaClient>>,
+    feature_flags: Option<FeatureFlags>,
+    audit_logger: Arc<AuditLogger>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+}
+
+impl AuthorizationService {
+    /// Creates a service that applies the built-in rules
+    pub fn new(
+        receiver_repository: Arc<dyn EventReceiverRepository>,
+        group_repository: Arc<dyn EventReceiverGroupRepository>,
+    ) -> Self {
+        Self {
+            receiver_repository,
+            group_repository,
+            opa_client: None,
+            feature_flags: None,
+            audit_logger: Arc::new(AuditLogger::new()),
+            metrics: None,
+        }
+    }
+
+    /// Evaluates policies with OPA, falling back to the built-in rules when
+    /// OPA fails
+    pub fn with_opa(mut self, opa_client: Arc<OpaClient>) -> Self {
+        self.opa_client = Some(opa_client);
+        self
+    }
+
+    /// Consults the `opa_enforcement` flag before asking OPA
+    pub fn with_feature_flags(mut self, feature_flags: FeatureFlags) -> Self {
+        self.feature_flags = Some(feature_flags);
+        self
+    }
+
+    /// Uses `audit_logger` to record decisions
+    pub fn with_audit_logger(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Records how long OPA evaluations take
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Drops cached OPA decisions about a receiver
+    ///
+    /// Returns false if OPA, and so its decision cache, is not configured.
+    pub async fn forget_receiver(&self, id: EventReceiverId) -> bool {
+        match &self.opa_client {
+            Some(opa_client) => {
+                opa_client
+                    .cache()
+                    .invalidate_resource("event_receiver", &id.to_string())
+                    .await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Requires the caller to be allowed `action` on `resource`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ReceiverNotFound` or `GroupNotFound` if the resource does
+    /// not exist, and an authorization error if the caller is denied.
+    pub async fn authorize(
+        &self,
+        caller: &Claims,
+        action: ResourceAction,
+        resource: ProtectedResource,
+    ) -> Result<()> {
+        let context = self.resource_context(resource).await?;
+        let decision = self.decide(caller, action.as_str(), &context).await;
+        if decision.allow {
+            return Ok(());
+        }
+
+        warn!(
+            user_id = %caller.sub,
+            action = %action,
+            resource_type = %context.resource_type,
+            resource_id = ?context.resource_id,
+            reason = ?decision.reason,
+            "Authorization denied"
+        );
+        Err(AuthorizationError::InsufficientPermissions {
+            action: format!("{} {}", action, context.resource_type),
+        }
+        .into())
+    }
+
+    /// Decides whether the caller may perform `action` on `resource` and
+    /// records the decision
+    pub async fn decide(
+        &self,
+        caller: &Claims,
+        action: &str,
+        resource: &ResourceContext,
+    ) -> AuthorizationDecision {
+        let decision = match &self.opa_client {
+            Some(opa_client) if self.opa_enforced_for(&caller.roles) => {
+                let input = OpaInput {
+                    user: UserContext {
+                        user_id: caller.sub.clone(),
+                        username: caller.sub.clone(),
+                        roles: caller.roles.clone(),
+                        groups: vec![],
+                    },
+                    action: action.to_string(),
+                    resource: resource.clone(),
+                };
+                let start = Instant::now();
+                match opa_client.evaluate(input).await {
+                    Ok(decision) => {
+                        if let Some(metrics) = &self.metrics {
+                            metrics.record_auth_duration(
+                                &format!("opa_{}", action),
+                                start.elapsed().as_secs_f64(),
+                            );
+                        }
+                        decision
+                    }
+                    Err(e) => {
+                        error!(
+                            user_id = %caller.sub,
+                            error = %e,
+                            "OPA policy evaluation failed, falling back to legacy RBAC"
+                        );
+                        AuthorizationDecision {
+                            allow: legacy_decision(caller, action, resource),
+                            reason: Some("OPA unavailable, used legacy RBAC".to_string()),
+                            metadata: None,
+                        }
+                    }
+                }
+            }
+            Some(_) => {
+                debug!(user_id = %caller.sub, "OPA enforcement disabled by feature flag");
+                AuthorizationDecision {
+                    allow: legacy_decision(caller, action, resource),
+                    reason: Some("OPA enforcement disabled, used legacy RBAC".to_string()),
+                    metadata: None,
+                }
+            }
+            None => AuthorizationDecision {
+                allow: legacy_decision(caller, action, resource),
+                reason: None,
+                metadata: None,
+            },
+        };
+
+        self.audit(caller, action, resource, &decision);
+        decision
+    }
+
+    /// Returns true if OPA should be consulted for a caller with `roles`
+    fn opa_enforced_for(&self, roles: &[String]) -> bool {
+        self.feature_flags
+            .as_ref()
+            .map(|flags| flags.is_enabled_for(FLAG_OPA_ENFORCEMENT, roles))
+            .unwrap_or(true)
+    }
+
+    async fn resource_context(&self, resource: ProtectedResource) -> Result<ResourceContext> {
+        match resource {
+            ProtectedResource::Receiver(id) => {
+                let receiver = self
+                    .receiver_repository
+                    .find_by_id(id)
+                    .await?
+                    .ok_or(DomainError::ReceiverNotFound)?;
+                receiver_context(&receiver, &*self.group_repository).await
+            }
+            ProtectedResource::Group(id) => {
+                let group = self
+                    .group_repository
+                    .find_by_id(id)
+                    .await?
+                    .ok_or(DomainError::GroupNotFound)?;
+                group_context(&group, &*self.group_repository).await
+            }
+        }
+    }
+
+    fn audit(
+        &self,
+        caller: &Claims,
+        action: &str,
+        resource: &ResourceContext,
+        decision: &AuthorizationDecision,
+    ) {
+        let outcome = if decision.allow {
+            AuditOutcome::Success
+        } else {
+            AuditOutcome::Denied
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("action".to_string(), action.to_string());
+        metadata.insert("resource_type".to_string(), resource.resource_type.clone());
+        if let Some(rid) = &resource.resource_id {
+            metadata.insert("resource_id".to_string(), rid.clone());
+        }
+        if let Some(oid) = &resource.owner_id {
+            metadata.insert("owner_id".to_string(), oid.clone());
+        }
+        if let Some(gid) = &resource.group_id {
+            metadata.insert("group_id".to_string(), gid.clone());
+        }
+        metadata.insert("decision_allowed".to_string(), decision.allow.to_string());
+        if let Some(reason) = &decision.reason {
+            metadata.insert("decision_reason".to_string(), reason.clone());
+        }
+
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(&caller.sub)
+                .action(AuditAction::PermissionCheck)
+                .resource(format!(
+                    "{}:{}",
+                    resource.resource_type,
+                    resource.resource_id.as_deref().unwrap_or("*")
+                ))
+                .outcome(outcome)
+                .metadata(metadata)
+                .build(),
+        );
+    }
+}
+
+/// Applies the built-in rules to a caller and resource
+pub fn legacy_decision(caller: &Claims, action: &str, resource: &ResourceContext) -> bool {
+    // Admin can do anything
+    if caller.has_role("admin") {
+        return true;
+    }
+
+    // Owner can do anything with their own resources
+    if resource.owner_id.as_deref() == Some(caller.sub.as_str()) {
+        return true;
+    }
+
+    // Group members can read resources in their group and post events to
+    // its receivers, matching the member actions of the OPA policy
+    if (action == "read" || action == "create")
+        && resource.group_id.is_some()
+        && resource.members.contains(&caller.sub)
+    {
+        return true;
+    }
+
+    // Check specific permissions
+    let permission = format!("{}:{}", resource.resource_type, action);
+    caller.has_permission(&permission)
+}
+
+/// Builds the authorization context of a receiver
+pub async fn receiver_context(
+    receiver: &EventReceiver,
+    group_repository: &dyn EventReceiverGroupRepository,
+) -> Result<ResourceContext> {
+    let (group_id, members) = receiver_group_context(group_repository, receiver.id()).await?;
+
+    Ok(ResourceContext {
+        resource_type: "event_receiver".to_string(),
+        resource_id: Some(receiver.id().to_string()),
+        owner_id: Some(receiver.owner_id().to_string()),
+        group_id,
+        members,
+        resource_version: receiver.resource_version(),
+    })
+}
+
+/// Builds the authorization context of a group
+pub async fn group_context(
+    group: &EventReceiverGroup,
+    group_repository: &dyn EventReceiverGroupRepository,
+) -> Result<ResourceContext> {
+    let members = group_repository
+        .get_group_members(group.id())
+        .await?
+        .into_iter()
+        .map(|member| member.to_string())
+        .collect();
+
+    Ok(ResourceContext {
+        resource_type: "event_receiver_group".to_string(),
+        resource_id: Some(group.id().to_string()),
+        owner_id: Some(group.owner_id().to_string()),
+        group_id: Some(group.id().to_string()),
+        members,
+        resource_version: group.resource_version(),
+    })
+}
+
+/// Looks up the groups containing a receiver and their current members
+///
+/// A receiver may sit in several groups; the first is reported as the
+/// context's group and the members of all of them are listed. Expired
+/// memberships are left out, so a lapsed member can no longer post events.
+pub async fn receiver_group_context(
+    group_repository: &dyn EventReceiverGroupRepository,
+    receiver_id: EventReceiverId,
+) -> Result<(Option<String>, Vec<String>)> {
+    let groups = group_repository
+        .find_by_event_receiver_id(receiver_id)
+        .await?;
+
+    let mut members = Vec::new();
+    for group in &groups {
+        for member in group_repository.get_group_members(group.id()).await? {
+            let member = member.to_string();
+            if !members.contains(&member) {
+                members.push(member);
+            }
+        }
+    }
+
+    Ok((groups.first().map(|g| g.id().to_string()), members))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(sub: &str, roles: &[&str], permissions: &[&str]) -> Claims {
+        Claims::new_access_token(
+            sub.to_string(),
+            roles.iter().map(|r| r.to_string()).collect(),
+            permissions.iter().map(|p| p.to_string()).collect(),
+            "xzepr".to_string(),
+            "xzepr-api".to_string(),
+            chrono::Duration::minutes(5),
+        )
+    }
+
+    fn group_resource() -> ResourceContext {
+        ResourceContext {
+            resource_type: "event_receiver_group".to_string(),
+            resource_id: Some("group-1".to_string()),
+            owner_id: Some("owner".to_string()),
+            group_id: Some("group-1".to_string()),
+            members: vec!["member".to_string()],
+            resource_version: 1,
+        }
+    }
+
+    #[test]
+    fn test_legacy_decision_by_relationship() {
+        let resource = group_resource();
+        let owner = claims("owner", &["user"], &[]);
+        let member = claims("member", &["user"], &[]);
+        let stranger = claims("stranger", &["event_manager"], &["group:update"]);
+        let admin = claims("admin", &["admin"], &[]);
+
+        for action in ["update", "delete", "add_member", "remove_member"] {
+            assert!(legacy_decision(&owner, action, &resource), "{}", action);
+            assert!(!legacy_decision(&member, action, &resource), "{}", action);
+            assert!(!legacy_decision(&stranger, action, &resource), "{}", action);
+            assert!(legacy_decision(&admin, action, &resource), "{}", action);
+        }
+        assert!(legacy_decision(&member, "read", &resource));
+        assert!(!legacy_decision(&stranger, "read", &resource));
+    }
+
+    #[test]
+    fn test_resource_permission_grants_access() {
+        let resource = group_resource();
+        let operator = claims("operator", &["user"], &["event_receiver_group:update"]);
+        assert!(legacy_decision(&operator, "update", &resource));
+        assert!(!legacy_decision(&operator, "delete", &resource));
+    }
+}
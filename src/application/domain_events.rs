@@ -0,0 +1,463 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/domain_events.rs
+
+//! In-process bus of domain notifications
+//!
+//! Handlers publish a [`DomainEvent`] once a change has been persisted, and
+//! features that react to changes register a [`DomainEventSubscriber`]
+//! instead of being called from the handlers. The
+//! [`KafkaForwarder`](crate::application::handlers::KafkaForwarder), for
+//! example, publishes the CloudEvents for receiver and group changes.
+//!
+//! # Delivery
+//!
+//! Delivery is best-effort and at most once, within this process only:
+//!
+//! - A subscriber receives the notifications published after it registered,
+//!   in publish order, and handles them one at a time.
+//! - Publishing never waits for subscribers, so a notification may be
+//!   handled after the request that caused it has returned.
+//! - Each subscriber has its own queue of `capacity` notifications. One
+//!   that falls behind skips the oldest notifications, which is counted in
+//!   `xzepr_domain_event_subscriber_lagged_total`; the queue length is
+//!   reported in `xzepr_domain_event_subscriber_pending`.
+//! - A subscriber that returns an error or panics is logged and counted in
+//!   `xzepr_domain_event_subscriber_failures_total`, and the notification
+//!   is not retried. Other subscribers and the publisher are unaffected.
+//! - Nothing is persisted: notifications still queued at shutdown are lost,
+//!   and notifications from other instances are not seen. Work that must
+//!   happen for every change belongs in the database, as with the event
+//!   outbox.
+
+use crate::domain::entities::delivery_failure::DeliveryFailure;
+use crate::domain::entities::event::Event;
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::entities::receiver_auto_disable::AutoDisableTrigger;
+use crate::domain::value_objects::{EventReceiverGroupId, UserId};
+use crate::error::Result;
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, warn};
+
+/// Default number of notifications queued per subscriber
+pub const DEFAULT_DOMAIN_EVENT_CAPACITY: usize = 1024;
+
+/// Change to a user's membership of a group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MembershipChange {
+    /// The user was added, until `expires_at` if set
+    Added { expires_at: Option<DateTime<Utc>> },
+    /// The membership's expiry was set, extended, or cleared
+    ExpiryChanged { expires_at: Option<DateTime<Utc>> },
+    /// The user was removed
+    Removed,
+}
+
+/// Notification that a change was persisted
+#[derive(Debug, Clone)]
+pub enum DomainEvent {
+    /// An event was stored
+    EventCreated { event: Event },
+    /// A receiver was created; `system_event` is the recorded
+    /// `xzepr.event.receiver.created` event, absent if it could not be built
+    ReceiverCreated {
+        receiver: EventReceiver,
+        system_event: Option<Event>,
+    },
+    /// A receiver's definition, sampling, allowed event names, or
+    /// auto-disable policy changed, or it was re-enabled; `system_event` is
+    /// the recorded `xzepr.event.receiver.updated` event, absent if it could
+    /// not be built
+    ReceiverUpdated {
+        receiver: EventReceiver,
+        changed_fields: Vec<String>,
+        system_event: Option<Event>,
+    },
+    /// A receiver was deleted; `receiver` is its final state and
+    /// `system_event` the recorded `xzepr.event.receiver.deleted` event,
+    /// absent if it could not be built
+    ReceiverDeleted {
+        receiver: EventReceiver,
+        deleted_at: DateTime<Utc>,
+        system_event: Option<Event>,
+    },
+    /// A receiver's failure rate breached its auto-disable policy and it
+    /// stopped accepting events; `system_event` is the recorded
+    /// `xzepr.event.receiver.auto_disabled` event, absent if it could not be
+    /// built
+    ReceiverAutoDisabled {
+        receiver: EventReceiver,
+        trigger: AutoDisableTrigger,
+        system_event: Option<Event>,
+    },
+    /// A group was created; `system_event` is the recorded
+    /// `xzepr.event.receiver.group.created` event, absent if it could not be
+    /// built
+    GroupCreated {
+        group: EventReceiverGroup,
+        system_event: Option<Event>,
+    },
+    /// A group's settings or member receivers changed; `system_event` is
+    /// the recorded `xzepr.event.receiver.group.updated` event, absent if it
+    /// could not be built
+    GroupUpdated {
+        group: EventReceiverGroup,
+        changed_fields: Vec<String>,
+        system_event: Option<Event>,
+    },
+    /// A group was deleted; `group` is its final state and `system_event`
+    /// the recorded `xzepr.event.receiver.group.deleted` event, absent if it
+    /// could not be built
+    GroupDeleted {
+        group: EventReceiverGroup,
+        deleted_at: DateTime<Utc>,
+        system_event: Option<Event>,
+    },
+    /// Every required receiver of a group reported a successful event for
+    /// `release`, for the first time; `system_event` is the recorded
+    /// `xzepr.event.receiver.group.release_completed` event, absent if it
+    /// could not be built
+    GroupReleaseCompleted {
+        group: EventReceiverGroup,
+        release: String,
+        system_event: Option<Event>,
+    },
+    /// A user's membership of a group changed
+    GroupMembershipChanged {
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        change: MembershipChange,
+    },
+    /// A delivery of `event` was given up on after its last retry
+    DeliveryFailed {
+        failure: DeliveryFailure,
+        event: Event,
+    },
+}
+
+impl DomainEvent {
+    /// Short name of the notification used in logs and metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::EventCreated { .. } => "event_created",
+            Self::ReceiverCreated { .. } => "receiver_created",
+            Self::ReceiverUpdated { .. } => "receiver_updated",
+            Self::ReceiverDeleted { .. } => "receiver_deleted",
+            Self::ReceiverAutoDisabled { .. } => "receiver_auto_disabled",
+            Self::GroupCreated { .. } => "group_created",
+            Self::GroupUpdated { .. } => "group_updated",
+            Self::GroupDeleted { .. } => "group_deleted",
+            Self::GroupReleaseCompleted { .. } => "group_release_completed",
+            Self::GroupMembershipChanged { .. } => "group_membership_changed",
+            Self::DeliveryFailed { .. } => "delivery_failed",
+        }
+    }
+}
+
+/// Reacts to domain notifications
+///
+/// Subscribers ignore the notifications they are not interested in.
+#[async_trait]
+pub trait DomainEventSubscriber: Send + Sync {
+    /// Name of the subscriber in logs and metrics
+    fn name(&self) -> &str;
+
+    /// Handles one notification
+    async fn handle(&self, event: &DomainEvent) -> Result<()>;
+}
+
+/// In-process publisher of [`DomainEvent`]s
+///
+/// Clones share the same subscribers.
+#[derive(Clone)]
+pub struct DomainEventBus {
+    sender: broadcast::Sender<Arc<DomainEvent>>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+}
+
+impl DomainEventBus {
+    /// Creates a bus queueing up to [`DEFAULT_DOMAIN_EVENT_CAPACITY`]
+    /// notifications per subscriber
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_DOMAIN_EVENT_CAPACITY)
+    }
+
+    /// Creates a bus queueing up to `capacity` notifications per subscriber
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self {
+            sender,
+            metrics: None,
+        }
+    }
+
+    /// Reports subscriber failures and lag
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Announces a persisted change to current subscribers without waiting
+    /// for them
+    pub fn publish(&self, event: DomainEvent) {
+        debug!(kind = event.kind(), "Publishing domain event");
+        // Sending only fails when nobody is subscribed
+        let _ = self.sender.send(Arc::new(event));
+    }
+
+    /// Returns a receiver of notifications published from now on
+    ///
+    /// For consumers that stream notifications themselves; a receiver that
+    /// falls behind gets `RecvError::Lagged` and skips ahead.
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<DomainEvent>> {
+        self.sender.subscribe()
+    }
+
+    /// Delivers notifications published from now on to `subscriber` on a
+    /// background task
+    ///
+    /// The task ends once every clone of the bus is dropped.
+    pub fn register(&self, subscriber: Arc<dyn DomainEventSubscriber>) -> JoinHandle<()> {
+        let mut receiver = self.sender.subscribe();
+        let metrics = self.metrics.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => {
+                        if let Some(metrics) = &metrics {
+                            metrics.set_domain_event_pending(subscriber.name(), receiver.len());
+                        }
+                        deliver(&subscriber, event, metrics.as_deref()).await;
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(
+                            subscriber = subscriber.name(),
+                            skipped = skipped,
+                            "Domain event subscriber fell behind and skipped notifications"
+                        );
+                        if let Some(metrics) = &metrics {
+                            metrics.record_domain_event_lag(subscriber.name(), skipped);
+                        }
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+impl Default for DomainEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hands one notification to a subscriber, containing errors and panics
+///
+/// The subscriber runs on its own task so a panic unwinds only that task.
+async fn deliver(
+    subscriber: &Arc<dyn DomainEventSubscriber>,
+    event: Arc<DomainEvent>,
+    metrics: Option<&PrometheusMetrics>,
+) {
+    let kind = event.kind();
+    let task = {
+        let subscriber = subscriber.clone();
+        tokio::spawn(async move { subscriber.handle(&event).await })
+    };
+
+    let reason = match task.await {
+        Ok(Ok(())) => return,
+        Ok(Err(e)) => {
+            error!(
+                subscriber = subscriber.name(),
+                kind = kind,
+                error = %e,
+                "Domain event subscriber failed"
+            );
+            "error"
+        }
+        Err(e) if e.is_panic() => {
+            error!(
+                subscriber = subscriber.name(),
+                kind = kind,
+                "Domain event subscriber panicked"
+            );
+            "panic"
+        }
+        // Cancelled while the runtime shuts down
+        Err(_) => return,
+    };
+
+    if let Some(metrics) = metrics {
+        metrics.record_domain_event_failure(subscriber.name(), kind, reason);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{DomainError, Error};
+    use crate::fixtures::{GroupFixture, ReceiverFixture};
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    /// Forwards the kind of every notification it handles
+    struct Recorder {
+        name: String,
+        seen: mpsc::UnboundedSender<&'static str>,
+    }
+
+    #[async_trait]
+    impl DomainEventSubscriber for Recorder {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn handle(&self, event: &DomainEvent) -> Result<()> {
+            let _ = self.seen.send(event.kind());
+            Ok(())
+        }
+    }
+
+    /// Panics on deletions and fails on everything else
+    struct Faulty {
+        calls: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl DomainEventSubscriber for Faulty {
+        fn name(&self) -> &str {
+            "faulty"
+        }
+
+        async fn handle(&self, event: &DomainEvent) -> Result<()> {
+            *self.calls.lock().unwrap() += 1;
+            if let DomainEvent::ReceiverDeleted { .. } = event {
+                panic!("subscriber bug");
+            }
+            Err(Error::Domain(DomainError::InvalidData(
+                "rejected".to_string(),
+            )))
+        }
+    }
+
+    fn receiver_deleted() -> DomainEvent {
+        DomainEvent::ReceiverDeleted {
+            receiver: ReceiverFixture::new().build(),
+            deleted_at: Utc::now(),
+            system_event: None,
+        }
+    }
+
+    fn group_deleted() -> DomainEvent {
+        DomainEvent::GroupDeleted {
+            group: GroupFixture::new().build(),
+            deleted_at: Utc::now(),
+            system_event: None,
+        }
+    }
+
+    fn recorder(bus: &DomainEventBus, name: &str) -> mpsc::UnboundedReceiver<&'static str> {
+        let (seen, received) = mpsc::unbounded_channel();
+        bus.register(Arc::new(Recorder {
+            name: name.to_string(),
+            seen,
+        }));
+        received
+    }
+
+    async fn next(received: &mut mpsc::UnboundedReceiver<&'static str>) -> &'static str {
+        tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .expect("notification not delivered")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_every_subscriber_receives_each_notification_in_order() {
+        let bus = DomainEventBus::new();
+        let mut first = recorder(&bus, "first");
+        let mut second = recorder(&bus, "second");
+
+        bus.publish(receiver_deleted());
+        bus.publish(group_deleted());
+
+        for received in [&mut first, &mut second] {
+            assert_eq!(next(received).await, "receiver_deleted");
+            assert_eq!(next(received).await, "group_deleted");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_and_panicking_subscriber_does_not_affect_others() {
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let bus = DomainEventBus::new().with_metrics(metrics.clone());
+        let faulty = Arc::new(Faulty {
+            calls: Mutex::new(0),
+        });
+        bus.register(faulty.clone());
+        let mut healthy = recorder(&bus, "healthy");
+
+        bus.publish(receiver_deleted());
+        bus.publish(group_deleted());
+        bus.publish(receiver_deleted());
+
+        assert_eq!(next(&mut healthy).await, "receiver_deleted");
+        assert_eq!(next(&mut healthy).await, "group_deleted");
+        assert_eq!(next(&mut healthy).await, "receiver_deleted");
+
+        // The faulty subscriber kept receiving after its first panic
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while *faulty.calls.lock().unwrap() < 3 {
+            assert!(tokio::time::Instant::now() < deadline);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_domain_event_subscriber_failures_total{event=\"receiver_deleted\",reason=\"panic\",subscriber=\"faulty\"} 2"
+        ));
+        assert!(output.contains(
+            "xzepr_domain_event_subscriber_failures_total{event=\"group_deleted\",reason=\"error\",subscriber=\"faulty\"} 1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_skips_ahead_and_reports_lag() {
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let bus = DomainEventBus::with_capacity(2).with_metrics(metrics.clone());
+        let mut slow = recorder(&bus, "slow");
+
+        // The subscriber task cannot run before the test awaits
+        bus.publish(receiver_deleted());
+        for _ in 0..4 {
+            bus.publish(group_deleted());
+        }
+
+        assert_eq!(next(&mut slow).await, "group_deleted");
+        assert_eq!(next(&mut slow).await, "group_deleted");
+        assert!(slow.try_recv().is_err());
+
+        let output = metrics.gather().unwrap();
+        assert!(
+            output.contains("xzepr_domain_event_subscriber_lagged_total{subscriber=\"slow\"} 3")
+        );
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_is_a_no_op() {
+        DomainEventBus::new().publish(receiver_deleted());
+    }
+}
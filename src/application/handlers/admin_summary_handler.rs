@@ -0,0 +1,533 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/admin_summary_handler.rs
+
+use crate::application::handlers::event_stats_handler::EventStatsHandler;
+use crate::domain::repositories::event_outbox_repo::EventOutboxRepository;
+use crate::domain::repositories::system_summary_repo::{
+    EventOutcomeCounts, GroupCounts, ReceiverVolume, SystemSummaryRepository,
+};
+use crate::error::Result;
+use crate::infrastructure::messaging::producer::KafkaEventPublisher;
+use crate::infrastructure::monitoring::SecurityMonitor;
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+/// Number of receivers listed in the top-volume ranking
+pub const TOP_RECEIVER_LIMIT: usize = 10;
+
+/// Receivers split by whether they created events in the last 24 hours
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReceiverCounts {
+    pub total: u64,
+    pub active: u64,
+    pub idle: u64,
+}
+
+/// System-wide statistics for the admin overview
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemSummary {
+    pub generated_at: DateTime<Utc>,
+    pub receivers: ReceiverCounts,
+    pub groups: GroupCounts,
+    pub events_last_hour: EventOutcomeCounts,
+    pub events_last_day: EventOutcomeCounts,
+    pub events_last_week: EventOutcomeCounts,
+    /// Busiest receivers over the last 24 hours
+    pub top_receivers: Vec<ReceiverVolume>,
+    /// Failed Kafka sends since start; `None` when publication is disabled
+    pub kafka_publish_failures: Option<u64>,
+    /// Events that exhausted their publish retries; `None` without an outbox
+    pub dead_letter_backlog: Option<u64>,
+    /// Events waiting for a publish retry; `None` without an outbox
+    pub outbox_backlog: Option<u64>,
+    pub active_users: u64,
+    /// Recent share of rejected requests; `None` without a security monitor
+    pub rate_limit_rejection_rate: Option<f64>,
+}
+
+/// Event aggregates over the summary's time windows
+struct EventWindows {
+    active_receivers: u64,
+    last_hour: EventOutcomeCounts,
+    last_day: EventOutcomeCounts,
+    last_week: EventOutcomeCounts,
+    top_receivers: Vec<ReceiverVolume>,
+}
+
+/// Application service building the admin overview
+///
+/// Summaries are cached for a few seconds so dashboard refreshes do not
+/// reach the database. Concurrent requests for an expired summary wait for
+/// a single refresh.
+#[derive(Clone)]
+pub struct AdminSummaryHandler {
+    repository: Arc<dyn SystemSummaryRepository>,
+    event_stats: Option<EventStatsHandler>,
+    event_publisher: Option<Arc<KafkaEventPublisher>>,
+    outbox: Option<Arc<dyn EventOutboxRepository>>,
+    monitor: Option<Arc<SecurityMonitor>>,
+    cache_ttl: Duration,
+    cache: Arc<Mutex<Option<(Instant, SystemSummary)>>>,
+}
+
+impl AdminSummaryHandler {
+    /// Creates a new admin summary handler with a 5 second cache
+    pub fn new(repository: Arc<dyn SystemSummaryRepository>) -> Self {
+        Self {
+            repository,
+            event_stats: None,
+            event_publisher: None,
+            outbox: None,
+            monitor: None,
+            cache_ttl: Duration::from_secs(5),
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Reads event counts from the hourly rollup instead of raw events
+    pub fn with_event_stats(mut self, event_stats: EventStatsHandler) -> Self {
+        self.event_stats = Some(event_stats);
+        self
+    }
+
+    /// Reports Kafka publish failures from this publisher
+    pub fn with_publisher(mut self, event_publisher: Arc<KafkaEventPublisher>) -> Self {
+        self.event_publisher = Some(event_publisher);
+        self
+    }
+
+    /// Reports the backlog of deferred and dead-lettered publications
+    pub fn with_outbox(mut self, outbox: Arc<dyn EventOutboxRepository>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
+    /// Reports the rate limit rejection rate seen by this monitor
+    pub fn with_monitor(mut self, monitor: Arc<SecurityMonitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Sets how long a summary is served from cache
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Returns the system summary, from cache when it is fresh enough
+    pub async fn summary(&self) -> Result<SystemSummary> {
+        let mut cache = self.cache.lock().await;
+        if let Some((loaded_at, summary)) = cache.as_ref() {
+            if loaded_at.elapsed() < self.cache_ttl {
+                debug!("Serving admin summary from cache");
+                return Ok(summary.clone());
+            }
+        }
+
+        let summary = self.load(Utc::now()).await?;
+        *cache = Some((Instant::now(), summary.clone()));
+        Ok(summary)
+    }
+
+    async fn load(&self, now: DateTime<Utc>) -> Result<SystemSummary> {
+        info!("Loading admin summary");
+
+        let total_receivers = self.repository.count_receivers().await?;
+        let backlog = match &self.outbox {
+            Some(outbox) => Some(outbox.backlog().await?),
+            None => None,
+        };
+        let events = match &self.event_stats {
+            Some(stats) => self.load_event_windows_from_stats(stats, now).await?,
+            None => self.load_event_windows(now).await?,
+        };
+
+        Ok(SystemSummary {
+            generated_at: now,
+            receivers: ReceiverCounts {
+                total: total_receivers,
+                active: events.active_receivers,
+                idle: total_receivers.saturating_sub(events.active_receivers),
+            },
+            groups: self.repository.count_groups().await?,
+            events_last_hour: events.last_hour,
+            events_last_day: events.last_day,
+            events_last_week: events.last_week,
+            top_receivers: events.top_receivers,
+            kafka_publish_failures: self
+                .event_publisher
+                .as_ref()
+                .map(|publisher| publisher.publish_failure_count()),
+            dead_letter_backlog: backlog.map(|b| b.dead_lettered),
+            outbox_backlog: backlog.map(|b| b.pending),
+            active_users: self.repository.count_active_users().await?,
+            rate_limit_rejection_rate: self
+                .monitor
+                .as_ref()
+                .map(|monitor| monitor.rate_limit_rejection_rate()),
+        })
+    }
+
+    async fn load_event_windows(&self, now: DateTime<Utc>) -> Result<EventWindows> {
+        let last_day = now - ChronoDuration::hours(24);
+
+        Ok(EventWindows {
+            active_receivers: self.repository.count_active_receivers(last_day).await?,
+            last_hour: self
+                .repository
+                .count_events_since(now - ChronoDuration::hours(1))
+                .await?,
+            last_day: self.repository.count_events_since(last_day).await?,
+            last_week: self
+                .repository
+                .count_events_since(now - ChronoDuration::days(7))
+                .await?,
+            top_receivers: self
+                .repository
+                .top_receivers_since(last_day, TOP_RECEIVER_LIMIT)
+                .await?,
+        })
+    }
+
+    async fn load_event_windows_from_stats(
+        &self,
+        stats: &EventStatsHandler,
+        now: DateTime<Utc>,
+    ) -> Result<EventWindows> {
+        let day = stats
+            .receiver_counts(now - ChronoDuration::hours(24), now)
+            .await?;
+        let last_day = day
+            .iter()
+            .fold(EventOutcomeCounts::default(), |mut total, counts| {
+                total.successful += counts.successful;
+                total.failed += counts.failed;
+                total
+            });
+
+        let busiest: Vec<_> = day.iter().take(TOP_RECEIVER_LIMIT).collect();
+        let ids: Vec<_> = busiest.iter().map(|counts| counts.receiver_id).collect();
+        let mut names = self.repository.receiver_names(&ids).await?;
+        let top_receivers = busiest
+            .into_iter()
+            .map(|counts| ReceiverVolume {
+                receiver_id: counts.receiver_id,
+                name: names.remove(&counts.receiver_id).unwrap_or_default(),
+                event_count: counts.total(),
+            })
+            .collect();
+
+        Ok(EventWindows {
+            active_receivers: day.len() as u64,
+            last_hour: stats
+                .outcome_counts(now - ChronoDuration::hours(1), now)
+                .await?,
+            last_day,
+            last_week: stats
+                .outcome_counts(now - ChronoDuration::days(7), now)
+                .await?,
+            top_receivers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::event_rollup_repo::{
+        EventCountBucket, EventCountRepository, ReceiverEventCounts,
+    };
+    use crate::domain::value_objects::EventReceiverId;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct SeededEvent {
+        receiver: usize,
+        success: bool,
+        age: ChronoDuration,
+    }
+
+    /// Computes aggregates from seeded rows and counts every query
+    struct MockSummaryRepository {
+        receivers: Vec<(EventReceiverId, String)>,
+        groups_enabled: Vec<bool>,
+        users_enabled: Vec<bool>,
+        events: Vec<SeededEvent>,
+        now: DateTime<Utc>,
+        queries: AtomicUsize,
+    }
+
+    impl MockSummaryRepository {
+        fn events_since(&self, since: DateTime<Utc>) -> impl Iterator<Item = &SeededEvent> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            self.events
+                .iter()
+                .filter(move |e| self.now - e.age >= since)
+        }
+    }
+
+    #[async_trait]
+    impl SystemSummaryRepository for MockSummaryRepository {
+        async fn count_receivers(&self) -> Result<u64> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            Ok(self.receivers.len() as u64)
+        }
+
+        async fn count_active_receivers(&self, since: DateTime<Utc>) -> Result<u64> {
+            let mut active: Vec<usize> = self.events_since(since).map(|e| e.receiver).collect();
+            active.sort();
+            active.dedup();
+            Ok(active.len() as u64)
+        }
+
+        async fn count_groups(&self) -> Result<GroupCounts> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            let enabled = self.groups_enabled.iter().filter(|e| **e).count() as u64;
+            Ok(GroupCounts {
+                enabled,
+                disabled: self.groups_enabled.len() as u64 - enabled,
+            })
+        }
+
+        async fn count_events_since(&self, since: DateTime<Utc>) -> Result<EventOutcomeCounts> {
+            let mut counts = EventOutcomeCounts::default();
+            for event in self.events_since(since) {
+                if event.success {
+                    counts.successful += 1;
+                } else {
+                    counts.failed += 1;
+                }
+            }
+            Ok(counts)
+        }
+
+        async fn top_receivers_since(
+            &self,
+            since: DateTime<Utc>,
+            limit: usize,
+        ) -> Result<Vec<ReceiverVolume>> {
+            let mut counts = vec![0u64; self.receivers.len()];
+            for event in self.events_since(since) {
+                counts[event.receiver] += 1;
+            }
+            let mut volumes: Vec<ReceiverVolume> = self
+                .receivers
+                .iter()
+                .zip(counts)
+                .filter(|(_, count)| *count > 0)
+                .map(|((id, name), event_count)| ReceiverVolume {
+                    receiver_id: *id,
+                    name: name.clone(),
+                    event_count,
+                })
+                .collect();
+            volumes.sort_by_key(|v| std::cmp::Reverse(v.event_count));
+            volumes.truncate(limit);
+            Ok(volumes)
+        }
+
+        async fn receiver_names(
+            &self,
+            ids: &[EventReceiverId],
+        ) -> Result<HashMap<EventReceiverId, String>> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            Ok(self
+                .receivers
+                .iter()
+                .filter(|(id, _)| ids.contains(id))
+                .cloned()
+                .collect())
+        }
+
+        async fn count_active_users(&self) -> Result<u64> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            Ok(self.users_enabled.iter().filter(|e| **e).count() as u64)
+        }
+    }
+
+    /// Serves both the raw and rollup side of the stats handler
+    #[async_trait]
+    impl EventCountRepository for MockSummaryRepository {
+        async fn receiver_event_counts(
+            &self,
+            start: DateTime<Utc>,
+            end: DateTime<Utc>,
+        ) -> Result<Vec<ReceiverEventCounts>> {
+            let mut counts: Vec<ReceiverEventCounts> = Vec::new();
+            for event in &self.events {
+                let created_at = self.now - event.age;
+                if created_at < start || created_at >= end {
+                    continue;
+                }
+                let receiver_id = self.receivers[event.receiver].0;
+                let index = match counts.iter().position(|c| c.receiver_id == receiver_id) {
+                    Some(index) => index,
+                    None => {
+                        counts.push(ReceiverEventCounts {
+                            receiver_id,
+                            successful: 0,
+                            failed: 0,
+                        });
+                        counts.len() - 1
+                    }
+                };
+                if event.success {
+                    counts[index].successful += 1;
+                } else {
+                    counts[index].failed += 1;
+                }
+            }
+            Ok(counts)
+        }
+
+        async fn bucket_counts(
+            &self,
+            _start: DateTime<Utc>,
+            _end: DateTime<Utc>,
+        ) -> Result<Vec<EventCountBucket>> {
+            // Only the reconciliation job reads buckets
+            Ok(Vec::new())
+        }
+    }
+
+    fn seeded_repository() -> Arc<MockSummaryRepository> {
+        let receivers: Vec<_> = (0..12)
+            .map(|n| (EventReceiverId::new(), format!("receiver-{}", n)))
+            .collect();
+
+        let mut events = Vec::new();
+        // Receiver n created n + 1 events in the last 24 hours; receiver 0 failed
+        for receiver in 0..11 {
+            for _ in 0..=receiver {
+                events.push(SeededEvent {
+                    receiver,
+                    success: receiver != 0,
+                    age: ChronoDuration::hours(2),
+                });
+            }
+        }
+        events.push(SeededEvent {
+            receiver: 10,
+            success: false,
+            age: ChronoDuration::minutes(10),
+        });
+        // Receiver 11 was only active three days ago
+        events.push(SeededEvent {
+            receiver: 11,
+            success: true,
+            age: ChronoDuration::days(3),
+        });
+        events.push(SeededEvent {
+            receiver: 11,
+            success: true,
+            age: ChronoDuration::days(30),
+        });
+
+        Arc::new(MockSummaryRepository {
+            receivers,
+            groups_enabled: vec![true, true, false],
+            users_enabled: vec![true, false, true, true],
+            events,
+            now: Utc::now(),
+            queries: AtomicUsize::new(0),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_summary_aggregates_seeded_data() {
+        let repo = seeded_repository();
+        let monitor = Arc::new(SecurityMonitor::new());
+        monitor.record_rate_limit_pass();
+        monitor.record_rate_limit_rejection("client", "/api/v1/events", 10);
+        let handler = AdminSummaryHandler::new(repo.clone()).with_monitor(monitor);
+
+        let summary = handler.load(repo.now).await.unwrap();
+
+        assert_eq!(
+            summary.receivers,
+            ReceiverCounts {
+                total: 12,
+                active: 11,
+                idle: 1
+            }
+        );
+        assert_eq!(
+            summary.groups,
+            GroupCounts {
+                enabled: 2,
+                disabled: 1
+            }
+        );
+        assert_eq!(
+            summary.events_last_hour,
+            EventOutcomeCounts {
+                successful: 0,
+                failed: 1
+            }
+        );
+        // 66 events two hours ago, one of them failed, plus the recent failure
+        assert_eq!(
+            summary.events_last_day,
+            EventOutcomeCounts {
+                successful: 65,
+                failed: 2
+            }
+        );
+        assert_eq!(summary.events_last_week.total(), 68);
+        assert_eq!(summary.top_receivers.len(), TOP_RECEIVER_LIMIT);
+        assert_eq!(summary.top_receivers[0].name, "receiver-10");
+        assert_eq!(summary.top_receivers[0].event_count, 12);
+        assert_eq!(summary.top_receivers[9].name, "receiver-1");
+        assert_eq!(summary.kafka_publish_failures, None);
+        assert_eq!(summary.dead_letter_backlog, None);
+        assert_eq!(summary.outbox_backlog, None);
+        assert_eq!(summary.active_users, 3);
+        assert_eq!(summary.rate_limit_rejection_rate, Some(0.5));
+    }
+
+    #[tokio::test]
+    async fn test_summary_from_rollup_matches_raw_queries() {
+        let repo = seeded_repository();
+        let raw = AdminSummaryHandler::new(repo.clone());
+        let stitched = AdminSummaryHandler::new(repo.clone())
+            .with_event_stats(EventStatsHandler::new(repo.clone(), repo.clone()));
+
+        assert_eq!(
+            stitched.load(repo.now).await.unwrap(),
+            raw.load(repo.now).await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_summary_is_served_from_cache() {
+        let repo = seeded_repository();
+        let handler = AdminSummaryHandler::new(repo.clone());
+
+        let first = handler.summary().await.unwrap();
+        let queries = repo.queries.load(Ordering::SeqCst);
+        assert!(queries > 0);
+
+        let second = handler.summary().await.unwrap();
+        assert_eq!(repo.queries.load(Ordering::SeqCst), queries);
+        assert_eq!(second, first);
+    }
+
+    #[tokio::test]
+    async fn test_expired_summary_is_reloaded() {
+        let repo = seeded_repository();
+        let handler = AdminSummaryHandler::new(repo.clone()).with_cache_ttl(Duration::ZERO);
+
+        handler.summary().await.unwrap();
+        let queries = repo.queries.load(Ordering::SeqCst);
+        handler.summary().await.unwrap();
+
+        assert_eq!(repo.queries.load(Ordering::SeqCst), 2 * queries);
+    }
+}
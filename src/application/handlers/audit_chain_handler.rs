@@ -0,0 +1,296 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/audit_chain_handler.rs
+
+use crate::domain::entities::audit_chain::{AuditChainReport, AuditChainVerifier};
+use crate::domain::repositories::audit_record_repo::AuditRecordRepository;
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+
+use chrono::NaiveDate;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Default number of records read per page while verifying
+pub const DEFAULT_VERIFY_PAGE_SIZE: usize = 1000;
+
+/// Application service verifying the audit record hash chain
+#[derive(Clone)]
+pub struct AuditChainHandler {
+    records: Arc<dyn AuditRecordRepository>,
+    page_size: usize,
+}
+
+impl AuditChainHandler {
+    /// Creates a handler reading the default page size
+    pub fn new(records: Arc<dyn AuditRecordRepository>) -> Self {
+        Self {
+            records,
+            page_size: DEFAULT_VERIFY_PAGE_SIZE,
+        }
+    }
+
+    /// Sets the number of records read per page
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Walks the chains of the days `from` through `to` and reports the
+    /// first broken link
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `from` is after `to`.
+    pub async fn verify(&self, from: NaiveDate, to: NaiveDate) -> Result<AuditChainReport> {
+        if from > to {
+            return Err(DomainError::ValidationError {
+                field: "from".to_string(),
+                message: Message::new("validation.audit_range_order"),
+            }
+            .into());
+        }
+
+        let mut verifier = AuditChainVerifier::new();
+        let mut after = None;
+        'pages: loop {
+            let page = self
+                .records
+                .find_records(from, to, after, self.page_size)
+                .await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = Some((last.chain_day, last.position));
+            let exhausted = page.len() < self.page_size;
+
+            for record in &page {
+                let predecessor = if verifier.needs_predecessor(record) {
+                    self.records
+                        .find_link(record.chain_day, record.position - 1)
+                        .await?
+                } else {
+                    None
+                };
+                if !verifier.check(record, predecessor.as_ref()) {
+                    break 'pages;
+                }
+            }
+
+            if exhausted {
+                break;
+            }
+        }
+
+        let report = verifier.finish();
+        match &report.first_broken {
+            None => info!(
+                from = %from,
+                to = %to,
+                records = report.records_checked,
+                "Audit chain verified"
+            ),
+            Some(broken) => warn!(
+                from = %from,
+                to = %to,
+                chain_day = %broken.chain_day,
+                position = broken.position,
+                record_id = broken.record_id,
+                fault = ?broken.fault,
+                "Audit chain is broken"
+            ),
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::audit_chain::{
+        next_link, record_hash, AuditChainFault, AuditRecord, ChainLink,
+    };
+    use crate::domain::repositories::audit_record_repo::PurgedAuditRecords;
+    use async_trait::async_trait;
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::sync::Mutex;
+
+    /// Records and anchors kept in chain order
+    #[derive(Default)]
+    struct MemoryRecords {
+        records: Mutex<Vec<AuditRecord>>,
+        anchors: Mutex<Vec<ChainLink>>,
+    }
+
+    #[async_trait]
+    impl AuditRecordRepository for MemoryRecords {
+        async fn append(&self, recorded_at: DateTime<Utc>, content: String) -> Result<AuditRecord> {
+            let mut records = self.records.lock().unwrap();
+            let chain_day = recorded_at.date_naive();
+            let last = records
+                .iter()
+                .rev()
+                .find(|r| r.chain_day == chain_day)
+                .map(AuditRecord::link);
+            let (position, prev_hash) = next_link(chain_day, last.as_ref());
+            let record = AuditRecord {
+                id: records.len() as i64 + 1,
+                chain_day,
+                position,
+                recorded_at,
+                hash: record_hash(&prev_hash, chain_day, position, &content),
+                content,
+                prev_hash,
+            };
+            records.push(record.clone());
+            Ok(record)
+        }
+
+        async fn find_records(
+            &self,
+            from: NaiveDate,
+            to: NaiveDate,
+            after: Option<(NaiveDate, i64)>,
+            limit: usize,
+        ) -> Result<Vec<AuditRecord>> {
+            Ok(self
+                .records
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.chain_day >= from && r.chain_day <= to)
+                .filter(|r| after.is_none_or(|cursor| (r.chain_day, r.position) > cursor))
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_link(
+            &self,
+            chain_day: NaiveDate,
+            position: i64,
+        ) -> Result<Option<ChainLink>> {
+            let records = self.records.lock().unwrap();
+            let stored = records
+                .iter()
+                .find(|r| r.chain_day == chain_day && r.position == position)
+                .map(AuditRecord::link);
+            Ok(stored.or_else(|| {
+                self.anchors
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|a| a.chain_day == chain_day && a.position == position)
+                    .cloned()
+            }))
+        }
+
+        async fn purge_before(
+            &self,
+            before: NaiveDate,
+            limit: usize,
+        ) -> Result<PurgedAuditRecords> {
+            let mut records = self.records.lock().unwrap();
+            let count = records
+                .iter()
+                .take_while(|r| r.chain_day < before)
+                .take(limit)
+                .count();
+            let purged: Vec<AuditRecord> = records.drain(..count).collect();
+            let mut anchors = self.anchors.lock().unwrap();
+            let mut report = PurgedAuditRecords {
+                records: purged.len(),
+                anchors: 0,
+            };
+            for (i, record) in purged.iter().enumerate() {
+                if purged.get(i + 1).map(|next| next.chain_day) != Some(record.chain_day) {
+                    anchors.push(record.link());
+                    report.anchors += 1;
+                }
+            }
+            Ok(report)
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 5, d).unwrap()
+    }
+
+    async fn seed(records: &MemoryRecords, days: &[u32], per_day: u32) {
+        for &d in days {
+            for n in 0..per_day {
+                let at = Utc.from_utc_datetime(&day(d).and_hms_opt(9, n, 0).unwrap());
+                records
+                    .append(at, format!(r#"{{"action":"login","n":{}}}"#, n))
+                    .await
+                    .unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verifies_seeded_records_across_pages() {
+        let records = Arc::new(MemoryRecords::default());
+        seed(&records, &[1, 2, 3], 7).await;
+        let handler = AuditChainHandler::new(records).with_page_size(3);
+
+        let report = handler.verify(day(1), day(3)).await.unwrap();
+        assert!(report.is_intact());
+        assert_eq!(report.records_checked, 21);
+        assert_eq!(report.days_checked, 3);
+
+        let report = handler.verify(day(2), day(2)).await.unwrap();
+        assert_eq!(report.records_checked, 7);
+    }
+
+    #[tokio::test]
+    async fn test_reports_the_position_of_a_mutated_record() {
+        let records = Arc::new(MemoryRecords::default());
+        seed(&records, &[1, 2], 5).await;
+        records.records.lock().unwrap()[8].content = r#"{"action":"logout"}"#.to_string();
+        let handler = AuditChainHandler::new(records).with_page_size(2);
+
+        let report = handler.verify(day(1), day(2)).await.unwrap();
+
+        let broken = report.first_broken.unwrap();
+        assert_eq!((broken.chain_day, broken.position), (day(2), 3));
+        assert_eq!(broken.record_id, 9);
+        assert_eq!(broken.fault, AuditChainFault::ContentMismatch);
+    }
+
+    #[tokio::test]
+    async fn test_remaining_records_verify_after_purge() {
+        let records = Arc::new(MemoryRecords::default());
+        seed(&records, &[1, 2], 5).await;
+        let handler = AuditChainHandler::new(records.clone());
+
+        // Purge all of the first day and part of the second
+        let purged = records.purge_before(day(3), 7).await.unwrap();
+        assert_eq!(purged.records, 7);
+        assert_eq!(purged.anchors, 2);
+
+        let report = handler.verify(day(1), day(2)).await.unwrap();
+        assert!(report.is_intact());
+        assert_eq!(report.records_checked, 3);
+
+        // Without its anchor the window no longer links up
+        records.anchors.lock().unwrap().clear();
+        let broken = handler
+            .verify(day(1), day(2))
+            .await
+            .unwrap()
+            .first_broken
+            .unwrap();
+        assert_eq!(broken.position, 2);
+        assert_eq!(broken.fault, AuditChainFault::MissingPredecessor);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_reversed_range() {
+        let handler = AuditChainHandler::new(Arc::new(MemoryRecords::default()));
+
+        assert!(handler.verify(day(2), day(1)).await.is_err());
+    }
+}
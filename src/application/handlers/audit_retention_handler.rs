@@ -0,0 +1,175 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/audit_retention_handler.rs
+
+use crate::domain::repositories::audit_record_repo::{AuditRecordRepository, PurgedAuditRecords};
+use crate::error::Result;
+use crate::infrastructure::jobs::{Job, JobReport, JobSchedule};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use tracing::info;
+
+/// Default number of days audit records are kept
+pub const DEFAULT_AUDIT_RETENTION_DAYS: u32 = 400;
+
+/// Default number of audit records deleted per pass
+pub const DEFAULT_AUDIT_RETENTION_BATCH_SIZE: usize = 1000;
+
+/// Default time between retention passes
+pub const DEFAULT_AUDIT_RETENTION_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(86400);
+
+/// Name of the audit retention job in settings and the admin API
+pub const AUDIT_RETENTION_JOB_NAME: &str = "audit_retention";
+
+/// Background job deleting audit records older than the retention window
+///
+/// Whole days are expired: a record is deleted once every record of its
+/// day is older than the window. Days are purged oldest record first, and
+/// each pass anchors the days it touched so the records left behind keep
+/// verifying.
+#[derive(Clone)]
+pub struct AuditRetentionHandler {
+    records: Arc<dyn AuditRecordRepository>,
+    retention_days: u32,
+    batch_size: usize,
+    interval: std::time::Duration,
+}
+
+impl AuditRetentionHandler {
+    /// Creates a handler keeping records for 400 days, running daily
+    pub fn new(records: Arc<dyn AuditRecordRepository>) -> Self {
+        Self {
+            records,
+            retention_days: DEFAULT_AUDIT_RETENTION_DAYS,
+            batch_size: DEFAULT_AUDIT_RETENTION_BATCH_SIZE,
+            interval: DEFAULT_AUDIT_RETENTION_INTERVAL,
+        }
+    }
+
+    /// Sets how many days records are kept
+    pub fn with_retention_days(mut self, retention_days: u32) -> Self {
+        self.retention_days = retention_days.max(1);
+        self
+    }
+
+    /// Sets the maximum number of records deleted per pass
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the time between passes when run as a job
+    pub fn with_interval(mut self, interval: std::time::Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Deletes up to one batch of records expired at `now`
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<PurgedAuditRecords> {
+        let cutoff = (now - Duration::days(i64::from(self.retention_days))).date_naive();
+        self.records.purge_before(cutoff, self.batch_size).await
+    }
+}
+
+#[async_trait]
+impl Job for AuditRetentionHandler {
+    fn name(&self) -> &str {
+        AUDIT_RETENTION_JOB_NAME
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::Every(self.interval)
+    }
+
+    async fn run(&self) -> Result<JobReport> {
+        let purged = self.run_once(Utc::now()).await?;
+        if purged.records > 0 {
+            info!(
+                records = purged.records,
+                anchors = purged.anchors,
+                retention_days = self.retention_days,
+                "Purged expired audit records"
+            );
+        }
+        Ok(JobReport {
+            processed: purged.records,
+            more_pending: purged.records >= self.batch_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::audit_chain::{AuditRecord, ChainLink};
+    use chrono::{NaiveDate, TimeZone};
+    use std::sync::Mutex;
+
+    /// Records the cutoff and limit of each purge
+    #[derive(Default)]
+    struct RecordingRecords {
+        purges: Mutex<Vec<(NaiveDate, usize)>>,
+    }
+
+    #[async_trait]
+    impl AuditRecordRepository for RecordingRecords {
+        async fn append(
+            &self,
+            _recorded_at: DateTime<Utc>,
+            _content: String,
+        ) -> Result<AuditRecord> {
+            unreachable!("retention never appends")
+        }
+
+        async fn find_records(
+            &self,
+            _from: NaiveDate,
+            _to: NaiveDate,
+            _after: Option<(NaiveDate, i64)>,
+            _limit: usize,
+        ) -> Result<Vec<AuditRecord>> {
+            unreachable!("retention never reads records")
+        }
+
+        async fn find_link(&self, _day: NaiveDate, _position: i64) -> Result<Option<ChainLink>> {
+            unreachable!("retention never reads links")
+        }
+
+        async fn purge_before(
+            &self,
+            before: NaiveDate,
+            limit: usize,
+        ) -> Result<PurgedAuditRecords> {
+            self.purges.lock().unwrap().push((before, limit));
+            Ok(PurgedAuditRecords {
+                records: limit,
+                anchors: 1,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_purges_days_older_than_the_window() {
+        let records = Arc::new(RecordingRecords::default());
+        let handler = AuditRetentionHandler::new(records.clone())
+            .with_retention_days(400)
+            .with_batch_size(50);
+
+        let now = Utc.with_ymd_and_hms(2026, 6, 5, 0, 30, 0).unwrap();
+        let purged = handler.run_once(now).await.unwrap();
+        assert_eq!(purged.records, 50);
+
+        // Records of the day the window starts on are all kept
+        assert_eq!(
+            records.purges.lock().unwrap()[0],
+            (NaiveDate::from_ymd_opt(2025, 5, 1).unwrap(), 50)
+        );
+
+        let report = handler.run().await.unwrap();
+        assert!(report.more_pending);
+    }
+}
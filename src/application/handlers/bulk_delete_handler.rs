@@ -0,0 +1,543 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/bulk_delete_handler.rs
+
+use crate::application::handlers::{ResourceHistoryHandler, SchemaResolver};
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// Default cap on the receivers and groups a single bulk delete may resolve
+pub const DEFAULT_BULK_DELETE_MAX_RESOURCES: usize = 100;
+
+/// Receivers and groups a bulk delete applies to
+///
+/// Explicit ids and name prefixes are combined; a resource matched more
+/// than once is only counted once. Prefixes are case sensitive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkDeleteSelector {
+    pub receiver_ids: Vec<EventReceiverId>,
+    pub receiver_name_prefixes: Vec<String>,
+    pub group_ids: Vec<EventReceiverGroupId>,
+    pub group_name_prefixes: Vec<String>,
+}
+
+impl BulkDeleteSelector {
+    /// Returns true if the selector names nothing
+    pub fn is_empty(&self) -> bool {
+        self.receiver_ids.is_empty()
+            && self.receiver_name_prefixes.is_empty()
+            && self.group_ids.is_empty()
+            && self.group_name_prefixes.is_empty()
+    }
+}
+
+/// A selected resource that cannot be deleted, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedResource {
+    /// `receiver` or `group`
+    pub resource_type: &'static str,
+    pub id: String,
+    pub reasons: Vec<String>,
+}
+
+/// What a bulk delete removed, or would remove on a dry run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkDeleteReport {
+    pub dry_run: bool,
+    /// Groups deleted, in deletion order
+    pub groups: Vec<EventReceiverGroupId>,
+    /// Receivers deleted after the groups, in deletion order
+    pub receivers: Vec<EventReceiverId>,
+    /// Selected resources left in place
+    pub blocked: Vec<BlockedResource>,
+}
+
+/// Application handler for deleting many receivers and groups at once
+///
+/// Receivers still referenced by events, or belonging to a group that is
+/// not deleted in the same call, are reported as blocked and left alone.
+/// Groups are deleted before receivers so memberships between selected
+/// resources never block each other.
+#[derive(Clone)]
+pub struct BulkDeleteHandler {
+    receiver_repository: Arc<dyn EventReceiverRepository>,
+    group_repository: Arc<dyn EventReceiverGroupRepository>,
+    event_repository: Arc<dyn EventRepository>,
+    max_resources: usize,
+    audit_logger: Arc<AuditLogger>,
+    schema_resolver: Option<SchemaResolver>,
+    history: Option<ResourceHistoryHandler>,
+}
+
+impl BulkDeleteHandler {
+    /// Creates a new bulk delete handler
+    pub fn new(
+        receiver_repository: Arc<dyn EventReceiverRepository>,
+        group_repository: Arc<dyn EventReceiverGroupRepository>,
+        event_repository: Arc<dyn EventRepository>,
+    ) -> Self {
+        Self {
+            receiver_repository,
+            group_repository,
+            event_repository,
+            max_resources: DEFAULT_BULK_DELETE_MAX_RESOURCES,
+            audit_logger: Arc::new(AuditLogger::new()),
+            schema_resolver: None,
+            history: None,
+        }
+    }
+
+    /// Sets the cap on resources a single call may resolve
+    pub fn with_max_resources(mut self, max_resources: usize) -> Self {
+        self.max_resources = max_resources;
+        self
+    }
+
+    /// Uses `audit_logger` to record bulk deletions
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Drops cached effective schemas after groups are deleted
+    pub fn with_schema_resolver(mut self, schema_resolver: SchemaResolver) -> Self {
+        self.schema_resolver = Some(schema_resolver);
+        self
+    }
+
+    /// Records the deletions in receiver and group history
+    pub fn with_history(mut self, history: ResourceHistoryHandler) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Resolves `selector`, checks dependencies, and deletes what it can
+    ///
+    /// With `dry_run` nothing is deleted and the report lists what would
+    /// be. Every call, including dry runs and failures, is audit-logged
+    /// with the full resolved id lists.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for an empty selector and a business
+    /// rule violation when the selector resolves to more resources than
+    /// the configured cap.
+    pub async fn execute(
+        &self,
+        selector: &BulkDeleteSelector,
+        dry_run: bool,
+        actor: &str,
+    ) -> Result<BulkDeleteReport> {
+        if selector.is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "selector".to_string(),
+                message: Message::new("validation.selection_required"),
+            }
+            .into());
+        }
+
+        let mut report = BulkDeleteReport {
+            dry_run,
+            ..BulkDeleteReport::default()
+        };
+        let groups = self.resolve_groups(selector, &mut report).await?;
+        let receivers = self.resolve_receivers(selector, &mut report).await?;
+
+        let resolved = groups.len() + receivers.len();
+        if resolved > self.max_resources {
+            return Err(DomainError::BusinessRuleViolation {
+                rule: Message::new("rule.bulk_delete_limit")
+                    .with("selected", resolved)
+                    .with("max", self.max_resources),
+            }
+            .into());
+        }
+
+        let group_set: HashSet<EventReceiverGroupId> = groups.iter().copied().collect();
+        report.groups = groups;
+        for receiver_id in receivers {
+            let reasons = self.receiver_blockers(receiver_id, &group_set).await?;
+            if reasons.is_empty() {
+                report.receivers.push(receiver_id);
+            } else {
+                report.blocked.push(BlockedResource {
+                    resource_type: "receiver",
+                    id: receiver_id.to_string(),
+                    reasons,
+                });
+            }
+        }
+
+        if !dry_run {
+            if let Err(e) = self.delete(&report).await {
+                error!("Bulk delete failed: {}", e);
+                self.audit(&report, actor, Some(&e.to_string()));
+                return Err(e);
+            }
+        }
+
+        self.audit(&report, actor, None);
+        info!(
+            actor = %actor,
+            dry_run,
+            groups = report.groups.len(),
+            receivers = report.receivers.len(),
+            blocked = report.blocked.len(),
+            "Bulk delete completed"
+        );
+
+        Ok(report)
+    }
+
+    async fn resolve_groups(
+        &self,
+        selector: &BulkDeleteSelector,
+        report: &mut BulkDeleteReport,
+    ) -> Result<Vec<EventReceiverGroupId>> {
+        let mut ids = Vec::new();
+        for id in &selector.group_ids {
+            if self.group_repository.find_by_id(*id).await?.is_some() {
+                ids.push(*id);
+            } else {
+                report.blocked.push(not_found("group", id.to_string()));
+            }
+        }
+        for prefix in &selector.group_name_prefixes {
+            for group in self.group_repository.find_by_name(prefix).await? {
+                if group.name().starts_with(prefix.as_str()) {
+                    ids.push(group.id());
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+        Ok(ids)
+    }
+
+    async fn resolve_receivers(
+        &self,
+        selector: &BulkDeleteSelector,
+        report: &mut BulkDeleteReport,
+    ) -> Result<Vec<EventReceiverId>> {
+        let mut ids = Vec::new();
+        for id in &selector.receiver_ids {
+            if self.receiver_repository.find_by_id(*id).await?.is_some() {
+                ids.push(*id);
+            } else {
+                report.blocked.push(not_found("receiver", id.to_string()));
+            }
+        }
+        for prefix in &selector.receiver_name_prefixes {
+            for receiver in self.receiver_repository.find_by_name(prefix).await? {
+                if receiver.name().starts_with(prefix.as_str()) {
+                    ids.push(receiver.id());
+                }
+            }
+        }
+
+        let mut seen = HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+        Ok(ids)
+    }
+
+    /// Returns why a receiver cannot be deleted; empty if it can
+    async fn receiver_blockers(
+        &self,
+        receiver_id: EventReceiverId,
+        deleted_groups: &HashSet<EventReceiverGroupId>,
+    ) -> Result<Vec<String>> {
+        let mut reasons = Vec::new();
+
+        let events = self
+            .event_repository
+            .count_by_receiver_id(receiver_id)
+            .await?;
+        if events > 0 {
+            reasons.push(format!("Referenced by {} events", events));
+        }
+
+        for group in self
+            .group_repository
+            .find_by_event_receiver_id(receiver_id)
+            .await?
+        {
+            if !deleted_groups.contains(&group.id()) {
+                reasons.push(format!("Member of group {}", group.id()));
+            }
+        }
+
+        Ok(reasons)
+    }
+
+    async fn delete(&self, report: &BulkDeleteReport) -> Result<()> {
+        if !report.groups.is_empty() {
+            self.group_repository.delete_many(&report.groups).await?;
+            if let Some(resolver) = &self.schema_resolver {
+                resolver.invalidate().await;
+            }
+        }
+        if !report.receivers.is_empty() {
+            self.receiver_repository
+                .delete_many(&report.receivers)
+                .await?;
+        }
+
+        if let Some(history) = &self.history {
+            let now = Utc::now();
+            for id in &report.groups {
+                history.record_group_deleted(*id, now).await;
+            }
+            for id in &report.receivers {
+                history.record_receiver_deleted(*id, now).await;
+            }
+        }
+        Ok(())
+    }
+
+    fn audit(&self, report: &BulkDeleteReport, actor: &str, failure: Option<&str>) {
+        let blocked: Vec<String> = report
+            .blocked
+            .iter()
+            .map(|resource| format!("{}:{}", resource.resource_type, resource.id))
+            .collect();
+
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(actor)
+                .action(AuditAction::ResourceDelete)
+                .resource("bulk_delete")
+                .outcome(if failure.is_some() {
+                    AuditOutcome::Error
+                } else {
+                    AuditOutcome::Success
+                })
+                .add_metadata("dry_run", report.dry_run.to_string())
+                .add_metadata("groups", join_ids(&report.groups))
+                .add_metadata("receivers", join_ids(&report.receivers))
+                .add_metadata("blocked", blocked.join(","))
+                .error_message_opt(failure)
+                .build(),
+        );
+    }
+}
+
+fn not_found(resource_type: &'static str, id: String) -> BlockedResource {
+    BlockedResource {
+        resource_type,
+        id,
+        reasons: vec!["Not found".to_string()],
+    }
+}
+
+fn join_ids<T: ToString>(ids: &[T]) -> String {
+    ids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{EventFixture, GroupFixture, ReceiverFixture};
+    use crate::infrastructure::memory::{
+        InMemoryEventReceiverGroupRepository, InMemoryEventReceiverRepository,
+        InMemoryEventRepository,
+    };
+
+    struct Fixture {
+        receivers: Arc<InMemoryEventReceiverRepository>,
+        groups: Arc<InMemoryEventReceiverGroupRepository>,
+        events: Arc<InMemoryEventRepository>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Self {
+                receivers: Arc::new(InMemoryEventReceiverRepository::new()),
+                groups: Arc::new(InMemoryEventReceiverGroupRepository::new()),
+                events: Arc::new(InMemoryEventRepository::new()),
+            }
+        }
+
+        async fn add_receiver(&self, name: &str) -> EventReceiverId {
+            ReceiverFixture::new()
+                .name(name)
+                .persist(&*self.receivers)
+                .await
+                .id()
+        }
+
+        async fn add_group(
+            &self,
+            name: &str,
+            receiver_ids: Vec<EventReceiverId>,
+        ) -> EventReceiverGroupId {
+            receiver_ids
+                .into_iter()
+                .fold(GroupFixture::new().name(name), GroupFixture::receiver_id)
+                .persist(&*self.groups)
+                .await
+                .id()
+        }
+
+        async fn add_events(&self, receiver_id: EventReceiverId, count: usize) {
+            for _ in 0..count {
+                EventFixture::new()
+                    .receiver_id(receiver_id)
+                    .persist(&*self.events)
+                    .await;
+            }
+        }
+
+        async fn receiver_names(&self) -> Vec<String> {
+            let receivers = self.receivers.find_by_name("").await.unwrap();
+            let mut names: Vec<String> = receivers.iter().map(|r| r.name().to_string()).collect();
+            names.sort();
+            names
+        }
+
+        async fn group_names(&self) -> Vec<String> {
+            let groups = self.groups.find_by_name("").await.unwrap();
+            let mut names: Vec<String> = groups.iter().map(|g| g.name().to_string()).collect();
+            names.sort();
+            names
+        }
+
+        fn handler(&self) -> BulkDeleteHandler {
+            BulkDeleteHandler::new(
+                self.receivers.clone(),
+                self.groups.clone(),
+                self.events.clone(),
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_matches_actual_deletion() {
+        let fixture = Fixture::new();
+        let grouped = fixture.add_receiver("legacy-grouped").await;
+        let with_events = fixture.add_receiver("legacy-events").await;
+        let shared = fixture.add_receiver("legacy-shared").await;
+        fixture.add_receiver("legacy-free").await;
+        fixture.add_receiver("current").await;
+        fixture.add_group("legacy-group", vec![grouped]).await;
+        let other = fixture.add_group("current-group", vec![shared]).await;
+        fixture.add_events(with_events, 3).await;
+
+        let selector = BulkDeleteSelector {
+            receiver_name_prefixes: vec!["legacy-".to_string()],
+            group_name_prefixes: vec!["legacy-".to_string()],
+            ..BulkDeleteSelector::default()
+        };
+        let handler = fixture.handler();
+
+        let preview = handler.execute(&selector, true, "admin").await.unwrap();
+        assert_eq!(fixture.receiver_names().await.len(), 5);
+        assert_eq!(fixture.group_names().await.len(), 2);
+
+        let mut report = handler.execute(&selector, false, "admin").await.unwrap();
+        assert!(preview.dry_run);
+        assert!(!report.dry_run);
+        assert_eq!(preview.groups, report.groups);
+        assert_eq!(preview.receivers, report.receivers);
+        assert_eq!(preview.blocked, report.blocked);
+
+        assert_eq!(report.groups.len(), 1);
+        assert_eq!(report.receivers.len(), 2);
+        report.blocked.sort_by_key(|b| b.reasons.clone());
+        assert_eq!(
+            report.blocked,
+            vec![
+                BlockedResource {
+                    resource_type: "receiver",
+                    id: shared.to_string(),
+                    reasons: vec![format!("Member of group {}", other)],
+                },
+                BlockedResource {
+                    resource_type: "receiver",
+                    id: with_events.to_string(),
+                    reasons: vec!["Referenced by 3 events".to_string()],
+                },
+            ]
+        );
+        assert_eq!(
+            fixture.receiver_names().await,
+            vec!["current", "legacy-events", "legacy-shared"]
+        );
+        assert_eq!(fixture.group_names().await, vec!["current-group"]);
+    }
+
+    #[tokio::test]
+    async fn test_missing_ids_are_reported_as_blocked() {
+        let fixture = Fixture::new();
+        let receiver = fixture.add_receiver("orders").await;
+        let missing = EventReceiverId::new();
+
+        let report = fixture
+            .handler()
+            .execute(
+                &BulkDeleteSelector {
+                    receiver_ids: vec![receiver, missing, receiver],
+                    ..BulkDeleteSelector::default()
+                },
+                false,
+                "admin",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.receivers, vec![receiver]);
+        assert_eq!(
+            report.blocked,
+            vec![not_found("receiver", missing.to_string())]
+        );
+        assert!(fixture.receiver_names().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_selection_above_cap_is_rejected() {
+        let fixture = Fixture::new();
+        for name in ["batch-1", "batch-2", "batch-3"] {
+            fixture.add_receiver(name).await;
+        }
+        let selector = BulkDeleteSelector {
+            receiver_name_prefixes: vec!["batch-".to_string()],
+            ..BulkDeleteSelector::default()
+        };
+
+        let handler = fixture.handler().with_max_resources(2);
+        for dry_run in [true, false] {
+            let err = handler
+                .execute(&selector, dry_run, "admin")
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("at most 2"));
+        }
+        assert_eq!(fixture.receiver_names().await.len(), 3);
+
+        let report = fixture
+            .handler()
+            .with_max_resources(3)
+            .execute(&selector, false, "admin")
+            .await
+            .unwrap();
+        assert_eq!(report.receivers.len(), 3);
+        assert!(handler
+            .execute(&BulkDeleteSelector::default(), true, "admin")
+            .await
+            .is_err());
+    }
+}
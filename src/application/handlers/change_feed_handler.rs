@@ -0,0 +1,221 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/change_feed_handler.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::repositories::change_feed_repo::{
+    ChangeCursor, ChangeSet, EventReceiverChangeFeedRepository,
+    EventReceiverGroupChangeFeedRepository,
+};
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+
+use std::sync::Arc;
+use tracing::info;
+
+/// Maximum number of changes returned by a single poll
+pub const MAX_CHANGE_PAGE_SIZE: usize = 1000;
+
+/// Application service for incremental synchronization of receivers and groups
+#[derive(Clone)]
+pub struct ChangeFeedHandler {
+    receiver_feed: Arc<dyn EventReceiverChangeFeedRepository>,
+    group_feed: Arc<dyn EventReceiverGroupChangeFeedRepository>,
+}
+
+impl ChangeFeedHandler {
+    /// Creates a new change feed handler
+    pub fn new(
+        receiver_feed: Arc<dyn EventReceiverChangeFeedRepository>,
+        group_feed: Arc<dyn EventReceiverGroupChangeFeedRepository>,
+    ) -> Self {
+        Self {
+            receiver_feed,
+            group_feed,
+        }
+    }
+
+    /// Returns event receivers created, updated, or deleted after `since`
+    pub async fn receiver_changes(
+        &self,
+        since: Option<ChangeCursor>,
+        limit: usize,
+    ) -> Result<ChangeSet<EventReceiver, EventReceiverId>> {
+        Self::validate_limit(limit)?;
+        let since = since.unwrap_or_else(ChangeCursor::beginning);
+
+        info!(since = %since, limit = %limit, "Polling event receiver changes");
+
+        self.receiver_feed
+            .receiver_changes_since(&since, limit)
+            .await
+    }
+
+    /// Returns event receiver groups created, updated, or deleted after `since`
+    pub async fn group_changes(
+        &self,
+        since: Option<ChangeCursor>,
+        limit: usize,
+    ) -> Result<ChangeSet<EventReceiverGroup, EventReceiverGroupId>> {
+        Self::validate_limit(limit)?;
+        let since = since.unwrap_or_else(ChangeCursor::beginning);
+
+        info!(since = %since, limit = %limit, "Polling event receiver group changes");
+
+        self.group_feed.group_changes_since(&since, limit).await
+    }
+
+    fn validate_limit(limit: usize) -> Result<()> {
+        if limit == 0 || limit > MAX_CHANGE_PAGE_SIZE {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range").with("max", MAX_CHANGE_PAGE_SIZE),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::change_feed_repo::Change;
+    use crate::domain::value_objects::UserId;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// In-memory change log that mimics the storage semantics: latest state
+    /// per id wins and deleted ids are kept as tombstones.
+    #[derive(Default)]
+    struct MockReceiverFeed {
+        receivers: Mutex<Vec<EventReceiver>>,
+        tombstones: Mutex<Vec<(EventReceiverId, DateTime<Utc>)>>,
+    }
+
+    impl MockReceiverFeed {
+        fn upsert(&self, receiver: &EventReceiver) {
+            let mut receivers = self.receivers.lock().unwrap();
+            receivers.retain(|r| r.id() != receiver.id());
+            receivers.push(receiver.clone());
+        }
+
+        fn delete(&self, id: EventReceiverId, at: DateTime<Utc>) {
+            self.receivers.lock().unwrap().retain(|r| r.id() != id);
+            self.tombstones.lock().unwrap().push((id, at));
+        }
+    }
+
+    #[async_trait]
+    impl EventReceiverChangeFeedRepository for MockReceiverFeed {
+        async fn receiver_changes_since(
+            &self,
+            since: &ChangeCursor,
+            limit: usize,
+        ) -> Result<ChangeSet<EventReceiver, EventReceiverId>> {
+            let upserts = self.receivers.lock().unwrap().clone();
+            let tombstones = self.tombstones.lock().unwrap().clone();
+            let changes =
+                upserts
+                    .into_iter()
+                    .map(|r| {
+                        (
+                            ChangeCursor::new(r.updated_at(), r.id().to_string()),
+                            Change::Upserted(r),
+                        )
+                    })
+                    .chain(tombstones.into_iter().map(|(id, at)| {
+                        (ChangeCursor::new(at, id.to_string()), Change::Deleted(id))
+                    }));
+            Ok(ChangeSet::from_changes(since, limit, changes))
+        }
+    }
+
+    struct EmptyGroupFeed;
+
+    #[async_trait]
+    impl EventReceiverGroupChangeFeedRepository for EmptyGroupFeed {
+        async fn group_changes_since(
+            &self,
+            since: &ChangeCursor,
+            limit: usize,
+        ) -> Result<ChangeSet<EventReceiverGroup, EventReceiverGroupId>> {
+            Ok(ChangeSet::from_changes(since, limit, Vec::new()))
+        }
+    }
+
+    fn create_receiver(name: &str) -> EventReceiver {
+        EventReceiver::new(
+            name.to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "Test receiver".to_string(),
+            json!({"type": "object"}),
+            UserId::new(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_update_delete_observed_across_polls() {
+        let feed = Arc::new(MockReceiverFeed::default());
+        let handler = ChangeFeedHandler::new(feed.clone(), Arc::new(EmptyGroupFeed));
+
+        let mut first = create_receiver("first");
+        let second = create_receiver("second");
+        feed.upsert(&first);
+        feed.upsert(&second);
+
+        let poll = handler.receiver_changes(None, 100).await.unwrap();
+        assert_eq!(poll.upserts.len(), 2);
+        assert!(poll.deleted.is_empty());
+        assert!(!poll.has_more);
+
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        first
+            .update(Some("first-renamed".to_string()), None, None, None, None)
+            .unwrap();
+        feed.upsert(&first);
+        feed.delete(second.id(), Utc::now());
+
+        let poll = handler
+            .receiver_changes(Some(poll.next_since), 100)
+            .await
+            .unwrap();
+        assert_eq!(poll.upserts.len(), 1);
+        assert_eq!(poll.upserts[0].name(), "first-renamed");
+        assert_eq!(poll.deleted, vec![second.id()]);
+
+        let poll = handler
+            .receiver_changes(Some(poll.next_since), 100)
+            .await
+            .unwrap();
+        assert!(poll.upserts.is_empty());
+        assert!(poll.deleted.is_empty());
+        assert!(!poll.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_limit_validation() {
+        let handler = ChangeFeedHandler::new(
+            Arc::new(MockReceiverFeed::default()),
+            Arc::new(EmptyGroupFeed),
+        );
+
+        assert!(handler.receiver_changes(None, 0).await.is_err());
+        assert!(handler
+            .group_changes(None, MAX_CHANGE_PAGE_SIZE + 1)
+            .await
+            .is_err());
+        assert!(handler
+            .group_changes(None, MAX_CHANGE_PAGE_SIZE)
+            .await
+            .is_ok());
+    }
+}
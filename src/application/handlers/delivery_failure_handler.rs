@@ -0,0 +1,308 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/delivery_failure_handler.rs
+
+use crate::application::domain_events::{DomainEvent, DomainEventSubscriber};
+use crate::application::handlers::system_events::{
+    report_system_event_failure, SystemEventFactory, DELIVERY_FAILED_EVENT,
+};
+use crate::domain::entities::delivery_failure::DeliveryFailure;
+use crate::domain::entities::event::Event;
+use crate::domain::repositories::delivery_failure_repo::{
+    DeliveryFailureRepository, FindDeliveryFailureCriteria,
+};
+use crate::domain::value_objects::UserId;
+use crate::error::{DomainError, Result};
+use crate::infrastructure::PrometheusMetrics;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::error;
+
+/// Name of the delivery failure log in logs and metrics
+pub const DELIVERY_FAILURE_SUBSCRIBER_NAME: &str = "delivery_failures";
+
+/// Tracing target of the delivery failure log, so log pipelines can route
+/// it on its own
+pub const DELIVERY_FAILURE_LOG_TARGET: &str = "delivery_failures";
+
+/// Default number of failures listed
+pub const DEFAULT_DELIVERY_FAILURE_LIMIT: usize = 50;
+
+/// Most failures listed at once
+pub const MAX_DELIVERY_FAILURE_LIMIT: usize = 500;
+
+/// Application service collecting terminal delivery failures in one place
+///
+/// As a domain event subscriber it records every
+/// [`DomainEvent::DeliveryFailed`], whichever mechanism gave up. The first
+/// record of a failure is logged under the `delivery_failures` target,
+/// counted in `xzepr_delivery_failures_total`, and, with a system event
+/// factory, recorded as an `xzepr.event.delivery.failed` system event so it
+/// shows in the timeline.
+#[derive(Clone)]
+pub struct DeliveryFailureHandler {
+    repository: Arc<dyn DeliveryFailureRepository>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    system_events: Option<SystemEventFactory>,
+}
+
+impl DeliveryFailureHandler {
+    /// Creates a handler recording failures in `repository`
+    pub fn new(repository: Arc<dyn DeliveryFailureRepository>) -> Self {
+        Self {
+            repository,
+            metrics: None,
+            system_events: None,
+        }
+    }
+
+    /// Counts failures by mechanism and error class
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Records a system event for every failure
+    pub fn with_system_event_factory(mut self, factory: SystemEventFactory) -> Self {
+        self.system_events = Some(factory);
+        self
+    }
+
+    /// Returns the latest failures matching `criteria`, newest first
+    ///
+    /// The limit defaults to [`DEFAULT_DELIVERY_FAILURE_LIMIT`] when zero
+    /// and is capped at [`MAX_DELIVERY_FAILURE_LIMIT`].
+    pub async fn recent(
+        &self,
+        mut criteria: FindDeliveryFailureCriteria,
+    ) -> Result<Vec<DeliveryFailure>> {
+        criteria.limit = match criteria.limit {
+            0 => DEFAULT_DELIVERY_FAILURE_LIMIT,
+            limit => limit.min(MAX_DELIVERY_FAILURE_LIMIT),
+        };
+        self.repository.find_recent(&criteria).await
+    }
+
+    /// Records a failure, reporting it if it is new
+    ///
+    /// `owner_id` owns the failed event and its system event.
+    pub async fn record(&self, failure: &DeliveryFailure, owner_id: UserId) -> Result<()> {
+        if !self.repository.record(failure).await? {
+            return Ok(());
+        }
+
+        error!(
+            target: DELIVERY_FAILURE_LOG_TARGET,
+            mechanism = failure.mechanism.as_str(),
+            topic = %failure.target,
+            event_id = %failure.event_id,
+            receiver_id = %failure.receiver_id,
+            error_class = failure.error_class.as_str(),
+            attempts = failure.attempts,
+            last_error = %failure.last_error,
+            "Delivery failed after its last retry"
+        );
+
+        if let Some(metrics) = &self.metrics {
+            metrics
+                .record_delivery_failure(failure.mechanism.as_str(), failure.error_class.as_str());
+        }
+
+        if let Some(factory) = &self.system_events {
+            match create_delivery_failed_event(factory, failure, owner_id) {
+                Ok(system_event) => factory.record(&system_event).await,
+                Err(e) => report_system_event_failure(
+                    self.metrics.as_deref(),
+                    DELIVERY_FAILED_EVENT,
+                    &failure.event_id.to_string(),
+                    &e,
+                ),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates a system event for a delivery given up on
+fn create_delivery_failed_event(
+    factory: &SystemEventFactory,
+    failure: &DeliveryFailure,
+    owner_id: UserId,
+) -> std::result::Result<Event, DomainError> {
+    use serde_json::json;
+
+    let payload = json!({
+        "mechanism": failure.mechanism.as_str(),
+        "target": failure.target,
+        "event_id": failure.event_id.to_string(),
+        "receiver_id": failure.receiver_id.to_string(),
+        "error_class": failure.error_class.as_str(),
+        "last_error": failure.last_error,
+        "attempts": failure.attempts,
+        "failed_at": failure.failed_at.to_rfc3339(),
+    });
+
+    factory.build(
+        DELIVERY_FAILED_EVENT,
+        format!(
+            "Delivery of event {} to '{}' failed after {} attempts",
+            failure.event_id, failure.target, failure.attempts
+        ),
+        payload,
+        failure.receiver_id,
+        owner_id,
+    )
+}
+
+#[async_trait]
+impl DomainEventSubscriber for DeliveryFailureHandler {
+    fn name(&self) -> &str {
+        DELIVERY_FAILURE_SUBSCRIBER_NAME
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<()> {
+        match event {
+            DomainEvent::DeliveryFailed { failure, event } => {
+                self.record(failure, event.owner_id()).await
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::delivery_failure::{DeliveryErrorClass, DeliveryMechanism};
+    use crate::domain::repositories::event_repo::EventRepository;
+    use crate::domain::value_objects::EventReceiverId;
+    use crate::fixtures::EventFixture;
+    use crate::infrastructure::memory::{
+        InMemoryDeliveryFailureRepository, InMemoryEventRepository,
+    };
+    use chrono::{Duration, Utc};
+
+    struct Fixture {
+        metrics: Arc<PrometheusMetrics>,
+        system_events: Arc<InMemoryEventRepository>,
+        handler: DeliveryFailureHandler,
+    }
+
+    fn fixture() -> Fixture {
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let system_events = Arc::new(InMemoryEventRepository::new());
+        let handler =
+            DeliveryFailureHandler::new(Arc::new(InMemoryDeliveryFailureRepository::default()))
+                .with_metrics(metrics.clone())
+                .with_system_event_factory(
+                    SystemEventFactory::new().with_store(system_events.clone()),
+                );
+        Fixture {
+            metrics,
+            system_events,
+            handler,
+        }
+    }
+
+    fn notification(event: &Event, topic: &str, error: &str) -> DomainEvent {
+        DomainEvent::DeliveryFailed {
+            failure: DeliveryFailure::new(
+                DeliveryMechanism::GroupTopicFanout,
+                topic,
+                event.id(),
+                event.event_receiver_id(),
+                error,
+                3,
+                Utc::now(),
+            ),
+            event: event.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failure_reported_twice_is_recorded_once() {
+        let f = fixture();
+        let event = EventFixture::new().build();
+        let failed = notification(&event, "payments.events", "Message timed out");
+
+        f.handler.handle(&failed).await.unwrap();
+        f.handler.handle(&failed).await.unwrap();
+
+        let failures = f.handler.recent(Default::default()).await.unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].error_class, DeliveryErrorClass::Timeout);
+        assert!(f.metrics.gather().unwrap().contains(
+            "xzepr_delivery_failures_total{error_class=\"timeout\",mechanism=\"group_topic_fanout\"} 1"
+        ));
+
+        let recorded = f
+            .system_events
+            .find_by_name(DELIVERY_FAILED_EVENT)
+            .await
+            .unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].event_receiver_id(), event.event_receiver_id());
+        assert_eq!(
+            recorded[0].payload().get("target").unwrap(),
+            "payments.events"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recent_filters_newest_first() {
+        let f = fixture();
+        let receiver = EventReceiverId::new();
+        let now = Utc::now();
+        for (minutes, receiver_id, error) in [
+            (3, receiver, "broker unavailable"),
+            (2, EventReceiverId::new(), "broker unavailable"),
+            (1, receiver, "Message size too large"),
+            (0, receiver, "Message timed out"),
+        ] {
+            let event = EventFixture::new().receiver_id(receiver_id).build();
+            let failure = DeliveryFailure::new(
+                DeliveryMechanism::EventPublish,
+                "xzepr.dev.events",
+                event.id(),
+                receiver_id,
+                error,
+                10,
+                now - Duration::minutes(minutes),
+            );
+            f.handler.record(&failure, event.owner_id()).await.unwrap();
+        }
+
+        let failures = f
+            .handler
+            .recent(FindDeliveryFailureCriteria {
+                receiver_id: Some(receiver),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let classes: Vec<_> = failures.iter().map(|f| f.error_class).collect();
+        assert_eq!(
+            classes,
+            vec![
+                DeliveryErrorClass::Timeout,
+                DeliveryErrorClass::Rejected,
+                DeliveryErrorClass::Unavailable,
+            ]
+        );
+
+        let failures = f
+            .handler
+            .recent(FindDeliveryFailureCriteria {
+                error_class: Some(DeliveryErrorClass::Unavailable),
+                limit: 1,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_ne!(failures[0].receiver_id, receiver);
+    }
+}
@@ -0,0 +1,270 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/description_renderer.rs
+
+use ammonia::{Builder, UrlRelative};
+use pulldown_cmark::{html, Event, Options, Parser};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Rendered descriptions kept before the cache is cleared
+const MAX_CACHED_DESCRIPTIONS: usize = 1_000;
+
+/// Tags the rendered HTML may contain
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "strong",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// URL schemes links may use; everything else, including `javascript:`
+/// and `data:`, is dropped
+const ALLOWED_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Renders a Markdown description to sanitized HTML
+///
+/// The Markdown is rendered as CommonMark with tables and strikethrough.
+/// Raw HTML in the source is never passed through; it is rendered as
+/// text. The result only holds the tags in an allowlist, links only keep
+/// `href` and `title`, may only point to `http`, `https`, and `mailto`
+/// URLs, and open with `rel="noopener noreferrer nofollow"`. Images are
+/// not rendered.
+///
+/// # Examples
+///
+/// ```
+/// use xzepr::application::handlers::description_renderer::render_description;
+///
+/// let html = render_description("**Restart** <script>alert(1)</script>");
+/// assert_eq!(
+///     html,
+///     "<p><strong>Restart</strong> &lt;script&gt;alert(1)&lt;/script&gt;</p>\n"
+/// );
+/// ```
+pub fn render_description(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        event => event,
+    });
+    let mut rendered = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut rendered, events);
+
+    sanitizer().clean(&rendered).to_string()
+}
+
+fn sanitizer() -> &'static Builder<'static> {
+    static SANITIZER: OnceLock<Builder<'static>> = OnceLock::new();
+    SANITIZER.get_or_init(|| {
+        let mut builder = Builder::empty();
+        builder
+            .tags(ALLOWED_TAGS.iter().copied().collect())
+            .tag_attributes(HashMap::from([
+                ("a", HashSet::from(["href", "title"])),
+                ("ol", HashSet::from(["start"])),
+            ]))
+            .url_schemes(ALLOWED_URL_SCHEMES.iter().copied().collect())
+            .url_relative(UrlRelative::PassThrough)
+            .link_rel(Some("noopener noreferrer nofollow"))
+            .clean_content_tags(HashSet::from(["script", "style"]))
+            .strip_comments(true);
+        builder
+    })
+}
+
+/// A rendered description and what it was rendered from
+struct RenderedDescription {
+    resource_version: i64,
+    digest: [u8; 32],
+    html: Arc<str>,
+}
+
+/// Renders receiver or group descriptions and caches the HTML
+///
+/// Entries are keyed by resource id and reused while the resource version
+/// and the description are unchanged. Receivers keep their resource
+/// version when only the description changes, so the description's digest
+/// is checked as well.
+#[derive(Clone)]
+pub struct DescriptionRenderer<K> {
+    cache: Arc<RwLock<HashMap<K, RenderedDescription>>>,
+}
+
+impl<K> Default for DescriptionRenderer<K> {
+    fn default() -> Self {
+        Self {
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl<K: Copy + Eq + Hash> DescriptionRenderer<K> {
+    /// Creates a renderer with an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the sanitized HTML of a resource's description
+    pub fn render(&self, id: K, resource_version: i64, description: &str) -> Arc<str> {
+        let digest: [u8; 32] = Sha256::digest(description.as_bytes()).into();
+        if let Some(cached) = self.cache.read().unwrap().get(&id) {
+            if cached.resource_version == resource_version && cached.digest == digest {
+                return cached.html.clone();
+            }
+        }
+
+        let html: Arc<str> = render_description(description).into();
+        let mut cache = self.cache.write().unwrap();
+        if cache.len() >= MAX_CACHED_DESCRIPTIONS {
+            cache.clear();
+        }
+        cache.insert(
+            id,
+            RenderedDescription {
+                resource_version,
+                digest,
+                html: html.clone(),
+            },
+        );
+        html
+    }
+
+    /// Drops the cached description of a resource
+    pub fn forget(&self, id: K) {
+        self.cache.write().unwrap().remove(&id);
+    }
+
+    /// Returns the number of cached descriptions
+    pub fn cached_len(&self) -> usize {
+        self.cache.read().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_renders_to_allowed_tags() {
+        let html = render_description(
+            "# Runbook\n\n1. Check `lag`\n2. ~~Restart~~ *Scale* the **consumer**\n\n\
+             | Step | Owner |\n|------|-------|\n| 1 | ops |\n\n> Page on-call\n",
+        );
+        for fragment in [
+            "<h1>Runbook</h1>",
+            "<li>Check <code>lag</code></li>",
+            "<li><del>Restart</del> <em>Scale</em> the <strong>consumer</strong></li>",
+            "<th>Step</th><th>Owner</th>",
+            "<td>1</td><td>ops</td>",
+            "<blockquote>\n<p>Page on-call</p>\n</blockquote>",
+        ] {
+            assert!(html.contains(fragment), "{} not in {}", fragment, html);
+        }
+    }
+
+    #[test]
+    fn test_raw_html_is_rendered_as_text() {
+        let html = render_description(
+            "<script>alert(1)</script>\n\nHello <img src=x onerror=alert(1)> <b>bold</b>",
+        );
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("<img"));
+        assert!(!html.contains("<b>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("&lt;img src=x onerror=alert(1)&gt;"));
+    }
+
+    #[test]
+    fn test_links_are_sanitized() {
+        let html = render_description(
+            "[docs](https://example.com/runbook \"Runbook\") \
+             [js](javascript:alert(1)) [data](data:text/html;base64,PHNjcmlwdD4=) \
+             [JS](JaVaScRiPt:alert(1)) [mail](mailto:ops@example.com) [rel](/receivers)",
+        );
+        assert!(html.contains(
+            "<a href=\"https://example.com/runbook\" title=\"Runbook\" rel=\"noopener noreferrer nofollow\">docs</a>"
+        ));
+        assert!(html.contains("<a rel=\"noopener noreferrer nofollow\">js</a>"));
+        assert!(html.contains("<a rel=\"noopener noreferrer nofollow\">data</a>"));
+        assert!(html.contains("<a rel=\"noopener noreferrer nofollow\">JS</a>"));
+        assert!(html.contains("href=\"mailto:ops@example.com\""));
+        assert!(html.contains("href=\"/receivers\""));
+        assert!(!html.to_lowercase().contains("javascript:"));
+        assert!(!html.contains("data:"));
+    }
+
+    #[test]
+    fn test_images_and_autolinks_cannot_smuggle_urls() {
+        let html = render_description(
+            "![x](javascript:alert(1)) ![pixel](https://example.com/p.png) \
+             <javascript:alert(1)> <https://example.com>",
+        );
+        assert!(!html.contains("<img"));
+        assert!(!html.to_lowercase().contains("href=\"javascript"));
+        assert!(html.contains("href=\"https://example.com\""));
+    }
+
+    #[test]
+    fn test_nested_markdown_edge_cases() {
+        // Emphasis spanning a link, an unterminated code span, and HTML
+        // inside a code block all stay inert
+        let html = render_description(
+            "*[click **me**](https://example.com)*\n\n`<b>unterminated\n\n\
+             ```html\n<script>alert(1)</script>\n```\n\n\
+             > > <iframe src=\"https://evil.example\"></iframe>\n",
+        );
+        assert!(html.contains(
+            "<em><a href=\"https://example.com\" rel=\"noopener noreferrer nofollow\">click <strong>me</strong></a></em>"
+        ));
+        assert!(html.contains("`&lt;b&gt;unterminated"));
+        assert!(html.contains("<pre><code>&lt;script&gt;alert(1)&lt;/script&gt;\n</code></pre>"));
+        assert!(!html.contains("<iframe"));
+        assert!(!html.contains("<script"));
+    }
+
+    #[test]
+    fn test_cache_follows_resource_version_and_description() {
+        let renderer = DescriptionRenderer::<u32>::new();
+        let first = renderer.render(1, 1, "**one**");
+        assert!(Arc::ptr_eq(&first, &renderer.render(1, 1, "**one**")));
+
+        // A description change without a version bump is still seen
+        let changed = renderer.render(1, 1, "**two**");
+        assert_eq!(&*changed, "<p><strong>two</strong></p>\n");
+
+        let bumped = renderer.render(1, 2, "**two**");
+        assert!(!Arc::ptr_eq(&changed, &bumped));
+        assert_eq!(renderer.cached_len(), 1);
+
+        renderer.forget(1);
+        assert_eq!(renderer.cached_len(), 0);
+    }
+}
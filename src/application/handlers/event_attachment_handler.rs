@@ -0,0 +1,557 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/event_attachment_handler.rs
+
+use crate::domain::entities::event::Event;
+use crate::domain::entities::event_attachment::{
+    media_type, sanitize_filename, storage_key, EventAttachment,
+};
+use crate::domain::repositories::event_attachment_repo::{
+    BlobStore, BlobWriter, ByteRange, ByteStream, EventAttachmentRepository,
+};
+use crate::domain::value_objects::{AttachmentId, EventId, UserId};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Default largest attachment in bytes
+pub const DEFAULT_MAX_ATTACHMENT_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Default total attachment bytes per receiver
+pub const DEFAULT_RECEIVER_QUOTA_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// A file submitted for upload
+#[derive(Debug, Clone)]
+pub struct NewAttachment {
+    /// Name sent by the client; sanitized before it is stored
+    pub filename: String,
+    /// Content type sent by the client; parameters are dropped
+    pub content_type: String,
+    pub uploaded_by: UserId,
+}
+
+/// Why an upload was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadRejection {
+    UnsupportedContentType {
+        content_type: String,
+        allowed: Vec<String>,
+    },
+    /// The upload exceeded `attachments.max_size_bytes`
+    TooLarge { limit: u64 },
+    /// The upload exceeded what is left of the receiver's quota
+    QuotaExceeded { remaining: u64 },
+}
+
+impl UploadRejection {
+    /// Returns the rejection as a validation error of the `file` part
+    pub fn error(&self) -> DomainError {
+        let message = match self {
+            UploadRejection::UnsupportedContentType {
+                content_type,
+                allowed,
+            } => Message::new("validation.attachment_content_type")
+                .with("value", content_type)
+                .with("options", allowed.join(", ")),
+            UploadRejection::TooLarge { limit } => {
+                Message::new("validation.attachment_too_large").with("max", limit)
+            }
+            UploadRejection::QuotaExceeded { remaining } => {
+                Message::new("validation.attachment_quota_exceeded").with("remaining", remaining)
+            }
+        };
+        DomainError::ValidationError {
+            field: "file".to_string(),
+            message,
+        }
+    }
+}
+
+/// Outcome of an upload
+#[derive(Debug)]
+pub enum AttachmentUpload {
+    Stored(EventAttachment),
+    Rejected(UploadRejection),
+}
+
+/// Application service for files attached to events
+///
+/// Uploads are streamed to the blob store chunk by chunk while their size
+/// and SHA-256 are computed, and stop as soon as they pass the size limit
+/// or the receiver's remaining quota. The metadata row is only written once
+/// the blob is committed, so a failed upload never shows up in listings or
+/// counts toward the quota.
+#[derive(Clone)]
+pub struct EventAttachmentHandler {
+    repository: Arc<dyn EventAttachmentRepository>,
+    blobs: Arc<dyn BlobStore>,
+    max_size_bytes: u64,
+    allowed_content_types: Vec<String>,
+    receiver_quota_bytes: Option<u64>,
+}
+
+impl EventAttachmentHandler {
+    /// Creates a handler with the default limits, accepting any content type
+    pub fn new(repository: Arc<dyn EventAttachmentRepository>, blobs: Arc<dyn BlobStore>) -> Self {
+        Self {
+            repository,
+            blobs,
+            max_size_bytes: DEFAULT_MAX_ATTACHMENT_BYTES,
+            allowed_content_types: Vec::new(),
+            receiver_quota_bytes: Some(DEFAULT_RECEIVER_QUOTA_BYTES),
+        }
+    }
+
+    /// Sets the largest accepted attachment
+    pub fn with_max_size_bytes(mut self, max_size_bytes: u64) -> Self {
+        self.max_size_bytes = max_size_bytes;
+        self
+    }
+
+    /// Limits attachments to the listed media types; an empty list accepts
+    /// any type
+    pub fn with_allowed_content_types(mut self, content_types: Vec<String>) -> Self {
+        self.allowed_content_types = content_types.iter().map(|t| media_type(t)).collect();
+        self
+    }
+
+    /// Sets the total attachment bytes allowed per receiver; 0 disables the
+    /// quota
+    pub fn with_receiver_quota_bytes(mut self, quota_bytes: u64) -> Self {
+        self.receiver_quota_bytes = (quota_bytes > 0).then_some(quota_bytes);
+        self
+    }
+
+    /// Stores `content` as a new attachment of `event`
+    ///
+    /// Chunks are pulled from `content` only while the upload is within its
+    /// limit, so an oversized upload is refused without reading the rest.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for an unusable filename, and storage and
+    /// database errors. An error reading `content` is returned as is.
+    pub async fn upload(
+        &self,
+        event: &Event,
+        file: NewAttachment,
+        content: &mut dyn ByteStream,
+    ) -> Result<AttachmentUpload> {
+        let filename = sanitize_filename(&file.filename)?;
+        let content_type = media_type(&file.content_type);
+        if !self.allowed_content_types.is_empty()
+            && !self.allowed_content_types.contains(&content_type)
+        {
+            return Ok(AttachmentUpload::Rejected(
+                UploadRejection::UnsupportedContentType {
+                    content_type,
+                    allowed: self.allowed_content_types.clone(),
+                },
+            ));
+        }
+
+        // The quota is checked against what was stored before this upload
+        // started; concurrent uploads to one receiver may overshoot it
+        let mut limit = self.max_size_bytes;
+        let mut rejection = UploadRejection::TooLarge { limit };
+        if let Some(quota) = self.receiver_quota_bytes {
+            let used = self
+                .repository
+                .total_size_by_receiver(event.event_receiver_id())
+                .await?;
+            let remaining = quota.saturating_sub(used);
+            if remaining < limit {
+                limit = remaining;
+                rejection = UploadRejection::QuotaExceeded { remaining };
+            }
+        }
+
+        let id = AttachmentId::new();
+        let key = storage_key(event.id(), id);
+        let mut writer = self.blobs.begin(&key).await?;
+        let mut hasher = Sha256::new();
+        let mut size_bytes: u64 = 0;
+
+        loop {
+            let chunk = match content.next_chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => {
+                    abort(writer, &key).await;
+                    return Err(e);
+                }
+            };
+            size_bytes += chunk.len() as u64;
+            if size_bytes > limit {
+                abort(writer, &key).await;
+                return Ok(AttachmentUpload::Rejected(rejection));
+            }
+            hasher.update(&chunk);
+            if let Err(e) = writer.write(&chunk).await {
+                abort(writer, &key).await;
+                return Err(e);
+            }
+        }
+        writer.commit().await?;
+
+        let attachment = EventAttachment {
+            id,
+            event_id: event.id(),
+            event_receiver_id: event.event_receiver_id(),
+            filename,
+            content_type,
+            size_bytes,
+            sha256: hex::encode(hasher.finalize()),
+            storage_key: key,
+            uploaded_by: file.uploaded_by,
+            created_at: Utc::now(),
+        };
+        if let Err(e) = self.repository.save(&attachment).await {
+            if let Err(delete_error) = self.blobs.delete(&attachment.storage_key).await {
+                warn!(
+                    key = %attachment.storage_key,
+                    error = %delete_error,
+                    "Failed to delete blob of unsaved attachment"
+                );
+            }
+            return Err(e);
+        }
+
+        info!(
+            attachment_id = %attachment.id,
+            event_id = %attachment.event_id,
+            size_bytes = attachment.size_bytes,
+            "Stored event attachment"
+        );
+        Ok(AttachmentUpload::Stored(attachment))
+    }
+
+    /// Lists the attachments of an event
+    pub async fn list(&self, event_id: EventId) -> Result<Vec<EventAttachment>> {
+        self.repository.find_by_event(event_id).await
+    }
+
+    /// Finds an attachment of `event_id`
+    pub async fn get(
+        &self,
+        event_id: EventId,
+        id: AttachmentId,
+    ) -> Result<Option<EventAttachment>> {
+        Ok(self
+            .repository
+            .find_by_id(id)
+            .await?
+            .filter(|attachment| attachment.event_id == event_id))
+    }
+
+    /// Reads `range` of an attachment's content, or all of it
+    ///
+    /// Returns `None` if the blob is missing from the store.
+    pub async fn open(
+        &self,
+        attachment: &EventAttachment,
+        range: Option<ByteRange>,
+    ) -> Result<Option<Box<dyn ByteStream>>> {
+        self.blobs.open(&attachment.storage_key, range).await
+    }
+
+    /// Deletes an attachment
+    ///
+    /// The record goes first, so the attachment disappears even if its blob
+    /// cannot be removed; such a blob is only logged.
+    pub async fn delete(&self, attachment: &EventAttachment) -> Result<()> {
+        self.repository.delete(attachment.id).await?;
+        if let Err(e) = self.blobs.delete(&attachment.storage_key).await {
+            warn!(
+                key = %attachment.storage_key,
+                error = %e,
+                "Failed to delete blob of deleted attachment"
+            );
+        }
+        info!(attachment_id = %attachment.id, "Deleted event attachment");
+        Ok(())
+    }
+
+    /// Deletes every attachment of an event, blobs first
+    ///
+    /// Used before the event itself is removed. A record is only deleted
+    /// once its blob is gone, so a failure leaves it to be retried.
+    pub async fn purge_event(&self, event_id: EventId) -> Result<usize> {
+        let attachments = self.repository.find_by_event(event_id).await?;
+        for attachment in &attachments {
+            self.blobs.delete(&attachment.storage_key).await?;
+            self.repository.delete(attachment.id).await?;
+        }
+        Ok(attachments.len())
+    }
+}
+
+/// Discards a partially written blob, logging failures
+async fn abort(writer: Box<dyn BlobWriter>, key: &str) {
+    if let Err(e) = writer.abort().await {
+        warn!(key = %key, error = %e, "Failed to discard partial attachment upload");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event::CreateEventParams;
+    use crate::domain::value_objects::EventReceiverId;
+    use crate::infrastructure::attachments::FilesystemBlobStore;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockAttachmentRepository {
+        attachments: Mutex<HashMap<AttachmentId, EventAttachment>>,
+    }
+
+    #[async_trait]
+    impl EventAttachmentRepository for MockAttachmentRepository {
+        async fn save(&self, attachment: &EventAttachment) -> Result<()> {
+            self.attachments
+                .lock()
+                .unwrap()
+                .insert(attachment.id, attachment.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: AttachmentId) -> Result<Option<EventAttachment>> {
+            Ok(self.attachments.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_event(&self, event_id: EventId) -> Result<Vec<EventAttachment>> {
+            Ok(self
+                .attachments
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|a| a.event_id == event_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete(&self, id: AttachmentId) -> Result<bool> {
+            Ok(self.attachments.lock().unwrap().remove(&id).is_some())
+        }
+
+        async fn total_size_by_receiver(&self, receiver_id: EventReceiverId) -> Result<u64> {
+            Ok(self
+                .attachments
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|a| a.event_receiver_id == receiver_id)
+                .map(|a| a.size_bytes)
+                .sum())
+        }
+    }
+
+    /// Yields `chunks` chunks of `chunk_size` bytes and counts how many
+    /// were pulled
+    struct CountingStream {
+        chunk_size: usize,
+        chunks: usize,
+        pulled: usize,
+    }
+
+    #[async_trait]
+    impl ByteStream for CountingStream {
+        async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+            if self.pulled == self.chunks {
+                return Ok(None);
+            }
+            self.pulled += 1;
+            Ok(Some(vec![b'x'; self.chunk_size]))
+        }
+    }
+
+    fn temp_root() -> PathBuf {
+        std::env::temp_dir().join(format!("xzepr-attachments-{}", ulid::Ulid::new()))
+    }
+
+    fn event() -> Event {
+        Event::new(CreateEventParams {
+            name: "build".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.01".to_string(),
+            platform_id: "linux".to_string(),
+            package: "docker".to_string(),
+            description: "attachments".to_string(),
+            payload: json!({}),
+            success: true,
+            receiver_id: EventReceiverId::new(),
+            owner_id: UserId::new(),
+        })
+        .unwrap()
+    }
+
+    fn file(content_type: &str) -> NewAttachment {
+        NewAttachment {
+            filename: "reports/junit.xml".to_string(),
+            content_type: content_type.to_string(),
+            uploaded_by: UserId::new(),
+        }
+    }
+
+    fn handler(root: &Path) -> (EventAttachmentHandler, Arc<MockAttachmentRepository>) {
+        let repository = Arc::new(MockAttachmentRepository::default());
+        let handler = EventAttachmentHandler::new(
+            repository.clone(),
+            Arc::new(FilesystemBlobStore::new(root)),
+        );
+        (handler, repository)
+    }
+
+    #[tokio::test]
+    async fn test_upload_records_size_and_hash() {
+        let root = temp_root();
+        let (handler, _) = handler(&root);
+        let event = event();
+        let mut content = CountingStream {
+            chunk_size: 1000,
+            chunks: 3,
+            pulled: 0,
+        };
+
+        let AttachmentUpload::Stored(attachment) = handler
+            .upload(&event, file("text/xml; charset=utf-8"), &mut content)
+            .await
+            .unwrap()
+        else {
+            panic!("upload was rejected");
+        };
+
+        assert_eq!(attachment.filename, "junit.xml");
+        assert_eq!(attachment.content_type, "text/xml");
+        assert_eq!(attachment.size_bytes, 3000);
+        assert_eq!(
+            attachment.sha256,
+            hex::encode(Sha256::digest(vec![b'x'; 3000]))
+        );
+        assert_eq!(
+            handler.list(event.id()).await.unwrap(),
+            std::slice::from_ref(&attachment)
+        );
+        assert!(handler
+            .get(EventId::new(), attachment.id)
+            .await
+            .unwrap()
+            .is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_oversized_upload_stops_reading_at_the_limit() {
+        let root = temp_root();
+        let (handler, repository) = handler(&root);
+        let handler = handler.with_max_size_bytes(10 * 1024);
+        let event = event();
+        let mut content = CountingStream {
+            chunk_size: 1024,
+            chunks: 100_000,
+            pulled: 0,
+        };
+
+        let outcome = handler
+            .upload(&event, file("text/plain"), &mut content)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            outcome,
+            AttachmentUpload::Rejected(UploadRejection::TooLarge { limit: 10240 })
+        ));
+        // The chunk that crossed the limit is the last one read
+        assert_eq!(content.pulled, 11);
+        assert!(repository.attachments.lock().unwrap().is_empty());
+        let event_dir = root.join(event.id().to_string());
+        assert_eq!(std::fs::read_dir(event_dir).unwrap().count(), 0);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_receiver_quota_and_content_types_are_enforced() {
+        let root = temp_root();
+        let (handler, _) = handler(&root);
+        let handler = handler
+            .with_receiver_quota_bytes(5000)
+            .with_allowed_content_types(vec!["text/plain".to_string()]);
+        let event = event();
+        let stream = |chunks| CountingStream {
+            chunk_size: 2000,
+            chunks,
+            pulled: 0,
+        };
+
+        let outcome = handler
+            .upload(&event, file("application/x-msdownload"), &mut stream(1))
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            AttachmentUpload::Rejected(UploadRejection::UnsupportedContentType { .. })
+        ));
+
+        let outcome = handler
+            .upload(&event, file("text/plain"), &mut stream(2))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, AttachmentUpload::Stored(_)));
+
+        let outcome = handler
+            .upload(&event, file("text/plain"), &mut stream(2))
+            .await
+            .unwrap();
+        let AttachmentUpload::Rejected(rejection) = outcome else {
+            panic!("upload over the quota was stored");
+        };
+        assert_eq!(
+            rejection,
+            UploadRejection::QuotaExceeded { remaining: 1000 }
+        );
+        assert_eq!(
+            rejection.error().localizable().unwrap().1.key(),
+            "validation.attachment_quota_exceeded"
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_purge_event_removes_blobs_and_records() {
+        let root = temp_root();
+        let (handler, repository) = handler(&root);
+        let event = event();
+        for _ in 0..2 {
+            let mut content = CountingStream {
+                chunk_size: 10,
+                chunks: 1,
+                pulled: 0,
+            };
+            handler
+                .upload(&event, file("text/plain"), &mut content)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(handler.purge_event(event.id()).await.unwrap(), 2);
+
+        assert!(repository.attachments.lock().unwrap().is_empty());
+        let event_dir = root.join(event.id().to_string());
+        assert_eq!(std::fs::read_dir(event_dir).unwrap().count(), 0);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -3,22 +3,226 @@
 
 // src/application/handlers/event_handler.rs
 
+use crate::application::domain_events::{DomainEvent, DomainEventBus};
+use crate::application::handlers::event_poll_handler::EventNotifier;
+use crate::application::handlers::group_topic_fanout::GroupTopicFanout;
+use crate::application::handlers::ingestion_timing::{
+    IngestionStage, IngestionTimings, INTERNAL_CHANNEL,
+};
+use crate::application::handlers::payload_offloader::PayloadOffloader;
+use crate::application::handlers::receiver_activity_tracker::ReceiverActivityTracker;
+use crate::application::handlers::resource_history_handler::ResourceHistoryHandler;
+use crate::application::handlers::schema_resolver::SchemaResolver;
+use crate::domain::entities::attestation::{Attestation, EventAttestation};
 use crate::domain::entities::event::{CreateEventParams, Event};
+use crate::domain::entities::event_name_constraint::{is_reserved_event_name, EventNameMatcher};
+use crate::domain::entities::event_publication::{
+    publish_retry_delay, PublishPolicy, PublishStatus,
+};
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_sampling::SamplingPolicy;
+use crate::domain::entities::ingestion_meta::{IngestionContext, IngestionMeta, PrincipalType};
+use crate::domain::entities::payload_transform::{PayloadTransform, TransformPreview};
+use crate::domain::entities::pii_detection::{PiiReport, PiiScanner};
+use crate::domain::entities::receiver_provisioning::{ReceiverProvisioningPolicy, ReceiverSpec};
+use crate::domain::repositories::attestation_repo::{
+    AttestationFilter, EventAttestationRepository,
+};
+use crate::domain::repositories::event_archive_repo::{ArchiveStore, EventArchiveIndexRepository};
+use crate::domain::repositories::event_outbox_repo::EventOutboxRepository;
 use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
-use crate::domain::repositories::event_repo::EventRepository;
-use crate::domain::value_objects::{EventId, EventReceiverId};
-use crate::error::{DomainError, Result};
-use crate::infrastructure::messaging::producer::KafkaEventPublisher;
-
-use std::sync::Arc;
+use crate::domain::repositories::event_repo::{EventRepository, FindEventCriteria};
+use crate::domain::repositories::event_sampling_repo::{
+    sampling_bucket, EventSamplingCounterRepository,
+};
+use crate::domain::repositories::ingestion_meta_repo::{
+    EventIngestionMetaRepository, IngestionMetaFilter,
+};
+use crate::domain::value_objects::{EventId, EventReceiverId, UserId, Version, VersionStrictness};
+use crate::error::{DomainError, Error, InfrastructureError, Result};
+use crate::i18n::Message;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::config_reload::ReloadableSettings;
+use crate::infrastructure::messaging::producer::{EventPublisher, KafkaEventPublisher};
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+use chrono::{DateTime, NaiveTime, Utc};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
 
+/// Result of looking up an event in cold storage
+#[derive(Debug, Clone)]
+pub enum ArchivedEventLookup {
+    /// The event was never archived, or archival is not configured
+    NotArchived,
+    /// The event was read back from its archive segment
+    Found(Box<Event>),
+    /// The archive index names a segment that no longer holds the event
+    Unavailable { segment: String },
+}
+
+/// Result of submitting an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreateEventOutcome {
+    /// The event was saved; `publish_status` is `None` when publication is
+    /// not configured
+    Stored {
+        event_id: EventId,
+        publish_status: Option<PublishStatus>,
+    },
+    /// The receiver's sample rate dropped the event; nothing was saved
+    SampledOut,
+}
+
+impl CreateEventOutcome {
+    /// Returns the id of the stored event
+    pub fn event_id(&self) -> Option<EventId> {
+        match self {
+            CreateEventOutcome::Stored { event_id, .. } => Some(*event_id),
+            CreateEventOutcome::SampledOut => None,
+        }
+    }
+
+    pub fn is_sampled_out(&self) -> bool {
+        matches!(self, CreateEventOutcome::SampledOut)
+    }
+}
+
+/// Whether stored events can currently reach the stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublishHealth {
+    /// No publisher is configured; events are only stored
+    Disabled,
+    /// The publisher accepted its last send
+    Healthy,
+    /// The publisher's last send failed
+    Failing,
+}
+
+/// What submitting an event would do, found by a dry run
+#[derive(Debug)]
+pub enum DryRunOutcome {
+    /// The event would be stored
+    WouldCreate,
+    /// The receiver's sample rate would drop the event
+    SampledOut,
+    /// The event would be rejected; never empty
+    Rejected(Vec<DomainError>),
+}
+
+impl DryRunOutcome {
+    /// Returns true if the event would be stored
+    pub fn would_create(&self) -> bool {
+        matches!(self, DryRunOutcome::WouldCreate)
+    }
+}
+
+/// Verdict of the checks an event passes before it is stored
+enum Admission {
+    /// With the attestation extracted from an attestation receiver's event
+    Accepted(Box<Event>, Option<Attestation>),
+    SampledOut,
+    /// Never empty
+    Rejected(Vec<DomainError>),
+}
+
+/// Receiver a [`ReceiverSpec`] resolves to, before anything is created
+#[derive(Debug, Clone)]
+pub struct ReceiverPreview {
+    pub receiver: EventReceiver,
+    /// True when no receiver matches the spec, so one would be created
+    pub would_create: bool,
+}
+
+/// Receiver resolved from a [`ReceiverSpec`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvisionedReceiver {
+    pub receiver_id: EventReceiverId,
+    /// True when this call created the receiver
+    pub created: bool,
+}
+
+/// Most receivers whose compiled name constraints are kept
+const MAX_CACHED_NAME_MATCHERS: usize = 10_000;
+
+/// Days of ingestion covered by a receiver's PII report
+pub const PII_REPORT_WINDOW_DAYS: i64 = 7;
+
+/// Most detector and path pairs a PII report lists
+pub const PII_REPORT_MAX_PATHS: usize = 50;
+
+/// Compiled event name constraints by receiver
+///
+/// An entry is current while the receiver's resource_version matches, so
+/// receiver updates need no explicit invalidation.
+#[derive(Clone, Default)]
+struct EventNameMatchers {
+    cache: Arc<RwLock<HashMap<EventReceiverId, VersionedMatcher>>>,
+}
+
+/// Compiled constraint with the receiver resource_version it was built from
+type VersionedMatcher = (i64, Arc<EventNameMatcher>);
+
+impl EventNameMatchers {
+    /// Returns the compiled constraint of `receiver`, if it has one
+    fn for_receiver(
+        &self,
+        receiver: &EventReceiver,
+    ) -> std::result::Result<Option<Arc<EventNameMatcher>>, DomainError> {
+        let Some(allowed) = receiver.allowed_event_names() else {
+            return Ok(None);
+        };
+
+        if let Some((version, matcher)) = self.cache.read().unwrap().get(&receiver.id()) {
+            if *version == receiver.resource_version() {
+                return Ok(Some(matcher.clone()));
+            }
+        }
+
+        let matcher = Arc::new(allowed.compile()?);
+        let mut cache = self.cache.write().unwrap();
+        if cache.len() >= MAX_CACHED_NAME_MATCHERS {
+            cache.clear();
+        }
+        cache.insert(
+            receiver.id(),
+            (receiver.resource_version(), matcher.clone()),
+        );
+        Ok(Some(matcher))
+    }
+}
+
 /// Application service for handling event operations
 #[derive(Clone)]
 pub struct EventHandler {
     event_repository: Arc<dyn EventRepository>,
     receiver_repository: Arc<dyn EventReceiverRepository>,
     event_publisher: Option<Arc<KafkaEventPublisher>>,
+    publisher: Option<Arc<dyn EventPublisher>>,
+    outbox: Option<Arc<dyn EventOutboxRepository>>,
+    publish_policy: PublishPolicy,
+    group_fanout: Option<GroupTopicFanout>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    ingestion_meta_repository: Option<Arc<dyn EventIngestionMetaRepository>>,
+    attestation_repository: Option<Arc<dyn EventAttestationRepository>>,
+    schema_resolver: Option<SchemaResolver>,
+    archive_store: Option<Arc<dyn ArchiveStore>>,
+    archive_index: Option<Arc<dyn EventArchiveIndexRepository>>,
+    sampling_counters: Option<Arc<dyn EventSamplingCounterRepository>>,
+    activity_tracker: Option<ReceiverActivityTracker>,
+    notifier: Option<EventNotifier>,
+    event_bus: Option<DomainEventBus>,
+    receiver_provisioning: ReceiverProvisioningPolicy,
+    receiver_history: Option<ResourceHistoryHandler>,
+    version_strictness: VersionStrictness,
+    receiver_daily_quota: Option<u64>,
+    audit_logger: Arc<AuditLogger>,
+    name_matchers: EventNameMatchers,
+    pii_scanner: PiiScanner,
+    payload_offloader: Option<PayloadOffloader>,
+    runtime_settings: Option<ReloadableSettings>,
 }
 
 impl EventHandler {
@@ -31,6 +235,29 @@ impl EventHandler {
             event_repository,
             receiver_repository,
             event_publisher: None,
+            publisher: None,
+            outbox: None,
+            publish_policy: PublishPolicy::default(),
+            group_fanout: None,
+            metrics: None,
+            ingestion_meta_repository: None,
+            attestation_repository: None,
+            schema_resolver: None,
+            archive_store: None,
+            archive_index: None,
+            sampling_counters: None,
+            activity_tracker: None,
+            notifier: None,
+            event_bus: None,
+            receiver_provisioning: ReceiverProvisioningPolicy::default(),
+            receiver_history: None,
+            version_strictness: VersionStrictness::default(),
+            receiver_daily_quota: None,
+            audit_logger: Arc::new(AuditLogger::new()),
+            name_matchers: EventNameMatchers::default(),
+            pii_scanner: PiiScanner::default(),
+            payload_offloader: None,
+            runtime_settings: None,
         }
     }
 
@@ -43,660 +270,2825 @@ impl EventHandler {
         Self {
             event_repository,
             receiver_repository,
-            event_publisher: Some(event_publisher),
+            event_publisher: Some(event_publisher.clone()),
+            publisher: Some(event_publisher),
+            outbox: None,
+            publish_policy: PublishPolicy::default(),
+            group_fanout: None,
+            metrics: None,
+            ingestion_meta_repository: None,
+            attestation_repository: None,
+            schema_resolver: None,
+            archive_store: None,
+            archive_index: None,
+            sampling_counters: None,
+            activity_tracker: None,
+            notifier: None,
+            event_bus: None,
+            receiver_provisioning: ReceiverProvisioningPolicy::default(),
+            receiver_history: None,
+            version_strictness: VersionStrictness::default(),
+            receiver_daily_quota: None,
+            audit_logger: Arc::new(AuditLogger::new()),
+            name_matchers: EventNameMatchers::default(),
+            pii_scanner: PiiScanner::default(),
+            payload_offloader: None,
+            runtime_settings: None,
         }
     }
 
-    /// Creates a new event
-    pub async fn create_event(&self, params: CreateEventParams) -> Result<EventId> {
-        info!(
-            name = %params.name,
-            version = %params.version,
-            release = %params.release,
-            platform_id = %params.platform_id,
-            package = %params.package,
-            success = %params.success,
-            receiver_id = %params.receiver_id,
-            "Creating new event"
-        );
+    /// Publishes stored events through `publisher` instead of Kafka
+    pub fn with_event_publisher(mut self, publisher: Arc<dyn EventPublisher>) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
 
-        // Verify that the event receiver exists
-        let receiver = self
-            .receiver_repository
-            .find_by_id(params.receiver_id)
-            .await?;
-        let receiver = receiver.ok_or_else(|| {
-            warn!(receiver_id = %params.receiver_id, "Event receiver not found");
-            DomainError::ReceiverNotFound
-        })?;
+    /// Queues events whose publish failed for a later retry
+    pub fn with_outbox(mut self, outbox: Arc<dyn EventOutboxRepository>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
 
-        info!(
-            receiver_id = %params.receiver_id,
-            receiver_name = %receiver.name(),
-            receiver_type = %receiver.receiver_type(),
-            "Found event receiver for event creation"
-        );
+    /// Sets the publish policy of requests that do not choose one
+    pub fn with_publish_policy(mut self, policy: PublishPolicy) -> Self {
+        self.publish_policy = policy;
+        self
+    }
 
-        // Validate the payload against the receiver's schema
-        if let Err(e) = receiver.validate_event_payload(&params.payload) {
-            error!(
-                receiver_id = %params.receiver_id,
-                error = %e,
-                "Event payload validation failed"
-            );
-            return Err(e.into());
-        }
+    /// Also publishes events to the dedicated topics of their receiver's
+    /// groups once the primary topic accepted them
+    pub fn with_group_fanout(mut self, group_fanout: GroupTopicFanout) -> Self {
+        self.group_fanout = Some(group_fanout);
+        self
+    }
 
-        // Create the domain entity
-        let event = Event::new(params)?;
+    /// Counts stored events by publish outcome
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
 
-        let event_id = event.id();
+    /// Enables recording of ingestion metadata
+    pub fn with_ingestion_meta(
+        mut self,
+        ingestion_meta_repository: Arc<dyn EventIngestionMetaRepository>,
+    ) -> Self {
+        self.ingestion_meta_repository = Some(ingestion_meta_repository);
+        self
+    }
 
-        // Save to repository
-        self.event_repository.save(&event).await?;
+    /// Scans payloads for personal data before they are stored
+    ///
+    /// Findings are recorded in the ingestion metadata, so only events
+    /// created with a context keep them.
+    pub fn with_pii_scanner(mut self, pii_scanner: PiiScanner) -> Self {
+        self.pii_scanner = pii_scanner;
+        self
+    }
 
-        info!(
-            event_id = %event_id,
-            receiver_id = %event.event_receiver_id(),
-            success = %event.success(),
-            "Event created successfully"
-        );
+    /// Moves payloads over the offloader's threshold to the blob store
+    ///
+    /// Events keep a summary of an offloaded payload; readers fetch the
+    /// full payload back with [`Self::load_full_payload`].
+    pub fn with_payload_offloader(mut self, payload_offloader: PayloadOffloader) -> Self {
+        self.payload_offloader = Some(payload_offloader);
+        self
+    }
 
-        // Publish event to Kafka if publisher is configured
-        if let Some(publisher) = &self.event_publisher {
-            if let Err(e) = publisher.publish(&event).await {
-                error!(
-                    event_id = %event_id,
-                    error = %e,
-                    "Failed to publish event to Kafka (event was saved to database)"
-                );
-                // Note: We don't fail the request since the event was saved to the database
-                // The event publication is best-effort
-            } else {
-                info!(
-                    event_id = %event_id,
-                    "Event published to Kafka successfully"
-                );
-            }
-        } else {
-            warn!("Event publisher not configured, skipping Kafka publication");
-        }
+    /// Enables storing the fields extracted from attestation events
+    pub fn with_attestations(
+        mut self,
+        attestation_repository: Arc<dyn EventAttestationRepository>,
+    ) -> Self {
+        self.attestation_repository = Some(attestation_repository);
+        self
+    }
 
-        Ok(event_id)
+    /// Enables group default schema inheritance during payload validation
+    pub fn with_schema_resolver(mut self, schema_resolver: SchemaResolver) -> Self {
+        self.schema_resolver = Some(schema_resolver);
+        self
     }
 
-    /// Gets an event by ID
-    pub async fn get_event(&self, id: EventId) -> Result<Option<Event>> {
-        info!(event_id = %id, "Retrieving event");
+    /// Enables lookups of events that were moved to cold storage
+    pub fn with_archive(
+        mut self,
+        archive_store: Arc<dyn ArchiveStore>,
+        archive_index: Arc<dyn EventArchiveIndexRepository>,
+    ) -> Self {
+        self.archive_store = Some(archive_store);
+        self.archive_index = Some(archive_index);
+        self
+    }
 
-        let event = self.event_repository.find_by_id(id).await?;
+    /// Enables per-receiver counters of events seen versus stored
+    pub fn with_sampling_counters(
+        mut self,
+        sampling_counters: Arc<dyn EventSamplingCounterRepository>,
+    ) -> Self {
+        self.sampling_counters = Some(sampling_counters);
+        self
+    }
 
-        if event.is_none() {
-            info!(event_id = %id, "Event not found");
-        }
+    /// Records receiver liveness for every stored event
+    pub fn with_activity_tracker(mut self, activity_tracker: ReceiverActivityTracker) -> Self {
+        self.activity_tracker = Some(activity_tracker);
+        self
+    }
 
-        Ok(event)
+    /// Announces every stored event to live consumers
+    pub fn with_notifier(mut self, notifier: EventNotifier) -> Self {
+        self.notifier = Some(notifier);
+        self
     }
 
-    /// Gets an event by ID, returning an error if not found
-    pub async fn get_event_or_error(&self, id: EventId) -> Result<Event> {
-        self.get_event(id).await?.ok_or_else(|| {
-            DomainError::EventCreationFailed {
-                reason: "Event not found".to_string(),
-            }
-            .into()
-        })
+    /// Announces every stored event on the domain event bus
+    pub fn with_event_bus(mut self, event_bus: DomainEventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
     }
 
-    /// Lists events by receiver ID
-    pub async fn find_by_receiver(&self, receiver_id: EventReceiverId) -> Result<Vec<Event>> {
-        info!(receiver_id = %receiver_id, "Finding events by receiver");
-        self.event_repository.find_by_receiver_id(receiver_id).await
+    /// Sets whether events may provision their receiver implicitly
+    pub fn with_receiver_provisioning(mut self, policy: ReceiverProvisioningPolicy) -> Self {
+        self.receiver_provisioning = policy;
+        self
     }
 
-    /// Lists events by success status
-    pub async fn find_by_success(&self, success: bool) -> Result<Vec<Event>> {
-        info!(success = %success, "Finding events by success status");
-        self.event_repository.find_by_success(success).await
+    /// Records a snapshot of every receiver provisioned by an event
+    pub fn with_receiver_history(mut self, history: ResourceHistoryHandler) -> Self {
+        self.receiver_history = Some(history);
+        self
     }
 
-    /// Lists events by name pattern
-    pub async fn find_by_name(&self, name_pattern: &str) -> Result<Vec<Event>> {
-        info!(name_pattern = %name_pattern, "Finding events by name pattern");
-        self.event_repository.find_by_name(name_pattern).await
+    /// Sets how strictly event and provisioned receiver versions must be
+    /// semantic versions
+    pub fn with_version_strictness(mut self, strictness: VersionStrictness) -> Self {
+        self.version_strictness = strictness;
+        self
     }
 
-    /// Lists events by platform ID
-    pub async fn find_by_platform(&self, platform_id: &str) -> Result<Vec<Event>> {
-        info!(platform_id = %platform_id, "Finding events by platform");
-        self.event_repository.find_by_platform_id(platform_id).await
+    /// Caps the user events a receiver may store per UTC day; zero removes
+    /// the cap
+    pub fn with_receiver_daily_quota(mut self, quota: u64) -> Self {
+        self.receiver_daily_quota = (quota > 0).then_some(quota);
+        self
     }
 
-    /// Lists events by package
-    pub async fn find_by_package(&self, package: &str) -> Result<Vec<Event>> {
-        info!(package = %package, "Finding events by package");
-        self.event_repository.find_by_package(package).await
+    /// Reads the receiver daily quota and PII scanning settings from
+    /// `settings` on each event, so reloads apply without a restart
+    ///
+    /// Takes precedence over [`Self::with_receiver_daily_quota`] and
+    /// [`Self::with_pii_scanner`].
+    pub fn with_runtime_settings(mut self, settings: ReloadableSettings) -> Self {
+        self.runtime_settings = Some(settings);
+        self
     }
 
-    /// Lists all events with pagination
-    pub async fn list_events(&self, limit: usize, offset: usize) -> Result<Vec<Event>> {
-        info!(limit = %limit, offset = %offset, "Listing events with pagination");
+    /// Uses `audit_logger` to record implicitly provisioned receivers and
+    /// dry runs
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
 
-        // Validate pagination parameters
-        if limit == 0 || limit > 1000 {
-            return Err(DomainError::ValidationError {
-                field: "limit".to_string(),
-                message: "Limit must be between 1 and 1000".to_string(),
-            }
-            .into());
-        }
+    /// Returns how many more events `receiver_id` may store today
+    ///
+    /// Returns `None` when no daily quota is configured. The day starts at
+    /// midnight UTC; sampled-out and system events do not count.
+    pub async fn remaining_quota(&self, receiver_id: EventReceiverId) -> Result<Option<u64>> {
+        let Some(quota) = self.receiver_daily_quota() else {
+            return Ok(None);
+        };
+        let today = Utc::now().date_naive().and_time(NaiveTime::MIN).and_utc();
+        let used = self
+            .event_repository
+            .count_by_receiver_since(receiver_id, today)
+            .await? as u64;
+        Ok(Some(quota.saturating_sub(used)))
+    }
 
-        self.event_repository.list(limit, offset).await
+    fn receiver_daily_quota(&self) -> Option<u64> {
+        match &self.runtime_settings {
+            Some(settings) => settings.current().receiver_daily_quota(),
+            None => self.receiver_daily_quota,
+        }
     }
 
-    /// Counts total number of events
-    pub async fn count_events(&self) -> Result<usize> {
-        info!("Counting total events");
-        self.event_repository.count().await
+    fn pii_scanner(&self) -> PiiScanner {
+        match &self.runtime_settings {
+            Some(settings) => settings.current().pii_scanner(),
+            None => self.pii_scanner,
+        }
     }
 
-    /// Counts events by receiver ID
-    pub async fn count_events_by_receiver(&self, receiver_id: EventReceiverId) -> Result<usize> {
-        info!(receiver_id = %receiver_id, "Counting events by receiver");
-        self.event_repository
-            .count_by_receiver_id(receiver_id)
-            .await
+    /// Returns the Kafka publisher, if event publication is enabled
+    pub fn event_publisher(&self) -> Option<&KafkaEventPublisher> {
+        self.event_publisher.as_deref()
     }
 
-    /// Counts successful events by receiver ID
-    pub async fn count_successful_events_by_receiver(
-        &self,
-        receiver_id: EventReceiverId,
-    ) -> Result<usize> {
-        info!(receiver_id = %receiver_id, "Counting successful events by receiver");
-        self.event_repository
-            .count_successful_by_receiver_id(receiver_id)
-            .await
+    /// Returns the publish policy of requests that do not choose one
+    pub fn publish_policy(&self) -> PublishPolicy {
+        self.publish_policy
     }
 
-    /// Deletes an event
-    pub async fn delete_event(&self, id: EventId) -> Result<()> {
-        info!(event_id = %id, "Deleting event");
+    /// Reports whether stored events can currently be published
+    pub fn publish_health(&self) -> PublishHealth {
+        match &self.publisher {
+            None => PublishHealth::Disabled,
+            Some(publisher) if publisher.is_healthy() => PublishHealth::Healthy,
+            Some(_) => PublishHealth::Failing,
+        }
+    }
 
-        // Check if the event exists
-        if self.event_repository.find_by_id(id).await?.is_none() {
-            return Err(DomainError::EventCreationFailed {
-                reason: "Event not found".to_string(),
-            }
-            .into());
+    /// Validates `payload` against the receiver's own schema and any schema
+    /// it inherits from its groups
+    ///
+    /// Event creation runs the same checks before storing an event.
+    pub async fn validate_payload(
+        &self,
+        receiver: &EventReceiver,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        match self
+            .payload_errors(receiver, payload)
+            .await?
+            .into_iter()
+            .next()
+        {
+            Some(e) => Err(e.into()),
+            None => Ok(()),
         }
+    }
 
-        self.event_repository.delete(id).await?;
+    /// Lists every way `payload` fails the receiver's own or inherited
+    /// schema; empty when the payload passes
+    pub async fn payload_errors(
+        &self,
+        receiver: &EventReceiver,
+        payload: &serde_json::Value,
+    ) -> Result<Vec<DomainError>> {
+        if let Err(e) = receiver.validate_event_payload(payload) {
+            return Ok(vec![e]);
+        }
 
-        info!(event_id = %id, "Event deleted successfully");
+        match &self.schema_resolver {
+            Some(resolver) => Ok(resolver.resolve(receiver).await?.payload_errors(payload)),
+            None => Ok(Vec::new()),
+        }
+    }
 
+    /// Builds the per-receiver state the first event would otherwise build
+    ///
+    /// Resolves the inherited schema and compiles the event name
+    /// constraint of `receiver`, so both are cached before traffic for it
+    /// arrives. Used by the startup warm-up for recently active receivers.
+    pub async fn prepare_receiver(&self, receiver: &EventReceiver) -> Result<()> {
+        if let Some(resolver) = &self.schema_resolver {
+            resolver.resolve(receiver).await?;
+        }
+        self.name_matchers.for_receiver(receiver)?;
         Ok(())
     }
 
-    /// Gets the latest event for a receiver
-    pub async fn get_latest_event_for_receiver(
+    /// Rejects event names `receiver` does not accept
+    ///
+    /// Names under the reserved `xzepr.` prefix are rejected for every
+    /// receiver; system events are built by `SystemEventFactory` and never
+    /// pass through here. Otherwise the receiver's `allowed_event_names`
+    /// constraint applies, compiled once per receiver version.
+    pub fn check_event_name(
         &self,
-        receiver_id: EventReceiverId,
-    ) -> Result<Option<Event>> {
-        info!(receiver_id = %receiver_id, "Getting latest event for receiver");
-        self.event_repository
-            .find_latest_by_receiver_id(receiver_id)
-            .await
+        receiver: &EventReceiver,
+        name: &str,
+    ) -> std::result::Result<(), DomainError> {
+        if is_reserved_event_name(name) {
+            return Err(DomainError::ReservedEventName {
+                name: name.to_string(),
+            });
+        }
+
+        match self.name_matchers.for_receiver(receiver)? {
+            Some(matcher) => matcher.check(name),
+            None => Ok(()),
+        }
     }
 
-    /// Gets the latest successful event for a receiver
-    pub async fn get_latest_successful_event_for_receiver(
+    /// Returns the payload transform of a receiver, if it has one
+    ///
+    /// `None` as well for receivers that do not exist.
+    pub async fn payload_transform(
         &self,
         receiver_id: EventReceiverId,
-    ) -> Result<Option<Event>> {
-        info!(receiver_id = %receiver_id, "Getting latest successful event for receiver");
-        self.event_repository
-            .find_latest_successful_by_receiver_id(receiver_id)
-            .await
+    ) -> Result<Option<PayloadTransform>> {
+        Ok(self
+            .receiver_repository
+            .find_by_id(receiver_id)
+            .await?
+            .and_then(|receiver| receiver.transform().cloned()))
     }
 
-    /// Checks if an event exists
-    pub async fn exists(&self, id: EventId) -> Result<bool> {
-        Ok(self.event_repository.find_by_id(id).await?.is_some())
+    /// Shows what the receiver's transform makes of `payload`
+    ///
+    /// `receiver` stands in for the lookup of `receiver_id`, as for
+    /// [`Self::dry_run_event`]. `None` when the receiver has no transform.
+    pub async fn preview_transform(
+        &self,
+        receiver_id: EventReceiverId,
+        receiver: Option<&EventReceiver>,
+        payload: &serde_json::Value,
+    ) -> Result<Option<TransformPreview>> {
+        let transform = match receiver {
+            Some(receiver) => receiver.transform().cloned(),
+            None => self.payload_transform(receiver_id).await?,
+        };
+        Ok(transform.map(|transform| TransformPreview::new(&transform, payload)))
     }
 
-    /// Gets events within a time range
-    pub async fn find_events_in_time_range(
+    /// Runs the checks an event passes before it is stored
+    ///
+    /// Event creation and dry runs share these checks, so a dry run accepts
+    /// exactly the events a real submission would. `receiver` stands in for
+    /// the lookup of `params.receiver_id`, for receivers a spec would
+    /// provision. Nothing is stored or counted.
+    ///
+    /// The schema check is lapped on `timings`; the time before it counts
+    /// as validation, and the caller laps validation again afterwards.
+    async fn admit(
         &self,
-        start: chrono::DateTime<chrono::Utc>,
-        end: chrono::DateTime<chrono::Utc>,
-    ) -> Result<Vec<Event>> {
+        mut params: CreateEventParams,
+        receiver: Option<EventReceiver>,
+        timings: &mut IngestionTimings,
+    ) -> Result<Admission> {
+        if is_reserved_event_name(&params.name) {
+            warn!(event_name = %params.name, "Event name uses the reserved prefix");
+            return Ok(Admission::Rejected(vec![DomainError::ReservedEventName {
+                name: params.name,
+            }]));
+        }
+
+        if let Err(e) = Version::parse(&params.version, self.version_strictness) {
+            return Ok(Admission::Rejected(vec![e]));
+        }
+
+        // Verify that the event receiver exists
+        let receiver = match receiver {
+            Some(receiver) => receiver,
+            None => match self
+                .receiver_repository
+                .find_by_id(params.receiver_id)
+                .await?
+            {
+                Some(receiver) => receiver,
+                None => {
+                    warn!(receiver_id = %params.receiver_id, "Event receiver not found");
+                    return Ok(Admission::Rejected(vec![DomainError::ReceiverNotFound]));
+                }
+            },
+        };
+
         info!(
-            start = %start,
-            end = %end,
-            "Finding events within time range"
+            receiver_id = %params.receiver_id,
+            receiver_name = %receiver.name(),
+            receiver_type = %receiver.receiver_type(),
+            "Found event receiver for event creation"
         );
 
-        if start >= end {
-            return Err(DomainError::ValidationError {
-                field: "time_range".to_string(),
-                message: "Start time must be before end time".to_string(),
-            }
-            .into());
+        if receiver.auto_disabled().is_some() {
+            warn!(receiver_id = %params.receiver_id, "Event receiver is auto-disabled");
+            return Ok(Admission::Rejected(vec![
+                DomainError::ReceiverAutoDisabled {
+                    receiver_id: params.receiver_id.to_string(),
+                },
+            ]));
         }
 
-        self.event_repository.find_by_time_range(start, end).await
-    }
+        if let Err(e) = self.check_event_name(&receiver, &params.name) {
+            return Ok(Admission::Rejected(vec![e]));
+        }
 
-    /// Gets events statistics for a receiver
-    pub async fn get_receiver_statistics(
-        &self,
-        receiver_id: EventReceiverId,
-    ) -> Result<ReceiverStatistics> {
-        info!(receiver_id = %receiver_id, "Getting receiver statistics");
+        // Everything after this, from validation to redaction, sees the
+        // payload as the receiver's transform leaves it
+        if let Some(transform) = receiver.transform() {
+            params.payload = match transform.apply(params.payload) {
+                Ok(payload) => payload,
+                Err(e) => return Ok(Admission::Rejected(vec![e])),
+            };
+        }
 
-        // Verify receiver exists
-        if self
-            .receiver_repository
-            .find_by_id(receiver_id)
-            .await?
-            .is_none()
-        {
-            return Err(DomainError::ReceiverNotFound.into());
+        // Validate the payload against the receiver's own and inherited schema
+        timings.lap(IngestionStage::Validation);
+        let errors = self.payload_errors(&receiver, &params.payload).await;
+        timings.lap(IngestionStage::SchemaCheck);
+        let errors = errors?;
+        if !errors.is_empty() {
+            return Ok(Admission::Rejected(errors));
         }
 
-        let total_events = self.count_events_by_receiver(receiver_id).await?;
-        let successful_events = self
-            .count_successful_events_by_receiver(receiver_id)
-            .await?;
-        let failed_events = total_events - successful_events;
-        let latest_event = self.get_latest_event_for_receiver(receiver_id).await?;
-        let latest_successful_event = self
-            .get_latest_successful_event_for_receiver(receiver_id)
-            .await?;
+        if !SamplingPolicy::for_receiver(&receiver).keeps(&params) {
+            return Ok(Admission::SampledOut);
+        }
 
-        let success_rate = if total_events > 0 {
-            (successful_events as f64 / total_events as f64) * 100.0
-        } else {
-            0.0
+        // The payload passed validation, which extracts attestations too
+        let attestation = match receiver.extract_attestation(&params.payload) {
+            Ok(attestation) => attestation,
+            Err(e) => return Ok(Admission::Rejected(vec![e])),
         };
 
-        Ok(ReceiverStatistics {
-            receiver_id,
-            total_events,
-            successful_events,
-            failed_events,
-            success_rate,
-            latest_event_id: latest_event.map(|e| e.id()),
-            latest_successful_event_id: latest_successful_event.map(|e| e.id()),
+        match Event::new(params) {
+            Ok(event) => Ok(Admission::Accepted(Box::new(event), attestation)),
+            Err(e) => Ok(Admission::Rejected(vec![e])),
+        }
+    }
+
+    /// Runs the ingestion checks for an event without storing it
+    ///
+    /// Nothing is saved, published, or counted towards sampling or receiver
+    /// activity. `receiver` stands in for the lookup of `params.receiver_id`,
+    /// as for a receiver [`Self::preview_receiver`] found would be created.
+    pub async fn dry_run_event(
+        &self,
+        params: CreateEventParams,
+        receiver: Option<EventReceiver>,
+    ) -> Result<DryRunOutcome> {
+        let mut timings = IngestionTimings::start(INTERNAL_CHANNEL);
+        Ok(match self.admit(params, receiver, &mut timings).await? {
+            Admission::Accepted(..) => DryRunOutcome::WouldCreate,
+            Admission::SampledOut => DryRunOutcome::SampledOut,
+            Admission::Rejected(errors) => DryRunOutcome::Rejected(errors),
         })
     }
-}
 
-/// Statistics for an event receiver
-#[derive(Debug, Clone)]
-pub struct ReceiverStatistics {
-    pub receiver_id: EventReceiverId,
-    pub total_events: usize,
-    pub successful_events: usize,
-    pub failed_events: usize,
-    pub success_rate: f64, // Percentage
-    pub latest_event_id: Option<EventId>,
-    pub latest_successful_event_id: Option<EventId>,
-}
+    /// Audit-logs a dry run, flagged so it is not mistaken for a submission
+    pub fn record_dry_run(
+        &self,
+        owner_id: UserId,
+        receiver_id: Option<EventReceiverId>,
+        outcome: &DryRunOutcome,
+    ) {
+        let resource = match receiver_id {
+            Some(receiver_id) => format!("event_receiver:{}/event", receiver_id),
+            None => "event".to_string(),
+        };
+        let result = match outcome {
+            DryRunOutcome::WouldCreate => "would_create",
+            DryRunOutcome::SampledOut => "sampled_out",
+            DryRunOutcome::Rejected(_) => "rejected",
+        };
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(owner_id.to_string())
+                .action(AuditAction::ResourceCreate)
+                .resource(resource)
+                .outcome(if outcome.would_create() {
+                    AuditOutcome::Success
+                } else {
+                    AuditOutcome::Failure
+                })
+                .add_metadata("dry_run", "true")
+                .add_metadata("result", result)
+                .build(),
+        );
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::entities::event_receiver::EventReceiver;
-    use crate::domain::repositories::event_repo::EventRepository;
-    use async_trait::async_trait;
-    use chrono::{DateTime, Utc};
-    use serde_json::json;
-    use std::collections::HashMap;
-    use std::sync::Mutex;
+    /// Creates a new event
+    ///
+    /// Receivers with a sample rate store only a deterministic fraction of
+    /// their events; dropped events are reported as `SampledOut`.
+    pub async fn create_event(&self, params: CreateEventParams) -> Result<CreateEventOutcome> {
+        self.create_event_inner(params, None, self.publish_policy)
+            .await
+    }
 
-    // Mock repositories for testing
-    struct MockEventRepository {
-        events: Arc<Mutex<HashMap<EventId, Event>>>,
+    /// Creates a new event and records who submitted it and how
+    pub async fn create_event_with_context(
+        &self,
+        params: CreateEventParams,
+        context: IngestionContext,
+    ) -> Result<CreateEventOutcome> {
+        self.create_event_inner(params, Some(context), self.publish_policy)
+            .await
     }
 
-    impl MockEventRepository {
-        fn new() -> Self {
-            Self {
-                events: Arc::new(Mutex::new(HashMap::new())),
+    /// Creates a new event, handling a failed publish as `policy` says
+    ///
+    /// `None` uses the configured default policy. Under
+    /// [`PublishPolicy::Require`] an event the stream does not accept is
+    /// removed again and the call fails with
+    /// [`InfrastructureError::PublishUnavailable`].
+    pub async fn create_event_with_policy(
+        &self,
+        params: CreateEventParams,
+        context: IngestionContext,
+        policy: Option<PublishPolicy>,
+    ) -> Result<CreateEventOutcome> {
+        self.create_event_inner(params, Some(context), policy.unwrap_or(self.publish_policy))
+            .await
+    }
+
+    /// Creates an event, timing each ingestion stage
+    ///
+    /// The stage timings are logged at debug level and, with metrics
+    /// attached, recorded per channel whatever the outcome.
+    async fn create_event_inner(
+        &self,
+        params: CreateEventParams,
+        context: Option<IngestionContext>,
+        publish_policy: PublishPolicy,
+    ) -> Result<CreateEventOutcome> {
+        let mut timings = IngestionTimings::for_context(context.as_ref());
+        let result = self
+            .ingest(params, context, publish_policy, &mut timings)
+            .await;
+        let outcome = match &result {
+            Ok(CreateEventOutcome::Stored { .. }) => "stored",
+            Ok(CreateEventOutcome::SampledOut) => "sampled_out",
+            Err(_) => "rejected",
+        };
+        timings.finish(self.metrics.as_deref(), outcome);
+        result
+    }
+
+    async fn ingest(
+        &self,
+        params: CreateEventParams,
+        context: Option<IngestionContext>,
+        publish_policy: PublishPolicy,
+        timings: &mut IngestionTimings,
+    ) -> Result<CreateEventOutcome> {
+        info!(
+            name = %params.name,
+            version = %params.version,
+            release = %params.release,
+            platform_id = %params.platform_id,
+            package = %params.package,
+            success = %params.success,
+            receiver_id = %params.receiver_id,
+            "Creating new event"
+        );
+
+        let receiver_id = params.receiver_id;
+        let admission = self.admit(params, None, timings).await;
+        timings.lap(IngestionStage::Validation);
+        let (mut event, attestation) = match admission? {
+            Admission::Accepted(event, attestation) => (*event, attestation),
+            Admission::SampledOut => {
+                info!(receiver_id = %receiver_id, "Event sampled out");
+                self.record_sampling(receiver_id, false).await;
+                return Ok(CreateEventOutcome::SampledOut);
+            }
+            Admission::Rejected(mut errors) => {
+                let e = errors.swap_remove(0);
+                error!(
+                    receiver_id = %receiver_id,
+                    error = %e,
+                    "Event validation failed"
+                );
+                return Err(e.into());
+            }
+        };
+
+        let remaining = self.remaining_quota(receiver_id).await;
+        timings.lap(IngestionStage::QuotaCheck);
+        if remaining? == Some(0) {
+            warn!(receiver_id = %receiver_id, "Event rejected by the daily quota");
+            return Err(DomainError::QuotaExceeded {
+                receiver_id: receiver_id.to_string(),
+                requested: 1,
+                remaining: 0,
+            }
+            .into());
+        }
+
+        // Without a stream, a required publish can never succeed
+        if publish_policy == PublishPolicy::Require && self.publisher.is_none() {
+            self.record_publish_outcome("rejected");
+            return Err(Error::Infrastructure(
+                InfrastructureError::PublishUnavailable {
+                    message: "Event publishing is not configured".to_string(),
+                },
+            ));
+        }
+
+        let event_id = event.id();
+
+        // Validation saw the payload as sent; only storage sees it masked
+        let pii_scanner = self.pii_scanner();
+        let pii = event.scan_payload(&pii_scanner);
+        if !pii.findings.is_empty() {
+            let detectors: BTreeSet<_> = pii.findings.iter().map(|f| f.detector.as_str()).collect();
+            warn!(
+                event_id = %event_id,
+                receiver_id = %receiver_id,
+                findings = pii.findings.len(),
+                truncated = pii.truncated,
+                detectors = ?detectors,
+                mode = ?pii_scanner.mode(),
+                "Event payload looks like it contains personal data"
+            );
+        } else if pii.skipped {
+            info!(
+                event_id = %event_id,
+                receiver_id = %receiver_id,
+                "Event payload is over the PII scan size cap and was not scanned"
+            );
+        }
+
+        // Large payloads go to the blob store first, so the row and the
+        // stream only carry their summary
+        if let Some(offloader) = &self.payload_offloader {
+            offloader.offload(&mut event).await?;
+        }
+
+        // Save to repository, then publish; a required publish that fails
+        // removes the event again before anything else records it
+        let saved = self.event_repository.save(&event).await;
+        timings.lap(IngestionStage::Persist);
+        if let Err(e) = saved {
+            self.discard_payload(&event).await;
+            return Err(e);
+        }
+        let published = self.publish_stored(&event, publish_policy).await;
+        if self.publisher.is_some() {
+            timings.lap(IngestionStage::Publish);
+        }
+        let publish_status = published?;
+        self.record_sampling(event.event_receiver_id(), true).await;
+
+        // Buffer receiver liveness; events without a context count as their owner
+        if let Some(tracker) = &self.activity_tracker {
+            let principal = match &context {
+                Some(context) => context.principal(),
+                None => format!("{}:{}", PrincipalType::User, event.owner_id()),
+            };
+            tracker.record(event.event_receiver_id(), principal, event.created_at());
+        }
+
+        if let Some(notifier) = &self.notifier {
+            notifier.notify(&event);
+        }
+        if let Some(bus) = &self.event_bus {
+            bus.publish(DomainEvent::EventCreated {
+                event: event.clone(),
+            });
+        }
+
+        info!(
+            event_id = %event_id,
+            receiver_id = %event.event_receiver_id(),
+            success = %event.success(),
+            publish_status = ?publish_status,
+            "Event created successfully"
+        );
+
+        // Record ingestion metadata if configured
+        if let (Some(repo), Some(context)) = (&self.ingestion_meta_repository, context) {
+            let meta = IngestionMeta::new(event_id, context).with_pii_findings(pii.findings);
+            if let Err(e) = repo.save_ingestion_meta(&meta).await {
+                error!(
+                    event_id = %event_id,
+                    error = %e,
+                    "Failed to record ingestion metadata (event was saved to database)"
+                );
+                // Note: We don't fail the request since the event was saved to the database
+            }
+        }
+        if let (Some(repo), Some(attestation)) = (&self.attestation_repository, attestation) {
+            let attestation =
+                EventAttestation::new(event_id, event.event_receiver_id(), attestation);
+            if let Err(e) = repo.save_attestation(&attestation).await {
+                error!(
+                    event_id = %event_id,
+                    error = %e,
+                    "Failed to record attestation fields (event was saved to database)"
+                );
             }
         }
+        timings.lap(IngestionStage::Persist);
+
+        Ok(CreateEventOutcome::Stored {
+            event_id,
+            publish_status,
+        })
     }
 
-    #[async_trait]
-    impl EventRepository for MockEventRepository {
-        async fn save(&self, event: &Event) -> Result<()> {
-            let mut events = self.events.lock().unwrap();
-            events.insert(event.id(), event.clone());
-            Ok(())
+    /// Publishes a saved event, handling a failure as `policy` says
+    ///
+    /// Returns `None` when no publisher is configured.
+    async fn publish_stored(
+        &self,
+        event: &Event,
+        policy: PublishPolicy,
+    ) -> Result<Option<PublishStatus>> {
+        let Some(publisher) = &self.publisher else {
+            warn!("Event publisher not configured, skipping Kafka publication");
+            return Ok(None);
+        };
+
+        let event_id = event.id();
+        let publish_error = match publisher.publish(event).await {
+            Ok(()) => {
+                info!(event_id = %event_id, "Event published to Kafka successfully");
+                self.record_publish_outcome("published");
+                if let Some(fanout) = &self.group_fanout {
+                    fanout.fan_out(event).await;
+                }
+                return Ok(Some(PublishStatus::Published));
+            }
+            Err(e) => e,
+        };
+
+        if policy == PublishPolicy::Require {
+            error!(
+                event_id = %event_id,
+                error = %publish_error,
+                "Failed to publish event to Kafka; removing it as the publish policy requires"
+            );
+            return self.remove_unpublished(event, &publish_error).await;
         }
 
-        async fn find_by_id(&self, id: EventId) -> Result<Option<Event>> {
-            let events = self.events.lock().unwrap();
-            Ok(events.get(&id).cloned())
+        // Only a queued event is retried, so one that cannot be queued is
+        // removed rather than reported as deferred
+        let queued = match &self.outbox {
+            Some(outbox) => {
+                let next_attempt_at = Utc::now() + publish_retry_delay(1);
+                outbox
+                    .enqueue(event_id, &publish_error.to_string(), next_attempt_at)
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            None => Err("no publish outbox is configured".to_string()),
+        };
+        if let Err(reason) = queued {
+            error!(
+                event_id = %event_id,
+                error = %publish_error,
+                reason = %reason,
+                "Failed to publish event to Kafka or queue it for retry; removing it"
+            );
+            return self.remove_unpublished(event, &publish_error).await;
         }
+        warn!(
+            event_id = %event_id,
+            error = %publish_error,
+            "Failed to publish event to Kafka; queued for retry"
+        );
+        self.record_publish_outcome("deferred");
 
-        async fn find_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<Vec<Event>> {
-            Ok(vec![])
+        Ok(Some(PublishStatus::Deferred))
+    }
+
+    /// Removes a saved event that will not be published and fails with
+    /// [`InfrastructureError::PublishUnavailable`]
+    async fn remove_unpublished(
+        &self,
+        event: &Event,
+        publish_error: &Error,
+    ) -> Result<Option<PublishStatus>> {
+        self.record_publish_outcome("rejected");
+        self.event_repository.delete(event.id()).await?;
+        self.discard_payload(event).await;
+        Err(Error::Infrastructure(
+            InfrastructureError::PublishUnavailable {
+                message: publish_error.to_string(),
+            },
+        ))
+    }
+
+    /// Deletes the offloaded payload of an event that is gone
+    ///
+    /// A blob left behind only costs storage, so failures are logged.
+    async fn discard_payload(&self, event: &Event) {
+        if let Some(offloader) = &self.payload_offloader {
+            if let Err(e) = offloader.discard(event).await {
+                warn!(
+                    event_id = %event.id(),
+                    error = %e,
+                    "Failed to delete offloaded event payload"
+                );
+            }
         }
+    }
 
-        async fn find_by_success(&self, _success: bool) -> Result<Vec<Event>> {
-            Ok(vec![])
+    /// Puts back the full payload of an event whose payload was offloaded
+    ///
+    /// Without an offloader configured, offloaded events keep their summary.
+    pub async fn load_full_payload(&self, event: &mut Event) -> Result<()> {
+        match &self.payload_offloader {
+            Some(offloader) => offloader.restore(event).await,
+            None => Ok(()),
         }
+    }
 
-        async fn find_by_name(&self, _name: &str) -> Result<Vec<Event>> {
-            Ok(vec![])
+    /// Puts back the full payloads of several events
+    pub async fn load_full_payloads(&self, events: &mut [Event]) -> Result<()> {
+        match &self.payload_offloader {
+            Some(offloader) => offloader.restore_all(events).await,
+            None => Ok(()),
         }
+    }
 
-        async fn find_by_platform_id(&self, _platform_id: &str) -> Result<Vec<Event>> {
-            Ok(vec![])
+    /// Records one ingestion stage timed outside of event creation
+    ///
+    /// Batch submissions time duplicate detection once per batch.
+    pub fn record_ingestion_stage(&self, stage: IngestionStage, channel: &str, duration: Duration) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_ingestion_stage(stage.as_str(), channel, duration.as_secs_f64());
         }
+    }
 
-        async fn find_by_package(&self, _package: &str) -> Result<Vec<Event>> {
-            Ok(vec![])
+    fn record_publish_outcome(&self, outcome: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_event_publish_outcome(outcome);
         }
+    }
 
-        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<Event>> {
-            Ok(vec![])
+    /// Returns the publish status of stored events, keyed by id
+    ///
+    /// Events still in the outbox are deferred, all others published. The
+    /// map is empty unless both publication and the outbox are configured.
+    pub async fn publish_statuses(
+        &self,
+        event_ids: &[EventId],
+    ) -> Result<HashMap<EventId, PublishStatus>> {
+        let (Some(_), Some(outbox)) = (&self.publisher, &self.outbox) else {
+            return Ok(HashMap::new());
+        };
+
+        let deferred: HashSet<EventId> = outbox
+            .find_entries(event_ids)
+            .await?
+            .into_iter()
+            .map(|entry| entry.event_id)
+            .collect();
+
+        Ok(event_ids
+            .iter()
+            .map(|id| {
+                let status = if deferred.contains(id) {
+                    PublishStatus::Deferred
+                } else {
+                    PublishStatus::Published
+                };
+                (*id, status)
+            })
+            .collect())
+    }
+
+    /// Resolves the receiver an event names by spec, creating it if needed
+    ///
+    /// An existing receiver with the spec's fingerprint is reused. Otherwise
+    /// the receiver is created and owned by `owner_id`, provided the
+    /// provisioning policy allows it for a caller with or without the
+    /// receiver create permission. Concurrent calls for the same spec create
+    /// exactly one receiver.
+    pub async fn provision_receiver(
+        &self,
+        spec: &ReceiverSpec,
+        owner_id: UserId,
+        can_create_receivers: bool,
+    ) -> Result<ProvisionedReceiver> {
+        let preview = self
+            .preview_receiver(spec, owner_id, can_create_receivers)
+            .await?;
+        if !preview.would_create {
+            return Ok(ProvisionedReceiver {
+                receiver_id: preview.receiver.id(),
+                created: false,
+            });
         }
 
-        async fn count(&self) -> Result<usize> {
-            let events = self.events.lock().unwrap();
-            Ok(events.len())
+        let (receiver, created) = self
+            .receiver_repository
+            .save_if_absent(&preview.receiver)
+            .await?;
+        if created {
+            info!(
+                receiver_id = %receiver.id(),
+                fingerprint = %receiver.fingerprint(),
+                owner_id = %owner_id,
+                "Event receiver provisioned by first event"
+            );
+            if let Some(history) = &self.receiver_history {
+                history.record_receiver(&receiver, Utc::now()).await;
+            }
+            self.audit_logger.log_event(
+                AuditEvent::builder()
+                    .user_id(owner_id.to_string())
+                    .action(AuditAction::ResourceCreate)
+                    .resource(format!("event_receiver:{}", receiver.id()))
+                    .outcome(AuditOutcome::Success)
+                    .add_metadata("provisioning", "implicit")
+                    .add_metadata("fingerprint", receiver.fingerprint())
+                    .build(),
+            );
         }
 
-        async fn count_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<usize> {
-            Ok(0)
+        Ok(ProvisionedReceiver {
+            receiver_id: receiver.id(),
+            created,
+        })
+    }
+
+    /// Resolves the receiver an event names by spec, without creating it
+    ///
+    /// Applies the same provisioning policy and conflict checks as
+    /// [`Self::provision_receiver`].
+    pub async fn preview_receiver(
+        &self,
+        spec: &ReceiverSpec,
+        owner_id: UserId,
+        can_create_receivers: bool,
+    ) -> Result<ReceiverPreview> {
+        if !self.receiver_provisioning.permits(can_create_receivers) {
+            let reason = match self.receiver_provisioning {
+                ReceiverProvisioningPolicy::Disabled => "provisioning is disabled",
+                _ => "caller may not create event receivers",
+            };
+            warn!(
+                owner_id = %owner_id,
+                receiver_name = %spec.name,
+                reason,
+                "Implicit receiver provisioning rejected"
+            );
+            return Err(DomainError::ReceiverProvisioningDisabled {
+                reason: reason.to_string(),
+            }
+            .into());
         }
 
-        async fn count_successful_by_receiver_id(
-            &self,
-            _receiver_id: EventReceiverId,
-        ) -> Result<usize> {
-            Ok(0)
+        Version::parse(&spec.version, self.version_strictness)?;
+        let candidate = spec.to_receiver(owner_id)?;
+        if let Some(existing) = self
+            .receiver_repository
+            .find_by_fingerprint(candidate.fingerprint())
+            .await?
+        {
+            return Ok(ReceiverPreview {
+                receiver: existing,
+                would_create: false,
+            });
         }
 
-        async fn delete(&self, id: EventId) -> Result<()> {
-            let mut events = self.events.lock().unwrap();
-            events.remove(&id);
-            Ok(())
+        // A different version or schema under the same name stays an error
+        if self
+            .receiver_repository
+            .exists_by_name_and_type(&spec.name, &spec.receiver_type)
+            .await?
+        {
+            return Err(DomainError::BusinessRuleViolation {
+                rule: Message::new("rule.receiver_exists"),
+            }
+            .into());
         }
 
-        async fn find_latest_by_receiver_id(
-            &self,
-            _receiver_id: EventReceiverId,
-        ) -> Result<Option<Event>> {
-            Ok(None)
+        Ok(ReceiverPreview {
+            receiver: candidate,
+            would_create: true,
+        })
+    }
+
+    /// Counts an event towards its receiver's sampling counters
+    async fn record_sampling(&self, receiver_id: EventReceiverId, stored: bool) {
+        if let Some(counters) = &self.sampling_counters {
+            if let Err(e) = counters
+                .record_sampling(receiver_id, sampling_bucket(Utc::now()), stored)
+                .await
+            {
+                // Counters are best-effort and never fail ingestion
+                error!(
+                    receiver_id = %receiver_id,
+                    error = %e,
+                    "Failed to record sampling counters"
+                );
+            }
+        }
+    }
+
+    /// Gets an event by ID
+    pub async fn get_event(&self, id: EventId) -> Result<Option<Event>> {
+        info!(event_id = %id, "Retrieving event");
+
+        let event = self.event_repository.find_by_id(id).await?;
+
+        if event.is_none() {
+            info!(event_id = %id, "Event not found");
         }
 
-        async fn find_latest_successful_by_receiver_id(
-            &self,
-            _receiver_id: EventReceiverId,
-        ) -> Result<Option<Event>> {
-            Ok(None)
-        }
+        Ok(event)
+    }
+
+    /// Looks up an event that is no longer in the primary database
+    ///
+    /// Reading a segment is much slower than a database lookup, so callers
+    /// should only fall back to this after `get_event` returned `None`.
+    pub async fn find_archived_event(&self, id: EventId) -> Result<ArchivedEventLookup> {
+        let (store, index) = match (&self.archive_store, &self.archive_index) {
+            (Some(store), Some(index)) => (store, index),
+            _ => return Ok(ArchivedEventLookup::NotArchived),
+        };
+
+        let entry = match index.find_archived(id).await? {
+            Some(entry) => entry,
+            None => return Ok(ArchivedEventLookup::NotArchived),
+        };
+
+        let started = Instant::now();
+        let event = store.get_by_id(&entry.segment, id).await?;
+        info!(
+            event_id = %id,
+            segment = %entry.segment,
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Fetched event from archive"
+        );
+
+        match event {
+            Some(event) => Ok(ArchivedEventLookup::Found(Box::new(event))),
+            None => {
+                warn!(
+                    event_id = %id,
+                    segment = %entry.segment,
+                    "Archived event is missing from its segment"
+                );
+                Ok(ArchivedEventLookup::Unavailable {
+                    segment: entry.segment,
+                })
+            }
+        }
+    }
+
+    /// Gets the ingestion metadata recorded for an event
+    ///
+    /// Returns `None` if the event has no metadata or recording is disabled.
+    pub async fn get_ingestion_meta(&self, id: EventId) -> Result<Option<IngestionMeta>> {
+        match &self.ingestion_meta_repository {
+            Some(repo) => repo.find_ingestion_meta(id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Lists events whose ingestion metadata matches a filter
+    pub async fn list_events_by_ingestion(
+        &self,
+        filter: IngestionMetaFilter,
+    ) -> Result<Vec<(Event, IngestionMeta)>> {
+        info!(?filter, "Listing events by ingestion metadata");
+
+        let limit = filter.limit.unwrap_or(50);
+        if limit == 0 || limit > 1000 {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range").with("max", 1000),
+            }
+            .into());
+        }
+
+        let repo = match &self.ingestion_meta_repository {
+            Some(repo) => repo,
+            None => {
+                warn!("Ingestion metadata not configured, returning no events");
+                return Ok(Vec::new());
+            }
+        };
+
+        let metas = repo
+            .find_ingestion_meta_by_filter(&filter.with_limit(limit))
+            .await?;
+
+        let mut events = Vec::with_capacity(metas.len());
+        for meta in metas {
+            if let Some(event) = self.event_repository.find_by_id(meta.event_id()).await? {
+                events.push((event, meta));
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Counts events whose ingestion metadata matches a filter, ignoring its
+    /// limit and offset
+    pub async fn count_events_by_ingestion(&self, filter: &IngestionMetaFilter) -> Result<usize> {
+        match &self.ingestion_meta_repository {
+            Some(repo) => repo.count_ingestion_meta_by_filter(filter).await,
+            None => Ok(0),
+        }
+    }
+
+    /// Aggregates the PII findings of a receiver's events ingested since
+    /// `since`
+    ///
+    /// Returns an empty report when ingestion metadata is not recorded.
+    pub async fn pii_report(
+        &self,
+        receiver_id: EventReceiverId,
+        since: DateTime<Utc>,
+    ) -> Result<PiiReport> {
+        let Some(repo) = &self.ingestion_meta_repository else {
+            return Ok(PiiReport::default());
+        };
+        repo.find_pii_report(receiver_id, since, PII_REPORT_MAX_PATHS)
+            .await
+    }
+
+    /// Lists attestation events matching a filter, newest first
+    ///
+    /// Returns no events if attestation fields are not recorded.
+    pub async fn list_events_by_attestation(
+        &self,
+        filter: AttestationFilter,
+    ) -> Result<Vec<(Event, EventAttestation)>> {
+        info!(?filter, "Listing events by attestation");
+
+        let limit = filter.limit.unwrap_or(50);
+        if limit == 0 || limit > 1000 {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range").with("max", 1000),
+            }
+            .into());
+        }
+
+        let repo = match &self.attestation_repository {
+            Some(repo) => repo,
+            None => {
+                warn!("Attestations not configured, returning no events");
+                return Ok(Vec::new());
+            }
+        };
+
+        let attestations = repo.find_attestations(&filter.with_limit(limit)).await?;
+
+        let mut events = Vec::with_capacity(attestations.len());
+        for attestation in attestations {
+            if let Some(event) = self
+                .event_repository
+                .find_by_id(attestation.event_id)
+                .await?
+            {
+                events.push((event, attestation));
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Gets an event by ID, returning an error if not found
+    pub async fn get_event_or_error(&self, id: EventId) -> Result<Event> {
+        self.get_event(id).await?.ok_or_else(|| {
+            DomainError::EventCreationFailed {
+                reason: "Event not found".to_string(),
+            }
+            .into()
+        })
+    }
+
+    /// Lists events by receiver ID
+    pub async fn find_by_receiver(&self, receiver_id: EventReceiverId) -> Result<Vec<Event>> {
+        info!(receiver_id = %receiver_id, "Finding events by receiver");
+        self.event_repository.find_by_receiver_id(receiver_id).await
+    }
+
+    /// Lists events by success status
+    pub async fn find_by_success(&self, success: bool) -> Result<Vec<Event>> {
+        info!(success = %success, "Finding events by success status");
+        self.event_repository.find_by_success(success).await
+    }
+
+    /// Lists events by name pattern
+    pub async fn find_by_name(&self, name_pattern: &str) -> Result<Vec<Event>> {
+        info!(name_pattern = %name_pattern, "Finding events by name pattern");
+        self.event_repository.find_by_name(name_pattern).await
+    }
+
+    /// Lists events by platform ID
+    pub async fn find_by_platform(&self, platform_id: &str) -> Result<Vec<Event>> {
+        info!(platform_id = %platform_id, "Finding events by platform");
+        self.event_repository.find_by_platform_id(platform_id).await
+    }
+
+    /// Lists events by package
+    pub async fn find_by_package(&self, package: &str) -> Result<Vec<Event>> {
+        info!(package = %package, "Finding events by package");
+        self.event_repository.find_by_package(package).await
+    }
+
+    /// Finds events using multiple criteria
+    pub async fn find_by_criteria(&self, criteria: FindEventCriteria) -> Result<Vec<Event>> {
+        info!(?criteria, "Finding events by criteria");
+
+        if criteria.is_empty() {
+            warn!("Empty criteria provided, using default pagination");
+            let criteria = FindEventCriteria {
+                limit: Some(50),
+                offset: Some(0),
+                ..criteria
+            };
+            return self.event_repository.find_by_criteria(criteria).await;
+        }
+
+        self.event_repository.find_by_criteria(criteria).await
+    }
+
+    /// Counts events matching criteria, ignoring their limit and offset
+    pub async fn count_by_criteria(&self, criteria: FindEventCriteria) -> Result<usize> {
+        self.event_repository.count_by_criteria(criteria).await
+    }
+
+    /// Lists all events with pagination
+    pub async fn list_events(&self, limit: usize, offset: usize) -> Result<Vec<Event>> {
+        info!(limit = %limit, offset = %offset, "Listing events with pagination");
+
+        // Validate pagination parameters
+        if limit == 0 || limit > 1000 {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range").with("max", 1000),
+            }
+            .into());
+        }
+
+        self.event_repository.list(limit, offset).await
+    }
+
+    /// Counts total number of events
+    pub async fn count_events(&self) -> Result<usize> {
+        info!("Counting total events");
+        self.event_repository.count().await
+    }
+
+    /// Counts events by receiver ID
+    pub async fn count_events_by_receiver(&self, receiver_id: EventReceiverId) -> Result<usize> {
+        info!(receiver_id = %receiver_id, "Counting events by receiver");
+        self.event_repository
+            .count_by_receiver_id(receiver_id)
+            .await
+    }
+
+    /// Counts successful events by receiver ID
+    pub async fn count_successful_events_by_receiver(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<usize> {
+        info!(receiver_id = %receiver_id, "Counting successful events by receiver");
+        self.event_repository
+            .count_successful_by_receiver_id(receiver_id)
+            .await
+    }
+
+    /// Deletes an event
+    pub async fn delete_event(&self, id: EventId) -> Result<()> {
+        info!(event_id = %id, "Deleting event");
+
+        // Check if the event exists
+        let Some(event) = self.event_repository.find_by_id(id).await? else {
+            return Err(DomainError::EventCreationFailed {
+                reason: "Event not found".to_string(),
+            }
+            .into());
+        };
+
+        self.event_repository.delete(id).await?;
+        self.discard_payload(&event).await;
+
+        info!(event_id = %id, "Event deleted successfully");
+
+        Ok(())
+    }
+
+    /// Gets the latest event for a receiver
+    pub async fn get_latest_event_for_receiver(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<Event>> {
+        info!(receiver_id = %receiver_id, "Getting latest event for receiver");
+        self.event_repository
+            .find_latest_by_receiver_id(receiver_id)
+            .await
+    }
+
+    /// Gets the latest successful event for a receiver
+    pub async fn get_latest_successful_event_for_receiver(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<Event>> {
+        info!(receiver_id = %receiver_id, "Getting latest successful event for receiver");
+        self.event_repository
+            .find_latest_successful_by_receiver_id(receiver_id)
+            .await
+    }
+
+    /// Checks if an event exists
+    pub async fn exists(&self, id: EventId) -> Result<bool> {
+        Ok(self.event_repository.find_by_id(id).await?.is_some())
+    }
+
+    /// Gets events within a time range
+    pub async fn find_events_in_time_range(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Event>> {
+        info!(
+            start = %start,
+            end = %end,
+            "Finding events within time range"
+        );
+
+        if start >= end {
+            return Err(DomainError::ValidationError {
+                field: "time_range".to_string(),
+                message: Message::new("validation.time_range_order"),
+            }
+            .into());
+        }
+
+        self.event_repository.find_by_time_range(start, end).await
+    }
+
+    /// Counts events within a time range
+    pub async fn count_events_in_time_range(
+        &self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<usize> {
+        if start >= end {
+            return Err(DomainError::ValidationError {
+                field: "time_range".to_string(),
+                message: Message::new("validation.time_range_order"),
+            }
+            .into());
+        }
+
+        self.event_repository.count_by_time_range(start, end).await
+    }
+
+    /// Gets events statistics for a receiver
+    pub async fn get_receiver_statistics(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<ReceiverStatistics> {
+        info!(receiver_id = %receiver_id, "Getting receiver statistics");
+
+        // Verify receiver exists
+        if self
+            .receiver_repository
+            .find_by_id(receiver_id)
+            .await?
+            .is_none()
+        {
+            return Err(DomainError::ReceiverNotFound.into());
+        }
+
+        let total_events = self.count_events_by_receiver(receiver_id).await?;
+        let successful_events = self
+            .count_successful_events_by_receiver(receiver_id)
+            .await?;
+        let failed_events = total_events - successful_events;
+        let latest_event = self.get_latest_event_for_receiver(receiver_id).await?;
+        let latest_successful_event = self
+            .get_latest_successful_event_for_receiver(receiver_id)
+            .await?;
+
+        let success_rate = if total_events > 0 {
+            (successful_events as f64 / total_events as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        // Stored events are already counted; add what sampling dropped
+        let sampled_out_events = match &self.sampling_counters {
+            Some(counters) => counters.sampling_counts(receiver_id).await?.sampled_out() as usize,
+            None => 0,
+        };
+
+        Ok(ReceiverStatistics {
+            receiver_id,
+            total_events,
+            successful_events,
+            failed_events,
+            success_rate,
+            seen_events: total_events + sampled_out_events,
+            sampled_out_events,
+            latest_event_id: latest_event.map(|e| e.id()),
+            latest_successful_event_id: latest_successful_event.map(|e| e.id()),
+        })
+    }
+}
+
+/// Statistics for an event receiver
+#[derive(Debug, Clone)]
+pub struct ReceiverStatistics {
+    pub receiver_id: EventReceiverId,
+    pub total_events: usize,
+    pub successful_events: usize,
+    pub failed_events: usize,
+    pub success_rate: f64, // Percentage
+    /// Events submitted to the receiver, including sampled-out ones
+    pub seen_events: usize,
+    /// Events dropped by the receiver's sample rate
+    pub sampled_out_events: usize,
+    pub latest_event_id: Option<EventId>,
+    pub latest_successful_event_id: Option<EventId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event_receiver::EventReceiver;
+    use crate::domain::entities::ingestion_meta::{IngestionSource, PrincipalType};
+    use crate::domain::entities::receiver_activity::{
+        distinct_principals_since, principal_window_start, ReceiverActivity, ReceiverActivityUpdate,
+    };
+    use crate::domain::repositories::event_repo::EventRepository;
+    use crate::domain::repositories::event_sampling_repo::SamplingCounts;
+    use crate::domain::repositories::receiver_activity_repo::ReceiverActivityRepository;
+    use crate::fixtures::ReceiverFixture;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    // Mock repositories for testing
+    struct MockEventRepository {
+        events: Arc<Mutex<HashMap<EventId, Event>>>,
+    }
+
+    impl MockEventRepository {
+        fn new() -> Self {
+            Self {
+                events: Arc::new(Mutex::new(HashMap::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventRepository for MockEventRepository {
+        async fn save(&self, event: &Event) -> Result<()> {
+            let mut events = self.events.lock().unwrap();
+            events.insert(event.id(), event.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: EventId) -> Result<Option<Event>> {
+            let events = self.events.lock().unwrap();
+            Ok(events.get(&id).cloned())
+        }
+
+        async fn find_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_success(&self, _success: bool) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_name(&self, _name: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_platform_id(&self, _platform_id: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_package(&self, _package: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn count(&self) -> Result<usize> {
+            let events = self.events.lock().unwrap();
+            Ok(events.len())
+        }
+
+        async fn count_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .values()
+                .filter(|e| e.event_receiver_id() == receiver_id)
+                .count())
+        }
+
+        async fn count_successful_by_receiver_id(
+            &self,
+            receiver_id: EventReceiverId,
+        ) -> Result<usize> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .values()
+                .filter(|e| e.event_receiver_id() == receiver_id && e.success())
+                .count())
+        }
+
+        async fn delete(&self, id: EventId) -> Result<()> {
+            let mut events = self.events.lock().unwrap();
+            events.remove(&id);
+            Ok(())
+        }
+
+        async fn find_latest_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_latest_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_by_time_range(
+            &self,
+            _start: DateTime<Utc>,
+            _end: DateTime<Utc>,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_criteria(
+            &self,
+            _criteria: crate::domain::repositories::event_repo::FindEventCriteria,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner(
+            &self,
+            _owner_id: crate::domain::value_objects::UserId,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner_paginated(
+            &self,
+            _owner_id: crate::domain::value_objects::UserId,
+            _limit: usize,
+            _offset: usize,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn is_owner(
+            &self,
+            _event_id: EventId,
+            _user_id: crate::domain::value_objects::UserId,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn get_resource_version(&self, _event_id: EventId) -> Result<Option<i64>> {
+            Ok(Some(1))
+        }
+    }
+
+    struct MockEventReceiverRepository {
+        receivers: Arc<Mutex<HashMap<EventReceiverId, EventReceiver>>>,
+    }
+
+    impl MockEventReceiverRepository {
+        fn new() -> Self {
+            Self {
+                receivers: Arc::new(Mutex::new(HashMap::new())),
+            }
+        }
+
+        fn insert(&self, receiver: EventReceiver) {
+            let mut receivers = self.receivers.lock().unwrap();
+            receivers.insert(receiver.id(), receiver);
+        }
+    }
+
+    #[async_trait]
+    impl EventReceiverRepository for MockEventReceiverRepository {
+        async fn save(&self, event_receiver: &EventReceiver) -> Result<()> {
+            self.insert(event_receiver.clone());
+            Ok(())
+        }
+
+        async fn save_if_absent(
+            &self,
+            event_receiver: &EventReceiver,
+        ) -> Result<(EventReceiver, bool)> {
+            let mut receivers = self.receivers.lock().unwrap();
+            if let Some(existing) = receivers
+                .values()
+                .find(|r| r.fingerprint() == event_receiver.fingerprint())
+            {
+                return Ok((existing.clone(), false));
+            }
+            receivers.insert(event_receiver.id(), event_receiver.clone());
+            Ok((event_receiver.clone(), true))
+        }
+
+        async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
+            let receivers = self.receivers.lock().unwrap();
+            Ok(receivers.get(&id).cloned())
+        }
+
+        async fn find_by_name(&self, _name: &str) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_type(&self, _receiver_type: &str) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_type_and_version(
+            &self,
+            _receiver_type: &str,
+            _version: &str,
+        ) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>> {
+            let receivers = self.receivers.lock().unwrap();
+            Ok(receivers
+                .values()
+                .find(|r| r.fingerprint() == fingerprint)
+                .cloned())
+        }
+
+        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn update(&self, _event_receiver: &EventReceiver) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: EventReceiverId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn exists_by_name_and_type(&self, _name: &str, _receiver_type: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn find_by_criteria(
+            &self,
+            _criteria: crate::domain::repositories::event_receiver_repo::FindEventReceiverCriteria,
+        ) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner(
+            &self,
+            _owner_id: crate::domain::value_objects::UserId,
+        ) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner_paginated(
+            &self,
+            _owner_id: crate::domain::value_objects::UserId,
+            _limit: usize,
+            _offset: usize,
+        ) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn is_owner(
+            &self,
+            _receiver_id: EventReceiverId,
+            _user_id: crate::domain::value_objects::UserId,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn get_resource_version(&self, _receiver_id: EventReceiverId) -> Result<Option<i64>> {
+            Ok(Some(1))
+        }
+    }
+
+    #[derive(Default)]
+    struct MockIngestionMetaRepository {
+        metas: Mutex<Vec<IngestionMeta>>,
+    }
+
+    #[async_trait]
+    impl EventIngestionMetaRepository for MockIngestionMetaRepository {
+        async fn save_ingestion_meta(&self, meta: &IngestionMeta) -> Result<()> {
+            self.metas.lock().unwrap().push(meta.clone());
+            Ok(())
+        }
+
+        async fn find_ingestion_meta(&self, event_id: EventId) -> Result<Option<IngestionMeta>> {
+            let metas = self.metas.lock().unwrap();
+            Ok(metas.iter().find(|m| m.event_id() == event_id).cloned())
+        }
+
+        async fn find_ingestion_meta_by_filter(
+            &self,
+            filter: &IngestionMetaFilter,
+        ) -> Result<Vec<IngestionMeta>> {
+            let metas = self.metas.lock().unwrap();
+            Ok(metas
+                .iter()
+                .rev()
+                .filter(|m| filter.matches(m))
+                .skip(filter.offset.unwrap_or(0))
+                .take(filter.limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect())
+        }
+
+        async fn count_ingestion_meta_by_filter(
+            &self,
+            filter: &IngestionMetaFilter,
+        ) -> Result<usize> {
+            let metas = self.metas.lock().unwrap();
+            Ok(metas.iter().filter(|m| filter.matches(m)).count())
+        }
+
+        // Every recorded event is taken to belong to the receiver
+        async fn find_pii_report(
+            &self,
+            _receiver_id: EventReceiverId,
+            since: DateTime<Utc>,
+            limit: usize,
+        ) -> Result<PiiReport> {
+            let metas = self.metas.lock().unwrap();
+            Ok(PiiReport::aggregate(
+                metas
+                    .iter()
+                    .filter(|m| m.recorded_at() >= since)
+                    .map(|m| m.pii_findings()),
+                limit,
+            ))
+        }
+    }
+
+    #[derive(Default)]
+    struct MockSamplingCounters {
+        counts: Mutex<HashMap<EventReceiverId, SamplingCounts>>,
+    }
+
+    #[async_trait]
+    impl EventSamplingCounterRepository for MockSamplingCounters {
+        async fn record_sampling(
+            &self,
+            receiver_id: EventReceiverId,
+            _bucket_start: DateTime<Utc>,
+            stored: bool,
+        ) -> Result<()> {
+            let mut counts = self.counts.lock().unwrap();
+            let entry = counts.entry(receiver_id).or_default();
+            entry.total_seen += 1;
+            entry.total_stored += u64::from(stored);
+            Ok(())
+        }
+
+        async fn sampling_counts(&self, receiver_id: EventReceiverId) -> Result<SamplingCounts> {
+            let counts = self.counts.lock().unwrap();
+            Ok(counts.get(&receiver_id).copied().unwrap_or_default())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockActivityRepository {
+        updates: Mutex<HashMap<EventReceiverId, ReceiverActivityUpdate>>,
+    }
+
+    #[async_trait]
+    impl ReceiverActivityRepository for MockActivityRepository {
+        async fn record_activity(&self, updates: &[ReceiverActivityUpdate]) -> Result<()> {
+            let mut stored = self.updates.lock().unwrap();
+            for update in updates {
+                match stored.get_mut(&update.receiver_id) {
+                    Some(existing) => existing.merge(update.clone()),
+                    None => {
+                        stored.insert(update.receiver_id, update.clone());
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        async fn find_activity(
+            &self,
+            receiver_ids: &[EventReceiverId],
+            window_start: chrono::NaiveDate,
+        ) -> Result<Vec<ReceiverActivity>> {
+            let stored = self.updates.lock().unwrap();
+            Ok(receiver_ids
+                .iter()
+                .filter_map(|id| stored.get(id))
+                .map(|update| ReceiverActivity {
+                    receiver_id: update.receiver_id,
+                    last_event_at: update.last_event_at,
+                    last_principal: update.last_principal.clone(),
+                    distinct_principals: distinct_principals_since(
+                        &update.daily_principals,
+                        window_start,
+                    ),
+                })
+                .collect())
+        }
+
+        async fn most_recently_active(&self, limit: usize) -> Result<Vec<EventReceiverId>> {
+            let stored = self.updates.lock().unwrap();
+            let mut updates: Vec<_> = stored.values().collect();
+            updates.sort_by_key(|update| std::cmp::Reverse(update.last_event_at));
+            Ok(updates
+                .into_iter()
+                .take(limit)
+                .map(|update| update.receiver_id)
+                .collect())
+        }
+    }
+
+    fn create_test_params(receiver_id: EventReceiverId) -> CreateEventParams {
+        CreateEventParams {
+            name: "test-event".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2023.11.16".to_string(),
+            platform_id: "linux".to_string(),
+            package: "docker".to_string(),
+            description: "Test event".to_string(),
+            payload: json!({"message": "Hello, world!"}),
+            success: true,
+            receiver_id,
+            owner_id: crate::domain::value_objects::UserId::new(),
+        }
+    }
+
+    fn create_test_receiver() -> EventReceiver {
+        ReceiverFixture::new()
+            .schema(json!({
+                "type": "object",
+                "properties": {
+                    "message": {"type": "string"}
+                }
+            }))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_create_event() {
+        let event_repo = Arc::new(MockEventRepository::new());
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+
+        let receiver = create_test_receiver();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+
+        let handler = EventHandler::new(event_repo, receiver_repo);
+
+        let payload = json!({"message": "Hello, world!"});
+        let result = handler
+            .create_event(CreateEventParams {
+                name: "test-event".to_string(),
+                version: "1.0.0".to_string(),
+                release: "2023.11.16".to_string(),
+                platform_id: "linux".to_string(),
+                package: "docker".to_string(),
+                description: "Test event".to_string(),
+                payload,
+                success: true,
+                receiver_id,
+                owner_id: crate::domain::value_objects::UserId::new(),
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    fn provisioning_spec() -> ReceiverSpec {
+        ReceiverSpec {
+            name: "ci-builds".to_string(),
+            receiver_type: "webhook".to_string(),
+            version: "1.0.0".to_string(),
+            schema: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provision_receiver_creates_then_reuses() {
+        let event_repo = Arc::new(MockEventRepository::new());
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let handler = EventHandler::new(event_repo.clone(), receiver_repo.clone())
+            .with_receiver_provisioning(ReceiverProvisioningPolicy::AllowWithPermission);
+        let owner_id = UserId::new();
+
+        let first = handler
+            .provision_receiver(&provisioning_spec(), owner_id, true)
+            .await
+            .unwrap();
+        assert!(first.created);
+        let receiver = receiver_repo
+            .find_by_id(first.receiver_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receiver.owner_id(), owner_id);
+
+        let event_id = handler
+            .create_event(create_test_params(first.receiver_id))
+            .await
+            .unwrap()
+            .event_id()
+            .unwrap();
+        assert!(event_repo.find_by_id(event_id).await.unwrap().is_some());
+
+        let second = handler
+            .provision_receiver(&provisioning_spec(), UserId::new(), true)
+            .await
+            .unwrap();
+        assert!(!second.created);
+        assert_eq!(second.receiver_id, first.receiver_id);
+        assert_eq!(receiver_repo.receivers.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_provision_receiver_respects_policy() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let handler =
+            EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo.clone());
+
+        let result = handler
+            .provision_receiver(&provisioning_spec(), UserId::new(), true)
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::Domain(
+                DomainError::ReceiverProvisioningDisabled { .. }
+            ))
+        ));
+
+        let handler =
+            handler.with_receiver_provisioning(ReceiverProvisioningPolicy::AllowWithPermission);
+        let result = handler
+            .provision_receiver(&provisioning_spec(), UserId::new(), false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(crate::error::Error::Domain(
+                DomainError::ReceiverProvisioningDisabled { .. }
+            ))
+        ));
+        assert!(receiver_repo.receivers.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_provisioning_creates_one_receiver() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let handler =
+            EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo.clone())
+                .with_receiver_provisioning(ReceiverProvisioningPolicy::AllowAll);
+
+        let tasks: Vec<_> = (0..16)
+            .map(|_| {
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    handler
+                        .provision_receiver(&provisioning_spec(), UserId::new(), false)
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+
+        let mut results = Vec::new();
+        for task in tasks {
+            results.push(task.await.unwrap());
+        }
+
+        assert_eq!(results.iter().filter(|r| r.created).count(), 1);
+        assert!(results
+            .iter()
+            .all(|r| r.receiver_id == results[0].receiver_id));
+        assert_eq!(receiver_repo.receivers.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_event_with_nonexistent_receiver() {
+        let event_repo = Arc::new(MockEventRepository::new());
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let handler = EventHandler::new(event_repo, receiver_repo);
+
+        let _payload = json!({"message": "Hello, world!"});
+        let _receiver_id = EventReceiverId::new(); // Non-existent receiver
+
+        let payload = json!({"message": "Non-existent receiver"});
+        let result = handler
+            .create_event(CreateEventParams {
+                name: "test-event".to_string(),
+                version: "1.0.0".to_string(),
+                release: "2023.11.16".to_string(),
+                platform_id: "linux".to_string(),
+                package: "docker".to_string(),
+                description: "Test event".to_string(),
+                payload,
+                success: true,
+                owner_id: crate::domain::value_objects::UserId::new(),
+                receiver_id: EventReceiverId::new(),
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_pagination_limits() {
+        let event_repo = Arc::new(MockEventRepository::new());
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let handler = EventHandler::new(event_repo, receiver_repo);
+
+        // Test invalid limits
+        let result1 = handler.list_events(0, 0).await;
+        assert!(result1.is_err());
+
+        let result2 = handler.list_events(1001, 0).await;
+        assert!(result2.is_err());
+
+        // Test valid limits
+        let result3 = handler.list_events(10, 0).await;
+        assert!(result3.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_time_range_validation() {
+        let event_repo = Arc::new(MockEventRepository::new());
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let handler = EventHandler::new(event_repo, receiver_repo);
+
+        let end_time = Utc::now();
+        let start_time = end_time + chrono::Duration::hours(1); // Start after end
+
+        let result = handler
+            .find_events_in_time_range(start_time, end_time)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_event_records_ingestion_source() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let receiver = create_test_receiver();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+
+        let meta_repo = Arc::new(MockIngestionMetaRepository::default());
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo)
+            .with_ingestion_meta(meta_repo.clone());
+
+        for source in IngestionSource::ALL {
+            let context = IngestionContext::new(PrincipalType::User, "user-1", source)
+                .with_client_ip(Some("192.0.2.10".to_string()))
+                .with_user_agent(Some("xzepr-cli/1.0".to_string()));
+            let event_id = handler
+                .create_event_with_context(create_test_params(receiver_id), context)
+                .await
+                .unwrap()
+                .event_id()
+                .unwrap();
+
+            let meta = handler.get_ingestion_meta(event_id).await.unwrap().unwrap();
+            assert_eq!(meta.source(), source);
+            assert_eq!(meta.principal_type(), PrincipalType::User);
+            assert_eq!(meta.client_ip(), Some("192.0.2.10"));
+            assert_eq!(meta.user_agent(), Some("xzepr-cli/1.0"));
+        }
+
+        // Events created without a context record nothing
+        let event_id = handler
+            .create_event(create_test_params(receiver_id))
+            .await
+            .unwrap()
+            .event_id()
+            .unwrap();
+        assert!(handler
+            .get_ingestion_meta(event_id)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pii_findings_are_recorded_and_redacted_after_validation() {
+        use crate::domain::entities::pii_detection::{PiiDetectionMode, PiiDetector, PiiFinding};
+
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        // The pattern only accepts the address as sent, not its mask
+        let receiver = EventReceiver::new(
+            "Contact Receiver".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "Contacts".to_string(),
+            json!({
+                "type": "object",
+                "properties": {"contact": {"type": "string", "pattern": "@example\\.com$"}}
+            }),
+            UserId::new(),
+        )
+        .unwrap();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+
+        for (mode, stored) in [
+            (PiiDetectionMode::Off, "jane.doe@example.com"),
+            (PiiDetectionMode::Warn, "jane.doe@example.com"),
+            (PiiDetectionMode::Redact, "[REDACTED]"),
+        ] {
+            let meta_repo = Arc::new(MockIngestionMetaRepository::default());
+            let handler =
+                EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo.clone())
+                    .with_ingestion_meta(meta_repo.clone())
+                    .with_pii_scanner(PiiScanner::new(mode));
+            let mut params = create_test_params(receiver_id);
+            params.payload = json!({"contact": "jane.doe@example.com"});
+
+            let event_id = handler
+                .create_event_with_context(
+                    params,
+                    IngestionContext::new(PrincipalType::User, "user-1", IngestionSource::Rest),
+                )
+                .await
+                .unwrap()
+                .event_id()
+                .unwrap();
+
+            let event = handler.get_event(event_id).await.unwrap().unwrap();
+            assert_eq!(event.payload()["contact"], stored, "{:?}", mode);
+            let meta = handler.get_ingestion_meta(event_id).await.unwrap().unwrap();
+            let expected = match mode {
+                PiiDetectionMode::Off => vec![],
+                _ => vec![PiiFinding {
+                    detector: PiiDetector::Email,
+                    path: "$.contact".to_string(),
+                }],
+            };
+            assert_eq!(meta.pii_findings(), expected.as_slice(), "{:?}", mode);
+
+            let report = handler
+                .pii_report(receiver_id, Utc::now() - chrono::Duration::days(1))
+                .await
+                .unwrap();
+            assert_eq!(report.events_with_findings, expected.len() as u64);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transform_runs_before_validation_and_redaction() {
+        use crate::domain::entities::pii_detection::PiiDetectionMode;
+
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let mut receiver = create_test_receiver();
+        receiver
+            .set_transform(Some(
+                serde_json::from_value(json!([
+                    {"op": "rename", "from": "msg", "to": "message"},
+                    {"op": "copy", "from": "author.email", "to": "contact"},
+                    {"op": "parse_number", "path": "attempt", "on_failure": "skip"},
+                ]))
+                .unwrap(),
+            ))
+            .unwrap();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo)
+            .with_pii_scanner(PiiScanner::new(PiiDetectionMode::Redact));
+
+        let mut params = create_test_params(receiver_id);
+        params.payload = json!({
+            "msg": "Hello, world!",
+            "author": {"email": "jane.doe@example.com"},
+            "attempt": "2"
+        });
+        let event_id = handler
+            .create_event(params)
+            .await
+            .unwrap()
+            .event_id()
+            .unwrap();
+
+        // The copied address is found and masked like the original
+        let event = handler.get_event(event_id).await.unwrap().unwrap();
+        assert_eq!(
+            event.payload(),
+            &json!({
+                "message": "Hello, world!",
+                "author": {"email": "[REDACTED]"},
+                "contact": "[REDACTED]",
+                "attempt": 2
+            })
+        );
+
+        let mut params = create_test_params(receiver_id);
+        params.payload = json!({"message": "Hello, world!"});
+        let err = handler.create_event(params).await.unwrap_err();
+        match err {
+            Error::Domain(DomainError::ValidationError { field, .. }) => {
+                assert_eq!(field, "transform[0]")
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_previews_the_transform() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let mut receiver = create_test_receiver();
+        receiver
+            .set_transform(Some(
+                serde_json::from_value(json!([{"op": "rename", "from": "msg", "to": "message"}]))
+                    .unwrap(),
+            ))
+            .unwrap();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+        let plain = create_test_receiver();
+        let plain_id = plain.id();
+        receiver_repo.insert(plain);
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo);
+
+        let payload = json!({"msg": "hi"});
+        let preview = handler
+            .preview_transform(receiver_id, None, &payload)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(preview.before, payload);
+        assert_eq!(preview.after, Some(json!({"message": "hi"})));
+        assert_eq!(
+            handler
+                .preview_transform(plain_id, None, &payload)
+                .await
+                .unwrap(),
+            None
+        );
+
+        let mut params = create_test_params(receiver_id);
+        params.payload = json!({"message": "hi"});
+        let outcome = handler.dry_run_event(params, None).await.unwrap();
+        assert!(matches!(outcome, DryRunOutcome::Rejected(errors) if errors.len() == 1));
+    }
+
+    #[tokio::test]
+    async fn test_create_event_updates_receiver_last_seen() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let receiver = create_test_receiver();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+
+        let activity_repo = Arc::new(MockActivityRepository::default());
+        let tracker = ReceiverActivityTracker::new(activity_repo.clone());
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo)
+            .with_activity_tracker(tracker.clone());
+
+        let before = Utc::now();
+        for principal in ["key-1", "key-2", "key-1"] {
+            let context =
+                IngestionContext::new(PrincipalType::ApiKey, principal, IngestionSource::Rest);
+            handler
+                .create_event_with_context(create_test_params(receiver_id), context)
+                .await
+                .unwrap();
+        }
+
+        // Nothing is written until the tracker flushes
+        let window_start = principal_window_start(Utc::now());
+        assert!(activity_repo
+            .find_activity(&[receiver_id], window_start)
+            .await
+            .unwrap()
+            .is_empty());
+
+        assert_eq!(tracker.flush().await.unwrap(), 1);
+        let activity = activity_repo
+            .find_activity(&[receiver_id], window_start)
+            .await
+            .unwrap();
+        assert_eq!(activity.len(), 1);
+        assert!(activity[0].last_event_at >= before);
+        assert_eq!(activity[0].last_principal, "api_key:key-1");
+        assert_eq!(activity[0].distinct_principals, 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_events_by_api_key() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let receiver = create_test_receiver();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo)
+            .with_ingestion_meta(Arc::new(MockIngestionMetaRepository::default()));
+
+        let mut key_events = Vec::new();
+        for principal_id in ["key-1", "key-2", "key-1"] {
+            let context =
+                IngestionContext::new(PrincipalType::ApiKey, principal_id, IngestionSource::Rest);
+            let event_id = handler
+                .create_event_with_context(create_test_params(receiver_id), context)
+                .await
+                .unwrap()
+                .event_id()
+                .unwrap();
+            if principal_id == "key-1" {
+                key_events.push(event_id);
+            }
+        }
+        handler
+            .create_event_with_context(
+                create_test_params(receiver_id),
+                IngestionContext::new(PrincipalType::User, "key-1", IngestionSource::Rest),
+            )
+            .await
+            .unwrap();
+
+        let filter = IngestionMetaFilter::new()
+            .with_principal_type(PrincipalType::ApiKey)
+            .with_principal_id("key-1".to_string());
+        let events = handler.list_events_by_ingestion(filter).await.unwrap();
+
+        assert_eq!(events.len(), 2);
+        for (event, meta) in &events {
+            assert!(key_events.contains(&event.id()));
+            assert_eq!(meta.principal_id(), "key-1");
+            assert_eq!(meta.principal_type(), PrincipalType::ApiKey);
+        }
+
+        let invalid = IngestionMetaFilter::new().with_limit(0);
+        assert!(handler.list_events_by_ingestion(invalid).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sampled_out_events_are_not_stored() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let mut receiver = create_test_receiver();
+        receiver.set_sample_rate(Some(0.0)).unwrap();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+
+        let event_repo = Arc::new(MockEventRepository::new());
+        let handler = EventHandler::new(event_repo.clone(), receiver_repo)
+            .with_sampling_counters(Arc::new(MockSamplingCounters::default()));
+
+        let outcome = handler
+            .create_event(create_test_params(receiver_id))
+            .await
+            .unwrap();
 
-        async fn find_by_time_range(
-            &self,
-            _start: DateTime<Utc>,
-            _end: DateTime<Utc>,
-        ) -> Result<Vec<Event>> {
-            Ok(vec![])
-        }
+        assert!(outcome.is_sampled_out());
+        assert_eq!(event_repo.count().await.unwrap(), 0);
+    }
 
-        async fn find_by_criteria(
-            &self,
-            _criteria: crate::domain::repositories::event_repo::FindEventCriteria,
-        ) -> Result<Vec<Event>> {
-            Ok(vec![])
-        }
+    #[tokio::test]
+    async fn test_dry_run_stores_and_counts_nothing() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let receiver = create_test_receiver();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+        let mut sampled = create_test_receiver();
+        sampled.set_sample_rate(Some(0.0)).unwrap();
+        let sampled_id = sampled.id();
+        receiver_repo.insert(sampled);
 
-        async fn find_by_owner(
-            &self,
-            _owner_id: crate::domain::value_objects::UserId,
-        ) -> Result<Vec<Event>> {
-            Ok(vec![])
-        }
+        let event_repo = Arc::new(MockEventRepository::new());
+        let counters = Arc::new(MockSamplingCounters::default());
+        let handler = EventHandler::new(event_repo.clone(), receiver_repo)
+            .with_sampling_counters(counters.clone());
 
-        async fn find_by_owner_paginated(
-            &self,
-            _owner_id: crate::domain::value_objects::UserId,
-            _limit: usize,
-            _offset: usize,
-        ) -> Result<Vec<Event>> {
-            Ok(vec![])
-        }
+        let outcome = handler
+            .dry_run_event(create_test_params(receiver_id), None)
+            .await
+            .unwrap();
+        assert!(outcome.would_create());
 
-        async fn is_owner(
-            &self,
-            _event_id: EventId,
-            _user_id: crate::domain::value_objects::UserId,
-        ) -> Result<bool> {
-            Ok(false)
+        let outcome = handler
+            .dry_run_event(create_test_params(sampled_id), None)
+            .await
+            .unwrap();
+        assert!(matches!(outcome, DryRunOutcome::SampledOut));
+
+        let mut params = create_test_params(receiver_id);
+        params.version = "not a version".to_string();
+        let outcome = handler.dry_run_event(params, None).await.unwrap();
+        assert!(matches!(outcome, DryRunOutcome::Rejected(errors) if errors.len() == 1));
+
+        assert_eq!(event_repo.count().await.unwrap(), 0);
+        for id in [receiver_id, sampled_id] {
+            assert_eq!(
+                counters.sampling_counts(id).await.unwrap(),
+                SamplingCounts::default()
+            );
         }
+    }
 
-        async fn get_resource_version(&self, _event_id: EventId) -> Result<Option<i64>> {
-            Ok(Some(1))
+    #[tokio::test]
+    async fn test_reserved_event_names_are_system_only() {
+        use crate::application::handlers::system_events::{
+            SystemEventFactory, GROUP_CREATED_EVENT,
+        };
+
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let receiver = create_test_receiver();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+        let event_repo = Arc::new(MockEventRepository::new());
+        let handler = EventHandler::new(event_repo.clone(), receiver_repo);
+
+        for name in [GROUP_CREATED_EVENT, "XZEPR.group.created"] {
+            let mut params = create_test_params(receiver_id);
+            params.name = name.to_string();
+            match handler.create_event(params).await {
+                Err(e @ Error::Domain(DomainError::ReservedEventName { .. })) => {
+                    assert_eq!(
+                        e.status_code(),
+                        axum::http::StatusCode::UNPROCESSABLE_ENTITY
+                    );
+                }
+                other => panic!("expected ReservedEventName for {}, got {:?}", name, other),
+            }
         }
+        assert_eq!(event_repo.count().await.unwrap(), 0);
+
+        let factory = SystemEventFactory::new().with_store(event_repo.clone());
+        let event = factory
+            .build(
+                GROUP_CREATED_EVENT,
+                "created".to_string(),
+                json!({}),
+                receiver_id,
+                UserId::new(),
+            )
+            .unwrap();
+        factory.record(&event).await;
+        assert_eq!(event_repo.count().await.unwrap(), 1);
     }
 
-    struct MockEventReceiverRepository {
-        receivers: Arc<Mutex<HashMap<EventReceiverId, EventReceiver>>>,
-    }
+    #[tokio::test]
+    async fn test_allowed_event_names_glob() {
+        use crate::domain::entities::event_name_constraint::AllowedEventNames;
 
-    impl MockEventReceiverRepository {
-        fn new() -> Self {
-            Self {
-                receivers: Arc::new(Mutex::new(HashMap::new())),
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let mut receiver = create_test_receiver();
+        receiver
+            .set_allowed_event_names(Some(AllowedEventNames::Glob("build.*".to_string())))
+            .unwrap();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver.clone());
+        let event_repo = Arc::new(MockEventRepository::new());
+        let handler = EventHandler::new(event_repo.clone(), receiver_repo.clone());
+
+        let create = |name: &str| {
+            let mut params = create_test_params(receiver_id);
+            params.name = name.to_string();
+            handler.create_event(params)
+        };
+
+        assert!(create("build.finished").await.is_ok());
+        assert!(create("build.linux.started").await.is_ok());
+        match create("deploy.finished").await {
+            Err(Error::Domain(DomainError::ValidationError { field, message })) => {
+                assert_eq!(field, "name");
+                assert!(message.to_string().contains("build.*"), "{}", message);
             }
+            other => panic!("expected a name validation error, got {:?}", other),
         }
+        assert_eq!(event_repo.count().await.unwrap(), 2);
+
+        // A new constraint replaces the cached one
+        receiver
+            .set_allowed_event_names(Some(AllowedEventNames::Names(vec![
+                "deploy.finished".to_string()
+            ])))
+            .unwrap();
+        receiver_repo.insert(receiver);
+        assert!(create("deploy.finished").await.is_ok());
+        assert!(create("build.finished").await.is_err());
+    }
 
-        fn insert(&self, receiver: EventReceiver) {
-            let mut receivers = self.receivers.lock().unwrap();
-            receivers.insert(receiver.id(), receiver);
-        }
+    #[tokio::test]
+    async fn test_prepare_receiver_compiles_name_constraint_ahead_of_events() {
+        use crate::domain::entities::event_name_constraint::AllowedEventNames;
+
+        let mut receiver = create_test_receiver();
+        receiver
+            .set_allowed_event_names(Some(AllowedEventNames::Glob("build.*".to_string())))
+            .unwrap();
+        let handler = EventHandler::new(
+            Arc::new(MockEventRepository::new()),
+            Arc::new(MockEventReceiverRepository::new()),
+        );
+
+        handler.prepare_receiver(&receiver).await.unwrap();
+        assert!(handler
+            .name_matchers
+            .cache
+            .read()
+            .unwrap()
+            .contains_key(&receiver.id()));
     }
 
+    struct AcceptingPublisher;
+
     #[async_trait]
-    impl EventReceiverRepository for MockEventReceiverRepository {
-        async fn save(&self, _event_receiver: &EventReceiver) -> Result<()> {
+    impl EventPublisher for AcceptingPublisher {
+        async fn publish(&self, _event: &Event) -> Result<()> {
             Ok(())
         }
 
-        async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
-            let receivers = self.receivers.lock().unwrap();
-            Ok(receivers.get(&id).cloned())
-        }
-
-        async fn find_by_name(&self, _name: &str) -> Result<Vec<EventReceiver>> {
-            Ok(vec![])
-        }
-
-        async fn find_by_type(&self, _receiver_type: &str) -> Result<Vec<EventReceiver>> {
-            Ok(vec![])
+        async fn publish_to(&self, _topic: &str, _event: &Event) -> Result<()> {
+            Ok(())
         }
 
-        async fn find_by_type_and_version(
+        async fn publish_message(
             &self,
-            _receiver_type: &str,
-            _version: &str,
-        ) -> Result<Vec<EventReceiver>> {
-            Ok(vec![])
+            _message: &crate::infrastructure::messaging::cloudevents::CloudEventMessage,
+        ) -> Result<()> {
+            Ok(())
         }
+    }
 
-        async fn find_by_fingerprint(&self, _fingerprint: &str) -> Result<Option<EventReceiver>> {
-            Ok(None)
-        }
+    /// Publisher whose stream is down
+    struct DownPublisher;
 
-        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<EventReceiver>> {
-            Ok(vec![])
+    #[async_trait]
+    impl EventPublisher for DownPublisher {
+        async fn publish(&self, _event: &Event) -> Result<()> {
+            Err(Error::Internal {
+                message: "broker down".to_string(),
+            })
         }
 
-        async fn count(&self) -> Result<usize> {
-            Ok(0)
+        async fn publish_to(&self, _topic: &str, event: &Event) -> Result<()> {
+            self.publish(event).await
         }
 
-        async fn update(&self, _event_receiver: &EventReceiver) -> Result<()> {
-            Ok(())
+        async fn publish_message(
+            &self,
+            _message: &crate::infrastructure::messaging::cloudevents::CloudEventMessage,
+        ) -> Result<()> {
+            Err(Error::Internal {
+                message: "broker down".to_string(),
+            })
         }
+    }
 
-        async fn delete(&self, _id: EventReceiverId) -> Result<()> {
-            Ok(())
-        }
+    /// Outbox whose database is unreachable
+    struct UnavailableOutbox;
 
-        async fn exists_by_name_and_type(&self, _name: &str, _receiver_type: &str) -> Result<bool> {
-            Ok(false)
+    #[async_trait]
+    impl EventOutboxRepository for UnavailableOutbox {
+        async fn enqueue(
+            &self,
+            _event_id: EventId,
+            _error: &str,
+            _next_attempt_at: DateTime<Utc>,
+        ) -> Result<()> {
+            Err(Error::Internal {
+                message: "database down".to_string(),
+            })
         }
 
-        async fn find_by_criteria(
+        async fn find_due(
             &self,
-            _criteria: crate::domain::repositories::event_receiver_repo::FindEventReceiverCriteria,
-        ) -> Result<Vec<EventReceiver>> {
-            Ok(vec![])
+            _now: DateTime<Utc>,
+            _limit: usize,
+        ) -> Result<Vec<crate::domain::repositories::event_outbox_repo::OutboxEntry>> {
+            unreachable!("ingestion never reads the outbox")
         }
 
-        async fn find_by_owner(
+        async fn find_entries(
             &self,
-            _owner_id: crate::domain::value_objects::UserId,
-        ) -> Result<Vec<EventReceiver>> {
-            Ok(vec![])
+            _event_ids: &[EventId],
+        ) -> Result<Vec<crate::domain::repositories::event_outbox_repo::OutboxEntry>> {
+            unreachable!("ingestion never reads the outbox")
         }
 
-        async fn find_by_owner_paginated(
-            &self,
-            _owner_id: crate::domain::value_objects::UserId,
-            _limit: usize,
-            _offset: usize,
-        ) -> Result<Vec<EventReceiver>> {
-            Ok(vec![])
+        async fn mark_published(&self, _event_id: EventId) -> Result<()> {
+            unreachable!("ingestion never marks entries published")
         }
 
-        async fn is_owner(
+        async fn record_failure(
             &self,
-            _receiver_id: EventReceiverId,
-            _user_id: crate::domain::value_objects::UserId,
-        ) -> Result<bool> {
-            Ok(false)
+            _event_id: EventId,
+            _error: &str,
+            _next_attempt_at: Option<DateTime<Utc>>,
+        ) -> Result<()> {
+            unreachable!("ingestion never records retries")
         }
 
-        async fn get_resource_version(&self, _receiver_id: EventReceiverId) -> Result<Option<i64>> {
-            Ok(Some(1))
+        async fn backlog(
+            &self,
+        ) -> Result<crate::domain::repositories::event_outbox_repo::OutboxBacklog> {
+            unreachable!("ingestion never counts the backlog")
         }
     }
 
-    fn create_test_receiver() -> EventReceiver {
-        let schema = json!({
-            "type": "object",
-            "properties": {
-                "message": {"type": "string"}
-            }
-        });
+    #[tokio::test]
+    async fn test_best_effort_publish_is_only_deferred_when_queued() {
+        use crate::infrastructure::memory::InMemoryEventOutboxRepository;
 
-        EventReceiver::new(
-            "Test Receiver".to_string(),
-            "webhook".to_string(),
-            "1.0.0".to_string(),
-            "A test receiver".to_string(),
-            schema,
-            crate::domain::value_objects::UserId::new(),
-        )
-        .unwrap()
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let receiver = create_test_receiver();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
+        let event_repo = Arc::new(MockEventRepository::new());
+        let handler = EventHandler::new(event_repo.clone(), receiver_repo)
+            .with_event_publisher(Arc::new(DownPublisher));
+
+        let create = |handler: EventHandler| async move {
+            handler
+                .create_event_with_policy(
+                    create_test_params(receiver_id),
+                    IngestionContext::new(PrincipalType::User, "user", IngestionSource::Rest),
+                    Some(PublishPolicy::BestEffort),
+                )
+                .await
+        };
+
+        // Nothing would retry the publish, so the event is not kept
+        for handler in [
+            handler.clone(),
+            handler.clone().with_outbox(Arc::new(UnavailableOutbox)),
+        ] {
+            let error = create(handler).await.unwrap_err();
+            assert!(matches!(
+                error,
+                Error::Infrastructure(InfrastructureError::PublishUnavailable { .. })
+            ));
+            assert!(event_repo.events.lock().unwrap().is_empty());
+        }
+
+        let outbox = Arc::new(InMemoryEventOutboxRepository::default());
+        let outcome = create(handler.with_outbox(outbox.clone())).await.unwrap();
+        let CreateEventOutcome::Stored {
+            event_id,
+            publish_status,
+        } = outcome
+        else {
+            panic!("event was not stored");
+        };
+        assert_eq!(publish_status, Some(PublishStatus::Deferred));
+        assert!(event_repo.events.lock().unwrap().contains_key(&event_id));
+        assert_eq!(outbox.find_entries(&[event_id]).await.unwrap().len(), 1);
+    }
+
+    /// Returns the value of the exposition line starting with `prefix`
+    fn sample(exposition: &str, prefix: &str) -> f64 {
+        exposition
+            .lines()
+            .find(|line| line.starts_with(prefix))
+            .and_then(|line| line.rsplit(' ').next())
+            .unwrap_or_else(|| panic!("no sample {}", prefix))
+            .parse()
+            .unwrap()
     }
 
     #[tokio::test]
-    async fn test_create_event() {
-        let event_repo = Arc::new(MockEventRepository::new());
+    async fn test_ingestion_stages_are_timed_once_and_add_up() {
         let receiver_repo = Arc::new(MockEventReceiverRepository::new());
-
         let receiver = create_test_receiver();
         let receiver_id = receiver.id();
         receiver_repo.insert(receiver);
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo)
+            .with_metrics(metrics.clone())
+            .with_event_publisher(Arc::new(AcceptingPublisher));
+
+        let context = IngestionContext::new(PrincipalType::ApiKey, "key", IngestionSource::Rest);
+        handler
+            .create_event_with_context(create_test_params(receiver_id), context)
+            .await
+            .unwrap();
+
+        let exposition = metrics.gather().unwrap();
+        let mut summed = 0.0;
+        for stage in [
+            IngestionStage::Validation,
+            IngestionStage::SchemaCheck,
+            IngestionStage::QuotaCheck,
+            IngestionStage::Persist,
+            IngestionStage::Publish,
+        ] {
+            let labels = format!("{{channel=\"rest\",stage=\"{}\"}}", stage.as_str());
+            let count = sample(
+                &exposition,
+                &format!(
+                    "xzepr_event_ingestion_stage_duration_seconds_count{}",
+                    labels
+                ),
+            );
+            assert_eq!(count, 1.0, "stage {}", stage.as_str());
+            summed += sample(
+                &exposition,
+                &format!("xzepr_event_ingestion_stage_duration_seconds_sum{}", labels),
+            );
+        }
+        // Deduplication is timed per batch, never per event
+        assert!(!exposition.contains("stage=\"dedup\""));
 
-        let handler = EventHandler::new(event_repo, receiver_repo);
-
-        let payload = json!({"message": "Hello, world!"});
-        let result = handler
-            .create_event(CreateEventParams {
-                name: "test-event".to_string(),
-                version: "1.0.0".to_string(),
-                release: "2023.11.16".to_string(),
-                platform_id: "linux".to_string(),
-                package: "docker".to_string(),
-                description: "Test event".to_string(),
-                payload,
-                success: true,
-                receiver_id,
-                owner_id: crate::domain::value_objects::UserId::new(),
-            })
-            .await;
-
-        assert!(result.is_ok());
+        let total = sample(
+            &exposition,
+            "xzepr_event_ingestion_duration_seconds_sum{channel=\"rest\",outcome=\"stored\"}",
+        );
+        assert!(summed <= total + 1e-9, "{} > {}", summed, total);
+        assert!(total - summed < 0.005, "{} unaccounted", total - summed);
     }
 
     #[tokio::test]
-    async fn test_create_event_with_nonexistent_receiver() {
-        let event_repo = Arc::new(MockEventRepository::new());
+    async fn test_stored_events_are_announced() {
         let receiver_repo = Arc::new(MockEventReceiverRepository::new());
-        let handler = EventHandler::new(event_repo, receiver_repo);
+        let mut receiver = create_test_receiver();
+        receiver.set_sample_rate(Some(0.0)).unwrap();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
 
-        let _payload = json!({"message": "Hello, world!"});
-        let _receiver_id = EventReceiverId::new(); // Non-existent receiver
+        let notifier = EventNotifier::new();
+        let mut notices = notifier.subscribe();
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo)
+            .with_notifier(notifier)
+            .with_event_bus(bus);
+
+        // Sampled-out events are never stored, so nobody is told about them
+        let outcome = handler
+            .create_event(create_test_params(receiver_id))
+            .await
+            .unwrap();
+        assert!(outcome.is_sampled_out());
 
-        let payload = json!({"message": "Non-existent receiver"});
-        let result = handler
-            .create_event(CreateEventParams {
-                name: "test-event".to_string(),
-                version: "1.0.0".to_string(),
-                release: "2023.11.16".to_string(),
-                platform_id: "linux".to_string(),
-                package: "docker".to_string(),
-                description: "Test event".to_string(),
-                payload,
-                success: true,
-                owner_id: crate::domain::value_objects::UserId::new(),
-                receiver_id: EventReceiverId::new(),
-            })
-            .await;
+        let mut params = create_test_params(receiver_id);
+        params.success = false;
+        let outcome = handler.create_event(params).await.unwrap();
 
-        assert!(result.is_err());
+        let notice = notices.try_recv().unwrap();
+        assert_eq!(Some(notice.event_id), outcome.event_id());
+        assert_eq!(notice.receiver_id, receiver_id);
+        assert!(notices.try_recv().is_err());
+
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::EventCreated { event } => {
+                assert_eq!(Some(event.id()), outcome.event_id())
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+        assert!(notifications.try_recv().is_err());
     }
 
     #[tokio::test]
-    async fn test_validate_pagination_limits() {
-        let event_repo = Arc::new(MockEventRepository::new());
+    async fn test_failures_are_always_stored() {
         let receiver_repo = Arc::new(MockEventReceiverRepository::new());
-        let handler = EventHandler::new(event_repo, receiver_repo);
-
-        // Test invalid limits
-        let result1 = handler.list_events(0, 0).await;
-        assert!(result1.is_err());
+        let mut receiver = create_test_receiver();
+        receiver.set_sample_rate(Some(0.0)).unwrap();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
 
-        let result2 = handler.list_events(1001, 0).await;
-        assert!(result2.is_err());
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo);
 
-        // Test valid limits
-        let result3 = handler.list_events(10, 0).await;
-        assert!(result3.is_ok());
+        for n in 0..20 {
+            let mut params = create_test_params(receiver_id);
+            params.payload = json!({"message": "failed", "attempt": n});
+            params.success = false;
+            let outcome = handler.create_event(params).await.unwrap();
+            assert!(outcome.event_id().is_some());
+        }
     }
 
     #[tokio::test]
-    async fn test_time_range_validation() {
-        let event_repo = Arc::new(MockEventRepository::new());
+    async fn test_receiver_statistics_include_sampled_out_events() {
         let receiver_repo = Arc::new(MockEventReceiverRepository::new());
-        let handler = EventHandler::new(event_repo, receiver_repo);
+        let mut receiver = create_test_receiver();
+        receiver.set_sample_rate(Some(0.5)).unwrap();
+        let receiver_id = receiver.id();
+        receiver_repo.insert(receiver);
 
-        let end_time = Utc::now();
-        let start_time = end_time + chrono::Duration::hours(1); // Start after end
+        let handler = EventHandler::new(Arc::new(MockEventRepository::new()), receiver_repo)
+            .with_sampling_counters(Arc::new(MockSamplingCounters::default()));
+
+        let mut stored = 0;
+        for n in 0..200 {
+            let mut params = create_test_params(receiver_id);
+            params.payload = json!({"message": "heartbeat", "sequence": n});
+            if handler
+                .create_event(params)
+                .await
+                .unwrap()
+                .event_id()
+                .is_some()
+            {
+                stored += 1;
+            }
+        }
 
-        let result = handler
-            .find_events_in_time_range(start_time, end_time)
-            .await;
-        assert!(result.is_err());
+        let stats = handler.get_receiver_statistics(receiver_id).await.unwrap();
+        assert_eq!(stats.seen_events, 200);
+        assert_eq!(stats.total_events, stored);
+        assert_eq!(stats.sampled_out_events, 200 - stored);
+        assert!(stored > 0 && stored < 200);
     }
 }
@@ -0,0 +1,679 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/event_outbox_relay.rs
+
+use crate::application::domain_events::{DomainEvent, DomainEventBus};
+use crate::application::handlers::group_topic_fanout::GroupTopicFanout;
+use crate::domain::entities::delivery_failure::{DeliveryFailure, DeliveryMechanism};
+use crate::domain::entities::event::Event;
+use crate::domain::entities::event_publication::publish_retry_delay;
+use crate::domain::repositories::event_outbox_repo::{EventOutboxRepository, OutboxEntry};
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::error::Result;
+use crate::infrastructure::maintenance::MaintenanceMode;
+use crate::infrastructure::messaging::producer::EventPublisher;
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+/// Default number of publish attempts before an event is dead-lettered
+pub const DEFAULT_OUTBOX_MAX_ATTEMPTS: u32 = 10;
+
+/// Default number of outbox entries retried per pass
+pub const DEFAULT_OUTBOX_BATCH_SIZE: usize = 100;
+
+/// Target reported for dead-lettered events when the primary topic's name
+/// is not set
+pub const PRIMARY_TOPIC_TARGET: &str = "primary";
+
+/// Outcome of a single relay pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutboxRelayReport {
+    pub published: usize,
+    /// Failed again and scheduled for a later attempt
+    pub deferred: usize,
+    pub dead_lettered: usize,
+}
+
+/// Application service retrying the publication of deferred events
+///
+/// Each pass loads the outbox entries that are due, publishes their events,
+/// and removes the entries that succeed. Failures back off exponentially;
+/// an event that fails `max_attempts` times is dead-lettered and no longer
+/// retried, and announced as [`DomainEvent::DeliveryFailed`] when an event
+/// bus is set. Events deleted in the meantime leave the outbox silently.
+/// Passes are skipped while maintenance mode is on, so publishing and
+/// committing outbox progress resume only once it ends.
+#[derive(Clone)]
+pub struct EventOutboxRelay {
+    outbox: Arc<dyn EventOutboxRepository>,
+    event_repository: Arc<dyn EventRepository>,
+    publisher: Arc<dyn EventPublisher>,
+    group_fanout: Option<GroupTopicFanout>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    maintenance: Option<MaintenanceMode>,
+    event_bus: Option<DomainEventBus>,
+    topic: String,
+    max_attempts: u32,
+    batch_size: usize,
+}
+
+impl EventOutboxRelay {
+    /// Creates a relay using the default attempt limit and batch size
+    pub fn new(
+        outbox: Arc<dyn EventOutboxRepository>,
+        event_repository: Arc<dyn EventRepository>,
+        publisher: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            outbox,
+            event_repository,
+            publisher,
+            group_fanout: None,
+            metrics: None,
+            maintenance: None,
+            event_bus: None,
+            topic: PRIMARY_TOPIC_TARGET.to_string(),
+            max_attempts: DEFAULT_OUTBOX_MAX_ATTEMPTS,
+            batch_size: DEFAULT_OUTBOX_BATCH_SIZE,
+        }
+    }
+
+    /// Sets how many failed attempts dead-letter an event
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the maximum number of entries retried per pass
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Fans retried events out to their group topics once published
+    pub fn with_group_fanout(mut self, group_fanout: GroupTopicFanout) -> Self {
+        self.group_fanout = Some(group_fanout);
+        self
+    }
+
+    /// Counts retried events by publish outcome
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Pauses the relay while `maintenance` is on
+    pub fn with_maintenance(mut self, maintenance: MaintenanceMode) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+
+    /// Announces dead-lettered events on the domain event bus
+    pub fn with_event_bus(mut self, event_bus: DomainEventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Names the primary topic in announced delivery failures
+    pub fn with_topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = topic.into();
+        self
+    }
+
+    /// Retries up to one batch of entries due at `now`
+    ///
+    /// Does nothing while maintenance mode is on.
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<OutboxRelayReport> {
+        let mut report = OutboxRelayReport::default();
+        if self
+            .maintenance
+            .as_ref()
+            .is_some_and(MaintenanceMode::is_active)
+        {
+            debug!("Event outbox relay paused for maintenance");
+            return Ok(report);
+        }
+
+        for entry in self.outbox.find_due(now, self.batch_size).await? {
+            let Some(event) = self.event_repository.find_by_id(entry.event_id).await? else {
+                warn!(event_id = %entry.event_id, "Deferred event no longer exists");
+                self.outbox.mark_published(entry.event_id).await?;
+                continue;
+            };
+
+            match self.publisher.publish(&event).await {
+                Ok(()) => {
+                    self.outbox.mark_published(entry.event_id).await?;
+                    self.record_outcome("retried");
+                    report.published += 1;
+                    if let Some(fanout) = &self.group_fanout {
+                        fanout.fan_out(&event).await;
+                    }
+                }
+                Err(e) => {
+                    if self.retry(&entry, &event, &e.to_string(), now).await? {
+                        report.deferred += 1;
+                    } else {
+                        report.dead_lettered += 1;
+                    }
+                }
+            }
+        }
+
+        if report != OutboxRelayReport::default() {
+            info!(
+                published = report.published,
+                deferred = report.deferred,
+                dead_lettered = report.dead_lettered,
+                "Event outbox relay pass complete"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Records a failed attempt; returns false if the entry was dead-lettered
+    async fn retry(
+        &self,
+        entry: &OutboxEntry,
+        event: &Event,
+        error: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let attempts = entry.attempts + 1;
+        if attempts >= self.max_attempts {
+            error!(
+                event_id = %entry.event_id,
+                attempts,
+                error,
+                "Giving up on publishing event; moved to dead letters"
+            );
+            self.outbox
+                .record_failure(entry.event_id, error, None)
+                .await?;
+            self.record_outcome("dead_lettered");
+            if let Some(bus) = &self.event_bus {
+                bus.publish(DomainEvent::DeliveryFailed {
+                    failure: DeliveryFailure::new(
+                        DeliveryMechanism::EventPublish,
+                        self.topic.clone(),
+                        entry.event_id,
+                        event.event_receiver_id(),
+                        error,
+                        attempts,
+                        now,
+                    ),
+                    event: event.clone(),
+                });
+            }
+            return Ok(false);
+        }
+
+        let next_attempt_at = now + publish_retry_delay(attempts);
+        warn!(
+            event_id = %entry.event_id,
+            attempts,
+            error,
+            next_attempt_at = %next_attempt_at,
+            "Publish retry failed"
+        );
+        self.outbox
+            .record_failure(entry.event_id, error, Some(next_attempt_at))
+            .await?;
+        Ok(true)
+    }
+
+    fn record_outcome(&self, outcome: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_event_publish_outcome(outcome);
+        }
+    }
+
+    /// Runs a pass every `interval` until the task is aborted
+    pub fn spawn(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once(Utc::now()).await {
+                    error!("Event outbox relay pass failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::delivery_failure::DeliveryErrorClass;
+    use crate::domain::entities::event::{DatabaseEventFields, Event, EventOrigin};
+    use crate::domain::repositories::event_outbox_repo::OutboxBacklog;
+    use crate::domain::repositories::event_repo::FindEventCriteria;
+    use crate::domain::value_objects::{EventId, EventReceiverId, UserId};
+    use crate::error::{Error, InfrastructureError};
+    use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockEventRepository {
+        events: Mutex<HashMap<EventId, Event>>,
+    }
+
+    #[async_trait]
+    impl EventRepository for MockEventRepository {
+        async fn save(&self, event: &Event) -> Result<()> {
+            self.events
+                .lock()
+                .unwrap()
+                .insert(event.id(), event.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: EventId) -> Result<Option<Event>> {
+            Ok(self.events.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_success(&self, _success: bool) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_name(&self, _name: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_platform_id(&self, _platform_id: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_package(&self, _package: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(self.events.lock().unwrap().len())
+        }
+
+        async fn count_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn count_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn delete(&self, id: EventId) -> Result<()> {
+            self.events.lock().unwrap().remove(&id);
+            Ok(())
+        }
+
+        async fn find_latest_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_latest_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_by_time_range(
+            &self,
+            _start: DateTime<Utc>,
+            _end: DateTime<Utc>,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_criteria(&self, criteria: FindEventCriteria) -> Result<Vec<Event>> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .values()
+                .take(criteria.limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_owner(&self, _owner_id: UserId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner_paginated(
+            &self,
+            _owner_id: UserId,
+            _limit: usize,
+            _offset: usize,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn is_owner(&self, _event_id: EventId, _user_id: UserId) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn get_resource_version(&self, _event_id: EventId) -> Result<Option<i64>> {
+            Ok(Some(1))
+        }
+    }
+
+    #[derive(Default)]
+    struct MockOutbox {
+        entries: Mutex<HashMap<EventId, OutboxEntry>>,
+    }
+
+    #[async_trait]
+    impl EventOutboxRepository for MockOutbox {
+        async fn enqueue(
+            &self,
+            event_id: EventId,
+            error: &str,
+            next_attempt_at: DateTime<Utc>,
+        ) -> Result<()> {
+            self.entries.lock().unwrap().insert(
+                event_id,
+                OutboxEntry {
+                    event_id,
+                    attempts: 1,
+                    last_error: error.to_string(),
+                    next_attempt_at,
+                    dead_lettered_at: None,
+                },
+            );
+            Ok(())
+        }
+
+        async fn find_due(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<OutboxEntry>> {
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|e| e.dead_lettered_at.is_none() && e.next_attempt_at <= now)
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_entries(&self, event_ids: &[EventId]) -> Result<Vec<OutboxEntry>> {
+            let entries = self.entries.lock().unwrap();
+            Ok(event_ids
+                .iter()
+                .filter_map(|id| entries.get(id).cloned())
+                .collect())
+        }
+
+        async fn mark_published(&self, event_id: EventId) -> Result<()> {
+            self.entries.lock().unwrap().remove(&event_id);
+            Ok(())
+        }
+
+        async fn record_failure(
+            &self,
+            event_id: EventId,
+            error: &str,
+            next_attempt_at: Option<DateTime<Utc>>,
+        ) -> Result<()> {
+            if let Some(entry) = self.entries.lock().unwrap().get_mut(&event_id) {
+                entry.attempts += 1;
+                entry.last_error = error.to_string();
+                match next_attempt_at {
+                    Some(at) => entry.next_attempt_at = at,
+                    None => entry.dead_lettered_at = Some(Utc::now()),
+                }
+            }
+            Ok(())
+        }
+
+        async fn backlog(&self) -> Result<OutboxBacklog> {
+            let entries = self.entries.lock().unwrap();
+            let dead = entries
+                .values()
+                .filter(|e| e.dead_lettered_at.is_some())
+                .count() as u64;
+            Ok(OutboxBacklog {
+                pending: entries.len() as u64 - dead,
+                dead_lettered: dead,
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct MockPublisher {
+        down: AtomicBool,
+        published: Mutex<Vec<EventId>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for MockPublisher {
+        async fn publish(&self, event: &Event) -> Result<()> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(Error::Infrastructure(
+                    InfrastructureError::KafkaProducerError {
+                        message: "broker unavailable".to_string(),
+                    },
+                ));
+            }
+            self.published.lock().unwrap().push(event.id());
+            Ok(())
+        }
+
+        async fn publish_to(&self, _topic: &str, event: &Event) -> Result<()> {
+            self.publish(event).await
+        }
+
+        async fn publish_message(&self, _message: &CloudEventMessage) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn event() -> Event {
+        Event::from_database(DatabaseEventFields {
+            id: EventId::new(),
+            name: "deploy".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.01".to_string(),
+            platform_id: "linux".to_string(),
+            package: "docker".to_string(),
+            description: "outbox test".to_string(),
+            payload: json!({"build": 42}),
+            success: true,
+            event_receiver_id: EventReceiverId::new(),
+            owner_id: UserId::new(),
+            origin: EventOrigin::User,
+            resource_version: 1,
+            created_at: Utc::now(),
+        })
+    }
+
+    struct Fixture {
+        events: Arc<MockEventRepository>,
+        outbox: Arc<MockOutbox>,
+        publisher: Arc<MockPublisher>,
+        relay: EventOutboxRelay,
+    }
+
+    fn fixture() -> Fixture {
+        let events = Arc::new(MockEventRepository::default());
+        let outbox = Arc::new(MockOutbox::default());
+        let publisher = Arc::new(MockPublisher::default());
+        let relay = EventOutboxRelay::new(outbox.clone(), events.clone(), publisher.clone());
+        Fixture {
+            events,
+            outbox,
+            publisher,
+            relay,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_due_entries_are_published_and_removed() {
+        let f = fixture();
+        let now = Utc::now();
+        let due = event();
+        let later = event();
+        for (event, at) in [(&due, now), (&later, now + Duration::minutes(5))] {
+            f.events.save(event).await.unwrap();
+            f.outbox.enqueue(event.id(), "timed out", at).await.unwrap();
+        }
+
+        let report = f.relay.run_once(now).await.unwrap();
+
+        assert_eq!(report.published, 1);
+        assert_eq!(*f.publisher.published.lock().unwrap(), vec![due.id()]);
+        let backlog = f.outbox.backlog().await.unwrap();
+        assert_eq!(backlog.pending, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failures_back_off_then_dead_letter() {
+        let f = fixture();
+        let relay = f.relay.clone().with_max_attempts(3);
+        f.publisher.down.store(true, Ordering::SeqCst);
+        let stuck = event();
+        let now = Utc::now();
+        f.events.save(&stuck).await.unwrap();
+        f.outbox
+            .enqueue(stuck.id(), "timed out", now)
+            .await
+            .unwrap();
+
+        let report = relay.run_once(now).await.unwrap();
+        assert_eq!(report.deferred, 1);
+        let entry = f.outbox.find_entries(&[stuck.id()]).await.unwrap()[0].clone();
+        assert_eq!(entry.attempts, 2);
+        assert_eq!(entry.next_attempt_at, now + publish_retry_delay(2));
+        assert_eq!(
+            relay.run_once(now).await.unwrap(),
+            OutboxRelayReport::default()
+        );
+
+        let report = relay.run_once(entry.next_attempt_at).await.unwrap();
+        assert_eq!(report.dead_lettered, 1);
+        let backlog = f.outbox.backlog().await.unwrap();
+        assert_eq!(backlog.dead_lettered, 1);
+        assert_eq!(backlog.pending, 0);
+    }
+
+    #[tokio::test]
+    async fn test_only_dead_letters_announce_a_delivery_failure() {
+        let f = fixture();
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let relay = f
+            .relay
+            .clone()
+            .with_max_attempts(3)
+            .with_event_bus(bus)
+            .with_topic("xzepr.dev.events");
+        let now = Utc::now();
+        let retry_at = now + publish_retry_delay(2);
+
+        // Fails once, then succeeds on its retry
+        let recovered = event();
+        f.events.save(&recovered).await.unwrap();
+        f.outbox
+            .enqueue(recovered.id(), "timed out", now)
+            .await
+            .unwrap();
+        f.publisher.down.store(true, Ordering::SeqCst);
+        relay.run_once(now).await.unwrap();
+        f.publisher.down.store(false, Ordering::SeqCst);
+        assert_eq!(relay.run_once(retry_at).await.unwrap().published, 1);
+        assert!(notifications.try_recv().is_err());
+
+        let stuck = event();
+        f.events.save(&stuck).await.unwrap();
+        f.outbox
+            .enqueue(stuck.id(), "timed out", now)
+            .await
+            .unwrap();
+        f.publisher.down.store(true, Ordering::SeqCst);
+        relay.run_once(now).await.unwrap();
+        assert_eq!(relay.run_once(retry_at).await.unwrap().dead_lettered, 1);
+
+        let notification = notifications.try_recv().unwrap();
+        let DomainEvent::DeliveryFailed { failure, event } = &*notification else {
+            panic!("expected DeliveryFailed, got {}", notification.kind());
+        };
+        assert_eq!(failure.mechanism, DeliveryMechanism::EventPublish);
+        assert_eq!(failure.target, "xzepr.dev.events");
+        assert_eq!(failure.event_id, stuck.id());
+        assert_eq!(failure.receiver_id, stuck.event_receiver_id());
+        assert_eq!(failure.error_class, DeliveryErrorClass::Unavailable);
+        assert_eq!(failure.attempts, 3);
+        assert_eq!(event.id(), stuck.id());
+        assert!(notifications.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_deleted_events_leave_the_outbox() {
+        let f = fixture();
+        let now = Utc::now();
+        f.outbox
+            .enqueue(EventId::new(), "timed out", now)
+            .await
+            .unwrap();
+
+        let report = f.relay.run_once(now).await.unwrap();
+
+        assert_eq!(report, OutboxRelayReport::default());
+        assert_eq!(f.outbox.backlog().await.unwrap(), OutboxBacklog::default());
+        assert!(f.publisher.published.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_relay_pauses_during_maintenance() {
+        let f = fixture();
+        let maintenance = MaintenanceMode::new();
+        let relay = f.relay.clone().with_maintenance(maintenance.clone());
+        let now = Utc::now();
+        let deferred = event();
+        f.events.save(&deferred).await.unwrap();
+        f.outbox
+            .enqueue(deferred.id(), "timed out", now)
+            .await
+            .unwrap();
+
+        maintenance
+            .enter("Migrating".to_string(), None, "admin")
+            .await
+            .unwrap();
+        assert_eq!(
+            relay.run_once(now).await.unwrap(),
+            OutboxRelayReport::default()
+        );
+        assert!(f.publisher.published.lock().unwrap().is_empty());
+        assert_eq!(f.outbox.backlog().await.unwrap().pending, 1);
+
+        maintenance.exit("admin").await.unwrap();
+        let report = relay.run_once(now).await.unwrap();
+        assert_eq!(report.published, 1);
+        assert_eq!(*f.publisher.published.lock().unwrap(), vec![deferred.id()]);
+        assert_eq!(f.outbox.backlog().await.unwrap().pending, 0);
+    }
+}
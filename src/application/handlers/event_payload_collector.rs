@@ -0,0 +1,192 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/event_payload_collector.rs
+
+use crate::domain::repositories::event_payload_repo::{
+    CollectedPayloads, EventPayloadRepository, PayloadStoreStats,
+};
+use crate::error::Result;
+use crate::infrastructure::jobs::{Job, JobReport, JobSchedule};
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+/// Default number of unreferenced payloads deleted per pass
+pub const DEFAULT_PAYLOAD_GC_BATCH_SIZE: usize = 500;
+
+/// Default time between collection passes
+pub const DEFAULT_PAYLOAD_GC_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Name of the payload collection job in settings and the admin API
+pub const PAYLOAD_GC_JOB_NAME: &str = "event_payload_gc";
+
+/// Background job deleting shared payloads that no event references
+///
+/// Deleting or archiving an event only drops its reference, so the last
+/// event sharing a payload leaves it behind with no references. Each pass
+/// deletes a batch of those and reports the size of the store and the bytes
+/// sharing saves.
+#[derive(Clone)]
+pub struct EventPayloadCollector {
+    payloads: Arc<dyn EventPayloadRepository>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    batch_size: usize,
+    interval: Duration,
+}
+
+impl EventPayloadCollector {
+    /// Creates a collector running hourly
+    pub fn new(payloads: Arc<dyn EventPayloadRepository>) -> Self {
+        Self {
+            payloads,
+            metrics: None,
+            batch_size: DEFAULT_PAYLOAD_GC_BATCH_SIZE,
+            interval: DEFAULT_PAYLOAD_GC_INTERVAL,
+        }
+    }
+
+    /// Reports the store size and bytes saved after each pass
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Sets the maximum number of payloads deleted per pass
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the time between passes when run as a job
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Deletes up to one batch of unreferenced payloads
+    pub async fn run_once(&self) -> Result<(CollectedPayloads, PayloadStoreStats)> {
+        let collected = self.payloads.collect_unreferenced(self.batch_size).await?;
+        let stats = self.payloads.payload_stats().await?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.set_event_payload_bytes(stats.stored_bytes, stats.saved_bytes);
+        }
+
+        Ok((collected, stats))
+    }
+}
+
+#[async_trait]
+impl Job for EventPayloadCollector {
+    fn name(&self) -> &str {
+        PAYLOAD_GC_JOB_NAME
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::Every(self.interval)
+    }
+
+    async fn run(&self) -> Result<JobReport> {
+        let (collected, stats) = self.run_once().await?;
+        if collected.payloads > 0 {
+            info!(
+                collected = collected.payloads,
+                collected_bytes = collected.bytes,
+                stored_bytes = stats.stored_bytes,
+                saved_bytes = stats.saved_bytes,
+                "Deleted unreferenced event payloads"
+            );
+        }
+        Ok(JobReport {
+            processed: collected.payloads,
+            more_pending: collected.payloads >= self.batch_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Reference counts and sizes keyed by hash
+    #[derive(Default)]
+    struct MemoryPayloads {
+        payloads: Mutex<HashMap<&'static str, (i64, u64)>>,
+    }
+
+    #[async_trait]
+    impl EventPayloadRepository for MemoryPayloads {
+        async fn collect_unreferenced(&self, limit: usize) -> Result<CollectedPayloads> {
+            let mut payloads = self.payloads.lock().unwrap();
+            let unreferenced: Vec<_> = payloads
+                .iter()
+                .filter(|(_, (refs, _))| *refs <= 0)
+                .map(|(hash, _)| *hash)
+                .take(limit)
+                .collect();
+            let mut collected = CollectedPayloads::default();
+            for hash in unreferenced {
+                let (_, size) = payloads.remove(hash).unwrap();
+                collected.payloads += 1;
+                collected.bytes += size;
+            }
+            Ok(collected)
+        }
+
+        async fn payload_stats(&self) -> Result<PayloadStoreStats> {
+            let payloads = self.payloads.lock().unwrap();
+            Ok(PayloadStoreStats {
+                payloads: payloads.len() as u64,
+                stored_bytes: payloads.values().map(|(_, size)| size).sum(),
+                saved_bytes: payloads
+                    .values()
+                    .map(|(refs, size)| (*refs - 1).max(0) as u64 * size)
+                    .sum(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collects_unreferenced_payloads_and_reports_savings() {
+        let payloads = Arc::new(MemoryPayloads::default());
+        payloads.payloads.lock().unwrap().extend([
+            ("shared", (3, 1000)),
+            ("released-1", (0, 400)),
+            ("released-2", (0, 600)),
+        ]);
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let collector = EventPayloadCollector::new(payloads.clone())
+            .with_metrics(metrics.clone())
+            .with_batch_size(1);
+
+        let report = collector.run().await.unwrap();
+        assert_eq!(report.processed, 1);
+        assert!(report.more_pending);
+
+        let (collected, stats) = collector.run_once().await.unwrap();
+        assert_eq!(collected.payloads, 1);
+        assert_eq!(
+            stats,
+            PayloadStoreStats {
+                payloads: 1,
+                stored_bytes: 1000,
+                saved_bytes: 2000,
+            }
+        );
+        assert!(payloads.payloads.lock().unwrap().contains_key("shared"));
+
+        let report = collector.run().await.unwrap();
+        assert_eq!(report.processed, 0);
+        assert!(!report.more_pending);
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains("xzepr_event_payload_stored_bytes 1000"));
+        assert!(output.contains("xzepr_event_payload_saved_bytes 2000"));
+    }
+}
@@ -0,0 +1,437 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/event_poll_handler.rs
+
+use crate::domain::entities::event::Event;
+use crate::domain::repositories::event_feed_repo::EventFeedRepository;
+use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::error::Result;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Instant;
+use tracing::debug;
+
+/// Default longest time a poll waits for new events
+pub const DEFAULT_POLL_MAX_TIMEOUT_SECONDS: u64 = 30;
+
+/// Default most events returned by one poll
+pub const DEFAULT_POLL_BATCH_SIZE: usize = 100;
+
+/// Default most polls one principal may hold open at once
+pub const DEFAULT_POLL_MAX_IN_FLIGHT: usize = 4;
+
+/// Notices buffered per subscriber before a slow subscriber skips ahead
+const NOTICE_CAPACITY: usize = 1024;
+
+/// Announcement that an event was stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventNotice {
+    pub event_id: EventId,
+    pub receiver_id: EventReceiverId,
+}
+
+/// In-process broadcast of stored events
+///
+/// Live consumers subscribe to learn about new events without querying the
+/// database in a loop. A notice is only a wake-up signal: notices are not
+/// kept, a subscriber that falls behind skips ahead, and events stored by
+/// other instances are not announced, so subscribers read the events
+/// themselves from the store.
+#[derive(Clone)]
+pub struct EventNotifier {
+    sender: broadcast::Sender<EventNotice>,
+}
+
+impl EventNotifier {
+    /// Creates a notifier without subscribers
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(NOTICE_CAPACITY);
+        Self { sender }
+    }
+
+    /// Announces a stored event to current subscribers
+    pub fn notify(&self, event: &Event) {
+        // Sending only fails when nobody is listening
+        let _ = self.sender.send(EventNotice {
+            event_id: event.id(),
+            receiver_id: event.event_receiver_id(),
+        });
+    }
+
+    /// Returns a receiver of notices sent from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<EventNotice> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Events returned by one poll
+#[derive(Debug, Clone)]
+pub struct EventPollBatch {
+    /// New events, oldest first
+    pub events: Vec<Event>,
+    /// Cursor for the next poll: the last event returned, or the requested
+    /// cursor when nothing arrived
+    pub next_cursor: Option<EventId>,
+}
+
+type InFlight = Arc<Mutex<HashMap<String, usize>>>;
+
+/// Slot of an open poll, released when dropped
+///
+/// Holding the permit in the request future releases it however the
+/// request ends, including when the client disconnects and the future is
+/// dropped mid-wait.
+pub struct PollPermit {
+    principal: String,
+    in_flight: InFlight,
+}
+
+impl Drop for PollPermit {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(count) = in_flight.get_mut(&self.principal) {
+            *count -= 1;
+            if *count == 0 {
+                in_flight.remove(&self.principal);
+            }
+        }
+    }
+}
+
+/// Application service for long-polling new events
+///
+/// A poll returns at once when events after the cursor exist. Otherwise it
+/// waits on the [`EventNotifier`] until a matching event is stored or the
+/// timeout passes, and then returns an empty batch with the cursor
+/// unchanged.
+#[derive(Clone)]
+pub struct EventPollHandler {
+    repository: Arc<dyn EventFeedRepository>,
+    notifier: EventNotifier,
+    max_timeout: Duration,
+    batch_size: usize,
+    max_in_flight: usize,
+    in_flight: InFlight,
+}
+
+impl EventPollHandler {
+    /// Creates a handler reading from `repository` and woken by `notifier`
+    pub fn new(repository: Arc<dyn EventFeedRepository>, notifier: EventNotifier) -> Self {
+        Self {
+            repository,
+            notifier,
+            max_timeout: Duration::from_secs(DEFAULT_POLL_MAX_TIMEOUT_SECONDS),
+            batch_size: DEFAULT_POLL_BATCH_SIZE,
+            max_in_flight: DEFAULT_POLL_MAX_IN_FLIGHT,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Sets the longest time a poll waits; longer requested timeouts are
+    /// shortened to it
+    pub fn with_max_timeout(mut self, max_timeout: Duration) -> Self {
+        self.max_timeout = max_timeout;
+        self
+    }
+
+    /// Sets the most events returned by one poll
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the most polls one principal may hold open at once
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight;
+        self
+    }
+
+    /// Returns the longest time a poll waits
+    pub fn max_timeout(&self) -> Duration {
+        self.max_timeout
+    }
+
+    /// Reserves a poll slot for `principal`
+    ///
+    /// Returns `None` when the principal already holds the maximum number
+    /// of open polls.
+    pub fn try_acquire(&self, principal: &str) -> Option<PollPermit> {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        let count = in_flight.entry(principal.to_string()).or_default();
+        if *count >= self.max_in_flight {
+            if *count == 0 {
+                in_flight.remove(principal);
+            }
+            return None;
+        }
+        *count += 1;
+
+        Some(PollPermit {
+            principal: principal.to_string(),
+            in_flight: self.in_flight.clone(),
+        })
+    }
+
+    /// Returns the number of polls `principal` holds open
+    pub fn in_flight(&self, principal: &str) -> usize {
+        self.in_flight
+            .lock()
+            .unwrap()
+            .get(principal)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns events after `cursor`, waiting up to `timeout` for one
+    ///
+    /// `None` starts before the first event. With a `receiver_id`, only
+    /// that receiver's events are returned and only its events end the wait.
+    pub async fn poll(
+        &self,
+        cursor: Option<EventId>,
+        receiver_id: Option<EventReceiverId>,
+        timeout: Duration,
+    ) -> Result<EventPollBatch> {
+        let deadline = Instant::now() + timeout.min(self.max_timeout);
+        // Subscribe before reading so an event stored in between still wakes us
+        let mut notices = self.notifier.subscribe();
+
+        loop {
+            let events = self
+                .repository
+                .find_events_after(cursor, receiver_id, self.batch_size)
+                .await?;
+            if let Some(last) = events.last() {
+                return Ok(EventPollBatch {
+                    next_cursor: Some(last.id()),
+                    events,
+                });
+            }
+
+            loop {
+                match tokio::time::timeout_at(deadline, notices.recv()).await {
+                    // The handler holds a sender, so the channel never closes
+                    Err(_) | Ok(Err(RecvError::Closed)) => {
+                        return Ok(EventPollBatch {
+                            events: Vec::new(),
+                            next_cursor: cursor,
+                        })
+                    }
+                    Ok(Ok(notice))
+                        if receiver_id.is_none() || receiver_id == Some(notice.receiver_id) =>
+                    {
+                        break
+                    }
+                    Ok(Ok(_)) => continue,
+                    // Skipped notices may have included a matching one
+                    Ok(Err(RecvError::Lagged(skipped))) => {
+                        debug!(skipped, "Event poll fell behind the notifier");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event::{DatabaseEventFields, EventOrigin};
+    use crate::domain::value_objects::UserId;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use serde_json::json;
+    use ulid::Ulid;
+
+    #[derive(Default)]
+    struct MockFeed {
+        events: Mutex<Vec<Event>>,
+    }
+
+    impl MockFeed {
+        fn store(&self, event: &Event) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[async_trait]
+    impl EventFeedRepository for MockFeed {
+        async fn find_events_after(
+            &self,
+            after: Option<EventId>,
+            receiver_id: Option<EventReceiverId>,
+            limit: usize,
+        ) -> Result<Vec<Event>> {
+            let mut events: Vec<Event> = self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| after.is_none_or(|after| e.id().as_ulid() > after.as_ulid()))
+                .filter(|e| receiver_id.is_none_or(|id| e.event_receiver_id() == id))
+                .cloned()
+                .collect();
+            events.sort_by_key(|e| e.id().as_ulid());
+            events.truncate(limit);
+            Ok(events)
+        }
+    }
+
+    /// Event whose id sorts by `sequence`
+    fn event(receiver_id: EventReceiverId, sequence: u64) -> Event {
+        Event::from_database(DatabaseEventFields {
+            id: EventId::from_ulid(Ulid::from_parts(1_700_000_000_000 + sequence, 0)),
+            name: "deploy.finished".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.04".to_string(),
+            platform_id: "linux".to_string(),
+            package: "web".to_string(),
+            description: "poll test".to_string(),
+            payload: json!({}),
+            success: true,
+            event_receiver_id: receiver_id,
+            owner_id: UserId::new(),
+            origin: EventOrigin::User,
+            resource_version: 1,
+            created_at: Utc::now(),
+        })
+    }
+
+    fn handler() -> (Arc<MockFeed>, EventNotifier, EventPollHandler) {
+        let feed = Arc::new(MockFeed::default());
+        let notifier = EventNotifier::new();
+        let handler = EventPollHandler::new(feed.clone(), notifier.clone());
+        (feed, notifier, handler)
+    }
+
+    #[tokio::test]
+    async fn test_poll_returns_backlog_immediately() {
+        let (feed, _, handler) = handler();
+        let handler = handler.with_batch_size(2);
+        let receiver_id = EventReceiverId::new();
+        let events: Vec<Event> = (1..=3).map(|n| event(receiver_id, n)).collect();
+        events.iter().for_each(|e| feed.store(e));
+
+        let started = Instant::now();
+        let batch = handler
+            .poll(Some(events[0].id()), None, Duration::from_secs(30))
+            .await
+            .unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        let ids: Vec<EventId> = batch.events.iter().map(Event::id).collect();
+        assert_eq!(ids, vec![events[1].id(), events[2].id()]);
+        assert_eq!(batch.next_cursor, Some(events[2].id()));
+
+        // Without a cursor the batch starts at the first event
+        let batch = handler.poll(None, None, Duration::ZERO).await.unwrap();
+        assert_eq!(batch.events.len(), 2);
+        assert_eq!(batch.next_cursor, Some(events[1].id()));
+    }
+
+    #[tokio::test]
+    async fn test_poll_wakes_on_new_event() {
+        let (feed, notifier, handler) = handler();
+        let receiver_id = EventReceiverId::new();
+        let other_receiver_id = EventReceiverId::new();
+        let seen = event(receiver_id, 1);
+        feed.store(&seen);
+
+        let poll = tokio::spawn({
+            let handler = handler.clone();
+            let cursor = seen.id();
+            async move {
+                handler
+                    .poll(Some(cursor), Some(receiver_id), Duration::from_secs(10))
+                    .await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // Another receiver's event does not end the wait
+        let unrelated = event(other_receiver_id, 2);
+        feed.store(&unrelated);
+        notifier.notify(&unrelated);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!poll.is_finished());
+
+        let started = Instant::now();
+        let new = event(receiver_id, 3);
+        feed.store(&new);
+        notifier.notify(&new);
+
+        let batch = poll.await.unwrap().unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(batch.events.len(), 1);
+        assert_eq!(batch.events[0].id(), new.id());
+        assert_eq!(batch.next_cursor, Some(new.id()));
+    }
+
+    #[tokio::test]
+    async fn test_poll_times_out_with_unchanged_cursor() {
+        let (feed, _, handler) = handler();
+        let handler = handler.with_max_timeout(Duration::from_millis(200));
+        let seen = event(EventReceiverId::new(), 1);
+        feed.store(&seen);
+
+        let started = Instant::now();
+        let batch = handler
+            .poll(Some(seen.id()), None, Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        // The requested timeout is capped by the configured maximum
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(5));
+        assert!(batch.events.is_empty());
+        assert_eq!(batch.next_cursor, Some(seen.id()));
+    }
+
+    #[tokio::test]
+    async fn test_open_polls_are_capped_per_principal() {
+        let (_, _, handler) = handler();
+        let handler = handler.with_max_in_flight(2);
+
+        let first = handler.try_acquire("user:alice").unwrap();
+        let _second = handler.try_acquire("user:alice").unwrap();
+        assert!(handler.try_acquire("user:alice").is_none());
+        assert!(handler.try_acquire("user:bob").is_some());
+        assert_eq!(handler.in_flight("user:alice"), 2);
+
+        drop(first);
+        assert_eq!(handler.in_flight("user:alice"), 1);
+        assert!(handler.try_acquire("user:alice").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_poll_releases_its_slot() {
+        let (_, _, handler) = handler();
+
+        // A disconnecting client drops the request future mid-wait
+        let poll = tokio::spawn({
+            let handler = handler.clone();
+            async move {
+                let _permit = handler.try_acquire("user:alice").unwrap();
+                handler.poll(None, None, Duration::from_secs(30)).await
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(handler.in_flight("user:alice"), 1);
+
+        poll.abort();
+        assert!(poll.await.unwrap_err().is_cancelled());
+        assert_eq!(handler.in_flight("user:alice"), 0);
+    }
+}
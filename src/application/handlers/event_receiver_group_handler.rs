@@ -3,20 +3,41 @@
 
 // src/application/handlers/event_receiver_group_handler.rs
 
+use crate::application::domain_events::{DomainEvent, DomainEventBus, MembershipChange};
+use crate::application::handlers::description_renderer::DescriptionRenderer;
+use crate::application::handlers::group_completeness_handler::GroupCompletenessHandler;
+use crate::application::handlers::resource_history_handler::ResourceHistoryHandler;
+use crate::application::handlers::schema_resolver::SchemaResolver;
+use crate::application::handlers::system_events::{
+    changed_fields, report_system_event_failure, SystemEventFactory, GROUP_CREATED_EVENT,
+    GROUP_DELETED_EVENT, GROUP_UPDATED_EVENT,
+};
 use crate::domain::entities::event::Event;
 use crate::domain::entities::event_receiver_group::EventReceiverGroup;
 use crate::domain::repositories::event_receiver_group_repo::{
-    EventReceiverGroupRepository, FindEventReceiverGroupCriteria,
+    EventReceiverGroupRepository, FindEventReceiverGroupCriteria, GroupMembership,
 };
 use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
-use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId, UserId};
+use crate::domain::repositories::pagination::{PaginationParams, DEFAULT_PAGE_SIZE};
+use crate::domain::repositories::resource_history_repo::GroupState;
+use crate::domain::value_objects::description::{
+    normalize_description, DEFAULT_MAX_DESCRIPTION_LENGTH,
+};
+use crate::domain::value_objects::{
+    EventReceiverGroupId, EventReceiverId, UserId, Version, VersionStrictness,
+};
 use crate::error::{DomainError, Result};
-use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
-use crate::infrastructure::messaging::producer::KafkaEventPublisher;
+use crate::i18n::Message;
+use crate::infrastructure::messaging::topics::TopicProvisioner;
+use crate::infrastructure::PrometheusMetrics;
 
+use chrono::{DateTime, Duration, Utc};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
+/// Default longest time a membership may be granted for
+pub const DEFAULT_MAX_MEMBERSHIP_DAYS: i64 = 365;
+
 /// Parameters for updating an event receiver group
 #[derive(Debug, Clone, Default)]
 pub struct UpdateEventReceiverGroupParams {
@@ -26,6 +47,14 @@ pub struct UpdateEventReceiverGroupParams {
     pub description: Option<String>,
     pub enabled: Option<bool>,
     pub event_receiver_ids: Option<Vec<EventReceiverId>>,
+    /// New member receivers that do not block release completeness; every
+    /// other member becomes required
+    pub optional_receiver_ids: Option<Vec<EventReceiverId>>,
+    /// New default schema for member receivers; an empty object clears it
+    pub default_schema: Option<serde_json::Value>,
+    /// New topic events of member receivers are fanned out to; an empty
+    /// name clears it
+    pub dedicated_topic: Option<String>,
 }
 
 /// Application service for handling event receiver group operations
@@ -33,7 +62,17 @@ pub struct UpdateEventReceiverGroupParams {
 pub struct EventReceiverGroupHandler {
     group_repository: Arc<dyn EventReceiverGroupRepository>,
     receiver_repository: Arc<dyn EventReceiverRepository>,
-    event_publisher: Option<Arc<KafkaEventPublisher>>,
+    event_bus: Option<DomainEventBus>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    system_events: SystemEventFactory,
+    schema_resolver: Option<SchemaResolver>,
+    topic_provisioner: Option<Arc<dyn TopicProvisioner>>,
+    version_strictness: VersionStrictness,
+    max_membership_duration: Duration,
+    max_description_length: usize,
+    descriptions: DescriptionRenderer<EventReceiverGroupId>,
+    history: Option<ResourceHistoryHandler>,
+    completeness: Option<GroupCompletenessHandler>,
 }
 
 impl EventReceiverGroupHandler {
@@ -45,20 +84,190 @@ impl EventReceiverGroupHandler {
         Self {
             group_repository,
             receiver_repository,
-            event_publisher: None,
+            event_bus: None,
+            metrics: None,
+            system_events: SystemEventFactory::new(),
+            schema_resolver: None,
+            topic_provisioner: None,
+            version_strictness: VersionStrictness::default(),
+            max_membership_duration: Duration::days(DEFAULT_MAX_MEMBERSHIP_DAYS),
+            max_description_length: DEFAULT_MAX_DESCRIPTION_LENGTH,
+            descriptions: DescriptionRenderer::new(),
+            history: None,
+            completeness: None,
         }
     }
 
-    /// Creates a new event receiver group handler with event publisher
-    pub fn with_publisher(
-        group_repository: Arc<dyn EventReceiverGroupRepository>,
-        receiver_repository: Arc<dyn EventReceiverRepository>,
-        event_publisher: Arc<KafkaEventPublisher>,
-    ) -> Self {
-        Self {
-            group_repository,
-            receiver_repository,
-            event_publisher: Some(event_publisher),
+    /// Announces group changes on the domain event bus once they are stored
+    pub fn with_event_bus(mut self, event_bus: DomainEventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Attaches metrics used to count system event failures
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Invalidates inherited schemas when group defaults or membership change
+    pub fn with_schema_resolver(mut self, schema_resolver: SchemaResolver) -> Self {
+        self.schema_resolver = Some(schema_resolver);
+        self
+    }
+
+    /// Creates the dedicated topics of groups as they are set
+    pub fn with_topic_provisioner(mut self, topic_provisioner: Arc<dyn TopicProvisioner>) -> Self {
+        self.topic_provisioner = Some(topic_provisioner);
+        self
+    }
+
+    /// Sets how strictly new and updated versions must be semantic versions
+    pub fn with_version_strictness(mut self, strictness: VersionStrictness) -> Self {
+        self.version_strictness = strictness;
+        self
+    }
+
+    /// Sets the longest time ahead a membership may be set to expire
+    pub fn with_max_membership_duration(mut self, max: Duration) -> Self {
+        self.max_membership_duration = max;
+        self
+    }
+
+    /// Sets the longest description, in characters, new and updated groups
+    /// may have
+    pub fn with_max_description_length(mut self, max_length: usize) -> Self {
+        self.max_description_length = max_length;
+        self
+    }
+
+    /// Publishes a stored change to the domain event bus, if one is attached
+    fn publish(&self, event: DomainEvent) {
+        if let Some(bus) = &self.event_bus {
+            bus.publish(event);
+        }
+    }
+
+    /// Stores a built system event, or reports why it could not be built
+    ///
+    /// The change it describes is already saved, so a failure never fails
+    /// the request; the change is then announced without a system event.
+    async fn record_system_event(
+        &self,
+        event_type: &str,
+        group_id: EventReceiverGroupId,
+        built: std::result::Result<Event, DomainError>,
+    ) -> Option<Event> {
+        match built {
+            Ok(system_event) => {
+                self.system_events.record(&system_event).await;
+                Some(system_event)
+            }
+            Err(e) => {
+                report_system_event_failure(
+                    self.metrics.as_deref(),
+                    event_type,
+                    &group_id.to_string(),
+                    &e,
+                );
+                None
+            }
+        }
+    }
+
+    /// Announces a stored update with the fields changed since `before`
+    async fn announce_update(&self, before: &EventReceiverGroup, group: EventReceiverGroup) {
+        let changed_fields = changed_fields(before, &group);
+        let system_event = self
+            .record_system_event(
+                GROUP_UPDATED_EVENT,
+                group.id(),
+                self.create_group_updated_event(&group, &changed_fields),
+            )
+            .await;
+        self.publish(DomainEvent::GroupUpdated {
+            group,
+            changed_fields,
+            system_event,
+        });
+    }
+
+    /// Drops cached schema resolutions after a group change
+    async fn invalidate_schemas(&self) {
+        if let Some(resolver) = &self.schema_resolver {
+            resolver.invalidate().await;
+        }
+    }
+
+    /// Ensures a group's dedicated topic exists before the group is saved
+    async fn provision_topic(&self, topic: Option<&str>) -> Result<()> {
+        let (Some(provisioner), Some(topic)) = (&self.topic_provisioner, topic) else {
+            return Ok(());
+        };
+
+        match provisioner.ensure_topic(topic).await {
+            Ok(created) => {
+                info!(topic = %topic, created, "Dedicated group topic is ready");
+                Ok(())
+            }
+            Err(e) => {
+                error!(topic = %topic, error = %e, "Failed to provision dedicated group topic");
+                Err(e)
+            }
+        }
+    }
+
+    /// Sets the factory that builds and stores system events
+    pub fn with_system_event_factory(mut self, factory: SystemEventFactory) -> Self {
+        self.system_events = factory;
+        self
+    }
+
+    /// Records a snapshot of every group and its members after each write
+    pub fn with_history(mut self, history: ResourceHistoryHandler) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Returns the group history, if one is attached
+    pub fn history(&self) -> Option<&ResourceHistoryHandler> {
+        self.history.as_ref()
+    }
+
+    /// Reports whether the receivers of each group reported a release
+    pub fn with_completeness(mut self, completeness: GroupCompletenessHandler) -> Self {
+        self.completeness = Some(completeness);
+        self
+    }
+
+    /// Returns the release completeness reports, if attached
+    pub fn completeness(&self) -> Option<&GroupCompletenessHandler> {
+        self.completeness.as_ref()
+    }
+
+    /// Records the stored state of a group and its members, if history is
+    /// attached; a group that no longer exists is recorded as deleted
+    async fn record_history(&self, id: EventReceiverGroupId) {
+        let Some(history) = &self.history else {
+            return;
+        };
+        let now = Utc::now();
+
+        let stored = match self.group_repository.find_by_id(id).await {
+            Ok(Some(group)) => self
+                .group_repository
+                .get_group_memberships(id)
+                .await
+                .map(|members| Some(GroupState { group, members })),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        };
+        match stored {
+            Ok(Some(state)) => history.record_group(state, now).await,
+            Ok(None) => history.record_group_deleted(id, now).await,
+            Err(e) => {
+                warn!(group_id = %id, error = %e, "Failed to read event receiver group for history");
+            }
         }
     }
 
@@ -72,6 +281,9 @@ impl EventReceiverGroupHandler {
         description: String,
         enabled: bool,
         event_receiver_ids: Vec<EventReceiverId>,
+        optional_receiver_ids: Vec<EventReceiverId>,
+        default_schema: Option<serde_json::Value>,
+        dedicated_topic: Option<String>,
         owner_id: crate::domain::value_objects::UserId,
     ) -> Result<EventReceiverGroupId> {
         info!(
@@ -83,6 +295,9 @@ impl EventReceiverGroupHandler {
             "Creating new event receiver group"
         );
 
+        Version::parse(&version, self.version_strictness)?;
+        let description = normalize_description(&description, self.max_description_length)?;
+
         // Check if a group with the same name and type already exists
         if self
             .group_repository
@@ -95,7 +310,7 @@ impl EventReceiverGroupHandler {
                 "Event receiver group with same name and type already exists"
             );
             return Err(DomainError::BusinessRuleViolation {
-                rule: "Event receiver group with the same name and type already exists".to_string(),
+                rule: Message::new("rule.group_exists"),
             }
             .into());
         }
@@ -122,12 +337,19 @@ impl EventReceiverGroupHandler {
             enabled,
             event_receiver_ids,
             owner_id,
-        )?;
+        )?
+        .with_optional_receivers(optional_receiver_ids)?
+        .with_default_schema(default_schema)?
+        .with_dedicated_topic(dedicated_topic)?;
 
         let group_id = event_receiver_group.id();
+        self.provision_topic(event_receiver_group.dedicated_topic())
+            .await?;
 
         // Save to repository
         self.group_repository.save(&event_receiver_group).await?;
+        self.invalidate_schemas().await;
+        self.record_history(group_id).await;
 
         info!(
             group_id = %group_id,
@@ -135,33 +357,28 @@ impl EventReceiverGroupHandler {
             "Event receiver group created successfully"
         );
 
-        // Publish system event to Kafka if publisher is configured
-        if let Some(publisher) = &self.event_publisher {
-            let system_event = self.create_group_created_event(&event_receiver_group);
-            let message =
-                CloudEventMessage::from_event_with_group(&system_event, &event_receiver_group);
-            if let Err(e) = publisher.publish_message(&message).await {
-                error!(
-                    group_id = %group_id,
-                    error = %e,
-                    "Failed to publish group creation event to Kafka"
-                );
-                // Note: We don't fail the request since the group was saved to the database
-            } else {
-                info!(
-                    group_id = %group_id,
-                    event_id = %system_event.id(),
-                    "Group creation event published to Kafka successfully"
-                );
-            }
-        }
+        // Record the system event and announce the group with it; subscribers
+        // such as the Kafka forwarder publish it
+        let system_event = self
+            .record_system_event(
+                GROUP_CREATED_EVENT,
+                group_id,
+                self.create_group_created_event(&event_receiver_group),
+            )
+            .await;
+        self.publish(DomainEvent::GroupCreated {
+            group: event_receiver_group,
+            system_event,
+        });
 
         Ok(group_id)
     }
 
     /// Creates a system event for group creation
-    fn create_group_created_event(&self, group: &EventReceiverGroup) -> Event {
-        use crate::domain::entities::event::CreateEventParams;
+    fn create_group_created_event(
+        &self,
+        group: &EventReceiverGroup,
+    ) -> std::result::Result<Event, DomainError> {
         use serde_json::json;
 
         let receiver_ids: Vec<String> = group
@@ -181,32 +398,72 @@ impl EventReceiverGroupHandler {
             "receiver_count": group.receiver_count(),
         });
 
-        // Create event - we need a receiver_id, so we'll use the first receiver in the group
-        // or create a synthetic system receiver ID if the group is empty
-        let receiver_id = group
+        self.system_events.build(
+            GROUP_CREATED_EVENT,
+            format!("Event receiver group '{}' created", group.name()),
+            payload,
+            Self::subject_receiver(group),
+            group.owner_id(),
+        )
+    }
+
+    /// Creates a system event for a group update
+    fn create_group_updated_event(
+        &self,
+        group: &EventReceiverGroup,
+        changed_fields: &[String],
+    ) -> std::result::Result<Event, DomainError> {
+        use serde_json::json;
+
+        let payload = json!({
+            "group_id": group.id().to_string(),
+            "group": group,
+            "changed_fields": changed_fields,
+        });
+
+        self.system_events.build(
+            GROUP_UPDATED_EVENT,
+            format!("Event receiver group '{}' updated", group.name()),
+            payload,
+            Self::subject_receiver(group),
+            group.owner_id(),
+        )
+    }
+
+    /// Creates a system event for a group deletion
+    fn create_group_deleted_event(
+        &self,
+        group: &EventReceiverGroup,
+        deleted_at: DateTime<Utc>,
+    ) -> std::result::Result<Event, DomainError> {
+        use serde_json::json;
+
+        let payload = json!({
+            "group_id": group.id().to_string(),
+            "group": group,
+            "deleted_at": deleted_at,
+        });
+
+        self.system_events.build(
+            GROUP_DELETED_EVENT,
+            format!("Event receiver group '{}' deleted", group.name()),
+            payload,
+            Self::subject_receiver(group),
+            group.owner_id(),
+        )
+    }
+
+    /// Receiver a group's system events are about
+    ///
+    /// System events need a receiver_id, so the first receiver in the group
+    /// is used, or the group's ID as a synthetic receiver ID if the group is
+    /// empty.
+    fn subject_receiver(group: &EventReceiverGroup) -> EventReceiverId {
+        group
             .event_receiver_ids()
             .first()
             .copied()
-            .unwrap_or_else(|| {
-                // If group has no receivers, use the group's ID as a synthetic receiver ID
-                // This is acceptable for system events
-                EventReceiverId::from(group.id().as_ulid())
-            });
-
-        // Create event - unwrap is safe here as we control all inputs
-        Event::new(CreateEventParams {
-            name: "xzepr.event.receiver.group.created".to_string(),
-            version: "1.0.0".to_string(),
-            release: "system".to_string(),
-            platform_id: "xzepr".to_string(),
-            package: "xzepr.system".to_string(),
-            description: format!("Event receiver group '{}' created", group.name()),
-            payload,
-            success: true,
-            receiver_id,
-            owner_id: group.owner_id(),
-        })
-        .expect("Failed to create system event for group creation")
+            .unwrap_or_else(|| EventReceiverId::from(group.id().as_ulid()))
     }
 
     /// Gets an event receiver group by ID
@@ -225,6 +482,16 @@ impl EventReceiverGroupHandler {
         Ok(group)
     }
 
+    /// Renders a group's Markdown description as sanitized HTML
+    ///
+    /// Returns `None` if the group does not exist.
+    pub async fn description_html(&self, id: EventReceiverGroupId) -> Result<Option<Arc<str>>> {
+        Ok(self.group_repository.find_by_id(id).await?.map(|group| {
+            self.descriptions
+                .render(id, group.resource_version(), group.description())
+        }))
+    }
+
     /// Gets an event receiver group by ID, returning an error if not found
     pub async fn get_event_receiver_group_or_error(
         &self,
@@ -259,7 +526,7 @@ impl EventReceiverGroupHandler {
             "Finding event receiver groups by type and version"
         );
         self.group_repository
-            .find_by_type_and_version(group_type, version)
+            .find_by_type_and_version(group_type, &Version::normalize_filter(version))
             .await
     }
 
@@ -293,17 +560,25 @@ impl EventReceiverGroupHandler {
     ) -> Result<Vec<EventReceiverGroup>> {
         info!(?criteria, "Finding event receiver groups by criteria");
 
-        if criteria.is_empty() {
+        let criteria = if criteria.is_empty() && criteria.limit.is_none() {
             warn!("Empty criteria provided, using default pagination");
-            let criteria = FindEventReceiverGroupCriteria::new()
-                .with_limit(50)
-                .with_offset(0);
-            return self.group_repository.find_by_criteria(criteria).await;
-        }
+            criteria.with_limit(DEFAULT_PAGE_SIZE)
+        } else {
+            criteria
+        };
 
         self.group_repository.find_by_criteria(criteria).await
     }
 
+    /// Counts event receiver groups matching the criteria, ignoring pagination
+    pub async fn count_by_criteria(
+        &self,
+        criteria: FindEventReceiverGroupCriteria,
+    ) -> Result<usize> {
+        info!(?criteria, "Counting event receiver groups by criteria");
+        self.group_repository.count_by_criteria(criteria).await
+    }
+
     /// Lists all event receiver groups with pagination
     pub async fn list_event_receiver_groups(
         &self,
@@ -313,13 +588,7 @@ impl EventReceiverGroupHandler {
         info!(limit = %limit, offset = %offset, "Listing event receiver groups with pagination");
 
         // Validate pagination parameters
-        if limit == 0 || limit > 1000 {
-            return Err(DomainError::ValidationError {
-                field: "limit".to_string(),
-                message: "Limit must be between 1 and 1000".to_string(),
-            }
-            .into());
-        }
+        PaginationParams::new(Some(limit), offset).validate()?;
 
         self.group_repository.list(limit, offset).await
     }
@@ -350,8 +619,17 @@ impl EventReceiverGroupHandler {
     ) -> Result<()> {
         info!(group_id = %id, "Updating event receiver group");
 
+        if let Some(ref version) = params.version {
+            Version::parse(version, self.version_strictness)?;
+        }
+        let description = params
+            .description
+            .map(|description| normalize_description(&description, self.max_description_length))
+            .transpose()?;
+
         // Get the existing group
         let mut group = self.get_event_receiver_group_or_error(id).await?;
+        let before = group.clone();
 
         // If name or type is being changed, check for conflicts
         if let (Some(ref new_name), Some(ref new_type)) = (&params.name, &params.group_type) {
@@ -362,8 +640,7 @@ impl EventReceiverGroupHandler {
                     .await?
             {
                 return Err(DomainError::BusinessRuleViolation {
-                    rule: "Event receiver group with the same name and type already exists"
-                        .to_string(),
+                    rule: Message::new("rule.group_exists"),
                 }
                 .into());
             }
@@ -375,8 +652,7 @@ impl EventReceiverGroupHandler {
                     .await?
             {
                 return Err(DomainError::BusinessRuleViolation {
-                    rule: "Event receiver group with the same name and type already exists"
-                        .to_string(),
+                    rule: Message::new("rule.group_exists"),
                 }
                 .into());
             }
@@ -388,8 +664,7 @@ impl EventReceiverGroupHandler {
                     .await?
             {
                 return Err(DomainError::BusinessRuleViolation {
-                    rule: "Event receiver group with the same name and type already exists"
-                        .to_string(),
+                    rule: Message::new("rule.group_exists"),
                 }
                 .into());
             }
@@ -415,13 +690,28 @@ impl EventReceiverGroupHandler {
             params.name,
             params.group_type,
             params.version,
-            params.description,
+            description,
             params.enabled,
             params.event_receiver_ids,
         )?;
+        if let Some(optional_receiver_ids) = params.optional_receiver_ids {
+            group.set_optional_receivers(optional_receiver_ids)?;
+        }
+        if let Some(default_schema) = params.default_schema {
+            group.set_default_schema(Some(default_schema))?;
+        }
+        if let Some(dedicated_topic) = params.dedicated_topic {
+            let previous = group.dedicated_topic().map(str::to_string);
+            group.set_dedicated_topic(Some(dedicated_topic))?;
+            if group.dedicated_topic() != previous.as_deref() {
+                self.provision_topic(group.dedicated_topic()).await?;
+            }
+        }
 
         // Save the updated group
         self.group_repository.update(&group).await?;
+        self.invalidate_schemas().await;
+        self.record_history(id).await;
 
         info!(
             group_id = %id,
@@ -429,6 +719,7 @@ impl EventReceiverGroupHandler {
             enabled = %group.enabled(),
             "Event receiver group updated successfully"
         );
+        self.announce_update(&before, group).await;
 
         Ok(())
     }
@@ -441,6 +732,7 @@ impl EventReceiverGroupHandler {
         info!(group_id = %id, "Enabling event receiver group");
 
         let mut group = self.get_event_receiver_group_or_error(id).await?;
+        let before = group.clone();
 
         if group.enabled() {
             info!(group_id = %id, "Event receiver group is already enabled");
@@ -449,8 +741,11 @@ impl EventReceiverGroupHandler {
 
         group.enable();
         self.group_repository.update(&group).await?;
+        self.invalidate_schemas().await;
+        self.record_history(id).await;
 
         info!(group_id = %id, "Event receiver group enabled successfully");
+        self.announce_update(&before, group).await;
         Ok(id)
     }
 
@@ -462,6 +757,7 @@ impl EventReceiverGroupHandler {
         info!(group_id = %id, "Disabling event receiver group");
 
         let mut group = self.get_event_receiver_group_or_error(id).await?;
+        let before = group.clone();
 
         if !group.enabled() {
             info!(group_id = %id, "Event receiver group is already disabled");
@@ -470,41 +766,49 @@ impl EventReceiverGroupHandler {
 
         group.disable();
         self.group_repository.update(&group).await?;
+        self.invalidate_schemas().await;
+        self.record_history(id).await;
 
         info!(group_id = %id, "Event receiver group disabled successfully");
+        self.announce_update(&before, group).await;
         Ok(id)
     }
 
     /// Adds an event receiver to a group
+    ///
+    /// Returns the updated group.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - The group or the receiver does not exist
+    /// - The receiver is already in the group
+    /// - Database operation fails
     pub async fn add_event_receiver_to_group(
         &self,
         group_id: EventReceiverGroupId,
         receiver_id: EventReceiverId,
-    ) -> Result<()> {
+    ) -> Result<EventReceiverGroup> {
         info!(
             group_id = %group_id,
             receiver_id = %receiver_id,
             "Adding event receiver to group"
         );
 
-        // Verify the receiver exists
-        if self
-            .receiver_repository
-            .find_by_id(receiver_id)
-            .await?
-            .is_none()
-        {
-            return Err(DomainError::ReceiverNotFound.into());
-        }
+        self.ensure_receiver_exists(receiver_id).await?;
 
         // Get the group
         let mut group = self.get_event_receiver_group_or_error(group_id).await?;
+        let before = group.clone();
 
         // Add the receiver to the group
+        Self::ensure_not_in_group(&group, receiver_id)?;
         group.add_event_receiver(receiver_id)?;
 
         // Save the updated group
         self.group_repository.update(&group).await?;
+        self.invalidate_schemas().await;
+        self.record_history(group_id).await;
 
         info!(
             group_id = %group_id,
@@ -512,16 +816,19 @@ impl EventReceiverGroupHandler {
             receiver_count = %group.receiver_count(),
             "Event receiver added to group successfully"
         );
+        self.announce_update(&before, group.clone()).await;
 
-        Ok(())
+        Ok(group)
     }
 
     /// Removes an event receiver from a group
+    ///
+    /// Returns the updated group.
     pub async fn remove_event_receiver_from_group(
         &self,
         group_id: EventReceiverGroupId,
         receiver_id: EventReceiverId,
-    ) -> Result<()> {
+    ) -> Result<EventReceiverGroup> {
         info!(
             group_id = %group_id,
             receiver_id = %receiver_id,
@@ -530,12 +837,15 @@ impl EventReceiverGroupHandler {
 
         // Get the group
         let mut group = self.get_event_receiver_group_or_error(group_id).await?;
+        let before = group.clone();
 
         // Remove the receiver from the group
         group.remove_event_receiver(receiver_id)?;
 
         // Save the updated group
         self.group_repository.update(&group).await?;
+        self.invalidate_schemas().await;
+        self.record_history(group_id).await;
 
         info!(
             group_id = %group_id,
@@ -543,7 +853,138 @@ impl EventReceiverGroupHandler {
             receiver_count = %group.receiver_count(),
             "Event receiver removed from group successfully"
         );
+        self.announce_update(&before, group.clone()).await;
+
+        Ok(group)
+    }
+
+    /// Moves an event receiver from one group to another
+    ///
+    /// Both groups are saved in one repository call, so a failure leaves
+    /// the receiver in the source group. Returns the updated source and
+    /// destination groups.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - Either group or the receiver does not exist
+    /// - The source and destination are the same group
+    /// - The receiver is not in the source group or is already in the
+    ///   destination group
+    /// - Database operation fails
+    pub async fn move_event_receiver(
+        &self,
+        from_group_id: EventReceiverGroupId,
+        to_group_id: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<(EventReceiverGroup, EventReceiverGroup)> {
+        info!(
+            from_group_id = %from_group_id,
+            to_group_id = %to_group_id,
+            receiver_id = %receiver_id,
+            "Moving event receiver between groups"
+        );
+
+        if from_group_id == to_group_id {
+            return Err(DomainError::BusinessRuleViolation {
+                rule: Message::new("rule.group_move_same_group"),
+            }
+            .into());
+        }
+        self.ensure_receiver_exists(receiver_id).await?;
+
+        let mut from_group = self
+            .get_event_receiver_group_or_error(from_group_id)
+            .await?;
+        let mut to_group = self.get_event_receiver_group_or_error(to_group_id).await?;
+
+        let (from_before, to_before) = (from_group.clone(), to_group.clone());
+        from_group.remove_event_receiver(receiver_id)?;
+        Self::ensure_not_in_group(&to_group, receiver_id)?;
+        to_group.add_event_receiver(receiver_id)?;
+
+        let groups = [from_group, to_group];
+        self.group_repository.update_many(&groups).await?;
+        let [from_group, to_group] = groups;
+        self.invalidate_schemas().await;
+        self.record_history(from_group_id).await;
+        self.record_history(to_group_id).await;
+
+        info!(
+            from_group_id = %from_group_id,
+            to_group_id = %to_group_id,
+            receiver_id = %receiver_id,
+            "Event receiver moved successfully"
+        );
+        self.announce_update(&from_before, from_group.clone()).await;
+        self.announce_update(&to_before, to_group.clone()).await;
+
+        Ok((from_group, to_group))
+    }
+
+    /// Makes a group the receiver's default schema group
+    ///
+    /// The receiver inherits this group's default schema when its enabled
+    /// groups define conflicting ones. Replaces any previous default.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - The group does not exist
+    /// - The receiver is not in the group
+    /// - Database operation fails
+    pub async fn set_default_schema_group(
+        &self,
+        group_id: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<()> {
+        info!(
+            group_id = %group_id,
+            receiver_id = %receiver_id,
+            "Setting default schema group"
+        );
+
+        self.get_event_receiver_group_or_error(group_id).await?;
+        self.group_repository
+            .set_default_schema_group(receiver_id, group_id)
+            .await?;
+        self.invalidate_schemas().await;
+
+        Ok(())
+    }
+
+    /// Returns the receiver's default schema group, if it has one
+    pub async fn find_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<EventReceiverGroupId>> {
+        self.group_repository
+            .find_default_schema_group(receiver_id)
+            .await
+    }
+
+    /// Fails with `ReceiverNotFound` unless the receiver exists
+    async fn ensure_receiver_exists(&self, receiver_id: EventReceiverId) -> Result<()> {
+        if self
+            .receiver_repository
+            .find_by_id(receiver_id)
+            .await?
+            .is_none()
+        {
+            return Err(DomainError::ReceiverNotFound.into());
+        }
+        Ok(())
+    }
 
+    /// Fails with a conflict when the receiver is already in the group
+    fn ensure_not_in_group(group: &EventReceiverGroup, receiver_id: EventReceiverId) -> Result<()> {
+        if group.contains_receiver(receiver_id) {
+            return Err(DomainError::AlreadyExists {
+                entity: "group receiver".to_string(),
+                identifier: receiver_id.to_string(),
+            }
+            .into());
+        }
         Ok(())
     }
 
@@ -551,17 +992,31 @@ impl EventReceiverGroupHandler {
     pub async fn delete_event_receiver_group(&self, id: EventReceiverGroupId) -> Result<()> {
         info!(group_id = %id, "Deleting event receiver group");
 
-        // Check if the group exists
-        if self.group_repository.find_by_id(id).await?.is_none() {
+        let Some(group) = self.group_repository.find_by_id(id).await? else {
             return Err(DomainError::GroupNotFound.into());
-        }
+        };
 
         // TODO: Check if group is being referenced by any events
         // This should be done by checking with event repository
 
         self.group_repository.delete(id).await?;
+        let deleted_at = Utc::now();
+        self.invalidate_schemas().await;
+        self.record_history(id).await;
 
         info!(group_id = %id, "Event receiver group deleted successfully");
+        let system_event = self
+            .record_system_event(
+                GROUP_DELETED_EVENT,
+                id,
+                self.create_group_deleted_event(&group, deleted_at),
+            )
+            .await;
+        self.publish(DomainEvent::GroupDeleted {
+            group,
+            deleted_at,
+            system_event,
+        });
 
         Ok(())
     }
@@ -626,6 +1081,7 @@ impl EventReceiverGroupHandler {
     /// * `group_id` - The ID of the group
     /// * `user_id` - The ID of the user to add as a member
     /// * `added_by` - The ID of the user adding the member (typically the group owner)
+    /// * `expires_at` - When the membership ends; `None` keeps it until removed
     ///
     /// # Returns
     ///
@@ -636,6 +1092,7 @@ impl EventReceiverGroupHandler {
     /// Returns error if:
     /// - The group does not exist
     /// - The user is already a member
+    /// - `expires_at` is not in the future or is further ahead than allowed
     /// - Database operation fails
     ///
     /// # Examples
@@ -648,7 +1105,7 @@ impl EventReceiverGroupHandler {
     /// let group_id = EventReceiverGroupId::new();
     /// let user_id = UserId::new();
     /// let owner_id = UserId::new();
-    /// handler.add_group_member(group_id, user_id, owner_id).await?;
+    /// handler.add_group_member(group_id, user_id, owner_id, None).await?;
     /// # Ok(())
     /// # }
     /// ```
@@ -657,10 +1114,95 @@ impl EventReceiverGroupHandler {
         group_id: EventReceiverGroupId,
         user_id: UserId,
         added_by: UserId,
+        expires_at: Option<DateTime<Utc>>,
     ) -> Result<()> {
+        self.validate_membership_expiry(expires_at, Utc::now())?;
+        if self.group_repository.is_member(group_id, user_id).await? {
+            return Err(DomainError::AlreadyExists {
+                entity: "group member".to_string(),
+                identifier: user_id.to_string(),
+            }
+            .into());
+        }
+
         self.group_repository
-            .add_member(group_id, user_id, added_by)
-            .await
+            .add_member(group_id, user_id, added_by, expires_at)
+            .await?;
+        self.record_history(group_id).await;
+        self.publish(DomainEvent::GroupMembershipChanged {
+            group_id,
+            user_id,
+            change: MembershipChange::Added { expires_at },
+        });
+
+        Ok(())
+    }
+
+    /// Changes when a member's access ends
+    ///
+    /// `None` keeps the member until removed. Returns the updated
+    /// membership.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - The user is not a current member of the group
+    /// - `expires_at` is not in the future or is further ahead than allowed
+    /// - Database operation fails
+    pub async fn set_group_member_expiry(
+        &self,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<GroupMembership> {
+        self.validate_membership_expiry(expires_at, Utc::now())?;
+        self.group_repository
+            .set_member_expiry(group_id, user_id, expires_at)
+            .await?;
+        self.record_history(group_id).await;
+
+        let membership = self
+            .group_repository
+            .get_group_memberships(group_id)
+            .await?
+            .into_iter()
+            .find(|m| m.user_id == user_id)
+            .ok_or_else(|| crate::error::Error::NotFound {
+                resource: format!("User {} is not a member of group {}", user_id, group_id),
+            })?;
+        self.publish(DomainEvent::GroupMembershipChanged {
+            group_id,
+            user_id,
+            change: MembershipChange::ExpiryChanged { expires_at },
+        });
+
+        Ok(membership)
+    }
+
+    /// Rejects an expiry that has passed or lies beyond the allowed duration
+    fn validate_membership_expiry(
+        &self,
+        expires_at: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let Some(expires_at) = expires_at else {
+            return Ok(());
+        };
+
+        let message = if expires_at <= now {
+            Message::new("validation.membership_expiry_past")
+        } else if expires_at > now + self.max_membership_duration {
+            Message::new("validation.membership_expiry_too_far")
+                .with("max_days", self.max_membership_duration.num_days())
+        } else {
+            return Ok(());
+        };
+
+        Err(DomainError::ValidationError {
+            field: "expires_at".to_string(),
+            message,
+        }
+        .into())
     }
 
     /// Removes a member from an event receiver group
@@ -699,7 +1241,17 @@ impl EventReceiverGroupHandler {
         group_id: EventReceiverGroupId,
         user_id: UserId,
     ) -> Result<()> {
-        self.group_repository.remove_member(group_id, user_id).await
+        self.group_repository
+            .remove_member(group_id, user_id)
+            .await?;
+        self.record_history(group_id).await;
+        self.publish(DomainEvent::GroupMembershipChanged {
+            group_id,
+            user_id,
+            change: MembershipChange::Removed,
+        });
+
+        Ok(())
     }
 
     /// Gets all members of an event receiver group
@@ -735,6 +1287,20 @@ impl EventReceiverGroupHandler {
         self.group_repository.get_group_members(group_id).await
     }
 
+    /// Gets the memberships of a group with who added each member and when,
+    /// oldest first
+    pub async fn get_group_memberships(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<GroupMembership>> {
+        self.group_repository.get_group_memberships(group_id).await
+    }
+
+    /// Finds the groups a user is a member of
+    pub async fn find_groups_for_user(&self, user_id: UserId) -> Result<Vec<EventReceiverGroup>> {
+        self.group_repository.find_groups_for_user(user_id).await
+    }
+
     /// Checks if a user is a member of an event receiver group
     ///
     /// # Arguments
@@ -778,9 +1344,13 @@ impl EventReceiverGroupHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::handlers::KafkaForwarder;
     use crate::domain::entities::event_receiver::EventReceiver;
     use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
     use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
+    use crate::fixtures::ReceiverFixture;
+    use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
+    use crate::infrastructure::messaging::producer::EventPublisher;
     use async_trait::async_trait;
     use serde_json::json;
     use std::collections::HashMap;
@@ -947,9 +1517,14 @@ mod tests {
         }
         async fn find_by_event_receiver_id(
             &self,
-            _receiver_id: EventReceiverId,
+            receiver_id: EventReceiverId,
         ) -> Result<Vec<EventReceiverGroup>> {
-            Ok(vec![])
+            let groups = self.groups.lock().unwrap();
+            Ok(groups
+                .values()
+                .filter(|g| g.contains_receiver(receiver_id))
+                .cloned()
+                .collect())
         }
         async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<EventReceiverGroup>> {
             Ok(vec![])
@@ -999,6 +1574,21 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn find_default_schema_group(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<EventReceiverGroupId>> {
+            Ok(None)
+        }
+
+        async fn set_default_schema_group(
+            &self,
+            _receiver_id: EventReceiverId,
+            _group_id: EventReceiverGroupId,
+        ) -> Result<()> {
+            Ok(())
+        }
+
         async fn find_by_owner(
             &self,
             _owner_id: crate::domain::value_objects::UserId,
@@ -1045,15 +1635,42 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_group_memberships(
+            &self,
+            _group_id: EventReceiverGroupId,
+        ) -> Result<Vec<crate::domain::repositories::event_receiver_group_repo::GroupMembership>>
+        {
+            Ok(vec![])
+        }
+
         async fn add_member(
             &self,
             _group_id: EventReceiverGroupId,
             _user_id: crate::domain::value_objects::UserId,
             _added_by: crate::domain::value_objects::UserId,
+            _expires_at: Option<DateTime<Utc>>,
         ) -> Result<()> {
             Ok(())
         }
 
+        async fn set_member_expiry(
+            &self,
+            _group_id: EventReceiverGroupId,
+            _user_id: crate::domain::value_objects::UserId,
+            _expires_at: Option<DateTime<Utc>>,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_expired_members(
+            &self,
+            _now: DateTime<Utc>,
+            _limit: usize,
+        ) -> Result<Vec<crate::domain::repositories::event_receiver_group_repo::ExpiredMembership>>
+        {
+            Ok(vec![])
+        }
+
         async fn remove_member(
             &self,
             _group_id: EventReceiverGroupId,
@@ -1077,15 +1694,7 @@ mod tests {
         let handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone());
 
         // Create a mock receiver
-        let receiver = EventReceiver::new(
-            "Test Receiver".to_string(),
-            "webhook".to_string(),
-            "1.0.0".to_string(),
-            "A test receiver".to_string(),
-            json!({"type": "object"}),
-            crate::domain::value_objects::UserId::new(),
-        )
-        .unwrap();
+        let receiver = ReceiverFixture::new().build();
         let receiver_id = receiver.id();
         receiver_repo.add_receiver(receiver);
 
@@ -1097,6 +1706,9 @@ mod tests {
                 "A test group".to_string(),
                 true,
                 vec![receiver_id],
+                Vec::new(),
+                None,
+                None,
                 crate::domain::value_objects::UserId::new(),
             )
             .await;
@@ -1113,6 +1725,250 @@ mod tests {
         assert_eq!(group.receiver_count(), 1);
     }
 
+    #[tokio::test]
+    async fn test_create_group_succeeds_when_system_event_construction_fails() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let handler =
+            EventReceiverGroupHandler::new(group_repo, receiver_repo.clone())
+                .with_event_bus(bus)
+                .with_metrics(metrics.clone())
+                .with_system_event_factory(SystemEventFactory::new().with_constructor(|_| {
+                    Err(DomainError::InvalidData("forced failure".to_string()))
+                }));
+
+        let receiver = ReceiverFixture::new().build();
+        let receiver_id = receiver.id();
+        receiver_repo.add_receiver(receiver);
+
+        let group_id = handler
+            .create_event_receiver_group(
+                "Test Group".to_string(),
+                "webhook_group".to_string(),
+                "1.0.0".to_string(),
+                "A test group".to_string(),
+                true,
+                vec![receiver_id],
+                Vec::new(),
+                None,
+                None,
+                crate::domain::value_objects::UserId::new(),
+            )
+            .await
+            .unwrap();
+
+        assert!(handler
+            .get_event_receiver_group(group_id)
+            .await
+            .unwrap()
+            .is_some());
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_system_event_failures_total{event_type=\"xzepr.event.receiver.group.created\"} 1"
+        ));
+
+        // The group is still announced, without a system event to forward
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::GroupCreated {
+                group,
+                system_event,
+            } => {
+                assert_eq!(group.id(), group_id);
+                assert!(system_event.is_none());
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_group_changes_are_announced_after_they_are_stored() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let handler =
+            EventReceiverGroupHandler::new(group_repo, receiver_repo.clone()).with_event_bus(bus);
+
+        let receiver = ReceiverFixture::new().build();
+        let receiver_id = receiver.id();
+        receiver_repo.add_receiver(receiver);
+
+        let group_id = handler
+            .create_event_receiver_group(
+                "Test Group".to_string(),
+                "webhook_group".to_string(),
+                "1.0.0".to_string(),
+                "A test group".to_string(),
+                true,
+                vec![receiver_id],
+                Vec::new(),
+                None,
+                None,
+                crate::domain::value_objects::UserId::new(),
+            )
+            .await
+            .unwrap();
+        handler
+            .disable_event_receiver_group(group_id)
+            .await
+            .unwrap();
+        handler.delete_event_receiver_group(group_id).await.unwrap();
+
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::GroupCreated {
+                group,
+                system_event: Some(system_event),
+            } => {
+                assert_eq!(group.id(), group_id);
+                assert_eq!(system_event.name(), GROUP_CREATED_EVENT);
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::GroupUpdated {
+                group,
+                changed_fields,
+                system_event: Some(system_event),
+            } => {
+                assert!(!group.enabled());
+                assert_eq!(changed_fields, &["enabled"]);
+                assert_eq!(system_event.name(), GROUP_UPDATED_EVENT);
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::GroupDeleted {
+                group,
+                system_event: Some(system_event),
+                ..
+            } => {
+                assert_eq!(group.id(), group_id);
+                assert!(!group.enabled());
+                assert_eq!(system_event.name(), GROUP_DELETED_EVENT);
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+        assert!(notifications.try_recv().is_err());
+    }
+
+    /// Publisher recording each message with its key
+    #[derive(Default)]
+    struct CapturingPublisher {
+        messages: Mutex<Vec<(String, CloudEventMessage)>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for CapturingPublisher {
+        async fn publish(&self, _event: &Event) -> Result<()> {
+            unreachable!("group changes are published as prepared messages")
+        }
+
+        async fn publish_to(&self, _topic: &str, _event: &Event) -> Result<()> {
+            unreachable!("group changes are published as prepared messages")
+        }
+
+        async fn publish_message(&self, _message: &CloudEventMessage) -> Result<()> {
+            unreachable!("group changes are published under the group id")
+        }
+
+        async fn publish_message_with_key(
+            &self,
+            key: &str,
+            message: &CloudEventMessage,
+        ) -> Result<()> {
+            self.messages
+                .lock()
+                .unwrap()
+                .push((key.to_string(), message.clone()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_group_lifecycle_is_published_in_order_under_the_group_id() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+        let bus = DomainEventBus::new();
+        let publisher = Arc::new(CapturingPublisher::default());
+        bus.register(Arc::new(KafkaForwarder::new(publisher.clone())));
+        let handler =
+            EventReceiverGroupHandler::new(group_repo, receiver_repo.clone()).with_event_bus(bus);
+
+        let receiver = ReceiverFixture::new().build();
+        let receiver_id = receiver.id();
+        receiver_repo.add_receiver(receiver);
+
+        let group_id = handler
+            .create_event_receiver_group(
+                "Test Group".to_string(),
+                "webhook_group".to_string(),
+                "1.0.0".to_string(),
+                "A test group".to_string(),
+                true,
+                vec![receiver_id],
+                Vec::new(),
+                None,
+                None,
+                crate::domain::value_objects::UserId::new(),
+            )
+            .await
+            .unwrap();
+        handler
+            .update_event_receiver_group(
+                group_id,
+                UpdateEventReceiverGroupParams {
+                    description: Some("Renamed pipeline".to_string()),
+                    version: Some("1.1.0".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        handler.delete_event_receiver_group(group_id).await.unwrap();
+
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+        while publisher.messages.lock().unwrap().len() < 3 {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "events not published"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let messages = publisher.messages.lock().unwrap();
+        let published: Vec<_> = messages
+            .iter()
+            .map(|(key, message)| (key.as_str(), message.event_type.as_str()))
+            .collect();
+        let key = group_id.to_string();
+        assert_eq!(
+            published,
+            [
+                (key.as_str(), GROUP_CREATED_EVENT),
+                (key.as_str(), GROUP_UPDATED_EVENT),
+                (key.as_str(), GROUP_DELETED_EVENT),
+            ]
+        );
+
+        let updated = &messages[1].1.data.events[0];
+        assert_eq!(
+            updated.payload()["changed_fields"],
+            json!(["description", "version"])
+        );
+        assert_eq!(updated.payload()["group"]["version"], "1.1.0");
+        let deleted = &messages[2].1.data.events[0];
+        assert_eq!(
+            deleted.payload()["group"]["description"],
+            "Renamed pipeline"
+        );
+        assert!(deleted.payload()["deleted_at"].is_string());
+        assert_eq!(messages[2].1.data.event_receiver_groups[0].id(), group_id);
+    }
+
     #[tokio::test]
     async fn test_create_group_with_nonexistent_receiver() {
         let receiver_repo = Arc::new(MockEventReceiverRepository::new());
@@ -1129,6 +1985,9 @@ mod tests {
                 "A test group".to_string(),
                 true,
                 vec![nonexistent_receiver_id],
+                Vec::new(),
+                None,
+                None,
                 crate::domain::value_objects::UserId::new(),
             )
             .await;
@@ -1143,15 +2002,7 @@ mod tests {
         let handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone());
 
         // Create a mock receiver
-        let receiver = EventReceiver::new(
-            "Test Receiver".to_string(),
-            "webhook".to_string(),
-            "1.0.0".to_string(),
-            "A test receiver".to_string(),
-            json!({"type": "object"}),
-            crate::domain::value_objects::UserId::new(),
-        )
-        .unwrap();
+        let receiver = ReceiverFixture::new().build();
         let receiver_id = receiver.id();
         receiver_repo.add_receiver(receiver);
 
@@ -1164,6 +2015,9 @@ mod tests {
                 "A test group".to_string(),
                 false,
                 vec![receiver_id],
+                Vec::new(),
+                None,
+                None,
                 crate::domain::value_objects::UserId::new(),
             )
             .await
@@ -1188,4 +2042,313 @@ mod tests {
         let result = handler.get_event_receiver_group_or_error(group_id).await;
         assert!(result.is_err());
     }
+
+    fn add_schemaless_receiver(receiver_repo: &MockEventReceiverRepository) -> EventReceiver {
+        let receiver = ReceiverFixture::new()
+            .name("Schemaless Receiver")
+            .description("Receiver without its own schema")
+            .schema(json!({}))
+            .build();
+        receiver_repo.add_receiver(receiver.clone());
+        receiver
+    }
+
+    async fn create_group_with_schema(
+        handler: &EventReceiverGroupHandler,
+        name: &str,
+        receiver_id: EventReceiverId,
+        default_schema: serde_json::Value,
+    ) -> EventReceiverGroupId {
+        handler
+            .create_event_receiver_group(
+                name.to_string(),
+                "webhook_group".to_string(),
+                "1.0.0".to_string(),
+                "Group with default schema".to_string(),
+                true,
+                vec![receiver_id],
+                Vec::new(),
+                Some(default_schema),
+                None,
+                crate::domain::value_objects::UserId::new(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_group_default_schema_inherited() {
+        use crate::application::handlers::EventReceiverHandler;
+        use crate::domain::entities::schema_inheritance::SchemaSource;
+
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+        let resolver = SchemaResolver::new(group_repo.clone());
+        let handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone())
+            .with_schema_resolver(resolver.clone());
+        let receiver_handler =
+            EventReceiverHandler::new(receiver_repo.clone()).with_schema_resolver(resolver.clone());
+
+        let receiver = add_schemaless_receiver(&receiver_repo);
+        let group_id = create_group_with_schema(
+            &handler,
+            "builds",
+            receiver.id(),
+            json!({"required": ["build_id"]}),
+        )
+        .await;
+
+        let resolved = resolver.resolve(&receiver).await.unwrap();
+        assert_eq!(resolved.source(), SchemaSource::Inherited);
+        assert!(receiver_handler
+            .validate_event_payload(receiver.id(), &json!({"build_id": "b1"}))
+            .await
+            .is_ok());
+        assert!(receiver_handler
+            .validate_event_payload(receiver.id(), &json!({"other": 1}))
+            .await
+            .is_err());
+
+        // A receiver's own schema overrides the group default
+        let own = ReceiverFixture::new()
+            .name("Own Schema Receiver")
+            .description("Receiver with its own schema")
+            .build();
+        receiver_repo.add_receiver(own.clone());
+        handler
+            .add_event_receiver_to_group(group_id, own.id())
+            .await
+            .unwrap();
+        assert_eq!(
+            resolver.resolve(&own).await.unwrap().source(),
+            SchemaSource::Own
+        );
+        assert!(receiver_handler
+            .validate_event_payload(own.id(), &json!({"other": 1}))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_group_schemas_flagged_ambiguous() {
+        use crate::domain::entities::schema_inheritance::SchemaSource;
+
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+        let resolver = SchemaResolver::new(group_repo.clone());
+        let handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone())
+            .with_schema_resolver(resolver.clone());
+
+        let receiver = add_schemaless_receiver(&receiver_repo);
+        create_group_with_schema(
+            &handler,
+            "builds",
+            receiver.id(),
+            json!({"required": ["a"]}),
+        )
+        .await;
+        let second = create_group_with_schema(
+            &handler,
+            "deploys",
+            receiver.id(),
+            json!({"required": ["b"]}),
+        )
+        .await;
+
+        let resolved = resolver.resolve(&receiver).await.unwrap();
+        assert_eq!(resolved.source(), SchemaSource::Ambiguous);
+        assert_eq!(resolved.group_ids().len(), 2);
+
+        // Disabling one of the groups resolves the conflict
+        handler.disable_event_receiver_group(second).await.unwrap();
+        let resolved = resolver.resolve(&receiver).await.unwrap();
+        assert_eq!(resolved.source(), SchemaSource::Inherited);
+    }
+
+    #[tokio::test]
+    async fn test_default_schema_group_settles_conflict() {
+        use crate::domain::entities::schema_inheritance::SchemaSource;
+        use crate::infrastructure::memory::InMemoryEventReceiverGroupRepository;
+
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let group_repo = Arc::new(InMemoryEventReceiverGroupRepository::new());
+        let resolver = SchemaResolver::new(group_repo.clone());
+        let handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone())
+            .with_schema_resolver(resolver.clone());
+
+        let receiver = add_schemaless_receiver(&receiver_repo);
+        let builds = create_group_with_schema(
+            &handler,
+            "builds",
+            receiver.id(),
+            json!({"required": ["a"]}),
+        )
+        .await;
+        let deploys = create_group_with_schema(
+            &handler,
+            "deploys",
+            receiver.id(),
+            json!({"required": ["b"]}),
+        )
+        .await;
+        assert_eq!(
+            resolver.resolve(&receiver).await.unwrap().source(),
+            SchemaSource::Ambiguous
+        );
+
+        handler
+            .set_default_schema_group(builds, receiver.id())
+            .await
+            .unwrap();
+        let resolved = resolver.resolve(&receiver).await.unwrap();
+        assert_eq!(resolved.source(), SchemaSource::Inherited);
+        assert_eq!(resolved.group_ids(), &[builds]);
+
+        // Setting another default replaces the first
+        handler
+            .set_default_schema_group(deploys, receiver.id())
+            .await
+            .unwrap();
+        let resolved = resolver.resolve(&receiver).await.unwrap();
+        assert_eq!(resolved.group_ids(), &[deploys]);
+        assert_eq!(
+            handler
+                .find_default_schema_group(receiver.id())
+                .await
+                .unwrap(),
+            Some(deploys)
+        );
+
+        let err = handler
+            .set_default_schema_group(EventReceiverGroupId::new(), receiver.id())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Domain(DomainError::GroupNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_schema_cache_invalidated_on_group_update() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+        let resolver = SchemaResolver::new(group_repo.clone());
+        let handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone())
+            .with_schema_resolver(resolver.clone());
+
+        let receiver = add_schemaless_receiver(&receiver_repo);
+        let group_id = create_group_with_schema(
+            &handler,
+            "builds",
+            receiver.id(),
+            json!({"required": ["a"]}),
+        )
+        .await;
+
+        let resolved = resolver.resolve(&receiver).await.unwrap();
+        assert_eq!(resolved.schema(), &json!({"required": ["a"]}));
+        assert_eq!(resolver.cached_len().await, 1);
+
+        handler
+            .update_event_receiver_group(
+                group_id,
+                UpdateEventReceiverGroupParams {
+                    default_schema: Some(json!({"required": ["b"]})),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(resolver.cached_len().await, 0);
+
+        let resolved = resolver.resolve(&receiver).await.unwrap();
+        assert_eq!(resolved.schema(), &json!({"required": ["b"]}));
+
+        // Membership changes invalidate as well
+        handler
+            .remove_event_receiver_from_group(group_id, receiver.id())
+            .await
+            .unwrap();
+        assert_eq!(resolver.cached_len().await, 0);
+        let resolved = resolver.resolve(&receiver).await.unwrap();
+        assert_eq!(resolved.schema(), &json!({}));
+    }
+
+    /// Records every topic it is asked to create
+    #[derive(Default)]
+    struct MockTopicProvisioner {
+        topics: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl TopicProvisioner for MockTopicProvisioner {
+        async fn ensure_topic(&self, topic: &str) -> Result<bool> {
+            self.topics.lock().unwrap().push(topic.to_string());
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dedicated_topic_is_provisioned() {
+        let receiver_repo = Arc::new(MockEventReceiverRepository::new());
+        let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+        let provisioner = Arc::new(MockTopicProvisioner::default());
+        let handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone())
+            .with_topic_provisioner(provisioner.clone());
+        let receiver = add_schemaless_receiver(&receiver_repo);
+
+        let create = |topic: &str| {
+            handler.create_event_receiver_group(
+                "Builds".to_string(),
+                "build_group".to_string(),
+                "1.0.0".to_string(),
+                "Build receivers".to_string(),
+                true,
+                vec![receiver.id()],
+                Vec::new(),
+                None,
+                Some(topic.to_string()),
+                UserId::new(),
+            )
+        };
+
+        let err = create("builds/ci").await.unwrap_err();
+        assert!(err.to_string().contains("dedicated_topic"), "{}", err);
+        assert!(provisioner.topics.lock().unwrap().is_empty());
+
+        let group_id = create("xzepr.builds").await.unwrap();
+        let group = handler
+            .get_event_receiver_group(group_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(group.dedicated_topic(), Some("xzepr.builds"));
+
+        // Unchanged topics are not provisioned again, and clearing the
+        // topic provisions nothing
+        for topic in ["xzepr.builds", "xzepr.releases", ""] {
+            handler
+                .update_event_receiver_group(
+                    group_id,
+                    UpdateEventReceiverGroupParams {
+                        dedicated_topic: Some(topic.to_string()),
+                        ..Default::default()
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        let group = handler
+            .get_event_receiver_group(group_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(group.dedicated_topic(), None);
+        assert_eq!(
+            *provisioner.topics.lock().unwrap(),
+            vec!["xzepr.builds", "xzepr.releases"]
+        );
+    }
 }
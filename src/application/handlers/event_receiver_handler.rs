@@ -3,25 +3,89 @@
 
 // src/application/handlers/event_receiver_handler.rs
 
+use crate::application::domain_events::{DomainEvent, DomainEventBus};
+use crate::application::handlers::description_renderer::DescriptionRenderer;
+use crate::application::handlers::receiver_timeline_handler::ReceiverTimelineHandler;
+use crate::application::handlers::resource_history_handler::ResourceHistoryHandler;
+use crate::application::handlers::schema_resolver::SchemaResolver;
+use crate::application::handlers::system_events::{
+    changed_fields, report_system_event_failure, SystemEventFactory, RECEIVER_AUTO_DISABLED_EVENT,
+    RECEIVER_CREATED_EVENT, RECEIVER_DELETED_EVENT, RECEIVER_UPDATED_EVENT,
+};
+use crate::auth::api_key::ApiKeyRepository;
 use crate::domain::entities::event::Event;
+use crate::domain::entities::event_name_constraint::AllowedEventNames;
 use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::payload_transform::PayloadTransform;
+use crate::domain::entities::receiver_activity::{principal_window_start, ReceiverActivity};
+use crate::domain::entities::receiver_auto_disable::{AutoDisablePolicy, AutoDisableTrigger};
+use crate::domain::entities::receiver_clone::{
+    clone_receiver, ReceiverCloneManifest, ReceiverCloneOptions, ReceiverCloneOverrides,
+};
+use crate::domain::entities::receiver_deletion::{
+    DeletionBlocker, DeletionCache, ReceiverDeletionOutcome, ReceiverDeletionReport,
+    DEFAULT_RECENT_EVENT_DAYS,
+};
+use crate::domain::entities::receiver_heartbeat::ReceiverHeartbeat;
+use crate::domain::entities::schema_inheritance::ResolvedSchema;
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
 use crate::domain::repositories::event_receiver_repo::{
     EventReceiverRepository, FindEventReceiverCriteria,
 };
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::domain::repositories::pagination::{PaginationParams, DEFAULT_PAGE_SIZE};
+use crate::domain::repositories::receiver_activity_repo::ReceiverActivityRepository;
+use crate::domain::repositories::receiver_heartbeat_repo::{
+    HeartbeatWrite, ReceiverHeartbeatRepository,
+};
+use crate::domain::value_objects::description::{
+    normalize_description, DEFAULT_MAX_DESCRIPTION_LENGTH,
+};
 #[allow(unused_imports)]
-use crate::domain::value_objects::{EventReceiverId, UserId};
-use crate::error::{DomainError, Result};
-use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
-use crate::infrastructure::messaging::producer::KafkaEventPublisher;
-
+use crate::domain::value_objects::{EventReceiverId, UserId, Version, VersionStrictness};
+use crate::error::{DomainError, Error, Result};
+use crate::i18n::Message;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::PrometheusMetrics;
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{error, info, warn};
+use tracing::{info, warn};
+
+/// Default minimum number of seconds between stored heartbeats of a receiver
+pub const DEFAULT_HEARTBEAT_INTERVAL_SECONDS: u64 = 60;
+
+/// Repositories holding what references a receiver
+///
+/// Deleting a receiver checks them for recent events and scoped API keys,
+/// removes the receiver from its groups, and revokes its keys.
+#[derive(Clone)]
+pub struct ReceiverDependents {
+    pub events: Arc<dyn EventRepository>,
+    pub groups: Arc<dyn EventReceiverGroupRepository>,
+    pub api_keys: Arc<dyn ApiKeyRepository>,
+}
 
 /// Application service for handling event receiver operations
 #[derive(Clone)]
 pub struct EventReceiverHandler {
     repository: Arc<dyn EventReceiverRepository>,
-    event_publisher: Option<Arc<KafkaEventPublisher>>,
+    event_bus: Option<DomainEventBus>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    system_events: SystemEventFactory,
+    schema_resolver: Option<SchemaResolver>,
+    activity_repository: Option<Arc<dyn ReceiverActivityRepository>>,
+    heartbeat_repository: Option<Arc<dyn ReceiverHeartbeatRepository>>,
+    heartbeat_interval: Duration,
+    version_strictness: VersionStrictness,
+    max_description_length: usize,
+    descriptions: DescriptionRenderer<EventReceiverId>,
+    history: Option<ResourceHistoryHandler>,
+    timeline: Option<ReceiverTimelineHandler>,
+    dependents: Option<ReceiverDependents>,
+    recent_event_age: Duration,
+    audit_logger: Arc<AuditLogger>,
 }
 
 impl EventReceiverHandler {
@@ -29,21 +93,184 @@ impl EventReceiverHandler {
     pub fn new(repository: Arc<dyn EventReceiverRepository>) -> Self {
         Self {
             repository,
-            event_publisher: None,
+            event_bus: None,
+            metrics: None,
+            system_events: SystemEventFactory::new(),
+            schema_resolver: None,
+            activity_repository: None,
+            heartbeat_repository: None,
+            heartbeat_interval: Duration::seconds(DEFAULT_HEARTBEAT_INTERVAL_SECONDS as i64),
+            version_strictness: VersionStrictness::default(),
+            max_description_length: DEFAULT_MAX_DESCRIPTION_LENGTH,
+            descriptions: DescriptionRenderer::new(),
+            history: None,
+            timeline: None,
+            dependents: None,
+            recent_event_age: Duration::days(DEFAULT_RECENT_EVENT_DAYS as i64),
+            audit_logger: Arc::new(AuditLogger::new()),
         }
     }
 
-    /// Creates a new event receiver handler with event publisher
-    pub fn with_publisher(
-        repository: Arc<dyn EventReceiverRepository>,
-        event_publisher: Arc<KafkaEventPublisher>,
+    /// Announces receiver changes on the domain event bus once they are
+    /// stored
+    pub fn with_event_bus(mut self, event_bus: DomainEventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Attaches metrics used to count system event failures
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Enables group default schema inheritance
+    pub fn with_schema_resolver(mut self, schema_resolver: SchemaResolver) -> Self {
+        self.schema_resolver = Some(schema_resolver);
+        self
+    }
+
+    /// Enables lookups of receiver liveness metadata
+    pub fn with_activity(
+        mut self,
+        activity_repository: Arc<dyn ReceiverActivityRepository>,
     ) -> Self {
-        Self {
-            repository,
-            event_publisher: Some(event_publisher),
+        self.activity_repository = Some(activity_repository);
+        self
+    }
+
+    /// Enables producer heartbeats, storing at most one per receiver every
+    /// `interval_seconds`
+    pub fn with_heartbeats(
+        mut self,
+        heartbeat_repository: Arc<dyn ReceiverHeartbeatRepository>,
+        interval_seconds: u64,
+    ) -> Self {
+        self.heartbeat_repository = Some(heartbeat_repository);
+        self.heartbeat_interval = Duration::seconds(interval_seconds as i64);
+        self
+    }
+
+    /// Records a snapshot of every receiver after each write
+    pub fn with_history(mut self, history: ResourceHistoryHandler) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Returns the receiver history, if one is attached
+    pub fn history(&self) -> Option<&ResourceHistoryHandler> {
+        self.history.as_ref()
+    }
+
+    /// Enables receiver timelines
+    pub fn with_timeline(mut self, timeline: ReceiverTimelineHandler) -> Self {
+        self.timeline = Some(timeline);
+        self
+    }
+
+    /// Checks and cleans up what references a receiver when it is deleted
+    ///
+    /// Without dependents a receiver is deleted without checking for events
+    /// or keys, and group memberships are only dropped by the repository.
+    pub fn with_dependents(mut self, dependents: ReceiverDependents) -> Self {
+        self.dependents = Some(dependents);
+        self
+    }
+
+    /// Sets how recent an event must be to block deleting its receiver
+    pub fn with_recent_event_age(mut self, age: Duration) -> Self {
+        self.recent_event_age = age;
+        self
+    }
+
+    /// Uses `audit_logger` to record deletions and key revocations
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Returns the receiver timeline, if one is attached
+    pub fn timeline(&self) -> Option<&ReceiverTimelineHandler> {
+        self.timeline.as_ref()
+    }
+
+    /// Sets how strictly new and updated versions must be semantic versions
+    pub fn with_version_strictness(mut self, strictness: VersionStrictness) -> Self {
+        self.version_strictness = strictness;
+        self
+    }
+
+    /// Sets the longest description, in characters, new and updated
+    /// receivers may have
+    pub fn with_max_description_length(mut self, max_length: usize) -> Self {
+        self.max_description_length = max_length;
+        self
+    }
+
+    /// Sets the factory that builds and stores system events
+    pub fn with_system_event_factory(mut self, factory: SystemEventFactory) -> Self {
+        self.system_events = factory;
+        self
+    }
+
+    /// Publishes a stored change to the domain event bus, if one is attached
+    fn publish(&self, event: DomainEvent) {
+        if let Some(bus) = &self.event_bus {
+            bus.publish(event);
+        }
+    }
+
+    /// Records the stored state of a receiver, if history is attached
+    async fn record_history(&self, receiver: &EventReceiver) {
+        if let Some(history) = &self.history {
+            history.record_receiver(receiver, Utc::now()).await;
+        }
+    }
+
+    /// Stores a built system event, or reports why it could not be built
+    ///
+    /// The change it describes is already saved, so a failure never fails
+    /// the request; the change is then announced without a system event.
+    async fn record_system_event(
+        &self,
+        event_type: &str,
+        receiver_id: EventReceiverId,
+        built: std::result::Result<Event, DomainError>,
+    ) -> Option<Event> {
+        match built {
+            Ok(system_event) => {
+                self.system_events.record(&system_event).await;
+                Some(system_event)
+            }
+            Err(e) => {
+                report_system_event_failure(
+                    self.metrics.as_deref(),
+                    event_type,
+                    &receiver_id.to_string(),
+                    &e,
+                );
+                None
+            }
         }
     }
 
+    /// Announces a stored update with the fields changed since `before`
+    async fn announce_update(&self, before: &EventReceiver, receiver: EventReceiver) {
+        let changed_fields = changed_fields(before, &receiver);
+        let system_event = self
+            .record_system_event(
+                RECEIVER_UPDATED_EVENT,
+                receiver.id(),
+                self.create_receiver_updated_event(&receiver, &changed_fields),
+            )
+            .await;
+        self.publish(DomainEvent::ReceiverUpdated {
+            receiver,
+            changed_fields,
+            system_event,
+        });
+    }
+
     /// Creates a new event receiver
     pub async fn create_event_receiver(
         &self,
@@ -61,10 +288,59 @@ impl EventReceiverHandler {
             "Creating new event receiver"
         );
 
+        Version::parse(&version, self.version_strictness)?;
+        let description = normalize_description(&description, self.max_description_length)?;
+
         // Check if a receiver with the same name and type already exists
+        self.ensure_unique(&name, &receiver_type).await?;
+
+        // Create the domain entity
+        let event_receiver =
+            EventReceiver::new(name, receiver_type, version, description, schema, owner_id)?;
+
+        self.insert_event_receiver(event_receiver).await
+    }
+
+    /// Creates a new receiver from the configuration of `source`
+    ///
+    /// The clone is validated, checked for uniqueness, recorded in history,
+    /// and announced exactly like a receiver from
+    /// [`Self::create_event_receiver`], and its configuration is saved in a
+    /// single write.
+    pub async fn clone_event_receiver(
+        &self,
+        source: &EventReceiver,
+        mut overrides: ReceiverCloneOverrides,
+        options: ReceiverCloneOptions,
+        owner_id: UserId,
+    ) -> Result<(EventReceiver, ReceiverCloneManifest)> {
+        info!(
+            source_id = %source.id(),
+            name = %overrides.name,
+            "Cloning event receiver"
+        );
+
+        if let Some(ref version) = overrides.version {
+            Version::parse(version, self.version_strictness)?;
+        }
+        overrides.description = overrides
+            .description
+            .map(|description| normalize_description(&description, self.max_description_length))
+            .transpose()?;
+
+        let (event_receiver, manifest) = clone_receiver(source, overrides, options, owner_id)?;
+        self.ensure_unique(event_receiver.name(), event_receiver.receiver_type())
+            .await?;
+        self.insert_event_receiver(event_receiver.clone()).await?;
+
+        Ok((event_receiver, manifest))
+    }
+
+    /// Rejects a name and type already used by another receiver
+    async fn ensure_unique(&self, name: &str, receiver_type: &str) -> Result<()> {
         if self
             .repository
-            .exists_by_name_and_type(&name, &receiver_type)
+            .exists_by_name_and_type(name, receiver_type)
             .await?
         {
             warn!(
@@ -73,19 +349,23 @@ impl EventReceiverHandler {
                 "Event receiver with same name and type already exists"
             );
             return Err(DomainError::BusinessRuleViolation {
-                rule: "Event receiver with the same name and type already exists".to_string(),
+                rule: Message::new("rule.receiver_exists"),
             }
             .into());
         }
+        Ok(())
+    }
 
-        // Create the domain entity
-        let event_receiver =
-            EventReceiver::new(name, receiver_type, version, description, schema, owner_id)?;
-
+    /// Saves a new receiver, records it, and announces it
+    async fn insert_event_receiver(
+        &self,
+        event_receiver: EventReceiver,
+    ) -> Result<EventReceiverId> {
         let receiver_id = event_receiver.id();
 
         // Save to repository
         self.repository.save(&event_receiver).await?;
+        self.record_history(&event_receiver).await;
 
         info!(
             receiver_id = %receiver_id,
@@ -93,33 +373,28 @@ impl EventReceiverHandler {
             "Event receiver created successfully"
         );
 
-        // Publish system event to Kafka if publisher is configured
-        if let Some(publisher) = &self.event_publisher {
-            let system_event = self.create_receiver_created_event(&event_receiver);
-            let message =
-                CloudEventMessage::from_event_with_receiver(&system_event, &event_receiver);
-            if let Err(e) = publisher.publish_message(&message).await {
-                error!(
-                    receiver_id = %receiver_id,
-                    error = %e,
-                    "Failed to publish receiver creation event to Kafka"
-                );
-                // Note: We don't fail the request since the receiver was saved to the database
-            } else {
-                info!(
-                    receiver_id = %receiver_id,
-                    event_id = %system_event.id(),
-                    "Receiver creation event published to Kafka successfully"
-                );
-            }
-        }
+        // Record the system event and announce the receiver with it;
+        // subscribers such as the Kafka forwarder publish it
+        let system_event = self
+            .record_system_event(
+                RECEIVER_CREATED_EVENT,
+                receiver_id,
+                self.create_receiver_created_event(&event_receiver),
+            )
+            .await;
+        self.publish(DomainEvent::ReceiverCreated {
+            receiver: event_receiver,
+            system_event,
+        });
 
         Ok(receiver_id)
     }
 
     /// Creates a system event for receiver creation
-    fn create_receiver_created_event(&self, receiver: &EventReceiver) -> Event {
-        use crate::domain::entities::event::CreateEventParams;
+    fn create_receiver_created_event(
+        &self,
+        receiver: &EventReceiver,
+    ) -> std::result::Result<Event, DomainError> {
         use serde_json::json;
 
         let payload = json!({
@@ -131,20 +406,59 @@ impl EventReceiverHandler {
             "description": receiver.description(),
         });
 
-        // Create event - unwrap is safe here as we control all inputs
-        Event::new(CreateEventParams {
-            name: "xzepr.event.receiver.created".to_string(),
-            version: "1.0.0".to_string(),
-            release: "system".to_string(),
-            platform_id: "xzepr".to_string(),
-            package: "xzepr.system".to_string(),
-            description: format!("Event receiver '{}' created", receiver.name()),
+        self.system_events.build(
+            RECEIVER_CREATED_EVENT,
+            format!("Event receiver '{}' created", receiver.name()),
+            payload,
+            receiver.id(),
+            receiver.owner_id(),
+        )
+    }
+
+    /// Creates a system event for a receiver update
+    fn create_receiver_updated_event(
+        &self,
+        receiver: &EventReceiver,
+        changed_fields: &[String],
+    ) -> std::result::Result<Event, DomainError> {
+        use serde_json::json;
+
+        let payload = json!({
+            "receiver_id": receiver.id().to_string(),
+            "receiver": receiver,
+            "changed_fields": changed_fields,
+        });
+
+        self.system_events.build(
+            RECEIVER_UPDATED_EVENT,
+            format!("Event receiver '{}' updated", receiver.name()),
             payload,
-            success: true,
-            receiver_id: receiver.id(),
-            owner_id: receiver.owner_id(),
-        })
-        .expect("Failed to create system event for receiver creation")
+            receiver.id(),
+            receiver.owner_id(),
+        )
+    }
+
+    /// Creates a system event for a receiver deletion
+    fn create_receiver_deleted_event(
+        &self,
+        receiver: &EventReceiver,
+        deleted_at: DateTime<Utc>,
+    ) -> std::result::Result<Event, DomainError> {
+        use serde_json::json;
+
+        let payload = json!({
+            "receiver_id": receiver.id().to_string(),
+            "receiver": receiver,
+            "deleted_at": deleted_at,
+        });
+
+        self.system_events.build(
+            RECEIVER_DELETED_EVENT,
+            format!("Event receiver '{}' deleted", receiver.name()),
+            payload,
+            receiver.id(),
+            receiver.owner_id(),
+        )
     }
 
     /// Gets an event receiver by ID
@@ -167,6 +481,16 @@ impl EventReceiverHandler {
             .ok_or_else(|| DomainError::ReceiverNotFound.into())
     }
 
+    /// Renders a receiver's Markdown description as sanitized HTML
+    ///
+    /// Returns `None` if the receiver does not exist.
+    pub async fn description_html(&self, id: EventReceiverId) -> Result<Option<Arc<str>>> {
+        Ok(self.repository.find_by_id(id).await?.map(|receiver| {
+            self.descriptions
+                .render(id, receiver.resource_version(), receiver.description())
+        }))
+    }
+
     /// Lists event receivers by name (partial match)
     pub async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiver>> {
         info!(name = %name, "Finding event receivers by name");
@@ -191,7 +515,7 @@ impl EventReceiverHandler {
             "Finding event receivers by type and version"
         );
         self.repository
-            .find_by_type_and_version(receiver_type, version)
+            .find_by_type_and_version(receiver_type, &Version::normalize_filter(version))
             .await
     }
 
@@ -202,17 +526,104 @@ impl EventReceiverHandler {
     ) -> Result<Vec<EventReceiver>> {
         info!(?criteria, "Finding event receivers by criteria");
 
-        if criteria.is_empty() {
+        let criteria = if criteria.is_empty() && criteria.limit.is_none() {
             warn!("Empty criteria provided, using default pagination");
-            let criteria = FindEventReceiverCriteria::new()
-                .with_limit(50)
-                .with_offset(0);
-            return self.repository.find_by_criteria(criteria).await;
-        }
+            criteria.with_limit(DEFAULT_PAGE_SIZE)
+        } else {
+            criteria
+        };
 
         self.repository.find_by_criteria(criteria).await
     }
 
+    /// Returns liveness metadata for the given receivers, keyed by id
+    ///
+    /// Receivers that never received an event are missing from the map, as
+    /// are all receivers when activity tracking is not configured.
+    pub async fn find_activity(
+        &self,
+        receiver_ids: &[EventReceiverId],
+    ) -> Result<HashMap<EventReceiverId, ReceiverActivity>> {
+        let Some(repository) = &self.activity_repository else {
+            return Ok(HashMap::new());
+        };
+
+        Ok(repository
+            .find_activity(receiver_ids, principal_window_start(Utc::now()))
+            .await?
+            .into_iter()
+            .map(|activity| (activity.receiver_id, activity))
+            .collect())
+    }
+
+    /// Records that a producer of the receiver is alive at `now`
+    ///
+    /// No event is created. A heartbeat arriving within the configured
+    /// interval of the stored one is rejected and not stored.
+    pub async fn record_heartbeat(
+        &self,
+        receiver_id: EventReceiverId,
+        status: Option<serde_json::Value>,
+        now: DateTime<Utc>,
+    ) -> Result<HeartbeatWrite> {
+        let Some(repository) = &self.heartbeat_repository else {
+            return Err(Error::Internal {
+                message: "Receiver heartbeats are not configured".to_string(),
+            });
+        };
+
+        let heartbeat = ReceiverHeartbeat::new(receiver_id, now, status)?;
+        self.get_event_receiver_or_error(receiver_id).await?;
+
+        let write = repository
+            .record_heartbeat(&heartbeat, now - self.heartbeat_interval)
+            .await?;
+        if let HeartbeatWrite::Throttled { last_heartbeat_at } = write {
+            warn!(
+                receiver_id = %receiver_id,
+                last_heartbeat_at = %last_heartbeat_at,
+                "Receiver heartbeat rejected: too soon after the previous one"
+            );
+        }
+
+        Ok(write)
+    }
+
+    /// Returns the time until a throttled heartbeat would be accepted
+    pub fn heartbeat_retry_after(
+        &self,
+        last_heartbeat_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Duration {
+        (last_heartbeat_at + self.heartbeat_interval - now).max(Duration::zero())
+    }
+
+    /// Returns the latest heartbeat of the given receivers, keyed by id
+    ///
+    /// Receivers that never sent one are missing from the map, as are all
+    /// receivers when heartbeats are not configured.
+    pub async fn find_heartbeats(
+        &self,
+        receiver_ids: &[EventReceiverId],
+    ) -> Result<HashMap<EventReceiverId, ReceiverHeartbeat>> {
+        let Some(repository) = &self.heartbeat_repository else {
+            return Ok(HashMap::new());
+        };
+
+        Ok(repository
+            .find_heartbeats(receiver_ids)
+            .await?
+            .into_iter()
+            .map(|heartbeat| (heartbeat.receiver_id, heartbeat))
+            .collect())
+    }
+
+    /// Counts event receivers matching the criteria, ignoring pagination
+    pub async fn count_by_criteria(&self, criteria: FindEventReceiverCriteria) -> Result<usize> {
+        info!(?criteria, "Counting event receivers by criteria");
+        self.repository.count_by_criteria(criteria).await
+    }
+
     /// Lists all event receivers with pagination
     pub async fn list_event_receivers(
         &self,
@@ -222,13 +633,7 @@ impl EventReceiverHandler {
         info!(limit = %limit, offset = %offset, "Listing event receivers with pagination");
 
         // Validate pagination parameters
-        if limit == 0 || limit > 1000 {
-            return Err(DomainError::ValidationError {
-                field: "limit".to_string(),
-                message: "Limit must be between 1 and 1000".to_string(),
-            }
-            .into());
-        }
+        PaginationParams::new(Some(limit), offset).validate()?;
 
         self.repository.list(limit, offset).await
     }
@@ -251,8 +656,16 @@ impl EventReceiverHandler {
     ) -> Result<()> {
         info!(receiver_id = %id, "Updating event receiver");
 
+        if let Some(ref version) = version {
+            Version::parse(version, self.version_strictness)?;
+        }
+        let description = description
+            .map(|description| normalize_description(&description, self.max_description_length))
+            .transpose()?;
+
         // Get the existing receiver
         let mut receiver = self.get_event_receiver_or_error(id).await?;
+        let before = receiver.clone();
 
         // If name or type is being changed, check for conflicts
         if let (Some(ref new_name), Some(ref new_type)) = (&name, &receiver_type) {
@@ -263,7 +676,7 @@ impl EventReceiverHandler {
                     .await?
             {
                 return Err(DomainError::BusinessRuleViolation {
-                    rule: "Event receiver with the same name and type already exists".to_string(),
+                    rule: Message::new("rule.receiver_exists"),
                 }
                 .into());
             }
@@ -275,7 +688,7 @@ impl EventReceiverHandler {
                     .await?
             {
                 return Err(DomainError::BusinessRuleViolation {
-                    rule: "Event receiver with the same name and type already exists".to_string(),
+                    rule: Message::new("rule.receiver_exists"),
                 }
                 .into());
             }
@@ -287,7 +700,7 @@ impl EventReceiverHandler {
                     .await?
             {
                 return Err(DomainError::BusinessRuleViolation {
-                    rule: "Event receiver with the same name and type already exists".to_string(),
+                    rule: Message::new("rule.receiver_exists"),
                 }
                 .into());
             }
@@ -298,33 +711,345 @@ impl EventReceiverHandler {
 
         // Save the updated receiver
         self.repository.update(&receiver).await?;
+        self.record_history(&receiver).await;
 
         info!(
             receiver_id = %id,
             fingerprint = %receiver.fingerprint(),
             "Event receiver updated successfully"
         );
+        self.announce_update(&before, receiver).await;
+
+        Ok(())
+    }
+
+    /// Sets the fraction of a receiver's events that are stored
+    ///
+    /// `None` disables sampling.
+    pub async fn update_sample_rate(
+        &self,
+        id: EventReceiverId,
+        sample_rate: Option<f64>,
+    ) -> Result<()> {
+        info!(receiver_id = %id, sample_rate = ?sample_rate, "Updating event receiver sampling");
+
+        let mut receiver = self.get_event_receiver_or_error(id).await?;
+        let before = receiver.clone();
+        receiver.set_sample_rate(sample_rate)?;
+        self.repository.update(&receiver).await?;
+        self.record_history(&receiver).await;
+        self.announce_update(&before, receiver).await;
+
+        Ok(())
+    }
+
+    /// Constrains the event names a receiver accepts
+    ///
+    /// `None` accepts any name. Invalid patterns are rejected as validation
+    /// errors on `allowed_event_names`.
+    pub async fn update_allowed_event_names(
+        &self,
+        id: EventReceiverId,
+        allowed_event_names: Option<AllowedEventNames>,
+    ) -> Result<()> {
+        info!(
+            receiver_id = %id,
+            allowed_event_names = ?allowed_event_names,
+            "Updating event receiver allowed event names"
+        );
+
+        let mut receiver = self.get_event_receiver_or_error(id).await?;
+        let before = receiver.clone();
+        receiver.set_allowed_event_names(allowed_event_names)?;
+        self.repository.update(&receiver).await?;
+        self.record_history(&receiver).await;
+        self.announce_update(&before, receiver).await;
+
+        Ok(())
+    }
+
+    /// Sets the failure rate at which a receiver is disabled
+    ///
+    /// `None` removes the policy. Invalid policies are rejected as
+    /// validation errors on `auto_disable`.
+    pub async fn update_auto_disable_policy(
+        &self,
+        id: EventReceiverId,
+        policy: Option<AutoDisablePolicy>,
+    ) -> Result<()> {
+        info!(
+            receiver_id = %id,
+            policy = ?policy,
+            "Updating event receiver auto-disable policy"
+        );
+
+        let mut receiver = self.get_event_receiver_or_error(id).await?;
+        let before = receiver.clone();
+        receiver.set_auto_disable_policy(policy)?;
+        self.repository.update(&receiver).await?;
+        self.record_history(&receiver).await;
+        self.announce_update(&before, receiver).await;
+
+        Ok(())
+    }
+
+    /// Sets the rewrites applied to a receiver's payloads before validation
+    ///
+    /// `None` stores payloads as they arrive. Invalid steps are rejected as
+    /// validation errors on `transform[i]`.
+    pub async fn update_transform(
+        &self,
+        id: EventReceiverId,
+        transform: Option<PayloadTransform>,
+    ) -> Result<()> {
+        info!(
+            receiver_id = %id,
+            steps = transform.as_ref().map_or(0, |t| t.steps().len()),
+            "Updating event receiver payload transform"
+        );
+
+        let mut receiver = self.get_event_receiver_or_error(id).await?;
+        let before = receiver.clone();
+        receiver.set_transform(transform)?;
+        self.repository.update(&receiver).await?;
+        self.record_history(&receiver).await;
+        self.announce_update(&before, receiver).await;
+
+        Ok(())
+    }
+
+    /// Lets a receiver its policy disabled accept events again
+    ///
+    /// The observation window restarts now. Enabling a receiver that is
+    /// not disabled changes nothing.
+    pub async fn enable_event_receiver(&self, id: EventReceiverId) -> Result<()> {
+        let mut receiver = self.get_event_receiver_or_error(id).await?;
+        let before = receiver.clone();
+        if !receiver.enable(Utc::now()) {
+            return Ok(());
+        }
+
+        info!(receiver_id = %id, "Re-enabling auto-disabled event receiver");
+        self.repository.update(&receiver).await?;
+        self.record_history(&receiver).await;
+        self.announce_update(&before, receiver).await;
 
         Ok(())
     }
 
+    /// Stops a receiver accepting events after `trigger` breached its policy
+    ///
+    /// Records an `xzepr.event.receiver.auto_disabled` system event and
+    /// announces the receiver with it. Returns false if the receiver was
+    /// already disabled.
+    pub async fn auto_disable_event_receiver(
+        &self,
+        id: EventReceiverId,
+        trigger: AutoDisableTrigger,
+    ) -> Result<bool> {
+        let mut receiver = self.get_event_receiver_or_error(id).await?;
+        if !receiver.auto_disable(trigger.clone()) {
+            return Ok(false);
+        }
+
+        self.repository.update(&receiver).await?;
+        self.record_history(&receiver).await;
+        warn!(
+            receiver_id = %id,
+            receiver_name = %receiver.name(),
+            failed = trigger.failed,
+            total = trigger.total,
+            failure_rate = trigger.failure_rate,
+            window_start = %trigger.window_start,
+            "Event receiver auto-disabled after a sustained failure rate"
+        );
+
+        let system_event = self
+            .record_system_event(
+                RECEIVER_AUTO_DISABLED_EVENT,
+                id,
+                self.create_receiver_auto_disabled_event(&receiver, &trigger),
+            )
+            .await;
+        self.publish(DomainEvent::ReceiverAutoDisabled {
+            receiver,
+            trigger,
+            system_event,
+        });
+
+        Ok(true)
+    }
+
+    /// Creates a system event for an auto-disabled receiver
+    fn create_receiver_auto_disabled_event(
+        &self,
+        receiver: &EventReceiver,
+        trigger: &AutoDisableTrigger,
+    ) -> std::result::Result<Event, DomainError> {
+        use serde_json::json;
+
+        let payload = json!({
+            "receiver_id": receiver.id().to_string(),
+            "name": receiver.name(),
+            "policy": receiver.auto_disable_policy(),
+            "statistics": trigger,
+        });
+
+        self.system_events.build(
+            RECEIVER_AUTO_DISABLED_EVENT,
+            format!(
+                "Event receiver '{}' auto-disabled after {} of {} events failed",
+                receiver.name(),
+                trigger.failed,
+                trigger.total
+            ),
+            payload,
+            receiver.id(),
+            receiver.owner_id(),
+        )
+    }
+
     /// Deletes an event receiver
-    pub async fn delete_event_receiver(&self, id: EventReceiverId) -> Result<()> {
-        info!(receiver_id = %id, "Deleting event receiver");
+    ///
+    /// Recent events and enabled keys scoped to the receiver block the
+    /// deletion unless `force` is set; a blocked deletion changes nothing.
+    /// Keys are revoked and group memberships removed before the receiver
+    /// itself, so a failure part way leaves the receiver in place and the
+    /// call can be retried.
+    pub async fn delete_event_receiver(
+        &self,
+        id: EventReceiverId,
+        force: bool,
+        actor: &str,
+    ) -> Result<ReceiverDeletionOutcome> {
+        info!(receiver_id = %id, force, "Deleting event receiver");
 
-        // Check if the receiver exists
-        if self.repository.find_by_id(id).await?.is_none() {
+        let Some(receiver) = self.repository.find_by_id(id).await? else {
             return Err(DomainError::ReceiverNotFound.into());
+        };
+
+        let blockers = self.deletion_blockers(id, Utc::now()).await?;
+        if !blockers.is_empty() && !force {
+            info!(
+                receiver_id = %id,
+                blockers = blockers.len(),
+                "Event receiver deletion blocked"
+            );
+            return Ok(ReceiverDeletionOutcome::Blocked(blockers));
         }
 
-        // TODO: Check if receiver is being used by any events or groups
-        // This should be done by checking with other repositories
+        let mut revoked_api_keys = Vec::new();
+        let mut removed_from_groups = Vec::new();
+        if let Some(dependents) = &self.dependents {
+            for key in dependents.api_keys.find_by_receiver_id(id).await? {
+                if !key.enabled {
+                    continue;
+                }
+                dependents.api_keys.revoke(key.id).await?;
+                self.audit_logger.log_event(
+                    AuditEvent::builder()
+                        .user_id(actor)
+                        .action(AuditAction::ApiKeyRevoke)
+                        .resource(format!("api_key:{}", key.id))
+                        .outcome(AuditOutcome::Success)
+                        .add_metadata("reason", "receiver_deleted")
+                        .add_metadata("receiver_id", id.to_string())
+                        .build(),
+                );
+                revoked_api_keys.push(key.id);
+            }
+
+            for group in dependents.groups.find_by_event_receiver_id(id).await? {
+                dependents
+                    .groups
+                    .remove_event_receiver_from_group(group.id(), id)
+                    .await?;
+                removed_from_groups.push(group.id());
+            }
+        }
 
         self.repository.delete(id).await?;
+        let deleted_at = Utc::now();
+        if let Some(history) = &self.history {
+            history.record_receiver_deleted(id, deleted_at).await;
+        }
+
+        // Cached receiver lookups are dropped by the repository itself
+        let mut invalidated_caches = vec![DeletionCache::ReceiverLookups];
+        self.descriptions.forget(id);
+        invalidated_caches.push(DeletionCache::Descriptions);
+        if let Some(resolver) = &self.schema_resolver {
+            resolver.invalidate().await;
+            invalidated_caches.push(DeletionCache::EffectiveSchemas);
+        }
+
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(actor)
+                .action(AuditAction::ResourceDelete)
+                .resource(format!("event_receiver:{}", id))
+                .outcome(AuditOutcome::Success)
+                .add_metadata("forced", (!blockers.is_empty()).to_string())
+                .add_metadata("groups", removed_from_groups.len().to_string())
+                .add_metadata("revoked_api_keys", revoked_api_keys.len().to_string())
+                .build(),
+        );
 
         info!(receiver_id = %id, "Event receiver deleted successfully");
+        let system_event = self
+            .record_system_event(
+                RECEIVER_DELETED_EVENT,
+                id,
+                self.create_receiver_deleted_event(&receiver, deleted_at),
+            )
+            .await;
+        self.publish(DomainEvent::ReceiverDeleted {
+            receiver,
+            deleted_at,
+            system_event,
+        });
 
-        Ok(())
+        Ok(ReceiverDeletionOutcome::Deleted(ReceiverDeletionReport {
+            receiver_id: id,
+            forced: !blockers.is_empty(),
+            blockers,
+            removed_from_groups,
+            revoked_api_keys,
+            invalidated_caches,
+            deleted_at,
+        }))
+    }
+
+    /// Returns what blocks deleting a receiver without force
+    async fn deletion_blockers(
+        &self,
+        id: EventReceiverId,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<DeletionBlocker>> {
+        let Some(dependents) = &self.dependents else {
+            return Ok(Vec::new());
+        };
+
+        let mut blockers = Vec::new();
+        let since = now - self.recent_event_age;
+        let count = dependents.events.count_by_receiver_since(id, since).await?;
+        if count > 0 {
+            blockers.push(DeletionBlocker::RecentEvents { count, since });
+        }
+
+        let key_ids: Vec<_> = dependents
+            .api_keys
+            .find_by_receiver_id(id)
+            .await?
+            .into_iter()
+            .filter(|key| key.enabled && key.expires_at.is_none_or(|expires| expires > now))
+            .map(|key| key.id)
+            .collect();
+        if !key_ids.is_empty() {
+            blockers.push(DeletionBlocker::ActiveApiKeys { key_ids });
+        }
+
+        Ok(blockers)
     }
 
     /// Validates an event payload against a receiver's schema
@@ -337,10 +1062,23 @@ impl EventReceiverHandler {
 
         let receiver = self.get_event_receiver_or_error(receiver_id).await?;
         receiver.validate_event_payload(payload)?;
+        self.resolve_schema(&receiver)
+            .await?
+            .validate_payload(payload)?;
 
         Ok(())
     }
 
+    /// Resolves the effective schema for a receiver
+    ///
+    /// Without a schema resolver the receiver's own schema is always used.
+    pub async fn resolve_schema(&self, receiver: &EventReceiver) -> Result<ResolvedSchema> {
+        match &self.schema_resolver {
+            Some(resolver) => resolver.resolve(receiver).await,
+            None => Ok(ResolvedSchema::resolve(receiver, &[])),
+        }
+    }
+
     /// Gets event receiver by fingerprint
     pub async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>> {
         info!(fingerprint = %fingerprint, "Finding event receiver by fingerprint");
@@ -356,7 +1094,10 @@ impl EventReceiverHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::handlers::KafkaForwarder;
     use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
+    use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
+    use crate::infrastructure::messaging::producer::EventPublisher;
     use async_trait::async_trait;
     use serde_json::json;
     use std::collections::HashMap;
@@ -514,6 +1255,247 @@ mod tests {
         assert_eq!(receiver.unwrap().name(), "Test Receiver");
     }
 
+    #[tokio::test]
+    async fn test_create_receiver_succeeds_when_system_event_construction_fails() {
+        let repository = Arc::new(MockEventReceiverRepository::new());
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let handler =
+            EventReceiverHandler::new(repository)
+                .with_event_bus(bus)
+                .with_metrics(metrics.clone())
+                .with_system_event_factory(SystemEventFactory::new().with_constructor(|_| {
+                    Err(DomainError::InvalidData("forced failure".to_string()))
+                }));
+
+        let receiver_id = handler
+            .create_event_receiver(
+                "Test Receiver".to_string(),
+                "webhook".to_string(),
+                "1.0.0".to_string(),
+                "A test receiver".to_string(),
+                json!({"type": "object"}),
+                UserId::new(),
+            )
+            .await
+            .unwrap();
+
+        assert!(handler
+            .get_event_receiver(receiver_id)
+            .await
+            .unwrap()
+            .is_some());
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_system_event_failures_total{event_type=\"xzepr.event.receiver.created\"} 1"
+        ));
+
+        // The receiver is still announced, without a system event to forward
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::ReceiverCreated {
+                receiver,
+                system_event,
+            } => {
+                assert_eq!(receiver.id(), receiver_id);
+                assert!(system_event.is_none());
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_receiver_changes_are_announced_after_they_are_stored() {
+        let repository = Arc::new(MockEventReceiverRepository::new());
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let handler = EventReceiverHandler::new(repository).with_event_bus(bus);
+
+        let receiver_id = handler
+            .create_event_receiver(
+                "Test Receiver".to_string(),
+                "webhook".to_string(),
+                "1.0.0".to_string(),
+                "A test receiver".to_string(),
+                json!({"type": "object"}),
+                UserId::new(),
+            )
+            .await
+            .unwrap();
+        handler
+            .update_sample_rate(receiver_id, Some(0.5))
+            .await
+            .unwrap();
+        handler
+            .delete_event_receiver(receiver_id, false, "test")
+            .await
+            .unwrap();
+
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::ReceiverCreated {
+                receiver,
+                system_event: Some(system_event),
+            } => {
+                assert_eq!(receiver.id(), receiver_id);
+                assert_eq!(system_event.name(), RECEIVER_CREATED_EVENT);
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::ReceiverUpdated {
+                receiver,
+                changed_fields,
+                system_event: Some(system_event),
+            } => {
+                assert_eq!(receiver.sample_rate(), Some(0.5));
+                assert_eq!(changed_fields, &["sample_rate"]);
+                assert_eq!(system_event.name(), RECEIVER_UPDATED_EVENT);
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::ReceiverDeleted {
+                receiver,
+                system_event: Some(system_event),
+                ..
+            } => {
+                assert_eq!(receiver.id(), receiver_id);
+                assert_eq!(receiver.sample_rate(), Some(0.5));
+                assert_eq!(system_event.name(), RECEIVER_DELETED_EVENT);
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+        assert!(notifications.try_recv().is_err());
+    }
+
+    /// Publisher recording each message with its key
+    #[derive(Default)]
+    struct CapturingPublisher {
+        messages: Mutex<Vec<(String, CloudEventMessage)>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for CapturingPublisher {
+        async fn publish(&self, _event: &Event) -> Result<()> {
+            unreachable!("receiver changes are published as prepared messages")
+        }
+
+        async fn publish_to(&self, _topic: &str, _event: &Event) -> Result<()> {
+            unreachable!("receiver changes are published as prepared messages")
+        }
+
+        async fn publish_message(&self, _message: &CloudEventMessage) -> Result<()> {
+            unreachable!("receiver changes are published under the receiver id")
+        }
+
+        async fn publish_message_with_key(
+            &self,
+            key: &str,
+            message: &CloudEventMessage,
+        ) -> Result<()> {
+            self.messages
+                .lock()
+                .unwrap()
+                .push((key.to_string(), message.clone()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_receiver_lifecycle_is_published_in_order_under_the_receiver_id() {
+        let bus = DomainEventBus::new();
+        let publisher = Arc::new(CapturingPublisher::default());
+        bus.register(Arc::new(KafkaForwarder::new(publisher.clone())));
+        let handler = EventReceiverHandler::new(Arc::new(MockEventReceiverRepository::new()))
+            .with_event_bus(bus);
+
+        let mut receiver_ids = Vec::new();
+        for name in ["first", "second"] {
+            let receiver_id = handler
+                .create_event_receiver(
+                    name.to_string(),
+                    "webhook".to_string(),
+                    "1.0.0".to_string(),
+                    "A test receiver".to_string(),
+                    json!({"type": "object"}),
+                    UserId::new(),
+                )
+                .await
+                .unwrap();
+            receiver_ids.push(receiver_id);
+        }
+        let [first, second] = [receiver_ids[0], receiver_ids[1]];
+        handler
+            .update_event_receiver(
+                first,
+                None,
+                None,
+                Some("1.1.0".to_string()),
+                Some("Build results".to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+        handler.update_sample_rate(second, Some(0.5)).await.unwrap();
+        handler
+            .delete_event_receiver(first, false, "test")
+            .await
+            .unwrap();
+
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+        while publisher.messages.lock().unwrap().len() < 5 {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "events not published"
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let messages = publisher.messages.lock().unwrap();
+        let published_for = |receiver_id: EventReceiverId| -> Vec<&CloudEventMessage> {
+            messages
+                .iter()
+                .filter(|(key, _)| *key == receiver_id.to_string())
+                .map(|(_, message)| message)
+                .collect()
+        };
+
+        let first_messages = published_for(first);
+        let types: Vec<_> = first_messages
+            .iter()
+            .map(|m| m.event_type.as_str())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                RECEIVER_CREATED_EVENT,
+                RECEIVER_UPDATED_EVENT,
+                RECEIVER_DELETED_EVENT
+            ]
+        );
+        let updated = &first_messages[1].data.events[0];
+        assert_eq!(
+            updated.payload()["changed_fields"],
+            json!(["description", "fingerprint", "version"])
+        );
+        assert_eq!(updated.payload()["receiver"]["version"], "1.1.0");
+        let deleted = &first_messages[2].data.events[0];
+        assert_eq!(
+            deleted.payload()["receiver"]["description"],
+            "Build results"
+        );
+        assert!(deleted.payload()["deleted_at"].is_string());
+        assert_eq!(first_messages[2].data.event_receivers[0].id(), first);
+
+        let second_messages = published_for(second);
+        assert_eq!(second_messages.len(), 2);
+        assert_eq!(
+            second_messages[1].data.events[0].payload()["changed_fields"],
+            json!(["sample_rate"])
+        );
+    }
+
     #[tokio::test]
     async fn test_create_duplicate_receiver() {
         let repository = Arc::new(MockEventReceiverRepository::new());
@@ -553,6 +1535,113 @@ mod tests {
         assert!(result2.is_err());
     }
 
+    #[tokio::test]
+    async fn test_create_receiver_follows_version_strictness() {
+        let create = |handler: EventReceiverHandler, name: &'static str| async move {
+            handler
+                .create_event_receiver(
+                    name.to_string(),
+                    "webhook".to_string(),
+                    " v1.2 ".to_string(),
+                    "A test receiver".to_string(),
+                    json!({"type": "object"}),
+                    UserId::new(),
+                )
+                .await
+        };
+
+        let handler = EventReceiverHandler::new(Arc::new(MockEventReceiverRepository::new()));
+        let receiver_id = create(handler.clone(), "lenient").await.unwrap();
+        let receiver = handler
+            .get_event_receiver_or_error(receiver_id)
+            .await
+            .unwrap();
+        assert_eq!(receiver.version(), "1.2.0");
+
+        let handler = EventReceiverHandler::new(Arc::new(MockEventReceiverRepository::new()))
+            .with_version_strictness(VersionStrictness::Strict);
+        match create(handler.clone(), "strict").await {
+            Err(Error::Domain(DomainError::ValidationError { field, .. })) => {
+                assert_eq!(field, "version")
+            }
+            other => panic!("expected a version validation error, got {:?}", other),
+        }
+
+        let receiver_id = handler
+            .create_event_receiver(
+                "strict".to_string(),
+                "webhook".to_string(),
+                "1.2.0".to_string(),
+                "A test receiver".to_string(),
+                json!({"type": "object"}),
+                UserId::new(),
+            )
+            .await
+            .unwrap();
+        let result = handler
+            .update_event_receiver(receiver_id, None, None, Some("v2".to_string()), None, None)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_description_html_follows_description_updates() {
+        let handler = EventReceiverHandler::new(Arc::new(MockEventReceiverRepository::new()))
+            .with_max_description_length(20);
+        let receiver_id = handler
+            .create_event_receiver(
+                "runbook".to_string(),
+                "webhook".to_string(),
+                "1.0.0".to_string(),
+                "**Restart**\r\n".to_string(),
+                json!({"type": "object"}),
+                UserId::new(),
+            )
+            .await
+            .unwrap();
+
+        let html = handler
+            .description_html(receiver_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&*html, "<p><strong>Restart</strong></p>\n");
+
+        handler
+            .update_event_receiver(
+                receiver_id,
+                None,
+                None,
+                None,
+                Some("[docs](javascript:x)".to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+        let html = handler
+            .description_html(receiver_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            &*html,
+            "<p><a rel=\"noopener noreferrer nofollow\">docs</a></p>\n"
+        );
+
+        let result = handler
+            .update_event_receiver(receiver_id, None, None, None, Some("x".repeat(21)), None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Domain(DomainError::ValidationError { ref field, .. })) if field == "description"
+        ));
+        assert!(handler
+            .description_html(EventReceiverId::new())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_get_nonexistent_receiver() {
         let repository = Arc::new(MockEventReceiverRepository::new());
@@ -579,4 +1668,161 @@ mod tests {
         let result3 = handler.list_event_receivers(10, 0).await;
         assert!(result3.is_ok());
     }
+
+    #[derive(Default)]
+    struct MockHeartbeatRepository {
+        heartbeats: Mutex<HashMap<EventReceiverId, ReceiverHeartbeat>>,
+    }
+
+    #[async_trait]
+    impl ReceiverHeartbeatRepository for MockHeartbeatRepository {
+        async fn record_heartbeat(
+            &self,
+            heartbeat: &ReceiverHeartbeat,
+            not_after: DateTime<Utc>,
+        ) -> Result<HeartbeatWrite> {
+            let mut heartbeats = self.heartbeats.lock().unwrap();
+            if let Some(stored) = heartbeats.get(&heartbeat.receiver_id) {
+                if stored.last_heartbeat_at > not_after {
+                    return Ok(HeartbeatWrite::Throttled {
+                        last_heartbeat_at: stored.last_heartbeat_at,
+                    });
+                }
+            }
+            heartbeats.insert(heartbeat.receiver_id, heartbeat.clone());
+            Ok(HeartbeatWrite::Recorded)
+        }
+
+        async fn find_heartbeats(
+            &self,
+            receiver_ids: &[EventReceiverId],
+        ) -> Result<Vec<ReceiverHeartbeat>> {
+            let heartbeats = self.heartbeats.lock().unwrap();
+            Ok(receiver_ids
+                .iter()
+                .filter_map(|id| heartbeats.get(id).cloned())
+                .collect())
+        }
+    }
+
+    async fn heartbeat_handler() -> (EventReceiverHandler, EventReceiverId, EventReceiverId) {
+        let handler = EventReceiverHandler::new(Arc::new(MockEventReceiverRepository::new()))
+            .with_heartbeats(Arc::new(MockHeartbeatRepository::default()), 60);
+        let mut ids = Vec::new();
+        for name in ["agent-a", "agent-b"] {
+            let id = handler
+                .create_event_receiver(
+                    name.to_string(),
+                    "agent".to_string(),
+                    "1.0.0".to_string(),
+                    "A long-running agent".to_string(),
+                    json!({}),
+                    UserId::new(),
+                )
+                .await
+                .unwrap();
+            ids.push(id);
+        }
+        (handler, ids[0], ids[1])
+    }
+
+    #[tokio::test]
+    async fn test_heartbeats_are_throttled_per_receiver() {
+        let (handler, a, b) = heartbeat_handler().await;
+        let now = Utc::now();
+
+        let first = handler
+            .record_heartbeat(a, Some(json!({"version": "1.0.0"})), now)
+            .await
+            .unwrap();
+        assert_eq!(first, HeartbeatWrite::Recorded);
+
+        // A second heartbeat inside the interval is rejected and not stored
+        let later = now + Duration::seconds(20);
+        let second = handler
+            .record_heartbeat(a, Some(json!({"version": "2.0.0"})), later)
+            .await
+            .unwrap();
+        assert_eq!(
+            second,
+            HeartbeatWrite::Throttled {
+                last_heartbeat_at: now
+            }
+        );
+        assert_eq!(
+            handler.heartbeat_retry_after(now, later),
+            Duration::seconds(40)
+        );
+        let stored = handler.find_heartbeats(&[a]).await.unwrap();
+        assert_eq!(stored[&a].status, Some(json!({"version": "1.0.0"})));
+
+        // Other receivers have their own budget
+        assert_eq!(
+            handler.record_heartbeat(b, None, later).await.unwrap(),
+            HeartbeatWrite::Recorded
+        );
+
+        assert_eq!(
+            handler
+                .record_heartbeat(a, None, now + Duration::seconds(60))
+                .await
+                .unwrap(),
+            HeartbeatWrite::Recorded
+        );
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_status_is_replaced_not_merged() {
+        let (handler, a, _) = heartbeat_handler().await;
+        let now = Utc::now();
+
+        handler
+            .record_heartbeat(
+                a,
+                Some(json!({"version": "1.0.0", "hostname": "build-01"})),
+                now,
+            )
+            .await
+            .unwrap();
+        handler
+            .record_heartbeat(
+                a,
+                Some(json!({"version": "1.1.0"})),
+                now + Duration::minutes(1),
+            )
+            .await
+            .unwrap();
+        let stored = handler.find_heartbeats(&[a]).await.unwrap();
+        assert_eq!(stored[&a].status, Some(json!({"version": "1.1.0"})));
+        assert_eq!(stored[&a].last_heartbeat_at, now + Duration::minutes(1));
+
+        // A heartbeat without a status clears the previous one
+        handler
+            .record_heartbeat(a, None, now + Duration::minutes(2))
+            .await
+            .unwrap();
+        let stored = handler.find_heartbeats(&[a]).await.unwrap();
+        assert_eq!(stored[&a].status, None);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_rejects_unknown_receiver_and_bad_status() {
+        let (handler, a, _) = heartbeat_handler().await;
+        let now = Utc::now();
+
+        let missing = handler
+            .record_heartbeat(EventReceiverId::new(), None, now)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            missing,
+            Error::Domain(DomainError::ReceiverNotFound)
+        ));
+
+        let invalid = handler
+            .record_heartbeat(a, Some(json!(["not", "an", "object"])), now)
+            .await;
+        assert!(invalid.is_err());
+        assert!(handler.find_heartbeats(&[a]).await.unwrap().is_empty());
+    }
 }
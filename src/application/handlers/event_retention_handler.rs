@@ -0,0 +1,608 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/event_retention_handler.rs
+
+use crate::application::handlers::{EventAttachmentHandler, PayloadOffloader};
+use crate::domain::entities::event::Event;
+use crate::domain::repositories::event_archive_repo::{
+    ArchiveIndexEntry, ArchiveSegmentKey, ArchiveStore, EventArchiveIndexRepository,
+};
+use crate::domain::repositories::event_repo::{EventRepository, FindEventCriteria};
+use crate::error::Result;
+use crate::infrastructure::jobs::{Job, JobReport, JobSchedule};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
+
+/// Default number of days events stay in the primary database
+pub const DEFAULT_RETENTION_DAYS: i64 = 90;
+
+/// Default number of events moved per retention pass
+pub const DEFAULT_RETENTION_BATCH_SIZE: usize = 500;
+
+/// Default time between retention passes
+pub const DEFAULT_RETENTION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Name of the retention job in settings and the admin API
+pub const RETENTION_JOB_NAME: &str = "event_retention";
+
+/// Outcome of a single retention pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionReport {
+    pub archived: usize,
+    pub deleted: usize,
+    /// Attachments of deleted events whose blobs were removed
+    pub attachments: usize,
+}
+
+/// Application service moving expired events to cold storage
+///
+/// Each pass selects events older than the retention window, writes them to
+/// the archive store grouped by receiver and day, records every event in
+/// the archive index, and only then deletes it from the primary database.
+/// If any step fails the pass stops and the affected events stay in the
+/// database to be retried on the next pass. Attachments are not archived;
+/// their blobs and records are deleted together with the event. Offloaded
+/// payloads are archived in full and their blobs deleted.
+#[derive(Clone)]
+pub struct EventRetentionHandler {
+    event_repository: Arc<dyn EventRepository>,
+    archive_store: Arc<dyn ArchiveStore>,
+    archive_index: Arc<dyn EventArchiveIndexRepository>,
+    attachments: Option<EventAttachmentHandler>,
+    payload_offloader: Option<PayloadOffloader>,
+    retention: Duration,
+    batch_size: usize,
+    interval: std::time::Duration,
+}
+
+impl EventRetentionHandler {
+    /// Creates a new retention handler using the default window and batch size
+    pub fn new(
+        event_repository: Arc<dyn EventRepository>,
+        archive_store: Arc<dyn ArchiveStore>,
+        archive_index: Arc<dyn EventArchiveIndexRepository>,
+    ) -> Self {
+        Self {
+            event_repository,
+            archive_store,
+            archive_index,
+            attachments: None,
+            payload_offloader: None,
+            retention: Duration::days(DEFAULT_RETENTION_DAYS),
+            batch_size: DEFAULT_RETENTION_BATCH_SIZE,
+            interval: DEFAULT_RETENTION_INTERVAL,
+        }
+    }
+
+    /// Sets how long events stay in the primary database
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    /// Sets the maximum number of events moved per pass
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Deletes the attachments of expired events along with them
+    pub fn with_attachments(mut self, attachments: EventAttachmentHandler) -> Self {
+        self.attachments = Some(attachments);
+        self
+    }
+
+    /// Archives offloaded payloads in full and deletes their blobs along
+    /// with the event
+    pub fn with_payload_offloader(mut self, payload_offloader: PayloadOffloader) -> Self {
+        self.payload_offloader = Some(payload_offloader);
+        self
+    }
+
+    /// Sets the time between retention passes when run as a job
+    pub fn with_interval(mut self, interval: std::time::Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Archives and deletes up to one batch of events expired at `now`
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<RetentionReport> {
+        let cutoff = now - self.retention;
+        let criteria = FindEventCriteria::new()
+            .with_end_time(cutoff)
+            .with_limit(self.batch_size);
+        let expired = self.event_repository.find_by_criteria(criteria).await?;
+
+        let mut segments: HashMap<ArchiveSegmentKey, Vec<Event>> = HashMap::new();
+        for event in expired {
+            segments
+                .entry(ArchiveSegmentKey::for_event(&event))
+                .or_default()
+                .push(event);
+        }
+
+        let mut report = RetentionReport::default();
+        for (key, events) in segments {
+            let segment = match &self.payload_offloader {
+                Some(offloader) => {
+                    let mut full = events.clone();
+                    offloader.restore_all(&mut full).await?;
+                    self.archive_store.put_batch(&key, &full).await?
+                }
+                None => self.archive_store.put_batch(&key, &events).await?,
+            };
+            report.archived += events.len();
+
+            let archived_at = Utc::now();
+            let entries: Vec<ArchiveIndexEntry> = events
+                .iter()
+                .map(|event| ArchiveIndexEntry {
+                    event_id: event.id(),
+                    event_receiver_id: event.event_receiver_id(),
+                    segment: segment.clone(),
+                    event_created_at: event.created_at(),
+                    archived_at,
+                })
+                .collect();
+            self.archive_index.record_archived(&entries).await?;
+
+            for event in &events {
+                if let Some(attachments) = &self.attachments {
+                    report.attachments += attachments.purge_event(event.id()).await?;
+                }
+                self.event_repository.delete(event.id()).await?;
+                if let Some(offloader) = &self.payload_offloader {
+                    offloader.discard(event).await?;
+                }
+                report.deleted += 1;
+            }
+
+            info!(
+                segment = %segment,
+                events = events.len(),
+                "Archived expired events"
+            );
+        }
+
+        Ok(report)
+    }
+}
+
+#[async_trait]
+impl Job for EventRetentionHandler {
+    fn name(&self) -> &str {
+        RETENTION_JOB_NAME
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::Every(self.interval)
+    }
+
+    /// A pass that fills a whole batch reports more pending so that a
+    /// backlog drains without waiting for the next tick
+    async fn run(&self) -> Result<JobReport> {
+        let report = self.run_once(Utc::now()).await?;
+        Ok(JobReport {
+            processed: report.archived,
+            more_pending: report.archived >= self.batch_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::handlers::{AttachmentUpload, NewAttachment};
+    use crate::domain::entities::event::{DatabaseEventFields, EventOrigin};
+    use crate::domain::entities::event_attachment::EventAttachment;
+    use crate::domain::repositories::event_attachment_repo::{
+        ByteStream, EventAttachmentRepository,
+    };
+    use crate::domain::value_objects::{AttachmentId, EventId, EventReceiverId, UserId};
+    use crate::infrastructure::archive::FilesystemArchiveStore;
+    use crate::infrastructure::attachments::FilesystemBlobStore;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    struct MockEventRepository {
+        events: Mutex<HashMap<EventId, Event>>,
+    }
+
+    #[async_trait]
+    impl EventRepository for MockEventRepository {
+        async fn save(&self, event: &Event) -> Result<()> {
+            self.events
+                .lock()
+                .unwrap()
+                .insert(event.id(), event.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: EventId) -> Result<Option<Event>> {
+            Ok(self.events.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_success(&self, _success: bool) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_name(&self, _name: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_platform_id(&self, _platform_id: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_package(&self, _package: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(self.events.lock().unwrap().len())
+        }
+
+        async fn count_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn count_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn delete(&self, id: EventId) -> Result<()> {
+            self.events.lock().unwrap().remove(&id);
+            Ok(())
+        }
+
+        async fn find_latest_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_latest_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_by_time_range(
+            &self,
+            _start: DateTime<Utc>,
+            _end: DateTime<Utc>,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_criteria(&self, criteria: FindEventCriteria) -> Result<Vec<Event>> {
+            let events = self.events.lock().unwrap();
+            Ok(events
+                .values()
+                .filter(|e| criteria.end_time.is_none_or(|end| e.created_at() <= end))
+                .take(criteria.limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_owner(&self, _owner_id: UserId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner_paginated(
+            &self,
+            _owner_id: UserId,
+            _limit: usize,
+            _offset: usize,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn is_owner(&self, _event_id: EventId, _user_id: UserId) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn get_resource_version(&self, _event_id: EventId) -> Result<Option<i64>> {
+            Ok(Some(1))
+        }
+    }
+
+    #[derive(Default)]
+    struct MockArchiveIndex {
+        entries: Mutex<HashMap<EventId, ArchiveIndexEntry>>,
+    }
+
+    #[async_trait]
+    impl EventArchiveIndexRepository for MockArchiveIndex {
+        async fn record_archived(&self, entries: &[ArchiveIndexEntry]) -> Result<()> {
+            let mut stored = self.entries.lock().unwrap();
+            for entry in entries {
+                stored.insert(entry.event_id, entry.clone());
+            }
+            Ok(())
+        }
+
+        async fn find_archived(&self, event_id: EventId) -> Result<Option<ArchiveIndexEntry>> {
+            Ok(self.entries.lock().unwrap().get(&event_id).cloned())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockAttachmentRepository {
+        attachments: Mutex<HashMap<AttachmentId, EventAttachment>>,
+    }
+
+    #[async_trait]
+    impl EventAttachmentRepository for MockAttachmentRepository {
+        async fn save(&self, attachment: &EventAttachment) -> Result<()> {
+            self.attachments
+                .lock()
+                .unwrap()
+                .insert(attachment.id, attachment.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: AttachmentId) -> Result<Option<EventAttachment>> {
+            Ok(self.attachments.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_event(&self, event_id: EventId) -> Result<Vec<EventAttachment>> {
+            Ok(self
+                .attachments
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|a| a.event_id == event_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn delete(&self, id: AttachmentId) -> Result<bool> {
+            Ok(self.attachments.lock().unwrap().remove(&id).is_some())
+        }
+
+        async fn total_size_by_receiver(&self, _receiver_id: EventReceiverId) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    struct OneChunk(Option<Vec<u8>>);
+
+    #[async_trait]
+    impl ByteStream for OneChunk {
+        async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+            Ok(self.0.take())
+        }
+    }
+
+    fn event_at(receiver_id: EventReceiverId, created_at: DateTime<Utc>) -> Event {
+        Event::from_database(DatabaseEventFields {
+            id: EventId::new(),
+            name: "deploy".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.01".to_string(),
+            platform_id: "linux".to_string(),
+            package: "docker".to_string(),
+            description: "retention test".to_string(),
+            payload: json!({"build": 42}),
+            success: true,
+            event_receiver_id: receiver_id,
+            owner_id: UserId::new(),
+            origin: EventOrigin::User,
+            resource_version: 1,
+            created_at,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_archive_then_delete_round_trip() {
+        let now = Utc::now();
+        let receiver_id = EventReceiverId::new();
+        let old_a = event_at(receiver_id, now - Duration::days(100));
+        let old_b = event_at(receiver_id, now - Duration::days(100));
+        let other_day = event_at(receiver_id, now - Duration::days(120));
+        let recent = event_at(receiver_id, now - Duration::days(1));
+
+        let events = Arc::new(MockEventRepository {
+            events: Mutex::new(HashMap::new()),
+        });
+        for event in [&old_a, &old_b, &other_day, &recent] {
+            events.save(event).await.unwrap();
+        }
+
+        let root = std::env::temp_dir().join(format!("xzepr-retention-{}", ulid::Ulid::new()));
+        let store = Arc::new(FilesystemArchiveStore::new(&root));
+        let index = Arc::new(MockArchiveIndex::default());
+        let handler = EventRetentionHandler::new(events.clone(), store.clone(), index.clone());
+
+        let report = handler.run_once(now).await.unwrap();
+
+        assert_eq!(
+            report,
+            RetentionReport {
+                archived: 3,
+                deleted: 3,
+                attachments: 0,
+            }
+        );
+        assert_eq!(events.count().await.unwrap(), 1);
+        assert!(events.find_by_id(recent.id()).await.unwrap().is_some());
+        assert!(index.find_archived(recent.id()).await.unwrap().is_none());
+
+        for original in [&old_a, &old_b, &other_day] {
+            assert!(events.find_by_id(original.id()).await.unwrap().is_none());
+            let entry = index.find_archived(original.id()).await.unwrap().unwrap();
+            assert_eq!(entry.event_created_at, original.created_at());
+            let restored = store
+                .get_by_id(&entry.segment, original.id())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(restored.payload(), original.payload());
+            assert_eq!(restored.created_at(), original.created_at());
+        }
+
+        let a = index.find_archived(old_a.id()).await.unwrap().unwrap();
+        let b = index.find_archived(old_b.id()).await.unwrap().unwrap();
+        let c = index.find_archived(other_day.id()).await.unwrap().unwrap();
+        assert_eq!(a.segment, b.segment);
+        assert_ne!(a.segment, c.segment);
+
+        assert_eq!(
+            handler.run_once(now).await.unwrap(),
+            RetentionReport::default()
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_size_limits_pass() {
+        let now = Utc::now();
+        let receiver_id = EventReceiverId::new();
+        let events = Arc::new(MockEventRepository {
+            events: Mutex::new(HashMap::new()),
+        });
+        for _ in 0..3 {
+            events
+                .save(&event_at(receiver_id, now - Duration::days(200)))
+                .await
+                .unwrap();
+        }
+
+        let root = std::env::temp_dir().join(format!("xzepr-retention-{}", ulid::Ulid::new()));
+        let handler = EventRetentionHandler::new(
+            events.clone(),
+            Arc::new(FilesystemArchiveStore::new(&root)),
+            Arc::new(MockArchiveIndex::default()),
+        )
+        .with_retention(Duration::days(30))
+        .with_batch_size(2);
+
+        // As a job, a full batch asks for another pass straight away
+        assert_eq!(
+            Job::run(&handler).await.unwrap(),
+            JobReport {
+                processed: 2,
+                more_pending: true,
+            }
+        );
+        assert_eq!(handler.run_once(now).await.unwrap().deleted, 1);
+        assert_eq!(events.count().await.unwrap(), 0);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retention_deletes_attachment_blobs() {
+        let now = Utc::now();
+        let receiver_id = EventReceiverId::new();
+        let old = event_at(receiver_id, now - Duration::days(100));
+        let recent = event_at(receiver_id, now - Duration::days(1));
+        let events = Arc::new(MockEventRepository {
+            events: Mutex::new(HashMap::new()),
+        });
+        events.save(&old).await.unwrap();
+        events.save(&recent).await.unwrap();
+
+        let root = std::env::temp_dir().join(format!("xzepr-retention-{}", ulid::Ulid::new()));
+        let blobs_root = root.join("attachments");
+        let attachment_records = Arc::new(MockAttachmentRepository::default());
+        let attachments = EventAttachmentHandler::new(
+            attachment_records.clone(),
+            Arc::new(FilesystemBlobStore::new(&blobs_root)),
+        );
+        let mut stored = Vec::new();
+        for event in [&old, &recent] {
+            let file = NewAttachment {
+                filename: "sbom.json".to_string(),
+                content_type: "application/json".to_string(),
+                uploaded_by: event.owner_id(),
+            };
+            let mut content = OneChunk(Some(b"{}".to_vec()));
+            match attachments.upload(event, file, &mut content).await.unwrap() {
+                AttachmentUpload::Stored(attachment) => stored.push(attachment),
+                AttachmentUpload::Rejected(rejection) => panic!("{:?}", rejection),
+            }
+        }
+
+        let handler = EventRetentionHandler::new(
+            events.clone(),
+            Arc::new(FilesystemArchiveStore::new(root.join("archive"))),
+            Arc::new(MockArchiveIndex::default()),
+        )
+        .with_attachments(attachments);
+
+        let report = handler.run_once(now).await.unwrap();
+
+        assert_eq!(report.deleted, 1);
+        assert_eq!(report.attachments, 1);
+        assert!(!blobs_root.join(&stored[0].storage_key).exists());
+        assert!(blobs_root.join(&stored[1].storage_key).is_file());
+        let remaining = attachment_records.attachments.lock().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(remaining.contains_key(&stored[1].id));
+        drop(remaining);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retention_archives_offloaded_payloads_in_full() {
+        let now = Utc::now();
+        let receiver_id = EventReceiverId::new();
+        let mut old = event_at(receiver_id, now - Duration::days(100));
+        let payload = json!({"build": 42, "log": "x".repeat(4096)});
+        old.restore_payload(payload.clone());
+
+        let root = std::env::temp_dir().join(format!("xzepr-retention-{}", ulid::Ulid::new()));
+        let blobs_root = root.join("payloads");
+        let offloader =
+            PayloadOffloader::new(Arc::new(FilesystemBlobStore::new(&blobs_root)), 1024);
+        assert!(offloader.offload(&mut old).await.unwrap());
+        let blob_path = blobs_root.join(&old.payload_summary().unwrap().blob_key);
+        assert!(blob_path.is_file());
+
+        let events = Arc::new(MockEventRepository {
+            events: Mutex::new(HashMap::new()),
+        });
+        events.save(&old).await.unwrap();
+        let store = Arc::new(FilesystemArchiveStore::new(root.join("archive")));
+        let index = Arc::new(MockArchiveIndex::default());
+        let handler = EventRetentionHandler::new(events.clone(), store.clone(), index.clone())
+            .with_payload_offloader(offloader);
+
+        assert_eq!(handler.run_once(now).await.unwrap().deleted, 1);
+
+        let entry = index.find_archived(old.id()).await.unwrap().unwrap();
+        let archived = store
+            .get_by_id(&entry.segment, old.id())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(archived.payload(), &payload);
+        assert!(archived.payload_summary().is_none());
+        assert!(!blob_path.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,661 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/event_stats_handler.rs
+
+use crate::domain::repositories::event_rollup_repo::{
+    rollup_bucket, rollup_bucket_width, EventCountBucket, EventCountRepository,
+    EventRollupRepository, ReceiverEventCounts,
+};
+use crate::domain::repositories::system_summary_repo::EventOutcomeCounts;
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use crate::infrastructure::jobs::{Job, JobReport, JobSchedule};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Default age after which a finished bucket is read from the rollup
+pub const DEFAULT_ROLLUP_FRESHNESS_SECONDS: i64 = 600;
+
+/// Default number of recent hours the reconciliation job recomputes
+pub const DEFAULT_RECONCILE_LOOKBACK_HOURS: i64 = 3;
+
+/// Default time between reconciliation passes
+pub const DEFAULT_RECONCILE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Name of the reconciliation job in settings and the admin API
+pub const RECONCILE_JOB_NAME: &str = "event_rollup_reconcile";
+
+/// Time a bucket must have been finished before it is reconciled
+///
+/// Events are stamped before they are inserted, so a bucket can still
+/// receive rows for a moment after it ends.
+const RECONCILE_GRACE_SECONDS: i64 = 60;
+
+/// Split of a stats query between the rollup and the raw events table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsQueryPlan {
+    /// Bucket-aligned range answered by the rollup
+    pub rollup: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    /// Partial buckets at either end, answered by raw events
+    pub raw: Vec<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+/// Application service answering event count queries
+///
+/// Finished buckets older than the freshness threshold are read from the
+/// hourly rollup; the current bucket, recent buckets the reconciliation job
+/// may still correct, and partial buckets at the edges of the range are
+/// counted from raw events. The two results are added together.
+#[derive(Clone)]
+pub struct EventStatsHandler {
+    raw: Arc<dyn EventCountRepository>,
+    rollup: Arc<dyn EventCountRepository>,
+    freshness: Duration,
+}
+
+impl EventStatsHandler {
+    /// Creates a new stats handler using the default freshness threshold
+    pub fn new(raw: Arc<dyn EventCountRepository>, rollup: Arc<dyn EventCountRepository>) -> Self {
+        Self {
+            raw,
+            rollup,
+            freshness: Duration::seconds(DEFAULT_ROLLUP_FRESHNESS_SECONDS),
+        }
+    }
+
+    /// Sets how long after it ends a bucket is still read from raw events
+    pub fn with_freshness(mut self, freshness: Duration) -> Self {
+        self.freshness = freshness;
+        self
+    }
+
+    /// Splits `[start, end)` into rollup and raw ranges as of `now`
+    pub fn plan(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> StatsQueryPlan {
+        if start >= end {
+            return StatsQueryPlan::default();
+        }
+
+        let first_full = match rollup_bucket(start) {
+            bucket if bucket == start => start,
+            bucket => bucket + rollup_bucket_width(),
+        };
+        let settled = rollup_bucket(now - self.freshness);
+        let rollup_end = rollup_bucket(end).min(settled);
+
+        if first_full >= rollup_end {
+            return StatsQueryPlan {
+                rollup: None,
+                raw: vec![(start, end)],
+            };
+        }
+
+        let mut raw = Vec::new();
+        if start < first_full {
+            raw.push((start, first_full));
+        }
+        if rollup_end < end {
+            raw.push((rollup_end, end));
+        }
+
+        StatsQueryPlan {
+            rollup: Some((first_full, rollup_end)),
+            raw,
+        }
+    }
+
+    /// Counts events per receiver created in `[start, end)`, busiest first
+    pub async fn receiver_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ReceiverEventCounts>> {
+        let plan = self.plan(start, end, Utc::now());
+        debug!(?plan, "Planned event count query");
+
+        let mut totals: HashMap<EventReceiverId, ReceiverEventCounts> = HashMap::new();
+        let mut add = |counts: Vec<ReceiverEventCounts>| {
+            for count in counts {
+                totals
+                    .entry(count.receiver_id)
+                    .and_modify(|total| {
+                        total.successful += count.successful;
+                        total.failed += count.failed;
+                    })
+                    .or_insert(count);
+            }
+        };
+
+        if let Some((rollup_start, rollup_end)) = plan.rollup {
+            add(self
+                .rollup
+                .receiver_event_counts(rollup_start, rollup_end)
+                .await?);
+        }
+        for (raw_start, raw_end) in plan.raw {
+            add(self.raw.receiver_event_counts(raw_start, raw_end).await?);
+        }
+
+        let mut counts: Vec<ReceiverEventCounts> = totals.into_values().collect();
+        counts.sort_by(|a, b| {
+            b.total()
+                .cmp(&a.total())
+                .then_with(|| a.receiver_id.to_string().cmp(&b.receiver_id.to_string()))
+        });
+        Ok(counts)
+    }
+
+    /// Counts events created in `[start, end)`, split by outcome
+    pub async fn outcome_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<EventOutcomeCounts> {
+        Ok(self.receiver_counts(start, end).await?.iter().fold(
+            EventOutcomeCounts::default(),
+            |mut total, counts| {
+                total.successful += counts.successful;
+                total.failed += counts.failed;
+                total
+            },
+        ))
+    }
+}
+
+/// Outcome of a single reconciliation pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// Buckets recomputed from raw events
+    pub buckets: usize,
+    /// Buckets whose rollup count differed from raw events
+    pub corrected: usize,
+}
+
+/// Background job healing drift between the rollup and raw events
+///
+/// Each pass recomputes the finished buckets of the last few hours from raw
+/// events and, if any differ, replaces them in the rollup. Run it more often
+/// than the stats freshness threshold so that buckets are healed before
+/// queries start reading them from the rollup.
+#[derive(Clone)]
+pub struct EventRollupReconciler {
+    raw: Arc<dyn EventCountRepository>,
+    rollup: Arc<dyn EventRollupRepository>,
+    lookback: Duration,
+    interval: std::time::Duration,
+}
+
+impl EventRollupReconciler {
+    /// Creates a new reconciler using the default lookback
+    pub fn new(raw: Arc<dyn EventCountRepository>, rollup: Arc<dyn EventRollupRepository>) -> Self {
+        Self {
+            raw,
+            rollup,
+            lookback: Duration::hours(DEFAULT_RECONCILE_LOOKBACK_HOURS),
+            interval: DEFAULT_RECONCILE_INTERVAL,
+        }
+    }
+
+    /// Sets how far back each pass recomputes buckets
+    pub fn with_lookback(mut self, lookback: Duration) -> Self {
+        self.lookback = lookback;
+        self
+    }
+
+    /// Sets the time between reconciliation passes when run as a job
+    pub fn with_interval(mut self, interval: std::time::Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Reconciles the finished buckets within the lookback as of `now`
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<ReconcileReport> {
+        let start = rollup_bucket(now - self.lookback);
+        let end = rollup_bucket(now - Duration::seconds(RECONCILE_GRACE_SECONDS));
+        if start >= end {
+            return Ok(ReconcileReport::default());
+        }
+
+        let raw = self.raw.bucket_counts(start, end).await?;
+        let mut rolled: HashMap<_, u64> = self
+            .rollup
+            .bucket_counts(start, end)
+            .await?
+            .into_iter()
+            .map(|bucket| (bucket_key(&bucket), bucket.count))
+            .collect();
+
+        let mut corrected = 0;
+        for bucket in &raw {
+            if rolled.remove(&bucket_key(bucket)) != Some(bucket.count) {
+                corrected += 1;
+            }
+        }
+        // Rollup rows without matching raw events
+        corrected += rolled.values().filter(|count| **count > 0).count();
+
+        if corrected > 0 {
+            self.rollup.replace_buckets(start, end, &raw).await?;
+            warn!(
+                start = %start,
+                end = %end,
+                corrected,
+                "Healed event rollup drift"
+            );
+        }
+
+        Ok(ReconcileReport {
+            buckets: raw.len(),
+            corrected,
+        })
+    }
+}
+
+#[async_trait]
+impl Job for EventRollupReconciler {
+    fn name(&self) -> &str {
+        RECONCILE_JOB_NAME
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::Every(self.interval)
+    }
+
+    async fn run(&self) -> Result<JobReport> {
+        let report = self.run_once(Utc::now()).await?;
+        if report.corrected > 0 {
+            info!(
+                buckets = report.buckets,
+                corrected = report.corrected,
+                "Event rollup reconciliation pass complete"
+            );
+        }
+        Ok(JobReport {
+            processed: report.buckets,
+            more_pending: false,
+        })
+    }
+}
+
+/// Identifies a rollup row
+type BucketKey = (EventReceiverId, DateTime<Utc>, String, bool);
+
+fn bucket_key(bucket: &EventCountBucket) -> BucketKey {
+    (
+        bucket.receiver_id,
+        bucket.bucket_start,
+        bucket.severity.clone(),
+        bucket.success,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::event_rollup_repo::event_severity;
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    struct RawEvent {
+        receiver_id: EventReceiverId,
+        created_at: DateTime<Utc>,
+        severity: String,
+        success: bool,
+    }
+
+    /// Raw events table that counts the queries it answers
+    #[derive(Default)]
+    struct MemoryEvents {
+        events: Mutex<Vec<RawEvent>>,
+        queries: AtomicUsize,
+    }
+
+    impl MemoryEvents {
+        fn buckets(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<EventCountBucket> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            let mut counts: HashMap<_, u64> = HashMap::new();
+            for event in self.events.lock().unwrap().iter() {
+                if event.created_at >= start && event.created_at < end {
+                    *counts
+                        .entry((
+                            event.receiver_id,
+                            rollup_bucket(event.created_at),
+                            event.severity.clone(),
+                            event.success,
+                        ))
+                        .or_default() += 1;
+                }
+            }
+            to_buckets(counts)
+        }
+    }
+
+    #[async_trait]
+    impl EventCountRepository for MemoryEvents {
+        async fn receiver_event_counts(
+            &self,
+            start: DateTime<Utc>,
+            end: DateTime<Utc>,
+        ) -> Result<Vec<ReceiverEventCounts>> {
+            Ok(by_receiver(self.buckets(start, end)))
+        }
+
+        async fn bucket_counts(
+            &self,
+            start: DateTime<Utc>,
+            end: DateTime<Utc>,
+        ) -> Result<Vec<EventCountBucket>> {
+            Ok(self.buckets(start, end))
+        }
+    }
+
+    /// Rollup table that counts the queries it answers
+    #[derive(Default)]
+    struct MemoryRollup {
+        rows: Mutex<HashMap<BucketKey, u64>>,
+        queries: AtomicUsize,
+    }
+
+    impl MemoryRollup {
+        fn buckets(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<EventCountBucket> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            let rows = self.rows.lock().unwrap();
+            to_buckets(
+                rows.iter()
+                    .filter(|((_, bucket_start, _, _), _)| {
+                        *bucket_start >= start && *bucket_start < end
+                    })
+                    .map(|(key, count)| (key.clone(), *count))
+                    .collect(),
+            )
+        }
+    }
+
+    #[async_trait]
+    impl EventCountRepository for MemoryRollup {
+        async fn receiver_event_counts(
+            &self,
+            start: DateTime<Utc>,
+            end: DateTime<Utc>,
+        ) -> Result<Vec<ReceiverEventCounts>> {
+            Ok(by_receiver(self.buckets(start, end)))
+        }
+
+        async fn bucket_counts(
+            &self,
+            start: DateTime<Utc>,
+            end: DateTime<Utc>,
+        ) -> Result<Vec<EventCountBucket>> {
+            Ok(self.buckets(start, end))
+        }
+    }
+
+    #[async_trait]
+    impl EventRollupRepository for MemoryRollup {
+        async fn replace_buckets(
+            &self,
+            start: DateTime<Utc>,
+            end: DateTime<Utc>,
+            buckets: &[EventCountBucket],
+        ) -> Result<()> {
+            let mut rows = self.rows.lock().unwrap();
+            rows.retain(|(_, bucket_start, _, _), _| *bucket_start < start || *bucket_start >= end);
+            for bucket in buckets {
+                rows.insert(bucket_key(bucket), bucket.count);
+            }
+            Ok(())
+        }
+    }
+
+    fn to_buckets(counts: HashMap<BucketKey, u64>) -> Vec<EventCountBucket> {
+        let mut buckets: Vec<EventCountBucket> = counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(
+                |((receiver_id, bucket_start, severity, success), count)| EventCountBucket {
+                    receiver_id,
+                    bucket_start,
+                    severity,
+                    success,
+                    count,
+                },
+            )
+            .collect();
+        buckets.sort_by_key(|b| {
+            (
+                b.receiver_id.to_string(),
+                b.bucket_start,
+                b.severity.clone(),
+                b.success,
+            )
+        });
+        buckets
+    }
+
+    fn by_receiver(buckets: Vec<EventCountBucket>) -> Vec<ReceiverEventCounts> {
+        let mut totals: HashMap<EventReceiverId, ReceiverEventCounts> = HashMap::new();
+        for bucket in buckets {
+            let total = totals
+                .entry(bucket.receiver_id)
+                .or_insert(ReceiverEventCounts {
+                    receiver_id: bucket.receiver_id,
+                    successful: 0,
+                    failed: 0,
+                });
+            if bucket.success {
+                total.successful += bucket.count;
+            } else {
+                total.failed += bucket.count;
+            }
+        }
+        totals.into_values().collect()
+    }
+
+    struct Store {
+        events: Arc<MemoryEvents>,
+        rollup: Arc<MemoryRollup>,
+    }
+
+    impl Store {
+        fn new() -> Self {
+            Self {
+                events: Arc::new(MemoryEvents::default()),
+                rollup: Arc::new(MemoryRollup::default()),
+            }
+        }
+
+        /// Inserts an event without touching the rollup
+        fn insert_raw(&self, receiver_id: EventReceiverId, created_at: DateTime<Utc>, n: usize) {
+            let payload = match n % 3 {
+                0 => json!({"severity": "ERROR"}),
+                1 => json!({"severity": "info"}),
+                _ => json!({}),
+            };
+            self.events.events.lock().unwrap().push(RawEvent {
+                receiver_id,
+                created_at,
+                severity: event_severity(&payload),
+                success: !n.is_multiple_of(4),
+            });
+        }
+
+        /// Inserts an event and increments its bucket, as ingestion does
+        fn ingest(&self, receiver_id: EventReceiverId, created_at: DateTime<Utc>, n: usize) {
+            self.insert_raw(receiver_id, created_at, n);
+            let events = self.events.events.lock().unwrap();
+            let event = events.last().unwrap();
+            *self
+                .rollup
+                .rows
+                .lock()
+                .unwrap()
+                .entry((
+                    receiver_id,
+                    rollup_bucket(created_at),
+                    event.severity.clone(),
+                    event.success,
+                ))
+                .or_default() += 1;
+        }
+
+        fn stats(&self) -> EventStatsHandler {
+            EventStatsHandler::new(self.events.clone(), self.rollup.clone())
+        }
+
+        fn reconciler(&self) -> EventRollupReconciler {
+            EventRollupReconciler::new(self.events.clone(), self.rollup.clone())
+        }
+
+        fn reset_queries(&self) {
+            self.events.queries.store(0, Ordering::SeqCst);
+            self.rollup.queries.store(0, Ordering::SeqCst);
+        }
+    }
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 5, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_plan_reads_partial_buckets_from_raw() {
+        let store = Store::new();
+        let stats = store.stats();
+
+        // Both edges are partial buckets; 10:00-13:00 is settled
+        let plan = stats.plan(at(9, 30), at(14, 20), at(14, 5));
+        assert_eq!(plan.rollup, Some((at(10, 0), at(13, 0))));
+        assert_eq!(
+            plan.raw,
+            vec![(at(9, 30), at(10, 0)), (at(13, 0), at(14, 20))]
+        );
+
+        // A range within one bucket never touches the rollup
+        let plan = stats.plan(at(9, 10), at(9, 50), at(14, 5));
+        assert_eq!(plan.rollup, None);
+        assert_eq!(plan.raw, vec![(at(9, 10), at(9, 50))]);
+
+        // A bucket that ended within the freshness threshold is still raw
+        let plan = stats.plan(at(10, 0), at(14, 0), at(14, 5));
+        assert_eq!(plan.rollup, Some((at(10, 0), at(13, 0))));
+        assert_eq!(plan.raw, vec![(at(13, 0), at(14, 0))]);
+    }
+
+    #[tokio::test]
+    async fn test_thirty_day_query_reads_only_rollup() {
+        let store = Store::new();
+        let receiver_id = EventReceiverId::new();
+        let end = rollup_bucket(Utc::now() - Duration::days(1));
+        let start = end - Duration::days(30);
+        for n in 0..720 {
+            store.ingest(receiver_id, start + Duration::hours(n as i64), n);
+        }
+        store.reset_queries();
+
+        let counts = store.stats().outcome_counts(start, end).await.unwrap();
+
+        assert_eq!(counts.total(), 720);
+        assert_eq!(store.rollup.queries.load(Ordering::SeqCst), 1);
+        assert_eq!(store.events.queries.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_recent_range_stitches_rollup_and_raw() {
+        let store = Store::new();
+        let busy = EventReceiverId::new();
+        let quiet = EventReceiverId::new();
+        let now = Utc::now();
+        for n in 0..300 {
+            store.ingest(busy, now - Duration::minutes(n as i64), n);
+        }
+        for n in 0..20 {
+            store.ingest(quiet, now - Duration::minutes(17 * n as i64), n);
+        }
+
+        let start = now - Duration::hours(4);
+        let end = now + Duration::seconds(1);
+        let stitched = store.stats().receiver_counts(start, end).await.unwrap();
+        let mut raw = store
+            .events
+            .receiver_event_counts(start, end)
+            .await
+            .unwrap();
+        raw.sort_by_key(|counts| std::cmp::Reverse(counts.total()));
+
+        assert_eq!(stitched, raw);
+        assert_eq!(stitched[0].receiver_id, busy);
+        assert!(store.rollup.queries.load(Ordering::SeqCst) > 0);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_heals_drift_after_crash() {
+        let store = Store::new();
+        let receivers = [EventReceiverId::new(), EventReceiverId::new()];
+        let now = Utc::now();
+        for n in 0..150 {
+            store.ingest(receivers[n % 2], now - Duration::minutes(n as i64), n);
+        }
+
+        // A crash between the event insert and the rollup increment loses
+        // increments; a replayed increment double counts
+        for n in 0..25 {
+            store.insert_raw(receivers[0], now - Duration::minutes(3 * n as i64 + 70), n);
+        }
+        store
+            .rollup
+            .rows
+            .lock()
+            .unwrap()
+            .values_mut()
+            .take(3)
+            .for_each(|count| *count += 2);
+
+        let window = (
+            rollup_bucket(now - Duration::hours(3)),
+            rollup_bucket(now - Duration::seconds(RECONCILE_GRACE_SECONDS)),
+        );
+        assert_ne!(
+            store
+                .rollup
+                .bucket_counts(window.0, window.1)
+                .await
+                .unwrap(),
+            store
+                .events
+                .bucket_counts(window.0, window.1)
+                .await
+                .unwrap()
+        );
+
+        let report = store.reconciler().run_once(now).await.unwrap();
+        assert!(report.corrected > 0);
+
+        assert_eq!(
+            store
+                .rollup
+                .bucket_counts(window.0, window.1)
+                .await
+                .unwrap(),
+            store
+                .events
+                .bucket_counts(window.0, window.1)
+                .await
+                .unwrap()
+        );
+
+        // A second pass finds nothing to fix
+        let report = store.reconciler().run_once(now).await.unwrap();
+        assert_eq!(report.corrected, 0);
+    }
+}
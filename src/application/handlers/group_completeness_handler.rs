@@ -0,0 +1,470 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/group_completeness_handler.rs
+
+use crate::application::domain_events::{DomainEvent, DomainEventBus, DomainEventSubscriber};
+use crate::application::handlers::system_events::{
+    report_system_event_failure, SystemEventFactory, GROUP_RELEASE_COMPLETED_EVENT,
+};
+use crate::domain::entities::event::{Event, EventOrigin};
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::entities::release_completeness::{GroupCompleteness, ReceiverReleaseStatus};
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::domain::repositories::release_completion_repo::ReleaseCompletionRepository;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+use crate::infrastructure::PrometheusMetrics;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Name of the completeness tracker in logs and metrics
+pub const GROUP_COMPLETENESS_SUBSCRIBER_NAME: &str = "group_completeness";
+
+/// Application service reporting whether the receivers of a group have all
+/// reported a release
+///
+/// A report costs one aggregate query over the events of the group's
+/// receivers. As a domain event subscriber the handler re-evaluates the
+/// groups of each successful event's receiver, and the first time a group
+/// completes a release it records an
+/// `xzepr.event.receiver.group.release_completed` system event. Completions
+/// are only announced with a completion repository, which makes sure each
+/// (group, release) pair is announced once.
+#[derive(Clone)]
+pub struct GroupCompletenessHandler {
+    group_repository: Arc<dyn EventReceiverGroupRepository>,
+    event_repository: Arc<dyn EventRepository>,
+    completions: Option<Arc<dyn ReleaseCompletionRepository>>,
+    event_bus: Option<DomainEventBus>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    system_events: SystemEventFactory,
+}
+
+impl GroupCompletenessHandler {
+    /// Creates a handler that reports completeness without announcing it
+    pub fn new(
+        group_repository: Arc<dyn EventReceiverGroupRepository>,
+        event_repository: Arc<dyn EventRepository>,
+    ) -> Self {
+        Self {
+            group_repository,
+            event_repository,
+            completions: None,
+            event_bus: None,
+            metrics: None,
+            system_events: SystemEventFactory::new(),
+        }
+    }
+
+    /// Remembers completed releases so each completion is announced once
+    pub fn with_completions(mut self, completions: Arc<dyn ReleaseCompletionRepository>) -> Self {
+        self.completions = Some(completions);
+        self
+    }
+
+    /// Announces completed releases on the domain event bus
+    pub fn with_event_bus(mut self, event_bus: DomainEventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Attaches metrics used to count system event failures
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Sets the factory that builds and stores system events
+    pub fn with_system_event_factory(mut self, factory: SystemEventFactory) -> Self {
+        self.system_events = factory;
+        self
+    }
+
+    /// Reports the release status of every receiver of a group
+    ///
+    /// Only events stored at or after `since` count when it is set. A
+    /// complete group is announced if it has not been before.
+    pub async fn completeness(
+        &self,
+        group_id: EventReceiverGroupId,
+        release: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<GroupCompleteness> {
+        if release.trim().is_empty() {
+            return Err(DomainError::ValidationError {
+                field: "release".to_string(),
+                message: Message::new("validation.completeness_release_empty"),
+            }
+            .into());
+        }
+
+        let group = self
+            .group_repository
+            .find_by_id(group_id)
+            .await?
+            .ok_or(DomainError::GroupNotFound)?;
+        self.evaluate(&group, release, since).await
+    }
+
+    /// Evaluates one group and announces it if it completed the release
+    async fn evaluate(
+        &self,
+        group: &EventReceiverGroup,
+        release: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<GroupCompleteness> {
+        let outcomes = self
+            .event_repository
+            .find_release_outcomes(group.event_receiver_ids(), release, since)
+            .await?;
+        let completeness = GroupCompleteness::evaluate(group, release, since, &outcomes);
+
+        if completeness.complete {
+            self.announce_completion(group, &completeness).await?;
+        }
+        Ok(completeness)
+    }
+
+    /// Records the first completion of a release and its system event
+    async fn announce_completion(
+        &self,
+        group: &EventReceiverGroup,
+        completeness: &GroupCompleteness,
+    ) -> Result<()> {
+        let Some(completions) = &self.completions else {
+            return Ok(());
+        };
+        if !completions
+            .record_completion(group.id(), &completeness.release, Utc::now())
+            .await?
+        {
+            return Ok(());
+        }
+
+        info!(
+            group_id = %group.id(),
+            release = %completeness.release,
+            "Event receiver group completed release"
+        );
+
+        let system_event = match self.create_release_completed_event(group, completeness) {
+            Ok(system_event) => {
+                self.system_events.record(&system_event).await;
+                Some(system_event)
+            }
+            Err(e) => {
+                // The completion is recorded, so it is not announced again
+                report_system_event_failure(
+                    self.metrics.as_deref(),
+                    GROUP_RELEASE_COMPLETED_EVENT,
+                    &group.id().to_string(),
+                    &e,
+                );
+                None
+            }
+        };
+        if let Some(bus) = &self.event_bus {
+            bus.publish(DomainEvent::GroupReleaseCompleted {
+                group: group.clone(),
+                release: completeness.release.clone(),
+                system_event,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Creates a system event for a group completing a release
+    fn create_release_completed_event(
+        &self,
+        group: &EventReceiverGroup,
+        completeness: &GroupCompleteness,
+    ) -> std::result::Result<Event, DomainError> {
+        use serde_json::json;
+
+        let receivers: Vec<_> = completeness
+            .receivers
+            .iter()
+            .map(|receiver| {
+                json!({
+                    "receiver_id": receiver.receiver_id.to_string(),
+                    "required": receiver.required,
+                    "status": receiver.status.as_str(),
+                })
+            })
+            .collect();
+
+        let payload = json!({
+            "group_id": group.id().to_string(),
+            "name": group.name(),
+            "release": completeness.release,
+            "receivers": receivers,
+            "complete_count": completeness.count(ReceiverReleaseStatus::Complete),
+        });
+
+        // A complete group has at least one required receiver
+        let receiver_id = completeness
+            .receivers
+            .iter()
+            .find(|receiver| receiver.required)
+            .map(|receiver| receiver.receiver_id)
+            .unwrap_or_else(|| EventReceiverId::from(group.id().as_ulid()));
+
+        self.system_events.build(
+            GROUP_RELEASE_COMPLETED_EVENT,
+            format!(
+                "Event receiver group '{}' completed release '{}'",
+                group.name(),
+                completeness.release
+            ),
+            payload,
+            receiver_id,
+            group.owner_id(),
+        )
+    }
+}
+
+#[async_trait]
+impl DomainEventSubscriber for GroupCompletenessHandler {
+    fn name(&self) -> &str {
+        GROUP_COMPLETENESS_SUBSCRIBER_NAME
+    }
+
+    /// Re-evaluates the groups requiring the receiver of a successful event
+    async fn handle(&self, event: &DomainEvent) -> Result<()> {
+        let DomainEvent::EventCreated { event } = event else {
+            return Ok(());
+        };
+        if event.origin() != EventOrigin::User || !event.success() || event.release().is_empty() {
+            return Ok(());
+        }
+
+        let receiver_id = event.event_receiver_id();
+        for group in self
+            .group_repository
+            .find_by_event_receiver_id(receiver_id)
+            .await?
+        {
+            // Optional receivers never change whether a group is complete
+            if !group.is_required(receiver_id) {
+                continue;
+            }
+            debug!(
+                group_id = %group.id(),
+                release = %event.release(),
+                "Re-evaluating group release completeness"
+            );
+            self.evaluate(&group, event.release(), None).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{EventFixture, GroupFixture};
+    use crate::infrastructure::memory::{
+        InMemoryEventReceiverGroupRepository, InMemoryEventRepository,
+        InMemoryReleaseCompletionRepository,
+    };
+
+    struct Fixture {
+        groups: Arc<InMemoryEventReceiverGroupRepository>,
+        events: Arc<InMemoryEventRepository>,
+        system_events: Arc<InMemoryEventRepository>,
+        handler: GroupCompletenessHandler,
+    }
+
+    fn fixture() -> Fixture {
+        let groups = Arc::new(InMemoryEventReceiverGroupRepository::new());
+        let events = Arc::new(InMemoryEventRepository::new());
+        let system_events = Arc::new(InMemoryEventRepository::new());
+        let handler = GroupCompletenessHandler::new(groups.clone(), events.clone())
+            .with_completions(Arc::new(InMemoryReleaseCompletionRepository::default()))
+            .with_system_event_factory(SystemEventFactory::new().with_store(system_events.clone()));
+        Fixture {
+            groups,
+            events,
+            system_events,
+            handler,
+        }
+    }
+
+    async fn save_group(
+        fixture: &Fixture,
+        receiver_ids: &[EventReceiverId],
+        optional: &[EventReceiverId],
+    ) -> EventReceiverGroup {
+        let mut group = GroupFixture::new().name("Release Gate");
+        for id in receiver_ids {
+            group = if optional.contains(id) {
+                group.optional_receiver_id(*id)
+            } else {
+                group.receiver_id(*id)
+            };
+        }
+        group.persist(fixture.groups.as_ref()).await
+    }
+
+    async fn store_event(
+        fixture: &Fixture,
+        receiver_id: EventReceiverId,
+        release: &str,
+        success: bool,
+    ) -> Event {
+        EventFixture::new()
+            .name("deploy.finished")
+            .release(release)
+            .receiver_id(receiver_id)
+            .success(success)
+            .persist(fixture.events.as_ref())
+            .await
+    }
+
+    async fn completion_events(fixture: &Fixture) -> Vec<Event> {
+        fixture
+            .system_events
+            .find_by_name(GROUP_RELEASE_COMPLETED_EVENT)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_completeness_reports_each_receiver() {
+        let fixture = fixture();
+        let ids = [
+            EventReceiverId::new(),
+            EventReceiverId::new(),
+            EventReceiverId::new(),
+        ];
+        let group = save_group(&fixture, &ids, &[]).await;
+        store_event(&fixture, ids[0], "2.0.0", true).await;
+        store_event(&fixture, ids[1], "2.0.0", false).await;
+        store_event(&fixture, ids[2], "1.9.0", true).await;
+
+        let completeness = fixture
+            .handler
+            .completeness(group.id(), "2.0.0", None)
+            .await
+            .unwrap();
+
+        assert!(!completeness.complete);
+        let statuses: Vec<_> = completeness.receivers.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ReceiverReleaseStatus::Complete,
+                ReceiverReleaseStatus::FailedOnly,
+                ReceiverReleaseStatus::Missing,
+            ]
+        );
+        assert!(completion_events(&fixture).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_completeness_rejects_empty_release_and_unknown_group() {
+        let fixture = fixture();
+        let group = save_group(&fixture, &[EventReceiverId::new()], &[]).await;
+
+        assert!(fixture
+            .handler
+            .completeness(group.id(), " ", None)
+            .await
+            .is_err());
+        assert!(fixture
+            .handler
+            .completeness(EventReceiverGroupId::new(), "2.0.0", None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_completion_event_fires_exactly_once() {
+        let fixture = fixture();
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let handler = fixture.handler.clone().with_event_bus(bus);
+        let ids = [EventReceiverId::new(), EventReceiverId::new()];
+        let optional = EventReceiverId::new();
+        let group = save_group(&fixture, &[ids[0], ids[1], optional], &[optional]).await;
+
+        let first = store_event(&fixture, ids[0], "2.0.0", true).await;
+        handler
+            .handle(&DomainEvent::EventCreated { event: first })
+            .await
+            .unwrap();
+        assert!(completion_events(&fixture).await.is_empty());
+
+        // The optional receiver never reports, and a failure after the
+        // success does not undo it
+        for success in [true, false] {
+            let event = store_event(&fixture, ids[1], "2.0.0", success).await;
+            handler
+                .handle(&DomainEvent::EventCreated { event })
+                .await
+                .unwrap();
+        }
+        let completeness = handler
+            .completeness(group.id(), "2.0.0", None)
+            .await
+            .unwrap();
+        assert!(completeness.complete);
+
+        let recorded = completion_events(&fixture).await;
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].payload()["release"], "2.0.0");
+        assert_eq!(recorded[0].event_receiver_id(), ids[0]);
+        match notifications.try_recv().unwrap().as_ref() {
+            DomainEvent::GroupReleaseCompleted {
+                group: completed,
+                release,
+                system_event,
+            } => {
+                assert_eq!(completed.id(), group.id());
+                assert_eq!(release, "2.0.0");
+                assert!(system_event.is_some());
+            }
+            other => panic!("unexpected notification {:?}", other.kind()),
+        }
+        assert!(notifications.try_recv().is_err());
+
+        // Another release completes separately
+        for id in ids {
+            let event = store_event(&fixture, id, "2.1.0", true).await;
+            handler
+                .handle(&DomainEvent::EventCreated { event })
+                .await
+                .unwrap();
+        }
+        assert_eq!(completion_events(&fixture).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_completion_is_not_announced_without_completions() {
+        let fixture = fixture();
+        let handler = GroupCompletenessHandler::new(fixture.groups.clone(), fixture.events.clone())
+            .with_system_event_factory(
+                SystemEventFactory::new().with_store(fixture.system_events.clone()),
+            );
+        let id = EventReceiverId::new();
+        let group = save_group(&fixture, &[id], &[]).await;
+        store_event(&fixture, id, "2.0.0", true).await;
+
+        assert!(
+            handler
+                .completeness(group.id(), "2.0.0", None)
+                .await
+                .unwrap()
+                .complete
+        );
+        assert!(completion_events(&fixture).await.is_empty());
+    }
+}
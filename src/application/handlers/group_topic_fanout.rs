@@ -0,0 +1,713 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/group_topic_fanout.rs
+
+use crate::application::domain_events::{DomainEvent, DomainEventBus};
+use crate::application::handlers::event_outbox_relay::{
+    DEFAULT_OUTBOX_BATCH_SIZE, DEFAULT_OUTBOX_MAX_ATTEMPTS,
+};
+use crate::domain::entities::delivery_failure::{DeliveryFailure, DeliveryMechanism};
+use crate::domain::entities::event::Event;
+use crate::domain::entities::event_publication::publish_retry_delay;
+use crate::domain::repositories::event_outbox_repo::{
+    GroupTopicOutboxEntry, GroupTopicOutboxRepository,
+};
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use crate::infrastructure::maintenance::MaintenanceMode;
+use crate::infrastructure::messaging::producer::EventPublisher;
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+use chrono::{DateTime, Utc};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+/// Outcome of fanning one event out to its group topics
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FanoutReport {
+    pub published: Vec<String>,
+    /// Topics whose publish failed and will be retried
+    pub deferred: Vec<String>,
+}
+
+/// Outcome of a single fan-out retry pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FanoutRetryReport {
+    pub published: usize,
+    /// Failed again and scheduled for a later attempt
+    pub deferred: usize,
+    pub dead_lettered: usize,
+    /// No longer routed, because the group was disabled or changed topic
+    pub dropped: usize,
+}
+
+/// Application service publishing events to the dedicated topics of their
+/// receiver's groups
+///
+/// The primary publish is authoritative: events are fanned out only once
+/// the primary topic accepted them, and a fan-out never fails the caller.
+/// A group topic that does not accept an event is retried from its own
+/// outbox, backing off like the primary outbox, without holding up the
+/// other topics. Fan-outs given up on are announced as
+/// [`DomainEvent::DeliveryFailed`] when an event bus is set.
+#[derive(Clone)]
+pub struct GroupTopicFanout {
+    group_repository: Arc<dyn EventReceiverGroupRepository>,
+    event_repository: Arc<dyn EventRepository>,
+    publisher: Arc<dyn EventPublisher>,
+    outbox: Option<Arc<dyn GroupTopicOutboxRepository>>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    maintenance: Option<MaintenanceMode>,
+    event_bus: Option<DomainEventBus>,
+    max_attempts: u32,
+    batch_size: usize,
+}
+
+impl GroupTopicFanout {
+    /// Creates a fan-out using the default attempt limit and batch size
+    pub fn new(
+        group_repository: Arc<dyn EventReceiverGroupRepository>,
+        event_repository: Arc<dyn EventRepository>,
+        publisher: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            group_repository,
+            event_repository,
+            publisher,
+            outbox: None,
+            metrics: None,
+            maintenance: None,
+            event_bus: None,
+            max_attempts: DEFAULT_OUTBOX_MAX_ATTEMPTS,
+            batch_size: DEFAULT_OUTBOX_BATCH_SIZE,
+        }
+    }
+
+    /// Queues fan-outs whose publish failed for a later retry
+    pub fn with_outbox(mut self, outbox: Arc<dyn GroupTopicOutboxRepository>) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
+    /// Counts fan-outs by topic and publish outcome
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Pauses retries while `maintenance` is on
+    pub fn with_maintenance(mut self, maintenance: MaintenanceMode) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+
+    /// Announces dead-lettered fan-outs on the domain event bus
+    pub fn with_event_bus(mut self, event_bus: DomainEventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Sets how many failed attempts dead-letter a fan-out
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the maximum number of entries retried per pass
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Returns the dedicated topics of the enabled groups containing
+    /// `receiver_id`, without duplicates
+    pub async fn routed_topics(&self, receiver_id: EventReceiverId) -> Result<Vec<String>> {
+        let mut topics: Vec<String> = self
+            .group_repository
+            .find_by_event_receiver_id(receiver_id)
+            .await?
+            .iter()
+            .filter_map(|group| group.routed_topic().map(str::to_string))
+            .collect();
+        topics.sort();
+        topics.dedup();
+        Ok(topics)
+    }
+
+    /// Publishes an event the primary topic accepted to its group topics
+    ///
+    /// Failed topics are queued for retry when an outbox is configured.
+    pub async fn fan_out(&self, event: &Event) -> FanoutReport {
+        let mut report = FanoutReport::default();
+
+        let topics = match self.routed_topics(event.event_receiver_id()).await {
+            Ok(topics) => topics,
+            Err(e) => {
+                error!(
+                    event_id = %event.id(),
+                    error = %e,
+                    "Failed to look up group topics; event not fanned out"
+                );
+                return report;
+            }
+        };
+
+        for topic in topics {
+            match self.publisher.publish_to(&topic, event).await {
+                Ok(()) => {
+                    self.record_outcome(&topic, "published");
+                    report.published.push(topic);
+                }
+                Err(publish_error) => {
+                    self.defer(event, &topic, &publish_error.to_string()).await;
+                    self.record_outcome(&topic, "deferred");
+                    report.deferred.push(topic);
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Queues the first failed publish of a fan-out
+    async fn defer(&self, event: &Event, topic: &str, publish_error: &str) {
+        let Some(outbox) = &self.outbox else {
+            error!(
+                event_id = %event.id(),
+                topic,
+                error = publish_error,
+                "Failed to publish event to group topic (no outbox to retry from)"
+            );
+            return;
+        };
+
+        let next_attempt_at = Utc::now() + publish_retry_delay(1);
+        match outbox
+            .enqueue(event.id(), topic, publish_error, next_attempt_at)
+            .await
+        {
+            Ok(()) => warn!(
+                event_id = %event.id(),
+                topic,
+                error = publish_error,
+                "Failed to publish event to group topic; queued for retry"
+            ),
+            Err(e) => error!(
+                event_id = %event.id(),
+                topic,
+                error = %e,
+                "Failed to queue group topic publish for retry"
+            ),
+        }
+    }
+
+    /// Retries up to one batch of fan-outs due at `now`
+    ///
+    /// Entries whose event was deleted, or whose topic is no longer routed
+    /// for the event's receiver, leave the outbox without a publish. Does
+    /// nothing while maintenance mode is on.
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<FanoutRetryReport> {
+        let mut report = FanoutRetryReport::default();
+        let Some(outbox) = &self.outbox else {
+            return Ok(report);
+        };
+        if self
+            .maintenance
+            .as_ref()
+            .is_some_and(MaintenanceMode::is_active)
+        {
+            debug!("Group topic fan-out retries paused for maintenance");
+            return Ok(report);
+        }
+
+        let mut routes: HashMap<EventReceiverId, Vec<String>> = HashMap::new();
+        for entry in outbox.find_due(now, self.batch_size).await? {
+            let Some(event) = self.event_repository.find_by_id(entry.event_id).await? else {
+                warn!(event_id = %entry.event_id, "Fanned out event no longer exists");
+                outbox.mark_published(entry.event_id, &entry.topic).await?;
+                continue;
+            };
+
+            let receiver_id = event.event_receiver_id();
+            if let Entry::Vacant(route) = routes.entry(receiver_id) {
+                route.insert(self.routed_topics(receiver_id).await?);
+            }
+            if !routes[&receiver_id].contains(&entry.topic) {
+                info!(
+                    event_id = %entry.event_id,
+                    topic = %entry.topic,
+                    "Group topic no longer routed; dropping fan-out retry"
+                );
+                outbox.mark_published(entry.event_id, &entry.topic).await?;
+                self.record_outcome(&entry.topic, "dropped");
+                report.dropped += 1;
+                continue;
+            }
+
+            match self.publisher.publish_to(&entry.topic, &event).await {
+                Ok(()) => {
+                    outbox.mark_published(entry.event_id, &entry.topic).await?;
+                    self.record_outcome(&entry.topic, "retried");
+                    report.published += 1;
+                }
+                Err(e) => {
+                    if self
+                        .retry(outbox, &entry, &event, &e.to_string(), now)
+                        .await?
+                    {
+                        report.deferred += 1;
+                    } else {
+                        report.dead_lettered += 1;
+                    }
+                }
+            }
+        }
+
+        if report != FanoutRetryReport::default() {
+            info!(
+                published = report.published,
+                deferred = report.deferred,
+                dead_lettered = report.dead_lettered,
+                dropped = report.dropped,
+                "Group topic fan-out retry pass complete"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Records a failed attempt; returns false if the entry was dead-lettered
+    async fn retry(
+        &self,
+        outbox: &Arc<dyn GroupTopicOutboxRepository>,
+        entry: &GroupTopicOutboxEntry,
+        event: &Event,
+        error: &str,
+        now: DateTime<Utc>,
+    ) -> Result<bool> {
+        let attempts = entry.attempts + 1;
+        if attempts >= self.max_attempts {
+            error!(
+                event_id = %entry.event_id,
+                topic = %entry.topic,
+                attempts,
+                error,
+                "Giving up on publishing event to group topic; moved to dead letters"
+            );
+            outbox
+                .record_failure(entry.event_id, &entry.topic, error, None)
+                .await?;
+            self.record_outcome(&entry.topic, "dead_lettered");
+            if let Some(bus) = &self.event_bus {
+                bus.publish(DomainEvent::DeliveryFailed {
+                    failure: DeliveryFailure::new(
+                        DeliveryMechanism::GroupTopicFanout,
+                        entry.topic.clone(),
+                        entry.event_id,
+                        event.event_receiver_id(),
+                        error,
+                        attempts,
+                        now,
+                    ),
+                    event: event.clone(),
+                });
+            }
+            return Ok(false);
+        }
+
+        let next_attempt_at = now + publish_retry_delay(attempts);
+        warn!(
+            event_id = %entry.event_id,
+            topic = %entry.topic,
+            attempts,
+            error,
+            next_attempt_at = %next_attempt_at,
+            "Group topic publish retry failed"
+        );
+        outbox
+            .record_failure(entry.event_id, &entry.topic, error, Some(next_attempt_at))
+            .await?;
+        Ok(true)
+    }
+
+    fn record_outcome(&self, topic: &str, outcome: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_group_topic_publish_outcome(topic, outcome);
+        }
+    }
+
+    /// Runs a retry pass every `interval` until the task is aborted
+    pub fn spawn(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once(Utc::now()).await {
+                    error!("Group topic fan-out retry pass failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event::{DatabaseEventFields, Event, EventOrigin};
+    use crate::domain::repositories::event_outbox_repo::OutboxBacklog;
+    use crate::domain::value_objects::{EventId, UserId};
+    use crate::error::{Error, InfrastructureError};
+    use crate::fixtures::GroupFixture;
+    use crate::infrastructure::memory::{
+        InMemoryEventReceiverGroupRepository, InMemoryEventRepository,
+    };
+    use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockOutbox {
+        entries: Mutex<HashMap<(EventId, String), GroupTopicOutboxEntry>>,
+    }
+
+    #[async_trait]
+    impl GroupTopicOutboxRepository for MockOutbox {
+        async fn enqueue(
+            &self,
+            event_id: EventId,
+            topic: &str,
+            error: &str,
+            next_attempt_at: DateTime<Utc>,
+        ) -> Result<()> {
+            self.entries.lock().unwrap().insert(
+                (event_id, topic.to_string()),
+                GroupTopicOutboxEntry {
+                    event_id,
+                    topic: topic.to_string(),
+                    attempts: 1,
+                    last_error: error.to_string(),
+                    next_attempt_at,
+                    dead_lettered_at: None,
+                },
+            );
+            Ok(())
+        }
+
+        async fn find_due(
+            &self,
+            now: DateTime<Utc>,
+            limit: usize,
+        ) -> Result<Vec<GroupTopicOutboxEntry>> {
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|e| e.dead_lettered_at.is_none() && e.next_attempt_at <= now)
+                .take(limit)
+                .cloned()
+                .collect())
+        }
+
+        async fn mark_published(&self, event_id: EventId, topic: &str) -> Result<()> {
+            self.entries
+                .lock()
+                .unwrap()
+                .remove(&(event_id, topic.to_string()));
+            Ok(())
+        }
+
+        async fn record_failure(
+            &self,
+            event_id: EventId,
+            topic: &str,
+            error: &str,
+            next_attempt_at: Option<DateTime<Utc>>,
+        ) -> Result<()> {
+            let mut entries = self.entries.lock().unwrap();
+            if let Some(entry) = entries.get_mut(&(event_id, topic.to_string())) {
+                entry.attempts += 1;
+                entry.last_error = error.to_string();
+                match next_attempt_at {
+                    Some(at) => entry.next_attempt_at = at,
+                    None => entry.dead_lettered_at = Some(Utc::now()),
+                }
+            }
+            Ok(())
+        }
+
+        async fn backlog(&self) -> Result<OutboxBacklog> {
+            let entries = self.entries.lock().unwrap();
+            let dead = entries
+                .values()
+                .filter(|e| e.dead_lettered_at.is_some())
+                .count() as u64;
+            Ok(OutboxBacklog {
+                pending: entries.len() as u64 - dead,
+                dead_lettered: dead,
+            })
+        }
+    }
+
+    /// Publisher whose group topics can be taken down one at a time
+    #[derive(Default)]
+    struct MockPublisher {
+        down_topics: Mutex<HashSet<String>>,
+        published: Mutex<Vec<(String, EventId)>>,
+    }
+
+    impl MockPublisher {
+        fn set_down(&self, topic: &str, down: bool) {
+            let mut down_topics = self.down_topics.lock().unwrap();
+            if down {
+                down_topics.insert(topic.to_string());
+            } else {
+                down_topics.remove(topic);
+            }
+        }
+
+        fn topics(&self) -> Vec<String> {
+            let published = self.published.lock().unwrap();
+            published.iter().map(|(topic, _)| topic.clone()).collect()
+        }
+    }
+
+    #[async_trait]
+    impl EventPublisher for MockPublisher {
+        async fn publish(&self, event: &Event) -> Result<()> {
+            self.publish_to("xzepr.events", event).await
+        }
+
+        async fn publish_to(&self, topic: &str, event: &Event) -> Result<()> {
+            if self.down_topics.lock().unwrap().contains(topic) {
+                return Err(Error::Infrastructure(
+                    InfrastructureError::KafkaProducerError {
+                        message: format!("topic {} unavailable", topic),
+                    },
+                ));
+            }
+            self.published
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), event.id()));
+            Ok(())
+        }
+
+        async fn publish_message(&self, _message: &CloudEventMessage) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn event(receiver_id: EventReceiverId) -> Event {
+        Event::from_database(DatabaseEventFields {
+            id: EventId::new(),
+            name: "payment.settled".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.03".to_string(),
+            platform_id: "linux".to_string(),
+            package: "payments".to_string(),
+            description: "fan-out test".to_string(),
+            payload: json!({"amount": 42}),
+            success: true,
+            event_receiver_id: receiver_id,
+            owner_id: UserId::new(),
+            origin: EventOrigin::User,
+            resource_version: 1,
+            created_at: Utc::now(),
+        })
+    }
+
+    struct Fixture {
+        receiver_id: EventReceiverId,
+        groups: Arc<InMemoryEventReceiverGroupRepository>,
+        events: Arc<InMemoryEventRepository>,
+        outbox: Arc<MockOutbox>,
+        publisher: Arc<MockPublisher>,
+        fanout: GroupTopicFanout,
+    }
+
+    /// A receiver in two routed groups, one unrouted group, and one
+    /// disabled routed group
+    async fn fixture() -> Fixture {
+        let receiver_id = EventReceiverId::new();
+        let groups = Arc::new(InMemoryEventReceiverGroupRepository::new());
+        let routes = [
+            (receiver_id, Some("payments.events"), true),
+            (receiver_id, Some("audit.events"), true),
+            (receiver_id, None, true),
+            (receiver_id, Some("archived.events"), false),
+            (EventReceiverId::new(), Some("other.events"), true),
+        ];
+        for (i, (member, topic, enabled)) in routes.into_iter().enumerate() {
+            let group = GroupFixture::new()
+                .name(format!("group-{}", i))
+                .receiver_id(member)
+                .enabled(enabled);
+            let group = match topic {
+                Some(topic) => group.dedicated_topic(topic),
+                None => group,
+            };
+            groups.save(&group.build()).await.unwrap();
+        }
+
+        let events = Arc::new(InMemoryEventRepository::new());
+        let outbox = Arc::new(MockOutbox::default());
+        let publisher = Arc::new(MockPublisher::default());
+        let fanout = GroupTopicFanout::new(groups.clone(), events.clone(), publisher.clone())
+            .with_outbox(outbox.clone());
+        Fixture {
+            receiver_id,
+            groups,
+            events,
+            outbox,
+            publisher,
+            fanout,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_publishes_to_enabled_group_topics() {
+        let f = fixture().await;
+        let event = event(f.receiver_id);
+
+        let report = f.fanout.fan_out(&event).await;
+
+        assert_eq!(report.published, vec!["audit.events", "payments.events"]);
+        assert!(report.deferred.is_empty());
+        assert_eq!(
+            f.publisher.topics(),
+            vec!["audit.events", "payments.events"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_topic_is_retried_on_its_own() {
+        let f = fixture().await;
+        let event = event(f.receiver_id);
+        f.events.save(&event).await.unwrap();
+        f.publisher.set_down("audit.events", true);
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let fanout = f.fanout.clone().with_metrics(metrics.clone());
+
+        let report = fanout.fan_out(&event).await;
+        assert_eq!(report.published, vec!["payments.events"]);
+        assert_eq!(report.deferred, vec!["audit.events"]);
+        assert_eq!(f.outbox.backlog().await.unwrap().pending, 1);
+
+        f.publisher.set_down("audit.events", false);
+        let report = fanout
+            .run_once(Utc::now() + publish_retry_delay(1))
+            .await
+            .unwrap();
+
+        assert_eq!(report.published, 1);
+        assert_eq!(
+            f.publisher.topics(),
+            vec!["payments.events", "audit.events"]
+        );
+        assert_eq!(f.outbox.backlog().await.unwrap(), OutboxBacklog::default());
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_group_topic_publish_outcomes_total{outcome=\"deferred\",topic=\"audit.events\"} 1"
+        ));
+        assert!(output.contains(
+            "xzepr_group_topic_publish_outcomes_total{outcome=\"retried\",topic=\"audit.events\"} 1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_group_stops_fan_out() {
+        let f = fixture().await;
+        let first = event(f.receiver_id);
+        f.events.save(&first).await.unwrap();
+        f.publisher.set_down("audit.events", true);
+        f.fanout.fan_out(&first).await;
+
+        for group in f
+            .groups
+            .find_by_event_receiver_id(f.receiver_id)
+            .await
+            .unwrap()
+        {
+            if group.dedicated_topic() == Some("audit.events") {
+                f.groups.disable(group.id()).await.unwrap();
+            }
+        }
+        f.publisher.set_down("audit.events", false);
+        let report = f
+            .fanout
+            .run_once(Utc::now() + publish_retry_delay(1))
+            .await
+            .unwrap();
+
+        assert_eq!(report.dropped, 1);
+        assert_eq!(f.outbox.backlog().await.unwrap(), OutboxBacklog::default());
+        let next = event(f.receiver_id);
+        assert_eq!(
+            f.fanout.fan_out(&next).await.published,
+            vec!["payments.events"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failing_topic_is_dead_lettered() {
+        let f = fixture().await;
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let fanout = f.fanout.clone().with_max_attempts(2).with_event_bus(bus);
+        let event = event(f.receiver_id);
+        f.events.save(&event).await.unwrap();
+        f.publisher.set_down("payments.events", true);
+        fanout.fan_out(&event).await;
+
+        let report = fanout
+            .run_once(Utc::now() + publish_retry_delay(1))
+            .await
+            .unwrap();
+
+        assert_eq!(report.dead_lettered, 1);
+        assert_eq!(f.outbox.backlog().await.unwrap().dead_lettered, 1);
+        let notification = notifications.try_recv().unwrap();
+        let DomainEvent::DeliveryFailed { failure, .. } = &*notification else {
+            panic!("expected DeliveryFailed, got {}", notification.kind());
+        };
+        assert_eq!(failure.mechanism, DeliveryMechanism::GroupTopicFanout);
+        assert_eq!(failure.target, "payments.events");
+        assert_eq!(failure.event_id, event.id());
+        assert_eq!(failure.receiver_id, f.receiver_id);
+        assert!(failure
+            .last_error
+            .contains("topic payments.events unavailable"));
+        assert_eq!(failure.attempts, 2);
+        assert!(notifications.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recovered_fan_out_announces_no_failure() {
+        let f = fixture().await;
+        let bus = DomainEventBus::new();
+        let mut notifications = bus.subscribe();
+        let fanout = f.fanout.clone().with_max_attempts(2).with_event_bus(bus);
+        let event = event(f.receiver_id);
+        f.events.save(&event).await.unwrap();
+        f.publisher.set_down("payments.events", true);
+        fanout.fan_out(&event).await;
+
+        f.publisher.set_down("payments.events", false);
+        let report = fanout
+            .run_once(Utc::now() + publish_retry_delay(1))
+            .await
+            .unwrap();
+
+        assert_eq!(report.published, 1);
+        assert!(notifications.try_recv().is_err());
+    }
+}
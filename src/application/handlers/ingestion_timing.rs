@@ -0,0 +1,195 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/ingestion_timing.rs
+
+use crate::domain::entities::ingestion_meta::IngestionContext;
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Channel label of events submitted without an ingestion context
+pub const INTERNAL_CHANNEL: &str = "internal";
+
+/// Stage of event ingestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IngestionStage {
+    /// Receiver lookup, name and version checks, sampling, and building
+    /// the event
+    Validation,
+    /// Payload check against the receiver's own and inherited schema
+    SchemaCheck,
+    /// Search for items repeating an earlier item; timed once per batch
+    Dedup,
+    /// Daily quota lookup
+    QuotaCheck,
+    /// Saving the event and its metadata. The hourly rollup count is
+    /// written in the same transaction as the event, so it is included
+    Persist,
+    /// Kafka publish, or queueing for retry when the publish fails
+    Publish,
+}
+
+impl IngestionStage {
+    /// All stages, in pipeline order
+    pub const ALL: [IngestionStage; 6] = [
+        IngestionStage::Validation,
+        IngestionStage::SchemaCheck,
+        IngestionStage::Dedup,
+        IngestionStage::QuotaCheck,
+        IngestionStage::Persist,
+        IngestionStage::Publish,
+    ];
+
+    /// Returns the metric label of the stage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestionStage::Validation => "validation",
+            IngestionStage::SchemaCheck => "schema_check",
+            IngestionStage::Dedup => "dedup",
+            IngestionStage::QuotaCheck => "quota_check",
+            IngestionStage::Persist => "persist",
+            IngestionStage::Publish => "publish",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Stage durations of one event submission
+///
+/// Stages are timed back to back: [`IngestionTimings::lap`] ends the
+/// running stage and starts the next, so the stages of a submission add
+/// up to its total. A stage lapped more than once accumulates. Only
+/// `Instant`s are taken until [`IngestionTimings::finish`].
+#[derive(Debug, Clone)]
+pub struct IngestionTimings {
+    channel: &'static str,
+    started: Instant,
+    lapped: Instant,
+    stages: [Option<Duration>; IngestionStage::ALL.len()],
+}
+
+impl IngestionTimings {
+    /// Starts timing a submission arriving through `channel`
+    pub fn start(channel: &'static str) -> Self {
+        let now = Instant::now();
+        Self {
+            channel,
+            started: now,
+            lapped: now,
+            stages: [None; IngestionStage::ALL.len()],
+        }
+    }
+
+    /// Starts timing a submission described by `context`
+    ///
+    /// The channel is the context's ingestion source, or
+    /// [`INTERNAL_CHANNEL`] without a context.
+    pub fn for_context(context: Option<&IngestionContext>) -> Self {
+        Self::start(context.map_or(INTERNAL_CHANNEL, |context| context.source.as_str()))
+    }
+
+    /// Ends the running stage, attributing the time since the last lap to
+    /// `stage`
+    pub fn lap(&mut self, stage: IngestionStage) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.lapped);
+        self.lapped = now;
+        let slot = &mut self.stages[stage.index()];
+        *slot = Some(slot.unwrap_or_default() + elapsed);
+    }
+
+    /// Returns the channel label
+    pub fn channel(&self) -> &'static str {
+        self.channel
+    }
+
+    /// Returns the duration of `stage`, if the submission reached it
+    pub fn stage(&self, stage: IngestionStage) -> Option<Duration> {
+        self.stages[stage.index()]
+    }
+
+    /// Logs the stage durations at debug level and records them in
+    /// `metrics`
+    ///
+    /// `outcome` is one of `stored`, `sampled_out`, or `rejected`. Returns
+    /// the total duration.
+    pub fn finish(self, metrics: Option<&PrometheusMetrics>, outcome: &str) -> Duration {
+        let total = self.started.elapsed();
+        let ms = |stage| self.stage(stage).map(|d| d.as_secs_f64() * 1000.0);
+        debug!(
+            channel = self.channel,
+            outcome,
+            total_ms = total.as_secs_f64() * 1000.0,
+            validation_ms = ms(IngestionStage::Validation),
+            schema_check_ms = ms(IngestionStage::SchemaCheck),
+            quota_check_ms = ms(IngestionStage::QuotaCheck),
+            persist_ms = ms(IngestionStage::Persist),
+            publish_ms = ms(IngestionStage::Publish),
+            "Event ingestion timings"
+        );
+
+        if let Some(metrics) = metrics {
+            for stage in IngestionStage::ALL {
+                if let Some(duration) = self.stage(stage) {
+                    metrics.record_ingestion_stage(
+                        stage.as_str(),
+                        self.channel,
+                        duration.as_secs_f64(),
+                    );
+                }
+            }
+            metrics.record_ingestion(self.channel, outcome, total.as_secs_f64());
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::ingestion_meta::{IngestionSource, PrincipalType};
+
+    #[test]
+    fn test_repeated_laps_accumulate_and_stages_add_up_to_total() {
+        let mut timings = IngestionTimings::start("rest");
+        timings.lap(IngestionStage::Validation);
+        std::thread::sleep(Duration::from_millis(5));
+        timings.lap(IngestionStage::SchemaCheck);
+        std::thread::sleep(Duration::from_millis(5));
+        timings.lap(IngestionStage::Validation);
+
+        assert!(timings.stage(IngestionStage::SchemaCheck).unwrap() >= Duration::from_millis(5));
+        assert!(timings.stage(IngestionStage::Validation).unwrap() >= Duration::from_millis(5));
+        assert!(timings.stage(IngestionStage::Persist).is_none());
+
+        let summed: Duration = IngestionStage::ALL
+            .into_iter()
+            .filter_map(|stage| timings.stage(stage))
+            .sum();
+        let total = timings.finish(None, "stored");
+        assert!(summed <= total);
+        assert!(total - summed < Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_channel_follows_ingestion_source() {
+        let context = IngestionContext::new(PrincipalType::ApiKey, "key", IngestionSource::Batch);
+        assert_eq!(
+            IngestionTimings::for_context(Some(&context)).channel(),
+            "batch"
+        );
+        assert_eq!(
+            IngestionTimings::for_context(None).channel(),
+            INTERNAL_CHANNEL
+        );
+        assert_eq!(
+            IngestionTimings::for_context(Some(&IngestionContext::kafka_consumer("c1"))).channel(),
+            "kafka"
+        );
+    }
+}
@@ -0,0 +1,357 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/kafka_forwarder.rs
+
+use crate::application::domain_events::{DomainEvent, DomainEventSubscriber};
+use crate::error::Result;
+use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
+use crate::infrastructure::messaging::producer::EventPublisher;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::info;
+
+/// Name of the forwarder in logs and metrics
+pub const KAFKA_FORWARDER_NAME: &str = "kafka_forwarder";
+
+/// Domain event subscriber publishing the system events of receivers and
+/// groups that were created, updated, or deleted, of auto-disabled
+/// receivers, and of groups that completed a release, to Kafka
+///
+/// The CloudEvent carries the system event together with the receiver or
+/// group it describes, in its final state for deletions, and is keyed by
+/// the resource id so the changes to one resource stay in order.
+/// Notifications without a system event, and all other notifications, are
+/// ignored; stored events are published by the
+/// [`EventHandler`](crate::application::handlers::EventHandler) itself so
+/// its publish policy and outbox apply.
+#[derive(Clone)]
+pub struct KafkaForwarder {
+    publisher: Arc<dyn EventPublisher>,
+}
+
+impl KafkaForwarder {
+    /// Creates a forwarder publishing through `publisher`
+    pub fn new(publisher: Arc<dyn EventPublisher>) -> Self {
+        Self { publisher }
+    }
+}
+
+#[async_trait]
+impl DomainEventSubscriber for KafkaForwarder {
+    fn name(&self) -> &str {
+        KAFKA_FORWARDER_NAME
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<()> {
+        let (key, message) = match event {
+            DomainEvent::ReceiverCreated {
+                receiver,
+                system_event: Some(system_event),
+            }
+            | DomainEvent::ReceiverUpdated {
+                receiver,
+                system_event: Some(system_event),
+                ..
+            }
+            | DomainEvent::ReceiverDeleted {
+                receiver,
+                system_event: Some(system_event),
+                ..
+            }
+            | DomainEvent::ReceiverAutoDisabled {
+                receiver,
+                system_event: Some(system_event),
+                ..
+            } => (
+                receiver.id().to_string(),
+                CloudEventMessage::from_event_with_receiver(system_event, receiver),
+            ),
+            DomainEvent::GroupCreated {
+                group,
+                system_event: Some(system_event),
+            }
+            | DomainEvent::GroupUpdated {
+                group,
+                system_event: Some(system_event),
+                ..
+            }
+            | DomainEvent::GroupDeleted {
+                group,
+                system_event: Some(system_event),
+                ..
+            }
+            | DomainEvent::GroupReleaseCompleted {
+                group,
+                system_event: Some(system_event),
+                ..
+            } => (
+                group.id().to_string(),
+                CloudEventMessage::from_event_with_group(system_event, group),
+            ),
+            _ => return Ok(()),
+        };
+
+        self.publisher
+            .publish_message_with_key(&key, &message)
+            .await?;
+        info!(
+            event_id = %message.id,
+            event_type = %message.event_type,
+            "System event published to Kafka successfully"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::handlers::system_events::{
+        SystemEventFactory, GROUP_CREATED_EVENT, GROUP_DELETED_EVENT, GROUP_UPDATED_EVENT,
+        RECEIVER_CREATED_EVENT, RECEIVER_DELETED_EVENT, RECEIVER_UPDATED_EVENT,
+    };
+    use crate::domain::entities::event::Event;
+    use crate::domain::entities::event_receiver::EventReceiver;
+    use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+    use crate::domain::value_objects::{EventReceiverId, UserId};
+    use crate::error::{Error, InfrastructureError};
+    use chrono::Utc;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockPublisher {
+        down: AtomicBool,
+        messages: Mutex<Vec<CloudEventMessage>>,
+        keys: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for MockPublisher {
+        async fn publish(&self, _event: &Event) -> Result<()> {
+            unreachable!("the forwarder only publishes prepared messages")
+        }
+
+        async fn publish_to(&self, _topic: &str, _event: &Event) -> Result<()> {
+            unreachable!("the forwarder only publishes prepared messages")
+        }
+
+        async fn publish_message(&self, message: &CloudEventMessage) -> Result<()> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(Error::Infrastructure(
+                    InfrastructureError::KafkaProducerError {
+                        message: "broker unavailable".to_string(),
+                    },
+                ));
+            }
+            self.messages.lock().unwrap().push(message.clone());
+            Ok(())
+        }
+
+        async fn publish_message_with_key(
+            &self,
+            key: &str,
+            message: &CloudEventMessage,
+        ) -> Result<()> {
+            self.publish_message(message).await?;
+            self.keys.lock().unwrap().push(key.to_string());
+            Ok(())
+        }
+    }
+
+    fn receiver() -> EventReceiver {
+        EventReceiver::new(
+            "builds".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "Build results".to_string(),
+            json!({"type": "object"}),
+            UserId::new(),
+        )
+        .unwrap()
+    }
+
+    fn group(receiver_id: EventReceiverId) -> EventReceiverGroup {
+        EventReceiverGroup::new(
+            "release".to_string(),
+            "pipeline".to_string(),
+            "1.0.0".to_string(),
+            "Release pipeline".to_string(),
+            true,
+            vec![receiver_id],
+            UserId::new(),
+        )
+        .unwrap()
+    }
+
+    fn system_event(name: &str, receiver_id: EventReceiverId) -> Event {
+        SystemEventFactory::new()
+            .build(
+                name,
+                "created".to_string(),
+                json!({"id": receiver_id.to_string()}),
+                receiver_id,
+                UserId::new(),
+            )
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_publishes_the_same_cloudevents_as_the_handlers_did() {
+        let publisher = Arc::new(MockPublisher::default());
+        let forwarder = KafkaForwarder::new(publisher.clone());
+        let receiver = receiver();
+        let group = group(receiver.id());
+        let receiver_event = system_event(RECEIVER_CREATED_EVENT, receiver.id());
+        let group_event = system_event(GROUP_CREATED_EVENT, receiver.id());
+
+        forwarder
+            .handle(&DomainEvent::ReceiverCreated {
+                receiver: receiver.clone(),
+                system_event: Some(receiver_event.clone()),
+            })
+            .await
+            .unwrap();
+        forwarder
+            .handle(&DomainEvent::GroupCreated {
+                group: group.clone(),
+                system_event: Some(group_event.clone()),
+            })
+            .await
+            .unwrap();
+
+        let messages = publisher.messages.lock().unwrap();
+        let published: Vec<_> = messages
+            .iter()
+            .map(|m| serde_json::to_value(m).unwrap())
+            .collect();
+        assert_eq!(
+            published,
+            vec![
+                serde_json::to_value(CloudEventMessage::from_event_with_receiver(
+                    &receiver_event,
+                    &receiver
+                ))
+                .unwrap(),
+                serde_json::to_value(CloudEventMessage::from_event_with_group(
+                    &group_event,
+                    &group
+                ))
+                .unwrap(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_events_are_keyed_by_resource_id() {
+        let publisher = Arc::new(MockPublisher::default());
+        let forwarder = KafkaForwarder::new(publisher.clone());
+        let receiver = receiver();
+        let group = group(receiver.id());
+
+        for event in [
+            DomainEvent::ReceiverUpdated {
+                receiver: receiver.clone(),
+                changed_fields: vec!["name".to_string()],
+                system_event: Some(system_event(RECEIVER_UPDATED_EVENT, receiver.id())),
+            },
+            DomainEvent::GroupUpdated {
+                group: group.clone(),
+                changed_fields: vec!["enabled".to_string()],
+                system_event: Some(system_event(GROUP_UPDATED_EVENT, receiver.id())),
+            },
+            DomainEvent::GroupDeleted {
+                group: group.clone(),
+                deleted_at: Utc::now(),
+                system_event: Some(system_event(GROUP_DELETED_EVENT, receiver.id())),
+            },
+            DomainEvent::ReceiverDeleted {
+                receiver: receiver.clone(),
+                deleted_at: Utc::now(),
+                system_event: Some(system_event(RECEIVER_DELETED_EVENT, receiver.id())),
+            },
+        ] {
+            forwarder.handle(&event).await.unwrap();
+        }
+
+        let types: Vec<_> = publisher
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|m| m.event_type.clone())
+            .collect();
+        assert_eq!(
+            types,
+            [
+                RECEIVER_UPDATED_EVENT,
+                GROUP_UPDATED_EVENT,
+                GROUP_DELETED_EVENT,
+                RECEIVER_DELETED_EVENT
+            ]
+        );
+        assert_eq!(
+            *publisher.keys.lock().unwrap(),
+            [
+                receiver.id().to_string(),
+                group.id().to_string(),
+                group.id().to_string(),
+                receiver.id().to_string()
+            ]
+        );
+
+        // Deletions carry the final state of the resource
+        let messages = publisher.messages.lock().unwrap();
+        assert_eq!(messages[2].data.event_receiver_groups, [group]);
+        assert_eq!(messages[3].data.event_receivers, [receiver]);
+    }
+
+    #[tokio::test]
+    async fn test_ignores_notifications_without_a_system_event() {
+        let publisher = Arc::new(MockPublisher::default());
+        let forwarder = KafkaForwarder::new(publisher.clone());
+        let receiver = receiver();
+
+        for event in [
+            DomainEvent::ReceiverCreated {
+                receiver: receiver.clone(),
+                system_event: None,
+            },
+            DomainEvent::ReceiverUpdated {
+                receiver: receiver.clone(),
+                changed_fields: vec!["name".to_string()],
+                system_event: None,
+            },
+            DomainEvent::GroupDeleted {
+                group: group(receiver.id()),
+                deleted_at: Utc::now(),
+                system_event: None,
+            },
+        ] {
+            forwarder.handle(&event).await.unwrap();
+        }
+
+        assert!(publisher.messages.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_publish_failure_is_returned_to_the_bus() {
+        let publisher = Arc::new(MockPublisher::default());
+        publisher.down.store(true, Ordering::SeqCst);
+        let forwarder = KafkaForwarder::new(publisher);
+        let receiver = receiver();
+
+        let result = forwarder
+            .handle(&DomainEvent::GroupCreated {
+                group: group(receiver.id()),
+                system_event: Some(system_event(GROUP_CREATED_EVENT, receiver.id())),
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
+}
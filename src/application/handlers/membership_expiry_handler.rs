@@ -0,0 +1,220 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/membership_expiry_handler.rs
+
+use crate::domain::repositories::event_receiver_group_repo::{
+    EventReceiverGroupRepository, ExpiredMembership,
+};
+use crate::error::Result;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::jobs::{Job, JobReport, JobSchedule};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime, SecondsFormat, Utc};
+use std::sync::Arc;
+use tracing::info;
+
+/// Default number of expired memberships deleted per pass
+pub const DEFAULT_MEMBERSHIP_EXPIRY_BATCH_SIZE: usize = 500;
+
+/// Name of the membership expiry job in settings and the admin API
+pub const MEMBERSHIP_EXPIRY_JOB_NAME: &str = "membership_expiry";
+
+/// Application service deleting group memberships that have expired
+///
+/// Expired memberships already grant nothing; reads skip them. Deleting the
+/// rows keeps the table small and records in the audit log whose access
+/// ended and who had granted it.
+#[derive(Clone)]
+pub struct MembershipExpiryHandler {
+    group_repository: Arc<dyn EventReceiverGroupRepository>,
+    audit_logger: Option<Arc<AuditLogger>>,
+    batch_size: usize,
+    run_at: NaiveTime,
+}
+
+impl MembershipExpiryHandler {
+    /// Creates a handler that runs daily at 03:00 UTC
+    pub fn new(group_repository: Arc<dyn EventReceiverGroupRepository>) -> Self {
+        Self {
+            group_repository,
+            audit_logger: None,
+            batch_size: DEFAULT_MEMBERSHIP_EXPIRY_BATCH_SIZE,
+            run_at: NaiveTime::from_hms_opt(3, 0, 0).expect("valid time"),
+        }
+    }
+
+    /// Records every deleted membership in the audit log
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = Some(audit_logger);
+        self
+    }
+
+    /// Sets the maximum number of memberships deleted per pass
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the UTC time of day the job runs
+    pub fn with_run_at(mut self, run_at: NaiveTime) -> Self {
+        self.run_at = run_at;
+        self
+    }
+
+    /// Deletes up to one batch of memberships expired at `now`
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<Vec<ExpiredMembership>> {
+        let expired = self
+            .group_repository
+            .remove_expired_members(now, self.batch_size)
+            .await?;
+
+        for removal in &expired {
+            info!(
+                group_id = %removal.group_id,
+                user_id = %removal.membership.user_id,
+                granted_by = %removal.membership.added_by,
+                "Removed expired group membership"
+            );
+            if let Some(audit_logger) = &self.audit_logger {
+                audit_logger.log_event(expiry_audit_event(removal));
+            }
+        }
+
+        Ok(expired)
+    }
+}
+
+/// Audit record of an expired membership naming who granted it
+fn expiry_audit_event(removal: &ExpiredMembership) -> AuditEvent {
+    let membership = &removal.membership;
+    let mut event = AuditEvent::builder()
+        .action(AuditAction::MembershipExpire)
+        .resource(format!(
+            "group:{}/member:{}",
+            removal.group_id, membership.user_id
+        ))
+        .outcome(AuditOutcome::Success)
+        .add_metadata("granted_by", membership.added_by.to_string())
+        .add_metadata(
+            "granted_at",
+            membership
+                .added_at
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        );
+    if let Some(expires_at) = membership.expires_at {
+        event = event.add_metadata(
+            "expired_at",
+            expires_at.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        );
+    }
+    event.build()
+}
+
+#[async_trait]
+impl Job for MembershipExpiryHandler {
+    fn name(&self) -> &str {
+        MEMBERSHIP_EXPIRY_JOB_NAME
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::DailyAt(self.run_at)
+    }
+
+    async fn run(&self) -> Result<JobReport> {
+        let removed = self.run_once(Utc::now()).await?.len();
+        Ok(JobReport {
+            processed: removed,
+            more_pending: removed >= self.batch_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{EventReceiverGroupId, UserId};
+    use crate::fixtures::GroupFixture;
+    use crate::infrastructure::memory::InMemoryEventReceiverGroupRepository;
+    use chrono::Duration;
+
+    async fn group_repo() -> (
+        Arc<InMemoryEventReceiverGroupRepository>,
+        EventReceiverGroupId,
+    ) {
+        let repo = Arc::new(InMemoryEventReceiverGroupRepository::new());
+        let group = GroupFixture::new().persist(&*repo).await;
+        (repo, group.id())
+    }
+
+    #[tokio::test]
+    async fn test_expired_memberships_are_removed_and_audited() {
+        let (repo, group_id) = group_repo().await;
+        let owner = UserId::new();
+        let (lapsed, current, permanent) = (UserId::new(), UserId::new(), UserId::new());
+        let now = Utc::now();
+        repo.add_member(group_id, lapsed, owner, Some(now - Duration::hours(1)))
+            .await
+            .unwrap();
+        repo.add_member(group_id, current, owner, Some(now + Duration::days(1)))
+            .await
+            .unwrap();
+        repo.add_member(group_id, permanent, owner, None)
+            .await
+            .unwrap();
+
+        let handler =
+            MembershipExpiryHandler::new(repo.clone()).with_audit(Arc::new(AuditLogger::new()));
+        let removed = handler.run_once(now).await.unwrap();
+
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].group_id, group_id);
+        assert_eq!(removed[0].membership.user_id, lapsed);
+        assert!(!repo.is_member(group_id, lapsed).await.unwrap());
+        assert!(repo.is_member(group_id, current).await.unwrap());
+        assert!(repo.is_member(group_id, permanent).await.unwrap());
+        assert_eq!(repo.get_group_memberships(group_id).await.unwrap().len(), 2);
+
+        let event = expiry_audit_event(&removed[0]);
+        assert_eq!(event.action, AuditAction::MembershipExpire);
+        assert_eq!(event.outcome, AuditOutcome::Success);
+        assert_eq!(
+            event.resource,
+            format!("group:{}/member:{}", group_id, lapsed)
+        );
+        assert_eq!(event.metadata["granted_by"], owner.to_string());
+        assert!(event.metadata.contains_key("expired_at"));
+
+        // Nothing is left to remove until another membership lapses
+        assert!(handler.run_once(now).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_full_batch_reports_more_pending() {
+        let (repo, group_id) = group_repo().await;
+        let owner = UserId::new();
+        for _ in 0..3 {
+            repo.add_member(
+                group_id,
+                UserId::new(),
+                owner,
+                Some(Utc::now() - Duration::minutes(5)),
+            )
+            .await
+            .unwrap();
+        }
+
+        let handler = MembershipExpiryHandler::new(repo.clone()).with_batch_size(2);
+        assert_eq!(handler.name(), MEMBERSHIP_EXPIRY_JOB_NAME);
+        assert!(matches!(handler.schedule(), JobSchedule::DailyAt(_)));
+
+        let report = handler.run().await.unwrap();
+        assert_eq!(report.processed, 2);
+        assert!(report.more_pending);
+
+        let report = handler.run().await.unwrap();
+        assert_eq!(report.processed, 1);
+        assert!(!report.more_pending);
+    }
+}
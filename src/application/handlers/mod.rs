@@ -3,10 +3,93 @@
 
 // Generated mod file
 
+pub mod admin_summary_handler;
+pub mod audit_chain_handler;
+pub mod audit_retention_handler;
+pub mod bulk_delete_handler;
+pub mod change_feed_handler;
+pub mod delivery_failure_handler;
+pub mod description_renderer;
+pub mod event_attachment_handler;
+pub mod event_batch_handler;
 pub mod event_handler;
+pub mod event_outbox_relay;
+pub mod event_payload_collector;
+pub mod event_poll_handler;
 pub mod event_receiver_group_handler;
 pub mod event_receiver_handler;
+pub mod event_retention_handler;
+pub mod event_stats_handler;
+pub mod group_completeness_handler;
+pub mod group_topic_fanout;
+pub mod ingestion_timing;
+pub mod kafka_forwarder;
+pub mod membership_expiry_handler;
+pub mod payload_offloader;
+pub mod receiver_activity_tracker;
+pub mod receiver_auto_disable_handler;
+pub mod receiver_hygiene_handler;
+pub mod receiver_timeline_handler;
+pub mod resource_history_handler;
+pub mod schema_preview_handler;
+pub mod schema_resolver;
+pub mod search_handler;
+pub mod system_events;
+pub mod user_import_handler;
+pub mod user_preferences_handler;
+pub mod version_normalization_handler;
 
-pub use event_handler::EventHandler;
+pub use admin_summary_handler::{AdminSummaryHandler, SystemSummary};
+pub use audit_chain_handler::AuditChainHandler;
+pub use audit_retention_handler::AuditRetentionHandler;
+pub use bulk_delete_handler::{
+    BlockedResource, BulkDeleteHandler, BulkDeleteReport, BulkDeleteSelector,
+};
+pub use change_feed_handler::ChangeFeedHandler;
+pub use delivery_failure_handler::DeliveryFailureHandler;
+pub use description_renderer::DescriptionRenderer;
+pub use event_attachment_handler::{
+    AttachmentUpload, EventAttachmentHandler, NewAttachment, UploadRejection,
+};
+pub use event_batch_handler::{
+    BatchItem, BatchItemOutcome, BatchItemResult, BatchReport, EventBatchHandler, QuotaMode,
+};
+pub use event_handler::{
+    ArchivedEventLookup, CreateEventOutcome, DryRunOutcome, EventHandler, ProvisionedReceiver,
+    PublishHealth, ReceiverPreview,
+};
+pub use event_outbox_relay::{EventOutboxRelay, OutboxRelayReport};
+pub use event_payload_collector::EventPayloadCollector;
+pub use event_poll_handler::{
+    EventNotice, EventNotifier, EventPollBatch, EventPollHandler, PollPermit,
+};
 pub use event_receiver_group_handler::EventReceiverGroupHandler;
-pub use event_receiver_handler::EventReceiverHandler;
+pub use event_receiver_handler::{EventReceiverHandler, ReceiverDependents};
+pub use event_retention_handler::{EventRetentionHandler, RetentionReport};
+pub use event_stats_handler::{EventRollupReconciler, EventStatsHandler, ReconcileReport};
+pub use group_completeness_handler::GroupCompletenessHandler;
+pub use group_topic_fanout::{FanoutReport, FanoutRetryReport, GroupTopicFanout};
+pub use ingestion_timing::{IngestionStage, IngestionTimings};
+pub use kafka_forwarder::KafkaForwarder;
+pub use membership_expiry_handler::MembershipExpiryHandler;
+pub use payload_offloader::PayloadOffloader;
+pub use receiver_activity_tracker::ReceiverActivityTracker;
+pub use receiver_auto_disable_handler::ReceiverAutoDisableHandler;
+pub use receiver_hygiene_handler::{ReceiverHygieneHandler, StaleReceiver};
+pub use receiver_timeline_handler::{ReceiverTimelineHandler, TimelinePage, TimelineQuery};
+pub use resource_history_handler::ResourceHistoryHandler;
+pub use schema_preview_handler::{
+    SchemaPreviewHandler, SchemaPreviewJob, SchemaPreviewJobStatus, SchemaPreviewOutcome,
+    SchemaPreviewReport, SchemaPreviewRequest,
+};
+pub use schema_resolver::SchemaResolver;
+pub use search_handler::SearchHandler;
+pub use system_events::SystemEventFactory;
+pub use user_import_handler::{
+    UserImportHandler, UserImportJob, UserImportJobStatus, UserImportOutcome, UserImportReport,
+    UserImportRowResult, UserImportRowStatus,
+};
+pub use user_preferences_handler::UserPreferencesHandler;
+pub use version_normalization_handler::{
+    SkippedVersion, VersionNormalizationHandler, VersionNormalizationReport, VersionRewrite,
+};
@@ -0,0 +1,247 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/payload_offloader.rs
+
+use crate::domain::canonical_json;
+use crate::domain::entities::event::Event;
+use crate::domain::entities::payload_summary::PayloadSummary;
+use crate::domain::repositories::event_attachment_repo::BlobStore;
+use crate::error::{Error, Result};
+
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Moves large event payloads to the blob store and back
+///
+/// A payload whose canonical JSON is larger than the threshold is written
+/// to the blob store before its event is saved; the event keeps a
+/// [`PayloadSummary`] in its place, so neither the events table nor the
+/// stream carries the full body. Readers that need the payload fetch it
+/// back with [`Self::restore`].
+#[derive(Clone)]
+pub struct PayloadOffloader {
+    blobs: Arc<dyn BlobStore>,
+    threshold_bytes: usize,
+    summary_paths: Vec<String>,
+}
+
+impl PayloadOffloader {
+    /// Creates an offloader for payloads larger than `threshold_bytes`
+    pub fn new(blobs: Arc<dyn BlobStore>, threshold_bytes: usize) -> Self {
+        Self {
+            blobs,
+            threshold_bytes,
+            summary_paths: Vec::new(),
+        }
+    }
+
+    /// Sets the dotted payload paths copied into summaries
+    pub fn with_summary_paths(mut self, paths: Vec<String>) -> Self {
+        self.summary_paths = paths;
+        self
+    }
+
+    /// Writes the payload of `event` to the blob store if it is over the
+    /// threshold, leaving its summary on the event
+    ///
+    /// Returns whether the payload was moved.
+    pub async fn offload(&self, event: &mut Event) -> Result<bool> {
+        if event.payload_summary().is_some() {
+            return Ok(false);
+        }
+        let canonical = canonical_json::canonical_bytes(event.payload())
+            .map_err(|e| Error::from(e.into_validation_error("payload")))?;
+        if canonical.len() <= self.threshold_bytes {
+            return Ok(false);
+        }
+
+        let key = PayloadSummary::blob_key_for(event.id());
+        let mut writer = self.blobs.begin(&key).await?;
+        if let Err(e) = writer.write(&canonical).await {
+            if let Err(abort_error) = writer.abort().await {
+                warn!(key = %key, error = %abort_error, "Failed to discard partial payload blob");
+            }
+            return Err(e);
+        }
+        writer.commit().await?;
+
+        let summary = PayloadSummary::new(key, event.payload(), &canonical, &self.summary_paths);
+        info!(
+            event_id = %event.id(),
+            size_bytes = summary.size_bytes,
+            "Event payload offloaded to the blob store"
+        );
+        event.offload_payload(summary);
+        Ok(true)
+    }
+
+    /// Puts the full payload of an offloaded event back in place
+    ///
+    /// Events whose payload is stored with them are left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob is missing or cannot be read.
+    pub async fn restore(&self, event: &mut Event) -> Result<()> {
+        let Some(summary) = event.payload_summary() else {
+            return Ok(());
+        };
+
+        let mut stream = self
+            .blobs
+            .open(&summary.blob_key, None)
+            .await?
+            .ok_or_else(|| Error::NotFound {
+                resource: format!("Payload blob {}", summary.blob_key),
+            })?;
+        let mut body = Vec::with_capacity(summary.size_bytes as usize);
+        while let Some(chunk) = stream.next_chunk().await? {
+            body.extend_from_slice(&chunk);
+        }
+
+        let payload = serde_json::from_slice(&body)?;
+        event.restore_payload(payload);
+        Ok(())
+    }
+
+    /// Restores the payloads of several events
+    pub async fn restore_all(&self, events: &mut [Event]) -> Result<()> {
+        for event in events {
+            self.restore(event).await?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the blob of an offloaded event
+    ///
+    /// Does nothing for events whose payload is stored with them.
+    pub async fn discard(&self, event: &Event) -> Result<()> {
+        match event.payload_summary() {
+            Some(summary) => self.blobs.delete(&summary.blob_key).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::EventFixture;
+    use crate::infrastructure::attachments::FilesystemBlobStore;
+    use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn temp_root() -> PathBuf {
+        std::env::temp_dir().join(format!("xzepr-payloads-{}", ulid::Ulid::new()))
+    }
+
+    /// A test result tree of roughly `cases` times 60 bytes
+    fn large_payload(cases: usize) -> serde_json::Value {
+        let cases: Vec<_> = (0..cases)
+            .map(|i| json!({"name": format!("case-{}", i), "status": "passed", "ms": i}))
+            .collect();
+        json!({
+            "suite": "integration",
+            "results": {"failed": 0, "cases": cases},
+        })
+    }
+
+    fn offloader(root: &PathBuf) -> PayloadOffloader {
+        PayloadOffloader::new(Arc::new(FilesystemBlobStore::new(root)), 1024)
+            .with_summary_paths(vec!["suite".to_string(), "results.failed".to_string()])
+    }
+
+    #[tokio::test]
+    async fn test_only_payloads_over_the_threshold_are_offloaded() {
+        let root = temp_root();
+        let offloader = offloader(&root);
+
+        let mut small = EventFixture::new().payload(large_payload(2)).build();
+        assert!(!offloader.offload(&mut small).await.unwrap());
+        assert!(small.payload_summary().is_none());
+        assert_eq!(small.payload(), &large_payload(2));
+
+        let payload = large_payload(100);
+        let mut large = EventFixture::new().payload(payload.clone()).build();
+        assert!(offloader.offload(&mut large).await.unwrap());
+        let summary = large.payload_summary().unwrap();
+        assert_eq!(
+            summary.hash,
+            canonical_json::content_hash(&payload).unwrap()
+        );
+        assert_eq!(summary.top_level_keys, ["results", "suite"]);
+        assert_eq!(summary.fields["suite"], json!("integration"));
+        assert_eq!(summary.fields["results.failed"], json!(0));
+        assert_eq!(large.payload(), &json!({}));
+
+        // Offloading again leaves the stored blob alone
+        assert!(!offloader.offload(&mut large).await.unwrap());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_restore_returns_the_payload_as_sent() {
+        let root = temp_root();
+        let offloader = offloader(&root);
+        let payload = json!({
+            "suite": "integration",
+            "unicode": "größe ✓",
+            "numbers": [1, -2.5, 1e21, 12345678901234567890u64],
+            "nested": {"b": [true, null], "a": {"deep": "x".repeat(2048)}}
+        });
+        let mut event = EventFixture::new().payload(payload.clone()).build();
+        assert!(offloader.offload(&mut event).await.unwrap());
+
+        offloader.restore(&mut event).await.unwrap();
+        assert_eq!(event.payload(), &payload);
+        assert!(event.payload_summary().is_none());
+
+        // Events stored inline are left as they are
+        offloader.restore(&mut event).await.unwrap();
+        assert_eq!(event.payload(), &payload);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_stream_message_carries_only_the_summary() {
+        let root = temp_root();
+        let offloader = offloader(&root);
+        let mut event = EventFixture::new().payload(large_payload(50_000)).build();
+        let inline_size = CloudEventMessage::from_event(&event)
+            .to_json()
+            .unwrap()
+            .len();
+        assert!(inline_size > 2 * 1024 * 1024);
+
+        assert!(offloader.offload(&mut event).await.unwrap());
+        let message = CloudEventMessage::from_event(&event).to_json().unwrap();
+        assert!(
+            message.len() < 4 * 1024,
+            "message is {} bytes",
+            message.len()
+        );
+        let message: serde_json::Value = serde_json::from_str(&message).unwrap();
+        let published = &message["data"]["events"][0];
+        assert_eq!(published["payload"], json!({}));
+        assert_eq!(
+            published["payload_summary"]["blob_key"],
+            PayloadSummary::blob_key_for(event.id())
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_discard_deletes_the_blob() {
+        let root = temp_root();
+        let offloader = offloader(&root);
+        let mut event = EventFixture::new().payload(large_payload(100)).build();
+        assert!(offloader.offload(&mut event).await.unwrap());
+
+        offloader.discard(&event).await.unwrap();
+        let error = offloader.restore(&mut event.clone()).await.unwrap_err();
+        assert!(matches!(error, Error::NotFound { .. }), "{error:?}");
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,188 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/receiver_activity_tracker.rs
+
+use crate::domain::entities::receiver_activity::ReceiverActivityUpdate;
+use crate::domain::repositories::receiver_activity_repo::ReceiverActivityRepository;
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+
+/// Default number of seconds between activity flushes
+pub const DEFAULT_ACTIVITY_FLUSH_SECONDS: u64 = 10;
+
+/// Buffers receiver liveness on the ingestion path
+///
+/// Recording an event only touches an in-memory map. Buffered activity is
+/// written in one batch per flush, so the database sees at most one update
+/// per receiver per interval however busy the receiver is. A failed flush
+/// keeps its updates for the next attempt.
+#[derive(Clone)]
+pub struct ReceiverActivityTracker {
+    repository: Arc<dyn ReceiverActivityRepository>,
+    pending: Arc<Mutex<HashMap<EventReceiverId, ReceiverActivityUpdate>>>,
+}
+
+impl ReceiverActivityTracker {
+    /// Creates a tracker writing to `repository`
+    pub fn new(repository: Arc<dyn ReceiverActivityRepository>) -> Self {
+        Self {
+            repository,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Records an accepted event from `principal`
+    pub fn record(&self, receiver_id: EventReceiverId, principal: String, at: DateTime<Utc>) {
+        let mut pending = self.pending.lock().unwrap();
+        match pending.get_mut(&receiver_id) {
+            Some(update) => update.record(principal, at),
+            None => {
+                pending.insert(
+                    receiver_id,
+                    ReceiverActivityUpdate::new(receiver_id, principal, at),
+                );
+            }
+        }
+    }
+
+    /// Writes buffered activity and returns the number of receivers updated
+    pub async fn flush(&self) -> Result<usize> {
+        let updates: Vec<ReceiverActivityUpdate> = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.drain().map(|(_, update)| update).collect()
+        };
+        if updates.is_empty() {
+            return Ok(0);
+        }
+
+        if let Err(e) = self.repository.record_activity(&updates).await {
+            let mut pending = self.pending.lock().unwrap();
+            for update in updates {
+                match pending.get_mut(&update.receiver_id) {
+                    Some(newer) => newer.merge(update),
+                    None => {
+                        pending.insert(update.receiver_id, update);
+                    }
+                }
+            }
+            return Err(e);
+        }
+
+        debug!(receivers = updates.len(), "Flushed receiver activity");
+        Ok(updates.len())
+    }
+
+    /// Flushes buffered activity every `interval` until the task is aborted
+    pub fn spawn(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.flush().await {
+                    error!("Failed to flush receiver activity: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::receiver_activity::ReceiverActivity;
+    use crate::error::DomainError;
+    use async_trait::async_trait;
+    use chrono::{Duration, NaiveDate};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[derive(Default)]
+    struct MockActivityRepository {
+        batches: Mutex<Vec<Vec<ReceiverActivityUpdate>>>,
+        fail: AtomicBool,
+    }
+
+    #[async_trait]
+    impl ReceiverActivityRepository for MockActivityRepository {
+        async fn record_activity(&self, updates: &[ReceiverActivityUpdate]) -> Result<()> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(DomainError::StorageError("database unavailable".to_string()).into());
+            }
+            self.batches.lock().unwrap().push(updates.to_vec());
+            Ok(())
+        }
+
+        async fn find_activity(
+            &self,
+            _receiver_ids: &[EventReceiverId],
+            _window_start: NaiveDate,
+        ) -> Result<Vec<ReceiverActivity>> {
+            Ok(vec![])
+        }
+
+        async fn most_recently_active(&self, _limit: usize) -> Result<Vec<EventReceiverId>> {
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_writes_one_update_per_receiver() {
+        let repo = Arc::new(MockActivityRepository::default());
+        let tracker = ReceiverActivityTracker::new(repo.clone());
+        let busy = EventReceiverId::new();
+        let quiet = EventReceiverId::new();
+        let now = Utc::now();
+
+        for i in 0..100 {
+            tracker.record(
+                busy,
+                format!("api_key:{}", i % 3),
+                now + Duration::seconds(i),
+            );
+        }
+        tracker.record(quiet, "user:a".to_string(), now);
+
+        assert_eq!(tracker.flush().await.unwrap(), 2);
+        let batches = repo.batches.lock().unwrap().clone();
+        assert_eq!(batches.len(), 1);
+        let update = batches[0].iter().find(|u| u.receiver_id == busy).unwrap();
+        assert_eq!(update.last_event_at, now + Duration::seconds(99));
+        assert_eq!(update.last_principal, "api_key:0");
+
+        // Nothing buffered means nothing written
+        assert_eq!(tracker.flush().await.unwrap(), 0);
+        assert_eq!(repo.batches.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_keeps_updates() {
+        let repo = Arc::new(MockActivityRepository::default());
+        let tracker = ReceiverActivityTracker::new(repo.clone());
+        let receiver_id = EventReceiverId::new();
+        let now = Utc::now();
+
+        tracker.record(receiver_id, "user:a".to_string(), now);
+        repo.fail.store(true, Ordering::SeqCst);
+        assert!(tracker.flush().await.is_err());
+
+        // Events recorded meanwhile merge with the retained update
+        tracker.record(
+            receiver_id,
+            "api_key:b".to_string(),
+            now + Duration::seconds(1),
+        );
+        repo.fail.store(false, Ordering::SeqCst);
+        assert_eq!(tracker.flush().await.unwrap(), 1);
+
+        let batches = repo.batches.lock().unwrap();
+        let update = &batches[0][0];
+        assert_eq!(update.last_principal, "api_key:b");
+        assert_eq!(update.daily_principals.len(), 2);
+    }
+}
@@ -0,0 +1,391 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/receiver_auto_disable_handler.rs
+
+use crate::application::handlers::event_receiver_handler::EventReceiverHandler;
+use crate::application::handlers::event_stats_handler::EventStatsHandler;
+use crate::domain::entities::receiver_auto_disable::AutoDisableTrigger;
+use crate::domain::repositories::event_rollup_repo::ReceiverEventCounts;
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use crate::infrastructure::jobs::{Job, JobReport, JobSchedule};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use tracing::info;
+
+/// Default time between auto-disable evaluations
+pub const DEFAULT_AUTO_DISABLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Name of the auto-disable job in settings and the admin API
+pub const RECEIVER_AUTO_DISABLE_JOB_NAME: &str = "receiver_auto_disable";
+
+/// Number of receivers loaded per query while evaluating policies
+const AUTO_DISABLE_PAGE_SIZE: usize = 500;
+
+/// Application service disabling receivers whose events keep failing
+///
+/// Each pass measures the failure rate of every receiver with an
+/// auto-disable policy over its observation window, using the same rollup
+/// backed counts as the stats endpoints. Receivers that breach their policy
+/// are disabled through the receiver handler, which records the system
+/// event and announces the change.
+#[derive(Clone)]
+pub struct ReceiverAutoDisableHandler {
+    receivers: EventReceiverHandler,
+    stats: EventStatsHandler,
+    interval: std::time::Duration,
+}
+
+impl ReceiverAutoDisableHandler {
+    /// Creates a new handler using the default interval
+    pub fn new(receivers: EventReceiverHandler, stats: EventStatsHandler) -> Self {
+        Self {
+            receivers,
+            stats,
+            interval: DEFAULT_AUTO_DISABLE_INTERVAL,
+        }
+    }
+
+    /// Sets the time between evaluations when run as a job
+    pub fn with_interval(mut self, interval: std::time::Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Evaluates every policy as of `now`, returning the receivers disabled
+    /// and the number of receivers evaluated
+    pub async fn run_once(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<(Vec<(EventReceiverId, AutoDisableTrigger)>, usize)> {
+        let mut windows = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .receivers
+                .list_event_receivers(AUTO_DISABLE_PAGE_SIZE, offset)
+                .await?;
+            let page_len = page.len();
+            offset += page_len;
+
+            for receiver in page {
+                if let (Some(policy), Some((start, end))) = (
+                    receiver.auto_disable_policy().copied(),
+                    receiver.auto_disable_window(now),
+                ) {
+                    windows.push((receiver.id(), policy, start, end));
+                }
+            }
+
+            if page_len < AUTO_DISABLE_PAGE_SIZE {
+                break;
+            }
+        }
+
+        // Receivers sharing a window length share one count query
+        let mut counts: HashMap<DateTime<Utc>, HashMap<EventReceiverId, ReceiverEventCounts>> =
+            HashMap::new();
+        let mut disabled = Vec::new();
+        for (receiver_id, policy, start, end) in &windows {
+            if !counts.contains_key(start) {
+                let window_counts = self
+                    .stats
+                    .receiver_counts(*start, *end)
+                    .await?
+                    .into_iter()
+                    .map(|counts| (counts.receiver_id, counts))
+                    .collect();
+                counts.insert(*start, window_counts);
+            }
+            let Some(receiver_counts) = counts[start].get(receiver_id) else {
+                continue;
+            };
+            if !policy.is_breached(receiver_counts.failed, receiver_counts.total()) {
+                continue;
+            }
+
+            let trigger = AutoDisableTrigger::new(
+                receiver_counts.failed,
+                receiver_counts.total(),
+                *start,
+                *end,
+            );
+            if self
+                .receivers
+                .auto_disable_event_receiver(*receiver_id, trigger.clone())
+                .await?
+            {
+                disabled.push((*receiver_id, trigger));
+            }
+        }
+
+        Ok((disabled, windows.len()))
+    }
+}
+
+#[async_trait]
+impl Job for ReceiverAutoDisableHandler {
+    fn name(&self) -> &str {
+        RECEIVER_AUTO_DISABLE_JOB_NAME
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::Every(self.interval)
+    }
+
+    async fn run(&self) -> Result<JobReport> {
+        let (disabled, evaluated) = self.run_once(Utc::now()).await?;
+        if !disabled.is_empty() {
+            info!(
+                evaluated,
+                disabled = disabled.len(),
+                "Receiver auto-disable pass complete"
+            );
+        }
+        Ok(JobReport {
+            processed: evaluated,
+            more_pending: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::domain_events::{DomainEvent, DomainEventBus};
+    use crate::application::handlers::system_events::{
+        SystemEventFactory, RECEIVER_AUTO_DISABLED_EVENT,
+    };
+    use crate::domain::entities::receiver_auto_disable::{AutoDisablePolicy, ReceiverState};
+    use crate::domain::repositories::event_repo::EventRepository;
+    use crate::domain::repositories::event_rollup_repo::{EventCountBucket, EventCountRepository};
+    use crate::domain::value_objects::UserId;
+    use crate::infrastructure::memory::{InMemoryEventReceiverRepository, InMemoryEventRepository};
+    use chrono::Duration;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    /// Event outcomes counted by creation time
+    #[derive(Default)]
+    struct MockCounts {
+        events: Mutex<Vec<(EventReceiverId, DateTime<Utc>, bool)>>,
+    }
+
+    impl MockCounts {
+        fn add(&self, receiver_id: EventReceiverId, at: DateTime<Utc>, failed: u64, ok: u64) {
+            let mut events = self.events.lock().unwrap();
+            for _ in 0..failed {
+                events.push((receiver_id, at, false));
+            }
+            for _ in 0..ok {
+                events.push((receiver_id, at, true));
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventCountRepository for MockCounts {
+        async fn receiver_event_counts(
+            &self,
+            start: DateTime<Utc>,
+            end: DateTime<Utc>,
+        ) -> Result<Vec<ReceiverEventCounts>> {
+            let mut counts: HashMap<EventReceiverId, ReceiverEventCounts> = HashMap::new();
+            for (receiver_id, at, success) in self.events.lock().unwrap().iter() {
+                if *at < start || *at >= end {
+                    continue;
+                }
+                let entry = counts.entry(*receiver_id).or_insert(ReceiverEventCounts {
+                    receiver_id: *receiver_id,
+                    successful: 0,
+                    failed: 0,
+                });
+                if *success {
+                    entry.successful += 1;
+                } else {
+                    entry.failed += 1;
+                }
+            }
+            Ok(counts.into_values().collect())
+        }
+
+        async fn bucket_counts(
+            &self,
+            _start: DateTime<Utc>,
+            _end: DateTime<Utc>,
+        ) -> Result<Vec<EventCountBucket>> {
+            unimplemented!()
+        }
+    }
+
+    struct Fixture {
+        receivers: EventReceiverHandler,
+        system_events: Arc<InMemoryEventRepository>,
+        counts: Arc<MockCounts>,
+        bus: DomainEventBus,
+        job: ReceiverAutoDisableHandler,
+    }
+
+    fn fixture() -> Fixture {
+        let system_events = Arc::new(InMemoryEventRepository::new());
+        let bus = DomainEventBus::new();
+        let receivers = EventReceiverHandler::new(Arc::new(InMemoryEventReceiverRepository::new()))
+            .with_event_bus(bus.clone())
+            .with_system_event_factory(SystemEventFactory::new().with_store(system_events.clone()));
+        let counts = Arc::new(MockCounts::default());
+        let stats = EventStatsHandler::new(counts.clone(), counts.clone());
+        let job = ReceiverAutoDisableHandler::new(receivers.clone(), stats);
+        Fixture {
+            receivers,
+            system_events,
+            counts,
+            bus,
+            job,
+        }
+    }
+
+    async fn receiver_with_policy(receivers: &EventReceiverHandler, name: &str) -> EventReceiverId {
+        let receiver_id = receivers
+            .create_event_receiver(
+                name.to_string(),
+                "webhook".to_string(),
+                "1.0.0".to_string(),
+                "Posts build results".to_string(),
+                json!({}),
+                UserId::new(),
+            )
+            .await
+            .unwrap();
+        receivers
+            .update_auto_disable_policy(
+                receiver_id,
+                Some(AutoDisablePolicy {
+                    failure_rate_threshold: 0.9,
+                    observation_window_minutes: 60,
+                    minimum_event_count: 20,
+                }),
+            )
+            .await
+            .unwrap();
+        receiver_id
+    }
+
+    #[tokio::test]
+    async fn test_threshold_breach_disables_receiver() {
+        let f = fixture();
+        let receiver_id = receiver_with_policy(&f.receivers, "flaky-integration").await;
+        let quiet_id = receiver_with_policy(&f.receivers, "recovered-integration").await;
+        let mut notifications = f.bus.subscribe();
+        let now = Utc::now();
+        f.counts
+            .add(receiver_id, now - Duration::minutes(10), 19, 1);
+        // Failures outside the window do not count
+        f.counts.add(quiet_id, now - Duration::minutes(90), 20, 0);
+        f.counts.add(quiet_id, now - Duration::minutes(10), 0, 20);
+
+        let (disabled, evaluated) = f.job.run_once(now).await.unwrap();
+        assert_eq!(evaluated, 2);
+        assert_eq!(disabled.len(), 1);
+        let (disabled_id, trigger) = &disabled[0];
+        assert_eq!(*disabled_id, receiver_id);
+        assert_eq!((trigger.failed, trigger.total), (19, 20));
+        assert_eq!(trigger.failure_rate, 0.95);
+        assert_eq!(trigger.window_start, now - Duration::minutes(60));
+
+        let receiver = f
+            .receivers
+            .get_event_receiver_or_error(receiver_id)
+            .await
+            .unwrap();
+        assert_eq!(receiver.state(), ReceiverState::AutoDisabled);
+        assert_eq!(receiver.auto_disabled(), Some(trigger));
+        let quiet = f
+            .receivers
+            .get_event_receiver_or_error(quiet_id)
+            .await
+            .unwrap();
+        assert_eq!(quiet.state(), ReceiverState::Active);
+
+        // The system event is stored and announced with the statistics
+        let stored = f
+            .system_events
+            .find_by_receiver_id(receiver_id)
+            .await
+            .unwrap();
+        let system_event = stored
+            .iter()
+            .find(|event| event.name() == RECEIVER_AUTO_DISABLED_EVENT)
+            .expect("auto-disabled system event was not stored");
+        assert_eq!(system_event.payload()["statistics"]["failed"], 19);
+        loop {
+            if let DomainEvent::ReceiverAutoDisabled {
+                receiver,
+                trigger: announced,
+                system_event,
+            } = &*notifications.recv().await.unwrap()
+            {
+                assert_eq!(receiver.id(), receiver_id);
+                assert_eq!(announced, trigger);
+                assert!(system_event.is_some());
+                break;
+            }
+        }
+
+        // A disabled receiver is not evaluated again
+        let (disabled, evaluated) = f.job.run_once(now).await.unwrap();
+        assert!(disabled.is_empty());
+        assert_eq!(evaluated, 1);
+    }
+
+    #[tokio::test]
+    async fn test_below_minimum_count_never_disables() {
+        let f = fixture();
+        let receiver_id = receiver_with_policy(&f.receivers, "flaky-integration").await;
+        let now = Utc::now();
+        f.counts.add(receiver_id, now - Duration::minutes(5), 19, 0);
+
+        let (disabled, _) = f.job.run_once(now).await.unwrap();
+        assert!(disabled.is_empty());
+        let receiver = f
+            .receivers
+            .get_event_receiver_or_error(receiver_id)
+            .await
+            .unwrap();
+        assert_eq!(receiver.state(), ReceiverState::Active);
+    }
+
+    #[tokio::test]
+    async fn test_re_enable_resets_the_window() {
+        let f = fixture();
+        let receiver_id = receiver_with_policy(&f.receivers, "flaky-integration").await;
+        let now = Utc::now();
+        f.counts
+            .add(receiver_id, now - Duration::minutes(10), 20, 0);
+        assert_eq!(f.job.run_once(now).await.unwrap().0.len(), 1);
+
+        f.receivers
+            .enable_event_receiver(receiver_id)
+            .await
+            .unwrap();
+        let receiver = f
+            .receivers
+            .get_event_receiver_or_error(receiver_id)
+            .await
+            .unwrap();
+        assert_eq!(receiver.state(), ReceiverState::Active);
+        assert_eq!(receiver.auto_disabled(), None);
+
+        // The failures that disabled it are before the new window
+        let later = Utc::now() + Duration::minutes(1);
+        assert!(f.job.run_once(later).await.unwrap().0.is_empty());
+
+        // New failures after re-enabling disable it again
+        f.counts.add(receiver_id, later, 20, 0);
+        let (disabled, _) = f.job.run_once(later + Duration::seconds(1)).await.unwrap();
+        assert_eq!(disabled.len(), 1);
+        assert!(disabled[0].1.window_start > now);
+    }
+}
@@ -0,0 +1,528 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/receiver_hygiene_handler.rs
+
+use crate::application::handlers::system_events::{
+    report_system_event_failure, SystemEventFactory, RECEIVER_STALE_EVENT,
+};
+use crate::domain::entities::event::Event;
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::receiver_activity::principal_window_start;
+use crate::domain::repositories::event_receiver_repo::{
+    EventReceiverRepository, FindEventReceiverCriteria,
+};
+use crate::domain::repositories::pagination::{ListOrder, ReceiverSortField};
+use crate::domain::repositories::receiver_activity_repo::ReceiverActivityRepository;
+use crate::domain::repositories::receiver_heartbeat_repo::ReceiverHeartbeatRepository;
+use crate::domain::value_objects::{EventReceiverId, UserId};
+use crate::error::{DomainError, Result};
+use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
+use crate::infrastructure::messaging::producer::KafkaEventPublisher;
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Default number of days without events before a receiver is stale
+pub const DEFAULT_STALE_DAYS: u32 = 90;
+
+/// Number of receivers loaded per query while building the report
+const STALE_PAGE_SIZE: usize = 500;
+
+/// A receiver with no events or heartbeats for longer than the stale window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleReceiver {
+    pub receiver_id: EventReceiverId,
+    pub name: String,
+    pub owner_id: UserId,
+    /// Time of the latest event; `None` if it never received one
+    pub last_event_at: Option<DateTime<Utc>>,
+    pub last_principal: Option<String>,
+    /// Time of the latest heartbeat; `None` if it never sent one
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+}
+
+/// Application service reporting receivers that stopped receiving events
+///
+/// Receivers created within the stale window are never reported, since
+/// they have not had the chance to go quiet yet. A recent heartbeat keeps a
+/// receiver healthy even when it has no recent events.
+#[derive(Clone)]
+pub struct ReceiverHygieneHandler {
+    receiver_repository: Arc<dyn EventReceiverRepository>,
+    activity_repository: Arc<dyn ReceiverActivityRepository>,
+    heartbeat_repository: Option<Arc<dyn ReceiverHeartbeatRepository>>,
+    event_publisher: Option<Arc<KafkaEventPublisher>>,
+    system_events: SystemEventFactory,
+    stale_after: Duration,
+}
+
+impl ReceiverHygieneHandler {
+    /// Creates a new hygiene handler using the default stale window
+    pub fn new(
+        receiver_repository: Arc<dyn EventReceiverRepository>,
+        activity_repository: Arc<dyn ReceiverActivityRepository>,
+    ) -> Self {
+        Self {
+            receiver_repository,
+            activity_repository,
+            heartbeat_repository: None,
+            event_publisher: None,
+            system_events: SystemEventFactory::new(),
+            stale_after: Duration::days(i64::from(DEFAULT_STALE_DAYS)),
+        }
+    }
+
+    /// Sets how long a receiver may go without events before it is stale
+    pub fn with_stale_days(mut self, stale_days: u32) -> Self {
+        self.stale_after = Duration::days(i64::from(stale_days));
+        self
+    }
+
+    /// Treats receivers with a recent heartbeat as healthy
+    pub fn with_heartbeats(
+        mut self,
+        heartbeat_repository: Arc<dyn ReceiverHeartbeatRepository>,
+    ) -> Self {
+        self.heartbeat_repository = Some(heartbeat_repository);
+        self
+    }
+
+    /// Publishes a system event for every stale receiver found
+    pub fn with_publisher(mut self, event_publisher: Arc<KafkaEventPublisher>) -> Self {
+        self.event_publisher = Some(event_publisher);
+        self
+    }
+
+    /// Sets the factory that builds and stores system events
+    pub fn with_system_event_factory(mut self, factory: SystemEventFactory) -> Self {
+        self.system_events = factory;
+        self
+    }
+
+    /// Finds receivers without events or heartbeats since the stale cutoff
+    /// at `now`
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<Vec<StaleReceiver>> {
+        let cutoff = now - self.stale_after;
+        let mut stale = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let criteria = FindEventReceiverCriteria::new()
+                .with_stale_since(cutoff)
+                .with_order(ListOrder::asc(ReceiverSortField::CreatedAt))
+                .with_limit(STALE_PAGE_SIZE)
+                .with_offset(offset);
+            let page = self.receiver_repository.find_by_criteria(criteria).await?;
+            let page_len = page.len();
+            offset += page_len;
+
+            let candidates: Vec<EventReceiver> = page
+                .into_iter()
+                .filter(|receiver| receiver.created_at() < cutoff)
+                .collect();
+            let ids: Vec<EventReceiverId> = candidates.iter().map(|r| r.id()).collect();
+            let activity: HashMap<_, _> = self
+                .activity_repository
+                .find_activity(&ids, principal_window_start(now))
+                .await?
+                .into_iter()
+                .map(|activity| (activity.receiver_id, activity))
+                .collect();
+            let heartbeats: HashMap<_, _> = match &self.heartbeat_repository {
+                Some(repository) => repository
+                    .find_heartbeats(&ids)
+                    .await?
+                    .into_iter()
+                    .map(|heartbeat| (heartbeat.receiver_id, heartbeat.last_heartbeat_at))
+                    .collect(),
+                None => HashMap::new(),
+            };
+
+            for receiver in candidates {
+                let last_heartbeat_at = heartbeats.get(&receiver.id()).copied();
+                if last_heartbeat_at.is_some_and(|at| at >= cutoff) {
+                    continue;
+                }
+                let activity = activity.get(&receiver.id());
+                stale.push(StaleReceiver {
+                    receiver_id: receiver.id(),
+                    name: receiver.name().to_string(),
+                    owner_id: receiver.owner_id(),
+                    last_event_at: activity.map(|a| a.last_event_at),
+                    last_principal: activity.map(|a| a.last_principal.clone()),
+                    last_heartbeat_at,
+                });
+            }
+
+            if page_len < STALE_PAGE_SIZE {
+                break;
+            }
+        }
+
+        for receiver in &stale {
+            warn!(
+                receiver_id = %receiver.receiver_id,
+                receiver_name = %receiver.name,
+                owner_id = %receiver.owner_id,
+                last_event_at = ?receiver.last_event_at,
+                last_principal = ?receiver.last_principal,
+                last_heartbeat_at = ?receiver.last_heartbeat_at,
+                "Event receiver is stale"
+            );
+            self.emit_stale(receiver).await;
+        }
+
+        info!(
+            stale = stale.len(),
+            stale_days = self.stale_after.num_days(),
+            "Receiver hygiene report complete"
+        );
+
+        Ok(stale)
+    }
+
+    /// Runs the report every `interval` until the task is aborted
+    pub fn spawn(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once(Utc::now()).await {
+                    error!("Receiver hygiene report failed: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn emit_stale(&self, receiver: &StaleReceiver) {
+        match self.create_receiver_stale_event(receiver) {
+            Ok(system_event) => {
+                self.system_events.record(&system_event).await;
+                let Some(publisher) = &self.event_publisher else {
+                    return;
+                };
+
+                let message = CloudEventMessage::from_event(&system_event);
+                if let Err(e) = publisher.publish_message(&message).await {
+                    error!(
+                        receiver_id = %receiver.receiver_id,
+                        error = %e,
+                        "Failed to publish stale receiver event to Kafka"
+                    );
+                }
+            }
+            Err(e) => report_system_event_failure(
+                None,
+                RECEIVER_STALE_EVENT,
+                &receiver.receiver_id.to_string(),
+                &e,
+            ),
+        }
+    }
+
+    /// Creates a system event for a stale receiver
+    fn create_receiver_stale_event(
+        &self,
+        receiver: &StaleReceiver,
+    ) -> std::result::Result<Event, DomainError> {
+        use serde_json::json;
+
+        let payload = json!({
+            "receiver_id": receiver.receiver_id.to_string(),
+            "name": receiver.name,
+            "last_event_at": receiver.last_event_at,
+            "last_principal": receiver.last_principal,
+            "last_heartbeat_at": receiver.last_heartbeat_at,
+            "stale_days": self.stale_after.num_days(),
+        });
+
+        self.system_events.build(
+            RECEIVER_STALE_EVENT,
+            format!("Event receiver '{}' is stale", receiver.name),
+            payload,
+            receiver.receiver_id,
+            receiver.owner_id,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::receiver_activity::{ReceiverActivity, ReceiverActivityUpdate};
+    use crate::domain::entities::receiver_heartbeat::ReceiverHeartbeat;
+    use crate::domain::repositories::receiver_heartbeat_repo::HeartbeatWrite;
+    use async_trait::async_trait;
+    use chrono::NaiveDate;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockReceiverRepository {
+        receivers: Mutex<Vec<EventReceiver>>,
+        activity: Mutex<HashMap<EventReceiverId, ReceiverActivityUpdate>>,
+        heartbeats: Mutex<HashMap<EventReceiverId, ReceiverHeartbeat>>,
+    }
+
+    impl MockReceiverRepository {
+        fn add(&self, name: &str) -> EventReceiverId {
+            let receiver = EventReceiver::new(
+                name.to_string(),
+                "webhook".to_string(),
+                "1.0.0".to_string(),
+                "A test receiver".to_string(),
+                json!({}),
+                UserId::new(),
+            )
+            .unwrap();
+            let id = receiver.id();
+            self.receivers.lock().unwrap().push(receiver);
+            id
+        }
+
+        fn seen(&self, receiver_id: EventReceiverId, at: DateTime<Utc>) {
+            self.activity.lock().unwrap().insert(
+                receiver_id,
+                ReceiverActivityUpdate::new(receiver_id, "api_key:ci".to_string(), at),
+            );
+        }
+
+        fn heartbeat(&self, receiver_id: EventReceiverId, at: DateTime<Utc>) {
+            self.heartbeats.lock().unwrap().insert(
+                receiver_id,
+                ReceiverHeartbeat::new(receiver_id, at, None).unwrap(),
+            );
+        }
+    }
+
+    #[async_trait]
+    impl EventReceiverRepository for MockReceiverRepository {
+        async fn save(&self, event_receiver: &EventReceiver) -> Result<()> {
+            self.receivers.lock().unwrap().push(event_receiver.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
+            let receivers = self.receivers.lock().unwrap();
+            Ok(receivers.iter().find(|r| r.id() == id).cloned())
+        }
+
+        async fn find_by_name(&self, _name: &str) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_type(&self, _receiver_type: &str) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_type_and_version(
+            &self,
+            _receiver_type: &str,
+            _version: &str,
+        ) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_fingerprint(&self, _fingerprint: &str) -> Result<Option<EventReceiver>> {
+            Ok(None)
+        }
+
+        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(self.receivers.lock().unwrap().len())
+        }
+
+        async fn update(&self, _event_receiver: &EventReceiver) -> Result<()> {
+            Ok(())
+        }
+
+        async fn delete(&self, _id: EventReceiverId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn exists_by_name_and_type(&self, _name: &str, _receiver_type: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn find_by_criteria(
+            &self,
+            criteria: FindEventReceiverCriteria,
+        ) -> Result<Vec<EventReceiver>> {
+            let receivers = self.receivers.lock().unwrap();
+            let activity = self.activity.lock().unwrap();
+            Ok(receivers
+                .iter()
+                .filter(|r| match criteria.stale_since {
+                    Some(since) => activity
+                        .get(&r.id())
+                        .is_none_or(|a| a.last_event_at < since),
+                    None => true,
+                })
+                .skip(criteria.offset.unwrap_or(0))
+                .take(criteria.limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_owner(&self, _owner_id: UserId) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner_paginated(
+            &self,
+            _owner_id: UserId,
+            _limit: usize,
+            _offset: usize,
+        ) -> Result<Vec<EventReceiver>> {
+            Ok(vec![])
+        }
+
+        async fn is_owner(&self, _receiver_id: EventReceiverId, _user_id: UserId) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn get_resource_version(&self, _receiver_id: EventReceiverId) -> Result<Option<i64>> {
+            Ok(Some(1))
+        }
+    }
+
+    #[async_trait]
+    impl ReceiverActivityRepository for MockReceiverRepository {
+        async fn record_activity(&self, _updates: &[ReceiverActivityUpdate]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn find_activity(
+            &self,
+            receiver_ids: &[EventReceiverId],
+            _window_start: NaiveDate,
+        ) -> Result<Vec<ReceiverActivity>> {
+            let activity = self.activity.lock().unwrap();
+            Ok(receiver_ids
+                .iter()
+                .filter_map(|id| activity.get(id))
+                .map(|update| ReceiverActivity {
+                    receiver_id: update.receiver_id,
+                    last_event_at: update.last_event_at,
+                    last_principal: update.last_principal.clone(),
+                    distinct_principals: update.daily_principals.len() as u64,
+                })
+                .collect())
+        }
+
+        async fn most_recently_active(&self, limit: usize) -> Result<Vec<EventReceiverId>> {
+            let activity = self.activity.lock().unwrap();
+            let mut updates: Vec<_> = activity.values().collect();
+            updates.sort_by_key(|update| std::cmp::Reverse(update.last_event_at));
+            Ok(updates
+                .into_iter()
+                .take(limit)
+                .map(|update| update.receiver_id)
+                .collect())
+        }
+    }
+
+    #[async_trait]
+    impl ReceiverHeartbeatRepository for MockReceiverRepository {
+        async fn record_heartbeat(
+            &self,
+            heartbeat: &ReceiverHeartbeat,
+            _not_after: DateTime<Utc>,
+        ) -> Result<HeartbeatWrite> {
+            self.heartbeat(heartbeat.receiver_id, heartbeat.last_heartbeat_at);
+            Ok(HeartbeatWrite::Recorded)
+        }
+
+        async fn find_heartbeats(
+            &self,
+            receiver_ids: &[EventReceiverId],
+        ) -> Result<Vec<ReceiverHeartbeat>> {
+            let heartbeats = self.heartbeats.lock().unwrap();
+            Ok(receiver_ids
+                .iter()
+                .filter_map(|id| heartbeats.get(id).cloned())
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_lists_receivers_without_recent_events() {
+        let repo = Arc::new(MockReceiverRepository::default());
+        let now = Utc::now();
+        let active = repo.add("active");
+        let quiet = repo.add("quiet");
+        let silent = repo.add("silent");
+        repo.seen(active, now + Duration::days(95));
+        repo.seen(quiet, now + Duration::days(5));
+
+        // 100 days on, the stale cutoff falls 10 days after creation
+        let handler = ReceiverHygieneHandler::new(repo.clone(), repo.clone());
+        let mut stale = handler.run_once(now + Duration::days(100)).await.unwrap();
+        stale.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(stale.len(), 2);
+        assert_eq!(stale[0].receiver_id, quiet);
+        assert_eq!(stale[0].last_event_at, Some(now + Duration::days(5)));
+        assert_eq!(stale[0].last_principal.as_deref(), Some("api_key:ci"));
+        assert_eq!(stale[1].receiver_id, silent);
+        assert_eq!(stale[1].last_event_at, None);
+    }
+
+    #[tokio::test]
+    async fn test_report_skips_receivers_created_inside_window() {
+        let repo = Arc::new(MockReceiverRepository::default());
+        repo.add("new");
+
+        let handler = ReceiverHygieneHandler::new(repo.clone(), repo.clone()).with_stale_days(30);
+        let now = Utc::now();
+        assert!(handler
+            .run_once(now + Duration::days(29))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            handler
+                .run_once(now + Duration::days(31))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recent_heartbeat_keeps_quiet_receiver_healthy() {
+        let repo = Arc::new(MockReceiverRepository::default());
+        let now = Utc::now();
+        let agent = repo.add("agent");
+        let crashed = repo.add("crashed");
+        let silent = repo.add("silent");
+        repo.seen(agent, now + Duration::days(1));
+        repo.heartbeat(agent, now + Duration::days(99));
+        repo.heartbeat(crashed, now + Duration::days(2));
+
+        let handler =
+            ReceiverHygieneHandler::new(repo.clone(), repo.clone()).with_heartbeats(repo.clone());
+        let mut stale = handler.run_once(now + Duration::days(100)).await.unwrap();
+        stale.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Heartbeats older than the cutoff do not help, but are reported
+        assert_eq!(stale.len(), 2);
+        assert_eq!(stale[0].receiver_id, crashed);
+        assert_eq!(stale[0].last_heartbeat_at, Some(now + Duration::days(2)));
+        assert_eq!(stale[1].receiver_id, silent);
+        assert_eq!(stale[1].last_heartbeat_at, None);
+
+        // Without heartbeats configured, the agent is reported as stale
+        let handler = ReceiverHygieneHandler::new(repo.clone(), repo.clone());
+        let stale = handler.run_once(now + Duration::days(100)).await.unwrap();
+        assert!(stale.iter().any(|r| r.receiver_id == agent));
+    }
+}
@@ -0,0 +1,353 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/receiver_timeline_handler.rs
+
+use crate::domain::repositories::receiver_timeline_repo::{
+    ReceiverTimelineRepository, TimelineCursor, TimelineEntry, TimelineKind,
+};
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+
+use chrono::{DateTime, Utc};
+use std::iter::Peekable;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Which part of a receiver timeline to read
+#[derive(Debug, Clone)]
+pub struct TimelineQuery {
+    /// Earliest entry time, inclusive; `None` starts at the first entry
+    pub start: Option<DateTime<Utc>>,
+    /// Latest entry time, exclusive; `None` reads up to now
+    pub end: Option<DateTime<Utc>>,
+    /// Cursor returned with the previous page; takes precedence over `start`
+    pub after: Option<TimelineCursor>,
+    pub limit: usize,
+    /// Kinds the caller may see; other kinds are never read
+    pub kinds: Vec<TimelineKind>,
+}
+
+/// A page of a receiver timeline
+#[derive(Debug, Clone)]
+pub struct TimelinePage {
+    pub entries: Vec<TimelineEntry>,
+    /// Cursor of the last entry, set when more entries follow
+    pub next_cursor: Option<TimelineCursor>,
+}
+
+/// Merges the events, configuration changes, and group membership changes
+/// of a receiver into one chronological timeline
+///
+/// Each page reads at most `limit + 1` entries per kind following the
+/// cursor and merges them, so a page costs the same however long the
+/// timeline is.
+#[derive(Clone)]
+pub struct ReceiverTimelineHandler {
+    repository: Arc<dyn ReceiverTimelineRepository>,
+}
+
+impl ReceiverTimelineHandler {
+    /// Creates a timeline handler backed by `repository`
+    pub fn new(repository: Arc<dyn ReceiverTimelineRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Returns a page of the timeline of `receiver_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `start` is not before `end`.
+    pub async fn timeline(
+        &self,
+        receiver_id: EventReceiverId,
+        query: TimelineQuery,
+    ) -> Result<TimelinePage> {
+        if let (Some(start), Some(end)) = (query.start, query.end) {
+            if start >= end {
+                return Err(DomainError::ValidationError {
+                    field: "start".to_string(),
+                    message: Message::new("validation.time_range_order"),
+                }
+                .into());
+            }
+        }
+
+        let after = query.after.unwrap_or_else(|| {
+            TimelineCursor::at(query.start.unwrap_or(DateTime::<Utc>::UNIX_EPOCH))
+        });
+        debug!(
+            receiver_id = %receiver_id,
+            after = %after,
+            limit = query.limit,
+            "Reading receiver timeline"
+        );
+
+        let mut sources = Vec::with_capacity(query.kinds.len());
+        for kind in TimelineKind::ALL {
+            if !query.kinds.contains(&kind) {
+                continue;
+            }
+            let entries = self
+                .repository
+                .find_timeline_entries(receiver_id, kind, &after, query.end, query.limit + 1)
+                .await?;
+            sources.push(entries.into_iter().peekable());
+        }
+
+        let mut entries = merge(sources, query.limit + 1);
+        let has_more = entries.len() > query.limit;
+        entries.truncate(query.limit);
+
+        Ok(TimelinePage {
+            next_cursor: has_more
+                .then(|| entries.last().map(|entry| entry.cursor.clone()))
+                .flatten(),
+            entries,
+        })
+    }
+}
+
+/// Merges sources already in timeline order, stopping after `limit`
+fn merge<I>(mut sources: Vec<Peekable<I>>, limit: usize) -> Vec<TimelineEntry>
+where
+    I: Iterator<Item = TimelineEntry>,
+{
+    let mut merged = Vec::with_capacity(limit);
+    while merged.len() < limit {
+        let next = sources
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, source)| source.peek().map(|entry| (index, &entry.cursor)))
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map(|(index, _)| index);
+        match next.and_then(|index| sources[index].next()) {
+            Some(entry) => merged.push(entry),
+            None => break,
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::receiver_timeline_repo::{
+        history_key, ConfigChangeAction, MembershipAction, TimelineDetail,
+    };
+    use crate::domain::value_objects::{EventId, EventReceiverGroupId};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Serves entries kept in memory, filtering like a storage backend
+    #[derive(Default)]
+    struct MockTimelineRepository {
+        entries: Vec<TimelineEntry>,
+        reads: Mutex<Vec<TimelineKind>>,
+    }
+
+    #[async_trait]
+    impl ReceiverTimelineRepository for MockTimelineRepository {
+        async fn find_timeline_entries(
+            &self,
+            _receiver_id: EventReceiverId,
+            kind: TimelineKind,
+            after: &TimelineCursor,
+            end: Option<DateTime<Utc>>,
+            limit: usize,
+        ) -> Result<Vec<TimelineEntry>> {
+            self.reads.lock().unwrap().push(kind);
+            let mut entries: Vec<_> = self
+                .entries
+                .iter()
+                .filter(|entry| entry.kind() == kind)
+                .filter(|entry| match after.key_after(kind) {
+                    Some(key) => {
+                        entry.at() > after.timestamp()
+                            || (entry.at() == after.timestamp()
+                                && entry.cursor > TimelineCursor::new(entry.at(), kind, key))
+                    }
+                    None => entry.at() > after.timestamp(),
+                })
+                .filter(|entry| end.is_none_or(|end| entry.at() < end))
+                .cloned()
+                .collect();
+            entries.sort_by(|a, b| a.cursor.cmp(&b.cursor));
+            entries.truncate(limit);
+            Ok(entries)
+        }
+    }
+
+    fn event(at: DateTime<Utc>, id: EventId) -> TimelineEntry {
+        TimelineEntry::new(
+            at,
+            id.to_string(),
+            TimelineDetail::Event {
+                id,
+                name: "build".to_string(),
+                success: true,
+                severity: String::new(),
+            },
+        )
+    }
+
+    fn config_change(at: DateTime<Utc>, row_id: i64) -> TimelineEntry {
+        TimelineEntry::new(
+            at,
+            history_key(row_id),
+            TimelineDetail::ConfigChange {
+                action: ConfigChangeAction::Updated,
+                changed_fields: vec!["description".to_string()],
+            },
+        )
+    }
+
+    fn membership(at: DateTime<Utc>, row_id: i64) -> TimelineEntry {
+        TimelineEntry::new(
+            at,
+            history_key(row_id),
+            TimelineDetail::GroupMembership {
+                group_id: EventReceiverGroupId::new(),
+                group_name: "release".to_string(),
+                action: MembershipAction::Added,
+            },
+        )
+    }
+
+    fn query(limit: usize, after: Option<TimelineCursor>) -> TimelineQuery {
+        TimelineQuery {
+            start: None,
+            end: None,
+            after,
+            limit,
+            kinds: TimelineKind::ALL.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interleaves_sources_with_identical_timestamps() {
+        let t0 = Utc::now();
+        let t1 = t0 + chrono::Duration::seconds(1);
+        let (first, second) = (EventId::new(), EventId::new());
+        let repository = MockTimelineRepository {
+            entries: vec![
+                membership(t1, 9),
+                config_change(t1, 4),
+                event(t1, second),
+                config_change(t0, 3),
+                event(t0, first),
+                membership(t0, 8),
+            ],
+            ..Default::default()
+        };
+        let handler = ReceiverTimelineHandler::new(Arc::new(repository));
+
+        let page = handler
+            .timeline(EventReceiverId::new(), query(10, None))
+            .await
+            .unwrap();
+
+        let order: Vec<_> = page
+            .entries
+            .iter()
+            .map(|entry| (entry.at(), entry.kind()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (t0, TimelineKind::Event),
+                (t0, TimelineKind::ConfigChange),
+                (t0, TimelineKind::GroupMembership),
+                (t1, TimelineKind::Event),
+                (t1, TimelineKind::ConfigChange),
+                (t1, TimelineKind::GroupMembership),
+            ]
+        );
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pages_cover_timeline_once() {
+        let t0 = Utc::now();
+        let mut entries = Vec::new();
+        for row_id in 0..4 {
+            entries.push(event(t0, EventId::new()));
+            entries.push(config_change(t0, row_id));
+            entries.push(membership(t0 + chrono::Duration::seconds(row_id), row_id));
+        }
+        let repository = MockTimelineRepository {
+            entries: entries.clone(),
+            ..Default::default()
+        };
+        let handler = ReceiverTimelineHandler::new(Arc::new(repository));
+        let receiver_id = EventReceiverId::new();
+
+        let mut seen = Vec::new();
+        let mut after = None;
+        loop {
+            let page = handler
+                .timeline(receiver_id, query(5, after.clone()))
+                .await
+                .unwrap();
+            assert!(page.entries.len() <= 5);
+            seen.extend(page.entries.iter().map(|entry| entry.cursor.clone()));
+
+            // Replaying a cursor returns the same page
+            let again = handler
+                .timeline(receiver_id, query(5, after.clone()))
+                .await
+                .unwrap();
+            assert_eq!(again.entries, page.entries);
+
+            match page.next_cursor {
+                Some(cursor) => {
+                    let parsed = TimelineCursor::parse(&cursor.to_string()).unwrap();
+                    assert_eq!(parsed, cursor);
+                    after = Some(parsed);
+                }
+                None => break,
+            }
+        }
+
+        let mut expected: Vec<_> = entries.into_iter().map(|entry| entry.cursor).collect();
+        expected.sort();
+        assert_eq!(seen, expected);
+    }
+
+    #[tokio::test]
+    async fn test_only_permitted_kinds_are_read() {
+        let t0 = Utc::now();
+        let repository = Arc::new(MockTimelineRepository {
+            entries: vec![
+                event(t0, EventId::new()),
+                config_change(t0, 1),
+                membership(t0, 2),
+            ],
+            ..Default::default()
+        });
+        let handler = ReceiverTimelineHandler::new(repository.clone());
+
+        let page = handler
+            .timeline(
+                EventReceiverId::new(),
+                TimelineQuery {
+                    kinds: vec![TimelineKind::Event, TimelineKind::GroupMembership],
+                    ..query(10, None)
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.entries.len(), 2);
+        assert!(page
+            .entries
+            .iter()
+            .all(|entry| entry.kind() != TimelineKind::ConfigChange));
+        assert!(!repository
+            .reads
+            .lock()
+            .unwrap()
+            .contains(&TimelineKind::ConfigChange));
+    }
+}
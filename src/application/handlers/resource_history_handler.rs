@@ -0,0 +1,293 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/resource_history_handler.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::repositories::resource_history_repo::{
+    GroupState, ResourceHistoryRepository, ResourceSnapshot,
+};
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::Result;
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Returns the state recorded by the latest snapshot at or before `instant`
+///
+/// `history` must be ordered oldest first. An instant between two
+/// snapshots sees the earlier one, and an instant equal to a snapshot's
+/// time sees that snapshot. The result is `None` before the first
+/// snapshot and while the resource was deleted.
+pub fn state_at<T>(history: Vec<ResourceSnapshot<T>>, instant: DateTime<Utc>) -> Option<T> {
+    history
+        .into_iter()
+        .take_while(|snapshot| snapshot.recorded_at <= instant)
+        .last()
+        .and_then(|snapshot| snapshot.state)
+}
+
+/// Records and reconstructs past states of receivers and groups
+///
+/// The receiver and group handlers record a snapshot after every write.
+/// Recording failures are logged and never fail the write, so history can
+/// have gaps; a gap reads as the state of the snapshot before it.
+#[derive(Clone)]
+pub struct ResourceHistoryHandler {
+    repository: Arc<dyn ResourceHistoryRepository>,
+}
+
+impl ResourceHistoryHandler {
+    /// Creates a history handler backed by `repository`
+    pub fn new(repository: Arc<dyn ResourceHistoryRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Records the state of `receiver` after a write at `at`
+    pub async fn record_receiver(&self, receiver: &EventReceiver, at: DateTime<Utc>) {
+        self.record_receiver_state(receiver.id(), Some(receiver.clone()), at)
+            .await;
+    }
+
+    /// Records that receiver `id` was deleted at `at`
+    pub async fn record_receiver_deleted(&self, id: EventReceiverId, at: DateTime<Utc>) {
+        self.record_receiver_state(id, None, at).await;
+    }
+
+    /// Records the state of a group and its members after a write at `at`
+    pub async fn record_group(&self, state: GroupState, at: DateTime<Utc>) {
+        self.record_group_state(state.group.id(), Some(state), at)
+            .await;
+    }
+
+    /// Records that group `id` was deleted at `at`
+    pub async fn record_group_deleted(&self, id: EventReceiverGroupId, at: DateTime<Utc>) {
+        self.record_group_state(id, None, at).await;
+    }
+
+    /// Returns receiver `id` as it was at `instant`
+    ///
+    /// Returns `None` if the receiver did not exist yet or was deleted at
+    /// that instant.
+    pub async fn receiver_as_of(
+        &self,
+        id: EventReceiverId,
+        instant: DateTime<Utc>,
+    ) -> Result<Option<EventReceiver>> {
+        let history = self.repository.receiver_history(id).await?;
+        Ok(state_at(history, instant))
+    }
+
+    /// Returns group `id` and its members as they were at `instant`
+    ///
+    /// Memberships that had expired by `instant` are left out, matching
+    /// what a member list read at that instant returned. Returns `None` if
+    /// the group did not exist yet or was deleted at that instant.
+    pub async fn group_as_of(
+        &self,
+        id: EventReceiverGroupId,
+        instant: DateTime<Utc>,
+    ) -> Result<Option<GroupState>> {
+        let history = self.repository.group_history(id).await?;
+        Ok(state_at(history, instant).map(|mut state| {
+            state
+                .members
+                .retain(|membership| !membership.is_expired_at(instant));
+            state
+        }))
+    }
+
+    async fn record_receiver_state(
+        &self,
+        id: EventReceiverId,
+        state: Option<EventReceiver>,
+        at: DateTime<Utc>,
+    ) {
+        let snapshot = ResourceSnapshot {
+            recorded_at: at,
+            state,
+        };
+        if let Err(e) = self.repository.record_receiver(id, &snapshot).await {
+            warn!(receiver_id = %id, error = %e, "Failed to record event receiver history");
+        }
+    }
+
+    async fn record_group_state(
+        &self,
+        id: EventReceiverGroupId,
+        state: Option<GroupState>,
+        at: DateTime<Utc>,
+    ) {
+        let snapshot = ResourceSnapshot {
+            recorded_at: at,
+            state,
+        };
+        if let Err(e) = self.repository.record_group(id, &snapshot).await {
+            warn!(group_id = %id, error = %e, "Failed to record event receiver group history");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+    use crate::domain::repositories::event_receiver_group_repo::GroupMembership;
+    use crate::domain::value_objects::UserId;
+    use crate::infrastructure::memory::InMemoryResourceHistoryRepository;
+    use chrono::{Duration, TimeZone};
+    use serde_json::json;
+
+    fn handler() -> ResourceHistoryHandler {
+        ResourceHistoryHandler::new(Arc::new(InMemoryResourceHistoryRepository::default()))
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 5, 1, hour, 0, 0).unwrap()
+    }
+
+    fn receiver(description: &str) -> EventReceiver {
+        EventReceiver::new(
+            "builds".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            description.to_string(),
+            json!({}),
+            UserId::new(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_receiver_reconstructs_latest_snapshot_at_or_before_instant() {
+        let history = handler();
+        let v1 = receiver("first");
+        let id = v1.id();
+        let mut v2 = v1.clone();
+        v2.update(None, None, None, Some("second".to_string()), None)
+            .unwrap();
+        let mut v3 = v2.clone();
+        v3.update(None, None, None, Some("third".to_string()), None)
+            .unwrap();
+
+        // Recorded out of order to check the history is kept sorted
+        history.record_receiver(&v3, at(14)).await;
+        history.record_receiver(&v1, at(10)).await;
+        history.record_receiver(&v2, at(12)).await;
+        history.record_receiver_deleted(id, at(16)).await;
+
+        let described =
+            |receiver: Option<EventReceiver>| receiver.map(|r| r.description().to_string());
+        assert_eq!(
+            described(history.receiver_as_of(id, at(9)).await.unwrap()),
+            None
+        );
+        assert_eq!(
+            described(history.receiver_as_of(id, at(10)).await.unwrap()),
+            Some("first".to_string())
+        );
+        assert_eq!(
+            described(history.receiver_as_of(id, at(11)).await.unwrap()),
+            Some("first".to_string())
+        );
+        assert_eq!(
+            described(history.receiver_as_of(id, at(13)).await.unwrap()),
+            Some("second".to_string())
+        );
+        assert_eq!(
+            described(history.receiver_as_of(id, at(15)).await.unwrap()),
+            Some("third".to_string())
+        );
+        assert_eq!(
+            described(history.receiver_as_of(id, at(17)).await.unwrap()),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_group_reconstruction_drops_members_expired_at_instant() {
+        let history = handler();
+        let owner = UserId::new();
+        let group = EventReceiverGroup::new(
+            "deploys".to_string(),
+            "release".to_string(),
+            "1.0.0".to_string(),
+            "first".to_string(),
+            true,
+            vec![],
+            owner,
+        )
+        .unwrap();
+        let id = group.id();
+        let member = |user_id: UserId, expires_at: Option<DateTime<Utc>>| GroupMembership {
+            user_id,
+            added_by: owner,
+            added_at: at(10),
+            expires_at,
+        };
+        let (alice, bob) = (UserId::new(), UserId::new());
+
+        history
+            .record_group(
+                GroupState {
+                    group: group.clone(),
+                    members: vec![],
+                },
+                at(10),
+            )
+            .await;
+        history
+            .record_group(
+                GroupState {
+                    group: group.clone(),
+                    members: vec![member(alice, None), member(bob, Some(at(13)))],
+                },
+                at(12),
+            )
+            .await;
+        let mut disabled = group.clone();
+        disabled.disable();
+        history
+            .record_group(
+                GroupState {
+                    group: disabled,
+                    members: vec![member(alice, None), member(bob, Some(at(13)))],
+                },
+                at(14),
+            )
+            .await;
+
+        assert!(history
+            .group_as_of(id, at(10) - Duration::seconds(1))
+            .await
+            .unwrap()
+            .is_none());
+
+        let before_members = history.group_as_of(id, at(11)).await.unwrap().unwrap();
+        assert!(before_members.members.is_empty());
+        assert!(before_members.group.enabled());
+
+        let members_of = |state: GroupState| -> Vec<UserId> {
+            state.members.iter().map(|m| m.user_id).collect()
+        };
+        let with_bob = history
+            .group_as_of(id, at(12) + Duration::minutes(30))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(members_of(with_bob), vec![alice, bob]);
+
+        // Bob's membership lapsed between snapshots
+        let lapsed = history
+            .group_as_of(id, at(13) + Duration::minutes(30))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(lapsed.group.enabled());
+        assert_eq!(members_of(lapsed), vec![alice]);
+
+        let latest = history.group_as_of(id, at(20)).await.unwrap().unwrap();
+        assert!(!latest.group.enabled());
+    }
+}
@@ -0,0 +1,666 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/schema_preview_handler.rs
+
+use crate::domain::entities::schema_inheritance::payload_violations;
+use crate::domain::repositories::event_repo::{EventRepository, FindEventCriteria};
+use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{error, info};
+
+/// Default number of events loaded per query while previewing
+pub const DEFAULT_PREVIEW_BATCH_SIZE: usize = 500;
+
+/// Default cap on the number of events a single preview scans
+pub const DEFAULT_PREVIEW_MAX_SCANNED: usize = 100_000;
+
+/// Default number of events scanned inline before a preview moves to a job
+pub const DEFAULT_PREVIEW_SYNC_THRESHOLD: usize = 1_000;
+
+/// Default number of distinct violation messages reported
+pub const DEFAULT_PREVIEW_TOP_VIOLATIONS: usize = 10;
+
+/// Default number of failing event ids reported
+pub const DEFAULT_PREVIEW_SAMPLE_SIZE: usize = 20;
+
+/// How long finished jobs stay available for polling
+const FINISHED_JOB_TTL_MINUTES: i64 = 60;
+
+/// Candidate schema and the stored events to check it against
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaPreviewRequest {
+    pub schema: JsonValue,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+}
+
+/// A violation message and how many events produced it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViolationCount {
+    pub message: String,
+    pub count: usize,
+}
+
+/// Result of checking stored events against a candidate schema
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaPreviewReport {
+    pub scanned: usize,
+    pub passing: usize,
+    pub failing: usize,
+    /// Most frequent violation messages, most common first
+    pub top_violations: Vec<ViolationCount>,
+    /// Sample of failing event ids, newest first
+    pub failing_event_ids: Vec<EventId>,
+    /// True if the scan stopped at the cap before reaching the oldest event
+    pub truncated: bool,
+}
+
+/// State of a background preview job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaPreviewJobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+impl SchemaPreviewJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaPreviewJobStatus::Running => "running",
+            SchemaPreviewJobStatus::Completed => "completed",
+            SchemaPreviewJobStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Snapshot of a background preview job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaPreviewJob {
+    pub id: String,
+    pub event_receiver_id: EventReceiverId,
+    pub status: SchemaPreviewJobStatus,
+    /// Events scanned so far
+    pub scanned: usize,
+    /// Most events the job will scan
+    pub max_scanned: usize,
+    /// Final report once the job completes
+    pub report: Option<SchemaPreviewReport>,
+    /// Failure message if the job failed
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Result of starting a preview
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaPreviewOutcome {
+    /// The range was small enough to scan inline
+    Completed(SchemaPreviewReport),
+    /// The scan continues in a background job
+    Started(SchemaPreviewJob),
+}
+
+/// Running totals while scanning events
+#[derive(Debug, Default)]
+struct PreviewTally {
+    scanned: usize,
+    passing: usize,
+    failing: usize,
+    violations: HashMap<String, usize>,
+    failing_event_ids: Vec<EventId>,
+}
+
+impl PreviewTally {
+    fn into_report(self, top_violations: usize, truncated: bool) -> SchemaPreviewReport {
+        let mut violations: Vec<ViolationCount> = self
+            .violations
+            .into_iter()
+            .map(|(message, count)| ViolationCount { message, count })
+            .collect();
+        violations.sort_by(|a, b| b.count.cmp(&a.count).then(a.message.cmp(&b.message)));
+        violations.truncate(top_violations);
+
+        SchemaPreviewReport {
+            scanned: self.scanned,
+            passing: self.passing,
+            failing: self.failing,
+            top_violations: violations,
+            failing_event_ids: self.failing_event_ids,
+            truncated,
+        }
+    }
+}
+
+/// Application service checking a candidate schema against stored events
+///
+/// Nothing is persisted: the candidate schema only lives for the duration
+/// of the scan. Events are read newest first in batches, with the end of
+/// the range pinned to the request time so later events cannot shift the
+/// pages. Ranges that fit under the sync threshold are answered inline;
+/// larger ones continue in a background job that callers poll by id.
+#[derive(Clone)]
+pub struct SchemaPreviewHandler {
+    event_repository: Arc<dyn EventRepository>,
+    jobs: Arc<Mutex<HashMap<String, SchemaPreviewJob>>>,
+    batch_size: usize,
+    max_scanned: usize,
+    sync_threshold: usize,
+    top_violations: usize,
+    sample_size: usize,
+}
+
+impl SchemaPreviewHandler {
+    /// Creates a new preview handler using the default limits
+    pub fn new(event_repository: Arc<dyn EventRepository>) -> Self {
+        Self {
+            event_repository,
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            batch_size: DEFAULT_PREVIEW_BATCH_SIZE,
+            max_scanned: DEFAULT_PREVIEW_MAX_SCANNED,
+            sync_threshold: DEFAULT_PREVIEW_SYNC_THRESHOLD,
+            top_violations: DEFAULT_PREVIEW_TOP_VIOLATIONS,
+            sample_size: DEFAULT_PREVIEW_SAMPLE_SIZE,
+        }
+    }
+
+    /// Sets the number of events loaded per query
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the most events a single preview scans
+    pub fn with_max_scanned(mut self, max_scanned: usize) -> Self {
+        self.max_scanned = max_scanned.max(1);
+        self
+    }
+
+    /// Sets how many events are scanned inline before moving to a job
+    pub fn with_sync_threshold(mut self, sync_threshold: usize) -> Self {
+        self.sync_threshold = sync_threshold.max(1);
+        self
+    }
+
+    /// Sets how many distinct violation messages are reported
+    pub fn with_top_violations(mut self, top_violations: usize) -> Self {
+        self.top_violations = top_violations;
+        self
+    }
+
+    /// Sets how many failing event ids are reported
+    pub fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = sample_size;
+        self
+    }
+
+    /// Checks the candidate schema against events stored for a receiver
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the schema is not a JSON object or the
+    /// time range is inverted.
+    pub async fn preview(
+        &self,
+        receiver_id: EventReceiverId,
+        request: SchemaPreviewRequest,
+    ) -> Result<SchemaPreviewOutcome> {
+        if !request.schema.is_object() {
+            return Err(DomainError::ValidationError {
+                field: "schema".to_string(),
+                message: Message::new("validation.schema_not_object"),
+            }
+            .into());
+        }
+        if let (Some(start), Some(end)) = (request.start_time, request.end_time) {
+            if start > end {
+                return Err(DomainError::ValidationError {
+                    field: "start_time".to_string(),
+                    message: Message::new("validation.preview_time_range_order"),
+                }
+                .into());
+            }
+        }
+
+        let now = Utc::now();
+        let mut criteria = FindEventCriteria::new()
+            .with_event_receiver_id(receiver_id)
+            .with_end_time(request.end_time.map_or(now, |end| end.min(now)));
+        if let Some(start) = request.start_time {
+            criteria = criteria.with_start_time(start);
+        }
+
+        let mut tally = PreviewTally::default();
+        let inline_limit = self.sync_threshold.min(self.max_scanned);
+        let exhausted = self
+            .scan(&criteria, &request.schema, &mut tally, inline_limit, |_| {})
+            .await?;
+
+        if exhausted || tally.scanned >= self.max_scanned {
+            return Ok(SchemaPreviewOutcome::Completed(
+                tally.into_report(self.top_violations, !exhausted),
+            ));
+        }
+
+        let job = SchemaPreviewJob {
+            id: ulid::Ulid::new().to_string(),
+            event_receiver_id: receiver_id,
+            status: SchemaPreviewJobStatus::Running,
+            scanned: tally.scanned,
+            max_scanned: self.max_scanned,
+            report: None,
+            error: None,
+            started_at: now,
+            finished_at: None,
+        };
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            let expiry = now - Duration::minutes(FINISHED_JOB_TTL_MINUTES);
+            jobs.retain(|_, job| job.finished_at.is_none_or(|finished| finished > expiry));
+            jobs.insert(job.id.clone(), job.clone());
+        }
+
+        info!(
+            job_id = %job.id,
+            receiver_id = %receiver_id,
+            scanned = tally.scanned,
+            "Schema preview continuing in background"
+        );
+
+        let handler = self.clone();
+        let job_id = job.id.clone();
+        let schema = request.schema;
+        tokio::spawn(async move {
+            let progress = |scanned| handler.update_job(&job_id, |job| job.scanned = scanned);
+            let result = handler
+                .scan(
+                    &criteria,
+                    &schema,
+                    &mut tally,
+                    handler.max_scanned,
+                    progress,
+                )
+                .await;
+
+            handler.update_job(&job_id, |job| {
+                job.finished_at = Some(Utc::now());
+                match result {
+                    Ok(exhausted) => {
+                        job.status = SchemaPreviewJobStatus::Completed;
+                        job.scanned = tally.scanned;
+                        job.report = Some(
+                            std::mem::take(&mut tally)
+                                .into_report(handler.top_violations, !exhausted),
+                        );
+                    }
+                    Err(e) => {
+                        error!(job_id = %job.id, "Schema preview job failed: {}", e);
+                        job.status = SchemaPreviewJobStatus::Failed;
+                        job.error = Some(e.to_string());
+                    }
+                }
+            });
+        });
+
+        Ok(SchemaPreviewOutcome::Started(job))
+    }
+
+    /// Returns the current state of a preview job, if it is still known
+    pub fn job(&self, job_id: &str) -> Option<SchemaPreviewJob> {
+        self.jobs.lock().unwrap().get(job_id).cloned()
+    }
+
+    fn update_job(&self, job_id: &str, update: impl FnOnce(&mut SchemaPreviewJob)) {
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(job_id) {
+            update(job);
+        }
+    }
+
+    /// Validates events until `limit` are scanned or the range runs out
+    ///
+    /// Returns true if every event in the range has been scanned.
+    async fn scan(
+        &self,
+        criteria: &FindEventCriteria,
+        schema: &JsonValue,
+        tally: &mut PreviewTally,
+        limit: usize,
+        progress: impl Fn(usize),
+    ) -> Result<bool> {
+        while tally.scanned < limit {
+            let wanted = self.batch_size.min(limit - tally.scanned);
+            let batch = self
+                .event_repository
+                .find_by_criteria(
+                    criteria
+                        .clone()
+                        .with_limit(wanted)
+                        .with_offset(tally.scanned),
+                )
+                .await?;
+
+            for event in &batch {
+                let violations = payload_violations(schema, event.payload());
+                if violations.is_empty() {
+                    tally.passing += 1;
+                } else {
+                    tally.failing += 1;
+                    if tally.failing_event_ids.len() < self.sample_size {
+                        tally.failing_event_ids.push(event.id());
+                    }
+                    for violation in violations {
+                        *tally.violations.entry(violation).or_default() += 1;
+                    }
+                }
+            }
+            tally.scanned += batch.len();
+            progress(tally.scanned);
+
+            if batch.len() < wanted {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event::{DatabaseEventFields, Event, EventOrigin};
+    use crate::domain::value_objects::UserId;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    #[derive(Default)]
+    struct MockEventRepository {
+        events: Mutex<Vec<Event>>,
+    }
+
+    #[async_trait]
+    impl EventRepository for MockEventRepository {
+        async fn save(&self, event: &Event) -> Result<()> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, _id: EventId) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_success(&self, _success: bool) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_name(&self, _name: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_platform_id(&self, _platform_id: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_package(&self, _package: &str) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn list(&self, _limit: usize, _offset: usize) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(self.events.lock().unwrap().len())
+        }
+
+        async fn count_by_receiver_id(&self, _receiver_id: EventReceiverId) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn count_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<usize> {
+            Ok(0)
+        }
+
+        async fn delete(&self, _id: EventId) -> Result<()> {
+            Ok(())
+        }
+
+        async fn find_latest_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_latest_successful_by_receiver_id(
+            &self,
+            _receiver_id: EventReceiverId,
+        ) -> Result<Option<Event>> {
+            Ok(None)
+        }
+
+        async fn find_by_time_range(
+            &self,
+            _start: DateTime<Utc>,
+            _end: DateTime<Utc>,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_criteria(&self, criteria: FindEventCriteria) -> Result<Vec<Event>> {
+            let mut events: Vec<Event> = self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| {
+                    criteria
+                        .event_receiver_id
+                        .is_none_or(|id| e.event_receiver_id() == id)
+                        && criteria.start_time.is_none_or(|s| e.created_at() >= s)
+                        && criteria.end_time.is_none_or(|end| e.created_at() <= end)
+                })
+                .cloned()
+                .collect();
+            events.sort_by_key(|e| std::cmp::Reverse(e.created_at()));
+            Ok(events
+                .into_iter()
+                .skip(criteria.offset.unwrap_or(0))
+                .take(criteria.limit.unwrap_or(usize::MAX))
+                .collect())
+        }
+
+        async fn find_by_owner(&self, _owner_id: UserId) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn find_by_owner_paginated(
+            &self,
+            _owner_id: UserId,
+            _limit: usize,
+            _offset: usize,
+        ) -> Result<Vec<Event>> {
+            Ok(vec![])
+        }
+
+        async fn is_owner(&self, _event_id: EventId, _user_id: UserId) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn get_resource_version(&self, _event_id: EventId) -> Result<Option<i64>> {
+            Ok(Some(1))
+        }
+    }
+
+    fn event(receiver_id: EventReceiverId, minutes_ago: i64, payload: JsonValue) -> Event {
+        Event::from_database(DatabaseEventFields {
+            id: EventId::new(),
+            name: "deploy".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.01".to_string(),
+            platform_id: "linux".to_string(),
+            package: "docker".to_string(),
+            description: "preview test".to_string(),
+            payload,
+            success: true,
+            event_receiver_id: receiver_id,
+            owner_id: UserId::new(),
+            origin: EventOrigin::User,
+            resource_version: 1,
+            created_at: Utc::now() - Duration::minutes(minutes_ago),
+        })
+    }
+
+    fn request() -> SchemaPreviewRequest {
+        SchemaPreviewRequest {
+            schema: json!({
+                "required": ["build"],
+                "properties": {"build": {"type": "integer"}}
+            }),
+            start_time: None,
+            end_time: None,
+        }
+    }
+
+    async fn repository_with(
+        receiver_id: EventReceiverId,
+        payloads: &[JsonValue],
+    ) -> Arc<MockEventRepository> {
+        let repo = Arc::new(MockEventRepository::default());
+        for (i, payload) in payloads.iter().enumerate() {
+            repo.save(&event(receiver_id, i as i64 + 1, payload.clone()))
+                .await
+                .unwrap();
+        }
+        repo
+    }
+
+    #[tokio::test]
+    async fn test_mixed_events_are_summarized_inline() {
+        let receiver_id = EventReceiverId::new();
+        let repo = repository_with(
+            receiver_id,
+            &[
+                json!({"build": 1}),
+                json!({"build": "two"}),
+                json!({}),
+                json!({"build": 4}),
+                json!({"other": true}),
+            ],
+        )
+        .await;
+        repo.save(&event(EventReceiverId::new(), 1, json!({})))
+            .await
+            .unwrap();
+        let handler = SchemaPreviewHandler::new(repo).with_batch_size(2);
+
+        let report = match handler.preview(receiver_id, request()).await.unwrap() {
+            SchemaPreviewOutcome::Completed(report) => report,
+            other => panic!("expected inline report, got {:?}", other),
+        };
+
+        assert_eq!(report.scanned, 5);
+        assert_eq!(report.passing, 2);
+        assert_eq!(report.failing, 3);
+        assert!(!report.truncated);
+        assert_eq!(report.failing_event_ids.len(), 3);
+        assert_eq!(
+            report.top_violations,
+            vec![
+                ViolationCount {
+                    message: "Missing required field 'build'".to_string(),
+                    count: 2,
+                },
+                ViolationCount {
+                    message: "Field 'build' must be of type 'integer'".to_string(),
+                    count: 1,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_stops_at_cap() {
+        let receiver_id = EventReceiverId::new();
+        let repo = repository_with(receiver_id, &vec![json!({}); 8]).await;
+        let handler = SchemaPreviewHandler::new(repo)
+            .with_batch_size(3)
+            .with_max_scanned(5)
+            .with_sample_size(2);
+
+        let report = match handler.preview(receiver_id, request()).await.unwrap() {
+            SchemaPreviewOutcome::Completed(report) => report,
+            other => panic!("expected inline report, got {:?}", other),
+        };
+
+        assert_eq!(report.scanned, 5);
+        assert_eq!(report.failing, 5);
+        assert!(report.truncated);
+        assert_eq!(report.failing_event_ids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_schema_rejected() {
+        let handler = SchemaPreviewHandler::new(Arc::new(MockEventRepository::default()));
+        let mut invalid = request();
+        invalid.schema = json!(["build"]);
+
+        assert!(handler
+            .preview(EventReceiverId::new(), invalid)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_large_range_completes_in_background() {
+        let receiver_id = EventReceiverId::new();
+        let mut payloads = vec![json!({"build": 1}); 5];
+        payloads.extend(vec![json!({}); 2]);
+        let repo = repository_with(receiver_id, &payloads).await;
+        let handler = SchemaPreviewHandler::new(repo)
+            .with_batch_size(2)
+            .with_sync_threshold(2);
+
+        let job = match handler.preview(receiver_id, request()).await.unwrap() {
+            SchemaPreviewOutcome::Started(job) => job,
+            other => panic!("expected background job, got {:?}", other),
+        };
+        assert_eq!(job.status, SchemaPreviewJobStatus::Running);
+        assert_eq!(job.scanned, 2);
+        assert_eq!(job.event_receiver_id, receiver_id);
+
+        let mut finished = None;
+        for _ in 0..100 {
+            let polled = handler.job(&job.id).unwrap();
+            if polled.status != SchemaPreviewJobStatus::Running {
+                finished = Some(polled);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let finished = finished.expect("preview job did not finish");
+        assert_eq!(finished.status, SchemaPreviewJobStatus::Completed);
+        assert_eq!(finished.scanned, 7);
+        assert!(finished.finished_at.is_some());
+        let report = finished.report.unwrap();
+        assert_eq!(report.scanned, 7);
+        assert_eq!(report.passing, 5);
+        assert_eq!(report.failing, 2);
+        assert!(!report.truncated);
+        assert!(handler.job("unknown").is_none());
+    }
+}
@@ -0,0 +1,97 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/schema_resolver.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::schema_inheritance::{ResolvedSchema, SchemaSource};
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::error::Result;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Resolves receiver schemas with group default inheritance
+///
+/// Resolved schemas are cached by receiver fingerprint. A fingerprint
+/// changes whenever the receiver's own schema changes, but group defaults
+/// and membership are not part of it, so group handlers must call
+/// [`SchemaResolver::invalidate`] after those change.
+#[derive(Clone)]
+pub struct SchemaResolver {
+    group_repository: Arc<dyn EventReceiverGroupRepository>,
+    cache: Arc<RwLock<HashMap<String, ResolvedSchema>>>,
+}
+
+impl SchemaResolver {
+    /// Creates a new schema resolver
+    pub fn new(group_repository: Arc<dyn EventReceiverGroupRepository>) -> Self {
+        Self {
+            group_repository,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the effective schema for a receiver
+    pub async fn resolve(&self, receiver: &EventReceiver) -> Result<ResolvedSchema> {
+        // An explicit schema always wins; no group lookup needed
+        if receiver.has_own_schema() {
+            return Ok(ResolvedSchema::resolve(receiver, &[]));
+        }
+
+        if let Some(resolved) = self.cache.read().await.get(receiver.fingerprint()) {
+            return Ok(resolved.clone());
+        }
+
+        let groups = self
+            .group_repository
+            .find_by_event_receiver_id(receiver.id())
+            .await?;
+        let default_group = self
+            .group_repository
+            .find_default_schema_group(receiver.id())
+            .await?;
+        let resolved = ResolvedSchema::resolve_with_default(receiver, &groups, default_group);
+
+        if resolved.source() == SchemaSource::Ambiguous {
+            let group_ids: Vec<String> = resolved
+                .group_ids()
+                .iter()
+                .map(|id| id.to_string())
+                .collect();
+            warn!(
+                receiver_id = %receiver.id(),
+                groups = ?group_ids,
+                "Receiver belongs to groups with conflicting default schemas; no schema inherited"
+            );
+        }
+
+        self.cache
+            .write()
+            .await
+            .insert(receiver.fingerprint().to_string(), resolved.clone());
+
+        Ok(resolved)
+    }
+
+    /// Drops all cached resolutions
+    ///
+    /// Called when a group's default schema, enabled state, or membership,
+    /// or a receiver's default schema group changes, since any member
+    /// receiver may be affected.
+    pub async fn invalidate(&self) {
+        let mut cache = self.cache.write().await;
+        debug!(
+            entries = cache.len(),
+            "Invalidating schema resolution cache"
+        );
+        cache.clear();
+    }
+
+    /// Returns the number of cached resolutions
+    pub async fn cached_len(&self) -> usize {
+        self.cache.read().await.len()
+    }
+}
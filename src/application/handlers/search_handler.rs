@@ -0,0 +1,236 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/search_handler.rs
+
+use crate::domain::entities::search::{SearchQuery, SearchResourceType, SearchResults};
+use crate::domain::repositories::search_repo::{SearchRepository, SearchViewer};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+
+use std::sync::Arc;
+use tracing::debug;
+
+/// Number of results returned when the caller does not pick a limit
+pub const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Most results a search may return
+pub const MAX_SEARCH_LIMIT: usize = 50;
+
+/// Application service for full-text search across resource types
+#[derive(Clone)]
+pub struct SearchHandler {
+    repository: Arc<dyn SearchRepository>,
+}
+
+impl SearchHandler {
+    /// Creates a new search handler
+    pub fn new(repository: Arc<dyn SearchRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Searches `types` for resources `viewer` may read
+    ///
+    /// Returns at most `limit` hits across all types, exact name matches
+    /// first and then by rank, with the number of matches per type. An
+    /// empty `types` searches every type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the query cannot be parsed, contains
+    /// only stop words, or `limit` is outside 1 to [`MAX_SEARCH_LIMIT`].
+    pub async fn search(
+        &self,
+        text: &str,
+        types: &[SearchResourceType],
+        viewer: &SearchViewer,
+        limit: Option<usize>,
+    ) -> Result<SearchResults> {
+        let limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+        if limit == 0 || limit > MAX_SEARCH_LIMIT {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range").with("max", MAX_SEARCH_LIMIT),
+            }
+            .into());
+        }
+
+        let query = SearchQuery::parse(text)?;
+        if !self.repository.is_searchable(&query).await? {
+            return Err(DomainError::ValidationError {
+                field: "q".to_string(),
+                message: Message::new("validation.search_query_stop_words"),
+            }
+            .into());
+        }
+
+        let types = if types.is_empty() {
+            &SearchResourceType::ALL[..]
+        } else {
+            types
+        };
+
+        let mut results = SearchResults::default();
+        for &resource_type in types {
+            let matches = self
+                .repository
+                .search(
+                    resource_type,
+                    &query,
+                    viewer.visibility(resource_type),
+                    limit,
+                )
+                .await?;
+            results.counts.set(resource_type, matches.total);
+            results.hits.extend(matches.hits);
+        }
+
+        results.hits.sort_by(|a, b| a.cmp_relevance(b));
+        results.hits.truncate(limit);
+
+        debug!(
+            query = %query.text(),
+            hits = results.hits.len(),
+            receivers = results.counts.receivers,
+            events = results.counts.events,
+            groups = results.counts.groups,
+            "Search completed"
+        );
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::search::SearchHit;
+    use crate::domain::repositories::search_repo::{SearchMatches, SearchVisibility};
+    use crate::domain::value_objects::UserId;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    /// Returns canned hits and records the visibility of each search
+    #[derive(Default)]
+    struct CannedSearch {
+        hits: Vec<SearchHit>,
+        searches: Mutex<Vec<(SearchResourceType, SearchVisibility)>>,
+    }
+
+    #[async_trait]
+    impl SearchRepository for CannedSearch {
+        async fn is_searchable(&self, query: &SearchQuery) -> Result<bool> {
+            Ok(query.text() != "the and")
+        }
+
+        async fn search(
+            &self,
+            resource_type: SearchResourceType,
+            _query: &SearchQuery,
+            visibility: SearchVisibility,
+            limit: usize,
+        ) -> Result<SearchMatches> {
+            self.searches
+                .lock()
+                .unwrap()
+                .push((resource_type, visibility));
+            let hits: Vec<SearchHit> = self
+                .hits
+                .iter()
+                .filter(|hit| hit.resource_type == resource_type)
+                .cloned()
+                .collect();
+            Ok(SearchMatches {
+                total: hits.len() as u64,
+                hits: hits.into_iter().take(limit).collect(),
+            })
+        }
+    }
+
+    fn hit(resource_type: SearchResourceType, name: &str, rank: f32) -> SearchHit {
+        SearchHit {
+            resource_type,
+            id: name.to_string(),
+            name: name.to_string(),
+            snippet: format!("<mark>{}</mark>", name),
+            rank,
+            exact_name: name == "deploy",
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merges_types_by_relevance_and_counts_each() {
+        let repository = Arc::new(CannedSearch {
+            hits: vec![
+                hit(SearchResourceType::Receiver, "deploy-bot", 0.8),
+                hit(SearchResourceType::Receiver, "deploy", 0.1),
+                hit(SearchResourceType::Event, "deploy.finished", 0.9),
+                hit(SearchResourceType::Event, "deploy.started", 0.3),
+                hit(SearchResourceType::Group, "deployers", 0.5),
+            ],
+            ..Default::default()
+        });
+        let handler = SearchHandler::new(repository);
+        let viewer = SearchViewer::new(UserId::new());
+
+        let results = handler
+            .search("deploy", &[], &viewer, Some(3))
+            .await
+            .unwrap();
+
+        let names: Vec<&str> = results.hits.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, ["deploy", "deploy.finished", "deploy-bot"]);
+        assert_eq!(results.counts.receivers, 2);
+        assert_eq!(results.counts.events, 2);
+        assert_eq!(results.counts.groups, 1);
+    }
+
+    #[tokio::test]
+    async fn test_searches_each_type_with_the_viewers_visibility() {
+        let repository = Arc::new(CannedSearch::default());
+        let handler = SearchHandler::new(repository.clone());
+        let user_id = UserId::new();
+        let viewer = SearchViewer::new(user_id).with_unrestricted(SearchResourceType::Event);
+
+        handler
+            .search(
+                "release",
+                &[SearchResourceType::Receiver, SearchResourceType::Event],
+                &viewer,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *repository.searches.lock().unwrap(),
+            [
+                (
+                    SearchResourceType::Receiver,
+                    SearchVisibility::OwnedOrMember(user_id)
+                ),
+                (SearchResourceType::Event, SearchVisibility::All),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejects_degenerate_queries_and_limits() {
+        let handler = SearchHandler::new(Arc::new(CannedSearch::default()));
+        let viewer = SearchViewer::new(UserId::new());
+
+        for (text, limit) in [
+            ("x", None),
+            ("the and", None),
+            ("deploy", Some(0)),
+            ("deploy", Some(MAX_SEARCH_LIMIT + 1)),
+        ] {
+            assert!(
+                handler.search(text, &[], &viewer, limit).await.is_err(),
+                "expected {:?} with limit {:?} to be rejected",
+                text,
+                limit
+            );
+        }
+    }
+}
@@ -0,0 +1,354 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/system_events.rs
+
+use crate::domain::entities::event::{CreateEventParams, Event, EventOrigin};
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::domain::value_objects::{EventReceiverId, UserId};
+use crate::error::DomainError;
+use crate::infrastructure::PrometheusMetrics;
+
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::error;
+
+/// Event name published when an event receiver is created
+pub const RECEIVER_CREATED_EVENT: &str = "xzepr.event.receiver.created";
+
+/// Event name published when an event receiver is changed
+pub const RECEIVER_UPDATED_EVENT: &str = "xzepr.event.receiver.updated";
+
+/// Event name published when an event receiver is deleted
+pub const RECEIVER_DELETED_EVENT: &str = "xzepr.event.receiver.deleted";
+
+/// Event name published when the hygiene report finds an inactive receiver
+pub const RECEIVER_STALE_EVENT: &str = "xzepr.event.receiver.stale";
+
+/// Event name published when a receiver is disabled by its failure rate
+pub const RECEIVER_AUTO_DISABLED_EVENT: &str = "xzepr.event.receiver.auto_disabled";
+
+/// Event name published when an event receiver group is created
+pub const GROUP_CREATED_EVENT: &str = "xzepr.event.receiver.group.created";
+
+/// Event name published when an event receiver group is changed
+pub const GROUP_UPDATED_EVENT: &str = "xzepr.event.receiver.group.updated";
+
+/// Event name published when an event receiver group is deleted
+pub const GROUP_DELETED_EVENT: &str = "xzepr.event.receiver.group.deleted";
+
+/// Event name published the first time every required receiver of a group
+/// reports a release
+pub const GROUP_RELEASE_COMPLETED_EVENT: &str = "xzepr.event.receiver.group.release_completed";
+
+/// Event name published when a delivery is given up on after its last retry
+pub const DELIVERY_FAILED_EVENT: &str = "xzepr.event.delivery.failed";
+
+/// Fields every change moves forward, left out of changed-field lists
+const BOOKKEEPING_FIELDS: [&str; 2] = ["resource_version", "updated_at"];
+
+/// Constructor used to build system events
+type SystemEventConstructor = fn(CreateEventParams) -> Result<Event, DomainError>;
+
+/// Builds, stores, and names the events xzepr emits about itself
+///
+/// Every system event is created with [`EventOrigin::System`], is named
+/// `xzepr.<entity>.<action>`, and targets the configured system receiver,
+/// falling back to the receiver the event is about. Handlers share one
+/// factory so these rules live in one place.
+#[derive(Clone)]
+pub struct SystemEventFactory {
+    constructor: SystemEventConstructor,
+    system_receiver: Option<EventReceiverId>,
+    store: Option<Arc<dyn EventRepository>>,
+}
+
+impl Default for SystemEventFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemEventFactory {
+    /// Creates a factory that builds events without storing them
+    pub fn new() -> Self {
+        Self {
+            constructor: Event::new_system,
+            system_receiver: None,
+            store: None,
+        }
+    }
+
+    /// Targets every system event at `receiver_id` instead of the receiver
+    /// the event is about
+    pub fn with_system_receiver(mut self, receiver_id: EventReceiverId) -> Self {
+        self.system_receiver = Some(receiver_id);
+        self
+    }
+
+    /// Stores every recorded system event so admins can query them
+    pub fn with_store(mut self, store: Arc<dyn EventRepository>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Replaces the event constructor to exercise the error path
+    #[cfg(test)]
+    pub(crate) fn with_constructor(mut self, constructor: SystemEventConstructor) -> Self {
+        self.constructor = constructor;
+        self
+    }
+
+    /// Builds a system event with the fixed system metadata
+    ///
+    /// `subject_receiver` is the receiver the event is about. Failures are
+    /// returned as `DomainError::SystemEventConstruction` so the caller
+    /// decides how to proceed instead of panicking inside a request.
+    pub fn build(
+        &self,
+        name: &str,
+        description: String,
+        payload: serde_json::Value,
+        subject_receiver: EventReceiverId,
+        owner_id: UserId,
+    ) -> Result<Event, DomainError> {
+        let construction_error = |reason: String| DomainError::SystemEventConstruction {
+            event_type: name.to_string(),
+            reason,
+        };
+
+        if !is_system_event_name(name) {
+            return Err(construction_error(
+                "Name must follow xzepr.<entity>.<action>".to_string(),
+            ));
+        }
+
+        let event = (self.constructor)(CreateEventParams {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            release: "system".to_string(),
+            platform_id: "xzepr".to_string(),
+            package: "xzepr.system".to_string(),
+            description,
+            payload,
+            success: true,
+            receiver_id: self.system_receiver.unwrap_or(subject_receiver),
+            owner_id,
+        })
+        .map_err(|e| construction_error(e.to_string()))?;
+
+        if event.origin() != EventOrigin::System {
+            return Err(construction_error(
+                "Event origin must be system".to_string(),
+            ));
+        }
+
+        Ok(event)
+    }
+
+    /// Stores `event` if a store is configured
+    ///
+    /// The write that triggered the event has already succeeded, so a
+    /// failure is logged rather than returned.
+    pub async fn record(&self, event: &Event) {
+        let Some(store) = &self.store else {
+            return;
+        };
+
+        if let Err(e) = store.save(event).await {
+            error!(
+                event_type = %event.name(),
+                event_id = %event.id(),
+                error = %e,
+                "Failed to store system event"
+            );
+        }
+    }
+}
+
+/// Returns true if `name` follows `xzepr.<entity>.<action>`
+///
+/// The entity may span several segments, as in
+/// `xzepr.event.receiver.created`. Segments are lowercase ASCII letters,
+/// digits, and underscores.
+pub fn is_system_event_name(name: &str) -> bool {
+    let Some(rest) = name.strip_prefix("xzepr.") else {
+        return false;
+    };
+    let segments: Vec<&str> = rest.split('.').collect();
+
+    segments.len() >= 2
+        && segments.iter().all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+        })
+}
+
+/// Returns the names of the top-level fields that differ between two
+/// states of a resource, sorted by name
+///
+/// `resource_version` and `updated_at` change on every update and are not
+/// reported.
+pub fn changed_fields<T: Serialize>(before: &T, after: &T) -> Vec<String> {
+    let (Ok(Value::Object(before)), Ok(Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+
+    after
+        .iter()
+        .filter(|(field, value)| {
+            !BOOKKEEPING_FIELDS.contains(&field.as_str()) && before.get(*field) != Some(*value)
+        })
+        .map(|(field, _)| field.clone())
+        .collect()
+}
+
+/// Logs and counts a system event that could not be constructed
+///
+/// The primary write has already succeeded when this is called, so the
+/// failure is reported but never propagated to the client.
+pub(crate) fn report_system_event_failure(
+    metrics: Option<&PrometheusMetrics>,
+    event_type: &str,
+    resource_id: &str,
+    err: &DomainError,
+) {
+    error!(
+        event_type = %event_type,
+        resource_id = %resource_id,
+        error = %err,
+        "Failed to construct system event, skipping Kafka publish"
+    );
+
+    if let Some(metrics) = metrics {
+        metrics.record_system_event_failure(event_type);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_system_event_success() {
+        let receiver_id = EventReceiverId::new();
+        let event = SystemEventFactory::new()
+            .build(
+                RECEIVER_CREATED_EVENT,
+                "created".to_string(),
+                json!({"receiver_id": "r1"}),
+                receiver_id,
+                UserId::new(),
+            )
+            .unwrap();
+
+        assert_eq!(event.name(), RECEIVER_CREATED_EVENT);
+        assert_eq!(event.release(), "system");
+        assert_eq!(event.origin(), EventOrigin::System);
+        assert_eq!(event.event_receiver_id(), receiver_id);
+    }
+
+    #[test]
+    fn test_build_targets_system_receiver() {
+        let system_receiver = EventReceiverId::new();
+        let event = SystemEventFactory::new()
+            .with_system_receiver(system_receiver)
+            .build(
+                GROUP_CREATED_EVENT,
+                "created".to_string(),
+                json!({}),
+                EventReceiverId::new(),
+                UserId::new(),
+            )
+            .unwrap();
+
+        assert_eq!(event.event_receiver_id(), system_receiver);
+    }
+
+    #[test]
+    fn test_build_system_event_failure_is_typed() {
+        let factory = SystemEventFactory::new();
+        let build = |name: &str, payload| {
+            factory.build(
+                name,
+                "created".to_string(),
+                payload,
+                EventReceiverId::new(),
+                UserId::new(),
+            )
+        };
+
+        for (name, payload) in [
+            (GROUP_CREATED_EVENT, json!("not an object")),
+            ("group.created", json!({})),
+            ("xzepr.created", json!({})),
+            ("xzepr.Group.created", json!({})),
+            ("xzepr.group..created", json!({})),
+        ] {
+            match build(name, payload) {
+                Err(DomainError::SystemEventConstruction { event_type, .. }) => {
+                    assert_eq!(event_type, name);
+                }
+                other => panic!("expected SystemEventConstruction, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_system_event_names() {
+        assert!(is_system_event_name(RECEIVER_CREATED_EVENT));
+        assert!(is_system_event_name(RECEIVER_UPDATED_EVENT));
+        assert!(is_system_event_name(RECEIVER_DELETED_EVENT));
+        assert!(is_system_event_name(RECEIVER_STALE_EVENT));
+        assert!(is_system_event_name(GROUP_CREATED_EVENT));
+        assert!(is_system_event_name(GROUP_UPDATED_EVENT));
+        assert!(is_system_event_name(GROUP_DELETED_EVENT));
+        assert!(is_system_event_name(GROUP_RELEASE_COMPLETED_EVENT));
+        assert!(is_system_event_name(DELIVERY_FAILED_EVENT));
+        assert!(is_system_event_name("xzepr.schema_preview.completed"));
+        assert!(!is_system_event_name("xzepr"));
+        assert!(!is_system_event_name("xzepr.group"));
+        assert!(!is_system_event_name("xzepr.group.created."));
+        assert!(!is_system_event_name("build.finished"));
+    }
+
+    #[test]
+    fn test_report_system_event_failure_counts() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        let err = DomainError::SystemEventConstruction {
+            event_type: GROUP_CREATED_EVENT.to_string(),
+            reason: "test".to_string(),
+        };
+
+        report_system_event_failure(Some(&metrics), GROUP_CREATED_EVENT, "g1", &err);
+        report_system_event_failure(None, GROUP_CREATED_EVENT, "g1", &err);
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains("xzepr_system_event_failures_total"));
+    }
+
+    #[test]
+    fn test_changed_fields_ignores_bookkeeping() {
+        let before = json!({
+            "name": "builds",
+            "sample_rate": null,
+            "resource_version": 1,
+            "updated_at": "2025-01-01T00:00:00Z",
+        });
+        let after = json!({
+            "name": "builds",
+            "sample_rate": 0.5,
+            "resource_version": 2,
+            "updated_at": "2025-01-02T00:00:00Z",
+        });
+
+        assert_eq!(changed_fields(&before, &after), ["sample_rate"]);
+        assert!(changed_fields(&after, &after).is_empty());
+    }
+}
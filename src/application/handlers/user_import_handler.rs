@@ -0,0 +1,1049 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/user_import_handler.rs
+
+use crate::auth::rbac::Role;
+use crate::domain::entities::user::{hash_password, AuthProvider, User};
+use crate::domain::entities::user_import::{parse_user_import, UserImportRow};
+use crate::domain::entities::user_invitation::{
+    hash_invitation_token, UserInvitation, DEFAULT_INVITATION_TTL_HOURS, MIN_PASSWORD_LENGTH,
+};
+use crate::domain::repositories::user_invitation_repo::UserInvitationRepository;
+use crate::domain::repositories::user_repo::UserRepository;
+use crate::domain::value_objects::UserId;
+use crate::error::{AuthError, DomainError, Error, Result};
+use crate::i18n::Message;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::jobs::{Job, JobReport, JobRunner, JobSchedule};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tracing::{error, info};
+
+/// Name of the background job that runs large imports
+pub const USER_IMPORT_JOB_NAME: &str = "user_import";
+
+/// Default number of users created per transaction
+pub const DEFAULT_USER_IMPORT_BATCH_SIZE: usize = 50;
+
+/// Default number of rows imported inline before an import moves to a job
+pub const DEFAULT_USER_IMPORT_SYNC_THRESHOLD: usize = 100;
+
+/// Default cap on the rows a single import file may have
+pub const DEFAULT_USER_IMPORT_MAX_ROWS: usize = 10_000;
+
+/// How long finished jobs stay available for polling
+const FINISHED_JOB_TTL_MINUTES: i64 = 60;
+
+/// How often the import job looks for queued imports it was not woken for
+const USER_IMPORT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// What happened to one row of an import
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserImportRowStatus {
+    /// The user was created
+    Created,
+    /// The user would be created; dry runs only
+    WouldCreate,
+    /// A user with the username, email, or external id already exists
+    Skipped,
+    /// The row has errors and was not imported
+    Invalid,
+    /// The row was valid but its batch could not be saved
+    Failed,
+}
+
+impl UserImportRowStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserImportRowStatus::Created => "created",
+            UserImportRowStatus::WouldCreate => "would_create",
+            UserImportRowStatus::Skipped => "skipped",
+            UserImportRowStatus::Invalid => "invalid",
+            UserImportRowStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Outcome of one row of an import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserImportRowResult {
+    /// Line the row starts on; the header is line 1
+    pub line: usize,
+    pub username: String,
+    pub email: String,
+    pub status: UserImportRowStatus,
+    /// Roles assigned, or that would be assigned
+    pub roles: Vec<Role>,
+    /// Id of the created user
+    pub user_id: Option<UserId>,
+    /// Invitation token of a created local account, only set on imports
+    /// answered inline; never set for OIDC-linked accounts
+    pub invitation_token: Option<String>,
+    /// Why the row was skipped, invalid, or failed
+    pub messages: Vec<String>,
+}
+
+/// What an import created, or would create on a dry run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserImportReport {
+    pub dry_run: bool,
+    /// Rows in the file, header excluded
+    pub total: usize,
+    /// Users created, or that would be created on a dry run
+    pub created: usize,
+    pub skipped: usize,
+    pub invalid: usize,
+    pub failed: usize,
+    /// One result per row, in file order
+    pub rows: Vec<UserImportRowResult>,
+}
+
+impl UserImportReport {
+    fn push(&mut self, result: UserImportRowResult) {
+        match result.status {
+            UserImportRowStatus::Created | UserImportRowStatus::WouldCreate => self.created += 1,
+            UserImportRowStatus::Skipped => self.skipped += 1,
+            UserImportRowStatus::Invalid => self.invalid += 1,
+            UserImportRowStatus::Failed => self.failed += 1,
+        }
+        self.rows.push(result);
+    }
+}
+
+/// State of a background import job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserImportJobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+impl UserImportJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserImportJobStatus::Running => "running",
+            UserImportJobStatus::Completed => "completed",
+            UserImportJobStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Snapshot of a background import job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserImportJob {
+    pub id: String,
+    pub dry_run: bool,
+    pub status: UserImportJobStatus,
+    /// Rows processed so far
+    pub processed: usize,
+    /// Rows in the file
+    pub total: usize,
+    /// Final report once the job completes
+    pub report: Option<UserImportReport>,
+    /// Failure message if the job failed
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Result of starting an import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserImportOutcome {
+    /// The file was small enough to import inline
+    Completed(UserImportReport),
+    /// The import continues in a background job
+    Started(UserImportJob),
+}
+
+/// An import waiting for the background job
+struct PendingImport {
+    job_id: String,
+    rows: Vec<UserImportRow>,
+    dry_run: bool,
+    actor: String,
+}
+
+/// Application service importing users and their roles from a CSV file
+///
+/// Every row is validated before anything is written. Rows whose
+/// username, email, or external id already belongs to a user are skipped,
+/// so re-running an import after a partial failure is safe. Valid rows are
+/// created in batches, each batch in one transaction. Every account gets a
+/// random password nobody knows: OIDC-linked accounts sign in through the
+/// identity provider, and local accounts choose their password by
+/// accepting an invitation.
+///
+/// Files over the sync threshold are queued for the `user_import` job of
+/// the [`JobRunner`] and polled by id. Their reports carry no invitation
+/// tokens, so no secret is kept in job state; admins issue invitations
+/// for those accounts with [`invite`](Self::invite).
+#[derive(Clone)]
+pub struct UserImportHandler {
+    user_repository: Arc<dyn UserRepository>,
+    invitation_repository: Arc<dyn UserInvitationRepository>,
+    // Both locks are held only for map and queue operations and for the
+    // field assignments of `update_job`, none of which panic, so they are
+    // never poisoned and unwrapping them cannot fail
+    jobs: Arc<Mutex<HashMap<String, UserImportJob>>>,
+    pending: Arc<Mutex<VecDeque<PendingImport>>>,
+    job_runner: Option<Arc<JobRunner>>,
+    batch_size: usize,
+    sync_threshold: usize,
+    max_rows: usize,
+    audit_logger: Arc<AuditLogger>,
+}
+
+impl UserImportHandler {
+    /// Creates a new import handler using the default limits
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        invitation_repository: Arc<dyn UserInvitationRepository>,
+    ) -> Self {
+        Self {
+            user_repository,
+            invitation_repository,
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            pending: Arc::new(Mutex::new(VecDeque::new())),
+            job_runner: None,
+            batch_size: DEFAULT_USER_IMPORT_BATCH_SIZE,
+            sync_threshold: DEFAULT_USER_IMPORT_SYNC_THRESHOLD,
+            max_rows: DEFAULT_USER_IMPORT_MAX_ROWS,
+            audit_logger: Arc::new(AuditLogger::new()),
+        }
+    }
+
+    /// Sets the number of users created per transaction
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets how many rows are imported inline before moving to a job
+    pub fn with_sync_threshold(mut self, sync_threshold: usize) -> Self {
+        self.sync_threshold = sync_threshold;
+        self
+    }
+
+    /// Sets the most rows a single file may have
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Uses `audit_logger` to record imports
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Runs large imports on `job_runner`
+    ///
+    /// The runner must have a clone of this handler registered as its
+    /// [`USER_IMPORT_JOB_NAME`] job. Without a runner, files over the sync
+    /// threshold are rejected.
+    pub fn with_job_runner(mut self, job_runner: Arc<JobRunner>) -> Self {
+        self.job_runner = Some(job_runner);
+        self
+    }
+
+    /// Validates `csv` and, unless `dry_run`, creates the users it lists
+    ///
+    /// A dry run reports what every row would do without creating
+    /// anything. Every import, including dry runs and failures, is
+    /// audit-logged with the ids of the users it created.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if the file cannot be read as an import
+    /// file, a storage error if looking up existing users fails, and
+    /// `Internal` if a large file cannot be handed to the import job.
+    pub async fn import(&self, csv: &str, dry_run: bool, actor: &str) -> Result<UserImportOutcome> {
+        let rows = parse_user_import(csv, self.max_rows)?;
+
+        if rows.len() <= self.sync_threshold {
+            let mut report = new_report(dry_run, rows.len());
+            let result = self.run(rows, &mut report, actor, true, |_| {}).await;
+            self.audit(
+                &report,
+                actor,
+                result.as_ref().err().map(ToString::to_string),
+            );
+            result?;
+            return Ok(UserImportOutcome::Completed(report));
+        }
+
+        let job_runner = self.job_runner.as_ref().ok_or_else(|| Error::Internal {
+            message: "User import job is not running".to_string(),
+        })?;
+
+        let now = Utc::now();
+        let job = UserImportJob {
+            id: ulid::Ulid::new().to_string(),
+            dry_run,
+            status: UserImportJobStatus::Running,
+            processed: 0,
+            total: rows.len(),
+            report: None,
+            error: None,
+            started_at: now,
+            finished_at: None,
+        };
+        {
+            // Never poisoned; see the field
+            let mut jobs = self.jobs.lock().unwrap();
+            let expiry = now - Duration::minutes(FINISHED_JOB_TTL_MINUTES);
+            jobs.retain(|_, job| job.finished_at.is_none_or(|finished| finished > expiry));
+            jobs.insert(job.id.clone(), job.clone());
+        }
+        // Never poisoned; see the field
+        self.pending.lock().unwrap().push_back(PendingImport {
+            job_id: job.id.clone(),
+            rows,
+            dry_run,
+            actor: actor.to_string(),
+        });
+
+        if let Err(e) = job_runner.wake(USER_IMPORT_JOB_NAME) {
+            // Neither lock is ever poisoned; see the fields
+            self.pending
+                .lock()
+                .unwrap()
+                .retain(|pending| pending.job_id != job.id);
+            self.jobs.lock().unwrap().remove(&job.id);
+            return Err(Error::Internal {
+                message: format!("User import job cannot run: {}", e),
+            });
+        }
+
+        info!(
+            job_id = %job.id,
+            rows = job.total,
+            dry_run,
+            "User import continuing in background"
+        );
+
+        Ok(UserImportOutcome::Started(job))
+    }
+
+    /// Returns the current state of an import job, if it is still known
+    pub fn job(&self, job_id: &str) -> Option<UserImportJob> {
+        // Never poisoned; see the field
+        self.jobs.lock().unwrap().get(job_id).cloned()
+    }
+
+    /// Issues a new invitation for the local account `user_id`
+    ///
+    /// Replaces any earlier invitation of the user, so a lost or expired
+    /// token can be reissued. Returns the invitation and its token; only
+    /// the token's hash is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the user does not exist and
+    /// `BusinessRuleViolation` if the account is linked to an OIDC subject.
+    pub async fn invite(&self, user_id: &UserId, actor: &str) -> Result<(UserInvitation, String)> {
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| Error::NotFound {
+                resource: format!("User {}", user_id),
+            })?;
+        if !matches!(user.auth_provider, AuthProvider::Local) {
+            return Err(DomainError::BusinessRuleViolation {
+                rule: Message::new("rule.invitation_local_only"),
+            }
+            .into());
+        }
+
+        let (invitation, token) = UserInvitation::issue(
+            user.id,
+            actor,
+            Duration::hours(DEFAULT_INVITATION_TTL_HOURS),
+        );
+        self.invitation_repository
+            .save_invitations(std::slice::from_ref(&invitation))
+            .await?;
+
+        info!(actor = %actor, user_id = %user.id, "Issued user invitation");
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(actor)
+                .action(AuditAction::UserUpdate)
+                .resource(format!("user:{}", user.id))
+                .outcome(AuditOutcome::Success)
+                .add_metadata("change", "invitation_issued")
+                .add_metadata("expires_at", invitation.expires_at.to_rfc3339())
+                .build(),
+        );
+
+        Ok((invitation, token))
+    }
+
+    /// Sets the password of the account invited with `token`
+    ///
+    /// The invitation is used up whether or not it has expired, so a token
+    /// works at most once. Returns the updated user.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ValidationError` if `password` is shorter than
+    /// [`MIN_PASSWORD_LENGTH`], leaving the invitation unused, and
+    /// `InvalidToken` if the token is unknown, used, or expired.
+    pub async fn accept_invitation(&self, token: &str, password: &str) -> Result<User> {
+        if password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Err(DomainError::ValidationError {
+                field: "password".to_string(),
+                message: Message::new("validation.password_too_short")
+                    .with("min", MIN_PASSWORD_LENGTH),
+            }
+            .into());
+        }
+
+        let invitation = self
+            .invitation_repository
+            .take_invitation(&hash_invitation_token(token))
+            .await?
+            .filter(|invitation| !invitation.is_expired(Utc::now()))
+            .ok_or(AuthError::InvalidToken)?;
+        let mut user = self
+            .user_repository
+            .find_by_id(&invitation.user_id)
+            .await?
+            .ok_or(AuthError::InvalidToken)?;
+
+        let password = password.to_string();
+        let password_hash = tokio::task::spawn_blocking(move || hash_password(&password))
+            .await
+            .map_err(|e| Error::Internal {
+                message: format!("Password hashing task failed: {}", e),
+            })??;
+        user.password_hash = Some(password_hash);
+        user.updated_at = Utc::now();
+        let user = self.user_repository.update(user).await?;
+
+        info!(user_id = %user.id, "Accepted user invitation");
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(user.id.to_string())
+                .action(AuditAction::UserUpdate)
+                .resource(format!("user:{}", user.id))
+                .outcome(AuditOutcome::Success)
+                .add_metadata("change", "invitation_accepted")
+                .add_metadata("invited_by", invitation.created_by)
+                .build(),
+        );
+
+        Ok(user)
+    }
+
+    /// Applies `update` to a job under the lock; `update` must not panic
+    fn update_job(&self, job_id: &str, update: impl FnOnce(&mut UserImportJob)) {
+        // Never poisoned while callers only assign fields; see the field
+        if let Some(job) = self.jobs.lock().unwrap().get_mut(job_id) {
+            update(job);
+        }
+    }
+
+    /// Runs a queued import and records its outcome in the import job
+    async fn run_pending(&self, pending: PendingImport) {
+        let PendingImport {
+            job_id,
+            rows,
+            dry_run,
+            actor,
+        } = pending;
+
+        let mut report = new_report(dry_run, rows.len());
+        let progress = |processed| self.update_job(&job_id, |job| job.processed = processed);
+        let result = self.run(rows, &mut report, &actor, false, progress).await;
+        self.audit(
+            &report,
+            &actor,
+            result.as_ref().err().map(ToString::to_string),
+        );
+
+        self.update_job(&job_id, |job| {
+            job.finished_at = Some(Utc::now());
+            job.processed = report.rows.len();
+            match result {
+                Ok(()) => {
+                    job.status = UserImportJobStatus::Completed;
+                    job.report = Some(report);
+                }
+                Err(e) => {
+                    error!(job_id = %job.id, "User import job failed: {}", e);
+                    job.status = UserImportJobStatus::Failed;
+                    job.error = Some(e.to_string());
+                }
+            }
+        });
+    }
+
+    /// Imports `rows` batch by batch, recording each row in `report`
+    ///
+    /// With `invite`, created local accounts get an invitation whose token
+    /// is put in their row of the report.
+    async fn run(
+        &self,
+        rows: Vec<UserImportRow>,
+        report: &mut UserImportReport,
+        actor: &str,
+        invite: bool,
+        progress: impl Fn(usize),
+    ) -> Result<()> {
+        for chunk in rows.chunks(self.batch_size) {
+            let mut results = Vec::with_capacity(chunk.len());
+            let mut to_create = Vec::new();
+            for row in chunk {
+                let mut result = UserImportRowResult {
+                    line: row.line,
+                    username: row.username.clone(),
+                    email: row.email.clone(),
+                    status: UserImportRowStatus::Invalid,
+                    roles: row.roles.clone(),
+                    user_id: None,
+                    invitation_token: None,
+                    messages: row.errors.clone(),
+                };
+                if row.is_valid() {
+                    let existing = self.existing_user(row).await?;
+                    if !existing.is_empty() {
+                        result.status = UserImportRowStatus::Skipped;
+                        result.messages = existing;
+                    } else if report.dry_run {
+                        result.status = UserImportRowStatus::WouldCreate;
+                    } else {
+                        result.status = UserImportRowStatus::Created;
+                        to_create.push((results.len(), row.clone()));
+                    }
+                }
+                results.push(result);
+            }
+
+            if !to_create.is_empty() {
+                let (indexes, rows): (Vec<usize>, Vec<UserImportRow>) =
+                    to_create.into_iter().unzip();
+                let users = new_users(rows).await?;
+
+                let mut invitations = Vec::new();
+                let mut tokens = Vec::new();
+                for (&index, user) in indexes.iter().zip(&users) {
+                    results[index].user_id = Some(user.id);
+                    if invite && matches!(user.auth_provider, AuthProvider::Local) {
+                        let (invitation, token) = UserInvitation::issue(
+                            user.id,
+                            actor,
+                            Duration::hours(DEFAULT_INVITATION_TTL_HOURS),
+                        );
+                        invitations.push(invitation);
+                        tokens.push((index, token));
+                    }
+                }
+
+                // The whole batch is one transaction, so a failure undoes
+                // every row of it that would have been created
+                if let Err(e) = self.user_repository.create_all(users).await {
+                    for result in results
+                        .iter_mut()
+                        .filter(|result| result.status == UserImportRowStatus::Created)
+                    {
+                        error!(
+                            actor = %actor,
+                            line = result.line,
+                            "Failed to import user {}: {}",
+                            result.username,
+                            e
+                        );
+                        result.status = UserImportRowStatus::Failed;
+                        result.user_id = None;
+                        result.messages = vec![e.to_string()];
+                    }
+                } else if !invitations.is_empty() {
+                    match self
+                        .invitation_repository
+                        .save_invitations(&invitations)
+                        .await
+                    {
+                        Ok(()) => {
+                            for (index, token) in tokens {
+                                results[index].invitation_token = Some(token);
+                            }
+                        }
+                        Err(e) => {
+                            error!(actor = %actor, "Failed to save user invitations: {}", e);
+                            for (index, _) in tokens {
+                                results[index]
+                                    .messages
+                                    .push(format!("Invitation was not saved: {}", e));
+                            }
+                        }
+                    }
+                }
+            }
+
+            for result in results {
+                report.push(result);
+            }
+            progress(report.rows.len());
+        }
+
+        info!(
+            actor = %actor,
+            dry_run = report.dry_run,
+            created = report.created,
+            skipped = report.skipped,
+            invalid = report.invalid,
+            failed = report.failed,
+            "Imported users"
+        );
+        Ok(())
+    }
+
+    /// Describes the existing users a row collides with
+    async fn existing_user(&self, row: &UserImportRow) -> Result<Vec<String>> {
+        let mut collisions = Vec::new();
+        if self
+            .user_repository
+            .find_by_username(&row.username)
+            .await?
+            .is_some()
+        {
+            collisions.push(format!("Username '{}' already exists", row.username));
+        }
+        if let Some(user) = self.user_repository.find_by_email(&row.email).await? {
+            collisions.push(format!(
+                "Email '{}' already belongs to user '{}'",
+                row.email, user.username
+            ));
+        }
+        if let Some(subject) = &row.external_id {
+            if let Some(user) = self.user_repository.find_by_oidc_subject(subject).await? {
+                collisions.push(format!(
+                    "External id '{}' is already linked to user '{}'",
+                    subject, user.username
+                ));
+            }
+        }
+        Ok(collisions)
+    }
+
+    fn audit(&self, report: &UserImportReport, actor: &str, failure: Option<String>) {
+        let created: Vec<String> = report
+            .rows
+            .iter()
+            .filter_map(|row| row.user_id.map(|id| id.to_string()))
+            .collect();
+
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(actor)
+                .action(AuditAction::UserCreate)
+                .resource("user_import")
+                .outcome(if failure.is_some() {
+                    AuditOutcome::Error
+                } else {
+                    AuditOutcome::Success
+                })
+                .add_metadata("dry_run", report.dry_run.to_string())
+                .add_metadata("created", created.join(","))
+                .add_metadata("skipped", report.skipped.to_string())
+                .add_metadata("invalid", report.invalid.to_string())
+                .add_metadata("failed", report.failed.to_string())
+                .error_message_opt(failure)
+                .build(),
+        );
+    }
+}
+
+/// Runs one queued import per run
+#[async_trait]
+impl Job for UserImportHandler {
+    fn name(&self) -> &str {
+        USER_IMPORT_JOB_NAME
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::Every(USER_IMPORT_POLL_INTERVAL)
+    }
+
+    async fn run(&self) -> Result<JobReport> {
+        // Never poisoned; see UserImportHandler::pending
+        let next = self.pending.lock().unwrap().pop_front();
+        let Some(pending) = next else {
+            return Ok(JobReport::default());
+        };
+
+        let processed = pending.rows.len();
+        self.run_pending(pending).await;
+        Ok(JobReport {
+            processed,
+            // Never poisoned; see UserImportHandler::pending
+            more_pending: !self.pending.lock().unwrap().is_empty(),
+        })
+    }
+}
+
+fn new_report(dry_run: bool, total: usize) -> UserImportReport {
+    UserImportReport {
+        dry_run,
+        total,
+        ..UserImportReport::default()
+    }
+}
+
+/// Builds the users valid rows describe
+///
+/// Each account's password is hashed with Argon2, which takes long enough
+/// to stall the runtime, so the batch is built on the blocking pool.
+async fn new_users(rows: Vec<UserImportRow>) -> Result<Vec<User>> {
+    tokio::task::spawn_blocking(move || rows.iter().map(new_user).collect())
+        .await
+        .map_err(|e| Error::Internal {
+            message: format!("Password hashing task failed: {}", e),
+        })?
+}
+
+/// Builds the user a valid row describes, with a random password nobody
+/// knows
+fn new_user(row: &UserImportRow) -> Result<User> {
+    let mut user = match &row.external_id {
+        Some(subject) => {
+            let mut user = User::new_oidc(row.username.clone(), row.email.clone(), subject.clone());
+            let password_hash = hash_password(&random_secret()).map_err(|e| {
+                DomainError::BusinessRuleViolation {
+                    rule: Message::new("rule.password_hashing").with("reason", e),
+                }
+            })?;
+            user.password_hash = Some(password_hash);
+            user
+        }
+        None => User::new_local(row.username.clone(), row.email.clone(), random_secret())?,
+    };
+    user.roles = row.roles.clone();
+    user.enabled = row.enabled;
+    Ok(user)
+}
+
+fn random_secret() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    hex::encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::user_invitation::INVITATION_TOKEN_PREFIX;
+    use crate::infrastructure::config::JobsConfig;
+    use crate::infrastructure::memory::{InMemoryUserInvitationRepository, InMemoryUserRepository};
+    use tokio::sync::watch;
+
+    const CSV: &str = "username,email,roles,enabled,external_id\n\
+                       alice,alice@example.com,admin;event_viewer,true,\n\
+                       bob,bob@example.com,event_manager,false,kc-bob\n\
+                       carol,carol@example.com,,,\n";
+
+    fn handler() -> (UserImportHandler, Arc<InMemoryUserRepository>) {
+        let repository = Arc::new(InMemoryUserRepository::default());
+        let invitations = Arc::new(InMemoryUserInvitationRepository::default());
+        (
+            UserImportHandler::new(repository.clone(), invitations),
+            repository,
+        )
+    }
+
+    async fn import(handler: &UserImportHandler, csv: &str, dry_run: bool) -> UserImportReport {
+        match handler.import(csv, dry_run, "admin-1").await.unwrap() {
+            UserImportOutcome::Completed(report) => report,
+            UserImportOutcome::Started(job) => panic!("unexpected job {}", job.id),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_errors_per_row() {
+        let (handler, repository) = handler();
+        let report = import(
+            &handler,
+            "username,email,roles\n\
+             alice,alice@example.com,admin\n\
+             bob,bob-at-example.com,user\n\
+             carol,carol@example.com,owner\n\
+             alice,dave@example.com,user\n",
+            true,
+        )
+        .await;
+
+        assert!(report.dry_run);
+        assert_eq!((report.created, report.invalid), (1, 3));
+        let statuses: Vec<_> = report.rows.iter().map(|row| row.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                UserImportRowStatus::WouldCreate,
+                UserImportRowStatus::Invalid,
+                UserImportRowStatus::Invalid,
+                UserImportRowStatus::Invalid,
+            ]
+        );
+        assert_eq!(report.rows[1].line, 3);
+        assert_eq!(
+            report.rows[1].messages,
+            vec!["Malformed email 'bob-at-example.com'"]
+        );
+        assert_eq!(report.rows[2].messages, vec!["Unknown role 'owner'"]);
+        assert_eq!(
+            report.rows[3].messages,
+            vec!["Duplicate username 'alice'; first used on line 2"]
+        );
+        assert!(report.rows.iter().all(|row| row.user_id.is_none()));
+        assert_eq!(repository.count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_apply_assigns_roles_and_flags() {
+        let (handler, repository) = handler();
+        let report = import(&handler, CSV, false).await;
+        assert_eq!(report.created, 3);
+
+        let alice = repository.find_by_username("alice").await.unwrap().unwrap();
+        assert_eq!(alice.roles, vec![Role::Admin, Role::EventViewer]);
+        assert!(alice.enabled);
+        assert_eq!(Some(alice.id), report.rows[0].user_id);
+
+        let bob = repository.find_by_username("bob").await.unwrap().unwrap();
+        assert_eq!(bob.roles, vec![Role::EventManager]);
+        assert!(!bob.enabled);
+        assert!(matches!(
+            bob.auth_provider,
+            AuthProvider::Keycloak { ref subject } if subject == "kc-bob"
+        ));
+
+        let carol = repository.find_by_username("carol").await.unwrap().unwrap();
+        assert_eq!(carol.roles, vec![Role::User]);
+    }
+
+    #[tokio::test]
+    async fn test_invitation_tokens_only_for_local_accounts() {
+        let (handler, repository) = handler();
+        let report = import(&handler, CSV, false).await;
+
+        let alice_token = report.rows[0].invitation_token.clone().unwrap();
+        assert!(alice_token.starts_with(INVITATION_TOKEN_PREFIX));
+        assert!(report.rows[1].invitation_token.is_none());
+        assert!(report.rows[2].invitation_token.is_some());
+        assert_ne!(
+            report.rows[0].invitation_token,
+            report.rows[2].invitation_token
+        );
+
+        // Every account gets a password nobody knows; the token is not it
+        for username in ["alice", "bob"] {
+            let user = repository
+                .find_by_username(username)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(user.password_hash.is_some());
+            assert!(!user.verify_password(&alice_token).unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accept_invitation_sets_password_once() {
+        let (handler, repository) = handler();
+        let report = import(&handler, CSV, false).await;
+        let token = report.rows[0].invitation_token.clone().unwrap();
+
+        let short = handler.accept_invitation(&token, "too-short").await;
+        assert!(matches!(
+            short,
+            Err(Error::Domain(DomainError::ValidationError { .. }))
+        ));
+
+        let alice = handler
+            .accept_invitation(&token, "correct horse battery")
+            .await
+            .unwrap();
+        assert_eq!(Some(alice.id), report.rows[0].user_id);
+        let stored = repository.find_by_username("alice").await.unwrap().unwrap();
+        assert!(stored.verify_password("correct horse battery").unwrap());
+
+        let reused = handler
+            .accept_invitation(&token, "another long password")
+            .await;
+        assert!(matches!(reused, Err(Error::Auth(AuthError::InvalidToken))));
+    }
+
+    #[tokio::test]
+    async fn test_accept_invitation_rejects_expired_token() {
+        let repository = Arc::new(InMemoryUserRepository::default());
+        let invitations = Arc::new(InMemoryUserInvitationRepository::default());
+        let handler = UserImportHandler::new(repository.clone(), invitations.clone());
+        let user = User::new_local(
+            "dave".to_string(),
+            "dave@example.com".to_string(),
+            random_secret(),
+        )
+        .unwrap();
+        repository.create(user.clone()).await.unwrap();
+
+        let (invitation, token) = UserInvitation::issue(user.id, "admin-1", Duration::zero());
+        invitations.save_invitations(&[invitation]).await.unwrap();
+
+        let result = handler
+            .accept_invitation(&token, "correct horse battery")
+            .await;
+        assert!(matches!(result, Err(Error::Auth(AuthError::InvalidToken))));
+    }
+
+    #[tokio::test]
+    async fn test_invite_replaces_earlier_invitation() {
+        let (handler, repository) = handler();
+        let report = import(&handler, CSV, false).await;
+        let first = report.rows[0].invitation_token.clone().unwrap();
+        let alice = report.rows[0].user_id.unwrap();
+
+        let (invitation, second) = handler.invite(&alice, "admin-2").await.unwrap();
+        assert_eq!(invitation.user_id, alice);
+        assert_eq!(invitation.created_by, "admin-2");
+        assert_eq!(invitation.token_hash, hash_invitation_token(&second));
+
+        let stale = handler
+            .accept_invitation(&first, "correct horse battery")
+            .await;
+        assert!(matches!(stale, Err(Error::Auth(AuthError::InvalidToken))));
+        handler
+            .accept_invitation(&second, "correct horse battery")
+            .await
+            .unwrap();
+
+        let bob = repository.find_by_username("bob").await.unwrap().unwrap();
+        assert!(matches!(
+            handler.invite(&bob.id, "admin-2").await,
+            Err(Error::Domain(DomainError::BusinessRuleViolation { .. }))
+        ));
+        assert!(matches!(
+            handler.invite(&UserId::new(), "admin-2").await,
+            Err(Error::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rerun_skips_existing_users() {
+        let (handler, repository) = handler();
+        let first = import(&handler, CSV, false).await;
+        assert_eq!(first.created, 3);
+
+        let rerun = import(
+            &handler,
+            "username,email,external_id\n\
+             alice,alice@example.com,\n\
+             robert,bob@example.com,kc-bob\n\
+             dave,dave@example.com,\n",
+            false,
+        )
+        .await;
+
+        assert_eq!((rerun.created, rerun.skipped), (1, 2));
+        assert_eq!(rerun.rows[0].status, UserImportRowStatus::Skipped);
+        assert_eq!(
+            rerun.rows[0].messages,
+            vec![
+                "Username 'alice' already exists",
+                "Email 'alice@example.com' already belongs to user 'alice'",
+            ]
+        );
+        assert_eq!(
+            rerun.rows[1].messages,
+            vec![
+                "Email 'bob@example.com' already belongs to user 'bob'",
+                "External id 'kc-bob' is already linked to user 'bob'",
+            ]
+        );
+        assert!(rerun.rows[0].invitation_token.is_none());
+        assert_eq!(rerun.rows[2].status, UserImportRowStatus::Created);
+        assert_eq!(repository.count().await.unwrap(), 4);
+
+        let again = import(&handler, CSV, false).await;
+        assert_eq!((again.created, again.skipped), (0, 3));
+        assert_eq!(repository.count().await.unwrap(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_large_import_runs_as_job() {
+        let (handler, repository) = handler();
+        let handler = handler.with_sync_threshold(1).with_batch_size(2);
+        let runner = Arc::new(
+            JobRunner::new(&JobsConfig {
+                stagger_seconds: 0,
+                ..JobsConfig::default()
+            })
+            .register(Arc::new(handler.clone())),
+        );
+        let handler = handler.with_job_runner(runner.clone());
+        let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+        runner.start(shutdown_rx);
+
+        let job = match handler.import(CSV, false, "admin-1").await.unwrap() {
+            UserImportOutcome::Started(job) => job,
+            UserImportOutcome::Completed(_) => panic!("expected a job"),
+        };
+        assert_eq!(job.status, UserImportJobStatus::Running);
+        assert_eq!(job.total, 3);
+
+        let mut polled = handler.job(&job.id).unwrap();
+        for _ in 0..600 {
+            if polled.status != UserImportJobStatus::Running {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            polled = handler.job(&job.id).unwrap();
+        }
+        assert_eq!(polled.status, UserImportJobStatus::Completed);
+        assert_eq!(polled.processed, 3);
+        let report = polled.report.unwrap();
+        assert_eq!(report.created, 3);
+        // Job state never holds invitation tokens
+        assert!(report.rows.iter().all(|row| row.invitation_token.is_none()));
+        assert_eq!(repository.count().await.unwrap(), 3);
+        assert!(handler.job("unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_large_import_requires_job_runner() {
+        let (handler, repository) = handler();
+        let handler = handler.with_sync_threshold(1);
+        assert!(matches!(
+            handler.import(CSV, false, "admin-1").await,
+            Err(Error::Internal { .. })
+        ));
+
+        let runner = Arc::new(
+            JobRunner::new(&JobsConfig {
+                disabled: vec![USER_IMPORT_JOB_NAME.to_string()],
+                ..JobsConfig::default()
+            })
+            .register(Arc::new(handler.clone())),
+        );
+        let handler = handler.with_job_runner(runner);
+        assert!(matches!(
+            handler.import(CSV, false, "admin-1").await,
+            Err(Error::Internal { .. })
+        ));
+        assert!(handler.pending.lock().unwrap().is_empty());
+        assert!(handler.jobs.lock().unwrap().is_empty());
+        assert_eq!(repository.count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unreadable_file() {
+        let (handler, _) = handler();
+        let result = handler.import("name,mail\n", true, "admin-1").await;
+        assert!(result.is_err());
+    }
+}
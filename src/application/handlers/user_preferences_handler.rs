@@ -0,0 +1,116 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/user_preferences_handler.rs
+
+use crate::domain::entities::user_preferences::UserPreferences;
+use crate::domain::repositories::user_preferences_repo::UserPreferencesRepository;
+use crate::error::Result;
+
+use serde_json::Value as JsonValue;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Application service for user preferences
+///
+/// Preferences are read from the repository on every call; nothing is
+/// cached here, so an update is visible to the user's next request.
+#[derive(Clone)]
+pub struct UserPreferencesHandler {
+    repository: Arc<dyn UserPreferencesRepository>,
+}
+
+impl UserPreferencesHandler {
+    /// Creates a new user preferences handler
+    pub fn new(repository: Arc<dyn UserPreferencesRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Gets a user's preferences
+    pub async fn get_preferences(&self, user_id: &str) -> Result<UserPreferences> {
+        self.repository.find_preferences(user_id).await
+    }
+
+    /// Merges a preference document into a user's stored preferences
+    ///
+    /// Returns the full preferences after the merge.
+    pub async fn update_preferences(
+        &self,
+        user_id: &str,
+        document: &JsonValue,
+    ) -> Result<UserPreferences> {
+        let mut preferences = self.repository.find_preferences(user_id).await?;
+
+        if let Err(e) = preferences.merge(document) {
+            warn!(user_id = %user_id, error = %e, "Rejected preference update");
+            return Err(e.into());
+        }
+
+        self.repository
+            .save_preferences(user_id, &preferences)
+            .await?;
+
+        info!(user_id = %user_id, "Updated user preferences");
+        Ok(preferences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockUserPreferencesRepository {
+        preferences: Mutex<HashMap<String, UserPreferences>>,
+    }
+
+    #[async_trait]
+    impl UserPreferencesRepository for MockUserPreferencesRepository {
+        async fn find_preferences(&self, user_id: &str) -> Result<UserPreferences> {
+            let preferences = self.preferences.lock().unwrap();
+            Ok(preferences.get(user_id).cloned().unwrap_or_default())
+        }
+
+        async fn save_preferences(
+            &self,
+            user_id: &str,
+            preferences: &UserPreferences,
+        ) -> Result<()> {
+            let mut stored = self.preferences.lock().unwrap();
+            stored.insert(user_id.to_string(), preferences.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_merges_and_rejects_invalid() {
+        let handler =
+            UserPreferencesHandler::new(Arc::new(MockUserPreferencesRepository::default()));
+
+        handler
+            .update_preferences("user-1", &json!({"default_page_size": 20}))
+            .await
+            .unwrap();
+        let merged = handler
+            .update_preferences("user-1", &json!({"export_timezone": "+02:00"}))
+            .await
+            .unwrap();
+        assert_eq!(merged.default_page_size(), Some(20));
+        assert!(merged.export_timezone().is_some());
+
+        // Unknown keys are rejected and nothing is stored
+        assert!(handler
+            .update_preferences("user-1", &json!({"default_page_size": 5, "theme": "dark"}))
+            .await
+            .is_err());
+        let stored = handler.get_preferences("user-1").await.unwrap();
+        assert_eq!(stored.default_page_size(), Some(20));
+
+        // Other users are unaffected
+        assert!(handler.get_preferences("user-2").await.unwrap().is_empty());
+    }
+}
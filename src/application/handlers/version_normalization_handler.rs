@@ -0,0 +1,299 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/application/handlers/version_normalization_handler.rs
+
+use crate::domain::repositories::version_normalization_repo::{
+    VersionNormalizationRepository, VersionedResource,
+};
+use crate::domain::value_objects::{StoredVersion, Version};
+use crate::error::Result;
+
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Stored version rewritten into canonical form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRewrite {
+    pub resource: VersionedResource,
+    pub from: String,
+    pub to: String,
+    pub rows: u64,
+}
+
+/// Stored version left unchanged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedVersion {
+    pub resource: VersionedResource,
+    pub version: String,
+    pub rows: u64,
+    pub reason: String,
+}
+
+/// Outcome of a normalization run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionNormalizationReport {
+    pub rewritten: Vec<VersionRewrite>,
+    pub skipped: Vec<SkippedVersion>,
+}
+
+impl VersionNormalizationReport {
+    /// Returns the number of rows whose version was rewritten
+    pub fn rows_rewritten(&self) -> u64 {
+        self.rewritten.iter().map(|rewrite| rewrite.rows).sum()
+    }
+
+    /// Returns the number of rows left with a non-canonical version
+    pub fn rows_skipped(&self) -> u64 {
+        self.skipped.iter().map(|skipped| skipped.rows).sum()
+    }
+}
+
+/// Application service normalizing versions stored before they were
+/// validated
+///
+/// Versions that parse leniently (`v1.0`, `1.0.0-rc1 `) are rewritten to
+/// their canonical semantic version. Anything else is left untouched and
+/// logged, so an operator can fix it by hand. Running it again once every
+/// version is canonical changes nothing.
+#[derive(Clone)]
+pub struct VersionNormalizationHandler {
+    repository: Arc<dyn VersionNormalizationRepository>,
+}
+
+impl VersionNormalizationHandler {
+    /// Creates a new version normalization handler
+    pub fn new(repository: Arc<dyn VersionNormalizationRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Normalizes the stored versions of every resource
+    ///
+    /// A rewrite that fails, e.g. because the recomputed fingerprint of a
+    /// receiver collides with another receiver, is reported as skipped and
+    /// does not stop the run.
+    pub async fn run(&self) -> Result<VersionNormalizationReport> {
+        let mut report = VersionNormalizationReport::default();
+
+        for resource in VersionedResource::ALL {
+            for stored in self.repository.stored_versions(resource).await? {
+                match Version::classify_stored(&stored.version) {
+                    StoredVersion::Canonical => {}
+                    StoredVersion::Rewrite(to) => {
+                        match self
+                            .repository
+                            .rewrite_version(resource, &stored.version, &to)
+                            .await
+                        {
+                            Ok(rows) => {
+                                info!(
+                                    resource = %resource,
+                                    from = %stored.version,
+                                    to = %to,
+                                    rows,
+                                    "Normalized stored version"
+                                );
+                                report.rewritten.push(VersionRewrite {
+                                    resource,
+                                    from: stored.version,
+                                    to,
+                                    rows,
+                                });
+                            }
+                            Err(e) => {
+                                warn!(
+                                    resource = %resource,
+                                    version = %stored.version,
+                                    error = %e,
+                                    "Failed to normalize stored version; left unchanged"
+                                );
+                                report.skipped.push(SkippedVersion {
+                                    resource,
+                                    version: stored.version,
+                                    rows: stored.rows,
+                                    reason: e.to_string(),
+                                });
+                            }
+                        }
+                    }
+                    StoredVersion::Unparseable => {
+                        warn!(
+                            resource = %resource,
+                            version = %stored.version,
+                            rows = stored.rows,
+                            "Stored version is not a semantic version; left unchanged"
+                        );
+                        report.skipped.push(SkippedVersion {
+                            resource,
+                            version: stored.version,
+                            rows: stored.rows,
+                            reason: "not a semantic version".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repositories::version_normalization_repo::StoredVersionCount;
+    use crate::error::Error;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Stores the version of every row, keyed by resource
+    #[derive(Default)]
+    struct MockVersionRepository {
+        rows: Mutex<HashMap<VersionedResource, Vec<String>>>,
+        failing: Option<String>,
+    }
+
+    impl MockVersionRepository {
+        fn with_rows(resource: VersionedResource, versions: &[&str]) -> Self {
+            let repository = Self::default();
+            repository.rows.lock().unwrap().insert(
+                resource,
+                versions.iter().map(|version| version.to_string()).collect(),
+            );
+            repository
+        }
+
+        fn versions(&self, resource: VersionedResource) -> Vec<String> {
+            self.rows
+                .lock()
+                .unwrap()
+                .get(&resource)
+                .cloned()
+                .unwrap_or_default()
+        }
+    }
+
+    #[async_trait]
+    impl VersionNormalizationRepository for MockVersionRepository {
+        async fn stored_versions(
+            &self,
+            resource: VersionedResource,
+        ) -> Result<Vec<StoredVersionCount>> {
+            let mut counts: Vec<StoredVersionCount> = Vec::new();
+            for version in self.versions(resource) {
+                match counts.iter_mut().find(|count| count.version == version) {
+                    Some(count) => count.rows += 1,
+                    None => counts.push(StoredVersionCount { version, rows: 1 }),
+                }
+            }
+            Ok(counts)
+        }
+
+        async fn rewrite_version(
+            &self,
+            resource: VersionedResource,
+            from: &str,
+            to: &str,
+        ) -> Result<u64> {
+            if self.failing.as_deref() == Some(from) {
+                return Err(Error::Internal {
+                    message: "fingerprint conflict".to_string(),
+                });
+            }
+            let mut rows = self.rows.lock().unwrap();
+            let mut changed = 0;
+            for version in rows.entry(resource).or_default().iter_mut() {
+                if version == from {
+                    *version = to.to_string();
+                    changed += 1;
+                }
+            }
+            Ok(changed)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_normalizes_and_reports_unparseable() {
+        let repository = Arc::new(MockVersionRepository::with_rows(
+            VersionedResource::EventReceiver,
+            &["1.0", "v1.0.0", "1.0.0-rc1 ", "2.0.0", "latest", "1.0"],
+        ));
+        let handler = VersionNormalizationHandler::new(repository.clone());
+
+        let report = handler.run().await.unwrap();
+
+        assert_eq!(
+            repository.versions(VersionedResource::EventReceiver),
+            vec!["1.0.0", "1.0.0", "1.0.0-rc1", "2.0.0", "latest", "1.0.0"]
+        );
+        assert_eq!(report.rows_rewritten(), 4);
+        assert_eq!(
+            report.skipped,
+            vec![SkippedVersion {
+                resource: VersionedResource::EventReceiver,
+                version: "latest".to_string(),
+                rows: 1,
+                reason: "not a semantic version".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_is_idempotent() {
+        let repository = Arc::new(MockVersionRepository::with_rows(
+            VersionedResource::Event,
+            &["v2", "2.0.0", "nightly"],
+        ));
+        let handler = VersionNormalizationHandler::new(repository.clone());
+
+        let first = handler.run().await.unwrap();
+        assert_eq!(first.rows_rewritten(), 1);
+
+        let second = handler.run().await.unwrap();
+        assert!(second.rewritten.is_empty());
+        assert_eq!(second.rows_skipped(), 1);
+        assert_eq!(
+            repository.versions(VersionedResource::Event),
+            vec!["2.0.0", "2.0.0", "nightly"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_leaves_normalized_data_alone() {
+        let repository = Arc::new(MockVersionRepository::with_rows(
+            VersionedResource::EventReceiverGroup,
+            &["1.0.0", "1.2.3-rc.1", "3.0.0+build.4"],
+        ));
+        let handler = VersionNormalizationHandler::new(repository.clone());
+
+        let report = handler.run().await.unwrap();
+
+        assert_eq!(report, VersionNormalizationReport::default());
+        assert_eq!(
+            repository.versions(VersionedResource::EventReceiverGroup),
+            vec!["1.0.0", "1.2.3-rc.1", "3.0.0+build.4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_rewrite_is_skipped() {
+        let repository = MockVersionRepository {
+            failing: Some("v1".to_string()),
+            ..MockVersionRepository::with_rows(VersionedResource::EventReceiver, &["v1", "v2"])
+        };
+        let repository = Arc::new(repository);
+        let handler = VersionNormalizationHandler::new(repository.clone());
+
+        let report = handler.run().await.unwrap();
+
+        assert_eq!(report.rows_rewritten(), 1);
+        assert_eq!(report.skipped.len(), 1);
+        assert_eq!(report.skipped[0].version, "v1");
+        assert!(report.skipped[0].reason.contains("fingerprint conflict"));
+        assert_eq!(
+            repository.versions(VersionedResource::EventReceiver),
+            vec!["v1", "2.0.0"]
+        );
+    }
+}
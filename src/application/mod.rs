@@ -3,6 +3,11 @@
 
 // Generated mod file
 
+pub mod authorization;
+pub mod demo;
+pub mod domain_events;
 pub mod handlers;
 
+pub use authorization::{AuthorizationService, ProtectedResource, ResourceAction};
+pub use domain_events::{DomainEvent, DomainEventBus, DomainEventSubscriber};
 pub use handlers::{EventReceiverGroupHandler, EventReceiverHandler};
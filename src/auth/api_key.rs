@@ -6,14 +6,117 @@
 // Original line: 0
 
 // src/auth/api_key.rs
+use crate::auth::api_key_usage::{ApiKeyOutcome, ApiKeyUsageTracker};
 use crate::domain::entities::user::User;
-use crate::domain::value_objects::{ApiKeyId, UserId};
-use crate::error::AuthError;
-use chrono::{DateTime, Utc};
+use crate::domain::value_objects::{ApiKeyId, EventReceiverGroupId, EventReceiverId, UserId};
+use crate::error::{AuthError, DomainError};
+use crate::i18n::Message;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::metrics::PrometheusMetrics;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+/// Grace period for the replaced secret when a rotation does not set one
+pub const DEFAULT_ROTATION_GRACE: Duration = Duration::hours(24);
+
+/// Unknown key hashes remembered before the negative cache is cleared
+const MAX_UNKNOWN_KEY_HASHES: usize = 10_000;
+
+/// Which of a key's secrets authenticated a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeySecret {
+    /// The secret issued by the latest rotation, or at creation
+    Current,
+    /// The secret replaced by the latest rotation, during its grace period
+    Previous,
+}
+
+impl ApiKeySecret {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeySecret::Current => "current",
+            ApiKeySecret::Previous => "previous",
+        }
+    }
+}
+
+impl fmt::Display for ApiKeySecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiKeySecret {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "current" => Ok(ApiKeySecret::Current),
+            "previous" => Ok(ApiKeySecret::Previous),
+            other => Err(format!("Unknown API key secret: {}", other)),
+        }
+    }
+}
+
+/// Receivers a key may post events to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyScope {
+    /// Every receiver the key's user may post to
+    Unscoped,
+    /// A single receiver
+    Receiver(EventReceiverId),
+    /// The receivers in a group at the time of each request
+    Group(EventReceiverGroupId),
+}
+
+impl ApiKeyScope {
+    /// Builds the scope from its optional parts
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error when both a receiver and a group are set;
+    /// a key has at most one scope.
+    pub fn from_parts(
+        receiver_id: Option<EventReceiverId>,
+        group_id: Option<EventReceiverGroupId>,
+    ) -> Result<Self, DomainError> {
+        match (receiver_id, group_id) {
+            (Some(_), Some(_)) => Err(DomainError::ValidationError {
+                field: "receiver_id".to_string(),
+                message: Message::new("validation.api_key_scope_exclusive"),
+            }),
+            (Some(receiver_id), None) => Ok(ApiKeyScope::Receiver(receiver_id)),
+            (None, Some(group_id)) => Ok(ApiKeyScope::Group(group_id)),
+            (None, None) => Ok(ApiKeyScope::Unscoped),
+        }
+    }
+
+    /// Returns the scoped receiver, if any
+    pub fn receiver_id(&self) -> Option<EventReceiverId> {
+        match self {
+            ApiKeyScope::Receiver(receiver_id) => Some(*receiver_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the scoped group, if any
+    pub fn group_id(&self) -> Option<EventReceiverGroupId> {
+        match self {
+            ApiKeyScope::Group(group_id) => Some(*group_id),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiKey {
@@ -25,6 +128,29 @@ pub struct ApiKey {
     pub enabled: bool,
     pub created_at: DateTime<Utc>,
     pub last_used_at: Option<DateTime<Utc>>,
+    /// Hash of the secret replaced by the latest rotation
+    #[serde(default)]
+    pub previous_hash: Option<String>,
+    /// End of the grace period in which the previous secret still works
+    #[serde(default)]
+    pub previous_expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub rotated_at: Option<DateTime<Utc>>,
+    /// Secret used by the last authenticated request
+    #[serde(default)]
+    pub last_used_with: Option<ApiKeySecret>,
+    /// When the last expiry warning was emitted for this key
+    #[serde(default)]
+    pub last_expiry_warning_at: Option<DateTime<Utc>>,
+    /// Only receiver this key may post events to
+    #[serde(default)]
+    pub receiver_id: Option<EventReceiverId>,
+    /// Group whose receivers this key may post events to
+    #[serde(default)]
+    pub group_id: Option<EventReceiverGroupId>,
+    /// Client address of the last recorded use
+    #[serde(default)]
+    pub last_used_ip: Option<String>,
 }
 
 impl ApiKey {
@@ -43,11 +169,45 @@ impl ApiKey {
     pub fn expires_at(&self) -> Option<DateTime<Utc>> {
         self.expires_at
     }
+
+    /// Returns which receivers the key may post events to
+    pub fn scope(&self) -> ApiKeyScope {
+        match (self.receiver_id, self.group_id) {
+            (Some(receiver_id), _) => ApiKeyScope::Receiver(receiver_id),
+            (None, Some(group_id)) => ApiKeyScope::Group(group_id),
+            (None, None) => ApiKeyScope::Unscoped,
+        }
+    }
+
+    /// Returns which secret hashes to `hash` and still authenticates at `now`
+    ///
+    /// The previous secret only matches until `previous_expires_at`.
+    pub fn secret_for(&self, hash: &str, now: DateTime<Utc>) -> Option<ApiKeySecret> {
+        if self.key_hash == hash {
+            return Some(ApiKeySecret::Current);
+        }
+        match (&self.previous_hash, self.previous_expires_at) {
+            (Some(previous), Some(until)) if previous == hash && now < until => {
+                Some(ApiKeySecret::Previous)
+            }
+            _ => None,
+        }
+    }
 }
 
 #[async_trait::async_trait]
 pub trait UserRepository: Send + Sync {
     async fn find_by_id(&self, id: UserId) -> Result<Option<User>, AuthError>;
+    /// Finds the users with the given ids; unknown ids are skipped
+    async fn find_by_ids(&self, ids: &[UserId]) -> Result<Vec<User>, AuthError> {
+        let mut users = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(user) = self.find_by_id(*id).await? {
+                users.push(user);
+            }
+        }
+        Ok(users)
+    }
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, AuthError>;
     async fn save(&self, user: &User) -> Result<(), AuthError>;
     async fn find_all(&self) -> Result<Vec<User>, AuthError>;
@@ -66,15 +226,68 @@ pub trait UserRepository: Send + Sync {
 #[async_trait::async_trait]
 pub trait ApiKeyRepository: Send + Sync {
     async fn save(&self, api_key: &ApiKey) -> Result<(), AuthError>;
+    async fn find_by_id(&self, id: ApiKeyId) -> Result<Option<ApiKey>, AuthError>;
+    /// Finds the key whose current or previous secret hashes to `hash`
+    ///
+    /// The previous secret's grace period is not checked here.
     async fn find_by_hash(&self, hash: &str) -> Result<Option<ApiKey>, AuthError>;
-    async fn update_last_used(&self, id: ApiKeyId) -> Result<(), AuthError>;
+    /// Records a use of the key and which of its secrets was presented
+    async fn update_last_used(&self, id: ApiKeyId, secret: ApiKeySecret) -> Result<(), AuthError>;
+    /// Records a use at `at` from `ip`
+    ///
+    /// Repositories that keep neither fall back to
+    /// [`Self::update_last_used`].
+    async fn record_last_use(
+        &self,
+        id: ApiKeyId,
+        secret: ApiKeySecret,
+        _at: DateTime<Utc>,
+        _ip: Option<&str>,
+    ) -> Result<(), AuthError> {
+        self.update_last_used(id, secret).await
+    }
     async fn find_by_user_id(&self, user_id: UserId) -> Result<Vec<ApiKey>, AuthError>;
+    /// Finds the keys scoped to a group, newest first
+    async fn find_by_group_id(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<ApiKey>, AuthError>;
+    /// Finds the keys scoped to a receiver, newest first
+    async fn find_by_receiver_id(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Vec<ApiKey>, AuthError>;
     async fn revoke(&self, id: ApiKeyId) -> Result<(), AuthError>;
+    /// Finds enabled keys expiring after `from` and no later than `until`
+    async fn find_expiring(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<ApiKey>, AuthError>;
+    async fn record_expiry_warning(&self, id: ApiKeyId, at: DateTime<Utc>)
+        -> Result<(), AuthError>;
 }
 
 pub struct ApiKeyService {
     user_repo: Arc<dyn UserRepository>,
     api_key_repo: Arc<dyn ApiKeyRepository>,
+    rotation_grace: Duration,
+    audit_logger: Arc<AuditLogger>,
+    negative_cache_ttl: std::time::Duration,
+    /// Hashes no key matched, and when that was found out
+    unknown_hashes: Mutex<HashMap<String, Instant>>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    usage: Option<ApiKeyUsageTracker>,
+    stale_after: Option<Duration>,
+}
+
+/// Where a key was presented, for usage tracking
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeyRequest {
+    /// Matched route of the request
+    pub route: String,
+    /// Client address of the request
+    pub ip: Option<String>,
 }
 
 impl ApiKeyService {
@@ -82,6 +295,14 @@ impl ApiKeyService {
         self.api_key_repo.find_by_user_id(*user_id).await
     }
 
+    /// Lists the keys scoped to `group_id`
+    pub async fn list_group_keys(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<ApiKey>, AuthError> {
+        self.api_key_repo.find_by_group_id(group_id).await
+    }
+
     pub async fn revoke_key(&self, key_id: ApiKeyId) -> Result<(), AuthError> {
         self.api_key_repo.revoke(key_id).await
     }
@@ -92,13 +313,163 @@ impl ApiKeyService {
         Self {
             user_repo,
             api_key_repo,
+            rotation_grace: DEFAULT_ROTATION_GRACE,
+            audit_logger: Arc::new(AuditLogger::new()),
+            negative_cache_ttl: std::time::Duration::ZERO,
+            unknown_hashes: Mutex::new(HashMap::new()),
+            metrics: None,
+            usage: None,
+            stale_after: None,
+        }
+    }
+
+    /// Sets how long a key no stored key matches is rejected without a
+    /// lookup
+    ///
+    /// Off by default. Only hashes are remembered, never the presented
+    /// keys. Keys issued or rotated through this service are never
+    /// rejected by a stale entry.
+    pub fn with_negative_cache_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.negative_cache_ttl = ttl;
+        self
+    }
+
+    /// Records negative cache hits and misses in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Buffers last uses and request outcomes in `tracker`
+    ///
+    /// Without a tracker every authentication writes the last use
+    /// directly and outcomes are not counted.
+    pub fn with_usage_tracker(mut self, tracker: ApiKeyUsageTracker) -> Self {
+        self.usage = Some(tracker);
+        self
+    }
+
+    /// Returns the usage tracker, if one is configured
+    pub fn usage_tracker(&self) -> Option<&ApiKeyUsageTracker> {
+        self.usage.as_ref()
+    }
+
+    /// Flags enabled keys unused for longer than `after` as stale
+    pub fn with_stale_after(mut self, after: Duration) -> Self {
+        self.stale_after = Some(after);
+        self
+    }
+
+    /// Returns whether an enabled key has gone unused for longer than the
+    /// stale period at `now`
+    ///
+    /// Keys never used count from their creation.
+    pub fn is_stale(&self, api_key: &ApiKey, now: DateTime<Utc>) -> bool {
+        self.stale_after.is_some_and(|after| {
+            api_key.enabled && now - api_key.last_used_at.unwrap_or(api_key.created_at) > after
+        })
+    }
+
+    /// Sets how long a replaced secret keeps working after a rotation
+    pub fn with_rotation_grace(mut self, grace: Duration) -> Self {
+        self.rotation_grace = grace;
+        self
+    }
+
+    /// Uses `audit_logger` to record rotations
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Returns the grace period used when a rotation does not set one
+    pub fn rotation_grace(&self) -> Duration {
+        self.rotation_grace
+    }
+
+    pub async fn get_key(&self, key_id: ApiKeyId) -> Result<ApiKey, AuthError> {
+        self.api_key_repo
+            .find_by_id(key_id)
+            .await?
+            .ok_or(AuthError::ApiKeyNotFound)
+    }
+
+    /// Issues a new secret for the key, keeping the current one valid for
+    /// `grace`, or the configured grace period when `None`
+    ///
+    /// Only one previous secret is kept, so rotating again ends the grace
+    /// period of the one before. A zero grace period invalidates the old
+    /// secret immediately. Returns the new plaintext secret, which is not
+    /// stored.
+    pub async fn rotate_key(
+        &self,
+        key_id: ApiKeyId,
+        grace: Option<Duration>,
+    ) -> Result<(String, ApiKey), AuthError> {
+        let mut api_key = self.get_key(key_id).await?;
+        if !api_key.enabled {
+            return Err(AuthError::ApiKeyDisabled);
+        }
+
+        let now = Utc::now();
+        if api_key
+            .expires_at
+            .is_some_and(|expires_at| now > expires_at)
+        {
+            return Err(AuthError::ApiKeyExpired);
+        }
+
+        let grace = grace.unwrap_or(self.rotation_grace);
+        let key = generate_random_key();
+        let previous_hash = std::mem::replace(&mut api_key.key_hash, hash_api_key(&key));
+        if grace > Duration::zero() {
+            api_key.previous_hash = Some(previous_hash);
+            api_key.previous_expires_at = Some(now + grace);
+        } else {
+            api_key.previous_hash = None;
+            api_key.previous_expires_at = None;
+        }
+        api_key.rotated_at = Some(now);
+
+        self.save_issued(&api_key).await?;
+
+        info!(
+            key_id = %api_key.id,
+            previous_expires_at = ?api_key.previous_expires_at,
+            "Rotated API key"
+        );
+        let mut audit = AuditEvent::builder()
+            .user_id(api_key.user_id.to_string())
+            .action(AuditAction::ApiKeyRotate)
+            .resource(format!("api_key:{}", api_key.id))
+            .outcome(AuditOutcome::Success);
+        if let Some(until) = api_key.previous_expires_at {
+            audit = audit.add_metadata(
+                "previous_expires_at",
+                until.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            );
         }
+        self.audit_logger.log_event(audit.build());
+
+        Ok((key, api_key))
     }
     pub async fn generate_api_key(
         &self,
         user_id: UserId,
         name: String,
         expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(String, ApiKey), AuthError> {
+        self.generate_scoped_api_key(user_id, name, expires_at, ApiKeyScope::Unscoped)
+            .await
+    }
+
+    /// Issues a key that may only post events within `scope`
+    pub async fn generate_scoped_api_key(
+        &self,
+        user_id: UserId,
+        name: String,
+        expires_at: Option<DateTime<Utc>>,
+        scope: ApiKeyScope,
     ) -> Result<(String, ApiKey), AuthError> {
         // Generate random key
         let key = generate_random_key();
@@ -114,22 +485,107 @@ impl ApiKeyService {
             enabled: true,
             created_at: Utc::now(),
             last_used_at: None,
+            previous_hash: None,
+            previous_expires_at: None,
+            rotated_at: None,
+            last_used_with: None,
+            last_expiry_warning_at: None,
+            receiver_id: scope.receiver_id(),
+            group_id: scope.group_id(),
+            last_used_ip: None,
         };
 
-        self.api_key_repo.save(&api_key).await?;
+        self.save_issued(&api_key).await?;
 
         // Return plaintext key only once
         Ok((key, api_key))
     }
 
     pub async fn verify_api_key(&self, key: &str) -> Result<User, AuthError> {
+        self.verify_api_key_at(key, Utc::now()).await
+    }
+
+    /// Verifies `key` as of `now`
+    ///
+    /// Both the current secret and, during its grace period, the previous
+    /// one authenticate. The secret used is recorded on the key.
+    pub async fn verify_api_key_at(
+        &self,
+        key: &str,
+        now: DateTime<Utc>,
+    ) -> Result<User, AuthError> {
+        self.authenticate_at(key, now, &ApiKeyRequest::default())
+            .await
+            .map(|(user, _)| user)
+    }
+
+    /// Verifies `key` and returns its user along with the key itself
+    ///
+    /// Callers use the key's [`ApiKey::scope`] to limit what the request
+    /// may do.
+    pub async fn authenticate(&self, key: &str) -> Result<(User, ApiKey), AuthError> {
+        self.authenticate_request(key, &ApiKeyRequest::default())
+            .await
+    }
+
+    /// Verifies `key` presented by `request`
+    ///
+    /// With a usage tracker, the request's address is kept as the key's
+    /// last use, and a rejected key that exists is counted as an
+    /// authentication failure on the request's route.
+    pub async fn authenticate_request(
+        &self,
+        key: &str,
+        request: &ApiKeyRequest,
+    ) -> Result<(User, ApiKey), AuthError> {
+        self.authenticate_at(key, Utc::now(), request).await
+    }
+
+    async fn authenticate_at(
+        &self,
+        key: &str,
+        now: DateTime<Utc>,
+        request: &ApiKeyRequest,
+    ) -> Result<(User, ApiKey), AuthError> {
         let key_hash = hash_api_key(key);
+        if self.is_known_unknown(&key_hash) {
+            self.record_cache("negative_hit");
+            return Err(AuthError::InvalidApiKey);
+        }
 
         // Find API key
-        let api_key = self
-            .api_key_repo
-            .find_by_hash(&key_hash)
-            .await?
+        if !self.negative_cache_ttl.is_zero() {
+            self.record_cache("miss");
+        }
+        let Some(api_key) = self.api_key_repo.find_by_hash(&key_hash).await? else {
+            self.remember_unknown(key_hash);
+            return Err(AuthError::InvalidApiKey);
+        };
+
+        let result = self.check_key(&api_key, &key_hash, now, request).await;
+        if let (Err(e), Some(usage)) = (&result, &self.usage) {
+            usage.record_outcome(
+                api_key.id,
+                ApiKeyOutcome::AuthFailure,
+                Some(failure_reason(e)),
+                &request.route,
+                now,
+            );
+        }
+        result.map(|user| (user, api_key))
+    }
+
+    /// Checks a key found by the hash of the presented secret and returns
+    /// its user
+    async fn check_key(
+        &self,
+        api_key: &ApiKey,
+        key_hash: &str,
+        now: DateTime<Utc>,
+        request: &ApiKeyRequest,
+    ) -> Result<User, AuthError> {
+        let secret = api_key
+            .secret_for(key_hash, now)
             .ok_or(AuthError::InvalidApiKey)?;
 
         // Check if enabled
@@ -139,14 +595,11 @@ impl ApiKeyService {
 
         // Check expiration
         if let Some(expires_at) = api_key.expires_at {
-            if Utc::now() > expires_at {
+            if now > expires_at {
                 return Err(AuthError::ApiKeyExpired);
             }
         }
 
-        // Update last used timestamp
-        self.api_key_repo.update_last_used(api_key.id).await?;
-
         // Get user
         let user = self
             .user_repo
@@ -154,8 +607,152 @@ impl ApiKeyService {
             .await?
             .ok_or(AuthError::UserNotFound)?;
 
+        // Update last used timestamp
+        match &self.usage {
+            Some(usage) => usage.record_use(api_key.id, key_hash, secret, request.ip.clone(), now),
+            None => {
+                self.api_key_repo
+                    .update_last_used(api_key.id, secret)
+                    .await?
+            }
+        }
+
         Ok(user)
     }
+
+    /// Saves a newly issued secret and drops any negative entry for it
+    async fn save_issued(&self, api_key: &ApiKey) -> Result<(), AuthError> {
+        self.api_key_repo.save(api_key).await?;
+        self.unknown_hashes
+            .lock()
+            .unwrap()
+            .remove(&api_key.key_hash);
+        Ok(())
+    }
+
+    /// Returns whether `key_hash` recently matched no key
+    fn is_known_unknown(&self, key_hash: &str) -> bool {
+        self.unknown_hashes
+            .lock()
+            .unwrap()
+            .get(key_hash)
+            .is_some_and(|seen| seen.elapsed() < self.negative_cache_ttl)
+    }
+
+    fn remember_unknown(&self, key_hash: String) {
+        if self.negative_cache_ttl.is_zero() {
+            return;
+        }
+        let mut unknown = self.unknown_hashes.lock().unwrap();
+        if unknown.len() >= MAX_UNKNOWN_KEY_HASHES {
+            unknown.clear();
+        }
+        unknown.insert(key_hash, Instant::now());
+    }
+
+    fn record_cache(&self, result: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_lookup_cache("api_key", result);
+        }
+    }
+}
+
+/// Warns about API keys that are about to expire
+///
+/// Each run looks for enabled keys expiring within the warning window and
+/// records an `api_key_expiring` audit event for each. A key is warned
+/// about at most once per UTC day, however often the job runs.
+pub struct ApiKeyExpiryNotifier {
+    api_key_repo: Arc<dyn ApiKeyRepository>,
+    warning_window: Duration,
+    audit_logger: Arc<AuditLogger>,
+}
+
+impl ApiKeyExpiryNotifier {
+    pub fn new(api_key_repo: Arc<dyn ApiKeyRepository>, warning_window: Duration) -> Self {
+        Self {
+            api_key_repo,
+            warning_window,
+            audit_logger: Arc::new(AuditLogger::new()),
+        }
+    }
+
+    /// Uses `audit_logger` to record the warnings
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Warns about keys expiring within the window after `now`
+    ///
+    /// Returns the keys warned about in this run.
+    pub async fn run_once(&self, now: DateTime<Utc>) -> Result<Vec<ApiKeyId>, AuthError> {
+        let expiring = self
+            .api_key_repo
+            .find_expiring(now, now + self.warning_window)
+            .await?;
+
+        let mut warned = Vec::new();
+        for api_key in expiring {
+            let already_warned = api_key
+                .last_expiry_warning_at
+                .is_some_and(|at| at.date_naive() == now.date_naive());
+            let expires_at = match api_key.expires_at {
+                Some(expires_at) if !already_warned => expires_at,
+                _ => continue,
+            };
+
+            warn!(
+                key_id = %api_key.id,
+                user_id = %api_key.user_id,
+                expires_at = %expires_at,
+                "API key expires soon"
+            );
+            self.audit_logger.log_event(
+                AuditEvent::builder()
+                    .user_id(api_key.user_id.to_string())
+                    .action(AuditAction::ApiKeyExpiring)
+                    .resource(format!("api_key:{}", api_key.id))
+                    .outcome(AuditOutcome::Success)
+                    .add_metadata("key_name", api_key.name.clone())
+                    .add_metadata(
+                        "expires_at",
+                        expires_at.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                    )
+                    .build(),
+            );
+            self.api_key_repo
+                .record_expiry_warning(api_key.id, now)
+                .await?;
+            warned.push(api_key.id);
+        }
+
+        Ok(warned)
+    }
+
+    /// Checks for expiring keys every `interval` until the task is aborted
+    pub fn spawn(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.run_once(Utc::now()).await {
+                    error!("Failed to check for expiring API keys: {}", e);
+                }
+            }
+        })
+    }
+}
+
+/// Reason code kept for a key that matched but was rejected
+fn failure_reason(e: &AuthError) -> &'static str {
+    match e {
+        AuthError::InvalidApiKey => "previous_secret_expired",
+        AuthError::ApiKeyDisabled => "key_disabled",
+        AuthError::ApiKeyExpired => "key_expired",
+        AuthError::UserNotFound => "user_not_found",
+        _ => "internal_error",
+    }
 }
 
 fn generate_random_key() -> String {
@@ -165,7 +762,7 @@ fn generate_random_key() -> String {
     format!("xzepr_{}", hex::encode(bytes))
 }
 
-fn hash_api_key(key: &str) -> String {
+pub(crate) fn hash_api_key(key: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(key.as_bytes());
     format!("{:x}", hasher.finalize())
@@ -181,12 +778,21 @@ impl ApiKeyRepository for StubApiKeyRepository {
         Ok(())
     }
 
+    async fn find_by_id(&self, _id: ApiKeyId) -> Result<Option<ApiKey>, AuthError> {
+        // Stub: no keys found
+        Ok(None)
+    }
+
     async fn find_by_hash(&self, _hash: &str) -> Result<Option<ApiKey>, AuthError> {
         // Stub: no keys found
         Ok(None)
     }
 
-    async fn update_last_used(&self, _id: ApiKeyId) -> Result<(), AuthError> {
+    async fn update_last_used(
+        &self,
+        _id: ApiKeyId,
+        _secret: ApiKeySecret,
+    ) -> Result<(), AuthError> {
         // Stub: pretend to update
         Ok(())
     }
@@ -196,8 +802,562 @@ impl ApiKeyRepository for StubApiKeyRepository {
         Ok(vec![])
     }
 
+    async fn find_by_group_id(
+        &self,
+        _group_id: EventReceiverGroupId,
+    ) -> Result<Vec<ApiKey>, AuthError> {
+        // Stub: return empty list
+        Ok(vec![])
+    }
+
+    async fn find_by_receiver_id(
+        &self,
+        _receiver_id: EventReceiverId,
+    ) -> Result<Vec<ApiKey>, AuthError> {
+        // Stub: return empty list
+        Ok(vec![])
+    }
+
     async fn revoke(&self, _id: ApiKeyId) -> Result<(), AuthError> {
         // Stub: pretend to revoke
         Ok(())
     }
+
+    async fn find_expiring(
+        &self,
+        _from: DateTime<Utc>,
+        _until: DateTime<Utc>,
+    ) -> Result<Vec<ApiKey>, AuthError> {
+        // Stub: no keys expiring
+        Ok(vec![])
+    }
+
+    async fn record_expiry_warning(
+        &self,
+        _id: ApiKeyId,
+        _at: DateTime<Utc>,
+    ) -> Result<(), AuthError> {
+        // Stub: pretend to record
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct InMemoryApiKeyRepository {
+        keys: Mutex<HashMap<ApiKeyId, ApiKey>>,
+        hash_lookups: AtomicUsize,
+        last_use_writes: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl ApiKeyRepository for InMemoryApiKeyRepository {
+        async fn save(&self, api_key: &ApiKey) -> Result<(), AuthError> {
+            self.keys
+                .lock()
+                .unwrap()
+                .insert(api_key.id, api_key.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: ApiKeyId) -> Result<Option<ApiKey>, AuthError> {
+            Ok(self.keys.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn find_by_hash(&self, hash: &str) -> Result<Option<ApiKey>, AuthError> {
+            self.hash_lookups.fetch_add(1, Ordering::SeqCst);
+            Ok(self
+                .keys
+                .lock()
+                .unwrap()
+                .values()
+                .find(|k| k.key_hash == hash || k.previous_hash.as_deref() == Some(hash))
+                .cloned())
+        }
+
+        async fn update_last_used(
+            &self,
+            id: ApiKeyId,
+            secret: ApiKeySecret,
+        ) -> Result<(), AuthError> {
+            if let Some(key) = self.keys.lock().unwrap().get_mut(&id) {
+                key.last_used_at = Some(Utc::now());
+                key.last_used_with = Some(secret);
+            }
+            Ok(())
+        }
+
+        async fn record_last_use(
+            &self,
+            id: ApiKeyId,
+            secret: ApiKeySecret,
+            at: DateTime<Utc>,
+            ip: Option<&str>,
+        ) -> Result<(), AuthError> {
+            self.last_use_writes.fetch_add(1, Ordering::SeqCst);
+            if let Some(key) = self.keys.lock().unwrap().get_mut(&id) {
+                key.last_used_at = Some(at);
+                key.last_used_with = Some(secret);
+                key.last_used_ip = ip.map(str::to_string);
+            }
+            Ok(())
+        }
+
+        async fn find_by_user_id(&self, user_id: UserId) -> Result<Vec<ApiKey>, AuthError> {
+            Ok(self
+                .keys
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|k| k.user_id == user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_group_id(
+            &self,
+            group_id: EventReceiverGroupId,
+        ) -> Result<Vec<ApiKey>, AuthError> {
+            Ok(self
+                .keys
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|k| k.group_id == Some(group_id))
+                .cloned()
+                .collect())
+        }
+
+        async fn find_by_receiver_id(
+            &self,
+            receiver_id: EventReceiverId,
+        ) -> Result<Vec<ApiKey>, AuthError> {
+            Ok(self
+                .keys
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|k| k.receiver_id == Some(receiver_id))
+                .cloned()
+                .collect())
+        }
+
+        async fn revoke(&self, id: ApiKeyId) -> Result<(), AuthError> {
+            if let Some(key) = self.keys.lock().unwrap().get_mut(&id) {
+                key.enabled = false;
+            }
+            Ok(())
+        }
+
+        async fn find_expiring(
+            &self,
+            from: DateTime<Utc>,
+            until: DateTime<Utc>,
+        ) -> Result<Vec<ApiKey>, AuthError> {
+            Ok(self
+                .keys
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|k| k.enabled && k.expires_at.is_some_and(|at| at > from && at <= until))
+                .cloned()
+                .collect())
+        }
+
+        async fn record_expiry_warning(
+            &self,
+            id: ApiKeyId,
+            at: DateTime<Utc>,
+        ) -> Result<(), AuthError> {
+            if let Some(key) = self.keys.lock().unwrap().get_mut(&id) {
+                key.last_expiry_warning_at = Some(at);
+            }
+            Ok(())
+        }
+    }
+
+    struct SingleUserRepository {
+        user: User,
+    }
+
+    #[async_trait::async_trait]
+    impl UserRepository for SingleUserRepository {
+        async fn find_by_id(&self, id: UserId) -> Result<Option<User>, AuthError> {
+            Ok((*self.user.id() == id).then(|| self.user.clone()))
+        }
+
+        async fn find_by_username(&self, _username: &str) -> Result<Option<User>, AuthError> {
+            Ok(None)
+        }
+
+        async fn save(&self, _user: &User) -> Result<(), AuthError> {
+            Ok(())
+        }
+
+        async fn find_all(&self) -> Result<Vec<User>, AuthError> {
+            Ok(vec![self.user.clone()])
+        }
+
+        async fn add_role(
+            &self,
+            _user_id: &UserId,
+            _role: crate::auth::rbac::roles::Role,
+        ) -> Result<(), AuthError> {
+            Ok(())
+        }
+
+        async fn remove_role(
+            &self,
+            _user_id: &UserId,
+            _role: crate::auth::rbac::roles::Role,
+        ) -> Result<(), AuthError> {
+            Ok(())
+        }
+    }
+
+    fn create_service() -> (ApiKeyService, Arc<InMemoryApiKeyRepository>, UserId) {
+        let user = User::new_oidc(
+            "producer".to_string(),
+            "producer@example.com".to_string(),
+            "subject-1".to_string(),
+        );
+        let user_id = *user.id();
+        let repo = Arc::new(InMemoryApiKeyRepository::default());
+        let service = ApiKeyService::new(Arc::new(SingleUserRepository { user }), repo.clone());
+        (service, repo, user_id)
+    }
+
+    #[tokio::test]
+    async fn test_previous_secret_works_only_during_grace_period() {
+        let (service, _repo, user_id) = create_service();
+        let (old_secret, api_key) = service
+            .generate_api_key(user_id, "ci".to_string(), None)
+            .await
+            .unwrap();
+
+        let (new_secret, rotated) = service
+            .rotate_key(api_key.id, Some(Duration::hours(1)))
+            .await
+            .unwrap();
+        assert_eq!(rotated.id, api_key.id);
+        assert_ne!(new_secret, old_secret);
+
+        let now = Utc::now();
+        assert!(service.verify_api_key_at(&old_secret, now).await.is_ok());
+        assert!(service.verify_api_key_at(&new_secret, now).await.is_ok());
+
+        let after_grace = now + Duration::hours(2);
+        assert!(matches!(
+            service.verify_api_key_at(&old_secret, after_grace).await,
+            Err(AuthError::InvalidApiKey)
+        ));
+        assert!(service
+            .verify_api_key_at(&new_secret, after_grace)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_zero_grace_invalidates_old_secret_immediately() {
+        let (service, _repo, user_id) = create_service();
+        let (old_secret, api_key) = service
+            .generate_api_key(user_id, "ci".to_string(), None)
+            .await
+            .unwrap();
+
+        let (_, rotated) = service
+            .rotate_key(api_key.id, Some(Duration::zero()))
+            .await
+            .unwrap();
+        assert!(rotated.previous_hash.is_none());
+        assert!(matches!(
+            service.verify_api_key(&old_secret).await,
+            Err(AuthError::InvalidApiKey)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_usage_tracking_flips_to_current_secret() {
+        let (service, _repo, user_id) = create_service();
+        let (old_secret, api_key) = service
+            .generate_api_key(user_id, "ci".to_string(), None)
+            .await
+            .unwrap();
+        let (new_secret, _) = service.rotate_key(api_key.id, None).await.unwrap();
+
+        service.verify_api_key(&old_secret).await.unwrap();
+        let key = service.get_key(api_key.id).await.unwrap();
+        assert_eq!(key.last_used_with, Some(ApiKeySecret::Previous));
+
+        service.verify_api_key(&new_secret).await.unwrap();
+        let key = service.get_key(api_key.id).await.unwrap();
+        assert_eq!(key.last_used_with, Some(ApiKeySecret::Current));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_rejects_disabled_and_unknown_keys() {
+        let (service, _repo, user_id) = create_service();
+        let (_, api_key) = service
+            .generate_api_key(user_id, "ci".to_string(), None)
+            .await
+            .unwrap();
+        service.revoke_key(api_key.id).await.unwrap();
+
+        assert!(matches!(
+            service.rotate_key(api_key.id, None).await,
+            Err(AuthError::ApiKeyDisabled)
+        ));
+        assert!(matches!(
+            service.rotate_key(ApiKeyId::new(), None).await,
+            Err(AuthError::ApiKeyNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_is_looked_up_once_per_window() {
+        let (service, repo, _) = create_service();
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let service = service
+            .with_negative_cache_ttl(std::time::Duration::from_millis(200))
+            .with_metrics(metrics.clone());
+
+        for _ in 0..3 {
+            assert!(matches!(
+                service.verify_api_key("xzepr_unknown").await,
+                Err(AuthError::InvalidApiKey)
+            ));
+        }
+        assert_eq!(repo.hash_lookups.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        assert!(service.verify_api_key("xzepr_unknown").await.is_err());
+        assert_eq!(repo.hash_lookups.load(Ordering::SeqCst), 2);
+
+        let output = metrics.gather().unwrap();
+        assert!(output
+            .contains("xzepr_lookup_cache_requests_total{cache=\"api_key\",result=\"miss\"} 2"));
+        assert!(output.contains(
+            "xzepr_lookup_cache_requests_total{cache=\"api_key\",result=\"negative_hit\"} 2"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_issued_key_clears_its_negative_entry() {
+        let (service, repo, user_id) = create_service();
+        let service = service.with_negative_cache_ttl(std::time::Duration::from_secs(60));
+        let secret = "xzepr_imported";
+        assert!(service.verify_api_key(secret).await.is_err());
+
+        let (_, mut api_key) = service
+            .generate_api_key(user_id, "ci".to_string(), None)
+            .await
+            .unwrap();
+        api_key.key_hash = hash_api_key(secret);
+        service.save_issued(&api_key).await.unwrap();
+
+        assert!(service.verify_api_key(secret).await.is_ok());
+        assert_eq!(repo.hash_lookups.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_group_scoped_key_keeps_its_scope() {
+        let (service, _repo, user_id) = create_service();
+        let group_id = EventReceiverGroupId::new();
+        let (secret, api_key) = service
+            .generate_scoped_api_key(
+                user_id,
+                "ci".to_string(),
+                None,
+                ApiKeyScope::Group(group_id),
+            )
+            .await
+            .unwrap();
+
+        let (user, authenticated) = service.authenticate(&secret).await.unwrap();
+        assert_eq!(*user.id(), user_id);
+        assert_eq!(authenticated.scope(), ApiKeyScope::Group(group_id));
+        assert!(authenticated.receiver_id.is_none());
+
+        let keys = service.list_group_keys(group_id).await.unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].id, api_key.id);
+        assert!(service
+            .list_group_keys(EventReceiverGroupId::new())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_scope_allows_receiver_or_group_but_not_both() {
+        let receiver_id = EventReceiverId::new();
+        let group_id = EventReceiverGroupId::new();
+
+        assert_eq!(
+            ApiKeyScope::from_parts(None, None).unwrap(),
+            ApiKeyScope::Unscoped
+        );
+        assert_eq!(
+            ApiKeyScope::from_parts(Some(receiver_id), None).unwrap(),
+            ApiKeyScope::Receiver(receiver_id)
+        );
+        assert_eq!(
+            ApiKeyScope::from_parts(None, Some(group_id)).unwrap(),
+            ApiKeyScope::Group(group_id)
+        );
+        assert!(matches!(
+            ApiKeyScope::from_parts(Some(receiver_id), Some(group_id)),
+            Err(DomainError::ValidationError { field, .. }) if field == "receiver_id"
+        ));
+    }
+
+    fn with_tracker(
+        service: ApiKeyService,
+        repo: &Arc<InMemoryApiKeyRepository>,
+    ) -> (ApiKeyService, ApiKeyUsageTracker) {
+        let tracker = ApiKeyUsageTracker::new(
+            repo.clone(),
+            Arc::new(crate::infrastructure::memory::InMemoryApiKeyUsageRepository::default()),
+        );
+        (service.with_usage_tracker(tracker.clone()), tracker)
+    }
+
+    #[tokio::test]
+    async fn test_last_use_is_written_at_most_once_per_minute() {
+        let (service, repo, user_id) = create_service();
+        let (service, tracker) = with_tracker(service, &repo);
+        let (secret, api_key) = service
+            .generate_api_key(user_id, "ci".to_string(), None)
+            .await
+            .unwrap();
+        let request = ApiKeyRequest {
+            route: "/api/v1/events".to_string(),
+            ip: Some("192.0.2.10".to_string()),
+        };
+
+        let now = Utc::now();
+        for seconds in [0, 10, 59] {
+            service
+                .authenticate_at(&secret, now + Duration::seconds(seconds), &request)
+                .await
+                .unwrap();
+        }
+        // Authentication itself never writes
+        assert_eq!(repo.last_use_writes.load(Ordering::SeqCst), 0);
+        assert_eq!(tracker.flush().await.unwrap(), 1);
+        assert_eq!(repo.last_use_writes.load(Ordering::SeqCst), 1);
+        let key = service.get_key(api_key.id).await.unwrap();
+        assert_eq!(key.last_used_at, Some(now));
+        assert_eq!(key.last_used_ip.as_deref(), Some("192.0.2.10"));
+
+        service
+            .authenticate_at(&secret, now + Duration::seconds(61), &request)
+            .await
+            .unwrap();
+        tracker.flush().await.unwrap();
+        assert_eq!(repo.last_use_writes.load(Ordering::SeqCst), 2);
+        let key = service.get_key(api_key.id).await.unwrap();
+        assert_eq!(key.last_used_at, Some(now + Duration::seconds(61)));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_key_counts_as_auth_failure() {
+        let (service, repo, user_id) = create_service();
+        let (service, tracker) = with_tracker(service, &repo);
+        let (secret, api_key) = service
+            .generate_api_key(user_id, "ci".to_string(), None)
+            .await
+            .unwrap();
+        service.revoke_key(api_key.id).await.unwrap();
+        let request = ApiKeyRequest {
+            route: "/api/v1/events".to_string(),
+            ip: None,
+        };
+
+        assert!(matches!(
+            service.authenticate_request(&secret, &request).await,
+            Err(AuthError::ApiKeyDisabled)
+        ));
+        // Unknown keys cannot be attributed to any key
+        assert!(service
+            .authenticate_request("xzepr_unknown", &request)
+            .await
+            .is_err());
+
+        let report = tracker
+            .report(api_key.id, Utc::now().date_naive())
+            .await
+            .unwrap();
+        assert_eq!(report.days.len(), 1);
+        assert_eq!(report.days[0].auth_failures, 1);
+        assert_eq!(report.days[0].successes, 0);
+        assert_eq!(report.recent_failures[0].reason, "key_disabled");
+        assert_eq!(report.recent_failures[0].route, "/api/v1/events");
+        assert!(report.last_use.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unused_enabled_keys_are_stale() {
+        let (service, _repo, user_id) = create_service();
+        let now = Utc::now();
+        let (_, mut api_key) = service
+            .generate_api_key(user_id, "ci".to_string(), None)
+            .await
+            .unwrap();
+        api_key.created_at = now - Duration::days(120);
+
+        // Without a stale period no key is stale
+        assert!(!service.is_stale(&api_key, now));
+
+        let service = service.with_stale_after(Duration::days(90));
+        assert!(service.is_stale(&api_key, now));
+
+        api_key.last_used_at = Some(now - Duration::days(30));
+        assert!(!service.is_stale(&api_key, now));
+
+        api_key.last_used_at = Some(now - Duration::days(91));
+        assert!(service.is_stale(&api_key, now));
+
+        api_key.enabled = false;
+        assert!(!service.is_stale(&api_key, now));
+    }
+
+    #[tokio::test]
+    async fn test_expiry_warning_emitted_once_per_key_per_day() {
+        let (service, repo, user_id) = create_service();
+        // A fixed time keeps the minute-later run on the same UTC day
+        let now = Utc.with_ymd_and_hms(2025, 4, 10, 9, 0, 0).unwrap();
+        let (_, expiring) = service
+            .generate_api_key(user_id, "soon".to_string(), Some(now + Duration::days(3)))
+            .await
+            .unwrap();
+        service
+            .generate_api_key(user_id, "later".to_string(), Some(now + Duration::days(30)))
+            .await
+            .unwrap();
+        service
+            .generate_api_key(user_id, "never".to_string(), None)
+            .await
+            .unwrap();
+
+        let notifier = ApiKeyExpiryNotifier::new(repo, Duration::days(7));
+        assert_eq!(notifier.run_once(now).await.unwrap(), vec![expiring.id]);
+        assert!(notifier.run_once(now).await.unwrap().is_empty());
+        assert!(notifier
+            .run_once(now + Duration::minutes(1))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let tomorrow = now + Duration::days(1);
+        assert_eq!(
+            notifier.run_once(tomorrow).await.unwrap(),
+            vec![expiring.id]
+        );
+    }
 }
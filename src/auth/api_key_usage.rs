@@ -0,0 +1,610 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/auth/api_key_usage.rs
+//! API key usage tracking
+//!
+//! Owners see when a key was last used, how many requests it made per day,
+//! and why its latest requests failed. Requests only touch an in-memory
+//! buffer; a background flush writes it in batches.
+
+use crate::auth::api_key::{hash_api_key, ApiKeyRepository, ApiKeySecret};
+use crate::domain::value_objects::ApiKeyId;
+use crate::error::AuthError;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+
+/// Minimum time between stored last-use updates of one key
+pub const DEFAULT_LAST_USED_INTERVAL: Duration = Duration::seconds(60);
+
+/// Default number of seconds between usage flushes
+pub const DEFAULT_USAGE_FLUSH_SECONDS: u64 = 10;
+
+/// Failures kept per key
+pub const MAX_RECENT_FAILURES: usize = 20;
+
+/// Days covered by a usage report, today included
+pub const USAGE_REPORT_DAYS: i64 = 30;
+
+/// Key hashes remembered for attributing rejected requests
+const MAX_KNOWN_KEY_HASHES: usize = 10_000;
+
+/// How a request made with a key ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiKeyOutcome {
+    /// The key authenticated and the request was not refused
+    Success,
+    /// The key matched but is disabled, expired, or superseded
+    AuthFailure,
+    /// The key authenticated but lacks permission for the request
+    AuthzDenied,
+    /// The request was rejected by rate limiting
+    RateLimited,
+}
+
+impl ApiKeyOutcome {
+    /// Classifies the response to an authenticated request
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            403 => Self::AuthzDenied,
+            429 => Self::RateLimited,
+            _ => Self::Success,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::AuthFailure => "auth_failure",
+            Self::AuthzDenied => "authz_denied",
+            Self::RateLimited => "rate_limited",
+        }
+    }
+}
+
+impl fmt::Display for ApiKeyOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Requests made with a key on one UTC day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApiKeyDailyUsage {
+    pub day: NaiveDate,
+    pub successes: u64,
+    pub auth_failures: u64,
+    pub authz_denials: u64,
+    pub rate_limited: u64,
+}
+
+impl ApiKeyDailyUsage {
+    pub fn new(day: NaiveDate) -> Self {
+        Self {
+            day,
+            successes: 0,
+            auth_failures: 0,
+            authz_denials: 0,
+            rate_limited: 0,
+        }
+    }
+
+    /// Counts one request ending in `outcome`
+    pub fn add(&mut self, outcome: ApiKeyOutcome) {
+        match outcome {
+            ApiKeyOutcome::Success => self.successes += 1,
+            ApiKeyOutcome::AuthFailure => self.auth_failures += 1,
+            ApiKeyOutcome::AuthzDenied => self.authz_denials += 1,
+            ApiKeyOutcome::RateLimited => self.rate_limited += 1,
+        }
+    }
+
+    /// Adds the counts of `other`, which covers the same day
+    pub fn merge(&mut self, other: &ApiKeyDailyUsage) {
+        self.successes += other.successes;
+        self.auth_failures += other.auth_failures;
+        self.authz_denials += other.authz_denials;
+        self.rate_limited += other.rate_limited;
+    }
+}
+
+/// A failed request made with a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyFailure {
+    pub occurred_at: DateTime<Utc>,
+    /// Reason code such as `key_expired`, `forbidden`, or `rate_limited`
+    pub reason: String,
+    /// Matched route of the request
+    pub route: String,
+}
+
+/// The latest authenticated use of a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyLastUse {
+    pub at: DateTime<Utc>,
+    pub secret: ApiKeySecret,
+    pub ip: Option<String>,
+}
+
+/// Usage of one key buffered since the last flush
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyUsageUpdate {
+    pub key_id: ApiKeyId,
+    /// Set when the key's last use is due to be stored
+    pub last_use: Option<ApiKeyLastUse>,
+    /// Counts per day, oldest first
+    pub days: Vec<ApiKeyDailyUsage>,
+    /// Failures, oldest first, at most [`MAX_RECENT_FAILURES`]
+    pub failures: Vec<ApiKeyFailure>,
+}
+
+impl ApiKeyUsageUpdate {
+    pub fn new(key_id: ApiKeyId) -> Self {
+        Self {
+            key_id,
+            last_use: None,
+            days: Vec::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    fn count(&mut self, outcome: ApiKeyOutcome, at: DateTime<Utc>) {
+        let day = at.date_naive();
+        match self.days.iter_mut().find(|usage| usage.day == day) {
+            Some(usage) => usage.add(outcome),
+            None => {
+                let mut usage = ApiKeyDailyUsage::new(day);
+                usage.add(outcome);
+                self.days.push(usage);
+                self.days.sort_by_key(|usage| usage.day);
+            }
+        }
+    }
+
+    fn push_failure(&mut self, failure: ApiKeyFailure) {
+        self.failures.push(failure);
+        if self.failures.len() > MAX_RECENT_FAILURES {
+            self.failures.remove(0);
+        }
+    }
+
+    /// Folds in `older`, an update that failed to flush
+    ///
+    /// Counts add up, the newer last use wins, and failures stay bounded.
+    pub fn merge(&mut self, older: ApiKeyUsageUpdate) {
+        if self.last_use.is_none() {
+            self.last_use = older.last_use;
+        }
+        for usage in older.days {
+            match self.days.iter_mut().find(|day| day.day == usage.day) {
+                Some(day) => day.merge(&usage),
+                None => self.days.push(usage),
+            }
+        }
+        self.days.sort_by_key(|usage| usage.day);
+
+        let newer = std::mem::replace(&mut self.failures, older.failures);
+        for failure in newer {
+            self.push_failure(failure);
+        }
+    }
+}
+
+/// Usage of a key over the last [`USAGE_REPORT_DAYS`] days
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyUsageReport {
+    pub key_id: ApiKeyId,
+    /// Last use not yet written to the key
+    pub last_use: Option<ApiKeyLastUse>,
+    /// First day covered
+    pub since: NaiveDate,
+    /// Days with at least one request, oldest first
+    pub days: Vec<ApiKeyDailyUsage>,
+    /// Latest failures, newest first
+    pub recent_failures: Vec<ApiKeyFailure>,
+}
+
+#[async_trait::async_trait]
+pub trait ApiKeyUsageRepository: Send + Sync {
+    /// Adds the counts and failures of `updates`, keeping at most
+    /// [`MAX_RECENT_FAILURES`] failures per key
+    async fn record_usage(&self, updates: &[ApiKeyUsageUpdate]) -> Result<(), AuthError>;
+    /// Finds the counts of a key from `since` on, oldest first
+    async fn find_daily_usage(
+        &self,
+        key_id: ApiKeyId,
+        since: NaiveDate,
+    ) -> Result<Vec<ApiKeyDailyUsage>, AuthError>;
+    /// Finds the latest failures of a key, newest first
+    async fn find_recent_failures(
+        &self,
+        key_id: ApiKeyId,
+        limit: usize,
+    ) -> Result<Vec<ApiKeyFailure>, AuthError>;
+}
+
+/// Buffers API key usage on the authentication path
+///
+/// Recording a request only touches in-memory maps. A key's last use is
+/// queued at most once per interval unless the secret it was made with
+/// changes, and counters are written in one batch per flush. A failed
+/// flush keeps its updates for the next attempt.
+#[derive(Clone)]
+pub struct ApiKeyUsageTracker {
+    api_key_repo: Arc<dyn ApiKeyRepository>,
+    usage_repo: Arc<dyn ApiKeyUsageRepository>,
+    last_used_interval: Duration,
+    pending: Arc<Mutex<HashMap<ApiKeyId, ApiKeyUsageUpdate>>>,
+    /// Last use queued for each key
+    last_queued: Arc<Mutex<HashMap<ApiKeyId, ApiKeyLastUse>>>,
+    /// Keys by the hash of a secret that authenticated them
+    known_hashes: Arc<Mutex<HashMap<String, ApiKeyId>>>,
+}
+
+impl ApiKeyUsageTracker {
+    /// Creates a tracker storing last uses in `api_key_repo` and counters
+    /// in `usage_repo`
+    pub fn new(
+        api_key_repo: Arc<dyn ApiKeyRepository>,
+        usage_repo: Arc<dyn ApiKeyUsageRepository>,
+    ) -> Self {
+        Self {
+            api_key_repo,
+            usage_repo,
+            last_used_interval: DEFAULT_LAST_USED_INTERVAL,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            last_queued: Arc::new(Mutex::new(HashMap::new())),
+            known_hashes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Sets the minimum time between stored last uses of one key
+    pub fn with_last_used_interval(mut self, interval: Duration) -> Self {
+        self.last_used_interval = interval;
+        self
+    }
+
+    /// Records that the secret hashing to `key_hash` authenticated a key
+    ///
+    /// Counting the request is left to [`Self::record_outcome`], once the
+    /// response is known.
+    pub fn record_use(
+        &self,
+        key_id: ApiKeyId,
+        key_hash: &str,
+        secret: ApiKeySecret,
+        ip: Option<String>,
+        at: DateTime<Utc>,
+    ) {
+        {
+            let mut known = self.known_hashes.lock().unwrap();
+            if !known.contains_key(key_hash) {
+                if known.len() >= MAX_KNOWN_KEY_HASHES {
+                    known.clear();
+                }
+                known.insert(key_hash.to_string(), key_id);
+            }
+        }
+
+        let last_use = ApiKeyLastUse { at, secret, ip };
+        {
+            let mut last_queued = self.last_queued.lock().unwrap();
+            let due = last_queued.get(&key_id).is_none_or(|queued| {
+                queued.secret != secret || at - queued.at >= self.last_used_interval
+            });
+            if !due {
+                return;
+            }
+            last_queued.insert(key_id, last_use.clone());
+        }
+        self.update(key_id, |update| update.last_use = Some(last_use));
+    }
+
+    /// Counts a request made with a key
+    ///
+    /// Anything but a success is also kept as a failure with `reason`,
+    /// or the outcome's name when `None`.
+    pub fn record_outcome(
+        &self,
+        key_id: ApiKeyId,
+        outcome: ApiKeyOutcome,
+        reason: Option<&str>,
+        route: &str,
+        at: DateTime<Utc>,
+    ) {
+        self.update(key_id, |update| {
+            update.count(outcome, at);
+            if outcome != ApiKeyOutcome::Success {
+                update.push_failure(ApiKeyFailure {
+                    occurred_at: at,
+                    reason: reason.unwrap_or(outcome.as_str()).to_string(),
+                    route: route.to_string(),
+                });
+            }
+        });
+    }
+
+    /// Counts a rate limited request made with the presented `key`
+    ///
+    /// Only keys that authenticated since startup are recognized; the key
+    /// is not looked up.
+    pub fn record_rate_limited(&self, key: &str, route: &str, at: DateTime<Utc>) {
+        let key_id = self
+            .known_hashes
+            .lock()
+            .unwrap()
+            .get(&hash_api_key(key))
+            .copied();
+        if let Some(key_id) = key_id {
+            self.record_outcome(key_id, ApiKeyOutcome::RateLimited, None, route, at);
+        }
+    }
+
+    fn update(&self, key_id: ApiKeyId, apply: impl FnOnce(&mut ApiKeyUsageUpdate)) {
+        let mut pending = self.pending.lock().unwrap();
+        apply(
+            pending
+                .entry(key_id)
+                .or_insert_with(|| ApiKeyUsageUpdate::new(key_id)),
+        );
+    }
+
+    /// Returns the usage of a key over the last [`USAGE_REPORT_DAYS`] days
+    /// up to `today`
+    ///
+    /// Buffered requests are included, so the report does not wait for the
+    /// next flush.
+    pub async fn report(
+        &self,
+        key_id: ApiKeyId,
+        today: NaiveDate,
+    ) -> Result<ApiKeyUsageReport, AuthError> {
+        let since = today - Duration::days(USAGE_REPORT_DAYS - 1);
+        let mut days = self.usage_repo.find_daily_usage(key_id, since).await?;
+        let mut recent_failures = self
+            .usage_repo
+            .find_recent_failures(key_id, MAX_RECENT_FAILURES)
+            .await?;
+
+        let mut last_use = None;
+        if let Some(pending) = self.pending.lock().unwrap().get(&key_id) {
+            last_use = pending.last_use.clone();
+            for usage in pending.days.iter().filter(|usage| usage.day >= since) {
+                match days.iter_mut().find(|day| day.day == usage.day) {
+                    Some(day) => day.merge(usage),
+                    None => days.push(*usage),
+                }
+            }
+            days.sort_by_key(|usage| usage.day);
+            recent_failures.extend(pending.failures.iter().cloned());
+            recent_failures.sort_by_key(|failure| std::cmp::Reverse(failure.occurred_at));
+            recent_failures.truncate(MAX_RECENT_FAILURES);
+        }
+
+        Ok(ApiKeyUsageReport {
+            key_id,
+            last_use,
+            since,
+            days,
+            recent_failures,
+        })
+    }
+
+    /// Writes buffered usage and returns the number of keys updated
+    pub async fn flush(&self) -> Result<usize, AuthError> {
+        let updates: Vec<ApiKeyUsageUpdate> = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.drain().map(|(_, update)| update).collect()
+        };
+        if updates.is_empty() {
+            return Ok(0);
+        }
+
+        if let Err(e) = self.write(&updates).await {
+            let mut pending = self.pending.lock().unwrap();
+            for update in updates {
+                match pending.get_mut(&update.key_id) {
+                    Some(newer) => newer.merge(update),
+                    None => {
+                        pending.insert(update.key_id, update);
+                    }
+                }
+            }
+            return Err(e);
+        }
+
+        debug!(keys = updates.len(), "Flushed API key usage");
+        Ok(updates.len())
+    }
+
+    async fn write(&self, updates: &[ApiKeyUsageUpdate]) -> Result<(), AuthError> {
+        for update in updates {
+            if let Some(last_use) = &update.last_use {
+                self.api_key_repo
+                    .record_last_use(
+                        update.key_id,
+                        last_use.secret,
+                        last_use.at,
+                        last_use.ip.as_deref(),
+                    )
+                    .await?;
+            }
+        }
+        self.usage_repo.record_usage(updates).await
+    }
+
+    /// Flushes buffered usage every `interval` until the task is aborted
+    pub fn spawn(self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.flush().await {
+                    error!("Failed to flush API key usage: {}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::api_key::StubApiKeyRepository;
+    use crate::infrastructure::memory::InMemoryApiKeyUsageRepository;
+    use chrono::TimeZone;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[derive(Default)]
+    struct FlakyUsageRepository {
+        inner: InMemoryApiKeyUsageRepository,
+        fail: AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl ApiKeyUsageRepository for FlakyUsageRepository {
+        async fn record_usage(&self, updates: &[ApiKeyUsageUpdate]) -> Result<(), AuthError> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(AuthError::InvalidCredentials);
+            }
+            self.inner.record_usage(updates).await
+        }
+
+        async fn find_daily_usage(
+            &self,
+            key_id: ApiKeyId,
+            since: NaiveDate,
+        ) -> Result<Vec<ApiKeyDailyUsage>, AuthError> {
+            self.inner.find_daily_usage(key_id, since).await
+        }
+
+        async fn find_recent_failures(
+            &self,
+            key_id: ApiKeyId,
+            limit: usize,
+        ) -> Result<Vec<ApiKeyFailure>, AuthError> {
+            self.inner.find_recent_failures(key_id, limit).await
+        }
+    }
+
+    fn tracker() -> (ApiKeyUsageTracker, Arc<FlakyUsageRepository>) {
+        let repo = Arc::new(FlakyUsageRepository::default());
+        let tracker = ApiKeyUsageTracker::new(Arc::new(StubApiKeyRepository), repo.clone());
+        (tracker, repo)
+    }
+
+    #[tokio::test]
+    async fn test_outcomes_are_counted_per_class() {
+        let (tracker, _repo) = tracker();
+        let key_id = ApiKeyId::new();
+        let now = Utc::now();
+        tracker.record_use(
+            key_id,
+            &hash_api_key("xzepr_secret"),
+            ApiKeySecret::Current,
+            None,
+            now,
+        );
+
+        tracker.record_outcome(key_id, ApiKeyOutcome::Success, None, "/api/v1/events", now);
+        tracker.record_outcome(key_id, ApiKeyOutcome::Success, None, "/api/v1/events", now);
+        tracker.record_outcome(
+            key_id,
+            ApiKeyOutcome::AuthzDenied,
+            Some("forbidden"),
+            "/api/v1/receivers",
+            now,
+        );
+        tracker.record_outcome(
+            key_id,
+            ApiKeyOutcome::AuthFailure,
+            Some("key_expired"),
+            "/api/v1/events",
+            now,
+        );
+        tracker.record_rate_limited("xzepr_secret", "/api/v1/events", now);
+        // A key that never authenticated is not attributed
+        tracker.record_rate_limited("xzepr_other", "/api/v1/events", now);
+
+        assert_eq!(tracker.flush().await.unwrap(), 1);
+        let report = tracker.report(key_id, now.date_naive()).await.unwrap();
+        assert_eq!(
+            report.days,
+            vec![ApiKeyDailyUsage {
+                day: now.date_naive(),
+                successes: 2,
+                auth_failures: 1,
+                authz_denials: 1,
+                rate_limited: 1,
+            }]
+        );
+        let reasons: Vec<&str> = report
+            .recent_failures
+            .iter()
+            .map(|failure| failure.reason.as_str())
+            .collect();
+        assert_eq!(reasons.len(), 3);
+        assert!(reasons.contains(&"forbidden"));
+        assert!(reasons.contains(&"key_expired"));
+        assert!(reasons.contains(&"rate_limited"));
+    }
+
+    #[tokio::test]
+    async fn test_report_covers_thirty_days_and_latest_failures() {
+        let (tracker, _repo) = tracker();
+        let key_id = ApiKeyId::new();
+        // A fixed time keeps every failure on the same UTC day
+        let now = Utc.with_ymd_and_hms(2025, 4, 10, 9, 0, 0).unwrap();
+
+        tracker.record_outcome(
+            key_id,
+            ApiKeyOutcome::Success,
+            None,
+            "/api/v1/events",
+            now - Duration::days(USAGE_REPORT_DAYS),
+        );
+        for i in 0..25 {
+            tracker.record_outcome(
+                key_id,
+                ApiKeyOutcome::AuthzDenied,
+                Some(&format!("reason-{}", i)),
+                "/api/v1/events",
+                now + Duration::seconds(i),
+            );
+        }
+        tracker.flush().await.unwrap();
+
+        let report = tracker.report(key_id, now.date_naive()).await.unwrap();
+        assert_eq!(report.days.len(), 1);
+        assert_eq!(report.days[0].authz_denials, 25);
+        assert_eq!(report.recent_failures.len(), MAX_RECENT_FAILURES);
+        assert_eq!(report.recent_failures[0].reason, "reason-24");
+        assert_eq!(report.recent_failures[19].reason, "reason-5");
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_keeps_usage() {
+        let (tracker, repo) = tracker();
+        let key_id = ApiKeyId::new();
+        let now = Utc::now();
+
+        tracker.record_outcome(key_id, ApiKeyOutcome::Success, None, "/api/v1/events", now);
+        repo.fail.store(true, Ordering::SeqCst);
+        assert!(tracker.flush().await.is_err());
+
+        // Requests recorded meanwhile merge with the retained counts
+        tracker.record_outcome(key_id, ApiKeyOutcome::Success, None, "/api/v1/events", now);
+        repo.fail.store(false, Ordering::SeqCst);
+        assert_eq!(tracker.flush().await.unwrap(), 1);
+
+        let stored = repo
+            .find_daily_usage(key_id, now.date_naive())
+            .await
+            .unwrap();
+        assert_eq!(stored[0].successes, 2);
+    }
+}
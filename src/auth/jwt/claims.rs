@@ -35,6 +35,16 @@ pub struct Claims {
     pub permissions: Vec<String>,
     /// Token type (access or refresh)
     pub token_type: TokenType,
+    /// Administrator acting as the subject, for impersonation tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+}
+
+/// Actor claim (RFC 8693) naming who really holds an impersonation token
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Actor {
+    /// User ID of the acting administrator
+    pub sub: String,
 }
 
 /// Token type enumeration
@@ -87,6 +97,37 @@ impl Claims {
             roles,
             permissions,
             token_type: TokenType::Access,
+            act: None,
+        }
+    }
+
+    /// Create new claims for an impersonation token
+    ///
+    /// The token authorizes as `user_id` with that user's roles and
+    /// permissions, while the `act` claim records the administrator
+    /// holding it.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The impersonated user ID (subject)
+    /// * `roles` - Roles of the impersonated user
+    /// * `permissions` - Permissions of the impersonated user
+    /// * `actor_id` - The administrator's user ID
+    /// * `issuer` - Token issuer
+    /// * `audience` - Token audience
+    /// * `expiration` - Token expiration duration
+    pub fn new_impersonation_token(
+        user_id: String,
+        roles: Vec<String>,
+        permissions: Vec<String>,
+        actor_id: String,
+        issuer: String,
+        audience: String,
+        expiration: Duration,
+    ) -> Self {
+        Self {
+            act: Some(Actor { sub: actor_id }),
+            ..Self::new_access_token(user_id, roles, permissions, issuer, audience, expiration)
         }
     }
 
@@ -125,6 +166,7 @@ impl Claims {
             roles: vec![],
             permissions: vec![],
             token_type: TokenType::Refresh,
+            act: None,
         }
     }
 
@@ -172,6 +214,16 @@ impl Claims {
         Ok(())
     }
 
+    /// Returns the administrator acting as the subject, if any
+    pub fn actor_id(&self) -> Option<&str> {
+        self.act.as_ref().map(|actor| actor.sub.as_str())
+    }
+
+    /// Check if the token was issued for impersonation
+    pub fn is_impersonation(&self) -> bool {
+        self.act.is_some()
+    }
+
     /// Check if the token has expired
     pub fn is_expired(&self) -> bool {
         let now = Utc::now().timestamp();
@@ -260,6 +312,39 @@ mod tests {
         assert_eq!(claims.token_type, TokenType::Refresh);
     }
 
+    #[test]
+    fn test_new_impersonation_token() {
+        let claims = Claims::new_impersonation_token(
+            "user123".to_string(),
+            vec!["user".to_string()],
+            vec!["EventRead".to_string()],
+            "admin1".to_string(),
+            "xzepr".to_string(),
+            "xzepr-api".to_string(),
+            Duration::minutes(15),
+        );
+
+        assert_eq!(claims.sub, "user123");
+        assert_eq!(claims.roles, vec!["user"]);
+        assert_eq!(claims.actor_id(), Some("admin1"));
+        assert!(claims.is_impersonation());
+
+        let json = serde_json::to_value(&claims).unwrap();
+        assert_eq!(json["act"]["sub"], "admin1");
+
+        // Ordinary tokens carry no act claim at all
+        let plain = Claims::new_access_token(
+            "user123".to_string(),
+            vec![],
+            vec![],
+            "xzepr".to_string(),
+            "xzepr-api".to_string(),
+            Duration::minutes(15),
+        );
+        assert!(serde_json::to_value(&plain).unwrap().get("act").is_none());
+        assert!(!plain.is_impersonation());
+    }
+
     #[test]
     fn test_validate_success() {
         let claims = Claims::new_access_token(
@@ -10,6 +10,8 @@
 use chrono::Duration;
 use serde::{Deserialize, Serialize};
 
+use crate::infrastructure::config::Secret;
+
 /// JWT configuration for the application
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwtConfig {
@@ -39,8 +41,10 @@ pub struct JwtConfig {
     /// Public key path for RS256 (PEM format)
     pub public_key_path: Option<String>,
 
-    /// Secret key for HS256 (not recommended for production)
-    pub secret_key: Option<String>,
+    /// Secret key for HS256 (not recommended for production); never
+    /// serialized
+    #[serde(default, skip_serializing)]
+    pub secret_key: Option<Secret<String>>,
 
     /// Enable token rotation on refresh
     #[serde(default = "default_enable_rotation")]
@@ -135,7 +139,7 @@ impl JwtConfig {
                     return Err("Secret key required for HS256".to_string());
                 }
                 if let Some(ref secret) = self.secret_key {
-                    if secret.len() < 32 {
+                    if secret.expose().len() < 32 {
                         return Err("Secret key must be at least 32 characters".to_string());
                     }
                 }
@@ -156,7 +160,9 @@ impl JwtConfig {
             algorithm: Algorithm::HS256,
             private_key_path: None,
             public_key_path: None,
-            secret_key: Some("dev-secret-key-min-32-characters-long".to_string()),
+            secret_key: Some(Secret::new(
+                "dev-secret-key-min-32-characters-long".to_string(),
+            )),
             enable_token_rotation: true,
             leeway_seconds: 60,
         }
@@ -298,7 +304,7 @@ mod tests {
     fn test_validate_hs256_short_secret() {
         let config = JwtConfig {
             algorithm: Algorithm::HS256,
-            secret_key: Some("short".to_string()),
+            secret_key: Some(Secret::new("short".to_string())),
             ..Default::default()
         };
         let result = config.validate();
@@ -326,6 +332,7 @@ mod tests {
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("xzepr-dev"));
         assert!(json.contains("HS256"));
+        assert!(!json.contains("dev-secret-key"));
     }
 
     #[test]
@@ -54,7 +54,8 @@ impl KeyPair {
                 config
                     .secret_key
                     .as_ref()
-                    .ok_or_else(|| JwtError::KeyError("Missing secret key".to_string()))?,
+                    .ok_or_else(|| JwtError::KeyError("Missing secret key".to_string()))?
+                    .expose(),
             ),
         }
     }
@@ -236,7 +237,7 @@ mod tests {
     fn test_keypair_from_config_hs256() {
         let mut config = JwtConfig::development();
         config.algorithm = Algorithm::HS256;
-        config.secret_key = Some(TEST_SECRET.to_string());
+        config.secret_key = Some(TEST_SECRET.to_string().into());
 
         let keypair = KeyPair::from_config(&config).unwrap();
         assert_eq!(keypair.algorithm(), jsonwebtoken::Algorithm::HS256);
@@ -13,6 +13,7 @@
 //! - Token validation with expiration and signature verification
 //! - Token blacklist for revocation
 //! - Key rotation support
+//! - Session listing and revocation, including impersonation sessions
 //! - Configurable token lifetimes
 //!
 //! # Example
@@ -47,10 +48,12 @@ pub mod config;
 pub mod error;
 pub mod keys;
 pub mod service;
+pub mod sessions;
 
 pub use blacklist::{Blacklist, TokenBlacklist};
-pub use claims::{Claims, TokenType};
+pub use claims::{Actor, Claims, TokenType};
 pub use config::{Algorithm, JwtConfig};
 pub use error::{JwtError, JwtResult};
 pub use keys::{KeyManager, KeyPair};
 pub use service::{JwtService, TokenPair};
+pub use sessions::{Session, SessionRegistry};
@@ -15,6 +15,7 @@ use super::claims::{Claims, TokenType};
 use super::config::JwtConfig;
 use super::error::{JwtError, JwtResult};
 use super::keys::KeyManager;
+use super::sessions::{Session, SessionRegistry};
 
 /// JWT service for token operations
 #[derive(Clone)]
@@ -25,6 +26,8 @@ pub struct JwtService {
     key_manager: KeyManager,
     /// Token blacklist for revocation
     blacklist: TokenBlacklist,
+    /// Access tokens issued by this service
+    sessions: SessionRegistry,
 }
 
 /// Token pair response (access + refresh tokens)
@@ -51,6 +54,7 @@ impl JwtService {
             config,
             key_manager,
             blacklist,
+            sessions: SessionRegistry::new(),
         }
     }
 
@@ -128,14 +132,59 @@ impl JwtService {
             self.config.access_token_expiration(),
         );
 
-        let header = Header::new(self.key_manager.current().algorithm());
-        let token = encode(&header, &claims, self.key_manager.current().encoding_key())
-            .map_err(|e| JwtError::EncodingError(e.to_string()))?;
+        let token = self.encode_claims(&claims)?;
+        self.sessions.record(Session::from_claims(&claims));
 
         debug!(jti = %claims.jti, "Generated access token");
         Ok(token)
     }
 
+    /// Generate an impersonation token
+    ///
+    /// The token authorizes as `user_id` with that user's roles and
+    /// permissions; its `act` claim names the administrator. No refresh
+    /// token is issued with it, and its session records `reason`.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The impersonated user ID
+    /// * `roles` - Roles of the impersonated user
+    /// * `permissions` - Permissions of the impersonated user
+    /// * `actor_id` - The administrator's user ID
+    /// * `reason` - Why the administrator impersonates the user
+    /// * `expiration` - Token lifetime
+    ///
+    /// # Returns
+    ///
+    /// The encoded token and its session
+    #[instrument(skip(self, roles, permissions, reason))]
+    pub fn generate_impersonation_token(
+        &self,
+        user_id: String,
+        roles: Vec<String>,
+        permissions: Vec<String>,
+        actor_id: String,
+        reason: String,
+        expiration: chrono::Duration,
+    ) -> JwtResult<(String, Session)> {
+        let claims = Claims::new_impersonation_token(
+            user_id,
+            roles,
+            permissions,
+            actor_id,
+            self.config.issuer.clone(),
+            self.config.audience.clone(),
+            expiration,
+        );
+
+        let token = self.encode_claims(&claims)?;
+        let session = Session::from_claims(&claims).with_reason(reason);
+        self.sessions.record(session.clone());
+
+        debug!(jti = %claims.jti, "Generated impersonation token");
+        Ok((token, session))
+    }
+
     /// Generate a refresh token
     ///
     /// # Arguments
@@ -154,9 +203,7 @@ impl JwtService {
             self.config.refresh_token_expiration(),
         );
 
-        let header = Header::new(self.key_manager.current().algorithm());
-        let token = encode(&header, &claims, self.key_manager.current().encoding_key())
-            .map_err(|e| JwtError::EncodingError(e.to_string()))?;
+        let token = self.encode_claims(&claims)?;
 
         debug!(jti = %claims.jti, "Generated refresh token");
         Ok(token)
@@ -291,6 +338,32 @@ impl JwtService {
         Ok(())
     }
 
+    /// Revoke a session by its JWT ID
+    ///
+    /// The token is blacklisted until it expires. Returns the revoked
+    /// session, or `None` if this service did not issue the token.
+    #[instrument(skip(self))]
+    pub async fn revoke_session(&self, id: &str) -> JwtResult<Option<Session>> {
+        let Some(session) = self.sessions.mark_revoked(id, Utc::now()) else {
+            return Ok(None);
+        };
+        self.blacklist
+            .revoke(session.id.clone(), session.expires_at)
+            .await?;
+        Ok(Some(session))
+    }
+
+    /// Get the session registry
+    pub fn sessions(&self) -> &SessionRegistry {
+        &self.sessions
+    }
+
+    fn encode_claims(&self, claims: &Claims) -> JwtResult<String> {
+        let header = Header::new(self.key_manager.current().algorithm());
+        encode(&header, claims, self.key_manager.current().encoding_key())
+            .map_err(|e| JwtError::EncodingError(e.to_string()))
+    }
+
     /// Get the blacklist reference (for cleanup tasks)
     pub fn blacklist(&self) -> &TokenBlacklist {
         &self.blacklist
@@ -455,6 +528,36 @@ mod tests {
         assert!(matches!(result, Err(JwtError::Revoked)));
     }
 
+    #[tokio::test]
+    async fn test_impersonation_session_is_listed_and_revocable() {
+        let service = create_test_service();
+        let (token, session) = service
+            .generate_impersonation_token(
+                "user123".to_string(),
+                vec!["user".to_string()],
+                vec!["EventRead".to_string()],
+                "admin1".to_string(),
+                "ticket 42".to_string(),
+                chrono::Duration::minutes(10),
+            )
+            .unwrap();
+
+        let claims = service.validate_token(&token).await.unwrap();
+        assert_eq!(claims.sub, "user123");
+        assert_eq!(claims.actor_id(), Some("admin1"));
+        assert_eq!(claims.jti, session.id);
+
+        let listed = service.sessions().list(Some("user123"));
+        assert_eq!(listed, vec![session.clone()]);
+        assert!(listed[0].is_impersonation());
+
+        let revoked = service.revoke_session(&session.id).await.unwrap().unwrap();
+        assert!(revoked.revoked_at.is_some());
+        let result = service.validate_token(&token).await;
+        assert!(matches!(result, Err(JwtError::Revoked)));
+        assert!(service.revoke_session("unknown").await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_token_rotation_on_refresh() {
         let service = create_test_service();
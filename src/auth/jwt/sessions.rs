@@ -0,0 +1,213 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Session Registry
+//!
+//! This module keeps a record of the access tokens this instance issued so
+//! they can be listed and revoked by ID. Impersonation sessions carry the
+//! administrator and the stated reason, so the impersonated user can see
+//! that someone acted as them.
+//!
+//! Like the blacklist, the registry is in memory; it is shared by every
+//! clone of the JWT service. Tokens are recorded as they are signed, so
+//! the registry locks synchronously.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::{debug, instrument};
+
+use super::claims::Claims;
+
+/// An issued access token
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Session {
+    /// JWT ID of the token
+    pub id: String,
+    /// User the token authorizes as
+    pub user_id: String,
+    /// Administrator holding the token, for impersonation sessions
+    pub actor_id: Option<String>,
+    /// Why the administrator impersonated the user
+    pub reason: Option<String>,
+    /// When the token was issued
+    pub issued_at: DateTime<Utc>,
+    /// When the token expires
+    pub expires_at: DateTime<Utc>,
+    /// When the token was revoked, if it was
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    /// Describes the token carrying `claims`
+    pub fn from_claims(claims: &Claims) -> Self {
+        let at = |ts: i64| DateTime::from_timestamp(ts, 0).unwrap_or_else(Utc::now);
+        Self {
+            id: claims.jti.clone(),
+            user_id: claims.sub.clone(),
+            actor_id: claims.actor_id().map(str::to_string),
+            reason: None,
+            issued_at: at(claims.iat),
+            expires_at: at(claims.exp),
+            revoked_at: None,
+        }
+    }
+
+    /// Sets the impersonation reason
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Check if an administrator holds the token as the user
+    pub fn is_impersonation(&self) -> bool {
+        self.actor_id.is_some()
+    }
+
+    /// Check if the token still authenticates at `now`
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+/// Registry of issued access tokens, keyed by JWT ID
+#[derive(Clone, Default)]
+pub struct SessionRegistry {
+    sessions: Arc<RwLock<HashMap<String, Session>>>,
+}
+
+impl SessionRegistry {
+    /// Create a new empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an issued token
+    #[instrument(skip(self, session), fields(jti = %session.id))]
+    pub fn record(&self, session: Session) {
+        self.sessions
+            .write()
+            .unwrap()
+            .insert(session.id.clone(), session);
+    }
+
+    /// Get a session by JWT ID
+    pub fn get(&self, id: &str) -> Option<Session> {
+        self.sessions.read().unwrap().get(id).cloned()
+    }
+
+    /// List sessions newest first, optionally only those authorizing as
+    /// `user_id`
+    pub fn list(&self, user_id: Option<&str>) -> Vec<Session> {
+        let mut sessions: Vec<Session> = self
+            .sessions
+            .read()
+            .unwrap()
+            .values()
+            .filter(|session| match user_id {
+                Some(user_id) => session.user_id == user_id,
+                None => true,
+            })
+            .cloned()
+            .collect();
+        sessions.sort_by(|a, b| b.issued_at.cmp(&a.issued_at).then(b.id.cmp(&a.id)));
+        sessions
+    }
+
+    /// Mark a session revoked at `at`
+    ///
+    /// Returns the session, or `None` if no token with this ID was issued.
+    /// Revoking twice keeps the first revocation time.
+    pub fn mark_revoked(&self, id: &str, at: DateTime<Utc>) -> Option<Session> {
+        let mut sessions = self.sessions.write().unwrap();
+        let session = sessions.get_mut(id)?;
+        session.revoked_at.get_or_insert(at);
+        Some(session.clone())
+    }
+
+    /// Remove sessions that expired before `cutoff`
+    ///
+    /// Returns the number of sessions removed.
+    pub fn cleanup_expired(&self, cutoff: DateTime<Utc>) -> usize {
+        let mut sessions = self.sessions.write().unwrap();
+        let before = sessions.len();
+        sessions.retain(|_, session| session.expires_at >= cutoff);
+        let removed = before - sessions.len();
+        if removed > 0 {
+            debug!(removed, "Removed expired sessions");
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn claims(sub: &str, actor: Option<&str>) -> Claims {
+        let (issuer, audience, expiration) = (
+            "xzepr".to_string(),
+            "xzepr-api".to_string(),
+            Duration::minutes(15),
+        );
+        match actor {
+            Some(actor) => Claims::new_impersonation_token(
+                sub.to_string(),
+                vec![],
+                vec![],
+                actor.to_string(),
+                issuer,
+                audience,
+                expiration,
+            ),
+            None => Claims::new_access_token(
+                sub.to_string(),
+                vec![],
+                vec![],
+                issuer,
+                audience,
+                expiration,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_list_filters_by_user_and_marks_impersonation() {
+        let registry = SessionRegistry::new();
+        registry.record(Session::from_claims(&claims("alice", None)));
+        registry
+            .record(Session::from_claims(&claims("alice", Some("admin"))).with_reason("ticket 42"));
+        registry.record(Session::from_claims(&claims("bob", None)));
+
+        assert_eq!(registry.list(None).len(), 3);
+        let alice = registry.list(Some("alice"));
+        assert_eq!(alice.len(), 2);
+        let impersonation = alice.iter().find(|s| s.is_impersonation()).unwrap();
+        assert_eq!(impersonation.actor_id.as_deref(), Some("admin"));
+        assert_eq!(impersonation.reason.as_deref(), Some("ticket 42"));
+    }
+
+    #[test]
+    fn test_revocation_and_cleanup() {
+        let registry = SessionRegistry::new();
+        let session = Session::from_claims(&claims("alice", None));
+        let id = session.id.clone();
+        registry.record(session);
+
+        let now = Utc::now();
+        let revoked = registry.mark_revoked(&id, now).unwrap();
+        assert_eq!(revoked.revoked_at, Some(now));
+        assert!(!revoked.is_active_at(now));
+        let again = registry
+            .mark_revoked(&id, now + Duration::minutes(1))
+            .unwrap();
+        assert_eq!(again.revoked_at, Some(now));
+        assert!(registry.mark_revoked("unknown", now).is_none());
+
+        assert_eq!(registry.cleanup_expired(now), 0);
+        assert_eq!(registry.cleanup_expired(now + Duration::hours(1)), 1);
+        assert!(registry.get(&id).is_none());
+    }
+}
@@ -4,8 +4,10 @@
 // Generated mod file
 
 pub mod api_key;
+pub mod api_key_usage;
 pub mod jwt;
 pub mod local;
 pub mod oidc;
 pub mod provisioning;
 pub mod rbac;
+pub mod sessions;
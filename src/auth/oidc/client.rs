@@ -9,16 +9,20 @@
 use openidconnect::core::{
     CoreAuthenticationFlow, CoreClient, CoreIdTokenClaims, CoreProviderMetadata,
 };
-use openidconnect::reqwest::async_http_client;
+use openidconnect::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
 use openidconnect::{
-    AuthorizationCode, ClientId, ClientSecret, CsrfToken, IssuerUrl, Nonce, OAuth2TokenResponse,
-    PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, RefreshToken, Scope, TokenResponse,
+    AuthorizationCode, ClientId, ClientSecret, CsrfToken, HttpRequest, HttpResponse, IssuerUrl,
+    Nonce, OAuth2TokenResponse, PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, RefreshToken,
+    Scope, TokenResponse,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
 use thiserror::Error;
 
 use super::config::OidcConfig;
+use crate::infrastructure::deadline;
+use crate::infrastructure::http_client::HttpClientFactory;
 
 /// Errors that can occur during OIDC operations
 #[derive(Error, Debug)]
@@ -137,6 +141,12 @@ pub struct OidcClient {
 
     /// Provider metadata
     provider_metadata: CoreProviderMetadata,
+
+    /// HTTP client for discovery and token requests
+    http_client: reqwest::Client,
+
+    /// Request timeout, shortened to the request deadline per call
+    request_timeout: Duration,
 }
 
 impl OidcClient {
@@ -175,6 +185,29 @@ impl OidcClient {
     /// # }
     /// ```
     pub async fn new(config: OidcConfig) -> Result<Self, OidcError> {
+        Self::with_http_client_factory(config, &HttpClientFactory::default()).await
+    }
+
+    /// Create a new OIDC client using the shared outbound HTTP settings
+    ///
+    /// Discovery and token requests go through the proxy and trust the CA
+    /// bundle configured on `factory`. Redirects are not followed.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`OidcClient::new`], and
+    /// [`OidcError::Http`] if the HTTP client cannot be built
+    pub async fn with_http_client_factory(
+        config: OidcConfig,
+        factory: &HttpClientFactory,
+    ) -> Result<Self, OidcError> {
+        let http_client = factory
+            .builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| OidcError::Http(e.to_string()))?;
+        let request_timeout = factory.request_timeout();
+
         config
             .validate()
             .map_err(|e| OidcError::Config(e.to_string()))?;
@@ -182,9 +215,11 @@ impl OidcClient {
         let issuer_url = IssuerUrl::new(config.issuer_url.clone())
             .map_err(|e| OidcError::Config(format!("Invalid issuer URL: {}", e)))?;
 
-        let provider_metadata = CoreProviderMetadata::discover_async(issuer_url, async_http_client)
-            .await
-            .map_err(|e| OidcError::Discovery(e.to_string()))?;
+        let provider_metadata = CoreProviderMetadata::discover_async(issuer_url, |request| {
+            send_request(&http_client, request_timeout, request)
+        })
+        .await
+        .map_err(|e| OidcError::Discovery(e.to_string()))?;
 
         let redirect_url = RedirectUrl::new(config.redirect_url.clone())
             .map_err(|e| OidcError::Config(format!("Invalid redirect URL: {}", e)))?;
@@ -192,7 +227,7 @@ impl OidcClient {
         let client = CoreClient::from_provider_metadata(
             provider_metadata.clone(),
             ClientId::new(config.client_id.clone()),
-            Some(ClientSecret::new(config.client_secret.clone())),
+            Some(ClientSecret::new(config.client_secret.expose().clone())),
         )
         .set_redirect_uri(redirect_url);
 
@@ -200,6 +235,8 @@ impl OidcClient {
             config,
             client,
             provider_metadata,
+            http_client,
+            request_timeout,
         })
     }
 
@@ -320,7 +357,7 @@ impl OidcClient {
         }
 
         let token_response = token_request
-            .request_async(async_http_client)
+            .request_async(|request| send_request(&self.http_client, self.request_timeout, request))
             .await
             .map_err(|e| OidcError::TokenExchange(e.to_string()))?;
 
@@ -379,7 +416,7 @@ impl OidcClient {
         let token_response = self
             .client
             .exchange_refresh_token(&RefreshToken::new(refresh_token))
-            .request_async(async_http_client)
+            .request_async(|request| send_request(&self.http_client, self.request_timeout, request))
             .await
             .map_err(|e| OidcError::RefreshFailed(e.to_string()))?;
 
@@ -526,10 +563,101 @@ impl OidcClient {
     }
 }
 
+/// Sends an OAuth2 request with the shared reqwest client
+///
+/// `openidconnect` ships an adapter for an older reqwest; this one converts
+/// between its `http` 0.2 types and the client built by
+/// [`HttpClientFactory`]. Inside a request, `timeout` is shortened to the
+/// time left before its deadline.
+async fn send_request(
+    client: &reqwest::Client,
+    timeout: Duration,
+    request: HttpRequest,
+) -> Result<HttpResponse, OidcError> {
+    let method = reqwest::Method::from_bytes(request.method.as_str().as_bytes())
+        .map_err(|e| OidcError::Http(e.to_string()))?;
+    let mut builder = client.request(method, request.url.as_str());
+    for (name, value) in &request.headers {
+        builder = builder.header(name.as_str(), value.as_bytes());
+    }
+
+    let response = builder
+        .body(request.body)
+        .timeout(deadline::capped_timeout(timeout))
+        .send()
+        .await
+        .map_err(|e| OidcError::Http(e.to_string()))?;
+
+    let status_code = StatusCode::from_u16(response.status().as_u16())
+        .map_err(|e| OidcError::Http(e.to_string()))?;
+    let mut headers = HeaderMap::new();
+    for (name, value) in response.headers() {
+        let name = HeaderName::from_bytes(name.as_str().as_bytes())
+            .map_err(|e| OidcError::Http(e.to_string()))?;
+        let value = HeaderValue::from_bytes(value.as_bytes())
+            .map_err(|e| OidcError::Http(e.to_string()))?;
+        headers.append(name, value);
+    }
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| OidcError::Http(e.to_string()))?
+        .to_vec();
+
+    Ok(HttpResponse {
+        status_code,
+        headers,
+        body,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_send_request_round_trips_through_shared_client() {
+        use axum::{http::HeaderMap as AxumHeaderMap, routing::post, Router};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().route(
+            "/token",
+            post(|headers: AxumHeaderMap, body: String| async move {
+                let accept = headers
+                    .get("accept")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                (
+                    axum::http::StatusCode::CREATED,
+                    [("x-echo-accept", accept)],
+                    body,
+                )
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut headers = HeaderMap::new();
+        headers.insert("accept", HeaderValue::from_static("application/json"));
+        let request = HttpRequest {
+            url: format!("http://{}/token", addr).parse().unwrap(),
+            method: openidconnect::http::Method::POST,
+            headers,
+            body: b"grant_type=refresh_token".to_vec(),
+        };
+
+        let factory = HttpClientFactory::default();
+        let client = factory.client().unwrap();
+        let response = send_request(&client, factory.request_timeout(), request)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status_code, StatusCode::CREATED);
+        assert_eq!(response.headers["x-echo-accept"], "application/json");
+        assert_eq!(response.body, b"grant_type=refresh_token");
+    }
+
     #[test]
     fn test_oidc_claims_serialization() {
         let claims = OidcClaims {
@@ -8,6 +8,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::infrastructure::config::Secret;
+
 /// OIDC client configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OidcConfig {
@@ -21,8 +23,9 @@ pub struct OidcConfig {
     /// OAuth2 client ID
     pub client_id: String,
 
-    /// OAuth2 client secret
-    pub client_secret: String,
+    /// OAuth2 client secret; never serialized
+    #[serde(skip_serializing)]
+    pub client_secret: Secret<String>,
 
     /// Redirect URL for OIDC callback (e.g., https://app.example.com/api/v1/auth/oidc/callback)
     pub redirect_url: String,
@@ -77,7 +80,7 @@ impl OidcConfig {
             enabled: true,
             issuer_url,
             client_id,
-            client_secret,
+            client_secret: Secret::new(client_secret),
             redirect_url,
             scopes: default_scopes(),
             role_claim_path: default_role_claim(),
@@ -117,7 +120,7 @@ impl OidcConfig {
             enabled: true,
             issuer_url: realm_url,
             client_id,
-            client_secret,
+            client_secret: Secret::new(client_secret),
             redirect_url,
             scopes: vec![
                 "openid".to_string(),
@@ -220,7 +223,7 @@ impl Default for OidcConfig {
             enabled: false,
             issuer_url: String::new(),
             client_id: String::new(),
-            client_secret: String::new(),
+            client_secret: Secret::default(),
             redirect_url: String::new(),
             scopes: default_scopes(),
             role_claim_path: default_role_claim(),
@@ -368,7 +371,7 @@ mod tests {
             "secret-at-least-16-chars".to_string(),
             "https://app.example.com/callback".to_string(),
         );
-        config.client_secret = String::new();
+        config.client_secret = Secret::default();
 
         let result = config.validate();
         assert!(result.is_err());
@@ -385,7 +388,7 @@ mod tests {
             "secret-at-least-16-chars".to_string(),
             "https://app.example.com/callback".to_string(),
         );
-        config.client_secret = "short".to_string();
+        config.client_secret = Secret::new("short".to_string());
 
         let result = config.validate();
         assert!(result.is_err());
@@ -467,6 +470,7 @@ mod tests {
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("keycloak.example.com"));
         assert!(json.contains("xzepr-client"));
+        assert!(!json.contains("secret-at-least-16-chars"));
     }
 
     #[test]
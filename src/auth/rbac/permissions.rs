@@ -16,18 +16,24 @@ pub enum Permission {
     EventRead,
     EventUpdate,
     EventDelete,
+    EventReadMeta,
+    EventReadPayload,
 
     // Receiver permissions
     ReceiverCreate,
     ReceiverRead,
     ReceiverUpdate,
     ReceiverDelete,
+    ReceiverReadSchema,
+    ReceiverReadMeta,
 
     // Group permissions
     GroupCreate,
     GroupRead,
     GroupUpdate,
     GroupDelete,
+    /// Create, list, and revoke the API keys scoped to a group
+    GroupManageKeys,
 
     // Admin permissions
     UserManage,
@@ -41,14 +47,19 @@ impl Permission {
             ("event", "read") => Some(Permission::EventRead),
             ("event", "update") => Some(Permission::EventUpdate),
             ("event", "delete") => Some(Permission::EventDelete),
+            ("event", "read_meta") => Some(Permission::EventReadMeta),
+            ("event", "read_payload") => Some(Permission::EventReadPayload),
             ("receiver", "create") => Some(Permission::ReceiverCreate),
             ("receiver", "read") => Some(Permission::ReceiverRead),
             ("receiver", "update") => Some(Permission::ReceiverUpdate),
             ("receiver", "delete") => Some(Permission::ReceiverDelete),
+            ("receiver", "read_schema") => Some(Permission::ReceiverReadSchema),
+            ("receiver", "read_meta") => Some(Permission::ReceiverReadMeta),
             ("group", "create") => Some(Permission::GroupCreate),
             ("group", "read") => Some(Permission::GroupRead),
             ("group", "update") => Some(Permission::GroupUpdate),
             ("group", "delete") => Some(Permission::GroupDelete),
+            ("group", "manage_keys") => Some(Permission::GroupManageKeys),
             _ => None,
         }
     }
@@ -82,6 +93,36 @@ mod tests {
         assert_eq!(perm, Some(Permission::EventDelete));
     }
 
+    #[test]
+    fn test_permission_event_read_meta() {
+        let perm = Permission::from_action("event", "read_meta");
+        assert_eq!(perm, Some(Permission::EventReadMeta));
+    }
+
+    #[test]
+    fn test_permission_event_read_payload() {
+        let perm = Permission::from_action("event", "read_payload");
+        assert_eq!(perm, Some(Permission::EventReadPayload));
+    }
+
+    #[test]
+    fn test_permission_receiver_read_schema() {
+        let perm = Permission::from_action("receiver", "read_schema");
+        assert_eq!(perm, Some(Permission::ReceiverReadSchema));
+    }
+
+    #[test]
+    fn test_permission_receiver_read_meta() {
+        let perm = Permission::from_action("receiver", "read_meta");
+        assert_eq!(perm, Some(Permission::ReceiverReadMeta));
+    }
+
+    #[test]
+    fn test_permission_group_manage_keys() {
+        let perm = Permission::from_action("group", "manage_keys");
+        assert_eq!(perm, Some(Permission::GroupManageKeys));
+    }
+
     #[test]
     fn test_permission_receiver_create() {
         let perm = Permission::from_action("receiver", "create");
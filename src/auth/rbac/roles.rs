@@ -19,6 +19,14 @@ pub enum Role {
 }
 
 impl Role {
+    /// Every built-in role
+    pub const ALL: [Role; 4] = [
+        Role::Admin,
+        Role::EventManager,
+        Role::EventViewer,
+        Role::User,
+    ];
+
     pub fn permissions(&self) -> Vec<Permission> {
         match self {
             Role::Admin => vec![
@@ -26,14 +34,19 @@ impl Role {
                 Permission::EventRead,
                 Permission::EventUpdate,
                 Permission::EventDelete,
+                Permission::EventReadMeta,
+                Permission::EventReadPayload,
                 Permission::ReceiverCreate,
                 Permission::ReceiverRead,
                 Permission::ReceiverUpdate,
                 Permission::ReceiverDelete,
+                Permission::ReceiverReadSchema,
+                Permission::ReceiverReadMeta,
                 Permission::GroupCreate,
                 Permission::GroupRead,
                 Permission::GroupUpdate,
                 Permission::GroupDelete,
+                Permission::GroupManageKeys,
                 Permission::UserManage,
                 Permission::RoleManage,
             ],
@@ -41,12 +54,15 @@ impl Role {
                 Permission::EventCreate,
                 Permission::EventRead,
                 Permission::EventUpdate,
+                Permission::EventReadPayload,
                 Permission::ReceiverCreate,
                 Permission::ReceiverRead,
                 Permission::ReceiverUpdate,
+                Permission::ReceiverReadSchema,
                 Permission::GroupCreate,
                 Permission::GroupRead,
                 Permission::GroupUpdate,
+                Permission::GroupManageKeys,
             ],
             Role::EventViewer => vec![
                 Permission::EventRead,
@@ -100,10 +116,14 @@ mod tests {
         assert!(perms.contains(&Permission::EventRead));
         assert!(perms.contains(&Permission::EventUpdate));
         assert!(perms.contains(&Permission::EventDelete));
+        assert!(perms.contains(&Permission::EventReadMeta));
+        assert!(perms.contains(&Permission::EventReadPayload));
         assert!(perms.contains(&Permission::ReceiverCreate));
         assert!(perms.contains(&Permission::ReceiverRead));
         assert!(perms.contains(&Permission::ReceiverUpdate));
         assert!(perms.contains(&Permission::ReceiverDelete));
+        assert!(perms.contains(&Permission::ReceiverReadSchema));
+        assert!(perms.contains(&Permission::ReceiverReadMeta));
         assert!(perms.contains(&Permission::GroupCreate));
         assert!(perms.contains(&Permission::GroupRead));
         assert!(perms.contains(&Permission::GroupUpdate));
@@ -121,10 +141,14 @@ mod tests {
         assert!(perms.contains(&Permission::EventRead));
         assert!(perms.contains(&Permission::EventUpdate));
         assert!(!perms.contains(&Permission::EventDelete));
+        assert!(!perms.contains(&Permission::EventReadMeta));
+        assert!(perms.contains(&Permission::EventReadPayload));
         assert!(perms.contains(&Permission::ReceiverCreate));
         assert!(perms.contains(&Permission::ReceiverRead));
         assert!(perms.contains(&Permission::ReceiverUpdate));
         assert!(!perms.contains(&Permission::ReceiverDelete));
+        assert!(perms.contains(&Permission::ReceiverReadSchema));
+        assert!(!perms.contains(&Permission::ReceiverReadMeta));
         assert!(perms.contains(&Permission::GroupCreate));
         assert!(perms.contains(&Permission::GroupRead));
         assert!(perms.contains(&Permission::GroupUpdate));
@@ -142,8 +166,10 @@ mod tests {
         assert!(perms.contains(&Permission::EventRead));
         assert!(!perms.contains(&Permission::EventUpdate));
         assert!(!perms.contains(&Permission::EventDelete));
+        assert!(!perms.contains(&Permission::EventReadPayload));
         assert!(!perms.contains(&Permission::ReceiverCreate));
         assert!(perms.contains(&Permission::ReceiverRead));
+        assert!(!perms.contains(&Permission::ReceiverReadSchema));
         assert!(!perms.contains(&Permission::ReceiverUpdate));
         assert!(!perms.contains(&Permission::ReceiverDelete));
         assert!(!perms.contains(&Permission::GroupCreate));
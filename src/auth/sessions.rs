@@ -0,0 +1,544 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/auth/sessions.rs
+
+//! Session listing, revocation, and admin impersonation
+//!
+//! An administrator can obtain a short-lived token that authorizes exactly
+//! as another user, to reproduce what that user sees. The token's `act`
+//! claim names the administrator, so audit events and ingestion metadata
+//! produced with it stay attributable. Impersonation tokens cannot mint
+//! further tokens, change passwords, or manage API keys; the RBAC middleware
+//! refuses those requests.
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::auth::api_key::UserRepository;
+use crate::auth::jwt::{Claims, JwtError, JwtService, Session};
+use crate::domain::value_objects::UserId;
+use crate::error::AuthError;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::jobs::{Job, JobReport, JobSchedule};
+
+/// Role allowed to impersonate users and manage every session
+pub const IMPERSONATION_ROLE: &str = "admin";
+
+/// Impersonation token lifetime when the request does not set one
+pub const DEFAULT_IMPERSONATION_SECONDS: i64 = 15 * 60;
+
+/// Longest impersonation token lifetime when none is configured
+pub const DEFAULT_MAX_IMPERSONATION_SECONDS: i64 = 60 * 60;
+
+/// Default time between removals of expired sessions and revocations
+pub const DEFAULT_SESSION_CLEANUP_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(300);
+
+/// Name of the session cleanup job in settings and the admin API
+pub const SESSION_CLEANUP_JOB_NAME: &str = "session_cleanup";
+
+/// Errors from session and impersonation requests
+#[derive(Error, Debug)]
+pub enum SessionError {
+    /// Caller lacks the admin role
+    #[error("Admin role required")]
+    NotAdmin,
+
+    /// Caller holds an impersonation token
+    #[error("Not allowed while impersonating a user")]
+    Impersonating,
+
+    /// No reason was given for an impersonation
+    #[error("A reason is required to impersonate a user")]
+    ReasonRequired,
+
+    /// Requested token lifetime is out of range
+    #[error("Duration must be between 1 and {max_seconds} seconds")]
+    InvalidDuration { max_seconds: i64 },
+
+    /// Administrators cannot impersonate themselves
+    #[error("Cannot impersonate yourself")]
+    SelfImpersonation,
+
+    /// Target user does not exist
+    #[error("User not found")]
+    UserNotFound,
+
+    /// Target user is disabled
+    #[error("User account disabled")]
+    UserDisabled,
+
+    /// Session does not exist or belongs to another user
+    #[error("Session not found")]
+    SessionNotFound,
+
+    /// User lookup failed
+    #[error(transparent)]
+    Repository(#[from] AuthError),
+
+    /// Token could not be issued or revoked
+    #[error(transparent)]
+    Token(#[from] JwtError),
+}
+
+/// An impersonation token and its session
+#[derive(Debug, Clone)]
+pub struct Impersonation {
+    /// Encoded token authorizing as the impersonated user
+    pub token: String,
+    /// Session of the token, marked as impersonation
+    pub session: Session,
+}
+
+/// Issues impersonation tokens and lists and revokes sessions
+pub struct SessionService {
+    jwt_service: Arc<JwtService>,
+    user_repo: Arc<dyn UserRepository>,
+    max_impersonation: Duration,
+    audit_logger: Arc<AuditLogger>,
+    cleanup_interval: std::time::Duration,
+}
+
+impl SessionService {
+    /// Creates a service issuing tokens through `jwt_service`
+    ///
+    /// The JWT middleware must validate with a clone of the same service,
+    /// so revocations made here take effect there.
+    pub fn new(jwt_service: Arc<JwtService>, user_repo: Arc<dyn UserRepository>) -> Self {
+        Self {
+            jwt_service,
+            user_repo,
+            max_impersonation: Duration::seconds(DEFAULT_MAX_IMPERSONATION_SECONDS),
+            audit_logger: Arc::new(AuditLogger::new()),
+            cleanup_interval: DEFAULT_SESSION_CLEANUP_INTERVAL,
+        }
+    }
+
+    /// Sets the longest lifetime of an impersonation token
+    pub fn with_max_impersonation(mut self, max: Duration) -> Self {
+        self.max_impersonation = max;
+        self
+    }
+
+    /// Uses `audit_logger` to record impersonations and revocations
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Sets the time between cleanup passes when run as a job
+    pub fn with_cleanup_interval(mut self, interval: std::time::Duration) -> Self {
+        self.cleanup_interval = interval;
+        self
+    }
+
+    /// Issues a token authorizing as `target` on behalf of `admin`
+    ///
+    /// The token carries the target's current roles and permissions. The
+    /// start is audit-logged with both identities and the reason.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless `admin` is an administrator holding an ordinary token,
+    /// `reason` is not blank, `duration` is within the configured maximum,
+    /// and `target` is another, enabled user.
+    pub async fn impersonate(
+        &self,
+        admin: &Claims,
+        target: UserId,
+        reason: &str,
+        duration: Option<Duration>,
+    ) -> Result<Impersonation, SessionError> {
+        if admin.is_impersonation() {
+            return Err(SessionError::Impersonating);
+        }
+        if !admin.has_role(IMPERSONATION_ROLE) {
+            return Err(SessionError::NotAdmin);
+        }
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(SessionError::ReasonRequired);
+        }
+        let duration = duration.unwrap_or_else(|| Duration::seconds(DEFAULT_IMPERSONATION_SECONDS));
+        if duration <= Duration::zero() || duration > self.max_impersonation {
+            return Err(SessionError::InvalidDuration {
+                max_seconds: self.max_impersonation.num_seconds(),
+            });
+        }
+        if admin.sub == target.to_string() {
+            return Err(SessionError::SelfImpersonation);
+        }
+
+        let user = self
+            .user_repo
+            .find_by_id(target)
+            .await?
+            .ok_or(SessionError::UserNotFound)?;
+        if !user.enabled() {
+            return Err(SessionError::UserDisabled);
+        }
+
+        let roles = user.roles().iter().map(ToString::to_string).collect();
+        let permissions = user
+            .roles()
+            .iter()
+            .flat_map(|role| role.permissions())
+            .map(|permission| format!("{:?}", permission))
+            .collect();
+        let (token, session) = self.jwt_service.generate_impersonation_token(
+            user.id().to_string(),
+            roles,
+            permissions,
+            admin.sub.clone(),
+            reason.to_string(),
+            duration,
+        )?;
+
+        info!(
+            actor_id = %admin.sub,
+            user_id = %session.user_id,
+            session_id = %session.id,
+            expires_at = %session.expires_at,
+            "Started impersonation"
+        );
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(&session.user_id)
+                .actor_id(&admin.sub)
+                .action(AuditAction::ImpersonationStart)
+                .resource(format!("session:{}", session.id))
+                .outcome(AuditOutcome::Success)
+                .session_id(&session.id)
+                .add_metadata("reason", reason)
+                .add_metadata("expires_at", session.expires_at.to_rfc3339())
+                .build(),
+        );
+
+        Ok(Impersonation { token, session })
+    }
+
+    /// Lists the sessions authorizing as `user_id`, newest first
+    ///
+    /// Includes impersonations of the user, so they can see that an
+    /// administrator acted as them.
+    pub fn sessions_for(&self, user_id: &str) -> Vec<Session> {
+        self.jwt_service.sessions().list(Some(user_id))
+    }
+
+    /// Lists every session, newest first; administrators only
+    pub fn all_sessions(&self, caller: &Claims) -> Result<Vec<Session>, SessionError> {
+        if !caller.has_role(IMPERSONATION_ROLE) {
+            return Err(SessionError::NotAdmin);
+        }
+        Ok(self.jwt_service.sessions().list(None))
+    }
+
+    /// Revokes session `id`
+    ///
+    /// Administrators may revoke any session, other users only their own,
+    /// which includes impersonations of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SessionError::SessionNotFound`] for unknown sessions and
+    /// for sessions the caller may not revoke, so other users' session IDs
+    /// are not disclosed.
+    pub async fn revoke(&self, caller: &Claims, id: &str) -> Result<Session, SessionError> {
+        let session = self
+            .jwt_service
+            .sessions()
+            .get(id)
+            .ok_or(SessionError::SessionNotFound)?;
+        if session.user_id != caller.sub && !caller.has_role(IMPERSONATION_ROLE) {
+            warn!(user_id = %caller.sub, session_id = %id, "Session revocation denied");
+            return Err(SessionError::SessionNotFound);
+        }
+
+        let session = self
+            .jwt_service
+            .revoke_session(id)
+            .await?
+            .ok_or(SessionError::SessionNotFound)?;
+
+        info!(
+            user_id = %caller.sub,
+            session_id = %id,
+            impersonation = session.is_impersonation(),
+            "Revoked session"
+        );
+        let mut audit = AuditEvent::builder()
+            .user_id(&caller.sub)
+            .action(AuditAction::SessionRevoke)
+            .resource(format!("session:{}", session.id))
+            .outcome(AuditOutcome::Success)
+            .session_id(&session.id)
+            .add_metadata("session_user_id", &session.user_id);
+        if let Some(actor_id) = &session.actor_id {
+            audit = audit.add_metadata("session_actor_id", actor_id);
+        }
+        self.audit_logger.log_event(audit.build());
+
+        Ok(session)
+    }
+}
+
+/// Forgets expired sessions and revocations of expired tokens
+///
+/// Every issued access token is recorded, so without this job the
+/// registry grows for the life of the process.
+#[async_trait]
+impl Job for SessionService {
+    fn name(&self) -> &str {
+        SESSION_CLEANUP_JOB_NAME
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::Every(self.cleanup_interval)
+    }
+
+    async fn run(&self) -> crate::error::Result<JobReport> {
+        let sessions = self.jwt_service.sessions().cleanup_expired(Utc::now());
+        let revocations = self.jwt_service.blacklist().cleanup_expired().await;
+        if sessions + revocations > 0 {
+            debug!(sessions, revocations, "Removed expired sessions");
+        }
+        Ok(JobReport {
+            processed: sessions + revocations,
+            more_pending: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::jwt::JwtConfig;
+    use crate::auth::rbac::Role;
+    use crate::domain::entities::user::User;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+
+    struct Users(HashMap<UserId, User>);
+
+    #[async_trait]
+    impl UserRepository for Users {
+        async fn find_by_id(&self, id: UserId) -> Result<Option<User>, AuthError> {
+            Ok(self.0.get(&id).cloned())
+        }
+
+        async fn find_by_username(&self, _username: &str) -> Result<Option<User>, AuthError> {
+            Ok(None)
+        }
+
+        async fn save(&self, _user: &User) -> Result<(), AuthError> {
+            Ok(())
+        }
+
+        async fn find_all(&self) -> Result<Vec<User>, AuthError> {
+            Ok(self.0.values().cloned().collect())
+        }
+
+        async fn add_role(&self, _user_id: &UserId, _role: Role) -> Result<(), AuthError> {
+            Ok(())
+        }
+
+        async fn remove_role(&self, _user_id: &UserId, _role: Role) -> Result<(), AuthError> {
+            Ok(())
+        }
+    }
+
+    fn service_with(user: &User) -> (SessionService, Arc<JwtService>) {
+        let jwt = Arc::new(JwtService::from_config(JwtConfig::development()).unwrap());
+        let users = Users(HashMap::from([(*user.id(), user.clone())]));
+        (SessionService::new(jwt.clone(), Arc::new(users)), jwt)
+    }
+
+    fn admin_claims() -> Claims {
+        Claims::new_access_token(
+            UserId::new().to_string(),
+            vec!["admin".to_string()],
+            vec![],
+            "xzepr-dev".to_string(),
+            "xzepr-api-dev".to_string(),
+            Duration::minutes(15),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_impersonation_token_authorizes_as_target() {
+        let target = User::new_oidc(
+            "viewer".to_string(),
+            "viewer@example.com".to_string(),
+            "oidc-viewer".to_string(),
+        );
+        let (service, jwt) = service_with(&target);
+        let admin = admin_claims();
+
+        let issued = service
+            .impersonate(&admin, *target.id(), " ticket 42 ", None)
+            .await
+            .unwrap();
+        let claims = jwt.validate_token(&issued.token).await.unwrap();
+
+        assert_eq!(claims.sub, target.id().to_string());
+        assert_eq!(claims.actor_id(), Some(admin.sub.as_str()));
+        let expected_roles: Vec<String> = target.roles().iter().map(ToString::to_string).collect();
+        assert_eq!(claims.roles, expected_roles);
+        assert!(!claims.has_role("admin"));
+        assert_eq!(issued.session.reason.as_deref(), Some("ticket 42"));
+        assert!(
+            issued.session.expires_at - issued.session.issued_at
+                <= Duration::seconds(DEFAULT_IMPERSONATION_SECONDS)
+        );
+
+        // The impersonated user sees the impersonation among their sessions
+        let visible = service.sessions_for(&target.id().to_string());
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].actor_id.as_deref(), Some(admin.sub.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_impersonation_preconditions() {
+        let target = User::new_oidc(
+            "viewer".to_string(),
+            "viewer@example.com".to_string(),
+            "oidc-viewer".to_string(),
+        );
+        let (service, jwt) = service_with(&target);
+        let admin = admin_claims();
+        let target_id = *target.id();
+
+        let mut user = admin.clone();
+        user.roles = vec!["user".to_string()];
+        assert!(matches!(
+            service.impersonate(&user, target_id, "debug", None).await,
+            Err(SessionError::NotAdmin)
+        ));
+        assert!(matches!(
+            service.impersonate(&admin, target_id, "  ", None).await,
+            Err(SessionError::ReasonRequired)
+        ));
+        assert!(matches!(
+            service
+                .impersonate(&admin, target_id, "debug", Some(Duration::hours(2)))
+                .await,
+            Err(SessionError::InvalidDuration { max_seconds: 3600 })
+        ));
+        assert!(matches!(
+            service
+                .impersonate(&admin, UserId::new(), "debug", None)
+                .await,
+            Err(SessionError::UserNotFound)
+        ));
+
+        // An impersonation token cannot start another impersonation
+        let issued = service
+            .impersonate(&admin, target_id, "debug", None)
+            .await
+            .unwrap();
+        let impersonating = jwt.validate_token(&issued.token).await.unwrap();
+        let mut impersonating_admin = impersonating.clone();
+        impersonating_admin.roles.push("admin".to_string());
+        assert!(matches!(
+            service
+                .impersonate(&impersonating_admin, UserId::new(), "debug", None)
+                .await,
+            Err(SessionError::Impersonating)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_impersonated_user_can_revoke_the_impersonation() {
+        let target = User::new_oidc(
+            "viewer".to_string(),
+            "viewer@example.com".to_string(),
+            "oidc-viewer".to_string(),
+        );
+        let (service, jwt) = service_with(&target);
+        let issued = service
+            .impersonate(&admin_claims(), *target.id(), "debug", None)
+            .await
+            .unwrap();
+
+        let bystander = Claims::new_access_token(
+            UserId::new().to_string(),
+            vec!["user".to_string()],
+            vec![],
+            "xzepr-dev".to_string(),
+            "xzepr-api-dev".to_string(),
+            Duration::minutes(15),
+        );
+        assert!(matches!(
+            service.revoke(&bystander, &issued.session.id).await,
+            Err(SessionError::SessionNotFound)
+        ));
+
+        let owner = Claims {
+            sub: target.id().to_string(),
+            ..bystander
+        };
+        let revoked = service.revoke(&owner, &issued.session.id).await.unwrap();
+        assert!(revoked.revoked_at.is_some());
+        assert!(matches!(
+            jwt.validate_token(&issued.token).await,
+            Err(JwtError::Revoked)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_job_evicts_expired_sessions() {
+        use crate::infrastructure::config::JobsConfig;
+        use crate::infrastructure::jobs::JobRunner;
+
+        let target = User::new_oidc(
+            "viewer".to_string(),
+            "viewer@example.com".to_string(),
+            "oidc-viewer".to_string(),
+        );
+        let (service, jwt) = service_with(&target);
+        let live = jwt
+            .generate_access_token("alice".to_string(), vec![], vec![])
+            .unwrap();
+        let mut expired = Session::from_claims(&admin_claims());
+        expired.expires_at = Utc::now() - Duration::minutes(1);
+        jwt.sessions().record(expired.clone());
+        assert_eq!(jwt.sessions().list(None).len(), 2);
+
+        let runner = Arc::new(
+            JobRunner::new(&JobsConfig {
+                stagger_seconds: 0,
+                ..JobsConfig::default()
+            })
+            .register(Arc::new(service)),
+        );
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let handles = runner.start(shutdown_rx);
+        for _ in 0..50 {
+            if jwt.sessions().get(&expired.id).is_none() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        shutdown_tx.send(true).unwrap();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert!(jwt.sessions().get(&expired.id).is_none());
+        let remaining = jwt.sessions().list(None);
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].user_id, "alice");
+        assert!(jwt.validate_token(&live).await.is_ok());
+        assert_eq!(
+            runner
+                .status(SESSION_CLEANUP_JOB_NAME)
+                .unwrap()
+                .success_count,
+            1
+        );
+    }
+}
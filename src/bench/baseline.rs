@@ -0,0 +1,302 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/bench/baseline.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::bench::harness::ScenarioResult;
+use crate::bench::HarnessError;
+
+/// Version written to and expected in baseline files
+pub const BASELINE_FORMAT_VERSION: u32 = 1;
+
+/// Regression tolerated before a comparison fails, in percent
+pub const DEFAULT_MAX_REGRESSION_PERCENT: f64 = 10.0;
+
+/// Committed throughput of each scenario
+///
+/// Stored as JSON keyed by scenario name. Scenarios missing from the
+/// baseline are reported as new and fail a comparison, since nothing
+/// guards their throughput; an empty baseline fails every scenario until
+/// one is recorded on the machine that runs the checks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub format_version: u32,
+    /// Where the numbers were recorded, e.g. the CI runner type
+    #[serde(default)]
+    pub recorded_on: Option<String>,
+    #[serde(default)]
+    pub scenarios: BTreeMap<String, BaselineEntry>,
+}
+
+/// Recorded numbers of one scenario
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub events_per_sec: f64,
+    /// Informational; comparisons only check throughput
+    pub p99_micros: u64,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Self {
+            format_version: BASELINE_FORMAT_VERSION,
+            recorded_on: None,
+            scenarios: BTreeMap::new(),
+        }
+    }
+}
+
+impl Baseline {
+    /// Builds a baseline from the results of a run
+    pub fn from_results(results: &[ScenarioResult], recorded_on: Option<String>) -> Self {
+        Self {
+            format_version: BASELINE_FORMAT_VERSION,
+            recorded_on,
+            scenarios: results
+                .iter()
+                .map(|result| {
+                    (
+                        result.name.clone(),
+                        BaselineEntry {
+                            events_per_sec: result.events_per_sec,
+                            p99_micros: result.latency.p99_micros,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Reads a baseline file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HarnessError::Io`] if the file cannot be read,
+    /// [`HarnessError::Json`] if it is not a baseline, and
+    /// [`HarnessError::BaselineVersion`] for another format version.
+    pub fn load(path: &Path) -> Result<Self, HarnessError> {
+        let text = std::fs::read_to_string(path).map_err(|source| HarnessError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        let baseline: Baseline =
+            serde_json::from_str(&text).map_err(|source| HarnessError::Json {
+                path: path.display().to_string(),
+                source,
+            })?;
+        if baseline.format_version != BASELINE_FORMAT_VERSION {
+            return Err(HarnessError::BaselineVersion {
+                found: baseline.format_version,
+                expected: BASELINE_FORMAT_VERSION,
+            });
+        }
+        Ok(baseline)
+    }
+
+    /// Writes the baseline as pretty-printed JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HarnessError::Io`] if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), HarnessError> {
+        super::write_json(path, self)
+    }
+
+    /// Compares `results` with the baseline
+    ///
+    /// A scenario regresses when its throughput is more than
+    /// `max_regression_percent` below the recorded throughput.
+    pub fn compare(&self, results: &[ScenarioResult], max_regression_percent: f64) -> Comparison {
+        let scenarios = results
+            .iter()
+            .map(|result| {
+                let baseline = self
+                    .scenarios
+                    .get(&result.name)
+                    .map(|entry| entry.events_per_sec);
+                let change_percent = baseline
+                    .filter(|recorded| *recorded > 0.0)
+                    .map(|recorded| (result.events_per_sec - recorded) / recorded * 100.0);
+                let status = match change_percent {
+                    None => ComparisonStatus::New,
+                    Some(change) if change < -max_regression_percent => ComparisonStatus::Regressed,
+                    Some(_) => ComparisonStatus::Ok,
+                };
+                ScenarioComparison {
+                    name: result.name.clone(),
+                    baseline_events_per_sec: baseline,
+                    events_per_sec: result.events_per_sec,
+                    change_percent,
+                    status,
+                }
+            })
+            .collect();
+
+        Comparison {
+            max_regression_percent,
+            scenarios,
+        }
+    }
+}
+
+/// Outcome of comparing a run with the baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comparison {
+    pub max_regression_percent: f64,
+    pub scenarios: Vec<ScenarioComparison>,
+}
+
+impl Comparison {
+    /// Scenarios whose throughput regressed beyond the tolerance
+    pub fn regressions(&self) -> impl Iterator<Item = &ScenarioComparison> {
+        self.scenarios
+            .iter()
+            .filter(|scenario| scenario.status == ComparisonStatus::Regressed)
+    }
+
+    /// Returns true if any scenario regressed
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+
+    /// Scenarios the baseline has no numbers for
+    pub fn unrecorded(&self) -> impl Iterator<Item = &ScenarioComparison> {
+        self.scenarios
+            .iter()
+            .filter(|scenario| scenario.status == ComparisonStatus::New)
+    }
+
+    /// Returns true if every scenario was recorded and none regressed
+    pub fn passed(&self) -> bool {
+        !self.has_regressions() && self.unrecorded().next().is_none()
+    }
+}
+
+/// Throughput of one scenario against its baseline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioComparison {
+    pub name: String,
+    pub baseline_events_per_sec: Option<f64>,
+    pub events_per_sec: f64,
+    /// Change against the baseline; negative is slower
+    pub change_percent: Option<f64>,
+    pub status: ComparisonStatus,
+}
+
+/// Verdict of one scenario
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparisonStatus {
+    /// Within the tolerance, or faster
+    Ok,
+    /// Slower than the baseline beyond the tolerance
+    Regressed,
+    /// Not in the baseline; fails the comparison
+    New,
+}
+
+impl fmt::Display for ComparisonStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ComparisonStatus::Ok => "ok",
+            ComparisonStatus::Regressed => "REGRESSED",
+            ComparisonStatus::New => "new",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::stats::LatencySummary;
+
+    fn result(name: &str, events_per_sec: f64) -> ScenarioResult {
+        ScenarioResult {
+            name: name.to_string(),
+            events_per_sec,
+            latency: LatencySummary {
+                p99_micros: 900,
+                ..LatencySummary::default()
+            },
+            ..ScenarioResult::default()
+        }
+    }
+
+    #[test]
+    fn test_compare_flags_regressions_beyond_tolerance() {
+        let baseline = Baseline::from_results(
+            &[
+                result("sync", 1000.0),
+                result("batch", 1000.0),
+                result("write_behind", 1000.0),
+            ],
+            None,
+        );
+
+        let comparison = baseline.compare(
+            &[
+                result("sync", 905.0),
+                result("batch", 850.0),
+                result("write_behind", 1200.0),
+                result("sync+schema", 10.0),
+            ],
+            10.0,
+        );
+
+        let statuses: Vec<_> = comparison
+            .scenarios
+            .iter()
+            .map(|scenario| (scenario.name.as_str(), scenario.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("sync", ComparisonStatus::Ok),
+                ("batch", ComparisonStatus::Regressed),
+                ("write_behind", ComparisonStatus::Ok),
+                ("sync+schema", ComparisonStatus::New),
+            ]
+        );
+        assert!(comparison.has_regressions());
+        assert_eq!(comparison.scenarios[1].change_percent, Some(-15.0));
+        assert_eq!(comparison.regressions().count(), 1);
+        assert!(!comparison.passed());
+    }
+
+    #[test]
+    fn test_unrecorded_scenarios_fail_the_comparison() {
+        let comparison = Baseline::default().compare(&[result("sync", 1.0)], 0.0);
+        assert!(!comparison.has_regressions());
+        assert_eq!(comparison.scenarios[0].status, ComparisonStatus::New);
+        assert!(!comparison.passed());
+
+        let baseline = Baseline::from_results(&[result("sync", 1.0)], None);
+        let comparison = baseline.compare(&[result("sync", 1.0)], 0.0);
+        assert!(comparison.passed());
+        let comparison = baseline.compare(&[result("sync", 1.0), result("batch", 1.0)], 0.0);
+        assert_eq!(comparison.unrecorded().count(), 1);
+        assert!(!comparison.passed());
+    }
+
+    #[test]
+    fn test_baseline_file_round_trip() {
+        let dir = std::env::temp_dir().join(format!("xzepr-baseline-{}", ulid::Ulid::new()));
+        let path = dir.join("baseline.json");
+        let baseline = Baseline::from_results(&[result("sync", 1234.5)], Some("ci".to_string()));
+
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+
+        std::fs::write(&path, r#"{"format_version": 99}"#).unwrap();
+        assert!(matches!(
+            Baseline::load(&path),
+            Err(HarnessError::BaselineVersion { found: 99, .. })
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
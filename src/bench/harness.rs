@@ -0,0 +1,775 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/bench/harness.rs
+
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::application::demo::{DemoDataGenerator, DemoDataset};
+use crate::application::handlers::event_batch_handler::DEFAULT_MAX_BATCH_EVENTS;
+use crate::application::handlers::{
+    BatchItem, BatchItemOutcome, CreateEventOutcome, EventBatchHandler, EventHandler, QuotaMode,
+    SchemaResolver,
+};
+use crate::bench::stats::{LatencyRecorder, LatencySummary};
+use crate::bench::HarnessError;
+use crate::domain::entities::event::{CreateEventParams, Event};
+use crate::domain::entities::event_publication::PublishPolicy;
+use crate::domain::entities::ingestion_meta::{IngestionContext, IngestionSource, PrincipalType};
+use crate::domain::repositories::event_outbox_repo::EventOutboxRepository;
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::error::{InfrastructureError, Result};
+use crate::infrastructure::database::{
+    PostgresEventReceiverGroupRepository, PostgresEventReceiverRepository, PostgresEventRepository,
+};
+use crate::infrastructure::memory::{
+    InMemoryEventOutboxRepository, InMemoryEventReceiverGroupRepository,
+    InMemoryEventReceiverRepository, InMemoryEventRepository,
+};
+use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
+use crate::infrastructure::messaging::producer::EventPublisher;
+
+/// Environment variable setting the number of concurrent producers
+pub const PRODUCERS_ENV: &str = "XZEPR_BENCH_PRODUCERS";
+/// Environment variable setting the events each producer submits
+pub const EVENTS_PER_PRODUCER_ENV: &str = "XZEPR_BENCH_EVENTS_PER_PRODUCER";
+/// Environment variable setting the events per batch request
+pub const BATCH_SIZE_ENV: &str = "XZEPR_BENCH_BATCH_SIZE";
+/// Environment variable setting how often a batch item repeats its predecessor
+pub const DUPLICATE_EVERY_ENV: &str = "XZEPR_BENCH_DUPLICATE_EVERY";
+/// Environment variable setting the simulated stream acknowledgement delay
+pub const PUBLISH_LATENCY_ENV: &str = "XZEPR_BENCH_PUBLISH_LATENCY_MICROS";
+
+/// Seed of the generated workload, distinct from the demo server's
+const WORKLOAD_SEED: u64 = 0x10ad_2025;
+
+/// Ingestion path a scenario drives
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionPath {
+    /// Single events under the `require` publish policy; every request
+    /// waits for the stream to acknowledge
+    #[default]
+    Sync,
+    /// Single events under the `best-effort` policy with the stream down;
+    /// every event is stored and queued in the outbox for the relay
+    WriteBehind,
+    /// Batch requests through the batch handler
+    Batch,
+}
+
+impl IngestionPath {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestionPath::Sync => "sync",
+            IngestionPath::WriteBehind => "write_behind",
+            IngestionPath::Batch => "batch",
+        }
+    }
+}
+
+impl fmt::Display for IngestionPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One measured combination of path and ingestion features
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scenario {
+    pub path: IngestionPath,
+    /// Validates payloads against receiver and group schemas
+    pub schema_validation: bool,
+    /// Skips batch items repeating an earlier item; batch path only
+    pub deduplication: bool,
+}
+
+impl Scenario {
+    /// Name used in reports and baselines, e.g. `batch+schema+dedup`
+    pub fn name(&self) -> String {
+        let mut name = self.path.as_str().to_string();
+        if self.schema_validation {
+            name.push_str("+schema");
+        }
+        if self.deduplication {
+            name.push_str("+dedup");
+        }
+        name
+    }
+
+    /// Every scenario the harness runs by default
+    ///
+    /// Deduplication only exists for batches, so single-event paths run
+    /// with and without schema validation only.
+    pub fn matrix() -> Vec<Scenario> {
+        let mut scenarios = Vec::new();
+        for path in [
+            IngestionPath::Sync,
+            IngestionPath::WriteBehind,
+            IngestionPath::Batch,
+        ] {
+            for schema_validation in [false, true] {
+                let dedup_options: &[bool] = if path == IngestionPath::Batch {
+                    &[false, true]
+                } else {
+                    &[false]
+                };
+                for &deduplication in dedup_options {
+                    scenarios.push(Scenario {
+                        path,
+                        schema_validation,
+                        deduplication,
+                    });
+                }
+            }
+        }
+        scenarios
+    }
+}
+
+/// Size and shape of the generated load
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LoadConfig {
+    /// Producers submitting concurrently
+    pub producers: usize,
+    /// Events each producer submits per scenario
+    pub events_per_producer: usize,
+    /// Events per request on the batch path
+    pub batch_size: usize,
+    /// Every n-th batch item repeats the item before it; 0 disables repeats
+    pub duplicate_every: usize,
+    /// Delay before the simulated stream acknowledges a publish
+    pub publish_latency_micros: u64,
+}
+
+impl Default for LoadConfig {
+    fn default() -> Self {
+        Self {
+            producers: 8,
+            events_per_producer: 250,
+            batch_size: 50,
+            duplicate_every: 10,
+            publish_latency_micros: 0,
+        }
+    }
+}
+
+impl LoadConfig {
+    /// Sets the number of concurrent producers
+    pub fn with_producers(mut self, producers: usize) -> Self {
+        self.producers = producers;
+        self
+    }
+
+    /// Sets the events each producer submits
+    pub fn with_events_per_producer(mut self, events_per_producer: usize) -> Self {
+        self.events_per_producer = events_per_producer;
+        self
+    }
+
+    /// Sets the events per batch request
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets how often a batch item repeats its predecessor
+    pub fn with_duplicate_every(mut self, duplicate_every: usize) -> Self {
+        self.duplicate_every = duplicate_every;
+        self
+    }
+
+    /// Sets the simulated stream acknowledgement delay
+    pub fn with_publish_latency(mut self, latency: Duration) -> Self {
+        self.publish_latency_micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        self
+    }
+
+    /// Reads the `XZEPR_BENCH_*` variables over the defaults
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HarnessError::InvalidSetting`] for a value that is not a
+    /// number or fails [`LoadConfig::validate`].
+    pub fn from_env() -> std::result::Result<Self, HarnessError> {
+        let defaults = Self::default();
+        let config = Self {
+            producers: env_number(PRODUCERS_ENV, defaults.producers)?,
+            events_per_producer: env_number(EVENTS_PER_PRODUCER_ENV, defaults.events_per_producer)?,
+            batch_size: env_number(BATCH_SIZE_ENV, defaults.batch_size)?,
+            duplicate_every: env_number(DUPLICATE_EVERY_ENV, defaults.duplicate_every)?,
+            publish_latency_micros: env_number(
+                PUBLISH_LATENCY_ENV,
+                defaults.publish_latency_micros,
+            )?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that the load can be generated
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HarnessError::InvalidSetting`] if there are no producers
+    /// or events, or if the batch size is outside the batch handler's cap.
+    pub fn validate(&self) -> std::result::Result<(), HarnessError> {
+        if self.producers == 0 {
+            return Err(HarnessError::invalid(PRODUCERS_ENV, "must be at least 1"));
+        }
+        if self.events_per_producer == 0 {
+            return Err(HarnessError::invalid(
+                EVENTS_PER_PRODUCER_ENV,
+                "must be at least 1",
+            ));
+        }
+        if !(1..=DEFAULT_MAX_BATCH_EVENTS).contains(&self.batch_size) {
+            return Err(HarnessError::invalid(
+                BATCH_SIZE_ENV,
+                format!("must be between 1 and {}", DEFAULT_MAX_BATCH_EVENTS),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Events submitted per scenario
+    pub fn total_events(&self) -> usize {
+        self.producers * self.events_per_producer
+    }
+
+    fn publish_latency(&self) -> Duration {
+        Duration::from_micros(self.publish_latency_micros)
+    }
+}
+
+fn env_number<T: std::str::FromStr>(
+    name: &'static str,
+    default: T,
+) -> std::result::Result<T, HarnessError> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .trim()
+            .parse()
+            .map_err(|_| HarnessError::invalid(name, format!("'{}' is not a number", value))),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Where the harness stores receivers and events
+#[derive(Clone)]
+pub enum Backend {
+    /// The in-memory repositories of the standalone server; every scenario
+    /// starts from empty repositories
+    Memory,
+    /// A migrated PostgreSQL database; events accumulate across scenarios
+    Postgres(PgPool),
+}
+
+impl Backend {
+    /// Connects to `url` and applies the migrations
+    ///
+    /// Use a throwaway database: the harness leaves its receivers and
+    /// every submitted event behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HarnessError::Database`] if the connection or a migration
+    /// fails.
+    pub async fn postgres(url: &str) -> std::result::Result<Self, HarnessError> {
+        let pool = PgPool::connect(url).await?;
+        sqlx::migrate!("./migrations").run(&pool).await?;
+        Ok(Backend::Postgres(pool))
+    }
+
+    /// Name recorded in reports
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::Memory => "memory",
+            Backend::Postgres(_) => "postgres",
+        }
+    }
+
+    /// Returns repositories holding the seeded receivers and groups
+    async fn stores(&self, dataset: &DemoDataset) -> Result<Stores> {
+        let stores = match self {
+            Backend::Memory => {
+                let events = Arc::new(InMemoryEventRepository::new());
+                Stores {
+                    events: events.clone(),
+                    receivers: Arc::new(InMemoryEventReceiverRepository::new()),
+                    groups: Arc::new(InMemoryEventReceiverGroupRepository::new()),
+                    outbox: Arc::new(InMemoryEventOutboxRepository::default()),
+                }
+            }
+            Backend::Postgres(pool) => {
+                let events = Arc::new(PostgresEventRepository::new(pool.clone()));
+                Stores {
+                    events: events.clone(),
+                    receivers: Arc::new(PostgresEventReceiverRepository::new(pool.clone())),
+                    groups: Arc::new(PostgresEventReceiverGroupRepository::new(pool.clone())),
+                    outbox: events,
+                }
+            }
+        };
+
+        // Seeding is idempotent so a database can be reused between runs
+        for receiver in &dataset.receivers {
+            if stores.receivers.find_by_id(receiver.id()).await?.is_none() {
+                stores.receivers.save(receiver).await?;
+            }
+        }
+        for group in &dataset.groups {
+            if stores.groups.find_by_id(group.id()).await?.is_none() {
+                stores.groups.save(group).await?;
+            }
+        }
+        Ok(stores)
+    }
+}
+
+struct Stores {
+    events: Arc<dyn EventRepository>,
+    receivers: Arc<dyn EventReceiverRepository>,
+    groups: Arc<dyn EventReceiverGroupRepository>,
+    outbox: Arc<dyn EventOutboxRepository>,
+}
+
+/// Stream stand-in that acknowledges after a fixed delay, or refuses
+/// every event
+pub struct SimulatedPublisher {
+    latency: Duration,
+    available: bool,
+}
+
+impl SimulatedPublisher {
+    /// Creates a stream that acknowledges after `latency`
+    pub fn acknowledging(latency: Duration) -> Self {
+        Self {
+            latency,
+            available: true,
+        }
+    }
+
+    /// Creates a stream that refuses every event after `latency`
+    pub fn unavailable(latency: Duration) -> Self {
+        Self {
+            latency,
+            available: false,
+        }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for SimulatedPublisher {
+    async fn publish(&self, _event: &Event) -> Result<()> {
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+        if self.available {
+            Ok(())
+        } else {
+            Err(InfrastructureError::KafkaProducerError {
+                message: "simulated stream is unavailable".to_string(),
+            }
+            .into())
+        }
+    }
+
+    async fn publish_to(&self, _topic: &str, event: &Event) -> Result<()> {
+        self.publish(event).await
+    }
+
+    async fn publish_message(&self, _message: &CloudEventMessage) -> Result<()> {
+        Ok(())
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.available
+    }
+}
+
+/// Measured numbers of one scenario
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub path: IngestionPath,
+    pub schema_validation: bool,
+    pub deduplication: bool,
+    /// Events submitted, including repeated batch items
+    pub events: usize,
+    /// Events stored
+    pub stored: usize,
+    /// Batch items skipped as repeats
+    pub deduplicated: usize,
+    /// Events rejected or failed; any value above zero skews the numbers
+    pub errors: usize,
+    pub elapsed_micros: u64,
+    /// Submitted events per second of wall-clock time
+    pub events_per_sec: f64,
+    /// Latency per request; a batch request counts once
+    pub latency: LatencySummary,
+}
+
+/// Drives seeded receivers with concurrent producers
+///
+/// Payloads are drawn from [`DemoDataGenerator`], so events have the shape,
+/// receiver spread, and failure mix of the demo dataset. Each scenario
+/// replays the same events through a freshly built [`EventHandler`].
+pub struct LoadHarness {
+    config: LoadConfig,
+    backend: Backend,
+    dataset: DemoDataset,
+}
+
+impl LoadHarness {
+    /// Generates the workload for `config`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HarnessError::InvalidSetting`] for an invalid `config`
+    /// and [`HarnessError::Setup`] if the workload cannot be generated.
+    pub fn new(config: LoadConfig, backend: Backend) -> std::result::Result<Self, HarnessError> {
+        config.validate()?;
+        // A fixed anchor keeps receiver ids stable, so seeding a database
+        // twice finds the receivers of the earlier run
+        let anchor = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let dataset = DemoDataGenerator::new(anchor)
+            .with_seed(WORKLOAD_SEED)
+            .with_event_count(config.total_events())
+            .generate()
+            .map_err(crate::error::Error::from)?;
+
+        Ok(Self {
+            config,
+            backend,
+            dataset,
+        })
+    }
+
+    pub fn config(&self) -> &LoadConfig {
+        &self.config
+    }
+
+    pub fn backend(&self) -> &Backend {
+        &self.backend
+    }
+
+    /// Runs every scenario in order
+    ///
+    /// # Errors
+    ///
+    /// Returns the first scenario that could not be set up.
+    pub async fn run_all(
+        &self,
+        scenarios: &[Scenario],
+    ) -> std::result::Result<Vec<ScenarioResult>, HarnessError> {
+        let mut results = Vec::with_capacity(scenarios.len());
+        for scenario in scenarios {
+            results.push(self.run(*scenario).await?);
+        }
+        Ok(results)
+    }
+
+    /// Runs one scenario
+    ///
+    /// Failed submissions are counted in [`ScenarioResult::errors`] rather
+    /// than aborting the run.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HarnessError::Setup`] if the repositories cannot be
+    /// seeded.
+    pub async fn run(
+        &self,
+        scenario: Scenario,
+    ) -> std::result::Result<ScenarioResult, HarnessError> {
+        let stores = self.backend.stores(&self.dataset).await?;
+        let event_handler = self.event_handler(scenario, stores);
+        let batch_handler = EventBatchHandler::new(event_handler.clone())
+            .with_deduplication(scenario.deduplication);
+        let context = IngestionContext::new(
+            PrincipalType::User,
+            self.dataset.owner_id.to_string(),
+            match scenario.path {
+                IngestionPath::Batch => IngestionSource::Batch,
+                _ => IngestionSource::Rest,
+            },
+        );
+
+        let mut producers = Vec::with_capacity(self.config.producers);
+        let started = Instant::now();
+        for events in self.producer_events(scenario) {
+            let producer = Producer {
+                path: scenario.path,
+                event_handler: event_handler.clone(),
+                batch_handler: batch_handler.clone(),
+                context: context.clone(),
+                batch_size: self.config.batch_size,
+            };
+            producers.push(tokio::spawn(producer.run(events)));
+        }
+
+        let mut tally = Tally::default();
+        for producer in producers {
+            match producer.await {
+                Ok(producer_tally) => tally.merge(producer_tally),
+                Err(e) => {
+                    tracing::error!("Load producer panicked: {}", e);
+                    tally.errors += self.config.events_per_producer;
+                }
+            }
+        }
+        let elapsed = started.elapsed();
+
+        let events = self.config.total_events();
+        Ok(ScenarioResult {
+            name: scenario.name(),
+            path: scenario.path,
+            schema_validation: scenario.schema_validation,
+            deduplication: scenario.deduplication,
+            events,
+            stored: tally.stored,
+            deduplicated: tally.deduplicated,
+            errors: tally.errors,
+            elapsed_micros: u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX),
+            events_per_sec: events as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+            latency: tally.latencies.summary(),
+        })
+    }
+
+    fn event_handler(&self, scenario: Scenario, stores: Stores) -> EventHandler {
+        let latency = self.config.publish_latency();
+        let publisher: Arc<dyn EventPublisher> = match scenario.path {
+            IngestionPath::WriteBehind => Arc::new(SimulatedPublisher::unavailable(latency)),
+            _ => Arc::new(SimulatedPublisher::acknowledging(latency)),
+        };
+
+        let mut handler =
+            EventHandler::new(stores.events, stores.receivers).with_event_publisher(publisher);
+        if scenario.schema_validation {
+            handler = handler.with_schema_resolver(SchemaResolver::new(stores.groups));
+        }
+        match scenario.path {
+            IngestionPath::Sync => handler.with_publish_policy(PublishPolicy::Require),
+            IngestionPath::WriteBehind => handler
+                .with_outbox(stores.outbox)
+                .with_publish_policy(PublishPolicy::BestEffort),
+            IngestionPath::Batch => handler.with_publish_policy(PublishPolicy::BestEffort),
+        }
+    }
+
+    /// Splits the workload between the producers
+    ///
+    /// On the batch path every `duplicate_every`-th item repeats the one
+    /// before it, so runs with and without deduplication submit the same
+    /// events.
+    fn producer_events(&self, scenario: Scenario) -> Vec<Vec<CreateEventParams>> {
+        let events: Vec<CreateEventParams> = self.dataset.events.iter().map(event_params).collect();
+        events
+            .chunks(self.config.events_per_producer)
+            .map(|chunk| {
+                let mut chunk = chunk.to_vec();
+                if scenario.path == IngestionPath::Batch && self.config.duplicate_every > 0 {
+                    for index in (self.config.duplicate_every..chunk.len())
+                        .step_by(self.config.duplicate_every)
+                    {
+                        chunk[index] = chunk[index - 1].clone();
+                    }
+                }
+                chunk
+            })
+            .collect()
+    }
+}
+
+/// Turns a generated event back into the request that would create it
+fn event_params(event: &Event) -> CreateEventParams {
+    CreateEventParams {
+        name: event.name().to_string(),
+        version: event.version().to_string(),
+        release: event.release().to_string(),
+        platform_id: event.platform_id().to_string(),
+        package: event.package().to_string(),
+        description: event.description().to_string(),
+        payload: event.payload().clone(),
+        success: event.success(),
+        receiver_id: event.event_receiver_id(),
+        owner_id: event.owner_id(),
+    }
+}
+
+/// One concurrent client of a scenario
+struct Producer {
+    path: IngestionPath,
+    event_handler: EventHandler,
+    batch_handler: EventBatchHandler,
+    context: IngestionContext,
+    batch_size: usize,
+}
+
+impl Producer {
+    async fn run(self, events: Vec<CreateEventParams>) -> Tally {
+        let mut tally = Tally {
+            latencies: LatencyRecorder::with_capacity(events.len()),
+            ..Tally::default()
+        };
+        match self.path {
+            IngestionPath::Batch => {
+                for chunk in events.chunks(self.batch_size) {
+                    let items = chunk.iter().cloned().map(BatchItem::Event).collect();
+                    let started = Instant::now();
+                    let report = self
+                        .batch_handler
+                        .submit(items, self.context.clone(), None, QuotaMode::BestEffort)
+                        .await;
+                    tally.latencies.record(started.elapsed());
+                    match report {
+                        Ok(report) => {
+                            tally.stored += report.count(BatchItemOutcome::Created)
+                                + report.count(BatchItemOutcome::PublishDeferred);
+                            tally.deduplicated += report.count(BatchItemOutcome::Deduplicated);
+                            tally.errors += report.count(BatchItemOutcome::ValidationFailed)
+                                + report.count(BatchItemOutcome::QuotaExceeded)
+                                + report.count(BatchItemOutcome::Failed);
+                        }
+                        Err(e) => {
+                            tracing::warn!("Load batch failed: {}", e);
+                            tally.errors += chunk.len();
+                        }
+                    }
+                }
+            }
+            IngestionPath::Sync | IngestionPath::WriteBehind => {
+                for params in events {
+                    let started = Instant::now();
+                    let outcome = self
+                        .event_handler
+                        .create_event_with_policy(params, self.context.clone(), None)
+                        .await;
+                    tally.latencies.record(started.elapsed());
+                    match outcome {
+                        Ok(CreateEventOutcome::Stored { .. }) => tally.stored += 1,
+                        Ok(CreateEventOutcome::SampledOut) => {}
+                        Err(e) => {
+                            tracing::warn!("Load event failed: {}", e);
+                            tally.errors += 1;
+                        }
+                    }
+                }
+            }
+        }
+        tally
+    }
+}
+
+/// Counts of one producer
+#[derive(Default)]
+struct Tally {
+    stored: usize,
+    deduplicated: usize,
+    errors: usize,
+    latencies: LatencyRecorder,
+}
+
+impl Tally {
+    fn merge(&mut self, other: Tally) {
+        self.stored += other.stored;
+        self.deduplicated += other.deduplicated;
+        self.errors += other.errors;
+        self.latencies.merge(other.latencies);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_config() -> LoadConfig {
+        LoadConfig::default()
+            .with_producers(3)
+            .with_events_per_producer(20)
+            .with_batch_size(8)
+            .with_duplicate_every(5)
+    }
+
+    #[test]
+    fn test_matrix_names_are_unique() {
+        let names: Vec<String> = Scenario::matrix().iter().map(Scenario::name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "sync",
+                "sync+schema",
+                "write_behind",
+                "write_behind+schema",
+                "batch",
+                "batch+dedup",
+                "batch+schema",
+                "batch+schema+dedup",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(LoadConfig::default().validate().is_ok());
+        assert!(LoadConfig::default().with_producers(0).validate().is_err());
+        assert!(LoadConfig::default()
+            .with_batch_size(DEFAULT_MAX_BATCH_EVENTS + 1)
+            .validate()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_every_scenario_stores_the_workload() {
+        let config = small_config();
+        let harness = LoadHarness::new(config.clone(), Backend::Memory).unwrap();
+        let results = harness.run_all(&Scenario::matrix()).await.unwrap();
+
+        for result in &results {
+            assert_eq!(result.events, config.total_events(), "{}", result.name);
+            assert_eq!(result.errors, 0, "{}", result.name);
+            assert_eq!(
+                result.stored + result.deduplicated,
+                result.events,
+                "{}",
+                result.name
+            );
+            assert!(result.events_per_sec > 0.0, "{}", result.name);
+        }
+
+        let by_name = |name: &str| results.iter().find(|r| r.name == name).unwrap();
+        assert_eq!(by_name("sync").latency.requests, config.total_events());
+        // Items 5, 10, and 15 of each producer repeat their predecessor
+        assert_eq!(by_name("batch+dedup").deduplicated, 3 * config.producers);
+        assert_eq!(by_name("batch").deduplicated, 0);
+        assert_eq!(by_name("batch").latency.requests, 3 * config.producers);
+    }
+
+    #[tokio::test]
+    async fn test_write_behind_queues_every_event() {
+        let harness = LoadHarness::new(small_config(), Backend::Memory).unwrap();
+        let dataset = &harness.dataset;
+        let stores = Backend::Memory.stores(dataset).await.unwrap();
+        let outbox = stores.outbox.clone();
+        let scenario = Scenario {
+            path: IngestionPath::WriteBehind,
+            schema_validation: true,
+            deduplication: false,
+        };
+        let handler = harness.event_handler(scenario, stores);
+
+        let params = event_params(&dataset.events[0]);
+        let outcome = handler.create_event(params).await.unwrap();
+        assert!(matches!(outcome, CreateEventOutcome::Stored { .. }));
+        assert_eq!(outbox.backlog().await.unwrap().pending, 1);
+    }
+}
@@ -0,0 +1,158 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/bench/mod.rs
+//! Ingestion load harness
+//!
+//! Built with the `bench` feature and driven by `benches/ingestion_load.rs`.
+//! [`LoadHarness`] seeds receivers from the demo generator, runs concurrent
+//! producers through the sync, write-behind, and batch ingestion paths, and
+//! reports throughput and latency percentiles per [`Scenario`]. A run is
+//! compared with a committed [`Baseline`] and fails when throughput drops
+//! by more than the configured percentage.
+
+pub mod baseline;
+pub mod harness;
+pub mod stats;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+use std::path::Path;
+use thiserror::Error;
+
+pub use baseline::{
+    Baseline, BaselineEntry, Comparison, ComparisonStatus, ScenarioComparison,
+    BASELINE_FORMAT_VERSION, DEFAULT_MAX_REGRESSION_PERCENT,
+};
+pub use harness::{
+    Backend, IngestionPath, LoadConfig, LoadHarness, Scenario, ScenarioResult, SimulatedPublisher,
+};
+pub use stats::{LatencyRecorder, LatencySummary};
+
+/// Errors of the load harness
+#[derive(Debug, Error)]
+pub enum HarnessError {
+    #[error("Invalid load harness setting {name}: {message}")]
+    InvalidSetting { name: &'static str, message: String },
+
+    #[error("Failed to access {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid JSON in {path}: {source}")]
+    Json {
+        path: String,
+        source: serde_json::Error,
+    },
+
+    #[error("Unsupported baseline format version {found}; expected {expected}")]
+    BaselineVersion { found: u32, expected: u32 },
+
+    #[error("Load harness database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Load harness migration failed: {0}")]
+    Migration(#[from] sqlx::migrate::MigrateError),
+
+    #[error("Load harness setup failed: {0}")]
+    Setup(#[from] crate::error::Error),
+}
+
+impl HarnessError {
+    fn invalid(name: &'static str, message: impl Into<String>) -> Self {
+        HarnessError::InvalidSetting {
+            name,
+            message: message.into(),
+        }
+    }
+}
+
+/// Results of one harness run, written as the JSON artifact
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub generated_at: DateTime<Utc>,
+    pub backend: String,
+    pub config: LoadConfig,
+    pub results: Vec<ScenarioResult>,
+    /// Absent when no baseline was found
+    pub comparison: Option<Comparison>,
+}
+
+impl RunReport {
+    /// Writes the report as pretty-printed JSON, creating parent directories
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HarnessError::Io`] if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), HarnessError> {
+        write_json(path, self)
+    }
+}
+
+impl fmt::Display for RunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} backend, {} producers x {} events, batch size {}",
+            self.backend,
+            self.config.producers,
+            self.config.events_per_producer,
+            self.config.batch_size
+        )?;
+        writeln!(
+            f,
+            "{:<22} {:>12} {:>10} {:>10} {:>10} {:>7} {:>10}",
+            "scenario", "events/sec", "p50 us", "p95 us", "p99 us", "errors", "vs base"
+        )?;
+        for result in &self.results {
+            let verdict = self
+                .comparison
+                .as_ref()
+                .and_then(|comparison| {
+                    comparison
+                        .scenarios
+                        .iter()
+                        .find(|scenario| scenario.name == result.name)
+                })
+                .map(|scenario| match scenario.change_percent {
+                    Some(change) => format!("{:+.1}% {}", change, scenario.status),
+                    None => scenario.status.to_string(),
+                })
+                .unwrap_or_default();
+            writeln!(
+                f,
+                "{:<22} {:>12.0} {:>10} {:>10} {:>10} {:>7} {:>10}",
+                result.name,
+                result.events_per_sec,
+                result.latency.p50_micros,
+                result.latency.p95_micros,
+                result.latency.p99_micros,
+                result.errors,
+                verdict
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `value` as pretty-printed JSON, creating parent directories
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), HarnessError> {
+    let io_error = |source| HarnessError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(|source| HarnessError::Json {
+        path: path.display().to_string(),
+        source,
+    })?;
+    std::fs::write(path, json + "\n").map_err(io_error)
+}
@@ -0,0 +1,114 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/bench/stats.rs
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Collects request latencies of one producer or one scenario
+#[derive(Debug, Clone, Default)]
+pub struct LatencyRecorder {
+    samples_micros: Vec<u64>,
+}
+
+impl LatencyRecorder {
+    /// Creates a recorder with room for `capacity` samples
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            samples_micros: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Records one request
+    pub fn record(&mut self, latency: Duration) {
+        self.samples_micros
+            .push(u64::try_from(latency.as_micros()).unwrap_or(u64::MAX));
+    }
+
+    /// Adds the samples of `other`
+    pub fn merge(&mut self, other: LatencyRecorder) {
+        self.samples_micros.extend(other.samples_micros);
+    }
+
+    /// Number of recorded requests
+    pub fn len(&self) -> usize {
+        self.samples_micros.len()
+    }
+
+    /// Returns true if nothing was recorded
+    pub fn is_empty(&self) -> bool {
+        self.samples_micros.is_empty()
+    }
+
+    /// Computes the percentiles of the recorded samples
+    pub fn summary(&self) -> LatencySummary {
+        let mut sorted = self.samples_micros.clone();
+        sorted.sort_unstable();
+        LatencySummary {
+            requests: sorted.len(),
+            p50_micros: percentile(&sorted, 50.0),
+            p95_micros: percentile(&sorted, 95.0),
+            p99_micros: percentile(&sorted, 99.0),
+            max_micros: sorted.last().copied().unwrap_or(0),
+        }
+    }
+}
+
+/// Request latency percentiles in microseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub requests: usize,
+    pub p50_micros: u64,
+    pub p95_micros: u64,
+    pub p99_micros: u64,
+    pub max_micros: u64,
+}
+
+/// Returns the nearest-rank `pct` percentile of `sorted`; 0 when empty
+pub fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_use_nearest_rank() {
+        let sorted: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&sorted, 50.0), 50);
+        assert_eq!(percentile(&sorted, 95.0), 95);
+        assert_eq!(percentile(&sorted, 99.0), 99);
+        assert_eq!(percentile(&sorted, 100.0), 100);
+        assert_eq!(percentile(&[7], 99.0), 7);
+        assert_eq!(percentile(&[], 50.0), 0);
+    }
+
+    #[test]
+    fn test_summary_merges_producers() {
+        let mut first = LatencyRecorder::default();
+        let mut second = LatencyRecorder::default();
+        for micros in [300, 100] {
+            first.record(Duration::from_micros(micros));
+        }
+        for micros in [200, 400] {
+            second.record(Duration::from_micros(micros));
+        }
+        first.merge(second);
+
+        let summary = first.summary();
+        assert_eq!(summary.requests, 4);
+        assert_eq!(summary.p50_micros, 200);
+        assert_eq!(summary.p99_micros, 400);
+        assert_eq!(summary.max_micros, 400);
+        assert_eq!(
+            LatencyRecorder::default().summary(),
+            LatencySummary::default()
+        );
+    }
+}
@@ -6,12 +6,16 @@
 // Original line: 0
 
 // src/bin/admin.rs
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use clap::{Parser, Subcommand};
 use sqlx::PgPool;
 use std::str::FromStr;
 use std::sync::Arc;
+use xzepr::application::handlers::{AuditChainHandler, VersionNormalizationHandler};
 use xzepr::auth::api_key::UserRepository;
+use xzepr::infrastructure::database::{
+    PostgresAuditRecordRepository, PostgresVersionNormalizationRepository,
+};
 use xzepr::{
     ApiKeyId, ApiKeyService, PostgresApiKeyRepository, PostgresUserRepository, Role, Settings, User,
 };
@@ -72,6 +76,36 @@ enum Commands {
         #[arg(short, long)]
         key_id: String,
     },
+    /// Issue a new secret for an API key, keeping the old one valid for a
+    /// grace period
+    RotateApiKey {
+        #[arg(short, long)]
+        key_id: String,
+        /// Seconds the old secret keeps working (default: configured grace)
+        #[arg(short, long)]
+        grace_seconds: Option<u64>,
+    },
+    /// Rewrite stored receiver, group, and event versions into canonical
+    /// semantic version form
+    NormalizeVersions,
+    /// Inspect the persisted audit records
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuditCommands {
+    /// Check the hash chain of the audit records of a range of UTC days
+    Verify {
+        /// First day checked, as YYYY-MM-DD (default: the --to day)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Last day checked, as YYYY-MM-DD (default: today)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
 }
 
 #[tokio::main]
@@ -82,12 +116,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let settings = Settings::new()?;
 
     // Connect to database
-    let pool = PgPool::connect(&settings.database.url).await?;
+    let pool = PgPool::connect(settings.database.url.expose()).await?;
 
     // Initialize repositories and services
     let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
     let api_key_repo = Arc::new(PostgresApiKeyRepository::new(pool.clone()));
-    let api_key_service = Arc::new(ApiKeyService::new(user_repo.clone(), api_key_repo));
+    let api_key_service = Arc::new(
+        ApiKeyService::new(user_repo.clone(), api_key_repo)
+            .with_rotation_grace(chrono::Duration::seconds(
+                settings.auth.api_keys.rotation_grace_seconds as i64,
+            ))
+            .with_stale_after(chrono::Duration::days(
+                settings.hygiene.key_stale_days as i64,
+            )),
+    );
 
     match cli.command {
         Commands::CreateUser {
@@ -223,8 +265,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             );
             println!("{}", "-".repeat(90));
 
+            let now = chrono::Utc::now();
             for key in keys {
-                let status = if key.enabled() { "Active" } else { "Disabled" };
+                let status = if !key.enabled() {
+                    "Disabled"
+                } else if api_key_service.is_stale(&key, now) {
+                    "Stale"
+                } else {
+                    "Active"
+                };
                 let expires = key
                     .expires_at()
                     .map(|e| e.to_string())
@@ -241,10 +290,92 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
 
         Commands::RevokeApiKey { key_id } => {
-            let key_id = ApiKeyId::parse(&key_id)?;
+            let key_id = key_id.parse::<ApiKeyId>()?;
             api_key_service.revoke_key(key_id).await?;
             println!("✓ API key revoked successfully");
         }
+
+        Commands::RotateApiKey {
+            key_id,
+            grace_seconds,
+        } => {
+            let key_id = key_id.parse::<ApiKeyId>()?;
+            let grace = grace_seconds.map(|seconds| chrono::Duration::seconds(seconds as i64));
+            let (key, api_key) = api_key_service.rotate_key(key_id, grace).await?;
+
+            println!("✓ API key rotated successfully!");
+            println!("\n⚠️  IMPORTANT: Save this key now - it won't be shown again!");
+            println!("\n  API Key: {}", key);
+            println!("  Key ID:  {}", api_key.id());
+            match api_key.previous_expires_at {
+                Some(until) => println!("  Old key valid until: {}", until),
+                None => println!("  Old key is no longer valid"),
+            }
+        }
+
+        Commands::NormalizeVersions => {
+            let handler = VersionNormalizationHandler::new(Arc::new(
+                PostgresVersionNormalizationRepository::new(pool.clone()),
+            ));
+            let report = handler.run().await?;
+
+            println!(
+                "✓ Normalized {} row(s) across {} version value(s)",
+                report.rows_rewritten(),
+                report.rewritten.len()
+            );
+            for rewrite in &report.rewritten {
+                println!(
+                    "  {:<22} {:?} -> {:?} ({} row(s))",
+                    rewrite.resource.as_str(),
+                    rewrite.from,
+                    rewrite.to,
+                    rewrite.rows
+                );
+            }
+
+            if !report.skipped.is_empty() {
+                println!("\n⚠️  {} row(s) left unchanged:", report.rows_skipped());
+                for skipped in &report.skipped {
+                    println!(
+                        "  {:<22} {:?} ({} row(s)): {}",
+                        skipped.resource.as_str(),
+                        skipped.version,
+                        skipped.rows,
+                        skipped.reason
+                    );
+                }
+            }
+        }
+
+        Commands::Audit {
+            command: AuditCommands::Verify { from, to },
+        } => {
+            let to = to.unwrap_or_else(|| Utc::now().date_naive());
+            let from = from.unwrap_or(to);
+            let handler =
+                AuditChainHandler::new(Arc::new(PostgresAuditRecordRepository::new(pool.clone())));
+            let report = handler.verify(from, to).await?;
+
+            match &report.first_broken {
+                None => println!(
+                    "✓ Audit chain intact from {} to {}: {} record(s) across {} day(s)",
+                    from, to, report.records_checked, report.days_checked
+                ),
+                Some(broken) => {
+                    println!(
+                        "✗ Audit chain broken ({} record(s) checked)",
+                        report.records_checked
+                    );
+                    println!("  Day:         {}", broken.chain_day);
+                    println!("  Position:    {}", broken.position);
+                    println!("  Record ID:   {}", broken.record_id);
+                    println!("  Recorded at: {}", broken.recorded_at);
+                    println!("  Fault:       {}", broken.fault.as_str());
+                    std::process::exit(1);
+                }
+            }
+        }
     }
 
     Ok(())
@@ -7,15 +7,53 @@
 //! A high-performance event tracking server with REST API for managing
 //! event receivers, events, and event receiver groups.
 
+use axum::{routing::get, Router};
+use clap::Parser;
 use std::sync::Arc;
 use tokio::signal;
+use tokio::sync::watch;
 use tracing::info;
 
-use xzepr::api::rest::{build_router, AppState};
-use xzepr::application::handlers::{EventHandler, EventReceiverGroupHandler, EventReceiverHandler};
+use xzepr::api::graphql::PersistedQueryStore;
+use xzepr::api::middleware::{DeprecationRegistry, JwtMiddlewareState, RequestDeadlines};
+use xzepr::api::rest::{build_protected_router, build_router, AppState};
+use xzepr::api::router::metrics_handler;
+use xzepr::application::authorization::AuthorizationService;
+use xzepr::application::demo::{
+    ensure_local_database, DemoDataGenerator, DemoTrickle, DEMO_ADMIN_USERNAME,
+};
+use xzepr::application::handlers::{
+    BulkDeleteHandler, ChangeFeedHandler, EventBatchHandler, EventHandler,
+    EventReceiverGroupHandler, EventReceiverHandler, ResourceHistoryHandler, SchemaPreviewHandler,
+    SchemaResolver, UserPreferencesHandler,
+};
+use xzepr::auth::jwt::{Algorithm, JwtConfig, JwtService};
+use xzepr::auth::sessions::SessionService;
+use xzepr::infrastructure::config::{ErrorFormat, GraphQLConfig};
+use xzepr::infrastructure::memory::{
+    InMemoryAttestationRepository, InMemoryEventReceiverGroupRepository,
+    InMemoryEventReceiverRepository, InMemoryEventRepository, InMemoryResourceHistoryRepository,
+    InMemoryUserPreferencesRepository, InMemoryUserRepository,
+};
+use xzepr::infrastructure::{FeatureFlags, MaintenanceMode, PrometheusMetrics};
+use xzepr::{Role, Settings};
+
+#[derive(Parser)]
+#[command(name = "xzepr-server")]
+#[command(about = "XZepr event tracking server with in-memory storage")]
+struct Cli {
+    /// Seed demo data, require a demo admin token, and skip Postgres and Kafka
+    #[arg(long)]
+    demo: bool,
+    /// Seconds between synthetic events in demo mode; 0 disables them
+    #[arg(long, default_value_t = 5)]
+    demo_trickle_seconds: u64,
+}
 
 #[tokio::main]
 async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+
     // Initialize logging
     tracing_subscriber::fmt()
         .with_target(false)
@@ -25,26 +63,141 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
 
     info!("Starting XZepr Event Tracking Server");
 
-    // For this example, we'll use mock repositories
-    // In a real implementation, these would be database-backed
-    let event_repo = Arc::new(MockEventRepository::new());
-    let receiver_repo = Arc::new(MockEventReceiverRepository::new());
-    let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+    // Demo mode must never run next to a real database
+    if cli.demo {
+        let settings = Settings::new()?;
+        ensure_local_database(settings.database.url.expose())?;
+    }
+
+    // Everything is kept in memory; nothing survives a restart
+    let event_repo = Arc::new(InMemoryEventRepository::new());
+    let receiver_repo = Arc::new(InMemoryEventReceiverRepository::new());
+    let group_repo = Arc::new(InMemoryEventReceiverGroupRepository::new());
+
+    let demo_dataset = if cli.demo {
+        let dataset = DemoDataGenerator::for_now().generate()?;
+        dataset
+            .seed(
+                event_repo.as_ref(),
+                receiver_repo.as_ref(),
+                group_repo.as_ref(),
+            )
+            .await?;
+        Some(dataset)
+    } else {
+        None
+    };
+
+    // Counts publish outcomes and system events that could not be built;
+    // served from /metrics
+    let metrics = Arc::new(PrometheusMetrics::new()?);
 
     // Create application handlers
-    let event_handler = EventHandler::new(event_repo, receiver_repo.clone());
-    let receiver_handler = EventReceiverHandler::new(receiver_repo.clone());
-    let group_handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone());
+    let schema_resolver = SchemaResolver::new(group_repo.clone());
+    let schema_preview_handler = SchemaPreviewHandler::new(event_repo.clone());
+    let history =
+        ResourceHistoryHandler::new(Arc::new(InMemoryResourceHistoryRepository::default()));
+    let event_handler = EventHandler::new(event_repo.clone(), receiver_repo.clone())
+        .with_schema_resolver(schema_resolver.clone())
+        .with_receiver_history(history.clone())
+        .with_attestations(Arc::new(InMemoryAttestationRepository::default()))
+        .with_metrics(metrics.clone());
+    let receiver_handler = EventReceiverHandler::new(receiver_repo.clone())
+        .with_schema_resolver(schema_resolver.clone())
+        .with_history(history.clone())
+        .with_metrics(metrics.clone());
+    let group_handler = EventReceiverGroupHandler::new(group_repo.clone(), receiver_repo.clone())
+        .with_schema_resolver(schema_resolver)
+        .with_history(history.clone())
+        .with_metrics(metrics.clone());
+    let bulk_delete_handler = BulkDeleteHandler::new(
+        receiver_repo.clone(),
+        group_repo.clone(),
+        event_repo.clone(),
+    )
+    .with_history(history);
+    let event_batch_handler = EventBatchHandler::new(event_handler.clone());
+    let authorization = AuthorizationService::new(receiver_repo.clone(), group_repo.clone());
+    let change_feed_handler = ChangeFeedHandler::new(receiver_repo.clone(), group_repo);
+    let user_preferences_handler =
+        UserPreferencesHandler::new(Arc::new(InMemoryUserPreferencesRepository::default()));
 
     // Create application state
-    let app_state = AppState {
+    let mut app_state = AppState {
         event_handler,
+        event_batch_handler,
         event_receiver_handler: receiver_handler,
         event_receiver_group_handler: group_handler,
+        authorization,
+        change_feed_handler,
+        user_preferences_handler,
+        feature_flags: FeatureFlags::default(),
+        admin_summary_handler: None,
+        schema_preview_handler,
+        bulk_delete_handler,
+        api_key_service: None,
+        event_poll_handler: None,
+        graphql: GraphQLConfig {
+            playground_enabled: true,
+            ..GraphQLConfig::default()
+        },
+        about: None,
+        jobs: None,
+        delivery_failures: None,
+        audit_chain: None,
+        search_handler: None,
+        attachment_handler: None,
+        user_import_handler: None,
+        session_service: None,
+        error_format: ErrorFormat::default(),
+        deprecations: Arc::new(DeprecationRegistry::default()),
+        maintenance: MaintenanceMode::default(),
+        persisted_queries: Some(PersistedQueryStore::new()),
+        runtime_settings: None,
+        request_deadlines: RequestDeadlines::default(),
     };
 
-    // Build the router
-    let app = build_router(app_state);
+    // Demo mode mints an admin token and stops publishing synthetic events
+    // when the server shuts down
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let app = match &demo_dataset {
+        Some(dataset) => {
+            let jwt_service = JwtService::from_config(demo_jwt_config())?;
+            let admin_permissions = Role::Admin
+                .permissions()
+                .iter()
+                .map(|permission| format!("{:?}", permission))
+                .collect();
+            let token = jwt_service.generate_access_token(
+                dataset.owner_id.to_string(),
+                vec![Role::Admin.to_string()],
+                admin_permissions,
+            )?;
+
+            if cli.demo_trickle_seconds > 0 {
+                DemoTrickle::new(app_state.event_handler.clone(), dataset).spawn(
+                    std::time::Duration::from_secs(cli.demo_trickle_seconds),
+                    shutdown_rx,
+                );
+            }
+
+            // Sessions are listed and revoked through the service issuing
+            // the tokens the middleware validates
+            app_state.session_service = Some(Arc::new(SessionService::new(
+                Arc::new(jwt_service.clone()),
+                Arc::new(InMemoryUserRepository::default()),
+            )));
+
+            print_demo_banner(&token, dataset.events.len());
+            build_protected_router(app_state, JwtMiddlewareState::new(jwt_service))
+        }
+        None => build_router(app_state),
+    };
+    let app = app.merge(
+        Router::new()
+            .route("/metrics", get(metrics_handler))
+            .with_state(metrics),
+    );
 
     // Server configuration
     let host = std::env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
@@ -57,6 +210,7 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
 
     info!("Server listening on http://{}:{}", host, port);
     info!("Health check: http://{}:{}/health", host, port);
+    info!("Metrics: http://{}:{}/metrics", host, port);
     info!("API documentation: http://{}:{}/api/v1", host, port);
     info!("GraphQL endpoint: http://{}:{}/graphql", host, port);
     info!(
@@ -72,11 +226,41 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     axum::serve(listener, app)
         .with_graceful_shutdown(shutdown_signal())
         .await?;
+    let _ = shutdown_tx.send(true);
 
     info!("Server shutdown complete");
     Ok(())
 }
 
+/// JWT settings for demo mode, signed with a secret generated per run
+fn demo_jwt_config() -> JwtConfig {
+    use rand::distributions::{Alphanumeric, DistString};
+
+    JwtConfig {
+        access_token_expiration_seconds: 24 * 3600,
+        algorithm: Algorithm::HS256,
+        secret_key: Some(
+            Alphanumeric
+                .sample_string(&mut rand::thread_rng(), 48)
+                .into(),
+        ),
+        ..JwtConfig::default()
+    }
+}
+
+/// Prints the demo admin credentials
+fn print_demo_banner(token: &str, seeded_events: usize) {
+    println!();
+    println!("==================== XZepr demo mode ====================");
+    println!("In-memory data only; nothing is persisted or published.");
+    println!("Seeded events: {}", seeded_events);
+    println!("Admin user:    {}", DEMO_ADMIN_USERNAME);
+    println!("Admin token (valid 24h, send as 'Authorization: Bearer'):");
+    println!("{}", token);
+    println!("=========================================================");
+    println!();
+}
+
 /// Graceful shutdown signal handler
 async fn shutdown_signal() {
     let ctrl_c = async {
@@ -105,606 +289,3 @@ async fn shutdown_signal() {
         },
     }
 }
-
-// Mock implementations for demonstration purposes
-// In a real application, these would be database-backed repositories
-
-use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
-use std::sync::Mutex;
-
-use xzepr::domain::entities::{
-    event::Event, event_receiver::EventReceiver, event_receiver_group::EventReceiverGroup,
-};
-use xzepr::domain::repositories::{
-    event_receiver_group_repo::{EventReceiverGroupRepository, FindEventReceiverGroupCriteria},
-    event_receiver_repo::{EventReceiverRepository, FindEventReceiverCriteria},
-    event_repo::{EventRepository, FindEventCriteria},
-};
-use xzepr::domain::value_objects::{EventId, EventReceiverGroupId, EventReceiverId, UserId};
-use xzepr::error::Result;
-
-/// Mock event repository that stores events in memory
-pub struct MockEventRepository {
-    events: Arc<Mutex<HashMap<EventId, Event>>>,
-}
-
-impl Default for MockEventRepository {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl MockEventRepository {
-    pub fn new() -> Self {
-        Self {
-            events: Arc::new(Mutex::new(HashMap::new())),
-        }
-    }
-}
-
-#[async_trait]
-impl EventRepository for MockEventRepository {
-    async fn save(&self, event: &Event) -> Result<()> {
-        let mut events = self.events.lock().unwrap();
-        events.insert(event.id(), event.clone());
-        info!("Saved event: {} ({})", event.name(), event.id());
-        Ok(())
-    }
-
-    async fn find_by_id(&self, id: EventId) -> Result<Option<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events.get(&id).cloned())
-    }
-
-    async fn find_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.event_receiver_id() == receiver_id)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_success(&self, success: bool) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.success() == success)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_name(&self, name: &str) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.name().contains(name))
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_platform_id(&self, platform_id: &str) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.platform_id() == platform_id)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_package(&self, package: &str) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.package() == package)
-            .cloned()
-            .collect())
-    }
-
-    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events.values().skip(offset).take(limit).cloned().collect())
-    }
-
-    async fn count(&self) -> Result<usize> {
-        let events = self.events.lock().unwrap();
-        Ok(events.len())
-    }
-
-    async fn count_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.event_receiver_id() == receiver_id)
-            .count())
-    }
-
-    async fn count_successful_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.event_receiver_id() == receiver_id && e.success())
-            .count())
-    }
-
-    async fn delete(&self, id: EventId) -> Result<()> {
-        let mut events = self.events.lock().unwrap();
-        events.remove(&id);
-        info!("Deleted event: {}", id);
-        Ok(())
-    }
-
-    async fn find_latest_by_receiver_id(
-        &self,
-        receiver_id: EventReceiverId,
-    ) -> Result<Option<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.event_receiver_id() == receiver_id)
-            .max_by_key(|e| e.created_at())
-            .cloned())
-    }
-
-    async fn find_latest_successful_by_receiver_id(
-        &self,
-        receiver_id: EventReceiverId,
-    ) -> Result<Option<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.event_receiver_id() == receiver_id && e.success())
-            .max_by_key(|e| e.created_at())
-            .cloned())
-    }
-
-    async fn find_by_time_range(
-        &self,
-        start: DateTime<Utc>,
-        end: DateTime<Utc>,
-    ) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.created_at() >= start && e.created_at() <= end)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_criteria(&self, _criteria: FindEventCriteria) -> Result<Vec<Event>> {
-        // Simplified implementation
-        let events = self.events.lock().unwrap();
-        Ok(events.values().cloned().collect())
-    }
-
-    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.owner_id() == owner_id)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_owner_paginated(
-        &self,
-        owner_id: UserId,
-        limit: usize,
-        offset: usize,
-    ) -> Result<Vec<Event>> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .values()
-            .filter(|e| e.owner_id() == owner_id)
-            .skip(offset)
-            .take(limit)
-            .cloned()
-            .collect())
-    }
-
-    async fn is_owner(&self, event_id: EventId, user_id: UserId) -> Result<bool> {
-        let events = self.events.lock().unwrap();
-        Ok(events
-            .get(&event_id)
-            .map(|e| e.owner_id() == user_id)
-            .unwrap_or(false))
-    }
-
-    async fn get_resource_version(&self, event_id: EventId) -> Result<Option<i64>> {
-        let events = self.events.lock().unwrap();
-        Ok(events.get(&event_id).map(|e| e.resource_version()))
-    }
-}
-
-/// Mock event receiver repository that stores receivers in memory
-pub struct MockEventReceiverRepository {
-    receivers: Arc<Mutex<HashMap<EventReceiverId, EventReceiver>>>,
-    name_type_index: Arc<Mutex<HashMap<(String, String), EventReceiverId>>>,
-}
-
-impl Default for MockEventReceiverRepository {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl MockEventReceiverRepository {
-    pub fn new() -> Self {
-        Self {
-            receivers: Arc::new(Mutex::new(HashMap::new())),
-            name_type_index: Arc::new(Mutex::new(HashMap::new())),
-        }
-    }
-}
-
-#[async_trait]
-impl EventReceiverRepository for MockEventReceiverRepository {
-    async fn save(&self, event_receiver: &EventReceiver) -> Result<()> {
-        let mut receivers = self.receivers.lock().unwrap();
-        let mut index = self.name_type_index.lock().unwrap();
-
-        receivers.insert(event_receiver.id(), event_receiver.clone());
-        index.insert(
-            (
-                event_receiver.name().to_string(),
-                event_receiver.receiver_type().to_string(),
-            ),
-            event_receiver.id(),
-        );
-
-        info!(
-            "Saved event receiver: {} ({})",
-            event_receiver.name(),
-            event_receiver.id()
-        );
-        Ok(())
-    }
-
-    async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers.get(&id).cloned())
-    }
-
-    async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiver>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers
-            .values()
-            .filter(|r| r.name().contains(name))
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_type(&self, receiver_type: &str) -> Result<Vec<EventReceiver>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers
-            .values()
-            .filter(|r| r.receiver_type() == receiver_type)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_type_and_version(
-        &self,
-        receiver_type: &str,
-        version: &str,
-    ) -> Result<Vec<EventReceiver>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers
-            .values()
-            .filter(|r| r.receiver_type() == receiver_type && r.version() == version)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers
-            .values()
-            .find(|r| r.fingerprint() == fingerprint)
-            .cloned())
-    }
-
-    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiver>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers
-            .values()
-            .skip(offset)
-            .take(limit)
-            .cloned()
-            .collect())
-    }
-
-    async fn count(&self) -> Result<usize> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers.len())
-    }
-
-    async fn update(&self, event_receiver: &EventReceiver) -> Result<()> {
-        self.save(event_receiver).await
-    }
-
-    async fn delete(&self, id: EventReceiverId) -> Result<()> {
-        let mut receivers = self.receivers.lock().unwrap();
-        let mut index = self.name_type_index.lock().unwrap();
-
-        if let Some(receiver) = receivers.remove(&id) {
-            index.remove(&(
-                receiver.name().to_string(),
-                receiver.receiver_type().to_string(),
-            ));
-            info!("Deleted event receiver: {} ({})", receiver.name(), id);
-        }
-        Ok(())
-    }
-
-    async fn exists_by_name_and_type(&self, name: &str, receiver_type: &str) -> Result<bool> {
-        let index = self.name_type_index.lock().unwrap();
-        Ok(index.contains_key(&(name.to_string(), receiver_type.to_string())))
-    }
-
-    async fn find_by_criteria(
-        &self,
-        _criteria: FindEventReceiverCriteria,
-    ) -> Result<Vec<EventReceiver>> {
-        // Simplified implementation
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers.values().cloned().collect())
-    }
-
-    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiver>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers
-            .values()
-            .filter(|r| r.owner_id() == owner_id)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_owner_paginated(
-        &self,
-        owner_id: UserId,
-        limit: usize,
-        offset: usize,
-    ) -> Result<Vec<EventReceiver>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers
-            .values()
-            .filter(|r| r.owner_id() == owner_id)
-            .skip(offset)
-            .take(limit)
-            .cloned()
-            .collect())
-    }
-
-    async fn is_owner(&self, receiver_id: EventReceiverId, user_id: UserId) -> Result<bool> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers
-            .get(&receiver_id)
-            .map(|r| r.owner_id() == user_id)
-            .unwrap_or(false))
-    }
-
-    async fn get_resource_version(&self, receiver_id: EventReceiverId) -> Result<Option<i64>> {
-        let receivers = self.receivers.lock().unwrap();
-        Ok(receivers.get(&receiver_id).map(|r| r.resource_version()))
-    }
-}
-
-/// Mock event receiver group repository that stores groups in memory
-pub struct MockEventReceiverGroupRepository {
-    groups: Arc<Mutex<HashMap<EventReceiverGroupId, EventReceiverGroup>>>,
-    name_type_index: Arc<Mutex<HashMap<(String, String), EventReceiverGroupId>>>,
-}
-
-impl Default for MockEventReceiverGroupRepository {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl MockEventReceiverGroupRepository {
-    pub fn new() -> Self {
-        Self {
-            groups: Arc::new(Mutex::new(HashMap::new())),
-            name_type_index: Arc::new(Mutex::new(HashMap::new())),
-        }
-    }
-}
-
-#[async_trait]
-impl EventReceiverGroupRepository for MockEventReceiverGroupRepository {
-    async fn save(&self, group: &EventReceiverGroup) -> Result<()> {
-        let mut groups = self.groups.lock().unwrap();
-        let mut index = self.name_type_index.lock().unwrap();
-
-        groups.insert(group.id(), group.clone());
-        index.insert(
-            (group.name().to_string(), group.group_type().to_string()),
-            group.id(),
-        );
-
-        info!(
-            "Saved event receiver group: {} ({})",
-            group.name(),
-            group.id()
-        );
-        Ok(())
-    }
-
-    async fn find_by_id(&self, id: EventReceiverGroupId) -> Result<Option<EventReceiverGroup>> {
-        let groups = self.groups.lock().unwrap();
-        Ok(groups.get(&id).cloned())
-    }
-
-    async fn exists_by_name_and_type(&self, name: &str, group_type: &str) -> Result<bool> {
-        let index = self.name_type_index.lock().unwrap();
-        Ok(index.contains_key(&(name.to_string(), group_type.to_string())))
-    }
-
-    async fn update(&self, group: &EventReceiverGroup) -> Result<()> {
-        self.save(group).await
-    }
-
-    async fn find_by_name(&self, _name: &str) -> Result<Vec<EventReceiverGroup>> {
-        Ok(vec![])
-    }
-
-    async fn find_by_type(&self, _group_type: &str) -> Result<Vec<EventReceiverGroup>> {
-        Ok(vec![])
-    }
-
-    async fn find_by_type_and_version(
-        &self,
-        _group_type: &str,
-        _version: &str,
-    ) -> Result<Vec<EventReceiverGroup>> {
-        Ok(vec![])
-    }
-
-    async fn find_enabled(&self) -> Result<Vec<EventReceiverGroup>> {
-        Ok(vec![])
-    }
-
-    async fn find_disabled(&self) -> Result<Vec<EventReceiverGroup>> {
-        Ok(vec![])
-    }
-
-    async fn find_by_event_receiver_id(
-        &self,
-        _receiver_id: EventReceiverId,
-    ) -> Result<Vec<EventReceiverGroup>> {
-        Ok(vec![])
-    }
-
-    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiverGroup>> {
-        let groups = self.groups.lock().unwrap();
-        Ok(groups.values().skip(offset).take(limit).cloned().collect())
-    }
-
-    async fn count(&self) -> Result<usize> {
-        let groups = self.groups.lock().unwrap();
-        Ok(groups.len())
-    }
-
-    async fn count_enabled(&self) -> Result<usize> {
-        Ok(0)
-    }
-
-    async fn count_disabled(&self) -> Result<usize> {
-        Ok(0)
-    }
-
-    async fn delete(&self, id: EventReceiverGroupId) -> Result<()> {
-        let mut groups = self.groups.lock().unwrap();
-        let mut index = self.name_type_index.lock().unwrap();
-
-        if let Some(group) = groups.remove(&id) {
-            index.remove(&(group.name().to_string(), group.group_type().to_string()));
-            info!("Deleted event receiver group: {} ({})", group.name(), id);
-        }
-        Ok(())
-    }
-
-    async fn enable(&self, _id: EventReceiverGroupId) -> Result<()> {
-        Ok(())
-    }
-
-    async fn disable(&self, _id: EventReceiverGroupId) -> Result<()> {
-        Ok(())
-    }
-
-    async fn find_by_criteria(
-        &self,
-        _criteria: FindEventReceiverGroupCriteria,
-    ) -> Result<Vec<EventReceiverGroup>> {
-        Ok(vec![])
-    }
-
-    async fn add_event_receiver_to_group(
-        &self,
-        _group_id: EventReceiverGroupId,
-        _receiver_id: EventReceiverId,
-    ) -> Result<()> {
-        Ok(())
-    }
-
-    async fn remove_event_receiver_from_group(
-        &self,
-        _group_id: EventReceiverGroupId,
-        _receiver_id: EventReceiverId,
-    ) -> Result<()> {
-        Ok(())
-    }
-
-    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiverGroup>> {
-        let groups = self.groups.lock().unwrap();
-        Ok(groups
-            .values()
-            .filter(|g| g.owner_id() == owner_id)
-            .cloned()
-            .collect())
-    }
-
-    async fn find_by_owner_paginated(
-        &self,
-        owner_id: UserId,
-        limit: usize,
-        offset: usize,
-    ) -> Result<Vec<EventReceiverGroup>> {
-        let groups = self.groups.lock().unwrap();
-        Ok(groups
-            .values()
-            .filter(|g| g.owner_id() == owner_id)
-            .skip(offset)
-            .take(limit)
-            .cloned()
-            .collect())
-    }
-
-    async fn is_owner(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<bool> {
-        let groups = self.groups.lock().unwrap();
-        Ok(groups
-            .get(&group_id)
-            .map(|g| g.owner_id() == user_id)
-            .unwrap_or(false))
-    }
-
-    async fn get_resource_version(&self, group_id: EventReceiverGroupId) -> Result<Option<i64>> {
-        let groups = self.groups.lock().unwrap();
-        Ok(groups.get(&group_id).map(|g| g.resource_version()))
-    }
-
-    async fn is_member(&self, _group_id: EventReceiverGroupId, _user_id: UserId) -> Result<bool> {
-        Ok(false)
-    }
-
-    async fn get_group_members(&self, _group_id: EventReceiverGroupId) -> Result<Vec<UserId>> {
-        Ok(vec![])
-    }
-
-    async fn add_member(
-        &self,
-        _group_id: EventReceiverGroupId,
-        _user_id: UserId,
-        _added_by: UserId,
-    ) -> Result<()> {
-        Ok(())
-    }
-
-    async fn remove_member(&self, _group_id: EventReceiverGroupId, _user_id: UserId) -> Result<()> {
-        Ok(())
-    }
-
-    async fn find_groups_for_user(&self, _user_id: UserId) -> Result<Vec<EventReceiverGroup>> {
-        Ok(vec![])
-    }
-
-    async fn get_group_event_receivers(
-        &self,
-        _group_id: EventReceiverGroupId,
-    ) -> Result<Vec<EventReceiverId>> {
-        Ok(vec![])
-    }
-}
@@ -0,0 +1,737 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/canonical_json.rs
+
+//! Canonical JSON shared by hashing, deduplication, and interning
+//!
+//! Every feature that compares or hashes JSON content goes through this
+//! module, so equal content always yields the same bytes and hash. The
+//! scheme follows RFC 8785 (JSON Canonicalization Scheme):
+//!
+//! * No insignificant whitespace.
+//! * Object members are sorted by their keys compared as UTF-16 code units.
+//! * Strings escape only `"`, `\`, and control characters, using the short
+//!   forms `\b`, `\t`, `\n`, `\f`, `\r` where they exist and lowercase
+//!   `\u00xx` otherwise. Everything else is written as UTF-8.
+//! * Floating point numbers are written like ECMAScript's
+//!   `Number.prototype.toString`: the shortest digits that round-trip, no
+//!   trailing `.0`, `-0` as `0`, and exponents like `1e+21` outside the
+//!   range `1e-7 < |x| < 1e21`. `1.0` and `1` therefore canonicalize the
+//!   same way.
+//!
+//! One deviation: integers are written exactly instead of being rounded to
+//! a double first, so distinct identifiers above 2^53 never share a hash.
+//! Within ±2^53 the output is identical to RFC 8785.
+//!
+//! Raw input parsed with [`parse`] rejects duplicate object keys, which
+//! `serde_json` would otherwise resolve silently by keeping the last one.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Number, Value as JsonValue};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Default limit on nested arrays and objects
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Default limit on the size of canonical output
+pub const DEFAULT_MAX_BYTES: usize = 16 * 1024 * 1024;
+
+/// Reasons JSON cannot be canonicalized
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CanonicalJsonError {
+    #[error("JSON nesting exceeds {max} levels")]
+    TooDeep { max: usize },
+
+    #[error("Canonical JSON exceeds {max} bytes")]
+    TooLarge { max: usize },
+
+    #[error("Duplicate object key '{key}'")]
+    DuplicateKey { key: String },
+
+    #[error("Invalid JSON: {message}")]
+    Invalid { message: String },
+}
+
+impl CanonicalJsonError {
+    /// Converts the error into a validation error of `field`
+    pub fn into_validation_error(self, field: &str) -> DomainError {
+        let message = match self {
+            Self::TooDeep { max } => Message::new("validation.json_too_deep").with("max", max),
+            Self::TooLarge { max } => Message::new("validation.json_too_large").with("max", max),
+            Self::DuplicateKey { key } => {
+                Message::new("validation.json_duplicate_key").with("key", key)
+            }
+            Self::Invalid { message } => {
+                Message::new("validation.json_invalid").with("reason", message)
+            }
+        };
+        DomainError::ValidationError {
+            field: field.to_string(),
+            message,
+        }
+    }
+}
+
+/// Canonicalizer with configurable limits
+///
+/// The free functions [`canonical_bytes`], [`content_hash`], and [`parse`]
+/// use the default limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CanonicalJson {
+    max_depth: usize,
+    max_bytes: usize,
+}
+
+impl Default for CanonicalJson {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+impl CanonicalJson {
+    /// Creates a canonicalizer with the default limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits nesting; a scalar has depth 0 and `[[1]]` has depth 2
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Limits the size of the canonical output
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Returns the canonical serialization of `value`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use xzepr::domain::canonical_json::CanonicalJson;
+    ///
+    /// let bytes = CanonicalJson::new()
+    ///     .bytes(&json!({"b": [1.0, -0.0, 1e21], "a": "x"}))
+    ///     .unwrap();
+    /// assert_eq!(bytes, br#"{"a":"x","b":[1,0,1e+21]}"#);
+    /// ```
+    pub fn bytes(&self, value: &JsonValue) -> Result<Vec<u8>, CanonicalJsonError> {
+        let mut out = Vec::new();
+        self.write(value, 0, &mut out)?;
+        Ok(out)
+    }
+
+    /// Returns the canonical serialization of `value` as a string
+    pub fn string(&self, value: &JsonValue) -> Result<String, CanonicalJsonError> {
+        let bytes = self.bytes(value)?;
+        // Only UTF-8 strings and ASCII are ever written
+        Ok(String::from_utf8(bytes).expect("canonical JSON is UTF-8"))
+    }
+
+    /// Returns the hex-encoded SHA-256 of the canonical serialization
+    pub fn hash(&self, value: &JsonValue) -> Result<String, CanonicalJsonError> {
+        Ok(hash_canonical(&self.bytes(value)?))
+    }
+
+    /// Parses raw JSON, rejecting duplicate object keys and input beyond
+    /// the limits
+    pub fn parse(&self, input: &[u8]) -> Result<JsonValue, CanonicalJsonError> {
+        if input.len() > self.max_bytes {
+            return Err(CanonicalJsonError::TooLarge {
+                max: self.max_bytes,
+            });
+        }
+
+        let mut deserializer = serde_json::Deserializer::from_slice(input);
+        let value = StrictValue::deserialize(&mut deserializer)
+            .and_then(|value| deserializer.end().map(|_| value))
+            .map_err(|e| {
+                let message = e.to_string();
+                match message.strip_prefix(DUPLICATE_KEY_PREFIX) {
+                    Some(rest) => CanonicalJsonError::DuplicateKey {
+                        key: rest
+                            .rsplit_once("' at line")
+                            .map_or(rest, |(key, _)| key)
+                            .to_string(),
+                    },
+                    None if message.starts_with("recursion limit exceeded") => {
+                        CanonicalJsonError::TooDeep {
+                            max: self.max_depth,
+                        }
+                    }
+                    None => CanonicalJsonError::Invalid { message },
+                }
+            })?
+            .0;
+        check_depth(&value, 0, self.max_depth)?;
+        Ok(value)
+    }
+
+    fn write(
+        &self,
+        value: &JsonValue,
+        depth: usize,
+        out: &mut Vec<u8>,
+    ) -> Result<(), CanonicalJsonError> {
+        match value {
+            JsonValue::Null => out.extend_from_slice(b"null"),
+            JsonValue::Bool(true) => out.extend_from_slice(b"true"),
+            JsonValue::Bool(false) => out.extend_from_slice(b"false"),
+            JsonValue::Number(number) => write_number(number, out),
+            JsonValue::String(string) => write_string(string, out),
+            JsonValue::Array(items) => {
+                let depth = self.enter(depth)?;
+                out.push(b'[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    self.write(item, depth, out)?;
+                }
+                out.push(b']');
+            }
+            JsonValue::Object(map) => {
+                let depth = self.enter(depth)?;
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_unstable_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+                out.push(b'{');
+                for (i, (key, item)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    write_string(key, out);
+                    out.push(b':');
+                    self.write(item, depth, out)?;
+                }
+                out.push(b'}');
+            }
+        }
+
+        if out.len() > self.max_bytes {
+            return Err(CanonicalJsonError::TooLarge {
+                max: self.max_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    fn enter(&self, depth: usize) -> Result<usize, CanonicalJsonError> {
+        if depth >= self.max_depth {
+            return Err(CanonicalJsonError::TooDeep {
+                max: self.max_depth,
+            });
+        }
+        Ok(depth + 1)
+    }
+}
+
+/// Returns the canonical serialization of `value` with the default limits
+pub fn canonical_bytes(value: &JsonValue) -> Result<Vec<u8>, CanonicalJsonError> {
+    CanonicalJson::new().bytes(value)
+}
+
+/// Returns the canonical serialization of `value` as a string with the
+/// default limits
+pub fn canonical_string(value: &JsonValue) -> Result<String, CanonicalJsonError> {
+    CanonicalJson::new().string(value)
+}
+
+/// Returns the hex-encoded SHA-256 of the canonical serialization of
+/// `value` with the default limits
+pub fn content_hash(value: &JsonValue) -> Result<String, CanonicalJsonError> {
+    CanonicalJson::new().hash(value)
+}
+
+/// Returns the content hash of bytes already in canonical form
+pub fn hash_canonical(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Parses raw JSON with the default limits, rejecting duplicate keys
+pub fn parse(input: &[u8]) -> Result<JsonValue, CanonicalJsonError> {
+    CanonicalJson::new().parse(input)
+}
+
+fn check_depth(value: &JsonValue, depth: usize, max: usize) -> Result<(), CanonicalJsonError> {
+    let children: Box<dyn Iterator<Item = &JsonValue>> = match value {
+        JsonValue::Array(items) => Box::new(items.iter()),
+        JsonValue::Object(map) => Box::new(map.values()),
+        _ => return Ok(()),
+    };
+    if depth >= max {
+        return Err(CanonicalJsonError::TooDeep { max });
+    }
+    children
+        .into_iter()
+        .try_for_each(|child| check_depth(child, depth + 1, max))
+}
+
+fn write_number(number: &Number, out: &mut Vec<u8>) {
+    if let Some(n) = number.as_u64() {
+        out.extend_from_slice(n.to_string().as_bytes());
+    } else if let Some(n) = number.as_i64() {
+        out.extend_from_slice(n.to_string().as_bytes());
+    } else if let Some(n) = number.as_f64() {
+        out.extend_from_slice(format_double(n).as_bytes());
+    }
+}
+
+/// Formats a finite double like ECMAScript's `Number.prototype.toString`
+fn format_double(value: f64) -> String {
+    if value == 0.0 {
+        return "0".to_string();
+    }
+    if value < 0.0 {
+        return format!("-{}", format_double(-value));
+    }
+
+    // Rust prints the shortest digits that round-trip, as ECMAScript does
+    let scientific = format!("{:e}", value);
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("scientific notation has an exponent");
+    let exponent: i32 = exponent.parse().expect("exponent is an integer");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let (digits, exponent) = round_tie_to_even(digits, exponent, value);
+    let k = digits.len() as i32;
+    // The value is 0.digits * 10^n
+    let n = exponent + 1;
+
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat((-n) as usize), digits)
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let fraction = if k > 1 {
+            format!(".{}", &digits[1..])
+        } else {
+            String::new()
+        };
+        format!("{}{}e{}{}", &digits[..1], fraction, sign, (n - 1).abs())
+    }
+}
+
+/// Picks the even candidate when the value lies exactly halfway between
+/// two shortest candidates, as ECMAScript does; Rust may pick either
+fn round_tie_to_even(digits: String, exponent: i32, value: f64) -> (String, i32) {
+    let last = digits.as_bytes()[digits.len() - 1] - b'0';
+    if last.is_multiple_of(2) {
+        return (digits, exponent);
+    }
+
+    // Doubles have at most 767 significant decimal digits
+    let exact = format!("{:.800e}", value);
+    let exact: String = exact
+        .split('e')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| *c != '.')
+        .collect();
+    let is_tie_after = |candidate: &str| {
+        exact
+            .strip_prefix(candidate)
+            .and_then(|rest| rest.strip_prefix('5'))
+            .is_some_and(|rest| rest.bytes().all(|b| b == b'0'))
+    };
+
+    let mut lower = digits.clone().into_bytes();
+    *lower.last_mut().expect("digits are not empty") -= 1;
+    let lower = String::from_utf8(lower).expect("digits are ASCII");
+    if is_tie_after(&lower) {
+        return (trim_zeros(lower), exponent);
+    }
+    if is_tie_after(&digits) {
+        let mut upper = digits.into_bytes();
+        let mut i = upper.len();
+        while i > 0 {
+            i -= 1;
+            if upper[i] == b'9' {
+                upper[i] = b'0';
+            } else {
+                upper[i] += 1;
+                return (
+                    trim_zeros(String::from_utf8(upper).expect("digits are ASCII")),
+                    exponent,
+                );
+            }
+        }
+        return ("1".to_string(), exponent + 1);
+    }
+    (digits, exponent)
+}
+
+fn trim_zeros(digits: String) -> String {
+    let trimmed = digits.trim_end_matches('0');
+    if trimmed.is_empty() {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn write_string(value: &str, out: &mut Vec<u8>) {
+    out.push(b'"');
+    for c in value.chars() {
+        match c {
+            '"' => out.extend_from_slice(b"\\\""),
+            '\\' => out.extend_from_slice(b"\\\\"),
+            '\u{08}' => out.extend_from_slice(b"\\b"),
+            '\t' => out.extend_from_slice(b"\\t"),
+            '\n' => out.extend_from_slice(b"\\n"),
+            '\u{0c}' => out.extend_from_slice(b"\\f"),
+            '\r' => out.extend_from_slice(b"\\r"),
+            c if c < ' ' => out.extend_from_slice(format!("\\u{:04x}", c as u32).as_bytes()),
+            c => {
+                let mut buffer = [0; 4];
+                out.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            }
+        }
+    }
+    out.push(b'"');
+}
+
+const DUPLICATE_KEY_PREFIX: &str = "duplicate object key '";
+
+/// A JSON value deserialized without tolerating duplicate keys
+struct StrictValue(JsonValue);
+
+impl<'de> Deserialize<'de> for StrictValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_any(StrictValueVisitor)
+            .map(StrictValue)
+    }
+}
+
+struct StrictValueVisitor;
+
+impl<'de> Visitor<'de> for StrictValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<JsonValue, E> {
+        Ok(JsonValue::Bool(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(value.into()))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Number(value.into()))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<JsonValue, E> {
+        Number::from_f64(value)
+            .map(JsonValue::Number)
+            .ok_or_else(|| E::custom("non-finite number"))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<JsonValue, E> {
+        Ok(JsonValue::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<JsonValue, E> {
+        Ok(JsonValue::String(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonValue, A::Error> {
+        let mut items = Vec::new();
+        while let Some(StrictValue(item)) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(JsonValue::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonValue, A::Error> {
+        let mut seen = HashSet::new();
+        let mut object = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if !seen.insert(key.clone()) {
+                return Err(de::Error::custom(format!(
+                    "{}{}'",
+                    DUPLICATE_KEY_PREFIX, key
+                )));
+            }
+            let StrictValue(value) = map.next_value()?;
+            object.insert(key, value);
+        }
+        Ok(JsonValue::Object(object))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use serde_json::json;
+
+    fn canonical(value: &JsonValue) -> String {
+        canonical_string(value).unwrap()
+    }
+
+    fn canonical_raw(input: &str) -> String {
+        canonical(&parse(input.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn test_rfc8785_number_vectors() {
+        // RFC 8785 Appendix B
+        let vectors: [(u64, &str); 24] = [
+            (0x0000000000000000, "0"),
+            (0x8000000000000000, "0"),
+            (0x0000000000000001, "5e-324"),
+            (0x8000000000000001, "-5e-324"),
+            (0x7fefffffffffffff, "1.7976931348623157e+308"),
+            (0xffefffffffffffff, "-1.7976931348623157e+308"),
+            (0x4340000000000000, "9007199254740992"),
+            (0xc340000000000000, "-9007199254740992"),
+            (0x4430000000000000, "295147905179352830000"),
+            (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+            (0x44b52d02c7e14af6, "1e+23"),
+            (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+            (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+            (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+            (0x444b1ae4d6e2ef50, "1e+21"),
+            (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+            (0x3eb0c6f7a0b5ed8d, "0.000001"),
+            (0x41b3de4355555553, "333333333.3333332"),
+            (0x41b3de4355555554, "333333333.33333325"),
+            (0x41b3de4355555555, "333333333.3333333"),
+            (0x41b3de4355555556, "333333333.3333334"),
+            (0x41b3de4355555557, "333333333.33333343"),
+            (0xbecbf647612f3696, "-0.0000033333333333333333"),
+            (0x43143ff3c1cb0959, "1424953923781206.2"),
+        ];
+
+        for (bits, expected) in vectors {
+            let value = JsonValue::from(f64::from_bits(bits));
+            assert_eq!(canonical(&value), expected, "bits {:016x}", bits);
+        }
+    }
+
+    #[test]
+    fn test_rfc8785_example() {
+        // RFC 8785 section 3.2.2
+        let input = r#"{
+            "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+            "literals": [null, true, false]
+        }"#;
+
+        assert_eq!(
+            canonical_raw(input),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+
+    #[test]
+    fn test_rfc8785_key_sorting_uses_utf16() {
+        // RFC 8785 section 3.2.3
+        let input = r#"{
+            "\u20ac": "Euro Sign",
+            "\r": "Carriage Return",
+            "\ufb33": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "\ud83d\ude00": "Emoji: Grinning Face",
+            "\u0080": "Control",
+            "\u00f6": "Latin Small Letter O With Diaeresis"
+        }"#;
+
+        assert_eq!(
+            canonical_raw(input),
+            "{\"\\r\":\"Carriage Return\",\"1\":\"One\",\"\u{80}\":\"Control\",\
+             \"ö\":\"Latin Small Letter O With Diaeresis\",\"€\":\"Euro Sign\",\
+             \"😀\":\"Emoji: Grinning Face\",\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}"
+        );
+    }
+
+    #[test]
+    fn test_equal_content_canonicalizes_equally() {
+        let pairs = [
+            (json!(1.0), json!(1)),
+            (json!(-0.0), json!(0)),
+            (json!(100.0), json!(100)),
+            (json!(1.5e3), json!(1500)),
+            (json!({"b": 1, "a": 2}), json!({"a": 2, "b": 1})),
+        ];
+        for (a, b) in pairs {
+            assert_eq!(canonical(&a), canonical(&b));
+            assert_eq!(content_hash(&a).unwrap(), content_hash(&b).unwrap());
+        }
+
+        assert_eq!(canonical_raw("1E3"), "1000");
+        assert_eq!(canonical_raw("1e-7"), "1e-7");
+        assert_eq!(canonical_raw("\"\\u00e9\""), "\"é\"");
+        assert_eq!(canonical_raw("\"\\u007f\\u2028\""), "\"\u{7f}\u{2028}\"");
+    }
+
+    #[test]
+    fn test_integers_are_written_exactly() {
+        assert_eq!(canonical(&json!(9007199254740993u64)), "9007199254740993");
+        assert_eq!(canonical(&json!(u64::MAX)), "18446744073709551615");
+        assert_eq!(canonical(&json!(i64::MIN)), "-9223372036854775808");
+        assert_ne!(
+            content_hash(&json!(9007199254740993u64)).unwrap(),
+            content_hash(&json!(9007199254740992u64)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_keys_are_rejected_at_parse_time() {
+        assert_eq!(
+            parse(br#"{"a": 1, "b": {"c": 1, "c": 2}}"#),
+            Err(CanonicalJsonError::DuplicateKey {
+                key: "c".to_string()
+            })
+        );
+        // Escaped and literal spellings of a key are the same key
+        assert!(matches!(
+            parse(br#"{"a": 1, "\u0061": 2}"#),
+            Err(CanonicalJsonError::DuplicateKey { .. })
+        ));
+        assert!(matches!(
+            parse(b"{\"a\": 1} trailing"),
+            Err(CanonicalJsonError::Invalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_limits_are_enforced() {
+        let nested = json!({"a": [[1]]});
+        let shallow = CanonicalJson::new().with_max_depth(2);
+        assert_eq!(
+            shallow.bytes(&nested),
+            Err(CanonicalJsonError::TooDeep { max: 2 })
+        );
+        assert!(CanonicalJson::new()
+            .with_max_depth(3)
+            .bytes(&nested)
+            .is_ok());
+        assert_eq!(
+            shallow.parse(br#"{"a": [[1]]}"#),
+            Err(CanonicalJsonError::TooDeep { max: 2 })
+        );
+
+        let small = CanonicalJson::new().with_max_bytes(8);
+        assert_eq!(
+            small.bytes(&json!("1234567")),
+            Err(CanonicalJsonError::TooLarge { max: 8 })
+        );
+        assert_eq!(small.bytes(&json!("123456")).unwrap(), b"\"123456\"");
+        assert_eq!(
+            small.parse(b"  \"123456\"  "),
+            Err(CanonicalJsonError::TooLarge { max: 8 })
+        );
+
+        let error = CanonicalJsonError::TooDeep { max: 2 }.into_validation_error("payload");
+        assert!(matches!(
+            error,
+            DomainError::ValidationError { ref field, .. } if field == "payload"
+        ));
+    }
+
+    fn random_string(rng: &mut StdRng) -> String {
+        const ALPHABET: &[char] = &[
+            'a', 'Z', '0', ' ', '"', '\\', '/', '\n', '\t', '\u{0}', '\u{1f}', '\u{7f}', 'é', 'ö',
+            '€', '\u{2028}', '\u{fb33}', '😀', '世',
+        ];
+        (0..rng.gen_range(0..8))
+            .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())])
+            .collect()
+    }
+
+    fn random_number(rng: &mut StdRng) -> JsonValue {
+        match rng.gen_range(0..5) {
+            0 => json!(rng.gen::<i64>()),
+            1 => json!(rng.gen::<u64>()),
+            2 => json!(rng.gen_range(-1000..1000) as f64),
+            3 => json!(rng.gen::<f64>() * 10f64.powi(rng.gen_range(-30..30))),
+            _ => {
+                let value = f64::from_bits(rng.gen());
+                if value.is_finite() {
+                    json!(value)
+                } else {
+                    json!(0.5)
+                }
+            }
+        }
+    }
+
+    fn random_value(rng: &mut StdRng, depth: usize) -> JsonValue {
+        let kinds = if depth >= 4 { 4 } else { 6 };
+        match rng.gen_range(0..kinds) {
+            0 => JsonValue::Null,
+            1 => JsonValue::Bool(rng.gen()),
+            2 => random_number(rng),
+            3 => JsonValue::String(random_string(rng)),
+            4 => JsonValue::Array(
+                (0..rng.gen_range(0..5))
+                    .map(|_| random_value(rng, depth + 1))
+                    .collect(),
+            ),
+            _ => JsonValue::Object(
+                (0..rng.gen_range(0..5))
+                    .map(|_| (random_string(rng), random_value(rng, depth + 1)))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_hash_is_stable_across_serde_round_trips() {
+        let mut rng = StdRng::seed_from_u64(8785);
+        for _ in 0..500 {
+            let value = random_value(&mut rng, 0);
+            let hash = content_hash(&value).unwrap();
+
+            let pretty = serde_json::to_string_pretty(&value).unwrap();
+            let reparsed: JsonValue = serde_json::from_str(&pretty).unwrap();
+            assert_eq!(content_hash(&reparsed).unwrap(), hash, "{}", pretty);
+
+            let strict = parse(pretty.as_bytes()).unwrap();
+            assert_eq!(content_hash(&strict).unwrap(), hash, "{}", pretty);
+        }
+    }
+
+    #[test]
+    fn test_canonicalization_is_idempotent() {
+        let mut rng = StdRng::seed_from_u64(20_200_600);
+        for _ in 0..500 {
+            let value = random_value(&mut rng, 0);
+            let once = canonical_bytes(&value).unwrap();
+            let twice = canonical_bytes(&parse(&once).unwrap()).unwrap();
+            assert_eq!(
+                String::from_utf8_lossy(&twice),
+                String::from_utf8_lossy(&once)
+            );
+        }
+    }
+}
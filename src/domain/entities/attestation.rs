@@ -0,0 +1,491 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/attestation.rs
+
+//! Supply-chain attestations carried in event payloads
+//!
+//! Receivers of type [`ATTESTATION_RECEIVER_TYPE`] accept CycloneDX SBOMs
+//! and in-toto statements with a SLSA provenance predicate. The document
+//! stays in the event payload; the fields needed to find it again, such as
+//! the digests of the artifacts it attests to, are extracted at ingest
+//! time and stored next to the event.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Receiver type whose payloads must be attestation documents
+pub const ATTESTATION_RECEIVER_TYPE: &str = "attestation";
+
+/// CycloneDX `specVersion` values that are accepted
+pub const CYCLONEDX_SPEC_VERSIONS: &[&str] = &["1.4", "1.5", "1.6"];
+
+/// in-toto statement `_type` values that are accepted
+pub const IN_TOTO_STATEMENT_TYPES: &[&str] = &[
+    "https://in-toto.io/Statement/v0.1",
+    "https://in-toto.io/Statement/v1",
+];
+
+/// Prefix of the SLSA provenance `predicateType`, followed by its version
+const SLSA_PROVENANCE_PREFIX: &str = "https://slsa.dev/provenance/";
+
+/// SLSA provenance versions that are accepted
+pub const SLSA_PROVENANCE_VERSIONS: &[&str] = &["v0.2", "v1"];
+
+/// Kind of attestation document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttestationFormat {
+    /// CycloneDX software bill of materials
+    #[serde(rename = "cyclonedx")]
+    CycloneDx,
+    /// SLSA provenance in an in-toto statement
+    SlsaProvenance,
+}
+
+impl AttestationFormat {
+    /// Returns the storage representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttestationFormat::CycloneDx => "cyclonedx",
+            AttestationFormat::SlsaProvenance => "slsa_provenance",
+        }
+    }
+}
+
+impl fmt::Display for AttestationFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AttestationFormat {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cyclonedx" => Ok(AttestationFormat::CycloneDx),
+            "slsa_provenance" => Ok(AttestationFormat::SlsaProvenance),
+            _ => Err(DomainError::ValidationError {
+                field: "format".to_string(),
+                message: Message::new("validation.attestation_format_unknown").with("value", s),
+            }),
+        }
+    }
+}
+
+/// Fields extracted from an attestation document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    pub format: AttestationFormat,
+    /// Declared version of the document's specification
+    pub spec_version: String,
+    /// Digests of the attested artifacts as `algorithm:hex`, lowercase
+    pub subject_digests: Vec<String>,
+    /// Builder that produced the artifacts; provenance only
+    pub builder_id: Option<String>,
+    /// Number of components, nested ones included; SBOMs only
+    pub component_count: Option<u64>,
+    /// Name of the component the SBOM describes
+    pub component_name: Option<String>,
+    /// Version of the component the SBOM describes
+    pub component_version: Option<String>,
+}
+
+impl Attestation {
+    /// Recognizes an attestation document and extracts its fields
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the payload is neither a CycloneDX
+    /// SBOM nor an in-toto statement, declares an unsupported version, or
+    /// lacks a required field.
+    pub fn extract(payload: &JsonValue) -> Result<Self, DomainError> {
+        if payload.get("bomFormat").and_then(JsonValue::as_str) == Some("CycloneDX") {
+            return Self::from_cyclonedx(payload);
+        }
+        if payload.get("_type").is_some() {
+            return Self::from_in_toto(payload);
+        }
+        Err(DomainError::ValidationError {
+            field: "payload".to_string(),
+            message: Message::new("validation.attestation_unrecognized"),
+        })
+    }
+
+    fn from_cyclonedx(bom: &JsonValue) -> Result<Self, DomainError> {
+        let spec_version = required_str(bom, &["specVersion"])?;
+        check_version("CycloneDX", spec_version, CYCLONEDX_SPEC_VERSIONS)?;
+
+        let component = bom.pointer("/metadata/component");
+        let subject_digests = component
+            .and_then(|component| component.get("hashes"))
+            .and_then(JsonValue::as_array)
+            .map(|hashes| {
+                hashes
+                    .iter()
+                    .map(|hash| {
+                        let algorithm = hash.get("alg").and_then(JsonValue::as_str);
+                        let content = hash.get("content").and_then(JsonValue::as_str);
+                        match (algorithm, content) {
+                            (Some(algorithm), Some(content)) => normalize_digest(&format!(
+                                "{}:{}",
+                                cyclonedx_algorithm(algorithm),
+                                content
+                            )),
+                            _ => Err(missing("metadata.component.hashes")),
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let component_field = |name: &str| {
+            component
+                .and_then(|component| component.get(name))
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+        };
+
+        Ok(Self {
+            format: AttestationFormat::CycloneDx,
+            spec_version: spec_version.to_string(),
+            subject_digests: dedup(subject_digests),
+            builder_id: None,
+            component_count: Some(count_components(bom)),
+            component_name: component_field("name"),
+            component_version: component_field("version"),
+        })
+    }
+
+    fn from_in_toto(statement: &JsonValue) -> Result<Self, DomainError> {
+        let statement_type = required_str(statement, &["_type"])?;
+        check_version("in-toto statement", statement_type, IN_TOTO_STATEMENT_TYPES)?;
+
+        let predicate_type = required_str(statement, &["predicateType"])?;
+        let version = predicate_type
+            .strip_prefix(SLSA_PROVENANCE_PREFIX)
+            .unwrap_or(predicate_type);
+        check_version("SLSA provenance", version, SLSA_PROVENANCE_VERSIONS)?;
+
+        let builder_path: &[&str] = if version == "v0.2" {
+            &["predicate", "builder", "id"]
+        } else {
+            &["predicate", "runDetails", "builder", "id"]
+        };
+        let builder_id = required_str(statement, builder_path)?;
+
+        let subjects = statement
+            .get("subject")
+            .and_then(JsonValue::as_array)
+            .filter(|subjects| !subjects.is_empty())
+            .ok_or_else(|| missing("subject"))?;
+        let mut subject_digests = Vec::new();
+        for subject in subjects {
+            let digests = subject
+                .get("digest")
+                .and_then(JsonValue::as_object)
+                .filter(|digests| !digests.is_empty())
+                .ok_or_else(|| missing("subject.digest"))?;
+            for (algorithm, value) in digests {
+                let value = value.as_str().ok_or_else(|| missing("subject.digest"))?;
+                subject_digests.push(normalize_digest(&format!("{}:{}", algorithm, value))?);
+            }
+        }
+
+        Ok(Self {
+            format: AttestationFormat::SlsaProvenance,
+            spec_version: version.to_string(),
+            subject_digests: dedup(subject_digests),
+            builder_id: Some(builder_id.to_string()),
+            component_count: None,
+            component_name: None,
+            component_version: None,
+        })
+    }
+}
+
+/// Attestation extracted from a stored event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventAttestation {
+    pub event_id: EventId,
+    pub event_receiver_id: EventReceiverId,
+    pub attestation: Attestation,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EventAttestation {
+    /// Records the attestation carried by an event
+    pub fn new(
+        event_id: EventId,
+        event_receiver_id: EventReceiverId,
+        attestation: Attestation,
+    ) -> Self {
+        Self {
+            event_id,
+            event_receiver_id,
+            attestation,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Normalizes a digest to lowercase `algorithm:hex`
+///
+/// # Errors
+///
+/// Returns a validation error unless the digest is an algorithm name of
+/// ASCII letters, digits, and `_`, a colon, and a hex value.
+pub fn normalize_digest(digest: &str) -> Result<String, DomainError> {
+    let invalid = || DomainError::ValidationError {
+        field: "digest".to_string(),
+        message: Message::new("validation.attestation_digest").with("value", digest),
+    };
+
+    let (algorithm, value) = digest.trim().split_once(':').ok_or_else(invalid)?;
+    if algorithm.is_empty()
+        || !algorithm
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        || value.is_empty()
+        || !value.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(invalid());
+    }
+    Ok(format!(
+        "{}:{}",
+        algorithm.to_ascii_lowercase(),
+        value.to_ascii_lowercase()
+    ))
+}
+
+/// Maps a CycloneDX hash algorithm to its in-toto digest name, so both
+/// formats store the same digest alike (`SHA-256` to `sha256`, `SHA3-256`
+/// to `sha3_256`)
+fn cyclonedx_algorithm(algorithm: &str) -> String {
+    let algorithm = algorithm.to_ascii_lowercase();
+    match algorithm.strip_prefix("sha-") {
+        Some(bits) => format!("sha{}", bits),
+        None => algorithm.replace('-', "_"),
+    }
+}
+
+/// Counts the components of a BOM, nested ones included
+fn count_components(node: &JsonValue) -> u64 {
+    node.get("components")
+        .and_then(JsonValue::as_array)
+        .map(|components| {
+            components
+                .iter()
+                .map(|component| 1 + count_components(component))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn required_str<'a>(document: &'a JsonValue, path: &[&str]) -> Result<&'a str, DomainError> {
+    path.iter()
+        .try_fold(document, |node, key| node.get(key))
+        .and_then(JsonValue::as_str)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| missing(&path.join(".")))
+}
+
+fn check_version(format: &str, version: &str, supported: &[&str]) -> Result<(), DomainError> {
+    if supported.contains(&version) {
+        return Ok(());
+    }
+    Err(DomainError::ValidationError {
+        field: "payload".to_string(),
+        message: Message::new("validation.attestation_spec_version")
+            .with("format", format)
+            .with("version", version)
+            .with("supported", supported.join(", ")),
+    })
+}
+
+fn missing(path: &str) -> DomainError {
+    DomainError::ValidationError {
+        field: "payload".to_string(),
+        message: Message::new("validation.attestation_field_missing").with("path", path),
+    }
+}
+
+fn dedup(mut digests: Vec<String>) -> Vec<String> {
+    digests.sort();
+    digests.dedup();
+    digests
+}
+
+/// Attestation documents under `tests/fixtures/attestations`
+#[cfg(test)]
+pub(crate) mod fixtures {
+    pub const CYCLONEDX_1_2: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/attestations/cyclonedx-1.2.json"
+    ));
+    pub const CYCLONEDX_1_5: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/attestations/cyclonedx-1.5.json"
+    ));
+    pub const SLSA_PROVENANCE_V0_2: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/attestations/slsa-provenance-v0.2.json"
+    ));
+    pub const SLSA_PROVENANCE_V1: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/attestations/slsa-provenance-v1.json"
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> JsonValue {
+        let document = match name {
+            "cyclonedx-1.2.json" => fixtures::CYCLONEDX_1_2,
+            "cyclonedx-1.5.json" => fixtures::CYCLONEDX_1_5,
+            "slsa-provenance-v0.2.json" => fixtures::SLSA_PROVENANCE_V0_2,
+            "slsa-provenance-v1.json" => fixtures::SLSA_PROVENANCE_V1,
+            _ => panic!("unknown fixture {}", name),
+        };
+        serde_json::from_str(document).unwrap()
+    }
+
+    fn message_key(error: DomainError) -> &'static str {
+        match error {
+            DomainError::ValidationError { message, .. } => message.key(),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_extracts_cyclonedx_sbom() {
+        let attestation = Attestation::extract(&fixture("cyclonedx-1.5.json")).unwrap();
+
+        assert_eq!(attestation.format, AttestationFormat::CycloneDx);
+        assert_eq!(attestation.spec_version, "1.5");
+        assert_eq!(
+            attestation.subject_digests,
+            vec![
+                "sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+                "sha3_256:36f028580bb02cc8272a9a020f4200e346e276ae664e45ee80745574e2f5ab80",
+            ]
+        );
+        assert_eq!(attestation.builder_id, None);
+        assert_eq!(attestation.component_count, Some(4));
+        assert_eq!(attestation.component_name.as_deref(), Some("xzepr"));
+        assert_eq!(attestation.component_version.as_deref(), Some("0.4.2"));
+    }
+
+    #[test]
+    fn test_extracts_slsa_provenance() {
+        let v02 = Attestation::extract(&fixture("slsa-provenance-v0.2.json")).unwrap();
+        assert_eq!(v02.format, AttestationFormat::SlsaProvenance);
+        assert_eq!(v02.spec_version, "v0.2");
+        assert_eq!(
+            v02.builder_id.as_deref(),
+            Some("https://github.com/slsa-framework/slsa-github-generator/generic@v1")
+        );
+        assert_eq!(
+            v02.subject_digests,
+            vec![
+                "sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+                "sha512:ee26b0dd4af7e749aa1a8ee3c10ae9923f618980772e473f8819a5d4940e0db27ac185f8a0e1d5f84f88bc887fd67b143732c304cc5fa9ad8e6f57f50028a8ff",
+            ]
+        );
+        assert_eq!(v02.component_count, None);
+
+        let v1 = Attestation::extract(&fixture("slsa-provenance-v1.json")).unwrap();
+        assert_eq!(v1.spec_version, "v1");
+        assert_eq!(
+            v1.builder_id.as_deref(),
+            Some("https://cloudbuild.googleapis.com/GoogleHostedWorker")
+        );
+        assert_eq!(
+            v1.subject_digests,
+            vec!["sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"]
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsupported_spec_version() {
+        let error = Attestation::extract(&fixture("cyclonedx-1.2.json")).unwrap_err();
+        assert!(error.to_string().contains("1.2"));
+        assert!(error.to_string().contains("1.4, 1.5, 1.6"));
+        assert_eq!(message_key(error), "validation.attestation_spec_version");
+
+        let mut statement = fixture("slsa-provenance-v1.json");
+        statement["predicateType"] = "https://slsa.dev/provenance/v9".into();
+        assert_eq!(
+            message_key(Attestation::extract(&statement).unwrap_err()),
+            "validation.attestation_spec_version"
+        );
+    }
+
+    #[test]
+    fn test_rejects_incomplete_documents() {
+        assert_eq!(
+            message_key(Attestation::extract(&serde_json::json!({"name": "x"})).unwrap_err()),
+            "validation.attestation_unrecognized"
+        );
+
+        let mut statement = fixture("slsa-provenance-v1.json");
+        statement["predicate"]["runDetails"]
+            .as_object_mut()
+            .unwrap()
+            .remove("builder");
+        let error = Attestation::extract(&statement).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("predicate.runDetails.builder.id"));
+
+        let mut statement = fixture("slsa-provenance-v1.json");
+        statement["subject"] = serde_json::json!([]);
+        assert_eq!(
+            message_key(Attestation::extract(&statement).unwrap_err()),
+            "validation.attestation_field_missing"
+        );
+    }
+
+    #[test]
+    fn test_normalize_digest() {
+        assert_eq!(normalize_digest("SHA256:ABCdef").unwrap(), "sha256:abcdef");
+        assert_eq!(normalize_digest(" sha1:00ff ").unwrap(), "sha1:00ff");
+        assert!(normalize_digest("abcdef").is_err());
+        assert!(normalize_digest("sha256:").is_err());
+        assert!(normalize_digest("sha256:xyz").is_err());
+        assert!(normalize_digest("sha-256:abcd").is_err());
+    }
+
+    #[test]
+    fn test_cyclonedx_algorithm_names_match_in_toto() {
+        assert_eq!(cyclonedx_algorithm("SHA-256"), "sha256");
+        assert_eq!(cyclonedx_algorithm("SHA3-512"), "sha3_512");
+        assert_eq!(cyclonedx_algorithm("BLAKE2b-256"), "blake2b_256");
+        assert_eq!(cyclonedx_algorithm("MD5"), "md5");
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        for format in [
+            AttestationFormat::CycloneDx,
+            AttestationFormat::SlsaProvenance,
+        ] {
+            assert_eq!(
+                format.as_str().parse::<AttestationFormat>().unwrap(),
+                format
+            );
+        }
+        assert!("spdx".parse::<AttestationFormat>().is_err());
+    }
+}
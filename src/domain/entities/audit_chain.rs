@@ -0,0 +1,357 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/audit_chain.rs
+
+//! Tamper-evident chaining of persisted audit records
+//!
+//! Audit records form one hash chain per UTC day. Each record stores the
+//! SHA-256 over the hash of the record before it, its day, its position in
+//! the day, and its canonical content. The first record of a day links to a
+//! genesis hash derived from the date. Changing a record breaks its own
+//! hash, and inserting or deleting one in the middle of a day breaks the
+//! link of the record after it.
+//!
+//! Days are chained independently so that concurrent writers only have to
+//! serialize within a day. When retention removes the oldest records of a
+//! day, the hash of the last removed record is kept as an anchor, so the
+//! records that remain still verify.
+//!
+//! The chain cannot show that records were removed from the end of a day,
+//! or that a whole day was removed.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// A persisted audit record
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// Storage id, increasing in insertion order
+    pub id: i64,
+    /// UTC day whose chain the record belongs to
+    pub chain_day: NaiveDate,
+    /// Zero-based position in the day's chain
+    pub position: i64,
+    pub recorded_at: DateTime<Utc>,
+    /// Canonical JSON of the audit event
+    pub content: String,
+    /// Hash of the record before this one, or the day's genesis hash
+    pub prev_hash: String,
+    /// Hex-encoded SHA-256 over `prev_hash`, day, position, and content
+    pub hash: String,
+}
+
+impl AuditRecord {
+    /// Returns the link other records chain to
+    pub fn link(&self) -> ChainLink {
+        ChainLink {
+            chain_day: self.chain_day,
+            position: self.position,
+            hash: self.hash.clone(),
+        }
+    }
+}
+
+/// The hash at one position of a day's chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainLink {
+    pub chain_day: NaiveDate,
+    pub position: i64,
+    pub hash: String,
+}
+
+/// Returns the hash the first record of `day` links to
+pub fn genesis_hash(day: NaiveDate) -> String {
+    hex::encode(Sha256::digest(format!("xzepr.audit.genesis:{}", day)))
+}
+
+/// Computes the hash of a record
+pub fn record_hash(prev_hash: &str, chain_day: NaiveDate, position: i64, content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.as_bytes());
+    hasher.update(format!("\n{}\n{}\n", chain_day, position).as_bytes());
+    hasher.update(content.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Returns the position and previous hash of the record appended to `day`
+/// after `last`, the day's current last link
+pub fn next_link(day: NaiveDate, last: Option<&ChainLink>) -> (i64, String) {
+    match last {
+        Some(link) => (link.position + 1, link.hash.clone()),
+        None => (0, genesis_hash(day)),
+    }
+}
+
+/// Why a record does not verify
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditChainFault {
+    /// The stored hash does not match the record's content
+    ContentMismatch,
+    /// The record does not link to the hash of the record before it
+    PreviousHashMismatch,
+    /// The record before this one is neither stored nor anchored
+    MissingPredecessor,
+}
+
+impl AuditChainFault {
+    /// Returns the name used in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ContentMismatch => "content_mismatch",
+            Self::PreviousHashMismatch => "previous_hash_mismatch",
+            Self::MissingPredecessor => "missing_predecessor",
+        }
+    }
+}
+
+/// The first record that failed verification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BrokenAuditLink {
+    pub record_id: i64,
+    pub chain_day: NaiveDate,
+    pub position: i64,
+    pub recorded_at: DateTime<Utc>,
+    pub fault: AuditChainFault,
+}
+
+/// Result of walking the audit chain over a range of days
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AuditChainReport {
+    pub records_checked: u64,
+    pub days_checked: u64,
+    /// `None` if every checked record verified
+    pub first_broken: Option<BrokenAuditLink>,
+}
+
+impl AuditChainReport {
+    /// Whether every checked record verified
+    pub fn is_intact(&self) -> bool {
+        self.first_broken.is_none()
+    }
+}
+
+/// Walks audit records in chain order and finds the first broken link
+///
+/// Feed records ordered by day and position. A record that starts a day
+/// after position zero links to a record outside the walk; the caller looks
+/// that link up, from the stored record or its retention anchor, and passes
+/// it along.
+#[derive(Debug, Default)]
+pub struct AuditChainVerifier {
+    last: Option<ChainLink>,
+    report: AuditChainReport,
+}
+
+impl AuditChainVerifier {
+    /// Creates a verifier that has seen no records
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether checking `record` needs the link before it from the caller
+    pub fn needs_predecessor(&self, record: &AuditRecord) -> bool {
+        record.position > 0 && self.starts_day(record)
+    }
+
+    /// Checks the next record
+    ///
+    /// `predecessor` is only consulted when [`needs_predecessor`] is true.
+    /// Returns `false` once a broken link has been found; later records are
+    /// not checked.
+    ///
+    /// [`needs_predecessor`]: Self::needs_predecessor
+    pub fn check(&mut self, record: &AuditRecord, predecessor: Option<&ChainLink>) -> bool {
+        if self.report.first_broken.is_some() {
+            return false;
+        }
+
+        let expected_prev = if !self.starts_day(record) {
+            self.last
+                .as_ref()
+                .filter(|last| last.position + 1 == record.position)
+                .map(|last| last.hash.clone())
+        } else if record.position == 0 {
+            Some(genesis_hash(record.chain_day))
+        } else {
+            predecessor
+                .filter(|link| {
+                    link.chain_day == record.chain_day && link.position + 1 == record.position
+                })
+                .map(|link| link.hash.clone())
+        };
+
+        if self.starts_day(record) {
+            self.report.days_checked += 1;
+        }
+        self.report.records_checked += 1;
+        self.last = Some(record.link());
+
+        let fault = match expected_prev {
+            None => Some(AuditChainFault::MissingPredecessor),
+            Some(prev) if prev != record.prev_hash => Some(AuditChainFault::PreviousHashMismatch),
+            Some(_)
+                if record_hash(
+                    &record.prev_hash,
+                    record.chain_day,
+                    record.position,
+                    &record.content,
+                ) != record.hash =>
+            {
+                Some(AuditChainFault::ContentMismatch)
+            }
+            Some(_) => None,
+        };
+
+        match fault {
+            Some(fault) => {
+                self.report.first_broken = Some(BrokenAuditLink {
+                    record_id: record.id,
+                    chain_day: record.chain_day,
+                    position: record.position,
+                    recorded_at: record.recorded_at,
+                    fault,
+                });
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Returns the report of the records checked so far
+    pub fn finish(self) -> AuditChainReport {
+        self.report
+    }
+
+    fn starts_day(&self, record: &AuditRecord) -> bool {
+        !matches!(&self.last, Some(last) if last.chain_day == record.chain_day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 5, d).unwrap()
+    }
+
+    /// Builds `count` chained records per day
+    fn seed(days: &[NaiveDate], count: i64) -> Vec<AuditRecord> {
+        let mut records = Vec::new();
+        for &chain_day in days {
+            let mut last: Option<ChainLink> = None;
+            for n in 0..count {
+                let (position, prev_hash) = next_link(chain_day, last.as_ref());
+                let content = format!(r#"{{"action":"login","n":{}}}"#, n);
+                let record = AuditRecord {
+                    id: records.len() as i64 + 1,
+                    chain_day,
+                    position,
+                    recorded_at: Utc
+                        .from_utc_datetime(&chain_day.and_hms_opt(12, 0, n as u32).unwrap()),
+                    hash: record_hash(&prev_hash, chain_day, position, &content),
+                    content,
+                    prev_hash,
+                };
+                last = Some(record.link());
+                records.push(record);
+            }
+        }
+        records
+    }
+
+    fn verify(records: &[AuditRecord], anchors: &[ChainLink]) -> AuditChainReport {
+        let mut verifier = AuditChainVerifier::new();
+        for record in records {
+            let predecessor = verifier
+                .needs_predecessor(record)
+                .then(|| {
+                    anchors.iter().find(|a| {
+                        a.chain_day == record.chain_day && a.position + 1 == record.position
+                    })
+                })
+                .flatten();
+            if !verifier.check(record, predecessor) {
+                break;
+            }
+        }
+        verifier.finish()
+    }
+
+    #[test]
+    fn test_seeded_chain_verifies() {
+        let records = seed(&[day(1), day(2)], 5);
+
+        let report = verify(&records, &[]);
+
+        assert!(report.is_intact());
+        assert_eq!(report.records_checked, 10);
+        assert_eq!(report.days_checked, 2);
+    }
+
+    #[test]
+    fn test_mutated_record_is_reported_with_its_position() {
+        let mut records = seed(&[day(1), day(2)], 5);
+        records[7].content = r#"{"action":"logout","n":2}"#.to_string();
+
+        let report = verify(&records, &[]);
+
+        let broken = report.first_broken.unwrap();
+        assert_eq!(broken.chain_day, day(2));
+        assert_eq!(broken.position, 2);
+        assert_eq!(broken.record_id, 8);
+        assert_eq!(broken.fault, AuditChainFault::ContentMismatch);
+        assert_eq!(report.records_checked, 8);
+    }
+
+    #[test]
+    fn test_rehashed_record_breaks_the_next_link() {
+        let mut records = seed(&[day(1)], 4);
+        let forged = r#"{"action":"logout","n":1}"#.to_string();
+        records[1].hash = record_hash(&records[1].prev_hash, day(1), 1, &forged);
+        records[1].content = forged;
+
+        let broken = verify(&records, &[]).first_broken.unwrap();
+
+        assert_eq!(broken.position, 2);
+        assert_eq!(broken.fault, AuditChainFault::PreviousHashMismatch);
+    }
+
+    #[test]
+    fn test_deleted_record_is_reported_as_missing() {
+        let mut records = seed(&[day(1)], 4);
+        records.remove(2);
+
+        let broken = verify(&records, &[]).first_broken.unwrap();
+
+        assert_eq!(broken.position, 3);
+        assert_eq!(broken.fault, AuditChainFault::MissingPredecessor);
+    }
+
+    #[test]
+    fn test_purged_prefix_verifies_from_its_anchor() {
+        let records = seed(&[day(1), day(2)], 5);
+        let anchor = records[2].link();
+        let remaining = &records[3..];
+
+        // Without the anchor the first remaining record has no predecessor
+        let broken = verify(remaining, &[]).first_broken.unwrap();
+        assert_eq!(broken.position, 3);
+        assert_eq!(broken.fault, AuditChainFault::MissingPredecessor);
+
+        let report = verify(remaining, std::slice::from_ref(&anchor));
+        assert!(report.is_intact());
+        assert_eq!(report.records_checked, 7);
+
+        // An anchor does not hide a record changed after it
+        let mut tampered = remaining.to_vec();
+        tampered[0].content = "{}".to_string();
+        let broken = verify(&tampered, &[anchor]).first_broken.unwrap();
+        assert_eq!(broken.position, 3);
+        assert_eq!(broken.fault, AuditChainFault::ContentMismatch);
+    }
+}
@@ -0,0 +1,223 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/delivery_failure.rs
+
+use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::error::DomainError;
+use crate::i18n::Message;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// How xzepr tried to deliver an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryMechanism {
+    /// Publication to the primary topic, retried from the event outbox
+    EventPublish,
+    /// Publication to a group's dedicated topic, retried from the fan-out
+    /// outbox
+    GroupTopicFanout,
+}
+
+impl DeliveryMechanism {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryMechanism::EventPublish => "event_publish",
+            DeliveryMechanism::GroupTopicFanout => "group_topic_fanout",
+        }
+    }
+}
+
+impl FromStr for DeliveryMechanism {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "event_publish" => Ok(DeliveryMechanism::EventPublish),
+            "group_topic_fanout" => Ok(DeliveryMechanism::GroupTopicFanout),
+            other => Err(DomainError::ValidationError {
+                field: "mechanism".to_string(),
+                message: Message::new("validation.delivery_mechanism_unknown")
+                    .with("value", other)
+                    .with("options", "event_publish, group_topic_fanout"),
+            }),
+        }
+    }
+}
+
+/// Broad cause of a failed delivery, telling on-call where to look
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryErrorClass {
+    /// The target did not answer in time
+    Timeout,
+    /// The target could not be reached
+    Unavailable,
+    /// The target answered and refused the delivery
+    Rejected,
+    /// Anything else
+    Other,
+}
+
+impl DeliveryErrorClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryErrorClass::Timeout => "timeout",
+            DeliveryErrorClass::Unavailable => "unavailable",
+            DeliveryErrorClass::Rejected => "rejected",
+            DeliveryErrorClass::Other => "other",
+        }
+    }
+
+    /// Classifies a delivery error from its message
+    pub fn classify(error: &str) -> Self {
+        let error = error.to_ascii_lowercase();
+        let mentions = |needles: &[&str]| needles.iter().any(|needle| error.contains(needle));
+
+        if mentions(&["timed out", "timeout"]) {
+            DeliveryErrorClass::Timeout
+        } else if mentions(&[
+            "unavailable",
+            "transport",
+            "all brokers down",
+            "allbrokersdown",
+            "connection refused",
+            "not enough replicas",
+        ]) {
+            DeliveryErrorClass::Unavailable
+        } else if mentions(&[
+            "rejected",
+            "too large",
+            "toolarge",
+            "authorization",
+            "unknown topic",
+            "unknowntopic",
+            "invalid",
+        ]) {
+            DeliveryErrorClass::Rejected
+        } else {
+            DeliveryErrorClass::Other
+        }
+    }
+}
+
+impl FromStr for DeliveryErrorClass {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "timeout" => Ok(DeliveryErrorClass::Timeout),
+            "unavailable" => Ok(DeliveryErrorClass::Unavailable),
+            "rejected" => Ok(DeliveryErrorClass::Rejected),
+            "other" => Ok(DeliveryErrorClass::Other),
+            other => Err(DomainError::ValidationError {
+                field: "error_class".to_string(),
+                message: Message::new("validation.delivery_error_class_unknown")
+                    .with("value", other)
+                    .with("options", "timeout, unavailable, rejected, other"),
+            }),
+        }
+    }
+}
+
+/// A delivery that was given up on after its last retry
+///
+/// There is at most one per mechanism, event, and target, since an
+/// outbox entry is dead-lettered once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryFailure {
+    pub mechanism: DeliveryMechanism,
+    /// Topic the event was being delivered to
+    pub target: String,
+    pub event_id: EventId,
+    /// Receiver of the event
+    pub receiver_id: EventReceiverId,
+    pub error_class: DeliveryErrorClass,
+    /// Error of the last attempt
+    pub last_error: String,
+    /// Attempts made, the first publish included
+    pub attempts: u32,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl DeliveryFailure {
+    /// Describes a delivery given up on after `attempts`, classifying its
+    /// last error
+    pub fn new(
+        mechanism: DeliveryMechanism,
+        target: impl Into<String>,
+        event_id: EventId,
+        receiver_id: EventReceiverId,
+        last_error: impl Into<String>,
+        attempts: u32,
+        failed_at: DateTime<Utc>,
+    ) -> Self {
+        let last_error = last_error.into();
+        Self {
+            mechanism,
+            target: target.into(),
+            event_id,
+            receiver_id,
+            error_class: DeliveryErrorClass::classify(&last_error),
+            last_error,
+            attempts,
+            failed_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_are_classified_by_message() {
+        for (error, class) in [
+            (
+                "Kafka producer error: Message production error: MessageTimedOut (Local: Message timed out)",
+                DeliveryErrorClass::Timeout,
+            ),
+            ("broker unavailable", DeliveryErrorClass::Unavailable),
+            (
+                "Kafka producer error: Local: All broker connections are down (AllBrokersDown)",
+                DeliveryErrorClass::Unavailable,
+            ),
+            (
+                "Kafka producer error: Broker: Message size too large",
+                DeliveryErrorClass::Rejected,
+            ),
+            (
+                "Broker: Topic authorization failed",
+                DeliveryErrorClass::Rejected,
+            ),
+            ("something odd", DeliveryErrorClass::Other),
+        ] {
+            assert_eq!(DeliveryErrorClass::classify(error), class, "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for class in [
+            DeliveryErrorClass::Timeout,
+            DeliveryErrorClass::Unavailable,
+            DeliveryErrorClass::Rejected,
+            DeliveryErrorClass::Other,
+        ] {
+            assert_eq!(class.as_str().parse::<DeliveryErrorClass>().unwrap(), class);
+        }
+        for mechanism in [
+            DeliveryMechanism::EventPublish,
+            DeliveryMechanism::GroupTopicFanout,
+        ] {
+            assert_eq!(
+                mechanism.as_str().parse::<DeliveryMechanism>().unwrap(),
+                mechanism
+            );
+        }
+        assert!("webhook".parse::<DeliveryErrorClass>().is_err());
+        assert!("webhook".parse::<DeliveryMechanism>().is_err());
+    }
+}
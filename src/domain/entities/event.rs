@@ -5,8 +5,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::value_objects::{EventId, EventReceiverId, UserId};
+use crate::domain::entities::payload_summary::PayloadSummary;
+use crate::domain::entities::pii_detection::{PiiScan, PiiScanner};
+use crate::domain::value_objects::{EventId, EventReceiverId, UserId, Version, VersionStrictness};
 use crate::error::DomainError;
+use crate::i18n::Message;
 
 /// Parameters for creating a new event
 #[derive(Debug, Clone)]
@@ -23,6 +26,50 @@ pub struct CreateEventParams {
     pub owner_id: UserId,
 }
 
+/// Who created an event
+///
+/// User events are submitted through the API. System events are emitted
+/// by xzepr itself, e.g. when a receiver group is created, and are left out
+/// of user-facing lists and stats unless asked for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventOrigin {
+    #[default]
+    User,
+    System,
+}
+
+impl EventOrigin {
+    /// Returns the name stored in the database and used in filters
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventOrigin::User => "user",
+            EventOrigin::System => "system",
+        }
+    }
+}
+
+impl std::fmt::Display for EventOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for EventOrigin {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(EventOrigin::User),
+            "system" => Ok(EventOrigin::System),
+            other => Err(DomainError::ValidationError {
+                field: "origin".to_string(),
+                message: Message::new("validation.origin_unknown").with("value", other),
+            }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
     id: EventId,
@@ -36,8 +83,14 @@ pub struct Event {
     success: bool,
     event_receiver_id: EventReceiverId,
     owner_id: UserId,
+    #[serde(default)]
+    origin: EventOrigin,
     resource_version: i64,
     created_at: DateTime<Utc>,
+    /// Present while the payload is held in the blob store; `payload` is
+    /// then an empty object
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    payload_summary: Option<PayloadSummary>,
 }
 
 impl Event {
@@ -49,7 +102,8 @@ impl Event {
     ///
     /// # Returns
     ///
-    /// Returns a new Event or DomainError if validation fails
+    /// Returns a new Event or DomainError if validation fails. The version
+    /// is stored in canonical semantic version form.
     ///
     /// # Examples
     ///
@@ -74,12 +128,13 @@ impl Event {
     /// assert!(event.is_ok());
     /// ```
     pub fn new(params: CreateEventParams) -> Result<Self, DomainError> {
+        let version = Version::normalize(&params.version, VersionStrictness::Lenient)?;
         Self::validate_payload(&params.payload)?;
 
         Ok(Self {
             id: EventId::new(),
             name: params.name,
-            version: params.version,
+            version,
             release: params.release,
             platform_id: params.platform_id,
             package: params.package,
@@ -88,17 +143,29 @@ impl Event {
             success: params.success,
             event_receiver_id: params.receiver_id,
             owner_id: params.owner_id,
+            origin: EventOrigin::User,
             resource_version: 1,
             created_at: Utc::now(),
+            payload_summary: None,
         })
     }
 
+    /// Creates an event emitted by xzepr itself
+    ///
+    /// Only internal creation paths call this; events submitted through
+    /// the API are always user events.
+    pub fn new_system(params: CreateEventParams) -> Result<Self, DomainError> {
+        let mut event = Self::new(params)?;
+        event.origin = EventOrigin::System;
+        Ok(event)
+    }
+
     fn validate_payload(payload: &serde_json::Value) -> Result<(), DomainError> {
         // Validation logic
         if !payload.is_object() {
             return Err(DomainError::ValidationError {
                 field: "payload".to_string(),
-                message: "Event payload must be a JSON object".to_string(),
+                message: Message::new("validation.event_payload_not_object"),
             });
         }
         Ok(())
@@ -137,6 +204,28 @@ impl Event {
         &self.payload
     }
 
+    /// Scans the payload for personal data, masking it in `redact` mode
+    pub fn scan_payload(&mut self, scanner: &PiiScanner) -> PiiScan {
+        scanner.apply(&mut self.payload)
+    }
+
+    /// Returns the summary of a payload held in the blob store
+    pub fn payload_summary(&self) -> Option<&PayloadSummary> {
+        self.payload_summary.as_ref()
+    }
+
+    /// Replaces the payload with the summary of its blob store copy
+    pub fn offload_payload(&mut self, summary: PayloadSummary) {
+        self.payload = serde_json::Value::Object(serde_json::Map::new());
+        self.payload_summary = Some(summary);
+    }
+
+    /// Puts the full payload of an offloaded event back in place
+    pub fn restore_payload(&mut self, payload: serde_json::Value) {
+        self.payload = payload;
+        self.payload_summary = None;
+    }
+
     pub fn success(&self) -> bool {
         self.success
     }
@@ -154,6 +243,11 @@ impl Event {
         self.owner_id
     }
 
+    /// Returns whether a user or xzepr itself created this event
+    pub fn origin(&self) -> EventOrigin {
+        self.origin
+    }
+
     /// Returns the current resource version for cache invalidation
     ///
     /// This version is used by the authorization cache to detect stale entries.
@@ -186,8 +280,10 @@ impl Event {
             success: fields.success,
             event_receiver_id: fields.event_receiver_id,
             owner_id: fields.owner_id,
+            origin: fields.origin,
             resource_version: fields.resource_version,
             created_at: fields.created_at,
+            payload_summary: None,
         }
     }
 }
@@ -206,6 +302,7 @@ pub struct DatabaseEventFields {
     pub success: bool,
     pub event_receiver_id: EventReceiverId,
     pub owner_id: UserId,
+    pub origin: EventOrigin,
     pub resource_version: i64,
     pub created_at: DateTime<Utc>,
 }
@@ -366,7 +463,7 @@ mod tests {
         match result.unwrap_err() {
             DomainError::ValidationError { field, message } => {
                 assert_eq!(field, "payload");
-                assert!(message.contains("JSON object"));
+                assert!(message.to_string().contains("JSON object"));
             }
             _ => panic!("Expected ValidationError"),
         }
@@ -637,4 +734,64 @@ mod tests {
         assert_eq!(event.owner_id(), owner_id);
         assert_eq!(event.resource_version(), 1);
     }
+
+    #[test]
+    fn test_version_is_normalized() {
+        let params = |version: &str| CreateEventParams {
+            name: "test".to_string(),
+            version: version.to_string(),
+            release: "release".to_string(),
+            platform_id: "platform".to_string(),
+            package: "package".to_string(),
+            description: "desc".to_string(),
+            payload: json!({}),
+            success: true,
+            receiver_id: EventReceiverId::new(),
+            owner_id: UserId::new(),
+        };
+
+        let event = Event::new(params("v3.1")).unwrap();
+        assert_eq!(event.version(), "3.1.0");
+
+        match Event::new(params("invalid-version")) {
+            Err(DomainError::ValidationError { field, message }) => {
+                assert_eq!(field, "version");
+                assert!(message.to_string().contains("MAJOR.MINOR.PATCH"));
+            }
+            other => panic!("expected a version validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_origin() {
+        let params = || CreateEventParams {
+            name: "xzepr.event.receiver.created".to_string(),
+            version: "1.0.0".to_string(),
+            release: "system".to_string(),
+            platform_id: "xzepr".to_string(),
+            package: "xzepr.system".to_string(),
+            description: "desc".to_string(),
+            payload: json!({}),
+            success: true,
+            receiver_id: EventReceiverId::new(),
+            owner_id: UserId::new(),
+        };
+
+        assert_eq!(Event::new(params()).unwrap().origin(), EventOrigin::User);
+        let system = Event::new_system(params()).unwrap();
+        assert_eq!(system.origin(), EventOrigin::System);
+
+        // Events serialized before origins existed are user events
+        let mut value = serde_json::to_value(&system).unwrap();
+        assert_eq!(value["origin"], "system");
+        value.as_object_mut().unwrap().remove("origin");
+        let event: Event = serde_json::from_value(value).unwrap();
+        assert_eq!(event.origin(), EventOrigin::User);
+
+        assert_eq!(
+            "system".parse::<EventOrigin>().unwrap(),
+            EventOrigin::System
+        );
+        assert!("admin".parse::<EventOrigin>().is_err());
+    }
 }
@@ -0,0 +1,148 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/event_attachment.rs
+
+//! Files attached to events
+//!
+//! Artifacts such as test reports and SBOMs are too large for an event
+//! payload, so they are stored as blobs next to the event. The database only
+//! holds their metadata and the key of the blob, which is derived from the
+//! event and attachment ids.
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::value_objects::{AttachmentId, EventId, EventReceiverId, UserId};
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Maximum length of an attachment filename in characters
+pub const MAX_FILENAME_LENGTH: usize = 255;
+
+/// Metadata of a file attached to an event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventAttachment {
+    pub id: AttachmentId,
+    pub event_id: EventId,
+    /// Receiver of the event, whose storage quota the attachment counts
+    /// toward
+    pub event_receiver_id: EventReceiverId,
+    pub filename: String,
+    /// Media type without parameters, such as `application/json`
+    pub content_type: String,
+    pub size_bytes: u64,
+    /// Hex-encoded SHA-256 of the content
+    pub sha256: String,
+    /// Location of the content in the blob store
+    pub storage_key: String,
+    pub uploaded_by: UserId,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Returns the blob store key of an attachment
+pub fn storage_key(event_id: EventId, id: AttachmentId) -> String {
+    format!("{}/{}", event_id, id)
+}
+
+/// Returns the name an uploaded file is stored and served under
+///
+/// Clients may send a full path, so only the part after the last `/` or
+/// `\` is kept. Control characters and double quotes are dropped so that
+/// the name can be placed in a `Content-Disposition` header.
+///
+/// # Errors
+///
+/// Returns a validation error if nothing is left or the name is longer than
+/// [`MAX_FILENAME_LENGTH`] characters.
+pub fn sanitize_filename(filename: &str) -> Result<String, DomainError> {
+    let name: String = filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .collect();
+    let name = name.trim();
+
+    if name.is_empty() || name == "." || name == ".." {
+        return Err(DomainError::ValidationError {
+            field: "file".to_string(),
+            message: Message::new("validation.attachment_filename_empty"),
+        });
+    }
+    if name.chars().count() > MAX_FILENAME_LENGTH {
+        return Err(DomainError::ValidationError {
+            field: "file".to_string(),
+            message: Message::new("validation.attachment_filename_too_long")
+                .with("max", MAX_FILENAME_LENGTH),
+        });
+    }
+    Ok(name.to_string())
+}
+
+/// Returns the lowercase media type of `content_type` without parameters
+pub fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_filename_keeps_the_last_path_segment() {
+        assert_eq!(sanitize_filename("report.xml").unwrap(), "report.xml");
+        assert_eq!(
+            sanitize_filename("../../etc/passwd").unwrap(),
+            "passwd".to_string()
+        );
+        assert_eq!(
+            sanitize_filename("C:\\builds\\sbom.json").unwrap(),
+            "sbom.json"
+        );
+        assert_eq!(
+            sanitize_filename(" \"junit\"\r\n.xml ").unwrap(),
+            "junit.xml"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_filename_rejects_empty_and_long_names() {
+        for name in ["", "  ", "dir/", "..", "\"\""] {
+            let error = sanitize_filename(name).unwrap_err();
+            assert_eq!(
+                error.localizable().unwrap().1.key(),
+                "validation.attachment_filename_empty",
+                "{:?}",
+                name
+            );
+        }
+
+        let long = "a".repeat(MAX_FILENAME_LENGTH + 1);
+        let error = sanitize_filename(&long).unwrap_err();
+        assert_eq!(
+            error.localizable().unwrap().1.key(),
+            "validation.attachment_filename_too_long"
+        );
+        assert!(sanitize_filename(&long[1..]).is_ok());
+    }
+
+    #[test]
+    fn test_storage_key_is_scoped_to_the_event() {
+        let event_id = EventId::new();
+        let id = AttachmentId::new();
+
+        assert_eq!(storage_key(event_id, id), format!("{}/{}", event_id, id));
+    }
+
+    #[test]
+    fn test_media_type_drops_parameters() {
+        assert_eq!(media_type("Text/Plain; charset=utf-8"), "text/plain");
+        assert_eq!(media_type("application/json"), "application/json");
+    }
+}
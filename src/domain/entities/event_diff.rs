@@ -0,0 +1,747 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/event_diff.rs
+
+//! Differences between two events
+//!
+//! Metadata is compared field by field. Payloads are compared structurally
+//! and the result is given twice: as a JSON Patch (RFC 6902) that turns the
+//! first payload into the second, and as a flat list of changed paths with
+//! the values on either side. Patch paths are JSON Pointers into the
+//! document as the patch is applied; listed paths are written like
+//! `$.build.steps[2].name` and use the index each value has in its own
+//! payload.
+//!
+//! Array elements are aligned on their longest common subsequence, so an
+//! insertion or removal does not show up as a change of every later
+//! element. Arrays too large to align are compared index by index.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value as JsonValue};
+
+use crate::domain::entities::event::Event;
+
+/// Serialized size above which a listed value is truncated
+pub const DEFAULT_MAX_VALUE_BYTES: usize = 256;
+
+/// Most cells the alignment table of two arrays may have
+const MAX_ALIGNMENT_CELLS: usize = 1_000_000;
+
+/// A metadata field whose value differs between the events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: JsonValue,
+    pub after: JsonValue,
+}
+
+/// Compares the metadata of two events
+///
+/// Covers the name, version, release, platform, package, description,
+/// outcome, receiver, origin, and creation time. IDs and owners are not
+/// compared.
+pub fn diff_metadata(before: &Event, after: &Event) -> Vec<FieldChange> {
+    let fields = [
+        ("name", json!(before.name()), json!(after.name())),
+        ("version", json!(before.version()), json!(after.version())),
+        ("release", json!(before.release()), json!(after.release())),
+        (
+            "platform_id",
+            json!(before.platform_id()),
+            json!(after.platform_id()),
+        ),
+        ("package", json!(before.package()), json!(after.package())),
+        (
+            "description",
+            json!(before.description()),
+            json!(after.description()),
+        ),
+        ("success", json!(before.success()), json!(after.success())),
+        (
+            "event_receiver_id",
+            json!(before.event_receiver_id().to_string()),
+            json!(after.event_receiver_id().to_string()),
+        ),
+        (
+            "origin",
+            json!(before.origin().as_str()),
+            json!(after.origin().as_str()),
+        ),
+        (
+            "created_at",
+            json!(before.created_at()),
+            json!(after.created_at()),
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, before, after)| before != after)
+        .map(|(field, before, after)| FieldChange {
+            field: field.to_string(),
+            before,
+            after,
+        })
+        .collect()
+}
+
+/// One JSON Patch operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: JsonValue },
+    Remove { path: String },
+    Replace { path: String, value: JsonValue },
+}
+
+/// How a listed path changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// Same JSON type, different value
+    Changed,
+    /// The value became a different JSON type
+    TypeChanged,
+}
+
+/// A value shown in a listed change
+///
+/// Values whose serialized form exceeds the limit are cut short: strings
+/// keep the start of their text, other values the start of their JSON, and
+/// `size_bytes` gives the full serialized size.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffValue {
+    pub value: JsonValue,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<usize>,
+}
+
+impl DiffValue {
+    /// Wraps `value`, truncating it beyond `max_bytes`
+    pub fn new(value: &JsonValue, max_bytes: usize) -> Self {
+        let serialized = value.to_string();
+        if serialized.len() <= max_bytes {
+            return Self {
+                value: value.clone(),
+                truncated: false,
+                size_bytes: None,
+            };
+        }
+
+        let text = match value {
+            JsonValue::String(text) => text.as_str(),
+            _ => serialized.as_str(),
+        };
+        Self {
+            value: JsonValue::String(truncate_at_char_boundary(text, max_bytes).to_string()),
+            truncated: true,
+            size_bytes: Some(serialized.len()),
+        }
+    }
+}
+
+/// A changed path with the values on either side
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<DiffValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<DiffValue>,
+}
+
+/// Differences between two payloads
+///
+/// `patch` and `changes` describe the same differences; each operation
+/// has one listed change, in the same order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PayloadDiff {
+    pub patch: Vec<PatchOperation>,
+    pub changes: Vec<PathChange>,
+}
+
+impl PayloadDiff {
+    /// Returns true if the payloads are equal
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of differences
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+}
+
+/// Compares two payloads
+///
+/// Listed values larger than `max_value_bytes` are truncated; the patch
+/// always carries complete values.
+pub fn diff_payloads(before: &JsonValue, after: &JsonValue, max_value_bytes: usize) -> PayloadDiff {
+    let mut differ = Differ {
+        max_value_bytes,
+        diff: PayloadDiff::default(),
+    };
+    differ.value(&Location::root(), before, after);
+    differ.diff
+}
+
+/// Where a value sits, as a JSON Pointer and as a listed path
+#[derive(Clone)]
+struct Location {
+    pointer: String,
+    path: String,
+}
+
+impl Location {
+    fn root() -> Self {
+        Self {
+            pointer: String::new(),
+            path: "$".to_string(),
+        }
+    }
+
+    fn key(&self, key: &str) -> Self {
+        Self {
+            pointer: format!("{}/{}", self.pointer, escape_pointer(key)),
+            path: format!("{}{}", self.path, path_key(key)),
+        }
+    }
+
+    /// `pointer_index` is the position while patching, `path_index` the
+    /// position in the payload the value comes from
+    fn index(&self, pointer_index: usize, path_index: usize) -> Self {
+        Self {
+            pointer: format!("{}/{}", self.pointer, pointer_index),
+            path: format!("{}[{}]", self.path, path_index),
+        }
+    }
+}
+
+struct Differ {
+    max_value_bytes: usize,
+    diff: PayloadDiff,
+}
+
+impl Differ {
+    fn value(&mut self, at: &Location, before: &JsonValue, after: &JsonValue) {
+        if before == after {
+            return;
+        }
+        match (before, after) {
+            (JsonValue::Object(before), JsonValue::Object(after)) => self.object(at, before, after),
+            (JsonValue::Array(before), JsonValue::Array(after)) => self.array(at, before, after),
+            _ => self.replace(at, before, after),
+        }
+    }
+
+    fn object(
+        &mut self,
+        at: &Location,
+        before: &Map<String, JsonValue>,
+        after: &Map<String, JsonValue>,
+    ) {
+        let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        for key in keys {
+            let at = at.key(key);
+            match (before.get(key), after.get(key)) {
+                (Some(before), Some(after)) => self.value(&at, before, after),
+                (Some(before), None) => self.remove(&at, before),
+                (None, Some(after)) => self.add(&at, after),
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn array(&mut self, at: &Location, before: &[JsonValue], after: &[JsonValue]) {
+        let matches = align(before, after);
+        let (mut b, mut a) = (0, 0);
+
+        // Between matched elements, pair up removed and added elements as
+        // changes, then remove or add the rest. `a` is also the position
+        // in the array being patched, since everything before it already
+        // matches `after`.
+        for (next_b, next_a) in matches
+            .into_iter()
+            .chain(std::iter::once((before.len(), after.len())))
+        {
+            while b < next_b && a < next_a {
+                self.value(&at.index(a, a), &before[b], &after[a]);
+                b += 1;
+                a += 1;
+            }
+            while b < next_b {
+                self.remove(&at.index(a, b), &before[b]);
+                b += 1;
+            }
+            while a < next_a {
+                self.add(&at.index(a, a), &after[a]);
+                a += 1;
+            }
+            b += 1;
+            a += 1;
+        }
+    }
+
+    fn add(&mut self, at: &Location, value: &JsonValue) {
+        self.diff.patch.push(PatchOperation::Add {
+            path: at.pointer.clone(),
+            value: value.clone(),
+        });
+        self.diff.changes.push(PathChange {
+            path: at.path.clone(),
+            kind: ChangeKind::Added,
+            before: None,
+            after: Some(DiffValue::new(value, self.max_value_bytes)),
+        });
+    }
+
+    fn remove(&mut self, at: &Location, value: &JsonValue) {
+        self.diff.patch.push(PatchOperation::Remove {
+            path: at.pointer.clone(),
+        });
+        self.diff.changes.push(PathChange {
+            path: at.path.clone(),
+            kind: ChangeKind::Removed,
+            before: Some(DiffValue::new(value, self.max_value_bytes)),
+            after: None,
+        });
+    }
+
+    fn replace(&mut self, at: &Location, before: &JsonValue, after: &JsonValue) {
+        let kind = if json_type(before) == json_type(after) {
+            ChangeKind::Changed
+        } else {
+            ChangeKind::TypeChanged
+        };
+        self.diff.patch.push(PatchOperation::Replace {
+            path: at.pointer.clone(),
+            value: after.clone(),
+        });
+        self.diff.changes.push(PathChange {
+            path: at.path.clone(),
+            kind,
+            before: Some(DiffValue::new(before, self.max_value_bytes)),
+            after: Some(DiffValue::new(after, self.max_value_bytes)),
+        });
+    }
+}
+
+/// Returns the index pairs of a longest common subsequence, in order
+///
+/// Common leading and trailing elements are matched directly. When the
+/// rest is too large to align, only those are matched.
+fn align(before: &[JsonValue], after: &[JsonValue]) -> Vec<(usize, usize)> {
+    let prefix = before
+        .iter()
+        .zip(after)
+        .take_while(|(before, after)| before == after)
+        .count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(before, after)| before == after)
+        .count();
+    let middle_before = &before[prefix..before.len() - suffix];
+    let middle_after = &after[prefix..after.len() - suffix];
+
+    let mut matches: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    let (n, m) = (middle_before.len(), middle_after.len());
+    if n > 0 && m > 0 && (n + 1).saturating_mul(m + 1) <= MAX_ALIGNMENT_CELLS {
+        // lengths[i][j] is the LCS length of middle_before[i..] and
+        // middle_after[j..]
+        let width = m + 1;
+        let mut lengths = vec![0u32; (n + 1) * width];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lengths[i * width + j] = if middle_before[i] == middle_after[j] {
+                    lengths[(i + 1) * width + j + 1] + 1
+                } else {
+                    lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if middle_before[i] == middle_after[j] {
+                matches.push((prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+    matches.extend((0..suffix).map(|k| (before.len() - suffix + k, after.len() - suffix + k)));
+    matches
+}
+
+fn json_type(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
+/// Escapes a key as a JSON Pointer reference token (RFC 6901)
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Writes a key as `.key`, or `["key"]` when it is not a plain identifier
+fn path_key(key: &str) -> String {
+    let plain = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    if plain {
+        format!(".{}", key)
+    } else {
+        format!("[{}]", JsonValue::String(key.to_string()))
+    }
+}
+
+fn truncate_at_char_boundary(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event::CreateEventParams;
+    use crate::domain::value_objects::{EventReceiverId, UserId};
+
+    fn diff(before: JsonValue, after: JsonValue) -> PayloadDiff {
+        diff_payloads(&before, &after, DEFAULT_MAX_VALUE_BYTES)
+    }
+
+    /// Applies a patch the way RFC 6902 does, for the operations produced
+    fn apply(document: &JsonValue, patch: &[PatchOperation]) -> JsonValue {
+        fn parent<'a>(root: &'a mut JsonValue, path: &str) -> (&'a mut JsonValue, String) {
+            let (parent, token) = path.rsplit_once('/').unwrap();
+            let token = token.replace("~1", "/").replace("~0", "~");
+            (root.pointer_mut(parent).unwrap(), token)
+        }
+
+        let mut document = document.clone();
+        for operation in patch {
+            match operation {
+                PatchOperation::Replace { path, value } if path.is_empty() => {
+                    document = value.clone();
+                }
+                PatchOperation::Replace { path, value } => {
+                    *document.pointer_mut(path).unwrap() = value.clone();
+                }
+                PatchOperation::Add { path, value } => match parent(&mut document, path) {
+                    (JsonValue::Array(items), token) => {
+                        items.insert(token.parse().unwrap(), value.clone())
+                    }
+                    (JsonValue::Object(map), token) => {
+                        map.insert(token, value.clone());
+                    }
+                    _ => panic!("add into a scalar"),
+                },
+                PatchOperation::Remove { path } => match parent(&mut document, path) {
+                    (JsonValue::Array(items), token) => {
+                        items.remove(token.parse::<usize>().unwrap());
+                    }
+                    (JsonValue::Object(map), token) => {
+                        map.remove(&token);
+                    }
+                    _ => panic!("remove from a scalar"),
+                },
+            }
+        }
+        document
+    }
+
+    /// Diffs the payloads and checks the patch reproduces `after`
+    fn round_trip(before: JsonValue, after: JsonValue) -> PayloadDiff {
+        let diff = diff(before.clone(), after.clone());
+        assert_eq!(apply(&before, &diff.patch), after);
+        assert_eq!(diff.patch.len(), diff.changes.len());
+        diff
+    }
+
+    fn paths(diff: &PayloadDiff) -> Vec<(&str, ChangeKind)> {
+        diff.changes
+            .iter()
+            .map(|change| (change.path.as_str(), change.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_equal_payloads_have_no_differences() {
+        let payload = json!({"a": [1, {"b": null}], "c": "d"});
+        let diff = diff(payload.clone(), payload);
+        assert!(diff.is_empty());
+        assert!(diff.patch.is_empty());
+    }
+
+    #[test]
+    fn test_added_removed_and_changed_keys() {
+        let diff = round_trip(
+            json!({"keep": 1, "drop": true, "build": {"id": 7, "host": "a"}}),
+            json!({"keep": 1, "new": [1], "build": {"id": 8, "host": "a"}}),
+        );
+
+        assert_eq!(
+            paths(&diff),
+            vec![
+                ("$.build.id", ChangeKind::Changed),
+                ("$.drop", ChangeKind::Removed),
+                ("$.new", ChangeKind::Added),
+            ]
+        );
+        assert_eq!(
+            diff.patch,
+            vec![
+                PatchOperation::Replace {
+                    path: "/build/id".to_string(),
+                    value: json!(8)
+                },
+                PatchOperation::Remove {
+                    path: "/drop".to_string()
+                },
+                PatchOperation::Add {
+                    path: "/new".to_string(),
+                    value: json!([1])
+                },
+            ]
+        );
+        assert_eq!(diff.changes[0].before.as_ref().unwrap().value, json!(7));
+        assert_eq!(diff.changes[1].after, None);
+        assert_eq!(diff.changes[2].before, None);
+    }
+
+    #[test]
+    fn test_type_changes() {
+        let diff = round_trip(
+            json!({"n": 1, "s": "1", "o": {"a": 1}, "l": [1], "z": null, "f": 1}),
+            json!({"n": "1", "s": "2", "o": [1], "l": {"a": 1}, "z": false, "f": 1.0}),
+        );
+
+        assert_eq!(
+            paths(&diff),
+            vec![
+                ("$.f", ChangeKind::Changed),
+                ("$.l", ChangeKind::TypeChanged),
+                ("$.n", ChangeKind::TypeChanged),
+                ("$.o", ChangeKind::TypeChanged),
+                ("$.s", ChangeKind::Changed),
+                ("$.z", ChangeKind::TypeChanged),
+            ]
+        );
+
+        let root = round_trip(json!({"a": 1}), json!("scalar"));
+        assert_eq!(paths(&root), vec![("$", ChangeKind::TypeChanged)]);
+        assert_eq!(
+            root.patch,
+            vec![PatchOperation::Replace {
+                path: String::new(),
+                value: json!("scalar")
+            }]
+        );
+    }
+
+    #[test]
+    fn test_array_insertions_and_removals_do_not_shift_later_elements() {
+        let diff = round_trip(json!([1, 2, 3, 4]), json!([0, 1, 2, 4, 5]));
+
+        assert_eq!(
+            paths(&diff),
+            vec![
+                ("$[0]", ChangeKind::Added),
+                ("$[2]", ChangeKind::Removed),
+                ("$[4]", ChangeKind::Added),
+            ]
+        );
+        assert_eq!(
+            diff.patch,
+            vec![
+                PatchOperation::Add {
+                    path: "/0".to_string(),
+                    value: json!(0)
+                },
+                PatchOperation::Remove {
+                    path: "/3".to_string()
+                },
+                PatchOperation::Add {
+                    path: "/4".to_string(),
+                    value: json!(5)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_array_reordering() {
+        let diff = round_trip(json!(["a", "b", "c"]), json!(["c", "a", "b"]));
+        assert_eq!(
+            paths(&diff),
+            vec![("$[0]", ChangeKind::Added), ("$[2]", ChangeKind::Removed)]
+        );
+
+        let swapped = round_trip(json!([1, 2]), json!([2, 1]));
+        assert_eq!(swapped.len(), 2);
+
+        let reversed = round_trip(json!([1, 2, 3, 4, 5]), json!([5, 4, 3, 2, 1]));
+        assert_eq!(reversed.len(), 8);
+    }
+
+    #[test]
+    fn test_changed_array_elements_are_diffed_in_place() {
+        let diff = round_trip(
+            json!({"steps": [{"name": "build", "ok": true}, {"name": "test", "ok": true}]}),
+            json!({"steps": [{"name": "build", "ok": true}, {"name": "test", "ok": false}]}),
+        );
+
+        assert_eq!(paths(&diff), vec![("$.steps[1].ok", ChangeKind::Changed)]);
+        assert_eq!(
+            diff.patch,
+            vec![PatchOperation::Replace {
+                path: "/steps/1/ok".to_string(),
+                value: json!(false)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_large_arrays_fall_back_to_index_comparison() {
+        let before: Vec<u32> = (0..2000).collect();
+        let after: Vec<u32> = (1..2001).collect();
+        let diff = round_trip(json!(before), json!(after));
+        assert_eq!(diff.len(), 2000);
+        assert!(diff
+            .changes
+            .iter()
+            .all(|change| change.kind == ChangeKind::Changed));
+    }
+
+    #[test]
+    fn test_unicode_and_special_keys() {
+        let diff = round_trip(
+            json!({"ключ": "значение", "a/b": 1, "m~n": 1, "with space": 1, "": 1, "9lives": 1}),
+            json!({"ключ": "значение 🚀", "a/b": 2, "m~n": 2, "with space": 2, "": 2, "9lives": 2}),
+        );
+
+        let pointers: Vec<&str> = diff
+            .patch
+            .iter()
+            .map(|operation| match operation {
+                PatchOperation::Replace { path, .. } => path.as_str(),
+                _ => panic!("unexpected operation"),
+            })
+            .collect();
+        assert_eq!(
+            pointers,
+            vec!["/", "/9lives", "/a~1b", "/m~0n", "/with space", "/ключ"]
+        );
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|change| change.path.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                r#"$[""]"#,
+                r#"$["9lives"]"#,
+                r#"$["a/b"]"#,
+                r#"$["m~n"]"#,
+                r#"$["with space"]"#,
+                "$.ключ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_large_values_are_truncated_at_char_boundaries() {
+        let text = "é".repeat(100);
+        let diff = diff_payloads(&json!({"s": "short"}), &json!({"s": text}), 15);
+        let after = diff.changes[0].after.as_ref().unwrap();
+
+        assert!(after.truncated);
+        assert_eq!(after.value, json!("é".repeat(7)));
+        assert_eq!(after.size_bytes, Some(202));
+        let before = diff.changes[0].before.as_ref().unwrap();
+        assert!(!before.truncated);
+        assert_eq!(before.size_bytes, None);
+
+        // Non-string values keep the start of their JSON
+        let value = DiffValue::new(&json!({"list": [1, 2, 3, 4, 5]}), 10);
+        assert_eq!(value.value, json!(r#"{"list":[1"#));
+        assert_eq!(value.size_bytes, Some(20));
+
+        // The patch is never truncated
+        assert_eq!(
+            diff.patch[0],
+            PatchOperation::Replace {
+                path: "/s".to_string(),
+                value: json!("é".repeat(100))
+            }
+        );
+        let serialized = serde_json::to_value(&diff.changes[0]).unwrap();
+        assert_eq!(serialized["before"], json!({"value": "short"}));
+        assert_eq!(serialized["after"]["truncated"], true);
+    }
+
+    fn event(version: &str, success: bool, receiver_id: EventReceiverId) -> Event {
+        Event::new(CreateEventParams {
+            name: "build".to_string(),
+            version: version.to_string(),
+            release: "1".to_string(),
+            platform_id: "linux".to_string(),
+            package: "pkg".to_string(),
+            description: "Build finished".to_string(),
+            payload: json!({}),
+            success,
+            receiver_id,
+            owner_id: UserId::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_metadata_differences() {
+        let receiver_id = EventReceiverId::new();
+        let passing = event("1.0.0", true, receiver_id);
+        let failing = event("1.0.1", false, receiver_id);
+
+        let changes = diff_metadata(&passing, &failing);
+        let fields: Vec<&str> = changes.iter().map(|change| change.field.as_str()).collect();
+        assert!(fields.contains(&"version"));
+        assert!(fields.contains(&"success"));
+        assert!(!fields.contains(&"name"));
+        assert!(!fields.contains(&"event_receiver_id"));
+        let success = changes.iter().find(|c| c.field == "success").unwrap();
+        assert_eq!(
+            (&success.before, &success.after),
+            (&json!(true), &json!(false))
+        );
+
+        assert!(diff_metadata(&passing, &passing).is_empty());
+    }
+}
@@ -0,0 +1,278 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/event_name_constraint.rs
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Name prefix reserved for events xzepr emits about itself
+pub const RESERVED_EVENT_NAME_PREFIX: &str = "xzepr.";
+
+/// Longest glob or regular expression a receiver may configure
+pub const MAX_EVENT_NAME_PATTERN_LEN: usize = 512;
+
+/// Most exact names a receiver may list
+pub const MAX_ALLOWED_EVENT_NAMES: usize = 100;
+
+/// Compiled size limit that keeps a hostile regex from exhausting memory
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Returns true if `name` uses the prefix reserved for system events
+///
+/// Compared case-insensitively so `XZEPR.group.created` cannot slip past.
+pub fn is_reserved_event_name(name: &str) -> bool {
+    name.get(..RESERVED_EVENT_NAME_PREFIX.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(RESERVED_EVENT_NAME_PREFIX))
+}
+
+/// Event names a receiver accepts
+///
+/// Serialized as exactly one of `{"names": [...]}`, `{"glob": "..."}`, or
+/// `{"regex": "..."}`. Globs and regular expressions must match the whole
+/// name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowedEventNames {
+    /// Exact event names
+    Names(Vec<String>),
+    /// Glob where `*` matches any run of characters and `?` one character
+    Glob(String),
+    /// Regular expression
+    Regex(String),
+}
+
+impl AllowedEventNames {
+    /// Checks the constraint can be compiled
+    pub fn validate(&self) -> Result<(), DomainError> {
+        self.compile().map(|_| ())
+    }
+
+    /// Compiles the constraint into a matcher
+    pub fn compile(&self) -> Result<EventNameMatcher, DomainError> {
+        let regex = match self {
+            AllowedEventNames::Names(names) => {
+                if names.is_empty() {
+                    return Err(invalid(Message::new(
+                        "validation.allowed_event_names_empty",
+                    )));
+                }
+                if names.len() > MAX_ALLOWED_EVENT_NAMES {
+                    return Err(invalid(
+                        Message::new("validation.allowed_event_names_too_many")
+                            .with("max", MAX_ALLOWED_EVENT_NAMES),
+                    ));
+                }
+                if names.iter().any(|name| name.trim().is_empty()) {
+                    return Err(invalid(Message::new(
+                        "validation.allowed_event_names_blank",
+                    )));
+                }
+                None
+            }
+            AllowedEventNames::Glob(glob) => Some(compile_pattern(&glob_to_regex(glob), glob)?),
+            AllowedEventNames::Regex(regex) => Some(compile_pattern(regex, regex)?),
+        };
+
+        Ok(EventNameMatcher {
+            constraint: self.clone(),
+            regex,
+        })
+    }
+
+    /// Returns the names or pattern, for error messages
+    pub fn patterns(&self) -> Vec<String> {
+        match self {
+            AllowedEventNames::Names(names) => names.clone(),
+            AllowedEventNames::Glob(pattern) | AllowedEventNames::Regex(pattern) => {
+                vec![pattern.clone()]
+            }
+        }
+    }
+}
+
+/// Compiled form of [`AllowedEventNames`]
+///
+/// Compiling a regular expression is far more expensive than matching one,
+/// so callers keep matchers for as long as the receiver is unchanged.
+#[derive(Debug, Clone)]
+pub struct EventNameMatcher {
+    constraint: AllowedEventNames,
+    regex: Option<Regex>,
+}
+
+impl EventNameMatcher {
+    /// Returns true if the receiver accepts events named `name`
+    pub fn permits(&self, name: &str) -> bool {
+        match (&self.constraint, &self.regex) {
+            (AllowedEventNames::Names(names), _) => names.iter().any(|n| n == name),
+            (_, Some(regex)) => regex.is_match(name),
+            (_, None) => false,
+        }
+    }
+
+    /// Rejects `name` unless the receiver accepts it
+    ///
+    /// The error lists the accepted names or pattern.
+    pub fn check(&self, name: &str) -> Result<(), DomainError> {
+        if self.permits(name) {
+            return Ok(());
+        }
+
+        Err(DomainError::ValidationError {
+            field: "name".to_string(),
+            message: Message::new("validation.event_name_not_allowed")
+                .with("name", name)
+                .with("allowed", self.constraint.patterns().join(", ")),
+        })
+    }
+
+    pub fn constraint(&self) -> &AllowedEventNames {
+        &self.constraint
+    }
+}
+
+fn invalid(message: Message) -> DomainError {
+    DomainError::ValidationError {
+        field: "allowed_event_names".to_string(),
+        message,
+    }
+}
+
+/// Anchors `regex` so it must match the whole name
+fn compile_pattern(regex: &str, pattern: &str) -> Result<Regex, DomainError> {
+    if pattern.trim().is_empty() {
+        return Err(invalid(Message::new(
+            "validation.allowed_event_names_empty",
+        )));
+    }
+    if pattern.len() > MAX_EVENT_NAME_PATTERN_LEN {
+        return Err(invalid(
+            Message::new("validation.event_name_pattern_too_long")
+                .with("max", MAX_EVENT_NAME_PATTERN_LEN),
+        ));
+    }
+
+    RegexBuilder::new(&format!("^(?:{})$", regex))
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| {
+            invalid(
+                Message::new("validation.event_name_pattern_invalid")
+                    .with("pattern", pattern)
+                    .with("reason", e),
+            )
+        })
+}
+
+/// Translates a glob into an unanchored regular expression
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::with_capacity(glob.len() * 2);
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserved_event_names() {
+        assert!(is_reserved_event_name("xzepr.event.receiver.group.created"));
+        assert!(is_reserved_event_name("XZEPR.group.created"));
+        assert!(is_reserved_event_name("xzepr."));
+        assert!(!is_reserved_event_name("xzepr"));
+        assert!(!is_reserved_event_name("xzeprs.build"));
+        assert!(!is_reserved_event_name("build.xzepr.finished"));
+    }
+
+    #[test]
+    fn test_glob_matches_whole_name() {
+        let matcher = AllowedEventNames::Glob("build.*.finished".to_string())
+            .compile()
+            .unwrap();
+
+        assert!(matcher.permits("build.linux.finished"));
+        assert!(matcher.permits("build..finished"));
+        assert!(!matcher.permits("build.linux.finished.late"));
+        assert!(!matcher.permits("buildXlinux.finished"));
+        assert!(!matcher.permits("deploy.finished"));
+
+        let matcher = AllowedEventNames::Glob("v?.ready".to_string())
+            .compile()
+            .unwrap();
+        assert!(matcher.permits("v1.ready"));
+        assert!(!matcher.permits("v10.ready"));
+    }
+
+    #[test]
+    fn test_names_and_regex() {
+        let names = AllowedEventNames::Names(vec![
+            "build.started".to_string(),
+            "build.finished".to_string(),
+        ])
+        .compile()
+        .unwrap();
+        assert!(names.permits("build.finished"));
+        assert!(!names.permits("build.*"));
+
+        match names.check("deploy.finished") {
+            Err(DomainError::ValidationError { field, message }) => {
+                assert_eq!(field, "name");
+                assert_eq!(message.key(), "validation.event_name_not_allowed");
+                assert!(message
+                    .to_string()
+                    .contains("build.started, build.finished"));
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+
+        let regex = AllowedEventNames::Regex(r"deploy\.(staging|prod)".to_string())
+            .compile()
+            .unwrap();
+        assert!(regex.permits("deploy.prod"));
+        assert!(!regex.permits("deploy.prod.rollback"));
+    }
+
+    #[test]
+    fn test_invalid_constraints_are_field_errors() {
+        for constraint in [
+            AllowedEventNames::Regex("build.(".to_string()),
+            AllowedEventNames::Regex(" ".to_string()),
+            AllowedEventNames::Glob("x".repeat(MAX_EVENT_NAME_PATTERN_LEN + 1)),
+            AllowedEventNames::Names(Vec::new()),
+            AllowedEventNames::Names(vec!["build".to_string(), "".to_string()]),
+        ] {
+            match constraint.validate() {
+                Err(DomainError::ValidationError { field, .. }) => {
+                    assert_eq!(field, "allowed_event_names", "{:?}", constraint);
+                }
+                other => panic!(
+                    "expected a field error for {:?}, got {:?}",
+                    constraint, other
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_serialized_form() {
+        let constraint: AllowedEventNames = serde_json::from_str(r#"{"glob": "build.*"}"#).unwrap();
+        assert_eq!(constraint, AllowedEventNames::Glob("build.*".to_string()));
+        assert_eq!(
+            serde_json::to_value(AllowedEventNames::Names(vec!["a".to_string()])).unwrap(),
+            serde_json::json!({"names": ["a"]})
+        );
+        assert!(
+            serde_json::from_str::<AllowedEventNames>(r#"{"glob": "a", "regex": "b"}"#).is_err()
+        );
+    }
+}
@@ -0,0 +1,111 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/event_publication.rs
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Header a client sets to choose the publish policy of one request
+pub const PUBLISH_POLICY_HEADER: &str = "x-publish-policy";
+
+/// Delay before the first retry of a deferred publication
+const FIRST_RETRY_DELAY_SECONDS: i64 = 5;
+
+/// Longest delay between retries of a deferred publication
+const MAX_RETRY_DELAY_SECONDS: i64 = 15 * 60;
+
+/// What to do with a stored event the stream did not accept
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PublishPolicy {
+    /// Keep the event and publish it later from the outbox
+    #[default]
+    BestEffort,
+    /// Remove the event and fail the request
+    Require,
+}
+
+impl PublishPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PublishPolicy::BestEffort => "best-effort",
+            PublishPolicy::Require => "require",
+        }
+    }
+}
+
+impl FromStr for PublishPolicy {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "best-effort" => Ok(PublishPolicy::BestEffort),
+            "require" => Ok(PublishPolicy::Require),
+            other => Err(DomainError::ValidationError {
+                field: "X-Publish-Policy".to_string(),
+                message: Message::new("validation.publish_policy_unknown")
+                    .with("value", other)
+                    .with("options", "require, best-effort"),
+            }),
+        }
+    }
+}
+
+/// Whether a stored event has reached the stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishStatus {
+    /// The stream accepted the event
+    Published,
+    /// The event waits in the outbox for a retry
+    Deferred,
+}
+
+impl PublishStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PublishStatus::Published => "published",
+            PublishStatus::Deferred => "deferred",
+        }
+    }
+}
+
+/// Returns how long to wait before retry number `attempts` of a deferred
+/// publication
+///
+/// The delay doubles with every failed attempt, up to 15 minutes.
+pub fn publish_retry_delay(attempts: u32) -> Duration {
+    let doublings = attempts.saturating_sub(1).min(16);
+    let seconds = FIRST_RETRY_DELAY_SECONDS.saturating_mul(1 << doublings);
+    Duration::seconds(seconds.min(MAX_RETRY_DELAY_SECONDS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_parses_header_values() {
+        assert_eq!(
+            "require".parse::<PublishPolicy>().unwrap(),
+            PublishPolicy::Require
+        );
+        assert_eq!(
+            " Best-Effort ".parse::<PublishPolicy>().unwrap(),
+            PublishPolicy::BestEffort
+        );
+        assert!("always".parse::<PublishPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off_to_cap() {
+        assert_eq!(publish_retry_delay(1), Duration::seconds(5));
+        assert_eq!(publish_retry_delay(2), Duration::seconds(10));
+        assert_eq!(publish_retry_delay(4), Duration::seconds(40));
+        assert_eq!(publish_retry_delay(50), Duration::minutes(15));
+    }
+}
@@ -3,8 +3,19 @@
 
 // src/domain/entities/event_receiver.rs
 
-use crate::domain::value_objects::{EventReceiverId, UserId};
+use crate::domain::canonical_json::canonical_bytes;
+use crate::domain::entities::attestation::{Attestation, ATTESTATION_RECEIVER_TYPE};
+use crate::domain::entities::event_name_constraint::AllowedEventNames;
+use crate::domain::entities::payload_transform::PayloadTransform;
+use crate::domain::entities::receiver_auto_disable::{
+    AutoDisablePolicy, AutoDisableTrigger, ReceiverState,
+};
+use crate::domain::value_objects::description::{
+    check_description_length, normalize_description, MAX_DESCRIPTION_LENGTH,
+};
+use crate::domain::value_objects::{EventReceiverId, UserId, Version, VersionStrictness};
 use crate::error::DomainError;
+use crate::i18n::Message;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -22,7 +33,14 @@ pub struct EventReceiverData {
     pub fingerprint: String,
     pub owner_id: UserId,
     pub resource_version: i64,
+    pub sample_rate: Option<f64>,
+    pub allowed_event_names: Option<AllowedEventNames>,
+    pub auto_disable_policy: Option<AutoDisablePolicy>,
+    pub auto_disabled: Option<AutoDisableTrigger>,
+    pub auto_disable_window_start: Option<DateTime<Utc>>,
+    pub transform: Option<PayloadTransform>,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 /// Event receiver entity representing a destination for events
@@ -37,7 +55,26 @@ pub struct EventReceiver {
     fingerprint: String,
     owner_id: UserId,
     resource_version: i64,
+    /// Fraction of events stored; `None` stores every event
+    #[serde(default)]
+    sample_rate: Option<f64>,
+    /// Event names accepted; `None` accepts any name
+    #[serde(default)]
+    allowed_event_names: Option<AllowedEventNames>,
+    /// Failure rate that disables the receiver; `None` never disables it
+    #[serde(default)]
+    auto_disable_policy: Option<AutoDisablePolicy>,
+    /// Statistics that disabled the receiver; `None` while it is active
+    #[serde(default)]
+    auto_disabled: Option<AutoDisableTrigger>,
+    /// Events before this time are not evaluated; set on re-enable
+    #[serde(default)]
+    auto_disable_window_start: Option<DateTime<Utc>>,
+    /// Rewrites applied to payloads before validation; `None` keeps them
+    #[serde(default)]
+    transform: Option<PayloadTransform>,
     created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
 }
 
 impl EventReceiver {
@@ -47,7 +84,8 @@ impl EventReceiver {
     ///
     /// * `name` - The name of the event receiver
     /// * `receiver_type` - The type of the event receiver
-    /// * `version` - The version of the event receiver
+    /// * `version` - The semantic version of the event receiver, stored in
+    ///   canonical form (`v1.0` becomes `1.0.0`)
     /// * `description` - A description of the event receiver
     /// * `schema` - The JSON schema for event validation
     /// * `owner_id` - The user ID of the owner who created this receiver
@@ -85,11 +123,12 @@ impl EventReceiver {
     ) -> Result<Self, DomainError> {
         Self::validate_name(&name)?;
         Self::validate_type(&receiver_type)?;
-        Self::validate_version(&version)?;
-        Self::validate_description(&description)?;
+        let version = Version::normalize(&version, VersionStrictness::Lenient)?;
+        let description = normalize_description(&description, MAX_DESCRIPTION_LENGTH)?;
         Self::validate_schema(&schema)?;
 
-        let fingerprint = Self::generate_fingerprint(&name, &receiver_type, &version, &schema);
+        let fingerprint = Self::generate_fingerprint(&name, &receiver_type, &version, &schema)?;
+        let now = Utc::now();
 
         Ok(Self {
             id: EventReceiverId::new(),
@@ -101,7 +140,14 @@ impl EventReceiver {
             fingerprint,
             owner_id,
             resource_version: 1,
-            created_at: Utc::now(),
+            sample_rate: None,
+            allowed_event_names: None,
+            auto_disable_policy: None,
+            auto_disabled: None,
+            auto_disable_window_start: None,
+            transform: None,
+            created_at: now,
+            updated_at: now,
         })
     }
 
@@ -110,8 +156,18 @@ impl EventReceiver {
         Self::validate_name(&data.name)?;
         Self::validate_type(&data.receiver_type)?;
         Self::validate_version(&data.version)?;
-        Self::validate_description(&data.description)?;
+        check_description_length(&data.description, MAX_DESCRIPTION_LENGTH)?;
         Self::validate_schema(&data.schema)?;
+        Self::validate_sample_rate(data.sample_rate)?;
+        if let Some(allowed) = &data.allowed_event_names {
+            allowed.validate()?;
+        }
+        if let Some(policy) = &data.auto_disable_policy {
+            policy.validate()?;
+        }
+        if let Some(transform) = &data.transform {
+            transform.validate()?;
+        }
 
         Ok(Self {
             id: data.id,
@@ -123,13 +179,21 @@ impl EventReceiver {
             fingerprint: data.fingerprint,
             owner_id: data.owner_id,
             resource_version: data.resource_version,
+            sample_rate: data.sample_rate,
+            allowed_event_names: data.allowed_event_names,
+            auto_disable_policy: data.auto_disable_policy,
+            auto_disabled: data.auto_disabled,
+            auto_disable_window_start: data.auto_disable_window_start,
+            transform: data.transform,
             created_at: data.created_at,
+            updated_at: data.updated_at,
         })
     }
 
     /// Updates the event receiver with new data
     ///
     /// Increments the resource_version on any update to support cache invalidation.
+    /// The updated_at timestamp moves forward whenever any field changes.
     ///
     /// # Arguments
     ///
@@ -151,6 +215,7 @@ impl EventReceiver {
         schema: Option<JsonValue>,
     ) -> Result<(), DomainError> {
         let mut updated = false;
+        let mut touched = false;
 
         if let Some(new_name) = name {
             Self::validate_name(&new_name)?;
@@ -165,14 +230,13 @@ impl EventReceiver {
         }
 
         if let Some(new_version) = version {
-            Self::validate_version(&new_version)?;
-            self.version = new_version;
+            self.version = Version::normalize(&new_version, VersionStrictness::Lenient)?;
             updated = true;
         }
 
         if let Some(new_description) = description {
-            Self::validate_description(&new_description)?;
-            self.description = new_description;
+            self.description = normalize_description(&new_description, MAX_DESCRIPTION_LENGTH)?;
+            touched = true;
         }
 
         if let Some(new_schema) = schema {
@@ -188,28 +252,172 @@ impl EventReceiver {
                 &self.receiver_type,
                 &self.version,
                 &self.schema,
-            );
+            )?;
+            self.resource_version += 1;
+        }
+
+        if updated || touched {
+            self.updated_at = Utc::now();
+        }
+
+        Ok(())
+    }
+
+    /// Sets the fraction of events this receiver stores
+    ///
+    /// `None` disables sampling. Changing the rate increments the
+    /// resource_version but leaves the fingerprint alone, since sampling
+    /// does not change what the receiver accepts.
+    pub fn set_sample_rate(&mut self, sample_rate: Option<f64>) -> Result<(), DomainError> {
+        Self::validate_sample_rate(sample_rate)?;
+
+        if sample_rate != self.sample_rate {
+            self.sample_rate = sample_rate;
+            self.resource_version += 1;
+            self.updated_at = Utc::now();
+        }
+
+        Ok(())
+    }
+
+    /// Constrains the event names this receiver accepts
+    ///
+    /// `None` accepts any name. Like the sample rate, the constraint
+    /// increments the resource_version but is not part of the fingerprint.
+    pub fn set_allowed_event_names(
+        &mut self,
+        allowed_event_names: Option<AllowedEventNames>,
+    ) -> Result<(), DomainError> {
+        if let Some(allowed) = &allowed_event_names {
+            allowed.validate()?;
+        }
+
+        if allowed_event_names != self.allowed_event_names {
+            self.allowed_event_names = allowed_event_names;
+            self.resource_version += 1;
+            self.updated_at = Utc::now();
+        }
+
+        Ok(())
+    }
+
+    /// Sets the failure rate at which this receiver is disabled
+    ///
+    /// `None` removes the policy; a receiver it already disabled stays
+    /// disabled until it is re-enabled.
+    pub fn set_auto_disable_policy(
+        &mut self,
+        policy: Option<AutoDisablePolicy>,
+    ) -> Result<(), DomainError> {
+        if let Some(policy) = &policy {
+            policy.validate()?;
+        }
+
+        if policy != self.auto_disable_policy {
+            self.auto_disable_policy = policy;
+            self.resource_version += 1;
+            self.updated_at = Utc::now();
+        }
+
+        Ok(())
+    }
+
+    /// Sets the rewrites applied to payloads before validation
+    ///
+    /// `None` or an empty transform stores payloads as they arrive. The
+    /// transform is not part of the fingerprint, since the schema still
+    /// describes what the receiver accepts once payloads are rewritten.
+    pub fn set_transform(
+        &mut self,
+        transform: Option<PayloadTransform>,
+    ) -> Result<(), DomainError> {
+        if let Some(transform) = &transform {
+            transform.validate()?;
+        }
+        let transform = transform.filter(|t| !t.is_empty());
+
+        if transform != self.transform {
+            self.transform = transform;
             self.resource_version += 1;
+            self.updated_at = Utc::now();
         }
 
         Ok(())
     }
 
+    /// Stops this receiver accepting events because `trigger` breached its
+    /// policy
+    ///
+    /// Returns false if it was already disabled, keeping the first trigger.
+    pub fn auto_disable(&mut self, trigger: AutoDisableTrigger) -> bool {
+        if self.auto_disabled.is_some() {
+            return false;
+        }
+
+        self.auto_disabled = Some(trigger);
+        self.resource_version += 1;
+        self.updated_at = Utc::now();
+        true
+    }
+
+    /// Accepts events again after an automatic disable
+    ///
+    /// The observation window restarts at `now`, so the failures that
+    /// disabled the receiver do not disable it again. Returns false if the
+    /// receiver was not disabled.
+    pub fn enable(&mut self, now: DateTime<Utc>) -> bool {
+        if self.auto_disabled.is_none() {
+            return false;
+        }
+
+        self.auto_disabled = None;
+        self.auto_disable_window_start = Some(now);
+        self.resource_version += 1;
+        self.updated_at = now;
+        true
+    }
+
+    /// Returns the window `[start, now)` the policy is evaluated over
+    ///
+    /// `None` without a policy or while disabled. The window never starts
+    /// before the receiver was last re-enabled.
+    pub fn auto_disable_window(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let policy = self.auto_disable_policy.as_ref()?;
+        if self.auto_disabled.is_some() {
+            return None;
+        }
+
+        let start = now - policy.observation_window();
+        let start = match self.auto_disable_window_start {
+            Some(reset_at) => start.max(reset_at),
+            None => start,
+        };
+        (start < now).then_some((start, now))
+    }
+
     /// Generates a unique fingerprint for the event receiver
-    fn generate_fingerprint(
+    ///
+    /// Also used to re-fingerprint stored receivers whose version is
+    /// normalized in place. The schema is hashed in canonical form, so
+    /// schemas differing only in key order or number spelling match.
+    pub fn generate_fingerprint(
         name: &str,
         receiver_type: &str,
         version: &str,
         schema: &JsonValue,
-    ) -> String {
+    ) -> Result<String, DomainError> {
+        let schema = canonical_bytes(schema).map_err(|e| e.into_validation_error("schema"))?;
         let mut hasher = Sha256::new();
         hasher.update(name.as_bytes());
         hasher.update(receiver_type.as_bytes());
         hasher.update(version.as_bytes());
-        hasher.update(schema.to_string().as_bytes());
+        hasher.update(schema);
 
         let result = hasher.finalize();
-        hex::encode(result)
+        Ok(hex::encode(result))
     }
 
     /// Validates event receiver name
@@ -217,14 +425,14 @@ impl EventReceiver {
         if name.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "name".to_string(),
-                message: "Event receiver name cannot be empty".to_string(),
+                message: Message::new("validation.receiver_name_empty"),
             });
         }
 
         if name.len() > 255 {
             return Err(DomainError::ValidationError {
                 field: "name".to_string(),
-                message: "Event receiver name cannot exceed 255 characters".to_string(),
+                message: Message::new("validation.receiver_name_too_long").with("max", 255),
             });
         }
 
@@ -236,49 +444,51 @@ impl EventReceiver {
         if receiver_type.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "type".to_string(),
-                message: "Event receiver type cannot be empty".to_string(),
+                message: Message::new("validation.receiver_type_empty"),
             });
         }
 
         if receiver_type.len() > 100 {
             return Err(DomainError::ValidationError {
                 field: "type".to_string(),
-                message: "Event receiver type cannot exceed 100 characters".to_string(),
+                message: Message::new("validation.receiver_type_too_long").with("max", 100),
             });
         }
 
         Ok(())
     }
 
-    /// Validates version string
+    /// Validates a stored version string
+    ///
+    /// Rows written before versions were normalized may hold values that
+    /// are not semantic versions, so only emptiness and length are checked.
     fn validate_version(version: &str) -> Result<(), DomainError> {
         if version.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "version".to_string(),
-                message: "Version cannot be empty".to_string(),
+                message: Message::new("validation.version_empty"),
             });
         }
 
         if version.len() > 50 {
             return Err(DomainError::ValidationError {
                 field: "version".to_string(),
-                message: "Version cannot exceed 50 characters".to_string(),
+                message: Message::new("validation.version_too_long").with("max", 50),
             });
         }
 
         Ok(())
     }
 
-    /// Validates description
-    fn validate_description(description: &str) -> Result<(), DomainError> {
-        if description.len() > 1000 {
-            return Err(DomainError::ValidationError {
-                field: "description".to_string(),
-                message: "Description cannot exceed 1000 characters".to_string(),
-            });
+    /// Validates a sample rate
+    fn validate_sample_rate(sample_rate: Option<f64>) -> Result<(), DomainError> {
+        match sample_rate {
+            Some(rate) if !(0.0..=1.0).contains(&rate) => Err(DomainError::ValidationError {
+                field: "sample_rate".to_string(),
+                message: Message::new("validation.sample_rate_range"),
+            }),
+            _ => Ok(()),
         }
-
-        Ok(())
     }
 
     /// Validates JSON schema
@@ -288,7 +498,7 @@ impl EventReceiver {
         if !schema.is_object() {
             return Err(DomainError::ValidationError {
                 field: "schema".to_string(),
-                message: "Schema must be a valid JSON object".to_string(),
+                message: Message::new("validation.schema_not_object"),
             });
         }
 
@@ -298,19 +508,45 @@ impl EventReceiver {
     }
 
     /// Validates an event payload against this receiver's schema
+    ///
+    /// Payloads of attestation receivers must also be a supported
+    /// attestation document.
     pub fn validate_event_payload(&self, payload: &JsonValue) -> Result<(), DomainError> {
         // Basic validation - in a real implementation, you'd use a proper JSON schema validator
         if !payload.is_object() {
             return Err(DomainError::ValidationError {
                 field: "payload".to_string(),
-                message: "Event payload must be a JSON object".to_string(),
+                message: Message::new("validation.event_payload_not_object"),
             });
         }
 
-        // Additional schema validation could be implemented here
+        self.extract_attestation(payload)?;
         Ok(())
     }
 
+    /// Returns true if payloads must be attestation documents
+    pub fn is_attestation_receiver(&self) -> bool {
+        self.receiver_type == ATTESTATION_RECEIVER_TYPE
+    }
+
+    /// Extracts the attestation an event payload carries
+    ///
+    /// Returns `None` for receivers other than attestation receivers.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if an attestation receiver's payload is
+    /// not a supported attestation document.
+    pub fn extract_attestation(
+        &self,
+        payload: &JsonValue,
+    ) -> Result<Option<Attestation>, DomainError> {
+        if !self.is_attestation_receiver() {
+            return Ok(None);
+        }
+        Attestation::extract(payload).map(Some)
+    }
+
     // Getters
     pub fn id(&self) -> EventReceiverId {
         self.id
@@ -336,6 +572,13 @@ impl EventReceiver {
         &self.schema
     }
 
+    /// Returns true if this receiver defines a non-empty schema
+    ///
+    /// Receivers with an empty schema `{}` may inherit a group default.
+    pub fn has_own_schema(&self) -> bool {
+        self.schema.as_object().is_some_and(|obj| !obj.is_empty())
+    }
+
     pub fn fingerprint(&self) -> &str {
         &self.fingerprint
     }
@@ -344,6 +587,11 @@ impl EventReceiver {
         self.created_at
     }
 
+    /// Returns when this event receiver was last modified
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
     /// Returns the owner user ID of this event receiver
     pub fn owner_id(&self) -> UserId {
         self.owner_id
@@ -356,6 +604,49 @@ impl EventReceiver {
     pub fn resource_version(&self) -> i64 {
         self.resource_version
     }
+
+    /// Returns the configured sample rate, if sampling is enabled
+    pub fn sample_rate(&self) -> Option<f64> {
+        self.sample_rate
+    }
+
+    /// Returns the fraction of events stored, 1.0 when sampling is disabled
+    pub fn effective_sample_rate(&self) -> f64 {
+        self.sample_rate.unwrap_or(1.0)
+    }
+
+    /// Returns the event names this receiver accepts, if constrained
+    pub fn allowed_event_names(&self) -> Option<&AllowedEventNames> {
+        self.allowed_event_names.as_ref()
+    }
+
+    /// Returns the auto-disable policy, if one is set
+    pub fn auto_disable_policy(&self) -> Option<&AutoDisablePolicy> {
+        self.auto_disable_policy.as_ref()
+    }
+
+    /// Returns the statistics that disabled this receiver, if it is disabled
+    pub fn auto_disabled(&self) -> Option<&AutoDisableTrigger> {
+        self.auto_disabled.as_ref()
+    }
+
+    /// Returns when this receiver was last re-enabled, if ever
+    pub fn auto_disable_window_start(&self) -> Option<DateTime<Utc>> {
+        self.auto_disable_window_start
+    }
+
+    /// Returns the rewrites applied to payloads, if any
+    pub fn transform(&self) -> Option<&PayloadTransform> {
+        self.transform.as_ref()
+    }
+
+    /// Returns whether this receiver accepts events
+    pub fn state(&self) -> ReceiverState {
+        match self.auto_disabled {
+            Some(_) => ReceiverState::AutoDisabled,
+            None => ReceiverState::Active,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -462,6 +753,42 @@ mod tests {
         assert_eq!(receiver1.fingerprint(), receiver2.fingerprint());
     }
 
+    #[test]
+    fn test_version_is_normalized() {
+        let schema = create_valid_schema();
+        let owner_id = crate::domain::value_objects::UserId::new();
+        let build = |version: &str| {
+            EventReceiver::new(
+                "Test Receiver".to_string(),
+                "webhook".to_string(),
+                version.to_string(),
+                "A test event receiver".to_string(),
+                schema.clone(),
+                owner_id,
+            )
+        };
+
+        // Spellings of one version share a fingerprint
+        let canonical = build("1.0.0").unwrap();
+        let mut receiver = build(" v1.0").unwrap();
+        assert_eq!(receiver.version(), "1.0.0");
+        assert_eq!(receiver.fingerprint(), canonical.fingerprint());
+
+        receiver
+            .update(None, None, Some("1.0.0-rc1 ".to_string()), None, None)
+            .unwrap();
+        assert_eq!(receiver.version(), "1.0.0-rc1");
+
+        let err = build("latest").unwrap_err();
+        assert!(
+            matches!(err, DomainError::ValidationError { ref field, .. } if field == "version")
+        );
+        assert!(receiver
+            .update(None, None, Some("1.x".to_string()), None, None)
+            .is_err());
+        assert_eq!(receiver.version(), "1.0.0-rc1");
+    }
+
     #[test]
     fn test_update_receiver() {
         let schema = create_valid_schema();
@@ -598,6 +925,33 @@ mod tests {
         assert_eq!(receiver.resource_version(), 3);
     }
 
+    #[test]
+    fn test_updated_at_advances_on_any_update() {
+        let schema = create_valid_schema();
+        let owner_id = crate::domain::value_objects::UserId::new();
+        let mut receiver = EventReceiver::new(
+            "Test Receiver".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "A test event receiver".to_string(),
+            schema,
+            owner_id,
+        )
+        .unwrap();
+
+        assert_eq!(receiver.created_at(), receiver.updated_at());
+        let original_updated_at = receiver.updated_at();
+
+        std::thread::sleep(std::time::Duration::from_millis(2));
+
+        // Description does not change the fingerprint but is still a change
+        receiver
+            .update(None, None, None, Some("New description".to_string()), None)
+            .unwrap();
+        assert!(receiver.updated_at() > original_updated_at);
+        assert_eq!(receiver.created_at(), original_updated_at);
+    }
+
     #[test]
     fn test_owner_id_is_preserved() {
         let schema = create_valid_schema();
@@ -621,4 +975,152 @@ mod tests {
             .unwrap();
         assert_eq!(receiver_copy.owner_id(), owner_id);
     }
+
+    #[test]
+    fn test_set_sample_rate() {
+        let mut receiver = EventReceiver::new(
+            "Noisy Receiver".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "Sampled receiver".to_string(),
+            create_valid_schema(),
+            crate::domain::value_objects::UserId::new(),
+        )
+        .unwrap();
+        let fingerprint = receiver.fingerprint().to_string();
+        assert_eq!(receiver.sample_rate(), None);
+        assert_eq!(receiver.effective_sample_rate(), 1.0);
+
+        receiver.set_sample_rate(Some(0.25)).unwrap();
+        assert_eq!(receiver.sample_rate(), Some(0.25));
+        assert_eq!(receiver.resource_version(), 2);
+        assert_eq!(receiver.fingerprint(), fingerprint);
+
+        for invalid in [-0.1, 1.5, f64::NAN] {
+            assert!(receiver.set_sample_rate(Some(invalid)).is_err());
+        }
+        assert_eq!(receiver.sample_rate(), Some(0.25));
+
+        receiver.set_sample_rate(None).unwrap();
+        assert_eq!(receiver.effective_sample_rate(), 1.0);
+    }
+
+    #[test]
+    fn test_set_allowed_event_names() {
+        let mut receiver = EventReceiver::new(
+            "Build Receiver".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "Accepts build events".to_string(),
+            create_valid_schema(),
+            crate::domain::value_objects::UserId::new(),
+        )
+        .unwrap();
+        let fingerprint = receiver.fingerprint().to_string();
+        assert_eq!(receiver.allowed_event_names(), None);
+
+        let glob = AllowedEventNames::Glob("build.*".to_string());
+        receiver
+            .set_allowed_event_names(Some(glob.clone()))
+            .unwrap();
+        assert_eq!(receiver.allowed_event_names(), Some(&glob));
+        assert_eq!(receiver.resource_version(), 2);
+        assert_eq!(receiver.fingerprint(), fingerprint);
+
+        let err = receiver
+            .set_allowed_event_names(Some(AllowedEventNames::Regex("build.(".to_string())))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::ValidationError { ref field, .. } if field == "allowed_event_names"
+        ));
+        assert_eq!(receiver.allowed_event_names(), Some(&glob));
+        assert_eq!(receiver.resource_version(), 2);
+    }
+
+    #[test]
+    fn test_set_transform() {
+        let mut receiver = EventReceiver::new(
+            "Build Receiver".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "Accepts build events".to_string(),
+            create_valid_schema(),
+            crate::domain::value_objects::UserId::new(),
+        )
+        .unwrap();
+        let fingerprint = receiver.fingerprint().to_string();
+        assert_eq!(receiver.transform(), None);
+
+        let transform: PayloadTransform =
+            serde_json::from_value(json!([{"op": "rename", "from": "msg", "to": "message"}]))
+                .unwrap();
+        receiver.set_transform(Some(transform.clone())).unwrap();
+        assert_eq!(receiver.transform(), Some(&transform));
+        assert_eq!(receiver.resource_version(), 2);
+        assert_eq!(receiver.fingerprint(), fingerprint);
+
+        let invalid: PayloadTransform =
+            serde_json::from_value(json!([{"op": "delete", "path": ".msg"}])).unwrap();
+        let err = receiver.set_transform(Some(invalid)).unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::ValidationError { ref field, .. } if field == "transform[0]"
+        ));
+        assert_eq!(receiver.transform(), Some(&transform));
+
+        // An empty transform is stored as no transform
+        receiver
+            .set_transform(Some(PayloadTransform::new(Vec::new())))
+            .unwrap();
+        assert_eq!(receiver.transform(), None);
+        assert_eq!(receiver.resource_version(), 3);
+    }
+
+    #[test]
+    fn test_auto_disable_and_enable_reset_the_window() {
+        let mut receiver = EventReceiver::new(
+            "Build Receiver".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "Accepts build events".to_string(),
+            create_valid_schema(),
+            crate::domain::value_objects::UserId::new(),
+        )
+        .unwrap();
+        let now = Utc::now();
+        assert_eq!(receiver.auto_disable_window(now), None);
+
+        receiver
+            .set_auto_disable_policy(Some(AutoDisablePolicy {
+                failure_rate_threshold: 0.5,
+                observation_window_minutes: 60,
+                minimum_event_count: 10,
+            }))
+            .unwrap();
+        assert_eq!(
+            receiver.auto_disable_window(now),
+            Some((now - chrono::Duration::minutes(60), now))
+        );
+        assert_eq!(receiver.state(), ReceiverState::Active);
+
+        let trigger = AutoDisableTrigger::new(10, 10, now - chrono::Duration::minutes(60), now);
+        assert!(receiver.auto_disable(trigger.clone()));
+        assert!(!receiver.auto_disable(trigger.clone()));
+        assert_eq!(receiver.state(), ReceiverState::AutoDisabled);
+        assert_eq!(receiver.auto_disabled(), Some(&trigger));
+        assert_eq!(receiver.auto_disable_window(now), None);
+
+        // Re-enabling restarts the window instead of re-reading old failures
+        let enabled_at = now + chrono::Duration::minutes(5);
+        assert!(receiver.enable(enabled_at));
+        assert!(!receiver.enable(enabled_at));
+        assert_eq!(receiver.state(), ReceiverState::Active);
+        let later = enabled_at + chrono::Duration::minutes(10);
+        assert_eq!(
+            receiver.auto_disable_window(later),
+            Some((enabled_at, later))
+        );
+        assert_eq!(receiver.auto_disable_window(enabled_at), None);
+    }
 }
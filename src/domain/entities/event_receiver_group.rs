@@ -3,12 +3,22 @@
 
 // src/domain/entities/event_receiver_group.rs
 
-use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId, UserId};
+use crate::domain::value_objects::description::{
+    check_description_length, normalize_description, MAX_DESCRIPTION_LENGTH,
+};
+use crate::domain::value_objects::{
+    EventReceiverGroupId, EventReceiverId, UserId, Version, VersionStrictness,
+};
 use crate::error::DomainError;
+use crate::i18n::Message;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use std::collections::HashSet;
 
+/// Longest topic name Kafka accepts
+const MAX_TOPIC_NAME_LENGTH: usize = 249;
+
 /// Parameters for creating an event receiver group from existing data
 #[derive(Debug, Clone)]
 pub struct EventReceiverGroupData {
@@ -19,6 +29,9 @@ pub struct EventReceiverGroupData {
     pub description: String,
     pub enabled: bool,
     pub event_receiver_ids: Vec<EventReceiverId>,
+    pub optional_receiver_ids: Vec<EventReceiverId>,
+    pub default_schema: Option<JsonValue>,
+    pub dedicated_topic: Option<String>,
     pub owner_id: UserId,
     pub resource_version: i64,
     pub created_at: DateTime<Utc>,
@@ -35,6 +48,12 @@ pub struct EventReceiverGroup {
     description: String,
     enabled: bool,
     event_receiver_ids: Vec<EventReceiverId>,
+    #[serde(default)]
+    optional_receiver_ids: Vec<EventReceiverId>,
+    #[serde(default)]
+    default_schema: Option<JsonValue>,
+    #[serde(default)]
+    dedicated_topic: Option<String>,
     owner_id: UserId,
     resource_version: i64,
     created_at: DateTime<Utc>,
@@ -48,7 +67,8 @@ impl EventReceiverGroup {
     ///
     /// * `name` - The name of the event receiver group
     /// * `group_type` - The type of the group
-    /// * `version` - The version of the group
+    /// * `version` - The semantic version of the group, stored in canonical
+    ///   form (`v1.0` becomes `1.0.0`)
     /// * `description` - A description of the group
     /// * `enabled` - Whether the group is enabled
     /// * `event_receiver_ids` - List of event receiver IDs in this group
@@ -88,8 +108,8 @@ impl EventReceiverGroup {
     ) -> Result<Self, DomainError> {
         Self::validate_name(&name)?;
         Self::validate_type(&group_type)?;
-        Self::validate_version(&version)?;
-        Self::validate_description(&description)?;
+        let version = Version::normalize(&version, VersionStrictness::Lenient)?;
+        let description = normalize_description(&description, MAX_DESCRIPTION_LENGTH)?;
         Self::validate_event_receiver_ids(&event_receiver_ids)?;
 
         let now = Utc::now();
@@ -102,6 +122,9 @@ impl EventReceiverGroup {
             description,
             enabled,
             event_receiver_ids,
+            optional_receiver_ids: Vec::new(),
+            default_schema: None,
+            dedicated_topic: None,
             owner_id,
             resource_version: 1,
             created_at: now,
@@ -114,8 +137,14 @@ impl EventReceiverGroup {
         Self::validate_name(&data.name)?;
         Self::validate_type(&data.group_type)?;
         Self::validate_version(&data.version)?;
-        Self::validate_description(&data.description)?;
+        check_description_length(&data.description, MAX_DESCRIPTION_LENGTH)?;
         Self::validate_event_receiver_ids(&data.event_receiver_ids)?;
+        let default_schema = Self::normalize_default_schema(data.default_schema)?;
+        let dedicated_topic = Self::normalize_dedicated_topic(data.dedicated_topic)?;
+        Self::validate_optional_receiver_ids(
+            &data.event_receiver_ids,
+            &data.optional_receiver_ids,
+        )?;
 
         Ok(Self {
             id: data.id,
@@ -125,6 +154,9 @@ impl EventReceiverGroup {
             description: data.description,
             enabled: data.enabled,
             event_receiver_ids: data.event_receiver_ids,
+            optional_receiver_ids: data.optional_receiver_ids,
+            default_schema,
+            dedicated_topic,
             owner_id: data.owner_id,
             resource_version: data.resource_version,
             created_at: data.created_at,
@@ -143,7 +175,8 @@ impl EventReceiverGroup {
     /// * `version` - Optional new version
     /// * `description` - Optional new description
     /// * `enabled` - Optional new enabled state
-    /// * `event_receiver_ids` - Optional new list of receiver IDs
+    /// * `event_receiver_ids` - Optional new list of receiver IDs; receivers
+    ///   that leave the group stop being optional
     ///
     /// # Returns
     ///
@@ -168,13 +201,11 @@ impl EventReceiverGroup {
         }
 
         if let Some(new_version) = version {
-            Self::validate_version(&new_version)?;
-            self.version = new_version;
+            self.version = Version::normalize(&new_version, VersionStrictness::Lenient)?;
         }
 
         if let Some(new_description) = description {
-            Self::validate_description(&new_description)?;
-            self.description = new_description;
+            self.description = normalize_description(&new_description, MAX_DESCRIPTION_LENGTH)?;
         }
 
         if let Some(new_enabled) = enabled {
@@ -183,6 +214,8 @@ impl EventReceiverGroup {
 
         if let Some(new_receiver_ids) = event_receiver_ids {
             Self::validate_event_receiver_ids(&new_receiver_ids)?;
+            self.optional_receiver_ids
+                .retain(|id| new_receiver_ids.contains(id));
             self.event_receiver_ids = new_receiver_ids;
         }
 
@@ -192,6 +225,86 @@ impl EventReceiverGroup {
         Ok(())
     }
 
+    /// Sets the default schema at construction time
+    ///
+    /// Member receivers without their own schema inherit this schema. An
+    /// empty object is treated as no default.
+    pub fn with_default_schema(
+        mut self,
+        default_schema: Option<JsonValue>,
+    ) -> Result<Self, DomainError> {
+        self.default_schema = Self::normalize_default_schema(default_schema)?;
+        Ok(self)
+    }
+
+    /// Replaces the default schema inherited by member receivers
+    pub fn set_default_schema(
+        &mut self,
+        default_schema: Option<JsonValue>,
+    ) -> Result<(), DomainError> {
+        self.default_schema = Self::normalize_default_schema(default_schema)?;
+        self.updated_at = Utc::now();
+        self.resource_version += 1;
+
+        Ok(())
+    }
+
+    /// Marks member receivers as optional at construction time
+    ///
+    /// Optional receivers are reported on but do not block the group's
+    /// release completeness. Every other member is required.
+    pub fn with_optional_receivers(
+        mut self,
+        optional_receiver_ids: Vec<EventReceiverId>,
+    ) -> Result<Self, DomainError> {
+        Self::validate_optional_receiver_ids(&self.event_receiver_ids, &optional_receiver_ids)?;
+        self.optional_receiver_ids = optional_receiver_ids;
+        Ok(self)
+    }
+
+    /// Replaces the member receivers that do not block release completeness
+    pub fn set_optional_receivers(
+        &mut self,
+        optional_receiver_ids: Vec<EventReceiverId>,
+    ) -> Result<(), DomainError> {
+        Self::validate_optional_receiver_ids(&self.event_receiver_ids, &optional_receiver_ids)?;
+        self.optional_receiver_ids = optional_receiver_ids;
+        self.updated_at = Utc::now();
+        self.resource_version += 1;
+
+        Ok(())
+    }
+
+    /// Sets the dedicated topic at construction time
+    ///
+    /// While the group is enabled, events of its member receivers are also
+    /// published to this topic. An empty name is treated as no topic.
+    pub fn with_dedicated_topic(
+        mut self,
+        dedicated_topic: Option<String>,
+    ) -> Result<Self, DomainError> {
+        self.dedicated_topic = Self::normalize_dedicated_topic(dedicated_topic)?;
+        Ok(self)
+    }
+
+    /// Replaces the topic events of member receivers are fanned out to
+    pub fn set_dedicated_topic(
+        &mut self,
+        dedicated_topic: Option<String>,
+    ) -> Result<(), DomainError> {
+        self.dedicated_topic = Self::normalize_dedicated_topic(dedicated_topic)?;
+        self.updated_at = Utc::now();
+        self.resource_version += 1;
+
+        Ok(())
+    }
+
+    /// Returns the topic events of member receivers are fanned out to, or
+    /// `None` while the group is disabled or has no dedicated topic
+    pub fn routed_topic(&self) -> Option<&str> {
+        self.dedicated_topic.as_deref().filter(|_| self.enabled)
+    }
+
     /// Enables the event receiver group
     pub fn enable(&mut self) {
         self.enabled = true;
@@ -210,7 +323,7 @@ impl EventReceiverGroup {
     pub fn add_event_receiver(&mut self, receiver_id: EventReceiverId) -> Result<(), DomainError> {
         if self.event_receiver_ids.contains(&receiver_id) {
             return Err(DomainError::BusinessRuleViolation {
-                rule: "Event receiver already exists in the group".to_string(),
+                rule: Message::new("rule.group_member_exists"),
             });
         }
 
@@ -228,10 +341,11 @@ impl EventReceiverGroup {
     ) -> Result<(), DomainError> {
         let initial_len = self.event_receiver_ids.len();
         self.event_receiver_ids.retain(|&id| id != receiver_id);
+        self.optional_receiver_ids.retain(|&id| id != receiver_id);
 
         if self.event_receiver_ids.len() == initial_len {
             return Err(DomainError::BusinessRuleViolation {
-                rule: "Event receiver not found in the group".to_string(),
+                rule: Message::new("rule.group_member_missing"),
             });
         }
 
@@ -251,19 +365,24 @@ impl EventReceiverGroup {
         self.event_receiver_ids.len()
     }
 
+    /// Checks if a member receiver blocks the group's release completeness
+    pub fn is_required(&self, receiver_id: EventReceiverId) -> bool {
+        self.contains_receiver(receiver_id) && !self.optional_receiver_ids.contains(&receiver_id)
+    }
+
     /// Validates group name
     fn validate_name(name: &str) -> Result<(), DomainError> {
         if name.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "name".to_string(),
-                message: "Event receiver group name cannot be empty".to_string(),
+                message: Message::new("validation.group_name_empty"),
             });
         }
 
         if name.len() > 255 {
             return Err(DomainError::ValidationError {
                 field: "name".to_string(),
-                message: "Event receiver group name cannot exceed 255 characters".to_string(),
+                message: Message::new("validation.group_name_too_long").with("max", 255),
             });
         }
 
@@ -275,45 +394,36 @@ impl EventReceiverGroup {
         if group_type.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "type".to_string(),
-                message: "Event receiver group type cannot be empty".to_string(),
+                message: Message::new("validation.group_type_empty"),
             });
         }
 
         if group_type.len() > 100 {
             return Err(DomainError::ValidationError {
                 field: "type".to_string(),
-                message: "Event receiver group type cannot exceed 100 characters".to_string(),
+                message: Message::new("validation.group_type_too_long").with("max", 100),
             });
         }
 
         Ok(())
     }
 
-    /// Validates version string
+    /// Validates a stored version string
+    ///
+    /// Rows written before versions were normalized may hold values that
+    /// are not semantic versions, so only emptiness and length are checked.
     fn validate_version(version: &str) -> Result<(), DomainError> {
         if version.trim().is_empty() {
             return Err(DomainError::ValidationError {
                 field: "version".to_string(),
-                message: "Version cannot be empty".to_string(),
+                message: Message::new("validation.version_empty"),
             });
         }
 
         if version.len() > 50 {
             return Err(DomainError::ValidationError {
                 field: "version".to_string(),
-                message: "Version cannot exceed 50 characters".to_string(),
-            });
-        }
-
-        Ok(())
-    }
-
-    /// Validates description
-    fn validate_description(description: &str) -> Result<(), DomainError> {
-        if description.len() > 1000 {
-            return Err(DomainError::ValidationError {
-                field: "description".to_string(),
-                message: "Description cannot exceed 1000 characters".to_string(),
+                message: Message::new("validation.version_too_long").with("max", 50),
             });
         }
 
@@ -325,7 +435,7 @@ impl EventReceiverGroup {
         if receiver_ids.len() > 100 {
             return Err(DomainError::ValidationError {
                 field: "event_receiver_ids".to_string(),
-                message: "Cannot have more than 100 event receivers in a group".to_string(),
+                message: Message::new("validation.group_too_many_receivers").with("max", 100),
             });
         }
 
@@ -334,13 +444,85 @@ impl EventReceiverGroup {
         if unique_ids.len() != receiver_ids.len() {
             return Err(DomainError::ValidationError {
                 field: "event_receiver_ids".to_string(),
-                message: "Duplicate event receiver IDs are not allowed".to_string(),
+                message: Message::new("validation.group_duplicate_receivers"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validates that optional receivers are distinct members of the group
+    fn validate_optional_receiver_ids(
+        receiver_ids: &[EventReceiverId],
+        optional_receiver_ids: &[EventReceiverId],
+    ) -> Result<(), DomainError> {
+        let unique_ids: HashSet<_> = optional_receiver_ids.iter().collect();
+        if unique_ids.len() != optional_receiver_ids.len() {
+            return Err(DomainError::ValidationError {
+                field: "optional_receiver_ids".to_string(),
+                message: Message::new("validation.group_duplicate_receivers"),
+            });
+        }
+
+        if let Some(id) = optional_receiver_ids
+            .iter()
+            .find(|id| !receiver_ids.contains(id))
+        {
+            return Err(DomainError::ValidationError {
+                field: "optional_receiver_ids".to_string(),
+                message: Message::new("validation.optional_receiver_not_member")
+                    .with("receiver_id", id),
             });
         }
 
         Ok(())
     }
 
+    /// Validates a default schema, mapping an empty object to `None`
+    fn normalize_default_schema(
+        default_schema: Option<JsonValue>,
+    ) -> Result<Option<JsonValue>, DomainError> {
+        match default_schema {
+            None => Ok(None),
+            Some(schema) => match schema.as_object() {
+                Some(obj) if obj.is_empty() => Ok(None),
+                Some(_) => Ok(Some(schema)),
+                None => Err(DomainError::ValidationError {
+                    field: "default_schema".to_string(),
+                    message: Message::new("validation.default_schema_not_object"),
+                }),
+            },
+        }
+    }
+
+    /// Validates a dedicated topic against Kafka's topic naming rules,
+    /// mapping an empty name to `None`
+    fn normalize_dedicated_topic(
+        dedicated_topic: Option<String>,
+    ) -> Result<Option<String>, DomainError> {
+        let Some(topic) = dedicated_topic.filter(|topic| !topic.is_empty()) else {
+            return Ok(None);
+        };
+
+        let message = if topic.len() > MAX_TOPIC_NAME_LENGTH {
+            Message::new("validation.dedicated_topic_too_long").with("max", MAX_TOPIC_NAME_LENGTH)
+        } else if topic == "." || topic == ".." {
+            Message::new("validation.dedicated_topic_reserved")
+        } else if !topic
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        {
+            Message::new("validation.dedicated_topic_characters")
+        } else {
+            return Ok(Some(topic));
+        };
+
+        Err(DomainError::ValidationError {
+            field: "dedicated_topic".to_string(),
+            message,
+        })
+    }
+
     // Getters
     pub fn id(&self) -> EventReceiverGroupId {
         self.id
@@ -370,6 +552,21 @@ impl EventReceiverGroup {
         &self.event_receiver_ids
     }
 
+    /// Returns the member receivers that do not block release completeness
+    pub fn optional_receiver_ids(&self) -> &[EventReceiverId] {
+        &self.optional_receiver_ids
+    }
+
+    /// Returns the schema inherited by member receivers without their own
+    pub fn default_schema(&self) -> Option<&JsonValue> {
+        self.default_schema.as_ref()
+    }
+
+    /// Returns the topic events of member receivers are fanned out to
+    pub fn dedicated_topic(&self) -> Option<&str> {
+        self.dedicated_topic.as_deref()
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -527,6 +724,34 @@ mod tests {
         assert!(group.remove_event_receiver(new_receiver_id).is_err());
     }
 
+    #[test]
+    fn test_version_is_normalized() {
+        let build = |version: &str| {
+            EventReceiverGroup::new(
+                "Test Group".to_string(),
+                "webhook_group".to_string(),
+                version.to_string(),
+                "A test event receiver group".to_string(),
+                true,
+                vec![EventReceiverId::new()],
+                UserId::new(),
+            )
+        };
+
+        let mut group = build("V2").unwrap();
+        assert_eq!(group.version(), "2.0.0");
+
+        group
+            .update(None, None, Some("2.1 ".to_string()), None, None, None)
+            .unwrap();
+        assert_eq!(group.version(), "2.1.0");
+
+        assert!(build("two").is_err());
+        assert!(group
+            .update(None, None, Some("2.1.0.1".to_string()), None, None, None)
+            .is_err());
+    }
+
     #[test]
     fn test_update_group() {
         let receiver_ids = vec![EventReceiverId::new()];
@@ -633,6 +858,36 @@ mod tests {
         assert_eq!(group.resource_version(), 3);
     }
 
+    #[test]
+    fn test_default_schema() {
+        let mut group = EventReceiverGroup::new(
+            "Test Group".to_string(),
+            "webhook_group".to_string(),
+            "1.0.0".to_string(),
+            "A test event receiver group".to_string(),
+            true,
+            vec![],
+            UserId::new(),
+        )
+        .unwrap()
+        .with_default_schema(Some(serde_json::json!({"required": ["id"]})))
+        .unwrap();
+
+        assert_eq!(group.resource_version(), 1);
+        assert!(group.default_schema().is_some());
+
+        // Empty object clears the default
+        group
+            .set_default_schema(Some(serde_json::json!({})))
+            .unwrap();
+        assert!(group.default_schema().is_none());
+        assert_eq!(group.resource_version(), 2);
+
+        assert!(group
+            .set_default_schema(Some(serde_json::json!("not a schema")))
+            .is_err());
+    }
+
     #[test]
     fn test_owner_id_is_preserved() {
         let receiver_ids = vec![EventReceiverId::new()];
@@ -5,6 +5,7 @@
 
 use crate::domain::value_objects::{EventReceiverGroupId, UserId};
 use crate::error::DomainError;
+use crate::i18n::Message;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -104,9 +105,7 @@ impl EventReceiverGroupMembership {
         // The group owner is implicitly a member and doesn't need explicit membership
         if user_id == added_by {
             return Err(DomainError::BusinessRuleViolation {
-                rule:
-                    "Users cannot add themselves to a group. Only the group owner can add members."
-                        .to_string(),
+                rule: Message::new("rule.self_membership"),
             });
         }
 
@@ -180,7 +179,8 @@ mod tests {
         assert!(result.is_err());
         match result.unwrap_err() {
             DomainError::BusinessRuleViolation { rule } => {
-                assert!(rule.contains("cannot add themselves"));
+                assert_eq!(rule.key(), "rule.self_membership");
+                assert!(rule.to_string().contains("cannot add themselves"));
             }
             _ => panic!("Expected BusinessRuleViolation"),
         }
@@ -0,0 +1,167 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/event_sampling.rs
+
+use crate::domain::entities::event::CreateEventParams;
+use crate::domain::entities::event_receiver::EventReceiver;
+use sha2::{Digest, Sha256};
+
+/// Payload `severity` values at or above Error; compared case-insensitively
+const ERROR_SEVERITIES: [&str; 5] = ["error", "critical", "alert", "emergency", "fatal"];
+
+/// Rules that store an event regardless of the sample rate
+pub const ALWAYS_KEEP_RULES: [&str; 2] = ["success=false", "severity>=error"];
+
+/// Decides which events of a sampled receiver are stored
+///
+/// The decision hashes the event content, so a retried submission of the
+/// same event always gets the same decision. Failed events and events with a
+/// payload `severity` of Error or above are always stored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplingPolicy {
+    sample_rate: f64,
+}
+
+impl SamplingPolicy {
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            sample_rate: sample_rate.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the policy configured on `receiver`
+    pub fn for_receiver(receiver: &EventReceiver) -> Self {
+        Self::new(receiver.effective_sample_rate())
+    }
+
+    pub fn sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+
+    /// Returns true if some events may be dropped
+    pub fn is_active(&self) -> bool {
+        self.sample_rate < 1.0
+    }
+
+    /// Returns true if the event should be stored
+    pub fn keeps(&self, params: &CreateEventParams) -> bool {
+        !self.is_active() || is_always_kept(params) || sample_point(params) < self.sample_rate
+    }
+}
+
+/// Returns true if an always-keep rule matches the event
+pub fn is_always_kept(params: &CreateEventParams) -> bool {
+    if !params.success {
+        return true;
+    }
+
+    params
+        .payload
+        .get("severity")
+        .and_then(|severity| severity.as_str())
+        .is_some_and(|severity| {
+            ERROR_SEVERITIES
+                .iter()
+                .any(|level| severity.eq_ignore_ascii_case(level))
+        })
+}
+
+/// Maps the event content to a stable point in `[0, 1)`
+fn sample_point(params: &CreateEventParams) -> f64 {
+    let mut hasher = Sha256::new();
+    for field in [
+        params.receiver_id.to_string().as_str(),
+        &params.name,
+        &params.version,
+        &params.release,
+        &params.platform_id,
+        &params.package,
+        &params.description,
+        &params.payload.to_string(),
+    ] {
+        hasher.update(field.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.update([params.success as u8]);
+
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    // Keep 53 bits so the quotient is exact in an f64
+    (u64::from_be_bytes(bytes) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{EventReceiverId, UserId};
+    use serde_json::json;
+
+    fn params(receiver_id: EventReceiverId, n: usize) -> CreateEventParams {
+        CreateEventParams {
+            name: "heartbeat".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.01".to_string(),
+            platform_id: "linux".to_string(),
+            package: "agent".to_string(),
+            description: "Periodic heartbeat".to_string(),
+            payload: json!({"sequence": n}),
+            success: true,
+            receiver_id,
+            owner_id: UserId::new(),
+        }
+    }
+
+    #[test]
+    fn test_rate_is_roughly_honored() {
+        let receiver_id = EventReceiverId::new();
+        let policy = SamplingPolicy::new(0.1);
+
+        let kept = (0..1000)
+            .filter(|n| policy.keeps(&params(receiver_id, *n)))
+            .count();
+
+        assert!((50..=150).contains(&kept), "kept {} of 1000", kept);
+    }
+
+    #[test]
+    fn test_decision_is_deterministic() {
+        let receiver_id = EventReceiverId::new();
+        let policy = SamplingPolicy::new(0.5);
+
+        for n in 0..100 {
+            let first = policy.keeps(&params(receiver_id, n));
+            // Retries come from another principal but carry the same content
+            assert_eq!(policy.keeps(&params(receiver_id, n)), first);
+        }
+    }
+
+    #[test]
+    fn test_failures_and_errors_are_always_kept() {
+        let receiver_id = EventReceiverId::new();
+        let policy = SamplingPolicy::new(0.0);
+
+        let mut failed = params(receiver_id, 1);
+        failed.success = false;
+        assert!(policy.keeps(&failed));
+
+        for severity in ["ERROR", "critical", "Fatal"] {
+            let mut severe = params(receiver_id, 2);
+            severe.payload = json!({"severity": severity});
+            assert!(policy.keeps(&severe), "{} was dropped", severity);
+        }
+
+        let mut warning = params(receiver_id, 3);
+        warning.payload = json!({"severity": "warning"});
+        assert!(!policy.keeps(&warning));
+    }
+
+    #[test]
+    fn test_full_rate_is_inactive() {
+        let policy = SamplingPolicy::new(1.0);
+        assert!(!policy.is_active());
+        assert!(policy.keeps(&params(EventReceiverId::new(), 0)));
+        assert_eq!(SamplingPolicy::new(2.0).sample_rate(), 1.0);
+    }
+}
@@ -0,0 +1,319 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/ingestion_meta.rs
+
+use crate::domain::entities::pii_detection::PiiFinding;
+use crate::domain::value_objects::EventId;
+use crate::error::DomainError;
+use crate::i18n::Message;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Maximum stored length of a user agent string
+pub const MAX_USER_AGENT_LENGTH: usize = 512;
+
+/// Kind of principal that submitted an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrincipalType {
+    /// Interactive or token-authenticated user
+    User,
+    /// API key
+    ApiKey,
+    /// Kafka consumer ingesting from a topic
+    KafkaConsumer,
+}
+
+impl PrincipalType {
+    /// Returns the storage representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrincipalType::User => "user",
+            PrincipalType::ApiKey => "api_key",
+            PrincipalType::KafkaConsumer => "kafka_consumer",
+        }
+    }
+}
+
+impl fmt::Display for PrincipalType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PrincipalType {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" => Ok(PrincipalType::User),
+            "api_key" => Ok(PrincipalType::ApiKey),
+            "kafka_consumer" => Ok(PrincipalType::KafkaConsumer),
+            _ => Err(DomainError::ValidationError {
+                field: "principal_type".to_string(),
+                message: Message::new("validation.principal_type_unknown").with("value", s),
+            }),
+        }
+    }
+}
+
+/// Channel through which an event was ingested
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionSource {
+    Rest,
+    Graphql,
+    Batch,
+    #[serde(rename = "cloudevents")]
+    CloudEvents,
+    Webhook,
+    Kafka,
+}
+
+impl IngestionSource {
+    /// All ingestion sources
+    pub const ALL: [IngestionSource; 6] = [
+        IngestionSource::Rest,
+        IngestionSource::Graphql,
+        IngestionSource::Batch,
+        IngestionSource::CloudEvents,
+        IngestionSource::Webhook,
+        IngestionSource::Kafka,
+    ];
+
+    /// Returns the storage representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IngestionSource::Rest => "rest",
+            IngestionSource::Graphql => "graphql",
+            IngestionSource::Batch => "batch",
+            IngestionSource::CloudEvents => "cloudevents",
+            IngestionSource::Webhook => "webhook",
+            IngestionSource::Kafka => "kafka",
+        }
+    }
+}
+
+impl fmt::Display for IngestionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IngestionSource {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IngestionSource::ALL
+            .into_iter()
+            .find(|source| source.as_str() == s)
+            .ok_or_else(|| DomainError::ValidationError {
+                field: "source".to_string(),
+                message: Message::new("validation.ingestion_source_unknown").with("value", s),
+            })
+    }
+}
+
+/// Who submitted an event, through which channel, and from where
+///
+/// Built by the transport layer and passed to the event handler at create
+/// time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IngestionContext {
+    pub principal_type: PrincipalType,
+    pub principal_id: String,
+    pub source: IngestionSource,
+    pub client_ip: Option<String>,
+    pub user_agent: Option<String>,
+    /// Administrator who submitted the event while impersonating the
+    /// principal
+    #[serde(default)]
+    pub actor_id: Option<String>,
+}
+
+impl IngestionContext {
+    /// Creates a context without network details
+    pub fn new(
+        principal_type: PrincipalType,
+        principal_id: impl Into<String>,
+        source: IngestionSource,
+    ) -> Self {
+        Self {
+            principal_type,
+            principal_id: principal_id.into(),
+            source,
+            client_ip: None,
+            user_agent: None,
+            actor_id: None,
+        }
+    }
+
+    /// Creates a context for an event consumed from Kafka
+    pub fn kafka_consumer(consumer_id: impl Into<String>) -> Self {
+        Self::new(
+            PrincipalType::KafkaConsumer,
+            consumer_id,
+            IngestionSource::Kafka,
+        )
+    }
+
+    /// Sets the client IP address
+    pub fn with_client_ip(mut self, client_ip: Option<String>) -> Self {
+        self.client_ip = client_ip;
+        self
+    }
+
+    /// Sets the user agent, truncated to [`MAX_USER_AGENT_LENGTH`] characters
+    pub fn with_user_agent(mut self, user_agent: Option<String>) -> Self {
+        self.user_agent =
+            user_agent.map(|ua| ua.chars().take(MAX_USER_AGENT_LENGTH).collect::<String>());
+        self
+    }
+
+    /// Sets the administrator impersonating the principal
+    pub fn with_actor(mut self, actor_id: Option<String>) -> Self {
+        self.actor_id = actor_id;
+        self
+    }
+
+    /// Returns the principal as `type:id`, e.g. `api_key:01J...`
+    pub fn principal(&self) -> String {
+        format!("{}:{}", self.principal_type, self.principal_id)
+    }
+}
+
+/// Ingestion metadata recorded for a single event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestionMeta {
+    event_id: EventId,
+    context: IngestionContext,
+    recorded_at: DateTime<Utc>,
+    /// Payload values that looked like personal data; never the values
+    #[serde(default)]
+    pii_findings: Vec<PiiFinding>,
+}
+
+impl IngestionMeta {
+    /// Creates ingestion metadata for a newly created event
+    pub fn new(event_id: EventId, context: IngestionContext) -> Self {
+        Self {
+            event_id,
+            context,
+            recorded_at: Utc::now(),
+            pii_findings: Vec::new(),
+        }
+    }
+
+    /// Reconstructs ingestion metadata from storage
+    pub fn from_existing(
+        event_id: EventId,
+        context: IngestionContext,
+        recorded_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            event_id,
+            context,
+            recorded_at,
+            pii_findings: Vec::new(),
+        }
+    }
+
+    /// Sets what PII detection found in the event's payload
+    pub fn with_pii_findings(mut self, pii_findings: Vec<PiiFinding>) -> Self {
+        self.pii_findings = pii_findings;
+        self
+    }
+
+    pub fn event_id(&self) -> EventId {
+        self.event_id
+    }
+
+    pub fn context(&self) -> &IngestionContext {
+        &self.context
+    }
+
+    pub fn principal_type(&self) -> PrincipalType {
+        self.context.principal_type
+    }
+
+    pub fn principal_id(&self) -> &str {
+        &self.context.principal_id
+    }
+
+    pub fn source(&self) -> IngestionSource {
+        self.context.source
+    }
+
+    pub fn client_ip(&self) -> Option<&str> {
+        self.context.client_ip.as_deref()
+    }
+
+    pub fn user_agent(&self) -> Option<&str> {
+        self.context.user_agent.as_deref()
+    }
+
+    pub fn actor_id(&self) -> Option<&str> {
+        self.context.actor_id.as_deref()
+    }
+
+    pub fn recorded_at(&self) -> DateTime<Utc> {
+        self.recorded_at
+    }
+
+    pub fn pii_findings(&self) -> &[PiiFinding] {
+        &self.pii_findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_round_trip() {
+        for source in IngestionSource::ALL {
+            assert_eq!(source.as_str().parse::<IngestionSource>().unwrap(), source);
+            assert_eq!(
+                serde_json::to_value(source).unwrap(),
+                serde_json::json!(source.as_str())
+            );
+        }
+        assert!("ftp".parse::<IngestionSource>().is_err());
+    }
+
+    #[test]
+    fn test_principal_type_round_trip() {
+        for principal in [
+            PrincipalType::User,
+            PrincipalType::ApiKey,
+            PrincipalType::KafkaConsumer,
+        ] {
+            assert_eq!(
+                principal.as_str().parse::<PrincipalType>().unwrap(),
+                principal
+            );
+        }
+        assert!("robot".parse::<PrincipalType>().is_err());
+    }
+
+    #[test]
+    fn test_context_builders() {
+        let context = IngestionContext::new(PrincipalType::ApiKey, "key-1", IngestionSource::Rest)
+            .with_client_ip(Some("203.0.113.1".to_string()))
+            .with_user_agent(Some("x".repeat(MAX_USER_AGENT_LENGTH + 10)));
+
+        assert_eq!(context.principal_id, "key-1");
+        assert_eq!(context.client_ip.as_deref(), Some("203.0.113.1"));
+        assert_eq!(
+            context.user_agent.as_ref().map(|ua| ua.len()),
+            Some(MAX_USER_AGENT_LENGTH)
+        );
+
+        let kafka = IngestionContext::kafka_consumer("xzepr-consumer");
+        assert_eq!(kafka.principal_type, PrincipalType::KafkaConsumer);
+        assert_eq!(kafka.source, IngestionSource::Kafka);
+    }
+}
@@ -3,8 +3,32 @@
 
 // Generated mod file
 
+pub mod attestation;
+pub mod audit_chain;
+pub mod delivery_failure;
 pub mod event;
+pub mod event_attachment;
+pub mod event_diff;
+pub mod event_name_constraint;
+pub mod event_publication;
 pub mod event_receiver;
 pub mod event_receiver_group;
 pub mod event_receiver_group_membership;
+pub mod event_sampling;
+pub mod ingestion_meta;
+pub mod payload_summary;
+pub mod payload_transform;
+pub mod pii_detection;
+pub mod receiver_activity;
+pub mod receiver_auto_disable;
+pub mod receiver_clone;
+pub mod receiver_deletion;
+pub mod receiver_heartbeat;
+pub mod receiver_provisioning;
+pub mod release_completeness;
+pub mod schema_inheritance;
+pub mod search;
 pub mod user;
+pub mod user_import;
+pub mod user_invitation;
+pub mod user_preferences;
@@ -0,0 +1,156 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/payload_summary.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+use crate::domain::canonical_json;
+use crate::domain::value_objects::EventId;
+
+/// Payload field the event rollups count by
+const SEVERITY_PATH: &str = "severity";
+
+/// What an event keeps of a payload moved to the blob store
+///
+/// Large payloads are stored whole as canonical JSON under `blob_key`; the
+/// event row and the stream carry only this summary. `hash` is the same
+/// content hash an inline or interned copy of the payload would have.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayloadSummary {
+    /// Blob store key of the canonical JSON
+    pub blob_key: String,
+    /// Size of the canonical JSON in bytes
+    pub size_bytes: u64,
+    /// Hex-encoded SHA-256 of the canonical JSON
+    pub hash: String,
+    /// Keys of the payload object, sorted
+    pub top_level_keys: Vec<String>,
+    /// Values found at the configured dotted paths, keyed by path; a
+    /// top-level `severity` is always kept for the event rollups
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, JsonValue>,
+}
+
+impl PayloadSummary {
+    /// Summarizes `payload`, whose canonical JSON is `canonical`
+    ///
+    /// Paths that are missing from the payload are left out of `fields`.
+    pub fn new(blob_key: String, payload: &JsonValue, canonical: &[u8], paths: &[String]) -> Self {
+        let mut top_level_keys: Vec<String> = payload
+            .as_object()
+            .map(|object| object.keys().cloned().collect())
+            .unwrap_or_default();
+        top_level_keys.sort();
+
+        let fields = paths
+            .iter()
+            .map(String::as_str)
+            .chain([SEVERITY_PATH])
+            .filter_map(|path| lookup(payload, path).map(|value| (path.to_string(), value.clone())))
+            .collect();
+
+        Self {
+            blob_key,
+            size_bytes: canonical.len() as u64,
+            hash: canonical_json::hash_canonical(canonical),
+            top_level_keys,
+            fields,
+        }
+    }
+
+    /// Returns the payload fields the event rollups read
+    ///
+    /// Offloaded events are counted by the same severity their full
+    /// payload would give them.
+    pub fn rollup_fields(&self) -> JsonValue {
+        let mut object = serde_json::Map::new();
+        if let Some(severity) = self.fields.get(SEVERITY_PATH) {
+            object.insert(SEVERITY_PATH.to_string(), severity.clone());
+        }
+        JsonValue::Object(object)
+    }
+
+    /// Returns the blob store key of an event's payload
+    ///
+    /// Keys sit beside the event's attachments, which are stored under
+    /// `{event_id}/{attachment_id}`.
+    pub fn blob_key_for(event_id: EventId) -> String {
+        format!("{}/payload.json", event_id)
+    }
+}
+
+/// Returns the value at a dotted path such as `results.summary.failed`
+///
+/// Numeric segments index into arrays.
+fn lookup<'a>(payload: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    path.split('.')
+        .try_fold(payload, |value, segment| match value {
+            JsonValue::Object(object) => object.get(segment),
+            JsonValue::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_summary_extracts_configured_paths() {
+        let payload = json!({
+            "suite": "integration",
+            "results": {"passed": 1200, "failed": 3, "cases": [{"name": "login"}]},
+            "tree": {"huge": true}
+        });
+        let canonical = canonical_json::canonical_bytes(&payload).unwrap();
+        let paths = vec![
+            "suite".to_string(),
+            "results.failed".to_string(),
+            "results.cases.0.name".to_string(),
+            "results.missing".to_string(),
+            "suite.nested".to_string(),
+        ];
+
+        let summary = PayloadSummary::new("key".to_string(), &payload, &canonical, &paths);
+
+        assert_eq!(summary.top_level_keys, ["results", "suite", "tree"]);
+        assert_eq!(
+            summary.fields,
+            BTreeMap::from([
+                ("suite".to_string(), json!("integration")),
+                ("results.failed".to_string(), json!(3)),
+                ("results.cases.0.name".to_string(), json!("login")),
+            ])
+        );
+        assert_eq!(summary.size_bytes, canonical.len() as u64);
+        assert_eq!(summary.rollup_fields(), json!({}));
+        assert_eq!(
+            summary.hash,
+            canonical_json::content_hash(&payload).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_summary_keeps_severity_for_rollups() {
+        let payload = json!({"severity": "CRITICAL", "tree": {"huge": true}});
+        let canonical = canonical_json::canonical_bytes(&payload).unwrap();
+
+        let summary = PayloadSummary::new("key".to_string(), &payload, &canonical, &[]);
+
+        assert_eq!(summary.fields["severity"], json!("CRITICAL"));
+        assert_eq!(summary.rollup_fields(), json!({"severity": "CRITICAL"}));
+    }
+
+    #[test]
+    fn test_blob_key_is_beside_attachments() {
+        let event_id = EventId::new();
+        assert_eq!(
+            PayloadSummary::blob_key_for(event_id),
+            format!("{}/payload.json", event_id)
+        );
+    }
+}
@@ -0,0 +1,702 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/payload_transform.rs
+
+//! Declarative rewrites of event payloads before validation
+//!
+//! A receiver's transform is an ordered list of steps, each renaming,
+//! copying, deleting, parsing, or defaulting the value at a dotted path.
+//! Steps run once each, in order, so a transform always finishes; the only
+//! condition is whether a value is missing, for defaults.
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value as JsonValue};
+
+/// Most steps a transform may have
+pub const MAX_TRANSFORM_STEPS: usize = 32;
+
+/// Longest path a step may name
+pub const MAX_TRANSFORM_PATH_LEN: usize = 256;
+
+/// Most segments a path may have
+pub const MAX_TRANSFORM_PATH_DEPTH: usize = 16;
+
+/// Timestamp format for RFC 3339 strings
+pub const RFC3339_FORMAT: &str = "rfc3339";
+
+/// Timestamp format for seconds since the Unix epoch
+pub const UNIX_SECONDS_FORMAT: &str = "unix_seconds";
+
+/// Timestamp format for milliseconds since the Unix epoch
+pub const UNIX_MILLIS_FORMAT: &str = "unix_millis";
+
+/// Ordered rewrites applied to a receiver's event payloads
+///
+/// Serialized as the list of its steps.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PayloadTransform {
+    steps: Vec<TransformStep>,
+}
+
+/// One rewrite and what happens when it cannot be applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransformStep {
+    #[serde(flatten)]
+    pub op: TransformOp,
+    #[serde(default)]
+    pub on_failure: TransformFailurePolicy,
+}
+
+/// A rewrite of the value at a dotted path such as `build.duration`
+///
+/// Numeric path segments index into arrays. Serialized with its name under
+/// `op`, such as `{"op": "rename", "from": "a", "to": "b"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TransformOp {
+    /// Moves the value at `from` to `to`
+    Rename { from: String, to: String },
+    /// Copies the value at `from` to `to`
+    Copy { from: String, to: String },
+    /// Removes the value at `path`; a missing value is left alone
+    Delete { path: String },
+    /// Turns a numeric string at `path` into a number
+    ParseNumber { path: String },
+    /// Turns the timestamp at `path` into an RFC 3339 string in UTC
+    ///
+    /// `format` is `rfc3339`, `unix_seconds`, `unix_millis`, or a strftime
+    /// pattern such as `%d/%m/%Y %H:%M`; patterns without an offset are
+    /// read as UTC.
+    ParseTimestamp { path: String, format: String },
+    /// Sets `path` to `value` when it is missing
+    Default { path: String, value: JsonValue },
+}
+
+/// What a step that cannot be applied does to the event
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransformFailurePolicy {
+    /// Rejects the event with an error naming the step
+    #[default]
+    Reject,
+    /// Leaves the payload as it was and goes on with the next step
+    Skip,
+}
+
+/// A payload before and after a receiver's transform
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransformPreview {
+    pub before: JsonValue,
+    /// `None` when the transform rejects the payload
+    pub after: Option<JsonValue>,
+}
+
+impl TransformPreview {
+    /// Applies `transform` to a copy of `payload`
+    pub fn new(transform: &PayloadTransform, payload: &JsonValue) -> Self {
+        Self {
+            before: payload.clone(),
+            after: transform.apply(payload.clone()).ok(),
+        }
+    }
+}
+
+impl PayloadTransform {
+    pub fn new(steps: Vec<TransformStep>) -> Self {
+        Self { steps }
+    }
+
+    pub fn steps(&self) -> &[TransformStep] {
+        &self.steps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Checks the step count, paths, and timestamp formats
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.steps.len() > MAX_TRANSFORM_STEPS {
+            return Err(DomainError::ValidationError {
+                field: "transform".to_string(),
+                message: Message::new("validation.transform_too_many_steps")
+                    .with("max", MAX_TRANSFORM_STEPS),
+            });
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            step.op.validate(index)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the steps to `payload` in order
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error on `transform[i]` for the first step
+    /// that cannot be applied and rejects the event.
+    pub fn apply(&self, mut payload: JsonValue) -> Result<JsonValue, DomainError> {
+        for (index, step) in self.steps.iter().enumerate() {
+            match step.op.apply(&mut payload) {
+                Ok(()) => {}
+                Err(_) if step.on_failure == TransformFailurePolicy::Skip => {}
+                Err(failure) => return Err(failure.into_error(index, &step.op)),
+            }
+        }
+        Ok(payload)
+    }
+}
+
+/// Why a step could not be applied
+enum StepFailure {
+    /// The value the step reads is missing
+    Missing(String),
+    /// The value at the path has the wrong form for the step
+    Unparseable(String),
+    /// A parent of the path the step writes is not an object
+    Blocked(String),
+}
+
+impl StepFailure {
+    fn into_error(self, index: usize, op: &TransformOp) -> DomainError {
+        let message = match self {
+            StepFailure::Missing(path) => {
+                Message::new("validation.transform_value_missing").with("path", path)
+            }
+            StepFailure::Unparseable(path) => match op {
+                TransformOp::ParseTimestamp { format, .. } => {
+                    Message::new("validation.transform_not_a_timestamp")
+                        .with("path", path)
+                        .with("format", format.as_str())
+                }
+                _ => Message::new("validation.transform_not_a_number").with("path", path),
+            },
+            StepFailure::Blocked(path) => {
+                Message::new("validation.transform_path_blocked").with("path", path)
+            }
+        };
+        DomainError::ValidationError {
+            field: format!("transform[{}]", index),
+            message: message.with("index", index).with("op", op.name()),
+        }
+    }
+}
+
+impl TransformOp {
+    /// Returns the name the step is serialized with
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransformOp::Rename { .. } => "rename",
+            TransformOp::Copy { .. } => "copy",
+            TransformOp::Delete { .. } => "delete",
+            TransformOp::ParseNumber { .. } => "parse_number",
+            TransformOp::ParseTimestamp { .. } => "parse_timestamp",
+            TransformOp::Default { .. } => "default",
+        }
+    }
+
+    fn validate(&self, index: usize) -> Result<(), DomainError> {
+        let invalid = |message: Message| DomainError::ValidationError {
+            field: format!("transform[{}]", index),
+            message: message.with("index", index),
+        };
+        let check_path = |path: &str| {
+            if is_valid_path(path) {
+                Ok(())
+            } else {
+                Err(invalid(
+                    Message::new("validation.transform_path_invalid")
+                        .with("path", path)
+                        .with("max", MAX_TRANSFORM_PATH_LEN),
+                ))
+            }
+        };
+
+        match self {
+            TransformOp::Rename { from, to } | TransformOp::Copy { from, to } => {
+                check_path(from)?;
+                check_path(to)?;
+                if from == to {
+                    return Err(invalid(Message::new("validation.transform_same_path")));
+                }
+                Ok(())
+            }
+            TransformOp::Delete { path }
+            | TransformOp::ParseNumber { path }
+            | TransformOp::Default { path, .. } => check_path(path),
+            TransformOp::ParseTimestamp { path, format } => {
+                check_path(path)?;
+                if is_valid_timestamp_format(format) {
+                    Ok(())
+                } else {
+                    Err(invalid(
+                        Message::new("validation.transform_format_invalid")
+                            .with("format", format.as_str()),
+                    ))
+                }
+            }
+        }
+    }
+
+    fn apply(&self, payload: &mut JsonValue) -> Result<(), StepFailure> {
+        match self {
+            TransformOp::Rename { from, to } => {
+                let value =
+                    remove(payload, from).ok_or_else(|| StepFailure::Missing(from.clone()))?;
+                // Put the value back if the target cannot take it
+                set(payload, to, value).map_err(|value| {
+                    let _ = set(payload, from, value);
+                    StepFailure::Blocked(to.clone())
+                })
+            }
+            TransformOp::Copy { from, to } => {
+                let value = lookup(payload, from)
+                    .cloned()
+                    .ok_or_else(|| StepFailure::Missing(from.clone()))?;
+                set(payload, to, value).map_err(|_| StepFailure::Blocked(to.clone()))
+            }
+            TransformOp::Delete { path } => {
+                remove(payload, path);
+                Ok(())
+            }
+            TransformOp::ParseNumber { path } => {
+                let value =
+                    lookup_mut(payload, path).ok_or_else(|| StepFailure::Missing(path.clone()))?;
+                if !value.is_number() {
+                    *value = parse_number(value)
+                        .ok_or_else(|| StepFailure::Unparseable(path.clone()))?;
+                }
+                Ok(())
+            }
+            TransformOp::ParseTimestamp { path, format } => {
+                let value =
+                    lookup_mut(payload, path).ok_or_else(|| StepFailure::Missing(path.clone()))?;
+                let timestamp = parse_timestamp(value, format)
+                    .ok_or_else(|| StepFailure::Unparseable(path.clone()))?;
+                *value = JsonValue::String(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+                Ok(())
+            }
+            TransformOp::Default { path, value } => {
+                if lookup(payload, path).is_some() {
+                    return Ok(());
+                }
+                set(payload, path, value.clone()).map_err(|_| StepFailure::Blocked(path.clone()))
+            }
+        }
+    }
+}
+
+/// Returns true for dot-separated, non-empty segments within the limits
+fn is_valid_path(path: &str) -> bool {
+    path.len() <= MAX_TRANSFORM_PATH_LEN
+        && path.split('.').count() <= MAX_TRANSFORM_PATH_DEPTH
+        && path
+            .split('.')
+            .all(|segment| !segment.is_empty() && !segment.chars().any(char::is_whitespace))
+}
+
+fn is_valid_timestamp_format(format: &str) -> bool {
+    use chrono::format::{Item, StrftimeItems};
+
+    matches!(
+        format,
+        RFC3339_FORMAT | UNIX_SECONDS_FORMAT | UNIX_MILLIS_FORMAT
+    ) || (!format.trim().is_empty()
+        && StrftimeItems::new(format).all(|item| !matches!(item, Item::Error)))
+}
+
+/// Splits `path` into its parent path and last segment
+fn split_last(path: &str) -> (Option<&str>, &str) {
+    match path.rsplit_once('.') {
+        Some((parent, last)) => (Some(parent), last),
+        None => (None, path),
+    }
+}
+
+fn lookup<'a>(payload: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    path.split('.')
+        .try_fold(payload, |value, segment| match value {
+            JsonValue::Object(object) => object.get(segment),
+            JsonValue::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+fn lookup_mut<'a>(payload: &'a mut JsonValue, path: &str) -> Option<&'a mut JsonValue> {
+    path.split('.')
+        .try_fold(payload, |value, segment| match value {
+            JsonValue::Object(object) => object.get_mut(segment),
+            JsonValue::Array(items) => segment
+                .parse::<usize>()
+                .ok()
+                .and_then(move |i| items.get_mut(i)),
+            _ => None,
+        })
+}
+
+/// Removes and returns the value at `path`
+fn remove(payload: &mut JsonValue, path: &str) -> Option<JsonValue> {
+    let (parent, last) = split_last(path);
+    let parent = match parent {
+        Some(parent) => lookup_mut(payload, parent)?,
+        None => payload,
+    };
+    match parent {
+        JsonValue::Object(object) => object.remove(last),
+        JsonValue::Array(items) => {
+            let index = last.parse::<usize>().ok().filter(|i| *i < items.len())?;
+            Some(items.remove(index))
+        }
+        _ => None,
+    }
+}
+
+/// Writes `value` at `path`, creating missing parent objects
+///
+/// Hands `value` back when a parent is neither an object nor an array
+/// holding the indexed element.
+fn set(payload: &mut JsonValue, path: &str, value: JsonValue) -> Result<(), JsonValue> {
+    let (parents, last) = split_last(path);
+    let mut current = payload;
+    for segment in parents.into_iter().flat_map(|p| p.split('.')) {
+        current = match current {
+            JsonValue::Object(object) => object
+                .entry(segment)
+                .or_insert_with(|| JsonValue::Object(Map::new())),
+            JsonValue::Array(items) => match segment.parse::<usize>().ok() {
+                Some(i) if i < items.len() => &mut items[i],
+                _ => return Err(value),
+            },
+            _ => return Err(value),
+        };
+    }
+
+    match current {
+        JsonValue::Object(object) => {
+            object.insert(last.to_string(), value);
+            Ok(())
+        }
+        JsonValue::Array(items) => match last.parse::<usize>().ok() {
+            Some(i) if i < items.len() => {
+                items[i] = value;
+                Ok(())
+            }
+            _ => Err(value),
+        },
+        _ => Err(value),
+    }
+}
+
+/// Reads a string holding an integer or a finite decimal as a number
+fn parse_number(value: &JsonValue) -> Option<JsonValue> {
+    let text = value.as_str()?.trim();
+    if let Ok(n) = text.parse::<i64>() {
+        return Some(JsonValue::Number(n.into()));
+    }
+    if let Ok(n) = text.parse::<u64>() {
+        return Some(JsonValue::Number(n.into()));
+    }
+    text.parse::<f64>()
+        .ok()
+        .and_then(Number::from_f64)
+        .map(JsonValue::Number)
+}
+
+fn parse_timestamp(value: &JsonValue, format: &str) -> Option<DateTime<Utc>> {
+    let epoch = |scale: i64| -> Option<DateTime<Utc>> {
+        let count = match value {
+            JsonValue::Number(n) => n.as_i64()?,
+            JsonValue::String(s) => s.trim().parse::<i64>().ok()?,
+            _ => return None,
+        };
+        match scale {
+            1 => Utc.timestamp_opt(count, 0).single(),
+            _ => Utc.timestamp_millis_opt(count).single(),
+        }
+    };
+
+    match format {
+        UNIX_SECONDS_FORMAT => epoch(1),
+        UNIX_MILLIS_FORMAT => epoch(1000),
+        RFC3339_FORMAT => DateTime::parse_from_rfc3339(value.as_str()?.trim())
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        pattern => {
+            let text = value.as_str()?.trim();
+            DateTime::parse_from_str(text, pattern)
+                .map(|t| t.with_timezone(&Utc))
+                .or_else(|_| NaiveDateTime::parse_from_str(text, pattern).map(|t| t.and_utc()))
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(text, pattern)
+                        .map(|d| d.and_hms_opt(0, 0, 0).expect("midnight exists").and_utc())
+                })
+                .ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transform(steps: JsonValue) -> PayloadTransform {
+        let transform: PayloadTransform = serde_json::from_value(steps).unwrap();
+        transform.validate().unwrap();
+        transform
+    }
+
+    fn failed_field(err: DomainError) -> String {
+        match err {
+            DomainError::ValidationError { field, .. } => field,
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rename_moves_nested_values() {
+        let t = transform(json!([
+            {"op": "rename", "from": "meta.build_id", "to": "build.id"},
+        ]));
+
+        let payload = t
+            .apply(json!({"meta": {"build_id": 7, "host": "ci"}}))
+            .unwrap();
+
+        assert_eq!(payload, json!({"meta": {"host": "ci"}, "build": {"id": 7}}));
+    }
+
+    #[test]
+    fn test_copy_lifts_a_value_and_keeps_the_source() {
+        let t = transform(json!([
+            {"op": "copy", "from": "results.0.status", "to": "status"},
+        ]));
+
+        let payload = t.apply(json!({"results": [{"status": "passed"}]})).unwrap();
+
+        assert_eq!(
+            payload,
+            json!({"results": [{"status": "passed"}], "status": "passed"})
+        );
+    }
+
+    #[test]
+    fn test_delete_ignores_missing_values() {
+        let t = transform(json!([
+            {"op": "delete", "path": "debug"},
+            {"op": "delete", "path": "never.there"},
+        ]));
+
+        let payload = t
+            .apply(json!({"debug": {"trace": []}, "ok": true}))
+            .unwrap();
+
+        assert_eq!(payload, json!({"ok": true}));
+    }
+
+    #[test]
+    fn test_parse_number_coerces_numeric_strings() {
+        let t = transform(json!([
+            {"op": "parse_number", "path": "count"},
+            {"op": "parse_number", "path": "ratio"},
+            {"op": "parse_number", "path": "already"},
+        ]));
+
+        let payload = t
+            .apply(json!({"count": " 42 ", "ratio": "0.25", "already": 3}))
+            .unwrap();
+
+        assert_eq!(payload, json!({"count": 42, "ratio": 0.25, "already": 3}));
+
+        let err = t
+            .apply(json!({"count": "forty", "ratio": "1", "already": 1}))
+            .unwrap_err();
+        assert_eq!(failed_field(err), "transform[0]");
+    }
+
+    #[test]
+    fn test_parse_timestamp_normalizes_to_utc() {
+        let t = transform(json!([
+            {"op": "parse_timestamp", "path": "started", "format": "%d/%m/%Y %H:%M"},
+            {"op": "parse_timestamp", "path": "finished", "format": "unix_millis"},
+            {"op": "parse_timestamp", "path": "queued", "format": "rfc3339"},
+            {"op": "parse_timestamp", "path": "day", "format": "%Y%m%d"},
+        ]));
+
+        let payload = t
+            .apply(json!({
+                "started": "07/06/2025 10:30",
+                "finished": 1749292200000_i64,
+                "queued": "2025-06-07T12:00:00+02:00",
+                "day": "20250607"
+            }))
+            .unwrap();
+
+        assert_eq!(
+            payload,
+            json!({
+                "started": "2025-06-07T10:30:00Z",
+                "finished": "2025-06-07T10:30:00Z",
+                "queued": "2025-06-07T10:00:00Z",
+                "day": "2025-06-07T00:00:00Z"
+            })
+        );
+    }
+
+    #[test]
+    fn test_default_only_fills_missing_values() {
+        let t = transform(json!([
+            {"op": "default", "path": "env", "value": "prod"},
+            {"op": "default", "path": "labels.team", "value": "platform"},
+        ]));
+
+        assert_eq!(
+            t.apply(json!({"env": "staging"})).unwrap(),
+            json!({"env": "staging", "labels": {"team": "platform"}})
+        );
+        assert_eq!(
+            t.apply(json!({"env": null, "labels": {"team": "core"}}))
+                .unwrap(),
+            json!({"env": null, "labels": {"team": "core"}})
+        );
+    }
+
+    #[test]
+    fn test_steps_run_in_order() {
+        let rename_then_default = transform(json!([
+            {"op": "rename", "from": "state", "to": "status", "on_failure": "skip"},
+            {"op": "default", "path": "status", "value": "unknown"},
+        ]));
+        let default_then_rename = transform(json!([
+            {"op": "default", "path": "status", "value": "unknown"},
+            {"op": "rename", "from": "state", "to": "status", "on_failure": "skip"},
+        ]));
+        let payload = json!({"state": "passed"});
+
+        assert_eq!(
+            rename_then_default.apply(payload.clone()).unwrap(),
+            json!({"status": "passed"})
+        );
+        // The default fills the path first and the rename then replaces it
+        assert_eq!(
+            default_then_rename.apply(payload).unwrap(),
+            json!({"status": "passed"})
+        );
+        assert_eq!(
+            default_then_rename.apply(json!({})).unwrap(),
+            json!({"status": "unknown"})
+        );
+
+        let copy_then_parse = transform(json!([
+            {"op": "copy", "from": "count", "to": "raw_count"},
+            {"op": "parse_number", "path": "count"},
+        ]));
+        let parse_then_copy = transform(json!([
+            {"op": "parse_number", "path": "count"},
+            {"op": "copy", "from": "count", "to": "raw_count"},
+        ]));
+        assert_eq!(
+            copy_then_parse.apply(json!({"count": "5"})).unwrap(),
+            json!({"count": 5, "raw_count": "5"})
+        );
+        assert_eq!(
+            parse_then_copy.apply(json!({"count": "5"})).unwrap(),
+            json!({"count": 5, "raw_count": 5})
+        );
+    }
+
+    #[test]
+    fn test_failure_policies() {
+        let t = transform(json!([
+            {"op": "parse_number", "path": "size", "on_failure": "skip"},
+            {"op": "rename", "from": "name", "to": "title"},
+        ]));
+
+        // A skipped step leaves its value alone
+        assert_eq!(
+            t.apply(json!({"size": "big", "name": "x"})).unwrap(),
+            json!({"size": "big", "title": "x"})
+        );
+
+        let err = t.apply(json!({"size": "1"})).unwrap_err();
+        match err {
+            DomainError::ValidationError { field, message } => {
+                assert_eq!(field, "transform[1]");
+                assert_eq!(message.key(), "validation.transform_value_missing");
+                assert_eq!(message.params().get("index"), Some("1"));
+                assert_eq!(message.params().get("op"), Some("rename"));
+            }
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rename_into_a_scalar_keeps_the_payload() {
+        let t = transform(json!([
+            {"op": "rename", "from": "id", "to": "build.id", "on_failure": "skip"},
+        ]));
+
+        assert_eq!(
+            t.apply(json!({"id": 1, "build": "b-1"})).unwrap(),
+            json!({"id": 1, "build": "b-1"})
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_specs() {
+        let invalid = |steps: JsonValue| {
+            serde_json::from_value::<PayloadTransform>(steps)
+                .unwrap()
+                .validate()
+                .unwrap_err()
+        };
+
+        assert_eq!(
+            failed_field(invalid(json!([{"op": "delete", "path": "a..b"}]))),
+            "transform[0]"
+        );
+        assert_eq!(
+            failed_field(invalid(json!([
+                {"op": "delete", "path": "ok"},
+                {"op": "rename", "from": "a", "to": "a"},
+            ]))),
+            "transform[1]"
+        );
+        assert_eq!(
+            failed_field(invalid(json!([
+                {"op": "parse_timestamp", "path": "t", "format": "%Q"},
+            ]))),
+            "transform[0]"
+        );
+        let too_many: Vec<JsonValue> = (0..=MAX_TRANSFORM_STEPS)
+            .map(|i| json!({"op": "delete", "path": format!("f{}", i)}))
+            .collect();
+        assert_eq!(
+            failed_field(invalid(JsonValue::Array(too_many))),
+            "transform"
+        );
+
+        // Unknown operations never deserialize
+        assert!(serde_json::from_value::<PayloadTransform>(json!([
+            {"op": "eval", "path": "a"},
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn test_preview_shows_both_payloads() {
+        let t = transform(json!([{"op": "parse_number", "path": "n"}]));
+
+        let preview = TransformPreview::new(&t, &json!({"n": "1"}));
+        assert_eq!(preview.before, json!({"n": "1"}));
+        assert_eq!(preview.after, Some(json!({"n": 1})));
+
+        let preview = TransformPreview::new(&t, &json!({"n": "one"}));
+        assert_eq!(preview.after, None);
+    }
+}
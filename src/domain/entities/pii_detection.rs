@@ -0,0 +1,618 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/pii_detection.rs
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Default largest payload scanned, in bytes of serialized JSON
+pub const DEFAULT_PII_MAX_PAYLOAD_BYTES: usize = 64 * 1024;
+
+/// Default most findings recorded for one event
+pub const DEFAULT_PII_MAX_FINDINGS: usize = 20;
+
+/// Text that replaces a detected value in `redact` mode
+pub const PII_MASK: &str = "[REDACTED]";
+
+/// What ingestion does with payload values that look like personal data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiDetectionMode {
+    /// Payloads are not scanned
+    #[default]
+    Off,
+    /// Findings are recorded; the payload is stored as sent
+    Warn,
+    /// Findings are recorded and the detected values are masked
+    Redact,
+}
+
+/// Kind of personal data a detector looks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiDetector {
+    /// Email addresses
+    Email,
+    /// Card numbers of 13 to 19 digits passing the Luhn check
+    CreditCard,
+    /// US social security and UK national insurance numbers
+    NationalId,
+    /// International numbers with a leading `+`, or separated 3-3-4 numbers
+    Phone,
+}
+
+impl PiiDetector {
+    /// Every detector, in the order they claim matches
+    ///
+    /// Card numbers and national ids are matched before phone numbers, so
+    /// their digits are not reported twice.
+    pub const ALL: [PiiDetector; 4] = [
+        PiiDetector::Email,
+        PiiDetector::CreditCard,
+        PiiDetector::NationalId,
+        PiiDetector::Phone,
+    ];
+
+    /// Returns the storage representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PiiDetector::Email => "email",
+            PiiDetector::CreditCard => "credit_card",
+            PiiDetector::NationalId => "national_id",
+            PiiDetector::Phone => "phone",
+        }
+    }
+
+    /// Returns the byte ranges of `text` this detector matches
+    pub fn find(&self, text: &str) -> Vec<Range<usize>> {
+        let matches = self.regex().find_iter(text);
+        match self {
+            PiiDetector::CreditCard => matches
+                .filter(|m| is_card_number(m.as_str()))
+                .map(|m| m.range())
+                .collect(),
+            PiiDetector::NationalId => matches
+                .filter(|m| !m.as_str().contains('-') || is_ssn(m.as_str()))
+                .map(|m| m.range())
+                .collect(),
+            _ => matches.map(|m| m.range()).collect(),
+        }
+    }
+
+    fn regex(&self) -> &'static Regex {
+        static EMAIL: OnceLock<Regex> = OnceLock::new();
+        static CREDIT_CARD: OnceLock<Regex> = OnceLock::new();
+        static NATIONAL_ID: OnceLock<Regex> = OnceLock::new();
+        static PHONE: OnceLock<Regex> = OnceLock::new();
+
+        let (cell, pattern) = match self {
+            PiiDetector::Email => (
+                &EMAIL,
+                r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}\b",
+            ),
+            PiiDetector::CreditCard => (&CREDIT_CARD, r"\b\d(?:[ -]?\d){12,18}\b"),
+            PiiDetector::NationalId => (
+                &NATIONAL_ID,
+                r"\b(?:\d{3}-\d{2}-\d{4}|[A-CEGHJ-PR-TW-Z]{2}\d{6}[A-D])\b",
+            ),
+            PiiDetector::Phone => (
+                &PHONE,
+                r"\+[1-9]\d{7,14}\b|(?:\+\d{1,3}[ .-])?(?:\(\d{3}\)|\b\d{3})[ .-]\d{3}[ .-]\d{4}\b",
+            ),
+        };
+        cell.get_or_init(|| Regex::new(pattern).expect("PII detector patterns are valid"))
+    }
+}
+
+impl fmt::Display for PiiDetector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PiiDetector {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PiiDetector::ALL
+            .into_iter()
+            .find(|detector| detector.as_str() == s)
+            .ok_or_else(|| DomainError::ValidationError {
+                field: "detector".to_string(),
+                message: Message::new("validation.pii_detector_unknown").with("value", s),
+            })
+    }
+}
+
+/// Returns true if `candidate` has 13 to 19 digits and passes the Luhn check
+fn is_card_number(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) || digits.iter().all(|&d| d == digits[0]) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match (i % 2 == 1, d * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => d,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// Returns true if `candidate` is a social security number that can be issued
+fn is_ssn(candidate: &str) -> bool {
+    let mut parts = candidate.split('-');
+    let (Some(area), Some(group), Some(serial)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    area != "000" && area != "666" && !area.starts_with('9') && group != "00" && serial != "0000"
+}
+
+/// A payload value that looks like personal data
+///
+/// Only the detector and where the value sits are kept, never the value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PiiFinding {
+    pub detector: PiiDetector,
+    /// JSON path of the string holding the value, e.g. `$.user.emails[0]`
+    pub path: String,
+}
+
+/// Result of scanning one payload
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiiScan {
+    /// At most one finding per detector and path
+    pub findings: Vec<PiiFinding>,
+    /// The payload was over the size cap and not scanned
+    pub skipped: bool,
+    /// More findings were found than the cap records
+    pub truncated: bool,
+}
+
+/// Scans event payloads for values that look like personal data
+///
+/// Only string leaves are scanned, and only in payloads up to a size cap,
+/// so the work per event stays bounded. At most `max_findings` findings are
+/// recorded; in `redact` mode the rest of the payload is still masked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PiiScanner {
+    mode: PiiDetectionMode,
+    max_payload_bytes: usize,
+    max_findings: usize,
+}
+
+impl Default for PiiScanner {
+    fn default() -> Self {
+        Self::new(PiiDetectionMode::Off)
+    }
+}
+
+impl PiiScanner {
+    pub fn new(mode: PiiDetectionMode) -> Self {
+        Self {
+            mode,
+            max_payload_bytes: DEFAULT_PII_MAX_PAYLOAD_BYTES,
+            max_findings: DEFAULT_PII_MAX_FINDINGS,
+        }
+    }
+
+    /// Sets the largest payload scanned, in bytes of serialized JSON
+    pub fn with_max_payload_bytes(mut self, max_payload_bytes: usize) -> Self {
+        self.max_payload_bytes = max_payload_bytes;
+        self
+    }
+
+    /// Sets the most findings recorded for one payload
+    pub fn with_max_findings(mut self, max_findings: usize) -> Self {
+        self.max_findings = max_findings;
+        self
+    }
+
+    pub fn mode(&self) -> PiiDetectionMode {
+        self.mode
+    }
+
+    /// Scans `payload`, masking detected values in `redact` mode
+    pub fn apply(&self, payload: &mut JsonValue) -> PiiScan {
+        let mut scan = PiiScan::default();
+        if self.mode == PiiDetectionMode::Off {
+            return scan;
+        }
+        let size = serde_json::to_vec(payload).map_or(usize::MAX, |bytes| bytes.len());
+        if size > self.max_payload_bytes {
+            scan.skipped = true;
+            return scan;
+        }
+
+        let mut seen = HashSet::new();
+        self.walk(payload, &mut String::from("$"), &mut scan, &mut seen);
+        scan
+    }
+
+    fn walk(
+        &self,
+        value: &mut JsonValue,
+        path: &mut String,
+        scan: &mut PiiScan,
+        seen: &mut HashSet<PiiFinding>,
+    ) {
+        let redact = self.mode == PiiDetectionMode::Redact;
+        if scan.truncated && !redact {
+            return;
+        }
+
+        match value {
+            JsonValue::String(text) => {
+                let (detectors, masked) = self.detect(text);
+                for detector in detectors {
+                    let finding = PiiFinding {
+                        detector,
+                        path: path.clone(),
+                    };
+                    if seen.contains(&finding) {
+                        continue;
+                    }
+                    if scan.findings.len() == self.max_findings {
+                        scan.truncated = true;
+                        break;
+                    }
+                    seen.insert(finding.clone());
+                    scan.findings.push(finding);
+                }
+                if redact {
+                    if let Some(masked) = masked {
+                        *text = masked;
+                    }
+                }
+            }
+            JsonValue::Array(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    let len = path.len();
+                    path.push_str(&format!("[{}]", index));
+                    self.walk(item, path, scan, seen);
+                    path.truncate(len);
+                }
+            }
+            JsonValue::Object(fields) => {
+                for (key, item) in fields.iter_mut() {
+                    let len = path.len();
+                    push_key(path, key);
+                    self.walk(item, path, scan, seen);
+                    path.truncate(len);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the detectors matching `text` and, if any did, the masked text
+    fn detect(&self, text: &str) -> (Vec<PiiDetector>, Option<String>) {
+        let mut claimed: Vec<Range<usize>> = Vec::new();
+        let mut detectors = Vec::new();
+        for detector in PiiDetector::ALL {
+            let mut matched = false;
+            for range in detector.find(text) {
+                if claimed
+                    .iter()
+                    .all(|c| range.end <= c.start || c.end <= range.start)
+                {
+                    claimed.push(range);
+                    matched = true;
+                }
+            }
+            if matched {
+                detectors.push(detector);
+            }
+        }
+        if claimed.is_empty() {
+            return (detectors, None);
+        }
+
+        claimed.sort_by_key(|range| range.start);
+        let mut masked = String::with_capacity(text.len());
+        let mut last = 0;
+        for range in claimed {
+            masked.push_str(&text[last..range.start]);
+            masked.push_str(PII_MASK);
+            last = range.end;
+        }
+        masked.push_str(&text[last..]);
+        (detectors, Some(masked))
+    }
+}
+
+/// Appends `key` to a JSON path, quoting keys that are not identifiers
+fn push_key(path: &mut String, key: &str) {
+    let plain = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        path.push('.');
+        path.push_str(key);
+    } else {
+        path.push_str(&format!("[{}]", JsonValue::String(key.to_string())));
+    }
+}
+
+/// How often one detector matched at one path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PiiPathCount {
+    pub detector: PiiDetector,
+    pub path: String,
+    pub count: u64,
+}
+
+/// Findings of a receiver's events over a period, aggregated by path
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct PiiReport {
+    /// Events with at least one finding
+    pub events_with_findings: u64,
+    /// Most frequent detector and path pairs, most frequent first
+    pub paths: Vec<PiiPathCount>,
+}
+
+impl PiiReport {
+    /// Aggregates the finding lists of individual events
+    ///
+    /// Keeps the `limit` most frequent paths; ties are ordered by detector,
+    /// then path. Useful for in-memory implementations.
+    pub fn aggregate<'a>(
+        findings: impl IntoIterator<Item = &'a [PiiFinding]>,
+        limit: usize,
+    ) -> Self {
+        let mut events_with_findings = 0;
+        let mut counts: BTreeMap<(PiiDetector, &str), u64> = BTreeMap::new();
+        for event in findings {
+            if event.is_empty() {
+                continue;
+            }
+            events_with_findings += 1;
+            for finding in event {
+                *counts
+                    .entry((finding.detector, finding.path.as_str()))
+                    .or_default() += 1;
+            }
+        }
+
+        let mut paths: Vec<PiiPathCount> = counts
+            .into_iter()
+            .map(|((detector, path), count)| PiiPathCount {
+                detector,
+                path: path.to_string(),
+                count,
+            })
+            .collect();
+        // Stable, so equal counts keep the detector and path order
+        paths.sort_by_key(|path| std::cmp::Reverse(path.count));
+        paths.truncate(limit);
+        Self {
+            events_with_findings,
+            paths,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn detected(detector: PiiDetector, text: &str) -> bool {
+        !detector.find(text).is_empty()
+    }
+
+    #[test]
+    fn test_detectors_against_fixtures() {
+        let cases: [(PiiDetector, &[&str], &[&str]); 4] = [
+            (
+                PiiDetector::Email,
+                &[
+                    "jane.doe@example.com",
+                    "contact: ops+alerts@mail.example.co.uk",
+                ],
+                &["not an email", "user@localhost", "@example.com", "v1.2.3"],
+            ),
+            (
+                PiiDetector::CreditCard,
+                &[
+                    "4111111111111111",
+                    "card 4111 1111 1111 1111 on file",
+                    "5500-0000-0000-0004",
+                ],
+                &[
+                    "4111111111111112",
+                    "0000000000000000",
+                    "1234567890",
+                    "build 20250612",
+                ],
+            ),
+            (
+                PiiDetector::NationalId,
+                &["ssn 123-45-6789", "AB123456C"],
+                &["000-12-3456", "666-12-3456", "123-00-4567", "2025-06-12"],
+            ),
+            (
+                PiiDetector::Phone,
+                &[
+                    "+14155552671",
+                    "call (415) 555-2671",
+                    "415.555.2671",
+                    "+44 207-946-0958",
+                ],
+                &["1700000000", "v1.2.3", "2025-06-12T10:00:00Z", "12-34"],
+            ),
+        ];
+
+        for (detector, positives, negatives) in cases {
+            for text in positives {
+                assert!(detected(detector, text), "{} missed {}", detector, text);
+            }
+            for text in negatives {
+                assert!(!detected(detector, text), "{} matched {}", detector, text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_card_digits_are_not_reported_as_phone() {
+        let mut payload = json!({"card": "4111-1111-1111-1111"});
+        let scan = PiiScanner::new(PiiDetectionMode::Warn).apply(&mut payload);
+
+        assert_eq!(
+            scan.findings,
+            vec![PiiFinding {
+                detector: PiiDetector::CreditCard,
+                path: "$.card".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_warn_reports_paths_without_values() {
+        let mut payload = json!({
+            "user": {"email": "jane.doe@example.com", "name": "Jane"},
+            "contacts": ["+14155552671", "nobody"],
+            "odd key": "ssn 123-45-6789",
+            "count": 4111111111111111_u64
+        });
+        let original = payload.clone();
+
+        let scan = PiiScanner::new(PiiDetectionMode::Warn).apply(&mut payload);
+
+        assert_eq!(payload, original);
+        assert!(!scan.skipped && !scan.truncated);
+        let found: Vec<(PiiDetector, &str)> = scan
+            .findings
+            .iter()
+            .map(|f| (f.detector, f.path.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (PiiDetector::Phone, "$.contacts[0]"),
+                (PiiDetector::NationalId, "$[\"odd key\"]"),
+                (PiiDetector::Email, "$.user.email"),
+            ]
+        );
+        let serialized = serde_json::to_string(&scan.findings).unwrap();
+        for value in ["jane.doe", "4155552671", "6789"] {
+            assert!(!serialized.contains(value), "{}", serialized);
+        }
+    }
+
+    #[test]
+    fn test_redact_masks_detected_values() {
+        let mut payload = json!({
+            "message": "mail jane.doe@example.com or call 415-555-2671",
+            "build": "v1.2.3"
+        });
+
+        let scan = PiiScanner::new(PiiDetectionMode::Redact).apply(&mut payload);
+
+        assert_eq!(scan.findings.len(), 2);
+        assert_eq!(
+            payload,
+            json!({
+                "message": "mail [REDACTED] or call [REDACTED]",
+                "build": "v1.2.3"
+            })
+        );
+    }
+
+    #[test]
+    fn test_off_mode_does_nothing() {
+        let mut payload = json!({"email": "jane.doe@example.com"});
+        let scan = PiiScanner::default().apply(&mut payload);
+
+        assert_eq!(scan, PiiScan::default());
+        assert_eq!(payload, json!({"email": "jane.doe@example.com"}));
+    }
+
+    #[test]
+    fn test_payloads_over_the_cap_are_skipped() {
+        let mut payload = json!({
+            "email": "jane.doe@example.com",
+            "padding": "x".repeat(200)
+        });
+
+        let scan = PiiScanner::new(PiiDetectionMode::Redact)
+            .with_max_payload_bytes(100)
+            .apply(&mut payload);
+
+        assert!(scan.skipped);
+        assert!(scan.findings.is_empty());
+        assert_eq!(payload["email"], "jane.doe@example.com");
+    }
+
+    #[test]
+    fn test_findings_are_capped_but_redaction_continues() {
+        let emails: Vec<String> = (0..5).map(|i| format!("user{}@example.com", i)).collect();
+        let mut warned = json!({ "emails": emails });
+        let mut redacted = warned.clone();
+
+        let scan = PiiScanner::new(PiiDetectionMode::Warn)
+            .with_max_findings(2)
+            .apply(&mut warned);
+        assert_eq!(scan.findings.len(), 2);
+        assert!(scan.truncated);
+
+        let scan = PiiScanner::new(PiiDetectionMode::Redact)
+            .with_max_findings(2)
+            .apply(&mut redacted);
+        assert_eq!(scan.findings.len(), 2);
+        assert!(redacted["emails"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|email| email == PII_MASK));
+    }
+
+    #[test]
+    fn test_report_aggregates_by_path() {
+        let email = |path: &str| PiiFinding {
+            detector: PiiDetector::Email,
+            path: path.to_string(),
+        };
+        let events = [
+            vec![email("$.a"), email("$.b")],
+            vec![],
+            vec![email("$.b")],
+            vec![email("$.c")],
+        ];
+
+        let report = PiiReport::aggregate(events.iter().map(Vec::as_slice), 2);
+
+        assert_eq!(report.events_with_findings, 3);
+        assert_eq!(
+            report.paths,
+            vec![
+                PiiPathCount {
+                    detector: PiiDetector::Email,
+                    path: "$.b".to_string(),
+                    count: 2,
+                },
+                PiiPathCount {
+                    detector: PiiDetector::Email,
+                    path: "$.a".to_string(),
+                    count: 1,
+                },
+            ]
+        );
+    }
+}
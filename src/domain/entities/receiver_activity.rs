@@ -0,0 +1,128 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/receiver_activity.rs
+
+use crate::domain::value_objects::EventReceiverId;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::collections::BTreeSet;
+
+/// Days covered by the distinct principal count, including today
+pub const DISTINCT_PRINCIPAL_WINDOW_DAYS: i64 = 30;
+
+/// Liveness of an event receiver as seen by ingestion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiverActivity {
+    pub receiver_id: EventReceiverId,
+    pub last_event_at: DateTime<Utc>,
+    /// Principal of the latest event, e.g. `api_key:01J...`
+    pub last_principal: String,
+    /// Distinct principals that sent events during the window
+    pub distinct_principals: u64,
+}
+
+/// Activity buffered for one receiver since the last flush
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiverActivityUpdate {
+    pub receiver_id: EventReceiverId,
+    pub last_event_at: DateTime<Utc>,
+    pub last_principal: String,
+    /// Each principal seen, once per UTC day
+    pub daily_principals: BTreeSet<(NaiveDate, String)>,
+}
+
+impl ReceiverActivityUpdate {
+    /// Starts an update from a single accepted event
+    pub fn new(receiver_id: EventReceiverId, principal: String, at: DateTime<Utc>) -> Self {
+        let mut daily_principals = BTreeSet::new();
+        daily_principals.insert((at.date_naive(), principal.clone()));
+        Self {
+            receiver_id,
+            last_event_at: at,
+            last_principal: principal,
+            daily_principals,
+        }
+    }
+
+    /// Folds another accepted event into the update
+    pub fn record(&mut self, principal: String, at: DateTime<Utc>) {
+        if at >= self.last_event_at {
+            self.last_event_at = at;
+            self.last_principal = principal.clone();
+        }
+        self.daily_principals.insert((at.date_naive(), principal));
+    }
+
+    /// Folds an update for the same receiver into this one
+    pub fn merge(&mut self, other: ReceiverActivityUpdate) {
+        if other.last_event_at >= self.last_event_at {
+            self.last_event_at = other.last_event_at;
+            self.last_principal = other.last_principal;
+        }
+        self.daily_principals.extend(other.daily_principals);
+    }
+}
+
+/// Returns the first day counted by the distinct principal window at `now`
+pub fn principal_window_start(now: DateTime<Utc>) -> NaiveDate {
+    (now - Duration::days(DISTINCT_PRINCIPAL_WINDOW_DAYS - 1)).date_naive()
+}
+
+/// Counts distinct principals seen on or after `window_start`
+pub fn distinct_principals_since<'a>(
+    daily_principals: impl IntoIterator<Item = &'a (NaiveDate, String)>,
+    window_start: NaiveDate,
+) -> u64 {
+    daily_principals
+        .into_iter()
+        .filter(|(day, _)| *day >= window_start)
+        .map(|(_, principal)| principal)
+        .collect::<BTreeSet<_>>()
+        .len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_keeps_latest_principal() {
+        let now = Utc::now();
+        let mut update =
+            ReceiverActivityUpdate::new(EventReceiverId::new(), "user:a".to_string(), now);
+        update.record("api_key:b".to_string(), now - Duration::minutes(5));
+        assert_eq!(update.last_principal, "user:a");
+
+        update.record("api_key:c".to_string(), now + Duration::minutes(5));
+        assert_eq!(update.last_principal, "api_key:c");
+        assert_eq!(update.last_event_at, now + Duration::minutes(5));
+        assert_eq!(update.daily_principals.len(), 3);
+    }
+
+    #[test]
+    fn test_distinct_principals_across_seeded_spread() {
+        let now = Utc::now();
+        let mut daily = BTreeSet::new();
+        // Principal i sends several events a day on days 3i..3i+2 ago
+        for i in 0..20_i64 {
+            for day in (i * 3)..(i * 3 + 3) {
+                for hour in 0..4 {
+                    let at = now - Duration::days(day) - Duration::minutes(hour);
+                    daily.insert((at.date_naive(), format!("api_key:{}", i)));
+                }
+            }
+        }
+
+        // Days 0..29 ago are in the window: principals 0..=9 started there,
+        // principal 10 starts on day 30
+        let window_start = principal_window_start(now);
+        assert_eq!(window_start, (now - Duration::days(29)).date_naive());
+        assert_eq!(distinct_principals_since(&daily, window_start), 10);
+
+        // Every principal counts once, however many days it sent on
+        assert_eq!(
+            distinct_principals_since(&daily, (now - Duration::days(365)).date_naive()),
+            20
+        );
+    }
+}
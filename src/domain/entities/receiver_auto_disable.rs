@@ -0,0 +1,182 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/receiver_auto_disable.rs
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Longest observation window a policy may use, one week
+pub const MAX_OBSERVATION_WINDOW_MINUTES: u32 = 7 * 24 * 60;
+
+/// Failure rate at which a receiver stops accepting events
+///
+/// The rate is the fraction of failed events among those the receiver
+/// stored in the trailing observation window. Windows holding fewer than
+/// `minimum_event_count` events are never evaluated, so a receiver that
+/// sees one failure on a quiet day is left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutoDisablePolicy {
+    /// Fraction of failed events that disables the receiver, above 0.0 and
+    /// at most 1.0
+    pub failure_rate_threshold: f64,
+    /// Length of the trailing window the rate is measured over
+    pub observation_window_minutes: u32,
+    /// Events the window must hold before the rate is considered
+    pub minimum_event_count: u64,
+}
+
+impl AutoDisablePolicy {
+    /// Checks the threshold, window, and minimum count are usable
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let threshold = self.failure_rate_threshold;
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err(invalid(Message::new(
+                "validation.auto_disable_threshold_range",
+            )));
+        }
+        if !(1..=MAX_OBSERVATION_WINDOW_MINUTES).contains(&self.observation_window_minutes) {
+            return Err(invalid(
+                Message::new("validation.auto_disable_window_range")
+                    .with("max", MAX_OBSERVATION_WINDOW_MINUTES),
+            ));
+        }
+        if self.minimum_event_count == 0 {
+            return Err(invalid(Message::new(
+                "validation.auto_disable_minimum_count",
+            )));
+        }
+        Ok(())
+    }
+
+    /// Length of the observation window
+    pub fn observation_window(&self) -> Duration {
+        Duration::minutes(i64::from(self.observation_window_minutes))
+    }
+
+    /// Returns true if `failed` of `total` events breach the policy
+    pub fn is_breached(&self, failed: u64, total: u64) -> bool {
+        total > 0
+            && total >= self.minimum_event_count
+            && failed as f64 / total as f64 >= self.failure_rate_threshold
+    }
+}
+
+/// Statistics of the window that disabled a receiver
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoDisableTrigger {
+    /// Failed events in the window
+    pub failed: u64,
+    /// All events in the window
+    pub total: u64,
+    /// `failed / total`
+    pub failure_rate: f64,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    /// When the receiver was disabled
+    pub disabled_at: DateTime<Utc>,
+}
+
+impl AutoDisableTrigger {
+    /// Records a breached window `[window_start, window_end)`
+    pub fn new(
+        failed: u64,
+        total: u64,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            failed,
+            total,
+            failure_rate: if total == 0 {
+                0.0
+            } else {
+                failed as f64 / total as f64
+            },
+            window_start,
+            window_end,
+            disabled_at: window_end,
+        }
+    }
+}
+
+/// Whether a receiver accepts events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiverState {
+    /// Events are accepted
+    Active,
+    /// Disabled by its auto-disable policy until an owner re-enables it
+    AutoDisabled,
+}
+
+fn invalid(message: Message) -> DomainError {
+    DomainError::ValidationError {
+        field: "auto_disable".to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> AutoDisablePolicy {
+        AutoDisablePolicy {
+            failure_rate_threshold: 0.9,
+            observation_window_minutes: 60,
+            minimum_event_count: 10,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(policy().validate().is_ok());
+
+        for invalid in [
+            AutoDisablePolicy {
+                failure_rate_threshold: 0.0,
+                ..policy()
+            },
+            AutoDisablePolicy {
+                failure_rate_threshold: 1.5,
+                ..policy()
+            },
+            AutoDisablePolicy {
+                failure_rate_threshold: f64::NAN,
+                ..policy()
+            },
+            AutoDisablePolicy {
+                observation_window_minutes: 0,
+                ..policy()
+            },
+            AutoDisablePolicy {
+                observation_window_minutes: MAX_OBSERVATION_WINDOW_MINUTES + 1,
+                ..policy()
+            },
+            AutoDisablePolicy {
+                minimum_event_count: 0,
+                ..policy()
+            },
+        ] {
+            let err = invalid.validate().unwrap_err();
+            assert!(matches!(
+                err,
+                DomainError::ValidationError { ref field, .. } if field == "auto_disable"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_is_breached() {
+        let policy = policy();
+        assert!(policy.is_breached(9, 10));
+        assert!(policy.is_breached(100, 100));
+        assert!(!policy.is_breached(8, 10));
+        // Below the minimum count the rate is not considered
+        assert!(!policy.is_breached(9, 9));
+        assert!(!policy.is_breached(0, 0));
+    }
+}
@@ -0,0 +1,344 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/receiver_clone.rs
+
+//! Copying a receiver's configuration into a new receiver
+//!
+//! A clone is a new receiver: it gets its own ID and fingerprint and starts
+//! without events, activity, or group memberships. Secrets are never
+//! copied, so delivery and inbound webhook secrets must be provisioned on
+//! the clone again.
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::value_objects::UserId;
+use crate::error::DomainError;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Sentence included in every clone manifest
+pub const SECRETS_NOT_COPIED_NOTICE: &str = "Secrets are never copied. Delivery and inbound \
+    webhook secrets must be provisioned on the new receiver.";
+
+/// Receiver fields that differ from the source
+///
+/// Fields left out keep the source's value, except the name, which is
+/// always new.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReceiverCloneOverrides {
+    pub name: String,
+    pub receiver_type: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// Labels requested for the clone; receivers do not carry labels, so
+    /// these are reported as skipped
+    pub labels: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// Configuration copied from the source
+///
+/// Everything is copied unless switched off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReceiverCloneOptions {
+    pub schema: bool,
+    pub quotas: bool,
+    pub sampling: bool,
+    pub redaction: bool,
+    pub delivery: bool,
+    pub allowed_event_names: bool,
+    pub auto_disable: bool,
+    pub transform: bool,
+}
+
+impl Default for ReceiverCloneOptions {
+    fn default() -> Self {
+        Self {
+            schema: true,
+            quotas: true,
+            sampling: true,
+            redaction: true,
+            delivery: true,
+            allowed_event_names: true,
+            auto_disable: true,
+            transform: true,
+        }
+    }
+}
+
+/// A part of a receiver's configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneItem {
+    Schema,
+    Quotas,
+    Sampling,
+    Redaction,
+    Delivery,
+    AllowedEventNames,
+    AutoDisable,
+    Transform,
+    Labels,
+    GroupMembership,
+    Secrets,
+}
+
+/// Why a part of the configuration was not copied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloneSkipReason {
+    /// The caller switched the item off
+    NotRequested,
+    /// The source has nothing to copy
+    NotConfigured,
+    /// Receivers do not carry this configuration; it applies server-wide
+    /// or does not exist
+    NotSupported,
+    /// The item is never copied
+    NeverCopied,
+}
+
+/// An item left out of a clone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedCloneItem {
+    pub item: CloneItem,
+    pub reason: CloneSkipReason,
+}
+
+/// What a clone copied from its source and what it left out
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiverCloneManifest {
+    pub copied: Vec<CloneItem>,
+    pub skipped: Vec<SkippedCloneItem>,
+    /// Always false
+    pub secrets_copied: bool,
+    /// Always [`SECRETS_NOT_COPIED_NOTICE`]
+    pub notice: String,
+}
+
+impl ReceiverCloneManifest {
+    fn new() -> Self {
+        Self {
+            copied: Vec::new(),
+            skipped: Vec::new(),
+            secrets_copied: false,
+            notice: SECRETS_NOT_COPIED_NOTICE.to_string(),
+        }
+    }
+
+    fn skip(&mut self, item: CloneItem, reason: CloneSkipReason) {
+        self.skipped.push(SkippedCloneItem { item, reason });
+    }
+
+    /// Records `item` as copied if `requested` and `configured`
+    fn decide(&mut self, item: CloneItem, requested: bool, configured: bool) -> bool {
+        match (requested, configured) {
+            (false, _) => self.skip(item, CloneSkipReason::NotRequested),
+            (true, false) => self.skip(item, CloneSkipReason::NotConfigured),
+            (true, true) => self.copied.push(item),
+        }
+        requested && configured
+    }
+
+    /// Returns true if `item` was copied
+    pub fn is_copied(&self, item: CloneItem) -> bool {
+        self.copied.contains(&item)
+    }
+}
+
+/// Builds a new receiver from `source`, owned by `owner_id`
+///
+/// Without the schema, the clone gets the empty schema `{}`. The clone is
+/// not saved.
+pub fn clone_receiver(
+    source: &EventReceiver,
+    overrides: ReceiverCloneOverrides,
+    options: ReceiverCloneOptions,
+    owner_id: UserId,
+) -> Result<(EventReceiver, ReceiverCloneManifest), DomainError> {
+    let mut manifest = ReceiverCloneManifest::new();
+
+    let schema = if manifest.decide(CloneItem::Schema, options.schema, source.has_own_schema()) {
+        source.schema().clone()
+    } else {
+        json!({})
+    };
+    let mut clone = EventReceiver::new(
+        overrides.name,
+        overrides
+            .receiver_type
+            .unwrap_or_else(|| source.receiver_type().to_string()),
+        overrides
+            .version
+            .unwrap_or_else(|| source.version().to_string()),
+        overrides
+            .description
+            .unwrap_or_else(|| source.description().to_string()),
+        schema,
+        owner_id,
+    )?;
+
+    if manifest.decide(
+        CloneItem::Sampling,
+        options.sampling,
+        source.sample_rate().is_some(),
+    ) {
+        clone.set_sample_rate(source.sample_rate())?;
+    }
+    if manifest.decide(
+        CloneItem::AllowedEventNames,
+        options.allowed_event_names,
+        source.allowed_event_names().is_some(),
+    ) {
+        clone.set_allowed_event_names(source.allowed_event_names().cloned())?;
+    }
+    if manifest.decide(
+        CloneItem::AutoDisable,
+        options.auto_disable,
+        source.auto_disable_policy().is_some(),
+    ) {
+        clone.set_auto_disable_policy(source.auto_disable_policy().copied())?;
+    }
+    if manifest.decide(
+        CloneItem::Transform,
+        options.transform,
+        source.transform().is_some(),
+    ) {
+        clone.set_transform(source.transform().cloned())?;
+    }
+
+    // Quotas and PII redaction are configured for the whole server, and
+    // receivers have no labels or delivery configuration
+    for (item, requested) in [
+        (CloneItem::Quotas, options.quotas),
+        (CloneItem::Redaction, options.redaction),
+        (CloneItem::Delivery, options.delivery),
+        (CloneItem::Labels, overrides.labels.is_some()),
+    ] {
+        if requested {
+            manifest.skip(item, CloneSkipReason::NotSupported);
+        }
+    }
+    manifest.skip(CloneItem::GroupMembership, CloneSkipReason::NeverCopied);
+    manifest.skip(CloneItem::Secrets, CloneSkipReason::NeverCopied);
+
+    Ok((clone, manifest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event_name_constraint::AllowedEventNames;
+    use crate::domain::entities::receiver_auto_disable::AutoDisablePolicy;
+
+    fn source() -> EventReceiver {
+        let mut receiver = EventReceiver::new(
+            "builds".to_string(),
+            "ci".to_string(),
+            "1.2.0".to_string(),
+            "Production builds".to_string(),
+            json!({"type": "object", "required": ["id"]}),
+            UserId::new(),
+        )
+        .unwrap();
+        receiver.set_sample_rate(Some(0.25)).unwrap();
+        receiver
+            .set_allowed_event_names(Some(AllowedEventNames::Glob("build.*".to_string())))
+            .unwrap();
+        receiver
+            .set_auto_disable_policy(Some(AutoDisablePolicy {
+                failure_rate_threshold: 0.5,
+                observation_window_minutes: 60,
+                minimum_event_count: 10,
+            }))
+            .unwrap();
+        receiver
+            .set_transform(Some(
+                serde_json::from_value(json!([{"op": "rename", "from": "build_id", "to": "id"}]))
+                    .unwrap(),
+            ))
+            .unwrap();
+        receiver
+    }
+
+    fn overrides(name: &str) -> ReceiverCloneOverrides {
+        ReceiverCloneOverrides {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_clone_copies_configuration_under_new_identity() {
+        let source = source();
+        let owner = UserId::new();
+
+        let (clone, manifest) = clone_receiver(
+            &source,
+            overrides("builds-staging"),
+            ReceiverCloneOptions::default(),
+            owner,
+        )
+        .unwrap();
+
+        assert_ne!(clone.id(), source.id());
+        assert_ne!(clone.fingerprint(), source.fingerprint());
+        assert_eq!(clone.name(), "builds-staging");
+        assert_eq!(clone.receiver_type(), "ci");
+        assert_eq!(clone.version(), "1.2.0");
+        assert_eq!(clone.schema(), source.schema());
+        assert_eq!(clone.sample_rate(), Some(0.25));
+        assert_eq!(clone.allowed_event_names(), source.allowed_event_names());
+        assert_eq!(clone.auto_disable_policy(), source.auto_disable_policy());
+        assert_eq!(clone.transform(), source.transform());
+        assert_eq!(clone.owner_id(), owner);
+        assert_eq!(
+            manifest.copied,
+            vec![
+                CloneItem::Schema,
+                CloneItem::Sampling,
+                CloneItem::AllowedEventNames,
+                CloneItem::AutoDisable,
+                CloneItem::Transform,
+            ]
+        );
+        assert!(!manifest.secrets_copied);
+        assert!(manifest.skipped.contains(&SkippedCloneItem {
+            item: CloneItem::Secrets,
+            reason: CloneSkipReason::NeverCopied,
+        }));
+        assert!(manifest.skipped.contains(&SkippedCloneItem {
+            item: CloneItem::Quotas,
+            reason: CloneSkipReason::NotSupported,
+        }));
+    }
+
+    #[test]
+    fn test_switched_off_items_are_not_copied() {
+        let options = ReceiverCloneOptions {
+            schema: false,
+            sampling: false,
+            ..Default::default()
+        };
+
+        let (clone, manifest) = clone_receiver(
+            &source(),
+            ReceiverCloneOverrides {
+                version: Some("2.0.0".to_string()),
+                ..overrides("builds-staging")
+            },
+            options,
+            UserId::new(),
+        )
+        .unwrap();
+
+        assert_eq!(clone.schema(), &json!({}));
+        assert_eq!(clone.sample_rate(), None);
+        assert_eq!(clone.version(), "2.0.0");
+        assert!(!manifest.is_copied(CloneItem::Schema));
+        assert!(manifest.skipped.contains(&SkippedCloneItem {
+            item: CloneItem::Sampling,
+            reason: CloneSkipReason::NotRequested,
+        }));
+    }
+}
@@ -0,0 +1,67 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/receiver_deletion.rs
+
+//! What deleting a receiver is blocked by and what it changes
+//!
+//! A receiver that still receives events, or that API keys are scoped to,
+//! is only deleted when an administrator forces it. Deleting a receiver
+//! removes it from its groups, revokes the keys scoped to it, drops cached
+//! lookups, and leaves a tombstone in the receiver change feed.
+
+use crate::domain::value_objects::{ApiKeyId, EventReceiverGroupId, EventReceiverId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default age in days of events that block deleting their receiver
+pub const DEFAULT_RECENT_EVENT_DAYS: u32 = 30;
+
+/// Why a receiver is not deleted without force
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeletionBlocker {
+    /// The receiver stored events at or after `since`
+    RecentEvents { count: usize, since: DateTime<Utc> },
+    /// Enabled, unexpired API keys are scoped to the receiver
+    ActiveApiKeys { key_ids: Vec<ApiKeyId> },
+}
+
+/// Cache that dropped a deleted receiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletionCache {
+    /// Receiver lookups by id
+    ReceiverLookups,
+    /// Rendered Markdown descriptions
+    Descriptions,
+    /// Effective schemas inherited from groups
+    EffectiveSchemas,
+    /// OPA authorization decisions
+    AuthorizationDecisions,
+}
+
+/// Side effects of deleting a receiver
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiverDeletionReport {
+    pub receiver_id: EventReceiverId,
+    /// True if an administrator deleted the receiver despite `blockers`
+    pub forced: bool,
+    /// Blockers the deletion overrode; empty unless forced
+    pub blockers: Vec<DeletionBlocker>,
+    /// Groups the receiver was removed from
+    pub removed_from_groups: Vec<EventReceiverGroupId>,
+    /// Keys scoped to the receiver that were revoked
+    pub revoked_api_keys: Vec<ApiKeyId>,
+    pub invalidated_caches: Vec<DeletionCache>,
+    /// When the receiver was deleted
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Result of asking to delete a receiver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiverDeletionOutcome {
+    /// Nothing was changed
+    Blocked(Vec<DeletionBlocker>),
+    Deleted(ReceiverDeletionReport),
+}
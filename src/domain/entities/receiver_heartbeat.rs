@@ -0,0 +1,74 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/receiver_heartbeat.rs
+
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::DomainError;
+use crate::i18n::Message;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// Largest serialized heartbeat status accepted, in bytes
+pub const MAX_HEARTBEAT_STATUS_BYTES: usize = 1024;
+
+/// Latest "I'm alive" signal from a producer of an event receiver
+///
+/// Only the newest heartbeat is kept; its status replaces the previous one
+/// wholesale, so a heartbeat without a status clears it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceiverHeartbeat {
+    pub receiver_id: EventReceiverId,
+    pub last_heartbeat_at: DateTime<Utc>,
+    /// Producer-reported details such as `version` and `hostname`
+    pub status: Option<Value>,
+}
+
+impl ReceiverHeartbeat {
+    /// Creates a heartbeat, rejecting a status that is not a small JSON object
+    pub fn new(
+        receiver_id: EventReceiverId,
+        at: DateTime<Utc>,
+        status: Option<Value>,
+    ) -> Result<Self, DomainError> {
+        if let Some(status) = &status {
+            if !status.is_object() {
+                return Err(DomainError::ValidationError {
+                    field: "status".to_string(),
+                    message: Message::new("validation.heartbeat_status_not_object"),
+                });
+            }
+            if status.to_string().len() > MAX_HEARTBEAT_STATUS_BYTES {
+                return Err(DomainError::ValidationError {
+                    field: "status".to_string(),
+                    message: Message::new("validation.heartbeat_status_too_large")
+                        .with("max", MAX_HEARTBEAT_STATUS_BYTES),
+                });
+            }
+        }
+
+        Ok(Self {
+            receiver_id,
+            last_heartbeat_at: at,
+            status,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_status_must_be_small_object() {
+        let id = EventReceiverId::new();
+        let now = Utc::now();
+        assert!(ReceiverHeartbeat::new(id, now, None).is_ok());
+        assert!(ReceiverHeartbeat::new(id, now, Some(json!({"version": "1.2.0"}))).is_ok());
+        assert!(ReceiverHeartbeat::new(id, now, Some(json!("up"))).is_err());
+
+        let large = json!({ "hostname": "h".repeat(MAX_HEARTBEAT_STATUS_BYTES) });
+        assert!(ReceiverHeartbeat::new(id, now, Some(large)).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/receiver_provisioning.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::value_objects::UserId;
+use crate::error::DomainError;
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+
+/// Description given to receivers created by their first event
+pub const PROVISIONED_RECEIVER_DESCRIPTION: &str = "Provisioned by its first event";
+
+/// Whether an event may name a receiver that does not exist yet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiverProvisioningPolicy {
+    /// Events must reference an existing receiver by id
+    #[default]
+    Disabled,
+    /// Callers allowed to create receivers may provision one implicitly
+    AllowWithPermission,
+    /// Any caller allowed to create events may provision a receiver
+    AllowAll,
+}
+
+impl ReceiverProvisioningPolicy {
+    /// Returns true if a caller may provision a receiver implicitly
+    pub fn permits(&self, can_create_receivers: bool) -> bool {
+        match self {
+            ReceiverProvisioningPolicy::Disabled => false,
+            ReceiverProvisioningPolicy::AllowWithPermission => can_create_receivers,
+            ReceiverProvisioningPolicy::AllowAll => true,
+        }
+    }
+}
+
+/// Receiver named by an event in place of a receiver id
+///
+/// Two specs with the same name, type, version, and schema resolve to the
+/// same receiver, since they produce the same fingerprint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceiverSpec {
+    pub name: String,
+    pub receiver_type: String,
+    pub version: String,
+    /// Payload schema; `None` accepts any payload
+    pub schema: Option<JsonValue>,
+}
+
+impl ReceiverSpec {
+    /// Builds the receiver this spec describes, owned by `owner_id`
+    pub fn to_receiver(&self, owner_id: UserId) -> Result<EventReceiver, DomainError> {
+        EventReceiver::new(
+            self.name.clone(),
+            self.receiver_type.clone(),
+            self.version.clone(),
+            PROVISIONED_RECEIVER_DESCRIPTION.to_string(),
+            self.schema.clone().unwrap_or_else(|| json!({})),
+            owner_id,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec() -> ReceiverSpec {
+        ReceiverSpec {
+            name: "ci-builds".to_string(),
+            receiver_type: "webhook".to_string(),
+            version: "1.0.0".to_string(),
+            schema: None,
+        }
+    }
+
+    #[test]
+    fn test_policy_permits() {
+        assert!(!ReceiverProvisioningPolicy::Disabled.permits(true));
+        assert!(ReceiverProvisioningPolicy::AllowWithPermission.permits(true));
+        assert!(!ReceiverProvisioningPolicy::AllowWithPermission.permits(false));
+        assert!(ReceiverProvisioningPolicy::AllowAll.permits(false));
+    }
+
+    #[test]
+    fn test_same_spec_has_same_fingerprint() {
+        let first = spec().to_receiver(UserId::new()).unwrap();
+        let second = spec().to_receiver(UserId::new()).unwrap();
+        assert_ne!(first.id(), second.id());
+        assert_eq!(first.fingerprint(), second.fingerprint());
+
+        let mut typed = spec();
+        typed.schema = Some(json!({"type": "object"}));
+        let typed = typed.to_receiver(UserId::new()).unwrap();
+        assert_ne!(typed.fingerprint(), first.fingerprint());
+    }
+}
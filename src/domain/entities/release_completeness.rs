@@ -0,0 +1,225 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/release_completeness.rs
+
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Events a receiver stored for one release
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReleaseOutcome {
+    /// At least one successful event was stored
+    pub succeeded: bool,
+    /// At least one failed event was stored
+    pub failed: bool,
+}
+
+/// Whether a receiver reported a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiverReleaseStatus {
+    /// At least one successful event
+    Complete,
+    /// Only failed events
+    FailedOnly,
+    /// No events
+    Missing,
+}
+
+impl ReceiverReleaseStatus {
+    /// Classifies the events a receiver stored for a release
+    pub fn from_outcome(outcome: Option<ReleaseOutcome>) -> Self {
+        match outcome {
+            Some(outcome) if outcome.succeeded => Self::Complete,
+            Some(outcome) if outcome.failed => Self::FailedOnly,
+            _ => Self::Missing,
+        }
+    }
+
+    /// Name of the status in API responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Complete => "complete",
+            Self::FailedOnly => "failed_only",
+            Self::Missing => "missing",
+        }
+    }
+}
+
+impl fmt::Display for ReceiverReleaseStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Release status of one member receiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiverCompleteness {
+    pub receiver_id: EventReceiverId,
+    /// Optional receivers are reported but never block completeness
+    pub required: bool,
+    pub status: ReceiverReleaseStatus,
+}
+
+/// Whether every required receiver of a group reported a release
+///
+/// A group is complete once each required receiver stored a successful
+/// event with the release. A group without required receivers is never
+/// complete, so an empty group cannot gate a promotion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupCompleteness {
+    pub group_id: EventReceiverGroupId,
+    pub release: String,
+    /// Start of the window events were looked up in; `None` covers every
+    /// stored event
+    pub since: Option<DateTime<Utc>>,
+    /// Member receivers in group order
+    pub receivers: Vec<ReceiverCompleteness>,
+    pub complete: bool,
+}
+
+impl GroupCompleteness {
+    /// Evaluates the members of `group` against their release outcomes
+    ///
+    /// Receivers missing from `outcomes` stored no events for the release.
+    pub fn evaluate(
+        group: &EventReceiverGroup,
+        release: &str,
+        since: Option<DateTime<Utc>>,
+        outcomes: &HashMap<EventReceiverId, ReleaseOutcome>,
+    ) -> Self {
+        let receivers: Vec<ReceiverCompleteness> = group
+            .event_receiver_ids()
+            .iter()
+            .map(|&receiver_id| ReceiverCompleteness {
+                receiver_id,
+                required: group.is_required(receiver_id),
+                status: ReceiverReleaseStatus::from_outcome(outcomes.get(&receiver_id).copied()),
+            })
+            .collect();
+
+        let mut required = receivers.iter().filter(|receiver| receiver.required);
+        let complete = required.clone().next().is_some()
+            && required.all(|receiver| receiver.status == ReceiverReleaseStatus::Complete);
+
+        Self {
+            group_id: group.id(),
+            release: release.to_string(),
+            since,
+            receivers,
+            complete,
+        }
+    }
+
+    /// Counts member receivers with `status`, required or not
+    pub fn count(&self, status: ReceiverReleaseStatus) -> usize {
+        self.receivers
+            .iter()
+            .filter(|receiver| receiver.status == status)
+            .count()
+    }
+
+    /// Returns the required receivers that have not completed the release
+    pub fn blocking_receivers(&self) -> impl Iterator<Item = &ReceiverCompleteness> {
+        self.receivers.iter().filter(|receiver| {
+            receiver.required && receiver.status != ReceiverReleaseStatus::Complete
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::UserId;
+
+    fn group(receiver_ids: &[EventReceiverId]) -> EventReceiverGroup {
+        EventReceiverGroup::new(
+            "Release Gate".to_string(),
+            "release".to_string(),
+            "1.0.0".to_string(),
+            "Receivers gating a release".to_string(),
+            true,
+            receiver_ids.to_vec(),
+            UserId::new(),
+        )
+        .unwrap()
+    }
+
+    fn outcome(succeeded: bool, failed: bool) -> ReleaseOutcome {
+        ReleaseOutcome { succeeded, failed }
+    }
+
+    #[test]
+    fn test_mixed_statuses_are_incomplete() {
+        let ids = [
+            EventReceiverId::new(),
+            EventReceiverId::new(),
+            EventReceiverId::new(),
+        ];
+        let outcomes = HashMap::from([
+            (ids[0], outcome(true, true)),
+            (ids[1], outcome(false, true)),
+        ]);
+
+        let completeness = GroupCompleteness::evaluate(&group(&ids), "1.2.0", None, &outcomes);
+
+        let statuses: Vec<_> = completeness.receivers.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ReceiverReleaseStatus::Complete,
+                ReceiverReleaseStatus::FailedOnly,
+                ReceiverReleaseStatus::Missing,
+            ]
+        );
+        assert!(!completeness.complete);
+        assert_eq!(completeness.count(ReceiverReleaseStatus::Complete), 1);
+        assert_eq!(completeness.count(ReceiverReleaseStatus::FailedOnly), 1);
+        assert_eq!(completeness.count(ReceiverReleaseStatus::Missing), 1);
+        assert_eq!(completeness.blocking_receivers().count(), 2);
+    }
+
+    #[test]
+    fn test_optional_receivers_do_not_block_completeness() {
+        let ids = [EventReceiverId::new(), EventReceiverId::new()];
+        let group = group(&ids).with_optional_receivers(vec![ids[1]]).unwrap();
+        let outcomes = HashMap::from([(ids[0], outcome(true, false))]);
+
+        let completeness = GroupCompleteness::evaluate(&group, "1.2.0", None, &outcomes);
+
+        assert!(completeness.complete);
+        assert!(!completeness.receivers[1].required);
+        assert_eq!(
+            completeness.receivers[1].status,
+            ReceiverReleaseStatus::Missing
+        );
+    }
+
+    #[test]
+    fn test_group_without_required_receivers_is_never_complete() {
+        let id = EventReceiverId::new();
+        let group = group(&[id]).with_optional_receivers(vec![id]).unwrap();
+        let outcomes = HashMap::from([(id, outcome(true, false))]);
+
+        assert!(!GroupCompleteness::evaluate(&group, "1.2.0", None, &outcomes).complete);
+        assert!(!GroupCompleteness::evaluate(&self::group(&[]), "1.2.0", None, &outcomes).complete);
+    }
+
+    #[test]
+    fn test_optional_receivers_must_be_members() {
+        let ids = [EventReceiverId::new(), EventReceiverId::new()];
+
+        assert!(group(&ids[..1])
+            .with_optional_receivers(vec![ids[1]])
+            .is_err());
+
+        let mut group = group(&ids).with_optional_receivers(vec![ids[1]]).unwrap();
+        group.remove_event_receiver(ids[1]).unwrap();
+        assert!(group.optional_receiver_ids().is_empty());
+    }
+}
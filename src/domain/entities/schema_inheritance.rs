@@ -0,0 +1,359 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/schema_inheritance.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::value_objects::EventReceiverGroupId;
+use crate::error::DomainError;
+use crate::i18n::Message;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::fmt;
+
+/// Where a receiver's effective payload schema comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaSource {
+    /// The receiver's own schema (possibly empty)
+    Own,
+    /// The default schema of the single enabled group the receiver belongs
+    /// to, or of its default schema group when several conflict
+    Inherited,
+    /// Several enabled groups define conflicting default schemas
+    Ambiguous,
+}
+
+impl SchemaSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaSource::Own => "own",
+            SchemaSource::Inherited => "inherited",
+            SchemaSource::Ambiguous => "ambiguous",
+        }
+    }
+}
+
+impl fmt::Display for SchemaSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Effective payload schema for a receiver after group inheritance
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSchema {
+    schema: JsonValue,
+    source: SchemaSource,
+    group_ids: Vec<EventReceiverGroupId>,
+}
+
+impl ResolvedSchema {
+    /// Resolves the effective schema for `receiver`
+    ///
+    /// An explicit receiver schema always wins. A receiver with an empty
+    /// schema inherits the default schema of its enabled groups when they
+    /// all agree; conflicting defaults are reported as ambiguous and no
+    /// schema is inherited.
+    pub fn resolve(receiver: &EventReceiver, groups: &[EventReceiverGroup]) -> Self {
+        Self::resolve_with_default(receiver, groups, None)
+    }
+
+    /// Resolves the effective schema, settling conflicts with a default group
+    ///
+    /// Works like [`ResolvedSchema::resolve`], except that conflicting
+    /// defaults are inherited from `default_group` when it is one of the
+    /// enabled groups defining a schema.
+    pub fn resolve_with_default(
+        receiver: &EventReceiver,
+        groups: &[EventReceiverGroup],
+        default_group: Option<EventReceiverGroupId>,
+    ) -> Self {
+        if receiver.has_own_schema() {
+            return Self::own(receiver);
+        }
+
+        let candidates: Vec<(&EventReceiverGroup, &JsonValue)> = groups
+            .iter()
+            .filter(|g| g.enabled() && g.contains_receiver(receiver.id()))
+            .filter_map(|g| g.default_schema().map(|s| (g, s)))
+            .collect();
+
+        let (first_group, first_schema) = match candidates.first() {
+            Some(candidate) => *candidate,
+            None => return Self::own(receiver),
+        };
+
+        let group_ids = candidates.iter().map(|(g, _)| g.id()).collect();
+        let default_candidate = candidates
+            .iter()
+            .find(|(g, _)| Some(g.id()) == default_group);
+        if candidates.iter().all(|(_, s)| *s == first_schema) {
+            Self {
+                schema: first_schema.clone(),
+                source: SchemaSource::Inherited,
+                group_ids: vec![first_group.id()],
+            }
+        } else if let Some((group, schema)) = default_candidate {
+            Self {
+                schema: (*schema).clone(),
+                source: SchemaSource::Inherited,
+                group_ids: vec![group.id()],
+            }
+        } else {
+            Self {
+                schema: receiver.schema().clone(),
+                source: SchemaSource::Ambiguous,
+                group_ids,
+            }
+        }
+    }
+
+    fn own(receiver: &EventReceiver) -> Self {
+        Self {
+            schema: receiver.schema().clone(),
+            source: SchemaSource::Own,
+            group_ids: Vec::new(),
+        }
+    }
+
+    pub fn schema(&self) -> &JsonValue {
+        &self.schema
+    }
+
+    pub fn source(&self) -> SchemaSource {
+        self.source
+    }
+
+    /// Groups the schema was inherited from, or the conflicting groups
+    pub fn group_ids(&self) -> &[EventReceiverGroupId] {
+        &self.group_ids
+    }
+
+    /// Validates a payload against an inherited schema
+    ///
+    /// Only inherited schemas are enforced here; a receiver's own schema is
+    /// handled by [`EventReceiver::validate_event_payload`]. Supports the
+    /// top-level `required` list and `type` on direct `properties`.
+    pub fn validate_payload(&self, payload: &JsonValue) -> Result<(), DomainError> {
+        match self.payload_errors(payload).into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Lists every way `payload` fails the inherited schema
+    ///
+    /// Returns an empty list when the payload passes, or when the schema is
+    /// not inherited.
+    pub fn payload_errors(&self, payload: &JsonValue) -> Vec<DomainError> {
+        if self.source != SchemaSource::Inherited {
+            return Vec::new();
+        }
+
+        if !payload.is_object() {
+            return vec![DomainError::ValidationError {
+                field: "payload".to_string(),
+                message: Message::new("validation.event_payload_not_object"),
+            }];
+        }
+
+        payload_violations(&self.schema, payload)
+            .into_iter()
+            .map(|violation| DomainError::ValidationError {
+                field: "payload".to_string(),
+                message: Message::new("validation.inherited_schema_violation")
+                    .with("detail", violation),
+            })
+            .collect()
+    }
+}
+
+/// Lists every way `payload` fails `schema`
+///
+/// Supports the same subset as [`ResolvedSchema::validate_payload`]: the
+/// top-level `required` list and `type` on direct `properties`. Returns an
+/// empty list when the payload passes.
+pub fn payload_violations(schema: &JsonValue, payload: &JsonValue) -> Vec<String> {
+    let payload = match payload.as_object() {
+        Some(obj) => obj,
+        None => return vec!["Event payload must be a JSON object".to_string()],
+    };
+
+    let mut violations = Vec::new();
+
+    if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+        for key in required.iter().filter_map(|k| k.as_str()) {
+            if !payload.contains_key(key) {
+                violations.push(format!("Missing required field '{}'", key));
+            }
+        }
+    }
+
+    if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+        for (key, property) in properties {
+            let expected = property.get("type").and_then(|t| t.as_str());
+            if let (Some(expected), Some(value)) = (expected, payload.get(key)) {
+                if !json_type_matches(expected, value) {
+                    violations.push(format!("Field '{}' must be of type '{}'", key, expected));
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+fn json_type_matches(expected: &str, value: &JsonValue) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "null" => value.is_null(),
+        // Unknown types are not enforced
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::UserId;
+    use serde_json::json;
+
+    fn receiver(schema: JsonValue) -> EventReceiver {
+        EventReceiver::new(
+            "Receiver".to_string(),
+            "webhook".to_string(),
+            "1.0.0".to_string(),
+            "Test receiver".to_string(),
+            schema,
+            UserId::new(),
+        )
+        .unwrap()
+    }
+
+    fn group(name: &str, receiver: &EventReceiver, schema: JsonValue) -> EventReceiverGroup {
+        EventReceiverGroup::new(
+            name.to_string(),
+            "webhook_group".to_string(),
+            "1.0.0".to_string(),
+            "Test group".to_string(),
+            true,
+            vec![receiver.id()],
+            UserId::new(),
+        )
+        .unwrap()
+        .with_default_schema(Some(schema))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_inherits_single_group_schema() {
+        let r = receiver(json!({}));
+        let g = group("g1", &r, json!({"required": ["build_id"]}));
+
+        let resolved = ResolvedSchema::resolve(&r, std::slice::from_ref(&g));
+        assert_eq!(resolved.source(), SchemaSource::Inherited);
+        assert_eq!(resolved.group_ids(), &[g.id()]);
+        assert!(resolved.validate_payload(&json!({"build_id": 1})).is_ok());
+        assert!(resolved.validate_payload(&json!({"other": 1})).is_err());
+    }
+
+    #[test]
+    fn test_own_schema_wins() {
+        let r = receiver(json!({"type": "object"}));
+        let g = group("g1", &r, json!({"required": ["build_id"]}));
+
+        let resolved = ResolvedSchema::resolve(&r, &[g]);
+        assert_eq!(resolved.source(), SchemaSource::Own);
+        assert!(resolved.validate_payload(&json!({"other": 1})).is_ok());
+    }
+
+    #[test]
+    fn test_conflicting_groups_are_ambiguous() {
+        let r = receiver(json!({}));
+        let g1 = group("g1", &r, json!({"required": ["a"]}));
+        let g2 = group("g2", &r, json!({"required": ["b"]}));
+        let g3 = group("g3", &r, json!({"required": ["a"]}));
+
+        let resolved = ResolvedSchema::resolve(&r, &[g1.clone(), g2]);
+        assert_eq!(resolved.source(), SchemaSource::Ambiguous);
+        assert_eq!(resolved.group_ids().len(), 2);
+        assert!(resolved.validate_payload(&json!({})).is_ok());
+
+        // Identical defaults do not conflict
+        let resolved = ResolvedSchema::resolve(&r, &[g1, g3]);
+        assert_eq!(resolved.source(), SchemaSource::Inherited);
+    }
+
+    #[test]
+    fn test_default_group_settles_conflict() {
+        let r = receiver(json!({}));
+        let g1 = group("g1", &r, json!({"required": ["a"]}));
+        let mut g2 = group("g2", &r, json!({"required": ["b"]}));
+        let groups = [g1.clone(), g2.clone()];
+
+        let resolved = ResolvedSchema::resolve_with_default(&r, &groups, Some(g2.id()));
+        assert_eq!(resolved.source(), SchemaSource::Inherited);
+        assert_eq!(resolved.group_ids(), &[g2.id()]);
+        assert!(resolved.validate_payload(&json!({"b": 1})).is_ok());
+        assert!(resolved.validate_payload(&json!({"a": 1})).is_err());
+
+        // A disabled default group settles nothing
+        g2.disable();
+        let resolved = ResolvedSchema::resolve_with_default(&r, &[g1, g2.clone()], Some(g2.id()));
+        assert_eq!(resolved.source(), SchemaSource::Inherited);
+        assert!(resolved.validate_payload(&json!({"a": 1})).is_ok());
+    }
+
+    #[test]
+    fn test_disabled_groups_ignored() {
+        let r = receiver(json!({}));
+        let mut g = group("g1", &r, json!({"required": ["a"]}));
+        g.disable();
+
+        let resolved = ResolvedSchema::resolve(&r, &[g]);
+        assert_eq!(resolved.source(), SchemaSource::Own);
+    }
+
+    #[test]
+    fn test_property_types_enforced() {
+        let r = receiver(json!({}));
+        let g = group(
+            "g1",
+            &r,
+            json!({"properties": {"count": {"type": "integer"}, "tag": {"type": "string"}}}),
+        );
+
+        let resolved = ResolvedSchema::resolve(&r, &[g]);
+        assert!(resolved
+            .validate_payload(&json!({"count": 3, "tag": "x"}))
+            .is_ok());
+        assert!(resolved.validate_payload(&json!({"count": "3"})).is_err());
+        assert!(resolved.validate_payload(&json!({"tag": 7})).is_err());
+    }
+
+    #[test]
+    fn test_payload_violations_lists_every_failure() {
+        let schema = json!({
+            "required": ["build", "status"],
+            "properties": {"build": {"type": "integer"}, "tag": {"type": "string"}}
+        });
+
+        assert!(payload_violations(&schema, &json!({"build": 1, "status": "ok"})).is_empty());
+        assert_eq!(
+            payload_violations(&schema, &json!({"build": "1", "tag": 7})),
+            vec![
+                "Missing required field 'status'".to_string(),
+                "Field 'build' must be of type 'integer'".to_string(),
+                "Field 'tag' must be of type 'string'".to_string(),
+            ]
+        );
+        assert_eq!(payload_violations(&schema, &json!([1])).len(), 1);
+    }
+}
@@ -0,0 +1,422 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/search.rs
+
+//! Full-text search over receivers, events, and groups
+//!
+//! A query is a list of terms that must all match. A term is a word, a
+//! word ending in `*` that matches as a prefix, or a `"quoted phrase"`
+//! whose words must appear next to each other. Words are split on
+//! punctuation, so `payments-api` searches for the phrase `payments api`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Fewest letters and digits a query must contain
+pub const MIN_SEARCH_QUERY_CHARS: usize = 2;
+
+/// Longest query accepted, in characters
+pub const MAX_SEARCH_QUERY_LENGTH: usize = 200;
+
+/// Marks the start of a match in snippets returned by the store
+pub const HIGHLIGHT_START: char = '\u{E000}';
+
+/// Marks the end of a match in snippets returned by the store
+pub const HIGHLIGHT_STOP: char = '\u{E001}';
+
+/// Kind of resource a search covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SearchResourceType {
+    Receiver,
+    Event,
+    Group,
+}
+
+impl SearchResourceType {
+    /// Every searchable type, in the order results are counted
+    pub const ALL: [SearchResourceType; 3] = [
+        SearchResourceType::Receiver,
+        SearchResourceType::Event,
+        SearchResourceType::Group,
+    ];
+
+    /// Returns the name used in the `types` parameter and in counts
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchResourceType::Receiver => "receivers",
+            SearchResourceType::Event => "events",
+            SearchResourceType::Group => "groups",
+        }
+    }
+
+    /// Returns the singular name tagging each result
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SearchResourceType::Receiver => "receiver",
+            SearchResourceType::Event => "event",
+            SearchResourceType::Group => "group",
+        }
+    }
+
+    /// Parses a comma-separated list of types; an empty list selects all
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, DomainError> {
+        let mut types = Vec::new();
+        for name in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let resource_type = name.parse()?;
+            if !types.contains(&resource_type) {
+                types.push(resource_type);
+            }
+        }
+        if types.is_empty() {
+            types = Self::ALL.to_vec();
+        }
+        Ok(types)
+    }
+}
+
+impl fmt::Display for SearchResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SearchResourceType {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|resource_type| resource_type.as_str() == s)
+            .ok_or_else(|| DomainError::ValidationError {
+                field: "types".to_string(),
+                message: Message::new("validation.search_type_unknown")
+                    .with("value", s)
+                    .with("options", "receivers, events, groups"),
+            })
+    }
+}
+
+/// One term of a search query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTerm {
+    /// Lowercased words that must appear in this order, next to each other
+    pub words: Vec<String>,
+    /// The last word matches as a prefix
+    pub prefix: bool,
+}
+
+/// A parsed search query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    text: String,
+    terms: Vec<SearchTerm>,
+}
+
+impl SearchQuery {
+    /// Parses a query
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if the query is longer than
+    /// [`MAX_SEARCH_QUERY_LENGTH`] or has fewer than
+    /// [`MIN_SEARCH_QUERY_CHARS`] letters and digits.
+    pub fn parse(input: &str) -> Result<Self, DomainError> {
+        let text = input.trim();
+        if text.chars().count() > MAX_SEARCH_QUERY_LENGTH {
+            return Err(DomainError::ValidationError {
+                field: "q".to_string(),
+                message: Message::new("validation.search_query_too_long")
+                    .with("max", MAX_SEARCH_QUERY_LENGTH),
+            });
+        }
+
+        let mut terms = Vec::new();
+        let mut rest = text;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+
+            // An unterminated quote runs to the end of the query
+            let (token, prefix) = if let Some(quoted) = rest.strip_prefix('"') {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                let phrase = &quoted[..end];
+                rest = quoted.get(end + 1..).unwrap_or_default();
+                (phrase, false)
+            } else {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '"')
+                    .unwrap_or(rest.len());
+                let word = &rest[..end];
+                rest = &rest[end..];
+                (word, word.ends_with('*'))
+            };
+
+            let words = split_words(token);
+            if !words.is_empty() {
+                terms.push(SearchTerm { words, prefix });
+            }
+        }
+
+        let chars: usize = terms
+            .iter()
+            .flat_map(|term| &term.words)
+            .map(|word| word.chars().count())
+            .sum();
+        if chars < MIN_SEARCH_QUERY_CHARS {
+            return Err(DomainError::ValidationError {
+                field: "q".to_string(),
+                message: Message::new("validation.search_query_too_short")
+                    .with("min", MIN_SEARCH_QUERY_CHARS),
+            });
+        }
+
+        Ok(Self {
+            text: text.to_string(),
+            terms,
+        })
+    }
+
+    /// Returns the query as entered, trimmed
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the parsed terms
+    pub fn terms(&self) -> &[SearchTerm] {
+        &self.terms
+    }
+
+    /// Returns the name a resource must have to match the query exactly
+    ///
+    /// Quotes and a trailing prefix marker are dropped, so `"build bot"`
+    /// and `build-bot*` exactly match `build bot` and `build-bot`.
+    pub fn exact_name(&self) -> String {
+        self.text
+            .replace('"', "")
+            .trim()
+            .trim_end_matches('*')
+            .trim()
+            .to_string()
+    }
+
+    /// Renders the query in PostgreSQL `to_tsquery` syntax
+    ///
+    /// Words only contain letters and digits, so the result never carries
+    /// tsquery operators from the input.
+    pub fn to_tsquery(&self) -> String {
+        self.terms
+            .iter()
+            .map(|term| {
+                let mut rendered = term.words.join(" <-> ");
+                if term.prefix {
+                    rendered.push_str(":*");
+                }
+                if term.words.len() > 1 {
+                    format!("({})", rendered)
+                } else {
+                    rendered
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+}
+
+fn split_words(token: &str) -> Vec<String> {
+    token
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Escapes a snippet for HTML and wraps its matches in `<mark>` tags
+///
+/// Matches are delimited by [`HIGHLIGHT_START`] and [`HIGHLIGHT_STOP`].
+/// Resource text is never trusted as markup.
+pub fn render_snippet(raw: &str) -> String {
+    let mut rendered = String::with_capacity(raw.len() + 16);
+    for c in raw.chars() {
+        match c {
+            HIGHLIGHT_START => rendered.push_str("<mark>"),
+            HIGHLIGHT_STOP => rendered.push_str("</mark>"),
+            '&' => rendered.push_str("&amp;"),
+            '<' => rendered.push_str("&lt;"),
+            '>' => rendered.push_str("&gt;"),
+            '"' => rendered.push_str("&quot;"),
+            '\'' => rendered.push_str("&#39;"),
+            c => rendered.push(c),
+        }
+    }
+    rendered
+}
+
+/// One resource matching a search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub resource_type: SearchResourceType,
+    pub id: String,
+    pub name: String,
+    /// HTML-escaped text around the match with matches in `<mark>` tags
+    pub snippet: String,
+    /// Relevance; higher is better
+    pub rank: f32,
+    /// The resource's name equals the query, ignoring case
+    pub exact_name: bool,
+}
+
+impl SearchHit {
+    /// Orders hits best first: exact name matches, then by rank
+    pub fn cmp_relevance(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .exact_name
+            .cmp(&self.exact_name)
+            .then_with(|| other.rank.total_cmp(&self.rank))
+    }
+}
+
+/// Matches per resource type, counted before results are capped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchCounts {
+    pub receivers: u64,
+    pub events: u64,
+    pub groups: u64,
+}
+
+impl SearchCounts {
+    /// Sets the count of one type
+    pub fn set(&mut self, resource_type: SearchResourceType, count: u64) {
+        match resource_type {
+            SearchResourceType::Receiver => self.receivers = count,
+            SearchResourceType::Event => self.events = count,
+            SearchResourceType::Group => self.groups = count,
+        }
+    }
+}
+
+/// Ranked hits across types with per-type counts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    pub counts: SearchCounts,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(words: &[&str], prefix: bool) -> SearchTerm {
+        SearchTerm {
+            words: words.iter().map(|w| w.to_string()).collect(),
+            prefix,
+        }
+    }
+
+    #[test]
+    fn test_parses_words_phrases_and_prefixes() {
+        let query = SearchQuery::parse(r#"  Deploy "Build Bot" jira-12* "#).unwrap();
+
+        assert_eq!(
+            query.terms(),
+            &[
+                term(&["deploy"], false),
+                term(&["build", "bot"], false),
+                term(&["jira", "12"], true),
+            ]
+        );
+        assert_eq!(
+            query.to_tsquery(),
+            "deploy & (build <-> bot) & (jira <-> 12:*)"
+        );
+    }
+
+    #[test]
+    fn test_tsquery_operators_in_input_are_dropped() {
+        let query = SearchQuery::parse("foo & !bar | (baz:*) 'q'").unwrap();
+
+        assert_eq!(query.to_tsquery(), "foo & bar & baz & q");
+    }
+
+    #[test]
+    fn test_unterminated_quote_runs_to_the_end() {
+        let query = SearchQuery::parse(r#"release "nightly build"#).unwrap();
+
+        assert_eq!(query.to_tsquery(), "release & (nightly <-> build)");
+    }
+
+    #[test]
+    fn test_rejects_degenerate_queries() {
+        for input in ["", "   ", "a", "x*", "\"\"", "-- !", "\"a\""] {
+            assert!(
+                SearchQuery::parse(input).is_err(),
+                "expected {:?} to be rejected",
+                input
+            );
+        }
+        assert!(SearchQuery::parse(&"a".repeat(MAX_SEARCH_QUERY_LENGTH + 1)).is_err());
+        assert!(SearchQuery::parse("ci").is_ok());
+    }
+
+    #[test]
+    fn test_exact_name_drops_quotes_and_prefix_marker() {
+        assert_eq!(
+            SearchQuery::parse(r#""Build Bot""#).unwrap().exact_name(),
+            "Build Bot"
+        );
+        assert_eq!(
+            SearchQuery::parse("build-bot*").unwrap().exact_name(),
+            "build-bot"
+        );
+    }
+
+    #[test]
+    fn test_parses_type_lists() {
+        assert_eq!(
+            SearchResourceType::parse_list("events, receivers,events").unwrap(),
+            vec![SearchResourceType::Event, SearchResourceType::Receiver]
+        );
+        assert_eq!(
+            SearchResourceType::parse_list("").unwrap(),
+            SearchResourceType::ALL.to_vec()
+        );
+        assert!(SearchResourceType::parse_list("receivers,users").is_err());
+    }
+
+    #[test]
+    fn test_snippet_marks_matches_and_escapes_markup() {
+        let raw = format!("<b>{}JIRA{}-42</b> & more", HIGHLIGHT_START, HIGHLIGHT_STOP);
+
+        assert_eq!(
+            render_snippet(&raw),
+            "&lt;b&gt;<mark>JIRA</mark>-42&lt;/b&gt; &amp; more"
+        );
+    }
+
+    #[test]
+    fn test_exact_name_matches_rank_first() {
+        let hit = |name: &str, rank: f32, exact_name: bool| SearchHit {
+            resource_type: SearchResourceType::Receiver,
+            id: name.to_string(),
+            name: name.to_string(),
+            snippet: String::new(),
+            rank,
+            exact_name,
+        };
+        let mut hits = [
+            hit("strong", 0.9, false),
+            hit("exact", 0.1, true),
+            hit("weak", 0.2, false),
+        ];
+
+        hits.sort_by(SearchHit::cmp_relevance);
+
+        let names: Vec<&str> = hits.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, ["exact", "strong", "weak"]);
+    }
+}
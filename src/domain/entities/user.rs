@@ -9,6 +9,7 @@
 use crate::auth::rbac::{permissions::Permission, roles::Role};
 use crate::domain::value_objects::UserId;
 use crate::error::{AuthError, DomainError};
+use crate::i18n::Message;
 use argon2::password_hash::{rand_core::OsRng, SaltString};
 use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
 use chrono::{DateTime, Utc};
@@ -43,7 +44,7 @@ impl User {
     ) -> Result<Self, DomainError> {
         let password_hash =
             hash_password(&password).map_err(|e| DomainError::BusinessRuleViolation {
-                rule: format!("Password hashing failed: {}", e),
+                rule: Message::new("rule.password_hashing").with("reason", e),
             })?;
 
         Ok(Self {
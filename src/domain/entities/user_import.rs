@@ -0,0 +1,419 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/user_import.rs
+
+use crate::auth::rbac::Role;
+use crate::error::DomainError;
+use crate::i18n::Message;
+use std::collections::HashMap;
+
+/// Columns every user import file must have
+pub const REQUIRED_IMPORT_COLUMNS: [&str; 2] = ["username", "email"];
+
+/// Columns a user import file may add
+pub const OPTIONAL_IMPORT_COLUMNS: [&str; 3] = ["roles", "enabled", "external_id"];
+
+/// Separator between role names in the `roles` column
+pub const ROLE_SEPARATOR: char = ';';
+
+/// Longest username or email the users table stores
+const MAX_FIELD_LENGTH: usize = 255;
+
+/// A user described by one row of an import file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserImportRow {
+    /// Line the row starts on; the header is line 1
+    pub line: usize,
+    pub username: String,
+    pub email: String,
+    /// Roles to assign; `user` when the column is empty
+    pub roles: Vec<Role>,
+    pub enabled: bool,
+    /// OIDC subject the account is linked to; `None` for local accounts
+    pub external_id: Option<String>,
+    /// Problems that keep the row from being imported
+    pub errors: Vec<String>,
+}
+
+impl UserImportRow {
+    /// Returns true if nothing keeps the row from being imported
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns true if the account signs in through OIDC
+    pub fn is_oidc(&self) -> bool {
+        self.external_id.is_some()
+    }
+}
+
+/// Parses a user import file
+///
+/// The first line names the columns in any order: `username` and `email`
+/// are required, `roles`, `enabled`, and `external_id` optional. Roles are
+/// separated by `;`; `enabled` accepts `true`/`false`, `yes`/`no`, or
+/// `1`/`0` and defaults to enabled. Every row is returned, with problems
+/// such as a malformed email, an unknown role, or a username or email
+/// repeated within the file listed in its `errors`.
+///
+/// # Errors
+///
+/// Returns `ValidationError` if the file has no header, has an
+/// unterminated quoted field, lacks a required column, names an unknown or
+/// repeated column, or has more than `max_rows` rows.
+pub fn parse_user_import(input: &str, max_rows: usize) -> Result<Vec<UserImportRow>, DomainError> {
+    let mut records = csv_records(input)?.into_iter();
+    let Some((_, header)) = records.next() else {
+        return Err(invalid(Message::new("validation.user_import_empty")));
+    };
+    let columns = ImportColumns::from_header(&header)?;
+
+    let records: Vec<(usize, Vec<String>)> = records.collect();
+    if records.len() > max_rows {
+        return Err(invalid(
+            Message::new("validation.user_import_too_many_rows")
+                .with("rows", records.len())
+                .with("max", max_rows),
+        ));
+    }
+
+    let mut usernames: HashMap<String, usize> = HashMap::new();
+    let mut emails: HashMap<String, usize> = HashMap::new();
+    let mut rows = Vec::with_capacity(records.len());
+    for (line, fields) in records {
+        let mut row = columns.row(line, &fields);
+        if !row.username.is_empty() {
+            if let Some(first) = usernames.get(&row.username) {
+                row.errors.push(format!(
+                    "Duplicate username '{}'; first used on line {}",
+                    row.username, first
+                ));
+            } else {
+                usernames.insert(row.username.clone(), line);
+            }
+        }
+        if !row.email.is_empty() {
+            let email = row.email.to_lowercase();
+            if let Some(first) = emails.get(&email) {
+                row.errors.push(format!(
+                    "Duplicate email '{}'; first used on line {}",
+                    row.email, first
+                ));
+            } else {
+                emails.insert(email, line);
+            }
+        }
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// Positions of the known columns in the header
+struct ImportColumns {
+    width: usize,
+    username: usize,
+    email: usize,
+    roles: Option<usize>,
+    enabled: Option<usize>,
+    external_id: Option<usize>,
+}
+
+impl ImportColumns {
+    fn from_header(header: &[String]) -> Result<Self, DomainError> {
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for (index, name) in header.iter().enumerate() {
+            let name = name.as_str();
+            let known = REQUIRED_IMPORT_COLUMNS
+                .iter()
+                .chain(OPTIONAL_IMPORT_COLUMNS.iter())
+                .find(|column| column.eq_ignore_ascii_case(name));
+            let Some(column) = known else {
+                return Err(invalid(
+                    Message::new("validation.user_import_unknown_column").with("column", name),
+                ));
+            };
+            if positions.insert(column, index).is_some() {
+                return Err(invalid(
+                    Message::new("validation.user_import_duplicate_column").with("column", *column),
+                ));
+            }
+        }
+
+        let required = |column: &str| {
+            positions.get(column).copied().ok_or_else(|| {
+                invalid(
+                    Message::new("validation.user_import_missing_column").with("column", column),
+                )
+            })
+        };
+        Ok(Self {
+            width: header.len(),
+            username: required("username")?,
+            email: required("email")?,
+            roles: positions.get("roles").copied(),
+            enabled: positions.get("enabled").copied(),
+            external_id: positions.get("external_id").copied(),
+        })
+    }
+
+    fn row(&self, line: usize, fields: &[String]) -> UserImportRow {
+        let field = |index: usize| fields.get(index).map_or("", String::as_str);
+        let optional = |index: Option<usize>| index.map_or("", field);
+        let mut errors = Vec::new();
+        if fields.len() != self.width {
+            errors.push(format!(
+                "Expected {} fields, found {}",
+                self.width,
+                fields.len()
+            ));
+        }
+
+        let username = field(self.username).to_string();
+        if username.is_empty() {
+            errors.push("Username is required".to_string());
+        } else if username.len() > MAX_FIELD_LENGTH || username.contains(char::is_whitespace) {
+            errors.push(format!("Invalid username '{}'", username));
+        }
+
+        let email = field(self.email).to_string();
+        if email.is_empty() {
+            errors.push("Email is required".to_string());
+        } else if email.len() > MAX_FIELD_LENGTH || !is_valid_email(&email) {
+            errors.push(format!("Malformed email '{}'", email));
+        }
+
+        let mut roles = Vec::new();
+        for name in optional(self.roles)
+            .split(ROLE_SEPARATOR)
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            match name.parse::<Role>() {
+                Ok(role) if !roles.contains(&role) => roles.push(role),
+                Ok(_) => {}
+                Err(_) => errors.push(format!("Unknown role '{}'", name)),
+            }
+        }
+        if roles.is_empty() {
+            roles.push(Role::User);
+        }
+
+        let enabled = match optional(self.enabled).to_ascii_lowercase().as_str() {
+            "" | "true" | "yes" | "1" => true,
+            "false" | "no" | "0" => false,
+            other => {
+                errors.push(format!("Invalid enabled flag '{}'", other));
+                true
+            }
+        };
+
+        let external_id = Some(optional(self.external_id))
+            .filter(|id| !id.is_empty())
+            .map(String::from);
+
+        UserImportRow {
+            line,
+            username,
+            email,
+            roles,
+            enabled,
+            external_id,
+            errors,
+        }
+    }
+}
+
+/// Returns true if `email` has a local part and a dotted domain
+fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !email.contains(char::is_whitespace)
+        && !domain.contains('@')
+        && domain.contains('.')
+        && domain.split('.').all(|label| !label.is_empty())
+}
+
+fn invalid(message: Message) -> DomainError {
+    DomainError::ValidationError {
+        field: "file".to_string(),
+        message,
+    }
+}
+
+/// Splits CSV text into trimmed records, each with the line it starts on
+///
+/// Quoted fields may contain commas, doubled quotes, and newlines. Blank
+/// lines are skipped.
+fn csv_records(input: &str) -> Result<Vec<(usize, Vec<String>)>, DomainError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut start_line = 1;
+
+    let mut chars = input.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(take_field(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(take_field(&mut field));
+                push_record(&mut records, start_line, std::mem::take(&mut record));
+                line += 1;
+                start_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(invalid(
+            Message::new("validation.user_import_unterminated_quote").with("line", start_line),
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(take_field(&mut field));
+        push_record(&mut records, start_line, record);
+    }
+    Ok(records)
+}
+
+fn take_field(field: &mut String) -> String {
+    let value = field.trim().to_string();
+    field.clear();
+    value
+}
+
+fn push_record(records: &mut Vec<(usize, Vec<String>)>, line: usize, record: Vec<String>) {
+    if record.iter().any(|field| !field.is_empty()) {
+        records.push((line, record));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_rows() {
+        let rows = parse_user_import(
+            "username,email,roles,enabled,external_id\n\
+             alice,alice@example.com,admin;event_viewer,true,\n\
+             bob,bob@example.com,,no,kc-123\n",
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(UserImportRow::is_valid));
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].roles, vec![Role::Admin, Role::EventViewer]);
+        assert!(rows[0].enabled);
+        assert!(!rows[0].is_oidc());
+        assert_eq!(rows[1].roles, vec![Role::User]);
+        assert!(!rows[1].enabled);
+        assert_eq!(rows[1].external_id.as_deref(), Some("kc-123"));
+    }
+
+    #[test]
+    fn test_parse_reports_errors_per_row() {
+        let rows = parse_user_import(
+            "email,username,roles\n\
+             alice@example.com,alice,superuser\n\
+             not-an-email,bob,user\n\
+             ALICE@example.com,carol,\n\
+             dave@example.com,alice,user\n\
+             erin@example.com,erin,user\n",
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(rows[0].errors, vec!["Unknown role 'superuser'"]);
+        assert_eq!(rows[1].errors, vec!["Malformed email 'not-an-email'"]);
+        assert_eq!(
+            rows[2].errors,
+            vec!["Duplicate email 'ALICE@example.com'; first used on line 2"]
+        );
+        assert_eq!(
+            rows[3].errors,
+            vec!["Duplicate username 'alice'; first used on line 2"]
+        );
+        assert!(rows[4].is_valid());
+    }
+
+    #[test]
+    fn test_parse_reports_missing_and_extra_fields() {
+        let rows =
+            parse_user_import("username,email,enabled\n,x@example.com,maybe,extra\n", 100).unwrap();
+
+        assert_eq!(
+            rows[0].errors,
+            vec![
+                "Expected 3 fields, found 4",
+                "Username is required",
+                "Invalid enabled flag 'maybe'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let rows = parse_user_import(
+            "\u{feff}username,email,roles\r\n\"alice\",\"alice@example.com\",\"admin; user\"\r\n\r\n",
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].username, "alice");
+        assert_eq!(rows[0].roles, vec![Role::Admin, Role::User]);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_files() {
+        for input in [
+            "",
+            "username\nalice\n",
+            "username,email,password\n",
+            "username,email,email\n",
+            "username,email\n\"alice,alice@example.com\n",
+        ] {
+            assert!(
+                matches!(
+                    parse_user_import(input, 100),
+                    Err(DomainError::ValidationError { .. })
+                ),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_enforces_row_limit() {
+        let input = "username,email\na,a@example.com\nb,b@example.com\n";
+        assert!(parse_user_import(input, 2).is_ok());
+        assert!(parse_user_import(input, 1).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/user_invitation.rs
+
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// Prefix of invitation tokens, so a leaked one is recognizable
+pub const INVITATION_TOKEN_PREFIX: &str = "xzepr_inv_";
+
+/// How long an invitation can be accepted
+pub const DEFAULT_INVITATION_TTL_HOURS: i64 = 72;
+
+/// Fewest characters a password set through an invitation may have
+pub const MIN_PASSWORD_LENGTH: usize = 12;
+
+/// Invitation letting a user choose the password of a local account
+///
+/// Only a SHA-256 hash of the token is kept. An invitation is deleted
+/// when accepted, so its token works once, and it expires. A user has at
+/// most one invitation; issuing another replaces it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserInvitation {
+    pub user_id: UserId,
+    /// Hex SHA-256 of the token, see [`hash_invitation_token`]
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    /// Who issued the invitation
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl UserInvitation {
+    /// Issues an invitation for `user_id` valid for `ttl`
+    ///
+    /// Returns the invitation to store and the token to hand to the user,
+    /// which is not kept anywhere.
+    pub fn issue(user_id: UserId, created_by: &str, ttl: Duration) -> (Self, String) {
+        let bytes: [u8; 32] = rand::thread_rng().gen();
+        let token = format!("{}{}", INVITATION_TOKEN_PREFIX, hex::encode(bytes));
+        let now = Utc::now();
+        let invitation = Self {
+            user_id,
+            token_hash: hash_invitation_token(&token),
+            expires_at: now + ttl,
+            created_by: created_by.to_string(),
+            created_at: now,
+        };
+        (invitation, token)
+    }
+
+    /// Returns true if the invitation can no longer be accepted at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Returns the hash an invitation token is stored and looked up by
+pub fn hash_invitation_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_keeps_only_the_token_hash() {
+        let user_id = UserId::new();
+        let (invitation, token) = UserInvitation::issue(user_id, "admin-1", Duration::hours(1));
+
+        assert!(token.starts_with(INVITATION_TOKEN_PREFIX));
+        assert_eq!(invitation.token_hash, hash_invitation_token(&token));
+        assert_ne!(invitation.token_hash, token);
+        assert_eq!(invitation.user_id, user_id);
+
+        let (other, other_token) = UserInvitation::issue(user_id, "admin-1", Duration::hours(1));
+        assert_ne!(token, other_token);
+        assert_ne!(invitation.token_hash, other.token_hash);
+    }
+
+    #[test]
+    fn test_expiry() {
+        let (invitation, _) = UserInvitation::issue(UserId::new(), "admin-1", Duration::hours(1));
+        assert!(!invitation.is_expired(invitation.created_at));
+        assert!(invitation.is_expired(invitation.expires_at));
+        assert!(invitation.is_expired(invitation.created_at + Duration::hours(2)));
+    }
+}
@@ -0,0 +1,387 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/entities/user_preferences.rs
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+use chrono::{Duration, FixedOffset};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Largest page size a user may choose as their default
+pub const MAX_DEFAULT_PAGE_SIZE: u64 = 1000;
+
+/// Longest default time range a user may choose (one year)
+pub const MAX_DEFAULT_TIME_RANGE_DAYS: i64 = 366;
+
+/// Maximum number of notification opt-outs stored per user
+pub const MAX_NOTIFICATION_OPT_OUTS: usize = 64;
+
+/// Preference keys users are allowed to store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PreferenceKey {
+    /// Page size used when a list request omits `limit`
+    DefaultPageSize,
+    /// Lookback window used when an event query omits its start time
+    DefaultTimeRange,
+    /// UTC offset used to format timestamps in exports
+    ExportTimezone,
+    /// Notification kinds the user does not want to receive
+    NotificationOptOuts,
+}
+
+impl PreferenceKey {
+    /// All known preference keys
+    pub const ALL: [PreferenceKey; 4] = [
+        PreferenceKey::DefaultPageSize,
+        PreferenceKey::DefaultTimeRange,
+        PreferenceKey::ExportTimezone,
+        PreferenceKey::NotificationOptOuts,
+    ];
+
+    /// Returns the storage representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PreferenceKey::DefaultPageSize => "default_page_size",
+            PreferenceKey::DefaultTimeRange => "default_time_range",
+            PreferenceKey::ExportTimezone => "export_timezone",
+            PreferenceKey::NotificationOptOuts => "notification_opt_outs",
+        }
+    }
+
+    /// Validates a value for this key
+    pub fn validate(&self, value: &JsonValue) -> Result<(), DomainError> {
+        let invalid = |message: Message| DomainError::ValidationError {
+            field: self.as_str().to_string(),
+            message,
+        };
+
+        match self {
+            PreferenceKey::DefaultPageSize => match value.as_u64() {
+                Some(size) if (1..=MAX_DEFAULT_PAGE_SIZE).contains(&size) => Ok(()),
+                _ => Err(invalid(
+                    Message::new("validation.preference_page_size")
+                        .with("max", MAX_DEFAULT_PAGE_SIZE),
+                )),
+            },
+            PreferenceKey::DefaultTimeRange => match value.as_str().and_then(parse_time_range) {
+                Some(_) => Ok(()),
+                None => Err(invalid(Message::new("validation.preference_time_range"))),
+            },
+            PreferenceKey::ExportTimezone => match value.as_str().and_then(parse_utc_offset) {
+                Some(_) => Ok(()),
+                None => Err(invalid(Message::new("validation.timezone"))),
+            },
+            PreferenceKey::NotificationOptOuts => {
+                let items = value
+                    .as_array()
+                    .ok_or_else(|| invalid(Message::new("validation.preference_string_list")))?;
+                if items.len() > MAX_NOTIFICATION_OPT_OUTS {
+                    return Err(invalid(Message::new(
+                        "validation.preference_too_many_opt_outs",
+                    )));
+                }
+                if items
+                    .iter()
+                    .all(|item| item.as_str().is_some_and(|s| !s.trim().is_empty()))
+                {
+                    Ok(())
+                } else {
+                    Err(invalid(Message::new(
+                        "validation.preference_non_empty_strings",
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for PreferenceKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PreferenceKey {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PreferenceKey::ALL
+            .into_iter()
+            .find(|key| key.as_str() == s)
+            .ok_or_else(|| DomainError::ValidationError {
+                field: s.to_string(),
+                message: Message::new("validation.preference_unknown").with("value", s),
+            })
+    }
+}
+
+/// Parses a time range such as `30m`, `24h`, `7d`, or `2w`
+///
+/// Returns None for malformed, zero, or overly long ranges.
+pub fn parse_time_range(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let unit = s.chars().last()?;
+    let amount: i64 = s[..s.len() - unit.len_utf8()].parse().ok()?;
+    if amount <= 0 {
+        return None;
+    }
+
+    let range = match unit {
+        'm' => Duration::try_minutes(amount)?,
+        'h' => Duration::try_hours(amount)?,
+        'd' => Duration::try_days(amount)?,
+        'w' => Duration::try_weeks(amount)?,
+        _ => return None,
+    };
+
+    if range > Duration::days(MAX_DEFAULT_TIME_RANGE_DAYS) {
+        return None;
+    }
+    Some(range)
+}
+
+/// Parses `UTC`, `Z`, or a `+HH:MM` / `-HH:MM` offset
+///
+/// Named zones such as `Europe/Berlin` are not supported because no time
+/// zone database is bundled.
+pub fn parse_utc_offset(s: &str) -> Option<FixedOffset> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("utc") || s == "Z" {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, rest) = match s.chars().next()? {
+        '+' => (1, &s[1..]),
+        '-' => (-1, &s[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = rest.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// A user's stored preferences
+///
+/// Only keys from [`PreferenceKey`] can be stored and every value is
+/// validated on the way in, so the typed accessors never fail.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserPreferences {
+    values: BTreeMap<PreferenceKey, JsonValue>,
+}
+
+impl UserPreferences {
+    /// Creates an empty set of preferences
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reconstructs preferences from stored key/value pairs
+    ///
+    /// Keys that are no longer known are skipped so that removing a key from
+    /// the allowlist does not break reads of older rows.
+    pub fn from_stored(rows: impl IntoIterator<Item = (String, JsonValue)>) -> Self {
+        let values = rows
+            .into_iter()
+            .filter_map(|(key, value)| key.parse::<PreferenceKey>().ok().map(|k| (k, value)))
+            .collect();
+        Self { values }
+    }
+
+    /// Merges a JSON document into these preferences
+    ///
+    /// Keys present in `document` replace the stored value; a `null` value
+    /// removes the key. Keys not mentioned are kept. The merge is applied
+    /// only if every key and value in the document is valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DomainError::ValidationError` for a non-object document, an
+    /// unknown key, or a value of the wrong shape.
+    pub fn merge(&mut self, document: &JsonValue) -> Result<(), DomainError> {
+        let document = document
+            .as_object()
+            .ok_or_else(|| DomainError::ValidationError {
+                field: "preferences".to_string(),
+                message: Message::new("validation.preferences_not_object"),
+            })?;
+
+        let mut changes = Vec::with_capacity(document.len());
+        for (key, value) in document {
+            let key: PreferenceKey = key.parse()?;
+            if !value.is_null() {
+                key.validate(value)?;
+            }
+            changes.push((key, value));
+        }
+
+        for (key, value) in changes {
+            if value.is_null() {
+                self.values.remove(&key);
+            } else {
+                self.values.insert(key, value.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the raw stored value for a key
+    pub fn get(&self, key: PreferenceKey) -> Option<&JsonValue> {
+        self.values.get(&key)
+    }
+
+    /// Iterates over stored key/value pairs
+    pub fn iter(&self) -> impl Iterator<Item = (PreferenceKey, &JsonValue)> {
+        self.values.iter().map(|(k, v)| (*k, v))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the full preference document with unset keys as `null`
+    pub fn to_document(&self) -> JsonValue {
+        let document: Map<String, JsonValue> = PreferenceKey::ALL
+            .iter()
+            .map(|key| {
+                (
+                    key.as_str().to_string(),
+                    self.values.get(key).cloned().unwrap_or(JsonValue::Null),
+                )
+            })
+            .collect();
+        JsonValue::Object(document)
+    }
+
+    /// Page size to use when a request omits `limit`
+    pub fn default_page_size(&self) -> Option<usize> {
+        self.get(PreferenceKey::DefaultPageSize)
+            .and_then(|v| v.as_u64())
+            .map(|size| size as usize)
+    }
+
+    /// Lookback window to use when an event query omits its start time
+    pub fn default_time_range(&self) -> Option<Duration> {
+        self.get(PreferenceKey::DefaultTimeRange)
+            .and_then(|v| v.as_str())
+            .and_then(parse_time_range)
+    }
+
+    /// UTC offset to use when formatting exported timestamps
+    pub fn export_timezone(&self) -> Option<FixedOffset> {
+        self.get(PreferenceKey::ExportTimezone)
+            .and_then(|v| v.as_str())
+            .and_then(parse_utc_offset)
+    }
+
+    /// Notification kinds the user opted out of
+    pub fn notification_opt_outs(&self) -> Vec<String> {
+        self.get(PreferenceKey::NotificationOptOuts)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unknown_key_rejected() {
+        let mut prefs = UserPreferences::new();
+        let err = prefs
+            .merge(&json!({"default_page_size": 25, "theme": "dark"}))
+            .unwrap_err();
+        assert!(err.to_string().contains("theme"));
+        // Nothing is applied when any key is invalid
+        assert!(prefs.is_empty());
+    }
+
+    #[test]
+    fn test_value_types_validated() {
+        let mut prefs = UserPreferences::new();
+        assert!(prefs.merge(&json!({"default_page_size": "25"})).is_err());
+        assert!(prefs.merge(&json!({"default_page_size": 0})).is_err());
+        assert!(prefs.merge(&json!({"default_page_size": 5000})).is_err());
+        assert!(prefs
+            .merge(&json!({"default_time_range": "forever"}))
+            .is_err());
+        assert!(prefs.merge(&json!({"default_time_range": "400d"})).is_err());
+        assert!(prefs
+            .merge(&json!({"export_timezone": "Mars/Olympus"}))
+            .is_err());
+        assert!(prefs.merge(&json!({"export_timezone": "+25:00"})).is_err());
+        assert!(prefs
+            .merge(&json!({"notification_opt_outs": ["ok", 3]}))
+            .is_err());
+        assert!(prefs.is_empty());
+
+        prefs
+            .merge(&json!({
+                "default_page_size": 25,
+                "default_time_range": "7d",
+                "export_timezone": "-05:00",
+                "notification_opt_outs": ["group.digest"]
+            }))
+            .unwrap();
+        assert_eq!(prefs.default_page_size(), Some(25));
+        assert_eq!(prefs.default_time_range(), Some(Duration::days(7)));
+        assert_eq!(prefs.export_timezone(), FixedOffset::west_opt(5 * 3600));
+        assert_eq!(prefs.notification_opt_outs(), vec!["group.digest"]);
+    }
+
+    #[test]
+    fn test_merge_and_remove() {
+        let mut prefs = UserPreferences::new();
+        prefs
+            .merge(&json!({"default_page_size": 25, "export_timezone": "UTC"}))
+            .unwrap();
+        prefs
+            .merge(&json!({"default_page_size": null, "default_time_range": "12h"}))
+            .unwrap();
+
+        let document = prefs.to_document();
+        assert_eq!(document["default_page_size"], JsonValue::Null);
+        assert_eq!(document["export_timezone"], "UTC");
+        assert_eq!(document["default_time_range"], "12h");
+        assert_eq!(document["notification_opt_outs"], JsonValue::Null);
+    }
+
+    #[test]
+    fn test_from_stored_skips_unknown_keys() {
+        let prefs = UserPreferences::from_stored(vec![
+            ("default_page_size".to_string(), json!(10)),
+            ("retired_key".to_string(), json!(true)),
+        ]);
+        assert_eq!(prefs.iter().count(), 1);
+        assert_eq!(prefs.default_page_size(), Some(10));
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(parse_utc_offset("utc"), FixedOffset::east_opt(0));
+        assert_eq!(parse_utc_offset("+05:30"), FixedOffset::east_opt(19800));
+        assert_eq!(parse_utc_offset("-08:00"), FixedOffset::west_opt(28800));
+        assert!(parse_utc_offset("0530").is_none());
+        assert!(parse_utc_offset("+5:30").is_none());
+    }
+}
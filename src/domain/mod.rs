@@ -3,6 +3,7 @@
 
 // Generated mod file
 
+pub mod canonical_json;
 pub mod entities;
 pub mod repositories;
 pub mod value_objects;
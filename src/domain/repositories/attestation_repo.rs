@@ -0,0 +1,128 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/attestation_repo.rs
+
+use crate::domain::entities::attestation::{AttestationFormat, EventAttestation};
+use crate::domain::value_objects::EventId;
+use crate::error::Result;
+use async_trait::async_trait;
+
+/// Repository for the fields extracted from attestation events
+///
+/// Extracted fields are stored next to the event and keyed by event id;
+/// the document itself stays in the event payload.
+#[async_trait]
+pub trait EventAttestationRepository: Send + Sync {
+    /// Saves the attestation extracted from an event
+    async fn save_attestation(&self, attestation: &EventAttestation) -> Result<()>;
+
+    /// Finds the attestation extracted from an event
+    async fn find_attestation(&self, event_id: EventId) -> Result<Option<EventAttestation>>;
+
+    /// Finds attestations matching a filter, newest first
+    async fn find_attestations(&self, filter: &AttestationFilter) -> Result<Vec<EventAttestation>>;
+}
+
+/// Filter for finding attestations by the artifacts they attest to
+#[derive(Debug, Clone, Default)]
+pub struct AttestationFilter {
+    /// Normalized `algorithm:hex` digest of an attested artifact
+    pub digest: Option<String>,
+    pub format: Option<AttestationFormat>,
+    pub builder_id: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl AttestationFilter {
+    /// Creates an empty filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the subject digest filter
+    pub fn with_digest(mut self, digest: String) -> Self {
+        self.digest = Some(digest);
+        self
+    }
+
+    /// Sets the attestation format filter
+    pub fn with_format(mut self, format: AttestationFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sets the builder ID filter
+    pub fn with_builder_id(mut self, builder_id: String) -> Self {
+        self.builder_id = Some(builder_id);
+        self
+    }
+
+    /// Sets pagination limit
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets pagination offset
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Returns true if `attestation` satisfies every set field
+    ///
+    /// Pagination is not considered. Useful for in-memory implementations.
+    pub fn matches(&self, attestation: &EventAttestation) -> bool {
+        let extracted = &attestation.attestation;
+        self.digest
+            .as_ref()
+            .is_none_or(|digest| extracted.subject_digests.contains(digest))
+            && self.format.is_none_or(|format| extracted.format == format)
+            && self
+                .builder_id
+                .as_deref()
+                .is_none_or(|id| extracted.builder_id.as_deref() == Some(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::attestation::Attestation;
+    use crate::domain::value_objects::EventReceiverId;
+
+    #[test]
+    fn test_filter_matches() {
+        let attestation = EventAttestation::new(
+            EventId::new(),
+            EventReceiverId::new(),
+            Attestation {
+                format: AttestationFormat::SlsaProvenance,
+                spec_version: "v1".to_string(),
+                subject_digests: vec!["sha256:abcd".to_string()],
+                builder_id: Some("https://builder.example".to_string()),
+                component_count: None,
+                component_name: None,
+                component_version: None,
+            },
+        );
+
+        assert!(AttestationFilter::new().matches(&attestation));
+        assert!(AttestationFilter::new()
+            .with_digest("sha256:abcd".to_string())
+            .with_format(AttestationFormat::SlsaProvenance)
+            .with_builder_id("https://builder.example".to_string())
+            .matches(&attestation));
+        assert!(!AttestationFilter::new()
+            .with_digest("sha256:ef01".to_string())
+            .matches(&attestation));
+        assert!(!AttestationFilter::new()
+            .with_format(AttestationFormat::CycloneDx)
+            .matches(&attestation));
+        assert!(!AttestationFilter::new()
+            .with_builder_id("https://other.example".to_string())
+            .matches(&attestation));
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/audit_record_repo.rs
+
+use crate::domain::entities::audit_chain::{AuditRecord, ChainLink};
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Audit records removed by a retention pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PurgedAuditRecords {
+    pub records: usize,
+    /// Days whose remaining records now start at a new anchor
+    pub anchors: usize,
+}
+
+/// Append-only store of hash-chained audit records
+///
+/// Records are never updated. Appends to the same day are serialized so
+/// that concurrent writers extend one unbroken chain, and retention deletes
+/// the oldest records of a day only together with an anchor holding the
+/// hash of the last one deleted.
+#[async_trait]
+pub trait AuditRecordRepository: Send + Sync {
+    /// Appends `content` to the chain of the UTC day of `recorded_at`
+    async fn append(&self, recorded_at: DateTime<Utc>, content: String) -> Result<AuditRecord>;
+
+    /// Returns up to `limit` records of the days `from` through `to`, in
+    /// day and position order, starting after the `(day, position)` cursor
+    async fn find_records(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        after: Option<(NaiveDate, i64)>,
+        limit: usize,
+    ) -> Result<Vec<AuditRecord>>;
+
+    /// Returns the link at `position` of a day's chain, from the stored
+    /// record or, once that was purged, its anchor
+    async fn find_link(&self, chain_day: NaiveDate, position: i64) -> Result<Option<ChainLink>>;
+
+    /// Deletes up to `limit` of the oldest records of the days before
+    /// `before`, anchoring each affected day at its last deleted record
+    async fn purge_before(&self, before: NaiveDate, limit: usize) -> Result<PurgedAuditRecords>;
+}
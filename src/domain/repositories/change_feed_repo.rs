@@ -0,0 +1,320 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/change_feed_repo.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// Position in a change feed
+///
+/// Changes are ordered by `(changed_at, id)` so that rows sharing the same
+/// timestamp still have a stable, total order. A client resumes polling by
+/// passing back the cursor returned with the previous page; only changes
+/// strictly after the cursor are returned.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ChangeCursor {
+    changed_at: DateTime<Utc>,
+    id: String,
+}
+
+impl ChangeCursor {
+    /// Creates a cursor positioned immediately after the given change
+    pub fn new(changed_at: DateTime<Utc>, id: impl Into<String>) -> Self {
+        Self {
+            changed_at,
+            id: id.into(),
+        }
+    }
+
+    /// Creates a cursor positioned before every change recorded at or
+    /// after the given timestamp
+    ///
+    /// A bare timestamp is treated as inclusive so a client that only knows
+    /// the time of its last sync may see a change twice but never misses one.
+    pub fn at(changed_at: DateTime<Utc>) -> Self {
+        Self::new(changed_at, String::new())
+    }
+
+    /// Creates a cursor positioned before the first change
+    pub fn beginning() -> Self {
+        Self::at(DateTime::<Utc>::UNIX_EPOCH)
+    }
+
+    /// Returns the timestamp component of the cursor
+    pub fn changed_at(&self) -> DateTime<Utc> {
+        self.changed_at
+    }
+
+    /// Returns the id component of the cursor
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Parses a `since` value supplied by a client
+    ///
+    /// Accepts either an RFC 3339 timestamp or an opaque cursor previously
+    /// returned as `next_since`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xzepr::domain::repositories::change_feed_repo::ChangeCursor;
+    ///
+    /// let from_time = ChangeCursor::parse("2025-01-15T10:00:00Z").unwrap();
+    /// assert_eq!(from_time.id(), "");
+    ///
+    /// let cursor = ChangeCursor::parse(&from_time.to_string()).unwrap();
+    /// assert_eq!(cursor, from_time);
+    ///
+    /// assert!(ChangeCursor::parse("not-a-cursor").is_err());
+    /// ```
+    pub fn parse(value: &str) -> std::result::Result<Self, DomainError> {
+        if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+            return Ok(Self::at(timestamp.with_timezone(&Utc)));
+        }
+
+        let invalid = || DomainError::ValidationError {
+            field: "since".to_string(),
+            message: Message::new("validation.since"),
+        };
+
+        let (nanos, id) = value.split_once('_').ok_or_else(invalid)?;
+        let nanos: i64 = nanos.parse().map_err(|_| invalid())?;
+
+        Ok(Self::new(DateTime::from_timestamp_nanos(nanos), id))
+    }
+}
+
+impl fmt::Display for ChangeCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Nanosecond precision keeps the encoding lossless for every
+        // timestamp chrono can hold within the i64 range.
+        let nanos = self.changed_at.timestamp_nanos_opt().unwrap_or(i64::MAX);
+        write!(f, "{}_{}", nanos, self.id)
+    }
+}
+
+/// A single change observed in a change feed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<T, I> {
+    /// The entity was created or updated
+    Upserted(T),
+    /// The entity was deleted
+    Deleted(I),
+}
+
+/// A bounded page of changes following a cursor
+#[derive(Debug, Clone)]
+pub struct ChangeSet<T, I> {
+    /// Entities created or updated after the cursor, in change order
+    pub upserts: Vec<T>,
+    /// Ids of entities deleted after the cursor, in change order
+    pub deleted: Vec<I>,
+    /// Cursor to pass on the next poll
+    pub next_since: ChangeCursor,
+    /// Whether more changes are available beyond this page
+    pub has_more: bool,
+}
+
+impl<T, I> ChangeSet<T, I> {
+    /// Builds a page from candidate changes
+    ///
+    /// Candidates at or before `since` are discarded, the rest are ordered
+    /// by cursor and truncated to `limit`. Storage backends should supply at
+    /// least `limit + 1` candidates per source so `has_more` is accurate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use xzepr::domain::repositories::change_feed_repo::{Change, ChangeCursor, ChangeSet};
+    ///
+    /// let now = Utc::now();
+    /// let changes = vec![
+    ///     (ChangeCursor::new(now, "b"), Change::Deleted("b")),
+    ///     (ChangeCursor::new(now, "a"), Change::Upserted("a")),
+    /// ];
+    ///
+    /// let page: ChangeSet<&str, &str> =
+    ///     ChangeSet::from_changes(&ChangeCursor::beginning(), 1, changes);
+    /// assert_eq!(page.upserts, vec!["a"]);
+    /// assert!(page.deleted.is_empty());
+    /// assert!(page.has_more);
+    /// assert_eq!(page.next_since, ChangeCursor::new(now, "a"));
+    /// ```
+    pub fn from_changes(
+        since: &ChangeCursor,
+        limit: usize,
+        changes: impl IntoIterator<Item = (ChangeCursor, Change<T, I>)>,
+    ) -> Self {
+        let mut pending: Vec<(ChangeCursor, Change<T, I>)> = changes
+            .into_iter()
+            .filter(|(cursor, _)| cursor > since)
+            .collect();
+        pending.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let has_more = pending.len() > limit;
+        pending.truncate(limit);
+
+        let next_since = pending
+            .last()
+            .map(|(cursor, _)| cursor.clone())
+            .unwrap_or_else(|| since.clone());
+
+        let mut upserts = Vec::new();
+        let mut deleted = Vec::new();
+        for (_, change) in pending {
+            match change {
+                Change::Upserted(entity) => upserts.push(entity),
+                Change::Deleted(id) => deleted.push(id),
+            }
+        }
+
+        Self {
+            upserts,
+            deleted,
+            next_since,
+            has_more,
+        }
+    }
+}
+
+/// Change feed over event receivers
+#[async_trait]
+pub trait EventReceiverChangeFeedRepository: Send + Sync {
+    /// Returns up to `limit` receiver changes strictly after `since`
+    async fn receiver_changes_since(
+        &self,
+        since: &ChangeCursor,
+        limit: usize,
+    ) -> Result<ChangeSet<EventReceiver, EventReceiverId>>;
+}
+
+/// Change feed over event receiver groups
+#[async_trait]
+pub trait EventReceiverGroupChangeFeedRepository: Send + Sync {
+    /// Returns up to `limit` group changes strictly after `since`
+    async fn group_changes_since(
+        &self,
+        since: &ChangeCursor,
+        limit: usize,
+    ) -> Result<ChangeSet<EventReceiverGroup, EventReceiverGroupId>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    type Log = Vec<(ChangeCursor, Change<String, String>)>;
+
+    /// Replays a change log the way a storage backend would: the latest
+    /// state per id wins, deletions replace upserts.
+    fn snapshot(log: &Log) -> Log {
+        let mut latest: std::collections::HashMap<String, (ChangeCursor, Change<String, String>)> =
+            std::collections::HashMap::new();
+        for (cursor, change) in log {
+            latest.insert(cursor.id().to_string(), (cursor.clone(), change.clone()));
+        }
+        latest.into_values().collect()
+    }
+
+    fn poll(log: &Log, since: &ChangeCursor, limit: usize) -> ChangeSet<String, String> {
+        ChangeSet::from_changes(since, limit, snapshot(log))
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = ChangeCursor::new(Utc::now(), "01JABCDEF");
+        let parsed = ChangeCursor::parse(&cursor.to_string()).unwrap();
+        assert_eq!(parsed, cursor);
+    }
+
+    #[test]
+    fn test_cursor_parse_timestamp_is_inclusive() {
+        let cursor = ChangeCursor::parse("2025-01-15T10:00:00Z").unwrap();
+        let at_same_time = ChangeCursor::new(cursor.changed_at(), "A");
+        assert!(at_same_time > cursor);
+    }
+
+    #[test]
+    fn test_cursor_parse_rejects_garbage() {
+        assert!(ChangeCursor::parse("").is_err());
+        assert!(ChangeCursor::parse("abc_def").is_err());
+        assert!(ChangeCursor::parse("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_empty_page_keeps_cursor() {
+        let since = ChangeCursor::new(Utc::now(), "X");
+        let page: ChangeSet<String, String> = ChangeSet::from_changes(&since, 10, Vec::new());
+        assert!(page.upserts.is_empty());
+        assert!(page.deleted.is_empty());
+        assert!(!page.has_more);
+        assert_eq!(page.next_since, since);
+    }
+
+    #[test]
+    fn test_create_update_delete_across_polls() {
+        let t0 = Utc::now();
+        let mut log: Log = vec![
+            (ChangeCursor::new(t0, "a"), Change::Upserted("a-v1".into())),
+            (ChangeCursor::new(t0, "b"), Change::Upserted("b-v1".into())),
+        ];
+
+        let first = poll(&log, &ChangeCursor::beginning(), 10);
+        assert_eq!(first.upserts, vec!["a-v1", "b-v1"]);
+        assert!(first.deleted.is_empty());
+        assert!(!first.has_more);
+
+        let t1 = t0 + Duration::seconds(1);
+        log.push((ChangeCursor::new(t1, "a"), Change::Upserted("a-v2".into())));
+        log.push((ChangeCursor::new(t1, "b"), Change::Deleted("b".into())));
+
+        let second = poll(&log, &first.next_since, 10);
+        assert_eq!(second.upserts, vec!["a-v2"]);
+        assert_eq!(second.deleted, vec!["b"]);
+
+        let third = poll(&log, &second.next_since, 10);
+        assert!(third.upserts.is_empty());
+        assert!(third.deleted.is_empty());
+        assert_eq!(third.next_since, second.next_since);
+    }
+
+    #[test]
+    fn test_equal_timestamps_paged_without_gaps_or_duplicates() {
+        let t0 = Utc::now();
+        let mut log: Log = Vec::new();
+        for i in 0..7 {
+            let id = format!("r{}", i);
+            log.push((ChangeCursor::new(t0, &id), Change::Upserted(id.clone())));
+        }
+        log.push((ChangeCursor::new(t0, "r9"), Change::Deleted("r9".into())));
+
+        let mut since = ChangeCursor::beginning();
+        let mut seen = Vec::new();
+        let mut deleted = Vec::new();
+        let mut polls = 0;
+        loop {
+            let page = poll(&log, &since, 3);
+            polls += 1;
+            seen.extend(page.upserts);
+            deleted.extend(page.deleted);
+            since = page.next_since;
+            if !page.has_more {
+                break;
+            }
+        }
+
+        assert_eq!(polls, 3);
+        assert_eq!(seen, vec!["r0", "r1", "r2", "r3", "r4", "r5", "r6"]);
+        assert_eq!(deleted, vec!["r9"]);
+    }
+}
@@ -0,0 +1,34 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/delivery_failure_repo.rs
+
+use crate::domain::entities::delivery_failure::{DeliveryErrorClass, DeliveryFailure};
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use async_trait::async_trait;
+
+/// Filters of the delivery failure listing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FindDeliveryFailureCriteria {
+    pub receiver_id: Option<EventReceiverId>,
+    pub error_class: Option<DeliveryErrorClass>,
+    /// Most failures returned
+    pub limit: usize,
+}
+
+/// Repository of deliveries given up on, across every mechanism
+#[async_trait]
+pub trait DeliveryFailureRepository: Send + Sync {
+    /// Records a failure
+    ///
+    /// Returns true only for the first record of a mechanism, event, and
+    /// target, so a failure reported twice is announced once.
+    async fn record(&self, failure: &DeliveryFailure) -> Result<bool>;
+
+    /// Returns the latest failures matching `criteria`, newest first
+    async fn find_recent(
+        &self,
+        criteria: &FindDeliveryFailureCriteria,
+    ) -> Result<Vec<DeliveryFailure>>;
+}
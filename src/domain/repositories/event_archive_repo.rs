@@ -0,0 +1,65 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/event_archive_repo.rs
+
+use crate::domain::entities::event::Event;
+use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Identifies the events that share an archive segment
+///
+/// A segment holds events of one receiver created on one (UTC) day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArchiveSegmentKey {
+    pub date: NaiveDate,
+    pub event_receiver_id: EventReceiverId,
+}
+
+impl ArchiveSegmentKey {
+    /// Returns the segment key an event belongs to
+    pub fn for_event(event: &Event) -> Self {
+        Self {
+            date: event.created_at().date_naive(),
+            event_receiver_id: event.event_receiver_id(),
+        }
+    }
+}
+
+/// Cold storage for events removed from the primary database
+///
+/// Segments are immutable: every `put_batch` call writes a new segment and
+/// returns its location, which callers record in the archive index.
+#[async_trait]
+pub trait ArchiveStore: Send + Sync {
+    /// Writes a batch of events as a new segment and returns its location
+    async fn put_batch(&self, key: &ArchiveSegmentKey, events: &[Event]) -> Result<String>;
+
+    /// Reads an event from a segment
+    ///
+    /// Returns `None` if the segment no longer exists or does not contain
+    /// the event.
+    async fn get_by_id(&self, segment: &str, id: EventId) -> Result<Option<Event>>;
+}
+
+/// Where an archived event was written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveIndexEntry {
+    pub event_id: EventId,
+    pub event_receiver_id: EventReceiverId,
+    pub segment: String,
+    pub event_created_at: DateTime<Utc>,
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Repository mapping archived event ids to archive segments
+#[async_trait]
+pub trait EventArchiveIndexRepository: Send + Sync {
+    /// Records segment locations; re-archiving an event replaces its entry
+    async fn record_archived(&self, entries: &[ArchiveIndexEntry]) -> Result<()>;
+
+    /// Finds the archive entry for an event
+    async fn find_archived(&self, event_id: EventId) -> Result<Option<ArchiveIndexEntry>>;
+}
@@ -0,0 +1,82 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/event_attachment_repo.rs
+
+use crate::domain::entities::event_attachment::EventAttachment;
+use crate::domain::value_objects::{AttachmentId, EventId, EventReceiverId};
+use crate::error::Result;
+use async_trait::async_trait;
+
+/// A sequence of byte chunks read one at a time
+///
+/// Uploads and downloads are passed through in chunks so that a large
+/// attachment is never held in memory as a whole.
+#[async_trait]
+pub trait ByteStream: Send {
+    /// Returns the next chunk, or `None` once the stream is exhausted
+    async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>>;
+}
+
+/// An inclusive range of byte offsets within a blob
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Returns the number of bytes in the range
+    pub fn length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// A blob being written
+///
+/// Nothing is readable under the key until `commit` returns, and `abort`
+/// discards everything written so far.
+#[async_trait]
+pub trait BlobWriter: Send {
+    async fn write(&mut self, chunk: &[u8]) -> Result<()>;
+
+    async fn commit(self: Box<Self>) -> Result<()>;
+
+    async fn abort(self: Box<Self>) -> Result<()>;
+}
+
+/// Storage for attachment content
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Starts writing the blob stored under `key`
+    async fn begin(&self, key: &str) -> Result<Box<dyn BlobWriter>>;
+
+    /// Reads `range` of a blob, or all of it when `range` is `None`
+    ///
+    /// Returns `None` if no blob is stored under `key`.
+    async fn open(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<Option<Box<dyn ByteStream>>>;
+
+    /// Deletes a blob; deleting a missing blob succeeds
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Repository for attachment metadata
+#[async_trait]
+pub trait EventAttachmentRepository: Send + Sync {
+    async fn save(&self, attachment: &EventAttachment) -> Result<()>;
+
+    async fn find_by_id(&self, id: AttachmentId) -> Result<Option<EventAttachment>>;
+
+    /// Returns the attachments of an event, oldest first
+    async fn find_by_event(&self, event_id: EventId) -> Result<Vec<EventAttachment>>;
+
+    /// Deletes an attachment record; returns false if it did not exist
+    async fn delete(&self, id: AttachmentId) -> Result<bool>;
+
+    /// Returns the total size of the attachments of a receiver's events
+    async fn total_size_by_receiver(&self, receiver_id: EventReceiverId) -> Result<u64>;
+}
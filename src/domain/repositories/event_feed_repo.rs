@@ -0,0 +1,27 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/event_feed_repo.rs
+
+use crate::domain::entities::event::Event;
+use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::error::Result;
+use async_trait::async_trait;
+
+/// Repository for reading new events in order, as live consumers do
+///
+/// Event ids are ULIDs, so id order is creation order and the id of the
+/// last event a consumer saw is a cursor it can resume from.
+#[async_trait]
+pub trait EventFeedRepository: Send + Sync {
+    /// Returns up to `limit` user events with an id after `after`, oldest
+    /// first
+    ///
+    /// `None` starts before the first event. System events are left out.
+    async fn find_events_after(
+        &self,
+        after: Option<EventId>,
+        receiver_id: Option<EventReceiverId>,
+        limit: usize,
+    ) -> Result<Vec<Event>>;
+}
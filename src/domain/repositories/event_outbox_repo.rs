@@ -0,0 +1,123 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/event_outbox_repo.rs
+
+use crate::domain::value_objects::EventId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Stored event waiting to be published to the stream
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEntry {
+    pub event_id: EventId,
+    /// Failed publish attempts so far, including the one at ingestion
+    pub attempts: u32,
+    pub last_error: String,
+    pub next_attempt_at: DateTime<Utc>,
+    /// Set once the entry gave up retrying and moved to the dead letters
+    pub dead_lettered_at: Option<DateTime<Utc>>,
+}
+
+/// Number of outbox entries by state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutboxBacklog {
+    /// Entries that will be retried
+    pub pending: u64,
+    /// Entries that exhausted their retries
+    pub dead_lettered: u64,
+}
+
+/// Repository of events whose publication was deferred
+///
+/// An event is in the outbox from its first failed publish until it is
+/// published; dead-lettered entries stay until an operator removes them.
+#[async_trait]
+pub trait EventOutboxRepository: Send + Sync {
+    /// Adds an event after its first failed publish
+    async fn enqueue(
+        &self,
+        event_id: EventId,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<()>;
+
+    /// Returns up to `limit` pending entries due at `now`, oldest first
+    async fn find_due(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<OutboxEntry>>;
+
+    /// Returns the entries of the given events that are still in the outbox
+    async fn find_entries(&self, event_ids: &[EventId]) -> Result<Vec<OutboxEntry>>;
+
+    /// Removes an event that was published
+    async fn mark_published(&self, event_id: EventId) -> Result<()>;
+
+    /// Records another failed attempt
+    ///
+    /// The entry is retried at `next_attempt_at`, or dead-lettered when it
+    /// is `None`.
+    async fn record_failure(
+        &self,
+        event_id: EventId,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<()>;
+
+    /// Counts pending and dead-lettered entries
+    async fn backlog(&self) -> Result<OutboxBacklog>;
+}
+
+/// Fan-out of a published event to a group topic waiting to be retried
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupTopicOutboxEntry {
+    pub event_id: EventId,
+    pub topic: String,
+    /// Failed publish attempts so far, including the first fan-out
+    pub attempts: u32,
+    pub last_error: String,
+    pub next_attempt_at: DateTime<Utc>,
+    /// Set once the entry gave up retrying and moved to the dead letters
+    pub dead_lettered_at: Option<DateTime<Utc>>,
+}
+
+/// Repository of group topic fan-outs whose publication failed
+///
+/// Entries are keyed by event and topic, so a failing group topic is
+/// retried on its own schedule without affecting the primary publish or
+/// other group topics.
+#[async_trait]
+pub trait GroupTopicOutboxRepository: Send + Sync {
+    /// Adds a fan-out after its first failed publish
+    async fn enqueue(
+        &self,
+        event_id: EventId,
+        topic: &str,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<()>;
+
+    /// Returns up to `limit` pending entries due at `now`, oldest first
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<GroupTopicOutboxEntry>>;
+
+    /// Removes a fan-out that was published or is no longer wanted
+    async fn mark_published(&self, event_id: EventId, topic: &str) -> Result<()>;
+
+    /// Records another failed attempt
+    ///
+    /// The entry is retried at `next_attempt_at`, or dead-lettered when it
+    /// is `None`.
+    async fn record_failure(
+        &self,
+        event_id: EventId,
+        topic: &str,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<()>;
+
+    /// Counts pending and dead-lettered entries
+    async fn backlog(&self) -> Result<OutboxBacklog>;
+}
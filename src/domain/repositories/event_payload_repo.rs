@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/event_payload_repo.rs
+
+use crate::error::Result;
+use async_trait::async_trait;
+
+/// Payloads removed from the shared payload store
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollectedPayloads {
+    pub payloads: usize,
+    pub bytes: u64,
+}
+
+/// Size of the shared payload store
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayloadStoreStats {
+    /// Number of stored payloads
+    pub payloads: u64,
+    /// Bytes held by stored payloads
+    pub stored_bytes: u64,
+    /// Bytes the referencing events would hold beyond `stored_bytes` if
+    /// each kept its own copy
+    pub saved_bytes: u64,
+}
+
+/// Repository of large event payloads stored once and shared by reference
+///
+/// Events reference stored payloads by content hash and every reference is
+/// counted. Saving and deleting events maintain the counts; payloads whose
+/// count drops to zero stay until collected.
+#[async_trait]
+pub trait EventPayloadRepository: Send + Sync {
+    /// Deletes up to `limit` payloads no event references
+    async fn collect_unreferenced(&self, limit: usize) -> Result<CollectedPayloads>;
+
+    /// Returns the size of the store and the bytes it saves
+    async fn payload_stats(&self) -> Result<PayloadStoreStats>;
+}
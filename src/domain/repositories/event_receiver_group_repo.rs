@@ -4,9 +4,14 @@
 // src/domain/repositories/event_receiver_group_repo.rs
 
 use crate::domain::entities::event_receiver_group::EventReceiverGroup;
-use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
-use crate::error::Result;
+use crate::domain::repositories::pagination::{GroupSortField, ListOrder};
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId, UserId, Version};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Repository trait for event receiver group persistence operations
 #[async_trait]
@@ -57,9 +62,32 @@ pub trait EventReceiverGroupRepository: Send + Sync {
     /// Updates an existing event receiver group
     async fn update(&self, group: &EventReceiverGroup) -> Result<()>;
 
+    /// Updates several groups together
+    ///
+    /// The default implementation updates them one at a time; repositories
+    /// backed by a database should update the batch in one transaction so
+    /// a failure leaves every group unchanged.
+    async fn update_many(&self, groups: &[EventReceiverGroup]) -> Result<()> {
+        for group in groups {
+            self.update(group).await?;
+        }
+        Ok(())
+    }
+
     /// Deletes an event receiver group by ID
     async fn delete(&self, id: EventReceiverGroupId) -> Result<()>;
 
+    /// Deletes several groups
+    ///
+    /// The default implementation deletes them one at a time; repositories
+    /// backed by a database should delete the batch in one transaction.
+    async fn delete_many(&self, ids: &[EventReceiverGroupId]) -> Result<()> {
+        for id in ids {
+            self.delete(*id).await?;
+        }
+        Ok(())
+    }
+
     /// Enables an event receiver group
     async fn enable(&self, id: EventReceiverGroupId) -> Result<()>;
 
@@ -75,6 +103,14 @@ pub trait EventReceiverGroupRepository: Send + Sync {
         criteria: FindEventReceiverGroupCriteria,
     ) -> Result<Vec<EventReceiverGroup>>;
 
+    /// Counts event receiver groups matching the criteria, ignoring pagination
+    ///
+    /// The default implementation loads every match; repositories backed by
+    /// a database should override it.
+    async fn count_by_criteria(&self, criteria: FindEventReceiverGroupCriteria) -> Result<usize> {
+        Ok(self.find_by_criteria(criteria.unpaged()).await?.len())
+    }
+
     /// Adds an event receiver to a group
     async fn add_event_receiver_to_group(
         &self,
@@ -95,6 +131,24 @@ pub trait EventReceiverGroupRepository: Send + Sync {
         group_id: EventReceiverGroupId,
     ) -> Result<Vec<EventReceiverId>>;
 
+    /// Finds the group a receiver takes its default schema from when the
+    /// defaults of its groups conflict
+    async fn find_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<EventReceiverGroupId>>;
+
+    /// Makes `group_id` the receiver's default schema group
+    ///
+    /// A receiver has at most one; the previous default is cleared in the
+    /// same transaction. Fails with `NotFound` when the receiver is not a
+    /// member of the group.
+    async fn set_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+        group_id: EventReceiverGroupId,
+    ) -> Result<()>;
+
     /// Finds all event receiver groups owned by a specific user
     async fn find_by_owner(
         &self,
@@ -119,27 +173,60 @@ pub trait EventReceiverGroupRepository: Send + Sync {
     /// Gets the current resource version of an event receiver group
     async fn get_resource_version(&self, group_id: EventReceiverGroupId) -> Result<Option<i64>>;
 
-    /// Checks if a user is a member of a specific group
+    /// Checks if a user is a current member of a specific group
+    ///
+    /// Expired memberships do not count.
     async fn is_member(
         &self,
         group_id: EventReceiverGroupId,
         user_id: crate::domain::value_objects::UserId,
     ) -> Result<bool>;
 
-    /// Gets all member user IDs for a specific group
+    /// Gets the user IDs of the current members of a specific group
     async fn get_group_members(
         &self,
         group_id: EventReceiverGroupId,
     ) -> Result<Vec<crate::domain::value_objects::UserId>>;
 
-    /// Adds a member to a group
+    /// Gets the current memberships of a group, oldest first
+    ///
+    /// Memberships added at the same instant are ordered by user ID.
+    /// Expired memberships are left out.
+    async fn get_group_memberships(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<GroupMembership>>;
+
+    /// Adds a member to a group, until `expires_at` if set
+    ///
+    /// An expired membership of the same user is replaced.
     async fn add_member(
         &self,
         group_id: EventReceiverGroupId,
         user_id: crate::domain::value_objects::UserId,
         added_by: crate::domain::value_objects::UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()>;
+
+    /// Sets when a current membership expires; `None` makes it permanent
+    ///
+    /// Fails with `NotFound` if the user is not a current member.
+    async fn set_member_expiry(
+        &self,
+        group_id: EventReceiverGroupId,
+        user_id: crate::domain::value_objects::UserId,
+        expires_at: Option<DateTime<Utc>>,
     ) -> Result<()>;
 
+    /// Deletes up to `limit` memberships that expired at or before `now`
+    ///
+    /// Returns the deleted memberships, earliest expiry first.
+    async fn remove_expired_members(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ExpiredMembership>>;
+
     /// Removes a member from a group
     async fn remove_member(
         &self,
@@ -147,13 +234,97 @@ pub trait EventReceiverGroupRepository: Send + Sync {
         user_id: crate::domain::value_objects::UserId,
     ) -> Result<()>;
 
-    /// Finds all groups that a user is a member of
+    /// Finds all groups that a user is a current member of
     async fn find_groups_for_user(
         &self,
         user_id: crate::domain::value_objects::UserId,
     ) -> Result<Vec<EventReceiverGroup>>;
 }
 
+/// A user's membership in a group
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupMembership {
+    pub user_id: UserId,
+    /// User who added the member
+    pub added_by: UserId,
+    pub added_at: DateTime<Utc>,
+    /// When the membership ends; `None` if it never does
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl GroupMembership {
+    /// Returns the cursor positioned at this membership
+    pub fn cursor(&self) -> MemberCursor {
+        MemberCursor {
+            added_at: self.added_at,
+            user_id: self.user_id.to_string(),
+        }
+    }
+
+    /// Returns true if the membership has ended at `now`
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// A membership deleted because it expired
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpiredMembership {
+    pub group_id: EventReceiverGroupId,
+    pub membership: GroupMembership,
+}
+
+/// Position in a group's member list
+///
+/// Members are ordered by `(added_at, user_id)`, so a cursor stays valid
+/// while members are added or removed; pages after it hold only members
+/// that sort strictly after it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemberCursor {
+    added_at: DateTime<Utc>,
+    user_id: String,
+}
+
+impl MemberCursor {
+    /// Parses a cursor previously returned to a client
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xzepr::domain::repositories::event_receiver_group_repo::MemberCursor;
+    ///
+    /// let cursor = MemberCursor::parse("1736935200000000000_01JD0A8K3V9ZP6Q2W4X7Y1T5RC").unwrap();
+    /// assert_eq!(
+    ///     cursor.to_string(),
+    ///     "1736935200000000000_01JD0A8K3V9ZP6Q2W4X7Y1T5RC"
+    /// );
+    ///
+    /// assert!(MemberCursor::parse("not-a-cursor").is_err());
+    /// ```
+    pub fn parse(value: &str) -> std::result::Result<Self, DomainError> {
+        let invalid = || DomainError::ValidationError {
+            field: "after".to_string(),
+            message: Message::new("validation.member_cursor"),
+        };
+
+        let (nanos, user_id) = value.split_once('_').ok_or_else(invalid)?;
+        let nanos: i64 = nanos.parse().map_err(|_| invalid())?;
+        let user_id: UserId = user_id.parse().map_err(|_| invalid())?;
+
+        Ok(Self {
+            added_at: DateTime::from_timestamp_nanos(nanos),
+            user_id: user_id.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for MemberCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.added_at.timestamp_nanos_opt().unwrap_or(i64::MAX);
+        write!(f, "{}_{}", nanos, self.user_id)
+    }
+}
+
 /// Criteria for finding event receiver groups
 #[derive(Debug, Clone, Default)]
 pub struct FindEventReceiverGroupCriteria {
@@ -163,6 +334,8 @@ pub struct FindEventReceiverGroupCriteria {
     pub version: Option<String>,
     pub enabled: Option<bool>,
     pub contains_receiver_id: Option<EventReceiverId>,
+    pub owner_id: Option<UserId>,
+    pub order: ListOrder<GroupSortField>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
@@ -191,9 +364,9 @@ impl FindEventReceiverGroupCriteria {
         self
     }
 
-    /// Sets the version filter
+    /// Sets the version filter, compared in canonical form
     pub fn with_version(mut self, version: String) -> Self {
-        self.version = Some(version);
+        self.version = Some(Version::normalize_filter(&version));
         self
     }
 
@@ -209,6 +382,18 @@ impl FindEventReceiverGroupCriteria {
         self
     }
 
+    /// Sets the owner filter
+    pub fn with_owner(mut self, owner_id: UserId) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
+    /// Sets the sort order
+    pub fn with_order(mut self, order: ListOrder<GroupSortField>) -> Self {
+        self.order = order;
+        self
+    }
+
     /// Sets pagination limit
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
@@ -229,6 +414,14 @@ impl FindEventReceiverGroupCriteria {
             && self.version.is_none()
             && self.enabled.is_none()
             && self.contains_receiver_id.is_none()
+            && self.owner_id.is_none()
+    }
+
+    /// Returns the same filters without limit and offset
+    pub fn unpaged(mut self) -> Self {
+        self.limit = None;
+        self.offset = None;
+        self
     }
 }
 
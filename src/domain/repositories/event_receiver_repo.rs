@@ -4,9 +4,11 @@
 // src/domain/repositories/event_receiver_repo.rs
 
 use crate::domain::entities::event_receiver::EventReceiver;
-use crate::domain::value_objects::EventReceiverId;
+use crate::domain::repositories::pagination::{ListOrder, ReceiverSortField};
+use crate::domain::value_objects::{EventReceiverId, UserId, Version};
 use crate::error::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 /// Repository trait for event receiver persistence operations
 #[async_trait]
@@ -33,6 +35,26 @@ pub trait EventReceiverRepository: Send + Sync {
     /// Finds event receivers by fingerprint (should be unique)
     async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>>;
 
+    /// Saves a new event receiver unless one with the same fingerprint exists
+    ///
+    /// Returns the stored receiver and whether this call created it. The
+    /// default implementation is not atomic; repositories shared between
+    /// processes must override it.
+    async fn save_if_absent(
+        &self,
+        event_receiver: &EventReceiver,
+    ) -> Result<(EventReceiver, bool)> {
+        if let Some(existing) = self
+            .find_by_fingerprint(event_receiver.fingerprint())
+            .await?
+        {
+            return Ok((existing, false));
+        }
+
+        self.save(event_receiver).await?;
+        Ok((event_receiver.clone(), true))
+    }
+
     /// Lists all event receivers with pagination
     async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiver>>;
 
@@ -45,6 +67,17 @@ pub trait EventReceiverRepository: Send + Sync {
     /// Deletes an event receiver by ID
     async fn delete(&self, id: EventReceiverId) -> Result<()>;
 
+    /// Deletes several event receivers
+    ///
+    /// The default implementation deletes them one at a time; repositories
+    /// backed by a database should delete the batch in one transaction.
+    async fn delete_many(&self, ids: &[EventReceiverId]) -> Result<()> {
+        for id in ids {
+            self.delete(*id).await?;
+        }
+        Ok(())
+    }
+
     /// Checks if an event receiver exists with the given name and type
     async fn exists_by_name_and_type(&self, name: &str, receiver_type: &str) -> Result<bool>;
 
@@ -54,6 +87,14 @@ pub trait EventReceiverRepository: Send + Sync {
         criteria: FindEventReceiverCriteria,
     ) -> Result<Vec<EventReceiver>>;
 
+    /// Counts event receivers matching the criteria, ignoring pagination
+    ///
+    /// The default implementation loads every match; repositories backed by
+    /// a database should override it.
+    async fn count_by_criteria(&self, criteria: FindEventReceiverCriteria) -> Result<usize> {
+        Ok(self.find_by_criteria(criteria.unpaged()).await?.len())
+    }
+
     /// Finds all event receivers owned by a specific user
     async fn find_by_owner(
         &self,
@@ -87,6 +128,10 @@ pub struct FindEventReceiverCriteria {
     pub receiver_type: Option<String>,
     pub version: Option<String>,
     pub fingerprint: Option<String>,
+    pub owner_id: Option<UserId>,
+    /// Only receivers with no events or heartbeats since this time
+    pub stale_since: Option<DateTime<Utc>>,
+    pub order: ListOrder<ReceiverSortField>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
@@ -115,9 +160,9 @@ impl FindEventReceiverCriteria {
         self
     }
 
-    /// Sets the version filter
+    /// Sets the version filter, compared in canonical form
     pub fn with_version(mut self, version: String) -> Self {
-        self.version = Some(version);
+        self.version = Some(Version::normalize_filter(&version));
         self
     }
 
@@ -127,6 +172,24 @@ impl FindEventReceiverCriteria {
         self
     }
 
+    /// Sets the owner filter
+    pub fn with_owner(mut self, owner_id: UserId) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
+    /// Keeps only receivers with no events or heartbeats since `since`
+    pub fn with_stale_since(mut self, since: DateTime<Utc>) -> Self {
+        self.stale_since = Some(since);
+        self
+    }
+
+    /// Sets the sort order
+    pub fn with_order(mut self, order: ListOrder<ReceiverSortField>) -> Self {
+        self.order = order;
+        self
+    }
+
     /// Sets pagination limit
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
@@ -146,6 +209,15 @@ impl FindEventReceiverCriteria {
             && self.receiver_type.is_none()
             && self.version.is_none()
             && self.fingerprint.is_none()
+            && self.owner_id.is_none()
+            && self.stale_since.is_none()
+    }
+
+    /// Returns the same filters without limit and offset
+    pub fn unpaged(mut self) -> Self {
+        self.limit = None;
+        self.offset = None;
+        self
     }
 }
 
@@ -172,5 +244,26 @@ mod tests {
     fn test_empty_criteria() {
         let criteria = FindEventReceiverCriteria::new();
         assert!(criteria.is_empty());
+        assert_eq!(
+            criteria.order,
+            ListOrder::desc(ReceiverSortField::CreatedAt)
+        );
+    }
+
+    #[test]
+    fn test_owner_filter_and_unpaged() {
+        let owner_id = UserId::new();
+        let criteria = FindEventReceiverCriteria::new()
+            .with_owner(owner_id)
+            .with_order(ListOrder::asc(ReceiverSortField::Name))
+            .with_limit(10)
+            .with_offset(20)
+            .unpaged();
+
+        assert!(!criteria.is_empty());
+        assert_eq!(criteria.owner_id, Some(owner_id));
+        assert_eq!(criteria.order, ListOrder::asc(ReceiverSortField::Name));
+        assert_eq!(criteria.limit, None);
+        assert_eq!(criteria.offset, None);
     }
 }
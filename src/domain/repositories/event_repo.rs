@@ -3,11 +3,14 @@
 
 // src/domain/repositories/event_repo.rs
 
-use crate::domain::entities::event::Event;
-use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::domain::entities::event::{Event, EventOrigin};
+use crate::domain::entities::release_completeness::ReleaseOutcome;
+use crate::domain::repositories::pagination::{EventSortField, ListOrder};
+use crate::domain::value_objects::{EventId, EventReceiverId, Version};
 use crate::error::Result;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 
 /// Repository trait for event persistence operations
 #[async_trait]
@@ -45,6 +48,60 @@ pub trait EventRepository: Send + Sync {
     /// Counts successful events by receiver ID
     async fn count_successful_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize>;
 
+    /// Counts user events stored for a receiver at or after `since`
+    ///
+    /// The default implementation loads every match; repositories backed by
+    /// a database should override it.
+    async fn count_by_receiver_since(
+        &self,
+        receiver_id: EventReceiverId,
+        since: DateTime<Utc>,
+    ) -> Result<usize> {
+        let criteria = FindEventCriteria::new()
+            .with_event_receiver_id(receiver_id)
+            .with_start_time(since)
+            .with_origin(EventOrigin::User);
+        Ok(self.find_by_criteria(criteria).await?.len())
+    }
+
+    /// Reports which of `receiver_ids` stored successful or failed user
+    /// events with `release`, at or after `since` if set
+    ///
+    /// Receivers without such events are left out. The default
+    /// implementation loads every event of the release; repositories backed
+    /// by a database should aggregate in one query.
+    async fn find_release_outcomes(
+        &self,
+        receiver_ids: &[EventReceiverId],
+        release: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<HashMap<EventReceiverId, ReleaseOutcome>> {
+        let mut criteria = FindEventCriteria::new()
+            .with_release(release.to_string())
+            .with_origin(EventOrigin::User);
+        if let Some(since) = since {
+            criteria = criteria.with_start_time(since);
+        }
+
+        let mut outcomes: HashMap<EventReceiverId, ReleaseOutcome> = HashMap::new();
+        for event in self.find_by_criteria(criteria).await? {
+            let matches = receiver_ids.contains(&event.event_receiver_id())
+                && event.release() == release
+                && event.origin() == EventOrigin::User
+                && since.is_none_or(|since| event.created_at() >= since);
+            if !matches {
+                continue;
+            }
+            let outcome = outcomes.entry(event.event_receiver_id()).or_default();
+            if event.success() {
+                outcome.succeeded = true;
+            } else {
+                outcome.failed = true;
+            }
+        }
+        Ok(outcomes)
+    }
+
     /// Deletes an event by ID
     async fn delete(&self, id: EventId) -> Result<()>;
 
@@ -67,9 +124,30 @@ pub trait EventRepository: Send + Sync {
         end: DateTime<Utc>,
     ) -> Result<Vec<Event>>;
 
+    /// Counts events within a time range
+    ///
+    /// The default implementation loads every match; repositories backed by
+    /// a database should override it.
+    async fn count_by_time_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<usize> {
+        Ok(self.find_by_time_range(start, end).await?.len())
+    }
+
     /// Finds events that match multiple criteria
     async fn find_by_criteria(&self, criteria: FindEventCriteria) -> Result<Vec<Event>>;
 
+    /// Counts events that match `criteria`, ignoring its limit and offset
+    ///
+    /// The default implementation loads every match; repositories backed by
+    /// a database should override it.
+    async fn count_by_criteria(&self, criteria: FindEventCriteria) -> Result<usize> {
+        let criteria = FindEventCriteria {
+            limit: None,
+            offset: None,
+            ..criteria
+        };
+        Ok(self.find_by_criteria(criteria).await?.len())
+    }
+
     /// Finds all events created by a specific user
     async fn find_by_owner(
         &self,
@@ -108,6 +186,9 @@ pub struct FindEventCriteria {
     pub event_receiver_id: Option<EventReceiverId>,
     pub start_time: Option<DateTime<Utc>>,
     pub end_time: Option<DateTime<Utc>>,
+    /// Restricts results to user or system events; `None` matches both
+    pub origin: Option<EventOrigin>,
+    pub order: ListOrder<EventSortField>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
@@ -130,9 +211,9 @@ impl FindEventCriteria {
         self
     }
 
-    /// Sets the version filter
+    /// Sets the version filter, compared in canonical form
     pub fn with_version(mut self, version: String) -> Self {
-        self.version = Some(version);
+        self.version = Some(Version::normalize_filter(&version));
         self
     }
 
@@ -178,6 +259,18 @@ impl FindEventCriteria {
         self
     }
 
+    /// Sets the origin filter
+    pub fn with_origin(mut self, origin: EventOrigin) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Sets the sort order
+    pub fn with_order(mut self, order: ListOrder<EventSortField>) -> Self {
+        self.order = order;
+        self
+    }
+
     /// Sets pagination limit
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
@@ -191,6 +284,9 @@ impl FindEventCriteria {
     }
 
     /// Checks if any criteria are set
+    ///
+    /// The origin filter is a scope rather than a search term, so it does
+    /// not count.
     pub fn is_empty(&self) -> bool {
         self.id.is_none()
             && self.name.is_none()
@@ -0,0 +1,116 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/event_rollup_repo.rs
+
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde_json::Value;
+
+/// Width of a rollup bucket
+pub fn rollup_bucket_width() -> Duration {
+    Duration::hours(1)
+}
+
+/// Returns the start of the hourly rollup bucket containing `at`
+pub fn rollup_bucket(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.duration_trunc(rollup_bucket_width()).unwrap_or(at)
+}
+
+/// Returns the severity an event is counted under
+///
+/// This is the payload `severity` string in lowercase, or an empty string
+/// when the payload has none. The SQL that aggregates raw events computes
+/// the same value.
+pub fn event_severity(payload: &Value) -> String {
+    payload
+        .get("severity")
+        .and_then(|severity| severity.as_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default()
+}
+
+/// Number of events sharing a receiver, hour, severity, and outcome
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventCountBucket {
+    pub receiver_id: EventReceiverId,
+    pub bucket_start: DateTime<Utc>,
+    pub severity: String,
+    pub success: bool,
+    pub count: u64,
+}
+
+/// Events a receiver created in a time range, split by outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReceiverEventCounts {
+    pub receiver_id: EventReceiverId,
+    pub successful: u64,
+    pub failed: u64,
+}
+
+impl ReceiverEventCounts {
+    pub fn total(&self) -> u64 {
+        self.successful + self.failed
+    }
+}
+
+/// Event counts over a half-open time range `[start, end)`
+///
+/// Implemented both by the raw events table and by the hourly rollup. The
+/// rollup can only answer ranges aligned to [`rollup_bucket`] boundaries.
+/// Only user events are counted; system events never reach the stats.
+#[async_trait]
+pub trait EventCountRepository: Send + Sync {
+    /// Counts events per receiver, omitting receivers with no events
+    async fn receiver_event_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ReceiverEventCounts>>;
+
+    /// Counts events per hourly bucket, receiver, severity, and outcome
+    async fn bucket_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<EventCountBucket>>;
+}
+
+/// Repository for the hourly event count rollup
+#[async_trait]
+pub trait EventRollupRepository: EventCountRepository {
+    /// Replaces every bucket starting in `[start, end)` with `buckets`
+    ///
+    /// Implementations must apply the replacement atomically.
+    async fn replace_buckets(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        buckets: &[EventCountBucket],
+    ) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    #[test]
+    fn test_rollup_bucket_truncates_to_hour() {
+        let at = Utc.with_ymd_and_hms(2025, 3, 5, 14, 59, 59).unwrap();
+        assert_eq!(
+            rollup_bucket(at),
+            Utc.with_ymd_and_hms(2025, 3, 5, 14, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_event_severity() {
+        assert_eq!(event_severity(&json!({"severity": "ERROR"})), "error");
+        assert_eq!(event_severity(&json!({"severity": 3})), "");
+        assert_eq!(event_severity(&json!({})), "");
+    }
+}
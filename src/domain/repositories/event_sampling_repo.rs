@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/event_sampling_repo.rs
+
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+
+/// Events a receiver saw versus events it stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SamplingCounts {
+    pub total_seen: u64,
+    pub total_stored: u64,
+}
+
+impl SamplingCounts {
+    /// Returns the number of events dropped by sampling
+    pub fn sampled_out(&self) -> u64 {
+        self.total_seen.saturating_sub(self.total_stored)
+    }
+}
+
+/// Returns the start of the hourly counter bucket containing `at`
+pub fn sampling_bucket(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.duration_trunc(Duration::hours(1)).unwrap_or(at)
+}
+
+/// Repository for per-receiver sampling counters, kept in hourly buckets
+#[async_trait]
+pub trait EventSamplingCounterRepository: Send + Sync {
+    /// Counts one event seen by a receiver, and whether it was stored
+    async fn record_sampling(
+        &self,
+        receiver_id: EventReceiverId,
+        bucket_start: DateTime<Utc>,
+        stored: bool,
+    ) -> Result<()>;
+
+    /// Sums the counters of every bucket for a receiver
+    async fn sampling_counts(&self, receiver_id: EventReceiverId) -> Result<SamplingCounts>;
+}
@@ -0,0 +1,33 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/feature_flag_repo.rs
+
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// Runtime state of a feature flag set through the admin API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredFeatureFlag {
+    pub name: String,
+    pub enabled: bool,
+    /// Per-role states that take precedence over `enabled`
+    pub role_overrides: BTreeMap<String, bool>,
+    pub updated_by: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Repository persisting runtime feature flag changes
+///
+/// Only flags changed at runtime are stored; flags that were never changed
+/// use the defaults from configuration.
+#[async_trait]
+pub trait FeatureFlagRepository: Send + Sync {
+    /// Lists every stored flag
+    async fn list_flags(&self) -> Result<Vec<StoredFeatureFlag>>;
+
+    /// Inserts or replaces the stored state of a flag
+    async fn save_flag(&self, flag: &StoredFeatureFlag) -> Result<()>;
+}
@@ -0,0 +1,167 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/ingestion_meta_repo.rs
+
+use crate::domain::entities::event::EventOrigin;
+use crate::domain::entities::ingestion_meta::{IngestionMeta, IngestionSource, PrincipalType};
+use crate::domain::entities::pii_detection::PiiReport;
+use crate::domain::repositories::pagination::{EventSortField, ListOrder};
+use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository for event ingestion metadata
+///
+/// Metadata is stored separately from the event itself and keyed by event
+/// id, so it can be restricted to privileged readers.
+#[async_trait]
+pub trait EventIngestionMetaRepository: Send + Sync {
+    /// Saves ingestion metadata for an event
+    async fn save_ingestion_meta(&self, meta: &IngestionMeta) -> Result<()>;
+
+    /// Finds ingestion metadata for an event
+    async fn find_ingestion_meta(&self, event_id: EventId) -> Result<Option<IngestionMeta>>;
+
+    /// Finds ingestion metadata matching a filter, ordered by the filter's
+    /// event sort
+    async fn find_ingestion_meta_by_filter(
+        &self,
+        filter: &IngestionMetaFilter,
+    ) -> Result<Vec<IngestionMeta>>;
+
+    /// Counts ingestion metadata matching a filter, ignoring its limit and
+    /// offset
+    async fn count_ingestion_meta_by_filter(&self, filter: &IngestionMetaFilter) -> Result<usize>;
+
+    /// Aggregates the PII findings of a receiver's events recorded since
+    /// `since`, keeping the `limit` most frequent paths
+    async fn find_pii_report(
+        &self,
+        receiver_id: EventReceiverId,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<PiiReport>;
+}
+
+/// Filter for listing events by ingestion metadata
+#[derive(Debug, Clone, Default)]
+pub struct IngestionMetaFilter {
+    pub principal_type: Option<PrincipalType>,
+    pub principal_id: Option<String>,
+    pub source: Option<IngestionSource>,
+    pub client_ip: Option<String>,
+    /// Origin of the events the metadata belongs to
+    pub origin: Option<EventOrigin>,
+    /// Sort applied to the events the metadata belongs to
+    pub order: ListOrder<EventSortField>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl IngestionMetaFilter {
+    /// Creates an empty filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the principal type filter
+    pub fn with_principal_type(mut self, principal_type: PrincipalType) -> Self {
+        self.principal_type = Some(principal_type);
+        self
+    }
+
+    /// Sets the principal ID filter
+    pub fn with_principal_id(mut self, principal_id: String) -> Self {
+        self.principal_id = Some(principal_id);
+        self
+    }
+
+    /// Sets the ingestion source filter
+    pub fn with_source(mut self, source: IngestionSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Sets the client IP filter
+    pub fn with_client_ip(mut self, client_ip: String) -> Self {
+        self.client_ip = Some(client_ip);
+        self
+    }
+
+    /// Sets the event origin filter
+    pub fn with_origin(mut self, origin: EventOrigin) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Sets the sort order
+    pub fn with_order(mut self, order: ListOrder<EventSortField>) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Sets pagination limit
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets pagination offset
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Returns true if `meta` satisfies every set field
+    ///
+    /// Pagination and origin are not considered, since origin belongs to
+    /// the event rather than its metadata. Useful for in-memory
+    /// implementations.
+    pub fn matches(&self, meta: &IngestionMeta) -> bool {
+        self.principal_type
+            .is_none_or(|t| meta.principal_type() == t)
+            && self
+                .principal_id
+                .as_deref()
+                .is_none_or(|id| meta.principal_id() == id)
+            && self.source.is_none_or(|s| meta.source() == s)
+            && self
+                .client_ip
+                .as_deref()
+                .is_none_or(|ip| meta.client_ip() == Some(ip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::ingestion_meta::IngestionContext;
+
+    #[test]
+    fn test_filter_matches() {
+        let meta = IngestionMeta::new(
+            EventId::new(),
+            IngestionContext::new(PrincipalType::ApiKey, "key-1", IngestionSource::Webhook)
+                .with_client_ip(Some("203.0.113.1".to_string())),
+        );
+
+        assert!(IngestionMetaFilter::new().matches(&meta));
+        assert!(IngestionMetaFilter::new()
+            .with_principal_type(PrincipalType::ApiKey)
+            .with_principal_id("key-1".to_string())
+            .with_source(IngestionSource::Webhook)
+            .with_client_ip("203.0.113.1".to_string())
+            .matches(&meta));
+        assert!(!IngestionMetaFilter::new()
+            .with_principal_id("key-2".to_string())
+            .matches(&meta));
+        assert!(!IngestionMetaFilter::new()
+            .with_source(IngestionSource::Rest)
+            .matches(&meta));
+        assert!(!IngestionMetaFilter::new()
+            .with_client_ip("198.51.100.1".to_string())
+            .matches(&meta));
+    }
+}
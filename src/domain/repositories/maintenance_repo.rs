@@ -0,0 +1,33 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/maintenance_repo.rs
+
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Maintenance mode state set through the admin API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredMaintenance {
+    pub enabled: bool,
+    /// Message returned with rejected writes
+    pub message: String,
+    /// When maintenance is scheduled to end, if known
+    pub ends_at: Option<DateTime<Utc>>,
+    pub updated_by: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Repository persisting the maintenance mode shared by every instance
+///
+/// Holds a single state; an instance that never had maintenance mode
+/// stored runs normally.
+#[async_trait]
+pub trait MaintenanceRepository: Send + Sync {
+    /// Loads the stored state, if any
+    async fn load_maintenance(&self) -> Result<Option<StoredMaintenance>>;
+
+    /// Replaces the stored state
+    async fn save_maintenance(&self, maintenance: &StoredMaintenance) -> Result<()>;
+}
@@ -3,7 +3,33 @@
 
 // Generated mod file
 
+pub mod attestation_repo;
+pub mod audit_record_repo;
+pub mod change_feed_repo;
+pub mod delivery_failure_repo;
+pub mod event_archive_repo;
+pub mod event_attachment_repo;
+pub mod event_feed_repo;
+pub mod event_outbox_repo;
+pub mod event_payload_repo;
 pub mod event_receiver_group_repo;
 pub mod event_receiver_repo;
 pub mod event_repo;
+pub mod event_rollup_repo;
+pub mod event_sampling_repo;
+pub mod feature_flag_repo;
+pub mod ingestion_meta_repo;
+pub mod maintenance_repo;
+pub mod pagination;
+pub mod persisted_query_repo;
+pub mod receiver_activity_repo;
+pub mod receiver_heartbeat_repo;
+pub mod receiver_timeline_repo;
+pub mod release_completion_repo;
+pub mod resource_history_repo;
+pub mod search_repo;
+pub mod system_summary_repo;
+pub mod user_invitation_repo;
+pub mod user_preferences_repo;
 pub mod user_repo;
+pub mod version_normalization_repo;
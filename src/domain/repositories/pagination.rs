@@ -0,0 +1,346 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/pagination.rs
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Page size used when neither the request nor the user picks one
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Largest page size a list request may ask for
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Direction of a sort
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    /// Returns the SQL keyword for this direction
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    /// Applies this direction to an ascending comparison
+    pub fn apply(&self, ordering: std::cmp::Ordering) -> std::cmp::Ordering {
+        match self {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    }
+}
+
+impl FromStr for SortDirection {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(SortDirection::Asc),
+            "desc" => Ok(SortDirection::Desc),
+            other => Err(DomainError::ValidationError {
+                field: "order".to_string(),
+                message: Message::new("validation.sort_order_unknown")
+                    .with("value", other)
+                    .with("options", "asc, desc"),
+            }),
+        }
+    }
+}
+
+/// Field a resource list can be sorted by
+///
+/// Each resource exposes a closed allowlist; repositories map the variants
+/// onto columns, so caller input never reaches SQL.
+pub trait SortField: Copy + Eq + fmt::Debug + Send + Sync + 'static {
+    /// Query parameter names paired with the fields they select
+    const FIELDS: &'static [(&'static str, Self)];
+
+    /// Field used when the caller does not pick one
+    const CREATED_AT: Self;
+
+    /// Alphabetical field
+    const NAME: Self;
+
+    /// Direction used when the caller picks a field but no order
+    fn default_direction(self) -> SortDirection {
+        if self == Self::NAME {
+            SortDirection::Asc
+        } else {
+            SortDirection::Desc
+        }
+    }
+
+    /// Returns the query parameter name of this field
+    fn as_str(self) -> &'static str {
+        Self::FIELDS
+            .iter()
+            .find(|(_, field)| *field == self)
+            .map(|(name, _)| *name)
+            .unwrap_or_default()
+    }
+
+    /// Parses a query parameter name against the allowlist
+    fn parse(name: &str) -> Result<Self, DomainError> {
+        Self::FIELDS
+            .iter()
+            .find(|(candidate, _)| *candidate == name)
+            .map(|(_, field)| *field)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Self::FIELDS.iter().map(|(name, _)| *name).collect();
+                DomainError::ValidationError {
+                    field: "sort".to_string(),
+                    message: Message::new("validation.sort_field_unknown")
+                        .with("value", name)
+                        .with("options", valid.join(", ")),
+                }
+            })
+    }
+}
+
+/// Sort order for list queries
+///
+/// Repositories break ties by id, so pages never overlap or skip rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListOrder<F> {
+    pub field: F,
+    pub direction: SortDirection,
+}
+
+impl<F: SortField> Default for ListOrder<F> {
+    /// Newest first
+    fn default() -> Self {
+        Self::desc(F::CREATED_AT)
+    }
+}
+
+impl<F: SortField> ListOrder<F> {
+    /// Creates a sort order
+    pub fn new(field: F, direction: SortDirection) -> Self {
+        Self { field, direction }
+    }
+
+    /// Sorts ascending by `field`
+    pub fn asc(field: F) -> Self {
+        Self::new(field, SortDirection::Asc)
+    }
+
+    /// Sorts descending by `field`
+    pub fn desc(field: F) -> Self {
+        Self::new(field, SortDirection::Desc)
+    }
+
+    /// Resolves the `sort` and `order` query parameters
+    ///
+    /// A missing field falls back to `created_at`; a missing order falls back
+    /// to the field's natural direction.
+    pub fn parse(sort: Option<&str>, order: Option<&str>) -> Result<Self, DomainError> {
+        let field = sort.map(F::parse).transpose()?.unwrap_or(F::CREATED_AT);
+        let direction = match order {
+            Some(order) => order.parse()?,
+            None => field.default_direction(),
+        };
+
+        Ok(Self::new(field, direction))
+    }
+}
+
+/// Sortable fields of event receivers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiverSortField {
+    CreatedAt,
+    UpdatedAt,
+    Name,
+    /// Events recorded in the hourly rollup
+    EventVolume,
+    /// Share of rolled-up events that succeeded
+    SuccessRate,
+}
+
+impl SortField for ReceiverSortField {
+    const FIELDS: &'static [(&'static str, Self)] = &[
+        ("created_at", Self::CreatedAt),
+        ("updated_at", Self::UpdatedAt),
+        ("name", Self::Name),
+        ("event_volume", Self::EventVolume),
+        ("success_rate", Self::SuccessRate),
+    ];
+    const CREATED_AT: Self = Self::CreatedAt;
+    const NAME: Self = Self::Name;
+}
+
+/// Sortable fields of event receiver groups
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupSortField {
+    CreatedAt,
+    UpdatedAt,
+    Name,
+}
+
+impl SortField for GroupSortField {
+    const FIELDS: &'static [(&'static str, Self)] = &[
+        ("created_at", Self::CreatedAt),
+        ("updated_at", Self::UpdatedAt),
+        ("name", Self::Name),
+    ];
+    const CREATED_AT: Self = Self::CreatedAt;
+    const NAME: Self = Self::Name;
+}
+
+/// Sortable fields of events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventSortField {
+    CreatedAt,
+    Name,
+    Success,
+}
+
+impl SortField for EventSortField {
+    const FIELDS: &'static [(&'static str, Self)] = &[
+        ("created_at", Self::CreatedAt),
+        ("name", Self::Name),
+        ("success", Self::Success),
+    ];
+    const CREATED_AT: Self = Self::CreatedAt;
+    const NAME: Self = Self::Name;
+}
+
+/// Limit and offset requested by a list query
+///
+/// REST and GraphQL both resolve their page through this type so the same
+/// bounds and defaults apply everywhere.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PaginationParams {
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+impl PaginationParams {
+    /// Creates pagination parameters
+    pub fn new(limit: Option<usize>, offset: usize) -> Self {
+        Self { limit, offset }
+    }
+
+    /// Rejects limits outside `1..=MAX_PAGE_SIZE`
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self
+            .limit
+            .is_some_and(|limit| limit == 0 || limit > MAX_PAGE_SIZE)
+        {
+            return Err(DomainError::ValidationError {
+                field: "limit".to_string(),
+                message: Message::new("validation.limit_range").with("max", MAX_PAGE_SIZE),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the requested limit, then `default_page_size`, then
+    /// [`DEFAULT_PAGE_SIZE`]
+    pub fn effective_limit(&self, default_page_size: Option<usize>) -> usize {
+        self.limit
+            .or(default_page_size)
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_limit_bounds() {
+        assert!(PaginationParams::new(None, 0).validate().is_ok());
+        assert!(PaginationParams::new(Some(1), 0).validate().is_ok());
+        assert!(PaginationParams::new(Some(MAX_PAGE_SIZE), 0)
+            .validate()
+            .is_ok());
+        assert!(PaginationParams::new(Some(0), 0).validate().is_err());
+        assert!(PaginationParams::new(Some(MAX_PAGE_SIZE + 1), 0)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_effective_limit_fallbacks() {
+        assert_eq!(
+            PaginationParams::new(Some(10), 0).effective_limit(Some(25)),
+            10
+        );
+        assert_eq!(PaginationParams::new(None, 0).effective_limit(Some(25)), 25);
+        assert_eq!(
+            PaginationParams::new(None, 0).effective_limit(None),
+            DEFAULT_PAGE_SIZE
+        );
+    }
+
+    #[test]
+    fn test_list_order_defaults() {
+        assert_eq!(
+            ListOrder::<ReceiverSortField>::default(),
+            ListOrder::desc(ReceiverSortField::CreatedAt)
+        );
+        assert_eq!(
+            ListOrder::<GroupSortField>::parse(None, None).unwrap(),
+            ListOrder::desc(GroupSortField::CreatedAt)
+        );
+        assert_eq!(
+            ListOrder::<EventSortField>::parse(Some("name"), None).unwrap(),
+            ListOrder::asc(EventSortField::Name)
+        );
+    }
+
+    fn assert_parses_all<F: SortField>() {
+        for (name, field) in F::FIELDS {
+            assert_eq!(field.as_str(), *name);
+            for (order, direction) in [("asc", SortDirection::Asc), ("desc", SortDirection::Desc)] {
+                assert_eq!(
+                    ListOrder::<F>::parse(Some(name), Some(order)).unwrap(),
+                    ListOrder::new(*field, direction)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_every_allowed_field_and_direction() {
+        assert_parses_all::<ReceiverSortField>();
+        assert_parses_all::<GroupSortField>();
+        assert_parses_all::<EventSortField>();
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_field_naming_options() {
+        let err = ListOrder::<GroupSortField>::parse(Some("owner_id"), None).unwrap_err();
+        assert!(matches!(
+            &err,
+            DomainError::ValidationError { field, message }
+                if field == "sort"
+                    && message.params().get("value") == Some("owner_id")
+                    && message.to_string().contains("created_at, updated_at, name")
+        ));
+
+        // Fields of one resource are not valid for another
+        assert!(ListOrder::<EventSortField>::parse(Some("event_volume"), None).is_err());
+        assert!(ListOrder::<ReceiverSortField>::parse(Some("success"), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_order() {
+        let err = ListOrder::<ReceiverSortField>::parse(Some("name"), Some("up")).unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::ValidationError { field, .. } if field == "order"
+        ));
+    }
+}
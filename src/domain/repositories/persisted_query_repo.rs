@@ -0,0 +1,39 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/persisted_query_repo.rs
+
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// GraphQL query text stored under the SHA-256 hash of that text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredPersistedQuery {
+    /// Lowercase hex SHA-256 of `query`
+    pub hash: String,
+    pub query: String,
+    /// Registered by an administrator, as opposed to cached from a client;
+    /// only registered queries run in persisted-only mode
+    pub registered: bool,
+    pub registered_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Repository persisting GraphQL persisted queries across restarts and
+/// instances
+#[async_trait]
+pub trait PersistedQueryRepository: Send + Sync {
+    /// Finds the query stored under `hash`
+    async fn find_query(&self, hash: &str) -> Result<Option<StoredPersistedQuery>>;
+
+    /// Stores a query; storing a registered query registers an existing one,
+    /// but a cached query never unregisters one
+    async fn save_query(&self, query: &StoredPersistedQuery) -> Result<()>;
+
+    /// Lists registered queries, oldest first
+    async fn list_registered(&self) -> Result<Vec<StoredPersistedQuery>>;
+
+    /// Deletes the query stored under `hash`, returning whether it existed
+    async fn delete_query(&self, hash: &str) -> Result<bool>;
+}
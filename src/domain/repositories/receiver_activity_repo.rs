@@ -0,0 +1,33 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/receiver_activity_repo.rs
+
+use crate::domain::entities::receiver_activity::{ReceiverActivity, ReceiverActivityUpdate};
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// Repository for receiver liveness metadata
+///
+/// Writes arrive in batches from the ingestion path, one update per
+/// receiver, so a busy receiver costs one row write per flush rather than
+/// one per event.
+#[async_trait]
+pub trait ReceiverActivityRepository: Send + Sync {
+    /// Applies a batch of updates; older timestamps never replace newer ones
+    async fn record_activity(&self, updates: &[ReceiverActivityUpdate]) -> Result<()>;
+
+    /// Returns activity for the given receivers, counting distinct
+    /// principals from `window_start`; receivers without events are omitted
+    async fn find_activity(
+        &self,
+        receiver_ids: &[EventReceiverId],
+        window_start: NaiveDate,
+    ) -> Result<Vec<ReceiverActivity>>;
+
+    /// Returns up to `limit` receivers with the latest events, most recent
+    /// first
+    async fn most_recently_active(&self, limit: usize) -> Result<Vec<EventReceiverId>>;
+}
@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/receiver_heartbeat_repo.rs
+
+use crate::domain::entities::receiver_heartbeat::ReceiverHeartbeat;
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Result of recording a heartbeat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatWrite {
+    /// The heartbeat replaced the previous one
+    Recorded,
+    /// The previous heartbeat is too recent; nothing was stored
+    Throttled { last_heartbeat_at: DateTime<Utc> },
+}
+
+/// Repository for the latest heartbeat of each receiver
+#[async_trait]
+pub trait ReceiverHeartbeatRepository: Send + Sync {
+    /// Stores `heartbeat` unless the stored one is later than `not_after`
+    ///
+    /// The check and the write are a single statement, so concurrent
+    /// heartbeats for one receiver store at most one of them.
+    async fn record_heartbeat(
+        &self,
+        heartbeat: &ReceiverHeartbeat,
+        not_after: DateTime<Utc>,
+    ) -> Result<HeartbeatWrite>;
+
+    /// Returns the latest heartbeat of the given receivers; receivers that
+    /// never sent one are omitted
+    async fn find_heartbeats(
+        &self,
+        receiver_ids: &[EventReceiverId],
+    ) -> Result<Vec<ReceiverHeartbeat>>;
+}
@@ -0,0 +1,286 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/receiver_timeline_repo.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::value_objects::{EventId, EventReceiverGroupId, EventReceiverId};
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// Kind of a receiver timeline entry
+///
+/// Entries sharing a timestamp are ordered by kind in declaration order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimelineKind {
+    /// An event stored by the receiver
+    Event,
+    /// A write to the receiver's configuration
+    ConfigChange,
+    /// The receiver joining or leaving a group
+    GroupMembership,
+}
+
+impl TimelineKind {
+    /// Every kind, in timeline order
+    pub const ALL: [TimelineKind; 3] = [
+        TimelineKind::Event,
+        TimelineKind::ConfigChange,
+        TimelineKind::GroupMembership,
+    ];
+
+    fn rank(self) -> u8 {
+        self as u8
+    }
+
+    fn from_rank(rank: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(rank)).copied()
+    }
+}
+
+/// Position in a receiver timeline
+///
+/// Entries are ordered by `(at, kind, key)`, where `key` is unique within
+/// a kind, so entries sharing a timestamp still have a stable, total
+/// order. A page after a cursor holds only entries that sort strictly
+/// after it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimelineCursor {
+    at: DateTime<Utc>,
+    kind: TimelineKind,
+    key: String,
+}
+
+impl TimelineCursor {
+    /// Creates a cursor positioned at the given entry
+    pub fn new(at: DateTime<Utc>, kind: TimelineKind, key: impl Into<String>) -> Self {
+        Self {
+            at,
+            kind,
+            key: key.into(),
+        }
+    }
+
+    /// Creates a cursor positioned before every entry at or after `at`
+    pub fn at(at: DateTime<Utc>) -> Self {
+        Self::new(at, TimelineKind::Event, String::new())
+    }
+
+    /// Returns the timestamp component of the cursor
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.at
+    }
+
+    /// Returns the lowest key of `kind` following the cursor at its
+    /// timestamp
+    ///
+    /// `None` means no entry of `kind` at the cursor's timestamp follows
+    /// it, so only later timestamps qualify. Storage backends select
+    /// entries where `at > timestamp() OR (at = timestamp() AND key > k)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use xzepr::domain::repositories::receiver_timeline_repo::{TimelineCursor, TimelineKind};
+    ///
+    /// let cursor = TimelineCursor::new(Utc::now(), TimelineKind::ConfigChange, "7");
+    /// assert_eq!(cursor.key_after(TimelineKind::Event), None);
+    /// assert_eq!(cursor.key_after(TimelineKind::ConfigChange), Some("7"));
+    /// assert_eq!(cursor.key_after(TimelineKind::GroupMembership), Some(""));
+    /// ```
+    pub fn key_after(&self, kind: TimelineKind) -> Option<&str> {
+        match kind.cmp(&self.kind) {
+            std::cmp::Ordering::Less => None,
+            std::cmp::Ordering::Equal => Some(&self.key),
+            std::cmp::Ordering::Greater => Some(""),
+        }
+    }
+
+    /// Parses a cursor previously returned to a client
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xzepr::domain::repositories::receiver_timeline_repo::TimelineCursor;
+    ///
+    /// let cursor = TimelineCursor::parse("1736935200000000000_1_00000000000000000042").unwrap();
+    /// assert_eq!(
+    ///     cursor.to_string(),
+    ///     "1736935200000000000_1_00000000000000000042"
+    /// );
+    ///
+    /// assert!(TimelineCursor::parse("not-a-cursor").is_err());
+    /// ```
+    pub fn parse(value: &str) -> std::result::Result<Self, DomainError> {
+        let invalid = || DomainError::ValidationError {
+            field: "cursor".to_string(),
+            message: Message::new("validation.timeline_cursor"),
+        };
+
+        let mut parts = value.splitn(3, '_');
+        let nanos: i64 = parts
+            .next()
+            .and_then(|nanos| nanos.parse().ok())
+            .ok_or_else(invalid)?;
+        let kind = parts
+            .next()
+            .and_then(|rank| rank.parse().ok())
+            .and_then(TimelineKind::from_rank)
+            .ok_or_else(invalid)?;
+        let key = parts.next().ok_or_else(invalid)?;
+
+        Ok(Self::new(DateTime::from_timestamp_nanos(nanos), kind, key))
+    }
+}
+
+impl fmt::Display for TimelineCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.at.timestamp_nanos_opt().unwrap_or(i64::MAX);
+        write!(f, "{}_{}_{}", nanos, self.kind.rank(), self.key)
+    }
+}
+
+/// How a configuration write changed a receiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigChangeAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// How a group membership of a receiver changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MembershipAction {
+    Added,
+    Removed,
+}
+
+/// What a timeline entry records
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineDetail {
+    /// An event, summarized
+    Event {
+        id: EventId,
+        name: String,
+        success: bool,
+        /// Payload severity as counted by rollups; empty when unset
+        severity: String,
+    },
+    /// A write to the receiver's configuration
+    ConfigChange {
+        action: ConfigChangeAction,
+        /// Configuration fields that differ from the previous state
+        changed_fields: Vec<String>,
+    },
+    /// The receiver joining or leaving a group
+    GroupMembership {
+        group_id: EventReceiverGroupId,
+        group_name: String,
+        action: MembershipAction,
+    },
+}
+
+/// A single entry of a receiver timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry {
+    pub cursor: TimelineCursor,
+    pub detail: TimelineDetail,
+}
+
+impl TimelineEntry {
+    /// Creates an entry, deriving its kind from `detail`
+    pub fn new(at: DateTime<Utc>, key: impl Into<String>, detail: TimelineDetail) -> Self {
+        let kind = match detail {
+            TimelineDetail::Event { .. } => TimelineKind::Event,
+            TimelineDetail::ConfigChange { .. } => TimelineKind::ConfigChange,
+            TimelineDetail::GroupMembership { .. } => TimelineKind::GroupMembership,
+        };
+        Self {
+            cursor: TimelineCursor::new(at, kind, key),
+            detail,
+        }
+    }
+
+    /// Returns the kind of the entry
+    pub fn kind(&self) -> TimelineKind {
+        self.cursor.kind
+    }
+
+    /// Returns when the entry happened
+    pub fn at(&self) -> DateTime<Utc> {
+        self.cursor.at
+    }
+}
+
+/// Returns the key of a history row, padded so keys sort like row ids
+pub fn history_key(row_id: i64) -> String {
+    format!("{:020}", row_id)
+}
+
+/// Describes the write that turned `previous` into `current`
+///
+/// `None` states are a receiver that did not exist yet or was deleted.
+/// Bookkeeping such as timestamps and the resource version is not
+/// reported as a change.
+pub fn config_change(
+    previous: Option<&EventReceiver>,
+    current: Option<&EventReceiver>,
+) -> TimelineDetail {
+    let (action, changed_fields) = match (previous, current) {
+        (Some(previous), Some(current)) => (
+            ConfigChangeAction::Updated,
+            changed_fields(previous, current),
+        ),
+        (_, Some(_)) => (ConfigChangeAction::Created, Vec::new()),
+        (_, None) => (ConfigChangeAction::Deleted, Vec::new()),
+    };
+    TimelineDetail::ConfigChange {
+        action,
+        changed_fields,
+    }
+}
+
+fn changed_fields(previous: &EventReceiver, current: &EventReceiver) -> Vec<String> {
+    [
+        ("name", previous.name() != current.name()),
+        ("type", previous.receiver_type() != current.receiver_type()),
+        ("version", previous.version() != current.version()),
+        (
+            "description",
+            previous.description() != current.description(),
+        ),
+        ("schema", previous.schema() != current.schema()),
+        ("owner_id", previous.owner_id() != current.owner_id()),
+        (
+            "sample_rate",
+            previous.sample_rate() != current.sample_rate(),
+        ),
+        (
+            "allowed_event_names",
+            previous.allowed_event_names() != current.allowed_event_names(),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(field, _)| field.to_string())
+    .collect()
+}
+
+/// Repository reading the sources a receiver timeline merges
+#[async_trait]
+pub trait ReceiverTimelineRepository: Send + Sync {
+    /// Returns up to `limit` entries of `kind` for a receiver, strictly
+    /// after `after` and before `end`, in timeline order
+    async fn find_timeline_entries(
+        &self,
+        receiver_id: EventReceiverId,
+        kind: TimelineKind,
+        after: &TimelineCursor,
+        end: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>>;
+}
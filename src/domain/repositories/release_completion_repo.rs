@@ -0,0 +1,24 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/release_completion_repo.rs
+
+use crate::domain::value_objects::EventReceiverGroupId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Repository remembering which releases each group has completed
+#[async_trait]
+pub trait ReleaseCompletionRepository: Send + Sync {
+    /// Records that `group_id` completed `release` at `completed_at`
+    ///
+    /// Returns true only for the first record of a pair, so exactly one
+    /// caller announces the completion even across instances.
+    async fn record_completion(
+        &self,
+        group_id: EventReceiverGroupId,
+        release: &str,
+        completed_at: DateTime<Utc>,
+    ) -> Result<bool>;
+}
@@ -0,0 +1,62 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/resource_history_repo.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::repositories::event_receiver_group_repo::GroupMembership;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// State of a resource recorded after a write
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceSnapshot<T> {
+    /// When the write happened
+    pub recorded_at: DateTime<Utc>,
+    /// State after the write; `None` if the write deleted the resource
+    pub state: Option<T>,
+}
+
+/// A group together with its memberships
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupState {
+    pub group: EventReceiverGroup,
+    pub members: Vec<GroupMembership>,
+}
+
+/// Repository of past receiver and group states
+///
+/// Snapshots are append-only. Each one holds the complete state after a
+/// write, so any single snapshot is enough to reconstruct the resource.
+#[async_trait]
+pub trait ResourceHistoryRepository: Send + Sync {
+    /// Appends the state of a receiver after a write
+    async fn record_receiver(
+        &self,
+        id: EventReceiverId,
+        snapshot: &ResourceSnapshot<EventReceiver>,
+    ) -> Result<()>;
+
+    /// Returns every snapshot of a receiver, oldest first
+    async fn receiver_history(
+        &self,
+        id: EventReceiverId,
+    ) -> Result<Vec<ResourceSnapshot<EventReceiver>>>;
+
+    /// Appends the state of a group after a write
+    async fn record_group(
+        &self,
+        id: EventReceiverGroupId,
+        snapshot: &ResourceSnapshot<GroupState>,
+    ) -> Result<()>;
+
+    /// Returns every snapshot of a group, oldest first
+    async fn group_history(
+        &self,
+        id: EventReceiverGroupId,
+    ) -> Result<Vec<ResourceSnapshot<GroupState>>>;
+}
@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/search_repo.rs
+
+use crate::domain::entities::search::{SearchHit, SearchQuery, SearchResourceType};
+use crate::domain::value_objects::UserId;
+use crate::error::Result;
+use async_trait::async_trait;
+
+/// Resources of one type a caller may find
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchVisibility {
+    /// Every resource of the type
+    All,
+    /// Resources the user owns or reaches through a current group
+    /// membership
+    OwnedOrMember(UserId),
+}
+
+/// Who is searching, and which types they may read in full
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchViewer {
+    /// `None` for administrators, who see everything
+    user_id: Option<UserId>,
+    unrestricted: Vec<SearchResourceType>,
+}
+
+impl SearchViewer {
+    /// A viewer limited to their own and their groups' resources
+    pub fn new(user_id: UserId) -> Self {
+        Self {
+            user_id: Some(user_id),
+            unrestricted: Vec::new(),
+        }
+    }
+
+    /// A viewer who may find every resource
+    pub fn admin() -> Self {
+        Self {
+            user_id: None,
+            unrestricted: SearchResourceType::ALL.to_vec(),
+        }
+    }
+
+    /// Lets the viewer find every resource of `resource_type`
+    pub fn with_unrestricted(mut self, resource_type: SearchResourceType) -> Self {
+        if !self.unrestricted.contains(&resource_type) {
+            self.unrestricted.push(resource_type);
+        }
+        self
+    }
+
+    /// Returns the resources of `resource_type` the viewer may find
+    pub fn visibility(&self, resource_type: SearchResourceType) -> SearchVisibility {
+        match self.user_id {
+            Some(user_id) if !self.unrestricted.contains(&resource_type) => {
+                SearchVisibility::OwnedOrMember(user_id)
+            }
+            _ => SearchVisibility::All,
+        }
+    }
+}
+
+/// Best matches of one type with the number of matches overall
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchMatches {
+    /// At most the requested number of hits, best first
+    pub hits: Vec<SearchHit>,
+    /// Visible matches before the limit was applied
+    pub total: u64,
+}
+
+/// Full-text index over receiver, event, and group text
+///
+/// Receivers are indexed by name and description, events by name,
+/// package, and description, and groups by name and description.
+#[async_trait]
+pub trait SearchRepository: Send + Sync {
+    /// Returns false if no term of the query is indexed, for example
+    /// because every word is a stop word
+    async fn is_searchable(&self, query: &SearchQuery) -> Result<bool>;
+
+    /// Returns up to `limit` visible resources of `resource_type` matching
+    /// every term of `query`, exact name matches first, then by rank
+    async fn search(
+        &self,
+        resource_type: SearchResourceType,
+        query: &SearchQuery,
+        visibility: SearchVisibility,
+        limit: usize,
+    ) -> Result<SearchMatches>;
+}
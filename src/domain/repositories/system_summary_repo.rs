@@ -0,0 +1,74 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/system_summary_repo.rs
+
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Event receiver groups split by enabled state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GroupCounts {
+    pub enabled: u64,
+    pub disabled: u64,
+}
+
+/// Events created in a time window, split by outcome
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventOutcomeCounts {
+    pub successful: u64,
+    pub failed: u64,
+}
+
+impl EventOutcomeCounts {
+    pub fn total(&self) -> u64 {
+        self.successful + self.failed
+    }
+}
+
+/// Number of events a receiver created in a time window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiverVolume {
+    pub receiver_id: EventReceiverId,
+    pub name: String,
+    pub event_count: u64,
+}
+
+/// Aggregate queries behind the admin overview
+///
+/// Every method is a single aggregate query; implementations must not load
+/// rows to count them.
+#[async_trait]
+pub trait SystemSummaryRepository: Send + Sync {
+    /// Counts all event receivers
+    async fn count_receivers(&self) -> Result<u64>;
+
+    /// Counts receivers that created at least one user event since `since`
+    async fn count_active_receivers(&self, since: DateTime<Utc>) -> Result<u64>;
+
+    /// Counts event receiver groups by enabled state
+    async fn count_groups(&self) -> Result<GroupCounts>;
+
+    /// Counts user events created since `since`, split by outcome
+    async fn count_events_since(&self, since: DateTime<Utc>) -> Result<EventOutcomeCounts>;
+
+    /// Returns the receivers with the most user events since `since`,
+    /// busiest first
+    async fn top_receivers_since(
+        &self,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ReceiverVolume>>;
+
+    /// Returns the names of the given receivers; unknown IDs are omitted
+    async fn receiver_names(
+        &self,
+        ids: &[EventReceiverId],
+    ) -> Result<HashMap<EventReceiverId, String>>;
+
+    /// Counts enabled user accounts
+    async fn count_active_users(&self) -> Result<u64>;
+}
@@ -0,0 +1,25 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/user_invitation_repo.rs
+
+use crate::domain::entities::user_invitation::UserInvitation;
+use crate::error::Result;
+use async_trait::async_trait;
+
+/// Repository for invitations to choose a local account's password
+///
+/// Invitations are found by token hash only; the token itself is never
+/// stored.
+#[async_trait]
+pub trait UserInvitationRepository: Send + Sync {
+    /// Stores `invitations`, replacing any earlier invitation of the same
+    /// users
+    async fn save_invitations(&self, invitations: &[UserInvitation]) -> Result<()>;
+
+    /// Deletes and returns the invitation stored under `token_hash`
+    ///
+    /// Taking an invitation is what makes its token single-use, so it is
+    /// taken whether or not it has expired; the caller checks expiry.
+    async fn take_invitation(&self, token_hash: &str) -> Result<Option<UserInvitation>>;
+}
@@ -0,0 +1,20 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/user_preferences_repo.rs
+
+use crate::domain::entities::user_preferences::UserPreferences;
+use crate::error::Result;
+use async_trait::async_trait;
+
+/// Repository for per-user preferences
+///
+/// Preferences are keyed by the authenticated principal's user id.
+#[async_trait]
+pub trait UserPreferencesRepository: Send + Sync {
+    /// Finds a user's preferences; users with none stored get an empty set
+    async fn find_preferences(&self, user_id: &str) -> Result<UserPreferences>;
+
+    /// Replaces a user's stored preferences with `preferences`
+    async fn save_preferences(&self, user_id: &str, preferences: &UserPreferences) -> Result<()>;
+}
@@ -114,6 +114,24 @@ pub trait UserRepository: Send + Sync {
     /// - Validation fails
     async fn create(&self, user: User) -> UserRepoResult<User>;
 
+    /// Create several users at once
+    ///
+    /// Database implementations create them in one transaction, so either
+    /// every user is created or none is. The default creates them one by
+    /// one.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if any username or email already exists, or if the
+    /// database operation fails
+    async fn create_all(&self, users: Vec<User>) -> UserRepoResult<Vec<User>> {
+        let mut created = Vec::with_capacity(users.len());
+        for user in users {
+            created.push(self.create(user).await?);
+        }
+        Ok(created)
+    }
+
     /// Update an existing user
     ///
     /// # Arguments
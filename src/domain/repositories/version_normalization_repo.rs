@@ -0,0 +1,67 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/repositories/version_normalization_repo.rs
+
+use crate::error::Result;
+use async_trait::async_trait;
+use std::fmt;
+
+/// Kind of record whose stored `version` is normalized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VersionedResource {
+    Event,
+    EventReceiver,
+    EventReceiverGroup,
+}
+
+impl VersionedResource {
+    /// Every resource with a version, in the order they are normalized
+    pub const ALL: [VersionedResource; 3] = [
+        VersionedResource::EventReceiver,
+        VersionedResource::EventReceiverGroup,
+        VersionedResource::Event,
+    ];
+
+    /// Returns the resource name used in logs and reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VersionedResource::Event => "event",
+            VersionedResource::EventReceiver => "event_receiver",
+            VersionedResource::EventReceiverGroup => "event_receiver_group",
+        }
+    }
+}
+
+impl fmt::Display for VersionedResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A stored version and the number of rows holding it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredVersionCount {
+    pub version: String,
+    pub rows: u64,
+}
+
+/// Repository rewriting stored versions into canonical form
+#[async_trait]
+pub trait VersionNormalizationRepository: Send + Sync {
+    /// Returns each distinct version stored for `resource` with its row
+    /// count
+    async fn stored_versions(&self, resource: VersionedResource)
+        -> Result<Vec<StoredVersionCount>>;
+
+    /// Rewrites every row of `resource` storing `from` to store `to`
+    ///
+    /// Receiver fingerprints cover the version and are recomputed in the
+    /// same transaction. Returns the number of rows changed.
+    async fn rewrite_version(
+        &self,
+        resource: VersionedResource,
+        from: &str,
+        to: &str,
+    ) -> Result<u64>;
+}
@@ -0,0 +1,234 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/value_objects/attachment_id.rs
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use ulid::Ulid;
+
+/// Value object representing a unique identifier for an event attachment
+///
+/// The canonical form is the 26 character uppercase ULID string, which is
+/// also the serde representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AttachmentId(Ulid);
+
+impl AttachmentId {
+    /// Creates a new attachment ID with a new ULID
+    pub fn new() -> Self {
+        Self(Ulid::new())
+    }
+
+    /// Creates an attachment ID from an existing ULID
+    pub fn from_ulid(ulid: Ulid) -> Self {
+        Self(ulid)
+    }
+
+    /// Returns the inner ULID
+    pub fn as_ulid(&self) -> Ulid {
+        self.0
+    }
+
+    /// Returns the string representation of the attachment ID
+    pub fn as_str(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Returns the timestamp component of the ULID in milliseconds since Unix epoch
+    pub fn timestamp_ms(&self) -> u64 {
+        self.0.timestamp_ms()
+    }
+}
+
+impl Default for AttachmentId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for AttachmentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<Ulid> for AttachmentId {
+    fn from(ulid: Ulid) -> Self {
+        Self(ulid)
+    }
+}
+
+impl From<AttachmentId> for Ulid {
+    fn from(id: AttachmentId) -> Self {
+        id.0
+    }
+}
+
+impl std::str::FromStr for AttachmentId {
+    type Err = ulid::DecodeError;
+
+    /// Parses the canonical form, rejecting lowercase ULIDs
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ulid = Ulid::from_string(s)?;
+        if ulid.to_string() != s {
+            return Err(ulid::DecodeError::InvalidChar);
+        }
+        Ok(Self(ulid))
+    }
+}
+
+impl TryFrom<String> for AttachmentId {
+    type Error = ulid::DecodeError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Serialize for AttachmentId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AttachmentId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|e| de::Error::custom(format!("invalid attachment ID: {}", e)))
+    }
+}
+
+// SQLx support for AttachmentId
+impl sqlx::Type<sqlx::Postgres> for AttachmentId {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        <String as sqlx::Type<sqlx::Postgres>>::type_info()
+    }
+}
+
+impl<'r> sqlx::Decode<'r, sqlx::Postgres> for AttachmentId {
+    fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let s = <String as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+        Ok(s.parse::<Self>()?)
+    }
+}
+
+impl<'q> sqlx::Encode<'q, sqlx::Postgres> for AttachmentId {
+    fn encode_by_ref(
+        &self,
+        buf: &mut sqlx::postgres::PgArgumentBuffer,
+    ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Send + Sync>> {
+        <String as sqlx::Encode<sqlx::Postgres>>::encode_by_ref(&self.to_string(), buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_attachment_id() {
+        let id1 = AttachmentId::new();
+        let id2 = AttachmentId::new();
+
+        // Each new ID should be unique
+        assert_ne!(id1, id2);
+    }
+
+    #[test]
+    fn test_from_ulid() {
+        let ulid = Ulid::new();
+        let id = AttachmentId::from_ulid(ulid);
+
+        assert_eq!(id.as_ulid(), ulid);
+    }
+
+    #[test]
+    fn test_parse() {
+        let ulid = Ulid::new();
+        let ulid_str = ulid.to_string();
+        let id: AttachmentId = ulid_str.parse().unwrap();
+
+        assert_eq!(id.as_ulid(), ulid);
+    }
+
+    #[test]
+    fn test_display() {
+        let ulid = Ulid::new();
+        let id = AttachmentId::from_ulid(ulid);
+
+        assert_eq!(id.to_string(), ulid.to_string());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let id = AttachmentId::new();
+        let json = serde_json::to_string(&id).unwrap();
+        let deserialized: AttachmentId = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(id, deserialized);
+    }
+
+    #[test]
+    fn test_parse_invalid_ulid() {
+        let result = "invalid-ulid".parse::<AttachmentId>();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let ulid = Ulid::new();
+        let ulid_str = ulid.to_string();
+        let id: AttachmentId = ulid_str.parse().unwrap();
+
+        assert_eq!(id.as_ulid(), ulid);
+    }
+
+    #[test]
+    fn test_default() {
+        let id = AttachmentId::default();
+        assert!(!id.as_str().is_empty());
+    }
+
+    #[test]
+    fn test_timestamp_ms() {
+        let id = AttachmentId::new();
+        let timestamp = id.timestamp_ms();
+
+        // Timestamp should be reasonable (after 2020 and before far future)
+        assert!(timestamp > 1_577_836_800_000); // Jan 1, 2020
+        assert!(timestamp < 2_000_000_000_000); // Some date far in future
+    }
+
+    #[test]
+    fn test_ordering_by_time() {
+        let id1 = AttachmentId::new();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let id2 = AttachmentId::new();
+
+        // Later IDs should have higher timestamps
+        assert!(id2.timestamp_ms() >= id1.timestamp_ms());
+    }
+
+    #[test]
+    fn test_serializes_as_canonical_string() {
+        let ulid = Ulid::new();
+        let id = AttachmentId::from_ulid(ulid);
+
+        let json = serde_json::to_value(id).unwrap();
+        assert_eq!(json, serde_json::json!(ulid.to_string()));
+    }
+
+    #[test]
+    fn test_rejects_lowercase_and_invalid_ulids() {
+        let lowercase = Ulid::new().to_string().to_lowercase();
+
+        assert!(lowercase.parse::<AttachmentId>().is_err());
+        assert!(AttachmentId::try_from("not-a-ulid".to_string()).is_err());
+
+        let error =
+            serde_json::from_str::<AttachmentId>(&format!("\"{}\"", lowercase)).unwrap_err();
+        assert!(error.to_string().starts_with("invalid attachment ID"));
+    }
+}
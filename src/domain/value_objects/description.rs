@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/value_objects/description.rs
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Longest receiver or group description any configuration may allow, in
+/// characters
+pub const MAX_DESCRIPTION_LENGTH: usize = 65_536;
+
+/// Default limit on receiver and group descriptions, in characters
+pub const DEFAULT_MAX_DESCRIPTION_LENGTH: usize = 10_000;
+
+/// Cleans a receiver or group description and checks its length
+///
+/// Descriptions are Markdown, so line breaks and tabs are kept; `\r\n` and
+/// lone `\r` become `\n` and every other control character is removed.
+/// The length is counted in characters after cleaning.
+///
+/// # Errors
+///
+/// Returns a `description` field validation error if the cleaned
+/// description is longer than `max_length` characters.
+///
+/// # Examples
+///
+/// ```
+/// use xzepr::domain::value_objects::description::normalize_description;
+///
+/// let description = normalize_description("# Runbook\r\n\u{7}Restart it", 100).unwrap();
+/// assert_eq!(description, "# Runbook\nRestart it");
+/// assert!(normalize_description("too long", 3).is_err());
+/// ```
+pub fn normalize_description(input: &str, max_length: usize) -> Result<String, DomainError> {
+    let mut description = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    description.push('\n');
+                }
+            }
+            '\n' | '\t' => description.push(c),
+            c if c.is_control() => {}
+            c => description.push(c),
+        }
+    }
+
+    check_description_length(&description, max_length)?;
+    Ok(description)
+}
+
+/// Checks that `description` has at most `max_length` characters
+///
+/// # Errors
+///
+/// Returns a `description` field validation error naming the limit.
+pub fn check_description_length(description: &str, max_length: usize) -> Result<(), DomainError> {
+    // Byte length bounds the character count, so most checks stop here
+    if description.len() > max_length && description.chars().count() > max_length {
+        return Err(DomainError::ValidationError {
+            field: "description".to_string(),
+            message: Message::new("validation.description_too_long").with("max", max_length),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_characters_are_stripped() {
+        let input = "line one\r\nline two\rline three\u{0}\u{1b}[31m\u{7f}\tend";
+        assert_eq!(
+            normalize_description(input, 100).unwrap(),
+            "line one\nline two\nline three[31m\tend"
+        );
+    }
+
+    #[test]
+    fn test_length_is_counted_in_characters() {
+        let umlauts = "ä".repeat(10);
+        assert_eq!(umlauts.len(), 20);
+        assert!(normalize_description(&umlauts, 10).is_ok());
+
+        let DomainError::ValidationError { field, message } =
+            normalize_description(&umlauts, 9).unwrap_err()
+        else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(field, "description");
+        assert_eq!(message.key(), "validation.description_too_long");
+    }
+}
@@ -3,12 +3,15 @@
 
 // src/domain/value_objects/event_id.rs
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use ulid::Ulid;
 
 /// Value object representing a unique identifier for an event
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// The canonical form is the 26 character uppercase ULID string, which is
+/// also the serde representation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EventId(Ulid);
 
 impl EventId {
@@ -23,8 +26,9 @@ impl EventId {
     }
 
     /// Parses an event ID from a string representation
+    #[deprecated(note = "use `str::parse` or `TryFrom<String>` instead")]
     pub fn parse(s: &str) -> Result<Self, ulid::DecodeError> {
-        Ok(Self(Ulid::from_string(s)?))
+        s.parse()
     }
 
     /// Returns the inner ULID
@@ -70,8 +74,35 @@ impl From<EventId> for Ulid {
 impl std::str::FromStr for EventId {
     type Err = ulid::DecodeError;
 
+    /// Parses the canonical form, rejecting lowercase ULIDs
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::parse(s)
+        let ulid = Ulid::from_string(s)?;
+        if ulid.to_string() != s {
+            return Err(ulid::DecodeError::InvalidChar);
+        }
+        Ok(Self(ulid))
+    }
+}
+
+impl TryFrom<String> for EventId {
+    type Error = ulid::DecodeError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Serialize for EventId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EventId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|e| de::Error::custom(format!("invalid event ID: {}", e)))
     }
 }
 
@@ -85,7 +116,7 @@ impl sqlx::Type<sqlx::Postgres> for EventId {
 impl<'r> sqlx::Decode<'r, sqlx::Postgres> for EventId {
     fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
         let s = <String as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
-        Ok(EventId::parse(&s)?)
+        Ok(s.parse::<Self>()?)
     }
 }
 
@@ -123,7 +154,7 @@ mod tests {
     fn test_parse() {
         let ulid = Ulid::new();
         let ulid_str = ulid.to_string();
-        let id = EventId::parse(&ulid_str).unwrap();
+        let id: EventId = ulid_str.parse().unwrap();
 
         assert_eq!(id.as_ulid(), ulid);
     }
@@ -147,7 +178,7 @@ mod tests {
 
     #[test]
     fn test_parse_invalid_ulid() {
-        let result = EventId::parse("invalid-ulid");
+        let result = "invalid-ulid".parse::<EventId>();
         assert!(result.is_err());
     }
 
@@ -179,4 +210,24 @@ mod tests {
         // Later IDs should have higher timestamps
         assert!(id2.timestamp_ms() >= id1.timestamp_ms());
     }
+
+    #[test]
+    fn test_serializes_as_canonical_string() {
+        let ulid = Ulid::new();
+        let id = EventId::from_ulid(ulid);
+
+        let json = serde_json::to_value(id).unwrap();
+        assert_eq!(json, serde_json::json!(ulid.to_string()));
+    }
+
+    #[test]
+    fn test_rejects_lowercase_and_invalid_ulids() {
+        let lowercase = Ulid::new().to_string().to_lowercase();
+
+        assert!(lowercase.parse::<EventId>().is_err());
+        assert!(EventId::try_from("not-a-ulid".to_string()).is_err());
+
+        let error = serde_json::from_str::<EventId>(&format!("\"{}\"", lowercase)).unwrap_err();
+        assert!(error.to_string().starts_with("invalid event ID"));
+    }
 }
@@ -4,13 +4,18 @@
 // Generated mod file
 
 pub mod api_key_id;
+pub mod attachment_id;
+pub mod description;
 pub mod event_id;
 pub mod event_receiver_group_id;
 pub mod event_receiver_id;
 pub mod user_id;
+pub mod version;
 
 pub use api_key_id::ApiKeyId;
+pub use attachment_id::AttachmentId;
 pub use event_id::EventId;
 pub use event_receiver_group_id::EventReceiverGroupId;
 pub use event_receiver_id::EventReceiverId;
 pub use user_id::UserId;
+pub use version::{StoredVersion, Version, VersionStrictness};
@@ -0,0 +1,247 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/domain/value_objects/version.rs
+
+use serde::Deserialize;
+use std::fmt;
+
+use crate::error::DomainError;
+use crate::i18n::Message;
+
+/// Longest version accepted, matching the narrowest `version` column
+pub const MAX_VERSION_LENGTH: usize = 50;
+
+/// How forgiving version parsing is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionStrictness {
+    /// Only exact semantic versions such as `1.2.3` or `1.0.0-rc.1`
+    Strict,
+    /// Also trims whitespace, strips a leading `v`, and pads missing minor
+    /// and patch components with `0`
+    #[default]
+    Lenient,
+}
+
+/// Value object representing a semantic version of a receiver, group, or
+/// event
+///
+/// The canonical form is the semver string, e.g. `1.2.3-rc.1`, which is
+/// what gets stored and compared.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Version(semver::Version);
+
+/// What normalizing an already stored version would do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StoredVersion {
+    /// The stored value is already canonical
+    Canonical,
+    /// The stored value parses leniently and should be rewritten
+    Rewrite(String),
+    /// The stored value is not a version and is left as is
+    Unparseable,
+}
+
+impl Version {
+    /// Parses a version with the given strictness
+    ///
+    /// # Errors
+    ///
+    /// Returns a `version` field validation error naming the expected
+    /// format if `input` is empty, too long, or not a semantic version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use xzepr::domain::value_objects::{Version, VersionStrictness};
+    ///
+    /// let version = Version::parse(" v1.2 ", VersionStrictness::Lenient).unwrap();
+    /// assert_eq!(version.to_string(), "1.2.0");
+    /// assert!(Version::parse("v1.2", VersionStrictness::Strict).is_err());
+    /// ```
+    pub fn parse(input: &str, strictness: VersionStrictness) -> Result<Self, DomainError> {
+        if input.trim().is_empty() {
+            return Err(version_error(Message::new("validation.version_empty")));
+        }
+
+        if input.len() > MAX_VERSION_LENGTH {
+            return Err(version_error(
+                Message::new("validation.version_too_long").with("max", MAX_VERSION_LENGTH),
+            ));
+        }
+
+        let parsed = match strictness {
+            VersionStrictness::Strict => semver::Version::parse(input),
+            VersionStrictness::Lenient => semver::Version::parse(&lenient_form(input)),
+        };
+
+        parsed.map(Self).map_err(|_| {
+            version_error(Message::new("validation.version_format").with("value", input))
+        })
+    }
+
+    /// Returns the canonical form of `input`, parsed with `strictness`
+    pub fn normalize(input: &str, strictness: VersionStrictness) -> Result<String, DomainError> {
+        Self::parse(input, strictness).map(|version| version.to_string())
+    }
+
+    /// Returns the form a version filter is compared in
+    ///
+    /// Filters that parse leniently match the canonical stored value;
+    /// anything else is only trimmed, so legacy values can still be found.
+    pub fn normalize_filter(input: &str) -> String {
+        Self::normalize(input, VersionStrictness::Lenient)
+            .unwrap_or_else(|_| input.trim().to_string())
+    }
+
+    /// Decides how a version stored before normalization is migrated
+    pub fn classify_stored(stored: &str) -> StoredVersion {
+        match Self::normalize(stored, VersionStrictness::Lenient) {
+            Ok(canonical) if canonical == stored => StoredVersion::Canonical,
+            Ok(canonical) => StoredVersion::Rewrite(canonical),
+            Err(_) => StoredVersion::Unparseable,
+        }
+    }
+
+    /// Returns the inner semver version
+    pub fn as_semver(&self) -> &semver::Version {
+        &self.0
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Rewrites `input` into the form lenient parsing hands to semver
+fn lenient_form(input: &str) -> String {
+    let trimmed = input.trim();
+    let trimmed = trimmed
+        .strip_prefix('v')
+        .or_else(|| trimmed.strip_prefix('V'))
+        .unwrap_or(trimmed);
+
+    // Only the MAJOR.MINOR.PATCH core is padded; pre-release and build
+    // metadata are kept as written
+    let core_end = trimmed.find(['-', '+']).unwrap_or(trimmed.len());
+    let (core, suffix) = trimmed.split_at(core_end);
+    let padding = match core.split('.').count() {
+        1 => ".0.0",
+        2 => ".0",
+        _ => "",
+    };
+
+    format!("{}{}{}", core, padding, suffix)
+}
+
+fn version_error(message: Message) -> DomainError {
+    DomainError::ValidationError {
+        field: "version".to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lenient_normalization() {
+        let cases = [
+            ("1.0.0", "1.0.0"),
+            ("1.0", "1.0.0"),
+            ("1", "1.0.0"),
+            ("v1.0.0", "1.0.0"),
+            ("V2.1", "2.1.0"),
+            ("1.0.0-rc1 ", "1.0.0-rc1"),
+            ("  v1.2-beta.2", "1.2.0-beta.2"),
+            ("1.2+build.5", "1.2.0+build.5"),
+            ("1.2.3-rc.1+sha.abc", "1.2.3-rc.1+sha.abc"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                Version::normalize(input, VersionStrictness::Lenient).unwrap(),
+                expected,
+                "input {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_lenient_rejects_garbage() {
+        for input in ["latest", "1.x", "1.0.0.0", "v", "1..0", "01.0.0", "1.0.0-"] {
+            let err = Version::parse(input, VersionStrictness::Lenient).unwrap_err();
+            match err {
+                DomainError::ValidationError { field, message } => {
+                    assert_eq!(field, "version");
+                    assert_eq!(message.key(), "validation.version_format");
+                    assert!(
+                        message.to_string().contains("MAJOR.MINOR.PATCH"),
+                        "{}",
+                        message
+                    );
+                }
+                other => panic!("unexpected error {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_rejections() {
+        for input in ["1.0", "v1.0.0", " 1.0.0", "1.0.0 ", "1", "latest"] {
+            assert!(
+                Version::parse(input, VersionStrictness::Strict).is_err(),
+                "input {:?}",
+                input
+            );
+        }
+
+        for input in ["1.0.0", "0.4.1", "1.0.0-rc.1", "2.0.0+build.7"] {
+            assert_eq!(
+                Version::normalize(input, VersionStrictness::Strict).unwrap(),
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_and_too_long() {
+        for strictness in [VersionStrictness::Strict, VersionStrictness::Lenient] {
+            assert!(Version::parse("  ", strictness).is_err());
+            let long = format!("1.0.0-{}", "a".repeat(MAX_VERSION_LENGTH));
+            assert!(Version::parse(&long, strictness).is_err());
+        }
+    }
+
+    #[test]
+    fn test_versions_compare_semantically() {
+        let parse = |v| Version::parse(v, VersionStrictness::Lenient).unwrap();
+        assert!(parse("1.10") > parse("1.9.0"));
+        assert!(parse("1.0.0-rc.1") < parse("1.0.0"));
+        assert_eq!(parse("v1.0"), parse("1.0.0"));
+    }
+
+    #[test]
+    fn test_normalize_filter() {
+        assert_eq!(Version::normalize_filter(" v1.0 "), "1.0.0");
+        assert_eq!(Version::normalize_filter(" nightly "), "nightly");
+    }
+
+    #[test]
+    fn test_classify_stored() {
+        assert_eq!(Version::classify_stored("1.0.0"), StoredVersion::Canonical);
+        assert_eq!(
+            Version::classify_stored("v1.0"),
+            StoredVersion::Rewrite("1.0.0".to_string())
+        );
+        assert_eq!(
+            Version::classify_stored("nightly"),
+            StoredVersion::Unparseable
+        );
+    }
+}
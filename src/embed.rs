@@ -0,0 +1,483 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/embed.rs
+//! Embedding XZepr in another binary
+//!
+//! [`XzeprBuilder`] assembles the handlers, router, and background jobs
+//! that the `xzepr` server wires up in `main`, so a host service can mount
+//! XZepr next to its own routes:
+//!
+//! ```rust,no_run
+//! use axum::Router;
+//! use xzepr::embed::XzeprBuilder;
+//! use xzepr::Settings;
+//!
+//! # async fn run() -> xzepr::Result<()> {
+//! let (xzepr, handle) = XzeprBuilder::new(Settings::new()?).build().await?;
+//! let app: Router = Router::new().nest("/xzepr", xzepr);
+//!
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+//! axum::serve(listener, app).await?;
+//! handle.shutdown().await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The router only uses paths relative to where it is mounted, so it can be
+//! nested under any prefix. Kafka consumers are not part of XZepr, so there
+//! is no consumer subsystem to toggle.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Router;
+use config::ConfigError;
+use sqlx::PgPool;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use crate::api::graphql::PersistedQueryStore;
+use crate::api::middleware::{DeprecationRegistry, JwtMiddlewareState, RequestDeadlines};
+use crate::api::rest::about::AboutInfo;
+use crate::api::rest::{build_protected_router_with, ApiSubsystems, AppState};
+use crate::application::authorization::AuthorizationService;
+use crate::application::domain_events::DomainEventBus;
+use crate::application::handlers::{
+    BulkDeleteHandler, ChangeFeedHandler, EventBatchHandler, EventHandler, EventPayloadCollector,
+    EventReceiverGroupHandler, EventReceiverHandler, EventRollupReconciler, EventStatsHandler,
+    GroupCompletenessHandler, KafkaForwarder, MembershipExpiryHandler, ReceiverAutoDisableHandler,
+    ReceiverTimelineHandler, ResourceHistoryHandler, SchemaPreviewHandler, SchemaResolver,
+    SearchHandler, UserPreferencesHandler,
+};
+use crate::auth::jwt::JwtService;
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::domain::repositories::ingestion_meta_repo::EventIngestionMetaRepository;
+use crate::domain::repositories::release_completion_repo::ReleaseCompletionRepository;
+use crate::error::Result;
+use crate::infrastructure::database::{
+    PostgresEventReceiverGroupRepository, PostgresEventReceiverRepository, PostgresEventRepository,
+    PostgresEventRollupRepository, PostgresFeatureFlagRepository, PostgresMaintenanceRepository,
+    PostgresPersistedQueryRepository, PostgresReceiverTimelineRepository,
+    PostgresReleaseCompletionRepository, PostgresResourceHistoryRepository,
+    PostgresSearchRepository, PostgresUserPreferencesRepository,
+};
+use crate::infrastructure::memory::{
+    InMemoryAttestationRepository, InMemoryEventReceiverGroupRepository,
+    InMemoryEventReceiverRepository, InMemoryEventRepository, InMemoryReleaseCompletionRepository,
+    InMemoryResourceHistoryRepository, InMemoryUserPreferencesRepository,
+};
+use crate::infrastructure::messaging::producer::KafkaEventPublisher;
+use crate::infrastructure::{FeatureFlags, Job, JobRunner, MaintenanceMode};
+use crate::Settings;
+
+/// Assembles an embeddable XZepr router and the handle that controls it
+///
+/// Without a pool, receivers, groups, and events are kept in memory like
+/// the `xzepr-server` binary does. With a pool, they are stored in
+/// PostgreSQL; migrations are left to the host. Without a publisher,
+/// nothing is published to Kafka.
+pub struct XzeprBuilder {
+    settings: Settings,
+    pool: Option<PgPool>,
+    publisher: Option<Arc<KafkaEventPublisher>>,
+    subsystems: ApiSubsystems,
+    jobs_enabled: bool,
+    jobs: Vec<Arc<dyn Job>>,
+}
+
+impl XzeprBuilder {
+    /// Creates a builder serving REST and GraphQL and running background
+    /// jobs, configured by `settings`
+    pub fn new(settings: Settings) -> Self {
+        Self {
+            settings,
+            pool: None,
+            publisher: None,
+            subsystems: ApiSubsystems::default(),
+            jobs_enabled: true,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Stores data in PostgreSQL through `pool`
+    pub fn with_pool(mut self, pool: PgPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Publishes events and the system events of created receivers and
+    /// groups through `publisher`
+    pub fn with_publisher(mut self, publisher: Arc<KafkaEventPublisher>) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Serves the `/api/v1` REST routes; enabled by default
+    pub fn with_rest(mut self, enabled: bool) -> Self {
+        self.subsystems.rest = enabled;
+        self
+    }
+
+    /// Serves the `/graphql` routes; enabled by default
+    pub fn with_graphql(mut self, enabled: bool) -> Self {
+        self.subsystems.graphql = enabled;
+        self
+    }
+
+    /// Runs background jobs on their schedules; enabled by default
+    ///
+    /// Disabled jobs can still be run on demand through the admin API and
+    /// [`XzeprHandle::jobs`].
+    pub fn with_jobs(mut self, enabled: bool) -> Self {
+        self.jobs_enabled = enabled;
+        self
+    }
+
+    /// Adds a host job to the runner shared with XZepr's own jobs
+    pub fn with_job(mut self, job: Arc<dyn Job>) -> Self {
+        self.jobs.push(job);
+        self
+    }
+
+    /// Builds the router and its handle
+    ///
+    /// The router requires a JWT signed with the keys in `auth.jwt` on
+    /// every `/api/v1` route.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JWT settings are invalid, or if feature
+    /// flags or maintenance mode cannot be loaded from the database.
+    pub async fn build(self) -> Result<(Router, XzeprHandle)> {
+        let settings = self.settings;
+        let invalid_jwt =
+            |message: String| ConfigError::Message(format!("Invalid JWT settings: {}", message));
+        let jwt_config = settings.auth.jwt.to_jwt_config().map_err(invalid_jwt)?;
+        let jwt_service =
+            JwtService::from_config(jwt_config).map_err(|e| invalid_jwt(e.to_string()))?;
+
+        let event_repo: Arc<dyn EventRepository>;
+        let mut ingestion_meta: Option<Arc<dyn EventIngestionMetaRepository>> = None;
+        let receiver_repo: Arc<dyn EventReceiverRepository>;
+        let group_repo: Arc<dyn EventReceiverGroupRepository>;
+        let mut job_runner = JobRunner::new(&settings.jobs);
+        let mut watchers = Vec::new();
+        let feature_flags = FeatureFlags::new(&settings.feature_flags);
+        let maintenance = MaintenanceMode::new();
+        let (
+            history,
+            preferences,
+            change_feed_handler,
+            timeline,
+            search_handler,
+            feature_flags,
+            maintenance,
+        ) = match &self.pool {
+            Some(pool) => {
+                let events = Arc::new(PostgresEventRepository::new(pool.clone()));
+                let receivers = Arc::new(PostgresEventReceiverRepository::new(pool.clone()));
+                let groups = Arc::new(PostgresEventReceiverGroupRepository::new(pool.clone()));
+                event_repo = events.clone();
+                ingestion_meta = Some(events.clone());
+                receiver_repo = receivers.clone();
+                group_repo = groups.clone();
+
+                let refresh_interval =
+                    Duration::from_secs(settings.feature_flags.refresh_interval_seconds);
+                let feature_flags = feature_flags
+                    .with_repository(Arc::new(PostgresFeatureFlagRepository::new(pool.clone())));
+                feature_flags.refresh().await?;
+                watchers.push(feature_flags.spawn_watcher(refresh_interval));
+                let maintenance = maintenance
+                    .with_repository(Arc::new(PostgresMaintenanceRepository::new(pool.clone())));
+                maintenance.refresh().await?;
+                watchers.push(maintenance.spawn_watcher(refresh_interval));
+
+                job_runner = job_runner
+                    .register(Arc::new(
+                        EventPayloadCollector::new(events.clone()).with_interval(
+                            Duration::from_secs(settings.ingestion.payload_gc_interval_seconds),
+                        ),
+                    ))
+                    .register(Arc::new(
+                        EventRollupReconciler::new(
+                            events,
+                            Arc::new(PostgresEventRollupRepository::new(pool.clone())),
+                        )
+                        .with_lookback(chrono::Duration::hours(
+                            settings.rollup.reconcile_lookback_hours as i64,
+                        ))
+                        .with_interval(Duration::from_secs(
+                            settings.rollup.reconcile_interval_seconds,
+                        )),
+                    ));
+
+                (
+                    ResourceHistoryHandler::new(Arc::new(PostgresResourceHistoryRepository::new(
+                        pool.clone(),
+                    ))),
+                    UserPreferencesHandler::new(Arc::new(PostgresUserPreferencesRepository::new(
+                        pool.clone(),
+                    ))),
+                    ChangeFeedHandler::new(receivers, groups),
+                    Some(ReceiverTimelineHandler::new(Arc::new(
+                        PostgresReceiverTimelineRepository::new(pool.clone()),
+                    ))),
+                    Some(SearchHandler::new(Arc::new(PostgresSearchRepository::new(
+                        pool.clone(),
+                    )))),
+                    feature_flags,
+                    maintenance,
+                )
+            }
+            None => {
+                let receivers = Arc::new(InMemoryEventReceiverRepository::new());
+                let groups = Arc::new(InMemoryEventReceiverGroupRepository::new());
+                event_repo = Arc::new(InMemoryEventRepository::new());
+                receiver_repo = receivers.clone();
+                group_repo = groups.clone();
+                (
+                    ResourceHistoryHandler::new(Arc::new(
+                        InMemoryResourceHistoryRepository::default(),
+                    )),
+                    UserPreferencesHandler::new(Arc::new(
+                        InMemoryUserPreferencesRepository::default(),
+                    )),
+                    ChangeFeedHandler::new(receivers, groups),
+                    None,
+                    None,
+                    feature_flags,
+                    maintenance,
+                )
+            }
+        };
+
+        // Handlers announce stored changes in process; the Kafka forwarder
+        // publishes the system events of created receivers and groups
+        let domain_events = DomainEventBus::new();
+        if let Some(publisher) = &self.publisher {
+            watchers.push(domain_events.register(Arc::new(KafkaForwarder::new(publisher.clone()))));
+        }
+
+        let schema_resolver = SchemaResolver::new(group_repo.clone());
+        let event_handler = match &self.publisher {
+            Some(publisher) => EventHandler::with_publisher(
+                event_repo.clone(),
+                receiver_repo.clone(),
+                publisher.clone(),
+            ),
+            None => EventHandler::new(event_repo.clone(), receiver_repo.clone()),
+        }
+        .with_event_bus(domain_events.clone())
+        .with_schema_resolver(schema_resolver.clone())
+        .with_receiver_history(history.clone())
+        .with_attestations(Arc::new(InMemoryAttestationRepository::default()))
+        .with_receiver_provisioning(settings.ingestion.receiver_provisioning)
+        .with_receiver_daily_quota(settings.ingestion.receiver_daily_event_quota)
+        .with_pii_scanner(settings.ingestion.pii_scanner());
+        // Ingestion metadata, and with it PII findings, needs stored events
+        let event_handler = match ingestion_meta {
+            Some(ingestion_meta) => event_handler.with_ingestion_meta(ingestion_meta),
+            None => event_handler,
+        };
+        let receiver_handler = EventReceiverHandler::new(receiver_repo.clone())
+            .with_event_bus(domain_events.clone())
+            .with_schema_resolver(schema_resolver.clone())
+            .with_history(history.clone());
+        let receiver_handler = match timeline {
+            Some(timeline) => receiver_handler.with_timeline(timeline),
+            None => receiver_handler,
+        };
+        // Completed releases are announced once per group, across restarts
+        // only when a pool is set
+        let completions: Arc<dyn ReleaseCompletionRepository> = match &self.pool {
+            Some(pool) => Arc::new(PostgresReleaseCompletionRepository::new(pool.clone())),
+            None => Arc::new(InMemoryReleaseCompletionRepository::default()),
+        };
+        let group_completeness =
+            GroupCompletenessHandler::new(group_repo.clone(), event_repo.clone())
+                .with_completions(completions)
+                .with_event_bus(domain_events.clone());
+        watchers.push(domain_events.register(Arc::new(group_completeness.clone())));
+        let group_handler =
+            EventReceiverGroupHandler::new(group_repo.clone(), receiver_repo.clone())
+                .with_event_bus(domain_events.clone())
+                .with_schema_resolver(schema_resolver.clone())
+                .with_history(history.clone())
+                .with_completeness(group_completeness);
+        let bulk_delete_handler = BulkDeleteHandler::new(
+            receiver_repo.clone(),
+            group_repo.clone(),
+            event_repo.clone(),
+        )
+        .with_max_resources(settings.admin.bulk_delete_max_resources)
+        .with_schema_resolver(schema_resolver)
+        .with_history(history);
+
+        // Host jobs share the runner so they show up in the admin API
+        job_runner =
+            job_runner.register(Arc::new(MembershipExpiryHandler::new(group_repo.clone())));
+        // Failure rates come from the rollup, which only Postgres keeps
+        if let Some(pool) = &self.pool {
+            let event_stats = EventStatsHandler::new(
+                Arc::new(PostgresEventRepository::new(pool.clone())),
+                Arc::new(PostgresEventRollupRepository::new(pool.clone())),
+            )
+            .with_freshness(chrono::Duration::seconds(
+                settings.rollup.freshness_seconds as i64,
+            ));
+            job_runner = job_runner.register(Arc::new(
+                ReceiverAutoDisableHandler::new(receiver_handler.clone(), event_stats)
+                    .with_interval(Duration::from_secs(
+                        settings.hygiene.auto_disable_interval_seconds,
+                    )),
+            ));
+        }
+        for job in self.jobs {
+            job_runner = job_runner.register(job);
+        }
+        let jobs = Arc::new(job_runner);
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let job_handles = if self.jobs_enabled {
+            jobs.start(shutdown_rx)
+        } else {
+            Vec::new()
+        };
+
+        // Registered GraphQL queries survive restarts only with a pool
+        let persisted_queries =
+            PersistedQueryStore::new().with_capacity(settings.graphql.persisted_query_cache_size);
+        let persisted_queries = match &self.pool {
+            Some(pool) => {
+                let persisted_queries = persisted_queries.with_repository(Arc::new(
+                    PostgresPersistedQueryRepository::new(pool.clone()),
+                ));
+                persisted_queries.load().await?;
+                persisted_queries
+            }
+            None => persisted_queries,
+        };
+
+        let deprecations = Arc::new(DeprecationRegistry::new(&settings.api));
+        let state = AppState {
+            event_batch_handler: EventBatchHandler::new(event_handler.clone())
+                .with_max_events(settings.ingestion.max_batch_events),
+            event_handler: event_handler.clone(),
+            event_receiver_handler: receiver_handler.clone(),
+            event_receiver_group_handler: group_handler.clone(),
+            authorization: AuthorizationService::new(receiver_repo.clone(), group_repo.clone())
+                .with_feature_flags(feature_flags.clone()),
+            change_feed_handler,
+            user_preferences_handler: preferences,
+            feature_flags,
+            admin_summary_handler: None,
+            schema_preview_handler: SchemaPreviewHandler::new(event_repo),
+            bulk_delete_handler,
+            api_key_service: None,
+            event_poll_handler: None,
+            graphql: settings.graphql.clone(),
+            about: Some(Arc::new(AboutInfo::from_settings(&settings))),
+            jobs: Some(jobs.clone()),
+            delivery_failures: None,
+            audit_chain: None,
+            search_handler,
+            attachment_handler: None,
+            user_import_handler: None,
+            session_service: None,
+            error_format: deprecations.error_format(),
+            deprecations,
+            maintenance,
+            persisted_queries: Some(persisted_queries),
+            runtime_settings: None,
+            request_deadlines: RequestDeadlines::from_config(&settings.deadlines),
+        };
+        let router = build_protected_router_with(
+            state,
+            JwtMiddlewareState::new(jwt_service),
+            self.subsystems,
+        );
+
+        info!(
+            rest = self.subsystems.rest,
+            graphql = self.subsystems.graphql,
+            jobs = self.jobs_enabled,
+            "Embedded XZepr router built"
+        );
+        let handle = XzeprHandle {
+            event_handler,
+            receiver_handler,
+            group_handler,
+            domain_events,
+            jobs,
+            shutdown,
+            job_handles,
+            watchers,
+        };
+        Ok((router, handle))
+    }
+}
+
+/// Runtime access to an embedded XZepr
+///
+/// Gives the host the handlers behind the router, in-process change
+/// notifications, and the job runner. Call [`shutdown`](Self::shutdown)
+/// once the host has stopped serving requests.
+pub struct XzeprHandle {
+    event_handler: EventHandler,
+    receiver_handler: EventReceiverHandler,
+    group_handler: EventReceiverGroupHandler,
+    domain_events: DomainEventBus,
+    jobs: Arc<JobRunner>,
+    shutdown: watch::Sender<bool>,
+    job_handles: Vec<JoinHandle<()>>,
+    watchers: Vec<JoinHandle<()>>,
+}
+
+impl XzeprHandle {
+    /// Returns the handler serving the event routes
+    pub fn event_handler(&self) -> &EventHandler {
+        &self.event_handler
+    }
+
+    /// Returns the handler serving the receiver routes
+    pub fn receiver_handler(&self) -> &EventReceiverHandler {
+        &self.receiver_handler
+    }
+
+    /// Returns the handler serving the group routes
+    pub fn group_handler(&self) -> &EventReceiverGroupHandler {
+        &self.group_handler
+    }
+
+    /// Returns the bus announcing stored events, receivers, and groups
+    ///
+    /// Use [`DomainEventBus::subscribe`] to stream notifications or
+    /// [`DomainEventBus::register`] to attach a subscriber.
+    pub fn domain_events(&self) -> &DomainEventBus {
+        &self.domain_events
+    }
+
+    /// Returns the runner of XZepr's and the host's background jobs
+    pub fn jobs(&self) -> &Arc<JobRunner> {
+        &self.jobs
+    }
+
+    /// Stops background work
+    ///
+    /// Scheduled jobs stop; a run in progress is awaited. Feature flag,
+    /// maintenance mode, and Kafka forwarding tasks are stopped.
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        for handle in self.job_handles {
+            let _ = handle.await;
+        }
+        for watcher in self.watchers {
+            watcher.abort();
+        }
+        info!("Embedded XZepr shut down");
+    }
+}
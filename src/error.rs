@@ -6,6 +6,8 @@
 
 use thiserror::Error;
 
+use crate::i18n::Message;
+
 /// Application-wide result type
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -167,10 +169,10 @@ pub enum DomainError {
     InvalidRoleAssignment,
 
     #[error("Business rule violation: {rule}")]
-    BusinessRuleViolation { rule: String },
+    BusinessRuleViolation { rule: Message },
 
     #[error("Validation error in field '{field}': {message}")]
-    ValidationError { field: String, message: String },
+    ValidationError { field: String, message: Message },
 
     #[error("Entity not found: {entity} with id {id}")]
     NotFound { entity: String, id: String },
@@ -183,6 +185,30 @@ pub enum DomainError {
 
     #[error("Storage error: {0}")]
     StorageError(String),
+
+    #[error("System event construction failed for '{event_type}': {reason}")]
+    SystemEventConstruction { event_type: String, reason: String },
+
+    #[error("Implicit receiver provisioning is not allowed: {reason}")]
+    ReceiverProvisioningDisabled { reason: String },
+
+    #[error("Event name '{name}' uses the 'xzepr.' prefix reserved for system events")]
+    ReservedEventName { name: String },
+
+    /// Storing `requested` more events would exceed the receiver's daily
+    /// event quota, which has `remaining` events left
+    #[error(
+        "Receiver {receiver_id} has {remaining} events left of its daily quota; {requested} requested"
+    )]
+    QuotaExceeded {
+        receiver_id: String,
+        requested: u64,
+        remaining: u64,
+    },
+
+    /// The receiver's auto-disable policy stopped it accepting events
+    #[error("Receiver {receiver_id} was disabled after a sustained failure rate; re-enable it to accept events")]
+    ReceiverAutoDisabled { receiver_id: String },
 }
 
 /// Infrastructure-related errors
@@ -194,6 +220,19 @@ pub enum InfrastructureError {
     #[error("Kafka producer error: {message}")]
     KafkaProducerError { message: String },
 
+    /// A message was not delivered; `code` is the broker or librdkafka
+    /// error code and `retriable` tells callers whether resending may help
+    #[error("Kafka delivery failed ({code}): {message}")]
+    KafkaDeliveryError {
+        code: String,
+        retriable: bool,
+        message: String,
+    },
+
+    /// The publish policy required a publish the stream did not accept
+    #[error("Event stream unavailable: {message}")]
+    PublishUnavailable { message: String },
+
     #[error("Kafka consumer error: {message}")]
     KafkaConsumerError { message: String },
 
@@ -205,6 +244,10 @@ pub enum InfrastructureError {
 
     #[error("External service error: {service}")]
     ExternalServiceError { service: String },
+
+    /// The request's deadline passed before or during `operation`
+    #[error("Request deadline exceeded during {operation}")]
+    DeadlineExceeded { operation: String },
 }
 
 /// Repository-related errors
@@ -245,6 +288,12 @@ impl Error {
                 _ => StatusCode::INTERNAL_SERVER_ERROR,
             },
             Error::Authorization(_) => StatusCode::FORBIDDEN,
+            Error::Infrastructure(InfrastructureError::PublishUnavailable { .. }) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            Error::Infrastructure(InfrastructureError::DeadlineExceeded { .. }) => {
+                StatusCode::GATEWAY_TIMEOUT
+            }
             Error::Validation(_) => StatusCode::BAD_REQUEST,
             Error::NotFound { .. } => StatusCode::NOT_FOUND,
             Error::BadRequest { .. } => StatusCode::BAD_REQUEST,
@@ -253,7 +302,13 @@ impl Error {
                     StatusCode::BAD_REQUEST
                 }
                 DomainError::ReceiverNotFound | DomainError::GroupNotFound => StatusCode::NOT_FOUND,
-                DomainError::UserAlreadyExists => StatusCode::CONFLICT,
+                DomainError::UserAlreadyExists
+                | DomainError::AlreadyExists { .. }
+                | DomainError::ReceiverAutoDisabled { .. } => StatusCode::CONFLICT,
+                DomainError::SystemEventConstruction { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+                DomainError::ReceiverProvisioningDisabled { .. }
+                | DomainError::ReservedEventName { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                DomainError::QuotaExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
                 _ => StatusCode::BAD_REQUEST,
             },
             _ => StatusCode::INTERNAL_SERVER_ERROR,
@@ -263,6 +318,29 @@ impl Error {
     pub fn message(&self) -> String {
         self.to_string()
     }
+
+    /// Returns the translatable message of a validation or business-rule
+    /// error, if any
+    pub fn localizable(&self) -> Option<(Option<&str>, &Message)> {
+        match self {
+            Error::Domain(domain_err) => domain_err.localizable(),
+            _ => None,
+        }
+    }
+}
+
+impl DomainError {
+    /// Returns the field and translatable message of a validation or
+    /// business-rule error
+    pub fn localizable(&self) -> Option<(Option<&str>, &Message)> {
+        match self {
+            DomainError::ValidationError { field, message } => {
+                Some((Some(field.as_str()), message))
+            }
+            DomainError::BusinessRuleViolation { rule } => Some((None, rule)),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -294,6 +372,16 @@ mod tests {
             Error::Validation(ValidationError::InvalidEmail).status_code(),
             StatusCode::BAD_REQUEST
         );
+
+        assert_eq!(
+            Error::Domain(DomainError::QuotaExceeded {
+                receiver_id: "01JN2Z5K8XQZJQY7WZXR5VQMB0".to_string(),
+                requested: 1,
+                remaining: 0,
+            })
+            .status_code(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
     }
 
     #[test]
@@ -355,6 +443,43 @@ mod tests {
             Error::Domain(DomainError::UserAlreadyExists).status_code(),
             StatusCode::CONFLICT
         );
+        assert_eq!(
+            Error::Domain(DomainError::AlreadyExists {
+                entity: "group member".to_string(),
+                identifier: "test".to_string()
+            })
+            .status_code(),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            Error::Domain(DomainError::SystemEventConstruction {
+                event_type: "xzepr.event.receiver.created".to_string(),
+                reason: "test".to_string()
+            })
+            .status_code(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            Error::Domain(DomainError::ReceiverProvisioningDisabled {
+                reason: "test".to_string()
+            })
+            .status_code(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            Error::Domain(DomainError::ReservedEventName {
+                name: "xzepr.group.created".to_string()
+            })
+            .status_code(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            Error::Domain(DomainError::ReceiverAutoDisabled {
+                receiver_id: "01HZ".to_string()
+            })
+            .status_code(),
+            StatusCode::CONFLICT
+        );
     }
 
     #[test]
@@ -420,10 +545,18 @@ mod tests {
 
         let error = DomainError::ValidationError {
             field: "name".to_string(),
-            message: "too short".to_string(),
+            message: Message::new("validation.name_empty"),
         };
-        assert!(error.to_string().contains("name"));
-        assert!(error.to_string().contains("too short"));
+        assert_eq!(
+            error.to_string(),
+            "Validation error in field 'name': Name cannot be empty"
+        );
+        assert_eq!(
+            error
+                .localizable()
+                .map(|(field, message)| (field, message.key())),
+            Some((Some("name"), "validation.name_empty"))
+        );
     }
 
     #[test]
@@ -476,4 +609,12 @@ mod tests {
         };
         assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
     }
+
+    #[test]
+    fn test_publish_unavailable_status_code() {
+        let error = Error::Infrastructure(InfrastructureError::PublishUnavailable {
+            message: "broker down".to_string(),
+        });
+        assert_eq!(error.status_code(), StatusCode::SERVICE_UNAVAILABLE);
+    }
 }
@@ -0,0 +1,187 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/fixtures/event.rs
+
+use super::{seeded_owner, seeded_time, seeded_ulid, IdKind};
+use crate::domain::entities::event::{CreateEventParams, DatabaseEventFields, Event};
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::domain::value_objects::{EventId, EventReceiverId, UserId};
+use crate::error::DomainError;
+use serde_json::{json, Value as JsonValue};
+
+/// Builder for events
+///
+/// Defaults to a successful `test.event` at version `1.0.0` with an empty
+/// payload. Without [`Self::receiver`], the event names the receiver a
+/// [`ReceiverFixture`](super::ReceiverFixture) with the same seed gets, or
+/// a fresh id when unseeded.
+#[derive(Debug, Clone)]
+pub struct EventFixture {
+    name: String,
+    version: String,
+    release: String,
+    platform_id: String,
+    package: String,
+    description: String,
+    payload: JsonValue,
+    success: bool,
+    receiver_id: Option<EventReceiverId>,
+    owner_id: Option<UserId>,
+    seed: Option<u64>,
+}
+
+impl Default for EventFixture {
+    fn default() -> Self {
+        Self {
+            name: "test.event".to_string(),
+            version: "1.0.0".to_string(),
+            release: "1.0.0".to_string(),
+            platform_id: "linux".to_string(),
+            package: "app".to_string(),
+            description: "A test event".to_string(),
+            payload: json!({}),
+            success: true,
+            receiver_id: None,
+            owner_id: None,
+            seed: None,
+        }
+    }
+}
+
+impl EventFixture {
+    /// Creates a builder with the defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn release(mut self, release: impl Into<String>) -> Self {
+        self.release = release.into();
+        self
+    }
+
+    pub fn platform_id(mut self, platform_id: impl Into<String>) -> Self {
+        self.platform_id = platform_id.into();
+        self
+    }
+
+    pub fn package(mut self, package: impl Into<String>) -> Self {
+        self.package = package.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn payload(mut self, payload: JsonValue) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    pub fn success(mut self, success: bool) -> Self {
+        self.success = success;
+        self
+    }
+
+    /// Sends the event to `receiver`
+    pub fn receiver(self, receiver: &EventReceiver) -> Self {
+        self.receiver_id(receiver.id())
+    }
+
+    pub fn receiver_id(mut self, receiver_id: EventReceiverId) -> Self {
+        self.receiver_id = Some(receiver_id);
+        self
+    }
+
+    pub fn owner(mut self, owner_id: UserId) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
+    /// Derives the id, owner, and creation time from `seed`
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Builds the event through [`Event::new`]
+    pub fn try_build(self) -> Result<Event, DomainError> {
+        let event = Event::new(CreateEventParams {
+            name: self.name,
+            version: self.version,
+            release: self.release,
+            platform_id: self.platform_id,
+            package: self.package,
+            description: self.description,
+            payload: self.payload,
+            success: self.success,
+            receiver_id: self.receiver_id.unwrap_or_else(|| {
+                self.seed.map_or_else(EventReceiverId::new, |seed| {
+                    EventReceiverId::from_ulid(seeded_ulid(IdKind::Receiver, seed))
+                })
+            }),
+            owner_id: self.owner_id.unwrap_or_else(|| seeded_owner(self.seed)),
+        })?;
+        let Some(seed) = self.seed else {
+            return Ok(event);
+        };
+
+        Ok(Event::from_database(DatabaseEventFields {
+            id: EventId::from_ulid(seeded_ulid(IdKind::Event, seed)),
+            name: event.name().to_string(),
+            version: event.version().to_string(),
+            release: event.release().to_string(),
+            platform_id: event.platform_id().to_string(),
+            package: event.package().to_string(),
+            description: event.description().to_string(),
+            payload: event.payload().clone(),
+            success: event.success(),
+            event_receiver_id: event.event_receiver_id(),
+            owner_id: event.owner_id(),
+            origin: event.origin(),
+            resource_version: event.resource_version(),
+            created_at: seeded_time(seed),
+        }))
+    }
+
+    /// Builds the event
+    ///
+    /// # Panics
+    ///
+    /// Panics if the event is invalid; use [`Self::try_build`] to test
+    /// validation.
+    pub fn build(self) -> Event {
+        self.try_build()
+            .unwrap_or_else(|e| panic!("invalid event fixture: {}", e))
+    }
+
+    /// Builds the event and saves it into `repository`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the event is invalid or cannot be saved.
+    pub async fn persist<R>(self, repository: &R) -> Event
+    where
+        R: EventRepository + ?Sized,
+    {
+        let event = self.build();
+        repository
+            .save(&event)
+            .await
+            .unwrap_or_else(|e| panic!("failed to persist event fixture: {}", e));
+        event
+    }
+}
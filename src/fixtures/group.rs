@@ -0,0 +1,189 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/fixtures/group.rs
+
+use super::{seeded_owner, seeded_time, seeded_ulid, IdKind};
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::{EventReceiverGroup, EventReceiverGroupData};
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId, UserId};
+use crate::error::DomainError;
+use serde_json::Value as JsonValue;
+
+/// Builder for event receiver groups
+///
+/// Defaults to an enabled, empty `webhook_group` named `Test Group` at
+/// version `1.0.0`, without a default schema or dedicated topic.
+#[derive(Debug, Clone)]
+pub struct GroupFixture {
+    name: String,
+    group_type: String,
+    version: String,
+    description: String,
+    enabled: bool,
+    receiver_ids: Vec<EventReceiverId>,
+    optional_receiver_ids: Vec<EventReceiverId>,
+    default_schema: Option<JsonValue>,
+    dedicated_topic: Option<String>,
+    owner_id: Option<UserId>,
+    seed: Option<u64>,
+}
+
+impl Default for GroupFixture {
+    fn default() -> Self {
+        Self {
+            name: "Test Group".to_string(),
+            group_type: "webhook_group".to_string(),
+            version: "1.0.0".to_string(),
+            description: "A test group".to_string(),
+            enabled: true,
+            receiver_ids: Vec::new(),
+            optional_receiver_ids: Vec::new(),
+            default_schema: None,
+            dedicated_topic: None,
+            owner_id: None,
+            seed: None,
+        }
+    }
+}
+
+impl GroupFixture {
+    /// Creates a builder with the defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn group_type(mut self, group_type: impl Into<String>) -> Self {
+        self.group_type = group_type.into();
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Adds `receiver` as a required member
+    pub fn receiver(self, receiver: &EventReceiver) -> Self {
+        self.receiver_id(receiver.id())
+    }
+
+    pub fn receiver_id(mut self, receiver_id: EventReceiverId) -> Self {
+        self.receiver_ids.push(receiver_id);
+        self
+    }
+
+    /// Adds `receiver` as a member the group's releases do not wait for
+    pub fn optional_receiver(self, receiver: &EventReceiver) -> Self {
+        self.optional_receiver_id(receiver.id())
+    }
+
+    pub fn optional_receiver_id(mut self, receiver_id: EventReceiverId) -> Self {
+        self.receiver_ids.push(receiver_id);
+        self.optional_receiver_ids.push(receiver_id);
+        self
+    }
+
+    pub fn default_schema(mut self, schema: JsonValue) -> Self {
+        self.default_schema = Some(schema);
+        self
+    }
+
+    pub fn dedicated_topic(mut self, topic: impl Into<String>) -> Self {
+        self.dedicated_topic = Some(topic.into());
+        self
+    }
+
+    pub fn owner(mut self, owner_id: UserId) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
+    /// Derives the id, owner, and timestamps from `seed`
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Builds the group through [`EventReceiverGroup::new`] and its
+    /// validating `with_*` methods
+    pub fn try_build(self) -> Result<EventReceiverGroup, DomainError> {
+        let group = EventReceiverGroup::new(
+            self.name,
+            self.group_type,
+            self.version,
+            self.description,
+            self.enabled,
+            self.receiver_ids,
+            self.owner_id.unwrap_or_else(|| seeded_owner(self.seed)),
+        )?
+        .with_optional_receivers(self.optional_receiver_ids)?
+        .with_default_schema(self.default_schema)?
+        .with_dedicated_topic(self.dedicated_topic)?;
+        let Some(seed) = self.seed else {
+            return Ok(group);
+        };
+
+        let created_at = seeded_time(seed);
+        EventReceiverGroup::from_existing(EventReceiverGroupData {
+            id: EventReceiverGroupId::from_ulid(seeded_ulid(IdKind::Group, seed)),
+            name: group.name().to_string(),
+            group_type: group.group_type().to_string(),
+            version: group.version().to_string(),
+            description: group.description().to_string(),
+            enabled: group.enabled(),
+            event_receiver_ids: group.event_receiver_ids().to_vec(),
+            optional_receiver_ids: group.optional_receiver_ids().to_vec(),
+            default_schema: group.default_schema().cloned(),
+            dedicated_topic: group.dedicated_topic().map(str::to_string),
+            owner_id: group.owner_id(),
+            resource_version: group.resource_version(),
+            created_at,
+            updated_at: created_at,
+        })
+    }
+
+    /// Builds the group
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group is invalid; use [`Self::try_build`] to test
+    /// validation.
+    pub fn build(self) -> EventReceiverGroup {
+        self.try_build()
+            .unwrap_or_else(|e| panic!("invalid group fixture: {}", e))
+    }
+
+    /// Builds the group and saves it into `repository`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group is invalid or cannot be saved.
+    pub async fn persist<R>(self, repository: &R) -> EventReceiverGroup
+    where
+        R: EventReceiverGroupRepository + ?Sized,
+    {
+        let group = self.build();
+        repository
+            .save(&group)
+            .await
+            .unwrap_or_else(|e| panic!("failed to persist group fixture: {}", e));
+        group
+    }
+}
@@ -0,0 +1,266 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/fixtures/mod.rs
+
+//! Fluent builders for domain entities in tests
+//!
+//! Every builder starts from sensible defaults and goes through the
+//! entity's real constructor, so fixtures obey the same invariants as
+//! production data. Given a seed, ids and timestamps are deterministic,
+//! which keeps snapshot tests stable.
+//!
+//! ```ignore
+//! let receiver = ReceiverFixture::new().name("deploys").seed(1).build();
+//! let event = EventFixture::new()
+//!     .receiver(&receiver)
+//!     .success(false)
+//!     .payload(json!({"stage": "canary"}))
+//!     .build();
+//! ```
+//!
+//! The module is compiled for unit tests, and for integration tests through
+//! the `test-util` feature.
+
+mod event;
+mod group;
+mod receiver;
+
+pub use event::EventFixture;
+pub use group::GroupFixture;
+pub use receiver::ReceiverFixture;
+
+use crate::domain::entities::event::Event;
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
+use crate::domain::repositories::event_repo::EventRepository;
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use ulid::Ulid;
+
+/// Creation time of a fixture seeded with `0`, 2025-01-01T00:00:00Z
+///
+/// Each seed adds one millisecond, so seeded fixtures sort by seed.
+pub const FIXTURE_EPOCH_MS: u64 = 1_735_689_600_000;
+
+/// Kinds of seeded ids; the same seed gives each kind a different id
+#[derive(Debug, Clone, Copy)]
+enum IdKind {
+    Event = 1,
+    Receiver = 2,
+    Group = 3,
+    User = 4,
+}
+
+/// Returns the ULID of a seeded fixture
+fn seeded_ulid(kind: IdKind, seed: u64) -> Ulid {
+    let state = seed ^ ((kind as u64) << 56);
+    let random = ((splitmix64(state) as u128) << 64) | splitmix64(!state) as u128;
+    Ulid::from_parts(FIXTURE_EPOCH_MS + seed, random)
+}
+
+/// Returns the creation time of a seeded fixture
+fn seeded_time(seed: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis((FIXTURE_EPOCH_MS + seed) as i64)
+        .expect("fixture seed out of range")
+}
+
+/// Returns the owner of a seeded fixture
+fn seeded_owner(seed: Option<u64>) -> UserId {
+    seed.map_or_else(UserId::new, |seed| {
+        UserId::from_ulid(seeded_ulid(IdKind::User, seed))
+    })
+}
+
+fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Receivers, groups, and events saved together
+///
+/// Entities are saved in dependency order: receivers, then the groups
+/// holding them, then their events.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureGraph {
+    pub receivers: Vec<EventReceiver>,
+    pub groups: Vec<EventReceiverGroup>,
+    pub events: Vec<Event>,
+}
+
+impl FixtureGraph {
+    /// Creates an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a receiver
+    pub fn receiver(mut self, receiver: EventReceiver) -> Self {
+        self.receivers.push(receiver);
+        self
+    }
+
+    /// Adds a group
+    pub fn group(mut self, group: EventReceiverGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Adds an event
+    pub fn event(mut self, event: Event) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Saves the graph into the repositories
+    ///
+    /// # Panics
+    ///
+    /// Panics if a repository fails to save an entity.
+    pub async fn persist(
+        &self,
+        receivers: &dyn EventReceiverRepository,
+        groups: &dyn EventReceiverGroupRepository,
+        events: &dyn EventRepository,
+    ) {
+        for receiver in &self.receivers {
+            receivers
+                .save(receiver)
+                .await
+                .unwrap_or_else(|e| panic!("failed to persist receiver fixture: {}", e));
+        }
+        for group in &self.groups {
+            groups
+                .save(group)
+                .await
+                .unwrap_or_else(|e| panic!("failed to persist group fixture: {}", e));
+        }
+        for event in &self.events {
+            events
+                .save(event)
+                .await
+                .unwrap_or_else(|e| panic!("failed to persist event fixture: {}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::memory::{
+        InMemoryEventReceiverGroupRepository, InMemoryEventReceiverRepository,
+        InMemoryEventRepository,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn test_seeded_fixtures_are_deterministic() {
+        let first = ReceiverFixture::new().seed(7).build();
+        let second = ReceiverFixture::new().seed(7).build();
+        assert_eq!(first, second);
+        assert_eq!(first.id().as_str(), second.id().as_str());
+        assert_eq!(first.created_at(), seeded_time(7));
+
+        let other = ReceiverFixture::new().seed(8).build();
+        assert_ne!(first.id(), other.id());
+        assert!(first.id().as_str() < other.id().as_str());
+
+        // The same seed gives each kind of entity its own id
+        let group = GroupFixture::new().receiver(&first).seed(7).build();
+        assert_ne!(group.id().as_ulid(), first.id().as_ulid());
+        assert_eq!(group.owner_id(), first.owner_id());
+
+        let event = EventFixture::new().receiver(&first).seed(7).build();
+        let again = EventFixture::new().receiver(&first).seed(7).build();
+        assert_eq!(event.id(), again.id());
+        assert_eq!(event.created_at(), again.created_at());
+        assert_ne!(event.id().as_ulid(), first.id().as_ulid());
+
+        // Unseeded fixtures get fresh ids
+        assert_ne!(
+            ReceiverFixture::new().build().id(),
+            ReceiverFixture::new().build().id()
+        );
+    }
+
+    #[test]
+    fn test_builders_keep_constructor_invariants() {
+        assert!(ReceiverFixture::new().name("").try_build().is_err());
+        assert!(ReceiverFixture::new()
+            .schema(json!("not an object"))
+            .try_build()
+            .is_err());
+        assert!(ReceiverFixture::new()
+            .seed(1)
+            .version("nope")
+            .try_build()
+            .is_err());
+        assert!(EventFixture::new()
+            .payload(json!([1, 2]))
+            .try_build()
+            .is_err());
+        let receiver = ReceiverFixture::new().build();
+        assert!(GroupFixture::new()
+            .receiver(&receiver)
+            .receiver(&receiver)
+            .try_build()
+            .is_err());
+
+        // Versions are normalized as they are by the constructors
+        let receiver = ReceiverFixture::new().version("v2").seed(3).build();
+        assert_eq!(receiver.version(), "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_graph_persists_into_repositories() {
+        let receiver = ReceiverFixture::new().name("deploys").seed(1).build();
+        let optional = ReceiverFixture::new().name("scans").seed(2).build();
+        let group = GroupFixture::new()
+            .receiver(&receiver)
+            .optional_receiver(&optional)
+            .seed(3)
+            .build();
+        let event = EventFixture::new()
+            .receiver(&receiver)
+            .success(false)
+            .payload(json!({"stage": "canary"}))
+            .seed(4)
+            .build();
+
+        let receivers = InMemoryEventReceiverRepository::new();
+        let groups = InMemoryEventReceiverGroupRepository::new();
+        let events = InMemoryEventRepository::new();
+        FixtureGraph::new()
+            .receiver(receiver.clone())
+            .receiver(optional.clone())
+            .group(group.clone())
+            .event(event.clone())
+            .persist(&receivers, &groups, &events)
+            .await;
+
+        assert_eq!(
+            receivers.find_by_id(receiver.id()).await.unwrap(),
+            Some(receiver.clone())
+        );
+        let stored = groups.find_by_id(group.id()).await.unwrap().unwrap();
+        assert_eq!(stored.event_receiver_ids(), [receiver.id(), optional.id()]);
+        assert_eq!(stored.optional_receiver_ids(), [optional.id()]);
+        let stored = events.find_by_id(event.id()).await.unwrap().unwrap();
+        assert!(!stored.success());
+        assert_eq!(stored.event_receiver_id(), receiver.id());
+
+        let persisted = ReceiverFixture::new()
+            .name("audits")
+            .persist(&receivers)
+            .await;
+        assert!(receivers
+            .find_by_id(persisted.id())
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
@@ -0,0 +1,147 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/fixtures/receiver.rs
+
+use super::{seeded_owner, seeded_time, seeded_ulid, IdKind};
+use crate::domain::entities::event_receiver::{EventReceiver, EventReceiverData};
+use crate::domain::repositories::event_receiver_repo::EventReceiverRepository;
+use crate::domain::value_objects::{EventReceiverId, UserId};
+use crate::error::DomainError;
+use serde_json::{json, Value as JsonValue};
+
+/// Builder for event receivers
+///
+/// Defaults to a `webhook` receiver named `Test Receiver` at version
+/// `1.0.0` accepting any object.
+#[derive(Debug, Clone)]
+pub struct ReceiverFixture {
+    name: String,
+    receiver_type: String,
+    version: String,
+    description: String,
+    schema: JsonValue,
+    owner_id: Option<UserId>,
+    seed: Option<u64>,
+}
+
+impl Default for ReceiverFixture {
+    fn default() -> Self {
+        Self {
+            name: "Test Receiver".to_string(),
+            receiver_type: "webhook".to_string(),
+            version: "1.0.0".to_string(),
+            description: "A test receiver".to_string(),
+            schema: json!({"type": "object"}),
+            owner_id: None,
+            seed: None,
+        }
+    }
+}
+
+impl ReceiverFixture {
+    /// Creates a builder with the defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn receiver_type(mut self, receiver_type: impl Into<String>) -> Self {
+        self.receiver_type = receiver_type.into();
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn schema(mut self, schema: JsonValue) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    pub fn owner(mut self, owner_id: UserId) -> Self {
+        self.owner_id = Some(owner_id);
+        self
+    }
+
+    /// Derives the id, owner, and timestamps from `seed`
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Builds the receiver through [`EventReceiver::new`]
+    pub fn try_build(self) -> Result<EventReceiver, DomainError> {
+        let receiver = EventReceiver::new(
+            self.name,
+            self.receiver_type,
+            self.version,
+            self.description,
+            self.schema,
+            self.owner_id.unwrap_or_else(|| seeded_owner(self.seed)),
+        )?;
+        let Some(seed) = self.seed else {
+            return Ok(receiver);
+        };
+
+        let created_at = seeded_time(seed);
+        EventReceiver::from_existing(EventReceiverData {
+            id: EventReceiverId::from_ulid(seeded_ulid(IdKind::Receiver, seed)),
+            name: receiver.name().to_string(),
+            receiver_type: receiver.receiver_type().to_string(),
+            version: receiver.version().to_string(),
+            description: receiver.description().to_string(),
+            schema: receiver.schema().clone(),
+            fingerprint: receiver.fingerprint().to_string(),
+            owner_id: receiver.owner_id(),
+            resource_version: receiver.resource_version(),
+            sample_rate: receiver.sample_rate(),
+            allowed_event_names: receiver.allowed_event_names().cloned(),
+            auto_disable_policy: receiver.auto_disable_policy().cloned(),
+            auto_disabled: receiver.auto_disabled().cloned(),
+            auto_disable_window_start: receiver.auto_disable_window_start(),
+            transform: receiver.transform().cloned(),
+            created_at,
+            updated_at: created_at,
+        })
+    }
+
+    /// Builds the receiver
+    ///
+    /// # Panics
+    ///
+    /// Panics if the receiver is invalid; use [`Self::try_build`] to test
+    /// validation.
+    pub fn build(self) -> EventReceiver {
+        self.try_build()
+            .unwrap_or_else(|e| panic!("invalid receiver fixture: {}", e))
+    }
+
+    /// Builds the receiver and saves it into `repository`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the receiver is invalid or cannot be saved.
+    pub async fn persist<R>(self, repository: &R) -> EventReceiver
+    where
+        R: EventReceiverRepository + ?Sized,
+    {
+        let receiver = self.build();
+        repository
+            .save(&receiver)
+            .await
+            .unwrap_or_else(|e| panic!("failed to persist receiver fixture: {}", e));
+        receiver
+    }
+}
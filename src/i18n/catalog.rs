@@ -0,0 +1,328 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/i18n/catalog.rs
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::message::{Message, MessageParams};
+
+/// Locale every lookup falls back to
+pub const DEFAULT_LOCALE: &str = "en";
+
+const BUNDLED: [(&str, &str); 2] = [
+    ("en", include_str!("locales/en.json")),
+    ("de", include_str!("locales/de.json")),
+];
+
+/// Errors loading locale files
+#[derive(Error, Debug)]
+pub enum CatalogError {
+    #[error("Failed to read locale file {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid locale file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+/// Message templates keyed by locale, then by message key
+///
+/// Templates use `{name}` placeholders. Locale tags are stored lowercase.
+#[derive(Debug, Clone, Default)]
+pub struct MessageCatalogs {
+    locales: HashMap<String, HashMap<String, String>>,
+}
+
+impl MessageCatalogs {
+    /// Returns the catalogs embedded in the binary
+    pub fn bundled() -> Self {
+        let mut catalogs = Self::default();
+        for (locale, source) in BUNDLED {
+            let templates = serde_json::from_str(source)
+                .unwrap_or_else(|e| panic!("bundled locale '{}' is invalid: {}", locale, e));
+            catalogs.merge(locale, templates);
+        }
+        catalogs
+    }
+
+    /// Loads every `<locale>.json` file in `dir`
+    ///
+    /// Each file is a flat JSON object of message keys to templates. Keys
+    /// are merged into the locale's existing catalog, so a file only needs
+    /// the keys it adds or overrides. Returns the loaded locale tags.
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> Result<Vec<String>, CatalogError> {
+        let dir = dir.as_ref();
+        let entries = std::fs::read_dir(dir).map_err(|source| CatalogError::Io {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|source| CatalogError::Io {
+                    path: dir.to_path_buf(),
+                    source,
+                })?
+                .path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut loaded = Vec::new();
+        for path in paths {
+            let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let locale = locale.to_ascii_lowercase();
+            let source = std::fs::read_to_string(&path).map_err(|source| CatalogError::Io {
+                path: path.clone(),
+                source,
+            })?;
+            let templates = serde_json::from_str(&source)
+                .map_err(|source| CatalogError::Parse { path, source })?;
+            self.merge(&locale, templates);
+            loaded.push(locale);
+        }
+
+        Ok(loaded)
+    }
+
+    fn merge(&mut self, locale: &str, templates: HashMap<String, String>) {
+        self.locales
+            .entry(locale.to_ascii_lowercase())
+            .or_default()
+            .extend(templates);
+    }
+
+    /// Returns true when a catalog exists for `locale`
+    pub fn has_locale(&self, locale: &str) -> bool {
+        self.locales.contains_key(&locale.to_ascii_lowercase())
+    }
+
+    /// Picks the best available locale for an `Accept-Language` header
+    ///
+    /// Language ranges are tried in order of their `q` weight; a range such
+    /// as `de-AT` also matches a `de` catalog. Falls back to
+    /// [`DEFAULT_LOCALE`] when the header is absent or nothing matches.
+    pub fn negotiate(&self, accept_language: Option<&str>) -> &str {
+        let mut ranges: Vec<(String, f32)> = accept_language
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let tag = parts.next()?.trim().to_ascii_lowercase();
+                let quality = parts
+                    .filter_map(|param| param.trim().strip_prefix("q="))
+                    .find_map(|q| q.trim().parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+            })
+            .collect();
+        // Stable sort keeps header order among equal weights
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        for (tag, _) in ranges {
+            if tag == "*" {
+                break;
+            }
+            let primary = tag.split('-').next().unwrap_or_default();
+            for candidate in [tag.as_str(), primary] {
+                if let Some((locale, _)) = self.locales.get_key_value(candidate) {
+                    return locale;
+                }
+            }
+        }
+
+        DEFAULT_LOCALE
+    }
+
+    /// Renders `message` in `locale`
+    ///
+    /// Falls back to the English template when the locale lacks the key,
+    /// and to the key itself when no catalog has it.
+    pub fn render(&self, locale: &str, message: &Message) -> String {
+        self.resolve(locale, message.key(), message.params())
+    }
+
+    /// Renders the template for `key` in `locale` with `params`
+    pub fn resolve(&self, locale: &str, key: &str, params: &MessageParams) -> String {
+        let template = self
+            .template(locale, key)
+            .or_else(|| self.template(DEFAULT_LOCALE, key))
+            .unwrap_or(key);
+        interpolate(template, params)
+    }
+
+    fn template(&self, locale: &str, key: &str) -> Option<&str> {
+        self.locales
+            .get(&locale.to_ascii_lowercase())
+            .and_then(|templates| templates.get(key))
+            .map(String::as_str)
+    }
+}
+
+/// Replaces `{name}` placeholders with parameter values
+///
+/// Placeholders without a matching parameter are left as written.
+fn interpolate(template: &str, params: &MessageParams) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => match params.get(&after[..end]) {
+                Some(value) => {
+                    output.push_str(value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    output.push('{');
+                    rest = after;
+                }
+            },
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    output.push_str(rest);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_locales_have_the_same_keys() {
+        let catalogs = MessageCatalogs::bundled();
+        let mut en: Vec<_> = catalogs.locales["en"].keys().collect();
+        let mut de: Vec<_> = catalogs.locales["de"].keys().collect();
+        en.sort();
+        de.sort();
+        assert_eq!(en, de);
+    }
+
+    #[test]
+    fn test_accept_language_selects_translation() {
+        let catalogs = MessageCatalogs::bundled();
+        let message = Message::new("validation.name_empty");
+
+        let locale = catalogs.negotiate(Some("de-DE,de;q=0.9,en;q=0.8"));
+        assert_eq!(locale, "de");
+        assert_eq!(
+            catalogs.render(locale, &message),
+            "Der Name darf nicht leer sein"
+        );
+
+        // Weights win over header order
+        assert_eq!(catalogs.negotiate(Some("en;q=0.3, de;q=0.7")), "de");
+        assert_eq!(catalogs.negotiate(Some("fr-CA, fr;q=0.9")), "en");
+        assert_eq!(catalogs.negotiate(Some("de;q=0, *")), "en");
+        assert_eq!(catalogs.negotiate(None), "en");
+    }
+
+    #[test]
+    fn test_missing_key_falls_back_to_english_then_key() {
+        let mut catalogs = MessageCatalogs::bundled();
+        catalogs.merge("fr", HashMap::new());
+
+        let message = Message::new("validation.name_empty");
+        assert_eq!(catalogs.negotiate(Some("fr")), "fr");
+        assert_eq!(catalogs.render("fr", &message), "Name cannot be empty");
+
+        let unknown = Message::new("validation.not_in_any_catalog");
+        assert_eq!(
+            catalogs.render("de", &unknown),
+            "validation.not_in_any_catalog"
+        );
+    }
+
+    #[test]
+    fn test_params_are_interpolated() {
+        let catalogs = MessageCatalogs::bundled();
+        let message = Message::new("rule.bulk_delete_limit")
+            .with("selected", 250)
+            .with("max", 100);
+
+        assert_eq!(
+            catalogs.render("en", &message),
+            "Bulk delete selects 250 resources; at most 100 may be deleted per call"
+        );
+        assert_eq!(
+            catalogs.render("de", &message),
+            "Die Massenlöschung wählt 250 Ressourcen aus; pro Aufruf dürfen höchstens 100 gelöscht werden"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_leaves_unknown_placeholders() {
+        let mut params = MessageParams::new();
+        params.insert("max", 5);
+        assert_eq!(interpolate("{max} of {total} {", &params), "5 of {total} {");
+    }
+
+    #[test]
+    fn test_load_dir_adds_and_overrides_locales() {
+        let dir = std::env::temp_dir().join(format!("xzepr-locales-{}", ulid::Ulid::new()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("fr.json"),
+            r#"{ "validation.limit_range": "La limite doit être comprise entre 1 et {max}" }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("de.json"),
+            r#"{ "validation.name_empty": "Bitte einen Namen angeben" }"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("README.txt"), "ignored").unwrap();
+
+        let mut catalogs = MessageCatalogs::bundled();
+        let loaded = catalogs.load_dir(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded, vec!["de".to_string(), "fr".to_string()]);
+        let limit = Message::new("validation.limit_range").with("max", 1000);
+        assert_eq!(
+            catalogs.render("fr", &limit),
+            "La limite doit être comprise entre 1 et 1000"
+        );
+        assert_eq!(
+            catalogs.render("de", &Message::new("validation.name_empty")),
+            "Bitte einen Namen angeben"
+        );
+        // Keys not in the override file keep the bundled translation
+        assert_eq!(
+            catalogs.render("de", &Message::new("validation.type_empty")),
+            "Der Typ darf nicht leer sein"
+        );
+    }
+
+    #[test]
+    fn test_load_dir_rejects_invalid_files() {
+        let dir = std::env::temp_dir().join(format!("xzepr-locales-{}", ulid::Ulid::new()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("es.json"), "[1, 2]").unwrap();
+
+        let result = MessageCatalogs::bundled().load_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(result, Err(CatalogError::Parse { .. })));
+    }
+}
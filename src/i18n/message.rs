@@ -0,0 +1,108 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/i18n/message.rs
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::{catalogs, DEFAULT_LOCALE};
+
+/// Named values interpolated into a message template
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MessageParams(BTreeMap<String, String>);
+
+impl MessageParams {
+    /// Creates an empty parameter set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `name` to the display form of `value`
+    pub fn insert(&mut self, name: impl Into<String>, value: impl fmt::Display) {
+        self.0.insert(name.into(), value.to_string());
+    }
+
+    /// Returns the value of `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Returns true when no parameters are set
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// A translatable message: a stable catalog key and its parameters
+///
+/// `Display` renders the English text, so a `Message` can stand in for a
+/// plain string in error types and logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    key: &'static str,
+    params: MessageParams,
+}
+
+impl Message {
+    /// Creates a message without parameters
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            params: MessageParams::new(),
+        }
+    }
+
+    /// Adds a parameter
+    pub fn with(mut self, name: &str, value: impl fmt::Display) -> Self {
+        self.params.insert(name, value);
+        self
+    }
+
+    /// Returns the catalog key
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Returns the parameters
+    pub fn params(&self) -> &MessageParams {
+        &self.params
+    }
+
+    /// Renders the message in `locale`, falling back to English
+    pub fn localize(&self, locale: &str) -> String {
+        catalogs().render(locale, self)
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localize(DEFAULT_LOCALE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_renders_english() {
+        let message = Message::new("validation.limit_range").with("max", 1000);
+        assert_eq!(message.to_string(), "Limit must be between 1 and 1000");
+        assert_eq!(message.key(), "validation.limit_range");
+        assert_eq!(message.params().get("max"), Some("1000"));
+    }
+
+    #[test]
+    fn test_params_serialize_as_map() {
+        let message = Message::new("validation.unknown_value")
+            .with("value", "latest")
+            .with("options", "asc, desc");
+        assert_eq!(
+            serde_json::to_value(message.params()).unwrap(),
+            serde_json::json!({ "options": "asc, desc", "value": "latest" })
+        );
+    }
+}
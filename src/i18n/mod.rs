@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Message catalogs for user-facing error messages
+//!
+//! Validation and business-rule errors carry a [`Message`]: a stable key
+//! such as `validation.name_empty` plus named parameters. The English text
+//! is rendered from the bundled `en` catalog, so logs and `Display` output
+//! are unchanged, while the API layer resolves the same key against the
+//! locale negotiated from the `Accept-Language` header.
+//!
+//! English and German catalogs are embedded in the binary. Additional
+//! `<locale>.json` files are loaded from `i18n.locales_dir` at startup; a
+//! file for an existing locale overrides individual keys.
+//!
+//! # Examples
+//!
+//! ```
+//! use xzepr::i18n::{Message, MessageCatalogs};
+//!
+//! let catalogs = MessageCatalogs::bundled();
+//! let message = Message::new("validation.description_too_long").with("max", 1000);
+//!
+//! assert_eq!(
+//!     catalogs.render("en", &message),
+//!     "Description cannot exceed 1000 characters"
+//! );
+//! assert_eq!(catalogs.negotiate(Some("de-AT, en;q=0.5")), "de");
+//! ```
+
+pub mod catalog;
+pub mod message;
+
+pub use catalog::{CatalogError, MessageCatalogs, DEFAULT_LOCALE};
+pub use message::{Message, MessageParams};
+
+use std::sync::OnceLock;
+
+static CATALOGS: OnceLock<MessageCatalogs> = OnceLock::new();
+
+/// Installs the process-wide catalogs
+///
+/// Call once at startup, before the first message is rendered. Returns the
+/// catalogs unchanged if they were already installed or already read, in
+/// which case the bundled catalogs stay in effect.
+pub fn install(catalogs: MessageCatalogs) -> Result<(), MessageCatalogs> {
+    CATALOGS.set(catalogs)
+}
+
+/// Returns the process-wide catalogs, the bundled ones if none were
+/// installed
+pub fn catalogs() -> &'static MessageCatalogs {
+    CATALOGS.get_or_init(MessageCatalogs::bundled)
+}
@@ -0,0 +1,157 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/archive/filesystem.rs
+
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use tracing::warn;
+
+use super::{encode_segment, find_in_segment, segment_name};
+use crate::domain::entities::event::Event;
+use crate::domain::repositories::event_archive_repo::{ArchiveSegmentKey, ArchiveStore};
+use crate::domain::value_objects::EventId;
+use crate::error::{DomainError, Result};
+
+/// Archive store writing segments below a local directory
+///
+/// Segments are written to a temporary file and renamed into place, so a
+/// reader never sees a partially written segment.
+#[derive(Debug, Clone)]
+pub struct FilesystemArchiveStore {
+    root: PathBuf,
+}
+
+impl FilesystemArchiveStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Resolves a segment name below the root directory
+    ///
+    /// Segment names come from the archive index, so anything that could
+    /// escape the root is rejected.
+    fn segment_path(&self, segment: &str) -> Result<PathBuf> {
+        let relative = Path::new(segment);
+        let is_plain = relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if segment.is_empty() || !is_plain {
+            return Err(DomainError::InvalidData(format!(
+                "Invalid archive segment name: {}",
+                segment
+            ))
+            .into());
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl ArchiveStore for FilesystemArchiveStore {
+    async fn put_batch(&self, key: &ArchiveSegmentKey, events: &[Event]) -> Result<String> {
+        let segment = segment_name(key);
+        let path = self.segment_path(&segment)?;
+        let bytes = encode_segment(events)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        tokio::fs::write(&tmp_path, &bytes).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+
+        Ok(segment)
+    }
+
+    async fn get_by_id(&self, segment: &str, id: EventId) -> Result<Option<Event>> {
+        let path = self.segment_path(segment)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => find_in_segment(&bytes, id),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                warn!(segment = %segment, "Archive segment not found");
+                Ok(None)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event::CreateEventParams;
+    use crate::domain::value_objects::{EventReceiverId, UserId};
+    use serde_json::json;
+
+    fn temp_root() -> PathBuf {
+        std::env::temp_dir().join(format!("xzepr-archive-{}", ulid::Ulid::new()))
+    }
+
+    fn event() -> Event {
+        Event::new(CreateEventParams {
+            name: "deploy".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.01".to_string(),
+            platform_id: "linux".to_string(),
+            package: "docker".to_string(),
+            description: "archived".to_string(),
+            payload: json!({"ok": true}),
+            success: true,
+            receiver_id: EventReceiverId::new(),
+            owner_id: UserId::new(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_put_then_get_by_id() {
+        let root = temp_root();
+        let store = FilesystemArchiveStore::new(&root);
+        let event = event();
+
+        let segment = store
+            .put_batch(
+                &ArchiveSegmentKey::for_event(&event),
+                std::slice::from_ref(&event),
+            )
+            .await
+            .unwrap();
+
+        assert!(root.join(&segment).is_file());
+        let found = store.get_by_id(&segment, event.id()).await.unwrap();
+        assert_eq!(found.map(|e| e.id()), Some(event.id()));
+        assert!(store
+            .get_by_id(&segment, EventId::new())
+            .await
+            .unwrap()
+            .is_none());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_missing_segment_returns_none() {
+        let store = FilesystemArchiveStore::new(temp_root());
+
+        let found = store
+            .get_by_id("2025-01-01/missing.jsonl.gz", EventId::new())
+            .await
+            .unwrap();
+
+        assert!(found.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_segments_outside_root() {
+        let store = FilesystemArchiveStore::new(temp_root());
+
+        for segment in ["../etc/passwd", "/etc/passwd", ""] {
+            assert!(store.get_by_id(segment, EventId::new()).await.is_err());
+        }
+    }
+}
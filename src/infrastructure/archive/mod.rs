@@ -0,0 +1,278 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/archive/mod.rs
+
+//! Cold storage for expired events
+//!
+//! The retention worker moves events older than the retention window out of
+//! Postgres into immutable segments. A segment is a gzip-compressed JSON
+//! Lines file holding events of one receiver created on one day, stored
+//! under `{date}/{receiver_id}-{ulid}.jsonl.gz`.
+
+pub mod filesystem;
+#[cfg(feature = "s3-archive")]
+pub mod s3;
+
+use std::io::{BufRead, BufReader, Write};
+use std::sync::Arc;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Deserialize;
+use ulid::Ulid;
+
+use crate::domain::entities::event::Event;
+use crate::domain::repositories::event_archive_repo::{ArchiveSegmentKey, ArchiveStore};
+use crate::domain::value_objects::EventId;
+use crate::error::Result;
+use crate::infrastructure::config::Secret;
+use crate::infrastructure::http_client::HttpClientFactory;
+
+pub use filesystem::FilesystemArchiveStore;
+#[cfg(feature = "s3-archive")]
+pub use s3::S3ArchiveStore;
+
+/// Archive backend selection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveBackend {
+    #[default]
+    Filesystem,
+    S3,
+}
+
+/// Connection settings for an S3-compatible object store
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3ArchiveConfig {
+    /// Base URL of the object store, e.g. `https://s3.us-east-1.amazonaws.com`
+    pub endpoint: String,
+    pub bucket: String,
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: Secret<String>,
+    /// Prepended to every segment key
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// Event retention and archival configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArchiveConfig {
+    /// Runs the retention worker; events are never deleted when disabled
+    #[serde(default)]
+    pub enabled: bool,
+    /// Events older than this many days are archived and deleted
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u32,
+    /// Maximum number of events moved per retention pass
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Delay between retention passes
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u64,
+    #[serde(default)]
+    pub backend: ArchiveBackend,
+    /// Root directory for the filesystem backend
+    #[serde(default = "default_archive_path")]
+    pub path: String,
+    /// Required for the `s3` backend
+    #[serde(default)]
+    pub s3: Option<S3ArchiveConfig>,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: default_retention_days(),
+            batch_size: default_batch_size(),
+            interval_seconds: default_interval_seconds(),
+            backend: ArchiveBackend::default(),
+            path: default_archive_path(),
+            s3: None,
+        }
+    }
+}
+
+fn default_retention_days() -> u32 {
+    90
+}
+
+fn default_batch_size() -> usize {
+    500
+}
+
+fn default_interval_seconds() -> u64 {
+    3600
+}
+
+fn default_archive_path() -> String {
+    "./archive".to_string()
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+/// Builds the archive store selected by `config`
+#[cfg_attr(not(feature = "s3-archive"), allow(unused_variables))]
+pub fn build_archive_store(
+    config: &ArchiveConfig,
+    http_clients: &HttpClientFactory,
+) -> Result<Arc<dyn ArchiveStore>> {
+    match config.backend {
+        ArchiveBackend::Filesystem => Ok(Arc::new(FilesystemArchiveStore::new(&config.path))),
+        #[cfg(feature = "s3-archive")]
+        ArchiveBackend::S3 => match &config.s3 {
+            Some(s3) => {
+                let client = http_clients
+                    .client()
+                    .map_err(|e| config::ConfigError::Message(e.to_string()))?;
+                Ok(Arc::new(
+                    S3ArchiveStore::new(s3.clone())?.with_http_client(client),
+                ))
+            }
+            None => Err(config::ConfigError::Message(
+                "archive.backend is 's3' but archive.s3 is not configured".to_string(),
+            )
+            .into()),
+        },
+        #[cfg(not(feature = "s3-archive"))]
+        ArchiveBackend::S3 => Err(config::ConfigError::Message(
+            "archive.backend 's3' requires building with the s3-archive feature".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Returns a new, unique segment name for `key`
+pub fn segment_name(key: &ArchiveSegmentKey) -> String {
+    format!(
+        "{}/{}-{}.jsonl.gz",
+        key.date.format("%Y-%m-%d"),
+        key.event_receiver_id,
+        Ulid::new()
+    )
+}
+
+/// Encodes events as a gzip-compressed JSON Lines segment
+pub fn encode_segment(events: &[Event]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    for event in events {
+        serde_json::to_writer(&mut encoder, event)?;
+        encoder.write_all(b"\n")?;
+    }
+    Ok(encoder.finish()?)
+}
+
+/// Decodes every event in a segment
+pub fn decode_segment(bytes: &[u8]) -> Result<Vec<Event>> {
+    let reader = BufReader::new(GzDecoder::new(bytes));
+    let mut events = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        events.push(serde_json::from_str(&line)?);
+    }
+    Ok(events)
+}
+
+/// Finds one event in a segment
+pub fn find_in_segment(bytes: &[u8], id: EventId) -> Result<Option<Event>> {
+    Ok(decode_segment(bytes)?
+        .into_iter()
+        .find(|event| event.id() == id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::event::CreateEventParams;
+    use crate::domain::value_objects::{EventReceiverId, UserId};
+    use serde_json::json;
+
+    fn event(receiver_id: EventReceiverId) -> Event {
+        Event::new(CreateEventParams {
+            name: "deploy".to_string(),
+            version: "1.0.0".to_string(),
+            release: "2025.01".to_string(),
+            platform_id: "linux".to_string(),
+            package: "docker".to_string(),
+            description: "archived".to_string(),
+            payload: json!({"ok": true}),
+            success: true,
+            receiver_id,
+            owner_id: UserId::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_segment_round_trip() {
+        let receiver_id = EventReceiverId::new();
+        let events = vec![event(receiver_id), event(receiver_id)];
+
+        let bytes = encode_segment(&events).unwrap();
+        let decoded = decode_segment(&bytes).unwrap();
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].id(), events[0].id());
+        assert_eq!(decoded[1].payload(), events[1].payload());
+
+        let found = find_in_segment(&bytes, events[1].id()).unwrap();
+        assert_eq!(found.map(|e| e.id()), Some(events[1].id()));
+        assert!(find_in_segment(&bytes, EventId::new()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_segment_name_includes_date_and_receiver() {
+        let event = event(EventReceiverId::new());
+        let key = ArchiveSegmentKey::for_event(&event);
+
+        let name = segment_name(&key);
+
+        assert!(name.starts_with(&format!(
+            "{}/{}-",
+            event.created_at().format("%Y-%m-%d"),
+            event.event_receiver_id()
+        )));
+        assert!(name.ends_with(".jsonl.gz"));
+        assert_ne!(name, segment_name(&key));
+    }
+
+    #[test]
+    fn test_archive_config_defaults() {
+        let config = ArchiveConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.retention_days, 90);
+        assert_eq!(config.backend, ArchiveBackend::Filesystem);
+    }
+
+    #[test]
+    fn test_archive_config_deserialize_s3() {
+        let yaml = r#"
+            enabled: true
+            retention_days: 30
+            backend: s3
+            s3:
+              endpoint: "http://minio:9000"
+              bucket: "xzepr-archive"
+              access_key_id: "minio"
+              secret_access_key: "minio123"
+        "#;
+
+        let config: ArchiveConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.retention_days, 30);
+        assert_eq!(config.batch_size, 500);
+        assert_eq!(config.backend, ArchiveBackend::S3);
+        let s3 = config.s3.unwrap();
+        assert_eq!(s3.region, "us-east-1");
+        assert_eq!(s3.prefix, "");
+    }
+}
@@ -0,0 +1,221 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/attachments/filesystem.rs
+
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use crate::domain::repositories::event_attachment_repo::{
+    BlobStore, BlobWriter, ByteRange, ByteStream,
+};
+use crate::error::{DomainError, Result};
+
+/// Size of the chunks blobs are read in
+const READ_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Blob store writing attachments below a local directory
+///
+/// Blobs are written to a temporary file and renamed into place on commit,
+/// so a reader never sees a partially written blob.
+#[derive(Debug, Clone)]
+pub struct FilesystemBlobStore {
+    root: PathBuf,
+}
+
+impl FilesystemBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Resolves a blob key below the root directory
+    ///
+    /// Anything that could escape the root is rejected.
+    fn blob_path(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        let is_plain = relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if key.is_empty() || !is_plain {
+            return Err(DomainError::InvalidData(format!("Invalid blob key: {}", key)).into());
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut tmp_path = path.to_path_buf().into_os_string();
+    tmp_path.push(".part");
+    tmp_path.into()
+}
+
+struct FilesystemBlobWriter {
+    file: File,
+    tmp_path: PathBuf,
+    path: PathBuf,
+}
+
+#[async_trait]
+impl BlobWriter for FilesystemBlobWriter {
+    async fn write(&mut self, chunk: &[u8]) -> Result<()> {
+        self.file.write_all(chunk).await?;
+        Ok(())
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<()> {
+        self.file.flush().await?;
+        self.file.sync_all().await?;
+        tokio::fs::rename(&self.tmp_path, &self.path).await?;
+        Ok(())
+    }
+
+    async fn abort(self: Box<Self>) -> Result<()> {
+        let FilesystemBlobWriter { file, tmp_path, .. } = *self;
+        drop(file);
+        match tokio::fs::remove_file(&tmp_path).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+struct FileChunks {
+    file: File,
+    remaining: u64,
+}
+
+#[async_trait]
+impl ByteStream for FileChunks {
+    async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        let size = self.remaining.min(READ_CHUNK_BYTES as u64) as usize;
+        let mut chunk = vec![0; size];
+        let read = self.file.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        chunk.truncate(read);
+        self.remaining -= read as u64;
+        Ok(Some(chunk))
+    }
+}
+
+#[async_trait]
+impl BlobStore for FilesystemBlobStore {
+    async fn begin(&self, key: &str) -> Result<Box<dyn BlobWriter>> {
+        let path = self.blob_path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let tmp_path = temp_path(&path);
+        let file = File::create(&tmp_path).await?;
+        Ok(Box::new(FilesystemBlobWriter {
+            file,
+            tmp_path,
+            path,
+        }))
+    }
+
+    async fn open(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<Option<Box<dyn ByteStream>>> {
+        let path = self.blob_path(key)?;
+        let mut file = match File::open(&path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let remaining = match range {
+            Some(range) => {
+                file.seek(SeekFrom::Start(range.start)).await?;
+                range.length()
+            }
+            None => file.metadata().await?.len(),
+        };
+        Ok(Some(Box::new(FileChunks { file, remaining })))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.blob_path(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root() -> PathBuf {
+        std::env::temp_dir().join(format!("xzepr-attachments-{}", ulid::Ulid::new()))
+    }
+
+    async fn read_all(mut stream: Box<dyn ByteStream>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        while let Some(chunk) = stream.next_chunk().await.unwrap() {
+            bytes.extend(chunk);
+        }
+        bytes
+    }
+
+    #[tokio::test]
+    async fn test_blob_is_only_visible_after_commit() {
+        let root = temp_root();
+        let store = FilesystemBlobStore::new(&root);
+
+        let mut writer = store.begin("event/one").await.unwrap();
+        writer.write(b"hello ").await.unwrap();
+        writer.write(b"world").await.unwrap();
+        assert!(store.open("event/one", None).await.unwrap().is_none());
+
+        writer.commit().await.unwrap();
+        let stream = store.open("event/one", None).await.unwrap().unwrap();
+        assert_eq!(read_all(stream).await, b"hello world");
+
+        let range = ByteRange { start: 6, end: 9 };
+        let stream = store.open("event/one", Some(range)).await.unwrap().unwrap();
+        assert_eq!(read_all(stream).await, b"worl");
+
+        store.delete("event/one").await.unwrap();
+        assert!(store.open("event/one", None).await.unwrap().is_none());
+        store.delete("event/one").await.unwrap();
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_abort_discards_written_bytes() {
+        let root = temp_root();
+        let store = FilesystemBlobStore::new(&root);
+
+        let mut writer = store.begin("event/two").await.unwrap();
+        writer.write(b"partial").await.unwrap();
+        writer.abort().await.unwrap();
+
+        assert!(store.open("event/two", None).await.unwrap().is_none());
+        assert_eq!(std::fs::read_dir(root.join("event")).unwrap().count(), 0);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_keys_outside_root() {
+        let store = FilesystemBlobStore::new(temp_root());
+
+        for key in ["../etc/passwd", "/etc/passwd", ""] {
+            assert!(store.open(key, None).await.is_err());
+            assert!(store.begin(key).await.is_err());
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/attachments/mod.rs
+
+//! Blob storage for event attachments
+//!
+//! Attachment content is stored under `{event_id}/{attachment_id}`, either
+//! below a local directory or, with the `s3-attachments` feature, in an
+//! S3-compatible object store. Offloaded event payloads share the store
+//! under `{event_id}/payload.json`.
+
+pub mod filesystem;
+#[cfg(feature = "s3-attachments")]
+pub mod s3;
+
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::domain::repositories::event_attachment_repo::BlobStore;
+use crate::error::Result;
+use crate::infrastructure::archive::S3ArchiveConfig;
+use crate::infrastructure::http_client::HttpClientFactory;
+
+pub use filesystem::FilesystemBlobStore;
+#[cfg(feature = "s3-attachments")]
+pub use s3::S3BlobStore;
+
+/// Attachment storage backend selection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlobBackend {
+    #[default]
+    Filesystem,
+    S3,
+}
+
+/// Event attachment configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct AttachmentsConfig {
+    /// Serves the attachment endpoints; they answer `503` when disabled
+    #[serde(default)]
+    pub enabled: bool,
+    /// Largest accepted attachment in bytes
+    #[serde(default = "default_max_size_bytes")]
+    pub max_size_bytes: u64,
+    /// Media types accepted for attachments
+    #[serde(default = "default_allowed_content_types")]
+    pub allowed_content_types: Vec<String>,
+    /// Total attachment bytes allowed per receiver; 0 disables the quota
+    #[serde(default = "default_receiver_quota_bytes")]
+    pub receiver_quota_bytes: u64,
+    #[serde(default)]
+    pub backend: BlobBackend,
+    /// Root directory for the filesystem backend
+    #[serde(default = "default_attachments_path")]
+    pub path: String,
+    /// Required for the `s3` backend; takes the same settings as
+    /// `archive.s3`
+    #[serde(default)]
+    pub s3: Option<S3ArchiveConfig>,
+}
+
+impl Default for AttachmentsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_size_bytes: default_max_size_bytes(),
+            allowed_content_types: default_allowed_content_types(),
+            receiver_quota_bytes: default_receiver_quota_bytes(),
+            backend: BlobBackend::default(),
+            path: default_attachments_path(),
+            s3: None,
+        }
+    }
+}
+
+fn default_max_size_bytes() -> u64 {
+    50 * 1024 * 1024
+}
+
+fn default_allowed_content_types() -> Vec<String> {
+    [
+        "application/gzip",
+        "application/json",
+        "application/pdf",
+        "application/spdx+json",
+        "application/vnd.cyclonedx+json",
+        "application/vnd.cyclonedx+xml",
+        "application/xml",
+        "application/zip",
+        "text/csv",
+        "text/html",
+        "text/plain",
+        "text/xml",
+    ]
+    .iter()
+    .map(|media_type| media_type.to_string())
+    .collect()
+}
+
+fn default_receiver_quota_bytes() -> u64 {
+    1024 * 1024 * 1024
+}
+
+fn default_attachments_path() -> String {
+    "./attachments".to_string()
+}
+
+/// Builds the blob store selected by `config`
+#[cfg_attr(not(feature = "s3-attachments"), allow(unused_variables))]
+pub fn build_blob_store(
+    config: &AttachmentsConfig,
+    http_clients: &HttpClientFactory,
+) -> Result<Arc<dyn BlobStore>> {
+    match config.backend {
+        BlobBackend::Filesystem => Ok(Arc::new(FilesystemBlobStore::new(&config.path))),
+        #[cfg(feature = "s3-attachments")]
+        BlobBackend::S3 => match &config.s3 {
+            Some(s3) => {
+                let client = http_clients
+                    .client()
+                    .map_err(|e| config::ConfigError::Message(e.to_string()))?;
+                Ok(Arc::new(
+                    S3BlobStore::new(s3.clone())?.with_http_client(client),
+                ))
+            }
+            None => Err(config::ConfigError::Message(
+                "attachments.backend is 's3' but attachments.s3 is not configured".to_string(),
+            )
+            .into()),
+        },
+        #[cfg(not(feature = "s3-attachments"))]
+        BlobBackend::S3 => Err(config::ConfigError::Message(
+            "attachments.backend 's3' requires building with the s3-attachments feature"
+                .to_string(),
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attachments_config_defaults() {
+        let config = AttachmentsConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.max_size_bytes, 50 * 1024 * 1024);
+        assert_eq!(config.receiver_quota_bytes, 1024 * 1024 * 1024);
+        assert_eq!(config.backend, BlobBackend::Filesystem);
+        assert!(config
+            .allowed_content_types
+            .contains(&"application/vnd.cyclonedx+json".to_string()));
+    }
+
+    #[test]
+    fn test_attachments_config_deserialize() {
+        let yaml = r#"
+            enabled: true
+            max_size_bytes: 1048576
+            allowed_content_types: ["application/json"]
+            receiver_quota_bytes: 0
+            path: "/var/lib/xzepr/attachments"
+        "#;
+
+        let config: AttachmentsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.max_size_bytes, 1024 * 1024);
+        assert_eq!(config.allowed_content_types, ["application/json"]);
+        assert_eq!(config.receiver_quota_bytes, 0);
+        assert_eq!(config.path, "/var/lib/xzepr/attachments");
+    }
+
+    #[cfg(not(feature = "s3-attachments"))]
+    #[test]
+    fn test_s3_backend_requires_feature() {
+        let config = AttachmentsConfig {
+            backend: BlobBackend::S3,
+            ..AttachmentsConfig::default()
+        };
+
+        let error = build_blob_store(&config, &HttpClientFactory::default())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("s3-attachments"));
+    }
+}
@@ -0,0 +1,212 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/attachments/s3.rs
+
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::{Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+
+use crate::domain::repositories::event_attachment_repo::{
+    BlobStore, BlobWriter, ByteRange, ByteStream,
+};
+use crate::error::{InfrastructureError, Result};
+use crate::infrastructure::archive::s3::{amz_date, sign_request, uri_encode, SigningParams};
+use crate::infrastructure::archive::S3ArchiveConfig;
+
+/// Blob store keeping attachments in an S3-compatible object store
+///
+/// Objects are addressed path-style and signed like archive segments. An
+/// upload is held in memory until it is committed with a single `PUT`, so
+/// `attachments.max_size_bytes` bounds the memory each upload may take.
+#[derive(Debug, Clone)]
+pub struct S3BlobStore {
+    client: reqwest::Client,
+    config: S3ArchiveConfig,
+    endpoint: Url,
+}
+
+impl S3BlobStore {
+    pub fn new(config: S3ArchiveConfig) -> Result<Self> {
+        let endpoint = Url::parse(&config.endpoint).map_err(|e| {
+            config::ConfigError::Message(format!("Invalid attachments.s3.endpoint: {}", e))
+        })?;
+        Ok(Self {
+            client: reqwest::Client::new(),
+            config,
+            endpoint,
+        })
+    }
+
+    /// Sends requests with `client` instead of a default client
+    pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    fn object_path(&self, key: &str) -> String {
+        let base = self.endpoint.path().trim_end_matches('/');
+        uri_encode(&format!(
+            "{}/{}/{}{}",
+            base, self.config.bucket, self.config.prefix, key
+        ))
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        range: Option<ByteRange>,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response> {
+        let path = self.object_path(key);
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+
+        let host = match (self.endpoint.host_str(), self.endpoint.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(external_error("invalid endpoint")),
+        };
+
+        let now = Utc::now();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+        let authorization = sign_request(
+            &SigningParams {
+                access_key_id: &self.config.access_key_id,
+                secret_access_key: self.config.secret_access_key.expose(),
+                region: &self.config.region,
+            },
+            method.as_str(),
+            &path,
+            &host,
+            &payload_hash,
+            now,
+        );
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("host", host)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date(now))
+            .header("authorization", authorization);
+        if let Some(range) = range {
+            request = request.header("range", format!("bytes={}-{}", range.start, range.end));
+        }
+        request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| external_error(&e.to_string()))
+    }
+}
+
+struct S3BlobWriter {
+    store: S3BlobStore,
+    key: String,
+    buffer: Vec<u8>,
+}
+
+#[async_trait]
+impl BlobWriter for S3BlobWriter {
+    async fn write(&mut self, chunk: &[u8]) -> Result<()> {
+        self.buffer.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    async fn commit(self: Box<Self>) -> Result<()> {
+        let response = self
+            .store
+            .send(Method::PUT, &self.key, None, self.buffer)
+            .await?;
+        if !response.status().is_success() {
+            return Err(external_error(&format!(
+                "PUT returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+
+    async fn abort(self: Box<Self>) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct ResponseChunks(reqwest::Response);
+
+#[async_trait]
+impl ByteStream for ResponseChunks {
+    async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        self.0
+            .chunk()
+            .await
+            .map(|chunk| chunk.map(|bytes| bytes.to_vec()))
+            .map_err(|e| external_error(&e.to_string()))
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    async fn begin(&self, key: &str) -> Result<Box<dyn BlobWriter>> {
+        Ok(Box::new(S3BlobWriter {
+            store: self.clone(),
+            key: key.to_string(),
+            buffer: Vec::new(),
+        }))
+    }
+
+    async fn open(
+        &self,
+        key: &str,
+        range: Option<ByteRange>,
+    ) -> Result<Option<Box<dyn ByteStream>>> {
+        let response = self.send(Method::GET, key, range, Vec::new()).await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(Box::new(ResponseChunks(response)))),
+            status => Err(external_error(&format!("GET returned {}", status))),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let response = self.send(Method::DELETE, key, None, Vec::new()).await?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(()),
+            status if status.is_success() => Ok(()),
+            status => Err(external_error(&format!("DELETE returned {}", status))),
+        }
+    }
+}
+
+fn external_error(detail: &str) -> crate::error::Error {
+    InfrastructureError::ExternalServiceError {
+        service: format!("s3 attachments: {}", detail),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_path_is_path_style_and_encoded() {
+        let store = S3BlobStore::new(S3ArchiveConfig {
+            endpoint: "http://localhost:9000/storage".to_string(),
+            bucket: "xzepr".to_string(),
+            region: "us-east-1".to_string(),
+            access_key_id: "key".to_string(),
+            secret_access_key: "secret".to_string().into(),
+            prefix: "attachments/".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(
+            store.object_path("01J0EVENT/01J0FILE"),
+            "/storage/xzepr/attachments/01J0EVENT/01J0FILE"
+        );
+    }
+}
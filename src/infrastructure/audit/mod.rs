@@ -5,7 +5,13 @@
 //!
 //! This module provides structured audit logging for security-relevant events.
 //! Audit logs are emitted as structured JSON to stdout/stderr for ingestion
-//! into centralized logging systems like ELK, Datadog, or similar.
+//! into centralized logging systems like ELK, Datadog, or similar. When a
+//! sink is installed with [`install_sink`], every event is also persisted,
+//! e.g. to the tamper-evident audit record store through [`AuditRecordSink`].
+//!
+//! Requests made with an impersonation token run inside [`with_actor`], so
+//! every event built while handling them names the administrator in
+//! `actor_id` next to the impersonated `user_id`.
 //!
 //! # Example
 //!
@@ -26,11 +32,35 @@
 //! logger.log_event(event);
 //! ```
 
+pub mod sink;
+
+pub use sink::{install_sink, AuditRecordSink, AuditSink};
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use tracing::{info, warn};
 
+tokio::task_local! {
+    static ACTOR: String;
+}
+
+/// Runs `future` with `actor` recorded on every audit event it builds
+///
+/// Without an actor, `future` runs unchanged.
+pub async fn with_actor<F: Future>(actor: Option<String>, future: F) -> F::Output {
+    match actor {
+        Some(actor) => ACTOR.scope(actor, future).await,
+        None => future.await,
+    }
+}
+
+/// Returns the actor set by the enclosing [`with_actor`], if any
+pub fn current_actor() -> Option<String> {
+    ACTOR.try_with(Clone::clone).ok()
+}
+
 /// Audit event action types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -77,6 +107,20 @@ pub enum AuditAction {
     ConfigChange,
     /// Security policy change
     SecurityPolicyChange,
+    /// API key secret rotation
+    ApiKeyRotate,
+    /// API key approaching its expiry
+    ApiKeyExpiring,
+    /// API key revoked
+    ApiKeyRevoke,
+    /// Background job started on demand
+    JobTrigger,
+    /// Expired group membership removed
+    MembershipExpire,
+    /// Administrator started impersonating a user
+    ImpersonationStart,
+    /// Session token revoked
+    SessionRevoke,
 }
 
 impl std::fmt::Display for AuditAction {
@@ -103,6 +147,13 @@ impl std::fmt::Display for AuditAction {
             AuditAction::ResourceDelete => write!(f, "resource_delete"),
             AuditAction::ConfigChange => write!(f, "config_change"),
             AuditAction::SecurityPolicyChange => write!(f, "security_policy_change"),
+            AuditAction::ApiKeyRotate => write!(f, "api_key_rotate"),
+            AuditAction::ApiKeyExpiring => write!(f, "api_key_expiring"),
+            AuditAction::ApiKeyRevoke => write!(f, "api_key_revoke"),
+            AuditAction::JobTrigger => write!(f, "job_trigger"),
+            AuditAction::MembershipExpire => write!(f, "membership_expire"),
+            AuditAction::ImpersonationStart => write!(f, "impersonation_start"),
+            AuditAction::SessionRevoke => write!(f, "session_revoke"),
         }
     }
 }
@@ -142,6 +193,9 @@ pub struct AuditEvent {
     pub timestamp: DateTime<Utc>,
     /// User ID performing the action (optional for anonymous)
     pub user_id: Option<String>,
+    /// Administrator acting as `user_id` through impersonation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<String>,
     /// Action being performed
     pub action: AuditAction,
     /// Resource being accessed or modified
@@ -226,6 +280,7 @@ impl AuditEvent {
 #[derive(Default)]
 pub struct AuditEventBuilder {
     user_id: Option<String>,
+    actor_id: Option<String>,
     action: Option<AuditAction>,
     resource: Option<String>,
     outcome: Option<AuditOutcome>,
@@ -251,6 +306,20 @@ impl AuditEventBuilder {
         self
     }
 
+    /// Set the impersonating administrator
+    ///
+    /// Defaults to the actor of the enclosing [`with_actor`].
+    pub fn actor_id(mut self, actor_id: impl Into<String>) -> Self {
+        self.actor_id = Some(actor_id.into());
+        self
+    }
+
+    /// Set the impersonating administrator as Option
+    pub fn actor_id_opt(mut self, actor_id: Option<impl Into<String>>) -> Self {
+        self.actor_id = actor_id.map(|a| a.into());
+        self
+    }
+
     /// Set the action
     pub fn action(mut self, action: AuditAction) -> Self {
         self.action = Some(action);
@@ -352,6 +421,7 @@ impl AuditEventBuilder {
         AuditEvent {
             timestamp: Utc::now(),
             user_id: self.user_id,
+            actor_id: self.actor_id.or_else(current_actor),
             action: self.action.expect("action is required"),
             resource: self.resource.expect("resource is required"),
             outcome: self.outcome.expect("outcome is required"),
@@ -396,8 +466,13 @@ impl AuditLogger {
     /// Log an audit event
     ///
     /// Emits the event as a structured JSON log at INFO level for successful
-    /// outcomes and WARN level for failures, denials, or errors.
+    /// outcomes and WARN level for failures, denials, or errors, and hands it
+    /// to the installed sink.
     pub fn log_event(&self, event: AuditEvent) {
+        if let Some(sink) = sink::installed_sink() {
+            sink.record(&event);
+        }
+
         match event.outcome {
             AuditOutcome::Success => {
                 info!(
@@ -406,6 +481,7 @@ impl AuditLogger {
                     env = %self.environment,
                     timestamp = %event.timestamp,
                     user_id = ?event.user_id,
+                    actor_id = ?event.actor_id,
                     action = %event.action,
                     resource = %event.resource,
                     outcome = %event.outcome,
@@ -425,6 +501,7 @@ impl AuditLogger {
                     env = %self.environment,
                     timestamp = %event.timestamp,
                     user_id = ?event.user_id,
+                    actor_id = ?event.actor_id,
                     action = %event.action,
                     resource = %event.resource,
                     outcome = %event.outcome,
@@ -446,6 +523,7 @@ impl AuditLogger {
                     env = %self.environment,
                     timestamp = %event.timestamp,
                     user_id = ?event.user_id,
+                    actor_id = ?event.actor_id,
                     action = %event.action,
                     resource = %event.resource,
                     outcome = %event.outcome,
@@ -658,6 +736,33 @@ mod tests {
         assert_eq!(event.ip_address, Some("192.168.1.1".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_events_built_under_an_actor_name_both_identities() {
+        let build = || {
+            AuditEvent::builder()
+                .user_id("user123")
+                .action(AuditAction::ResourceUpdate)
+                .resource("event_receiver:1")
+                .outcome(AuditOutcome::Success)
+                .build()
+        };
+
+        let event = with_actor(Some("admin1".to_string()), async { build() }).await;
+        assert_eq!(event.user_id.as_deref(), Some("user123"));
+        assert_eq!(event.actor_id.as_deref(), Some("admin1"));
+        assert_eq!(serde_json::to_value(&event).unwrap()["actor_id"], "admin1");
+
+        // Outside an impersonated request nothing is added, so the stored
+        // content of ordinary events is unchanged
+        let event = with_actor(None, async { build() }).await;
+        assert!(event.actor_id.is_none());
+        assert!(serde_json::to_value(&event)
+            .unwrap()
+            .get("actor_id")
+            .is_none());
+        assert!(current_actor().is_none());
+    }
+
     #[test]
     fn test_login_success_event() {
         let event = AuditEvent::login_success("user123", Some("192.168.1.1"));
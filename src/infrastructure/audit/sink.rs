@@ -0,0 +1,197 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/audit/sink.rs
+
+//! Persistence of audit events
+//!
+//! Every [`AuditLogger`](super::AuditLogger) hands its events to the
+//! process-wide sink, if one is installed, in addition to logging them.
+//! [`AuditRecordSink`] appends them to the hash-chained audit record store.
+
+use std::sync::{Arc, OnceLock};
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use super::AuditEvent;
+use crate::domain::canonical_json::{canonical_string, CanonicalJsonError};
+use crate::domain::repositories::audit_record_repo::AuditRecordRepository;
+
+static SINK: OnceLock<Arc<dyn AuditSink>> = OnceLock::new();
+
+/// Destination of audit events beyond the log
+pub trait AuditSink: Send + Sync {
+    /// Accepts an event without blocking the caller
+    fn record(&self, event: &AuditEvent);
+}
+
+/// Installs the process-wide sink
+///
+/// Call once at startup. Returns the sink unchanged if one was already
+/// installed.
+pub fn install_sink(sink: Arc<dyn AuditSink>) -> Result<(), Arc<dyn AuditSink>> {
+    SINK.set(sink)
+}
+
+/// Returns the process-wide sink, if one was installed
+pub fn installed_sink() -> Option<&'static Arc<dyn AuditSink>> {
+    SINK.get()
+}
+
+/// Returns the content stored and hashed for an event
+pub fn canonical_content(event: &AuditEvent) -> Result<String, CanonicalJsonError> {
+    canonical_string(&serde_json::to_value(event).unwrap_or_default())
+}
+
+/// Sink appending audit events to an [`AuditRecordRepository`]
+///
+/// Events are queued and written in order by a background task, so logging
+/// never waits for the database. An event that cannot be written is logged
+/// as an error and dropped.
+#[derive(Clone)]
+pub struct AuditRecordSink {
+    sender: mpsc::UnboundedSender<AuditEvent>,
+}
+
+impl AuditRecordSink {
+    /// Starts the writer task; it stops once every sender is dropped
+    pub fn spawn(records: Arc<dyn AuditRecordRepository>) -> (Self, JoinHandle<()>) {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<AuditEvent>();
+        let handle = tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                let appended = match canonical_content(&event) {
+                    Ok(content) => records.append(event.timestamp, content).await,
+                    Err(e) => Err(e.into_validation_error("content").into()),
+                };
+                if let Err(e) = appended {
+                    error!(
+                        action = %event.action,
+                        resource = %event.resource,
+                        error = %e,
+                        "Failed to persist audit event"
+                    );
+                }
+            }
+        });
+
+        (Self { sender }, handle)
+    }
+}
+
+impl AuditSink for AuditRecordSink {
+    fn record(&self, event: &AuditEvent) {
+        if self.sender.send(event.clone()).is_err() {
+            error!(
+                action = %event.action,
+                resource = %event.resource,
+                "Audit record writer has stopped; event not persisted"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::audit_chain::{
+        next_link, record_hash, AuditChainVerifier, AuditRecord, ChainLink,
+    };
+    use crate::domain::repositories::audit_record_repo::PurgedAuditRecords;
+    use crate::error::Result;
+    use crate::infrastructure::audit::{AuditAction, AuditOutcome};
+    use async_trait::async_trait;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryRecords {
+        records: Mutex<Vec<AuditRecord>>,
+    }
+
+    #[async_trait]
+    impl AuditRecordRepository for MemoryRecords {
+        async fn append(&self, recorded_at: DateTime<Utc>, content: String) -> Result<AuditRecord> {
+            let mut records = self.records.lock().unwrap();
+            let chain_day = recorded_at.date_naive();
+            let last = records
+                .iter()
+                .rev()
+                .find(|r| r.chain_day == chain_day)
+                .map(AuditRecord::link);
+            let (position, prev_hash) = next_link(chain_day, last.as_ref());
+            let record = AuditRecord {
+                id: records.len() as i64 + 1,
+                chain_day,
+                position,
+                recorded_at,
+                hash: record_hash(&prev_hash, chain_day, position, &content),
+                content,
+                prev_hash,
+            };
+            records.push(record.clone());
+            Ok(record)
+        }
+
+        async fn find_records(
+            &self,
+            _from: NaiveDate,
+            _to: NaiveDate,
+            _after: Option<(NaiveDate, i64)>,
+            _limit: usize,
+        ) -> Result<Vec<AuditRecord>> {
+            Ok(self.records.lock().unwrap().clone())
+        }
+
+        async fn find_link(&self, _day: NaiveDate, _position: i64) -> Result<Option<ChainLink>> {
+            Ok(None)
+        }
+
+        async fn purge_before(
+            &self,
+            _before: NaiveDate,
+            _limit: usize,
+        ) -> Result<PurgedAuditRecords> {
+            Ok(PurgedAuditRecords::default())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_are_appended_in_order_to_a_verifiable_chain() {
+        let records = Arc::new(MemoryRecords::default());
+        let (sink, writer) = AuditRecordSink::spawn(records.clone());
+
+        let events: Vec<AuditEvent> = ["alice", "bob", "carol"]
+            .into_iter()
+            .map(|user| {
+                AuditEvent::builder()
+                    .user_id(user)
+                    .action(AuditAction::Login)
+                    .resource("/api/v1/auth/login")
+                    .outcome(AuditOutcome::Success)
+                    .add_metadata("method", "local")
+                    .build()
+            })
+            .collect();
+        for event in &events {
+            sink.record(event);
+        }
+        drop(sink);
+        writer.await.unwrap();
+
+        let stored = records.records.lock().unwrap().clone();
+        assert_eq!(stored.len(), 3);
+        for (record, event) in stored.iter().zip(&events) {
+            assert_eq!(record.content, canonical_content(event).unwrap());
+            let decoded: AuditEvent = serde_json::from_str(&record.content).unwrap();
+            assert_eq!(decoded.user_id, event.user_id);
+        }
+
+        let mut verifier = AuditChainVerifier::new();
+        for record in &stored {
+            assert!(verifier.check(record, None));
+        }
+        assert!(verifier.finish().is_intact());
+    }
+}
@@ -0,0 +1,77 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/build_info.rs
+
+//! Build metadata embedded at compile time by `build.rs`
+
+use chrono::{DateTime, Utc};
+
+/// Length of the abbreviated commit shown to unauthenticated callers
+const SHORT_SHA_LEN: usize = 12;
+
+/// Version and provenance of the running binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// Crate version from `Cargo.toml`
+    pub version: &'static str,
+    /// Full commit SHA, or `unknown` when built outside a git checkout
+    pub git_sha: &'static str,
+    /// Output of `rustc --version` for the compiler that built the binary
+    pub rustc_version: &'static str,
+    build_epoch: &'static str,
+}
+
+impl BuildInfo {
+    /// Returns the metadata of the running binary
+    pub const fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            git_sha: env!("XZEPR_GIT_SHA"),
+            rustc_version: env!("XZEPR_RUSTC_VERSION"),
+            build_epoch: env!("XZEPR_BUILD_EPOCH"),
+        }
+    }
+
+    /// Returns the abbreviated commit SHA
+    pub fn short_sha(&self) -> &'static str {
+        match self.git_sha.get(..SHORT_SHA_LEN) {
+            Some(short) => short,
+            None => self.git_sha,
+        }
+    }
+
+    /// Returns when the binary was built
+    pub fn built_at(&self) -> Option<DateTime<Utc>> {
+        let seconds = self.build_epoch.parse::<i64>().ok()?;
+        DateTime::from_timestamp(seconds, 0)
+    }
+
+    /// Returns the optional Cargo features compiled into the binary
+    pub fn cargo_features(&self) -> Vec<&'static str> {
+        let mut features = Vec::new();
+        if cfg!(feature = "s3-archive") {
+            features.push("s3-archive");
+        }
+        features
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_metadata_is_populated() {
+        let info = BuildInfo::current();
+
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.git_sha.is_empty());
+        assert!(info.short_sha().len() <= SHORT_SHA_LEN);
+        assert!(info.git_sha.starts_with(info.short_sha()));
+        assert!(info.rustc_version.starts_with("rustc "));
+
+        let built_at = info.built_at().expect("build timestamp is embedded");
+        assert!(built_at <= Utc::now());
+    }
+}
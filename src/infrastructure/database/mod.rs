@@ -3,14 +3,51 @@
 
 // src/infrastructure/database/mod.rs
 
+pub mod payload_interning;
 pub mod postgres;
+pub mod postgres_audit_record_repo;
+pub mod postgres_delivery_failure_repo;
+pub mod postgres_event_attachment_repo;
 pub mod postgres_event_receiver_group_repo;
 pub mod postgres_event_receiver_repo;
 pub mod postgres_event_repo;
+pub mod postgres_event_rollup_repo;
+pub mod postgres_feature_flag_repo;
+pub mod postgres_group_topic_outbox_repo;
+pub mod postgres_maintenance_repo;
+pub mod postgres_persisted_query_repo;
+pub mod postgres_receiver_timeline_repo;
+pub mod postgres_release_completion_repo;
+pub mod postgres_resource_history_repo;
+pub mod postgres_search_repo;
+pub mod postgres_system_summary_repo;
+pub mod postgres_user_invitation_repo;
+pub mod postgres_user_preferences_repo;
 pub mod postgres_user_repo;
+pub mod postgres_version_normalization_repo;
+pub mod schema;
 
-pub use postgres::PostgresApiKeyRepository;
+pub use postgres::{PostgresApiKeyRepository, PostgresApiKeyUsageRepository};
+pub use postgres_audit_record_repo::PostgresAuditRecordRepository;
+pub use postgres_delivery_failure_repo::PostgresDeliveryFailureRepository;
+pub use postgres_event_attachment_repo::PostgresEventAttachmentRepository;
 pub use postgres_event_receiver_group_repo::PostgresEventReceiverGroupRepository;
 pub use postgres_event_receiver_repo::PostgresEventReceiverRepository;
 pub use postgres_event_repo::PostgresEventRepository;
+pub use postgres_event_rollup_repo::PostgresEventRollupRepository;
+pub use postgres_feature_flag_repo::PostgresFeatureFlagRepository;
+pub use postgres_group_topic_outbox_repo::PostgresGroupTopicOutboxRepository;
+pub use postgres_maintenance_repo::PostgresMaintenanceRepository;
+pub use postgres_persisted_query_repo::PostgresPersistedQueryRepository;
+pub use postgres_receiver_timeline_repo::PostgresReceiverTimelineRepository;
+pub use postgres_release_completion_repo::PostgresReleaseCompletionRepository;
+pub use postgres_resource_history_repo::PostgresResourceHistoryRepository;
+pub use postgres_search_repo::PostgresSearchRepository;
+pub use postgres_system_summary_repo::PostgresSystemSummaryRepository;
+pub use postgres_user_invitation_repo::PostgresUserInvitationRepository;
+pub use postgres_user_preferences_repo::PostgresUserPreferencesRepository;
 pub use postgres_user_repo::PostgresUserRepository;
+pub use postgres_version_normalization_repo::PostgresVersionNormalizationRepository;
+pub use schema::{
+    check_schema, LiveSchema, SchemaCheckMode, SchemaCheckReport, SchemaDrift, EXPECTED_SCHEMA,
+};
@@ -0,0 +1,128 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/payload_interning.rs
+
+//! Content addressing for large event payloads
+//!
+//! Payloads above the configured threshold are stored once in the
+//! `event_payloads` table, keyed by the SHA-256 of their canonical JSON, and
+//! events keep only the hash. Payloads that differ only in key order,
+//! formatting, or number spelling share one stored copy; see
+//! [`canonical_json`](crate::domain::canonical_json).
+
+use serde_json::Value;
+
+use crate::domain::canonical_json::{self, CanonicalJsonError};
+use crate::error::Result;
+
+/// A payload prepared for the shared payload store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternedPayload {
+    /// Hex-encoded SHA-256 of `body`
+    pub hash: String,
+    /// Canonical JSON of the payload
+    pub body: String,
+}
+
+impl InternedPayload {
+    /// Canonicalizes and hashes a payload
+    pub fn new(payload: &Value) -> Result<Self> {
+        let body = canonical_json::canonical_string(payload).map_err(invalid_payload)?;
+        let hash = canonical_json::hash_canonical(body.as_bytes());
+        Ok(Self { hash, body })
+    }
+
+    /// Returns the payload for interning if its canonical JSON is larger
+    /// than `threshold_bytes`
+    pub fn above_threshold(payload: &Value, threshold_bytes: usize) -> Result<Option<Self>> {
+        let interned = Self::new(payload)?;
+        Ok((interned.size_bytes() > threshold_bytes).then_some(interned))
+    }
+
+    /// Size of the stored body in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.body.len()
+    }
+
+    /// Parses a stored body back into the payload
+    pub fn decode(body: &str) -> Result<Value> {
+        Ok(serde_json::from_str(body)?)
+    }
+}
+
+fn invalid_payload(error: CanonicalJsonError) -> crate::error::Error {
+    error.into_validation_error("payload").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_key_order_and_whitespace_do_not_change_the_hash() {
+        let a: Value =
+            serde_json::from_str(r#"{"b": [1, {"y": 2, "x": 1}], "a": "sbom"}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"a":"sbom","b":[1,{"x":1,"y":2}]}"#).unwrap();
+
+        let interned = InternedPayload::new(&a).unwrap();
+        assert_eq!(interned.body, r#"{"a":"sbom","b":[1,{"x":1,"y":2}]}"#);
+        assert_eq!(interned, InternedPayload::new(&b).unwrap());
+        assert_eq!(interned.hash, canonical_json::content_hash(&a).unwrap());
+        assert_ne!(
+            interned.hash,
+            InternedPayload::new(&json!({"a": "sbom", "b": [{"x": 1, "y": 2}, 1]}))
+                .unwrap()
+                .hash
+        );
+    }
+
+    #[test]
+    fn test_decode_round_trips_unicode_and_numbers() {
+        let payload = json!({
+            "name": "Grüße, 世界 🚀",
+            "escaped": "tab\tquote\"backslash\\ nul\u{0001}",
+            "integer": 9007199254740993u64,
+            "negative": -42,
+            "float": 0.1,
+            "exponent": 1.5e300,
+            "tiny": 5e-324,
+            "nested": [null, true, false, {"é": []}]
+        });
+
+        let interned = InternedPayload::new(&payload).unwrap();
+        let decoded = InternedPayload::decode(&interned.body).unwrap();
+
+        assert_eq!(decoded, payload);
+        assert_eq!(decoded["integer"].as_u64(), Some(9007199254740993));
+        assert_eq!(InternedPayload::new(&decoded).unwrap(), interned);
+    }
+
+    #[test]
+    fn test_whole_floats_share_a_copy_with_integers() {
+        let float = InternedPayload::new(&json!({"count": 1.0, "ratio": -0.0})).unwrap();
+        let integer = InternedPayload::new(&json!({"count": 1, "ratio": 0})).unwrap();
+
+        assert_eq!(float, integer);
+        assert_eq!(
+            InternedPayload::decode(&float.body).unwrap(),
+            json!({"count": 1, "ratio": 0})
+        );
+    }
+
+    #[test]
+    fn test_only_payloads_above_threshold_are_interned() {
+        let payload = json!({"sbom": "x".repeat(100)});
+        let size = InternedPayload::new(&payload).unwrap().size_bytes();
+
+        assert!(InternedPayload::above_threshold(&payload, size)
+            .unwrap()
+            .is_none());
+        let interned = InternedPayload::above_threshold(&payload, size - 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(interned.size_bytes(), size);
+        assert_eq!(interned.hash.len(), 64);
+    }
+}
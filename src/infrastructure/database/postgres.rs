@@ -6,13 +6,18 @@
 // Original line: 0
 
 // src/infrastructure/database/postgres.rs
-use crate::auth::api_key::{ApiKey, ApiKeyRepository, UserRepository};
+use crate::auth::api_key::{ApiKey, ApiKeyRepository, ApiKeySecret, UserRepository};
+use crate::auth::api_key_usage::{
+    ApiKeyDailyUsage, ApiKeyFailure, ApiKeyUsageRepository, ApiKeyUsageUpdate, MAX_RECENT_FAILURES,
+};
 use crate::auth::rbac::roles::Role;
 use crate::domain::entities::user::{AuthProvider, User};
-use crate::domain::value_objects::{ApiKeyId, UserId};
+use crate::domain::value_objects::{ApiKeyId, EventReceiverGroupId, EventReceiverId, UserId};
 use crate::error::AuthError;
+use chrono::{DateTime, NaiveDate, Utc};
 use sqlx::{PgPool, Row};
 use std::str::FromStr;
+use tracing::instrument;
 
 // EventRow struct removed - using regular queries instead of macros for demo
 
@@ -28,6 +33,10 @@ impl PostgresUserRepository {
 
 #[async_trait::async_trait]
 impl UserRepository for PostgresUserRepository {
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn find_by_id(&self, id: UserId) -> Result<Option<User>, AuthError> {
         let row = sqlx::query(
             "SELECT id, username, email, password_hash, auth_provider_type AS auth_provider, auth_provider_subject, enabled, created_at, updated_at FROM users WHERE id = $1"
@@ -48,6 +57,40 @@ impl UserRepository for PostgresUserRepository {
         }
     }
 
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
+    async fn find_by_ids(&self, ids: &[UserId]) -> Result<Vec<User>, AuthError> {
+        let ids: Vec<String> = ids.iter().map(|id| id.as_ulid().to_string()).collect();
+        let rows = sqlx::query(
+            "SELECT id, username, email, password_hash, auth_provider_type AS auth_provider, auth_provider_subject, enabled, created_at, updated_at FROM users WHERE id = ANY($1)"
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error in find_by_ids: {}", e);
+            AuthError::InvalidCredentials
+        })?;
+
+        let mut users = Vec::with_capacity(rows.len());
+        for row in rows {
+            let user_id = row
+                .get::<String, _>("id")
+                .parse::<UserId>()
+                .map_err(|_| AuthError::InvalidCredentials)?;
+            let roles = self.get_user_roles(&user_id).await?;
+            users.push(self.row_to_user(row, roles)?);
+        }
+
+        Ok(users)
+    }
+
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, AuthError> {
         let row = sqlx::query(
             "SELECT id, username, email, password_hash, auth_provider_type AS auth_provider, auth_provider_subject, enabled, created_at, updated_at FROM users WHERE username = $1"
@@ -61,7 +104,9 @@ impl UserRepository for PostgresUserRepository {
         })?;
 
         if let Some(row) = row {
-            let user_id = UserId::parse(&row.get::<String, _>("id"))
+            let user_id = row
+                .get::<String, _>("id")
+                .parse::<UserId>()
                 .map_err(|_| AuthError::InvalidCredentials)?;
             let roles = self.get_user_roles(&user_id).await?;
             Ok(Some(self.row_to_user(row, roles)?))
@@ -70,6 +115,10 @@ impl UserRepository for PostgresUserRepository {
         }
     }
 
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "INSERT users")
+    )]
     async fn save(&self, user: &User) -> Result<(), AuthError> {
         let auth_provider_str = match user.auth_provider {
             AuthProvider::Local => "local",
@@ -137,6 +186,10 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn find_all(&self) -> Result<Vec<User>, AuthError> {
         let rows = sqlx::query(
             "SELECT id, username, email, password_hash, auth_provider_type AS auth_provider, auth_provider_subject, enabled, created_at, updated_at FROM users ORDER BY created_at DESC"
@@ -150,7 +203,9 @@ impl UserRepository for PostgresUserRepository {
 
         let mut users = Vec::new();
         for row in rows {
-            let user_id = UserId::parse(&row.get::<String, _>("id"))
+            let user_id = row
+                .get::<String, _>("id")
+                .parse::<UserId>()
                 .map_err(|_| AuthError::InvalidCredentials)?;
             let roles = self.get_user_roles(&user_id).await?;
             users.push(self.row_to_user(row, roles)?);
@@ -159,6 +214,10 @@ impl UserRepository for PostgresUserRepository {
         Ok(users)
     }
 
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "INSERT user_roles")
+    )]
     async fn add_role(&self, user_id: &UserId, role: Role) -> Result<(), AuthError> {
         sqlx::query(
             "INSERT INTO user_roles (user_id, role) VALUES ($1, $2) ON CONFLICT DO NOTHING",
@@ -175,6 +234,10 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "DELETE user_roles")
+    )]
     async fn remove_role(&self, user_id: &UserId, role: Role) -> Result<(), AuthError> {
         sqlx::query("DELETE FROM user_roles WHERE user_id = $1 AND role = $2")
             .bind(user_id.as_ulid().to_string())
@@ -226,7 +289,9 @@ impl PostgresUserRepository {
         };
 
         Ok(User {
-            id: UserId::parse(&row.get::<String, _>("id"))
+            id: row
+                .get::<String, _>("id")
+                .parse::<UserId>()
                 .map_err(|_| AuthError::InvalidCredentials)?,
             username: row.get("username"),
             email: row.get("email"),
@@ -253,20 +318,78 @@ impl PostgresApiKeyRepository {
     }
 }
 
+const API_KEY_COLUMNS: &str = "id, user_id, key_hash, name, expires_at, enabled, created_at, \
+    last_used_at, previous_hash, previous_expires_at, rotated_at, last_used_with, \
+    last_expiry_warning_at, receiver_id, group_id, last_used_ip";
+
+fn row_to_api_key(row: &sqlx::postgres::PgRow) -> Result<ApiKey, AuthError> {
+    let last_used_with = row
+        .get::<Option<String>, _>("last_used_with")
+        .map(|secret| secret.parse::<ApiKeySecret>())
+        .transpose()
+        .map_err(|_| AuthError::InvalidCredentials)?;
+    let receiver_id = row
+        .get::<Option<String>, _>("receiver_id")
+        .map(|id| id.parse::<EventReceiverId>())
+        .transpose()
+        .map_err(|_| AuthError::InvalidCredentials)?;
+    let group_id = row
+        .get::<Option<String>, _>("group_id")
+        .map(|id| id.parse::<EventReceiverGroupId>())
+        .transpose()
+        .map_err(|_| AuthError::InvalidCredentials)?;
+
+    Ok(ApiKey {
+        id: row
+            .get::<String, _>("id")
+            .parse::<ApiKeyId>()
+            .map_err(|_| AuthError::InvalidCredentials)?,
+        user_id: row
+            .get::<String, _>("user_id")
+            .parse::<UserId>()
+            .map_err(|_| AuthError::InvalidCredentials)?,
+        key_hash: row.get("key_hash"),
+        name: row.get("name"),
+        expires_at: row.get("expires_at"),
+        enabled: row.get("enabled"),
+        created_at: row.get("created_at"),
+        last_used_at: row.get("last_used_at"),
+        previous_hash: row.get("previous_hash"),
+        previous_expires_at: row.get("previous_expires_at"),
+        rotated_at: row.get("rotated_at"),
+        last_used_with,
+        last_expiry_warning_at: row.get("last_expiry_warning_at"),
+        receiver_id,
+        group_id,
+        last_used_ip: row.get("last_used_ip"),
+    })
+}
+
 #[async_trait::async_trait]
 impl ApiKeyRepository for PostgresApiKeyRepository {
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "INSERT api_keys")
+    )]
     async fn save(&self, api_key: &ApiKey) -> Result<(), AuthError> {
         sqlx::query(
             r#"
-            INSERT INTO api_keys (id, user_id, key_hash, name, expires_at, enabled, created_at, last_used_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO api_keys (id, user_id, key_hash, name, expires_at, enabled, created_at,
+                                  last_used_at, previous_hash, previous_expires_at, rotated_at,
+                                  last_used_with, last_expiry_warning_at, receiver_id, group_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             ON CONFLICT (id) DO UPDATE SET
                 key_hash = EXCLUDED.key_hash,
                 name = EXCLUDED.name,
                 expires_at = EXCLUDED.expires_at,
                 enabled = EXCLUDED.enabled,
-                last_used_at = EXCLUDED.last_used_at
-            "#
+                last_used_at = EXCLUDED.last_used_at,
+                previous_hash = EXCLUDED.previous_hash,
+                previous_expires_at = EXCLUDED.previous_expires_at,
+                rotated_at = EXCLUDED.rotated_at,
+                last_used_with = EXCLUDED.last_used_with,
+                last_expiry_warning_at = EXCLUDED.last_expiry_warning_at
+            "#,
         )
         .bind(api_key.id().as_ulid().to_string())
         .bind(api_key.user_id.as_ulid().to_string())
@@ -276,6 +399,13 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         .bind(api_key.enabled)
         .bind(api_key.created_at)
         .bind(api_key.last_used_at)
+        .bind(&api_key.previous_hash)
+        .bind(api_key.previous_expires_at)
+        .bind(api_key.rotated_at)
+        .bind(api_key.last_used_with.map(|secret| secret.as_str()))
+        .bind(api_key.last_expiry_warning_at)
+        .bind(api_key.receiver_id.map(|id| id.to_string()))
+        .bind(api_key.group_id.map(|id| id.to_string()))
         .execute(&self.pool)
         .await
         .map_err(|e| {
@@ -286,10 +416,35 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT api_keys")
+    )]
+    async fn find_by_id(&self, id: ApiKeyId) -> Result<Option<ApiKey>, AuthError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE id = $1",
+            API_KEY_COLUMNS
+        ))
+        .bind(id.as_ulid().to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error in api_key find_by_id: {}", e);
+            AuthError::InvalidCredentials
+        })?;
+
+        row.as_ref().map(row_to_api_key).transpose()
+    }
+
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT api_keys")
+    )]
     async fn find_by_hash(&self, hash: &str) -> Result<Option<ApiKey>, AuthError> {
-        let row = sqlx::query(
-            "SELECT id, user_id, key_hash, name, expires_at, enabled, created_at, last_used_at FROM api_keys WHERE key_hash = $1"
-        )
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE key_hash = $1 OR previous_hash = $1 LIMIT 1",
+            API_KEY_COLUMNS
+        ))
         .bind(hash)
         .fetch_optional(&self.pool)
         .await
@@ -298,27 +453,17 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
             AuthError::InvalidCredentials
         })?;
 
-        if let Some(row) = row {
-            Ok(Some(ApiKey {
-                id: ApiKeyId::parse(&row.get::<String, _>("id"))
-                    .map_err(|_| AuthError::InvalidCredentials)?,
-                user_id: UserId::parse(&row.get::<String, _>("user_id"))
-                    .map_err(|_| AuthError::InvalidCredentials)?,
-                key_hash: row.get("key_hash"),
-                name: row.get("name"),
-                expires_at: row.get("expires_at"),
-                enabled: row.get("enabled"),
-                created_at: row.get("created_at"),
-                last_used_at: row.get("last_used_at"),
-            }))
-        } else {
-            Ok(None)
-        }
+        row.as_ref().map(row_to_api_key).transpose()
     }
 
-    async fn update_last_used(&self, id: ApiKeyId) -> Result<(), AuthError> {
-        sqlx::query("UPDATE api_keys SET last_used_at = NOW() WHERE id = $1")
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "UPDATE api_keys")
+    )]
+    async fn update_last_used(&self, id: ApiKeyId, secret: ApiKeySecret) -> Result<(), AuthError> {
+        sqlx::query("UPDATE api_keys SET last_used_at = NOW(), last_used_with = $2 WHERE id = $1")
             .bind(id.as_ulid().to_string())
+            .bind(secret.as_str())
             .execute(&self.pool)
             .await
             .map_err(|e| {
@@ -329,10 +474,45 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         Ok(())
     }
 
-    async fn find_by_user_id(&self, user_id: UserId) -> Result<Vec<ApiKey>, AuthError> {
-        let rows = sqlx::query(
-            "SELECT id, user_id, key_hash, name, expires_at, enabled, created_at, last_used_at FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC"
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "UPDATE api_keys")
+    )]
+    async fn record_last_use(
+        &self,
+        id: ApiKeyId,
+        secret: ApiKeySecret,
+        at: DateTime<Utc>,
+        ip: Option<&str>,
+    ) -> Result<(), AuthError> {
+        // A delayed flush never moves the last use back in time
+        sqlx::query(
+            "UPDATE api_keys SET last_used_at = $3, last_used_with = $2, last_used_ip = $4 \
+             WHERE id = $1 AND (last_used_at IS NULL OR last_used_at <= $3)",
         )
+        .bind(id.as_ulid().to_string())
+        .bind(secret.as_str())
+        .bind(at)
+        .bind(ip)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error in record_last_use: {}", e);
+            AuthError::InvalidCredentials
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT api_keys")
+    )]
+    async fn find_by_user_id(&self, user_id: UserId) -> Result<Vec<ApiKey>, AuthError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE user_id = $1 ORDER BY created_at DESC",
+            API_KEY_COLUMNS
+        ))
         .bind(user_id.as_ulid().to_string())
         .fetch_all(&self.pool)
         .await
@@ -341,25 +521,59 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
             AuthError::InvalidCredentials
         })?;
 
-        let mut api_keys = Vec::new();
-        for row in rows {
-            api_keys.push(ApiKey {
-                id: ApiKeyId::parse(&row.get::<String, _>("id"))
-                    .map_err(|_| AuthError::InvalidCredentials)?,
-                user_id: UserId::parse(&row.get::<String, _>("user_id"))
-                    .map_err(|_| AuthError::InvalidCredentials)?,
-                key_hash: row.get("key_hash"),
-                name: row.get("name"),
-                expires_at: row.get("expires_at"),
-                enabled: row.get("enabled"),
-                created_at: row.get("created_at"),
-                last_used_at: row.get("last_used_at"),
-            });
-        }
+        rows.iter().map(row_to_api_key).collect()
+    }
 
-        Ok(api_keys)
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT api_keys")
+    )]
+    async fn find_by_group_id(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<ApiKey>, AuthError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE group_id = $1 ORDER BY created_at DESC",
+            API_KEY_COLUMNS
+        ))
+        .bind(group_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error in find_by_group_id: {}", e);
+            AuthError::InvalidCredentials
+        })?;
+
+        rows.iter().map(row_to_api_key).collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT api_keys")
+    )]
+    async fn find_by_receiver_id(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Vec<ApiKey>, AuthError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys WHERE receiver_id = $1 ORDER BY created_at DESC",
+            API_KEY_COLUMNS
+        ))
+        .bind(receiver_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error in find_by_receiver_id: {}", e);
+            AuthError::InvalidCredentials
+        })?;
+
+        rows.iter().map(row_to_api_key).collect()
+    }
+
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "UPDATE api_keys")
+    )]
     async fn revoke(&self, id: ApiKeyId) -> Result<(), AuthError> {
         sqlx::query("UPDATE api_keys SET enabled = FALSE WHERE id = $1")
             .bind(id.as_ulid().to_string())
@@ -372,4 +586,211 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
 
         Ok(())
     }
+
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT api_keys")
+    )]
+    async fn find_expiring(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<ApiKey>, AuthError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM api_keys \
+             WHERE enabled AND expires_at > $1 AND expires_at <= $2 \
+             ORDER BY expires_at",
+            API_KEY_COLUMNS
+        ))
+        .bind(from)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error in find_expiring: {}", e);
+            AuthError::InvalidCredentials
+        })?;
+
+        rows.iter().map(row_to_api_key).collect()
+    }
+
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "UPDATE api_keys")
+    )]
+    async fn record_expiry_warning(
+        &self,
+        id: ApiKeyId,
+        at: DateTime<Utc>,
+    ) -> Result<(), AuthError> {
+        sqlx::query("UPDATE api_keys SET last_expiry_warning_at = $2 WHERE id = $1")
+            .bind(id.as_ulid().to_string())
+            .bind(at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                eprintln!("Database error in record_expiry_warning: {}", e);
+                AuthError::InvalidCredentials
+            })?;
+
+        Ok(())
+    }
+}
+
+// PostgresApiKeyUsageRepository implementation
+pub struct PostgresApiKeyUsageRepository {
+    pool: PgPool,
+}
+
+impl PostgresApiKeyUsageRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn usage_error(operation: &str, e: sqlx::Error) -> AuthError {
+    eprintln!("Database error in {}: {}", operation, e);
+    AuthError::InvalidCredentials
+}
+
+#[async_trait::async_trait]
+impl ApiKeyUsageRepository for PostgresApiKeyUsageRepository {
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "INSERT api_key_daily_usage")
+    )]
+    async fn record_usage(&self, updates: &[ApiKeyUsageUpdate]) -> Result<(), AuthError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| usage_error("record_usage", e))?;
+
+        // Keys deleted since their requests are skipped rather than
+        // failing the batch on the foreign key
+        for update in updates {
+            let key_id = update.key_id.as_ulid().to_string();
+            for usage in &update.days {
+                sqlx::query(
+                    r#"
+                    INSERT INTO api_key_daily_usage
+                        (key_id, day, successes, auth_failures, authz_denials, rate_limited)
+                    SELECT $1, $2, $3, $4, $5, $6
+                    WHERE EXISTS (SELECT 1 FROM api_keys WHERE id = $1)
+                    ON CONFLICT (key_id, day) DO UPDATE SET
+                        successes = api_key_daily_usage.successes + EXCLUDED.successes,
+                        auth_failures = api_key_daily_usage.auth_failures + EXCLUDED.auth_failures,
+                        authz_denials = api_key_daily_usage.authz_denials + EXCLUDED.authz_denials,
+                        rate_limited = api_key_daily_usage.rate_limited + EXCLUDED.rate_limited
+                    "#,
+                )
+                .bind(&key_id)
+                .bind(usage.day)
+                .bind(usage.successes as i64)
+                .bind(usage.auth_failures as i64)
+                .bind(usage.authz_denials as i64)
+                .bind(usage.rate_limited as i64)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| usage_error("record_usage", e))?;
+            }
+
+            if update.failures.is_empty() {
+                continue;
+            }
+            for failure in &update.failures {
+                sqlx::query(
+                    "INSERT INTO api_key_failures (key_id, occurred_at, reason, route) \
+                     SELECT $1, $2, $3, $4 WHERE EXISTS (SELECT 1 FROM api_keys WHERE id = $1)",
+                )
+                .bind(&key_id)
+                .bind(failure.occurred_at)
+                .bind(&failure.reason)
+                .bind(&failure.route)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| usage_error("record_usage", e))?;
+            }
+            sqlx::query(
+                r#"
+                DELETE FROM api_key_failures
+                WHERE key_id = $1 AND id NOT IN (
+                    SELECT id FROM api_key_failures
+                    WHERE key_id = $1
+                    ORDER BY occurred_at DESC, id DESC
+                    LIMIT $2
+                )
+                "#,
+            )
+            .bind(&key_id)
+            .bind(MAX_RECENT_FAILURES as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| usage_error("record_usage", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| usage_error("record_usage", e))
+    }
+
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT api_key_daily_usage")
+    )]
+    async fn find_daily_usage(
+        &self,
+        key_id: ApiKeyId,
+        since: NaiveDate,
+    ) -> Result<Vec<ApiKeyDailyUsage>, AuthError> {
+        let rows = sqlx::query(
+            "SELECT day, successes, auth_failures, authz_denials, rate_limited \
+             FROM api_key_daily_usage WHERE key_id = $1 AND day >= $2 ORDER BY day",
+        )
+        .bind(key_id.as_ulid().to_string())
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| usage_error("find_daily_usage", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ApiKeyDailyUsage {
+                day: row.get("day"),
+                successes: row.get::<i64, _>("successes") as u64,
+                auth_failures: row.get::<i64, _>("auth_failures") as u64,
+                authz_denials: row.get::<i64, _>("authz_denials") as u64,
+                rate_limited: row.get::<i64, _>("rate_limited") as u64,
+            })
+            .collect())
+    }
+
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT api_key_failures")
+    )]
+    async fn find_recent_failures(
+        &self,
+        key_id: ApiKeyId,
+        limit: usize,
+    ) -> Result<Vec<ApiKeyFailure>, AuthError> {
+        let rows = sqlx::query(
+            "SELECT occurred_at, reason, route FROM api_key_failures \
+             WHERE key_id = $1 ORDER BY occurred_at DESC, id DESC LIMIT $2",
+        )
+        .bind(key_id.as_ulid().to_string())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| usage_error("find_recent_failures", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ApiKeyFailure {
+                occurred_at: row.get("occurred_at"),
+                reason: row.get("reason"),
+                route: row.get("route"),
+            })
+            .collect())
+    }
 }
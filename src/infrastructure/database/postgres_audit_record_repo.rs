@@ -0,0 +1,226 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_audit_record_repo.rs
+
+use crate::domain::entities::audit_chain::{next_link, record_hash, AuditRecord, ChainLink};
+use crate::domain::repositories::audit_record_repo::{AuditRecordRepository, PurgedAuditRecords};
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// First key of the advisory locks serializing appends to a day's chain;
+/// the second key is the day
+const AUDIT_CHAIN_LOCK_CLASS: i32 = 0x4155_4454;
+
+/// PostgreSQL implementation of the AuditRecordRepository trait
+pub struct PostgresAuditRecordRepository {
+    pool: PgPool,
+}
+
+impl PostgresAuditRecordRepository {
+    /// Creates a new PostgreSQL audit record repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Converts a database row to an AuditRecord
+    fn row_to_record(row: sqlx::postgres::PgRow) -> Result<AuditRecord> {
+        Ok(AuditRecord {
+            id: row.try_get("id")?,
+            chain_day: row.try_get("chain_day")?,
+            position: row.try_get("position")?,
+            recorded_at: row.try_get("recorded_at")?,
+            content: row.try_get("content")?,
+            prev_hash: row.try_get("prev_hash")?,
+            hash: row.try_get("hash")?,
+        })
+    }
+}
+
+#[async_trait]
+impl AuditRecordRepository for PostgresAuditRecordRepository {
+    #[instrument(
+        skip(self, content),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT audit_records"
+        )
+    )]
+    async fn append(&self, recorded_at: DateTime<Utc>, content: String) -> Result<AuditRecord> {
+        let chain_day = recorded_at.date_naive();
+        let mut tx = self.pool.begin().await?;
+
+        // Held until commit, so the day's last link cannot change under us
+        sqlx::query("SELECT pg_advisory_xact_lock($1, $2)")
+            .bind(AUDIT_CHAIN_LOCK_CLASS)
+            .bind(chain_day.num_days_from_ce())
+            .execute(&mut *tx)
+            .await?;
+
+        // A day whose records were all purged continues from its anchor
+        let last = sqlx::query(
+            r#"
+            SELECT position, hash FROM (
+                SELECT position, hash FROM audit_records WHERE chain_day = $1
+                UNION ALL
+                SELECT position, hash FROM audit_chain_anchors WHERE chain_day = $1
+            ) AS links
+            ORDER BY position DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(chain_day)
+        .fetch_optional(&mut *tx)
+        .await?
+        .map(|row| -> Result<ChainLink> {
+            Ok(ChainLink {
+                chain_day,
+                position: row.try_get("position")?,
+                hash: row.try_get("hash")?,
+            })
+        })
+        .transpose()?;
+
+        let (position, prev_hash) = next_link(chain_day, last.as_ref());
+        let hash = record_hash(&prev_hash, chain_day, position, &content);
+        let row = sqlx::query(
+            r#"
+            INSERT INTO audit_records (chain_day, position, recorded_at, content, prev_hash, hash)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, chain_day, position, recorded_at, content, prev_hash, hash
+            "#,
+        )
+        .bind(chain_day)
+        .bind(position)
+        .bind(recorded_at)
+        .bind(&content)
+        .bind(&prev_hash)
+        .bind(&hash)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Self::row_to_record(row)
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT audit_records"
+        )
+    )]
+    async fn find_records(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        after: Option<(NaiveDate, i64)>,
+        limit: usize,
+    ) -> Result<Vec<AuditRecord>> {
+        let (after_day, after_position) = match after {
+            Some((day, position)) => (Some(day), position),
+            None => (None, -1),
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, chain_day, position, recorded_at, content, prev_hash, hash
+            FROM audit_records
+            WHERE chain_day BETWEEN $1 AND $2
+              AND ($3::date IS NULL OR (chain_day, position) > ($3, $4))
+            ORDER BY chain_day, position
+            LIMIT $5
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(after_day)
+        .bind(after_position)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_record).collect()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT audit_records, audit_chain_anchors"
+        )
+    )]
+    async fn find_link(&self, chain_day: NaiveDate, position: i64) -> Result<Option<ChainLink>> {
+        let hash: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT hash FROM audit_records WHERE chain_day = $1 AND position = $2
+            UNION ALL
+            SELECT hash FROM audit_chain_anchors WHERE chain_day = $1 AND position = $2
+            LIMIT 1
+            "#,
+        )
+        .bind(chain_day)
+        .bind(position)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(hash.map(|hash| ChainLink {
+            chain_day,
+            position,
+            hash,
+        }))
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE audit_records"
+        )
+    )]
+    async fn purge_before(&self, before: NaiveDate, limit: usize) -> Result<PurgedAuditRecords> {
+        // Deleting and anchoring in one statement keeps every remaining
+        // record verifiable, even if the pass is interrupted
+        let row = sqlx::query(
+            r#"
+            WITH purged AS (
+                DELETE FROM audit_records
+                WHERE id IN (
+                    SELECT id FROM audit_records
+                    WHERE chain_day < $1
+                    ORDER BY chain_day, position
+                    LIMIT $2
+                )
+                RETURNING chain_day, position, hash
+            ),
+            anchored AS (
+                INSERT INTO audit_chain_anchors (chain_day, position, hash)
+                SELECT DISTINCT ON (chain_day) chain_day, position, hash
+                FROM purged
+                ORDER BY chain_day, position DESC
+                ON CONFLICT (chain_day, position) DO NOTHING
+                RETURNING chain_day
+            )
+            SELECT
+                (SELECT COUNT(*) FROM purged) AS records,
+                (SELECT COUNT(*) FROM anchored) AS anchors
+            "#,
+        )
+        .bind(before)
+        .bind(limit as i64)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(PurgedAuditRecords {
+            records: row.try_get::<i64, _>("records")? as usize,
+            anchors: row.try_get::<i64, _>("anchors")? as usize,
+        })
+    }
+}
@@ -0,0 +1,114 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_delivery_failure_repo.rs
+
+use crate::domain::entities::delivery_failure::{
+    DeliveryErrorClass, DeliveryFailure, DeliveryMechanism,
+};
+use crate::domain::repositories::delivery_failure_repo::{
+    DeliveryFailureRepository, FindDeliveryFailureCriteria,
+};
+use crate::error::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the DeliveryFailureRepository trait
+pub struct PostgresDeliveryFailureRepository {
+    pool: PgPool,
+}
+
+impl PostgresDeliveryFailureRepository {
+    /// Creates a new PostgreSQL delivery failure repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Converts a database row to a DeliveryFailure
+    fn row_to_failure(row: sqlx::postgres::PgRow) -> Result<DeliveryFailure> {
+        Ok(DeliveryFailure {
+            mechanism: row
+                .try_get::<String, _>("mechanism")?
+                .parse::<DeliveryMechanism>()?,
+            target: row.try_get("target")?,
+            event_id: row.try_get("event_id")?,
+            receiver_id: row.try_get("receiver_id")?,
+            error_class: row
+                .try_get::<String, _>("error_class")?
+                .parse::<DeliveryErrorClass>()?,
+            last_error: row.try_get("last_error")?,
+            attempts: row.try_get::<i32, _>("attempts")? as u32,
+            failed_at: row.try_get("failed_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl DeliveryFailureRepository for PostgresDeliveryFailureRepository {
+    #[instrument(
+        skip(self, failure),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT delivery_failures",
+            event_id = %failure.event_id
+        )
+    )]
+    async fn record(&self, failure: &DeliveryFailure) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO delivery_failures (
+                mechanism, target, event_id, receiver_id, error_class,
+                last_error, attempts, failed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (mechanism, event_id, target) DO NOTHING
+            "#,
+        )
+        .bind(failure.mechanism.as_str())
+        .bind(&failure.target)
+        .bind(failure.event_id)
+        .bind(failure.receiver_id)
+        .bind(failure.error_class.as_str())
+        .bind(&failure.last_error)
+        .bind(failure.attempts as i32)
+        .bind(failure.failed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT delivery_failures"
+        )
+    )]
+    async fn find_recent(
+        &self,
+        criteria: &FindDeliveryFailureCriteria,
+    ) -> Result<Vec<DeliveryFailure>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT mechanism, target, event_id, receiver_id, error_class,
+                   last_error, attempts, failed_at
+            FROM delivery_failures
+            WHERE ($1::text IS NULL OR receiver_id = $1)
+              AND ($2::text IS NULL OR error_class = $2)
+            ORDER BY failed_at DESC, event_id DESC, target
+            LIMIT $3
+            "#,
+        )
+        .bind(criteria.receiver_id.map(|id| id.to_string()))
+        .bind(criteria.error_class.map(|class| class.as_str()))
+        .bind(criteria.limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_failure).collect()
+    }
+}
@@ -0,0 +1,154 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_event_attachment_repo.rs
+
+use crate::domain::entities::event_attachment::EventAttachment;
+use crate::domain::repositories::event_attachment_repo::EventAttachmentRepository;
+use crate::domain::value_objects::{AttachmentId, EventId, EventReceiverId};
+use crate::error::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+const ATTACHMENT_COLUMNS: &str = "id, event_id, event_receiver_id, filename, content_type, \
+     size_bytes, sha256, storage_key, uploaded_by, created_at";
+
+/// PostgreSQL implementation of the EventAttachmentRepository trait
+pub struct PostgresEventAttachmentRepository {
+    pool: PgPool,
+}
+
+impl PostgresEventAttachmentRepository {
+    /// Creates a new PostgreSQL event attachment repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Converts a database row to an EventAttachment
+    fn row_to_attachment(row: sqlx::postgres::PgRow) -> Result<EventAttachment> {
+        Ok(EventAttachment {
+            id: row.try_get("id")?,
+            event_id: row.try_get("event_id")?,
+            event_receiver_id: row.try_get("event_receiver_id")?,
+            filename: row.try_get("filename")?,
+            content_type: row.try_get("content_type")?,
+            size_bytes: row.try_get::<i64, _>("size_bytes")?.max(0) as u64,
+            sha256: row.try_get("sha256")?,
+            storage_key: row.try_get("storage_key")?,
+            uploaded_by: row.try_get("uploaded_by")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl EventAttachmentRepository for PostgresEventAttachmentRepository {
+    #[instrument(
+        skip(self, attachment),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_attachments"
+        )
+    )]
+    async fn save(&self, attachment: &EventAttachment) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO event_attachments (id, event_id, event_receiver_id, filename,
+                                           content_type, size_bytes, sha256, storage_key,
+                                           uploaded_by, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(attachment.id)
+        .bind(attachment.event_id)
+        .bind(attachment.event_receiver_id)
+        .bind(&attachment.filename)
+        .bind(&attachment.content_type)
+        .bind(attachment.size_bytes as i64)
+        .bind(&attachment.sha256)
+        .bind(&attachment.storage_key)
+        .bind(attachment.uploaded_by)
+        .bind(attachment.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_attachments"
+        )
+    )]
+    async fn find_by_id(&self, id: AttachmentId) -> Result<Option<EventAttachment>> {
+        sqlx::query(&format!(
+            "SELECT {} FROM event_attachments WHERE id = $1",
+            ATTACHMENT_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?
+        .map(Self::row_to_attachment)
+        .transpose()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_attachments"
+        )
+    )]
+    async fn find_by_event(&self, event_id: EventId) -> Result<Vec<EventAttachment>> {
+        sqlx::query(&format!(
+            "SELECT {} FROM event_attachments WHERE event_id = $1 ORDER BY created_at, id",
+            ATTACHMENT_COLUMNS
+        ))
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .map(Self::row_to_attachment)
+        .collect()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_attachments"
+        )
+    )]
+    async fn delete(&self, id: AttachmentId) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM event_attachments WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT SUM event_attachments"
+        )
+    )]
+    async fn total_size_by_receiver(&self, receiver_id: EventReceiverId) -> Result<u64> {
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(size_bytes), 0)::BIGINT FROM event_attachments \
+             WHERE event_receiver_id = $1",
+        )
+        .bind(receiver_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(total.max(0) as u64)
+    }
+}
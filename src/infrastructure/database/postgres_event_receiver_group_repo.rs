@@ -4,14 +4,21 @@
 // src/infrastructure/database/postgres_event_receiver_group_repo.rs
 
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
 
 use crate::domain::entities::event_receiver_group::{EventReceiverGroup, EventReceiverGroupData};
+use crate::domain::repositories::change_feed_repo::{
+    Change, ChangeCursor, ChangeSet, EventReceiverGroupChangeFeedRepository,
+};
 use crate::domain::repositories::event_receiver_group_repo::{
-    EventReceiverGroupRepository, FindEventReceiverGroupCriteria,
+    EventReceiverGroupRepository, ExpiredMembership, FindEventReceiverGroupCriteria,
+    GroupMembership,
 };
+use crate::domain::repositories::pagination::{GroupSortField, ListOrder};
 use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId, UserId};
-use crate::error::Result;
+use crate::error::{DomainError, Result};
+use tracing::instrument;
 
 /// PostgreSQL implementation of EventReceiverGroupRepository
 pub struct PostgresEventReceiverGroupRepository {
@@ -29,18 +36,22 @@ impl PostgresEventReceiverGroupRepository {
         use sqlx::Row;
 
         Ok(EventReceiverGroupData {
-            id: EventReceiverGroupId::parse(&row.get::<String, _>("id")).map_err(|e| {
-                crate::error::Error::BadRequest {
+            id: row
+                .get::<String, _>("id")
+                .parse::<EventReceiverGroupId>()
+                .map_err(|e| crate::error::Error::BadRequest {
                     message: format!("Invalid group ID: {}", e),
-                }
-            })?,
+                })?,
             name: row.get("name"),
             group_type: row.get("group_type"),
             version: row.get("version"),
             description: row.get("description"),
             enabled: row.get("enabled"),
             event_receiver_ids: vec![], // Will be loaded separately
-            owner_id: UserId::from_string(row.get::<String, _>("owner_id")).map_err(|e| {
+            optional_receiver_ids: vec![],
+            default_schema: row.get("default_schema"),
+            dedicated_topic: row.get("dedicated_topic"),
+            owner_id: UserId::try_from(row.get::<String, _>("owner_id")).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid owner ID: {}", e),
                 }
@@ -51,6 +62,23 @@ impl PostgresEventReceiverGroupRepository {
         })
     }
 
+    /// Converts a group member row to a GroupMembership
+    fn row_to_membership(row: &sqlx::postgres::PgRow) -> Result<GroupMembership> {
+        let parse = |column: &str| {
+            let id_str: String = sqlx::Row::get(row, column);
+            UserId::try_from(id_str).map_err(|e| crate::error::Error::BadRequest {
+                message: format!("Invalid user ID: {}", e),
+            })
+        };
+
+        Ok(GroupMembership {
+            user_id: parse("user_id")?,
+            added_by: parse("added_by")?,
+            added_at: sqlx::Row::get(row, "added_at"),
+            expires_at: sqlx::Row::get(row, "expires_at"),
+        })
+    }
+
     /// Loads event receiver IDs for a group
     async fn load_receiver_ids(
         &self,
@@ -67,59 +95,195 @@ impl PostgresEventReceiverGroupRepository {
         rows.iter()
             .map(|row| {
                 let id_str: String = sqlx::Row::get(row, "receiver_id");
-                EventReceiverId::parse(&id_str).map_err(|e| crate::error::Error::BadRequest {
-                    message: format!("Invalid receiver ID: {}", e),
-                })
+                id_str
+                    .parse::<EventReceiverId>()
+                    .map_err(|e| crate::error::Error::BadRequest {
+                        message: format!("Invalid receiver ID: {}", e),
+                    })
             })
             .collect()
     }
 
-    /// Saves event receiver IDs for a group
-    async fn save_receiver_ids(
-        &self,
-        group_id: EventReceiverGroupId,
-        receiver_ids: &[EventReceiverId],
-    ) -> Result<()> {
-        // Delete existing associations
-        sqlx::query("DELETE FROM event_receiver_group_receivers WHERE group_id = $1")
-            .bind(group_id.to_string())
-            .execute(&self.pool)
-            .await
-            .map_err(crate::error::Error::Database)?;
+    /// Loads the member receivers of a group into `data`, splitting off the
+    /// optional ones
+    async fn load_receivers(&self, data: &mut EventReceiverGroupData) -> Result<()> {
+        let rows = sqlx::query(
+            "SELECT receiver_id, required FROM event_receiver_group_receivers WHERE group_id = $1 ORDER BY added_at",
+        )
+        .bind(data.id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        data.event_receiver_ids = Vec::with_capacity(rows.len());
+        data.optional_receiver_ids = Vec::new();
+        for row in &rows {
+            let id_str: String = sqlx::Row::get(row, "receiver_id");
+            let receiver_id =
+                id_str
+                    .parse::<EventReceiverId>()
+                    .map_err(|e| crate::error::Error::BadRequest {
+                        message: format!("Invalid receiver ID: {}", e),
+                    })?;
+            if !sqlx::Row::get::<bool, _>(row, "required") {
+                data.optional_receiver_ids.push(receiver_id);
+            }
+            data.event_receiver_ids.push(receiver_id);
+        }
+
+        Ok(())
+    }
+
+    /// Saves the member receivers of a group and whether each is required
+    ///
+    /// Memberships that remain are updated in place so their `is_default`
+    /// flag survives.
+    async fn save_receivers(conn: &mut PgConnection, group: &EventReceiverGroup) -> Result<()> {
+        let receiver_ids: Vec<String> = group
+            .event_receiver_ids()
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+
+        // Delete associations the group no longer has
+        sqlx::query(
+            "DELETE FROM event_receiver_group_receivers WHERE group_id = $1 AND NOT (receiver_id = ANY($2))",
+        )
+        .bind(group.id().to_string())
+        .bind(&receiver_ids)
+        .execute(&mut *conn)
+        .await
+        .map_err(crate::error::Error::Database)?;
 
-        // Insert new associations
-        for receiver_id in receiver_ids {
+        // Insert new associations and update the remaining ones
+        for &receiver_id in group.event_receiver_ids() {
             sqlx::query(
-                "INSERT INTO event_receiver_group_receivers (group_id, receiver_id) VALUES ($1, $2)",
+                "INSERT INTO event_receiver_group_receivers (group_id, receiver_id, required) VALUES ($1, $2, $3) \
+                 ON CONFLICT (group_id, receiver_id) DO UPDATE SET required = EXCLUDED.required",
             )
-            .bind(group_id.to_string())
+            .bind(group.id().to_string())
             .bind(receiver_id.to_string())
-            .execute(&self.pool)
+            .bind(group.is_required(receiver_id))
+            .execute(&mut *conn)
             .await
             .map_err(crate::error::Error::Database)?;
         }
 
         Ok(())
     }
+
+    /// Builds the FROM and WHERE clauses for criteria, aliasing groups as `g`
+    fn build_filter_clause(criteria: &FindEventReceiverGroupCriteria) -> String {
+        let mut conditions = Vec::new();
+        let mut param_count = 1;
+
+        if criteria.id.is_some() {
+            conditions.push(format!("g.id = ${}", param_count));
+            param_count += 1;
+        }
+        if criteria.name.is_some() {
+            conditions.push(format!("g.name ILIKE ${}", param_count));
+            param_count += 1;
+        }
+        if criteria.group_type.is_some() {
+            conditions.push(format!("g.group_type = ${}", param_count));
+            param_count += 1;
+        }
+        if criteria.version.is_some() {
+            conditions.push(format!("g.version = ${}", param_count));
+            param_count += 1;
+        }
+        if criteria.enabled.is_some() {
+            conditions.push(format!("g.enabled = ${}", param_count));
+            param_count += 1;
+        }
+        if criteria.owner_id.is_some() {
+            conditions.push(format!("g.owner_id = ${}", param_count));
+            param_count += 1;
+        }
+
+        let mut clause = "FROM event_receiver_groups g".to_string();
+        if criteria.contains_receiver_id.is_some() {
+            clause.push_str(" INNER JOIN event_receiver_group_receivers gr ON g.id = gr.group_id");
+            conditions.push(format!("gr.receiver_id = ${}", param_count));
+        }
+
+        if !conditions.is_empty() {
+            clause.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+
+        clause
+    }
+
+    /// Binds the parameters of a clause built by `build_filter_clause`
+    fn bind_criteria<'q>(
+        mut query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+        criteria: &FindEventReceiverGroupCriteria,
+    ) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+        if let Some(id) = &criteria.id {
+            query = query.bind(id.to_string());
+        }
+        if let Some(name) = &criteria.name {
+            query = query.bind(format!("%{}%", name));
+        }
+        if let Some(group_type) = &criteria.group_type {
+            query = query.bind(group_type.clone());
+        }
+        if let Some(version) = &criteria.version {
+            query = query.bind(version.clone());
+        }
+        if let Some(enabled) = criteria.enabled {
+            query = query.bind(enabled);
+        }
+        if let Some(owner_id) = &criteria.owner_id {
+            query = query.bind(owner_id.to_string());
+        }
+        if let Some(receiver_id) = &criteria.contains_receiver_id {
+            query = query.bind(receiver_id.to_string());
+        }
+        query
+    }
+
+    /// Returns the ORDER BY clause for a sort order, tie-broken by id
+    fn order_by_clause(order: ListOrder<GroupSortField>) -> String {
+        let column = match order.field {
+            GroupSortField::CreatedAt => "g.created_at",
+            GroupSortField::UpdatedAt => "g.updated_at",
+            GroupSortField::Name => "g.name",
+        };
+        let direction = order.direction.as_sql();
+        format!("ORDER BY {} {}, g.id {}", column, direction, direction)
+    }
 }
 
 #[async_trait]
 impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receiver_groups"
+        )
+    )]
     async fn save(&self, group: &EventReceiverGroup) -> Result<()> {
         // Save the group
         sqlx::query(
             r#"
             INSERT INTO event_receiver_groups (
                 id, name, group_type, version, description, enabled,
-                owner_id, resource_version, created_at, updated_at
+                owner_id, resource_version, created_at, updated_at, default_schema,
+                dedicated_topic
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             ON CONFLICT (id) DO UPDATE SET
                 name = EXCLUDED.name,
                 group_type = EXCLUDED.group_type,
                 version = EXCLUDED.version,
                 description = EXCLUDED.description,
                 enabled = EXCLUDED.enabled,
+                default_schema = EXCLUDED.default_schema,
+                dedicated_topic = EXCLUDED.dedicated_topic,
                 owner_id = EXCLUDED.owner_id,
                 resource_version = EXCLUDED.resource_version,
                 updated_at = EXCLUDED.updated_at
@@ -135,22 +299,36 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         .bind(group.resource_version())
         .bind(group.created_at())
         .bind(group.updated_at())
+        .bind(group.default_schema())
+        .bind(group.dedicated_topic())
         .execute(&self.pool)
         .await
         .map_err(crate::error::Error::Database)?;
 
         // Save receiver associations
-        self.save_receiver_ids(group.id(), group.event_receiver_ids())
-            .await?;
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(crate::error::Error::Database)?;
+        Self::save_receivers(&mut conn, group).await?;
 
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_by_id(&self, id: EventReceiverGroupId) -> Result<Option<EventReceiverGroup>> {
         let row = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             WHERE id = $1
             "#,
@@ -163,7 +341,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         match row {
             Some(r) => {
                 let mut data = Self::row_to_data(&r)?;
-                data.event_receiver_ids = self.load_receiver_ids(id).await?;
+                self.load_receivers(&mut data).await?;
                 Ok(Some(EventReceiverGroup::from_existing(data).map_err(
                     |e| crate::error::Error::BadRequest {
                         message: format!("Invalid event receiver group data: {}", e),
@@ -174,11 +352,19 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         }
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiverGroup>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             WHERE name ILIKE $1
             ORDER BY created_at DESC
@@ -192,7 +378,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -203,11 +389,19 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_by_type(&self, group_type: &str) -> Result<Vec<EventReceiverGroup>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             WHERE group_type = $1
             ORDER BY created_at DESC
@@ -221,7 +415,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -232,6 +426,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_by_type_and_version(
         &self,
         group_type: &str,
@@ -240,7 +442,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let rows = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             WHERE group_type = $1 AND version = $2
             ORDER BY created_at DESC
@@ -255,7 +457,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -266,11 +468,19 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_enabled(&self) -> Result<Vec<EventReceiverGroup>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             WHERE enabled = true
             ORDER BY created_at DESC
@@ -283,7 +493,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -294,11 +504,19 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_disabled(&self) -> Result<Vec<EventReceiverGroup>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             WHERE enabled = false
             ORDER BY created_at DESC
@@ -311,7 +529,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -322,6 +540,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_by_event_receiver_id(
         &self,
         receiver_id: EventReceiverId,
@@ -329,7 +555,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let rows = sqlx::query(
             r#"
             SELECT g.id, g.name, g.group_type, g.version, g.description, g.enabled,
-                   g.owner_id, g.resource_version, g.created_at, g.updated_at
+                   g.default_schema, g.dedicated_topic, g.owner_id, g.resource_version, g.created_at, g.updated_at
             FROM event_receiver_groups g
             INNER JOIN event_receiver_group_receivers gr ON g.id = gr.group_id
             WHERE gr.receiver_id = $1
@@ -344,7 +570,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -355,11 +581,19 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiverGroup>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
@@ -374,7 +608,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -385,6 +619,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn count(&self) -> Result<usize> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM event_receiver_groups")
             .fetch_one(&self.pool)
@@ -395,6 +637,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(count as usize)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn count_enabled(&self) -> Result<usize> {
         let row =
             sqlx::query("SELECT COUNT(*) as count FROM event_receiver_groups WHERE enabled = true")
@@ -406,6 +656,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(count as usize)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn count_disabled(&self) -> Result<usize> {
         let row = sqlx::query(
             "SELECT COUNT(*) as count FROM event_receiver_groups WHERE enabled = false",
@@ -418,63 +676,147 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(count as usize)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_receiver_groups"
+        )
+    )]
     async fn update(&self, group: &EventReceiverGroup) -> Result<()> {
-        let result = sqlx::query(
-            r#"
-            UPDATE event_receiver_groups
-            SET name = $2,
-                group_type = $3,
-                version = $4,
-                description = $5,
-                enabled = $6,
-                owner_id = $7,
-                resource_version = $8,
-                updated_at = $9
-            WHERE id = $1
-            "#,
+        self.update_many(std::slice::from_ref(group)).await
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_receiver_groups"
         )
-        .bind(group.id().to_string())
-        .bind(group.name())
-        .bind(group.group_type())
-        .bind(group.version())
-        .bind(group.description())
-        .bind(group.enabled())
-        .bind(group.owner_id().to_string())
-        .bind(group.resource_version())
-        .bind(group.updated_at())
-        .execute(&self.pool)
-        .await
-        .map_err(crate::error::Error::Database)?;
+    )]
+    async fn update_many(&self, groups: &[EventReceiverGroup]) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::Error::Database)?;
 
-        if result.rows_affected() == 0 {
-            return Err(crate::error::Error::NotFound {
-                resource: format!("Event receiver group with ID {} not found", group.id()),
-            });
+        for group in groups {
+            let result = sqlx::query(
+                r#"
+                UPDATE event_receiver_groups
+                SET name = $2,
+                    group_type = $3,
+                    version = $4,
+                    description = $5,
+                    enabled = $6,
+                    owner_id = $7,
+                    resource_version = $8,
+                    updated_at = $9,
+                    default_schema = $10,
+                    dedicated_topic = $11
+                WHERE id = $1
+                "#,
+            )
+            .bind(group.id().to_string())
+            .bind(group.name())
+            .bind(group.group_type())
+            .bind(group.version())
+            .bind(group.description())
+            .bind(group.enabled())
+            .bind(group.owner_id().to_string())
+            .bind(group.resource_version())
+            .bind(group.updated_at())
+            .bind(group.default_schema())
+            .bind(group.dedicated_topic())
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::Error::Database)?;
+
+            if result.rows_affected() == 0 {
+                return Err(crate::error::Error::NotFound {
+                    resource: format!("Event receiver group with ID {} not found", group.id()),
+                });
+            }
+
+            // Update receiver associations
+            Self::save_receivers(&mut tx, group).await?;
         }
 
-        // Update receiver associations
-        self.save_receiver_ids(group.id(), group.event_receiver_ids())
-            .await?;
+        tx.commit().await.map_err(crate::error::Error::Database)?;
 
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_receiver_groups"
+        )
+    )]
     async fn delete(&self, id: EventReceiverGroupId) -> Result<()> {
-        let result = sqlx::query("DELETE FROM event_receiver_groups WHERE id = $1")
-            .bind(id.to_string())
-            .execute(&self.pool)
+        self.delete_many(&[id]).await
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_receiver_groups"
+        )
+    )]
+    async fn delete_many(&self, ids: &[EventReceiverGroupId]) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
             .await
             .map_err(crate::error::Error::Database)?;
 
-        if result.rows_affected() == 0 {
-            return Err(crate::error::Error::NotFound {
-                resource: format!("Event receiver group with ID {} not found", id),
-            });
+        for id in ids {
+            let result = sqlx::query("DELETE FROM event_receiver_groups WHERE id = $1")
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::Error::Database)?;
+
+            if result.rows_affected() == 0 {
+                return Err(crate::error::Error::NotFound {
+                    resource: format!("Event receiver group with ID {} not found", id),
+                });
+            }
+
+            // Record the deletion so change feed consumers can observe it
+            sqlx::query(
+                r#"
+                INSERT INTO event_receiver_group_tombstones (id, deleted_at)
+                VALUES ($1, NOW())
+                ON CONFLICT (id) DO UPDATE SET deleted_at = EXCLUDED.deleted_at
+                "#,
+            )
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::Error::Database)?;
         }
 
+        tx.commit().await.map_err(crate::error::Error::Database)?;
+
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_receiver_groups"
+        )
+    )]
     async fn enable(&self, id: EventReceiverGroupId) -> Result<()> {
         let result = sqlx::query(
             "UPDATE event_receiver_groups SET enabled = true, updated_at = NOW() WHERE id = $1",
@@ -493,6 +835,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_receiver_groups"
+        )
+    )]
     async fn disable(&self, id: EventReceiverGroupId) -> Result<()> {
         let result = sqlx::query(
             "UPDATE event_receiver_groups SET enabled = false, updated_at = NOW() WHERE id = $1",
@@ -511,6 +861,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn exists_by_name_and_type(&self, name: &str, group_type: &str) -> Result<bool> {
         let row = sqlx::query(
             "SELECT EXISTS(SELECT 1 FROM event_receiver_groups WHERE name = $1 AND group_type = $2) as exists",
@@ -527,71 +885,28 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(exists)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_by_criteria(
         &self,
         criteria: FindEventReceiverGroupCriteria,
     ) -> Result<Vec<EventReceiverGroup>> {
-        let mut conditions = Vec::new();
-        let mut param_count = 1;
-
-        if criteria.id.is_some() {
-            conditions.push(format!("g.id = ${}", param_count));
-            param_count += 1;
-        }
-        if criteria.name.is_some() {
-            conditions.push(format!("g.name ILIKE ${}", param_count));
-            param_count += 1;
-        }
-        if criteria.group_type.is_some() {
-            conditions.push(format!("g.group_type = ${}", param_count));
-            param_count += 1;
-        }
-        if criteria.version.is_some() {
-            conditions.push(format!("g.version = ${}", param_count));
-            param_count += 1;
-        }
-        if criteria.enabled.is_some() {
-            conditions.push(format!("g.enabled = ${}", param_count));
-            param_count += 1;
-        }
-
-        let where_clause = if conditions.is_empty() {
-            String::new()
-        } else {
-            format!("WHERE {}", conditions.join(" AND "))
-        };
-
-        let mut query = if criteria.contains_receiver_id.is_some() {
-            let receiver_clause = format!("gr.receiver_id = ${}", param_count);
-            format!(
-                r#"
-                SELECT DISTINCT g.id, g.name, g.group_type, g.version, g.description, g.enabled,
-                       g.owner_id, g.resource_version, g.created_at, g.updated_at
-                FROM event_receiver_groups g
-                INNER JOIN event_receiver_group_receivers gr ON g.id = gr.group_id
-                {}
-                {}
-                ORDER BY g.created_at DESC
-                "#,
-                if !where_clause.is_empty() {
-                    where_clause.clone() + " AND"
-                } else {
-                    "WHERE".to_string()
-                },
-                receiver_clause
-            )
-        } else {
-            format!(
-                r#"
-                SELECT id, name, group_type, version, description, enabled,
-                       owner_id, resource_version, created_at, updated_at
-                FROM event_receiver_groups g
-                {}
-                ORDER BY created_at DESC
-                "#,
-                where_clause
-            )
-        };
+        let mut query = format!(
+            r#"
+            SELECT DISTINCT g.id, g.name, g.group_type, g.version, g.description, g.enabled,
+                   g.default_schema, g.dedicated_topic, g.owner_id, g.resource_version, g.created_at, g.updated_at
+            {}
+            {}
+            "#,
+            Self::build_filter_clause(&criteria),
+            Self::order_by_clause(criteria.order)
+        );
 
         if let Some(limit) = criteria.limit {
             query.push_str(&format!(" LIMIT {}", limit));
@@ -600,26 +915,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
             query.push_str(&format!(" OFFSET {}", offset));
         }
 
-        let mut sql_query = sqlx::query(&query);
-
-        if let Some(id) = &criteria.id {
-            sql_query = sql_query.bind(id.to_string());
-        }
-        if let Some(name) = &criteria.name {
-            sql_query = sql_query.bind(format!("%{}%", name));
-        }
-        if let Some(group_type) = &criteria.group_type {
-            sql_query = sql_query.bind(group_type);
-        }
-        if let Some(version) = &criteria.version {
-            sql_query = sql_query.bind(version);
-        }
-        if let Some(enabled) = criteria.enabled {
-            sql_query = sql_query.bind(enabled);
-        }
-        if let Some(receiver_id) = &criteria.contains_receiver_id {
-            sql_query = sql_query.bind(receiver_id.to_string());
-        }
+        let sql_query = Self::bind_criteria(sqlx::query(&query), &criteria);
 
         let rows = sql_query
             .fetch_all(&self.pool)
@@ -629,7 +925,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -640,6 +936,37 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
+    async fn count_by_criteria(&self, criteria: FindEventReceiverGroupCriteria) -> Result<usize> {
+        let query = format!(
+            "SELECT COUNT(DISTINCT g.id) as count {}",
+            Self::build_filter_clause(&criteria)
+        );
+
+        let row = Self::bind_criteria(sqlx::query(&query), &criteria)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(crate::error::Error::Database)?;
+
+        let count: i64 = sqlx::Row::get(&row, "count");
+        Ok(count as usize)
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receiver_group_receivers"
+        )
+    )]
     async fn add_event_receiver_to_group(
         &self,
         group_id: EventReceiverGroupId,
@@ -666,6 +993,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_receiver_group_receivers"
+        )
+    )]
     async fn remove_event_receiver_from_group(
         &self,
         group_id: EventReceiverGroupId,
@@ -690,6 +1025,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_group_receivers"
+        )
+    )]
     async fn get_group_event_receivers(
         &self,
         group_id: EventReceiverGroupId,
@@ -697,11 +1040,100 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         self.load_receiver_ids(group_id).await
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_group_receivers"
+        )
+    )]
+    async fn find_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<EventReceiverGroupId>> {
+        let group_id: Option<String> = sqlx::query_scalar(
+            "SELECT group_id FROM event_receiver_group_receivers WHERE receiver_id = $1 AND is_default",
+        )
+        .bind(receiver_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        group_id
+            .map(|id| {
+                id.parse::<EventReceiverGroupId>()
+                    .map_err(|e| crate::error::Error::BadRequest {
+                        message: format!("Invalid group ID: {}", e),
+                    })
+            })
+            .transpose()
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_receiver_group_receivers"
+        )
+    )]
+    async fn set_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+        group_id: EventReceiverGroupId,
+    ) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::Error::Database)?;
+
+        // Clear the previous default first; idx_group_receivers_one_default
+        // rejects a second one even within the transaction
+        sqlx::query(
+            "UPDATE event_receiver_group_receivers SET is_default = FALSE WHERE receiver_id = $1 AND is_default",
+        )
+        .bind(receiver_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        let result = sqlx::query(
+            "UPDATE event_receiver_group_receivers SET is_default = TRUE WHERE receiver_id = $1 AND group_id = $2",
+        )
+        .bind(receiver_id.to_string())
+        .bind(group_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        if result.rows_affected() == 0 {
+            // Dropping the transaction rolls back the cleared default
+            return Err(DomainError::NotFound {
+                entity: "group membership".to_string(),
+                id: format!("{}/{}", group_id, receiver_id),
+            }
+            .into());
+        }
+
+        tx.commit().await.map_err(crate::error::Error::Database)?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiverGroup>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             WHERE owner_id = $1
             ORDER BY created_at DESC
@@ -715,7 +1147,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -726,6 +1158,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_by_owner_paginated(
         &self,
         owner_id: UserId,
@@ -735,7 +1175,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let rows = sqlx::query(
             r#"
             SELECT id, name, group_type, version, description, enabled,
-                   owner_id, resource_version, created_at, updated_at
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
             FROM event_receiver_groups
             WHERE owner_id = $1
             ORDER BY created_at DESC
@@ -752,7 +1192,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -763,6 +1203,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(groups)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn is_owner(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<bool> {
         let row = sqlx::query(
             "SELECT EXISTS(SELECT 1 FROM event_receiver_groups WHERE id = $1 AND owner_id = $2) as is_owner",
@@ -779,6 +1227,14 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(is_owner)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn get_resource_version(&self, group_id: EventReceiverGroupId) -> Result<Option<i64>> {
         let row = sqlx::query("SELECT resource_version FROM event_receiver_groups WHERE id = $1")
             .bind(group_id.to_string())
@@ -795,9 +1251,17 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         }
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_group_members"
+        )
+    )]
     async fn is_member(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<bool> {
         let row = sqlx::query(
-            "SELECT EXISTS(SELECT 1 FROM event_receiver_group_members WHERE group_id = $1 AND user_id = $2) as is_member",
+            "SELECT EXISTS(SELECT 1 FROM event_receiver_group_members WHERE group_id = $1 AND user_id = $2 AND (expires_at IS NULL OR expires_at > NOW())) as is_member",
         )
         .bind(group_id.to_string())
         .bind(user_id.to_string())
@@ -811,9 +1275,17 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(is_member)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_group_members"
+        )
+    )]
     async fn get_group_members(&self, group_id: EventReceiverGroupId) -> Result<Vec<UserId>> {
         let rows = sqlx::query(
-            "SELECT user_id FROM event_receiver_group_members WHERE group_id = $1 ORDER BY added_at",
+            "SELECT user_id FROM event_receiver_group_members WHERE group_id = $1 AND (expires_at IS NULL OR expires_at > NOW()) ORDER BY added_at",
         )
         .bind(group_id.to_string())
         .fetch_all(&self.pool)
@@ -825,34 +1297,175 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         rows.iter()
             .map(|row| {
                 let id_str: String = sqlx::Row::get(row, "user_id");
-                UserId::from_string(id_str).map_err(|e| crate::error::Error::BadRequest {
+                UserId::try_from(id_str).map_err(|e| crate::error::Error::BadRequest {
                     message: format!("Invalid user ID: {}", e),
                 })
             })
             .collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_group_members"
+        )
+    )]
+    async fn get_group_memberships(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<GroupMembership>> {
+        let rows = sqlx::query(
+            "SELECT user_id, added_by, added_at, expires_at FROM event_receiver_group_members WHERE group_id = $1 AND (expires_at IS NULL OR expires_at > NOW()) ORDER BY added_at, user_id",
+        )
+        .bind(group_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        rows.iter().map(Self::row_to_membership).collect()
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receiver_group_members"
+        )
+    )]
     async fn add_member(
         &self,
         group_id: EventReceiverGroupId,
         user_id: UserId,
         added_by: UserId,
+        expires_at: Option<DateTime<Utc>>,
     ) -> Result<()> {
-        sqlx::query(
-            "INSERT INTO event_receiver_group_members (group_id, user_id, added_by) VALUES ($1, $2, $3)",
+        // An expired row the expiry job has not deleted yet is replaced; a
+        // current one is left alone and reported as a conflict
+        let result = sqlx::query(
+            r#"
+            INSERT INTO event_receiver_group_members (group_id, user_id, added_by, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (group_id, user_id) DO UPDATE
+            SET added_by = EXCLUDED.added_by, added_at = NOW(), expires_at = EXCLUDED.expires_at
+            WHERE event_receiver_group_members.expires_at <= NOW()
+            "#,
         )
         .bind(group_id.to_string())
         .bind(user_id.to_string())
         .bind(added_by.to_string())
+        .bind(expires_at)
         .execute(&self.pool)
         .await
-        .map_err(|e| {
-            crate::error::Error::Database(e)
-        })?;
+        .map_err(crate::error::Error::Database)?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::AlreadyExists {
+                entity: "group member".to_string(),
+                identifier: user_id.to_string(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_receiver_group_members"
+        )
+    )]
+    async fn set_member_expiry(
+        &self,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let result = sqlx::query(
+            r#"
+            UPDATE event_receiver_group_members SET expires_at = $3
+            WHERE group_id = $1 AND user_id = $2 AND (expires_at IS NULL OR expires_at > NOW())
+            "#,
+        )
+        .bind(group_id.to_string())
+        .bind(user_id.to_string())
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        if result.rows_affected() == 0 {
+            return Err(crate::error::Error::NotFound {
+                resource: format!("User {} is not a member of group {}", user_id, group_id),
+            });
+        }
 
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_receiver_group_members"
+        )
+    )]
+    async fn remove_expired_members(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ExpiredMembership>> {
+        let rows = sqlx::query(
+            r#"
+            DELETE FROM event_receiver_group_members
+            WHERE (group_id, user_id) IN (
+                SELECT group_id, user_id FROM event_receiver_group_members
+                WHERE expires_at <= $1
+                ORDER BY expires_at, group_id, user_id
+                LIMIT $2
+            )
+            RETURNING group_id, user_id, added_by, added_at, expires_at
+            "#,
+        )
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        let mut expired = rows
+            .iter()
+            .map(|row| {
+                let group_id: String = sqlx::Row::get(row, "group_id");
+                Ok(ExpiredMembership {
+                    group_id: group_id.parse::<EventReceiverGroupId>().map_err(|e| {
+                        crate::error::Error::BadRequest {
+                            message: format!("Invalid group ID: {}", e),
+                        }
+                    })?,
+                    membership: Self::row_to_membership(row)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        expired.sort_by_key(|e| e.membership.expires_at);
+
+        Ok(expired)
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_receiver_group_members"
+        )
+    )]
     async fn remove_member(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<()> {
         let result = sqlx::query(
             "DELETE FROM event_receiver_group_members WHERE group_id = $1 AND user_id = $2",
@@ -872,14 +1485,22 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
     async fn find_groups_for_user(&self, user_id: UserId) -> Result<Vec<EventReceiverGroup>> {
         let rows = sqlx::query(
             r#"
             SELECT g.id, g.name, g.group_type, g.version, g.description, g.enabled,
-                   g.owner_id, g.resource_version, g.created_at, g.updated_at
+                   g.default_schema, g.dedicated_topic, g.owner_id, g.resource_version, g.created_at, g.updated_at
             FROM event_receiver_groups g
             INNER JOIN event_receiver_group_members m ON g.id = m.group_id
-            WHERE m.user_id = $1
+            WHERE m.user_id = $1 AND (m.expires_at IS NULL OR m.expires_at > NOW())
             ORDER BY g.created_at DESC
             "#,
         )
@@ -891,7 +1512,7 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
         let mut groups = Vec::new();
         for row in rows {
             let mut data = Self::row_to_data(&row)?;
-            data.event_receiver_ids = self.load_receiver_ids(data.id).await?;
+            self.load_receivers(&mut data).await?;
             groups.push(EventReceiverGroup::from_existing(data).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid event receiver group data: {}", e),
@@ -903,10 +1524,127 @@ impl EventReceiverGroupRepository for PostgresEventReceiverGroupRepository {
     }
 }
 
+#[async_trait]
+impl EventReceiverGroupChangeFeedRepository for PostgresEventReceiverGroupRepository {
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
+    async fn group_changes_since(
+        &self,
+        since: &ChangeCursor,
+        limit: usize,
+    ) -> Result<ChangeSet<EventReceiverGroup, EventReceiverGroupId>> {
+        // Fetch one extra row from each source so has_more is exact after merging.
+        // Ids compare bytewise (COLLATE "C") to match ChangeCursor ordering.
+        let fetch = limit as i64 + 1;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, group_type, version, description, enabled,
+                   default_schema, dedicated_topic, owner_id, resource_version, created_at, updated_at
+            FROM event_receiver_groups
+            WHERE (updated_at, id COLLATE "C") > ($1, $2)
+            ORDER BY updated_at, id COLLATE "C"
+            LIMIT $3
+            "#,
+        )
+        .bind(since.changed_at())
+        .bind(since.id())
+        .bind(fetch)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        let tombstones = sqlx::query(
+            r#"
+            SELECT id, deleted_at
+            FROM event_receiver_group_tombstones
+            WHERE (deleted_at, id COLLATE "C") > ($1, $2)
+            ORDER BY deleted_at, id COLLATE "C"
+            LIMIT $3
+            "#,
+        )
+        .bind(since.changed_at())
+        .bind(since.id())
+        .bind(fetch)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        let mut changes = Vec::with_capacity(rows.len() + tombstones.len());
+
+        for row in &rows {
+            let mut data = Self::row_to_data(row)?;
+            self.load_receivers(&mut data).await?;
+            let group = EventReceiverGroup::from_existing(data).map_err(|e| {
+                crate::error::Error::BadRequest {
+                    message: format!("Invalid event receiver group data: {}", e),
+                }
+            })?;
+            let cursor = ChangeCursor::new(group.updated_at(), group.id().to_string());
+            changes.push((cursor, Change::Upserted(group)));
+        }
+
+        for row in &tombstones {
+            let id_str: String = sqlx::Row::get(row, "id");
+            let id = id_str.parse::<EventReceiverGroupId>().map_err(|e| {
+                crate::error::Error::BadRequest {
+                    message: format!("Invalid group ID: {}", e),
+                }
+            })?;
+            let cursor = ChangeCursor::new(sqlx::Row::get(row, "deleted_at"), id_str);
+            changes.push((cursor, Change::Deleted(id)));
+        }
+
+        Ok(ChangeSet::from_changes(since, limit, changes))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::domain::repositories::pagination::SortField;
+
     #[test]
     fn test_repository_creation() {
         // This is a placeholder test - actual database tests would require test database setup
     }
+
+    #[test]
+    fn test_build_filter_clause() {
+        let criteria = FindEventReceiverGroupCriteria::new();
+        assert_eq!(
+            PostgresEventReceiverGroupRepository::build_filter_clause(&criteria),
+            "FROM event_receiver_groups g"
+        );
+
+        let criteria = FindEventReceiverGroupCriteria::new()
+            .with_enabled(true)
+            .with_owner(UserId::new())
+            .containing_receiver(EventReceiverId::new());
+        let clause = PostgresEventReceiverGroupRepository::build_filter_clause(&criteria);
+        assert!(clause.contains("INNER JOIN event_receiver_group_receivers gr"));
+        assert!(
+            clause.ends_with("WHERE g.enabled = $1 AND g.owner_id = $2 AND gr.receiver_id = $3")
+        );
+    }
+
+    #[test]
+    fn test_order_by_clause_maps_every_field() {
+        for (name, field) in GroupSortField::FIELDS {
+            for order in [ListOrder::asc(*field), ListOrder::desc(*field)] {
+                // Ties on the sort column are broken by id so pages stay stable
+                let direction = order.direction.as_sql();
+                assert_eq!(
+                    PostgresEventReceiverGroupRepository::order_by_clause(order),
+                    format!("ORDER BY g.{name} {direction}, g.id {direction}")
+                );
+            }
+        }
+    }
 }
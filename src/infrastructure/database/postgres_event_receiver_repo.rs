@@ -4,14 +4,31 @@
 // src/infrastructure/database/postgres_event_receiver_repo.rs
 
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::Value as JsonValue;
+use sqlx::types::Json;
 use sqlx::PgPool;
 
 use crate::domain::entities::event_receiver::{EventReceiver, EventReceiverData};
+use crate::domain::entities::receiver_activity::{ReceiverActivity, ReceiverActivityUpdate};
+use crate::domain::entities::receiver_heartbeat::ReceiverHeartbeat;
+use crate::domain::repositories::change_feed_repo::{
+    Change, ChangeCursor, ChangeSet, EventReceiverChangeFeedRepository,
+};
 use crate::domain::repositories::event_receiver_repo::{
     EventReceiverRepository, FindEventReceiverCriteria,
 };
+use crate::domain::repositories::event_sampling_repo::{
+    EventSamplingCounterRepository, SamplingCounts,
+};
+use crate::domain::repositories::pagination::{ListOrder, ReceiverSortField};
+use crate::domain::repositories::receiver_activity_repo::ReceiverActivityRepository;
+use crate::domain::repositories::receiver_heartbeat_repo::{
+    HeartbeatWrite, ReceiverHeartbeatRepository,
+};
 use crate::domain::value_objects::{EventReceiverId, UserId};
 use crate::error::Result;
+use tracing::instrument;
 
 /// PostgreSQL implementation of EventReceiverRepository
 pub struct PostgresEventReceiverRepository {
@@ -29,24 +46,56 @@ impl PostgresEventReceiverRepository {
         use sqlx::Row;
 
         Ok(EventReceiverData {
-            id: EventReceiverId::parse(&row.get::<String, _>("id")).map_err(|e| {
-                crate::error::Error::BadRequest {
+            id: row
+                .get::<String, _>("id")
+                .parse::<EventReceiverId>()
+                .map_err(|e| crate::error::Error::BadRequest {
                     message: format!("Invalid receiver ID: {}", e),
-                }
-            })?,
+                })?,
             name: row.get("name"),
             receiver_type: row.get("receiver_type"),
             version: row.get("version"),
             description: row.get("description"),
             schema: row.get("schema"),
             fingerprint: row.get("fingerprint"),
-            owner_id: UserId::from_string(row.get::<String, _>("owner_id")).map_err(|e| {
+            owner_id: UserId::try_from(row.get::<String, _>("owner_id")).map_err(|e| {
                 crate::error::Error::BadRequest {
                     message: format!("Invalid owner ID: {}", e),
                 }
             })?,
             resource_version: row.get("resource_version"),
+            sample_rate: row.get("sample_rate"),
+            allowed_event_names: row
+                .get::<Option<JsonValue>, _>("allowed_event_names")
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| crate::error::Error::BadRequest {
+                    message: format!("Invalid allowed event names: {}", e),
+                })?,
+            auto_disable_policy: row
+                .get::<Option<JsonValue>, _>("auto_disable_policy")
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| crate::error::Error::BadRequest {
+                    message: format!("Invalid auto-disable policy: {}", e),
+                })?,
+            auto_disabled: row
+                .get::<Option<JsonValue>, _>("auto_disabled")
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| crate::error::Error::BadRequest {
+                    message: format!("Invalid auto-disable statistics: {}", e),
+                })?,
+            auto_disable_window_start: row.get("auto_disable_window_start"),
+            transform: row
+                .get::<Option<JsonValue>, _>("transform")
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|e| crate::error::Error::BadRequest {
+                    message: format!("Invalid payload transform: {}", e),
+                })?,
             created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
         })
     }
 
@@ -83,6 +132,27 @@ impl PostgresEventReceiverRepository {
         if let Some(fingerprint) = &criteria.fingerprint {
             conditions.push(format!("fingerprint = ${}", param_count));
             params.push(fingerprint.clone());
+            param_count += 1;
+        }
+
+        if let Some(owner_id) = &criteria.owner_id {
+            conditions.push(format!("owner_id = ${}", param_count));
+            params.push(owner_id.to_string());
+            param_count += 1;
+        }
+
+        if let Some(since) = &criteria.stale_since {
+            // A recent heartbeat keeps a quiet receiver alive
+            conditions.push(format!(
+                "NOT EXISTS (SELECT 1 FROM event_receiver_activity a \
+                 WHERE a.event_receiver_id = event_receivers.id \
+                 AND a.last_event_at >= ${0}::timestamptz) \
+                 AND NOT EXISTS (SELECT 1 FROM event_receiver_heartbeats h \
+                 WHERE h.event_receiver_id = event_receivers.id \
+                 AND h.last_heartbeat_at >= ${0}::timestamptz)",
+                param_count
+            ));
+            params.push(since.to_rfc3339());
         }
 
         let where_clause = if conditions.is_empty() {
@@ -93,18 +163,77 @@ impl PostgresEventReceiverRepository {
 
         (where_clause, params)
     }
+
+    /// Binds the parameters of a WHERE clause built by `build_where_clause`
+    fn bind_criteria<'q>(
+        query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+        params: Vec<String>,
+    ) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+        params
+            .into_iter()
+            .fold(query, |query, param| query.bind(param))
+    }
+
+    /// Returns the join a sort order needs, if any
+    ///
+    /// Rollup metrics are aggregated once per query from
+    /// `event_counts_hourly` rather than per receiver.
+    fn stats_join(order: ListOrder<ReceiverSortField>) -> &'static str {
+        match order.field {
+            ReceiverSortField::EventVolume | ReceiverSortField::SuccessRate => {
+                "LEFT JOIN (
+                    SELECT event_receiver_id,
+                           SUM(count) AS event_volume,
+                           SUM(count) FILTER (WHERE success)::float8
+                               / NULLIF(SUM(count), 0) AS success_rate
+                    FROM event_counts_hourly
+                    GROUP BY event_receiver_id
+                ) receiver_stats ON receiver_stats.event_receiver_id = event_receivers.id"
+            }
+            ReceiverSortField::CreatedAt
+            | ReceiverSortField::UpdatedAt
+            | ReceiverSortField::Name => "",
+        }
+    }
+
+    /// Returns the ORDER BY clause for a sort order, tie-broken by id
+    fn order_by_clause(order: ListOrder<ReceiverSortField>) -> String {
+        let (column, nulls) = match order.field {
+            ReceiverSortField::CreatedAt => ("created_at", ""),
+            ReceiverSortField::UpdatedAt => ("updated_at", ""),
+            ReceiverSortField::Name => ("name", ""),
+            ReceiverSortField::EventVolume => ("COALESCE(receiver_stats.event_volume, 0)", ""),
+            // Receivers without events have no rate and sort last both ways
+            ReceiverSortField::SuccessRate => ("receiver_stats.success_rate", " NULLS LAST"),
+        };
+        let direction = order.direction.as_sql();
+        format!(
+            "ORDER BY {} {}{}, id {}",
+            column, direction, nulls, direction
+        )
+    }
 }
 
 #[async_trait]
 impl EventReceiverRepository for PostgresEventReceiverRepository {
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receivers"
+        )
+    )]
     async fn save(&self, event_receiver: &EventReceiver) -> Result<()> {
         sqlx::query(
             r#"
             INSERT INTO event_receivers (
                 id, name, receiver_type, version, description, schema,
-                fingerprint, owner_id, resource_version, created_at
+                fingerprint, owner_id, resource_version, sample_rate,
+                allowed_event_names, auto_disable_policy, auto_disabled,
+                auto_disable_window_start, transform, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
             ON CONFLICT (id) DO UPDATE SET
                 name = EXCLUDED.name,
                 receiver_type = EXCLUDED.receiver_type,
@@ -113,7 +242,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
                 schema = EXCLUDED.schema,
                 fingerprint = EXCLUDED.fingerprint,
                 owner_id = EXCLUDED.owner_id,
-                resource_version = EXCLUDED.resource_version
+                resource_version = EXCLUDED.resource_version,
+                sample_rate = EXCLUDED.sample_rate,
+                allowed_event_names = EXCLUDED.allowed_event_names,
+                auto_disable_policy = EXCLUDED.auto_disable_policy,
+                auto_disabled = EXCLUDED.auto_disabled,
+                auto_disable_window_start = EXCLUDED.auto_disable_window_start,
+                transform = EXCLUDED.transform,
+                updated_at = EXCLUDED.updated_at
             "#,
         )
         .bind(event_receiver.id().to_string())
@@ -125,7 +261,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         .bind(event_receiver.fingerprint())
         .bind(event_receiver.owner_id().to_string())
         .bind(event_receiver.resource_version())
+        .bind(event_receiver.sample_rate())
+        .bind(event_receiver.allowed_event_names().map(Json))
+        .bind(event_receiver.auto_disable_policy().map(Json))
+        .bind(event_receiver.auto_disabled().map(Json))
+        .bind(event_receiver.auto_disable_window_start())
+        .bind(event_receiver.transform().map(Json))
         .bind(event_receiver.created_at())
+        .bind(event_receiver.updated_at())
         .execute(&self.pool)
         .await
         .map_err(crate::error::Error::Database)?;
@@ -133,11 +276,21 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
         let row = sqlx::query(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             WHERE id = $1
             "#,
@@ -160,11 +313,21 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         }
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiver>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             WHERE name ILIKE $1
             ORDER BY created_at DESC
@@ -185,11 +348,21 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
             .collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn find_by_type(&self, receiver_type: &str) -> Result<Vec<EventReceiver>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             WHERE receiver_type = $1
             ORDER BY created_at DESC
@@ -210,6 +383,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
             .collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn find_by_type_and_version(
         &self,
         receiver_type: &str,
@@ -218,7 +399,9 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         let rows = sqlx::query(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             WHERE receiver_type = $1 AND version = $2
             ORDER BY created_at DESC
@@ -240,11 +423,21 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
             .collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>> {
         let row = sqlx::query(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             WHERE fingerprint = $1
             "#,
@@ -267,11 +460,85 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         }
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receivers"
+        )
+    )]
+    async fn save_if_absent(
+        &self,
+        event_receiver: &EventReceiver,
+    ) -> Result<(EventReceiver, bool)> {
+        // The unique fingerprint index makes concurrent inserts race safely
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO event_receivers (
+                id, name, receiver_type, version, description, schema,
+                fingerprint, owner_id, resource_version, sample_rate,
+                allowed_event_names, auto_disable_policy, auto_disabled,
+                auto_disable_window_start, transform, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            ON CONFLICT (fingerprint) DO NOTHING
+            "#,
+        )
+        .bind(event_receiver.id().to_string())
+        .bind(event_receiver.name())
+        .bind(event_receiver.receiver_type())
+        .bind(event_receiver.version())
+        .bind(event_receiver.description())
+        .bind(event_receiver.schema())
+        .bind(event_receiver.fingerprint())
+        .bind(event_receiver.owner_id().to_string())
+        .bind(event_receiver.resource_version())
+        .bind(event_receiver.sample_rate())
+        .bind(event_receiver.allowed_event_names().map(Json))
+        .bind(event_receiver.auto_disable_policy().map(Json))
+        .bind(event_receiver.auto_disabled().map(Json))
+        .bind(event_receiver.auto_disable_window_start())
+        .bind(event_receiver.transform().map(Json))
+        .bind(event_receiver.created_at())
+        .bind(event_receiver.updated_at())
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?
+        .rows_affected()
+            == 1;
+
+        if inserted {
+            return Ok((event_receiver.clone(), true));
+        }
+
+        let existing = self
+            .find_by_fingerprint(event_receiver.fingerprint())
+            .await?
+            .ok_or_else(|| crate::error::Error::NotFound {
+                resource: format!(
+                    "event receiver with fingerprint {}",
+                    event_receiver.fingerprint()
+                ),
+            })?;
+        Ok((existing, false))
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiver>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
@@ -293,6 +560,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
             .collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn count(&self) -> Result<usize> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM event_receivers")
             .fetch_one(&self.pool)
@@ -303,6 +578,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         Ok(count as usize)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_receivers"
+        )
+    )]
     async fn update(&self, event_receiver: &EventReceiver) -> Result<()> {
         let result = sqlx::query(
             r#"
@@ -314,7 +597,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
                 schema = $6,
                 fingerprint = $7,
                 owner_id = $8,
-                resource_version = $9
+                resource_version = $9,
+                sample_rate = $10,
+                allowed_event_names = $11,
+                auto_disable_policy = $12,
+                auto_disabled = $13,
+                auto_disable_window_start = $14,
+                transform = $15,
+                updated_at = $16
             WHERE id = $1
             "#,
         )
@@ -327,6 +617,13 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         .bind(event_receiver.fingerprint())
         .bind(event_receiver.owner_id().to_string())
         .bind(event_receiver.resource_version())
+        .bind(event_receiver.sample_rate())
+        .bind(event_receiver.allowed_event_names().map(Json))
+        .bind(event_receiver.auto_disable_policy().map(Json))
+        .bind(event_receiver.auto_disabled().map(Json))
+        .bind(event_receiver.auto_disable_window_start())
+        .bind(event_receiver.transform().map(Json))
+        .bind(event_receiver.updated_at())
         .execute(&self.pool)
         .await
         .map_err(crate::error::Error::Database)?;
@@ -340,22 +637,95 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_receivers"
+        )
+    )]
     async fn delete(&self, id: EventReceiverId) -> Result<()> {
-        let result = sqlx::query("DELETE FROM event_receivers WHERE id = $1")
+        self.delete_many(&[id]).await
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_receivers"
+        )
+    )]
+    async fn delete_many(&self, ids: &[EventReceiverId]) -> Result<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::Error::Database)?;
+
+        for id in ids {
+            // Groups lose this receiver through the cascade, which is a change to them
+            sqlx::query(
+                r#"
+                UPDATE event_receiver_groups
+                SET updated_at = NOW()
+                WHERE id IN (
+                    SELECT group_id FROM event_receiver_group_receivers WHERE receiver_id = $1
+                )
+                "#,
+            )
             .bind(id.to_string())
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(crate::error::Error::Database)?;
 
-        if result.rows_affected() == 0 {
-            return Err(crate::error::Error::NotFound {
-                resource: format!("Event receiver with ID {} not found", id),
-            });
+            let result = sqlx::query("DELETE FROM event_receivers WHERE id = $1")
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::Error::Database)?;
+
+            if result.rows_affected() == 0 {
+                return Err(crate::error::Error::NotFound {
+                    resource: format!("Event receiver with ID {} not found", id),
+                });
+            }
+
+            // Keys scoped to the receiver could never authorize again
+            sqlx::query("UPDATE api_keys SET enabled = FALSE WHERE receiver_id = $1 AND enabled")
+                .bind(id.to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(crate::error::Error::Database)?;
+
+            // Record the deletion so change feed consumers can observe it
+            sqlx::query(
+                r#"
+                INSERT INTO event_receiver_tombstones (id, deleted_at)
+                VALUES ($1, NOW())
+                ON CONFLICT (id) DO UPDATE SET deleted_at = EXCLUDED.deleted_at
+                "#,
+            )
+            .bind(id.to_string())
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::Error::Database)?;
         }
 
+        tx.commit().await.map_err(crate::error::Error::Database)?;
+
         Ok(())
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn exists_by_name_and_type(&self, name: &str, receiver_type: &str) -> Result<bool> {
         let row = sqlx::query(
             "SELECT EXISTS(SELECT 1 FROM event_receivers WHERE name = $1 AND receiver_type = $2) as exists",
@@ -372,21 +742,34 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         Ok(exists)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn find_by_criteria(
         &self,
         criteria: FindEventReceiverCriteria,
     ) -> Result<Vec<EventReceiver>> {
-        let (where_clause, _params) = Self::build_where_clause(&criteria);
+        let (where_clause, params) = Self::build_where_clause(&criteria);
 
         let mut query = format!(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             {}
-            ORDER BY created_at DESC
+            {}
+            {}
             "#,
-            where_clause
+            Self::stats_join(criteria.order),
+            where_clause,
+            Self::order_by_clause(criteria.order)
         );
 
         if let Some(limit) = criteria.limit {
@@ -397,23 +780,7 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
             query.push_str(&format!(" OFFSET {}", offset));
         }
 
-        let mut sql_query = sqlx::query(&query);
-
-        if let Some(id) = &criteria.id {
-            sql_query = sql_query.bind(id.to_string());
-        }
-        if let Some(name) = &criteria.name {
-            sql_query = sql_query.bind(format!("%{}%", name));
-        }
-        if let Some(receiver_type) = &criteria.receiver_type {
-            sql_query = sql_query.bind(receiver_type);
-        }
-        if let Some(version) = &criteria.version {
-            sql_query = sql_query.bind(version);
-        }
-        if let Some(fingerprint) = &criteria.fingerprint {
-            sql_query = sql_query.bind(fingerprint);
-        }
+        let sql_query = Self::bind_criteria(sqlx::query(&query), params);
 
         let rows = sql_query
             .fetch_all(&self.pool)
@@ -430,11 +797,45 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
             .collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
+    async fn count_by_criteria(&self, criteria: FindEventReceiverCriteria) -> Result<usize> {
+        let (where_clause, params) = Self::build_where_clause(&criteria);
+        let query = format!(
+            "SELECT COUNT(*) as count FROM event_receivers {}",
+            where_clause
+        );
+
+        let row = Self::bind_criteria(sqlx::query(&query), params)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(crate::error::Error::Database)?;
+
+        let count: i64 = sqlx::Row::get(&row, "count");
+        Ok(count as usize)
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiver>> {
         let rows = sqlx::query(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             WHERE owner_id = $1
             ORDER BY created_at DESC
@@ -455,6 +856,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
             .collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn find_by_owner_paginated(
         &self,
         owner_id: UserId,
@@ -464,7 +873,9 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         let rows = sqlx::query(
             r#"
             SELECT id, name, receiver_type, version, description, schema,
-                   fingerprint, owner_id, resource_version, created_at
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
             FROM event_receivers
             WHERE owner_id = $1
             ORDER BY created_at DESC
@@ -488,6 +899,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
             .collect()
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn is_owner(&self, receiver_id: EventReceiverId, user_id: UserId) -> Result<bool> {
         let row = sqlx::query(
             "SELECT EXISTS(SELECT 1 FROM event_receivers WHERE id = $1 AND owner_id = $2) as is_owner",
@@ -504,6 +923,14 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
         Ok(is_owner)
     }
 
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
     async fn get_resource_version(&self, receiver_id: EventReceiverId) -> Result<Option<i64>> {
         let row = sqlx::query("SELECT resource_version FROM event_receivers WHERE id = $1")
             .bind(receiver_id.to_string())
@@ -521,9 +948,404 @@ impl EventReceiverRepository for PostgresEventReceiverRepository {
     }
 }
 
+#[async_trait]
+impl EventReceiverChangeFeedRepository for PostgresEventReceiverRepository {
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
+    async fn receiver_changes_since(
+        &self,
+        since: &ChangeCursor,
+        limit: usize,
+    ) -> Result<ChangeSet<EventReceiver, EventReceiverId>> {
+        // Fetch one extra row from each source so has_more is exact after merging.
+        // Ids compare bytewise (COLLATE "C") to match ChangeCursor ordering.
+        let fetch = limit as i64 + 1;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, receiver_type, version, description, schema,
+                   fingerprint, owner_id, resource_version, sample_rate, allowed_event_names,
+                   auto_disable_policy, auto_disabled, auto_disable_window_start, transform,
+                   created_at, updated_at
+            FROM event_receivers
+            WHERE (updated_at, id COLLATE "C") > ($1, $2)
+            ORDER BY updated_at, id COLLATE "C"
+            LIMIT $3
+            "#,
+        )
+        .bind(since.changed_at())
+        .bind(since.id())
+        .bind(fetch)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        let tombstones = sqlx::query(
+            r#"
+            SELECT id, deleted_at
+            FROM event_receiver_tombstones
+            WHERE (deleted_at, id COLLATE "C") > ($1, $2)
+            ORDER BY deleted_at, id COLLATE "C"
+            LIMIT $3
+            "#,
+        )
+        .bind(since.changed_at())
+        .bind(since.id())
+        .bind(fetch)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        let mut changes = Vec::with_capacity(rows.len() + tombstones.len());
+
+        for row in &rows {
+            let data = Self::row_to_data(row)?;
+            let receiver = EventReceiver::from_existing(data).map_err(|e| {
+                crate::error::Error::BadRequest {
+                    message: format!("Invalid event receiver data: {}", e),
+                }
+            })?;
+            let cursor = ChangeCursor::new(receiver.updated_at(), receiver.id().to_string());
+            changes.push((cursor, Change::Upserted(receiver)));
+        }
+
+        for row in &tombstones {
+            let id_str: String = sqlx::Row::get(row, "id");
+            let id =
+                id_str
+                    .parse::<EventReceiverId>()
+                    .map_err(|e| crate::error::Error::BadRequest {
+                        message: format!("Invalid receiver ID: {}", e),
+                    })?;
+            let cursor = ChangeCursor::new(sqlx::Row::get(row, "deleted_at"), id_str);
+            changes.push((cursor, Change::Deleted(id)));
+        }
+
+        Ok(ChangeSet::from_changes(since, limit, changes))
+    }
+}
+
+#[async_trait]
+impl EventSamplingCounterRepository for PostgresEventReceiverRepository {
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_sampling_counters"
+        )
+    )]
+    async fn record_sampling(
+        &self,
+        receiver_id: EventReceiverId,
+        bucket_start: DateTime<Utc>,
+        stored: bool,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO event_sampling_counters (
+                event_receiver_id, bucket_start, total_seen, total_stored
+            )
+            VALUES ($1, $2, 1, $3)
+            ON CONFLICT (event_receiver_id, bucket_start) DO UPDATE SET
+                total_seen = event_sampling_counters.total_seen + 1,
+                total_stored = event_sampling_counters.total_stored + EXCLUDED.total_stored
+            "#,
+        )
+        .bind(receiver_id.to_string())
+        .bind(bucket_start)
+        .bind(stored as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_sampling_counters"
+        )
+    )]
+    async fn sampling_counts(&self, receiver_id: EventReceiverId) -> Result<SamplingCounts> {
+        let row = sqlx::query(
+            r#"
+            SELECT COALESCE(SUM(total_seen), 0)::BIGINT AS total_seen,
+                   COALESCE(SUM(total_stored), 0)::BIGINT AS total_stored
+            FROM event_sampling_counters
+            WHERE event_receiver_id = $1
+            "#,
+        )
+        .bind(receiver_id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        Ok(SamplingCounts {
+            total_seen: sqlx::Row::get::<i64, _>(&row, "total_seen") as u64,
+            total_stored: sqlx::Row::get::<i64, _>(&row, "total_stored") as u64,
+        })
+    }
+}
+
+#[async_trait]
+impl ReceiverActivityRepository for PostgresEventReceiverRepository {
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receiver_activity"
+        )
+    )]
+    async fn record_activity(&self, updates: &[ReceiverActivityUpdate]) -> Result<()> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(crate::error::Error::Database)?;
+
+        for update in updates {
+            sqlx::query(
+                r#"
+                INSERT INTO event_receiver_activity (
+                    event_receiver_id, last_event_at, last_principal
+                )
+                VALUES ($1, $2, $3)
+                ON CONFLICT (event_receiver_id) DO UPDATE SET
+                    last_event_at = EXCLUDED.last_event_at,
+                    last_principal = EXCLUDED.last_principal
+                WHERE event_receiver_activity.last_event_at <= EXCLUDED.last_event_at
+                "#,
+            )
+            .bind(update.receiver_id.to_string())
+            .bind(update.last_event_at)
+            .bind(&update.last_principal)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::Error::Database)?;
+
+            let (days, principals): (Vec<NaiveDate>, Vec<String>) =
+                update.daily_principals.iter().cloned().unzip();
+            sqlx::query(
+                r#"
+                INSERT INTO event_receiver_daily_principals (event_receiver_id, day, principal)
+                SELECT $1, day, principal FROM UNNEST($2::date[], $3::text[]) AS t(day, principal)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(update.receiver_id.to_string())
+            .bind(days)
+            .bind(principals)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::error::Error::Database)?;
+        }
+
+        tx.commit().await.map_err(crate::error::Error::Database)?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_activity"
+        )
+    )]
+    async fn find_activity(
+        &self,
+        receiver_ids: &[EventReceiverId],
+        window_start: NaiveDate,
+    ) -> Result<Vec<ReceiverActivity>> {
+        if receiver_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<String> = receiver_ids.iter().map(|id| id.to_string()).collect();
+        let rows = sqlx::query(
+            r#"
+            SELECT a.event_receiver_id, a.last_event_at, a.last_principal,
+                   (SELECT COUNT(DISTINCT p.principal)
+                    FROM event_receiver_daily_principals p
+                    WHERE p.event_receiver_id = a.event_receiver_id
+                      AND p.day >= $2)::BIGINT AS distinct_principals
+            FROM event_receiver_activity a
+            WHERE a.event_receiver_id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .bind(window_start)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ReceiverActivity {
+                    receiver_id: sqlx::Row::get::<String, _>(row, "event_receiver_id")
+                        .parse::<EventReceiverId>()
+                        .map_err(|e| crate::error::Error::BadRequest {
+                            message: format!("Invalid receiver ID: {}", e),
+                        })?,
+                    last_event_at: sqlx::Row::get(row, "last_event_at"),
+                    last_principal: sqlx::Row::get(row, "last_principal"),
+                    distinct_principals: sqlx::Row::get::<i64, _>(row, "distinct_principals")
+                        as u64,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_activity ORDER BY last_event_at"
+        )
+    )]
+    async fn most_recently_active(&self, limit: usize) -> Result<Vec<EventReceiverId>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT event_receiver_id
+            FROM event_receiver_activity
+            ORDER BY last_event_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        rows.iter()
+            .map(|row| {
+                sqlx::Row::get::<String, _>(row, "event_receiver_id")
+                    .parse::<EventReceiverId>()
+                    .map_err(|e| crate::error::Error::BadRequest {
+                        message: format!("Invalid receiver ID: {}", e),
+                    })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ReceiverHeartbeatRepository for PostgresEventReceiverRepository {
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receiver_heartbeats"
+        )
+    )]
+    async fn record_heartbeat(
+        &self,
+        heartbeat: &ReceiverHeartbeat,
+        not_after: DateTime<Utc>,
+    ) -> Result<HeartbeatWrite> {
+        let receiver_id = heartbeat.receiver_id.to_string();
+        let recorded = sqlx::query(
+            r#"
+            INSERT INTO event_receiver_heartbeats (event_receiver_id, last_heartbeat_at, status)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (event_receiver_id) DO UPDATE SET
+                last_heartbeat_at = EXCLUDED.last_heartbeat_at,
+                status = EXCLUDED.status
+            WHERE event_receiver_heartbeats.last_heartbeat_at <= $4
+            RETURNING event_receiver_id
+            "#,
+        )
+        .bind(&receiver_id)
+        .bind(heartbeat.last_heartbeat_at)
+        .bind(&heartbeat.status)
+        .bind(not_after)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        if recorded.is_some() {
+            return Ok(HeartbeatWrite::Recorded);
+        }
+
+        let row = sqlx::query(
+            "SELECT last_heartbeat_at FROM event_receiver_heartbeats WHERE event_receiver_id = $1",
+        )
+        .bind(&receiver_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        Ok(HeartbeatWrite::Throttled {
+            last_heartbeat_at: sqlx::Row::get(&row, "last_heartbeat_at"),
+        })
+    }
+
+    #[instrument(
+        skip_all,
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_heartbeats"
+        )
+    )]
+    async fn find_heartbeats(
+        &self,
+        receiver_ids: &[EventReceiverId],
+    ) -> Result<Vec<ReceiverHeartbeat>> {
+        if receiver_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<String> = receiver_ids.iter().map(|id| id.to_string()).collect();
+        let rows = sqlx::query(
+            r#"
+            SELECT event_receiver_id, last_heartbeat_at, status
+            FROM event_receiver_heartbeats
+            WHERE event_receiver_id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(crate::error::Error::Database)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ReceiverHeartbeat {
+                    receiver_id: sqlx::Row::get::<String, _>(row, "event_receiver_id")
+                        .parse::<EventReceiverId>()
+                        .map_err(|e| crate::error::Error::BadRequest {
+                            message: format!("Invalid receiver ID: {}", e),
+                        })?,
+                    last_heartbeat_at: sqlx::Row::get(row, "last_heartbeat_at"),
+                    status: sqlx::Row::get(row, "status"),
+                })
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::repositories::pagination::SortField;
 
     #[test]
     fn test_build_where_clause_empty() {
@@ -555,4 +1377,74 @@ mod tests {
         assert!(where_clause.contains("AND"));
         assert_eq!(params.len(), 3);
     }
+
+    #[test]
+    fn test_build_where_clause_with_owner() {
+        let owner_id = UserId::new();
+        let criteria = FindEventReceiverCriteria::new()
+            .with_type("webhook".to_string())
+            .with_owner(owner_id);
+        let (where_clause, params) = PostgresEventReceiverRepository::build_where_clause(&criteria);
+        assert!(where_clause.contains("owner_id = $2"));
+        assert_eq!(params, vec!["webhook".to_string(), owner_id.to_string()]);
+    }
+
+    #[test]
+    fn test_build_where_clause_with_stale_since() {
+        let since = Utc::now();
+        let criteria = FindEventReceiverCriteria::new()
+            .with_owner(UserId::new())
+            .with_stale_since(since);
+        let (where_clause, params) = PostgresEventReceiverRepository::build_where_clause(&criteria);
+        assert!(where_clause.contains("NOT EXISTS"));
+        assert!(where_clause.contains("a.last_event_at >= $2::timestamptz"));
+        assert!(where_clause.contains("h.last_heartbeat_at >= $2::timestamptz"));
+        assert_eq!(params[1], since.to_rfc3339());
+    }
+
+    #[test]
+    fn test_order_by_clause_is_stable() {
+        for (_, field) in ReceiverSortField::FIELDS {
+            for order in [ListOrder::asc(*field), ListOrder::desc(*field)] {
+                // Ties on the sort column are broken by id so pages stay stable
+                let clause = PostgresEventReceiverRepository::order_by_clause(order);
+                let tie_break = format!(", id {}", order.direction.as_sql());
+                assert!(clause.ends_with(&tie_break), "{}", clause);
+            }
+        }
+        assert_eq!(
+            PostgresEventReceiverRepository::order_by_clause(ListOrder::asc(
+                ReceiverSortField::Name
+            )),
+            "ORDER BY name ASC, id ASC"
+        );
+        assert_eq!(
+            PostgresEventReceiverRepository::order_by_clause(ListOrder::desc(
+                ReceiverSortField::UpdatedAt
+            )),
+            "ORDER BY updated_at DESC, id DESC"
+        );
+    }
+
+    #[test]
+    fn test_metric_sorts_join_rollup() {
+        for field in [
+            ReceiverSortField::EventVolume,
+            ReceiverSortField::SuccessRate,
+        ] {
+            let join = PostgresEventReceiverRepository::stats_join(ListOrder::desc(field));
+            assert!(join.contains("FROM event_counts_hourly"));
+            assert!(join.contains("GROUP BY event_receiver_id"));
+        }
+        assert_eq!(
+            PostgresEventReceiverRepository::stats_join(ListOrder::default()),
+            ""
+        );
+        assert_eq!(
+            PostgresEventReceiverRepository::order_by_clause(ListOrder::asc(
+                ReceiverSortField::SuccessRate
+            )),
+            "ORDER BY receiver_stats.success_rate ASC NULLS LAST, id ASC"
+        );
+    }
 }
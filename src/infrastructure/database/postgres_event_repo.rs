@@ -3,13 +3,41 @@
 
 // src/infrastructure/database/postgres_event_repo.rs
 
-use crate::domain::entities::event::{DatabaseEventFields, Event};
+use crate::domain::entities::attestation::{Attestation, EventAttestation};
+use crate::domain::entities::event::{DatabaseEventFields, Event, EventOrigin};
+use crate::domain::entities::ingestion_meta::{IngestionContext, IngestionMeta};
+use crate::domain::entities::payload_summary::PayloadSummary;
+use crate::domain::entities::pii_detection::{PiiPathCount, PiiReport};
+use crate::domain::entities::release_completeness::ReleaseOutcome;
+use crate::domain::repositories::attestation_repo::{
+    AttestationFilter, EventAttestationRepository,
+};
+use crate::domain::repositories::event_archive_repo::{
+    ArchiveIndexEntry, EventArchiveIndexRepository,
+};
+use crate::domain::repositories::event_feed_repo::EventFeedRepository;
+use crate::domain::repositories::event_outbox_repo::{
+    EventOutboxRepository, OutboxBacklog, OutboxEntry,
+};
+use crate::domain::repositories::event_payload_repo::{
+    CollectedPayloads, EventPayloadRepository, PayloadStoreStats,
+};
 use crate::domain::repositories::event_repo::{EventRepository, FindEventCriteria};
+use crate::domain::repositories::event_rollup_repo::{
+    event_severity, rollup_bucket, EventCountBucket, EventCountRepository, ReceiverEventCounts,
+};
+use crate::domain::repositories::ingestion_meta_repo::{
+    EventIngestionMetaRepository, IngestionMetaFilter,
+};
+use crate::domain::repositories::pagination::{EventSortField, ListOrder};
 use crate::domain::value_objects::{EventId, EventReceiverId};
 use crate::error::Result;
+use crate::infrastructure::database::payload_interning::InternedPayload;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use sqlx::{PgPool, Row};
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use std::collections::HashMap;
 use tracing::{error, instrument};
 
 /// PostgreSQL implementation of the EventRepository trait
@@ -30,6 +58,7 @@ use tracing::{error, instrument};
 /// ```
 pub struct PostgresEventRepository {
     pool: PgPool,
+    intern_threshold: Option<usize>,
 }
 
 impl PostgresEventRepository {
@@ -50,7 +79,107 @@ impl PostgresEventRepository {
     /// }
     /// ```
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            intern_threshold: None,
+        }
+    }
+
+    /// Stores payloads larger than `threshold_bytes` once in the shared
+    /// payload store, referenced by content hash
+    ///
+    /// Reads reassemble interned payloads whether or not this is set.
+    pub fn with_intern_threshold(mut self, threshold_bytes: usize) -> Self {
+        self.intern_threshold = Some(threshold_bytes);
+        self
+    }
+
+    /// Builds the `AND` conditions for the filters set in `criteria`
+    ///
+    /// Returns the conditions and the number of the next free parameter;
+    /// [`Self::bind_criteria`] binds their values in the same order.
+    fn criteria_conditions(criteria: &FindEventCriteria) -> (String, usize) {
+        let columns = [
+            (criteria.id.is_some(), "id ="),
+            (criteria.name.is_some(), "name ILIKE"),
+            (criteria.version.is_some(), "version ="),
+            (criteria.release.is_some(), "release ="),
+            (criteria.platform_id.is_some(), "platform_id ="),
+            (criteria.package.is_some(), "package ="),
+            (criteria.success.is_some(), "success ="),
+            (criteria.event_receiver_id.is_some(), "event_receiver_id ="),
+            (criteria.start_time.is_some(), "created_at >="),
+            (criteria.end_time.is_some(), "created_at <="),
+            (criteria.origin.is_some(), "origin ="),
+        ];
+
+        let mut conditions = String::new();
+        let mut param_count = 1;
+        for (_, condition) in columns.iter().filter(|(set, _)| *set) {
+            conditions.push_str(&format!(" AND {} ${}", condition, param_count));
+            param_count += 1;
+        }
+
+        (conditions, param_count)
+    }
+
+    /// Binds the filter values of `criteria` in condition order
+    fn bind_criteria<'q>(
+        mut query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+        criteria: &FindEventCriteria,
+    ) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+        if let Some(id) = criteria.id {
+            query = query.bind(id);
+        }
+        if let Some(name) = &criteria.name {
+            query = query.bind(format!("%{}%", name));
+        }
+        if let Some(version) = &criteria.version {
+            query = query.bind(version.clone());
+        }
+        if let Some(release) = &criteria.release {
+            query = query.bind(release.clone());
+        }
+        if let Some(platform_id) = &criteria.platform_id {
+            query = query.bind(platform_id.clone());
+        }
+        if let Some(package) = &criteria.package {
+            query = query.bind(package.clone());
+        }
+        if let Some(success) = criteria.success {
+            query = query.bind(success);
+        }
+        if let Some(receiver_id) = criteria.event_receiver_id {
+            query = query.bind(receiver_id);
+        }
+        if let Some(start_time) = criteria.start_time {
+            query = query.bind(start_time);
+        }
+        if let Some(end_time) = criteria.end_time {
+            query = query.bind(end_time);
+        }
+        if let Some(origin) = criteria.origin {
+            query = query.bind(origin.as_str());
+        }
+        query
+    }
+
+    /// Returns the ORDER BY clause for a sort order, tie-broken by id
+    ///
+    /// `table` qualifies the columns when the events table is aliased.
+    fn order_by_clause(order: ListOrder<EventSortField>, table: &'static str) -> String {
+        let column = match order.field {
+            EventSortField::CreatedAt => "created_at",
+            EventSortField::Name => "name",
+            EventSortField::Success => "success",
+        };
+        let direction = order.direction.as_sql();
+        format!(
+            "ORDER BY {table}{column} {direction}, {table}id {direction}",
+            table = table,
+            column = column,
+            direction = direction
+        )
     }
 
     /// Converts a database row to an Event entity
@@ -103,10 +232,14 @@ impl PostgresEventRepository {
             e
         })?;
 
-        let payload: serde_json::Value = row.try_get("payload").map_err(|e| {
-            error!("Failed to get payload from row: {}", e);
-            e
-        })?;
+        // Interned payloads are read from the shared payload store
+        let payload = match row.try_get::<Option<String>, _>("interned_payload") {
+            Ok(Some(body)) => InternedPayload::decode(&body)?,
+            _ => row.try_get("payload").map_err(|e| {
+                error!("Failed to get payload from row: {}", e);
+                e
+            })?,
+        };
 
         let success: bool = row.try_get("success").map_err(|e| {
             error!("Failed to get success from row: {}", e);
@@ -121,13 +254,27 @@ impl PostgresEventRepository {
         let owner_id: crate::domain::value_objects::UserId = row
             .try_get::<String, _>("owner_id")
             .ok()
-            .and_then(|s| crate::domain::value_objects::UserId::from_string(s).ok())
+            .and_then(|s| crate::domain::value_objects::UserId::try_from(s).ok())
             .unwrap_or_else(crate::domain::value_objects::UserId::new);
 
+        let origin: EventOrigin = row
+            .try_get::<String, _>("origin")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default();
+
         let resource_version: i64 = row.try_get("resource_version").unwrap_or(1);
 
+        // Offloaded payloads stay in the blob store until a caller asks
+        let payload_summary = row
+            .try_get::<Option<Json<PayloadSummary>>, _>("payload_summary")
+            .map_err(|e| {
+                error!("Failed to get payload_summary from row: {}", e);
+                e
+            })?;
+
         // Reconstruct event from database fields with original ID and timestamp
-        Ok(Event::from_database(DatabaseEventFields {
+        let mut event = Event::from_database(DatabaseEventFields {
             id,
             name,
             version,
@@ -140,8 +287,13 @@ impl PostgresEventRepository {
             event_receiver_id,
             created_at,
             owner_id,
+            origin,
             resource_version,
-        }))
+        });
+        if let Some(summary) = payload_summary {
+            event.offload_payload(summary.0);
+        }
+        Ok(event)
     }
 }
 
@@ -159,16 +311,58 @@ impl EventRepository for PostgresEventRepository {
     /// # Errors
     ///
     /// Returns an error if the database operation fails
-    #[instrument(skip(self, event), fields(event_id = %event.id()))]
+    #[instrument(
+        skip(self, event),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT events",
+            event_id = %event.id()
+        )
+    )]
     async fn save(&self, event: &Event) -> Result<()> {
-        sqlx::query(
+        let mut tx = self.pool.begin().await?;
+
+        // Saving over an event releases the payload it referenced
+        let previous_hash: Option<String> =
+            sqlx::query_scalar("SELECT payload_hash FROM events WHERE id = $1 FOR UPDATE")
+                .bind(event.id())
+                .fetch_optional(&mut *tx)
+                .await?
+                .flatten();
+
+        let interned = match self.intern_threshold {
+            Some(threshold) => InternedPayload::above_threshold(event.payload(), threshold)?,
+            None => None,
+        };
+        if let Some(interned) = &interned {
+            // Taking the reference locks the row, so collection cannot
+            // remove a payload that is being shared again
+            sqlx::query(
+                r#"
+                INSERT INTO event_payloads (hash, body, size_bytes, ref_count)
+                VALUES ($1, $2, $3, 1)
+                ON CONFLICT (hash) DO UPDATE SET
+                    ref_count = event_payloads.ref_count + 1
+                "#,
+            )
+            .bind(&interned.hash)
+            .bind(&interned.body)
+            .bind(interned.size_bytes() as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // xmax is 0 only for a freshly inserted row
+        let inserted: bool = sqlx::query_scalar(
             r#"
             INSERT INTO events (
                 id, event_receiver_id, name, version, release,
                 platform_id, package, description, payload, success,
-                created_at, owner_id, resource_version
+                created_at, owner_id, origin, resource_version, payload_hash,
+                payload_summary
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
             ON CONFLICT (id) DO UPDATE SET
                 name = EXCLUDED.name,
                 version = EXCLUDED.version,
@@ -177,8 +371,11 @@ impl EventRepository for PostgresEventRepository {
                 package = EXCLUDED.package,
                 description = EXCLUDED.description,
                 payload = EXCLUDED.payload,
+                payload_hash = EXCLUDED.payload_hash,
+                payload_summary = EXCLUDED.payload_summary,
                 success = EXCLUDED.success,
                 resource_version = EXCLUDED.resource_version
+            RETURNING (xmax = 0) AS inserted
             "#,
         )
         .bind(event.id())
@@ -189,14 +386,46 @@ impl EventRepository for PostgresEventRepository {
         .bind(event.platform_id())
         .bind(event.package())
         .bind(event.description())
-        .bind(event.payload())
+        .bind(interned.is_none().then(|| event.payload()))
         .bind(event.success())
         .bind(event.created_at())
         .bind(event.owner_id().to_string())
+        .bind(event.origin().as_str())
         .bind(event.resource_version())
-        .execute(&self.pool)
+        .bind(interned.as_ref().map(|interned| interned.hash.as_str()))
+        .bind(event.payload_summary().map(Json))
+        .fetch_one(&mut *tx)
         .await?;
 
+        if let Some(hash) = previous_hash {
+            release_payload(&mut tx, &hash).await?;
+        }
+
+        // Count new user events in the hourly rollup; updates are left to
+        // the reconciliation job
+        if inserted && event.origin() == EventOrigin::User {
+            sqlx::query(
+                r#"
+                INSERT INTO event_counts_hourly (
+                    event_receiver_id, bucket_start, severity, success, count
+                )
+                VALUES ($1, $2, $3, $4, 1)
+                ON CONFLICT (event_receiver_id, bucket_start, severity, success) DO UPDATE SET
+                    count = event_counts_hourly.count + 1
+                "#,
+            )
+            .bind(event.event_receiver_id())
+            .bind(rollup_bucket(event.created_at()))
+            .bind(match event.payload_summary() {
+                Some(summary) => event_severity(&summary.rollup_fields()),
+                None => event_severity(event.payload()),
+            })
+            .bind(event.success())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
         Ok(())
     }
 
@@ -213,13 +442,23 @@ impl EventRepository for PostgresEventRepository {
     /// # Errors
     ///
     /// Returns an error if the database query fails
-    #[instrument(skip(self), fields(event_id = %id))]
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT events",
+            event_id = %id
+        )
+    )]
     async fn find_by_id(&self, id: EventId) -> Result<Option<Event>> {
         let row = sqlx::query(
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at, owner_id, resource_version
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, owner_id, origin, resource_version
             FROM events
             WHERE id = $1
             "#,
@@ -243,13 +482,23 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns a vector of events matching the receiver ID
-    #[instrument(skip(self), fields(receiver_id = %receiver_id))]
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT events",
+            receiver_id = %receiver_id
+        )
+    )]
     async fn find_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<Vec<Event>> {
         let rows = sqlx::query(
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             WHERE event_receiver_id = $1
             ORDER BY created_at DESC
@@ -271,13 +520,18 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns a vector of events matching the success status
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn find_by_success(&self, success: bool) -> Result<Vec<Event>> {
         let rows = sqlx::query(
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             WHERE success = $1
             ORDER BY created_at DESC
@@ -299,14 +553,19 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns a vector of events with names matching the pattern
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn find_by_name(&self, name: &str) -> Result<Vec<Event>> {
         let pattern = format!("%{}%", name);
         let rows = sqlx::query(
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             WHERE name ILIKE $1
             ORDER BY created_at DESC
@@ -328,13 +587,18 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns a vector of events matching the platform ID
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn find_by_platform_id(&self, platform_id: &str) -> Result<Vec<Event>> {
         let rows = sqlx::query(
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             WHERE platform_id = $1
             ORDER BY created_at DESC
@@ -356,13 +620,18 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns a vector of events matching the package
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn find_by_package(&self, package: &str) -> Result<Vec<Event>> {
         let rows = sqlx::query(
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             WHERE package = $1
             ORDER BY created_at DESC
@@ -385,13 +654,18 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns a paginated list of events ordered by creation time (newest first)
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn list(&self, limit: usize, offset: usize) -> Result<Vec<Event>> {
         let rows = sqlx::query(
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
@@ -410,7 +684,10 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns the total count of events in the database
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn count(&self) -> Result<usize> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM events")
             .fetch_one(&self.pool)
@@ -430,7 +707,15 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns the count of events for the specified receiver
-    #[instrument(skip(self), fields(receiver_id = %receiver_id))]
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT events",
+            receiver_id = %receiver_id
+        )
+    )]
     async fn count_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM events WHERE event_receiver_id = $1")
             .bind(receiver_id)
@@ -451,7 +736,15 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns the count of successful events for the specified receiver
-    #[instrument(skip(self), fields(receiver_id = %receiver_id))]
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT events",
+            receiver_id = %receiver_id
+        )
+    )]
     async fn count_successful_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
         let row = sqlx::query(
             "SELECT COUNT(*) as count FROM events WHERE event_receiver_id = $1 AND success = true",
@@ -465,6 +758,94 @@ impl EventRepository for PostgresEventRepository {
         Ok(count as usize)
     }
 
+    /// Counts user events stored for a receiver at or after `since`
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT events",
+            receiver_id = %receiver_id
+        )
+    )]
+    async fn count_by_receiver_since(
+        &self,
+        receiver_id: EventReceiverId,
+        since: DateTime<Utc>,
+    ) -> Result<usize> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) as count FROM events \
+             WHERE event_receiver_id = $1 AND created_at >= $2 AND origin = $3",
+        )
+        .bind(receiver_id)
+        .bind(since)
+        .bind(EventOrigin::User.as_str())
+        .fetch_one(&self.pool)
+        .await?;
+
+        let count: i64 = row.try_get("count")?;
+
+        Ok(count as usize)
+    }
+
+    /// Aggregates the release outcomes of several receivers in one query
+    #[instrument(
+        skip(self, receiver_ids),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT events",
+            receiver_count = receiver_ids.len()
+        )
+    )]
+    async fn find_release_outcomes(
+        &self,
+        receiver_ids: &[EventReceiverId],
+        release: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<HashMap<EventReceiverId, ReleaseOutcome>> {
+        if receiver_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids: Vec<String> = receiver_ids.iter().map(|id| id.to_string()).collect();
+        let rows = sqlx::query(
+            r#"
+            SELECT event_receiver_id,
+                   BOOL_OR(success) AS succeeded,
+                   BOOL_OR(NOT success) AS failed
+            FROM events
+            WHERE event_receiver_id = ANY($1)
+              AND release = $2
+              AND origin = $3
+              AND ($4::timestamptz IS NULL OR created_at >= $4)
+            GROUP BY event_receiver_id
+            "#,
+        )
+        .bind(ids)
+        .bind(release)
+        .bind(EventOrigin::User.as_str())
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let receiver_id = row
+                    .try_get::<String, _>("event_receiver_id")?
+                    .parse::<EventReceiverId>()
+                    .map_err(|e| crate::error::Error::BadRequest {
+                        message: format!("Invalid receiver ID: {}", e),
+                    })?;
+                let outcome = ReleaseOutcome {
+                    succeeded: row.try_get("succeeded")?,
+                    failed: row.try_get("failed")?,
+                };
+                Ok((receiver_id, outcome))
+            })
+            .collect()
+    }
+
     /// Deletes an event by ID
     ///
     /// # Arguments
@@ -474,13 +855,29 @@ impl EventRepository for PostgresEventRepository {
     /// # Errors
     ///
     /// Returns an error if the database operation fails
-    #[instrument(skip(self), fields(event_id = %id))]
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE events",
+            event_id = %id
+        )
+    )]
     async fn delete(&self, id: EventId) -> Result<()> {
-        sqlx::query("DELETE FROM events WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        let mut tx = self.pool.begin().await?;
+
+        let payload_hash: Option<String> =
+            sqlx::query_scalar("DELETE FROM events WHERE id = $1 RETURNING payload_hash")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .flatten();
+        if let Some(hash) = payload_hash {
+            release_payload(&mut tx, &hash).await?;
+        }
 
+        tx.commit().await?;
         Ok(())
     }
 
@@ -493,7 +890,15 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns the most recent event for the receiver, if any exists
-    #[instrument(skip(self), fields(receiver_id = %receiver_id))]
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT events",
+            receiver_id = %receiver_id
+        )
+    )]
     async fn find_latest_by_receiver_id(
         &self,
         receiver_id: EventReceiverId,
@@ -502,7 +907,9 @@ impl EventRepository for PostgresEventRepository {
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             WHERE event_receiver_id = $1
             ORDER BY created_at DESC
@@ -528,7 +935,15 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns the most recent successful event for the receiver, if any exists
-    #[instrument(skip(self), fields(receiver_id = %receiver_id))]
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT events",
+            receiver_id = %receiver_id
+        )
+    )]
     async fn find_latest_successful_by_receiver_id(
         &self,
         receiver_id: EventReceiverId,
@@ -537,7 +952,9 @@ impl EventRepository for PostgresEventRepository {
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             WHERE event_receiver_id = $1 AND success = true
             ORDER BY created_at DESC
@@ -564,7 +981,10 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns a vector of events created within the specified time range
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn find_by_time_range(
         &self,
         start: DateTime<Utc>,
@@ -574,7 +994,9 @@ impl EventRepository for PostgresEventRepository {
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, origin
             FROM events
             WHERE created_at >= $1 AND created_at <= $2
             ORDER BY created_at DESC
@@ -588,6 +1010,25 @@ impl EventRepository for PostgresEventRepository {
         rows.into_iter().map(|row| self.row_to_event(row)).collect()
     }
 
+    /// Counts events within a time range
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
+    async fn count_by_time_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<usize> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) as count FROM events WHERE created_at >= $1 AND created_at <= $2",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let count: i64 = row.try_get("count")?;
+
+        Ok(count as usize)
+    }
+
     /// Finds events that match multiple criteria
     ///
     /// # Arguments
@@ -597,68 +1038,25 @@ impl EventRepository for PostgresEventRepository {
     /// # Returns
     ///
     /// Returns a vector of events matching all specified criteria
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn find_by_criteria(&self, criteria: FindEventCriteria) -> Result<Vec<Event>> {
-        let mut query = String::from(
+        let (conditions, mut param_count) = Self::criteria_conditions(&criteria);
+        let mut query = format!(
             "SELECT id, event_receiver_id, name, version, release, \
-             platform_id, package, description, payload, success, created_at \
-             FROM events WHERE 1=1",
+             platform_id, package, description, payload, success, created_at, \
+             owner_id, origin, resource_version, \
+             (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash) \
+             AS interned_payload, payload_summary \
+             FROM events WHERE 1=1{}",
+            conditions
         );
-        let mut param_count = 1;
-
-        // Build dynamic WHERE clauses
-        if criteria.id.is_some() {
-            query.push_str(&format!(" AND id = ${}", param_count));
-            param_count += 1;
-        }
-
-        if criteria.name.is_some() {
-            query.push_str(&format!(" AND name ILIKE ${}", param_count));
-            param_count += 1;
-        }
-
-        if criteria.version.is_some() {
-            query.push_str(&format!(" AND version = ${}", param_count));
-            param_count += 1;
-        }
-
-        if criteria.release.is_some() {
-            query.push_str(&format!(" AND release = ${}", param_count));
-            param_count += 1;
-        }
 
-        if criteria.platform_id.is_some() {
-            query.push_str(&format!(" AND platform_id = ${}", param_count));
-            param_count += 1;
-        }
-
-        if criteria.package.is_some() {
-            query.push_str(&format!(" AND package = ${}", param_count));
-            param_count += 1;
-        }
-
-        if criteria.success.is_some() {
-            query.push_str(&format!(" AND success = ${}", param_count));
-            param_count += 1;
-        }
-
-        if criteria.event_receiver_id.is_some() {
-            query.push_str(&format!(" AND event_receiver_id = ${}", param_count));
-            param_count += 1;
-        }
-
-        if criteria.start_time.is_some() {
-            query.push_str(&format!(" AND created_at >= ${}", param_count));
-            param_count += 1;
-        }
-
-        if criteria.end_time.is_some() {
-            query.push_str(&format!(" AND created_at <= ${}", param_count));
-            param_count += 1;
-        }
-
-        // Add ordering
-        query.push_str(" ORDER BY created_at DESC");
+        // Add ordering; break ties by id so offset pages never overlap
+        query.push(' ');
+        query.push_str(&Self::order_by_clause(criteria.order, ""));
 
         // Add pagination
         if criteria.limit.is_some() {
@@ -670,39 +1068,7 @@ impl EventRepository for PostgresEventRepository {
             query.push_str(&format!(" OFFSET ${}", param_count));
         }
 
-        // Build query with bindings
-        let mut sql_query = sqlx::query(&query);
-
-        if let Some(id) = criteria.id {
-            sql_query = sql_query.bind(id);
-        }
-        if let Some(name) = criteria.name {
-            sql_query = sql_query.bind(format!("%{}%", name));
-        }
-        if let Some(version) = criteria.version {
-            sql_query = sql_query.bind(version);
-        }
-        if let Some(release) = criteria.release {
-            sql_query = sql_query.bind(release);
-        }
-        if let Some(platform_id) = criteria.platform_id {
-            sql_query = sql_query.bind(platform_id);
-        }
-        if let Some(package) = criteria.package {
-            sql_query = sql_query.bind(package);
-        }
-        if let Some(success) = criteria.success {
-            sql_query = sql_query.bind(success);
-        }
-        if let Some(receiver_id) = criteria.event_receiver_id {
-            sql_query = sql_query.bind(receiver_id);
-        }
-        if let Some(start_time) = criteria.start_time {
-            sql_query = sql_query.bind(start_time);
-        }
-        if let Some(end_time) = criteria.end_time {
-            sql_query = sql_query.bind(end_time);
-        }
+        let mut sql_query = Self::bind_criteria(sqlx::query(&query), &criteria);
         if let Some(limit) = criteria.limit {
             sql_query = sql_query.bind(limit as i64);
         }
@@ -715,7 +1081,32 @@ impl EventRepository for PostgresEventRepository {
         rows.into_iter().map(|row| self.row_to_event(row)).collect()
     }
 
+    /// Counts events that match criteria, ignoring their limit and offset
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
+    async fn count_by_criteria(&self, criteria: FindEventCriteria) -> Result<usize> {
+        let (conditions, _) = Self::criteria_conditions(&criteria);
+        let query = format!(
+            "SELECT COUNT(*) as count FROM events WHERE 1=1{}",
+            conditions
+        );
+
+        let row = Self::bind_criteria(sqlx::query(&query), &criteria)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let count: i64 = row.try_get("count")?;
+
+        Ok(count as usize)
+    }
+
     /// Finds events owned by a specific user
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn find_by_owner(
         &self,
         owner_id: crate::domain::value_objects::UserId,
@@ -724,7 +1115,9 @@ impl EventRepository for PostgresEventRepository {
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at, owner_id, resource_version
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, owner_id, origin, resource_version
             FROM events
             WHERE owner_id = $1
             ORDER BY created_at DESC
@@ -743,6 +1136,10 @@ impl EventRepository for PostgresEventRepository {
     }
 
     /// Finds events by owner with pagination
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn find_by_owner_paginated(
         &self,
         owner_id: crate::domain::value_objects::UserId,
@@ -753,7 +1150,9 @@ impl EventRepository for PostgresEventRepository {
             r#"
             SELECT id, event_receiver_id, name, version, release,
                    platform_id, package, description, payload, success,
-                   created_at, owner_id, resource_version
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, owner_id, origin, resource_version
             FROM events
             WHERE owner_id = $1
             ORDER BY created_at DESC
@@ -775,6 +1174,10 @@ impl EventRepository for PostgresEventRepository {
     }
 
     /// Checks if a user owns a specific event
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn is_owner(
         &self,
         event_id: EventId,
@@ -797,6 +1200,10 @@ impl EventRepository for PostgresEventRepository {
     }
 
     /// Gets the current resource version of an event
+    #[instrument(
+        skip_all,
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
     async fn get_resource_version(&self, event_id: EventId) -> Result<Option<i64>> {
         let result = sqlx::query_scalar::<_, i64>(
             r#"
@@ -813,10 +1220,816 @@ impl EventRepository for PostgresEventRepository {
     }
 }
 
+impl PostgresEventRepository {
+    /// Converts a database row to an OutboxEntry
+    fn row_to_outbox_entry(row: sqlx::postgres::PgRow) -> Result<OutboxEntry> {
+        Ok(OutboxEntry {
+            event_id: row.try_get("event_id")?,
+            attempts: row.try_get::<i32, _>("attempts")? as u32,
+            last_error: row.try_get("last_error")?,
+            next_attempt_at: row.try_get("next_attempt_at")?,
+            dead_lettered_at: row.try_get("dead_lettered_at")?,
+        })
+    }
+
+    /// Converts a database row to an EventAttestation entity
+    fn row_to_attestation(row: sqlx::postgres::PgRow) -> Result<EventAttestation> {
+        let format: String = row.try_get("format")?;
+        Ok(EventAttestation {
+            event_id: row.try_get("event_id")?,
+            event_receiver_id: row.try_get("event_receiver_id")?,
+            attestation: Attestation {
+                format: format.parse()?,
+                spec_version: row.try_get("spec_version")?,
+                subject_digests: row.try_get("subject_digests")?,
+                builder_id: row.try_get("builder_id")?,
+                component_count: row
+                    .try_get::<Option<i64>, _>("component_count")?
+                    .map(|count| count as u64),
+                component_name: row.try_get("component_name")?,
+                component_version: row.try_get("component_version")?,
+            },
+            created_at: row.try_get("created_at")?,
+        })
+    }
+
+    /// Converts a database row to an IngestionMeta entity
+    fn row_to_ingestion_meta(row: sqlx::postgres::PgRow) -> Result<IngestionMeta> {
+        let event_id: EventId = row.try_get("event_id")?;
+        let principal_type: String = row.try_get("principal_type")?;
+        let source: String = row.try_get("source")?;
+
+        let context = IngestionContext {
+            principal_type: principal_type.parse()?,
+            principal_id: row.try_get("principal_id")?,
+            source: source.parse()?,
+            client_ip: row.try_get("client_ip")?,
+            user_agent: row.try_get("user_agent")?,
+            actor_id: row.try_get("actor_id")?,
+        };
+
+        let pii_findings: serde_json::Value = row.try_get("pii_findings")?;
+        Ok(
+            IngestionMeta::from_existing(event_id, context, row.try_get("recorded_at")?)
+                .with_pii_findings(serde_json::from_value(pii_findings)?),
+        )
+    }
+}
+
+#[async_trait]
+impl EventIngestionMetaRepository for PostgresEventRepository {
+    #[instrument(
+        skip(self, meta),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_ingestion_meta",
+            event_id = %meta.event_id()
+        )
+    )]
+    async fn save_ingestion_meta(&self, meta: &IngestionMeta) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO event_ingestion_meta (
+                event_id, principal_type, principal_id, source,
+                client_ip, user_agent, recorded_at, actor_id, pii_findings
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (event_id) DO NOTHING
+            "#,
+        )
+        .bind(meta.event_id())
+        .bind(meta.principal_type().as_str())
+        .bind(meta.principal_id())
+        .bind(meta.source().as_str())
+        .bind(meta.client_ip())
+        .bind(meta.user_agent())
+        .bind(meta.recorded_at())
+        .bind(meta.actor_id())
+        .bind(serde_json::to_value(meta.pii_findings())?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_ingestion_meta"
+        )
+    )]
+    async fn find_ingestion_meta(&self, event_id: EventId) -> Result<Option<IngestionMeta>> {
+        let row = sqlx::query(
+            r#"
+            SELECT event_id, principal_type, principal_id, source,
+                   client_ip, user_agent, recorded_at, actor_id, pii_findings
+            FROM event_ingestion_meta
+            WHERE event_id = $1
+            "#,
+        )
+        .bind(event_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(Self::row_to_ingestion_meta).transpose()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_ingestion_meta"
+        )
+    )]
+    async fn find_ingestion_meta_by_filter(
+        &self,
+        filter: &IngestionMetaFilter,
+    ) -> Result<Vec<IngestionMeta>> {
+        let query = format!(
+            r#"
+            SELECT m.event_id, m.principal_type, m.principal_id, m.source,
+                   m.client_ip, m.user_agent, m.recorded_at, m.actor_id, m.pii_findings
+            FROM event_ingestion_meta m
+            JOIN events e ON e.id = m.event_id
+            WHERE ($1::TEXT IS NULL OR m.principal_type = $1)
+              AND ($2::TEXT IS NULL OR m.principal_id = $2)
+              AND ($3::TEXT IS NULL OR m.source = $3)
+              AND ($4::TEXT IS NULL OR m.client_ip = $4)
+              AND ($7::TEXT IS NULL OR e.origin = $7)
+            {}
+            LIMIT $5 OFFSET $6
+            "#,
+            Self::order_by_clause(filter.order, "e.")
+        );
+        let rows = sqlx::query(&query)
+            .bind(filter.principal_type.map(|t| t.as_str()))
+            .bind(filter.principal_id.as_deref())
+            .bind(filter.source.map(|s| s.as_str()))
+            .bind(filter.client_ip.as_deref())
+            .bind(filter.limit.unwrap_or(50) as i64)
+            .bind(filter.offset.unwrap_or(0) as i64)
+            .bind(filter.origin.map(|o| o.as_str()))
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter().map(Self::row_to_ingestion_meta).collect()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_ingestion_meta"
+        )
+    )]
+    async fn count_ingestion_meta_by_filter(&self, filter: &IngestionMetaFilter) -> Result<usize> {
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM event_ingestion_meta m
+            JOIN events e ON e.id = m.event_id
+            WHERE ($1::TEXT IS NULL OR m.principal_type = $1)
+              AND ($2::TEXT IS NULL OR m.principal_id = $2)
+              AND ($3::TEXT IS NULL OR m.source = $3)
+              AND ($4::TEXT IS NULL OR m.client_ip = $4)
+              AND ($5::TEXT IS NULL OR e.origin = $5)
+            "#,
+        )
+        .bind(filter.principal_type.map(|t| t.as_str()))
+        .bind(filter.principal_id.as_deref())
+        .bind(filter.source.map(|s| s.as_str()))
+        .bind(filter.client_ip.as_deref())
+        .bind(filter.origin.map(|o| o.as_str()))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count as usize)
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_ingestion_meta pii_findings"
+        )
+    )]
+    async fn find_pii_report(
+        &self,
+        receiver_id: EventReceiverId,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<PiiReport> {
+        let events_with_findings: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM event_ingestion_meta m
+            JOIN events e ON e.id = m.event_id
+            WHERE e.event_receiver_id = $1
+              AND m.recorded_at >= $2
+              AND m.pii_findings <> '[]'::jsonb
+            "#,
+        )
+        .bind(receiver_id.to_string())
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT f.finding->>'detector' AS detector,
+                   f.finding->>'path' AS path,
+                   COUNT(*) AS count
+            FROM event_ingestion_meta m
+            JOIN events e ON e.id = m.event_id
+            CROSS JOIN LATERAL jsonb_array_elements(m.pii_findings) AS f(finding)
+            WHERE e.event_receiver_id = $1
+              AND m.recorded_at >= $2
+              AND m.pii_findings <> '[]'::jsonb
+            GROUP BY 1, 2
+            ORDER BY count DESC, detector, path
+            LIMIT $3
+            "#,
+        )
+        .bind(receiver_id.to_string())
+        .bind(since)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let paths = rows
+            .into_iter()
+            .map(|row| -> Result<PiiPathCount> {
+                let detector: String = row.try_get("detector")?;
+                let count: i64 = row.try_get("count")?;
+                Ok(PiiPathCount {
+                    detector: detector.parse()?,
+                    path: row.try_get("path")?,
+                    count: count as u64,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(PiiReport {
+            events_with_findings: events_with_findings as u64,
+            paths,
+        })
+    }
+}
+
+const ATTESTATION_COLUMNS: &str = "event_id, event_receiver_id, format, spec_version, \
+     subject_digests, builder_id, component_count, component_name, component_version, created_at";
+
+#[async_trait]
+impl EventAttestationRepository for PostgresEventRepository {
+    #[instrument(
+        skip(self, attestation),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_attestations",
+            event_id = %attestation.event_id
+        )
+    )]
+    async fn save_attestation(&self, attestation: &EventAttestation) -> Result<()> {
+        let extracted = &attestation.attestation;
+        sqlx::query(
+            r#"
+            INSERT INTO event_attestations (
+                event_id, event_receiver_id, format, spec_version, subject_digests,
+                builder_id, component_count, component_name, component_version, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (event_id) DO NOTHING
+            "#,
+        )
+        .bind(attestation.event_id)
+        .bind(attestation.event_receiver_id)
+        .bind(extracted.format.as_str())
+        .bind(&extracted.spec_version)
+        .bind(&extracted.subject_digests)
+        .bind(extracted.builder_id.as_deref())
+        .bind(extracted.component_count.map(|count| count as i64))
+        .bind(extracted.component_name.as_deref())
+        .bind(extracted.component_version.as_deref())
+        .bind(attestation.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_attestations"
+        )
+    )]
+    async fn find_attestation(&self, event_id: EventId) -> Result<Option<EventAttestation>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM event_attestations WHERE event_id = $1",
+            ATTESTATION_COLUMNS
+        ))
+        .bind(event_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(Self::row_to_attestation).transpose()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_attestations"
+        )
+    )]
+    async fn find_attestations(&self, filter: &AttestationFilter) -> Result<Vec<EventAttestation>> {
+        // The containment test on subject_digests can use its GIN index
+        let query = format!(
+            r#"
+            SELECT {}
+            FROM event_attestations
+            WHERE ($1::TEXT IS NULL OR subject_digests @> ARRAY[$1::TEXT])
+              AND ($2::TEXT IS NULL OR format = $2)
+              AND ($3::TEXT IS NULL OR builder_id = $3)
+            ORDER BY created_at DESC, event_id DESC
+            LIMIT $4 OFFSET $5
+            "#,
+            ATTESTATION_COLUMNS
+        );
+        let rows = sqlx::query(&query)
+            .bind(filter.digest.as_deref())
+            .bind(filter.format.map(|f| f.as_str()))
+            .bind(filter.builder_id.as_deref())
+            .bind(filter.limit.unwrap_or(50) as i64)
+            .bind(filter.offset.unwrap_or(0) as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter().map(Self::row_to_attestation).collect()
+    }
+}
+
+#[async_trait]
+impl EventFeedRepository for PostgresEventRepository {
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
+    async fn find_events_after(
+        &self,
+        after: Option<EventId>,
+        receiver_id: Option<EventReceiverId>,
+        limit: usize,
+    ) -> Result<Vec<Event>> {
+        // ULID text sorts in creation order only byte-wise
+        let rows = sqlx::query(
+            r#"
+            SELECT id, event_receiver_id, name, version, release,
+                   platform_id, package, description, payload, success,
+                   (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash)
+                       AS interned_payload, payload_summary,
+                   created_at, owner_id, origin, resource_version
+            FROM events
+            WHERE origin = 'user'
+              AND ($1::TEXT IS NULL OR id COLLATE "C" > $1)
+              AND ($2::TEXT IS NULL OR event_receiver_id = $2)
+            ORDER BY id COLLATE "C"
+            LIMIT $3
+            "#,
+        )
+        .bind(after)
+        .bind(receiver_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(|row| self.row_to_event(row)).collect()
+    }
+}
+
+#[async_trait]
+impl EventArchiveIndexRepository for PostgresEventRepository {
+    #[instrument(
+        skip(self, entries),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_archive_index",
+            count = entries.len()
+        )
+    )]
+    async fn record_archived(&self, entries: &[ArchiveIndexEntry]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for entry in entries {
+            sqlx::query(
+                r#"
+                INSERT INTO event_archive_index (
+                    event_id, event_receiver_id, segment, event_created_at, archived_at
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (event_id) DO UPDATE
+                SET segment = EXCLUDED.segment,
+                    archived_at = EXCLUDED.archived_at
+                "#,
+            )
+            .bind(entry.event_id)
+            .bind(entry.event_receiver_id)
+            .bind(&entry.segment)
+            .bind(entry.event_created_at)
+            .bind(entry.archived_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_archive_index"
+        )
+    )]
+    async fn find_archived(&self, event_id: EventId) -> Result<Option<ArchiveIndexEntry>> {
+        let row = sqlx::query(
+            r#"
+            SELECT event_id, event_receiver_id, segment, event_created_at, archived_at
+            FROM event_archive_index
+            WHERE event_id = $1
+            "#,
+        )
+        .bind(event_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(ArchiveIndexEntry {
+                event_id: row.try_get("event_id")?,
+                event_receiver_id: row.try_get("event_receiver_id")?,
+                segment: row.try_get("segment")?,
+                event_created_at: row.try_get("event_created_at")?,
+                archived_at: row.try_get("archived_at")?,
+            })
+        })
+        .transpose()
+    }
+}
+
+#[async_trait]
+impl EventOutboxRepository for PostgresEventRepository {
+    #[instrument(
+        skip(self, error),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_publish_outbox"
+        )
+    )]
+    async fn enqueue(
+        &self,
+        event_id: EventId,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO event_publish_outbox (event_id, attempts, last_error, next_attempt_at)
+            VALUES ($1, 1, $2, $3)
+            ON CONFLICT (event_id) DO NOTHING
+            "#,
+        )
+        .bind(event_id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_publish_outbox"
+        )
+    )]
+    async fn find_due(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<OutboxEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT event_id, attempts, last_error, next_attempt_at, dead_lettered_at
+            FROM event_publish_outbox
+            WHERE dead_lettered_at IS NULL AND next_attempt_at <= $1
+            ORDER BY next_attempt_at, event_id
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_outbox_entry).collect()
+    }
+
+    #[instrument(
+        skip(self, event_ids),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_publish_outbox",
+            count = event_ids.len()
+        )
+    )]
+    async fn find_entries(&self, event_ids: &[EventId]) -> Result<Vec<OutboxEntry>> {
+        if event_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<String> = event_ids.iter().map(|id| id.to_string()).collect();
+        let rows = sqlx::query(
+            r#"
+            SELECT event_id, attempts, last_error, next_attempt_at, dead_lettered_at
+            FROM event_publish_outbox
+            WHERE event_id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_outbox_entry).collect()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_publish_outbox"
+        )
+    )]
+    async fn mark_published(&self, event_id: EventId) -> Result<()> {
+        sqlx::query("DELETE FROM event_publish_outbox WHERE event_id = $1")
+            .bind(event_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, error),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_publish_outbox"
+        )
+    )]
+    async fn record_failure(
+        &self,
+        event_id: EventId,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE event_publish_outbox
+            SET attempts = attempts + 1,
+                last_error = $2,
+                next_attempt_at = COALESCE($3, next_attempt_at),
+                dead_lettered_at = CASE WHEN $3::timestamptz IS NULL THEN NOW() END
+            WHERE event_id = $1
+            "#,
+        )
+        .bind(event_id)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_publish_outbox"
+        )
+    )]
+    async fn backlog(&self) -> Result<OutboxBacklog> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE dead_lettered_at IS NULL) AS pending,
+                   COUNT(*) FILTER (WHERE dead_lettered_at IS NOT NULL) AS dead_lettered
+            FROM event_publish_outbox
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(OutboxBacklog {
+            pending: row.try_get::<i64, _>("pending")? as u64,
+            dead_lettered: row.try_get::<i64, _>("dead_lettered")? as u64,
+        })
+    }
+}
+
+/// Drops one reference to an interned payload
+///
+/// Payloads left without references are removed later by
+/// [`EventPayloadRepository::collect_unreferenced`].
+async fn release_payload(tx: &mut Transaction<'_, Postgres>, hash: &str) -> Result<()> {
+    sqlx::query("UPDATE event_payloads SET ref_count = ref_count - 1 WHERE hash = $1")
+        .bind(hash)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+#[async_trait]
+impl EventPayloadRepository for PostgresEventRepository {
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "DELETE event_payloads")
+    )]
+    async fn collect_unreferenced(&self, limit: usize) -> Result<CollectedPayloads> {
+        // Rows being referenced again are locked by the saving transaction
+        // and skipped; the outer check re-reads the count once locked
+        let sizes: Vec<i64> = sqlx::query_scalar(
+            r#"
+            DELETE FROM event_payloads
+            WHERE hash IN (
+                SELECT hash FROM event_payloads
+                WHERE ref_count <= 0
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            AND ref_count <= 0
+            RETURNING size_bytes
+            "#,
+        )
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(CollectedPayloads {
+            payloads: sizes.len(),
+            bytes: sizes.iter().map(|size| *size as u64).sum(),
+        })
+    }
+
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT event_payloads")
+    )]
+    async fn payload_stats(&self) -> Result<PayloadStoreStats> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) AS payloads,
+                   COALESCE(SUM(size_bytes), 0)::BIGINT AS stored_bytes,
+                   COALESCE(SUM(size_bytes * GREATEST(ref_count - 1, 0)), 0)::BIGINT
+                       AS saved_bytes
+            FROM event_payloads
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(PayloadStoreStats {
+            payloads: row.try_get::<i64, _>("payloads")? as u64,
+            stored_bytes: row.try_get::<i64, _>("stored_bytes")? as u64,
+            saved_bytes: row.try_get::<i64, _>("saved_bytes")? as u64,
+        })
+    }
+}
+
+/// Severity expression matching [`event_severity`]
+///
+/// Reads the severity of interned payloads from the shared payload store.
+pub(crate) const SEVERITY_SQL: &str = "COALESCE(LOWER(CASE WHEN jsonb_typeof(severity_payload->'severity') = 'string' THEN severity_payload->>'severity' END), '')";
+
+/// Payload fields [`SEVERITY_SQL`] reads for an event row
+///
+/// The payload whether stored inline or interned, or the summary fields of
+/// an offloaded payload, which keep its severity.
+pub(crate) const SEVERITY_PAYLOAD_SQL: &str = "COALESCE(payload_summary->'fields', payload, (SELECT p.body::jsonb FROM event_payloads p WHERE p.hash = payload_hash))";
+
+#[async_trait]
+impl EventCountRepository for PostgresEventRepository {
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
+    async fn receiver_event_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ReceiverEventCounts>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT event_receiver_id,
+                   COUNT(*) FILTER (WHERE success) AS successful,
+                   COUNT(*) FILTER (WHERE NOT success) AS failed
+            FROM events
+            WHERE created_at >= $1 AND created_at < $2 AND origin = 'user'
+            GROUP BY event_receiver_id
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(ReceiverEventCounts {
+                    receiver_id: row.try_get("event_receiver_id")?,
+                    successful: row.try_get::<i64, _>("successful")? as u64,
+                    failed: row.try_get::<i64, _>("failed")? as u64,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
+    async fn bucket_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<EventCountBucket>> {
+        let sql = format!(
+            r#"
+            SELECT event_receiver_id,
+                   date_trunc('hour', created_at, 'UTC') AS bucket_start,
+                   {} AS severity,
+                   success,
+                   COUNT(*) AS count
+            FROM (SELECT *, {} AS severity_payload FROM events) AS events
+            WHERE created_at >= $1 AND created_at < $2 AND origin = 'user'
+            GROUP BY 1, 2, 3, 4
+            "#,
+            SEVERITY_SQL, SEVERITY_PAYLOAD_SQL
+        );
+        let rows = sqlx::query(&sql)
+            .bind(start)
+            .bind(end)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(EventCountBucket {
+                    receiver_id: row.try_get("event_receiver_id")?,
+                    bucket_start: row.try_get("bucket_start")?,
+                    severity: row.try_get("severity")?,
+                    success: row.try_get("success")?,
+                    count: row.try_get::<i64, _>("count")? as u64,
+                })
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Integration tests require a running PostgreSQL instance
     // These are placeholder tests - full integration tests should use testcontainers
+    use super::*;
+    use crate::domain::repositories::pagination::SortField;
+
+    #[test]
+    fn test_order_by_clause_maps_every_field() {
+        for (name, field) in EventSortField::FIELDS {
+            for order in [ListOrder::asc(*field), ListOrder::desc(*field)] {
+                let direction = order.direction.as_sql();
+                assert_eq!(
+                    PostgresEventRepository::order_by_clause(order, "e."),
+                    format!("ORDER BY e.{name} {direction}, e.id {direction}")
+                );
+            }
+        }
+    }
 
     #[tokio::test]
     #[ignore = "requires database"]
@@ -0,0 +1,162 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_event_rollup_repo.rs
+
+use crate::domain::repositories::event_rollup_repo::{
+    EventCountBucket, EventCountRepository, EventRollupRepository, ReceiverEventCounts,
+};
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the hourly event count rollup
+///
+/// Rows are incremented by `PostgresEventRepository::save` in the same
+/// transaction as the event insert. Range queries filter on `bucket_start`,
+/// so callers must pass bucket-aligned ranges.
+pub struct PostgresEventRollupRepository {
+    pool: PgPool,
+}
+
+impl PostgresEventRollupRepository {
+    /// Creates a new PostgreSQL event rollup repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EventCountRepository for PostgresEventRollupRepository {
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_counts_hourly"
+        )
+    )]
+    async fn receiver_event_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<ReceiverEventCounts>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT event_receiver_id,
+                   COALESCE(SUM(count) FILTER (WHERE success), 0)::BIGINT AS successful,
+                   COALESCE(SUM(count) FILTER (WHERE NOT success), 0)::BIGINT AS failed
+            FROM event_counts_hourly
+            WHERE bucket_start >= $1 AND bucket_start < $2
+            GROUP BY event_receiver_id
+            HAVING SUM(count) > 0
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(ReceiverEventCounts {
+                    receiver_id: row.try_get("event_receiver_id")?,
+                    successful: row.try_get::<i64, _>("successful")? as u64,
+                    failed: row.try_get::<i64, _>("failed")? as u64,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_counts_hourly"
+        )
+    )]
+    async fn bucket_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<EventCountBucket>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT event_receiver_id, bucket_start, severity, success, count
+            FROM event_counts_hourly
+            WHERE bucket_start >= $1 AND bucket_start < $2 AND count > 0
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(EventCountBucket {
+                    receiver_id: row.try_get("event_receiver_id")?,
+                    bucket_start: row.try_get("bucket_start")?,
+                    severity: row.try_get("severity")?,
+                    success: row.try_get("success")?,
+                    count: row.try_get::<i64, _>("count")? as u64,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventRollupRepository for PostgresEventRollupRepository {
+    #[instrument(
+        skip(self, buckets),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_counts_hourly",
+            count = buckets.len()
+        )
+    )]
+    async fn replace_buckets(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        buckets: &[EventCountBucket],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "DELETE FROM event_counts_hourly WHERE bucket_start >= $1 AND bucket_start < $2",
+        )
+        .bind(start)
+        .bind(end)
+        .execute(&mut *tx)
+        .await?;
+
+        for bucket in buckets {
+            sqlx::query(
+                r#"
+                INSERT INTO event_counts_hourly (
+                    event_receiver_id, bucket_start, severity, success, count
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (event_receiver_id, bucket_start, severity, success) DO UPDATE SET
+                    count = EXCLUDED.count
+                "#,
+            )
+            .bind(bucket.receiver_id)
+            .bind(bucket.bucket_start)
+            .bind(&bucket.severity)
+            .bind(bucket.success)
+            .bind(bucket.count as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
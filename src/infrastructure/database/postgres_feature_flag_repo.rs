@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_feature_flag_repo.rs
+
+use crate::domain::repositories::feature_flag_repo::{FeatureFlagRepository, StoredFeatureFlag};
+use crate::error::Result;
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the FeatureFlagRepository trait
+pub struct PostgresFeatureFlagRepository {
+    pool: PgPool,
+}
+
+impl PostgresFeatureFlagRepository {
+    /// Creates a new PostgreSQL feature flag repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FeatureFlagRepository for PostgresFeatureFlagRepository {
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT feature_flags"
+        )
+    )]
+    async fn list_flags(&self) -> Result<Vec<StoredFeatureFlag>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT name, enabled, role_overrides, updated_by, updated_at
+            FROM feature_flags
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let role_overrides: JsonValue = row.get("role_overrides");
+                Ok(StoredFeatureFlag {
+                    name: row.get("name"),
+                    enabled: row.get("enabled"),
+                    role_overrides: serde_json::from_value(role_overrides)?,
+                    updated_by: row.get("updated_by"),
+                    updated_at: row.get("updated_at"),
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(
+        skip(self, flag),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT feature_flags",
+            flag = %flag.name
+        )
+    )]
+    async fn save_flag(&self, flag: &StoredFeatureFlag) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO feature_flags (name, enabled, role_overrides, updated_by, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (name) DO UPDATE
+            SET enabled = EXCLUDED.enabled,
+                role_overrides = EXCLUDED.role_overrides,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(&flag.name)
+        .bind(flag.enabled)
+        .bind(serde_json::to_value(&flag.role_overrides)?)
+        .bind(&flag.updated_by)
+        .bind(flag.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_group_topic_outbox_repo.rs
+
+use crate::domain::repositories::event_outbox_repo::{
+    GroupTopicOutboxEntry, GroupTopicOutboxRepository, OutboxBacklog,
+};
+use crate::domain::value_objects::EventId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the GroupTopicOutboxRepository trait
+pub struct PostgresGroupTopicOutboxRepository {
+    pool: PgPool,
+}
+
+impl PostgresGroupTopicOutboxRepository {
+    /// Creates a new PostgreSQL group topic outbox repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Converts a database row to a GroupTopicOutboxEntry
+    fn row_to_entry(row: sqlx::postgres::PgRow) -> Result<GroupTopicOutboxEntry> {
+        Ok(GroupTopicOutboxEntry {
+            event_id: row.try_get("event_id")?,
+            topic: row.try_get("topic")?,
+            attempts: row.try_get::<i32, _>("attempts")? as u32,
+            last_error: row.try_get("last_error")?,
+            next_attempt_at: row.try_get("next_attempt_at")?,
+            dead_lettered_at: row.try_get("dead_lettered_at")?,
+        })
+    }
+}
+
+#[async_trait]
+impl GroupTopicOutboxRepository for PostgresGroupTopicOutboxRepository {
+    #[instrument(
+        skip(self, error),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_group_topic_outbox"
+        )
+    )]
+    async fn enqueue(
+        &self,
+        event_id: EventId,
+        topic: &str,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO event_group_topic_outbox (
+                event_id, topic, attempts, last_error, next_attempt_at
+            )
+            VALUES ($1, $2, 1, $3, $4)
+            ON CONFLICT (event_id, topic) DO NOTHING
+            "#,
+        )
+        .bind(event_id)
+        .bind(topic)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_group_topic_outbox"
+        )
+    )]
+    async fn find_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<GroupTopicOutboxEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT event_id, topic, attempts, last_error, next_attempt_at, dead_lettered_at
+            FROM event_group_topic_outbox
+            WHERE dead_lettered_at IS NULL AND next_attempt_at <= $1
+            ORDER BY next_attempt_at, event_id, topic
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_entry).collect()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE event_group_topic_outbox"
+        )
+    )]
+    async fn mark_published(&self, event_id: EventId, topic: &str) -> Result<()> {
+        sqlx::query("DELETE FROM event_group_topic_outbox WHERE event_id = $1 AND topic = $2")
+            .bind(event_id)
+            .bind(topic)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, error),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE event_group_topic_outbox"
+        )
+    )]
+    async fn record_failure(
+        &self,
+        event_id: EventId,
+        topic: &str,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE event_group_topic_outbox
+            SET attempts = attempts + 1,
+                last_error = $3,
+                next_attempt_at = COALESCE($4, next_attempt_at),
+                dead_lettered_at = CASE WHEN $4::timestamptz IS NULL THEN NOW() END
+            WHERE event_id = $1 AND topic = $2
+            "#,
+        )
+        .bind(event_id)
+        .bind(topic)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_group_topic_outbox"
+        )
+    )]
+    async fn backlog(&self) -> Result<OutboxBacklog> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE dead_lettered_at IS NULL) AS pending,
+                   COUNT(*) FILTER (WHERE dead_lettered_at IS NOT NULL) AS dead_lettered
+            FROM event_group_topic_outbox
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(OutboxBacklog {
+            pending: row.try_get::<i64, _>("pending")? as u64,
+            dead_lettered: row.try_get::<i64, _>("dead_lettered")? as u64,
+        })
+    }
+}
@@ -0,0 +1,86 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_maintenance_repo.rs
+
+use crate::domain::repositories::maintenance_repo::{MaintenanceRepository, StoredMaintenance};
+use crate::error::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the MaintenanceRepository trait
+pub struct PostgresMaintenanceRepository {
+    pool: PgPool,
+}
+
+impl PostgresMaintenanceRepository {
+    /// Creates a new PostgreSQL maintenance mode repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl MaintenanceRepository for PostgresMaintenanceRepository {
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT maintenance_mode"
+        )
+    )]
+    async fn load_maintenance(&self) -> Result<Option<StoredMaintenance>> {
+        let row = sqlx::query(
+            r#"
+            SELECT enabled, message, ends_at, updated_by, updated_at
+            FROM maintenance_mode
+            WHERE id = 1
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| StoredMaintenance {
+            enabled: row.get("enabled"),
+            message: row.get("message"),
+            ends_at: row.get("ends_at"),
+            updated_by: row.get("updated_by"),
+            updated_at: row.get("updated_at"),
+        }))
+    }
+
+    #[instrument(
+        skip(self, maintenance),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT maintenance_mode",
+            enabled = maintenance.enabled
+        )
+    )]
+    async fn save_maintenance(&self, maintenance: &StoredMaintenance) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO maintenance_mode (id, enabled, message, ends_at, updated_by, updated_at)
+            VALUES (1, $1, $2, $3, $4, $5)
+            ON CONFLICT (id) DO UPDATE
+            SET enabled = EXCLUDED.enabled,
+                message = EXCLUDED.message,
+                ends_at = EXCLUDED.ends_at,
+                updated_by = EXCLUDED.updated_by,
+                updated_at = EXCLUDED.updated_at
+            "#,
+        )
+        .bind(maintenance.enabled)
+        .bind(&maintenance.message)
+        .bind(maintenance.ends_at)
+        .bind(&maintenance.updated_by)
+        .bind(maintenance.updated_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
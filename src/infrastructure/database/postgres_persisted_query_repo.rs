@@ -0,0 +1,133 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_persisted_query_repo.rs
+
+use crate::domain::repositories::persisted_query_repo::{
+    PersistedQueryRepository, StoredPersistedQuery,
+};
+use crate::error::Result;
+use async_trait::async_trait;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the PersistedQueryRepository trait
+pub struct PostgresPersistedQueryRepository {
+    pool: PgPool,
+}
+
+impl PostgresPersistedQueryRepository {
+    /// Creates a new PostgreSQL persisted query repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_query(row: PgRow) -> StoredPersistedQuery {
+    StoredPersistedQuery {
+        hash: row.get("hash"),
+        query: row.get("query"),
+        registered: row.get("registered"),
+        registered_by: row.get("registered_by"),
+        created_at: row.get("created_at"),
+    }
+}
+
+#[async_trait]
+impl PersistedQueryRepository for PostgresPersistedQueryRepository {
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT graphql_persisted_queries"
+        )
+    )]
+    async fn find_query(&self, hash: &str) -> Result<Option<StoredPersistedQuery>> {
+        let row = sqlx::query(
+            r#"
+            SELECT hash, query, registered, registered_by, created_at
+            FROM graphql_persisted_queries
+            WHERE hash = $1
+            "#,
+        )
+        .bind(hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(row_to_query))
+    }
+
+    #[instrument(
+        skip(self, query),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT graphql_persisted_queries",
+            hash = %query.hash
+        )
+    )]
+    async fn save_query(&self, query: &StoredPersistedQuery) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO graphql_persisted_queries
+                (hash, query, registered, registered_by, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (hash) DO UPDATE
+            SET registered = TRUE,
+                registered_by = EXCLUDED.registered_by
+            WHERE EXCLUDED.registered
+            "#,
+        )
+        .bind(&query.hash)
+        .bind(&query.query)
+        .bind(query.registered)
+        .bind(&query.registered_by)
+        .bind(query.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT graphql_persisted_queries"
+        )
+    )]
+    async fn list_registered(&self) -> Result<Vec<StoredPersistedQuery>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT hash, query, registered, registered_by, created_at
+            FROM graphql_persisted_queries
+            WHERE registered
+            ORDER BY created_at, hash
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(row_to_query).collect())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE graphql_persisted_queries"
+        )
+    )]
+    async fn delete_query(&self, hash: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM graphql_persisted_queries WHERE hash = $1")
+            .bind(hash)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
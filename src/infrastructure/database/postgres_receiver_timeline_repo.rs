@@ -0,0 +1,238 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_receiver_timeline_repo.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::repositories::receiver_timeline_repo::{
+    config_change, history_key, MembershipAction, ReceiverTimelineRepository, TimelineCursor,
+    TimelineDetail, TimelineEntry, TimelineKind,
+};
+use crate::domain::value_objects::{EventId, EventReceiverId};
+use crate::error::Result;
+use crate::infrastructure::database::postgres_event_repo::{SEVERITY_PAYLOAD_SQL, SEVERITY_SQL};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value as JsonValue;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// Key of a history row, matching [`history_key`]
+const HISTORY_KEY_SQL: &str = "LPAD(id::TEXT, 20, '0')";
+
+/// PostgreSQL implementation of the ReceiverTimelineRepository trait
+///
+/// Events come from the events table, configuration changes from the
+/// receiver history, and membership changes from the group history of
+/// groups the receiver ever belonged to. Each query seeks past the cursor
+/// and stops at the limit, so reading a page does not load the timeline.
+pub struct PostgresReceiverTimelineRepository {
+    pool: PgPool,
+}
+
+impl PostgresReceiverTimelineRepository {
+    /// Creates a new PostgreSQL receiver timeline repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn events(
+        &self,
+        receiver_id: EventReceiverId,
+        after: &TimelineCursor,
+        end: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>> {
+        // ULID text sorts in creation order only byte-wise
+        let sql = format!(
+            r#"
+            SELECT id, name, success, created_at, {} AS severity
+            FROM (
+                SELECT id, name, success, created_at, {} AS severity_payload
+                FROM events
+                WHERE event_receiver_id = $1
+                  AND origin = 'user'
+                  AND (created_at > $2
+                       OR (created_at = $2 AND $3::TEXT IS NOT NULL AND id COLLATE "C" > $3))
+                  AND ($4::TIMESTAMPTZ IS NULL OR created_at < $4)
+                ORDER BY created_at, id COLLATE "C"
+                LIMIT $5
+            ) AS events
+            ORDER BY created_at, id COLLATE "C"
+            "#,
+            SEVERITY_SQL, SEVERITY_PAYLOAD_SQL
+        );
+        let rows = sqlx::query(&sql)
+            .bind(receiver_id)
+            .bind(after.timestamp())
+            .bind(after.key_after(TimelineKind::Event))
+            .bind(end)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let id: EventId = row.try_get("id")?;
+                Ok(TimelineEntry::new(
+                    row.try_get("created_at")?,
+                    id.to_string(),
+                    TimelineDetail::Event {
+                        id,
+                        name: row.try_get("name")?,
+                        success: row.try_get("success")?,
+                        severity: row.try_get("severity")?,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    async fn config_changes(
+        &self,
+        receiver_id: EventReceiverId,
+        after: &TimelineCursor,
+        end: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>> {
+        let sql = format!(
+            r#"
+            SELECT id, recorded_at, state, previous_state
+            FROM (
+                SELECT id, recorded_at, state,
+                       LAG(state) OVER (ORDER BY recorded_at, id) AS previous_state
+                FROM event_receiver_history
+                WHERE event_receiver_id = $1
+            ) AS history
+            WHERE (recorded_at > $2
+                   OR (recorded_at = $2 AND $3::TEXT IS NOT NULL AND {} > $3))
+              AND ($4::TIMESTAMPTZ IS NULL OR recorded_at < $4)
+            ORDER BY recorded_at, id
+            LIMIT $5
+            "#,
+            HISTORY_KEY_SQL
+        );
+        let rows = sqlx::query(&sql)
+            .bind(receiver_id)
+            .bind(after.timestamp())
+            .bind(after.key_after(TimelineKind::ConfigChange))
+            .bind(end)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let state = receiver_state(row.try_get("state")?)?;
+                let previous = receiver_state(row.try_get("previous_state")?)?;
+                Ok(TimelineEntry::new(
+                    row.try_get("recorded_at")?,
+                    history_key(row.try_get("id")?),
+                    config_change(previous.as_ref(), state.as_ref()),
+                ))
+            })
+            .collect()
+    }
+
+    async fn membership_changes(
+        &self,
+        receiver_id: EventReceiverId,
+        after: &TimelineCursor,
+        end: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>> {
+        // A change is a snapshot whose membership differs from the previous
+        // snapshot of the same group; deleting a group removes its receivers
+        let sql = format!(
+            r#"
+            SELECT id, event_receiver_group_id, recorded_at, group_name, member
+            FROM (
+                SELECT id, event_receiver_group_id, recorded_at,
+                       COALESCE(state->'group'->>'name',
+                                LAG(state->'group'->>'name') OVER snapshots) AS group_name,
+                       COALESCE(state->'group'->'event_receiver_ids' ? $1, FALSE) AS member,
+                       COALESCE(LAG(state->'group'->'event_receiver_ids' ? $1) OVER snapshots,
+                                FALSE) AS was_member
+                FROM event_receiver_group_history
+                WHERE event_receiver_group_id IN (
+                    SELECT event_receiver_group_id
+                    FROM event_receiver_group_history
+                    WHERE state->'group'->'event_receiver_ids' ? $1
+                )
+                WINDOW snapshots AS (
+                    PARTITION BY event_receiver_group_id ORDER BY recorded_at, id
+                )
+            ) AS history
+            WHERE member <> was_member
+              AND (recorded_at > $2
+                   OR (recorded_at = $2 AND $3::TEXT IS NOT NULL AND {} > $3))
+              AND ($4::TIMESTAMPTZ IS NULL OR recorded_at < $4)
+            ORDER BY recorded_at, id
+            LIMIT $5
+            "#,
+            HISTORY_KEY_SQL
+        );
+        let rows = sqlx::query(&sql)
+            .bind(receiver_id)
+            .bind(after.timestamp())
+            .bind(after.key_after(TimelineKind::GroupMembership))
+            .bind(end)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let member: bool = row.try_get("member")?;
+                Ok(TimelineEntry::new(
+                    row.try_get("recorded_at")?,
+                    history_key(row.try_get("id")?),
+                    TimelineDetail::GroupMembership {
+                        group_id: row.try_get("event_receiver_group_id")?,
+                        group_name: row
+                            .try_get::<Option<String>, _>("group_name")?
+                            .unwrap_or_default(),
+                        action: if member {
+                            MembershipAction::Added
+                        } else {
+                            MembershipAction::Removed
+                        },
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+fn receiver_state(state: Option<JsonValue>) -> Result<Option<EventReceiver>> {
+    Ok(state.map(serde_json::from_value).transpose()?)
+}
+
+#[async_trait]
+impl ReceiverTimelineRepository for PostgresReceiverTimelineRepository {
+    #[instrument(
+        skip(self, after),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT receiver timeline"
+        )
+    )]
+    async fn find_timeline_entries(
+        &self,
+        receiver_id: EventReceiverId,
+        kind: TimelineKind,
+        after: &TimelineCursor,
+        end: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<TimelineEntry>> {
+        match kind {
+            TimelineKind::Event => self.events(receiver_id, after, end, limit).await,
+            TimelineKind::ConfigChange => self.config_changes(receiver_id, after, end, limit).await,
+            TimelineKind::GroupMembership => {
+                self.membership_changes(receiver_id, after, end, limit)
+                    .await
+            }
+        }
+    }
+}
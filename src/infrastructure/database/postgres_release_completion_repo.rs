@@ -0,0 +1,58 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_release_completion_repo.rs
+
+use crate::domain::repositories::release_completion_repo::ReleaseCompletionRepository;
+use crate::domain::value_objects::EventReceiverGroupId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// PostgreSQL implementation of the ReleaseCompletionRepository trait
+pub struct PostgresReleaseCompletionRepository {
+    pool: PgPool,
+}
+
+impl PostgresReleaseCompletionRepository {
+    /// Creates a new PostgreSQL release completion repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReleaseCompletionRepository for PostgresReleaseCompletionRepository {
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT group_release_completions",
+            group_id = %group_id
+        )
+    )]
+    async fn record_completion(
+        &self,
+        group_id: EventReceiverGroupId,
+        release: &str,
+        completed_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO group_release_completions (group_id, release, completed_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (group_id, release) DO NOTHING
+            "#,
+        )
+        .bind(group_id.to_string())
+        .bind(release)
+        .bind(completed_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,165 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_resource_history_repo.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::repositories::resource_history_repo::{
+    GroupState, ResourceHistoryRepository, ResourceSnapshot,
+};
+use crate::domain::value_objects::{EventReceiverGroupId, EventReceiverId};
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the ResourceHistoryRepository trait
+///
+/// Snapshots are stored as JSON so that history survives changes to the
+/// receiver and group tables. Rows recorded at the same instant keep their
+/// insertion order.
+pub struct PostgresResourceHistoryRepository {
+    pool: PgPool,
+}
+
+impl PostgresResourceHistoryRepository {
+    /// Creates a new PostgreSQL resource history repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn state_json<T: Serialize>(snapshot: &ResourceSnapshot<T>) -> Result<Option<JsonValue>> {
+    Ok(snapshot
+        .state
+        .as_ref()
+        .map(serde_json::to_value)
+        .transpose()?)
+}
+
+fn row_to_snapshot<T: DeserializeOwned>(row: &PgRow) -> Result<ResourceSnapshot<T>> {
+    let recorded_at: DateTime<Utc> = row.get("recorded_at");
+    let state: Option<JsonValue> = row.get("state");
+    Ok(ResourceSnapshot {
+        recorded_at,
+        state: state.map(serde_json::from_value).transpose()?,
+    })
+}
+
+#[async_trait]
+impl ResourceHistoryRepository for PostgresResourceHistoryRepository {
+    #[instrument(
+        skip(self, snapshot),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receiver_history"
+        )
+    )]
+    async fn record_receiver(
+        &self,
+        id: EventReceiverId,
+        snapshot: &ResourceSnapshot<EventReceiver>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO event_receiver_history (event_receiver_id, recorded_at, state)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(snapshot.recorded_at)
+        .bind(state_json(snapshot)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_history"
+        )
+    )]
+    async fn receiver_history(
+        &self,
+        id: EventReceiverId,
+    ) -> Result<Vec<ResourceSnapshot<EventReceiver>>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT recorded_at, state
+            FROM event_receiver_history
+            WHERE event_receiver_id = $1
+            ORDER BY recorded_at, id
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(row_to_snapshot).collect()
+    }
+
+    #[instrument(
+        skip(self, snapshot),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT event_receiver_group_history"
+        )
+    )]
+    async fn record_group(
+        &self,
+        id: EventReceiverGroupId,
+        snapshot: &ResourceSnapshot<GroupState>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO event_receiver_group_history (event_receiver_group_id, recorded_at, state)
+            VALUES ($1, $2, $3)
+            "#,
+        )
+        .bind(id.to_string())
+        .bind(snapshot.recorded_at)
+        .bind(state_json(snapshot)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_group_history"
+        )
+    )]
+    async fn group_history(
+        &self,
+        id: EventReceiverGroupId,
+    ) -> Result<Vec<ResourceSnapshot<GroupState>>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT recorded_at, state
+            FROM event_receiver_group_history
+            WHERE event_receiver_group_id = $1
+            ORDER BY recorded_at, id
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(row_to_snapshot).collect()
+    }
+}
@@ -0,0 +1,173 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_search_repo.rs
+
+use crate::domain::entities::search::{
+    render_snippet, SearchHit, SearchQuery, SearchResourceType, HIGHLIGHT_START, HIGHLIGHT_STOP,
+};
+use crate::domain::repositories::search_repo::{SearchMatches, SearchRepository, SearchVisibility};
+use crate::error::Result;
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// Keeps receivers the viewer owns or reaches through a current group
+/// membership; `$3` is the viewer, or NULL to keep every receiver
+const RECEIVER_VISIBLE_SQL: &str = r#"
+    ($3::text IS NULL OR r.owner_id = $3 OR EXISTS (
+        SELECT 1
+        FROM event_receiver_group_receivers gr
+        JOIN event_receiver_group_members gm ON gm.group_id = gr.group_id
+        WHERE gr.receiver_id = r.id
+          AND gm.user_id = $3
+          AND (gm.expires_at IS NULL OR gm.expires_at > NOW())
+    ))
+"#;
+
+/// Keeps events the viewer owns or whose receiver is in one of their
+/// groups; system events are never searched
+const EVENT_VISIBLE_SQL: &str = r#"
+    r.origin = 'user'
+    AND ($3::text IS NULL OR r.owner_id = $3 OR EXISTS (
+        SELECT 1
+        FROM event_receiver_group_receivers gr
+        JOIN event_receiver_group_members gm ON gm.group_id = gr.group_id
+        WHERE gr.receiver_id = r.event_receiver_id
+          AND gm.user_id = $3
+          AND (gm.expires_at IS NULL OR gm.expires_at > NOW())
+    ))
+"#;
+
+/// Keeps groups the viewer owns or is a current member of
+const GROUP_VISIBLE_SQL: &str = r#"
+    ($3::text IS NULL OR r.owner_id = $3 OR EXISTS (
+        SELECT 1
+        FROM event_receiver_group_members gm
+        WHERE gm.group_id = r.id
+          AND gm.user_id = $3
+          AND (gm.expires_at IS NULL OR gm.expires_at > NOW())
+    ))
+"#;
+
+/// PostgreSQL implementation of the SearchRepository trait
+///
+/// Matches use the GIN indexes over the generated `search_vector` columns.
+/// Snippets are only built for the rows returned.
+pub struct PostgresSearchRepository {
+    pool: PgPool,
+}
+
+impl PostgresSearchRepository {
+    /// Creates a new PostgreSQL search repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the table, the text snippets are cut from, and the
+    /// visibility filter of a resource type
+    fn source(resource_type: SearchResourceType) -> (&'static str, &'static str, &'static str) {
+        match resource_type {
+            SearchResourceType::Receiver => (
+                "event_receivers",
+                "r.name || ': ' || coalesce(r.description, '')",
+                RECEIVER_VISIBLE_SQL,
+            ),
+            SearchResourceType::Event => (
+                "events",
+                "r.name || ' (' || r.package || '): ' || coalesce(r.description, '')",
+                EVENT_VISIBLE_SQL,
+            ),
+            SearchResourceType::Group => (
+                "event_receiver_groups",
+                "r.name || ': ' || coalesce(r.description, '')",
+                GROUP_VISIBLE_SQL,
+            ),
+        }
+    }
+
+    fn headline_options() -> String {
+        format!(
+            "StartSel=\"{}\", StopSel=\"{}\", MaxWords=30, MinWords=10, MaxFragments=2, FragmentDelimiter=\" … \"",
+            HIGHLIGHT_START, HIGHLIGHT_STOP
+        )
+    }
+}
+
+#[async_trait]
+impl SearchRepository for PostgresSearchRepository {
+    #[instrument(
+        skip(self, query),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT to_tsquery")
+    )]
+    async fn is_searchable(&self, query: &SearchQuery) -> Result<bool> {
+        let nodes: i32 = sqlx::query_scalar("SELECT numnode(to_tsquery('english', $1))")
+            .bind(query.to_tsquery())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(nodes > 0)
+    }
+
+    #[instrument(
+        skip(self, query),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT search_vector")
+    )]
+    async fn search(
+        &self,
+        resource_type: SearchResourceType,
+        query: &SearchQuery,
+        visibility: SearchVisibility,
+        limit: usize,
+    ) -> Result<SearchMatches> {
+        let (table, text, visible) = Self::source(resource_type);
+        let viewer = match visibility {
+            SearchVisibility::All => None,
+            SearchVisibility::OwnedOrMember(user_id) => Some(user_id.to_string()),
+        };
+
+        // The window count sees every visible match, before the limit
+        let sql = format!(
+            r#"
+            WITH q AS (SELECT to_tsquery('english', $1) AS query)
+            SELECT m.id, m.name, m.rank, m.exact_name, m.total,
+                   ts_headline('english', m.body, q.query, $5) AS snippet
+            FROM (
+                SELECT r.id, r.name, {text} AS body,
+                       ts_rank_cd(r.search_vector, q.query) AS rank,
+                       lower(r.name) = lower($2) AS exact_name,
+                       COUNT(*) OVER () AS total
+                FROM {table} r, q
+                WHERE r.search_vector @@ q.query
+                  AND {visible}
+                ORDER BY exact_name DESC, rank DESC, r.id
+                LIMIT $4
+            ) m, q
+            ORDER BY m.exact_name DESC, m.rank DESC, m.id
+            "#,
+        );
+        let rows = sqlx::query(&sql)
+            .bind(query.to_tsquery())
+            .bind(query.exact_name())
+            .bind(viewer)
+            .bind(limit as i64)
+            .bind(Self::headline_options())
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut matches = SearchMatches::default();
+        for row in rows {
+            matches.total = row.try_get::<i64, _>("total")? as u64;
+            matches.hits.push(SearchHit {
+                resource_type,
+                id: row.try_get("id")?,
+                name: row.try_get("name")?,
+                snippet: render_snippet(&row.try_get::<String, _>("snippet")?),
+                rank: row.try_get("rank")?,
+                exact_name: row.try_get("exact_name")?,
+            });
+        }
+
+        Ok(matches)
+    }
+}
@@ -0,0 +1,190 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_system_summary_repo.rs
+
+use crate::domain::repositories::system_summary_repo::{
+    EventOutcomeCounts, GroupCounts, ReceiverVolume, SystemSummaryRepository,
+};
+use crate::domain::value_objects::EventReceiverId;
+use crate::error::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use tracing::instrument;
+
+/// PostgreSQL implementation of the SystemSummaryRepository trait
+///
+/// Event queries filter on `created_at` so they use `idx_events_created_at`
+/// instead of scanning the events table.
+pub struct PostgresSystemSummaryRepository {
+    pool: PgPool,
+}
+
+impl PostgresSystemSummaryRepository {
+    /// Creates a new PostgreSQL system summary repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn count(&self, sql: &str) -> Result<u64> {
+        let count: i64 = sqlx::query_scalar(sql).fetch_one(&self.pool).await?;
+        Ok(count as u64)
+    }
+}
+
+#[async_trait]
+impl SystemSummaryRepository for PostgresSystemSummaryRepository {
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers"
+        )
+    )]
+    async fn count_receivers(&self) -> Result<u64> {
+        self.count("SELECT COUNT(*) FROM event_receivers").await
+    }
+
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
+    async fn count_active_receivers(&self, since: DateTime<Utc>) -> Result<u64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(DISTINCT event_receiver_id) FROM events \
+             WHERE created_at >= $1 AND origin = 'user'",
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count as u64)
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receiver_groups"
+        )
+    )]
+    async fn count_groups(&self) -> Result<GroupCounts> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE enabled) AS enabled,
+                   COUNT(*) FILTER (WHERE NOT enabled) AS disabled
+            FROM event_receiver_groups
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(GroupCounts {
+            enabled: row.get::<i64, _>("enabled") as u64,
+            disabled: row.get::<i64, _>("disabled") as u64,
+        })
+    }
+
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
+    async fn count_events_since(&self, since: DateTime<Utc>) -> Result<EventOutcomeCounts> {
+        let row = sqlx::query(
+            r#"
+            SELECT COUNT(*) FILTER (WHERE success) AS successful,
+                   COUNT(*) FILTER (WHERE NOT success) AS failed
+            FROM events
+            WHERE created_at >= $1 AND origin = 'user'
+            "#,
+        )
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(EventOutcomeCounts {
+            successful: row.get::<i64, _>("successful") as u64,
+            failed: row.get::<i64, _>("failed") as u64,
+        })
+    }
+
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT events")
+    )]
+    async fn top_receivers_since(
+        &self,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ReceiverVolume>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT e.event_receiver_id, COALESCE(r.name, '') AS name, COUNT(*) AS event_count
+            FROM events e
+            LEFT JOIN event_receivers r ON r.id = e.event_receiver_id
+            WHERE e.created_at >= $1 AND e.origin = 'user'
+            GROUP BY e.event_receiver_id, r.name
+            ORDER BY event_count DESC, e.event_receiver_id
+            LIMIT $2
+            "#,
+        )
+        .bind(since)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let id: String = row.get("event_receiver_id");
+                Ok(ReceiverVolume {
+                    receiver_id: id.parse::<EventReceiverId>().map_err(|e| {
+                        crate::error::Error::BadRequest {
+                            message: format!("Invalid receiver ID: {}", e),
+                        }
+                    })?,
+                    name: row.get("name"),
+                    event_count: row.get::<i64, _>("event_count") as u64,
+                })
+            })
+            .collect()
+    }
+
+    #[instrument(
+        skip(self, ids),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT event_receivers",
+            count = ids.len()
+        )
+    )]
+    async fn receiver_names(
+        &self,
+        ids: &[EventReceiverId],
+    ) -> Result<HashMap<EventReceiverId, String>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        let rows = sqlx::query("SELECT id, name FROM event_receivers WHERE id = ANY($1)")
+            .bind(&ids)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| Ok((row.try_get("id")?, row.try_get("name")?)))
+            .collect()
+    }
+
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
+    async fn count_active_users(&self) -> Result<u64> {
+        self.count("SELECT COUNT(*) FROM users WHERE enabled").await
+    }
+}
@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_user_invitation_repo.rs
+
+use crate::domain::entities::user_invitation::UserInvitation;
+use crate::domain::repositories::user_invitation_repo::UserInvitationRepository;
+use crate::domain::value_objects::UserId;
+use crate::error::{DomainError, Result};
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the UserInvitationRepository trait
+pub struct PostgresUserInvitationRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserInvitationRepository {
+    /// Creates a new PostgreSQL user invitation repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserInvitationRepository for PostgresUserInvitationRepository {
+    #[instrument(
+        skip(self, invitations),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT user_invitations",
+            count = invitations.len()
+        )
+    )]
+    async fn save_invitations(&self, invitations: &[UserInvitation]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for invitation in invitations {
+            sqlx::query(
+                r#"
+                INSERT INTO user_invitations (user_id, token_hash, expires_at, created_by, created_at)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (user_id) DO UPDATE
+                SET token_hash = EXCLUDED.token_hash,
+                    expires_at = EXCLUDED.expires_at,
+                    created_by = EXCLUDED.created_by,
+                    created_at = EXCLUDED.created_at
+                "#,
+            )
+            .bind(invitation.user_id.to_string())
+            .bind(&invitation.token_hash)
+            .bind(invitation.expires_at)
+            .bind(&invitation.created_by)
+            .bind(invitation.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[instrument(
+        skip(self, token_hash),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "DELETE user_invitations"
+        )
+    )]
+    async fn take_invitation(&self, token_hash: &str) -> Result<Option<UserInvitation>> {
+        let row = sqlx::query(
+            r#"
+            DELETE FROM user_invitations
+            WHERE token_hash = $1
+            RETURNING user_id, token_hash, expires_at, created_by, created_at
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            let user_id: String = row.get("user_id");
+            let user_id = UserId::try_from(user_id)
+                .map_err(|e| DomainError::InvalidData(format!("Invalid user ID: {}", e)))?;
+            Ok(UserInvitation {
+                user_id,
+                token_hash: row.get("token_hash"),
+                expires_at: row.get("expires_at"),
+                created_by: row.get("created_by"),
+                created_at: row.get("created_at"),
+            })
+        })
+        .transpose()
+    }
+}
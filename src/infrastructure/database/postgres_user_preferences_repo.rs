@@ -0,0 +1,154 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_user_preferences_repo.rs
+
+use crate::domain::entities::user_preferences::UserPreferences;
+use crate::domain::repositories::user_preferences_repo::UserPreferencesRepository;
+use crate::domain::value_objects::UserId;
+use crate::error::{DomainError, Result};
+use crate::i18n::Message;
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+use ulid::Ulid;
+
+/// PostgreSQL implementation of the UserPreferencesRepository trait
+///
+/// Each preference is stored as its own `(user_id, key)` row so keys can be
+/// added to the allowlist without a schema change.
+pub struct PostgresUserPreferencesRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserPreferencesRepository {
+    /// Creates a new PostgreSQL user preferences repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Returns the canonical uppercase form of a user id given in any case
+///
+/// User ids arrive here as token subjects rather than parsed ids; one that
+/// is not a ULID at all is rejected instead of being stored.
+fn canonical_user_id(user_id: &str) -> Result<String> {
+    // The parser wraps values past the 128-bit range instead of rejecting
+    // them, so the round trip must also reproduce the input.
+    match user_id.parse::<Ulid>() {
+        Ok(ulid) if ulid.to_string() == user_id.to_ascii_uppercase() => {
+            Ok(UserId::from_ulid(ulid).to_string())
+        }
+        _ => Err(DomainError::ValidationError {
+            field: "user_id".to_string(),
+            message: Message::new("validation.id_not_ulid")
+                .with("field", "user_id")
+                .with("value", user_id),
+        }
+        .into()),
+    }
+}
+
+#[async_trait]
+impl UserPreferencesRepository for PostgresUserPreferencesRepository {
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT user_preferences"
+        )
+    )]
+    async fn find_preferences(&self, user_id: &str) -> Result<UserPreferences> {
+        let user_id = canonical_user_id(user_id)?;
+        let rows = sqlx::query(
+            r#"
+            SELECT key, value
+            FROM user_preferences
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(&user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(UserPreferences::from_stored(rows.into_iter().map(|row| {
+            let key: String = row.get("key");
+            let value: JsonValue = row.get("value");
+            (key, value)
+        })))
+    }
+
+    #[instrument(
+        skip(self, preferences),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "INSERT user_preferences"
+        )
+    )]
+    async fn save_preferences(&self, user_id: &str, preferences: &UserPreferences) -> Result<()> {
+        let user_id = canonical_user_id(user_id)?;
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM user_preferences WHERE user_id = $1")
+            .bind(&user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (key, value) in preferences.iter() {
+            sqlx::query(
+                r#"
+                INSERT INTO user_preferences (user_id, key, value, updated_at)
+                VALUES ($1, $2, $3, NOW())
+                "#,
+            )
+            .bind(&user_id)
+            .bind(key.as_str())
+            .bind(value)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_user_id_uppercases_lowercase_ulids() {
+        assert_eq!(
+            canonical_user_id("01jc2x3y4z5a6b7c8d9e0f1g2h").unwrap(),
+            "01JC2X3Y4Z5A6B7C8D9E0F1G2H"
+        );
+        assert_eq!(
+            canonical_user_id("01JC2X3Y4Z5A6B7C8D9E0F1G2H").unwrap(),
+            "01JC2X3Y4Z5A6B7C8D9E0F1G2H"
+        );
+    }
+
+    #[test]
+    fn test_canonical_user_id_rejects_malformed_ids() {
+        for user_id in [
+            "",
+            "not-a-ulid",
+            "01JC2X3Y4Z5A6B7C8D9E0F1G2",
+            "81JC2X3Y4Z5A6B7C8D9E0F1G2H",
+        ] {
+            let err = canonical_user_id(user_id).unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    crate::error::Error::Domain(DomainError::ValidationError { ref field, .. })
+                        if field == "user_id"
+                ),
+                "{user_id} gave {err:?}"
+            );
+        }
+    }
+}
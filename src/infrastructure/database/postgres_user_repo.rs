@@ -49,7 +49,7 @@ impl PostgresUserRepository {
             .try_get("id")
             .map_err(|e| DomainError::InvalidData(format!("Missing id: {}", e)))?;
 
-        let user_id = UserId::from_string(id_str)
+        let user_id = UserId::try_from(id_str)
             .map_err(|e| DomainError::InvalidData(format!("Invalid user ID: {}", e)))?;
 
         let username: String = row
@@ -129,6 +129,55 @@ impl PostgresUserRepository {
         })
     }
 
+    /// Insert `user` through `executor`, a pool or an open transaction
+    async fn insert_user<'e, E>(executor: E, user: &User) -> UserRepoResult<User>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let (provider_type, provider_subject) = match &user.auth_provider {
+            AuthProvider::Local => ("local", None),
+            AuthProvider::Keycloak { subject } => ("keycloak", Some(subject.clone())),
+            AuthProvider::ApiKey => ("api_key", None),
+        };
+
+        let roles = Self::roles_to_strings(&user.roles);
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO users (id, username, email, password_hash, auth_provider_type,
+                             auth_provider_subject, roles, enabled, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id, username, email, password_hash, auth_provider_type,
+                      auth_provider_subject, roles, enabled, created_at, updated_at
+            "#,
+        )
+        .bind(user.id.to_string())
+        .bind(&user.username)
+        .bind(&user.email)
+        .bind(&user.password_hash)
+        .bind(provider_type)
+        .bind(provider_subject)
+        .bind(&roles)
+        .bind(user.enabled)
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .fetch_one(executor)
+        .await
+        .map_err(|e| {
+            if let Some(db_err) = e.as_database_error() {
+                if db_err.is_unique_violation() {
+                    return DomainError::AlreadyExists {
+                        entity: "User".to_string(),
+                        identifier: user.username.clone(),
+                    };
+                }
+            }
+            DomainError::StorageError(format!("Database error: {}", e))
+        })?;
+
+        Self::row_to_user(&result)
+    }
+
     /// Convert roles to string array for database
     fn roles_to_strings(roles: &[Role]) -> Vec<String> {
         roles
@@ -145,7 +194,10 @@ impl PostgresUserRepository {
 
 #[async_trait]
 impl UserRepository for PostgresUserRepository {
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn find_by_id(&self, id: &UserId) -> UserRepoResult<Option<User>> {
         let result = sqlx::query(
             r#"
@@ -166,7 +218,10 @@ impl UserRepository for PostgresUserRepository {
         }
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn find_by_username(&self, username: &str) -> UserRepoResult<Option<User>> {
         let result = sqlx::query(
             r#"
@@ -187,7 +242,10 @@ impl UserRepository for PostgresUserRepository {
         }
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn find_by_email(&self, email: &str) -> UserRepoResult<Option<User>> {
         let result = sqlx::query(
             r#"
@@ -208,7 +266,10 @@ impl UserRepository for PostgresUserRepository {
         }
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn find_by_oidc_subject(&self, subject: &str) -> UserRepoResult<Option<User>> {
         let result = sqlx::query(
             r#"
@@ -229,54 +290,39 @@ impl UserRepository for PostgresUserRepository {
         }
     }
 
-    #[instrument(skip(self, user))]
+    #[instrument(
+        skip(self, user),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "INSERT users")
+    )]
     async fn create(&self, user: User) -> UserRepoResult<User> {
-        let (provider_type, provider_subject) = match &user.auth_provider {
-            AuthProvider::Local => ("local", None),
-            AuthProvider::Keycloak { subject } => ("keycloak", Some(subject.clone())),
-            AuthProvider::ApiKey => ("api_key", None),
-        };
-
-        let roles = Self::roles_to_strings(&user.roles);
+        let created = Self::insert_user(&self.pool, &user).await?;
+        debug!("Created user: {}", user.username);
+        Ok(created)
+    }
 
-        let result = sqlx::query(
-            r#"
-            INSERT INTO users (id, username, email, password_hash, auth_provider_type,
-                             auth_provider_subject, roles, enabled, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-            RETURNING id, username, email, password_hash, auth_provider_type,
-                      auth_provider_subject, roles, enabled, created_at, updated_at
-            "#,
-        )
-        .bind(user.id.to_string())
-        .bind(&user.username)
-        .bind(&user.email)
-        .bind(&user.password_hash)
-        .bind(provider_type)
-        .bind(provider_subject)
-        .bind(&roles)
-        .bind(user.enabled)
-        .bind(user.created_at)
-        .bind(user.updated_at)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(|e| {
-            if let Some(db_err) = e.as_database_error() {
-                if db_err.is_unique_violation() {
-                    return DomainError::AlreadyExists {
-                        entity: "User".to_string(),
-                        identifier: user.username.clone(),
-                    };
-                }
-            }
-            DomainError::StorageError(format!("Database error: {}", e))
-        })?;
+    #[instrument(
+        skip(self, users),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "INSERT users")
+    )]
+    async fn create_all(&self, users: Vec<User>) -> UserRepoResult<Vec<User>> {
+        let storage_error =
+            |e: sqlx::Error| DomainError::StorageError(format!("Database error: {}", e));
+        let mut tx = self.pool.begin().await.map_err(storage_error)?;
+
+        let mut created = Vec::with_capacity(users.len());
+        for user in &users {
+            created.push(Self::insert_user(&mut *tx, user).await?);
+        }
 
-        debug!("Created user: {}", user.username);
-        Self::row_to_user(&result)
+        tx.commit().await.map_err(storage_error)?;
+        debug!("Created {} users", created.len());
+        Ok(created)
     }
 
-    #[instrument(skip(self, user))]
+    #[instrument(
+        skip(self, user),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "UPDATE users")
+    )]
     async fn update(&self, user: User) -> UserRepoResult<User> {
         let (provider_type, provider_subject) = match &user.auth_provider {
             AuthProvider::Local => ("local", None),
@@ -322,7 +368,10 @@ impl UserRepository for PostgresUserRepository {
         }
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "DELETE users")
+    )]
     async fn delete(&self, id: &UserId) -> UserRepoResult<()> {
         let result = sqlx::query(
             r#"
@@ -346,7 +395,10 @@ impl UserRepository for PostgresUserRepository {
         Ok(())
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn username_exists(&self, username: &str) -> UserRepoResult<bool> {
         let result = sqlx::query(
             r#"
@@ -365,7 +417,10 @@ impl UserRepository for PostgresUserRepository {
         Ok(exists)
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn email_exists(&self, email: &str) -> UserRepoResult<bool> {
         let result = sqlx::query(
             r#"
@@ -384,7 +439,10 @@ impl UserRepository for PostgresUserRepository {
         Ok(exists)
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "INSERT users")
+    )]
     async fn create_or_update_oidc_user(
         &self,
         subject: String,
@@ -411,7 +469,10 @@ impl UserRepository for PostgresUserRepository {
         self.create(user).await
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn list(&self, limit: i64, offset: i64) -> UserRepoResult<Vec<User>> {
         let rows = sqlx::query(
             r#"
@@ -433,7 +494,10 @@ impl UserRepository for PostgresUserRepository {
         users
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn count(&self) -> UserRepoResult<i64> {
         let result = sqlx::query(
             r#"
@@ -452,7 +516,10 @@ impl UserRepository for PostgresUserRepository {
         Ok(count)
     }
 
-    #[instrument(skip(self))]
+    #[instrument(
+        skip(self),
+        fields(otel.kind = "client", db.system = "postgresql", db.statement = "SELECT users")
+    )]
     async fn find_by_provider(&self, provider: &AuthProvider) -> UserRepoResult<Vec<User>> {
         let provider_type = match provider {
             AuthProvider::Local => "local",
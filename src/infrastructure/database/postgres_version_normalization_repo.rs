@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/postgres_version_normalization_repo.rs
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::repositories::version_normalization_repo::{
+    StoredVersionCount, VersionNormalizationRepository, VersionedResource,
+};
+use crate::error::Result;
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use sqlx::{PgPool, Row};
+use tracing::instrument;
+
+/// PostgreSQL implementation of the VersionNormalizationRepository trait
+pub struct PostgresVersionNormalizationRepository {
+    pool: PgPool,
+}
+
+impl PostgresVersionNormalizationRepository {
+    /// Creates a new PostgreSQL version normalization repository
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the table holding `resource`
+    fn table(resource: VersionedResource) -> &'static str {
+        match resource {
+            VersionedResource::Event => "events",
+            VersionedResource::EventReceiver => "event_receivers",
+            VersionedResource::EventReceiverGroup => "event_receiver_groups",
+        }
+    }
+
+    /// Rewrites receiver versions one row at a time, since the fingerprint
+    /// of each receiver covers its name, type, and schema as well
+    async fn rewrite_receiver_version(&self, from: &str, to: &str) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, receiver_type, schema
+            FROM event_receivers
+            WHERE version = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(from)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for row in &rows {
+            let schema: JsonValue = row.get("schema");
+            let fingerprint = EventReceiver::generate_fingerprint(
+                row.get("name"),
+                row.get("receiver_type"),
+                to,
+                &schema,
+            )?;
+
+            sqlx::query(
+                r#"
+                UPDATE event_receivers
+                SET version = $2,
+                    fingerprint = $3,
+                    resource_version = resource_version + 1,
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(row.get::<String, _>("id"))
+            .bind(to)
+            .bind(fingerprint)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(rows.len() as u64)
+    }
+}
+
+#[async_trait]
+impl VersionNormalizationRepository for PostgresVersionNormalizationRepository {
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "SELECT version"
+        )
+    )]
+    async fn stored_versions(
+        &self,
+        resource: VersionedResource,
+    ) -> Result<Vec<StoredVersionCount>> {
+        let rows = sqlx::query(&format!(
+            "SELECT version, COUNT(*) AS row_count FROM {} GROUP BY version ORDER BY version",
+            Self::table(resource)
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| StoredVersionCount {
+                version: row.get("version"),
+                rows: row.get::<i64, _>("row_count") as u64,
+            })
+            .collect())
+    }
+
+    #[instrument(
+        skip(self),
+        fields(
+            otel.kind = "client",
+            db.system = "postgresql",
+            db.statement = "UPDATE version"
+        )
+    )]
+    async fn rewrite_version(
+        &self,
+        resource: VersionedResource,
+        from: &str,
+        to: &str,
+    ) -> Result<u64> {
+        let query = match resource {
+            VersionedResource::EventReceiver => {
+                return self.rewrite_receiver_version(from, to).await;
+            }
+            VersionedResource::EventReceiverGroup => {
+                r#"
+                UPDATE event_receiver_groups
+                SET version = $2,
+                    resource_version = resource_version + 1,
+                    updated_at = NOW()
+                WHERE version = $1
+                "#
+            }
+            VersionedResource::Event => {
+                r#"
+                UPDATE events
+                SET version = $2,
+                    resource_version = resource_version + 1
+                WHERE version = $1
+                "#
+            }
+        };
+
+        let result = sqlx::query(query)
+            .bind(from)
+            .bind(to)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
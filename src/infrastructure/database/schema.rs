@@ -0,0 +1,919 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/database/schema.rs
+
+//! Schema drift detection
+//!
+//! [`EXPECTED_SCHEMA`] lists the tables, columns, and indexes the Postgres
+//! repositories depend on. After migrations run, the server introspects the
+//! live schema and compares it with the manifest, so a hotfix applied by hand
+//! that drops an index or changes a column type is reported at startup
+//! instead of surfacing later as slow or failing queries. Keep the manifest
+//! in step with `migrations/` when adding a migration.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// What the server does when the live schema differs from the manifest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaCheckMode {
+    /// Refuse to start on any drift
+    Strict,
+    /// Log drift and start anyway
+    #[default]
+    Warn,
+    /// Skip the check
+    Off,
+}
+
+/// A column the repositories read or write
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectedColumn {
+    pub name: &'static str,
+    /// Type as reported by `information_schema.columns.data_type`
+    pub data_type: &'static str,
+}
+
+/// A table with the columns and indexes the repositories rely on
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectedTable {
+    pub name: &'static str,
+    pub columns: &'static [ExpectedColumn],
+    pub indexes: &'static [&'static str],
+}
+
+const fn column(name: &'static str, data_type: &'static str) -> ExpectedColumn {
+    ExpectedColumn { name, data_type }
+}
+
+const TEXT: &str = "text";
+const VARCHAR: &str = "character varying";
+const JSONB: &str = "jsonb";
+const BOOLEAN: &str = "boolean";
+const BIGINT: &str = "bigint";
+const INTEGER: &str = "integer";
+const DOUBLE: &str = "double precision";
+const DATE: &str = "date";
+const TIMESTAMPTZ: &str = "timestamp with time zone";
+const ARRAY: &str = "ARRAY";
+const TSVECTOR: &str = "tsvector";
+
+/// Schema produced by the migrations in `migrations/`
+pub const EXPECTED_SCHEMA: &[ExpectedTable] = &[
+    ExpectedTable {
+        name: "users",
+        columns: &[
+            column("id", TEXT),
+            column("username", VARCHAR),
+            column("email", VARCHAR),
+            column("password_hash", TEXT),
+            column("auth_provider_type", VARCHAR),
+            column("auth_provider_subject", TEXT),
+            column("enabled", BOOLEAN),
+            column("roles", ARRAY),
+            column("created_at", TIMESTAMPTZ),
+            column("updated_at", TIMESTAMPTZ),
+        ],
+        indexes: &[
+            "idx_users_username",
+            "idx_users_email",
+            "idx_users_keycloak_subject",
+            "idx_users_roles",
+        ],
+    },
+    ExpectedTable {
+        name: "user_roles",
+        columns: &[
+            column("user_id", TEXT),
+            column("role", VARCHAR),
+            column("created_at", TIMESTAMPTZ),
+        ],
+        indexes: &["idx_user_roles_role"],
+    },
+    ExpectedTable {
+        name: "api_keys",
+        columns: &[
+            column("id", TEXT),
+            column("user_id", TEXT),
+            column("key_hash", TEXT),
+            column("name", VARCHAR),
+            column("expires_at", TIMESTAMPTZ),
+            column("enabled", BOOLEAN),
+            column("created_at", TIMESTAMPTZ),
+            column("last_used_at", TIMESTAMPTZ),
+            column("previous_hash", TEXT),
+            column("previous_expires_at", TIMESTAMPTZ),
+            column("rotated_at", TIMESTAMPTZ),
+            column("last_used_with", TEXT),
+            column("last_expiry_warning_at", TIMESTAMPTZ),
+            column("receiver_id", TEXT),
+            column("group_id", TEXT),
+            column("last_used_ip", TEXT),
+        ],
+        indexes: &[
+            "idx_api_keys_user_id",
+            "idx_api_keys_key_hash",
+            "idx_api_keys_previous_hash",
+            "idx_api_keys_expires_at",
+            "idx_api_keys_group_id",
+            "idx_api_keys_receiver_id",
+        ],
+    },
+    ExpectedTable {
+        name: "events",
+        columns: &[
+            column("id", TEXT),
+            column("name", VARCHAR),
+            column("version", VARCHAR),
+            column("release", VARCHAR),
+            column("platform_id", VARCHAR),
+            column("package", VARCHAR),
+            column("description", TEXT),
+            column("payload", JSONB),
+            column("success", BOOLEAN),
+            column("event_receiver_id", VARCHAR),
+            column("created_at", TIMESTAMPTZ),
+            column("owner_id", VARCHAR),
+            column("resource_version", BIGINT),
+            column("origin", TEXT),
+            column("payload_hash", TEXT),
+            column("payload_summary", JSONB),
+            column("search_vector", TSVECTOR),
+        ],
+        indexes: &[
+            "idx_events_name",
+            "idx_events_version",
+            "idx_events_created_at",
+            "idx_events_payload",
+            "idx_events_name_version",
+            "idx_events_name_created_at",
+            "idx_events_owner_id",
+            "idx_events_owner_created",
+            "idx_events_origin_created_at",
+            "idx_events_feed",
+            "idx_events_payload_hash",
+            "idx_events_search",
+            "idx_events_receiver_release",
+            "idx_events_receiver_created",
+            "idx_events_success_created",
+            "idx_events_platform_created",
+        ],
+    },
+    ExpectedTable {
+        name: "event_payloads",
+        columns: &[
+            column("hash", TEXT),
+            column("body", TEXT),
+            column("size_bytes", BIGINT),
+            column("ref_count", BIGINT),
+            column("created_at", TIMESTAMPTZ),
+        ],
+        indexes: &["idx_event_payloads_unreferenced"],
+    },
+    ExpectedTable {
+        name: "event_receivers",
+        columns: &[
+            column("id", VARCHAR),
+            column("name", VARCHAR),
+            column("receiver_type", VARCHAR),
+            column("version", VARCHAR),
+            column("description", TEXT),
+            column("schema", JSONB),
+            column("fingerprint", VARCHAR),
+            column("owner_id", VARCHAR),
+            column("resource_version", BIGINT),
+            column("created_at", TIMESTAMPTZ),
+            column("updated_at", TIMESTAMPTZ),
+            column("sample_rate", DOUBLE),
+            column("allowed_event_names", JSONB),
+            column("auto_disable_policy", JSONB),
+            column("auto_disabled", JSONB),
+            column("auto_disable_window_start", TIMESTAMPTZ),
+            column("search_vector", TSVECTOR),
+            column("transform", JSONB),
+        ],
+        indexes: &[
+            "idx_event_receivers_name",
+            "idx_event_receivers_type",
+            "idx_event_receivers_fingerprint",
+            "idx_event_receivers_owner_id",
+            "idx_event_receivers_created_at",
+            "idx_event_receivers_type_version",
+            "idx_event_receivers_owner_created",
+            "idx_event_receivers_updated_id",
+            "idx_event_receivers_search",
+        ],
+    },
+    ExpectedTable {
+        name: "event_receiver_groups",
+        columns: &[
+            column("id", VARCHAR),
+            column("name", VARCHAR),
+            column("group_type", VARCHAR),
+            column("version", VARCHAR),
+            column("description", TEXT),
+            column("enabled", BOOLEAN),
+            column("owner_id", VARCHAR),
+            column("resource_version", BIGINT),
+            column("created_at", TIMESTAMPTZ),
+            column("updated_at", TIMESTAMPTZ),
+            column("default_schema", JSONB),
+            column("dedicated_topic", TEXT),
+            column("search_vector", TSVECTOR),
+        ],
+        indexes: &[
+            "idx_event_receiver_groups_name",
+            "idx_event_receiver_groups_type",
+            "idx_event_receiver_groups_enabled",
+            "idx_event_receiver_groups_owner_id",
+            "idx_event_receiver_groups_created_at",
+            "idx_event_receiver_groups_owner_created",
+            "idx_event_receiver_groups_enabled_created",
+            "idx_event_receiver_groups_updated_id",
+            "idx_event_receiver_groups_search",
+        ],
+    },
+    ExpectedTable {
+        name: "event_receiver_group_receivers",
+        columns: &[
+            column("group_id", VARCHAR),
+            column("receiver_id", VARCHAR),
+            column("added_at", TIMESTAMPTZ),
+            column("required", BOOLEAN),
+            column("is_default", BOOLEAN),
+        ],
+        indexes: &[
+            "idx_group_receivers_receiver_group",
+            "idx_group_receivers_added_at",
+            "idx_group_receivers_one_default",
+        ],
+    },
+    ExpectedTable {
+        name: "event_receiver_group_members",
+        columns: &[
+            column("group_id", VARCHAR),
+            column("user_id", VARCHAR),
+            column("added_by", VARCHAR),
+            column("added_at", TIMESTAMPTZ),
+            column("expires_at", TIMESTAMPTZ),
+        ],
+        indexes: &[
+            "idx_group_members_added_by",
+            "idx_group_members_added_at",
+            "idx_group_members_user_group",
+            "idx_group_members_expires_at",
+        ],
+    },
+    ExpectedTable {
+        name: "event_receiver_tombstones",
+        columns: &[column("id", VARCHAR), column("deleted_at", TIMESTAMPTZ)],
+        indexes: &["idx_event_receiver_tombstones_deleted_id"],
+    },
+    ExpectedTable {
+        name: "event_receiver_group_tombstones",
+        columns: &[column("id", VARCHAR), column("deleted_at", TIMESTAMPTZ)],
+        indexes: &["idx_event_receiver_group_tombstones_deleted_id"],
+    },
+    ExpectedTable {
+        name: "event_ingestion_meta",
+        columns: &[
+            column("event_id", TEXT),
+            column("principal_type", VARCHAR),
+            column("principal_id", VARCHAR),
+            column("source", VARCHAR),
+            column("client_ip", VARCHAR),
+            column("user_agent", VARCHAR),
+            column("recorded_at", TIMESTAMPTZ),
+            column("actor_id", VARCHAR),
+            column("pii_findings", JSONB),
+        ],
+        indexes: &[
+            "idx_event_ingestion_meta_principal",
+            "idx_event_ingestion_meta_source",
+            "idx_event_ingestion_meta_client_ip",
+            "idx_event_ingestion_meta_pii_findings",
+        ],
+    },
+    ExpectedTable {
+        name: "user_preferences",
+        columns: &[
+            column("user_id", TEXT),
+            column("key", VARCHAR),
+            column("value", JSONB),
+            column("updated_at", TIMESTAMPTZ),
+        ],
+        indexes: &[],
+    },
+    ExpectedTable {
+        name: "event_archive_index",
+        columns: &[
+            column("event_id", TEXT),
+            column("event_receiver_id", VARCHAR),
+            column("segment", TEXT),
+            column("event_created_at", TIMESTAMPTZ),
+            column("archived_at", TIMESTAMPTZ),
+        ],
+        indexes: &["idx_event_archive_index_segment"],
+    },
+    ExpectedTable {
+        name: "feature_flags",
+        columns: &[
+            column("name", VARCHAR),
+            column("enabled", BOOLEAN),
+            column("role_overrides", JSONB),
+            column("updated_by", TEXT),
+            column("updated_at", TIMESTAMPTZ),
+        ],
+        indexes: &[],
+    },
+    ExpectedTable {
+        name: "event_sampling_counters",
+        columns: &[
+            column("event_receiver_id", VARCHAR),
+            column("bucket_start", TIMESTAMPTZ),
+            column("total_seen", BIGINT),
+            column("total_stored", BIGINT),
+        ],
+        indexes: &[],
+    },
+    ExpectedTable {
+        name: "event_counts_hourly",
+        columns: &[
+            column("event_receiver_id", VARCHAR),
+            column("bucket_start", TIMESTAMPTZ),
+            column("severity", TEXT),
+            column("success", BOOLEAN),
+            column("count", BIGINT),
+        ],
+        indexes: &["idx_event_counts_hourly_bucket_start"],
+    },
+    ExpectedTable {
+        name: "event_receiver_activity",
+        columns: &[
+            column("event_receiver_id", VARCHAR),
+            column("last_event_at", TIMESTAMPTZ),
+            column("last_principal", TEXT),
+        ],
+        indexes: &["idx_event_receiver_activity_last_event_at"],
+    },
+    ExpectedTable {
+        name: "event_receiver_daily_principals",
+        columns: &[
+            column("event_receiver_id", VARCHAR),
+            column("day", DATE),
+            column("principal", TEXT),
+        ],
+        indexes: &["idx_event_receiver_daily_principals_day"],
+    },
+    ExpectedTable {
+        name: "event_receiver_heartbeats",
+        columns: &[
+            column("event_receiver_id", VARCHAR),
+            column("last_heartbeat_at", TIMESTAMPTZ),
+            column("status", JSONB),
+        ],
+        indexes: &["idx_event_receiver_heartbeats_last_heartbeat_at"],
+    },
+    ExpectedTable {
+        name: "event_publish_outbox",
+        columns: &[
+            column("event_id", TEXT),
+            column("attempts", INTEGER),
+            column("last_error", TEXT),
+            column("next_attempt_at", TIMESTAMPTZ),
+            column("dead_lettered_at", TIMESTAMPTZ),
+            column("created_at", TIMESTAMPTZ),
+        ],
+        indexes: &["idx_event_publish_outbox_due"],
+    },
+    ExpectedTable {
+        name: "event_group_topic_outbox",
+        columns: &[
+            column("event_id", TEXT),
+            column("topic", TEXT),
+            column("attempts", INTEGER),
+            column("last_error", TEXT),
+            column("next_attempt_at", TIMESTAMPTZ),
+            column("dead_lettered_at", TIMESTAMPTZ),
+            column("created_at", TIMESTAMPTZ),
+        ],
+        indexes: &["idx_event_group_topic_outbox_due"],
+    },
+    ExpectedTable {
+        name: "delivery_failures",
+        columns: &[
+            column("mechanism", TEXT),
+            column("target", TEXT),
+            column("event_id", TEXT),
+            column("receiver_id", TEXT),
+            column("error_class", TEXT),
+            column("last_error", TEXT),
+            column("attempts", INTEGER),
+            column("failed_at", TIMESTAMPTZ),
+        ],
+        indexes: &[
+            "idx_delivery_failures_failed_at",
+            "idx_delivery_failures_receiver_failed_at",
+        ],
+    },
+    ExpectedTable {
+        name: "id_quarantine",
+        columns: &[
+            column("id", BIGINT),
+            column("table_name", TEXT),
+            column("column_name", TEXT),
+            column("value", TEXT),
+            column("reason", TEXT),
+            column("row_data", JSONB),
+            column("detected_at", TIMESTAMPTZ),
+        ],
+        indexes: &["idx_id_quarantine_table_column"],
+    },
+    ExpectedTable {
+        name: "audit_records",
+        columns: &[
+            column("id", BIGINT),
+            column("chain_day", DATE),
+            column("position", BIGINT),
+            column("recorded_at", TIMESTAMPTZ),
+            column("content", TEXT),
+            column("prev_hash", TEXT),
+            column("hash", TEXT),
+        ],
+        indexes: &[
+            "audit_records_chain_position",
+            "idx_audit_records_recorded_at",
+        ],
+    },
+    ExpectedTable {
+        name: "audit_chain_anchors",
+        columns: &[
+            column("chain_day", DATE),
+            column("position", BIGINT),
+            column("hash", TEXT),
+            column("anchored_at", TIMESTAMPTZ),
+        ],
+        indexes: &[],
+    },
+    ExpectedTable {
+        name: "event_attachments",
+        columns: &[
+            column("id", TEXT),
+            column("event_id", TEXT),
+            column("event_receiver_id", VARCHAR),
+            column("filename", TEXT),
+            column("content_type", TEXT),
+            column("size_bytes", BIGINT),
+            column("sha256", TEXT),
+            column("storage_key", TEXT),
+            column("uploaded_by", VARCHAR),
+            column("created_at", TIMESTAMPTZ),
+        ],
+        indexes: &[
+            "idx_event_attachments_event_id",
+            "idx_event_attachments_event_receiver_id",
+        ],
+    },
+    ExpectedTable {
+        name: "event_attestations",
+        columns: &[
+            column("event_id", TEXT),
+            column("event_receiver_id", VARCHAR),
+            column("format", VARCHAR),
+            column("spec_version", VARCHAR),
+            column("subject_digests", ARRAY),
+            column("builder_id", TEXT),
+            column("component_count", BIGINT),
+            column("component_name", TEXT),
+            column("component_version", TEXT),
+            column("created_at", TIMESTAMPTZ),
+        ],
+        indexes: &[
+            "idx_event_attestations_subject_digests",
+            "idx_event_attestations_builder_id",
+            "idx_event_attestations_created_at",
+        ],
+    },
+    ExpectedTable {
+        name: "event_receiver_history",
+        columns: &[
+            column("id", BIGINT),
+            column("event_receiver_id", VARCHAR),
+            column("recorded_at", TIMESTAMPTZ),
+            column("state", JSONB),
+        ],
+        indexes: &["idx_event_receiver_history_receiver_recorded"],
+    },
+    ExpectedTable {
+        name: "event_receiver_group_history",
+        columns: &[
+            column("id", BIGINT),
+            column("event_receiver_group_id", VARCHAR),
+            column("recorded_at", TIMESTAMPTZ),
+            column("state", JSONB),
+        ],
+        indexes: &["idx_event_receiver_group_history_group_recorded"],
+    },
+    ExpectedTable {
+        name: "maintenance_mode",
+        columns: &[
+            column("id", INTEGER),
+            column("enabled", BOOLEAN),
+            column("message", TEXT),
+            column("ends_at", TIMESTAMPTZ),
+            column("updated_by", TEXT),
+            column("updated_at", TIMESTAMPTZ),
+        ],
+        indexes: &[],
+    },
+    ExpectedTable {
+        name: "graphql_persisted_queries",
+        columns: &[
+            column("hash", VARCHAR),
+            column("query", TEXT),
+            column("registered", BOOLEAN),
+            column("registered_by", TEXT),
+            column("created_at", TIMESTAMPTZ),
+        ],
+        indexes: &["idx_graphql_persisted_queries_registered"],
+    },
+    ExpectedTable {
+        name: "group_release_completions",
+        columns: &[
+            column("group_id", VARCHAR),
+            column("release", VARCHAR),
+            column("completed_at", TIMESTAMPTZ),
+        ],
+        indexes: &[],
+    },
+    ExpectedTable {
+        name: "api_key_daily_usage",
+        columns: &[
+            column("key_id", TEXT),
+            column("day", DATE),
+            column("successes", BIGINT),
+            column("auth_failures", BIGINT),
+            column("authz_denials", BIGINT),
+            column("rate_limited", BIGINT),
+        ],
+        indexes: &[],
+    },
+    ExpectedTable {
+        name: "api_key_failures",
+        columns: &[
+            column("id", BIGINT),
+            column("key_id", TEXT),
+            column("occurred_at", TIMESTAMPTZ),
+            column("reason", TEXT),
+            column("route", TEXT),
+        ],
+        indexes: &["idx_api_key_failures_key_occurred"],
+    },
+    ExpectedTable {
+        name: "user_invitations",
+        columns: &[
+            column("user_id", TEXT),
+            column("token_hash", TEXT),
+            column("expires_at", TIMESTAMPTZ),
+            column("created_by", TEXT),
+            column("created_at", TIMESTAMPTZ),
+        ],
+        indexes: &["user_invitations_token_hash_key"],
+    },
+];
+
+/// Tables, columns, and indexes found in the database
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiveSchema {
+    /// Column data types keyed by table, then column
+    pub columns: BTreeMap<String, BTreeMap<String, String>>,
+    /// Index names keyed by table
+    pub indexes: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl LiveSchema {
+    /// Reads the current schema of the connected database
+    ///
+    /// Columns come from `information_schema.columns`; indexes are not part
+    /// of the information schema and are read from `pg_indexes`.
+    pub async fn introspect(pool: &PgPool) -> Result<Self, sqlx::Error> {
+        let columns: Vec<(String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT table_name::TEXT, column_name::TEXT, data_type::TEXT
+            FROM information_schema.columns
+            WHERE table_schema = current_schema()
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let indexes: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT tablename::TEXT, indexname::TEXT
+            FROM pg_indexes
+            WHERE schemaname = current_schema()
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut schema = Self::default();
+        for (table, column, data_type) in columns {
+            schema.add_column(&table, &column, &data_type);
+        }
+        for (table, index) in indexes {
+            schema.add_index(&table, &index);
+        }
+        Ok(schema)
+    }
+
+    /// Records a column and its `information_schema` data type
+    pub fn add_column(&mut self, table: &str, column: &str, data_type: &str) {
+        self.columns
+            .entry(table.to_string())
+            .or_default()
+            .insert(column.to_string(), data_type.to_string());
+    }
+
+    /// Records an index on a table
+    pub fn add_index(&mut self, table: &str, index: &str) {
+        self.indexes
+            .entry(table.to_string())
+            .or_default()
+            .insert(index.to_string());
+    }
+
+    /// Lists every difference from `expected`, in manifest order
+    ///
+    /// Extra tables, columns, and indexes are not drift; only what the
+    /// repositories need is checked.
+    pub fn diff(&self, expected: &[ExpectedTable]) -> Vec<SchemaDrift> {
+        let mut drift = Vec::new();
+
+        for table in expected {
+            let Some(columns) = self.columns.get(table.name) else {
+                drift.push(SchemaDrift::MissingTable {
+                    table: table.name.to_string(),
+                });
+                continue;
+            };
+
+            for column in table.columns {
+                match columns.get(column.name) {
+                    None => drift.push(SchemaDrift::MissingColumn {
+                        table: table.name.to_string(),
+                        column: column.name.to_string(),
+                    }),
+                    Some(actual) if actual != column.data_type => {
+                        drift.push(SchemaDrift::WrongType {
+                            table: table.name.to_string(),
+                            column: column.name.to_string(),
+                            expected: column.data_type.to_string(),
+                            actual: actual.clone(),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            let indexes = self.indexes.get(table.name);
+            for index in table.indexes {
+                if !indexes.is_some_and(|indexes| indexes.contains(*index)) {
+                    drift.push(SchemaDrift::MissingIndex {
+                        table: table.name.to_string(),
+                        index: index.to_string(),
+                    });
+                }
+            }
+        }
+
+        drift
+    }
+}
+
+/// One difference between the live schema and the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SchemaDrift {
+    MissingTable {
+        table: String,
+    },
+    MissingColumn {
+        table: String,
+        column: String,
+    },
+    WrongType {
+        table: String,
+        column: String,
+        expected: String,
+        actual: String,
+    },
+    MissingIndex {
+        table: String,
+        index: String,
+    },
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaDrift::MissingTable { table } => write!(f, "table {} is missing", table),
+            SchemaDrift::MissingColumn { table, column } => {
+                write!(f, "column {}.{} is missing", table, column)
+            }
+            SchemaDrift::WrongType {
+                table,
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "column {}.{} has type {}, expected {}",
+                table, column, actual, expected
+            ),
+            SchemaDrift::MissingIndex { table, index } => {
+                write!(f, "index {} on {} is missing", index, table)
+            }
+        }
+    }
+}
+
+/// Outcome of the startup schema check, reported by `/health/ready`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaCheckReport {
+    pub mode: SchemaCheckMode,
+    pub drift: Vec<SchemaDrift>,
+}
+
+impl SchemaCheckReport {
+    /// Report for a check that was turned off
+    pub fn skipped() -> Self {
+        Self {
+            mode: SchemaCheckMode::Off,
+            drift: Vec::new(),
+        }
+    }
+
+    /// Returns true when the schema matched the manifest
+    pub fn is_clean(&self) -> bool {
+        self.drift.is_empty()
+    }
+}
+
+/// Drift found while `database.schema_check` is `strict`
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error(
+    "database schema differs from what this build expects: {}. Add a migration that restores \
+     the expected shape, or set database.schema_check to warn to start anyway",
+    .drift.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+)]
+pub struct SchemaDriftError {
+    pub drift: Vec<SchemaDrift>,
+}
+
+/// Compares the live schema with `expected` and applies `mode`
+///
+/// In warn mode every difference is logged and the report is returned so
+/// startup can continue.
+///
+/// # Errors
+///
+/// Returns [`SchemaDriftError`] when `mode` is strict and any drift is found.
+pub fn check_schema(
+    mode: SchemaCheckMode,
+    live: &LiveSchema,
+    expected: &[ExpectedTable],
+) -> Result<SchemaCheckReport, SchemaDriftError> {
+    if mode == SchemaCheckMode::Off {
+        return Ok(SchemaCheckReport::skipped());
+    }
+
+    let drift = live.diff(expected);
+    if drift.is_empty() {
+        info!(tables = expected.len(), "Database schema matches manifest");
+    } else if mode == SchemaCheckMode::Strict {
+        return Err(SchemaDriftError { drift });
+    } else {
+        for problem in &drift {
+            warn!(drift = %problem, "Database schema drift detected");
+        }
+        warn!(
+            count = drift.len(),
+            "Starting with schema drift because database.schema_check is warn"
+        );
+    }
+
+    Ok(SchemaCheckReport { mode, drift })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrated_schema() -> LiveSchema {
+        let mut live = LiveSchema::default();
+        for table in EXPECTED_SCHEMA {
+            for column in table.columns {
+                live.add_column(table.name, column.name, column.data_type);
+            }
+            for index in table.indexes {
+                live.add_index(table.name, index);
+            }
+            live.add_index(table.name, &format!("{}_pkey", table.name));
+        }
+        live
+    }
+
+    #[test]
+    fn test_migrated_schema_has_no_drift() {
+        let mut live = migrated_schema();
+        live.add_column("events", "added_by_hotfix", "text");
+
+        assert!(live.diff(EXPECTED_SCHEMA).is_empty());
+        let report = check_schema(SchemaCheckMode::Strict, &live, EXPECTED_SCHEMA).unwrap();
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn test_altered_schema_reports_drift() {
+        let mut live = migrated_schema();
+        live.columns.get_mut("events").unwrap().remove("origin");
+        live.add_column("api_keys", "rotated_at", "timestamp without time zone");
+        live.indexes
+            .get_mut("events")
+            .unwrap()
+            .remove("idx_events_feed");
+        live.columns.remove("feature_flags");
+
+        assert_eq!(
+            live.diff(EXPECTED_SCHEMA),
+            vec![
+                SchemaDrift::WrongType {
+                    table: "api_keys".to_string(),
+                    column: "rotated_at".to_string(),
+                    expected: "timestamp with time zone".to_string(),
+                    actual: "timestamp without time zone".to_string(),
+                },
+                SchemaDrift::MissingColumn {
+                    table: "events".to_string(),
+                    column: "origin".to_string(),
+                },
+                SchemaDrift::MissingIndex {
+                    table: "events".to_string(),
+                    index: "idx_events_feed".to_string(),
+                },
+                SchemaDrift::MissingTable {
+                    table: "feature_flags".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_warn_mode_starts_with_drift() {
+        let mut live = migrated_schema();
+        live.columns.get_mut("events").unwrap().remove("origin");
+
+        let report = check_schema(SchemaCheckMode::Warn, &live, EXPECTED_SCHEMA).unwrap();
+        assert_eq!(report.mode, SchemaCheckMode::Warn);
+        assert_eq!(report.drift.len(), 1);
+
+        let details = serde_json::to_value(&report).unwrap();
+        assert_eq!(details["mode"], "warn");
+        assert_eq!(details["drift"][0]["kind"], "missing_column");
+        assert_eq!(details["drift"][0]["column"], "origin");
+    }
+
+    #[test]
+    fn test_strict_mode_refuses_drift() {
+        let mut live = migrated_schema();
+        live.indexes
+            .get_mut("api_keys")
+            .unwrap()
+            .remove("idx_api_keys_key_hash");
+
+        let err = check_schema(SchemaCheckMode::Strict, &live, EXPECTED_SCHEMA).unwrap_err();
+        assert_eq!(err.drift.len(), 1);
+        let message = err.to_string();
+        assert!(message.contains("index idx_api_keys_key_hash on api_keys is missing"));
+        assert!(message.contains("database.schema_check"));
+    }
+
+    #[test]
+    fn test_off_mode_skips_check() {
+        let report = check_schema(
+            SchemaCheckMode::Off,
+            &LiveSchema::default(),
+            EXPECTED_SCHEMA,
+        )
+        .unwrap();
+        assert_eq!(report, SchemaCheckReport::skipped());
+    }
+}
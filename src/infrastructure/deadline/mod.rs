@@ -0,0 +1,239 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/deadline/mod.rs
+
+//! Request deadlines
+//!
+//! The deadline middleware runs each request inside [`with_deadline`].
+//! Repository, Kafka, and outbound HTTP calls made while handling it read
+//! the deadline with [`current_deadline`], so a request its client has
+//! given up on stops at the next expensive operation instead of running to
+//! completion. Work outside a request, such as background jobs and domain
+//! event subscribers, has no deadline and is not limited.
+//!
+//! [`guard`] refuses to start an operation once the deadline has passed and
+//! cancels one still running when it does. Cancelling drops the operation's
+//! future: a database query is abandoned and its connection discarded, and
+//! a Kafka message already handed to the producer may still be delivered.
+
+pub mod repositories;
+
+pub use repositories::{
+    DeadlineEventReceiverGroupRepository, DeadlineEventReceiverRepository, DeadlineEventRepository,
+};
+
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::time::Instant;
+
+use crate::error::{Error as XzeprError, InfrastructureError};
+
+tokio::task_local! {
+    static DEADLINE: RequestDeadline;
+}
+
+/// Where a request's deadline came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadlineSource {
+    /// The client's `X-Request-Deadline`, earlier than the route budget
+    Client,
+    /// The route's own budget
+    Server,
+}
+
+impl DeadlineSource {
+    /// Returns the `reason` label of `xzepr_request_timeouts_total`
+    pub fn timeout_reason(self) -> &'static str {
+        match self {
+            DeadlineSource::Client => "deadline_exceeded",
+            DeadlineSource::Server => "server_timeout",
+        }
+    }
+}
+
+/// The instant by which a request must be answered
+///
+/// Clones share whether the deadline cut an operation short, so the
+/// middleware learns about aborts made deep inside the handler.
+#[derive(Debug, Clone)]
+pub struct RequestDeadline {
+    at: Instant,
+    source: DeadlineSource,
+    exceeded: Arc<OnceLock<&'static str>>,
+}
+
+impl RequestDeadline {
+    /// Creates a deadline at `at`
+    pub fn new(at: Instant, source: DeadlineSource) -> Self {
+        Self {
+            at,
+            source,
+            exceeded: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Creates a deadline `budget` from now
+    pub fn after(budget: Duration, source: DeadlineSource) -> Self {
+        Self::new(Instant::now() + budget, source)
+    }
+
+    pub fn at(&self) -> Instant {
+        self.at
+    }
+
+    pub fn source(&self) -> DeadlineSource {
+        self.source
+    }
+
+    /// Returns the time left, zero once the deadline has passed
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_duration_since(Instant::now())
+    }
+
+    /// Returns true once the deadline has passed
+    pub fn has_passed(&self) -> bool {
+        Instant::now() >= self.at
+    }
+
+    /// Returns the first operation the deadline cut short, if any
+    pub fn exceeded_during(&self) -> Option<&'static str> {
+        self.exceeded.get().copied()
+    }
+
+    fn record_exceeded(&self, operation: &'static str) -> DeadlineExceeded {
+        let _ = self.exceeded.set(operation);
+        DeadlineExceeded { operation }
+    }
+}
+
+/// An operation was refused or cancelled at the request's deadline
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Request deadline exceeded during {operation}")]
+pub struct DeadlineExceeded {
+    pub operation: &'static str,
+}
+
+impl From<DeadlineExceeded> for XzeprError {
+    fn from(err: DeadlineExceeded) -> Self {
+        XzeprError::Infrastructure(InfrastructureError::DeadlineExceeded {
+            operation: err.operation.to_string(),
+        })
+    }
+}
+
+/// Runs `future` with `deadline` visible to every operation it starts
+///
+/// Without a deadline, `future` runs unchanged.
+pub async fn with_deadline<F: Future>(deadline: Option<RequestDeadline>, future: F) -> F::Output {
+    match deadline {
+        Some(deadline) => DEADLINE.scope(deadline, future).await,
+        None => future.await,
+    }
+}
+
+/// Returns the deadline set by the enclosing [`with_deadline`], if any
+pub fn current_deadline() -> Option<RequestDeadline> {
+    DEADLINE.try_with(Clone::clone).ok()
+}
+
+/// Runs `operation` unless the current deadline has passed, cancelling it
+/// when the deadline passes first
+///
+/// Without a deadline, `future` runs to completion.
+///
+/// # Errors
+///
+/// Returns [`DeadlineExceeded`] naming `operation` when the deadline
+/// passed before or while it ran.
+pub async fn guard<F: Future>(
+    operation: &'static str,
+    future: F,
+) -> Result<F::Output, DeadlineExceeded> {
+    let Some(deadline) = current_deadline() else {
+        return Ok(future.await);
+    };
+    if deadline.has_passed() {
+        return Err(deadline.record_exceeded(operation));
+    }
+    tokio::time::timeout_at(deadline.at, future)
+        .await
+        .map_err(|_| deadline.record_exceeded(operation))
+}
+
+/// Returns `timeout`, shortened to the time left before the current
+/// deadline
+///
+/// Outbound HTTP calls pass this as their request timeout.
+pub fn capped_timeout(timeout: Duration) -> Duration {
+    current_deadline().map_or(timeout, |deadline| timeout.min(deadline.remaining()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_guard_refuses_to_start_after_the_deadline() {
+        let deadline = RequestDeadline::new(Instant::now(), DeadlineSource::Client);
+        let mut started = false;
+
+        let result = with_deadline(
+            Some(deadline.clone()),
+            guard("test.operation", async { started = true }),
+        )
+        .await;
+
+        assert_eq!(
+            result,
+            Err(DeadlineExceeded {
+                operation: "test.operation"
+            })
+        );
+        assert!(!started);
+        assert_eq!(deadline.exceeded_during(), Some("test.operation"));
+    }
+
+    #[tokio::test]
+    async fn test_guard_cancels_at_the_deadline() {
+        let deadline = RequestDeadline::after(Duration::from_millis(100), DeadlineSource::Server);
+        let started = Instant::now();
+
+        let result = with_deadline(
+            Some(deadline.clone()),
+            guard("test.sleep", tokio::time::sleep(Duration::from_secs(10))),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(deadline.exceeded_during(), Some("test.sleep"));
+    }
+
+    #[tokio::test]
+    async fn test_without_a_deadline_nothing_is_limited() {
+        assert!(current_deadline().is_none());
+        assert_eq!(guard("test.operation", async { 7 }).await, Ok(7));
+        assert_eq!(
+            capped_timeout(Duration::from_secs(5)),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capped_timeout_uses_the_time_left() {
+        let deadline = RequestDeadline::after(Duration::from_secs(2), DeadlineSource::Client);
+
+        let timeout = with_deadline(Some(deadline), async {
+            capped_timeout(Duration::from_secs(5))
+        })
+        .await;
+
+        assert!(timeout <= Duration::from_secs(2));
+        assert!(timeout > Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,721 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/deadline/repositories.rs
+
+//! Repositories that stop at the request deadline
+//!
+//! Each wraps a repository and runs every call through [`guard`], so a call
+//! made after the request's deadline never reaches the database and one
+//! still running at the deadline is cancelled. The operation is named
+//! after the repository and method, such as `events.find_by_criteria`.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::entities::event::Event;
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::entities::event_receiver_group::EventReceiverGroup;
+use crate::domain::entities::release_completeness::ReleaseOutcome;
+use crate::domain::repositories::event_receiver_group_repo::{
+    EventReceiverGroupRepository, ExpiredMembership, FindEventReceiverGroupCriteria,
+    GroupMembership,
+};
+use crate::domain::repositories::event_receiver_repo::{
+    EventReceiverRepository, FindEventReceiverCriteria,
+};
+use crate::domain::repositories::event_repo::{EventRepository, FindEventCriteria};
+use crate::domain::value_objects::{EventId, EventReceiverGroupId, EventReceiverId, UserId};
+use crate::error::Result;
+use crate::infrastructure::deadline::guard;
+
+/// Event repository that stops at the request deadline
+pub struct DeadlineEventRepository {
+    inner: Arc<dyn EventRepository>,
+}
+
+impl DeadlineEventRepository {
+    pub fn new(inner: Arc<dyn EventRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+/// Receiver repository that stops at the request deadline
+pub struct DeadlineEventReceiverRepository {
+    inner: Arc<dyn EventReceiverRepository>,
+}
+
+impl DeadlineEventReceiverRepository {
+    pub fn new(inner: Arc<dyn EventReceiverRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+/// Receiver group repository that stops at the request deadline
+pub struct DeadlineEventReceiverGroupRepository {
+    inner: Arc<dyn EventReceiverGroupRepository>,
+}
+
+impl DeadlineEventReceiverGroupRepository {
+    pub fn new(inner: Arc<dyn EventReceiverGroupRepository>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl EventRepository for DeadlineEventRepository {
+    async fn save(&self, event: &Event) -> Result<()> {
+        guard("events.save", self.inner.save(event)).await?
+    }
+
+    async fn find_by_id(&self, id: EventId) -> Result<Option<Event>> {
+        guard("events.find_by_id", self.inner.find_by_id(id)).await?
+    }
+
+    async fn find_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<Vec<Event>> {
+        guard(
+            "events.find_by_receiver_id",
+            self.inner.find_by_receiver_id(receiver_id),
+        )
+        .await?
+    }
+
+    async fn find_by_success(&self, success: bool) -> Result<Vec<Event>> {
+        guard(
+            "events.find_by_success",
+            self.inner.find_by_success(success),
+        )
+        .await?
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<Event>> {
+        guard("events.find_by_name", self.inner.find_by_name(name)).await?
+    }
+
+    async fn find_by_platform_id(&self, platform_id: &str) -> Result<Vec<Event>> {
+        guard(
+            "events.find_by_platform_id",
+            self.inner.find_by_platform_id(platform_id),
+        )
+        .await?
+    }
+
+    async fn find_by_package(&self, package: &str) -> Result<Vec<Event>> {
+        guard(
+            "events.find_by_package",
+            self.inner.find_by_package(package),
+        )
+        .await?
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<Event>> {
+        guard("events.list", self.inner.list(limit, offset)).await?
+    }
+
+    async fn count(&self) -> Result<usize> {
+        guard("events.count", self.inner.count()).await?
+    }
+
+    async fn count_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
+        guard(
+            "events.count_by_receiver_id",
+            self.inner.count_by_receiver_id(receiver_id),
+        )
+        .await?
+    }
+
+    async fn count_successful_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
+        guard(
+            "events.count_successful_by_receiver_id",
+            self.inner.count_successful_by_receiver_id(receiver_id),
+        )
+        .await?
+    }
+
+    async fn count_by_receiver_since(
+        &self,
+        receiver_id: EventReceiverId,
+        since: DateTime<Utc>,
+    ) -> Result<usize> {
+        guard(
+            "events.count_by_receiver_since",
+            self.inner.count_by_receiver_since(receiver_id, since),
+        )
+        .await?
+    }
+
+    async fn find_release_outcomes(
+        &self,
+        receiver_ids: &[EventReceiverId],
+        release: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<HashMap<EventReceiverId, ReleaseOutcome>> {
+        guard(
+            "events.find_release_outcomes",
+            self.inner
+                .find_release_outcomes(receiver_ids, release, since),
+        )
+        .await?
+    }
+
+    async fn delete(&self, id: EventId) -> Result<()> {
+        guard("events.delete", self.inner.delete(id)).await?
+    }
+
+    async fn find_latest_by_receiver_id(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<Event>> {
+        guard(
+            "events.find_latest_by_receiver_id",
+            self.inner.find_latest_by_receiver_id(receiver_id),
+        )
+        .await?
+    }
+
+    async fn find_latest_successful_by_receiver_id(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<Event>> {
+        guard(
+            "events.find_latest_successful_by_receiver_id",
+            self.inner
+                .find_latest_successful_by_receiver_id(receiver_id),
+        )
+        .await?
+    }
+
+    async fn find_by_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Event>> {
+        guard(
+            "events.find_by_time_range",
+            self.inner.find_by_time_range(start, end),
+        )
+        .await?
+    }
+
+    async fn count_by_time_range(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<usize> {
+        guard(
+            "events.count_by_time_range",
+            self.inner.count_by_time_range(start, end),
+        )
+        .await?
+    }
+
+    async fn find_by_criteria(&self, criteria: FindEventCriteria) -> Result<Vec<Event>> {
+        guard(
+            "events.find_by_criteria",
+            self.inner.find_by_criteria(criteria),
+        )
+        .await?
+    }
+
+    async fn count_by_criteria(&self, criteria: FindEventCriteria) -> Result<usize> {
+        guard(
+            "events.count_by_criteria",
+            self.inner.count_by_criteria(criteria),
+        )
+        .await?
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<Event>> {
+        guard("events.find_by_owner", self.inner.find_by_owner(owner_id)).await?
+    }
+
+    async fn find_by_owner_paginated(
+        &self,
+        owner_id: UserId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Event>> {
+        guard(
+            "events.find_by_owner_paginated",
+            self.inner.find_by_owner_paginated(owner_id, limit, offset),
+        )
+        .await?
+    }
+
+    async fn is_owner(&self, event_id: EventId, user_id: UserId) -> Result<bool> {
+        guard("events.is_owner", self.inner.is_owner(event_id, user_id)).await?
+    }
+
+    async fn get_resource_version(&self, event_id: EventId) -> Result<Option<i64>> {
+        guard(
+            "events.get_resource_version",
+            self.inner.get_resource_version(event_id),
+        )
+        .await?
+    }
+}
+
+#[async_trait]
+impl EventReceiverRepository for DeadlineEventReceiverRepository {
+    async fn save(&self, event_receiver: &EventReceiver) -> Result<()> {
+        guard("receivers.save", self.inner.save(event_receiver)).await?
+    }
+
+    async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
+        guard("receivers.find_by_id", self.inner.find_by_id(id)).await?
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiver>> {
+        guard("receivers.find_by_name", self.inner.find_by_name(name)).await?
+    }
+
+    async fn find_by_type(&self, receiver_type: &str) -> Result<Vec<EventReceiver>> {
+        guard(
+            "receivers.find_by_type",
+            self.inner.find_by_type(receiver_type),
+        )
+        .await?
+    }
+
+    async fn find_by_type_and_version(
+        &self,
+        receiver_type: &str,
+        version: &str,
+    ) -> Result<Vec<EventReceiver>> {
+        guard(
+            "receivers.find_by_type_and_version",
+            self.inner.find_by_type_and_version(receiver_type, version),
+        )
+        .await?
+    }
+
+    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>> {
+        guard(
+            "receivers.find_by_fingerprint",
+            self.inner.find_by_fingerprint(fingerprint),
+        )
+        .await?
+    }
+
+    async fn save_if_absent(
+        &self,
+        event_receiver: &EventReceiver,
+    ) -> Result<(EventReceiver, bool)> {
+        guard(
+            "receivers.save_if_absent",
+            self.inner.save_if_absent(event_receiver),
+        )
+        .await?
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiver>> {
+        guard("receivers.list", self.inner.list(limit, offset)).await?
+    }
+
+    async fn count(&self) -> Result<usize> {
+        guard("receivers.count", self.inner.count()).await?
+    }
+
+    async fn update(&self, event_receiver: &EventReceiver) -> Result<()> {
+        guard("receivers.update", self.inner.update(event_receiver)).await?
+    }
+
+    async fn delete(&self, id: EventReceiverId) -> Result<()> {
+        guard("receivers.delete", self.inner.delete(id)).await?
+    }
+
+    async fn delete_many(&self, ids: &[EventReceiverId]) -> Result<()> {
+        guard("receivers.delete_many", self.inner.delete_many(ids)).await?
+    }
+
+    async fn exists_by_name_and_type(&self, name: &str, receiver_type: &str) -> Result<bool> {
+        guard(
+            "receivers.exists_by_name_and_type",
+            self.inner.exists_by_name_and_type(name, receiver_type),
+        )
+        .await?
+    }
+
+    async fn find_by_criteria(
+        &self,
+        criteria: FindEventReceiverCriteria,
+    ) -> Result<Vec<EventReceiver>> {
+        guard(
+            "receivers.find_by_criteria",
+            self.inner.find_by_criteria(criteria),
+        )
+        .await?
+    }
+
+    async fn count_by_criteria(&self, criteria: FindEventReceiverCriteria) -> Result<usize> {
+        guard(
+            "receivers.count_by_criteria",
+            self.inner.count_by_criteria(criteria),
+        )
+        .await?
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiver>> {
+        guard(
+            "receivers.find_by_owner",
+            self.inner.find_by_owner(owner_id),
+        )
+        .await?
+    }
+
+    async fn find_by_owner_paginated(
+        &self,
+        owner_id: UserId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<EventReceiver>> {
+        guard(
+            "receivers.find_by_owner_paginated",
+            self.inner.find_by_owner_paginated(owner_id, limit, offset),
+        )
+        .await?
+    }
+
+    async fn is_owner(&self, receiver_id: EventReceiverId, user_id: UserId) -> Result<bool> {
+        guard(
+            "receivers.is_owner",
+            self.inner.is_owner(receiver_id, user_id),
+        )
+        .await?
+    }
+
+    async fn get_resource_version(&self, receiver_id: EventReceiverId) -> Result<Option<i64>> {
+        guard(
+            "receivers.get_resource_version",
+            self.inner.get_resource_version(receiver_id),
+        )
+        .await?
+    }
+}
+
+#[async_trait]
+impl EventReceiverGroupRepository for DeadlineEventReceiverGroupRepository {
+    async fn save(&self, group: &EventReceiverGroup) -> Result<()> {
+        guard("groups.save", self.inner.save(group)).await?
+    }
+
+    async fn find_by_id(&self, id: EventReceiverGroupId) -> Result<Option<EventReceiverGroup>> {
+        guard("groups.find_by_id", self.inner.find_by_id(id)).await?
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiverGroup>> {
+        guard("groups.find_by_name", self.inner.find_by_name(name)).await?
+    }
+
+    async fn find_by_type(&self, group_type: &str) -> Result<Vec<EventReceiverGroup>> {
+        guard("groups.find_by_type", self.inner.find_by_type(group_type)).await?
+    }
+
+    async fn find_by_type_and_version(
+        &self,
+        group_type: &str,
+        version: &str,
+    ) -> Result<Vec<EventReceiverGroup>> {
+        guard(
+            "groups.find_by_type_and_version",
+            self.inner.find_by_type_and_version(group_type, version),
+        )
+        .await?
+    }
+
+    async fn find_enabled(&self) -> Result<Vec<EventReceiverGroup>> {
+        guard("groups.find_enabled", self.inner.find_enabled()).await?
+    }
+
+    async fn find_disabled(&self) -> Result<Vec<EventReceiverGroup>> {
+        guard("groups.find_disabled", self.inner.find_disabled()).await?
+    }
+
+    async fn find_by_event_receiver_id(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Vec<EventReceiverGroup>> {
+        guard(
+            "groups.find_by_event_receiver_id",
+            self.inner.find_by_event_receiver_id(receiver_id),
+        )
+        .await?
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiverGroup>> {
+        guard("groups.list", self.inner.list(limit, offset)).await?
+    }
+
+    async fn count(&self) -> Result<usize> {
+        guard("groups.count", self.inner.count()).await?
+    }
+
+    async fn count_enabled(&self) -> Result<usize> {
+        guard("groups.count_enabled", self.inner.count_enabled()).await?
+    }
+
+    async fn count_disabled(&self) -> Result<usize> {
+        guard("groups.count_disabled", self.inner.count_disabled()).await?
+    }
+
+    async fn update(&self, group: &EventReceiverGroup) -> Result<()> {
+        guard("groups.update", self.inner.update(group)).await?
+    }
+
+    async fn update_many(&self, groups: &[EventReceiverGroup]) -> Result<()> {
+        guard("groups.update_many", self.inner.update_many(groups)).await?
+    }
+
+    async fn delete(&self, id: EventReceiverGroupId) -> Result<()> {
+        guard("groups.delete", self.inner.delete(id)).await?
+    }
+
+    async fn delete_many(&self, ids: &[EventReceiverGroupId]) -> Result<()> {
+        guard("groups.delete_many", self.inner.delete_many(ids)).await?
+    }
+
+    async fn enable(&self, id: EventReceiverGroupId) -> Result<()> {
+        guard("groups.enable", self.inner.enable(id)).await?
+    }
+
+    async fn disable(&self, id: EventReceiverGroupId) -> Result<()> {
+        guard("groups.disable", self.inner.disable(id)).await?
+    }
+
+    async fn exists_by_name_and_type(&self, name: &str, group_type: &str) -> Result<bool> {
+        guard(
+            "groups.exists_by_name_and_type",
+            self.inner.exists_by_name_and_type(name, group_type),
+        )
+        .await?
+    }
+
+    async fn find_by_criteria(
+        &self,
+        criteria: FindEventReceiverGroupCriteria,
+    ) -> Result<Vec<EventReceiverGroup>> {
+        guard(
+            "groups.find_by_criteria",
+            self.inner.find_by_criteria(criteria),
+        )
+        .await?
+    }
+
+    async fn count_by_criteria(&self, criteria: FindEventReceiverGroupCriteria) -> Result<usize> {
+        guard(
+            "groups.count_by_criteria",
+            self.inner.count_by_criteria(criteria),
+        )
+        .await?
+    }
+
+    async fn add_event_receiver_to_group(
+        &self,
+        group_id: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<()> {
+        guard(
+            "groups.add_event_receiver_to_group",
+            self.inner
+                .add_event_receiver_to_group(group_id, receiver_id),
+        )
+        .await?
+    }
+
+    async fn remove_event_receiver_from_group(
+        &self,
+        group_id: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<()> {
+        guard(
+            "groups.remove_event_receiver_from_group",
+            self.inner
+                .remove_event_receiver_from_group(group_id, receiver_id),
+        )
+        .await?
+    }
+
+    async fn get_group_event_receivers(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<EventReceiverId>> {
+        guard(
+            "groups.get_group_event_receivers",
+            self.inner.get_group_event_receivers(group_id),
+        )
+        .await?
+    }
+
+    async fn find_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<EventReceiverGroupId>> {
+        guard(
+            "groups.find_default_schema_group",
+            self.inner.find_default_schema_group(receiver_id),
+        )
+        .await?
+    }
+
+    async fn set_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+        group_id: EventReceiverGroupId,
+    ) -> Result<()> {
+        guard(
+            "groups.set_default_schema_group",
+            self.inner.set_default_schema_group(receiver_id, group_id),
+        )
+        .await?
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiverGroup>> {
+        guard("groups.find_by_owner", self.inner.find_by_owner(owner_id)).await?
+    }
+
+    async fn find_by_owner_paginated(
+        &self,
+        owner_id: UserId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<EventReceiverGroup>> {
+        guard(
+            "groups.find_by_owner_paginated",
+            self.inner.find_by_owner_paginated(owner_id, limit, offset),
+        )
+        .await?
+    }
+
+    async fn is_owner(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<bool> {
+        guard("groups.is_owner", self.inner.is_owner(group_id, user_id)).await?
+    }
+
+    async fn get_resource_version(&self, group_id: EventReceiverGroupId) -> Result<Option<i64>> {
+        guard(
+            "groups.get_resource_version",
+            self.inner.get_resource_version(group_id),
+        )
+        .await?
+    }
+
+    async fn is_member(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<bool> {
+        guard("groups.is_member", self.inner.is_member(group_id, user_id)).await?
+    }
+
+    async fn get_group_members(&self, group_id: EventReceiverGroupId) -> Result<Vec<UserId>> {
+        guard(
+            "groups.get_group_members",
+            self.inner.get_group_members(group_id),
+        )
+        .await?
+    }
+
+    async fn get_group_memberships(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<GroupMembership>> {
+        guard(
+            "groups.get_group_memberships",
+            self.inner.get_group_memberships(group_id),
+        )
+        .await?
+    }
+
+    async fn add_member(
+        &self,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        added_by: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        guard(
+            "groups.add_member",
+            self.inner
+                .add_member(group_id, user_id, added_by, expires_at),
+        )
+        .await?
+    }
+
+    async fn set_member_expiry(
+        &self,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        guard(
+            "groups.set_member_expiry",
+            self.inner.set_member_expiry(group_id, user_id, expires_at),
+        )
+        .await?
+    }
+
+    async fn remove_expired_members(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ExpiredMembership>> {
+        guard(
+            "groups.remove_expired_members",
+            self.inner.remove_expired_members(now, limit),
+        )
+        .await?
+    }
+
+    async fn remove_member(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<()> {
+        guard(
+            "groups.remove_member",
+            self.inner.remove_member(group_id, user_id),
+        )
+        .await?
+    }
+
+    async fn find_groups_for_user(&self, user_id: UserId) -> Result<Vec<EventReceiverGroup>> {
+        guard(
+            "groups.find_groups_for_user",
+            self.inner.find_groups_for_user(user_id),
+        )
+        .await?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Error, InfrastructureError};
+    use crate::fixtures::EventFixture;
+    use crate::infrastructure::deadline::{with_deadline, DeadlineSource, RequestDeadline};
+    use crate::infrastructure::memory::InMemoryEventRepository;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[tokio::test]
+    async fn test_expired_deadline_never_reaches_the_repository() {
+        let inner = Arc::new(InMemoryEventRepository::new());
+        let repo = DeadlineEventRepository::new(inner.clone());
+        let event = EventFixture::new().build();
+        let deadline = RequestDeadline::new(Instant::now(), DeadlineSource::Client);
+
+        let result = with_deadline(Some(deadline.clone()), repo.save(&event)).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Infrastructure(InfrastructureError::DeadlineExceeded { ref operation }))
+                if operation == "events.save"
+        ));
+        assert_eq!(deadline.exceeded_during(), Some("events.save"));
+        assert!(inner.find_by_id(event.id()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_calls_within_the_deadline_pass_through() {
+        let inner = Arc::new(InMemoryEventRepository::new());
+        let repo = DeadlineEventRepository::new(inner.clone());
+        let event = EventFixture::new().build();
+        let deadline = RequestDeadline::after(Duration::from_secs(30), DeadlineSource::Server);
+
+        with_deadline(Some(deadline.clone()), repo.save(&event))
+            .await
+            .unwrap();
+
+        assert!(inner.find_by_id(event.id()).await.unwrap().is_some());
+        assert_eq!(deadline.exceeded_during(), None);
+    }
+}
@@ -0,0 +1,445 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/feature_flags.rs
+
+//! Server-side feature flags
+//!
+//! Flags and their default states are declared in configuration. Operators
+//! can toggle a flag at runtime through the admin API; runtime changes are
+//! persisted and take precedence over the configured defaults, so they
+//! survive restarts. Every instance keeps the current state in memory and
+//! refreshes it from the repository in the background, so evaluating a flag
+//! never touches the database.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::domain::repositories::feature_flag_repo::{FeatureFlagRepository, StoredFeatureFlag};
+use crate::error::{Error, Result};
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+
+/// Routes authorization decisions through OPA; when disabled the legacy
+/// RBAC check is used instead
+pub const FLAG_OPA_ENFORCEMENT: &str = "opa_enforcement";
+
+/// Default state of a feature flag
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct FeatureFlagConfig {
+    /// State for callers without a matching role override
+    #[serde(default)]
+    pub enabled: bool,
+    /// Per-role states that take precedence over `enabled`
+    #[serde(default)]
+    pub roles: BTreeMap<String, bool>,
+}
+
+/// Feature flag configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeatureFlagsConfig {
+    /// How often runtime changes made on other instances are picked up
+    #[serde(default = "default_refresh_interval_seconds")]
+    pub refresh_interval_seconds: u64,
+    /// Flag defaults by name; built-in flags may be overridden here
+    #[serde(default)]
+    pub flags: BTreeMap<String, FeatureFlagConfig>,
+}
+
+impl Default for FeatureFlagsConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_seconds: default_refresh_interval_seconds(),
+            flags: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_refresh_interval_seconds() -> u64 {
+    30
+}
+
+/// Flags known to the server regardless of configuration
+fn builtin_flags() -> BTreeMap<String, FeatureFlagConfig> {
+    BTreeMap::from([(
+        FLAG_OPA_ENFORCEMENT.to_string(),
+        FeatureFlagConfig {
+            enabled: true,
+            roles: BTreeMap::new(),
+        },
+    )])
+}
+
+/// Current state of a feature flag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeatureFlagState {
+    pub name: String,
+    pub enabled: bool,
+    pub roles: BTreeMap<String, bool>,
+}
+
+struct FlagSlot {
+    enabled: AtomicBool,
+    roles: RwLock<BTreeMap<String, bool>>,
+}
+
+impl FlagSlot {
+    fn new(config: &FeatureFlagConfig) -> Self {
+        Self {
+            enabled: AtomicBool::new(config.enabled),
+            roles: RwLock::new(config.roles.clone()),
+        }
+    }
+
+    fn set(&self, enabled: bool, roles: BTreeMap<String, bool>) {
+        *self.roles.write().unwrap() = roles;
+        self.enabled.store(enabled, Ordering::Release);
+    }
+
+    fn evaluate(&self, roles: &[String]) -> bool {
+        let overrides = self.roles.read().unwrap();
+        if !overrides.is_empty() {
+            let matches: Vec<bool> = roles
+                .iter()
+                .filter_map(|role| overrides.get(role).copied())
+                .collect();
+            if !matches.is_empty() {
+                return matches.contains(&true);
+            }
+        }
+        self.enabled.load(Ordering::Acquire)
+    }
+}
+
+/// Shared handle for evaluating and toggling feature flags
+///
+/// Cloning is cheap; all clones see the same state. The set of flag names
+/// is fixed at construction, so unknown names are rejected rather than
+/// silently created.
+#[derive(Clone)]
+pub struct FeatureFlags {
+    flags: Arc<BTreeMap<String, FlagSlot>>,
+    repository: Option<Arc<dyn FeatureFlagRepository>>,
+    audit_logger: Arc<AuditLogger>,
+}
+
+impl FeatureFlags {
+    /// Creates flags from the built-in defaults and `config`
+    pub fn new(config: &FeatureFlagsConfig) -> Self {
+        let mut defaults = builtin_flags();
+        defaults.extend(config.flags.clone());
+
+        let flags = defaults
+            .iter()
+            .map(|(name, config)| (name.clone(), FlagSlot::new(config)))
+            .collect();
+
+        Self {
+            flags: Arc::new(flags),
+            repository: None,
+            audit_logger: Arc::new(AuditLogger::new()),
+        }
+    }
+
+    /// Persists runtime changes and loads them on refresh
+    pub fn with_repository(mut self, repository: Arc<dyn FeatureFlagRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Uses `audit_logger` to record flag changes
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Returns true if `name` is a known flag
+    pub fn is_known(&self, name: &str) -> bool {
+        self.flags.contains_key(name)
+    }
+
+    /// Returns the flag state for callers without role overrides
+    ///
+    /// Unknown flags are disabled.
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.is_enabled_for(name, &[])
+    }
+
+    /// Evaluates a flag for a caller with `roles`
+    ///
+    /// If any of the caller's roles has an override, the flag is enabled
+    /// when at least one of those overrides enables it. Otherwise the flag's
+    /// default state applies. Unknown flags are disabled.
+    pub fn is_enabled_for(&self, name: &str, roles: &[String]) -> bool {
+        self.flags
+            .get(name)
+            .map(|slot| slot.evaluate(roles))
+            .unwrap_or(false)
+    }
+
+    /// Evaluates every flag for a caller with `roles`
+    pub fn evaluate_for(&self, roles: &[String]) -> BTreeMap<String, bool> {
+        self.flags
+            .iter()
+            .map(|(name, slot)| (name.clone(), slot.evaluate(roles)))
+            .collect()
+    }
+
+    /// Returns the current state of a flag
+    pub fn state(&self, name: &str) -> Option<FeatureFlagState> {
+        self.flags.get(name).map(|slot| FeatureFlagState {
+            name: name.to_string(),
+            enabled: slot.enabled.load(Ordering::Acquire),
+            roles: slot.roles.read().unwrap().clone(),
+        })
+    }
+
+    /// Changes a flag at runtime
+    ///
+    /// `roles` replaces the flag's role overrides; `None` keeps them. The
+    /// change is persisted before it is applied, so a failed write leaves
+    /// the flag unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::NotFound` if the flag is not known.
+    pub async fn set_flag(
+        &self,
+        name: &str,
+        enabled: bool,
+        roles: Option<BTreeMap<String, bool>>,
+        actor: &str,
+    ) -> Result<FeatureFlagState> {
+        let current = match self.state(name) {
+            Some(state) => state,
+            None => {
+                return Err(Error::NotFound {
+                    resource: format!("feature flag '{}'", name),
+                })
+            }
+        };
+        let roles = roles.unwrap_or(current.roles.clone());
+
+        if let Some(repository) = &self.repository {
+            repository
+                .save_flag(&StoredFeatureFlag {
+                    name: name.to_string(),
+                    enabled,
+                    role_overrides: roles.clone(),
+                    updated_by: Some(actor.to_string()),
+                    updated_at: Utc::now(),
+                })
+                .await?;
+        }
+
+        self.flags[name].set(enabled, roles.clone());
+
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(actor)
+                .action(AuditAction::ConfigChange)
+                .resource(format!("feature_flag:{}", name))
+                .outcome(AuditOutcome::Success)
+                .add_metadata("previous_enabled", current.enabled.to_string())
+                .add_metadata("enabled", enabled.to_string())
+                .add_metadata("roles", serde_json::to_string(&roles).unwrap_or_default())
+                .build(),
+        );
+        info!(flag = %name, enabled, actor = %actor, "Feature flag changed");
+
+        Ok(FeatureFlagState {
+            name: name.to_string(),
+            enabled,
+            roles,
+        })
+    }
+
+    /// Loads runtime changes from the repository
+    ///
+    /// Stored flags that are no longer known are ignored.
+    pub async fn refresh(&self) -> Result<()> {
+        let repository = match &self.repository {
+            Some(repository) => repository,
+            None => return Ok(()),
+        };
+
+        for stored in repository.list_flags().await? {
+            match self.flags.get(&stored.name) {
+                Some(slot) => slot.set(stored.enabled, stored.role_overrides),
+                None => warn!(flag = %stored.name, "Ignoring stored state of unknown feature flag"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Refreshes flags every `interval` until the task is aborted
+    pub fn spawn_watcher(&self, interval: Duration) -> JoinHandle<()> {
+        let flags = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = flags.refresh().await {
+                    warn!("Failed to refresh feature flags: {}", e);
+                }
+            }
+        })
+    }
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self::new(&FeatureFlagsConfig::default())
+    }
+}
+
+impl std::fmt::Debug for FeatureFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FeatureFlags")
+            .field("flags", &self.evaluate_for(&[]))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockFeatureFlagRepository {
+        flags: Mutex<BTreeMap<String, StoredFeatureFlag>>,
+    }
+
+    #[async_trait]
+    impl FeatureFlagRepository for MockFeatureFlagRepository {
+        async fn list_flags(&self) -> Result<Vec<StoredFeatureFlag>> {
+            Ok(self.flags.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn save_flag(&self, flag: &StoredFeatureFlag) -> Result<()> {
+            self.flags
+                .lock()
+                .unwrap()
+                .insert(flag.name.clone(), flag.clone());
+            Ok(())
+        }
+    }
+
+    fn config(yaml: &str) -> FeatureFlagsConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn roles(roles: &[&str]) -> Vec<String> {
+        roles.iter().map(|r| r.to_string()).collect()
+    }
+
+    #[test]
+    fn test_role_override_evaluation() {
+        let flags = FeatureFlags::new(&config(
+            r#"
+            flags:
+              async_ingestion:
+                enabled: false
+                roles:
+                  admin: true
+                  beta: true
+                  restricted: false
+            "#,
+        ));
+
+        assert!(!flags.is_enabled("async_ingestion"));
+        assert!(!flags.is_enabled_for("async_ingestion", &roles(&["user"])));
+        assert!(flags.is_enabled_for("async_ingestion", &roles(&["admin"])));
+        assert!(flags.is_enabled_for("async_ingestion", &roles(&["user", "beta"])));
+        assert!(!flags.is_enabled_for("async_ingestion", &roles(&["restricted"])));
+        // Any enabling override wins over a disabling one
+        assert!(flags.is_enabled_for("async_ingestion", &roles(&["restricted", "admin"])));
+    }
+
+    #[test]
+    fn test_builtin_flags_and_unknown_names() {
+        let flags = FeatureFlags::default();
+
+        assert!(flags.is_known(FLAG_OPA_ENFORCEMENT));
+        assert!(flags.is_enabled(FLAG_OPA_ENFORCEMENT));
+        assert!(!flags.is_known("does_not_exist"));
+        assert!(!flags.is_enabled("does_not_exist"));
+
+        let flags = FeatureFlags::new(&config(
+            r#"
+            flags:
+              opa_enforcement:
+                enabled: false
+            "#,
+        ));
+        assert!(!flags.is_enabled(FLAG_OPA_ENFORCEMENT));
+    }
+
+    #[tokio::test]
+    async fn test_runtime_toggle_takes_effect_and_persists() {
+        let repository = Arc::new(MockFeatureFlagRepository::default());
+        let config = config(
+            r#"
+            flags:
+              webhook_delivery:
+                enabled: false
+            "#,
+        );
+        let flags = FeatureFlags::new(&config).with_repository(repository.clone());
+        let clone = flags.clone();
+
+        let state = flags
+            .set_flag(
+                "webhook_delivery",
+                true,
+                Some(BTreeMap::from([("user".to_string(), false)])),
+                "admin-1",
+            )
+            .await
+            .unwrap();
+
+        assert!(state.enabled);
+        assert!(clone.is_enabled("webhook_delivery"));
+        assert!(!clone.is_enabled_for("webhook_delivery", &roles(&["user"])));
+
+        // Omitting roles keeps the existing overrides
+        flags
+            .set_flag("webhook_delivery", true, None, "admin-1")
+            .await
+            .unwrap();
+        assert_eq!(
+            flags.state("webhook_delivery").unwrap().roles,
+            BTreeMap::from([("user".to_string(), false)])
+        );
+
+        // A restarted instance starts from the defaults and loads the change
+        let restarted = FeatureFlags::new(&config).with_repository(repository.clone());
+        assert!(!restarted.is_enabled("webhook_delivery"));
+        restarted.refresh().await.unwrap();
+        assert!(restarted.is_enabled("webhook_delivery"));
+
+        let stored = repository.list_flags().await.unwrap();
+        assert_eq!(stored[0].updated_by.as_deref(), Some("admin-1"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_flag_rejected() {
+        let repository = Arc::new(MockFeatureFlagRepository::default());
+        let flags = FeatureFlags::default().with_repository(repository.clone());
+
+        let result = flags.set_flag("nope", true, None, "admin-1").await;
+
+        assert!(matches!(result, Err(Error::NotFound { .. })));
+        assert!(!flags.is_known("nope"));
+        assert!(repository.list_flags().await.unwrap().is_empty());
+    }
+}
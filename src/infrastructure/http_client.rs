@@ -0,0 +1,551 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/http_client.rs
+
+//! Shared outbound HTTP client configuration
+//!
+//! Every outbound HTTP client (OIDC discovery and token exchange, OPA policy
+//! queries, S3 archive uploads) is built from one [`HttpClientFactory`] so
+//! corporate proxies and private certificate authorities are configured in a
+//! single place. The factory reads the CA bundle once at startup and rejects
+//! an invalid proxy URL or an unreadable bundle before the server starts.
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use reqwest::{Certificate, Client, ClientBuilder, Proxy, Url};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::infrastructure::config::Secret;
+
+/// Outbound HTTP client configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct HttpClientConfig {
+    /// Proxy for all outbound requests, e.g. `http://proxy.corp:3128`;
+    /// may carry credentials, so it is redacted in debug output
+    #[serde(default)]
+    pub proxy_url: Option<Secret<String>>,
+    /// Hosts, domains, and CIDR ranges that bypass the proxy
+    #[serde(default)]
+    pub no_proxy: Vec<String>,
+    /// PEM bundle of CA certificates trusted in addition to the system roots
+    #[serde(default)]
+    pub additional_ca_bundle: Option<String>,
+    /// Time allowed to establish a connection
+    #[serde(default = "default_connect_timeout_seconds")]
+    pub connect_timeout_seconds: u64,
+    /// Time allowed for a whole request, including reading the response
+    #[serde(default = "default_request_timeout_seconds")]
+    pub request_timeout_seconds: u64,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            proxy_url: None,
+            no_proxy: Vec::new(),
+            additional_ca_bundle: None,
+            connect_timeout_seconds: default_connect_timeout_seconds(),
+            request_timeout_seconds: default_request_timeout_seconds(),
+        }
+    }
+}
+
+fn default_connect_timeout_seconds() -> u64 {
+    10
+}
+
+fn default_request_timeout_seconds() -> u64 {
+    30
+}
+
+/// Errors raised while validating the outbound HTTP client configuration
+#[derive(Error, Debug)]
+pub enum HttpClientError {
+    #[error("{setting}: {message}")]
+    InvalidSetting {
+        setting: &'static str,
+        message: String,
+    },
+
+    #[error("Failed to build HTTP client: {0}")]
+    Build(#[from] reqwest::Error),
+}
+
+impl HttpClientError {
+    fn invalid(setting: &'static str, message: impl Into<String>) -> Self {
+        Self::InvalidSetting {
+            setting,
+            message: message.into(),
+        }
+    }
+}
+
+/// A single `no_proxy` entry
+#[derive(Debug, Clone, PartialEq)]
+enum NoProxyRule {
+    /// `*` bypasses the proxy for every host
+    All,
+    /// A host name that also matches its subdomains
+    Domain(String),
+    /// An IP address or CIDR range
+    Network { addr: IpAddr, prefix: u8 },
+}
+
+impl NoProxyRule {
+    fn parse(entry: &str) -> Option<Self> {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            return None;
+        }
+        if entry == "*" {
+            return Some(Self::All);
+        }
+
+        let (addr, prefix) = match entry.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (entry, None),
+        };
+        if let Ok(addr) = addr
+            .trim_matches(|c| c == '[' || c == ']')
+            .parse::<IpAddr>()
+        {
+            let max = if addr.is_ipv4() { 32 } else { 128 };
+            let prefix = match prefix {
+                Some(prefix) => prefix.parse::<u8>().ok().filter(|p| *p <= max)?,
+                None => max,
+            };
+            return Some(Self::Network { addr, prefix });
+        }
+
+        Some(Self::Domain(
+            entry
+                .trim_start_matches("*.")
+                .trim_start_matches('.')
+                .to_ascii_lowercase(),
+        ))
+    }
+
+    fn matches(&self, host: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Domain(domain) => {
+                let host = host.trim_end_matches('.').to_ascii_lowercase();
+                host == *domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|sub| sub.ends_with('.'))
+            }
+            Self::Network { addr, prefix } => host
+                .trim_matches(|c| c == '[' || c == ']')
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| in_network(ip, *addr, *prefix)),
+        }
+    }
+}
+
+fn in_network(ip: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Builds outbound HTTP clients that share proxy, CA, and timeout settings
+///
+/// Construct one factory at startup with [`HttpClientFactory::new`] and pass
+/// it to every component that makes outbound requests. Components that need
+/// different behavior (a shorter timeout, no redirects) start from
+/// [`HttpClientFactory::builder`] and adjust it.
+#[derive(Debug, Clone)]
+pub struct HttpClientFactory {
+    proxy_url: Option<Url>,
+    no_proxy: Vec<NoProxyRule>,
+    certificates: Vec<Certificate>,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+}
+
+impl Default for HttpClientFactory {
+    fn default() -> Self {
+        let config = HttpClientConfig::default();
+        Self {
+            proxy_url: None,
+            no_proxy: Vec::new(),
+            certificates: Vec::new(),
+            connect_timeout: Duration::from_secs(config.connect_timeout_seconds),
+            request_timeout: Duration::from_secs(config.request_timeout_seconds),
+        }
+    }
+}
+
+impl HttpClientFactory {
+    /// Validates `config` and loads the additional CA bundle
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HttpClientError::InvalidSetting`] naming the offending
+    /// setting if the proxy URL is invalid, a `no_proxy` entry cannot be
+    /// parsed, the CA bundle cannot be read or contains no certificates, or
+    /// a timeout is zero.
+    pub fn new(config: &HttpClientConfig) -> Result<Self, HttpClientError> {
+        let proxy_url = config
+            .proxy_url
+            .as_ref()
+            .map(|url| url.expose().as_str())
+            .filter(|url| !url.trim().is_empty())
+            .map(parse_proxy_url)
+            .transpose()?;
+
+        let no_proxy = config
+            .no_proxy
+            .iter()
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                NoProxyRule::parse(entry).ok_or_else(|| {
+                    HttpClientError::invalid(
+                        "http_client.no_proxy",
+                        format!("invalid entry '{}'", entry),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let certificates = match config.additional_ca_bundle.as_deref() {
+            Some(path) if !path.trim().is_empty() => load_ca_bundle(path)?,
+            _ => Vec::new(),
+        };
+
+        if config.connect_timeout_seconds == 0 {
+            return Err(HttpClientError::invalid(
+                "http_client.connect_timeout_seconds",
+                "must be greater than zero",
+            ));
+        }
+        if config.request_timeout_seconds == 0 {
+            return Err(HttpClientError::invalid(
+                "http_client.request_timeout_seconds",
+                "must be greater than zero",
+            ));
+        }
+
+        Ok(Self {
+            proxy_url,
+            no_proxy,
+            certificates,
+            connect_timeout: Duration::from_secs(config.connect_timeout_seconds),
+            request_timeout: Duration::from_secs(config.request_timeout_seconds),
+        })
+    }
+
+    /// Returns true if requests to `host` go directly rather than via the proxy
+    pub fn bypasses_proxy(&self, host: &str) -> bool {
+        self.no_proxy.iter().any(|rule| rule.matches(host))
+    }
+
+    /// Returns the request timeout of the clients this factory builds
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// Returns a client builder with the shared settings applied
+    pub fn builder(&self) -> ClientBuilder {
+        let mut builder = Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.request_timeout);
+
+        for certificate in &self.certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+
+        if let Some(proxy_url) = self.proxy_url.clone() {
+            let no_proxy = self.no_proxy.clone();
+            builder = builder.proxy(Proxy::custom(move |url| {
+                let bypass = url
+                    .host_str()
+                    .is_some_and(|host| no_proxy.iter().any(|rule| rule.matches(host)));
+                (!bypass).then(|| proxy_url.clone())
+            }));
+        }
+
+        builder
+    }
+
+    /// Builds a client with the shared settings
+    pub fn client(&self) -> Result<Client, HttpClientError> {
+        Ok(self.builder().build()?)
+    }
+}
+
+fn parse_proxy_url(value: &str) -> Result<Url, HttpClientError> {
+    let url = Url::parse(value.trim())
+        .map_err(|e| HttpClientError::invalid("http_client.proxy_url", e.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(HttpClientError::invalid(
+            "http_client.proxy_url",
+            format!("unsupported scheme '{}'", url.scheme()),
+        ));
+    }
+    if url.host_str().is_none() {
+        return Err(HttpClientError::invalid(
+            "http_client.proxy_url",
+            "missing host",
+        ));
+    }
+    Ok(url)
+}
+
+fn load_ca_bundle(path: &str) -> Result<Vec<Certificate>, HttpClientError> {
+    let pem = std::fs::read(path).map_err(|e| {
+        HttpClientError::invalid(
+            "http_client.additional_ca_bundle",
+            format!("cannot read {}: {}", path, e),
+        )
+    })?;
+    let certificates = Certificate::from_pem_bundle(&pem).map_err(|e| {
+        HttpClientError::invalid(
+            "http_client.additional_ca_bundle",
+            format!("invalid PEM in {}: {}", path, e),
+        )
+    })?;
+    if certificates.is_empty() {
+        return Err(HttpClientError::invalid(
+            "http_client.additional_ca_bundle",
+            format!("no certificates found in {}", path),
+        ));
+    }
+    Ok(certificates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const CA_BUNDLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls/ca.pem");
+    const SERVER_CERT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/tls/localhost.pem"
+    );
+    const SERVER_KEY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/tls/localhost.key"
+    );
+
+    fn config() -> HttpClientConfig {
+        HttpClientConfig::default()
+    }
+
+    /// Accepts connections and answers each request with `response`
+    async fn stub_server(response: &'static str, delay: Duration) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    tokio::time::sleep(delay).await;
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn test_no_proxy_matching() {
+        let factory = HttpClientFactory::new(&HttpClientConfig {
+            no_proxy: vec![
+                "internal.example.com".to_string(),
+                ".corp".to_string(),
+                "10.0.0.0/8".to_string(),
+                "::1".to_string(),
+            ],
+            ..config()
+        })
+        .unwrap();
+
+        assert!(factory.bypasses_proxy("internal.example.com"));
+        assert!(factory.bypasses_proxy("auth.internal.example.com"));
+        assert!(factory.bypasses_proxy("INTERNAL.example.com"));
+        assert!(factory.bypasses_proxy("keycloak.corp"));
+        assert!(factory.bypasses_proxy("corp"));
+        assert!(factory.bypasses_proxy("10.1.2.3"));
+        assert!(factory.bypasses_proxy("[::1]"));
+
+        assert!(!factory.bypasses_proxy("example.com"));
+        assert!(!factory.bypasses_proxy("notinternal.example.com"));
+        assert!(!factory.bypasses_proxy("mycorp"));
+        assert!(!factory.bypasses_proxy("11.0.0.1"));
+
+        let all = HttpClientFactory::new(&HttpClientConfig {
+            no_proxy: vec!["*".to_string()],
+            ..config()
+        })
+        .unwrap();
+        assert!(all.bypasses_proxy("anything.example.com"));
+    }
+
+    #[test]
+    fn test_invalid_settings_name_the_setting() {
+        let cases = [
+            (
+                HttpClientConfig {
+                    proxy_url: Some("not a url".to_string().into()),
+                    ..config()
+                },
+                "http_client.proxy_url",
+            ),
+            (
+                HttpClientConfig {
+                    proxy_url: Some("ftp://proxy.example.com".to_string().into()),
+                    ..config()
+                },
+                "http_client.proxy_url",
+            ),
+            (
+                HttpClientConfig {
+                    no_proxy: vec!["10.0.0.0/40".to_string()],
+                    ..config()
+                },
+                "http_client.no_proxy",
+            ),
+            (
+                HttpClientConfig {
+                    additional_ca_bundle: Some("/nonexistent/ca.pem".to_string()),
+                    ..config()
+                },
+                "http_client.additional_ca_bundle",
+            ),
+            (
+                HttpClientConfig {
+                    additional_ca_bundle: Some(SERVER_KEY.to_string()),
+                    ..config()
+                },
+                "http_client.additional_ca_bundle",
+            ),
+            (
+                HttpClientConfig {
+                    request_timeout_seconds: 0,
+                    ..config()
+                },
+                "http_client.request_timeout_seconds",
+            ),
+        ];
+
+        for (config, setting) in cases {
+            let err = HttpClientFactory::new(&config).unwrap_err();
+            assert!(
+                err.to_string().starts_with(setting),
+                "expected {} in '{}'",
+                setting,
+                err
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proxy_is_used_unless_bypassed() {
+        let proxy = stub_server(
+            "HTTP/1.1 200 OK\r\ncontent-length: 7\r\nconnection: close\r\n\r\nproxied",
+            Duration::ZERO,
+        )
+        .await;
+        let direct = stub_server(
+            "HTTP/1.1 200 OK\r\ncontent-length: 6\r\nconnection: close\r\n\r\ndirect",
+            Duration::ZERO,
+        )
+        .await;
+
+        let client = HttpClientFactory::new(&HttpClientConfig {
+            proxy_url: Some(format!("http://{}", proxy).into()),
+            no_proxy: vec!["127.0.0.1".to_string()],
+            ..config()
+        })
+        .unwrap()
+        .client()
+        .unwrap();
+
+        let proxied = client
+            .get("http://opa.example.invalid/health")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(proxied.text().await.unwrap(), "proxied");
+
+        let bypassed = client
+            .get(format!("http://{}/health", direct))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(bypassed.text().await.unwrap(), "direct");
+    }
+
+    #[tokio::test]
+    async fn test_additional_ca_reaches_self_signed_server() {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let tls = axum_server::tls_rustls::RustlsConfig::from_pem_file(SERVER_CERT, SERVER_KEY)
+            .await
+            .unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let app = Router::new().route("/health", get(|| async { "ok" }));
+        tokio::spawn(async move {
+            axum_server::from_tcp_rustls(listener, tls)
+                .serve(app.into_make_service())
+                .await
+        });
+        let url = format!("https://localhost:{}/health", port);
+
+        let untrusted = HttpClientFactory::default().client().unwrap();
+        assert!(untrusted.get(&url).send().await.is_err());
+
+        let trusted = HttpClientFactory::new(&HttpClientConfig {
+            additional_ca_bundle: Some(CA_BUNDLE.to_string()),
+            ..config()
+        })
+        .unwrap()
+        .client()
+        .unwrap();
+        let response = trusted.get(&url).send().await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_applies() {
+        let slow = stub_server(
+            "HTTP/1.1 200 OK\r\ncontent-length: 4\r\nconnection: close\r\n\r\nslow",
+            Duration::from_secs(3),
+        )
+        .await;
+
+        let factory = HttpClientFactory::new(&HttpClientConfig {
+            request_timeout_seconds: 1,
+            ..config()
+        })
+        .unwrap();
+
+        let started = std::time::Instant::now();
+        let err = factory
+            .client()
+            .unwrap()
+            .get(format!("http://{}/", slow))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
@@ -0,0 +1,609 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/http_server.rs
+
+//! Connection limits and timeouts for the HTTP and HTTPS listeners
+//!
+//! Both listeners accept connections through [`ConnectionLimitAcceptor`],
+//! which caps how many connections are served at once, closes keep-alive
+//! connections that sit idle, and closes a connection after a set number of
+//! requests. A connection over the cap is answered with `503 Service
+//! Unavailable` and closed rather than queued. Every limit is off unless
+//! configured, so an unconfigured server behaves as hyper does by default.
+
+use std::future::{ready, Future, Ready};
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderValue, Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use axum_server::accept::Accept;
+use http_body::{Frame, SizeHint};
+use hyper_util::rt::{TokioExecutor, TokioTimer};
+use hyper_util::server::conn::auto::Builder;
+use serde_json::json;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::{Instant, Sleep};
+use tower::Service;
+use tracing::{debug, warn};
+
+use crate::infrastructure::config::ServerConfig;
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+/// Time a connection over the cap is kept open to receive its `503`
+pub const REJECTED_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Limits applied to accepted connections
+///
+/// `None` leaves a limit off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionLimits {
+    /// Most connections served at once
+    pub max_connections: Option<usize>,
+    /// Time a client has to send complete HTTP/1 request headers
+    pub header_read_timeout: Option<Duration>,
+    /// Time a connection may go without traffic while no request is in
+    /// progress
+    pub idle_timeout: Option<Duration>,
+    /// Requests served on one HTTP/1 connection before it is closed
+    pub max_requests_per_connection: Option<u64>,
+}
+
+impl ConnectionLimits {
+    /// Reads the limits from the server settings, treating zero as unset
+    pub fn from_settings(config: &ServerConfig) -> Self {
+        let seconds = |value: Option<u64>| value.filter(|s| *s > 0).map(Duration::from_secs);
+        Self {
+            max_connections: config.max_connections.filter(|n| *n > 0),
+            header_read_timeout: seconds(config.header_read_timeout_seconds),
+            idle_timeout: seconds(config.keep_alive_timeout_seconds),
+            max_requests_per_connection: config.max_requests_per_connection.filter(|n| *n > 0),
+        }
+    }
+
+    /// Applies the header read timeout to a server's HTTP/1 settings
+    pub fn configure(&self, builder: &mut Builder<TokioExecutor>) {
+        if let Some(timeout) = self.header_read_timeout {
+            builder
+                .http1()
+                .timer(TokioTimer::new())
+                .header_read_timeout(timeout);
+        }
+    }
+}
+
+/// Acceptor enforcing [`ConnectionLimits`] on every accepted connection
+///
+/// Use it directly for plain HTTP, or as the inner acceptor of the TLS
+/// acceptor so over-limit connections are turned away before the handshake
+/// completes a request.
+#[derive(Clone)]
+pub struct ConnectionLimitAcceptor {
+    limits: ConnectionLimits,
+    permits: Option<Arc<Semaphore>>,
+    open: Arc<AtomicUsize>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+}
+
+impl ConnectionLimitAcceptor {
+    /// Creates an acceptor enforcing `limits`
+    pub fn new(limits: ConnectionLimits) -> Self {
+        Self {
+            permits: limits
+                .max_connections
+                .map(|max| Arc::new(Semaphore::new(max))),
+            limits,
+            open: Arc::new(AtomicUsize::new(0)),
+            metrics: None,
+        }
+    }
+
+    /// Reports open and rejected connections
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// The limits being enforced
+    pub fn limits(&self) -> &ConnectionLimits {
+        &self.limits
+    }
+
+    /// Number of connections currently being served
+    pub fn open_connections(&self) -> usize {
+        self.open.load(Ordering::Acquire)
+    }
+
+    fn admit(&self) -> ConnectionState {
+        let permit = match &self.permits {
+            Some(permits) => match permits.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    warn!(
+                        max_connections = self.limits.max_connections,
+                        "Connection limit reached, rejecting connection"
+                    );
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_http_connection_rejected();
+                    }
+                    return ConnectionState::new(None, Some(REJECTED_CONNECTION_TIMEOUT), Some(1));
+                }
+            },
+            None => None,
+        };
+
+        let count = self.open.fetch_add(1, Ordering::AcqRel) + 1;
+        if let Some(metrics) = &self.metrics {
+            metrics.set_http_open_connections(count);
+        }
+
+        ConnectionState::new(
+            Some(Admission {
+                _permit: permit,
+                open: self.open.clone(),
+                metrics: self.metrics.clone(),
+            }),
+            self.limits.idle_timeout,
+            self.limits.max_requests_per_connection,
+        )
+    }
+}
+
+impl<I, S> Accept<I, S> for ConnectionLimitAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin,
+{
+    type Stream = LimitedStream<I>;
+    type Service = LimitedService<S>;
+    type Future = Ready<io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let connection = Arc::new(self.admit());
+        ready(Ok((
+            LimitedStream {
+                inner: stream,
+                connection: connection.clone(),
+                idle: None,
+            },
+            LimitedService {
+                inner: service,
+                connection,
+            },
+        )))
+    }
+}
+
+/// A served connection's share of the limit, released on close
+struct Admission {
+    _permit: Option<OwnedSemaphorePermit>,
+    open: Arc<AtomicUsize>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+}
+
+impl Drop for Admission {
+    fn drop(&mut self) {
+        let count = self.open.fetch_sub(1, Ordering::AcqRel) - 1;
+        if let Some(metrics) = &self.metrics {
+            metrics.set_http_open_connections(count);
+        }
+    }
+}
+
+/// State shared by a connection's stream and service
+struct ConnectionState {
+    /// `None` for connections over the cap
+    admission: Option<Admission>,
+    idle_timeout: Option<Duration>,
+    max_requests: Option<u64>,
+    requests: AtomicU64,
+    in_flight: AtomicUsize,
+    last_activity: Mutex<Instant>,
+}
+
+impl ConnectionState {
+    fn new(
+        admission: Option<Admission>,
+        idle_timeout: Option<Duration>,
+        max_requests: Option<u64>,
+    ) -> Self {
+        Self {
+            admission,
+            idle_timeout,
+            max_requests,
+            requests: AtomicU64::new(0),
+            in_flight: AtomicUsize::new(0),
+            last_activity: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn touch(&self) {
+        *self.last_activity.lock().unwrap() = Instant::now();
+    }
+
+    /// When the connection counts as idle, pushed back while a request is
+    /// in progress
+    fn idle_deadline(&self) -> Option<Instant> {
+        let timeout = self.idle_timeout?;
+        if self.in_flight.load(Ordering::Acquire) > 0 {
+            Some(Instant::now() + timeout)
+        } else {
+            Some(*self.last_activity.lock().unwrap() + timeout)
+        }
+    }
+}
+
+/// Marks a request in progress until its response body is dropped
+struct RequestGuard(Arc<ConnectionState>);
+
+impl RequestGuard {
+    fn start(connection: Arc<ConnectionState>) -> Self {
+        connection.in_flight.fetch_add(1, Ordering::AcqRel);
+        Self(connection)
+    }
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        self.0.touch();
+        self.0.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Connection stream that reports end of input once the idle timeout passes
+pub struct LimitedStream<I> {
+    inner: I,
+    connection: Arc<ConnectionState>,
+    idle: Option<Pin<Box<Sleep>>>,
+}
+
+impl<I> LimitedStream<I> {
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            let Some(deadline) = self.connection.idle_deadline() else {
+                return Poll::Pending;
+            };
+            let sleep = self
+                .idle
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+            if sleep.deadline() != deadline {
+                sleep.as_mut().reset(deadline);
+            }
+            std::task::ready!(sleep.as_mut().poll(cx));
+
+            if self.connection.in_flight.load(Ordering::Acquire) == 0 && Instant::now() >= deadline
+            {
+                debug!("Closing idle connection");
+                return Poll::Ready(Ok(()));
+            }
+        }
+    }
+}
+
+impl<I> AsyncRead for LimitedStream<I>
+where
+    I: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        match Pin::new(&mut self.inner).poll_read(cx, buf) {
+            Poll::Ready(result) => {
+                if buf.filled().len() > filled {
+                    self.connection.touch();
+                }
+                Poll::Ready(result)
+            }
+            Poll::Pending => self.poll_idle(cx),
+        }
+    }
+}
+
+impl<I> AsyncWrite for LimitedStream<I>
+where
+    I: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write(cx, buf);
+        if matches!(result, Poll::Ready(Ok(n)) if n > 0) {
+            self.connection.touch();
+        }
+        result
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut self.inner).poll_write_vectored(cx, bufs);
+        if matches!(result, Poll::Ready(Ok(n)) if n > 0) {
+            self.connection.touch();
+        }
+        result
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// Connection service answering over-limit connections with `503` and
+/// closing connections that reach the request limit
+#[derive(Clone)]
+pub struct LimitedService<S> {
+    inner: S,
+    connection: Arc<ConnectionState>,
+}
+
+impl<S, B> Service<Request<B>> for LimitedService<S>
+where
+    S: Service<Request<B>, Response = Response>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        if self.connection.admission.is_none() {
+            return Box::pin(ready(Ok(busy_response())));
+        }
+
+        let served = self.connection.requests.fetch_add(1, Ordering::AcqRel) + 1;
+        let last = self
+            .connection
+            .max_requests
+            .is_some_and(|max| served >= max);
+        let guard = RequestGuard::start(self.connection.clone());
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let mut response = future.await?;
+            if last {
+                response
+                    .headers_mut()
+                    .insert(header::CONNECTION, HeaderValue::from_static("close"));
+            }
+            Ok(response.map(|body| {
+                Body::new(TrackedBody {
+                    inner: body,
+                    _request: guard,
+                })
+            }))
+        })
+    }
+}
+
+fn busy_response() -> Response {
+    let mut response = (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({
+            "error": "server_busy",
+            "message": "Too many open connections, retry later",
+        })),
+    )
+        .into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONNECTION, HeaderValue::from_static("close"));
+    headers.insert(header::RETRY_AFTER, HeaderValue::from_static("1"));
+    response
+}
+
+/// Response body keeping its request counted as in progress while it streams
+struct TrackedBody {
+    inner: Body,
+    _request: RequestGuard,
+}
+
+impl http_body::Body for TrackedBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        http_body::Body::poll_frame(Pin::new(&mut self.inner), cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        http_body::Body::is_end_stream(&self.inner)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        http_body::Body::size_hint(&self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use axum::Router;
+    use std::net::SocketAddr;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    const REQUEST: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+
+    async fn start_server(limits: ConnectionLimits) -> (SocketAddr, ConnectionLimitAcceptor) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let acceptor = ConnectionLimitAcceptor::new(limits)
+            .with_metrics(Arc::new(PrometheusMetrics::new().unwrap()));
+
+        let mut server = axum_server::from_tcp(listener).acceptor(acceptor.clone());
+        limits.configure(server.http_builder());
+        let router = Router::new().route("/", get(|| async { "ok" }));
+        tokio::spawn(async move {
+            server
+                .serve(router.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+        });
+
+        (addr, acceptor)
+    }
+
+    /// Reads until the server closes the connection
+    async fn read_until_closed(stream: &mut TcpStream) -> String {
+        let mut output = Vec::new();
+        tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut output))
+            .await
+            .expect("server kept the connection open")
+            .unwrap();
+        String::from_utf8_lossy(&output).to_lowercase()
+    }
+
+    async fn wait_for_open(acceptor: &ConnectionLimitAcceptor, expected: usize) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while acceptor.open_connections() != expected {
+            assert!(
+                Instant::now() < deadline,
+                "expected {} open connections, found {}",
+                expected,
+                acceptor.open_connections()
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[test]
+    fn test_limits_from_settings_treat_zero_as_unset() {
+        let config = ServerConfig {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            enable_https: false,
+            trust_proxy: false,
+            trusted_proxies: Vec::new(),
+            max_connections: Some(0),
+            header_read_timeout_seconds: Some(10),
+            keep_alive_timeout_seconds: None,
+            max_requests_per_connection: Some(100),
+        };
+
+        let limits = ConnectionLimits::from_settings(&config);
+
+        assert_eq!(limits.max_connections, None);
+        assert_eq!(limits.header_read_timeout, Some(Duration::from_secs(10)));
+        assert_eq!(limits.idle_timeout, None);
+        assert_eq!(limits.max_requests_per_connection, Some(100));
+    }
+
+    #[tokio::test]
+    async fn test_idle_connections_hold_the_cap_until_they_time_out() {
+        const CAP: usize = 50;
+        let (addr, acceptor) = start_server(ConnectionLimits {
+            max_connections: Some(CAP),
+            idle_timeout: Some(Duration::from_millis(500)),
+            ..Default::default()
+        })
+        .await;
+
+        let mut idle = Vec::new();
+        for _ in 0..CAP {
+            idle.push(TcpStream::connect(addr).await.unwrap());
+        }
+        wait_for_open(&acceptor, CAP).await;
+
+        // Over the cap: answered at once and closed rather than queued
+        let mut rejected = TcpStream::connect(addr).await.unwrap();
+        rejected.write_all(REQUEST).await.unwrap();
+        let response = read_until_closed(&mut rejected).await;
+        assert!(response.starts_with("http/1.1 503"), "{}", response);
+        assert!(response.contains("connection: close"));
+        assert!(response.contains("server_busy"));
+        assert_eq!(acceptor.open_connections(), CAP);
+
+        // The idle connections are closed by the server, freeing the cap
+        for stream in &mut idle {
+            assert_eq!(read_until_closed(stream).await, "");
+        }
+        wait_for_open(&acceptor, 0).await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(REQUEST).await.unwrap();
+        let mut buf = [0u8; 12];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"HTTP/1.1 200");
+    }
+
+    #[tokio::test]
+    async fn test_slow_headers_are_cut_off() {
+        let (addr, acceptor) = start_server(ConnectionLimits {
+            header_read_timeout: Some(Duration::from_millis(300)),
+            ..Default::default()
+        })
+        .await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: loc")
+            .await
+            .unwrap();
+        let response = read_until_closed(&mut stream).await;
+
+        assert!(!response.contains("200 ok"));
+        wait_for_open(&acceptor, 0).await;
+    }
+
+    #[tokio::test]
+    async fn test_connection_closed_after_max_requests() {
+        let (addr, _acceptor) = start_server(ConnectionLimits {
+            max_requests_per_connection: Some(2),
+            ..Default::default()
+        })
+        .await;
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(&REQUEST.repeat(3)).await.unwrap();
+        let response = read_until_closed(&mut stream).await;
+
+        assert_eq!(response.matches("http/1.1 200").count(), 2);
+        assert_eq!(response.matches("connection: close").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_by_default() {
+        let (addr, acceptor) = start_server(ConnectionLimits::default()).await;
+
+        let mut streams = Vec::new();
+        for _ in 0..20 {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(REQUEST).await.unwrap();
+            let mut buf = [0u8; 12];
+            stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(&buf, b"HTTP/1.1 200");
+            streams.push(stream);
+        }
+        assert_eq!(acceptor.open_connections(), 20);
+    }
+}
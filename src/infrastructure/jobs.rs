@@ -0,0 +1,668 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/jobs.rs
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::error::Result;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::config::JobsConfig;
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+/// Wait before the first retry of a failed job
+pub const DEFAULT_JOB_BACKOFF: Duration = Duration::from_secs(30);
+
+/// When a job runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobSchedule {
+    /// After each run, wait this long before the next
+    Every(Duration),
+    /// Once a day at this UTC time
+    DailyAt(NaiveTime),
+}
+
+impl JobSchedule {
+    /// Returns how long to wait after `now` before the next scheduled run
+    pub fn delay_after(&self, now: DateTime<Utc>) -> Duration {
+        match self {
+            JobSchedule::Every(interval) => *interval,
+            JobSchedule::DailyAt(time) => {
+                let today = now.date_naive().and_time(*time).and_utc();
+                let next = if today > now {
+                    today
+                } else {
+                    today + chrono::Duration::days(1)
+                };
+                (next - now).to_std().unwrap_or_default()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for JobSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobSchedule::Every(interval) => write!(f, "every {}s", interval.as_secs()),
+            JobSchedule::DailyAt(time) => write!(f, "daily at {} UTC", time.format("%H:%M")),
+        }
+    }
+}
+
+/// Outcome of a successful job run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JobReport {
+    /// Items the run handled
+    pub processed: usize,
+    /// The run stopped at a batch limit; the runner starts another at once
+    pub more_pending: bool,
+}
+
+/// Periodic background work driven by a [`JobRunner`]
+#[async_trait]
+pub trait Job: Send + Sync + 'static {
+    /// Unique name used in settings, metrics, and the admin API
+    fn name(&self) -> &str;
+
+    /// When the job runs
+    fn schedule(&self) -> JobSchedule;
+
+    /// Performs one run
+    async fn run(&self) -> Result<JobReport>;
+}
+
+/// How a job run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobOutcome {
+    Success,
+    Failure,
+    Panic,
+}
+
+impl JobOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobOutcome::Success => "success",
+            JobOutcome::Failure => "failure",
+            JobOutcome::Panic => "panic",
+        }
+    }
+}
+
+/// Snapshot of a registered job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobStatus {
+    pub name: String,
+    pub schedule: String,
+    pub enabled: bool,
+    pub running: bool,
+    pub last_started_at: Option<DateTime<Utc>>,
+    pub last_finished_at: Option<DateTime<Utc>>,
+    pub last_duration_ms: Option<u64>,
+    pub last_outcome: Option<JobOutcome>,
+    pub last_error: Option<String>,
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub consecutive_failures: u32,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+/// Reasons a job cannot be started on demand
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum JobTriggerError {
+    #[error("Unknown job: {0}")]
+    UnknownJob(String),
+
+    #[error("Job '{0}' is disabled")]
+    Disabled(String),
+}
+
+struct JobEntry {
+    job: Arc<dyn Job>,
+    enabled: bool,
+    /// Held for the length of a run so scheduled and manual runs never
+    /// overlap
+    run_lock: tokio::sync::Mutex<()>,
+    /// Cuts the wait before the next scheduled run short
+    wake: tokio::sync::Notify,
+    status: Mutex<JobStatus>,
+}
+
+/// Schedules background jobs and reports their status
+///
+/// Each enabled job runs on its own task. First runs are staggered so that
+/// jobs do not all hit the database at startup. Every run is executed on a
+/// separate task, so a job that panics is recorded as failed instead of
+/// taking the loop down with it. Failed runs are retried with exponential
+/// backoff. Jobs listed in `jobs.disabled` are registered but never run.
+pub struct JobRunner {
+    jobs: Vec<Arc<JobEntry>>,
+    disabled: Vec<String>,
+    stagger: Duration,
+    backoff: Duration,
+    max_backoff: Duration,
+    metrics: Option<Arc<PrometheusMetrics>>,
+    audit_logger: Arc<AuditLogger>,
+}
+
+impl JobRunner {
+    /// Creates a runner with the stagger, backoff, and disabled jobs from
+    /// `config`
+    pub fn new(config: &JobsConfig) -> Self {
+        Self {
+            jobs: Vec::new(),
+            disabled: config.disabled.clone(),
+            stagger: Duration::from_secs(config.stagger_seconds),
+            backoff: DEFAULT_JOB_BACKOFF,
+            max_backoff: Duration::from_secs(config.max_backoff_seconds),
+            metrics: None,
+            audit_logger: Arc::new(AuditLogger::new()),
+        }
+    }
+
+    /// Records run counts and durations in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Uses `audit_logger` to record manual runs
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Sets the gap between the first runs of consecutive jobs
+    pub fn with_stagger(mut self, stagger: Duration) -> Self {
+        self.stagger = stagger;
+        self
+    }
+
+    /// Sets the first retry wait and its upper bound
+    pub fn with_backoff(mut self, backoff: Duration, max_backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self.max_backoff = max_backoff.max(backoff);
+        self
+    }
+
+    /// Adds a job
+    ///
+    /// # Panics
+    ///
+    /// Panics if a job with the same name is already registered.
+    pub fn register(mut self, job: Arc<dyn Job>) -> Self {
+        let name = job.name().to_string();
+        assert!(
+            self.entry(&name).is_none(),
+            "job '{}' is registered twice",
+            name
+        );
+
+        let enabled = !self.disabled.contains(&name);
+        let status = JobStatus {
+            name,
+            schedule: job.schedule().to_string(),
+            enabled,
+            running: false,
+            last_started_at: None,
+            last_finished_at: None,
+            last_duration_ms: None,
+            last_outcome: None,
+            last_error: None,
+            success_count: 0,
+            failure_count: 0,
+            consecutive_failures: 0,
+            next_run_at: None,
+        };
+        self.jobs.push(Arc::new(JobEntry {
+            job,
+            enabled,
+            run_lock: tokio::sync::Mutex::new(()),
+            wake: tokio::sync::Notify::new(),
+            status: Mutex::new(status),
+        }));
+        self
+    }
+
+    /// Starts the schedule of every enabled job
+    ///
+    /// The loops stop once `shutdown` changes or its sender is dropped. A
+    /// run already in progress is allowed to finish.
+    pub fn start(self: &Arc<Self>, shutdown: watch::Receiver<bool>) -> Vec<JoinHandle<()>> {
+        let now = Utc::now();
+        let mut handles = Vec::new();
+        for entry in self.jobs.iter().filter(|entry| entry.enabled) {
+            let first_run = match entry.job.schedule() {
+                JobSchedule::Every(_) => self.stagger * handles.len() as u32,
+                schedule @ JobSchedule::DailyAt(_) => schedule.delay_after(now),
+            };
+            info!(
+                job = %entry.job.name(),
+                schedule = %entry.job.schedule(),
+                "Scheduling background job"
+            );
+            handles.push(tokio::spawn(self.clone().run_loop(
+                entry.clone(),
+                first_run,
+                shutdown.clone(),
+            )));
+        }
+
+        for entry in self.jobs.iter().filter(|entry| !entry.enabled) {
+            info!(job = %entry.job.name(), "Background job disabled");
+        }
+
+        handles
+    }
+
+    /// Runs the job called `name` now and returns its status afterwards
+    ///
+    /// Waits for a run already in progress to finish first, so the job
+    /// runs exactly once more. Failed runs are reported in the status, not
+    /// as an error.
+    pub async fn trigger(
+        &self,
+        name: &str,
+        actor: &str,
+    ) -> std::result::Result<JobStatus, JobTriggerError> {
+        let entry = self
+            .entry(name)
+            .ok_or_else(|| JobTriggerError::UnknownJob(name.to_string()))?;
+        if !entry.enabled {
+            return Err(JobTriggerError::Disabled(name.to_string()));
+        }
+
+        info!(job = %name, actor = %actor, "Running background job on demand");
+        let outcome = self.execute(entry).await.0;
+
+        self.audit_logger.log_event(
+            AuditEvent::builder()
+                .user_id(actor)
+                .action(AuditAction::JobTrigger)
+                .resource(format!("job:{}", name))
+                .outcome(match outcome {
+                    JobOutcome::Success => AuditOutcome::Success,
+                    JobOutcome::Failure | JobOutcome::Panic => AuditOutcome::Failure,
+                })
+                .add_metadata("job_outcome", outcome.as_str())
+                .build(),
+        );
+
+        Ok(Self::snapshot(entry))
+    }
+
+    /// Starts the next scheduled run of the job called `name` now
+    ///
+    /// Unlike [`trigger`](Self::trigger), returns at once; the run happens
+    /// on the job's own schedule loop once [`start`](Self::start) has been
+    /// called. A job woken while it runs runs once more right after.
+    pub fn wake(&self, name: &str) -> std::result::Result<(), JobTriggerError> {
+        let entry = self
+            .entry(name)
+            .ok_or_else(|| JobTriggerError::UnknownJob(name.to_string()))?;
+        if !entry.enabled {
+            return Err(JobTriggerError::Disabled(name.to_string()));
+        }
+        entry.wake.notify_one();
+        Ok(())
+    }
+
+    /// Returns the status of every registered job in registration order
+    pub fn statuses(&self) -> Vec<JobStatus> {
+        self.jobs
+            .iter()
+            .map(|entry| Self::snapshot(entry))
+            .collect()
+    }
+
+    /// Returns the status of the job called `name`
+    pub fn status(&self, name: &str) -> Option<JobStatus> {
+        self.entry(name).map(|entry| Self::snapshot(entry))
+    }
+
+    fn entry(&self, name: &str) -> Option<&Arc<JobEntry>> {
+        self.jobs.iter().find(|entry| entry.job.name() == name)
+    }
+
+    fn snapshot(entry: &JobEntry) -> JobStatus {
+        entry.status.lock().unwrap().clone()
+    }
+
+    async fn run_loop(
+        self: Arc<Self>,
+        entry: Arc<JobEntry>,
+        first_run: Duration,
+        mut shutdown: watch::Receiver<bool>,
+    ) {
+        let mut delay = first_run;
+        loop {
+            entry.status.lock().unwrap().next_run_at = chrono::Duration::from_std(delay)
+                .ok()
+                .map(|d| Utc::now() + d);
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = entry.wake.notified() => {}
+                _ = shutdown.changed() => break,
+            }
+
+            let (outcome, report) = self.execute(&entry).await;
+            let scheduled = entry.job.schedule().delay_after(Utc::now());
+            delay = match outcome {
+                JobOutcome::Success if report.more_pending => Duration::ZERO,
+                JobOutcome::Success => scheduled,
+                JobOutcome::Failure | JobOutcome::Panic => {
+                    let failures = entry.status.lock().unwrap().consecutive_failures;
+                    scheduled.max(self.backoff_delay(failures))
+                }
+            };
+        }
+
+        entry.status.lock().unwrap().next_run_at = None;
+        info!(job = %entry.job.name(), "Background job stopped");
+    }
+
+    /// Returns the retry wait after `failures` consecutive failed runs
+    fn backoff_delay(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(16);
+        self.backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff)
+    }
+
+    /// Runs the job once and records the result
+    async fn execute(&self, entry: &JobEntry) -> (JobOutcome, JobReport) {
+        let _guard = entry.run_lock.lock().await;
+        let name = entry.job.name().to_string();
+        {
+            let mut status = entry.status.lock().unwrap();
+            status.running = true;
+            status.last_started_at = Some(Utc::now());
+        }
+
+        let started = Instant::now();
+        let job = entry.job.clone();
+        let result = tokio::spawn(async move { job.run().await }).await;
+        let elapsed = started.elapsed();
+
+        let (outcome, report, failure) = match result {
+            Ok(Ok(report)) => (JobOutcome::Success, report, None),
+            Ok(Err(e)) => {
+                error!(job = %name, "Background job failed: {}", e);
+                (
+                    JobOutcome::Failure,
+                    JobReport::default(),
+                    Some(e.to_string()),
+                )
+            }
+            Err(e) if e.is_panic() => {
+                error!(job = %name, "Background job panicked");
+                (
+                    JobOutcome::Panic,
+                    JobReport::default(),
+                    Some("job panicked".to_string()),
+                )
+            }
+            Err(e) => {
+                warn!(job = %name, "Background job was cancelled: {}", e);
+                (
+                    JobOutcome::Failure,
+                    JobReport::default(),
+                    Some(e.to_string()),
+                )
+            }
+        };
+
+        {
+            let mut status = entry.status.lock().unwrap();
+            status.running = false;
+            status.last_finished_at = Some(Utc::now());
+            status.last_duration_ms = Some(elapsed.as_millis() as u64);
+            status.last_outcome = Some(outcome);
+            status.last_error = failure;
+            if outcome == JobOutcome::Success {
+                status.success_count += 1;
+                status.consecutive_failures = 0;
+            } else {
+                status.failure_count += 1;
+                status.consecutive_failures += 1;
+            }
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_job_run(&name, outcome.as_str(), elapsed.as_secs_f64());
+        }
+
+        (outcome, report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts its runs; panics or fails on the runs it is told to
+    struct CountingJob {
+        name: &'static str,
+        schedule: JobSchedule,
+        runs: Arc<AtomicUsize>,
+        panics: bool,
+        fails: bool,
+    }
+
+    impl CountingJob {
+        fn every(name: &'static str, interval: Duration) -> Self {
+            Self {
+                name,
+                schedule: JobSchedule::Every(interval),
+                runs: Arc::new(AtomicUsize::new(0)),
+                panics: false,
+                fails: false,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Job for CountingJob {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn schedule(&self) -> JobSchedule {
+            self.schedule
+        }
+
+        async fn run(&self) -> Result<JobReport> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            if self.panics {
+                panic!("job {} blew up", self.name);
+            }
+            if self.fails {
+                return Err(Error::Internal {
+                    message: "boom".to_string(),
+                });
+            }
+            Ok(JobReport::default())
+        }
+    }
+
+    fn config() -> JobsConfig {
+        JobsConfig {
+            stagger_seconds: 0,
+            ..JobsConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_daily_schedule_delay() {
+        let schedule = JobSchedule::DailyAt(NaiveTime::from_hms_opt(3, 0, 0).unwrap());
+        let before = "2025-04-25T01:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(schedule.delay_after(before), Duration::from_secs(90 * 60));
+
+        let at = "2025-04-25T03:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(schedule.delay_after(at), Duration::from_secs(24 * 3600));
+
+        assert_eq!(schedule.to_string(), "daily at 03:00 UTC");
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_runs_follow_interval() {
+        let job = CountingJob::every("counting", Duration::from_millis(20));
+        let runs = job.runs.clone();
+        let runner = Arc::new(JobRunner::new(&config()).register(Arc::new(job)));
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handles = runner.start(shutdown_rx);
+        tokio::time::sleep(Duration::from_millis(110)).await;
+        shutdown_tx.send(true).unwrap();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        // Immediately, then about every 20ms
+        let count = runs.load(Ordering::SeqCst);
+        assert!((3..=7).contains(&count), "ran {} times", count);
+
+        let status = runner.status("counting").unwrap();
+        assert_eq!(status.success_count, count as u64);
+        assert_eq!(status.last_outcome, Some(JobOutcome::Success));
+        assert_eq!(status.next_run_at, None);
+
+        // Nothing runs after shutdown
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), count);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_is_isolated_and_backs_off() {
+        let mut panicking = CountingJob::every("panicking", Duration::from_millis(5));
+        panicking.panics = true;
+        let panics = panicking.runs.clone();
+        let healthy = CountingJob::every("healthy", Duration::from_millis(5));
+        let healthy_runs = healthy.runs.clone();
+
+        let runner = Arc::new(
+            JobRunner::new(&config())
+                .with_backoff(Duration::from_millis(40), Duration::from_secs(60))
+                .register(Arc::new(panicking))
+                .register(Arc::new(healthy)),
+        );
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handles = runner.start(shutdown_rx);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        shutdown_tx.send(true).unwrap();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        // Retried after 40ms and 80ms rather than every 5ms
+        let panic_count = panics.load(Ordering::SeqCst);
+        assert!((2..=3).contains(&panic_count), "ran {} times", panic_count);
+        assert!(healthy_runs.load(Ordering::SeqCst) > 10);
+
+        let status = runner.status("panicking").unwrap();
+        assert_eq!(status.last_outcome, Some(JobOutcome::Panic));
+        assert_eq!(status.failure_count, panic_count as u64);
+        assert_eq!(status.consecutive_failures, panic_count as u32);
+        assert_eq!(status.last_error.as_deref(), Some("job panicked"));
+        assert!(!status.running);
+
+        assert_eq!(runner.backoff_delay(1), Duration::from_millis(40));
+        assert_eq!(runner.backoff_delay(3), Duration::from_millis(160));
+        assert_eq!(runner.backoff_delay(40), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_manual_trigger_runs_once() {
+        let job = CountingJob::every("counting", Duration::from_secs(3600));
+        let runs = job.runs.clone();
+        let mut failing = CountingJob::every("failing", Duration::from_secs(3600));
+        failing.fails = true;
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let runner = JobRunner::new(&config())
+            .with_metrics(metrics.clone())
+            .register(Arc::new(job))
+            .register(Arc::new(failing));
+
+        let status = runner.trigger("counting", "admin-1").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(status.success_count, 1);
+        assert!(status.last_duration_ms.is_some());
+
+        let status = runner.trigger("failing", "admin-1").await.unwrap();
+        assert_eq!(status.last_outcome, Some(JobOutcome::Failure));
+        assert!(status.last_error.unwrap().contains("boom"));
+
+        assert_eq!(
+            runner.trigger("missing", "admin-1").await,
+            Err(JobTriggerError::UnknownJob("missing".to_string()))
+        );
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains("xzepr_job_runs_total{job=\"counting\",outcome=\"success\"} 1"));
+        assert!(output.contains("xzepr_job_runs_total{job=\"failing\",outcome=\"failure\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn test_wake_runs_the_job_without_waiting_for_its_schedule() {
+        let job = CountingJob::every("counting", Duration::from_secs(3600));
+        let runs = job.runs.clone();
+        let runner = Arc::new(JobRunner::new(&config()).register(Arc::new(job)));
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handles = runner.start(shutdown_rx);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        runner.wake("counting").unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            runner.wake("missing"),
+            Err(JobTriggerError::UnknownJob("missing".to_string()))
+        );
+
+        shutdown_tx.send(true).unwrap();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled_job_never_runs() {
+        let job = CountingJob::every("counting", Duration::from_millis(5));
+        let runs = job.runs.clone();
+        let runner = Arc::new(
+            JobRunner::new(&JobsConfig {
+                disabled: vec!["counting".to_string()],
+                ..config()
+            })
+            .register(Arc::new(job)),
+        );
+
+        let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+        assert!(runner.start(shutdown_rx).is_empty());
+        assert_eq!(
+            runner.trigger("counting", "admin-1").await,
+            Err(JobTriggerError::Disabled("counting".to_string()))
+        );
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        let status = runner.status("counting").unwrap();
+        assert!(!status.enabled);
+        assert_eq!(status.last_started_at, None);
+    }
+}
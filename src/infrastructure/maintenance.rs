@@ -0,0 +1,334 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/maintenance.rs
+
+//! Read-only maintenance mode
+//!
+//! While maintenance mode is on, the API keeps serving reads but refuses
+//! writes, so schema migrations can run without a full outage. Operators
+//! toggle it through the admin API; the state is persisted like runtime
+//! feature flags, so every instance picks it up on its next refresh and it
+//! survives restarts. Checking the mode never touches the database.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::domain::repositories::maintenance_repo::{MaintenanceRepository, StoredMaintenance};
+use crate::error::Result;
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+
+/// `Retry-After` of rejected writes once the scheduled end has passed
+pub const OVERDUE_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// An active maintenance window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    /// Message returned with rejected writes
+    pub message: String,
+    /// When maintenance is scheduled to end, if known
+    pub ends_at: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    pub started_by: Option<String>,
+}
+
+impl MaintenanceWindow {
+    /// Returns how long clients should wait before retrying a write
+    ///
+    /// `None` without a scheduled end; [`OVERDUE_RETRY_AFTER`] once the
+    /// scheduled end has passed.
+    pub fn retry_after(&self, now: DateTime<Utc>) -> Option<Duration> {
+        let ends_at = self.ends_at?;
+        Some(
+            (ends_at - now)
+                .to_std()
+                .ok()
+                .filter(|remaining| !remaining.is_zero())
+                .map_or(OVERDUE_RETRY_AFTER, |remaining| {
+                    // Round up so clients never retry before the end
+                    Duration::from_secs(
+                        remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0),
+                    )
+                }),
+        )
+    }
+}
+
+impl From<StoredMaintenance> for Option<MaintenanceWindow> {
+    fn from(stored: StoredMaintenance) -> Self {
+        stored.enabled.then_some(MaintenanceWindow {
+            message: stored.message,
+            ends_at: stored.ends_at,
+            started_at: stored.updated_at,
+            started_by: stored.updated_by,
+        })
+    }
+}
+
+/// Shared handle for checking and toggling maintenance mode
+///
+/// Cloning is cheap; all clones see the same state.
+#[derive(Clone)]
+pub struct MaintenanceMode {
+    window: Arc<RwLock<Option<MaintenanceWindow>>>,
+    repository: Option<Arc<dyn MaintenanceRepository>>,
+    audit_logger: Arc<AuditLogger>,
+}
+
+impl MaintenanceMode {
+    /// Creates a handle with maintenance mode off
+    pub fn new() -> Self {
+        Self {
+            window: Arc::new(RwLock::new(None)),
+            repository: None,
+            audit_logger: Arc::new(AuditLogger::new()),
+        }
+    }
+
+    /// Persists changes and loads them on refresh
+    pub fn with_repository(mut self, repository: Arc<dyn MaintenanceRepository>) -> Self {
+        self.repository = Some(repository);
+        self
+    }
+
+    /// Uses `audit_logger` to record entering and exiting maintenance
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Returns the active maintenance window, if any
+    pub fn current(&self) -> Option<MaintenanceWindow> {
+        self.window.read().unwrap().clone()
+    }
+
+    /// Returns true while writes are refused
+    pub fn is_active(&self) -> bool {
+        self.window.read().unwrap().is_some()
+    }
+
+    /// Turns maintenance mode on, or updates the active window
+    ///
+    /// The change is persisted before it is applied, so a failed write
+    /// leaves the mode unchanged.
+    pub async fn enter(
+        &self,
+        message: String,
+        ends_at: Option<DateTime<Utc>>,
+        actor: &str,
+    ) -> Result<MaintenanceWindow> {
+        let now = Utc::now();
+        let started_at = self.current().map_or(now, |window| window.started_at);
+        let window = MaintenanceWindow {
+            message,
+            ends_at,
+            started_at,
+            started_by: Some(actor.to_string()),
+        };
+
+        self.save(true, &window.message, window.ends_at, actor, now)
+            .await?;
+        *self.window.write().unwrap() = Some(window.clone());
+
+        self.audit(actor, true, &window.message, window.ends_at);
+        info!(
+            actor = %actor,
+            ends_at = ?window.ends_at,
+            "Entered maintenance mode"
+        );
+
+        Ok(window)
+    }
+
+    /// Turns maintenance mode off
+    ///
+    /// Exiting while maintenance mode is off is not an error.
+    pub async fn exit(&self, actor: &str) -> Result<()> {
+        self.save(false, "", None, actor, Utc::now()).await?;
+        let previous = self.window.write().unwrap().take();
+
+        if previous.is_some() {
+            self.audit(actor, false, "", None);
+            info!(actor = %actor, "Exited maintenance mode");
+        }
+
+        Ok(())
+    }
+
+    /// Loads the state set on other instances from the repository
+    pub async fn refresh(&self) -> Result<()> {
+        let Some(repository) = &self.repository else {
+            return Ok(());
+        };
+
+        let window = repository
+            .load_maintenance()
+            .await?
+            .and_then(Option::<MaintenanceWindow>::from);
+        let mut current = self.window.write().unwrap();
+        if current.is_some() != window.is_some() {
+            info!(
+                active = window.is_some(),
+                "Maintenance mode changed on another instance"
+            );
+        }
+        *current = window;
+
+        Ok(())
+    }
+
+    /// Refreshes the state every `interval` until the task is aborted
+    pub fn spawn_watcher(&self, interval: Duration) -> JoinHandle<()> {
+        let maintenance = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = maintenance.refresh().await {
+                    warn!("Failed to refresh maintenance mode: {}", e);
+                }
+            }
+        })
+    }
+
+    async fn save(
+        &self,
+        enabled: bool,
+        message: &str,
+        ends_at: Option<DateTime<Utc>>,
+        actor: &str,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        if let Some(repository) = &self.repository {
+            repository
+                .save_maintenance(&StoredMaintenance {
+                    enabled,
+                    message: message.to_string(),
+                    ends_at,
+                    updated_by: Some(actor.to_string()),
+                    updated_at: now,
+                })
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn audit(&self, actor: &str, enabled: bool, message: &str, ends_at: Option<DateTime<Utc>>) {
+        let mut event = AuditEvent::builder()
+            .user_id(actor)
+            .action(AuditAction::ConfigChange)
+            .resource("maintenance_mode")
+            .outcome(AuditOutcome::Success)
+            .add_metadata("enabled", enabled.to_string());
+        if enabled {
+            event = event.add_metadata("message", message);
+        }
+        if let Some(ends_at) = ends_at {
+            event = event.add_metadata("ends_at", ends_at.to_rfc3339());
+        }
+        self.audit_logger.log_event(event.build());
+    }
+}
+
+impl Default for MaintenanceMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for MaintenanceMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaintenanceMode")
+            .field("window", &self.current())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockMaintenanceRepository {
+        stored: Mutex<Option<StoredMaintenance>>,
+    }
+
+    #[async_trait]
+    impl MaintenanceRepository for MockMaintenanceRepository {
+        async fn load_maintenance(&self) -> Result<Option<StoredMaintenance>> {
+            Ok(self.stored.lock().unwrap().clone())
+        }
+
+        async fn save_maintenance(&self, maintenance: &StoredMaintenance) -> Result<()> {
+            *self.stored.lock().unwrap() = Some(maintenance.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_toggle_persists_and_reaches_other_instances() {
+        let repository = Arc::new(MockMaintenanceRepository::default());
+        let maintenance = MaintenanceMode::new().with_repository(repository.clone());
+        let other = MaintenanceMode::new().with_repository(repository.clone());
+        let ends_at = Utc::now() + chrono::Duration::minutes(30);
+
+        let window = maintenance
+            .enter("Migrating".to_string(), Some(ends_at), "admin-1")
+            .await
+            .unwrap();
+        assert!(maintenance.is_active());
+        assert_eq!(window.message, "Migrating");
+        assert_eq!(window.started_by.as_deref(), Some("admin-1"));
+        assert!(repository.stored.lock().unwrap().as_ref().unwrap().enabled);
+
+        assert!(!other.is_active());
+        other.refresh().await.unwrap();
+        assert_eq!(other.current().unwrap().ends_at, Some(ends_at));
+
+        // Updating the window keeps its start
+        let updated = maintenance
+            .enter("Still migrating".to_string(), None, "admin-2")
+            .await
+            .unwrap();
+        assert_eq!(updated.started_at, window.started_at);
+
+        maintenance.exit("admin-1").await.unwrap();
+        assert!(!maintenance.is_active());
+        other.refresh().await.unwrap();
+        assert!(!other.is_active());
+
+        // Exiting again is harmless
+        maintenance.exit("admin-1").await.unwrap();
+    }
+
+    #[test]
+    fn test_retry_after_follows_scheduled_end() {
+        let now = Utc::now();
+        let window = |ends_at| MaintenanceWindow {
+            message: "Migrating".to_string(),
+            ends_at,
+            started_at: now,
+            started_by: None,
+        };
+
+        assert_eq!(window(None).retry_after(now), None);
+        assert_eq!(
+            window(Some(now + chrono::Duration::seconds(90))).retry_after(now),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            window(Some(now + chrono::Duration::milliseconds(1500))).retry_after(now),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            window(Some(now - chrono::Duration::seconds(5))).retry_after(now),
+            Some(OVERDUE_RETRY_AFTER)
+        );
+    }
+}
@@ -0,0 +1,1607 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/memory.rs
+//! In-memory repositories
+//!
+//! Used by the standalone server, demo mode, and the ingestion load
+//! harness. Nothing is persisted; every repository is a map behind a mutex.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+use crate::auth::api_key_usage::{
+    ApiKeyDailyUsage, ApiKeyFailure, ApiKeyUsageRepository, ApiKeyUsageUpdate, MAX_RECENT_FAILURES,
+};
+use crate::auth::rbac::roles::Role;
+use crate::domain::entities::{
+    attestation::EventAttestation,
+    delivery_failure::DeliveryFailure,
+    event::Event,
+    event_receiver::EventReceiver,
+    event_receiver_group::EventReceiverGroup,
+    user::{AuthProvider, User},
+    user_invitation::UserInvitation,
+    user_preferences::UserPreferences,
+};
+use crate::domain::repositories::{
+    attestation_repo::{AttestationFilter, EventAttestationRepository},
+    change_feed_repo::{
+        Change, ChangeCursor, ChangeSet, EventReceiverChangeFeedRepository,
+        EventReceiverGroupChangeFeedRepository,
+    },
+    delivery_failure_repo::{DeliveryFailureRepository, FindDeliveryFailureCriteria},
+    event_outbox_repo::{EventOutboxRepository, OutboxBacklog, OutboxEntry},
+    event_receiver_group_repo::{
+        EventReceiverGroupRepository, ExpiredMembership, FindEventReceiverGroupCriteria,
+        GroupMembership,
+    },
+    event_receiver_repo::{EventReceiverRepository, FindEventReceiverCriteria},
+    event_repo::{EventRepository, FindEventCriteria},
+    release_completion_repo::ReleaseCompletionRepository,
+    resource_history_repo::{GroupState, ResourceHistoryRepository, ResourceSnapshot},
+    user_invitation_repo::UserInvitationRepository,
+    user_preferences_repo::UserPreferencesRepository,
+    user_repo::{UserRepoResult, UserRepository},
+};
+use crate::domain::value_objects::{
+    ApiKeyId, EventId, EventReceiverGroupId, EventReceiverId, UserId,
+};
+use crate::error::{AuthError, DomainError, Result};
+
+/// Event repository that keeps events in memory
+pub struct InMemoryEventRepository {
+    events: Arc<Mutex<HashMap<EventId, Event>>>,
+}
+
+impl Default for InMemoryEventRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryEventRepository {
+    pub fn new() -> Self {
+        Self {
+            events: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl EventRepository for InMemoryEventRepository {
+    async fn save(&self, event: &Event) -> Result<()> {
+        let mut events = self.events.lock().unwrap();
+        events.insert(event.id(), event.clone());
+        info!("Saved event: {} ({})", event.name(), event.id());
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: EventId) -> Result<Option<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events.get(&id).cloned())
+    }
+
+    async fn find_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.event_receiver_id() == receiver_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_success(&self, success: bool) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.success() == success)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.name().contains(name))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_platform_id(&self, platform_id: &str) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.platform_id() == platform_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_package(&self, package: &str) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.package() == package)
+            .cloned()
+            .collect())
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events.values().skip(offset).take(limit).cloned().collect())
+    }
+
+    async fn count(&self) -> Result<usize> {
+        let events = self.events.lock().unwrap();
+        Ok(events.len())
+    }
+
+    async fn count_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.event_receiver_id() == receiver_id)
+            .count())
+    }
+
+    async fn count_successful_by_receiver_id(&self, receiver_id: EventReceiverId) -> Result<usize> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.event_receiver_id() == receiver_id && e.success())
+            .count())
+    }
+
+    async fn delete(&self, id: EventId) -> Result<()> {
+        let mut events = self.events.lock().unwrap();
+        events.remove(&id);
+        info!("Deleted event: {}", id);
+        Ok(())
+    }
+
+    async fn find_latest_by_receiver_id(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.event_receiver_id() == receiver_id)
+            .max_by_key(|e| e.created_at())
+            .cloned())
+    }
+
+    async fn find_latest_successful_by_receiver_id(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.event_receiver_id() == receiver_id && e.success())
+            .max_by_key(|e| e.created_at())
+            .cloned())
+    }
+
+    async fn find_by_time_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.created_at() >= start && e.created_at() <= end)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_criteria(&self, _criteria: FindEventCriteria) -> Result<Vec<Event>> {
+        // Simplified implementation
+        let events = self.events.lock().unwrap();
+        Ok(events.values().cloned().collect())
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.owner_id() == owner_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_owner_paginated(
+        &self,
+        owner_id: UserId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .values()
+            .filter(|e| e.owner_id() == owner_id)
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn is_owner(&self, event_id: EventId, user_id: UserId) -> Result<bool> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .get(&event_id)
+            .map(|e| e.owner_id() == user_id)
+            .unwrap_or(false))
+    }
+
+    async fn get_resource_version(&self, event_id: EventId) -> Result<Option<i64>> {
+        let events = self.events.lock().unwrap();
+        Ok(events.get(&event_id).map(|e| e.resource_version()))
+    }
+}
+
+/// Deleted ids with their deletion time, consumed by the change feed
+type Tombstones<I> = Arc<Mutex<Vec<(I, DateTime<Utc>)>>>;
+
+/// Event receiver repository that keeps receivers in memory
+pub struct InMemoryEventReceiverRepository {
+    receivers: Arc<Mutex<HashMap<EventReceiverId, EventReceiver>>>,
+    name_type_index: Arc<Mutex<HashMap<(String, String), EventReceiverId>>>,
+    tombstones: Tombstones<EventReceiverId>,
+}
+
+impl Default for InMemoryEventReceiverRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryEventReceiverRepository {
+    pub fn new() -> Self {
+        Self {
+            receivers: Arc::new(Mutex::new(HashMap::new())),
+            name_type_index: Arc::new(Mutex::new(HashMap::new())),
+            tombstones: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl EventReceiverRepository for InMemoryEventReceiverRepository {
+    async fn save(&self, event_receiver: &EventReceiver) -> Result<()> {
+        let mut receivers = self.receivers.lock().unwrap();
+        let mut index = self.name_type_index.lock().unwrap();
+
+        receivers.insert(event_receiver.id(), event_receiver.clone());
+        index.insert(
+            (
+                event_receiver.name().to_string(),
+                event_receiver.receiver_type().to_string(),
+            ),
+            event_receiver.id(),
+        );
+
+        info!(
+            "Saved event receiver: {} ({})",
+            event_receiver.name(),
+            event_receiver.id()
+        );
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers.get(&id).cloned())
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiver>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers
+            .values()
+            .filter(|r| r.name().contains(name))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_type(&self, receiver_type: &str) -> Result<Vec<EventReceiver>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers
+            .values()
+            .filter(|r| r.receiver_type() == receiver_type)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_type_and_version(
+        &self,
+        receiver_type: &str,
+        version: &str,
+    ) -> Result<Vec<EventReceiver>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers
+            .values()
+            .filter(|r| r.receiver_type() == receiver_type && r.version() == version)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers
+            .values()
+            .find(|r| r.fingerprint() == fingerprint)
+            .cloned())
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiver>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers
+            .values()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn count(&self) -> Result<usize> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers.len())
+    }
+
+    async fn update(&self, event_receiver: &EventReceiver) -> Result<()> {
+        self.save(event_receiver).await
+    }
+
+    async fn delete(&self, id: EventReceiverId) -> Result<()> {
+        let mut receivers = self.receivers.lock().unwrap();
+        let mut index = self.name_type_index.lock().unwrap();
+
+        if let Some(receiver) = receivers.remove(&id) {
+            index.remove(&(
+                receiver.name().to_string(),
+                receiver.receiver_type().to_string(),
+            ));
+            self.tombstones.lock().unwrap().push((id, Utc::now()));
+            info!("Deleted event receiver: {} ({})", receiver.name(), id);
+        }
+        Ok(())
+    }
+
+    async fn exists_by_name_and_type(&self, name: &str, receiver_type: &str) -> Result<bool> {
+        let index = self.name_type_index.lock().unwrap();
+        Ok(index.contains_key(&(name.to_string(), receiver_type.to_string())))
+    }
+
+    async fn find_by_criteria(
+        &self,
+        _criteria: FindEventReceiverCriteria,
+    ) -> Result<Vec<EventReceiver>> {
+        // Simplified implementation
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers.values().cloned().collect())
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiver>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers
+            .values()
+            .filter(|r| r.owner_id() == owner_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_owner_paginated(
+        &self,
+        owner_id: UserId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<EventReceiver>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers
+            .values()
+            .filter(|r| r.owner_id() == owner_id)
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn is_owner(&self, receiver_id: EventReceiverId, user_id: UserId) -> Result<bool> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers
+            .get(&receiver_id)
+            .map(|r| r.owner_id() == user_id)
+            .unwrap_or(false))
+    }
+
+    async fn get_resource_version(&self, receiver_id: EventReceiverId) -> Result<Option<i64>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers.get(&receiver_id).map(|r| r.resource_version()))
+    }
+}
+
+/// Event receiver group repository that keeps groups in memory
+pub struct InMemoryEventReceiverGroupRepository {
+    groups: Arc<Mutex<HashMap<EventReceiverGroupId, EventReceiverGroup>>>,
+    name_type_index: Arc<Mutex<HashMap<(String, String), EventReceiverGroupId>>>,
+    tombstones: Tombstones<EventReceiverGroupId>,
+    default_schema_groups: Arc<Mutex<HashMap<EventReceiverId, EventReceiverGroupId>>>,
+    members: Arc<Mutex<HashMap<EventReceiverGroupId, Vec<GroupMembership>>>>,
+}
+
+impl Default for InMemoryEventReceiverGroupRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryEventReceiverGroupRepository {
+    pub fn new() -> Self {
+        Self {
+            groups: Arc::new(Mutex::new(HashMap::new())),
+            name_type_index: Arc::new(Mutex::new(HashMap::new())),
+            tombstones: Arc::new(Mutex::new(Vec::new())),
+            default_schema_groups: Arc::new(Mutex::new(HashMap::new())),
+            members: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the memberships of a group that have not expired at `now`
+    fn current_memberships(
+        &self,
+        group_id: EventReceiverGroupId,
+        now: DateTime<Utc>,
+    ) -> Vec<GroupMembership> {
+        let members = self.members.lock().unwrap();
+        let mut current: Vec<GroupMembership> = members
+            .get(&group_id)
+            .into_iter()
+            .flatten()
+            .filter(|m| !m.is_expired_at(now))
+            .cloned()
+            .collect();
+        current.sort_by_key(|m| (m.added_at, m.user_id.to_string()));
+        current
+    }
+
+    /// Applies `change` to a stored group, failing when it does not exist
+    fn modify(
+        &self,
+        id: EventReceiverGroupId,
+        change: impl FnOnce(&mut EventReceiverGroup),
+    ) -> Result<()> {
+        let mut groups = self.groups.lock().unwrap();
+        let group = groups
+            .get_mut(&id)
+            .ok_or_else(|| crate::error::Error::NotFound {
+                resource: format!("Event receiver group with ID {} not found", id),
+            })?;
+        change(group);
+        Ok(())
+    }
+
+    fn filtered(&self, keep: impl Fn(&EventReceiverGroup) -> bool) -> Vec<EventReceiverGroup> {
+        let groups = self.groups.lock().unwrap();
+        groups.values().filter(|g| keep(g)).cloned().collect()
+    }
+}
+
+#[async_trait]
+impl EventReceiverGroupRepository for InMemoryEventReceiverGroupRepository {
+    async fn save(&self, group: &EventReceiverGroup) -> Result<()> {
+        let mut groups = self.groups.lock().unwrap();
+        let mut index = self.name_type_index.lock().unwrap();
+
+        groups.insert(group.id(), group.clone());
+        index.insert(
+            (group.name().to_string(), group.group_type().to_string()),
+            group.id(),
+        );
+
+        info!(
+            "Saved event receiver group: {} ({})",
+            group.name(),
+            group.id()
+        );
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: EventReceiverGroupId) -> Result<Option<EventReceiverGroup>> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups.get(&id).cloned())
+    }
+
+    async fn exists_by_name_and_type(&self, name: &str, group_type: &str) -> Result<bool> {
+        let index = self.name_type_index.lock().unwrap();
+        Ok(index.contains_key(&(name.to_string(), group_type.to_string())))
+    }
+
+    async fn update(&self, group: &EventReceiverGroup) -> Result<()> {
+        self.save(group).await
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiverGroup>> {
+        Ok(self.filtered(|g| g.name().contains(name)))
+    }
+
+    async fn find_by_type(&self, group_type: &str) -> Result<Vec<EventReceiverGroup>> {
+        Ok(self.filtered(|g| g.group_type() == group_type))
+    }
+
+    async fn find_by_type_and_version(
+        &self,
+        group_type: &str,
+        version: &str,
+    ) -> Result<Vec<EventReceiverGroup>> {
+        Ok(self.filtered(|g| g.group_type() == group_type && g.version() == version))
+    }
+
+    async fn find_enabled(&self) -> Result<Vec<EventReceiverGroup>> {
+        Ok(self.filtered(|g| g.enabled()))
+    }
+
+    async fn find_disabled(&self) -> Result<Vec<EventReceiverGroup>> {
+        Ok(self.filtered(|g| !g.enabled()))
+    }
+
+    async fn find_by_event_receiver_id(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Vec<EventReceiverGroup>> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups
+            .values()
+            .filter(|g| g.contains_receiver(receiver_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiverGroup>> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups.values().skip(offset).take(limit).cloned().collect())
+    }
+
+    async fn count(&self) -> Result<usize> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups.len())
+    }
+
+    async fn count_enabled(&self) -> Result<usize> {
+        Ok(self.filtered(|g| g.enabled()).len())
+    }
+
+    async fn count_disabled(&self) -> Result<usize> {
+        Ok(self.filtered(|g| !g.enabled()).len())
+    }
+
+    async fn delete(&self, id: EventReceiverGroupId) -> Result<()> {
+        let mut groups = self.groups.lock().unwrap();
+        let mut index = self.name_type_index.lock().unwrap();
+
+        if let Some(group) = groups.remove(&id) {
+            index.remove(&(group.name().to_string(), group.group_type().to_string()));
+            self.members.lock().unwrap().remove(&id);
+            self.tombstones.lock().unwrap().push((id, Utc::now()));
+            info!("Deleted event receiver group: {} ({})", group.name(), id);
+        }
+        Ok(())
+    }
+
+    async fn enable(&self, id: EventReceiverGroupId) -> Result<()> {
+        self.modify(id, EventReceiverGroup::enable)
+    }
+
+    async fn disable(&self, id: EventReceiverGroupId) -> Result<()> {
+        self.modify(id, EventReceiverGroup::disable)
+    }
+
+    async fn find_by_criteria(
+        &self,
+        _criteria: FindEventReceiverGroupCriteria,
+    ) -> Result<Vec<EventReceiverGroup>> {
+        Ok(vec![])
+    }
+
+    async fn add_event_receiver_to_group(
+        &self,
+        group_id: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<()> {
+        let mut groups = self.groups.lock().unwrap();
+        if let Some(group) = groups.get_mut(&group_id) {
+            if !group.contains_receiver(receiver_id) {
+                group.add_event_receiver(receiver_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove_event_receiver_from_group(
+        &self,
+        group_id: EventReceiverGroupId,
+        receiver_id: EventReceiverId,
+    ) -> Result<()> {
+        let mut groups = self.groups.lock().unwrap();
+        if let Some(group) = groups.get_mut(&group_id) {
+            if group.contains_receiver(receiver_id) {
+                group.remove_event_receiver(receiver_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiverGroup>> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups
+            .values()
+            .filter(|g| g.owner_id() == owner_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_owner_paginated(
+        &self,
+        owner_id: UserId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<EventReceiverGroup>> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups
+            .values()
+            .filter(|g| g.owner_id() == owner_id)
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    async fn is_owner(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<bool> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups
+            .get(&group_id)
+            .map(|g| g.owner_id() == user_id)
+            .unwrap_or(false))
+    }
+
+    async fn get_resource_version(&self, group_id: EventReceiverGroupId) -> Result<Option<i64>> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups.get(&group_id).map(|g| g.resource_version()))
+    }
+
+    async fn is_member(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<bool> {
+        Ok(self
+            .current_memberships(group_id, Utc::now())
+            .iter()
+            .any(|m| m.user_id == user_id))
+    }
+
+    async fn get_group_members(&self, group_id: EventReceiverGroupId) -> Result<Vec<UserId>> {
+        Ok(self
+            .current_memberships(group_id, Utc::now())
+            .into_iter()
+            .map(|m| m.user_id)
+            .collect())
+    }
+
+    async fn get_group_memberships(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<GroupMembership>> {
+        Ok(self.current_memberships(group_id, Utc::now()))
+    }
+
+    // Like the Postgres upsert, an expired membership the expiry job has
+    // not removed yet is replaced and a current one is a conflict
+    async fn add_member(
+        &self,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        added_by: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        if !self.groups.lock().unwrap().contains_key(&group_id) {
+            return Err(DomainError::GroupNotFound.into());
+        }
+
+        let now = Utc::now();
+        let mut members = self.members.lock().unwrap();
+        let memberships = members.entry(group_id).or_default();
+        if let Some(existing) = memberships.iter().find(|m| m.user_id == user_id) {
+            if !existing.is_expired_at(now) {
+                return Err(DomainError::AlreadyExists {
+                    entity: "group member".to_string(),
+                    identifier: user_id.to_string(),
+                }
+                .into());
+            }
+        }
+        memberships.retain(|m| m.user_id != user_id);
+        memberships.push(GroupMembership {
+            user_id,
+            added_by,
+            added_at: now,
+            expires_at,
+        });
+        Ok(())
+    }
+
+    async fn set_member_expiry(
+        &self,
+        group_id: EventReceiverGroupId,
+        user_id: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let now = Utc::now();
+        let mut members = self.members.lock().unwrap();
+        let membership = members
+            .get_mut(&group_id)
+            .and_then(|memberships| {
+                memberships
+                    .iter_mut()
+                    .find(|m| m.user_id == user_id && !m.is_expired_at(now))
+            })
+            .ok_or_else(|| crate::error::Error::NotFound {
+                resource: format!("User {} is not a member of group {}", user_id, group_id),
+            })?;
+        membership.expires_at = expires_at;
+        Ok(())
+    }
+
+    async fn remove_expired_members(
+        &self,
+        now: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ExpiredMembership>> {
+        let mut members = self.members.lock().unwrap();
+        let mut expired: Vec<ExpiredMembership> = members
+            .iter()
+            .flat_map(|(group_id, memberships)| {
+                memberships
+                    .iter()
+                    .filter(|m| m.is_expired_at(now))
+                    .map(|m| ExpiredMembership {
+                        group_id: *group_id,
+                        membership: m.clone(),
+                    })
+            })
+            .collect();
+        expired.sort_by_key(|e| {
+            (
+                e.membership.expires_at,
+                e.group_id.to_string(),
+                e.membership.user_id.to_string(),
+            )
+        });
+        expired.truncate(limit);
+
+        for removal in &expired {
+            if let Some(memberships) = members.get_mut(&removal.group_id) {
+                memberships.retain(|m| m.user_id != removal.membership.user_id);
+            }
+        }
+        Ok(expired)
+    }
+
+    async fn remove_member(&self, group_id: EventReceiverGroupId, user_id: UserId) -> Result<()> {
+        let mut members = self.members.lock().unwrap();
+        let memberships = members.entry(group_id).or_default();
+        let before = memberships.len();
+        memberships.retain(|m| m.user_id != user_id);
+        if memberships.len() == before {
+            return Err(crate::error::Error::NotFound {
+                resource: format!("User {} is not a member of group {}", user_id, group_id),
+            });
+        }
+        Ok(())
+    }
+
+    async fn find_groups_for_user(&self, user_id: UserId) -> Result<Vec<EventReceiverGroup>> {
+        let now = Utc::now();
+        let group_ids: HashSet<EventReceiverGroupId> = {
+            let members = self.members.lock().unwrap();
+            members
+                .iter()
+                .filter(|(_, memberships)| {
+                    memberships
+                        .iter()
+                        .any(|m| m.user_id == user_id && !m.is_expired_at(now))
+                })
+                .map(|(group_id, _)| *group_id)
+                .collect()
+        };
+        let mut groups = self.filtered(|g| group_ids.contains(&g.id()));
+        groups.sort_by_key(|g| std::cmp::Reverse(g.created_at()));
+        Ok(groups)
+    }
+
+    async fn get_group_event_receivers(
+        &self,
+        group_id: EventReceiverGroupId,
+    ) -> Result<Vec<EventReceiverId>> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups
+            .get(&group_id)
+            .map(|g| g.event_receiver_ids().to_vec())
+            .unwrap_or_default())
+    }
+
+    // A default outlives neither the group nor the membership, as the
+    // membership row holding it would be gone in Postgres
+    async fn find_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+    ) -> Result<Option<EventReceiverGroupId>> {
+        let groups = self.groups.lock().unwrap();
+        let defaults = self.default_schema_groups.lock().unwrap();
+        Ok(defaults.get(&receiver_id).copied().filter(|group_id| {
+            groups
+                .get(group_id)
+                .is_some_and(|g| g.contains_receiver(receiver_id))
+        }))
+    }
+
+    async fn set_default_schema_group(
+        &self,
+        receiver_id: EventReceiverId,
+        group_id: EventReceiverGroupId,
+    ) -> Result<()> {
+        let groups = self.groups.lock().unwrap();
+        if !groups
+            .get(&group_id)
+            .is_some_and(|g| g.contains_receiver(receiver_id))
+        {
+            return Err(DomainError::NotFound {
+                entity: "group membership".to_string(),
+                id: format!("{}/{}", group_id, receiver_id),
+            }
+            .into());
+        }
+        self.default_schema_groups
+            .lock()
+            .unwrap()
+            .insert(receiver_id, group_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventReceiverChangeFeedRepository for InMemoryEventReceiverRepository {
+    async fn receiver_changes_since(
+        &self,
+        since: &ChangeCursor,
+        limit: usize,
+    ) -> Result<ChangeSet<EventReceiver, EventReceiverId>> {
+        let receivers = self.receivers.lock().unwrap();
+        let tombstones = self.tombstones.lock().unwrap();
+
+        let upserts = receivers.values().map(|r| {
+            (
+                ChangeCursor::new(r.updated_at(), r.id().to_string()),
+                Change::Upserted(r.clone()),
+            )
+        });
+        let deletions = tombstones
+            .iter()
+            .map(|(id, at)| (ChangeCursor::new(*at, id.to_string()), Change::Deleted(*id)));
+
+        Ok(ChangeSet::from_changes(
+            since,
+            limit,
+            upserts.chain(deletions),
+        ))
+    }
+}
+
+#[async_trait]
+impl EventReceiverGroupChangeFeedRepository for InMemoryEventReceiverGroupRepository {
+    async fn group_changes_since(
+        &self,
+        since: &ChangeCursor,
+        limit: usize,
+    ) -> Result<ChangeSet<EventReceiverGroup, EventReceiverGroupId>> {
+        let groups = self.groups.lock().unwrap();
+        let tombstones = self.tombstones.lock().unwrap();
+
+        let upserts = groups.values().map(|g| {
+            (
+                ChangeCursor::new(g.updated_at(), g.id().to_string()),
+                Change::Upserted(g.clone()),
+            )
+        });
+        let deletions = tombstones
+            .iter()
+            .map(|(id, at)| (ChangeCursor::new(*at, id.to_string()), Change::Deleted(*id)));
+
+        Ok(ChangeSet::from_changes(
+            since,
+            limit,
+            upserts.chain(deletions),
+        ))
+    }
+}
+
+/// Receiver and group history kept in memory
+#[derive(Default)]
+pub struct InMemoryResourceHistoryRepository {
+    receivers: Mutex<HashMap<EventReceiverId, Vec<ResourceSnapshot<EventReceiver>>>>,
+    groups: Mutex<HashMap<EventReceiverGroupId, Vec<ResourceSnapshot<GroupState>>>>,
+}
+
+/// Inserts `snapshot` after every snapshot recorded at or before it
+fn append_snapshot<T: Clone>(
+    history: &mut Vec<ResourceSnapshot<T>>,
+    snapshot: &ResourceSnapshot<T>,
+) {
+    let position = history.partition_point(|s| s.recorded_at <= snapshot.recorded_at);
+    history.insert(position, snapshot.clone());
+}
+
+#[async_trait]
+impl ResourceHistoryRepository for InMemoryResourceHistoryRepository {
+    async fn record_receiver(
+        &self,
+        id: EventReceiverId,
+        snapshot: &ResourceSnapshot<EventReceiver>,
+    ) -> Result<()> {
+        let mut receivers = self.receivers.lock().unwrap();
+        append_snapshot(receivers.entry(id).or_default(), snapshot);
+        Ok(())
+    }
+
+    async fn receiver_history(
+        &self,
+        id: EventReceiverId,
+    ) -> Result<Vec<ResourceSnapshot<EventReceiver>>> {
+        let receivers = self.receivers.lock().unwrap();
+        Ok(receivers.get(&id).cloned().unwrap_or_default())
+    }
+
+    async fn record_group(
+        &self,
+        id: EventReceiverGroupId,
+        snapshot: &ResourceSnapshot<GroupState>,
+    ) -> Result<()> {
+        let mut groups = self.groups.lock().unwrap();
+        append_snapshot(groups.entry(id).or_default(), snapshot);
+        Ok(())
+    }
+
+    async fn group_history(
+        &self,
+        id: EventReceiverGroupId,
+    ) -> Result<Vec<ResourceSnapshot<GroupState>>> {
+        let groups = self.groups.lock().unwrap();
+        Ok(groups.get(&id).cloned().unwrap_or_default())
+    }
+}
+
+/// User preferences repository that keeps preferences in memory
+#[derive(Default)]
+pub struct InMemoryUserPreferencesRepository {
+    preferences: Arc<Mutex<HashMap<String, UserPreferences>>>,
+}
+
+#[async_trait]
+impl UserPreferencesRepository for InMemoryUserPreferencesRepository {
+    async fn find_preferences(&self, user_id: &str) -> Result<UserPreferences> {
+        let preferences = self.preferences.lock().unwrap();
+        Ok(preferences.get(user_id).cloned().unwrap_or_default())
+    }
+
+    async fn save_preferences(&self, user_id: &str, preferences: &UserPreferences) -> Result<()> {
+        let mut stored = self.preferences.lock().unwrap();
+        stored.insert(user_id.to_string(), preferences.clone());
+        Ok(())
+    }
+}
+
+/// User invitation repository that keeps invitations in memory
+#[derive(Default)]
+pub struct InMemoryUserInvitationRepository {
+    invitations: Arc<Mutex<HashMap<UserId, UserInvitation>>>,
+}
+
+#[async_trait]
+impl UserInvitationRepository for InMemoryUserInvitationRepository {
+    async fn save_invitations(&self, invitations: &[UserInvitation]) -> Result<()> {
+        let mut stored = self.invitations.lock().unwrap();
+        for invitation in invitations {
+            stored.insert(invitation.user_id, invitation.clone());
+        }
+        Ok(())
+    }
+
+    async fn take_invitation(&self, token_hash: &str) -> Result<Option<UserInvitation>> {
+        let mut stored = self.invitations.lock().unwrap();
+        let user_id = stored
+            .values()
+            .find(|invitation| invitation.token_hash == token_hash)
+            .map(|invitation| invitation.user_id);
+        Ok(user_id.and_then(|user_id| stored.remove(&user_id)))
+    }
+}
+
+/// Attestation repository that keeps extracted fields in memory
+#[derive(Default)]
+pub struct InMemoryAttestationRepository {
+    attestations: Arc<Mutex<Vec<EventAttestation>>>,
+}
+
+#[async_trait]
+impl EventAttestationRepository for InMemoryAttestationRepository {
+    async fn save_attestation(&self, attestation: &EventAttestation) -> Result<()> {
+        let mut attestations = self.attestations.lock().unwrap();
+        if !attestations
+            .iter()
+            .any(|stored| stored.event_id == attestation.event_id)
+        {
+            attestations.push(attestation.clone());
+        }
+        Ok(())
+    }
+
+    async fn find_attestation(&self, event_id: EventId) -> Result<Option<EventAttestation>> {
+        let attestations = self.attestations.lock().unwrap();
+        Ok(attestations
+            .iter()
+            .find(|attestation| attestation.event_id == event_id)
+            .cloned())
+    }
+
+    async fn find_attestations(&self, filter: &AttestationFilter) -> Result<Vec<EventAttestation>> {
+        let attestations = self.attestations.lock().unwrap();
+        let mut matching: Vec<EventAttestation> = attestations
+            .iter()
+            .filter(|attestation| filter.matches(attestation))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|attestation| {
+            std::cmp::Reverse((attestation.created_at, attestation.event_id.as_ulid()))
+        });
+        Ok(matching
+            .into_iter()
+            .skip(filter.offset.unwrap_or(0))
+            .take(filter.limit.unwrap_or(50))
+            .collect())
+    }
+}
+
+/// Release completion repository that keeps completed pairs in memory
+#[derive(Default)]
+pub struct InMemoryReleaseCompletionRepository {
+    completed: Arc<Mutex<HashSet<(EventReceiverGroupId, String)>>>,
+}
+
+#[async_trait]
+impl ReleaseCompletionRepository for InMemoryReleaseCompletionRepository {
+    async fn record_completion(
+        &self,
+        group_id: EventReceiverGroupId,
+        release: &str,
+        _completed_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let mut completed = self.completed.lock().unwrap();
+        Ok(completed.insert((group_id, release.to_string())))
+    }
+}
+
+/// API key usage repository that keeps counters and failures in memory
+#[derive(Default)]
+pub struct InMemoryApiKeyUsageRepository {
+    days: Arc<Mutex<HashMap<(ApiKeyId, NaiveDate), ApiKeyDailyUsage>>>,
+    /// Failures per key, oldest first
+    failures: Arc<Mutex<HashMap<ApiKeyId, Vec<ApiKeyFailure>>>>,
+}
+
+#[async_trait]
+impl ApiKeyUsageRepository for InMemoryApiKeyUsageRepository {
+    async fn record_usage(
+        &self,
+        updates: &[ApiKeyUsageUpdate],
+    ) -> std::result::Result<(), AuthError> {
+        let mut days = self.days.lock().unwrap();
+        let mut failures = self.failures.lock().unwrap();
+        for update in updates {
+            for usage in &update.days {
+                days.entry((update.key_id, usage.day))
+                    .or_insert_with(|| ApiKeyDailyUsage::new(usage.day))
+                    .merge(usage);
+            }
+            let kept = failures.entry(update.key_id).or_default();
+            kept.extend(update.failures.iter().cloned());
+            let excess = kept.len().saturating_sub(MAX_RECENT_FAILURES);
+            kept.drain(..excess);
+        }
+        Ok(())
+    }
+
+    async fn find_daily_usage(
+        &self,
+        key_id: ApiKeyId,
+        since: NaiveDate,
+    ) -> std::result::Result<Vec<ApiKeyDailyUsage>, AuthError> {
+        let days = self.days.lock().unwrap();
+        let mut found: Vec<ApiKeyDailyUsage> = days
+            .iter()
+            .filter(|((id, day), _)| *id == key_id && *day >= since)
+            .map(|(_, usage)| *usage)
+            .collect();
+        found.sort_by_key(|usage| usage.day);
+        Ok(found)
+    }
+
+    async fn find_recent_failures(
+        &self,
+        key_id: ApiKeyId,
+        limit: usize,
+    ) -> std::result::Result<Vec<ApiKeyFailure>, AuthError> {
+        let failures = self.failures.lock().unwrap();
+        Ok(failures
+            .get(&key_id)
+            .map(|kept| kept.iter().rev().take(limit).cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Outbox that keeps deferred publications in memory
+#[derive(Default)]
+pub struct InMemoryEventOutboxRepository {
+    entries: Arc<Mutex<HashMap<EventId, OutboxEntry>>>,
+}
+
+#[async_trait]
+impl EventOutboxRepository for InMemoryEventOutboxRepository {
+    async fn enqueue(
+        &self,
+        event_id: EventId,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.entry(event_id).or_insert_with(|| OutboxEntry {
+            event_id,
+            attempts: 1,
+            last_error: error.to_string(),
+            next_attempt_at,
+            dead_lettered_at: None,
+        });
+        Ok(())
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<OutboxEntry>> {
+        let entries = self.entries.lock().unwrap();
+        let mut due: Vec<OutboxEntry> = entries
+            .values()
+            .filter(|e| e.dead_lettered_at.is_none() && e.next_attempt_at <= now)
+            .cloned()
+            .collect();
+        due.sort_by_key(|e| e.next_attempt_at);
+        due.truncate(limit);
+        Ok(due)
+    }
+
+    async fn find_entries(&self, event_ids: &[EventId]) -> Result<Vec<OutboxEntry>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(event_ids
+            .iter()
+            .filter_map(|id| entries.get(id).cloned())
+            .collect())
+    }
+
+    async fn mark_published(&self, event_id: EventId) -> Result<()> {
+        self.entries.lock().unwrap().remove(&event_id);
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        event_id: EventId,
+        error: &str,
+        next_attempt_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(entry) = entries.get_mut(&event_id) {
+            entry.attempts += 1;
+            entry.last_error = error.to_string();
+            match next_attempt_at {
+                Some(at) => entry.next_attempt_at = at,
+                None => entry.dead_lettered_at = Some(Utc::now()),
+            }
+        }
+        Ok(())
+    }
+
+    async fn backlog(&self) -> Result<OutboxBacklog> {
+        let entries = self.entries.lock().unwrap();
+        let dead_lettered = entries
+            .values()
+            .filter(|e| e.dead_lettered_at.is_some())
+            .count() as u64;
+        Ok(OutboxBacklog {
+            pending: entries.len() as u64 - dead_lettered,
+            dead_lettered,
+        })
+    }
+}
+
+/// Delivery failure repository that keeps failures in memory
+#[derive(Default)]
+pub struct InMemoryDeliveryFailureRepository {
+    failures: Arc<Mutex<Vec<DeliveryFailure>>>,
+}
+
+#[async_trait]
+impl DeliveryFailureRepository for InMemoryDeliveryFailureRepository {
+    async fn record(&self, failure: &DeliveryFailure) -> Result<bool> {
+        let mut failures = self.failures.lock().unwrap();
+        if failures.iter().any(|f| {
+            f.mechanism == failure.mechanism
+                && f.event_id == failure.event_id
+                && f.target == failure.target
+        }) {
+            return Ok(false);
+        }
+        failures.push(failure.clone());
+        Ok(true)
+    }
+
+    async fn find_recent(
+        &self,
+        criteria: &FindDeliveryFailureCriteria,
+    ) -> Result<Vec<DeliveryFailure>> {
+        let mut failures: Vec<DeliveryFailure> = self
+            .failures
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|f| criteria.receiver_id.is_none_or(|id| f.receiver_id == id))
+            .filter(|f| {
+                criteria
+                    .error_class
+                    .is_none_or(|class| f.error_class == class)
+            })
+            .cloned()
+            .collect();
+        failures.sort_by(|a, b| {
+            b.failed_at
+                .cmp(&a.failed_at)
+                .then_with(|| b.event_id.to_string().cmp(&a.event_id.to_string()))
+                .then_with(|| a.target.cmp(&b.target))
+        });
+        failures.truncate(criteria.limit);
+        Ok(failures)
+    }
+}
+
+/// User repository that keeps users in memory
+#[derive(Default)]
+pub struct InMemoryUserRepository {
+    users: Arc<Mutex<Vec<User>>>,
+}
+
+impl InMemoryUserRepository {
+    fn find(&self, matches: impl Fn(&User) -> bool) -> Option<User> {
+        self.users
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|user| matches(user))
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_by_id(&self, id: &UserId) -> UserRepoResult<Option<User>> {
+        Ok(self.find(|user| user.id == *id))
+    }
+
+    async fn find_by_username(&self, username: &str) -> UserRepoResult<Option<User>> {
+        Ok(self.find(|user| user.username == username))
+    }
+
+    async fn find_by_email(&self, email: &str) -> UserRepoResult<Option<User>> {
+        Ok(self.find(|user| user.email == email))
+    }
+
+    async fn find_by_oidc_subject(&self, subject: &str) -> UserRepoResult<Option<User>> {
+        Ok(self.find(|user| {
+            matches!(&user.auth_provider, AuthProvider::Keycloak { subject: stored } if stored == subject)
+        }))
+    }
+
+    async fn create(&self, user: User) -> UserRepoResult<User> {
+        let mut users = self.users.lock().unwrap();
+        if users.iter().any(|stored| same_identity(stored, &user)) {
+            return Err(user_exists(&user));
+        }
+        users.push(user.clone());
+        Ok(user)
+    }
+
+    async fn create_all(&self, new_users: Vec<User>) -> UserRepoResult<Vec<User>> {
+        let mut users = self.users.lock().unwrap();
+        for (index, user) in new_users.iter().enumerate() {
+            if users
+                .iter()
+                .chain(&new_users[..index])
+                .any(|stored| same_identity(stored, user))
+            {
+                return Err(user_exists(user));
+            }
+        }
+        users.extend(new_users.iter().cloned());
+        Ok(new_users)
+    }
+
+    async fn update(&self, user: User) -> UserRepoResult<User> {
+        let mut users = self.users.lock().unwrap();
+        let Some(stored) = users.iter_mut().find(|stored| stored.id == user.id) else {
+            return Err(DomainError::NotFound {
+                entity: "User".to_string(),
+                id: user.id.to_string(),
+            });
+        };
+        *stored = user.clone();
+        Ok(user)
+    }
+
+    async fn delete(&self, id: &UserId) -> UserRepoResult<()> {
+        let mut users = self.users.lock().unwrap();
+        let before = users.len();
+        users.retain(|user| user.id != *id);
+        if users.len() == before {
+            return Err(DomainError::NotFound {
+                entity: "User".to_string(),
+                id: id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    async fn username_exists(&self, username: &str) -> UserRepoResult<bool> {
+        Ok(self.find(|user| user.username == username).is_some())
+    }
+
+    async fn email_exists(&self, email: &str) -> UserRepoResult<bool> {
+        Ok(self.find(|user| user.email == email).is_some())
+    }
+
+    async fn create_or_update_oidc_user(
+        &self,
+        subject: String,
+        username: String,
+        email: Option<String>,
+        _name: Option<String>,
+    ) -> UserRepoResult<User> {
+        match self.find_by_oidc_subject(&subject).await? {
+            Some(mut user) => {
+                if let Some(email) = email {
+                    user.email = email;
+                }
+                user.updated_at = Utc::now();
+                self.update(user).await
+            }
+            None => {
+                self.create(User::new_oidc(username, email.unwrap_or_default(), subject))
+                    .await
+            }
+        }
+    }
+
+    async fn list(&self, limit: i64, offset: i64) -> UserRepoResult<Vec<User>> {
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn count(&self) -> UserRepoResult<i64> {
+        Ok(self.users.lock().unwrap().len() as i64)
+    }
+
+    async fn find_by_provider(&self, provider: &AuthProvider) -> UserRepoResult<Vec<User>> {
+        let users = self.users.lock().unwrap();
+        Ok(users
+            .iter()
+            .filter(|user| {
+                std::mem::discriminant(&user.auth_provider) == std::mem::discriminant(provider)
+            })
+            .cloned()
+            .collect())
+    }
+}
+
+/// Serves token issuance and impersonation, which read users through the
+/// authentication repository
+#[async_trait]
+impl crate::auth::api_key::UserRepository for InMemoryUserRepository {
+    async fn find_by_id(&self, id: UserId) -> std::result::Result<Option<User>, AuthError> {
+        Ok(self.find(|user| user.id == id))
+    }
+
+    async fn find_by_username(
+        &self,
+        username: &str,
+    ) -> std::result::Result<Option<User>, AuthError> {
+        Ok(self.find(|user| user.username == username))
+    }
+
+    async fn save(&self, user: &User) -> std::result::Result<(), AuthError> {
+        let mut users = self.users.lock().unwrap();
+        match users.iter_mut().find(|stored| stored.id == user.id) {
+            Some(stored) => *stored = user.clone(),
+            None => users.push(user.clone()),
+        }
+        Ok(())
+    }
+
+    async fn find_all(&self) -> std::result::Result<Vec<User>, AuthError> {
+        Ok(self.users.lock().unwrap().clone())
+    }
+
+    async fn add_role(&self, user_id: &UserId, role: Role) -> std::result::Result<(), AuthError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|user| user.id == *user_id)
+            .ok_or(AuthError::UserNotFound)?;
+        if !user.roles.contains(&role) {
+            user.roles.push(role);
+        }
+        Ok(())
+    }
+
+    async fn remove_role(
+        &self,
+        user_id: &UserId,
+        role: Role,
+    ) -> std::result::Result<(), AuthError> {
+        let mut users = self.users.lock().unwrap();
+        let user = users
+            .iter_mut()
+            .find(|user| user.id == *user_id)
+            .ok_or(AuthError::UserNotFound)?;
+        user.roles.retain(|held| *held != role);
+        Ok(())
+    }
+}
+
+/// Returns true if two users share a username or email
+fn same_identity(a: &User, b: &User) -> bool {
+    a.username == b.username || a.email == b.email
+}
+
+fn user_exists(user: &User) -> DomainError {
+    DomainError::AlreadyExists {
+        entity: "User".to_string(),
+        identifier: user.username.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_outbox_tracks_retries_and_dead_letters() {
+        let outbox = InMemoryEventOutboxRepository::default();
+        let (first, second) = (EventId::new(), EventId::new());
+        let now = Utc::now();
+
+        outbox.enqueue(first, "broker down", now).await.unwrap();
+        outbox
+            .enqueue(second, "broker down", now + chrono::Duration::minutes(5))
+            .await
+            .unwrap();
+        let due = outbox.find_due(now, 10).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].event_id, first);
+
+        outbox
+            .record_failure(first, "still down", None)
+            .await
+            .unwrap();
+        outbox.mark_published(second).await.unwrap();
+        assert_eq!(
+            outbox.backlog().await.unwrap(),
+            OutboxBacklog {
+                pending: 0,
+                dead_lettered: 1
+            }
+        );
+        let entries = outbox.find_entries(&[first, second]).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_add_member_replaces_only_expired_memberships() {
+        let repo = InMemoryEventReceiverGroupRepository::new();
+        let group = crate::fixtures::GroupFixture::new().persist(&repo).await;
+        let (owner, user) = (UserId::new(), UserId::new());
+        let lapsed = Some(Utc::now() - chrono::Duration::minutes(1));
+
+        repo.add_member(group.id(), user, owner, lapsed)
+            .await
+            .unwrap();
+        assert!(!repo.is_member(group.id(), user).await.unwrap());
+
+        repo.add_member(group.id(), user, owner, None)
+            .await
+            .unwrap();
+        assert!(repo.is_member(group.id(), user).await.unwrap());
+        let err = repo
+            .add_member(group.id(), user, owner, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Domain(DomainError::AlreadyExists { .. })
+        ));
+        assert!(repo
+            .add_member(EventReceiverGroupId::new(), user, owner, None)
+            .await
+            .is_err());
+
+        repo.remove_member(group.id(), user).await.unwrap();
+        assert!(repo.find_groups_for_user(user).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_default_schema_group_swap_keeps_one_default() {
+        let repo = InMemoryEventReceiverGroupRepository::new();
+        let receiver_id = EventReceiverId::new();
+        let group = |name: &str| {
+            EventReceiverGroup::new(
+                name.to_string(),
+                "webhook_group".to_string(),
+                "1.0.0".to_string(),
+                "Test group".to_string(),
+                true,
+                vec![receiver_id],
+                UserId::new(),
+            )
+            .unwrap()
+        };
+        let (first, second) = (group("builds"), group("deploys"));
+        repo.save(&first).await.unwrap();
+        repo.save(&second).await.unwrap();
+
+        repo.set_default_schema_group(receiver_id, first.id())
+            .await
+            .unwrap();
+        repo.set_default_schema_group(receiver_id, second.id())
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.find_default_schema_group(receiver_id).await.unwrap(),
+            Some(second.id())
+        );
+
+        // A non-member cannot take a default, and the current one is kept
+        let err = repo
+            .set_default_schema_group(EventReceiverId::new(), first.id())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Domain(DomainError::NotFound { .. })
+        ));
+
+        // The default goes with the group
+        repo.delete(second.id()).await.unwrap();
+        assert_eq!(
+            repo.find_default_schema_group(receiver_id).await.unwrap(),
+            None
+        );
+    }
+}
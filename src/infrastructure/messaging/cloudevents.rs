@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::domain::entities::event::Event;
+use crate::domain::entities::event::{Event, EventOrigin};
 use crate::domain::entities::event_receiver::EventReceiver;
 use crate::domain::entities::event_receiver_group::EventReceiverGroup;
 
@@ -52,6 +52,11 @@ pub struct CloudEventMessage {
     /// Extension to Cloud Events Spec - package name
     pub package: String,
 
+    /// Extension to Cloud Events Spec - `user` or `system`, so consumers can
+    /// skip the events xzepr emits about itself
+    #[serde(default)]
+    pub origin: EventOrigin,
+
     /// Cloud Events Spec 1.0.1 - event payload
     pub data: CloudEventData,
 }
@@ -106,6 +111,7 @@ impl CloudEventMessage {
             release: event.release().to_string(),
             platform_id: event.platform_id().to_string(),
             package: event.package().to_string(),
+            origin: event.origin(),
             data: CloudEventData {
                 events: vec![event.clone()],
                 event_receivers: vec![],
@@ -114,12 +120,13 @@ impl CloudEventMessage {
         }
     }
 
-    /// Creates a CloudEvents-compatible message for an event receiver creation
+    /// Creates a CloudEvents-compatible message for a system event about an
+    /// event receiver
     ///
     /// # Arguments
     ///
     /// * `event` - The system event
-    /// * `receiver` - The event receiver that was created
+    /// * `receiver` - The event receiver the system event describes
     ///
     /// # Returns
     ///
@@ -137,6 +144,7 @@ impl CloudEventMessage {
             release: event.release().to_string(),
             platform_id: event.platform_id().to_string(),
             package: event.package().to_string(),
+            origin: event.origin(),
             data: CloudEventData {
                 events: vec![event.clone()],
                 event_receivers: vec![receiver.clone()],
@@ -145,12 +153,13 @@ impl CloudEventMessage {
         }
     }
 
-    /// Creates a CloudEvents-compatible message for an event receiver group creation
+    /// Creates a CloudEvents-compatible message for a system event about an
+    /// event receiver group
     ///
     /// # Arguments
     ///
     /// * `event` - The system event
-    /// * `group` - The event receiver group that was created
+    /// * `group` - The event receiver group the system event describes
     ///
     /// # Returns
     ///
@@ -168,6 +177,7 @@ impl CloudEventMessage {
             release: event.release().to_string(),
             platform_id: event.platform_id().to_string(),
             package: event.package().to_string(),
+            origin: event.origin(),
             data: CloudEventData {
                 events: vec![event.clone()],
                 event_receivers: vec![],
@@ -247,6 +257,7 @@ mod tests {
         assert_eq!(message.release, "1.0.0-rc.1");
         assert_eq!(message.platform_id, "kubernetes");
         assert_eq!(message.package, "test-package");
+        assert_eq!(message.origin, EventOrigin::User);
         assert_eq!(message.data.events.len(), 1);
         assert_eq!(message.data.event_receivers.len(), 0);
         assert_eq!(message.data.event_receiver_groups.len(), 0);
@@ -294,7 +305,7 @@ mod tests {
     #[test]
     fn test_cloudevent_message_with_system_event() {
         let receiver_id = EventReceiverId::new();
-        let event = Event::new(CreateEventParams {
+        let event = Event::new_system(CreateEventParams {
             name: "xzepr.event.receiver.created".to_string(),
             version: "1.0.0".to_string(),
             release: "system".to_string(),
@@ -317,6 +328,8 @@ mod tests {
         assert_eq!(message.release, "system");
         assert_eq!(message.platform_id, "xzepr");
         assert_eq!(message.package, "xzepr.system");
+        assert_eq!(message.origin, EventOrigin::System);
+        assert!(message.to_json().unwrap().contains(r#""origin":"system""#));
     }
 
     #[test]
@@ -407,6 +420,7 @@ mod tests {
         assert!(parsed.get("release").is_some());
         assert!(parsed.get("platform_id").is_some());
         assert!(parsed.get("package").is_some());
+        assert!(parsed.get("origin").is_some());
         assert!(parsed.get("data").is_some());
 
         // Verify data has correct array fields
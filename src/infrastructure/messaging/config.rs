@@ -5,10 +5,13 @@
 
 use rdkafka::config::ClientConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use thiserror::Error;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+use crate::infrastructure::config::{Secret, REDACTED};
+
 /// Errors that can occur during Kafka authentication configuration
 #[derive(Error, Debug)]
 pub enum ConfigError {
@@ -26,6 +29,9 @@ pub enum ConfigError {
 
     #[error("SSL certificate file not found: {0}")]
     SslCertificateNotFound(String),
+
+    #[error("Invalid producer configuration: {0}")]
+    InvalidProducerConfig(String),
 }
 
 /// Security protocol for Kafka connections
@@ -153,7 +159,7 @@ impl std::str::FromStr for SaslMechanism {
 ///
 /// The password field is automatically cleared from memory when the struct is dropped
 /// using the Zeroize trait for enhanced security.
-#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+#[derive(Debug, Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct SaslConfig {
     /// SASL mechanism to use
     #[zeroize(skip)]
@@ -161,7 +167,8 @@ pub struct SaslConfig {
     /// SASL username
     pub username: String,
     /// SASL password (will be redacted in debug output and cleared from memory on drop)
-    pub password: String,
+    #[serde(serialize_with = "Secret::serialize_exposed")]
+    pub password: Secret<String>,
 }
 
 impl SaslConfig {
@@ -192,7 +199,7 @@ impl SaslConfig {
         Self {
             mechanism,
             username,
-            password,
+            password: Secret::new(password),
         }
     }
 
@@ -225,17 +232,7 @@ impl SaslConfig {
     pub fn apply_to_client_config(&self, client_config: &mut ClientConfig) {
         client_config.set("sasl.mechanism", self.mechanism.as_str());
         client_config.set("sasl.username", &self.username);
-        client_config.set("sasl.password", &self.password);
-    }
-}
-
-impl fmt::Debug for SaslConfig {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("SaslConfig")
-            .field("mechanism", &self.mechanism)
-            .field("username", &self.username)
-            .field("password", &"[REDACTED]")
-            .finish()
+        client_config.set("sasl.password", self.password.expose());
     }
 }
 
@@ -572,6 +569,202 @@ impl KafkaAuthConfig {
     }
 }
 
+/// Broker acknowledgements required before a produce request succeeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProducerAcks {
+    /// Wait for all in-sync replicas
+    #[serde(rename = "all", alias = "-1")]
+    All,
+    /// Wait for the partition leader only
+    #[serde(rename = "1")]
+    Leader,
+    /// Do not wait for any acknowledgement
+    #[serde(rename = "0")]
+    None,
+}
+
+impl ProducerAcks {
+    /// Get the rdkafka configuration string for this setting
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProducerAcks::All => "all",
+            ProducerAcks::Leader => "1",
+            ProducerAcks::None => "0",
+        }
+    }
+}
+
+impl fmt::Display for ProducerAcks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Compression codec applied to produced message batches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionType {
+    None,
+    Gzip,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
+impl CompressionType {
+    /// Get the rdkafka configuration string for this codec
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompressionType::None => "none",
+            CompressionType::Gzip => "gzip",
+            CompressionType::Snappy => "snappy",
+            CompressionType::Lz4 => "lz4",
+            CompressionType::Zstd => "zstd",
+        }
+    }
+}
+
+impl fmt::Display for CompressionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Kafka producer delivery and batching configuration
+///
+/// Defaults match librdkafka's own defaults, except `message_timeout_ms`
+/// which keeps the 5 second delivery timeout the publisher has always used.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KafkaProducerConfig {
+    /// Acknowledgements required from the broker (`all`, `1`, or `0`)
+    pub acks: ProducerAcks,
+    /// Enable the idempotent producer (requires `acks = all`)
+    pub enable_idempotence: bool,
+    /// Maximum time to deliver a message, including retries
+    pub message_timeout_ms: u32,
+    /// Number of times to retry a failed produce request
+    pub retries: u32,
+    /// Backoff between retries
+    pub retry_backoff_ms: u32,
+    /// Compression codec for message batches
+    pub compression_type: CompressionType,
+    /// Time to wait for more messages before sending a batch
+    pub linger_ms: u32,
+    /// Maximum number of messages batched in one request
+    pub batch_num_messages: u32,
+}
+
+impl Default for KafkaProducerConfig {
+    fn default() -> Self {
+        Self {
+            acks: ProducerAcks::All,
+            enable_idempotence: false,
+            message_timeout_ms: 5000,
+            retries: i32::MAX as u32,
+            retry_backoff_ms: 100,
+            compression_type: CompressionType::None,
+            linger_ms: 5,
+            batch_num_messages: 10000,
+        }
+    }
+}
+
+impl KafkaProducerConfig {
+    /// Validate the producer configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns Ok(()) if the configuration is valid
+    ///
+    /// # Errors
+    ///
+    /// Returns ConfigError::InvalidProducerConfig if settings are out of range
+    /// or cannot be combined
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.enable_idempotence {
+            if self.acks != ProducerAcks::All {
+                return Err(ConfigError::InvalidProducerConfig(format!(
+                    "enable_idempotence requires acks=all (got acks={})",
+                    self.acks
+                )));
+            }
+            if self.retries == 0 {
+                return Err(ConfigError::InvalidProducerConfig(
+                    "enable_idempotence requires retries > 0".to_string(),
+                ));
+            }
+        }
+
+        if self.retries > i32::MAX as u32 {
+            return Err(ConfigError::InvalidProducerConfig(format!(
+                "retries must be at most {}",
+                i32::MAX
+            )));
+        }
+
+        // A message timeout of 0 means infinite in librdkafka
+        if self.message_timeout_ms != 0 && self.message_timeout_ms <= self.linger_ms {
+            return Err(ConfigError::InvalidProducerConfig(format!(
+                "message_timeout_ms ({}) must be greater than linger_ms ({})",
+                self.message_timeout_ms, self.linger_ms
+            )));
+        }
+
+        if !(1..=1_000_000).contains(&self.batch_num_messages) {
+            return Err(ConfigError::InvalidProducerConfig(format!(
+                "batch_num_messages must be between 1 and 1000000 (got {})",
+                self.batch_num_messages
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Apply this producer configuration to an rdkafka ClientConfig
+    ///
+    /// # Arguments
+    ///
+    /// * `client_config` - The ClientConfig to modify
+    pub fn apply_to_client_config(&self, client_config: &mut ClientConfig) {
+        client_config
+            .set("acks", self.acks.as_str())
+            .set("enable.idempotence", self.enable_idempotence.to_string())
+            .set("message.timeout.ms", self.message_timeout_ms.to_string())
+            .set("retries", self.retries.to_string())
+            .set("retry.backoff.ms", self.retry_backoff_ms.to_string())
+            .set("compression.type", self.compression_type.as_str())
+            .set("linger.ms", self.linger_ms.to_string())
+            .set("batch.num.messages", self.batch_num_messages.to_string());
+    }
+}
+
+/// Returns the entries of a ClientConfig with secrets redacted
+///
+/// Any key naming a password, secret, or private key has its value
+/// replaced, so the result is safe to log or return from debug endpoints.
+pub fn redacted_client_config(client_config: &ClientConfig) -> BTreeMap<String, String> {
+    client_config
+        .config_map()
+        .iter()
+        .map(|(key, value)| {
+            let value = if is_secret_key(key) {
+                REDACTED.to_string()
+            } else {
+                value.clone()
+            };
+            (key.clone(), value)
+        })
+        .collect()
+}
+
+fn is_secret_key(key: &str) -> bool {
+    key.contains("password")
+        || key.contains("secret")
+        || key == "ssl.key.pem"
+        || key == "sasl.oauthbearer.config"
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -672,7 +865,7 @@ mod tests {
         );
         assert_eq!(config.mechanism, SaslMechanism::ScramSha256);
         assert_eq!(config.username, "testuser");
-        assert_eq!(config.password, "testpass");
+        assert_eq!(config.password.expose(), "testpass");
     }
 
     #[test]
@@ -861,7 +1054,7 @@ mod tests {
         let sasl = config.sasl_config.unwrap();
         assert_eq!(sasl.mechanism, SaslMechanism::ScramSha256);
         assert_eq!(sasl.username, "testuser");
-        assert_eq!(sasl.password, "testpass");
+        assert_eq!(sasl.password.expose(), "testpass");
 
         cleanup_env_vars();
     }
@@ -902,4 +1095,98 @@ mod tests {
 
         cleanup_env_vars();
     }
+
+    #[test]
+    fn test_producer_config_applies_client_config_keys() {
+        let producer = KafkaProducerConfig {
+            acks: ProducerAcks::All,
+            enable_idempotence: true,
+            message_timeout_ms: 30000,
+            retries: 10,
+            retry_backoff_ms: 250,
+            compression_type: CompressionType::Zstd,
+            linger_ms: 20,
+            batch_num_messages: 500,
+        };
+        assert!(producer.validate().is_ok());
+
+        let mut client_config = ClientConfig::new();
+        producer.apply_to_client_config(&mut client_config);
+
+        assert_eq!(client_config.get("acks"), Some("all"));
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+        assert_eq!(client_config.get("message.timeout.ms"), Some("30000"));
+        assert_eq!(client_config.get("retries"), Some("10"));
+        assert_eq!(client_config.get("retry.backoff.ms"), Some("250"));
+        assert_eq!(client_config.get("compression.type"), Some("zstd"));
+        assert_eq!(client_config.get("linger.ms"), Some("20"));
+        assert_eq!(client_config.get("batch.num.messages"), Some("500"));
+    }
+
+    #[test]
+    fn test_producer_config_rejects_incompatible_settings() {
+        let idempotent_leader_acks = KafkaProducerConfig {
+            acks: ProducerAcks::Leader,
+            enable_idempotence: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            idempotent_leader_acks.validate(),
+            Err(ConfigError::InvalidProducerConfig(_))
+        ));
+
+        let idempotent_no_retries = KafkaProducerConfig {
+            enable_idempotence: true,
+            retries: 0,
+            ..Default::default()
+        };
+        assert!(idempotent_no_retries.validate().is_err());
+
+        let timeout_below_linger = KafkaProducerConfig {
+            message_timeout_ms: 10,
+            linger_ms: 50,
+            ..Default::default()
+        };
+        assert!(timeout_below_linger.validate().is_err());
+
+        let empty_batch = KafkaProducerConfig {
+            batch_num_messages: 0,
+            ..Default::default()
+        };
+        assert!(empty_batch.validate().is_err());
+
+        assert!(KafkaProducerConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_producer_config_deserialize() {
+        let yaml = r#"
+            acks: "1"
+            compression_type: lz4
+            linger_ms: 10
+        "#;
+
+        let config: KafkaProducerConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.acks, ProducerAcks::Leader);
+        assert_eq!(config.compression_type, CompressionType::Lz4);
+        assert_eq!(config.linger_ms, 10);
+        // Unset fields keep their defaults
+        assert_eq!(config.message_timeout_ms, 5000);
+
+        let config: KafkaProducerConfig = serde_yaml::from_str("acks: \"-1\"").unwrap();
+        assert_eq!(config.acks, ProducerAcks::All);
+    }
+
+    #[test]
+    fn test_redacted_client_config() {
+        let auth_config =
+            KafkaAuthConfig::scram_sha256_ssl("user".to_string(), "super-secret".to_string(), None);
+        let mut client_config = ClientConfig::new();
+        auth_config.apply_to_client_config(&mut client_config);
+
+        let redacted = redacted_client_config(&client_config);
+        assert_eq!(redacted.get("sasl.username").unwrap(), "user");
+        assert_eq!(redacted.get("sasl.password").unwrap(), "[REDACTED]");
+        assert!(!redacted.values().any(|v| v.contains("super-secret")));
+    }
 }
@@ -8,4 +8,4 @@ pub mod config;
 pub mod producer;
 pub mod topics;
 
-pub use topics::TopicManager;
+pub use topics::{KafkaTopicProvisioner, TopicManager, TopicProvisioner};
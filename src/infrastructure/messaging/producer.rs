@@ -3,21 +3,33 @@
 
 // src/infrastructure/messaging/producer.rs
 
+use async_trait::async_trait;
 use rdkafka::config::ClientConfig;
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::{Header, OwnedHeaders};
 use rdkafka::producer::{FutureProducer, FutureRecord};
 use serde_json;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
-use tracing::info;
+use tracing::{info, instrument};
 
 use crate::domain::entities::event::Event;
 use crate::error::{Error, InfrastructureError, Result};
+use crate::infrastructure::deadline;
 use crate::infrastructure::messaging::cloudevents::CloudEventMessage;
-use crate::infrastructure::messaging::config::KafkaAuthConfig;
+use crate::infrastructure::messaging::config::{
+    redacted_client_config, KafkaAuthConfig, KafkaProducerConfig,
+};
+use crate::infrastructure::tracing::current_trace_headers;
 
 /// Kafka event publisher for sending events to Kafka topics
 pub struct KafkaEventPublisher {
     producer: FutureProducer,
     topic: String,
+    effective_config: BTreeMap<String, String>,
+    publish_failures: AtomicU64,
+    last_send_failed: AtomicBool,
 }
 
 impl KafkaEventPublisher {
@@ -36,21 +48,7 @@ impl KafkaEventPublisher {
     /// let publisher = KafkaEventPublisher::new("localhost:9092", "xzepr.dev.events").unwrap();
     /// ```
     pub fn new(brokers: &str, topic: &str) -> Result<Self> {
-        let producer: FutureProducer = ClientConfig::new()
-            .set("bootstrap.servers", brokers)
-            .set("message.timeout.ms", "5000")
-            .set("client.id", "xzepr-event-publisher")
-            .create()
-            .map_err(|e| {
-                Error::Infrastructure(InfrastructureError::KafkaProducerError {
-                    message: format!("Failed to create Kafka producer: {}", e),
-                })
-            })?;
-
-        Ok(Self {
-            producer,
-            topic: topic.to_string(),
-        })
+        Self::with_auth(brokers, topic, None)
     }
 
     /// Create a new KafkaEventPublisher with authentication
@@ -101,16 +99,56 @@ impl KafkaEventPublisher {
         topic: &str,
         auth_config: Option<&KafkaAuthConfig>,
     ) -> Result<Self> {
-        let mut client_config = ClientConfig::new();
-        client_config
-            .set("bootstrap.servers", brokers)
-            .set("message.timeout.ms", "5000")
-            .set("client.id", "xzepr-event-publisher");
+        Self::with_config(brokers, topic, auth_config, &KafkaProducerConfig::default())
+    }
 
-        // Apply authentication configuration if provided
-        if let Some(auth) = auth_config {
-            auth.apply_to_client_config(&mut client_config);
-        }
+    /// Create a new KafkaEventPublisher with authentication and producer settings
+    ///
+    /// # Arguments
+    ///
+    /// * `brokers` - Comma-separated list of Kafka broker addresses
+    /// * `topic` - The Kafka topic to publish events to
+    /// * `auth_config` - Optional authentication configuration for SASL/SCRAM or SSL/TLS
+    /// * `producer_config` - Delivery guarantees, retries, compression, and batching
+    ///
+    /// # Errors
+    ///
+    /// Returns InfrastructureError if:
+    /// - The producer configuration combines incompatible settings
+    /// - Producer creation fails
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use xzepr::infrastructure::messaging::config::{KafkaProducerConfig, ProducerAcks};
+    /// use xzepr::infrastructure::messaging::producer::KafkaEventPublisher;
+    ///
+    /// let producer_config = KafkaProducerConfig {
+    ///     acks: ProducerAcks::All,
+    ///     enable_idempotence: true,
+    ///     ..Default::default()
+    /// };
+    /// let publisher = KafkaEventPublisher::with_config(
+    ///     "localhost:9092",
+    ///     "xzepr.dev.events",
+    ///     None,
+    ///     &producer_config,
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn with_config(
+        brokers: &str,
+        topic: &str,
+        auth_config: Option<&KafkaAuthConfig>,
+        producer_config: &KafkaProducerConfig,
+    ) -> Result<Self> {
+        producer_config.validate().map_err(|e| {
+            Error::Infrastructure(InfrastructureError::KafkaProducerError {
+                message: e.to_string(),
+            })
+        })?;
+
+        let client_config = Self::build_client_config(brokers, auth_config, producer_config);
 
         let producer: FutureProducer = client_config.create().map_err(|e| {
             Error::Infrastructure(InfrastructureError::KafkaProducerError {
@@ -121,9 +159,70 @@ impl KafkaEventPublisher {
         Ok(Self {
             producer,
             topic: topic.to_string(),
+            effective_config: redacted_client_config(&client_config),
+            publish_failures: AtomicU64::new(0),
+            last_send_failed: AtomicBool::new(false),
         })
     }
 
+    /// Build the rdkafka client configuration used by the publisher
+    ///
+    /// Producer settings are applied before authentication so neither can
+    /// override the other's keys.
+    pub fn build_client_config(
+        brokers: &str,
+        auth_config: Option<&KafkaAuthConfig>,
+        producer_config: &KafkaProducerConfig,
+    ) -> ClientConfig {
+        let mut client_config = ClientConfig::new();
+        client_config
+            .set("bootstrap.servers", brokers)
+            .set("client.id", "xzepr-event-publisher");
+
+        producer_config.apply_to_client_config(&mut client_config);
+
+        // Apply authentication configuration if provided
+        if let Some(auth) = auth_config {
+            auth.apply_to_client_config(&mut client_config);
+        }
+
+        client_config
+    }
+
+    /// The topic this publisher sends to
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Effective client configuration with secrets redacted
+    pub fn effective_config(&self) -> &BTreeMap<String, String> {
+        &self.effective_config
+    }
+
+    /// Number of sends that failed since the publisher was created
+    pub fn publish_failure_count(&self) -> u64 {
+        self.publish_failures.load(Ordering::Relaxed)
+    }
+
+    fn send_failed(&self, err: KafkaError, context: &str) -> Error {
+        self.publish_failures.fetch_add(1, Ordering::Relaxed);
+        self.last_send_failed.store(true, Ordering::Relaxed);
+        delivery_error(err, context)
+    }
+
+    /// Message headers carrying the trace of the current span, so consumers
+    /// can continue it
+    fn trace_headers() -> OwnedHeaders {
+        current_trace_headers()
+            .iter()
+            .fold(OwnedHeaders::new(), |headers, (key, value)| {
+                headers.insert(Header {
+                    key,
+                    value: Some(value),
+                })
+            })
+    }
+
     /// Publish an event to Kafka
     ///
     /// # Arguments
@@ -138,6 +237,33 @@ impl KafkaEventPublisher {
     ///
     /// Returns InfrastructureError if publishing fails
     pub async fn publish(&self, event: &Event) -> Result<()> {
+        self.publish_to(&self.topic, event).await
+    }
+
+    /// Publish an event to a topic other than the publisher's own
+    ///
+    /// Used to fan events out to the dedicated topics of receiver groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The Kafka topic to publish the event to
+    /// * `event` - The event to publish
+    ///
+    /// # Errors
+    ///
+    /// Returns InfrastructureError if publishing fails
+    #[instrument(
+        skip_all,
+        fields(
+            otel.name = %format!("{} publish", topic),
+            otel.kind = "producer",
+            messaging.system = "kafka",
+            messaging.destination.name = %topic,
+            messaging.operation = "publish",
+            messaging.message.id = %event.id()
+        )
+    )]
+    pub async fn publish_to(&self, topic: &str, event: &Event) -> Result<()> {
         // Convert Event to CloudEvents format for compatibility
         let cloudevent = CloudEventMessage::from_event(event);
 
@@ -149,22 +275,25 @@ impl KafkaEventPublisher {
 
         let key = event.id().to_string();
 
-        let record = FutureRecord::to(&self.topic).key(&key).payload(&payload);
+        let record = FutureRecord::to(topic)
+            .key(&key)
+            .payload(&payload)
+            .headers(Self::trace_headers());
 
-        self.producer
-            .send(record, Duration::from_secs(5))
-            .await
-            .map_err(|(err, _)| {
-                Error::Infrastructure(InfrastructureError::KafkaProducerError {
-                    message: format!("Failed to send event to Kafka: {}", err),
-                })
-            })?;
+        // Stops waiting for the delivery report at the request deadline
+        let delivery = self
+            .producer
+            .send(record, deadline::capped_timeout(Duration::from_secs(5)));
+        deadline::guard("kafka.publish", delivery)
+            .await?
+            .map_err(|(err, _)| self.send_failed(err, "Failed to send event to Kafka"))?;
+        self.last_send_failed.store(false, Ordering::Relaxed);
 
         info!(
             "Published CloudEvent {} (type: {}) to topic {}",
             event.id(),
             event.name(),
-            self.topic
+            topic
         );
 
         Ok(())
@@ -182,26 +311,72 @@ impl KafkaEventPublisher {
     ///
     /// # Errors
     ///
-    /// Returns InfrastructureError if publishing fails
+    /// Returns InfrastructureError::KafkaDeliveryError carrying the broker
+    /// error code if the message could not be delivered
+    #[instrument(
+        skip_all,
+        fields(
+            otel.name = %format!("{} publish", self.topic),
+            otel.kind = "producer",
+            messaging.system = "kafka",
+            messaging.destination.name = %self.topic,
+            messaging.operation = "publish",
+            messaging.message.id = %message.id
+        )
+    )]
     pub async fn publish_message(&self, message: &CloudEventMessage) -> Result<()> {
+        self.send_message(&message.id, message).await
+    }
+
+    /// Publish a CloudEventMessage to Kafka under a message key
+    ///
+    /// Messages sharing a key go to the same partition, so consumers see
+    /// them in publish order.
+    ///
+    /// # Errors
+    ///
+    /// Returns InfrastructureError::KafkaDeliveryError carrying the broker
+    /// error code if the message could not be delivered
+    #[instrument(
+        skip_all,
+        fields(
+            otel.name = %format!("{} publish", self.topic),
+            otel.kind = "producer",
+            messaging.system = "kafka",
+            messaging.destination.name = %self.topic,
+            messaging.operation = "publish",
+            messaging.message.id = %message.id,
+            messaging.kafka.message.key = %key
+        )
+    )]
+    pub async fn publish_message_with_key(
+        &self,
+        key: &str,
+        message: &CloudEventMessage,
+    ) -> Result<()> {
+        self.send_message(key, message).await
+    }
+
+    async fn send_message(&self, key: &str, message: &CloudEventMessage) -> Result<()> {
         let payload = serde_json::to_string(message).map_err(|e| {
             Error::Infrastructure(InfrastructureError::KafkaProducerError {
                 message: format!("Failed to serialize CloudEvent message: {}", e),
             })
         })?;
 
-        let key = message.id.clone();
+        let record = FutureRecord::to(&self.topic)
+            .key(key)
+            .payload(&payload)
+            .headers(Self::trace_headers());
 
-        let record = FutureRecord::to(&self.topic).key(&key).payload(&payload);
-
-        self.producer
-            .send(record, Duration::from_secs(5))
-            .await
-            .map_err(|(err, _)| {
-                Error::Infrastructure(InfrastructureError::KafkaProducerError {
-                    message: format!("Failed to send message to Kafka: {}", err),
-                })
-            })?;
+        // Stops waiting for the delivery report at the request deadline
+        let delivery = self
+            .producer
+            .send(record, deadline::capped_timeout(Duration::from_secs(5)));
+        deadline::guard("kafka.publish", delivery)
+            .await?
+            .map_err(|(err, _)| self.send_failed(err, "Failed to send message to Kafka"))?;
+        self.last_send_failed.store(false, Ordering::Relaxed);
 
         info!(
             "Published CloudEvent {} (type: {}) to topic {}",
@@ -212,6 +387,98 @@ impl KafkaEventPublisher {
     }
 }
 
+/// Sends stored events to the event stream
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    /// Publishes one event, failing if the stream did not accept it
+    async fn publish(&self, event: &Event) -> Result<()>;
+
+    /// Publishes one event to `topic` instead of the primary stream
+    async fn publish_to(&self, topic: &str, event: &Event) -> Result<()>;
+
+    /// Publishes a prepared CloudEvent to the primary stream
+    async fn publish_message(&self, message: &CloudEventMessage) -> Result<()>;
+
+    /// Publishes a prepared CloudEvent to the primary stream under `key`
+    ///
+    /// Messages with the same key keep their publish order. Publishers
+    /// without keyed partitions publish the message as is.
+    async fn publish_message_with_key(
+        &self,
+        _key: &str,
+        message: &CloudEventMessage,
+    ) -> Result<()> {
+        self.publish_message(message).await
+    }
+
+    /// Returns false while the most recent send failed
+    fn is_healthy(&self) -> bool {
+        true
+    }
+}
+
+#[async_trait]
+impl EventPublisher for KafkaEventPublisher {
+    async fn publish(&self, event: &Event) -> Result<()> {
+        KafkaEventPublisher::publish(self, event).await
+    }
+
+    async fn publish_to(&self, topic: &str, event: &Event) -> Result<()> {
+        KafkaEventPublisher::publish_to(self, topic, event).await
+    }
+
+    async fn publish_message(&self, message: &CloudEventMessage) -> Result<()> {
+        KafkaEventPublisher::publish_message(self, message).await
+    }
+
+    async fn publish_message_with_key(&self, key: &str, message: &CloudEventMessage) -> Result<()> {
+        KafkaEventPublisher::publish_message_with_key(self, key, message).await
+    }
+
+    fn is_healthy(&self) -> bool {
+        !self.last_send_failed.load(Ordering::Relaxed)
+    }
+}
+
+/// Returns true if a delivery failure with this code may succeed on retry
+///
+/// Transient broker, network, and timeout conditions are retriable. Anything
+/// else, including a missing topic, an oversized message, or an
+/// authorization failure, will fail again and should go to a dead letter
+/// path instead.
+pub fn is_retriable_delivery_error(code: RDKafkaErrorCode) -> bool {
+    matches!(
+        code,
+        RDKafkaErrorCode::MessageTimedOut
+            | RDKafkaErrorCode::QueueFull
+            | RDKafkaErrorCode::RequestTimedOut
+            | RDKafkaErrorCode::LeaderNotAvailable
+            | RDKafkaErrorCode::NotLeaderForPartition
+            | RDKafkaErrorCode::NotEnoughReplicas
+            | RDKafkaErrorCode::NotEnoughReplicasAfterAppend
+            | RDKafkaErrorCode::NetworkException
+            | RDKafkaErrorCode::BrokerNotAvailable
+            | RDKafkaErrorCode::AllBrokersDown
+            | RDKafkaErrorCode::BrokerTransportFailure
+            | RDKafkaErrorCode::KafkaStorageError
+            | RDKafkaErrorCode::Resolve
+    )
+}
+
+/// Converts a send failure into a typed delivery error
+fn delivery_error(err: KafkaError, context: &str) -> Error {
+    let (code, retriable) = match err.rdkafka_error_code() {
+        Some(code) => (format!("{:?}", code), is_retriable_delivery_error(code)),
+        None => ("Unknown".to_string(), false),
+    };
+
+    Error::Infrastructure(InfrastructureError::KafkaDeliveryError {
+        code,
+        retriable,
+        message: format!("{}: {}", context, err),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -297,7 +564,7 @@ mod tests {
         let sasl_config = SaslConfig {
             mechanism: SaslMechanism::ScramSha256,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         };
 
         let auth_config = KafkaAuthConfig {
@@ -323,7 +590,7 @@ mod tests {
         let sasl_config = SaslConfig {
             mechanism: SaslMechanism::ScramSha512,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         };
 
         let auth_config = KafkaAuthConfig {
@@ -347,7 +614,7 @@ mod tests {
         let sasl_config = SaslConfig {
             mechanism: SaslMechanism::Plain,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         };
 
         let auth_config = KafkaAuthConfig {
@@ -373,7 +640,7 @@ mod tests {
         let sasl_config = SaslConfig {
             mechanism: SaslMechanism::ScramSha256,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         };
 
         let auth_config = KafkaAuthConfig {
@@ -399,4 +666,88 @@ mod tests {
         assert!(result_new.is_ok());
         assert!(result_with_auth.is_ok());
     }
+
+    #[test]
+    fn test_kafka_publisher_with_config() {
+        use crate::infrastructure::messaging::config::{
+            CompressionType, KafkaAuthConfig, ProducerAcks,
+        };
+
+        let producer_config = KafkaProducerConfig {
+            enable_idempotence: true,
+            compression_type: CompressionType::Lz4,
+            ..Default::default()
+        };
+        let auth_config =
+            KafkaAuthConfig::scram_sha256_ssl("user".to_string(), "password".to_string(), None);
+
+        let client_config = KafkaEventPublisher::build_client_config(
+            "localhost:9092",
+            Some(&auth_config),
+            &producer_config,
+        );
+        assert_eq!(
+            client_config.get("bootstrap.servers"),
+            Some("localhost:9092")
+        );
+        assert_eq!(client_config.get("enable.idempotence"), Some("true"));
+        assert_eq!(client_config.get("compression.type"), Some("lz4"));
+        assert_eq!(client_config.get("sasl.username"), Some("user"));
+
+        let publisher = KafkaEventPublisher::with_config(
+            "localhost:9092",
+            "test-topic",
+            None,
+            &producer_config,
+        )
+        .unwrap();
+        assert_eq!(publisher.topic(), "test-topic");
+        assert_eq!(
+            publisher.effective_config().get("acks").map(String::as_str),
+            Some("all")
+        );
+
+        // Incompatible settings are rejected before the producer is built
+        let invalid = KafkaProducerConfig {
+            acks: ProducerAcks::Leader,
+            enable_idempotence: true,
+            ..Default::default()
+        };
+        assert!(
+            KafkaEventPublisher::with_config("localhost:9092", "test-topic", None, &invalid)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_delivery_error_classification() {
+        assert!(is_retriable_delivery_error(
+            RDKafkaErrorCode::MessageTimedOut
+        ));
+        assert!(is_retriable_delivery_error(
+            RDKafkaErrorCode::NotLeaderForPartition
+        ));
+        assert!(!is_retriable_delivery_error(
+            RDKafkaErrorCode::UnknownTopicOrPartition
+        ));
+        assert!(!is_retriable_delivery_error(
+            RDKafkaErrorCode::MessageSizeTooLarge
+        ));
+
+        let err = delivery_error(
+            KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
+            "Failed to send message to Kafka",
+        );
+        match err {
+            Error::Infrastructure(InfrastructureError::KafkaDeliveryError {
+                code,
+                retriable,
+                ..
+            }) => {
+                assert_eq!(code, "QueueFull");
+                assert!(retriable);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
 }
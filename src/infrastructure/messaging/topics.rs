@@ -3,6 +3,7 @@
 
 // src/infrastructure/messaging/topics.rs
 
+use async_trait::async_trait;
 use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
 use rdkafka::client::DefaultClientContext;
 use rdkafka::config::ClientConfig;
@@ -252,6 +253,41 @@ impl TopicManager {
     }
 }
 
+/// Creates topics on demand, such as the dedicated topics of receiver groups
+#[async_trait]
+pub trait TopicProvisioner: Send + Sync {
+    /// Ensures `topic` exists; returns true if it was created
+    async fn ensure_topic(&self, topic: &str) -> Result<bool>;
+}
+
+/// Provisions topics through a [`TopicManager`] with fixed partition and
+/// replication settings
+pub struct KafkaTopicProvisioner {
+    manager: TopicManager,
+    num_partitions: i32,
+    replication_factor: i32,
+}
+
+impl KafkaTopicProvisioner {
+    /// Creates a provisioner creating topics with the given settings
+    pub fn new(manager: TopicManager, num_partitions: i32, replication_factor: i32) -> Self {
+        Self {
+            manager,
+            num_partitions,
+            replication_factor,
+        }
+    }
+}
+
+#[async_trait]
+impl TopicProvisioner for KafkaTopicProvisioner {
+    async fn ensure_topic(&self, topic: &str) -> Result<bool> {
+        self.manager
+            .ensure_topic_exists(topic, self.num_partitions, self.replication_factor)
+            .await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,7 +340,7 @@ mod tests {
         let sasl_config = SaslConfig {
             mechanism: SaslMechanism::ScramSha256,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         };
 
         let auth_config = KafkaAuthConfig {
@@ -329,7 +365,7 @@ mod tests {
         let sasl_config = SaslConfig {
             mechanism: SaslMechanism::ScramSha512,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         };
 
         let auth_config = KafkaAuthConfig {
@@ -352,7 +388,7 @@ mod tests {
         let sasl_config = SaslConfig {
             mechanism: SaslMechanism::Plain,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         };
 
         let auth_config = KafkaAuthConfig {
@@ -377,7 +413,7 @@ mod tests {
         let sasl_config = SaslConfig {
             mechanism: SaslMechanism::ScramSha256,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         };
 
         let auth_config = KafkaAuthConfig {
@@ -0,0 +1,250 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/metrics/exemplars.rs
+
+//! Exemplars linking histogram observations to traces
+//!
+//! A histogram bucket keeps at most one exemplar per sampling interval, so
+//! memory is bounded by the number of buckets however many observations
+//! are made. Exemplars are only served in the OpenMetrics format.
+
+use prometheus::{HistogramOpts, HistogramVec, Registry, DEFAULT_BUCKETS};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// Default time a bucket keeps its exemplar before another may replace it,
+/// matching a common scrape interval
+pub const DEFAULT_EXEMPLAR_INTERVAL: Duration = Duration::from_secs(15);
+
+/// An observation and the trace it was made in
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exemplar {
+    /// OpenTelemetry trace id, 32 lowercase hex digits
+    pub trace_id: String,
+    pub value: f64,
+    pub timestamp: SystemTime,
+}
+
+/// A bucket of one series of a histogram
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BucketKey {
+    /// Label values in the order the histogram declares its labels
+    label_values: Vec<String>,
+    /// Index of the bucket; the `+Inf` bucket comes after the last bound
+    bucket: usize,
+}
+
+/// Keeps at most one exemplar per bucket per interval
+#[derive(Debug)]
+pub struct ExemplarSampler {
+    interval: Duration,
+    exemplars: Mutex<HashMap<BucketKey, Exemplar>>,
+}
+
+impl ExemplarSampler {
+    /// Creates a sampler replacing a bucket's exemplar at most once per
+    /// `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            exemplars: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Offers an observation as the exemplar of its bucket
+    ///
+    /// It is kept if the bucket has no exemplar yet or the current one is
+    /// at least `interval` old. Returns whether it was kept.
+    fn offer(&self, key: BucketKey, exemplar: Exemplar) -> bool {
+        let mut exemplars = self.exemplars.lock().unwrap();
+        if let Some(current) = exemplars.get(&key) {
+            let age = exemplar.timestamp.duration_since(current.timestamp).ok();
+            if age.is_none_or(|age| age < self.interval) {
+                return false;
+            }
+        }
+        exemplars.insert(key, exemplar);
+        true
+    }
+
+    fn get(&self, key: &BucketKey) -> Option<Exemplar> {
+        self.exemplars.lock().unwrap().get(key).cloned()
+    }
+
+    /// Returns the number of buckets holding an exemplar
+    pub fn len(&self) -> usize {
+        self.exemplars.lock().unwrap().len()
+    }
+
+    /// Returns true if no bucket holds an exemplar
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A histogram vector that samples exemplars of traced observations
+///
+/// Observations are counted by a plain [`HistogramVec`], so the classic
+/// text format is unaffected; exemplars are kept next to it.
+#[derive(Clone)]
+pub struct ExemplarHistogramVec {
+    histogram: HistogramVec,
+    name: String,
+    label_names: Vec<String>,
+    buckets: Vec<f64>,
+    sampler: Arc<ExemplarSampler>,
+}
+
+impl ExemplarHistogramVec {
+    /// Creates a histogram vector whose buckets keep at most one exemplar
+    /// per `interval`
+    pub fn new(
+        opts: HistogramOpts,
+        label_names: &[&str],
+        interval: Duration,
+    ) -> Result<Self, prometheus::Error> {
+        let name = opts.common_opts.fq_name();
+        let buckets = if opts.buckets.is_empty() {
+            DEFAULT_BUCKETS.to_vec()
+        } else {
+            opts.buckets.clone()
+        };
+        Ok(Self {
+            histogram: HistogramVec::new(opts, label_names)?,
+            name,
+            label_names: label_names.iter().map(|name| name.to_string()).collect(),
+            buckets,
+            sampler: Arc::new(ExemplarSampler::new(interval)),
+        })
+    }
+
+    /// Registers the histogram with `registry`
+    pub fn register(&self, registry: &Registry) -> Result<(), prometheus::Error> {
+        registry.register(Box::new(self.histogram.clone()))
+    }
+
+    /// Returns the fully qualified metric name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the sampler holding the exemplars
+    pub fn sampler(&self) -> &ExemplarSampler {
+        &self.sampler
+    }
+
+    /// Observes `value`, offering it as an exemplar when `trace_id` is set
+    pub fn observe(&self, label_values: &[&str], value: f64, trace_id: Option<&str>) {
+        self.histogram
+            .with_label_values(label_values)
+            .observe(value);
+
+        if let Some(trace_id) = trace_id {
+            let bucket = self
+                .buckets
+                .iter()
+                .position(|bound| value <= *bound)
+                .unwrap_or(self.buckets.len());
+            self.sampler.offer(
+                BucketKey {
+                    label_values: label_values.iter().map(|v| v.to_string()).collect(),
+                    bucket,
+                },
+                Exemplar {
+                    trace_id: trace_id.to_string(),
+                    value,
+                    timestamp: SystemTime::now(),
+                },
+            );
+        }
+    }
+
+    /// Returns the exemplar of a bucket of the series labelled `labels`
+    ///
+    /// `labels` are name and value pairs in any order, as gathered from a
+    /// registry. The `+Inf` bucket's index is the number of bounds.
+    pub fn exemplar(&self, labels: &[(&str, &str)], bucket: usize) -> Option<Exemplar> {
+        let label_values = self
+            .label_names
+            .iter()
+            .map(|name| {
+                labels
+                    .iter()
+                    .find(|(label, _)| label == name)
+                    .map(|(_, value)| value.to_string())
+            })
+            .collect::<Option<Vec<_>>>()?;
+        self.sampler.get(&BucketKey {
+            label_values,
+            bucket,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACE_ID: &str = "0af7651916cd43dd8448eb211c80319c";
+
+    fn latency_histogram(interval: Duration) -> ExemplarHistogramVec {
+        ExemplarHistogramVec::new(
+            HistogramOpts::new("test_duration_seconds", "Test durations").buckets(vec![0.1, 1.0]),
+            &["route"],
+            interval,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_exemplars_are_kept_per_bucket() {
+        let histogram = latency_histogram(DEFAULT_EXEMPLAR_INTERVAL);
+        histogram.observe(&["/a"], 0.05, Some(TRACE_ID));
+        histogram.observe(&["/a"], 5.0, Some("1af7651916cd43dd8448eb211c80319c"));
+        histogram.observe(&["/a"], 0.5, None);
+
+        let exemplar = histogram.exemplar(&[("route", "/a")], 0).unwrap();
+        assert_eq!(exemplar.trace_id, TRACE_ID);
+        assert_eq!(exemplar.value, 0.05);
+        assert!(histogram.exemplar(&[("route", "/a")], 1).is_none());
+        assert_eq!(
+            histogram.exemplar(&[("route", "/a")], 2).unwrap().value,
+            5.0
+        );
+        assert!(histogram.exemplar(&[("route", "/b")], 0).is_none());
+        assert!(histogram.exemplar(&[], 0).is_none());
+    }
+
+    #[test]
+    fn test_sampling_keeps_one_exemplar_per_bucket_and_interval() {
+        let histogram = latency_histogram(Duration::from_secs(3600));
+        for i in 0..10_000 {
+            let trace_id = format!("{:032x}", i + 1);
+            histogram.observe(&["/a"], 0.01, Some(&trace_id));
+            histogram.observe(
+                &[if i % 2 == 0 { "/a" } else { "/b" }],
+                0.5,
+                Some(&trace_id),
+            );
+        }
+
+        // Three buckets were hit, each keeping its first exemplar
+        assert_eq!(histogram.sampler().len(), 3);
+        assert_eq!(
+            histogram.exemplar(&[("route", "/a")], 0).unwrap().trace_id,
+            format!("{:032x}", 1)
+        );
+
+        // Once the interval has passed, a new observation replaces it
+        let histogram = latency_histogram(Duration::ZERO);
+        histogram.observe(&["/a"], 0.01, Some(TRACE_ID));
+        histogram.observe(&["/a"], 0.02, Some("1af7651916cd43dd8448eb211c80319c"));
+        assert_eq!(histogram.sampler().len(), 1);
+        assert_eq!(
+            histogram.exemplar(&[("route", "/a")], 0).unwrap().value,
+            0.02
+        );
+    }
+}
@@ -1,13 +1,20 @@
 // SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
 // SPDX-License-Identifier: Apache-2.0
 
-// src/infrastructure/metrics.rs
+// src/infrastructure/metrics/mod.rs
 
+pub mod exemplars;
+pub mod openmetrics;
+
+use exemplars::{ExemplarHistogramVec, DEFAULT_EXEMPLAR_INTERVAL};
 use prometheus::{
-    CounterVec, Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry, TextEncoder,
+    Counter, CounterVec, Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, Opts, Registry,
+    TextEncoder,
 };
 use std::sync::Arc;
 
+use crate::infrastructure::tracing::current_trace_id;
+
 /// Prometheus metrics for security and application monitoring
 #[derive(Clone)]
 pub struct PrometheusMetrics {
@@ -37,8 +44,27 @@ pub struct PrometheusMetrics {
 
     // Application metrics
     http_requests_total: CounterVec,
-    http_request_duration_seconds: HistogramVec,
+    http_request_duration_seconds: ExemplarHistogramVec,
     active_connections: Gauge,
+    http_open_connections: Gauge,
+    http_connections_rejected_total: Counter,
+    system_event_failures_total: CounterVec,
+    event_publish_outcomes_total: CounterVec,
+    group_topic_publish_outcomes_total: CounterVec,
+    delivery_failures_total: CounterVec,
+    job_runs_total: CounterVec,
+    job_duration_seconds: HistogramVec,
+    job_last_run_timestamp_seconds: GaugeVec,
+    event_payload_stored_bytes: Gauge,
+    event_payload_saved_bytes: Gauge,
+    domain_event_subscriber_failures_total: CounterVec,
+    domain_event_subscriber_lagged_total: CounterVec,
+    domain_event_subscriber_pending: GaugeVec,
+    lookup_cache_requests_total: CounterVec,
+    event_ingestion_duration_seconds: HistogramVec,
+    event_ingestion_stage_duration_seconds: ExemplarHistogramVec,
+    graphql_persisted_queries_total: CounterVec,
+    request_timeouts_total: CounterVec,
 
     // System metrics
     uptime_seconds: Gauge,
@@ -204,7 +230,7 @@ impl PrometheusMetrics {
         )?;
         registry.register(Box::new(http_requests_total.clone()))?;
 
-        let http_request_duration_seconds = HistogramVec::new(
+        let http_request_duration_seconds = ExemplarHistogramVec::new(
             HistogramOpts::new(
                 "xzepr_http_request_duration_seconds",
                 "HTTP request duration in seconds",
@@ -213,13 +239,181 @@ impl PrometheusMetrics {
                 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
             ]),
             &["method", "path", "status"],
+            DEFAULT_EXEMPLAR_INTERVAL,
         )?;
-        registry.register(Box::new(http_request_duration_seconds.clone()))?;
+        http_request_duration_seconds.register(&registry)?;
 
         let active_connections =
             Gauge::new("xzepr_active_connections", "Number of active connections")?;
         registry.register(Box::new(active_connections.clone()))?;
 
+        let http_open_connections = Gauge::new(
+            "xzepr_http_open_connections",
+            "Number of client connections currently being served",
+        )?;
+        registry.register(Box::new(http_open_connections.clone()))?;
+
+        let http_connections_rejected_total = Counter::new(
+            "xzepr_http_connections_rejected_total",
+            "Total number of connections answered with 503 at the connection limit",
+        )?;
+        registry.register(Box::new(http_connections_rejected_total.clone()))?;
+
+        let system_event_failures_total = CounterVec::new(
+            Opts::new(
+                "xzepr_system_event_failures_total",
+                "Total number of system events that could not be constructed",
+            ),
+            &["event_type"],
+        )?;
+        registry.register(Box::new(system_event_failures_total.clone()))?;
+
+        let event_publish_outcomes_total = CounterVec::new(
+            Opts::new(
+                "xzepr_event_publish_outcomes_total",
+                "Total number of stored events by Kafka publish outcome",
+            ),
+            &["outcome"],
+        )?;
+        registry.register(Box::new(event_publish_outcomes_total.clone()))?;
+
+        let group_topic_publish_outcomes_total = CounterVec::new(
+            Opts::new(
+                "xzepr_group_topic_publish_outcomes_total",
+                "Total number of event fan-outs to group topics by publish outcome",
+            ),
+            &["topic", "outcome"],
+        )?;
+        registry.register(Box::new(group_topic_publish_outcomes_total.clone()))?;
+
+        let delivery_failures_total = CounterVec::new(
+            Opts::new(
+                "xzepr_delivery_failures_total",
+                "Total number of deliveries given up on after their last retry",
+            ),
+            &["mechanism", "error_class"],
+        )?;
+        registry.register(Box::new(delivery_failures_total.clone()))?;
+
+        let job_runs_total = CounterVec::new(
+            Opts::new(
+                "xzepr_job_runs_total",
+                "Total number of background job runs by outcome",
+            ),
+            &["job", "outcome"],
+        )?;
+        registry.register(Box::new(job_runs_total.clone()))?;
+
+        let job_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "xzepr_job_duration_seconds",
+                "Background job run duration in seconds",
+            )
+            .buckets(vec![0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 15.0, 60.0, 300.0]),
+            &["job"],
+        )?;
+        registry.register(Box::new(job_duration_seconds.clone()))?;
+
+        let job_last_run_timestamp_seconds = GaugeVec::new(
+            Opts::new(
+                "xzepr_job_last_run_timestamp_seconds",
+                "Unix time at which each background job last finished",
+            ),
+            &["job"],
+        )?;
+        registry.register(Box::new(job_last_run_timestamp_seconds.clone()))?;
+
+        let event_payload_stored_bytes = Gauge::new(
+            "xzepr_event_payload_stored_bytes",
+            "Bytes held by payloads stored once and shared by events",
+        )?;
+        registry.register(Box::new(event_payload_stored_bytes.clone()))?;
+
+        let event_payload_saved_bytes = Gauge::new(
+            "xzepr_event_payload_saved_bytes",
+            "Bytes not stored because events share identical payloads",
+        )?;
+        registry.register(Box::new(event_payload_saved_bytes.clone()))?;
+
+        let domain_event_subscriber_failures_total = CounterVec::new(
+            Opts::new(
+                "xzepr_domain_event_subscriber_failures_total",
+                "Total number of domain notifications a subscriber failed to handle",
+            ),
+            &["subscriber", "event", "reason"],
+        )?;
+        registry.register(Box::new(domain_event_subscriber_failures_total.clone()))?;
+
+        let domain_event_subscriber_lagged_total = CounterVec::new(
+            Opts::new(
+                "xzepr_domain_event_subscriber_lagged_total",
+                "Total number of domain notifications skipped by subscribers that fell behind",
+            ),
+            &["subscriber"],
+        )?;
+        registry.register(Box::new(domain_event_subscriber_lagged_total.clone()))?;
+
+        let domain_event_subscriber_pending = GaugeVec::new(
+            Opts::new(
+                "xzepr_domain_event_subscriber_pending",
+                "Domain notifications queued for each subscriber",
+            ),
+            &["subscriber"],
+        )?;
+        registry.register(Box::new(domain_event_subscriber_pending.clone()))?;
+
+        let lookup_cache_requests_total = CounterVec::new(
+            Opts::new(
+                "xzepr_lookup_cache_requests_total",
+                "Receiver and API key lookups by cache result",
+            ),
+            &["cache", "result"],
+        )?;
+        registry.register(Box::new(lookup_cache_requests_total.clone()))?;
+
+        let event_ingestion_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "xzepr_event_ingestion_duration_seconds",
+                "Time to ingest one event, by channel and outcome",
+            )
+            .buckets(vec![
+                0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+            ]),
+            &["channel", "outcome"],
+        )?;
+        registry.register(Box::new(event_ingestion_duration_seconds.clone()))?;
+
+        let event_ingestion_stage_duration_seconds = ExemplarHistogramVec::new(
+            HistogramOpts::new(
+                "xzepr_event_ingestion_stage_duration_seconds",
+                "Time spent in each event ingestion stage, by channel",
+            )
+            .buckets(vec![
+                0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+            ]),
+            &["stage", "channel"],
+            DEFAULT_EXEMPLAR_INTERVAL,
+        )?;
+        event_ingestion_stage_duration_seconds.register(&registry)?;
+
+        let graphql_persisted_queries_total = CounterVec::new(
+            Opts::new(
+                "xzepr_graphql_persisted_queries_total",
+                "GraphQL requests using persisted queries, by result",
+            ),
+            &["result"],
+        )?;
+        registry.register(Box::new(graphql_persisted_queries_total.clone()))?;
+
+        let request_timeouts_total = CounterVec::new(
+            Opts::new(
+                "xzepr_request_timeouts_total",
+                "Requests aborted at their deadline, by whose deadline it was",
+            ),
+            &["reason"],
+        )?;
+        registry.register(Box::new(request_timeouts_total.clone()))?;
+
         // System metrics
         let uptime_seconds = Gauge::new("xzepr_uptime_seconds", "Server uptime in seconds")?;
         registry.register(Box::new(uptime_seconds.clone()))?;
@@ -245,6 +439,25 @@ impl PrometheusMetrics {
             http_requests_total,
             http_request_duration_seconds,
             active_connections,
+            http_open_connections,
+            http_connections_rejected_total,
+            system_event_failures_total,
+            event_publish_outcomes_total,
+            group_topic_publish_outcomes_total,
+            delivery_failures_total,
+            job_runs_total,
+            job_duration_seconds,
+            job_last_run_timestamp_seconds,
+            event_payload_stored_bytes,
+            event_payload_saved_bytes,
+            domain_event_subscriber_failures_total,
+            domain_event_subscriber_lagged_total,
+            domain_event_subscriber_pending,
+            lookup_cache_requests_total,
+            event_ingestion_duration_seconds,
+            event_ingestion_stage_duration_seconds,
+            graphql_persisted_queries_total,
+            request_timeouts_total,
             uptime_seconds,
             info,
             opa_authorization_requests_total,
@@ -331,15 +544,32 @@ impl PrometheusMetrics {
 
     /// Records an HTTP request
     pub fn record_http_request(&self, method: &str, path: &str, status: u16, duration_secs: f64) {
+        self.record_http_request_with_trace(method, path, status, duration_secs, None);
+    }
+
+    /// Records an HTTP request made in a trace
+    ///
+    /// The duration may become the exemplar of its bucket, linking it to
+    /// `trace_id` in OpenMetrics scrapes.
+    pub fn record_http_request_with_trace(
+        &self,
+        method: &str,
+        path: &str,
+        status: u16,
+        duration_secs: f64,
+        trace_id: Option<&str>,
+    ) {
         let status_str = status.to_string();
 
         self.http_requests_total
             .with_label_values(&[method, path, &status_str])
             .inc();
 
-        self.http_request_duration_seconds
-            .with_label_values(&[method, path, &status_str])
-            .observe(duration_secs);
+        self.http_request_duration_seconds.observe(
+            &[method, path, &status_str],
+            duration_secs,
+            trace_id,
+        );
     }
 
     /// Sets the number of active connections
@@ -357,6 +587,150 @@ impl PrometheusMetrics {
         self.active_connections.dec();
     }
 
+    /// Records a system event that could not be constructed
+    pub fn record_system_event_failure(&self, event_type: &str) {
+        self.system_event_failures_total
+            .with_label_values(&[event_type])
+            .inc();
+    }
+
+    /// Records how the publication of a stored event ended
+    ///
+    /// Outcomes are `published`, `deferred`, `rejected`, `retried`, and
+    /// `dead_lettered`.
+    pub fn record_event_publish_outcome(&self, outcome: &str) {
+        self.event_publish_outcomes_total
+            .with_label_values(&[outcome])
+            .inc();
+    }
+
+    /// Records how the fan-out of an event to a group topic ended
+    ///
+    /// Outcomes are `published`, `deferred`, `retried`, `dead_lettered`,
+    /// and `dropped`.
+    pub fn record_group_topic_publish_outcome(&self, topic: &str, outcome: &str) {
+        self.group_topic_publish_outcomes_total
+            .with_label_values(&[topic, outcome])
+            .inc();
+    }
+
+    /// Records a delivery given up on after its last retry
+    ///
+    /// Mechanisms are `event_publish` and `group_topic_fanout`; error
+    /// classes are `timeout`, `unavailable`, `rejected`, and `other`.
+    pub fn record_delivery_failure(&self, mechanism: &str, error_class: &str) {
+        self.delivery_failures_total
+            .with_label_values(&[mechanism, error_class])
+            .inc();
+    }
+
+    /// Records a finished background job run
+    ///
+    /// Outcomes are `success`, `failure`, and `panic`.
+    pub fn record_job_run(&self, job: &str, outcome: &str, duration_secs: f64) {
+        self.job_runs_total.with_label_values(&[job, outcome]).inc();
+        self.job_duration_seconds
+            .with_label_values(&[job])
+            .observe(duration_secs);
+        self.job_last_run_timestamp_seconds
+            .with_label_values(&[job])
+            .set(chrono::Utc::now().timestamp() as f64);
+    }
+
+    /// Sets the number of client connections being served
+    pub fn set_http_open_connections(&self, count: usize) {
+        self.http_open_connections.set(count as f64);
+    }
+
+    /// Records a connection turned away at the connection limit
+    pub fn record_http_connection_rejected(&self) {
+        self.http_connections_rejected_total.inc();
+    }
+
+    /// Updates the size of the shared payload store and the bytes it saves
+    pub fn set_event_payload_bytes(&self, stored_bytes: u64, saved_bytes: u64) {
+        self.event_payload_stored_bytes.set(stored_bytes as f64);
+        self.event_payload_saved_bytes.set(saved_bytes as f64);
+    }
+
+    /// Records a domain notification a subscriber failed to handle
+    ///
+    /// Reasons are `error` and `panic`.
+    pub fn record_domain_event_failure(&self, subscriber: &str, event: &str, reason: &str) {
+        self.domain_event_subscriber_failures_total
+            .with_label_values(&[subscriber, event, reason])
+            .inc();
+    }
+
+    /// Records domain notifications a lagging subscriber skipped
+    pub fn record_domain_event_lag(&self, subscriber: &str, skipped: u64) {
+        self.domain_event_subscriber_lagged_total
+            .with_label_values(&[subscriber])
+            .inc_by(skipped as f64);
+    }
+
+    /// Sets the number of domain notifications queued for a subscriber
+    pub fn set_domain_event_pending(&self, subscriber: &str, pending: usize) {
+        self.domain_event_subscriber_pending
+            .with_label_values(&[subscriber])
+            .set(pending as f64);
+    }
+
+    /// Records a lookup served by, or missing, a lookup cache
+    ///
+    /// Caches are `event_receiver` and `api_key`. Results are `hit`,
+    /// `negative_hit` for a cached not-found answer, and `miss`.
+    pub fn record_lookup_cache(&self, cache: &str, result: &str) {
+        self.lookup_cache_requests_total
+            .with_label_values(&[cache, result])
+            .inc();
+    }
+
+    /// Records a GraphQL request that used, or was refused, a persisted query
+    ///
+    /// Results are `hit` for a hash found in the store, `miss` for query
+    /// text sent with its hash and stored, `not_found` for an unknown hash,
+    /// and `rejected` for a hash mismatch or a query refused in
+    /// persisted-only mode.
+    pub fn record_persisted_query(&self, result: &str) {
+        self.graphql_persisted_queries_total
+            .with_label_values(&[result])
+            .inc();
+    }
+
+    /// Records a request aborted at its deadline
+    ///
+    /// Reasons are `deadline_exceeded` when the client's
+    /// `X-Request-Deadline` ran out and `server_timeout` when the route's
+    /// own budget did.
+    pub fn record_request_timeout(&self, reason: &str) {
+        self.request_timeouts_total
+            .with_label_values(&[reason])
+            .inc();
+    }
+
+    /// Records the total time to ingest one event
+    ///
+    /// Outcomes are `stored`, `sampled_out`, and `rejected`.
+    pub fn record_ingestion(&self, channel: &str, outcome: &str, duration_secs: f64) {
+        self.event_ingestion_duration_seconds
+            .with_label_values(&[channel, outcome])
+            .observe(duration_secs);
+    }
+
+    /// Records the time one event spent in an ingestion stage
+    ///
+    /// The trace of the current span, if it is sampled, may become the
+    /// exemplar of the observed bucket.
+    pub fn record_ingestion_stage(&self, stage: &str, channel: &str, duration_secs: f64) {
+        let trace_id = current_trace_id();
+        self.event_ingestion_stage_duration_seconds.observe(
+            &[stage, channel],
+            duration_secs,
+            trace_id.as_deref(),
+        );
+    }
+
     /// Updates the uptime gauge
     pub fn update_uptime(&self, uptime_secs: u64) {
         self.uptime_seconds.set(uptime_secs as f64);
@@ -446,6 +820,18 @@ impl PrometheusMetrics {
         })
     }
 
+    /// Gathers all metrics and returns them in the OpenMetrics text format,
+    /// with exemplars on the latency histograms
+    pub fn gather_openmetrics(&self) -> String {
+        openmetrics::encode(
+            &self.registry.gather(),
+            &[
+                &self.http_request_duration_seconds,
+                &self.event_ingestion_stage_duration_seconds,
+            ],
+        )
+    }
+
     /// Gets a reference to the registry
     pub fn registry(&self) -> &Registry {
         &self.registry
@@ -706,4 +1092,148 @@ mod tests {
         assert!(output.contains("xzepr_opa_cache_hits_total"));
         assert!(output.contains("xzepr_opa_cache_misses_total"));
     }
+
+    #[test]
+    fn test_record_system_event_failure() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_system_event_failure("xzepr.event.receiver.group.created");
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_system_event_failures_total{event_type=\"xzepr.event.receiver.group.created\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_record_job_run() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_job_run("event_retention", "success", 0.2);
+        metrics.record_job_run("event_retention", "panic", 0.1);
+
+        let output = metrics.gather().unwrap();
+        assert!(
+            output.contains("xzepr_job_runs_total{job=\"event_retention\",outcome=\"success\"} 1")
+        );
+        assert!(
+            output.contains("xzepr_job_runs_total{job=\"event_retention\",outcome=\"panic\"} 1")
+        );
+        assert!(output.contains("xzepr_job_duration_seconds_count{job=\"event_retention\"} 2"));
+        assert!(output.contains("xzepr_job_last_run_timestamp_seconds{job=\"event_retention\"}"));
+    }
+
+    #[test]
+    fn test_record_event_publish_outcome() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_event_publish_outcome("published");
+        metrics.record_event_publish_outcome("deferred");
+        metrics.record_event_publish_outcome("deferred");
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains("xzepr_event_publish_outcomes_total{outcome=\"published\"} 1"));
+        assert!(output.contains("xzepr_event_publish_outcomes_total{outcome=\"deferred\"} 2"));
+    }
+
+    #[test]
+    fn test_record_group_topic_publish_outcome() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_group_topic_publish_outcome("payments.events", "published");
+        metrics.record_group_topic_publish_outcome("payments.events", "deferred");
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_group_topic_publish_outcomes_total{outcome=\"deferred\",topic=\"payments.events\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_record_delivery_failure() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_delivery_failure("group_topic_fanout", "timeout");
+        metrics.record_delivery_failure("group_topic_fanout", "timeout");
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_delivery_failures_total{error_class=\"timeout\",mechanism=\"group_topic_fanout\"} 2"
+        ));
+    }
+
+    #[test]
+    fn test_set_event_payload_bytes() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.set_event_payload_bytes(2048, 6144);
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains("xzepr_event_payload_stored_bytes 2048"));
+        assert!(output.contains("xzepr_event_payload_saved_bytes 6144"));
+    }
+
+    #[test]
+    fn test_http_connection_metrics() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.set_http_open_connections(12);
+        metrics.record_http_connection_rejected();
+        metrics.record_http_connection_rejected();
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains("xzepr_http_open_connections 12"));
+        assert!(output.contains("xzepr_http_connections_rejected_total 2"));
+    }
+
+    #[test]
+    fn test_domain_event_subscriber_metrics() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_domain_event_failure("kafka_forwarder", "group_created", "error");
+        metrics.record_domain_event_lag("kafka_forwarder", 7);
+        metrics.set_domain_event_pending("kafka_forwarder", 3);
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_domain_event_subscriber_failures_total{event=\"group_created\",reason=\"error\",subscriber=\"kafka_forwarder\"} 1"
+        ));
+        assert!(output.contains(
+            "xzepr_domain_event_subscriber_lagged_total{subscriber=\"kafka_forwarder\"} 7"
+        ));
+        assert!(output
+            .contains("xzepr_domain_event_subscriber_pending{subscriber=\"kafka_forwarder\"} 3"));
+    }
+
+    #[test]
+    fn test_record_lookup_cache() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_lookup_cache("event_receiver", "hit");
+        metrics.record_lookup_cache("event_receiver", "negative_hit");
+        metrics.record_lookup_cache("event_receiver", "negative_hit");
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_lookup_cache_requests_total{cache=\"event_receiver\",result=\"hit\"} 1"
+        ));
+        assert!(output.contains(
+            "xzepr_lookup_cache_requests_total{cache=\"event_receiver\",result=\"negative_hit\"} 2"
+        ));
+    }
+
+    #[test]
+    fn test_record_persisted_query() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_persisted_query("hit");
+        metrics.record_persisted_query("hit");
+        metrics.record_persisted_query("not_found");
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains("xzepr_graphql_persisted_queries_total{result=\"hit\"} 2"));
+        assert!(output.contains("xzepr_graphql_persisted_queries_total{result=\"not_found\"} 1"));
+    }
+
+    #[test]
+    fn test_record_request_timeout() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_request_timeout("deadline_exceeded");
+        metrics.record_request_timeout("deadline_exceeded");
+        metrics.record_request_timeout("server_timeout");
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains("xzepr_request_timeouts_total{reason=\"deadline_exceeded\"} 2"));
+        assert!(output.contains("xzepr_request_timeouts_total{reason=\"server_timeout\"} 1"));
+    }
 }
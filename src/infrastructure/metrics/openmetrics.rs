@@ -0,0 +1,303 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/metrics/openmetrics.rs
+
+//! OpenMetrics text encoding
+//!
+//! The `prometheus` crate only writes the classic text format, which has
+//! no room for exemplars, so gathered families are encoded here instead
+//! when a scraper asks for OpenMetrics.
+
+use super::exemplars::{Exemplar, ExemplarHistogramVec};
+use prometheus::proto::{Metric, MetricFamily, MetricType};
+use std::fmt::Write;
+use std::time::UNIX_EPOCH;
+
+/// Media type of the OpenMetrics text format
+pub const OPENMETRICS_MEDIA_TYPE: &str = "application/openmetrics-text";
+
+/// Content type of an OpenMetrics response
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Returns true if the Accept header prefers OpenMetrics
+///
+/// The media range with the highest quality wins, the first one on a tie,
+/// so scrapers that list the classic format first keep getting it.
+pub fn prefers_openmetrics(accept: &str) -> bool {
+    let mut best: Option<(f32, &str)> = None;
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let media_type = params.next().unwrap_or_default().trim();
+        if media_type.is_empty() {
+            continue;
+        }
+        let quality = params
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if quality > 0.0 && best.is_none_or(|(best, _)| quality > best) {
+            best = Some((quality, media_type));
+        }
+    }
+
+    best.is_some_and(|(_, media_type)| media_type.eq_ignore_ascii_case(OPENMETRICS_MEDIA_TYPE))
+}
+
+/// Encodes metric families in the OpenMetrics text format
+///
+/// Buckets of `histograms` are annotated with their sampled exemplars.
+pub fn encode(families: &[MetricFamily], histograms: &[&ExemplarHistogramVec]) -> String {
+    let mut out = String::new();
+    for family in families {
+        let metric_type = family.get_field_type();
+        let name = match metric_type {
+            MetricType::COUNTER => family
+                .name()
+                .strip_suffix("_total")
+                .unwrap_or(family.name()),
+            _ => family.name(),
+        };
+        let histogram = histograms
+            .iter()
+            .find(|histogram| histogram.name() == family.name());
+
+        let type_name = match metric_type {
+            MetricType::COUNTER => "counter",
+            MetricType::GAUGE => "gauge",
+            MetricType::HISTOGRAM => "histogram",
+            MetricType::SUMMARY => "summary",
+            MetricType::UNTYPED => "unknown",
+        };
+        let _ = writeln!(out, "# TYPE {} {}", name, type_name);
+        let _ = writeln!(out, "# HELP {} {}", name, escape(family.help(), false));
+
+        for metric in family.get_metric() {
+            match metric_type {
+                MetricType::COUNTER => {
+                    let value = metric.get_counter().value();
+                    write_sample(&mut out, name, "_total", metric, None, value);
+                }
+                MetricType::GAUGE => {
+                    let value = metric.get_gauge().value();
+                    write_sample(&mut out, name, "", metric, None, value);
+                }
+                MetricType::UNTYPED => {
+                    let value = metric.untyped.value();
+                    write_sample(&mut out, name, "", metric, None, value);
+                }
+                MetricType::SUMMARY => {
+                    let summary = metric.get_summary();
+                    for quantile in summary.get_quantile() {
+                        let le = ("quantile", format!("{:?}", quantile.quantile()));
+                        write_sample(&mut out, name, "", metric, Some(le), quantile.value());
+                    }
+                    write_sample(&mut out, name, "_sum", metric, None, summary.sample_sum());
+                    let count = summary.sample_count() as f64;
+                    write_sample(&mut out, name, "_count", metric, None, count);
+                }
+                MetricType::HISTOGRAM => {
+                    write_histogram(&mut out, name, metric, histogram.copied());
+                }
+            }
+        }
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+fn write_histogram(
+    out: &mut String,
+    name: &str,
+    metric: &Metric,
+    exemplars: Option<&ExemplarHistogramVec>,
+) {
+    let labels = metric
+        .get_label()
+        .iter()
+        .map(|label| (label.name(), label.value()))
+        .collect::<Vec<_>>();
+    let exemplar = |bucket| exemplars.and_then(|histogram| histogram.exemplar(&labels, bucket));
+
+    let histogram = metric.get_histogram();
+    let buckets = histogram.get_bucket();
+    let mut has_inf = false;
+    for (index, bucket) in buckets.iter().enumerate() {
+        let upper_bound = bucket.upper_bound();
+        has_inf |= upper_bound.is_infinite();
+        write_sample(
+            out,
+            name,
+            "_bucket",
+            metric,
+            Some(("le", format_float(upper_bound))),
+            bucket.cumulative_count() as f64,
+        );
+        append_exemplar(out, exemplar(index));
+    }
+    if !has_inf {
+        let count = histogram.get_sample_count() as f64;
+        write_sample(
+            out,
+            name,
+            "_bucket",
+            metric,
+            Some(("le", "+Inf".to_string())),
+            count,
+        );
+        append_exemplar(out, exemplar(buckets.len()));
+    }
+    write_sample(out, name, "_sum", metric, None, histogram.get_sample_sum());
+    let count = histogram.get_sample_count() as f64;
+    write_sample(out, name, "_count", metric, None, count);
+}
+
+/// Writes one sample line
+fn write_sample(
+    out: &mut String,
+    name: &str,
+    suffix: &str,
+    metric: &Metric,
+    extra_label: Option<(&str, String)>,
+    value: f64,
+) {
+    out.push_str(name);
+    out.push_str(suffix);
+
+    let labels = metric
+        .get_label()
+        .iter()
+        .map(|label| (label.name(), label.value().to_string()))
+        .chain(extra_label)
+        .collect::<Vec<_>>();
+    if !labels.is_empty() {
+        out.push('{');
+        for (index, (name, value)) in labels.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            let _ = write!(out, "{}=\"{}\"", name, escape(value, true));
+        }
+        out.push('}');
+    }
+    let _ = writeln!(out, " {}", format_value(value));
+}
+
+/// Appends ` # {trace_id="..."} value timestamp` to the sample line just
+/// written
+fn append_exemplar(out: &mut String, exemplar: Option<Exemplar>) {
+    let Some(exemplar) = exemplar else {
+        return;
+    };
+    out.pop();
+    let timestamp = exemplar
+        .timestamp
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64();
+    let _ = writeln!(
+        out,
+        " # {{trace_id=\"{}\"}} {} {:.3}",
+        escape(&exemplar.trace_id, true),
+        format_value(exemplar.value),
+        timestamp
+    );
+}
+
+/// Formats a bucket bound the way OpenMetrics expects, always with a
+/// fractional part
+fn format_float(value: f64) -> String {
+    if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        format_float(value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape(value: &str, quote: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' if quote => escaped.push_str("\\\""),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::metrics::exemplars::DEFAULT_EXEMPLAR_INTERVAL;
+    use prometheus::{CounterVec, HistogramOpts, Opts, Registry};
+
+    #[test]
+    fn test_prefers_openmetrics() {
+        assert!(prefers_openmetrics("application/openmetrics-text"));
+        assert!(prefers_openmetrics(
+            "application/openmetrics-text;version=1.0.0,text/plain;version=0.0.4;q=0.5,*/*;q=0.1"
+        ));
+        assert!(prefers_openmetrics(
+            "text/plain;q=0.5, application/openmetrics-text; version=1.0.0"
+        ));
+        assert!(!prefers_openmetrics("text/plain;version=0.0.4"));
+        assert!(!prefers_openmetrics("*/*"));
+        assert!(!prefers_openmetrics(""));
+        assert!(!prefers_openmetrics(
+            "text/plain, application/openmetrics-text"
+        ));
+        assert!(!prefers_openmetrics("application/openmetrics-text;q=0"));
+    }
+
+    #[test]
+    fn test_encode_counters_and_histograms_with_exemplars() {
+        let registry = Registry::new();
+        let counter = CounterVec::new(
+            Opts::new("test_requests_total", "Test \"requests\""),
+            &["path"],
+        )
+        .unwrap();
+        registry.register(Box::new(counter.clone())).unwrap();
+        let histogram = ExemplarHistogramVec::new(
+            HistogramOpts::new("test_duration_seconds", "Test durations").buckets(vec![0.1, 1.0]),
+            &["path"],
+            DEFAULT_EXEMPLAR_INTERVAL,
+        )
+        .unwrap();
+        histogram.register(&registry).unwrap();
+
+        counter.with_label_values(&["/a\"b"]).inc();
+        histogram.observe(&["/a"], 0.5, Some("0af7651916cd43dd8448eb211c80319c"));
+        histogram.observe(&["/a"], 0.05, None);
+
+        let text = encode(&registry.gather(), &[&histogram]);
+        let lines = text.lines().collect::<Vec<_>>();
+
+        assert!(lines.contains(&"# TYPE test_requests counter"));
+        assert!(lines.contains(&"# HELP test_requests Test \"requests\""));
+        assert!(lines.contains(&"test_requests_total{path=\"/a\\\"b\"} 1"));
+        assert!(lines.contains(&"# TYPE test_duration_seconds histogram"));
+        assert!(lines.contains(&"test_duration_seconds_bucket{path=\"/a\",le=\"0.1\"} 1"));
+        let traced = lines
+            .iter()
+            .find(|line| line.starts_with("test_duration_seconds_bucket{path=\"/a\",le=\"1.0\"} 2"))
+            .unwrap();
+        assert!(traced.contains(" # {trace_id=\"0af7651916cd43dd8448eb211c80319c\"} 0.5 "));
+        assert!(lines.contains(&"test_duration_seconds_bucket{path=\"/a\",le=\"+Inf\"} 2"));
+        assert!(lines.contains(&"test_duration_seconds_count{path=\"/a\"} 2"));
+        assert_eq!(lines.last(), Some(&"# EOF"));
+    }
+}
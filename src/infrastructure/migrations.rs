@@ -0,0 +1,581 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/migrations.rs
+
+//! Database migrations with per-migration diagnostics
+//!
+//! [`MigrationRunner`] applies the embedded migrations the way sqlx does,
+//! but one at a time: it logs the pending migrations before anything runs,
+//! then the start, end, and duration of each. When a migration fails, the
+//! error names it, says whether running it again is safe, and gives the
+//! schema version the database was left at. Every run can also write a
+//! [`MigrationReport`] as JSON for deployment pipelines to gate on.
+//!
+//! # Re-running a failed migration
+//!
+//! A migration runs in a transaction unless it starts with sqlx's
+//! `-- no-transaction` directive, so a failed one leaves nothing behind
+//! and is safe to run again once the cause is fixed. A migration without a
+//! transaction, such as one creating an index concurrently, can stop part
+//! way. It is only reported safe to re-run when it is written to tolerate
+//! that, with `IF NOT EXISTS` and similar guards, and says so on a line of
+//! its own:
+//!
+//! ```sql
+//! -- xzepr:rerunnable
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::migrate::{Migrate, MigrateError, Migration, Migrator};
+use sqlx::{PgConnection, PgPool};
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+
+use crate::infrastructure::startup::{
+    classify_migrate_error, classify_sqlx_error, describe_checksum_mismatch, DependencyError,
+};
+
+/// Line marking a migration without a transaction as safe to re-run
+pub const RERUNNABLE_MARKER: &str = "-- xzepr:rerunnable";
+
+/// An embedded migration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationInfo {
+    pub version: i64,
+    pub description: String,
+    /// Runs in a transaction, so a failure leaves nothing behind
+    pub transactional: bool,
+    /// Running it again after a failure is safe
+    pub safe_to_rerun: bool,
+}
+
+impl MigrationInfo {
+    fn from_migration(migration: &Migration) -> Self {
+        let rerunnable = migration
+            .sql
+            .lines()
+            .any(|line| line.trim() == RERUNNABLE_MARKER);
+        Self {
+            version: migration.version,
+            description: migration.description.to_string(),
+            transactional: !migration.no_tx,
+            safe_to_rerun: !migration.no_tx || rerunnable,
+        }
+    }
+}
+
+/// Which embedded migrations a database has applied
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationStatus {
+    /// Latest applied migration; `None` for an empty database
+    pub schema_version: Option<i64>,
+    pub applied: Vec<MigrationInfo>,
+    pub pending: Vec<MigrationInfo>,
+}
+
+impl MigrationStatus {
+    /// Returns true when no migration is pending
+    pub fn is_current(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl fmt::Display for MigrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.schema_version {
+            Some(version) => write!(f, "Schema version: {}", version)?,
+            None => write!(f, "Schema version: none")?,
+        }
+        writeln!(
+            f,
+            " ({} applied, {} pending)",
+            self.applied.len(),
+            self.pending.len()
+        )?;
+
+        let rows = self
+            .applied
+            .iter()
+            .map(|m| ("applied", m))
+            .chain(self.pending.iter().map(|m| ("pending", m)));
+        for (state, migration) in rows {
+            write!(
+                f,
+                "{:<8} {:<15} {}",
+                state, migration.version, migration.description
+            )?;
+            if !migration.transactional {
+                write!(f, " (no transaction)")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of a migration run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationOutcome {
+    /// Every pending migration was applied
+    Succeeded,
+    /// A migration failed or the migrations could not be checked
+    Failed,
+    /// Nothing was applied; `pending` lists what would run
+    DryRun,
+}
+
+/// A migration applied during a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationRun {
+    pub version: i64,
+    pub description: String,
+    pub duration_ms: u64,
+}
+
+/// The migration a run stopped at
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationFailure {
+    pub version: i64,
+    pub description: String,
+    pub error: String,
+    pub safe_to_rerun: bool,
+    /// What to do before running the migrations again
+    pub guidance: String,
+}
+
+impl MigrationFailure {
+    fn new(migration: &MigrationInfo, error: String) -> Self {
+        let guidance = if migration.transactional {
+            "It ran in a transaction that was rolled back, so nothing was applied. Fix the \
+             cause and run the migrations again"
+        } else if migration.safe_to_rerun {
+            "It ran without a transaction and may be partly applied. It is marked rerunnable, \
+             so fix the cause and run the migrations again"
+        } else {
+            "It ran without a transaction and may be partly applied. Finish or revert its \
+             changes by hand before running the migrations again"
+        };
+        Self {
+            version: migration.version,
+            description: migration.description.clone(),
+            error,
+            safe_to_rerun: migration.safe_to_rerun,
+            guidance: guidance.to_string(),
+        }
+    }
+}
+
+/// Machine-readable record of a migration run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationReport {
+    pub outcome: MigrationOutcome,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// Schema version before the run; `None` for an empty database or
+    /// when the database could not be read
+    pub schema_version_before: Option<i64>,
+    /// Schema version after the run
+    pub schema_version: Option<i64>,
+    /// Migrations applied by this run, in order
+    pub applied: Vec<MigrationRun>,
+    /// Migrations still pending after the run
+    pub pending: Vec<MigrationInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_migration: Option<MigrationFailure>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl MigrationReport {
+    fn new(outcome: MigrationOutcome, started_at: DateTime<Utc>) -> Self {
+        Self {
+            outcome,
+            started_at,
+            finished_at: started_at,
+            schema_version_before: None,
+            schema_version: None,
+            applied: Vec::new(),
+            pending: Vec::new(),
+            failed_migration: None,
+            error: None,
+        }
+    }
+
+    fn record_status(&mut self, status: &MigrationStatus) {
+        self.schema_version_before = status.schema_version;
+        self.schema_version = status.schema_version;
+        self.pending = status.pending.clone();
+    }
+
+    fn fail(&mut self, error: &DependencyError) {
+        self.outcome = MigrationOutcome::Failed;
+        self.error = Some(error.message().to_string());
+    }
+
+    /// Writes the report as pretty-printed JSON, creating parent
+    /// directories as needed
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Applies embedded migrations one at a time and reports on them
+///
+/// Follows the rules of [`Migrator::run`]: the database is locked while
+/// migrations run, and an applied migration that was modified or is not
+/// part of the build stops the run before anything is applied.
+pub struct MigrationRunner<'m> {
+    migrator: &'m Migrator,
+    report_path: Option<PathBuf>,
+}
+
+impl<'m> MigrationRunner<'m> {
+    /// Creates a runner for the migrations of `migrator`
+    pub fn new(migrator: &'m Migrator) -> Self {
+        Self {
+            migrator,
+            report_path: None,
+        }
+    }
+
+    /// Writes a [`MigrationReport`] to `path` after every run and dry run
+    pub fn with_report_path(mut self, path: Option<PathBuf>) -> Self {
+        self.report_path = path;
+        self
+    }
+
+    /// Reads which migrations the database has applied
+    ///
+    /// Changes nothing, not even on an empty database.
+    ///
+    /// # Errors
+    ///
+    /// Returns a retriable error when the database cannot be reached, and
+    /// a fatal one when an applied migration was modified, is not part of
+    /// this build, or only partially applied.
+    pub async fn status(&self, pool: &PgPool) -> Result<MigrationStatus, DependencyError> {
+        let mut conn = pool.acquire().await.map_err(|e| classify_sqlx_error(&e))?;
+        self.read_status(&mut conn).await
+    }
+
+    /// Reports the migrations that would run without applying any
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::status`].
+    pub async fn dry_run(&self, pool: &PgPool) -> Result<MigrationReport, DependencyError> {
+        let mut report = MigrationReport::new(MigrationOutcome::DryRun, Utc::now());
+        let result = self.status(pool).await;
+        match &result {
+            Ok(status) => {
+                report.record_status(status);
+                self.log_pending(status);
+            }
+            Err(e) => report.fail(e),
+        }
+        report.finished_at = Utc::now();
+        self.write_report(&report);
+        result.map(|_| report)
+    }
+
+    /// Applies every pending migration in version order
+    ///
+    /// # Errors
+    ///
+    /// Returns a fatal error naming the failed migration, whether it is
+    /// safe to run again, and the schema version the database is at. Only
+    /// failures to reach the database before a migration started are
+    /// retriable.
+    pub async fn run(&self, pool: &PgPool) -> Result<MigrationReport, DependencyError> {
+        let mut report = MigrationReport::new(MigrationOutcome::Succeeded, Utc::now());
+        let result = match pool.acquire().await {
+            Ok(mut conn) => self.run_locked(&mut conn, &mut report).await,
+            Err(e) => Err(classify_sqlx_error(&e)),
+        };
+        if let Err(e) = &result {
+            report.fail(e);
+        }
+        report.finished_at = Utc::now();
+        self.write_report(&report);
+        result.map(|()| report)
+    }
+
+    async fn run_locked(
+        &self,
+        conn: &mut PgConnection,
+        report: &mut MigrationReport,
+    ) -> Result<(), DependencyError> {
+        if self.migrator.locking {
+            conn.lock().await.map_err(|e| classify_migrate_error(&e))?;
+        }
+        let result = self.apply_pending(conn, report).await;
+        if self.migrator.locking {
+            if let Err(e) = conn.unlock().await {
+                warn!(error = %e, "Failed to release the migration lock");
+            }
+        }
+        result
+    }
+
+    async fn apply_pending(
+        &self,
+        conn: &mut PgConnection,
+        report: &mut MigrationReport,
+    ) -> Result<(), DependencyError> {
+        conn.ensure_migrations_table()
+            .await
+            .map_err(|e| classify_migrate_error(&e))?;
+        let status = self.read_status(conn).await?;
+        report.record_status(&status);
+        self.log_pending(&status);
+
+        let pending: Vec<&Migration> = self
+            .migrator
+            .iter()
+            .filter(|m| status.pending.iter().any(|p| p.version == m.version))
+            .collect();
+        for migration in pending {
+            let info = MigrationInfo::from_migration(migration);
+            info!(
+                version = info.version,
+                description = %info.description,
+                transactional = info.transactional,
+                "Applying migration"
+            );
+            let started = Instant::now();
+
+            if let Err(e) = conn.apply(migration).await {
+                let failure = MigrationFailure::new(&info, e.to_string());
+                error!(
+                    version = info.version,
+                    description = %info.description,
+                    duration_ms = started.elapsed().as_millis() as u64,
+                    safe_to_rerun = failure.safe_to_rerun,
+                    schema_version = ?report.schema_version,
+                    error = %e,
+                    "Migration failed"
+                );
+                let message = describe_failure(&failure, report.schema_version);
+                report.failed_migration = Some(failure);
+                return Err(DependencyError::Fatal(message));
+            }
+
+            let duration_ms = started.elapsed().as_millis() as u64;
+            info!(
+                version = info.version,
+                description = %info.description,
+                duration_ms,
+                "Migration applied"
+            );
+            report.applied.push(MigrationRun {
+                version: info.version,
+                description: info.description,
+                duration_ms,
+            });
+            report.schema_version = Some(migration.version);
+            report.pending.retain(|p| p.version != migration.version);
+        }
+
+        info!(
+            applied = report.applied.len(),
+            schema_version = ?report.schema_version,
+            "Database migrations finished"
+        );
+        Ok(())
+    }
+
+    async fn read_status(
+        &self,
+        conn: &mut PgConnection,
+    ) -> Result<MigrationStatus, DependencyError> {
+        let classify = |e: sqlx::Error| classify_sqlx_error(&e);
+        let exists: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(classify)?;
+        let rows: Vec<(i64, Vec<u8>, bool)> = if exists {
+            sqlx::query_as(
+                "SELECT version, checksum, success FROM _sqlx_migrations ORDER BY version",
+            )
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(classify)?
+        } else {
+            Vec::new()
+        };
+
+        if let Some((version, ..)) = rows.iter().find(|(.., success)| !success) {
+            return Err(classify_migrate_error(&MigrateError::Dirty(*version)));
+        }
+        let applied: HashMap<i64, Vec<u8>> = rows
+            .into_iter()
+            .map(|(version, checksum, _)| (version, checksum))
+            .collect();
+
+        let embedded: Vec<&Migration> = self
+            .migrator
+            .iter()
+            .filter(|m| !m.migration_type.is_down_migration())
+            .collect();
+        if !self.migrator.ignore_missing {
+            let mut missing: Vec<i64> = applied
+                .keys()
+                .copied()
+                .filter(|version| !embedded.iter().any(|m| m.version == *version))
+                .collect();
+            missing.sort_unstable();
+            if let Some(version) = missing.first() {
+                return Err(classify_migrate_error(&MigrateError::VersionMissing(
+                    *version,
+                )));
+            }
+        }
+
+        let mut status = MigrationStatus {
+            schema_version: applied.keys().max().copied(),
+            applied: Vec::new(),
+            pending: Vec::new(),
+        };
+        for migration in embedded {
+            match applied.get(&migration.version) {
+                Some(checksum) if *checksum != *migration.checksum => {
+                    return Err(DependencyError::Fatal(describe_checksum_mismatch(
+                        migration.version,
+                        Some(&migration.description),
+                        Some(&migration.checksum),
+                        Some(checksum),
+                    )));
+                }
+                Some(_) => status
+                    .applied
+                    .push(MigrationInfo::from_migration(migration)),
+                None => status
+                    .pending
+                    .push(MigrationInfo::from_migration(migration)),
+            }
+        }
+        Ok(status)
+    }
+
+    fn log_pending(&self, status: &MigrationStatus) {
+        info!(
+            schema_version = ?status.schema_version,
+            applied = status.applied.len(),
+            pending = status.pending.len(),
+            "Database migration status"
+        );
+        for migration in &status.pending {
+            info!(
+                version = migration.version,
+                description = %migration.description,
+                transactional = migration.transactional,
+                "Pending migration"
+            );
+        }
+    }
+
+    fn write_report(&self, report: &MigrationReport) {
+        let Some(path) = &self.report_path else {
+            return;
+        };
+        match report.write_to(path) {
+            Ok(()) => info!(path = %path.display(), "Migration report written"),
+            Err(e) => warn!(
+                path = %path.display(),
+                error = %e,
+                "Failed to write the migration report"
+            ),
+        }
+    }
+}
+
+/// Explains a failed migration for the startup error
+fn describe_failure(failure: &MigrationFailure, schema_version: Option<i64>) -> String {
+    let schema_version = schema_version.map_or_else(|| "none".to_string(), |v| v.to_string());
+    format!(
+        "migration {} ({}) failed: {}. The database is at schema version {}. {}",
+        failure.version, failure.description, failure.error, schema_version, failure.guidance
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::migrate::MigrationType;
+
+    fn migration(version: i64, description: &str, sql: &str, no_tx: bool) -> Migration {
+        Migration::new(
+            version,
+            description.to_string().into(),
+            MigrationType::Simple,
+            sql.to_string().into(),
+            no_tx,
+        )
+    }
+
+    #[test]
+    fn test_failed_migration_is_safe_to_rerun_only_when_it_cannot_be_partial() {
+        let transactional =
+            MigrationInfo::from_migration(&migration(1, "add table", "CREATE TABLE t ()", false));
+        let marked = MigrationInfo::from_migration(&migration(
+            2,
+            "add index",
+            "-- no-transaction\n-- xzepr:rerunnable\nCREATE INDEX CONCURRENTLY IF NOT EXISTS i ON t (id)",
+            true,
+        ));
+        let unmarked = MigrationInfo::from_migration(&migration(
+            3,
+            "add index",
+            "-- no-transaction\nCREATE INDEX CONCURRENTLY i ON t (id)",
+            true,
+        ));
+
+        assert!(transactional.safe_to_rerun);
+        assert!(marked.safe_to_rerun && !marked.transactional);
+        assert!(!unmarked.safe_to_rerun);
+
+        let failure = MigrationFailure::new(&unmarked, "canceling statement".to_string());
+        let message = describe_failure(&failure, Some(2));
+        assert!(message.starts_with("migration 3 (add index) failed: canceling statement."));
+        assert!(message.contains("schema version 2"));
+        assert!(message.contains("by hand"));
+    }
+
+    #[test]
+    fn test_status_lists_applied_and_pending_migrations() {
+        let status = MigrationStatus {
+            schema_version: Some(20250101000001),
+            applied: vec![MigrationInfo::from_migration(&migration(
+                20250101000001,
+                "create users table",
+                "",
+                false,
+            ))],
+            pending: vec![MigrationInfo::from_migration(&migration(
+                20250102000001,
+                "add events index",
+                "",
+                true,
+            ))],
+        };
+
+        assert!(!status.is_current());
+        assert_eq!(
+            status.to_string(),
+            "Schema version: 20250101000001 (1 applied, 1 pending)\n\
+             applied  20250101000001  create users table\n\
+             pending  20250102000001  add events index (no transaction)\n"
+        );
+    }
+}
@@ -3,25 +3,59 @@
 
 // Generated mod file
 
+pub mod archive;
+pub mod attachments;
 pub mod audit;
+pub mod build_info;
 pub mod config;
+pub mod config_reload;
 pub mod database;
+pub mod deadline;
+pub mod feature_flags;
+pub mod http_client;
+pub mod http_server;
+pub mod jobs;
+pub mod maintenance;
+pub mod memory;
 pub mod messaging;
 pub mod metrics;
+pub mod migrations;
 pub mod monitoring;
+pub mod receiver_cache;
 pub mod security_config;
+pub mod startup;
+pub mod tls;
 pub mod tracing;
 
+pub use archive::{build_archive_store, ArchiveConfig, FilesystemArchiveStore};
+pub use attachments::{build_blob_store, AttachmentsConfig, FilesystemBlobStore};
 pub use audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+pub use build_info::BuildInfo;
+pub use config_reload::{DynamicSettings, ReloadReport, ReloadableSettings, SettingChange};
+pub use deadline::{
+    DeadlineEventReceiverGroupRepository, DeadlineEventReceiverRepository, DeadlineEventRepository,
+    RequestDeadline,
+};
+pub use feature_flags::{FeatureFlags, FeatureFlagsConfig};
+pub use http_client::{HttpClientConfig, HttpClientError, HttpClientFactory};
+pub use http_server::{ConnectionLimitAcceptor, ConnectionLimits};
+pub use jobs::{Job, JobOutcome, JobReport, JobRunner, JobSchedule, JobStatus, JobTriggerError};
+pub use maintenance::{MaintenanceMode, MaintenanceWindow};
 pub use messaging::TopicManager;
 pub use metrics::PrometheusMetrics;
+pub use migrations::{MigrationReport, MigrationRunner, MigrationStatus};
 pub use monitoring::{
     ComponentHealth, HealthCheck, HealthStatus, SecurityMetrics, SecurityMonitor,
 };
+pub use receiver_cache::CachedEventReceiverRepository;
 pub use security_config::{
-    CorsSecurityConfig, MonitoringConfig, RateLimitSecurityConfig, SecurityConfig,
-    SecurityHeadersConfig, ValidationSecurityConfig,
+    BucketLimit, ClassRateLimits, CorsSecurityConfig, MediaTypeSecurityConfig, MonitoringConfig,
+    PrincipalClass, RateLimitSecurityConfig, RouteRateLimit, SecurityConfig, SecurityHeadersConfig,
+    ValidationSecurityConfig,
 };
+pub use startup::{StartupConfig, StartupReadiness, Warmup, WarmupConfig};
+pub use tls::{TlsConfigBuilder, TlsMinVersion, TlsReloader};
 pub use tracing::{
-    extract_trace_context, init_tracing, inject_trace_context, shutdown_tracing, TracingConfig,
+    current_trace_headers, extract_parent_context, extract_trace_context, init_tracing,
+    inject_trace_context, set_log_level, shutdown_tracing, TracingConfig,
 };
@@ -3,17 +3,66 @@
 
 // src/infrastructure/monitoring.rs
 
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tracing::{error, info, warn};
 
 use crate::infrastructure::metrics::PrometheusMetrics;
 
+/// Minutes of rate limit decisions used for the rejection rate
+const RATE_LIMIT_WINDOW_MINUTES: u64 = 5;
+
+/// Allowed and rejected rate limit decisions in one-minute buckets
+#[derive(Debug, Default)]
+struct RateLimitWindow {
+    /// (minute since start, allowed, rejected), oldest first
+    buckets: Mutex<VecDeque<(u64, u64, u64)>>,
+}
+
+impl RateLimitWindow {
+    fn record(&self, minute: u64, rejected: bool) {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.back().is_none_or(|(m, _, _)| *m != minute) {
+            buckets.push_back((minute, 0, 0));
+        }
+        while buckets
+            .front()
+            .is_some_and(|(m, _, _)| *m + RATE_LIMIT_WINDOW_MINUTES <= minute)
+        {
+            buckets.pop_front();
+        }
+        if let Some((_, allowed, rejections)) = buckets.back_mut() {
+            if rejected {
+                *rejections += 1;
+            } else {
+                *allowed += 1;
+            }
+        }
+    }
+
+    fn rejection_rate(&self, minute: u64) -> f64 {
+        let buckets = self.buckets.lock().unwrap();
+        let (allowed, rejected) = buckets
+            .iter()
+            .filter(|(m, _, _)| *m + RATE_LIMIT_WINDOW_MINUTES > minute)
+            .fold((0, 0), |(a, r), (_, allowed, rejected)| {
+                (a + allowed, r + rejected)
+            });
+        if allowed + rejected == 0 {
+            0.0
+        } else {
+            rejected as f64 / (allowed + rejected) as f64
+        }
+    }
+}
+
 /// Security monitoring metrics and logging
 #[derive(Clone)]
 pub struct SecurityMonitor {
     start_time: Instant,
     metrics: Option<Arc<PrometheusMetrics>>,
+    rate_limits: Arc<RateLimitWindow>,
 }
 
 impl SecurityMonitor {
@@ -22,6 +71,7 @@ impl SecurityMonitor {
         Self {
             start_time: Instant::now(),
             metrics: None,
+            rate_limits: Arc::default(),
         }
     }
 
@@ -30,6 +80,7 @@ impl SecurityMonitor {
         Self {
             start_time: Instant::now(),
             metrics: Some(metrics),
+            rate_limits: Arc::default(),
         }
     }
 
@@ -49,11 +100,27 @@ impl SecurityMonitor {
             "Rate limit exceeded"
         );
 
+        self.rate_limits.record(self.minutes_since_start(), true);
+
         if let Some(metrics) = &self.metrics {
             metrics.record_rate_limit_rejection(endpoint, client_id);
         }
     }
 
+    /// Records a request that passed a rate limit check
+    pub fn record_rate_limit_pass(&self) {
+        self.rate_limits.record(self.minutes_since_start(), false);
+    }
+
+    /// Fraction of rate limit checks rejected over the last few minutes
+    pub fn rate_limit_rejection_rate(&self) -> f64 {
+        self.rate_limits.rejection_rate(self.minutes_since_start())
+    }
+
+    fn minutes_since_start(&self) -> u64 {
+        self.start_time.elapsed().as_secs() / 60
+    }
+
     /// Records an authentication failure
     pub fn record_auth_failure(&self, client_id: &str, reason: &str) {
         warn!(
@@ -393,6 +460,31 @@ mod tests {
         monitor.record_error("database", "Connection timeout");
     }
 
+    #[test]
+    fn test_rate_limit_rejection_rate() {
+        let monitor = SecurityMonitor::new();
+        assert_eq!(monitor.rate_limit_rejection_rate(), 0.0);
+
+        for _ in 0..3 {
+            monitor.record_rate_limit_pass();
+        }
+        monitor.record_rate_limit_rejection("client1", "/api/events", 10);
+
+        assert_eq!(monitor.rate_limit_rejection_rate(), 0.25);
+        // Clones share the same window
+        assert_eq!(monitor.clone().rate_limit_rejection_rate(), 0.25);
+    }
+
+    #[test]
+    fn test_rate_limit_window_drops_old_buckets() {
+        let window = RateLimitWindow::default();
+        window.record(0, true);
+        window.record(RATE_LIMIT_WINDOW_MINUTES, false);
+
+        assert_eq!(window.rejection_rate(RATE_LIMIT_WINDOW_MINUTES), 0.0);
+        assert_eq!(window.buckets.lock().unwrap().len(), 1);
+    }
+
     #[test]
     fn test_metrics_collector() {
         let metrics = SecurityMetrics::new();
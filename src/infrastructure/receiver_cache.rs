@@ -0,0 +1,488 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// src/infrastructure/receiver_cache.rs
+//! Receiver lookup cache
+//!
+//! Every submitted event looks up its receiver by id. The cache keeps found
+//! receivers for a while and remembers ids that were not found for a much
+//! shorter while, so a producer retrying with a deleted receiver id does
+//! not cost a database query per request.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::domain::entities::event_receiver::EventReceiver;
+use crate::domain::repositories::event_receiver_repo::{
+    EventReceiverRepository, FindEventReceiverCriteria,
+};
+use crate::domain::value_objects::{EventReceiverId, UserId};
+use crate::error::Result;
+use crate::infrastructure::metrics::PrometheusMetrics;
+
+/// Default time a found receiver is served from cache
+pub const DEFAULT_RECEIVER_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Default time an unknown receiver id is answered from cache
+pub const DEFAULT_RECEIVER_NEGATIVE_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Entries kept before the cache is cleared
+const MAX_CACHED_RECEIVER_LOOKUPS: usize = 10_000;
+
+/// Label of this cache in `xzepr_lookup_cache_requests_total`
+const CACHE_LABEL: &str = "event_receiver";
+
+/// Result of a receiver lookup and when it was made
+struct CachedLookup {
+    receiver: Option<EventReceiver>,
+    cached_at: Instant,
+}
+
+/// Receiver repository that caches lookups by id
+///
+/// Only [`EventReceiverRepository::find_by_id`] is cached; everything else
+/// goes to the wrapped repository. Saves, updates, and deletes made through
+/// the cache drop the entry for their receiver, so a receiver created with
+/// an id that was just looked up is found at once. Changes made by other
+/// processes are seen once the entry expires. A zero TTL disables the
+/// respective half of the cache.
+pub struct CachedEventReceiverRepository {
+    inner: Arc<dyn EventReceiverRepository>,
+    ttl: Duration,
+    negative_ttl: Duration,
+    entries: RwLock<HashMap<EventReceiverId, CachedLookup>>,
+    metrics: Option<Arc<PrometheusMetrics>>,
+}
+
+impl CachedEventReceiverRepository {
+    /// Creates a cache in front of `inner` with the default TTLs
+    pub fn new(inner: Arc<dyn EventReceiverRepository>) -> Self {
+        Self {
+            inner,
+            ttl: DEFAULT_RECEIVER_CACHE_TTL,
+            negative_ttl: DEFAULT_RECEIVER_NEGATIVE_CACHE_TTL,
+            entries: RwLock::new(HashMap::new()),
+            metrics: None,
+        }
+    }
+
+    /// Sets how long a found receiver is served from cache
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets how long an unknown receiver id is answered from cache
+    pub fn with_negative_ttl(mut self, negative_ttl: Duration) -> Self {
+        self.negative_ttl = negative_ttl;
+        self
+    }
+
+    /// Records hits, negative hits, and misses in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<PrometheusMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Loads `ids` into the cache ahead of their first lookup
+    ///
+    /// Ids that are not found are skipped rather than remembered as
+    /// unknown, since the negative TTL is too short to be worth it. Returns
+    /// the receivers that were loaded.
+    pub async fn preload(&self, ids: &[EventReceiverId]) -> Result<Vec<EventReceiver>> {
+        let mut loaded = Vec::with_capacity(ids.len());
+        for &id in ids {
+            if let Some(receiver) = self.inner.find_by_id(id).await? {
+                self.remember(id, Some(receiver.clone()));
+                loaded.push(receiver);
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Returns the cached lookup of `id`, if it is still fresh
+    fn cached(&self, id: EventReceiverId) -> Option<Option<EventReceiver>> {
+        let entries = self.entries.read().unwrap();
+        let entry = entries.get(&id)?;
+        let ttl = match entry.receiver {
+            Some(_) => self.ttl,
+            None => self.negative_ttl,
+        };
+        (entry.cached_at.elapsed() < ttl).then(|| entry.receiver.clone())
+    }
+
+    fn remember(&self, id: EventReceiverId, receiver: Option<EventReceiver>) {
+        let ttl = match receiver {
+            Some(_) => self.ttl,
+            None => self.negative_ttl,
+        };
+        if ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries.write().unwrap();
+        if entries.len() >= MAX_CACHED_RECEIVER_LOOKUPS {
+            entries.clear();
+        }
+        entries.insert(
+            id,
+            CachedLookup {
+                receiver,
+                cached_at: Instant::now(),
+            },
+        );
+    }
+
+    fn forget(&self, ids: &[EventReceiverId]) {
+        let mut entries = self.entries.write().unwrap();
+        for id in ids {
+            entries.remove(id);
+        }
+    }
+
+    fn record(&self, result: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_lookup_cache(CACHE_LABEL, result);
+        }
+    }
+}
+
+#[async_trait]
+impl EventReceiverRepository for CachedEventReceiverRepository {
+    async fn save(&self, event_receiver: &EventReceiver) -> Result<()> {
+        self.inner.save(event_receiver).await?;
+        self.forget(&[event_receiver.id()]);
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
+        if let Some(receiver) = self.cached(id) {
+            self.record(if receiver.is_some() {
+                "hit"
+            } else {
+                "negative_hit"
+            });
+            return Ok(receiver);
+        }
+
+        self.record("miss");
+        let receiver = self.inner.find_by_id(id).await?;
+        self.remember(id, receiver.clone());
+        Ok(receiver)
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiver>> {
+        self.inner.find_by_name(name).await
+    }
+
+    async fn find_by_type(&self, receiver_type: &str) -> Result<Vec<EventReceiver>> {
+        self.inner.find_by_type(receiver_type).await
+    }
+
+    async fn find_by_type_and_version(
+        &self,
+        receiver_type: &str,
+        version: &str,
+    ) -> Result<Vec<EventReceiver>> {
+        self.inner
+            .find_by_type_and_version(receiver_type, version)
+            .await
+    }
+
+    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>> {
+        self.inner.find_by_fingerprint(fingerprint).await
+    }
+
+    async fn save_if_absent(
+        &self,
+        event_receiver: &EventReceiver,
+    ) -> Result<(EventReceiver, bool)> {
+        let (stored, created) = self.inner.save_if_absent(event_receiver).await?;
+        if created {
+            self.forget(&[stored.id()]);
+        }
+        Ok((stored, created))
+    }
+
+    async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiver>> {
+        self.inner.list(limit, offset).await
+    }
+
+    async fn count(&self) -> Result<usize> {
+        self.inner.count().await
+    }
+
+    async fn update(&self, event_receiver: &EventReceiver) -> Result<()> {
+        self.inner.update(event_receiver).await?;
+        self.forget(&[event_receiver.id()]);
+        Ok(())
+    }
+
+    async fn delete(&self, id: EventReceiverId) -> Result<()> {
+        self.inner.delete(id).await?;
+        self.forget(&[id]);
+        Ok(())
+    }
+
+    async fn delete_many(&self, ids: &[EventReceiverId]) -> Result<()> {
+        self.inner.delete_many(ids).await?;
+        self.forget(ids);
+        Ok(())
+    }
+
+    async fn exists_by_name_and_type(&self, name: &str, receiver_type: &str) -> Result<bool> {
+        self.inner
+            .exists_by_name_and_type(name, receiver_type)
+            .await
+    }
+
+    async fn find_by_criteria(
+        &self,
+        criteria: FindEventReceiverCriteria,
+    ) -> Result<Vec<EventReceiver>> {
+        self.inner.find_by_criteria(criteria).await
+    }
+
+    async fn count_by_criteria(&self, criteria: FindEventReceiverCriteria) -> Result<usize> {
+        self.inner.count_by_criteria(criteria).await
+    }
+
+    async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiver>> {
+        self.inner.find_by_owner(owner_id).await
+    }
+
+    async fn find_by_owner_paginated(
+        &self,
+        owner_id: UserId,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<EventReceiver>> {
+        self.inner
+            .find_by_owner_paginated(owner_id, limit, offset)
+            .await
+    }
+
+    async fn is_owner(&self, receiver_id: EventReceiverId, user_id: UserId) -> Result<bool> {
+        self.inner.is_owner(receiver_id, user_id).await
+    }
+
+    async fn get_resource_version(&self, receiver_id: EventReceiverId) -> Result<Option<i64>> {
+        self.inner.get_resource_version(receiver_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::memory::InMemoryEventReceiverRepository;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts the lookups by id that reach the stored receivers
+    #[derive(Default)]
+    struct CountingRepository {
+        receivers: InMemoryEventReceiverRepository,
+        lookups: AtomicUsize,
+    }
+
+    impl CountingRepository {
+        fn lookups(&self) -> usize {
+            self.lookups.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl EventReceiverRepository for CountingRepository {
+        async fn save(&self, event_receiver: &EventReceiver) -> Result<()> {
+            self.receivers.save(event_receiver).await
+        }
+
+        async fn find_by_id(&self, id: EventReceiverId) -> Result<Option<EventReceiver>> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            self.receivers.find_by_id(id).await
+        }
+
+        async fn find_by_name(&self, name: &str) -> Result<Vec<EventReceiver>> {
+            self.receivers.find_by_name(name).await
+        }
+
+        async fn find_by_type(&self, receiver_type: &str) -> Result<Vec<EventReceiver>> {
+            self.receivers.find_by_type(receiver_type).await
+        }
+
+        async fn find_by_type_and_version(
+            &self,
+            receiver_type: &str,
+            version: &str,
+        ) -> Result<Vec<EventReceiver>> {
+            self.receivers
+                .find_by_type_and_version(receiver_type, version)
+                .await
+        }
+
+        async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Option<EventReceiver>> {
+            self.receivers.find_by_fingerprint(fingerprint).await
+        }
+
+        async fn list(&self, limit: usize, offset: usize) -> Result<Vec<EventReceiver>> {
+            self.receivers.list(limit, offset).await
+        }
+
+        async fn count(&self) -> Result<usize> {
+            self.receivers.count().await
+        }
+
+        async fn update(&self, event_receiver: &EventReceiver) -> Result<()> {
+            self.receivers.update(event_receiver).await
+        }
+
+        async fn delete(&self, id: EventReceiverId) -> Result<()> {
+            self.receivers.delete(id).await
+        }
+
+        async fn exists_by_name_and_type(&self, name: &str, receiver_type: &str) -> Result<bool> {
+            self.receivers
+                .exists_by_name_and_type(name, receiver_type)
+                .await
+        }
+
+        async fn find_by_criteria(
+            &self,
+            criteria: FindEventReceiverCriteria,
+        ) -> Result<Vec<EventReceiver>> {
+            self.receivers.find_by_criteria(criteria).await
+        }
+
+        async fn find_by_owner(&self, owner_id: UserId) -> Result<Vec<EventReceiver>> {
+            self.receivers.find_by_owner(owner_id).await
+        }
+
+        async fn find_by_owner_paginated(
+            &self,
+            owner_id: UserId,
+            limit: usize,
+            offset: usize,
+        ) -> Result<Vec<EventReceiver>> {
+            self.receivers
+                .find_by_owner_paginated(owner_id, limit, offset)
+                .await
+        }
+
+        async fn is_owner(&self, receiver_id: EventReceiverId, user_id: UserId) -> Result<bool> {
+            self.receivers.is_owner(receiver_id, user_id).await
+        }
+
+        async fn get_resource_version(&self, receiver_id: EventReceiverId) -> Result<Option<i64>> {
+            self.receivers.get_resource_version(receiver_id).await
+        }
+    }
+
+    fn receiver() -> EventReceiver {
+        EventReceiver::new(
+            "builds".to_string(),
+            "ci.build".to_string(),
+            "1.0.0".to_string(),
+            "Build results".to_string(),
+            serde_json::json!({"type": "object"}),
+            UserId::new(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unknown_receiver_hits_the_repository_once_per_window() {
+        let inner = Arc::new(CountingRepository::default());
+        let cache = CachedEventReceiverRepository::new(inner.clone())
+            .with_negative_ttl(Duration::from_millis(200));
+        let unknown = EventReceiverId::new();
+
+        for _ in 0..5 {
+            assert!(cache.find_by_id(unknown).await.unwrap().is_none());
+        }
+        assert_eq!(inner.lookups(), 1);
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert!(cache.find_by_id(unknown).await.unwrap().is_none());
+        assert_eq!(inner.lookups(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_creating_a_receiver_clears_its_negative_entry() {
+        let inner = Arc::new(CountingRepository::default());
+        let cache = CachedEventReceiverRepository::new(inner.clone())
+            .with_negative_ttl(Duration::from_secs(60));
+        let receiver = receiver();
+
+        assert!(cache.find_by_id(receiver.id()).await.unwrap().is_none());
+        cache.save(&receiver).await.unwrap();
+
+        let found = cache.find_by_id(receiver.id()).await.unwrap();
+        assert_eq!(found.map(|r| r.id()), Some(receiver.id()));
+        assert_eq!(inner.lookups(), 2);
+
+        // Found receivers are cached until deleted
+        assert!(cache.find_by_id(receiver.id()).await.unwrap().is_some());
+        assert_eq!(inner.lookups(), 2);
+        cache.delete(receiver.id()).await.unwrap();
+        assert!(cache.find_by_id(receiver.id()).await.unwrap().is_none());
+        assert_eq!(inner.lookups(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_preloaded_receivers_skip_the_first_lookup() {
+        let inner = Arc::new(CountingRepository::default());
+        let cache = CachedEventReceiverRepository::new(inner.clone());
+        let receiver = receiver();
+        inner.save(&receiver).await.unwrap();
+        let unknown = EventReceiverId::new();
+
+        let loaded = cache.preload(&[receiver.id(), unknown]).await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(inner.lookups(), 2);
+
+        assert!(cache.find_by_id(receiver.id()).await.unwrap().is_some());
+        assert_eq!(inner.lookups(), 2);
+        assert!(cache.find_by_id(unknown).await.unwrap().is_none());
+        assert_eq!(inner.lookups(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_count_negative_hits_separately() {
+        let metrics = Arc::new(PrometheusMetrics::new().unwrap());
+        let inner = Arc::new(CountingRepository::default());
+        let cache = CachedEventReceiverRepository::new(inner.clone()).with_metrics(metrics.clone());
+        let receiver = receiver();
+        inner.save(&receiver).await.unwrap();
+        let unknown = EventReceiverId::new();
+
+        cache.find_by_id(receiver.id()).await.unwrap();
+        cache.find_by_id(receiver.id()).await.unwrap();
+        cache.find_by_id(unknown).await.unwrap();
+        cache.find_by_id(unknown).await.unwrap();
+        cache.find_by_id(unknown).await.unwrap();
+
+        let output = metrics.gather().unwrap();
+        assert!(output.contains(
+            "xzepr_lookup_cache_requests_total{cache=\"event_receiver\",result=\"miss\"} 2"
+        ));
+        assert!(output.contains(
+            "xzepr_lookup_cache_requests_total{cache=\"event_receiver\",result=\"hit\"} 1"
+        ));
+        assert!(output.contains(
+            "xzepr_lookup_cache_requests_total{cache=\"event_receiver\",result=\"negative_hit\"} 2"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_zero_negative_ttl_disables_negative_caching() {
+        let inner = Arc::new(CountingRepository::default());
+        let cache =
+            CachedEventReceiverRepository::new(inner.clone()).with_negative_ttl(Duration::ZERO);
+        let unknown = EventReceiverId::new();
+
+        cache.find_by_id(unknown).await.unwrap();
+        cache.find_by_id(unknown).await.unwrap();
+        assert_eq!(inner.lookups(), 2);
+    }
+}
@@ -6,6 +6,8 @@
 use serde::Deserialize;
 use std::collections::HashMap;
 
+use crate::infrastructure::config::Secret;
+
 /// Security configuration for production deployments
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct SecurityConfig {
@@ -35,25 +37,151 @@ pub struct CorsSecurityConfig {
 }
 
 /// Rate limiting security configuration
+///
+/// Every caller has a read bucket (GET, HEAD, OPTIONS) and a write bucket
+/// (everything else), sized by its principal class. Requests to the paths
+/// of a route bucket use that bucket instead.
 #[derive(Debug, Clone, Deserialize)]
 pub struct RateLimitSecurityConfig {
-    /// Requests per minute for anonymous users
-    #[serde(default = "default_anonymous_rpm")]
-    pub anonymous_rpm: u32,
-    /// Requests per minute for authenticated users
-    #[serde(default = "default_authenticated_rpm")]
-    pub authenticated_rpm: u32,
-    /// Requests per minute for admin users
-    #[serde(default = "default_admin_rpm")]
-    pub admin_rpm: u32,
-    /// Per-endpoint rate limits
+    /// Read and write buckets per principal class; classes left out use
+    /// [`ClassRateLimits::default_for`]
     #[serde(default)]
-    pub per_endpoint: HashMap<String, u32>,
+    pub classes: HashMap<PrincipalClass, ClassRateLimits>,
+    /// Buckets for expensive routes by name, replacing the read and write
+    /// buckets on their paths
+    #[serde(default = "default_route_limits")]
+    pub routes: HashMap<String, RouteRateLimit>,
     /// Enable Redis backend for distributed rate limiting
     #[serde(default)]
     pub use_redis: bool,
-    /// Redis connection URL
-    pub redis_url: Option<String>,
+    /// Redis connection URL; may carry a password
+    pub redis_url: Option<Secret<String>>,
+    /// Per-IP burst for login and OIDC callback attempts
+    #[serde(default = "default_auth_burst")]
+    pub auth_burst: u32,
+    /// Per-IP sustained login and OIDC callback attempts per minute
+    #[serde(default = "default_auth_sustained_per_minute")]
+    pub auth_sustained_per_minute: u32,
+}
+
+impl RateLimitSecurityConfig {
+    /// Returns the buckets of `class`, or its defaults if not configured
+    pub fn class_limits(&self, class: PrincipalClass) -> ClassRateLimits {
+        self.classes
+            .get(&class)
+            .copied()
+            .unwrap_or_else(|| ClassRateLimits::default_for(class))
+    }
+}
+
+/// Kind of caller a rate limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrincipalClass {
+    /// No valid credentials; limited per client IP
+    Anonymous,
+    /// User with a valid token and without the admin role
+    Authenticated,
+    /// API key sent in the `X-API-Key` header
+    ApiKey,
+    /// User with the admin role
+    Admin,
+}
+
+impl PrincipalClass {
+    /// All classes
+    pub const ALL: [PrincipalClass; 4] = [
+        PrincipalClass::Anonymous,
+        PrincipalClass::Authenticated,
+        PrincipalClass::ApiKey,
+        PrincipalClass::Admin,
+    ];
+
+    /// Returns the configuration name of the class
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrincipalClass::Anonymous => "anonymous",
+            PrincipalClass::Authenticated => "authenticated",
+            PrincipalClass::ApiKey => "api_key",
+            PrincipalClass::Admin => "admin",
+        }
+    }
+}
+
+/// Sustained rate and burst of a token bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct BucketLimit {
+    /// Tokens added per minute
+    pub rate_per_minute: u32,
+    /// Bucket capacity, the most requests allowed at once
+    pub burst: u32,
+}
+
+impl BucketLimit {
+    /// Creates a bucket limit
+    pub const fn new(rate_per_minute: u32, burst: u32) -> Self {
+        Self {
+            rate_per_minute,
+            burst,
+        }
+    }
+}
+
+/// Read and write buckets of a principal class
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct ClassRateLimits {
+    /// Bucket for GET, HEAD, and OPTIONS requests
+    pub read: BucketLimit,
+    /// Bucket for POST, PUT, PATCH, and DELETE requests
+    pub write: BucketLimit,
+}
+
+impl ClassRateLimits {
+    /// Returns the default buckets of `class`
+    ///
+    /// | Class | Read (per minute / burst) | Write (per minute / burst) |
+    /// |-------|---------------------------|----------------------------|
+    /// | `anonymous` | 60 / 20 | 10 / 5 |
+    /// | `authenticated` | 600 / 100 | 120 / 30 |
+    /// | `api_key` | 300 / 50 | 1200 / 200 |
+    /// | `admin` | 3000 / 500 | 600 / 100 |
+    pub fn default_for(class: PrincipalClass) -> Self {
+        let (read, write) = match class {
+            PrincipalClass::Anonymous => (BucketLimit::new(60, 20), BucketLimit::new(10, 5)),
+            PrincipalClass::Authenticated => {
+                (BucketLimit::new(600, 100), BucketLimit::new(120, 30))
+            }
+            PrincipalClass::ApiKey => (BucketLimit::new(300, 50), BucketLimit::new(1200, 200)),
+            PrincipalClass::Admin => (BucketLimit::new(3000, 500), BucketLimit::new(600, 100)),
+        };
+        Self { read, write }
+    }
+
+    /// Uses `limit` for both reads and writes
+    pub const fn uniform(limit: BucketLimit) -> Self {
+        Self {
+            read: limit,
+            write: limit,
+        }
+    }
+}
+
+/// Bucket for an expensive route class, shared by every method
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RouteRateLimit {
+    /// Paths the bucket applies to, including their subpaths
+    pub paths: Vec<String>,
+    /// Tokens added per minute
+    pub rate_per_minute: u32,
+    /// Bucket capacity
+    pub burst: u32,
+}
+
+impl RouteRateLimit {
+    /// Returns the bucket limit
+    pub fn limit(&self) -> BucketLimit {
+        BucketLimit::new(self.rate_per_minute, self.burst)
+    }
 }
 
 /// Input validation security configuration
@@ -71,6 +199,27 @@ pub struct ValidationSecurityConfig {
     /// Enable strict validation mode
     #[serde(default = "default_strict_mode")]
     pub strict_mode: bool,
+    /// Content negotiation allow lists
+    #[serde(default)]
+    pub media_types: MediaTypeSecurityConfig,
+}
+
+/// Content negotiation configuration
+///
+/// Mutating requests with a body must use one of the allowed content types
+/// and `Accept` headers must allow one of the produced types.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MediaTypeSecurityConfig {
+    /// Request body types accepted on routes without a specific entry
+    #[serde(default = "default_content_types")]
+    pub content_types: Vec<String>,
+    /// Request body types per route prefix, replacing `content_types`;
+    /// `:name` segments match any single path segment
+    #[serde(default = "default_route_content_types")]
+    pub routes: HashMap<String, Vec<String>>,
+    /// Response types the API produces
+    #[serde(default = "default_produces")]
+    pub produces: Vec<String>,
 }
 
 /// Security headers configuration
@@ -133,12 +282,12 @@ impl Default for CorsSecurityConfig {
 impl Default for RateLimitSecurityConfig {
     fn default() -> Self {
         Self {
-            anonymous_rpm: 10,
-            authenticated_rpm: 100,
-            admin_rpm: 1000,
-            per_endpoint: HashMap::new(),
+            classes: HashMap::new(),
+            routes: default_route_limits(),
             use_redis: false,
             redis_url: None,
+            auth_burst: 5,
+            auth_sustained_per_minute: 5,
         }
     }
 }
@@ -150,6 +299,17 @@ impl Default for ValidationSecurityConfig {
             max_string_length: 10_000,
             max_array_length: 1_000,
             strict_mode: true,
+            media_types: MediaTypeSecurityConfig::default(),
+        }
+    }
+}
+
+impl Default for MediaTypeSecurityConfig {
+    fn default() -> Self {
+        Self {
+            content_types: default_content_types(),
+            routes: default_route_content_types(),
+            produces: default_produces(),
         }
     }
 }
@@ -193,23 +353,19 @@ impl SecurityConfig {
                 max_age_seconds: 3600,
             },
             rate_limit: RateLimitSecurityConfig {
-                anonymous_rpm: 10,
-                authenticated_rpm: 100,
-                admin_rpm: 1000,
-                per_endpoint: [
-                    ("/auth/login".to_string(), 5),
-                    ("/auth/register".to_string(), 3),
-                ]
-                .into_iter()
-                .collect(),
+                classes: HashMap::new(),
+                routes: default_route_limits(),
                 use_redis: true,
                 redis_url: None,
+                auth_burst: 5,
+                auth_sustained_per_minute: 5,
             },
             validation: ValidationSecurityConfig {
                 max_body_size: 1024 * 1024, // 1MB
                 max_string_length: 5_000,
                 max_array_length: 500,
                 strict_mode: true,
+                media_types: MediaTypeSecurityConfig::default(),
             },
             headers: SecurityHeadersConfig {
                 enable_csp: true,
@@ -242,18 +398,27 @@ impl SecurityConfig {
                 max_age_seconds: 3600,
             },
             rate_limit: RateLimitSecurityConfig {
-                anonymous_rpm: 10000,
-                authenticated_rpm: 10000,
-                admin_rpm: 10000,
-                per_endpoint: HashMap::new(),
+                classes: PrincipalClass::ALL
+                    .into_iter()
+                    .map(|class| {
+                        (
+                            class,
+                            ClassRateLimits::uniform(BucketLimit::new(10000, 10000)),
+                        )
+                    })
+                    .collect(),
+                routes: HashMap::new(),
                 use_redis: false,
                 redis_url: None,
+                auth_burst: 1000,
+                auth_sustained_per_minute: 1000,
             },
             validation: ValidationSecurityConfig {
                 max_body_size: 10 * 1024 * 1024, // 10MB
                 max_string_length: 100_000,
                 max_array_length: 10_000,
                 strict_mode: false,
+                media_types: MediaTypeSecurityConfig::default(),
             },
             headers: SecurityHeadersConfig {
                 enable_csp: false,
@@ -301,8 +466,31 @@ impl SecurityConfig {
         }
 
         // Validate rate limits
-        if self.rate_limit.anonymous_rpm == 0 {
-            return Err("Rate limit for anonymous users cannot be 0".to_string());
+        for (class, limits) in &self.rate_limit.classes {
+            for (operation, limit) in [("read", limits.read), ("write", limits.write)] {
+                if limit.rate_per_minute == 0 || limit.burst == 0 {
+                    return Err(format!(
+                        "Rate limit {} {} rate and burst cannot be 0",
+                        class.as_str(),
+                        operation
+                    ));
+                }
+            }
+        }
+        for (name, route) in &self.rate_limit.routes {
+            if route.paths.is_empty() {
+                return Err(format!("Rate limit route {} has no paths", name));
+            }
+            if route.rate_per_minute == 0 || route.burst == 0 {
+                return Err(format!(
+                    "Rate limit route {} rate and burst cannot be 0",
+                    name
+                ));
+            }
+        }
+
+        if self.rate_limit.auth_burst == 0 {
+            return Err("Authentication rate limit burst cannot be 0".to_string());
         }
 
         // Validate Redis configuration if enabled
@@ -323,16 +511,31 @@ fn default_max_age() -> u64 {
     3600
 }
 
-fn default_anonymous_rpm() -> u32 {
-    10
+fn default_route_limits() -> HashMap<String, RouteRateLimit> {
+    [
+        ("export", "/api/v1/events/export", 10, 2),
+        ("search", "/api/v1/search", 60, 10),
+    ]
+    .into_iter()
+    .map(|(name, path, rate_per_minute, burst)| {
+        (
+            name.to_string(),
+            RouteRateLimit {
+                paths: vec![path.to_string()],
+                rate_per_minute,
+                burst,
+            },
+        )
+    })
+    .collect()
 }
 
-fn default_authenticated_rpm() -> u32 {
-    100
+fn default_auth_burst() -> u32 {
+    5
 }
 
-fn default_admin_rpm() -> u32 {
-    1000
+fn default_auth_sustained_per_minute() -> u32 {
+    5
 }
 
 fn default_max_body_size() -> usize {
@@ -351,6 +554,53 @@ fn default_strict_mode() -> bool {
     true
 }
 
+fn default_content_types() -> Vec<String> {
+    vec!["application/json".to_string()]
+}
+
+fn default_route_content_types() -> HashMap<String, Vec<String>> {
+    // CloudEvents and binary encodings are only ingested on the events
+    // route, attachments are uploaded as multipart forms
+    [
+        (
+            "/api/v1/events".to_string(),
+            vec![
+                "application/json".to_string(),
+                "application/cloudevents+json".to_string(),
+                "application/cloudevents-batch+json".to_string(),
+                #[cfg(feature = "msgpack")]
+                "application/msgpack".to_string(),
+                #[cfg(feature = "cbor")]
+                "application/cbor".to_string(),
+            ],
+        ),
+        (
+            "/api/v1/events/:id/attachments".to_string(),
+            vec!["multipart/form-data".to_string()],
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
+fn default_produces() -> Vec<String> {
+    [
+        "application/json",
+        "application/problem+json",
+        "application/openmetrics-text",
+        "text/csv",
+        "text/html",
+        "text/plain",
+        #[cfg(feature = "msgpack")]
+        "application/msgpack",
+        #[cfg(feature = "cbor")]
+        "application/cbor",
+    ]
+    .iter()
+    .map(|media_type| media_type.to_string())
+    .collect()
+}
+
 fn default_true() -> bool {
     true
 }
@@ -375,7 +625,11 @@ mod tests {
     fn test_default_security_config() {
         let config = SecurityConfig::default();
         assert!(config.cors.allow_credentials);
-        assert_eq!(config.rate_limit.anonymous_rpm, 10);
+        assert_eq!(
+            config.rate_limit.class_limits(PrincipalClass::Anonymous),
+            ClassRateLimits::default_for(PrincipalClass::Anonymous)
+        );
+        assert!(config.rate_limit.routes.contains_key("export"));
         assert!(config.monitoring.metrics_enabled);
     }
 
@@ -412,11 +666,66 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_auth_burst() {
+        let mut config = SecurityConfig::development();
+        config.rate_limit.auth_burst = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rate_limit_buckets() {
+        let mut config = SecurityConfig::development();
+        config.rate_limit.classes.insert(
+            PrincipalClass::ApiKey,
+            ClassRateLimits {
+                read: BucketLimit::new(60, 10),
+                write: BucketLimit::new(60, 0),
+            },
+        );
+        assert!(config.validate().is_err());
+
+        let mut config = SecurityConfig::development();
+        config.rate_limit.routes.insert(
+            "replay".to_string(),
+            RouteRateLimit {
+                paths: vec![],
+                rate_per_minute: 5,
+                burst: 1,
+            },
+        );
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_classes_from_yaml() {
+        let config: RateLimitSecurityConfig = serde_yaml::from_str(
+            r#"
+            classes:
+              api_key:
+                read: { rate_per_minute: 100, burst: 10 }
+                write: { rate_per_minute: 2000, burst: 400 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.class_limits(PrincipalClass::ApiKey).write,
+            BucketLimit::new(2000, 400)
+        );
+        // Missing classes and routes fall back to the defaults
+        assert_eq!(
+            config.class_limits(PrincipalClass::Admin),
+            ClassRateLimits::default_for(PrincipalClass::Admin)
+        );
+        assert_eq!(config.routes["export"].limit(), BucketLimit::new(10, 2));
+    }
+
     #[test]
     fn test_validate_valid_config() {
         let mut config = SecurityConfig::production();
         config.cors.allowed_origins = vec!["https://app.example.com".to_string()];
-        config.rate_limit.redis_url = Some("redis://localhost:6379".to_string());
+        config.rate_limit.redis_url = Some("redis://localhost:6379".to_string().into());
         assert!(config.validate().is_ok());
     }
 }
@@ -1,80 +1,447 @@
 // SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
 // SPDX-License-Identifier: Apache-2.0
 
-// Generated from xzepr-architecture-plan.md
-// Section: Multiple sections
-// Original line: 0
+// src/infrastructure/tls/mod.rs
 
-// src/infrastructure/tls/config.rs
-use rustls::{ServerConfig, PrivateKey, Certificate};
-use rustls_pemfile::{certs, pkcs8_private_keys};
+//! TLS configuration for the HTTPS server
+//!
+//! [`TlsConfigBuilder`] loads the certificate, key, and optional client CA
+//! into the rustls configuration axum-server serves, and [`TlsReloader`]
+//! swaps in renewed certificates without a restart.
+
+use crate::error::{InfrastructureError, Result};
+use crate::infrastructure::audit::{AuditAction, AuditEvent, AuditLogger, AuditOutcome};
+use crate::infrastructure::config::TlsConfig;
+
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig, SupportedProtocolVersion};
+use serde::Deserialize;
 use std::fs::File;
 use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
 
-pub struct TlsConfig {
-    cert_path: String,
-    key_path: String,
+static TLS13_ONLY: &[&SupportedProtocolVersion] = &[&rustls::version::TLS13];
+
+/// Oldest TLS version clients may negotiate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum TlsMinVersion {
+    #[default]
+    #[serde(rename = "1.2")]
+    Tls12,
+    #[serde(rename = "1.3")]
+    Tls13,
 }
 
-impl TlsConfig {
-    pub fn load_server_config(&self) -> Result<ServerConfig, TlsError> {
-        // Load certificates
-        let cert_file = File::open(&self.cert_path)?;
-        let mut cert_reader = BufReader::new(cert_file);
-        let certs: Vec<Certificate> = certs(&mut cert_reader)?
-            .into_iter()
-            .map(Certificate)
-            .collect();
+impl TlsMinVersion {
+    fn protocol_versions(self) -> &'static [&'static SupportedProtocolVersion] {
+        match self {
+            TlsMinVersion::Tls12 => rustls::ALL_VERSIONS,
+            TlsMinVersion::Tls13 => TLS13_ONLY,
+        }
+    }
+}
 
-        // Load private key
-        let key_file = File::open(&self.key_path)?;
-        let mut key_reader = BufReader::new(key_file);
-        let mut keys = pkcs8_private_keys(&mut key_reader)?;
+/// Builds the rustls server configuration from PEM files
+#[derive(Debug, Clone)]
+pub struct TlsConfigBuilder {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    client_ca_path: Option<PathBuf>,
+    min_version: TlsMinVersion,
+}
+
+impl TlsConfigBuilder {
+    /// Creates a builder serving the certificate chain in `cert_path` with
+    /// the private key in `key_path`
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            client_ca_path: None,
+            min_version: TlsMinVersion::default(),
+        }
+    }
 
-        if keys.is_empty() {
-            return Err(TlsError::NoPrivateKey);
+    /// Creates a builder from the `tls` settings
+    pub fn from_settings(settings: &TlsConfig) -> Self {
+        let builder = Self::new(&settings.cert_path, &settings.key_path)
+            .with_min_version(settings.min_version);
+        match &settings.client_ca_path {
+            Some(client_ca_path) => builder.with_client_ca(client_ca_path),
+            None => builder,
         }
+    }
+
+    /// Sets the oldest TLS version clients may negotiate
+    pub fn with_min_version(mut self, min_version: TlsMinVersion) -> Self {
+        self.min_version = min_version;
+        self
+    }
 
-        let key = PrivateKey(keys.remove(0));
+    /// Requires clients to present a certificate issued by a CA in
+    /// `client_ca_path`
+    pub fn with_client_ca(mut self, client_ca_path: impl Into<PathBuf>) -> Self {
+        self.client_ca_path = Some(client_ca_path.into());
+        self
+    }
 
-        // Configure TLS 1.3 only
-        let config = ServerConfig::builder()
-            .with_safe_default_cipher_suites()
-            .with_safe_default_kx_groups()
-            .with_protocol_versions(&[&rustls::version::TLS13])
-            .expect("TLS 1.3 should be supported")
-            .with_no_client_auth()
-            .with_single_cert(certs, key)?;
+    /// Returns the files the configuration is loaded from
+    pub fn paths(&self) -> Vec<&Path> {
+        let mut paths = vec![self.cert_path.as_path(), self.key_path.as_path()];
+        paths.extend(self.client_ca_path.as_deref());
+        paths
+    }
+
+    /// Loads and validates the server configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns a TLS configuration error if a file cannot be read or parsed,
+    /// or if the private key does not belong to the certificate.
+    pub fn build_server_config(&self) -> Result<ServerConfig> {
+        let certs = read_certs(&self.cert_path)?;
+        let key = read_private_key(&self.key_path)?;
+
+        let builder =
+            ServerConfig::builder_with_protocol_versions(self.min_version.protocol_versions());
+        let builder = match &self.client_ca_path {
+            Some(client_ca_path) => {
+                let mut roots = RootCertStore::empty();
+                for cert in read_certs(client_ca_path)? {
+                    roots.add(cert).map_err(|e| {
+                        tls_error(format!(
+                            "Invalid client CA certificate in {}: {}",
+                            client_ca_path.display(),
+                            e
+                        ))
+                    })?;
+                }
+                let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                    .build()
+                    .map_err(|e| tls_error(format!("Invalid client CA: {}", e)))?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder.with_single_cert(certs, key).map_err(|e| {
+            tls_error(format!(
+                "Cannot use certificate {} with key {}: {}",
+                self.cert_path.display(),
+                self.key_path.display(),
+                e
+            ))
+        })?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
 
         Ok(config)
     }
+
+    /// Loads the configuration axum-server serves
+    pub fn build_rustls_config(&self) -> Result<RustlsConfig> {
+        Ok(RustlsConfig::from_config(Arc::new(
+            self.build_server_config()?,
+        )))
+    }
+
+    fn modification_times(&self) -> Vec<Option<SystemTime>> {
+        self.paths()
+            .into_iter()
+            .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .collect()
+    }
 }
 
-// src/main.rs - Server startup with TLS
-use axum_server::tls_rustls::RustlsConfig;
+/// Reloads the served certificate when its files change
+///
+/// The files are polled for a new modification time, so renewals written
+/// by certbot or a mounted secret are picked up. A replacement that does
+/// not load is logged and the previous certificate keeps being served.
+pub struct TlsReloader {
+    builder: TlsConfigBuilder,
+    config: RustlsConfig,
+    audit_logger: Arc<AuditLogger>,
+    modified: Vec<Option<SystemTime>>,
+}
+
+impl TlsReloader {
+    /// Creates a reloader updating `config`, which was built by `builder`
+    pub fn new(builder: TlsConfigBuilder, config: RustlsConfig) -> Self {
+        let modified = builder.modification_times();
+        Self {
+            builder,
+            config,
+            audit_logger: Arc::new(AuditLogger::new()),
+            modified,
+        }
+    }
+
+    /// Uses `audit_logger` to record certificate reloads
+    pub fn with_audit(mut self, audit_logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = audit_logger;
+        self
+    }
+
+    /// Reloads the configuration if any of its files changed
+    ///
+    /// Returns whether a new configuration is being served. A failed
+    /// reload is not retried until the files change again.
+    pub fn run_once(&mut self) -> Result<bool> {
+        let modified = self.builder.modification_times();
+        if modified == self.modified {
+            return Ok(false);
+        }
+        self.modified = modified;
+
+        let cert_path = self.builder.cert_path.display().to_string();
+        match self.builder.build_server_config() {
+            Ok(server_config) => {
+                self.config.reload_from_config(Arc::new(server_config));
+                info!(cert_path = %cert_path, "Reloaded TLS certificate");
+                self.audit_logger.log_event(
+                    AuditEvent::builder()
+                        .action(AuditAction::ConfigChange)
+                        .resource("tls_certificate")
+                        .outcome(AuditOutcome::Success)
+                        .add_metadata("cert_path", cert_path)
+                        .build(),
+                );
+                Ok(true)
+            }
+            Err(e) => {
+                warn!(
+                    cert_path = %cert_path,
+                    error = %e,
+                    "Failed to reload TLS certificate; keeping the previous one"
+                );
+                Err(e)
+            }
+        }
+    }
+
+    /// Checks for changed files every `interval` until the task is aborted
+    pub fn spawn(mut self, interval: std::time::Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                // Failures are logged by run_once
+                let _ = self.run_once();
+            }
+        })
+    }
+}
+
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let mut reader = open(path)?;
+    let certs = rustls_pemfile::certs(&mut reader)
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(|e| tls_error(format!("Invalid certificate in {}: {}", path.display(), e)))?;
+
+    if certs.is_empty() {
+        return Err(tls_error(format!(
+            "No certificate found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
+
+fn read_private_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let mut reader = open(path)?;
+    rustls_pemfile::private_key(&mut reader)
+        .map_err(|e| tls_error(format!("Invalid private key in {}: {}", path.display(), e)))?
+        .ok_or_else(|| tls_error(format!("No private key found in {}", path.display())))
+}
+
+fn open(path: &Path) -> Result<BufReader<File>> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| tls_error(format!("Cannot read {}: {}", path.display(), e)))
+}
+
+fn tls_error(message: String) -> crate::error::Error {
+    InfrastructureError::TlsConfigError { message }.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    struct TestPki {
+        dir: PathBuf,
+    }
+
+    impl TestPki {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("xzepr-tls-{}", ulid::Ulid::new()));
+            std::fs::create_dir_all(&dir).unwrap();
+            Self { dir }
+        }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Load TLS config
-    let tls_config = TlsConfig::new(
-        "certs/server.crt".to_string(),
-        "certs/server.key".to_string(),
-    );
+        fn path(&self, name: &str) -> PathBuf {
+            self.dir.join(name)
+        }
 
-    let rustls_config = RustlsConfig::from_config(
-        Arc::new(tls_config.load_server_config()?)
-    );
+        /// Writes a new self-signed pair and moves its modification time
+        /// forward, so the change is seen even on coarse-grained clocks
+        fn write_pair(&self, generation: u64) -> rcgen::CertifiedKey {
+            let pair = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+            self.write("cert.pem", &pair.cert.pem(), generation);
+            self.write("key.pem", &pair.key_pair.serialize_pem(), generation);
+            pair
+        }
 
-    // Build app
-    let app = build_app().await?;
+        fn write(&self, name: &str, contents: &str, generation: u64) {
+            std::fs::write(self.path(name), contents).unwrap();
+            File::options()
+                .write(true)
+                .open(self.path(name))
+                .unwrap()
+                .set_modified(SystemTime::now() + Duration::from_secs(generation))
+                .unwrap();
+        }
 
-    // Start HTTPS server
-    let addr = SocketAddr::from(([0, 0, 0, 0], 8443));
-    tracing::info!("Starting HTTPS server on {}", addr);
+        fn builder(&self) -> TlsConfigBuilder {
+            TlsConfigBuilder::new(self.path("cert.pem"), self.path("key.pem"))
+        }
+    }
 
-    axum_server::bind_rustls(addr, rustls_config)
-        .serve(app.into_make_service())
-        .await?;
+    /// Runs an in-memory handshake between `server` and a client that
+    /// trusts `pair` and only speaks `version`
+    fn handshake(
+        server: Arc<ServerConfig>,
+        pair: &rcgen::CertifiedKey,
+        version: &'static SupportedProtocolVersion,
+    ) -> std::result::Result<rustls::ProtocolVersion, rustls::Error> {
+        let mut roots = RootCertStore::empty();
+        roots.add(pair.cert.der().clone()).unwrap();
+        let client = rustls::ClientConfig::builder_with_protocol_versions(&[version])
+            .with_root_certificates(roots)
+            .with_no_client_auth();
 
-    Ok(())
+        let mut client = rustls::Connection::Client(
+            rustls::ClientConnection::new(Arc::new(client), "localhost".try_into().unwrap())
+                .unwrap(),
+        );
+        let mut server = rustls::Connection::Server(rustls::ServerConnection::new(server).unwrap());
+        for _ in 0..10 {
+            if !client.is_handshaking() && !server.is_handshaking() {
+                return Ok(client.protocol_version().unwrap());
+            }
+            transfer(&mut client, &mut server)?;
+            transfer(&mut server, &mut client)?;
+        }
+        panic!("handshake did not complete");
+    }
+
+    /// Moves every pending TLS record from `from` to `to`
+    fn transfer(
+        from: &mut rustls::Connection,
+        to: &mut rustls::Connection,
+    ) -> std::result::Result<(), rustls::Error> {
+        let mut records = Vec::new();
+        while from.wants_write() {
+            from.write_tls(&mut records).unwrap();
+        }
+        let mut records = records.as_slice();
+        while !records.is_empty() {
+            to.read_tls(&mut records).unwrap();
+            to.process_new_packets()?;
+        }
+        Ok(())
+    }
+
+    impl Drop for TestPki {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn test_rejects_mismatched_key_and_cert() {
+        let pki = TestPki::new();
+        pki.write_pair(0);
+        let other = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        pki.write("key.pem", &other.key_pair.serialize_pem(), 0);
+
+        let err = pki.builder().build_server_config().unwrap_err();
+        assert!(
+            err.to_string().contains("Cannot use certificate"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_rejects_missing_files() {
+        let pki = TestPki::new();
+        let err = pki.builder().build_server_config().unwrap_err();
+        assert!(err.to_string().contains("Cannot read"), "{}", err);
+    }
+
+    #[test]
+    fn test_min_version_and_client_ca() {
+        let pki = TestPki::new();
+        let pair = pki.write_pair(0);
+        pki.write("ca.pem", &pair.cert.pem(), 0);
+
+        let config = Arc::new(
+            pki.builder()
+                .with_min_version(TlsMinVersion::Tls13)
+                .build_server_config()
+                .unwrap(),
+        );
+        let tls12 = handshake(config.clone(), &pair, &rustls::version::TLS12).unwrap_err();
+        assert!(
+            matches!(tls12, rustls::Error::PeerIncompatible(_)),
+            "{}",
+            tls12
+        );
+        assert_eq!(
+            handshake(config, &pair, &rustls::version::TLS13).unwrap(),
+            rustls::ProtocolVersion::TLSv1_3
+        );
+
+        let builder = pki.builder().with_client_ca(pki.path("ca.pem"));
+        assert_eq!(builder.paths().len(), 3);
+        assert!(builder.build_server_config().is_ok());
+    }
+
+    #[test]
+    fn test_min_version_deserializes_from_version_string() {
+        let version: TlsMinVersion = serde_json::from_str("\"1.3\"").unwrap();
+        assert_eq!(version, TlsMinVersion::Tls13);
+        assert!(serde_json::from_str::<TlsMinVersion>("\"1.1\"").is_err());
+    }
+
+    #[test]
+    fn test_invalid_replacement_keeps_previous_config() {
+        let pki = TestPki::new();
+        pki.write_pair(0);
+        let builder = pki.builder();
+        let config = builder.build_rustls_config().unwrap();
+        let mut reloader = TlsReloader::new(builder, config.clone());
+        let original = config.get_inner();
+
+        assert!(!reloader.run_once().unwrap());
+
+        pki.write("cert.pem", "not a certificate", 10);
+        assert!(reloader.run_once().is_err());
+        assert!(Arc::ptr_eq(&original, &config.get_inner()));
+
+        // Not retried until the files change again
+        assert!(!reloader.run_once().unwrap());
+
+        pki.write_pair(20);
+        assert!(reloader.run_once().unwrap());
+        assert!(!Arc::ptr_eq(&original, &config.get_inner()));
+    }
 }
@@ -29,19 +29,34 @@
 //! - Multi-layer subscriber architecture
 //! - Configurable sampling rates
 
-use opentelemetry::{global, trace::TracerProvider as _, KeyValue};
+use crate::infrastructure::config::TracingExportConfig;
+use opentelemetry::{
+    global,
+    propagation::{Extractor, TextMapPropagator},
+    trace::{TraceContextExt as _, TracerProvider as _},
+    Context, KeyValue,
+};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
     trace::{self as sdktrace, Config, RandomIdGenerator, Sampler},
     Resource,
 };
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 use tracing_subscriber::{
     fmt::{self, format::FmtSpan},
     layer::SubscriberExt,
+    reload,
     util::SubscriberInitExt,
     EnvFilter, Registry,
 };
 
+/// Log filter installed by [`init_tracing`], with the directives it
+/// started from
+static LOG_FILTER: OnceLock<(reload::Handle<EnvFilter, Registry>, String)> = OnceLock::new();
+
 /// Tracing configuration
 #[derive(Debug, Clone)]
 pub struct TracingConfig {
@@ -187,6 +202,20 @@ impl TracingConfig {
 
         config
     }
+
+    /// Applies the `tracing` section of the settings
+    ///
+    /// Export is only switched on when the section names an endpoint, so an
+    /// empty section leaves the environment-based configuration in place.
+    pub fn with_export(mut self, export: &TracingExportConfig) -> Self {
+        self.service_name = export.service_name.clone();
+        self.sample_rate = export.sampling_ratio;
+        if let Some(endpoint) = &export.otlp_endpoint {
+            self.otlp_endpoint = Some(endpoint.clone());
+            self.enable_otlp = true;
+        }
+        self
+    }
 }
 
 /// Initialize OpenTelemetry tracer with OTLP exporter
@@ -221,7 +250,8 @@ fn init_otlp_tracer(
         .with_endpoint(otlp_endpoint)
         .build_span_exporter()?;
 
-    // Configure sampler based on sample rate
+    // Configure sampler based on sample rate; a caller's sampling decision
+    // in `traceparent` takes precedence so traces are not cut in half
     let sampler = if config.sample_rate >= 1.0 {
         Sampler::AlwaysOn
     } else if config.sample_rate <= 0.0 {
@@ -229,6 +259,7 @@ fn init_otlp_tracer(
     } else {
         Sampler::TraceIdRatioBased(config.sample_rate)
     };
+    let sampler = Sampler::ParentBased(Box::new(sampler));
 
     // Create resource with service information
     let resource = Resource::new(vec![
@@ -287,13 +318,14 @@ pub fn init_tracing(config: TracingConfig) -> Result<(), Box<dyn std::error::Err
         return Ok(());
     }
 
-    // Create environment filter
-    let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-        EnvFilter::new(format!(
-            "{}={},tower_http=debug,axum=debug",
-            config.service_name, config.log_level
-        ))
-    });
+    // Create environment filter; targets are module paths, so the crate
+    // name is used even when the service is exported under another name
+    let env_filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(log_filter_directives(&config.log_level)));
+    // The filter can be swapped later when the log level is reloaded
+    let initial_directives = env_filter.to_string();
+    let (env_filter, filter_handle) = reload::Layer::new(env_filter);
+    let _ = LOG_FILTER.set((filter_handle, initial_directives));
 
     // Initialize OTLP tracer if enabled and get tracer
     let otlp_tracer = if config.enable_otlp {
@@ -466,6 +498,48 @@ pub fn extract_trace_context(headers: &axum::http::HeaderMap) -> Option<String>
     None
 }
 
+/// Reads the W3C `traceparent` and `tracestate` headers of a request
+///
+/// Returns an empty context when the caller did not send a valid trace
+/// context, so the request starts a new trace.
+pub fn extract_parent_context(headers: &axum::http::HeaderMap) -> Context {
+    TraceContextPropagator::new().extract(&HeaderExtractor(headers))
+}
+
+/// Returns the W3C trace context headers of the current span
+///
+/// Used to carry the trace into messages sent to other services. The map
+/// is empty when no OpenTelemetry layer is installed.
+pub fn current_trace_headers() -> HashMap<String, String> {
+    let mut headers = HashMap::new();
+    TraceContextPropagator::new().inject_context(&tracing::Span::current().context(), &mut headers);
+    headers
+}
+
+/// Returns the trace id of the current span if its trace is sampled
+///
+/// Used to link metrics to traces; unsampled traces are never exported,
+/// so pointing at them would lead nowhere.
+pub fn current_trace_id() -> Option<String> {
+    let context = tracing::Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    (span_context.is_valid() && span_context.is_sampled())
+        .then(|| span_context.trace_id().to_string())
+}
+
+struct HeaderExtractor<'a>(&'a axum::http::HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
 /// Inject trace context into HTTP headers
 ///
 /// Used to propagate traces to downstream services.
@@ -480,6 +554,42 @@ pub fn inject_trace_context(headers: &mut axum::http::HeaderMap, trace_id: &str)
     // Example: 00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01
 }
 
+/// Returns the filter directives that log the xzepr crate at `level`
+fn log_filter_directives(level: &str) -> String {
+    format!(
+        "{}={},tower_http=debug,axum=debug",
+        env!("CARGO_CRATE_NAME"),
+        level
+    )
+}
+
+/// Checks that `level` is a log level the filter accepts
+pub fn validate_log_level(level: &str) -> Result<(), String> {
+    level
+        .parse::<tracing::level_filters::LevelFilter>()
+        .map(|_| ())
+        .map_err(|_| format!("'{}' is not a log level", level))
+}
+
+/// Logs the xzepr crate at `level` from now on
+///
+/// `None` restores the filter tracing was initialized with. Does nothing
+/// before [`init_tracing`] has run.
+pub fn set_log_level(level: Option<&str>) -> Result<(), String> {
+    let Some((handle, initial)) = LOG_FILTER.get() else {
+        return Ok(());
+    };
+    let directives = match level {
+        Some(level) => {
+            validate_log_level(level)?;
+            log_filter_directives(level)
+        }
+        None => initial.clone(),
+    };
+    let filter = EnvFilter::try_new(&directives).map_err(|e| e.to_string())?;
+    handle.reload(filter).map_err(|e| e.to_string())
+}
+
 /// Generate a unique trace ID
 ///
 /// Creates a trace ID compatible with distributed tracing systems.
@@ -596,6 +706,55 @@ mod tests {
         assert!(trace_id.is_none());
     }
 
+    #[test]
+    fn test_with_export_enables_otlp_only_with_endpoint() {
+        let export = TracingExportConfig {
+            otlp_endpoint: None,
+            sampling_ratio: 0.2,
+            service_name: "xzepr-edge".to_string(),
+            log_level: None,
+        };
+        let config = TracingConfig::default().with_export(&export);
+        assert!(!config.enable_otlp);
+        assert_eq!(config.sample_rate, 0.2);
+        assert_eq!(config.service_name, "xzepr-edge");
+
+        let export = TracingExportConfig {
+            otlp_endpoint: Some("http://collector:4317".to_string()),
+            ..export
+        };
+        let config = TracingConfig::default().with_export(&export);
+        assert!(config.enable_otlp);
+        assert_eq!(
+            config.otlp_endpoint.as_deref(),
+            Some("http://collector:4317")
+        );
+    }
+
+    #[test]
+    fn test_extract_parent_context() {
+        use axum::http::HeaderMap;
+        use opentelemetry::trace::TraceContextExt;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+                .parse()
+                .unwrap(),
+        );
+        let context = extract_parent_context(&headers);
+        let span_context = context.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "0af7651916cd43dd8448eb211c80319c"
+        );
+
+        let context = extract_parent_context(&HeaderMap::new());
+        assert!(!context.span().span_context().is_valid());
+    }
+
     #[test]
     fn test_inject_trace_context() {
         use axum::http::HeaderMap;
@@ -6,8 +6,14 @@
 pub mod api;
 pub mod application;
 pub mod auth;
+#[cfg(feature = "bench")]
+pub mod bench;
 pub mod domain;
+pub mod embed;
 pub mod error;
+#[cfg(any(test, feature = "test-util"))]
+pub mod fixtures;
+pub mod i18n;
 pub mod infrastructure;
 pub mod opa;
 
@@ -27,6 +33,9 @@ pub use infrastructure::messaging::TopicManager;
 // Application services
 pub use application::handlers::{EventHandler, EventReceiverGroupHandler, EventReceiverHandler};
 
+// Embedding
+pub use embed::{XzeprBuilder, XzeprHandle};
+
 // GraphQL
 pub use api::graphql::{create_schema, Schema};
 
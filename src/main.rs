@@ -11,13 +11,15 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use axum::{
     body::Bytes,
-    extract::{Path, Query, State},
-    http::{header, HeaderValue, Method, StatusCode},
+    extract::{DefaultBodyLimit, Multipart, OriginalUri, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware,
     response::{IntoResponse, Json},
-    routing::{delete, get, post, put},
-    Router,
+    routing::{delete, get, post, put, MethodRouter},
+    Extension, Router,
 };
 use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::PgPool;
@@ -25,27 +27,92 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 use std::{net::SocketAddr, sync::Arc};
 use tokio::signal;
-use tower::ServiceBuilder;
-use tower_http::{
-    cors::CorsLayer,
-    trace::{DefaultMakeSpan, DefaultOnResponse, TraceLayer},
-    LatencyUnit,
-};
-use tracing::{error, info, warn, Level};
+use tower_http::cors::CorsLayer;
+use tracing::{error, info, warn};
 use xzepr::{
-    api::graphql::{create_schema, graphql_handler, graphql_health, graphql_playground},
-    application::handlers::{EventHandler, EventReceiverGroupHandler, EventReceiverHandler},
-    auth::api_key::UserRepository,
+    api::graphql::{
+        create_schema_with_search, graphql_handler, graphql_health, graphql_playground,
+        query_limits_middleware, PersistedQueryStore, QueryLimits,
+    },
+    api::middleware::{
+        api_key_auth_middleware, auth_rate_limit_middleware, binary_body_middleware,
+        client_ip_middleware, content_negotiation_middleware, deadline_middleware,
+        deprecation_middleware, jwt_auth_middleware, localize_errors_middleware,
+        maintenance_middleware, problem_details_middleware, rbac_enforcement_middleware,
+        tracing_middleware, ApiKeyPrincipal, AuthRateLimitConfig, AuthRateLimiterState,
+        AuthenticatedUser, ClientIp, ContentNegotiationConfig, DeprecationRegistry,
+        JwtMiddlewareState, MaintenanceGuard, RequestDeadlines, TrustedProxies, API_KEY_HEADER,
+    },
+    api::rest::about::AboutInfo,
+    api::rest::health::StartupGate,
+    api::rest::rbac_matrix::RbacMatrix,
+    api::router::metrics_handler,
+    application::authorization::AuthorizationService,
+    application::domain_events::DomainEventBus,
+    application::handlers::{
+        AdminSummaryHandler, AuditChainHandler, AuditRetentionHandler, BulkDeleteHandler,
+        ChangeFeedHandler, DeliveryFailureHandler, EventAttachmentHandler, EventBatchHandler,
+        EventHandler, EventNotifier, EventOutboxRelay, EventPayloadCollector, EventPollHandler,
+        EventReceiverGroupHandler, EventReceiverHandler, EventRetentionHandler,
+        EventRollupReconciler, EventStatsHandler, GroupCompletenessHandler, GroupTopicFanout,
+        KafkaForwarder, MembershipExpiryHandler, PayloadOffloader, ReceiverActivityTracker,
+        ReceiverAutoDisableHandler, ReceiverDependents, ReceiverHygieneHandler,
+        ReceiverTimelineHandler, ResourceHistoryHandler, SchemaPreviewHandler, SchemaResolver,
+        SearchHandler, SystemEventFactory, UserImportHandler, UserPreferencesHandler,
+    },
+    auth::api_key::{ApiKeyExpiryNotifier, ApiKeyService, UserRepository},
+    auth::api_key_usage::ApiKeyUsageTracker,
+    auth::jwt::JwtService,
+    auth::sessions::SessionService,
     domain::entities::{
-        event::Event, event_receiver::EventReceiver, event_receiver_group::EventReceiverGroup,
+        event::{Event, EventOrigin},
+        event_receiver::EventReceiver,
+        event_receiver_group::EventReceiverGroup,
     },
     domain::repositories::{
+        audit_record_repo::AuditRecordRepository,
+        change_feed_repo::{
+            Change, ChangeCursor, ChangeSet, EventReceiverChangeFeedRepository,
+            EventReceiverGroupChangeFeedRepository,
+        },
+        event_feed_repo::EventFeedRepository,
         event_receiver_group_repo::{EventReceiverGroupRepository, FindEventReceiverGroupCriteria},
         event_receiver_repo::{EventReceiverRepository, FindEventReceiverCriteria},
         event_repo::{EventRepository, FindEventCriteria},
+        receiver_activity_repo::ReceiverActivityRepository,
+    },
+    domain::value_objects::{
+        description::MAX_DESCRIPTION_LENGTH, EventId, EventReceiverGroupId, EventReceiverId, UserId,
+    },
+    i18n::{self, MessageCatalogs},
+    infrastructure::config::{ErrorFormat, GraphQLConfig},
+    infrastructure::startup::{
+        check_jwt_keys, classify_kafka_error, classify_opa_error, classify_sqlx_error, prime_opa,
+        retry_with_backoff, DependencyError, RetryPolicy, DEPENDENCY_DATABASE, DEPENDENCY_KAFKA,
+        DEPENDENCY_MIGRATIONS, DEPENDENCY_OPA, DEPENDENCY_SCHEMA, DEPENDENCY_WARMUP,
+    },
+    infrastructure::{
+        audit::{install_sink, AuditRecordSink},
+        build_archive_store, build_blob_store,
+        database::{
+            check_schema, LiveSchema, PostgresAuditRecordRepository,
+            PostgresDeliveryFailureRepository, PostgresEventAttachmentRepository,
+            PostgresGroupTopicOutboxRepository, PostgresPersistedQueryRepository,
+            PostgresReleaseCompletionRepository, PostgresSearchRepository, SchemaCheckMode,
+            EXPECTED_SCHEMA,
+        },
+        init_tracing,
+        memory::InMemoryAttestationRepository,
+        messaging::producer::KafkaEventPublisher,
+        messaging::KafkaTopicProvisioner,
+        migrations::MigrationRunner,
+        set_log_level, shutdown_tracing, AuditLogger, BuildInfo, CachedEventReceiverRepository,
+        ConnectionLimitAcceptor, ConnectionLimits, DeadlineEventReceiverGroupRepository,
+        DeadlineEventReceiverRepository, DeadlineEventRepository, FeatureFlags, HttpClientFactory,
+        JobRunner, MaintenanceMode, PrometheusMetrics, ReloadableSettings, SecurityMonitor,
+        StartupReadiness, TlsConfigBuilder, TlsReloader, TracingConfig, Warmup,
     },
-    domain::value_objects::{EventId, EventReceiverGroupId, EventReceiverId, UserId},
-    infrastructure::messaging::producer::KafkaEventPublisher,
+    opa::client::OpaClient,
     PostgresApiKeyRepository, PostgresUserRepository, Settings, TopicManager,
 };
 
@@ -58,114 +125,377 @@ pub struct AppState {
     pub db_pool: PgPool,
     pub user_repo: Arc<PostgresUserRepository>,
     pub api_key_repo: Arc<PostgresApiKeyRepository>,
+    pub api_key_service: Arc<ApiKeyService>,
     // Domain handlers
     pub event_handler: EventHandler,
+    pub event_batch_handler: EventBatchHandler,
     pub event_receiver_handler: EventReceiverHandler,
     pub event_receiver_group_handler: EventReceiverGroupHandler,
-    // GraphQL schema
+    pub authorization: AuthorizationService,
+    pub change_feed_handler: ChangeFeedHandler,
+    pub user_preferences_handler: UserPreferencesHandler,
+    pub feature_flags: FeatureFlags,
+    pub admin_summary_handler: AdminSummaryHandler,
+    pub schema_preview_handler: SchemaPreviewHandler,
+    pub bulk_delete_handler: BulkDeleteHandler,
+    pub event_poll_handler: EventPollHandler,
+    pub search_handler: SearchHandler,
+    // GraphQL schema and endpoint settings
     pub graphql_schema: Schema,
+    pub graphql: GraphQLConfig,
+    // Shape of error response bodies
+    pub error_format: ErrorFormat,
+    // Registered deprecations and their usage
+    pub deprecations: Arc<DeprecationRegistry>,
+    // Read-only maintenance mode
+    pub maintenance: MaintenanceMode,
+    // Instance description for support diagnostics
+    pub about: Arc<AboutInfo>,
+    // Scheduled background jobs
+    pub jobs: Arc<JobRunner>,
+    // Deliveries given up on, across every mechanism
+    pub delivery_failure_handler: DeliveryFailureHandler,
+    // Verification of persisted audit records, when enabled
+    pub audit_chain: Option<AuditChainHandler>,
+    // Files attached to events, when enabled
+    pub attachment_handler: Option<EventAttachmentHandler>,
+    // Bulk user import from CSV
+    pub user_import_handler: UserImportHandler,
+    // Session listing, revocation, and impersonation, when JWTs are enabled
+    pub session_service: Option<Arc<SessionService>>,
+    // Automatic and registered GraphQL persisted queries
+    pub persisted_queries: PersistedQueryStore,
+    // Settings applied without a restart
+    pub runtime_settings: ReloadableSettings,
+    // Route budgets bounding each request's deadline
+    pub request_deadlines: RequestDeadlines,
+    // Prometheus registry served from /metrics
+    pub metrics: Arc<PrometheusMetrics>,
+}
+
+#[derive(Parser)]
+#[command(name = "xzepr")]
+#[command(about = "XZepr event tracking server")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Apply pending database migrations and exit
+    Migrate {
+        /// Print applied and pending migrations without changing anything
+        #[arg(long, conflicts_with = "dry_run")]
+        status: bool,
+        /// Report the migrations that would run without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Inspect role-based access control
+    Rbac {
+        #[command(subcommand)]
+        command: RbacCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum RbacCommand {
+    /// Print the permission and role each route and GraphQL operation
+    /// requires, and the roles that satisfy it
+    ///
+    /// The matrix is what the library's protected router enforces; this
+    /// server enforces it only on administration and API key routes.
+    Matrix {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = MatrixFormat::Json)]
+        format: MatrixFormat,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum MatrixFormat {
+    Json,
+    Csv,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing subscriber for structured logging
-    tracing_subscriber::fmt()
-        .with_target(false)
-        .with_level(true)
-        .with_thread_ids(true)
-        .with_file(true)
-        .with_line_number(true)
-        .init();
+    let cli = Cli::parse();
 
-    info!("Starting XZepr Event Tracking Server");
+    // The matrix is compiled in, so it needs no configuration
+    if let Some(Command::Rbac {
+        command: RbacCommand::Matrix { format },
+    }) = cli.command
+    {
+        return print_rbac_matrix(format);
+    }
 
-    // Load configuration
+    // Load configuration first; it decides where spans are exported
     let settings = Settings::new().context("Failed to load configuration")?;
+    let about = AboutInfo::from_settings(&settings);
+
+    // Initialize structured logging and, when an OTLP endpoint is
+    // configured, span export
+    init_tracing(TracingConfig::from_env().with_export(&settings.tracing))
+        .map_err(|e| anyhow::anyhow!("Failed to initialize tracing: {}", e))?;
+    if settings.tracing.log_level.is_some() {
+        set_log_level(settings.tracing.log_level.as_deref()).map_err(anyhow::Error::msg)?;
+    }
+
+    if let Some(Command::Migrate { status, dry_run }) = cli.command {
+        return migrate(&settings, status, dry_run).await;
+    }
+
+    // Settings that SIGHUP and the admin API reload without a restart
+    let runtime_settings =
+        ReloadableSettings::new(&settings).with_audit(Arc::new(AuditLogger::new()));
+    #[cfg(unix)]
+    runtime_settings
+        .spawn_sighup_listener()
+        .context("Failed to install the SIGHUP handler")?;
+
+    // Install error message catalogs before any request is served
+    let mut catalogs = MessageCatalogs::bundled();
+    if let Some(locales_dir) = &settings.i18n.locales_dir {
+        let loaded = catalogs
+            .load_dir(locales_dir)
+            .context("Failed to load message catalogs")?;
+        info!("Loaded message catalogs {:?} from {}", loaded, locales_dir);
+    }
+    if i18n::install(catalogs).is_err() {
+        warn!("Message catalogs were already installed");
+    }
+
+    info!("Starting XZepr Event Tracking Server");
     info!("Configuration loaded successfully");
     info!(
         "  Server: {}:{}",
         settings.server.host, settings.server.port
     );
     info!("  HTTPS: {}", settings.server.enable_https);
-    info!("  Database: {}", mask_password(&settings.database.url));
+    info!("  Database: {}", settings.database.url.masked_url());
     info!("  Kafka: {}", settings.kafka.brokers);
 
+    // Validate proxy and CA settings and load the CA bundle once; every
+    // outbound HTTP client is built from this factory
+    let http_clients = HttpClientFactory::new(&settings.http_client)
+        .context("Invalid outbound HTTP client configuration")?;
+    if let Some(proxy_url) = &settings.http_client.proxy_url {
+        info!("  Outbound proxy: {}", proxy_url.masked_url());
+    }
+
+    // Determine bind address
+    let addr = SocketAddr::from((
+        settings
+            .server
+            .host
+            .parse::<std::net::IpAddr>()
+            .unwrap_or_else(|_| std::net::IpAddr::V4(std::net::Ipv4Addr::new(0, 0, 0, 0))),
+        settings.server.port,
+    ));
+
+    // Bind the listener before waiting on dependencies so orchestrators see
+    // a live (but not ready) server instead of a crash loop
+    let opa_client = settings
+        .opa
+        .clone()
+        .filter(|config| config.enabled)
+        .map(|config| OpaClient::with_http_client_factory(config, &http_clients))
+        .transpose()
+        .context("Failed to build OPA client")?;
+    let mut dependencies = vec![DEPENDENCY_DATABASE, DEPENDENCY_MIGRATIONS];
+    if settings.database.schema_check != SchemaCheckMode::Off {
+        dependencies.push(DEPENDENCY_SCHEMA);
+    }
+    dependencies.push(DEPENDENCY_KAFKA);
+    if opa_client.is_some() {
+        dependencies.push(DEPENDENCY_OPA);
+    }
+    dependencies.push(DEPENDENCY_WARMUP);
+    let readiness = StartupReadiness::new(dependencies);
+    let gate = StartupGate::new(readiness.clone());
+    let server = spawn_server(&settings, addr, gate.router()).await?;
+    info!("Liveness probe:     http://{}/health/live", addr);
+    info!("Readiness probe:    http://{}/health/ready", addr);
+
+    let retry_policy = RetryPolicy::from_config(&settings.startup);
+    info!(
+        "Waiting up to {}s for startup dependencies",
+        settings.startup.max_wait_seconds
+    );
+
     // Connect to database
     info!("Connecting to database...");
-    let db_pool = PgPool::connect(&settings.database.url)
-        .await
-        .context("Failed to connect to database")?;
+    let db_pool = retry_with_backoff(DEPENDENCY_DATABASE, &retry_policy, || async {
+        let pool = PgPool::connect(settings.database.url.expose())
+            .await
+            .map_err(|e| classify_sqlx_error(&e))?;
+        sqlx::query("SELECT 1")
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| classify_sqlx_error(&e))?;
+        Ok(pool)
+    })
+    .await
+    .context("Failed to connect to database")?;
+    readiness.mark_ready(DEPENDENCY_DATABASE);
     info!("Database connection established");
 
     // Run migrations
     info!("Running database migrations...");
-    sqlx::migrate!("./migrations")
-        .run(&db_pool)
-        .await
-        .context("Failed to run database migrations")?;
+    let migrator = sqlx::migrate!("./migrations");
+    let migration_runner = MigrationRunner::new(&migrator).with_report_path(
+        settings
+            .database
+            .migration_report_path
+            .as_ref()
+            .map(std::path::PathBuf::from),
+    );
+    let migration_status = retry_with_backoff(DEPENDENCY_MIGRATIONS, &retry_policy, || async {
+        gate.record_migrations(migration_runner.status(&db_pool).await?);
+        migration_runner.run(&db_pool).await?;
+        migration_runner.status(&db_pool).await
+    })
+    .await
+    .context("Failed to run database migrations")?;
+    gate.record_migrations(migration_status.clone());
+    let about = about.with_migrations(&migration_status);
+    readiness.mark_ready(DEPENDENCY_MIGRATIONS);
     info!("Database migrations completed");
 
-    // Test database connection
-    sqlx::query("SELECT 1")
-        .fetch_one(&db_pool)
+    // Compare the migrated schema with what the repositories expect
+    let schema_check = settings.database.schema_check;
+    if schema_check != SchemaCheckMode::Off {
+        info!("Checking database schema for drift...");
+        let live = retry_with_backoff(DEPENDENCY_SCHEMA, &retry_policy, || async {
+            LiveSchema::introspect(&db_pool)
+                .await
+                .map_err(|e| classify_sqlx_error(&e))
+        })
         .await
-        .context("Failed to verify database connection")?;
-    info!("Database health check passed");
+        .context("Failed to read database schema")?;
+        let report = check_schema(schema_check, &live, EXPECTED_SCHEMA)
+            .context("Database schema check failed")?;
+        gate.record_schema_check(report);
+        readiness.mark_ready(DEPENDENCY_SCHEMA);
+    }
 
     // Initialize Kafka topic
     info!("Ensuring Kafka topic exists...");
-    let topic_manager = TopicManager::new(&settings.kafka.brokers)
-        .context("Failed to create Kafka topic manager")?;
+    let topic_manager =
+        TopicManager::with_auth(&settings.kafka.brokers, settings.kafka.auth.as_ref())
+            .context("Failed to create Kafka topic manager")?;
+
+    let created = retry_with_backoff(DEPENDENCY_KAFKA, &retry_policy, || async {
+        topic_manager
+            .ensure_topic_exists(
+                &settings.kafka.default_topic,
+                settings.kafka.default_topic_partitions,
+                settings.kafka.default_topic_replication_factor,
+            )
+            .await
+            .map_err(|e| classify_kafka_error(&e))
+    })
+    .await
+    .context("Failed to ensure Kafka topic exists")?;
+    readiness.mark_ready(DEPENDENCY_KAFKA);
 
-    match topic_manager
-        .ensure_topic_exists(
-            &settings.kafka.default_topic,
+    if created {
+        info!(
+            "Created Kafka topic '{}' with {} partitions and replication factor {}",
+            settings.kafka.default_topic,
             settings.kafka.default_topic_partitions,
-            settings.kafka.default_topic_replication_factor,
-        )
+            settings.kafka.default_topic_replication_factor
+        );
+    } else {
+        info!(
+            "Kafka topic '{}' already exists",
+            settings.kafka.default_topic
+        );
+    }
+
+    // Probe OPA when policy evaluation is enabled
+    if let Some(ref opa_client) = opa_client {
+        info!("Checking OPA health...");
+        retry_with_backoff(DEPENDENCY_OPA, &retry_policy, || async {
+            opa_client
+                .health_check()
+                .await
+                .map_err(|e| classify_opa_error(&e))
+        })
         .await
-    {
-        Ok(created) => {
-            if created {
-                info!(
-                    "Created Kafka topic '{}' with {} partitions and replication factor {}",
-                    settings.kafka.default_topic,
-                    settings.kafka.default_topic_partitions,
-                    settings.kafka.default_topic_replication_factor
-                );
-            } else {
-                info!(
-                    "Kafka topic '{}' already exists",
-                    settings.kafka.default_topic
-                );
-            }
-        }
-        Err(e) => {
-            error!(
-                "Failed to ensure Kafka topic exists: {}. Continuing anyway...",
-                e
-            );
-            // Don't fail startup if Kafka topic creation fails
-            // This allows the server to start even if Kafka is temporarily unavailable
-        }
+        .context("Failed to reach OPA")?;
+        readiness.mark_ready(DEPENDENCY_OPA);
     }
 
     // Initialize authentication repositories
     let user_repo = Arc::new(PostgresUserRepository::new(db_pool.clone()));
     let api_key_repo = Arc::new(PostgresApiKeyRepository::new(db_pool.clone()));
+    // Key usage is buffered so authentication never waits on a write
+    let api_key_usage = ApiKeyUsageTracker::new(
+        api_key_repo.clone(),
+        Arc::new(
+            xzepr::infrastructure::database::PostgresApiKeyUsageRepository::new(db_pool.clone()),
+        ),
+    );
+    api_key_usage.clone().spawn(std::time::Duration::from_secs(
+        settings.hygiene.key_usage_flush_seconds,
+    ));
+    let api_key_service = Arc::new(
+        ApiKeyService::new(user_repo.clone(), api_key_repo.clone())
+            .with_rotation_grace(chrono::Duration::seconds(
+                settings.auth.api_keys.rotation_grace_seconds as i64,
+            ))
+            .with_negative_cache_ttl(std::time::Duration::from_secs(
+                settings.auth.api_keys.negative_cache_ttl_seconds,
+            ))
+            .with_usage_tracker(api_key_usage)
+            .with_stale_after(chrono::Duration::days(
+                settings.hygiene.key_stale_days as i64,
+            )),
+    );
+    if settings.auth.api_keys.expiry_check_interval_seconds > 0 {
+        ApiKeyExpiryNotifier::new(
+            api_key_repo.clone(),
+            chrono::Duration::days(settings.auth.api_keys.expiry_warning_days as i64),
+        )
+        .spawn(std::time::Duration::from_secs(
+            settings.auth.api_keys.expiry_check_interval_seconds,
+        ));
+    }
 
     // Initialize domain repositories (using mock for now)
     // TODO: Replace with PostgreSQL implementations when available
     info!("Initializing event repositories (in-memory mode)...");
-    let event_repo = Arc::new(MockEventRepository::new());
-    let receiver_repo = Arc::new(MockEventReceiverRepository::new());
-    let group_repo = Arc::new(MockEventReceiverGroupRepository::new());
+    let event_store = Arc::new(MockEventRepository::new());
+    let receiver_store = Arc::new(MockEventReceiverRepository::new());
+    let group_store = Arc::new(MockEventReceiverGroupRepository::new());
+    // Calls made while handling a request stop at the request's deadline
+    let event_repo = Arc::new(DeadlineEventRepository::new(event_store.clone()));
+    let receiver_repo = Arc::new(DeadlineEventReceiverRepository::new(receiver_store.clone()));
+    // Lookups by id are cached, and unknown ids are remembered briefly so a
+    // producer retrying with a deleted receiver does not reach the store
+    let receiver_lookups = Arc::new(
+        CachedEventReceiverRepository::new(receiver_repo.clone())
+            .with_ttl(std::time::Duration::from_secs(
+                settings.ingestion.receiver_cache_ttl_seconds,
+            ))
+            .with_negative_ttl(std::time::Duration::from_secs(
+                settings.ingestion.receiver_negative_cache_ttl_seconds,
+            )),
+    );
+    let group_repo = Arc::new(DeadlineEventReceiverGroupRepository::new(
+        group_store.clone(),
+    ));
 
     // Initialize Kafka event publisher
     info!("Initializing Kafka event publisher...");
-    let event_publisher = match KafkaEventPublisher::new(
+    let event_publisher = match KafkaEventPublisher::with_config(
         &settings.kafka.brokers,
         &settings.kafka.default_topic,
+        settings.kafka.auth.as_ref(),
+        &settings.kafka.producer,
     ) {
         Ok(publisher) => {
             info!(
@@ -183,54 +513,639 @@ async fn main() -> Result<()> {
         }
     };
 
+    // Handlers announce stored changes in process; the Kafka forwarder
+    // publishes the system events of created receivers and groups
+    let domain_events = DomainEventBus::new();
+    if let Some(ref publisher) = event_publisher {
+        domain_events.register(Arc::new(KafkaForwarder::new(publisher.clone())));
+    }
+
+    // Counts publish outcomes and system events that could not be built;
+    // served from /metrics
+    let metrics =
+        Arc::new(PrometheusMetrics::new().context("Failed to create the metrics registry")?);
+
     // Create application handlers with event publisher
     let event_handler = if let Some(ref publisher) = event_publisher {
-        EventHandler::with_publisher(event_repo, receiver_repo.clone(), publisher.clone())
+        EventHandler::with_publisher(
+            event_repo.clone(),
+            receiver_lookups.clone(),
+            publisher.clone(),
+        )
+    } else {
+        EventHandler::new(event_repo.clone(), receiver_lookups.clone())
+    };
+    let event_handler = event_handler
+        .with_event_bus(domain_events.clone())
+        .with_metrics(metrics.clone());
+
+    // Extract subject digests and builders from attestation events; kept
+    // next to the events, which this server holds in memory
+    let event_handler =
+        event_handler.with_attestations(Arc::new(InMemoryAttestationRepository::default()));
+
+    // Check candidate schemas against stored events without saving them
+    let schema_preview_handler = SchemaPreviewHandler::new(event_repo.clone());
+
+    // Count events seen versus stored for sampled receivers
+    let event_handler = event_handler.with_sampling_counters(Arc::new(
+        xzepr::infrastructure::database::PostgresEventReceiverRepository::new(db_pool.clone()),
+    ));
+
+    // Track when each receiver last saw an event, written behind ingestion
+    // in batches, and report receivers that went quiet
+    let receiver_activity = Arc::new(
+        xzepr::infrastructure::database::PostgresEventReceiverRepository::new(db_pool.clone()),
+    );
+    let activity_tracker = ReceiverActivityTracker::new(receiver_activity.clone());
+    activity_tracker
+        .clone()
+        .spawn(std::time::Duration::from_secs(
+            settings.hygiene.activity_flush_seconds,
+        ));
+    let event_handler = event_handler.with_activity_tracker(activity_tracker);
+    // System events are stored with origin `system` so admins can query
+    // them without polluting user lists and stats
+    let system_events = SystemEventFactory::new().with_store(event_repo.clone());
+    let hygiene_handler =
+        ReceiverHygieneHandler::new(receiver_activity.clone(), receiver_activity.clone())
+            .with_stale_days(settings.hygiene.stale_days)
+            .with_heartbeats(receiver_activity.clone());
+    let hygiene_handler = if settings.hygiene.emit_system_events {
+        let hygiene_handler = hygiene_handler.with_system_event_factory(system_events.clone());
+        match &event_publisher {
+            Some(publisher) => hygiene_handler.with_publisher(publisher.clone()),
+            None => hygiene_handler,
+        }
+    } else {
+        hygiene_handler
+    };
+    hygiene_handler.spawn(std::time::Duration::from_secs(
+        settings.hygiene.report_interval_seconds,
+    ));
+
+    // Collect deliveries given up on by any mechanism in one log, so
+    // on-call finds them in one place
+    let delivery_failure_handler = DeliveryFailureHandler::new(Arc::new(
+        PostgresDeliveryFailureRepository::new(db_pool.clone()),
+    ));
+    let delivery_failure_handler = if settings.messaging.delivery_failure_system_events {
+        delivery_failure_handler.with_system_event_factory(system_events.clone())
+    } else {
+        delivery_failure_handler
+    };
+    domain_events.register(Arc::new(delivery_failure_handler.clone()));
+
+    // Wake long-polling consumers as soon as an event is stored
+    let event_notifier = EventNotifier::new();
+    let event_handler = event_handler.with_notifier(event_notifier.clone());
+    let event_poll_handler = EventPollHandler::new(event_store.clone(), event_notifier)
+        .with_max_timeout(std::time::Duration::from_secs(
+            settings.long_poll.max_timeout_seconds,
+        ))
+        .with_batch_size(settings.long_poll.batch_size)
+        .with_max_in_flight(settings.long_poll.max_in_flight_per_principal);
+
+    // Let events provision their receiver when the policy allows it
+    let event_handler =
+        event_handler.with_receiver_provisioning(settings.ingestion.receiver_provisioning);
+
+    // Cap the events each receiver accepts per UTC day and scan payloads
+    // for values that look like personal data; both follow reloads
+    let event_handler = event_handler.with_runtime_settings(runtime_settings.clone());
+
+    // Keep payloads over the threshold in blob storage, with a summary on
+    // the event
+    let payload_offloader = match settings.ingestion.offload_threshold_bytes {
+        Some(threshold) => {
+            info!(
+                "Event payload offloading enabled (threshold: {} bytes)",
+                threshold
+            );
+            let blob_store = build_blob_store(&settings.attachments, &http_clients)
+                .context("Failed to configure payload offload storage")?;
+            Some(
+                PayloadOffloader::new(blob_store, threshold)
+                    .with_summary_paths(settings.ingestion.offload_summary_paths.clone()),
+            )
+        }
+        None => None,
+    };
+    let event_handler = match &payload_offloader {
+        Some(offloader) => event_handler.with_payload_offloader(offloader.clone()),
+        None => event_handler,
+    };
+
+    // Read-only maintenance mode; changes made on other instances are picked
+    // up on the feature flag refresh interval
+    let maintenance = MaintenanceMode::new()
+        .with_repository(Arc::new(
+            xzepr::infrastructure::database::PostgresMaintenanceRepository::new(db_pool.clone()),
+        ))
+        .with_audit(Arc::new(AuditLogger::new()));
+    maintenance
+        .refresh()
+        .await
+        .context("Failed to load maintenance mode")?;
+    maintenance.spawn_watcher(std::time::Duration::from_secs(
+        settings.feature_flags.refresh_interval_seconds,
+    ));
+    gate.record_maintenance(maintenance.clone());
+
+    // Keep events the stream did not accept in an outbox and retry them
+    let event_handler =
+        event_handler.with_publish_policy(settings.messaging.default_publish_policy);
+    let event_outbox =
+        Arc::new(xzepr::infrastructure::database::PostgresEventRepository::new(db_pool.clone()));
+    let event_handler = match &event_publisher {
+        Some(publisher) => {
+            // Also publish to the dedicated topics of the receiver's groups
+            let group_fanout =
+                GroupTopicFanout::new(group_repo.clone(), event_repo.clone(), publisher.clone())
+                    .with_outbox(Arc::new(PostgresGroupTopicOutboxRepository::new(
+                        db_pool.clone(),
+                    )))
+                    .with_max_attempts(settings.messaging.outbox_max_attempts)
+                    .with_maintenance(maintenance.clone())
+                    .with_event_bus(domain_events.clone());
+            group_fanout.clone().spawn(std::time::Duration::from_secs(
+                settings.messaging.outbox_relay_interval_seconds,
+            ));
+
+            EventOutboxRelay::new(event_outbox.clone(), event_repo.clone(), publisher.clone())
+                .with_max_attempts(settings.messaging.outbox_max_attempts)
+                .with_group_fanout(group_fanout.clone())
+                .with_maintenance(maintenance.clone())
+                .with_event_bus(domain_events.clone())
+                .with_topic(settings.kafka.default_topic.clone())
+                .spawn(std::time::Duration::from_secs(
+                    settings.messaging.outbox_relay_interval_seconds,
+                ));
+            event_handler
+                .with_outbox(event_outbox.clone())
+                .with_group_fanout(group_fanout)
+        }
+        None => event_handler,
+    };
+
+    // Bearer tokens are only accepted when JWT key material is configured;
+    // otherwise authenticated routes take API keys alone
+    let jwt_service = if settings.auth.jwt.has_key_material() {
+        let jwt_config = settings
+            .auth
+            .jwt
+            .to_jwt_config()
+            .map_err(anyhow::Error::msg)
+            .context("Invalid auth.jwt settings")?;
+        Some(JwtService::from_config(jwt_config).context("Failed to load JWT key material")?)
+    } else {
+        warn!("No JWT key material configured; administration requires an API key");
+        None
+    };
+    // Sessions are listed and revoked through the service issuing the
+    // tokens the JWT middleware validates
+    let session_service = jwt_service.as_ref().map(|jwt_service| {
+        Arc::new(
+            SessionService::new(Arc::new(jwt_service.clone()), user_repo.clone())
+                .with_audit(Arc::new(AuditLogger::new())),
+        )
+    });
+
+    // Periodic maintenance shares one runner so that it can be inspected
+    // and triggered through the admin API
+    let mut job_runner = JobRunner::new(&settings.jobs).with_audit(Arc::new(AuditLogger::new()));
+    // Issued tokens are recorded as sessions until they expire
+    if let Some(session_service) = &session_service {
+        job_runner = job_runner.register(session_service.clone());
+    }
+
+    // Keep audit events in a hash chain per day, verifiable through the
+    // admin API and the admin CLI, and expire them after the retention window
+    let audit_chain = if settings.audit.persist {
+        info!(
+            "Audit record persistence enabled (retention: {} days)",
+            settings.audit.retention_days
+        );
+        let audit_records: Arc<dyn AuditRecordRepository> =
+            Arc::new(PostgresAuditRecordRepository::new(db_pool.clone()));
+        let (audit_sink, _audit_writer) = AuditRecordSink::spawn(audit_records.clone());
+        if install_sink(Arc::new(audit_sink)).is_err() {
+            warn!("An audit sink was already installed; audit events are not persisted");
+        }
+        job_runner = job_runner.register(Arc::new(
+            AuditRetentionHandler::new(audit_records.clone())
+                .with_retention_days(settings.audit.retention_days)
+                .with_interval(std::time::Duration::from_secs(
+                    settings.audit.retention_interval_seconds,
+                )),
+        ));
+        Some(AuditChainHandler::new(audit_records))
     } else {
-        EventHandler::new(event_repo, receiver_repo.clone())
+        None
     };
 
-    let receiver_handler = if let Some(ref publisher) = event_publisher {
-        EventReceiverHandler::with_publisher(receiver_repo.clone(), publisher.clone())
+    // Store files attached to events next to them
+    let attachment_handler = if settings.attachments.enabled {
+        info!(
+            "Event attachments enabled (backend: {:?}, max size: {} bytes)",
+            settings.attachments.backend, settings.attachments.max_size_bytes
+        );
+        let blob_store = build_blob_store(&settings.attachments, &http_clients)
+            .context("Failed to configure attachment storage")?;
+        Some(
+            EventAttachmentHandler::new(
+                Arc::new(PostgresEventAttachmentRepository::new(db_pool.clone())),
+                blob_store,
+            )
+            .with_max_size_bytes(settings.attachments.max_size_bytes)
+            .with_allowed_content_types(settings.attachments.allowed_content_types.clone())
+            .with_receiver_quota_bytes(settings.attachments.receiver_quota_bytes),
+        )
     } else {
-        EventReceiverHandler::new(receiver_repo.clone())
+        None
     };
 
-    let group_handler = if let Some(ref publisher) = event_publisher {
-        EventReceiverGroupHandler::with_publisher(group_repo, receiver_repo, publisher.clone())
+    // Move expired events to cold storage and serve them from there
+    let event_handler = if settings.archive.enabled {
+        info!(
+            "Event archival enabled (retention: {} days, backend: {:?})",
+            settings.archive.retention_days, settings.archive.backend
+        );
+        let archive_store = build_archive_store(&settings.archive, &http_clients)
+            .context("Failed to configure event archive")?;
+        let archive_index = Arc::new(
+            xzepr::infrastructure::database::PostgresEventRepository::new(db_pool.clone()),
+        );
+        let mut retention = EventRetentionHandler::new(
+            event_repo.clone(),
+            archive_store.clone(),
+            archive_index.clone(),
+        )
+        .with_retention(chrono::Duration::days(i64::from(
+            settings.archive.retention_days,
+        )))
+        .with_batch_size(settings.archive.batch_size)
+        .with_interval(std::time::Duration::from_secs(
+            settings.archive.interval_seconds,
+        ));
+        if let Some(attachments) = &attachment_handler {
+            retention = retention.with_attachments(attachments.clone());
+        }
+        if let Some(offloader) = &payload_offloader {
+            retention = retention.with_payload_offloader(offloader.clone());
+        }
+        job_runner = job_runner.register(Arc::new(retention));
+        event_handler.with_archive(archive_store, archive_index)
     } else {
-        EventReceiverGroupHandler::new(group_repo, receiver_repo)
+        event_handler
+    };
+
+    let receiver_handler = EventReceiverHandler::new(receiver_lookups.clone())
+        .with_event_bus(domain_events.clone())
+        .with_metrics(metrics.clone());
+    // Groups are re-evaluated as events arrive; the first time every
+    // required receiver reports a release, a system event records it
+    let group_completeness = GroupCompletenessHandler::new(group_repo.clone(), event_repo.clone())
+        .with_completions(Arc::new(PostgresReleaseCompletionRepository::new(
+            db_pool.clone(),
+        )))
+        .with_system_event_factory(system_events.clone())
+        .with_event_bus(domain_events.clone());
+    domain_events.register(Arc::new(group_completeness.clone()));
+    let group_handler =
+        EventReceiverGroupHandler::new(group_repo.clone(), receiver_lookups.clone())
+            .with_event_bus(domain_events)
+            .with_completeness(group_completeness)
+            .with_metrics(metrics.clone());
+
+    // Create the dedicated topics of groups as they are configured
+    let group_handler = group_handler.with_topic_provisioner(Arc::new(KafkaTopicProvisioner::new(
+        topic_manager,
+        settings.kafka.default_topic_partitions,
+        settings.kafka.default_topic_replication_factor,
+    )));
+
+    let receiver_handler = receiver_handler.with_system_event_factory(system_events.clone());
+    let group_handler = group_handler.with_system_event_factory(system_events);
+
+    // Deleting a receiver checks and cleans up its events, groups, and keys
+    let receiver_handler = receiver_handler
+        .with_dependents(ReceiverDependents {
+            events: event_repo.clone(),
+            groups: group_repo.clone(),
+            api_keys: api_key_repo.clone(),
+        })
+        .with_recent_event_age(chrono::Duration::days(i64::from(
+            settings.hygiene.delete_recent_event_days,
+        )))
+        .with_activity(receiver_activity.clone())
+        .with_heartbeats(
+            receiver_activity.clone(),
+            settings.hygiene.heartbeat_interval_seconds,
+        );
+
+    // Versions are normalized, or rejected unless exact in strict mode
+    let version_strictness = settings.validation.version_strictness;
+    let event_handler = event_handler.with_version_strictness(version_strictness);
+    let receiver_handler = receiver_handler.with_version_strictness(version_strictness);
+    let group_handler = group_handler.with_version_strictness(version_strictness);
+
+    // Descriptions are capped by configuration, never beyond the hard limit
+    let max_description_length = settings
+        .validation
+        .max_description_length
+        .min(MAX_DESCRIPTION_LENGTH);
+    let receiver_handler = receiver_handler.with_max_description_length(max_description_length);
+    let group_handler = group_handler.with_max_description_length(max_description_length);
+
+    // Share one schema resolver so group changes invalidate inherited schemas
+    let schema_resolver = SchemaResolver::new(group_repo.clone());
+    let event_handler = event_handler.with_schema_resolver(schema_resolver.clone());
+    let receiver_handler = receiver_handler.with_schema_resolver(schema_resolver.clone());
+    let group_handler = group_handler.with_schema_resolver(schema_resolver.clone());
+
+    // Memberships lapse at their expiry; the rows are removed once a day
+    let group_handler = group_handler.with_max_membership_duration(chrono::Duration::days(
+        i64::from(settings.groups.max_membership_days),
+    ));
+    job_runner = job_runner.register(Arc::new(
+        MembershipExpiryHandler::new(group_repo.clone()).with_audit(Arc::new(AuditLogger::new())),
+    ));
+
+    // Every receiver and group write leaves a snapshot for `as_of` reads
+    let history = ResourceHistoryHandler::new(Arc::new(
+        xzepr::infrastructure::database::PostgresResourceHistoryRepository::new(db_pool.clone()),
+    ));
+    let event_handler = event_handler.with_receiver_history(history.clone());
+    let receiver_handler = receiver_handler
+        .with_history(history.clone())
+        .with_timeline(ReceiverTimelineHandler::new(Arc::new(
+            xzepr::infrastructure::database::PostgresReceiverTimelineRepository::new(
+                db_pool.clone(),
+            ),
+        )));
+    let group_handler = group_handler.with_history(history.clone());
+
+    // Admin cleanup of many receivers and groups, capped per call
+    let bulk_delete_handler = BulkDeleteHandler::new(
+        receiver_lookups.clone(),
+        group_repo.clone(),
+        event_repo.clone(),
+    )
+    .with_max_resources(settings.admin.bulk_delete_max_resources)
+    .with_schema_resolver(schema_resolver)
+    .with_history(history);
+
+    // Bulk user import, created in batches inside transactions
+    let user_import_handler = UserImportHandler::new(
+        Arc::new(xzepr::infrastructure::database::PostgresUserRepository::new(db_pool.clone())),
+        Arc::new(
+            xzepr::infrastructure::database::PostgresUserInvitationRepository::new(db_pool.clone()),
+        ),
+    )
+    .with_audit(Arc::new(AuditLogger::new()));
+    // Files too large to import inline run as the user_import job
+    job_runner = job_runner.register(Arc::new(user_import_handler.clone()));
+
+    // Many events per request, each with its own outcome
+    let event_batch_handler = EventBatchHandler::new(event_handler.clone())
+        .with_runtime_settings(runtime_settings.clone());
+
+    // Owner, member, and admin checks shared by REST and GraphQL mutations
+    let authorization = AuthorizationService::new(receiver_lookups.clone(), group_repo.clone());
+    let opa_client = opa_client.map(Arc::new);
+    let authorization = match &opa_client {
+        Some(opa_client) => authorization.with_opa(opa_client.clone()),
+        None => authorization,
+    };
+
+    let change_feed_handler = ChangeFeedHandler::new(receiver_store, group_store);
+    let search_handler =
+        SearchHandler::new(Arc::new(PostgresSearchRepository::new(db_pool.clone())));
+    let user_preferences_handler = UserPreferencesHandler::new(Arc::new(
+        xzepr::infrastructure::database::PostgresUserPreferencesRepository::new(db_pool.clone()),
+    ));
+
+    // Registered GraphQL queries are loaded up front; persisted-only mode
+    // rejects everything else
+    let persisted_queries = PersistedQueryStore::new()
+        .with_capacity(settings.graphql.persisted_query_cache_size)
+        .with_repository(Arc::new(PostgresPersistedQueryRepository::new(
+            db_pool.clone(),
+        )));
+    let registered = persisted_queries
+        .load()
+        .await
+        .context("Failed to load GraphQL persisted queries")?;
+    info!("Loaded {} registered GraphQL persisted queries", registered);
+
+    // Load feature flags; runtime changes in the database override Settings
+    let feature_flags = FeatureFlags::new(&settings.feature_flags).with_repository(Arc::new(
+        xzepr::infrastructure::database::PostgresFeatureFlagRepository::new(db_pool.clone()),
+    ));
+    feature_flags
+        .refresh()
+        .await
+        .context("Failed to load feature flags")?;
+    feature_flags.spawn_watcher(std::time::Duration::from_secs(
+        settings.feature_flags.refresh_interval_seconds,
+    ));
+    let authorization = authorization.with_feature_flags(feature_flags.clone());
+
+    // Serve settled event counts from the hourly rollup and keep recent
+    // buckets in line with the events table
+    let raw_event_counts =
+        Arc::new(xzepr::infrastructure::database::PostgresEventRepository::new(db_pool.clone()));
+    let event_rollup = Arc::new(
+        xzepr::infrastructure::database::PostgresEventRollupRepository::new(db_pool.clone()),
+    );
+    let event_stats = EventStatsHandler::new(raw_event_counts.clone(), event_rollup.clone())
+        .with_freshness(chrono::Duration::seconds(
+            settings.rollup.freshness_seconds as i64,
+        ));
+    // Receivers whose events keep failing are disabled by their policy
+    job_runner = job_runner.register(Arc::new(
+        ReceiverAutoDisableHandler::new(receiver_handler.clone(), event_stats.clone())
+            .with_interval(std::time::Duration::from_secs(
+                settings.hygiene.auto_disable_interval_seconds,
+            )),
+    ));
+    // Large payloads are stored once and shared; the last reference to go
+    // leaves the payload for the collector
+    let payload_store =
+        xzepr::infrastructure::database::PostgresEventRepository::new(db_pool.clone());
+    let payload_store = match settings.ingestion.intern_threshold_bytes {
+        Some(threshold) => {
+            info!(
+                "Event payload interning enabled (threshold: {} bytes)",
+                threshold
+            );
+            payload_store.with_intern_threshold(threshold)
+        }
+        None => payload_store,
+    };
+    job_runner = job_runner.register(Arc::new(
+        EventPayloadCollector::new(Arc::new(payload_store)).with_interval(
+            std::time::Duration::from_secs(settings.ingestion.payload_gc_interval_seconds),
+        ),
+    ));
+
+    let job_runner = Arc::new(
+        job_runner.register(Arc::new(
+            EventRollupReconciler::new(raw_event_counts, event_rollup)
+                .with_lookback(chrono::Duration::hours(
+                    settings.rollup.reconcile_lookback_hours as i64,
+                ))
+                .with_interval(std::time::Duration::from_secs(
+                    settings.rollup.reconcile_interval_seconds,
+                )),
+        )),
+    );
+    let user_import_handler = user_import_handler.with_job_runner(job_runner.clone());
+    let (jobs_shutdown, jobs_shutdown_rx) = tokio::sync::watch::channel(false);
+    let job_handles = job_runner.start(jobs_shutdown_rx);
+
+    // Aggregate statistics for the admin overview, cached briefly
+    let security_monitor = Arc::new(SecurityMonitor::new());
+    let admin_summary_handler = AdminSummaryHandler::new(Arc::new(
+        xzepr::infrastructure::database::PostgresSystemSummaryRepository::new(db_pool.clone()),
+    ))
+    .with_event_stats(event_stats)
+    .with_monitor(security_monitor.clone())
+    .with_cache_ttl(std::time::Duration::from_secs(
+        settings.admin.summary_cache_seconds,
+    ));
+    let admin_summary_handler = match &event_publisher {
+        Some(publisher) => admin_summary_handler
+            .with_publisher(publisher.clone())
+            .with_outbox(event_outbox.clone()),
+        None => admin_summary_handler,
     };
 
+    // Warm caches and connections before readiness is reported, so the
+    // first requests after a deploy do not pay for them
+    let warmup_config = &settings.startup.warmup;
+    let mut warmup = Warmup::new().step("jwt_keys", check_jwt_keys(&settings.auth.jwt));
+    if warmup_config.enabled {
+        warmup = warmup.step("hot_receivers", async {
+            let ids = receiver_activity
+                .most_recently_active(warmup_config.hot_receivers)
+                .await
+                .map_err(|e| DependencyError::Retriable(e.to_string()))?;
+            let receivers = receiver_lookups
+                .preload(&ids)
+                .await
+                .map_err(|e| DependencyError::Retriable(e.to_string()))?;
+            for receiver in &receivers {
+                if let Err(e) = event_handler.prepare_receiver(receiver).await {
+                    warn!(receiver_id = %receiver.id(), error = %e, "Failed to prepare receiver");
+                }
+            }
+            Ok(format!(
+                "{} of {} recently active receivers cached",
+                receivers.len(),
+                ids.len()
+            ))
+        });
+        if let Some(opa_client) = &opa_client {
+            warmup = warmup.step("opa", prime_opa(opa_client));
+        }
+    } else {
+        info!("Warm-up disabled; only JWT key material is checked");
+    }
+    warmup
+        .run(&readiness)
+        .await
+        .context("Startup warm-up failed")?;
+
     // Create GraphQL schema
-    let schema = create_schema(
+    let schema = create_schema_with_search(
+        Arc::new(event_handler.clone()),
         Arc::new(receiver_handler.clone()),
         Arc::new(group_handler.clone()),
+        authorization.clone(),
+        user_repo.clone(),
+        Arc::new(search_handler.clone()),
+        Some(persisted_queries.clone()),
+        &settings.graphql,
     );
 
+    // Deprecations past their sunset may change the served error format
+    let deprecations = Arc::new(DeprecationRegistry::new(&settings.api));
+
+    // Long polls may wait their full timeout before doing any work, so they
+    // get that on top of the default budget unless configured otherwise
+    let mut deadline_config = settings.deadlines.clone();
+    deadline_config
+        .route_budgets
+        .entry("/api/v1/events/poll".to_string())
+        .or_insert(settings.long_poll.max_timeout_seconds + deadline_config.default_budget_seconds);
+    let request_deadlines = RequestDeadlines::from_config(&deadline_config);
+
     // Create unified application state
     let app_state = AppState {
         db_pool: db_pool.clone(),
         user_repo,
         api_key_repo,
+        api_key_service,
         event_handler,
+        event_batch_handler,
         event_receiver_handler: receiver_handler,
         event_receiver_group_handler: group_handler,
+        authorization,
+        change_feed_handler,
+        user_preferences_handler,
+        feature_flags,
+        admin_summary_handler,
+        schema_preview_handler,
+        bulk_delete_handler,
+        event_poll_handler,
+        search_handler,
         graphql_schema: schema,
+        graphql: settings.graphql.clone(),
+        error_format: deprecations.error_format(),
+        deprecations,
+        maintenance,
+        about: Arc::new(about),
+        jobs: job_runner,
+        delivery_failure_handler,
+        audit_chain,
+        attachment_handler,
+        user_import_handler,
+        session_service,
+        persisted_queries,
+        runtime_settings: runtime_settings.clone(),
+        request_deadlines,
+        metrics,
     };
 
+    // Resolve client IPs through trusted proxies only
+    let trusted_proxies = TrustedProxies::new(
+        settings.server.trust_proxy,
+        &settings.server.trusted_proxies,
+    )
+    .map_err(anyhow::Error::msg)
+    .context("Invalid server.trusted_proxies")?;
+
+    // Per-IP limiter for login attempts; reloads keep it current
+    let auth_rate_limiter = AuthRateLimiterState::new(AuthRateLimitConfig::new(
+        settings.security.rate_limit.auth_burst,
+        settings.security.rate_limit.auth_sustained_per_minute,
+    ))
+    .with_runtime_settings(runtime_settings)
+    .with_trusted_proxies(trusted_proxies.clone())
+    .with_audit(Arc::new(AuditLogger::new()))
+    .with_monitor(security_monitor);
+
+    let jwt_state = jwt_service.map(|jwt_service| {
+        JwtMiddlewareState::new(jwt_service).with_trusted_proxies(trusted_proxies.clone())
+    });
+
     // Build the unified router
-    let app = build_router(app_state);
+    let app = build_router(app_state, jwt_state, auth_rate_limiter, trusted_proxies);
 
-    // Determine bind address
-    let addr = SocketAddr::from((
-        settings
-            .server
-            .host
-            .parse::<std::net::IpAddr>()
-            .unwrap_or_else(|_| std::net::IpAddr::V4(std::net::Ipv4Addr::new(0, 0, 0, 0))),
-        settings.server.port,
-    ));
+    // Hand the application over to the already-running listener
+    gate.install(app);
 
     info!("=================================================");
     info!("XZepr Event Tracking Server Ready");
@@ -238,87 +1153,188 @@ async fn main() -> Result<()> {
     info!("Health check:       http://{}/health", addr);
     info!("API status:         http://{}/api/v1/status", addr);
     info!("GraphQL endpoint:   http://{}/graphql", addr);
-    info!("GraphQL Playground: http://{}/graphql/playground", addr);
+    if settings.graphql.playground_enabled {
+        info!("GraphQL Playground: http://{}/graphql/playground", addr);
+    }
     info!("GraphQL health:     http://{}/graphql/health", addr);
     info!("=================================================");
 
-    // Start server with graceful shutdown
-    if settings.server.enable_https {
-        info!("TLS/HTTPS enabled");
-        info!("Certificate: {}", settings.tls.cert_path);
-        info!("Private key: {}", settings.tls.key_path);
-
-        // Load TLS configuration
-        let tls_config = load_tls_config(&settings.tls.cert_path, &settings.tls.key_path)
-            .await
-            .context("Failed to load TLS configuration")?;
-
-        info!("Starting HTTPS server on https://{}", addr);
-
-        // Start HTTPS server
-        axum_server::bind_rustls(addr, tls_config)
-            .serve(app.into_make_service())
-            .await
-            .context("Server error")?;
-    } else {
-        info!("TLS/HTTPS disabled - running in HTTP mode");
-        info!("Starting HTTP server on http://{}", addr);
-
-        // Start HTTP server
-        let listener = tokio::net::TcpListener::bind(addr)
-            .await
-            .context("Failed to bind to address")?;
+    server.await.context("Server task failed")??;
 
-        axum::serve(listener, app)
-            .with_graceful_shutdown(shutdown_signal())
-            .await
-            .context("Server error")?;
+    // Stop scheduling jobs and let runs in progress finish
+    let _ = jobs_shutdown.send(true);
+    for handle in job_handles {
+        let _ = handle.await;
     }
 
     info!("Server shutdown complete");
+
+    // Flush spans still buffered by the exporter; the flush blocks, so it
+    // runs off the async workers
+    tokio::task::spawn_blocking(shutdown_tracing)
+        .await
+        .context("Failed to flush traces")?;
     Ok(())
 }
 
+/// GraphQL endpoint, health check, and, when enabled, playground
+///
+/// The endpoint is bounded by the query limits in `graphql`, as it is in
+/// the library router.
+fn graphql_routes<S>(
+    graphql: &GraphQLConfig,
+    endpoint: MethodRouter<S>,
+    health: MethodRouter<S>,
+    playground: MethodRouter<S>,
+) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let mut routes = Router::new()
+        .route(
+            "/graphql",
+            endpoint.layer(middleware::from_fn_with_state(
+                QueryLimits::from(graphql),
+                query_limits_middleware,
+            )),
+        )
+        .route("/graphql/health", health);
+    if graphql.playground_enabled {
+        routes = routes.route("/graphql/playground", playground);
+    }
+    routes
+}
+
 /// Build the unified application router with all routes and middleware
-fn build_router(state: AppState) -> Router {
+fn build_router(
+    state: AppState,
+    jwt_state: Option<JwtMiddlewareState>,
+    auth_rate_limiter: AuthRateLimiterState,
+    trusted_proxies: TrustedProxies,
+) -> Router {
     // Create CORS layer
     let cors = CorsLayer::new()
         .allow_origin("*".parse::<HeaderValue>().unwrap())
         .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]);
-
-    // Create tracing layer for request logging
-    let trace_layer = TraceLayer::new_for_http()
-        .make_span_with(DefaultMakeSpan::new().level(Level::INFO))
-        .on_response(
-            DefaultOnResponse::new()
-                .level(Level::INFO)
-                .latency_unit(LatencyUnit::Millis),
-        );
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::HeaderName::from_static(API_KEY_HEADER),
+        ]);
+
+    let graphql_routes = graphql_routes(
+        &state.graphql,
+        post(graphql_handler_wrapper),
+        get(graphql_health_wrapper),
+        get(graphql_playground_wrapper),
+    );
+
+    let error_format = state.error_format;
+    let deprecations = state.deprecations.clone();
+    let maintenance = MaintenanceGuard::new(state.maintenance.clone())
+        .with_persisted_queries(Some(state.persisted_queries.clone()));
+    let request_deadlines = state.request_deadlines.clone();
 
     // Build unified router with single state type
     Router::new()
         // Root routes
         .route("/", get(root_handler))
         .route("/health", get(health_check))
+        .route("/metrics", get(metrics_wrapper))
         // GraphQL routes
-        .route("/graphql", post(graphql_handler_wrapper))
-        .route("/graphql/playground", get(graphql_playground_wrapper))
-        .route("/graphql/health", get(graphql_health_wrapper))
+        .merge(graphql_routes)
         // API routes
         .route("/api/v1/status", get(api_status))
-        .route("/api/v1/auth/login", post(login))
-        .route("/api/v1/events", post(create_event_wrapper))
+        .route(
+            "/api/v1/auth/login",
+            post(login).layer(middleware::from_fn_with_state(
+                auth_rate_limiter.clone(),
+                auth_rate_limit_middleware,
+            )),
+        )
+        .route(
+            "/api/v1/events",
+            post(create_event_wrapper)
+                .layer(middleware::from_fn(binary_body_middleware))
+                .layer(middleware::from_fn_with_state(
+                    state.api_key_service.clone(),
+                    api_key_auth_middleware,
+                )),
+        )
+        .route(
+            "/api/v1/events/batch",
+            post(create_event_batch_wrapper)
+                .layer(middleware::from_fn(binary_body_middleware))
+                .layer(middleware::from_fn_with_state(
+                    state.api_key_service.clone(),
+                    api_key_auth_middleware,
+                )),
+        )
+        .route(
+            "/api/v1/auth/invitations/accept",
+            post(accept_user_invitation_wrapper).layer(middleware::from_fn_with_state(
+                auth_rate_limiter.clone(),
+                auth_rate_limit_middleware,
+            )),
+        )
+        .merge(authenticated_routes(&state, jwt_state))
+        .route("/api/v1/search", get(search_wrapper))
+        .route("/api/v1/events/poll", get(poll_events_wrapper))
         .route("/api/v1/events/:id", get(get_event_wrapper))
+        .route(
+            "/api/v1/events/:id/attachments",
+            post(upload_attachment_wrapper).layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/api/v1/events/:id/attachments/:attachment_id",
+            get(download_attachment_wrapper).delete(delete_attachment_wrapper),
+        )
+        .route(
+            "/api/v1/attestations/by-digest/:digest",
+            get(find_attestations_by_digest_wrapper),
+        )
         .route("/api/v1/receivers", post(create_event_receiver_wrapper))
         .route("/api/v1/receivers", get(list_event_receivers_wrapper))
+        .route(
+            "/api/v1/receivers/changes",
+            get(list_event_receiver_changes_wrapper),
+        )
         .route("/api/v1/receivers/:id", get(get_event_receiver_wrapper))
+        .route(
+            "/api/v1/receivers/:id/heartbeat",
+            post(record_receiver_heartbeat_wrapper),
+        )
+        .route(
+            "/api/v1/receivers/:id/description/html",
+            get(get_receiver_description_html_wrapper),
+        )
+        .route(
+            "/api/v1/receivers/:id/clone",
+            post(clone_event_receiver_wrapper),
+        )
+        .route(
+            "/api/v1/receivers/:id/diagnose",
+            post(diagnose_receiver_wrapper),
+        )
+        .route(
+            "/api/v1/receivers/:id/timeline",
+            get(get_receiver_timeline_wrapper),
+        )
+        .route(
+            "/api/v1/receivers/:id/pii-report",
+            get(get_receiver_pii_report_wrapper),
+        )
         .route("/api/v1/receivers/:id", put(update_event_receiver_wrapper))
         .route(
             "/api/v1/receivers/:id",
             delete(delete_event_receiver_wrapper),
         )
         .route("/api/v1/groups", post(create_event_receiver_group_wrapper))
+        .route("/api/v1/groups", get(list_event_receiver_groups_wrapper))
+        .route(
+            "/api/v1/groups/changes",
+            get(list_event_receiver_group_changes_wrapper),
+        )
         .route("/api/v1/groups/:id", get(get_event_receiver_group_wrapper))
         .route(
             "/api/v1/groups/:id",
@@ -328,18 +1344,202 @@ fn build_router(state: AppState) -> Router {
             "/api/v1/groups/:id",
             delete(delete_event_receiver_group_wrapper),
         )
+        .route(
+            "/api/v1/groups/:id/description/html",
+            get(get_group_description_html_wrapper),
+        )
+        .route(
+            "/api/v1/groups/:id/completeness",
+            get(get_group_completeness_wrapper),
+        )
+        .route(
+            "/api/v1/groups/:id/receivers/:receiver_id/default-schema",
+            put(set_default_schema_group_wrapper),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            deprecations,
+            deprecation_middleware,
+        ))
         .with_state(state)
-        .layer(ServiceBuilder::new().layer(trace_layer).layer(cors))
+        .layer(middleware::from_fn_with_state(
+            trusted_proxies,
+            client_ip_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            Arc::new(ContentNegotiationConfig::default()),
+            content_negotiation_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            request_deadlines,
+            deadline_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            maintenance,
+            maintenance_middleware,
+        ))
+        .layer(middleware::from_fn(localize_errors_middleware))
+        .layer(middleware::from_fn_with_state(
+            error_format,
+            problem_details_middleware,
+        ))
+        .layer(middleware::from_fn(tracing_middleware))
+        .layer(cors)
 }
 
-/// Health check endpoint
-async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
-    // Check database connection
-    let db_status = match sqlx::query("SELECT 1").fetch_one(&state.db_pool).await {
-        Ok(_) => "healthy",
-        Err(_) => "unhealthy",
-    };
-
+/// Routes that act with the caller's own identity and permissions
+///
+/// Administration and API key management never run as the development
+/// user: callers authenticate with an API key or, when `auth.jwt` has key
+/// material, a bearer token, and the RBAC middleware enforces
+/// [`ROUTE_RULES`](xzepr::api::middleware::ROUTE_RULES) on them.
+fn authenticated_routes(
+    state: &AppState,
+    jwt_state: Option<JwtMiddlewareState>,
+) -> Router<AppState> {
+    let routes = Router::new()
+        .route("/api/v1/admin/about", get(get_about_wrapper))
+        .route("/api/v1/admin/rbac/matrix", get(get_rbac_matrix_wrapper))
+        .route("/api/v1/admin/deprecations", get(list_deprecations_wrapper))
+        .route(
+            "/api/v1/admin/maintenance",
+            get(get_maintenance_wrapper).put(update_maintenance_wrapper),
+        )
+        .route("/api/v1/admin/config/reload", post(reload_config_wrapper))
+        .route("/api/v1/admin/jobs", get(list_jobs_wrapper))
+        .route("/api/v1/admin/jobs/:name/run", post(run_job_wrapper))
+        .route(
+            "/api/v1/admin/delivery-failures",
+            get(list_delivery_failures_wrapper),
+        )
+        .route("/api/v1/admin/users/import", post(import_users_wrapper))
+        .route(
+            "/api/v1/admin/users/import/:job_id",
+            get(get_user_import_job_wrapper),
+        )
+        .route(
+            "/api/v1/admin/users/:id/invitation",
+            post(issue_user_invitation_wrapper),
+        )
+        .route("/api/v1/admin/impersonate", post(impersonate_user_wrapper))
+        .route("/api/v1/admin/sessions", get(list_sessions_wrapper))
+        .route("/api/v1/admin/sessions/:id", delete(revoke_session_wrapper))
+        .route("/api/v1/me/sessions", get(list_my_sessions_wrapper))
+        .route("/api/v1/me/sessions/:id", delete(revoke_my_session_wrapper))
+        .route(
+            "/api/v1/admin/graphql/persisted-queries",
+            get(list_persisted_queries_wrapper).post(register_persisted_query_wrapper),
+        )
+        .route(
+            "/api/v1/admin/graphql/persisted-queries/:hash",
+            delete(delete_persisted_query_wrapper),
+        )
+        .route(
+            "/api/v1/admin/audit/verify",
+            get(verify_audit_chain_wrapper),
+        )
+        .route("/api/v1/api-keys/:id", get(get_api_key_wrapper))
+        .route("/api/v1/api-keys/:id/rotate", post(rotate_api_key_wrapper))
+        .route("/api/v1/api-keys/:id/usage", get(get_api_key_usage_wrapper))
+        .route(
+            "/api/v1/groups/:id/keys",
+            post(create_group_api_key_wrapper).get(list_group_api_keys_wrapper),
+        )
+        .route(
+            "/api/v1/groups/:id/keys/:key_id",
+            delete(revoke_group_api_key_wrapper),
+        )
+        .route_layer(middleware::from_fn(rbac_enforcement_middleware));
+    let routes = match jwt_state {
+        Some(jwt_state) => routes.route_layer(middleware::from_fn_with_state(
+            jwt_state,
+            jwt_auth_middleware,
+        )),
+        None => routes,
+    };
+    routes.route_layer(middleware::from_fn_with_state(
+        state.api_key_service.clone(),
+        api_key_auth_middleware,
+    ))
+}
+
+/// Runs `xzepr migrate`: applies pending migrations, or with `status`
+/// or `dry_run` only reports on them
+async fn migrate(settings: &Settings, status: bool, dry_run: bool) -> Result<()> {
+    let db_pool = PgPool::connect(settings.database.url.expose())
+        .await
+        .context("Failed to connect to database")?;
+    let migrator = sqlx::migrate!("./migrations");
+    let runner = MigrationRunner::new(&migrator).with_report_path(
+        settings
+            .database
+            .migration_report_path
+            .as_ref()
+            .map(std::path::PathBuf::from),
+    );
+    let failed = |e: DependencyError| anyhow::anyhow!(e.message().to_string());
+
+    if status {
+        let status = runner
+            .status(&db_pool)
+            .await
+            .map_err(failed)
+            .context("Failed to read database migration status")?;
+        print!("{}", status);
+    } else if dry_run {
+        let report = runner
+            .dry_run(&db_pool)
+            .await
+            .map_err(failed)
+            .context("Failed to read database migration status")?;
+        if report.pending.is_empty() {
+            println!("No pending migrations");
+        } else {
+            println!("Would apply {} migration(s):", report.pending.len());
+            for migration in &report.pending {
+                println!("  {:<15} {}", migration.version, migration.description);
+            }
+        }
+    } else {
+        let report = runner
+            .run(&db_pool)
+            .await
+            .map_err(failed)
+            .context("Failed to run database migrations")?;
+        for migration in &report.applied {
+            println!(
+                "Applied {:<15} {} ({} ms)",
+                migration.version, migration.description, migration.duration_ms
+            );
+        }
+        match report.schema_version {
+            Some(version) => println!("Schema version: {}", version),
+            None => println!("Schema version: none"),
+        }
+    }
+    Ok(())
+}
+
+/// Runs `xzepr rbac matrix`: prints the effective RBAC matrix
+fn print_rbac_matrix(format: MatrixFormat) -> Result<()> {
+    let matrix = RbacMatrix::build();
+    match format {
+        MatrixFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&matrix).context("Failed to render RBAC matrix")?
+        ),
+        MatrixFormat::Csv => print!("{}", matrix.to_csv()),
+    }
+    Ok(())
+}
+
+/// Health check endpoint
+async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
+    // Check database connection
+    let db_status = match sqlx::query("SELECT 1").fetch_one(&state.db_pool).await {
+        Ok(_) => "healthy",
+        Err(_) => "unhealthy",
+    };
+
     let status = if db_status == "healthy" {
         StatusCode::OK
     } else {
@@ -364,6 +1564,7 @@ async fn root_handler() -> impl IntoResponse {
     Json(json!({
         "service": "XZepr Event Tracking Server",
         "version": env!("CARGO_PKG_VERSION"),
+        "git_sha": BuildInfo::current().short_sha(),
         "description": "High-performance event tracking with real-time streaming",
         "endpoints": {
             "health": "/health",
@@ -389,22 +1590,89 @@ async fn api_status() -> impl IntoResponse {
     }))
 }
 
-/// Load TLS configuration from certificate and key files
-async fn load_tls_config(
-    cert_path: &str,
-    key_path: &str,
-) -> Result<axum_server::tls_rustls::RustlsConfig> {
-    use axum_server::tls_rustls::RustlsConfig;
+/// Bind the listener and serve `router` in a background task
+///
+/// Binding happens before this returns, so address and TLS problems fail
+/// startup immediately rather than after the dependency wait.
+async fn spawn_server(
+    settings: &Settings,
+    addr: SocketAddr,
+    router: Router,
+) -> Result<tokio::task::JoinHandle<Result<()>>> {
+    let service = router.into_make_service_with_connect_info::<SocketAddr>();
 
-    info!("Loading TLS certificate from: {}", cert_path);
-    info!("Loading TLS private key from: {}", key_path);
+    let limits = ConnectionLimits::from_settings(&settings.server);
+    if limits != ConnectionLimits::default() {
+        info!("Connection limits: {:?}", limits);
+    }
+    let acceptor = ConnectionLimitAcceptor::new(limits);
 
-    let config = RustlsConfig::from_pem_file(cert_path, key_path)
-        .await
-        .context("Failed to load TLS certificate and key")?;
+    if settings.server.enable_https {
+        info!("TLS/HTTPS enabled");
+        info!("Certificate: {}", settings.tls.cert_path);
+        info!("Private key: {}", settings.tls.key_path);
+
+        // Load TLS configuration
+        let tls = TlsConfigBuilder::from_settings(&settings.tls);
+        let tls_config = tls
+            .build_rustls_config()
+            .context("Failed to load TLS configuration")?;
+        info!("Minimum TLS version: {:?}", settings.tls.min_version);
+        if let Some(client_ca_path) = &settings.tls.client_ca_path {
+            info!("Client certificates required (CA: {})", client_ca_path);
+        }
+
+        // Serve renewed certificates without a restart
+        if settings.tls.reload_interval_seconds > 0 {
+            TlsReloader::new(tls, tls_config.clone())
+                .with_audit(Arc::new(AuditLogger::new()))
+                .spawn(std::time::Duration::from_secs(
+                    settings.tls.reload_interval_seconds,
+                ));
+        }
+
+        let listener = std::net::TcpListener::bind(addr).context("Failed to bind to address")?;
+        listener
+            .set_nonblocking(true)
+            .context("Failed to configure listener")?;
+
+        info!("Starting HTTPS server on https://{}", addr);
 
-    info!("TLS configuration loaded successfully");
-    Ok(config)
+        // Connection limits apply beneath TLS, before the handshake
+        let mut server =
+            axum_server::from_tcp_rustls(listener, tls_config).map(|tls| tls.acceptor(acceptor));
+        limits.configure(server.http_builder());
+
+        // Start HTTPS server
+        Ok(tokio::spawn(async move {
+            server.serve(service).await.context("Server error")
+        }))
+    } else {
+        info!("TLS/HTTPS disabled - running in HTTP mode");
+        info!("Starting HTTP server on http://{}", addr);
+
+        // Start HTTP server
+        let listener = std::net::TcpListener::bind(addr).context("Failed to bind to address")?;
+        listener
+            .set_nonblocking(true)
+            .context("Failed to configure listener")?;
+
+        let handle = axum_server::Handle::new();
+        let shutdown = handle.clone();
+        tokio::spawn(async move {
+            shutdown_signal().await;
+            shutdown.graceful_shutdown(None);
+        });
+
+        let mut server = axum_server::from_tcp(listener)
+            .acceptor(acceptor)
+            .handle(handle);
+        limits.configure(server.http_builder());
+
+        Ok(tokio::spawn(async move {
+            server.serve(service).await.context("Server error")
+        }))
+    }
 }
 
 /// Graceful shutdown signal handler
@@ -436,21 +1704,6 @@ async fn shutdown_signal() {
     }
 }
 
-/// Mask password in database URL for logging
-fn mask_password(url: &str) -> String {
-    if let Some(at_pos) = url.rfind('@') {
-        if let Some(proto_end) = url.find("://") {
-            let proto = &url[..proto_end + 3];
-            let after_at = &url[at_pos..];
-            if let Some(colon_pos) = url[proto_end + 3..at_pos].find(':') {
-                let username = &url[proto_end + 3..proto_end + 3 + colon_pos];
-                return format!("{}{}:***{}", proto, username, after_at);
-            }
-        }
-    }
-    url.to_string()
-}
-
 /// Create a default authenticated user for development/test wrappers
 fn create_dev_user() -> xzepr::api::middleware::AuthenticatedUser {
     use chrono::Duration;
@@ -465,128 +1718,707 @@ fn create_dev_user() -> xzepr::api::middleware::AuthenticatedUser {
         Duration::hours(24),
     );
 
-    xzepr::api::middleware::AuthenticatedUser::new(claims)
+    xzepr::api::middleware::AuthenticatedUser::new(claims)
+}
+
+/// Wrapper for GraphQL handler that extracts schema from AppState
+async fn graphql_handler_wrapper(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<serde_json::Value>,
+) -> axum::response::Response {
+    use xzepr::api::graphql::handlers::GraphQLRequest;
+
+    // Parse the GraphQL request
+    let graphql_req: GraphQLRequest = match serde_json::from_value(req) {
+        Ok(r) => r,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("Invalid GraphQL request: {}", e)})),
+            )
+                .into_response();
+        }
+    };
+
+    // Call the actual handler with schema from state
+    let user = create_dev_user();
+    graphql_handler(
+        State(state.graphql_schema),
+        user,
+        headers,
+        Json(graphql_req),
+    )
+    .await
+}
+
+/// Wrapper for GraphQL playground
+async fn graphql_playground_wrapper(
+    State(state): State<AppState>,
+    uri: OriginalUri,
+    user: Option<AuthenticatedUser>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    graphql_playground(State(state.graphql_schema), uri, user, headers).await
+}
+
+/// Wrapper for GraphQL health check
+async fn graphql_health_wrapper(State(_state): State<AppState>) -> impl IntoResponse {
+    graphql_health().await
+}
+
+// ============================================================================
+// API Handler Wrappers
+// ============================================================================
+// These wrappers convert from main::AppState to api::rest::events::AppState
+
+/// Convert main AppState to API AppState
+/// Serves the Prometheus registry
+async fn metrics_wrapper(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> axum::response::Response {
+    metrics_handler(State(state.metrics.clone()), headers).await
+}
+
+fn to_api_state(state: &AppState) -> xzepr::api::rest::events::AppState {
+    xzepr::api::rest::events::AppState {
+        event_handler: state.event_handler.clone(),
+        event_batch_handler: state.event_batch_handler.clone(),
+        event_receiver_handler: state.event_receiver_handler.clone(),
+        event_receiver_group_handler: state.event_receiver_group_handler.clone(),
+        authorization: state.authorization.clone(),
+        change_feed_handler: state.change_feed_handler.clone(),
+        user_preferences_handler: state.user_preferences_handler.clone(),
+        feature_flags: state.feature_flags.clone(),
+        admin_summary_handler: Some(state.admin_summary_handler.clone()),
+        schema_preview_handler: state.schema_preview_handler.clone(),
+        bulk_delete_handler: state.bulk_delete_handler.clone(),
+        api_key_service: Some(state.api_key_service.clone()),
+        event_poll_handler: Some(state.event_poll_handler.clone()),
+        graphql: state.graphql.clone(),
+        about: Some(state.about.clone()),
+        jobs: Some(state.jobs.clone()),
+        delivery_failures: Some(state.delivery_failure_handler.clone()),
+        audit_chain: state.audit_chain.clone(),
+        search_handler: Some(state.search_handler.clone()),
+        attachment_handler: state.attachment_handler.clone(),
+        user_import_handler: Some(state.user_import_handler.clone()),
+        session_service: state.session_service.clone(),
+        error_format: state.error_format,
+        deprecations: state.deprecations.clone(),
+        maintenance: state.maintenance.clone(),
+        persisted_queries: Some(state.persisted_queries.clone()),
+        runtime_settings: Some(state.runtime_settings.clone()),
+        request_deadlines: state.request_deadlines.clone(),
+    }
+}
+
+async fn create_event_wrapper(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    client_ip: Option<Extension<ClientIp>>,
+    key_user: Option<Extension<AuthenticatedUser>>,
+    api_key: Option<Extension<ApiKeyPrincipal>>,
+    query: Query<xzepr::api::rest::CreateEventQueryParams>,
+    body: Bytes,
+) -> axum::response::Response {
+    use xzepr::api::rest::events::create_event;
+    let api_state = to_api_state(&state);
+    let json_result = serde_json::from_slice(&body);
+    match json_result {
+        Ok(json) => {
+            // Requests with an API key act as the key's user
+            let user = key_user.map_or_else(create_dev_user, |Extension(user)| user);
+            create_event(
+                State(api_state),
+                user,
+                headers,
+                client_ip,
+                api_key,
+                query,
+                Json(json),
+            )
+        }
+        .await
+        .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid JSON: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+async fn create_event_batch_wrapper(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    client_ip: Option<Extension<ClientIp>>,
+    key_user: Option<Extension<AuthenticatedUser>>,
+    api_key: Option<Extension<ApiKeyPrincipal>>,
+    body: Bytes,
+) -> axum::response::Response {
+    use xzepr::api::rest::batch::create_event_batch;
+    let api_state = to_api_state(&state);
+    match serde_json::from_slice(&body) {
+        Ok(json) => {
+            // Requests with an API key act as the key's user
+            let user = key_user.map_or_else(create_dev_user, |Extension(user)| user);
+            create_event_batch(
+                State(api_state),
+                user,
+                headers,
+                client_ip,
+                api_key,
+                Json(json),
+            )
+            .await
+            .into_response()
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid JSON: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+async fn get_event_wrapper(
+    State(state): State<AppState>,
+    path: Path<String>,
+    query: Query<xzepr::api::rest::dtos::EventQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::events::get_event;
+    let api_state = to_api_state(&state);
+    get_event(State(api_state), Some(create_dev_user()), path, query)
+        .await
+        .into_response()
+}
+
+async fn upload_attachment_wrapper(
+    State(state): State<AppState>,
+    path: Path<String>,
+    multipart: Multipart,
+) -> axum::response::Response {
+    use xzepr::api::rest::attachments::upload_attachment;
+    let api_state = to_api_state(&state);
+    upload_attachment(State(api_state), create_dev_user(), path, multipart)
+        .await
+        .into_response()
+}
+
+async fn download_attachment_wrapper(
+    State(state): State<AppState>,
+    path: Path<(String, String)>,
+    headers: HeaderMap,
+) -> axum::response::Response {
+    use xzepr::api::rest::attachments::download_attachment;
+    let api_state = to_api_state(&state);
+    download_attachment(State(api_state), create_dev_user(), path, headers)
+        .await
+        .into_response()
+}
+
+async fn delete_attachment_wrapper(
+    State(state): State<AppState>,
+    path: Path<(String, String)>,
+) -> axum::response::Response {
+    use xzepr::api::rest::attachments::delete_attachment;
+    let api_state = to_api_state(&state);
+    delete_attachment(State(api_state), create_dev_user(), path)
+        .await
+        .into_response()
+}
+
+async fn create_event_receiver_wrapper(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> axum::response::Response {
+    use xzepr::api::rest::events::create_event_receiver;
+    let api_state = to_api_state(&state);
+    let json_result = serde_json::from_slice(&body);
+    match json_result {
+        Ok(json) => {
+            let user = create_dev_user();
+            create_event_receiver(State(api_state), user, Json(json))
+        }
+        .await
+        .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid JSON: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+async fn list_event_receivers_wrapper(
+    State(state): State<AppState>,
+    query: Query<xzepr::api::rest::dtos::EventReceiverQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::events::list_event_receivers;
+    use xzepr::api::rest::preferences::RequestPreferences;
+    let api_state = to_api_state(&state);
+    let user = create_dev_user();
+    let preferences =
+        RequestPreferences::load(&api_state.user_preferences_handler, Some(&user)).await;
+    list_event_receivers(State(api_state), Some(user), preferences, query)
+        .await
+        .into_response()
+}
+
+async fn list_event_receiver_changes_wrapper(
+    State(state): State<AppState>,
+    query: Query<xzepr::api::rest::dtos::ChangesQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::changes::list_event_receiver_changes;
+    let api_state = to_api_state(&state);
+    list_event_receiver_changes(State(api_state), Some(create_dev_user()), query)
+        .await
+        .into_response()
+}
+
+async fn poll_events_wrapper(
+    State(state): State<AppState>,
+    query: Query<xzepr::api::rest::dtos::EventPollQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::poll::poll_events;
+    let api_state = to_api_state(&state);
+    poll_events(State(api_state), create_dev_user(), query)
+        .await
+        .into_response()
+}
+
+async fn get_event_receiver_wrapper(
+    State(state): State<AppState>,
+    path: Path<String>,
+    query: Query<xzepr::api::rest::dtos::HistoricalQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::events::get_event_receiver;
+    let api_state = to_api_state(&state);
+    get_event_receiver(State(api_state), Some(create_dev_user()), path, query)
+        .await
+        .into_response()
+}
+
+async fn get_api_key_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::api_keys::get_api_key;
+    let api_state = to_api_state(&state);
+    get_api_key(State(api_state), user, path)
+        .await
+        .into_response()
+}
+
+async fn get_api_key_usage_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::api_keys::get_api_key_usage;
+    let api_state = to_api_state(&state);
+    get_api_key_usage(State(api_state), user, path)
+        .await
+        .into_response()
+}
+
+async fn rotate_api_key_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+    body: Bytes,
+) -> axum::response::Response {
+    use xzepr::api::rest::api_keys::rotate_api_key;
+    let api_state = to_api_state(&state);
+    rotate_api_key(State(api_state), user, path, body)
+        .await
+        .into_response()
+}
+
+async fn create_group_api_key_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+    body: Bytes,
+) -> axum::response::Response {
+    use xzepr::api::rest::api_keys::create_group_api_key;
+    let api_state = to_api_state(&state);
+    match serde_json::from_slice(&body) {
+        Ok(json) => create_group_api_key(State(api_state), user, path, Json(json))
+            .await
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid JSON: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+async fn list_group_api_keys_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::api_keys::list_group_api_keys;
+    let api_state = to_api_state(&state);
+    list_group_api_keys(State(api_state), user, path)
+        .await
+        .into_response()
+}
+
+async fn revoke_group_api_key_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<(String, String)>,
+) -> axum::response::Response {
+    use xzepr::api::rest::api_keys::revoke_group_api_key;
+    let api_state = to_api_state(&state);
+    revoke_group_api_key(State(api_state), user, path)
+        .await
+        .into_response()
+}
+
+async fn get_about_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> axum::response::Response {
+    use xzepr::api::rest::about::get_about;
+    let api_state = to_api_state(&state);
+    get_about(State(api_state), user).await.into_response()
+}
+
+/// Serves the RBAC matrix, which this binary enforces only on the routes
+/// in [`authenticated_routes`]
+async fn get_rbac_matrix_wrapper(
+    user: AuthenticatedUser,
+    headers: HeaderMap,
+) -> axum::response::Response {
+    use xzepr::api::rest::rbac_matrix::get_rbac_matrix;
+    get_rbac_matrix(headers, user).await.into_response()
+}
+
+async fn list_deprecations_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    query: Query<xzepr::api::rest::DeprecationQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::deprecations::list_deprecations;
+    let api_state = to_api_state(&state);
+    list_deprecations(State(api_state), user, query)
+        .await
+        .into_response()
+}
+
+async fn get_maintenance_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> axum::response::Response {
+    use xzepr::api::rest::maintenance::get_maintenance;
+    let api_state = to_api_state(&state);
+    get_maintenance(State(api_state), user)
+        .await
+        .into_response()
+}
+
+async fn update_maintenance_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<xzepr::api::rest::UpdateMaintenanceRequest>,
+) -> axum::response::Response {
+    use xzepr::api::rest::maintenance::update_maintenance;
+    let api_state = to_api_state(&state);
+    update_maintenance(State(api_state), user, Json(request))
+        .await
+        .into_response()
+}
+
+async fn reload_config_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> axum::response::Response {
+    use xzepr::api::rest::config_reload::reload_config;
+    let api_state = to_api_state(&state);
+    reload_config(State(api_state), user).await.into_response()
+}
+
+async fn list_jobs_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> axum::response::Response {
+    use xzepr::api::rest::jobs::list_jobs;
+    let api_state = to_api_state(&state);
+    list_jobs(State(api_state), user).await.into_response()
+}
+
+async fn run_job_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::jobs::run_job;
+    let api_state = to_api_state(&state);
+    run_job(State(api_state), user, path).await.into_response()
+}
+
+async fn import_users_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    query: Query<xzepr::api::rest::dtos::UserImportQuery>,
+    body: String,
+) -> axum::response::Response {
+    use xzepr::api::rest::user_import::import_users;
+    let api_state = to_api_state(&state);
+    import_users(State(api_state), user, query, body)
+        .await
+        .into_response()
+}
+
+async fn get_user_import_job_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::user_import::get_user_import_job;
+    let api_state = to_api_state(&state);
+    get_user_import_job(State(api_state), user, path)
+        .await
+        .into_response()
+}
+
+async fn issue_user_invitation_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::user_import::issue_user_invitation;
+    let api_state = to_api_state(&state);
+    issue_user_invitation(State(api_state), user, path)
+        .await
+        .into_response()
+}
+
+async fn impersonate_user_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    request: Json<xzepr::api::rest::dtos::ImpersonateRequest>,
+) -> axum::response::Response {
+    use xzepr::api::rest::sessions::impersonate_user;
+    let api_state = to_api_state(&state);
+    impersonate_user(State(api_state), user, request)
+        .await
+        .into_response()
+}
+
+async fn list_sessions_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    query: Query<xzepr::api::rest::dtos::ListSessionsQuery>,
+) -> axum::response::Response {
+    use xzepr::api::rest::sessions::list_sessions;
+    let api_state = to_api_state(&state);
+    list_sessions(State(api_state), user, query)
+        .await
+        .into_response()
+}
+
+async fn revoke_session_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::sessions::revoke_session;
+    let api_state = to_api_state(&state);
+    revoke_session(State(api_state), user, path)
+        .await
+        .into_response()
+}
+
+async fn list_my_sessions_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> axum::response::Response {
+    use xzepr::api::rest::sessions::list_my_sessions;
+    let api_state = to_api_state(&state);
+    list_my_sessions(State(api_state), user)
+        .await
+        .into_response()
+}
+
+async fn revoke_my_session_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::sessions::revoke_my_session;
+    let api_state = to_api_state(&state);
+    revoke_my_session(State(api_state), user, path)
+        .await
+        .into_response()
+}
+
+async fn accept_user_invitation_wrapper(
+    State(state): State<AppState>,
+    request: Json<xzepr::api::rest::dtos::AcceptInvitationRequest>,
+) -> axum::response::Response {
+    use xzepr::api::rest::user_import::accept_user_invitation;
+    let api_state = to_api_state(&state);
+    accept_user_invitation(State(api_state), request)
+        .await
+        .into_response()
 }
 
-/// Wrapper for GraphQL handler that extracts schema from AppState
-async fn graphql_handler_wrapper(
+async fn list_delivery_failures_wrapper(
     State(state): State<AppState>,
-    Json(req): Json<serde_json::Value>,
+    user: AuthenticatedUser,
+    query: Query<xzepr::api::rest::dtos::DeliveryFailureQueryParams>,
 ) -> axum::response::Response {
-    use xzepr::api::graphql::handlers::GraphQLRequest;
+    use xzepr::api::rest::delivery_failures::list_delivery_failures;
+    let api_state = to_api_state(&state);
+    list_delivery_failures(State(api_state), user, query)
+        .await
+        .into_response()
+}
 
-    // Parse the GraphQL request
-    let graphql_req: GraphQLRequest = match serde_json::from_value(req) {
-        Ok(r) => r,
-        Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(json!({"error": format!("Invalid GraphQL request: {}", e)})),
-            )
-                .into_response();
-        }
-    };
+async fn list_persisted_queries_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> axum::response::Response {
+    use xzepr::api::rest::persisted_queries::list_persisted_queries;
+    let api_state = to_api_state(&state);
+    list_persisted_queries(State(api_state), user)
+        .await
+        .into_response()
+}
 
-    // Call the actual handler with schema from state
-    let user = create_dev_user();
-    graphql_handler(State(state.graphql_schema), user, Json(graphql_req)).await
+async fn register_persisted_query_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(request): Json<xzepr::api::rest::dtos::RegisterPersistedQueryRequest>,
+) -> axum::response::Response {
+    use xzepr::api::rest::persisted_queries::register_persisted_query;
+    let api_state = to_api_state(&state);
+    register_persisted_query(State(api_state), user, Json(request))
+        .await
+        .into_response()
 }
 
-/// Wrapper for GraphQL playground
-async fn graphql_playground_wrapper(State(state): State<AppState>) -> impl IntoResponse {
-    graphql_playground(State(state.graphql_schema)).await
+async fn delete_persisted_query_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::persisted_queries::delete_persisted_query;
+    let api_state = to_api_state(&state);
+    delete_persisted_query(State(api_state), user, path)
+        .await
+        .into_response()
 }
 
-/// Wrapper for GraphQL health check
-async fn graphql_health_wrapper(State(_state): State<AppState>) -> impl IntoResponse {
-    graphql_health().await
+async fn verify_audit_chain_wrapper(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    query: Query<xzepr::api::rest::dtos::AuditVerifyQuery>,
+) -> axum::response::Response {
+    use xzepr::api::rest::audit::verify_audit_chain;
+    let api_state = to_api_state(&state);
+    verify_audit_chain(State(api_state), user, query)
+        .await
+        .into_response()
 }
 
-// ============================================================================
-// API Handler Wrappers
-// ============================================================================
-// These wrappers convert from main::AppState to api::rest::events::AppState
+async fn search_wrapper(
+    State(state): State<AppState>,
+    query: Query<xzepr::api::rest::dtos::SearchQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::search::search;
+    let api_state = to_api_state(&state);
+    search(State(api_state), create_dev_user(), query)
+        .await
+        .into_response()
+}
 
-/// Convert main AppState to API AppState
-fn to_api_state(state: &AppState) -> xzepr::api::rest::events::AppState {
-    xzepr::api::rest::events::AppState {
-        event_handler: state.event_handler.clone(),
-        event_receiver_handler: state.event_receiver_handler.clone(),
-        event_receiver_group_handler: state.event_receiver_group_handler.clone(),
-    }
+async fn find_attestations_by_digest_wrapper(
+    State(state): State<AppState>,
+    path: Path<String>,
+    query: Query<xzepr::api::rest::dtos::AttestationQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::attestations::find_attestations_by_digest;
+    let api_state = to_api_state(&state);
+    find_attestations_by_digest(State(api_state), create_dev_user(), path, query)
+        .await
+        .into_response()
 }
 
-async fn create_event_wrapper(
+async fn diagnose_receiver_wrapper(
     State(state): State<AppState>,
+    path: Path<String>,
     body: Bytes,
 ) -> axum::response::Response {
-    use xzepr::api::rest::events::create_event;
+    use xzepr::api::rest::diagnose::diagnose_receiver;
     let api_state = to_api_state(&state);
-    let json_result = serde_json::from_slice(&body);
-    match json_result {
-        Ok(json) => {
-            let user = create_dev_user();
-            create_event(State(api_state), user, Json(json))
-        }
+    diagnose_receiver(State(api_state), create_dev_user(), None, path, body)
         .await
-        .into_response(),
-        Err(e) => (
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": format!("Invalid JSON: {}", e)})),
-        )
-            .into_response(),
-    }
+        .into_response()
 }
 
-async fn get_event_wrapper(
+async fn get_receiver_timeline_wrapper(
     State(state): State<AppState>,
     path: Path<String>,
+    query: Query<xzepr::api::rest::dtos::ReceiverTimelineQueryParams>,
 ) -> axum::response::Response {
-    use xzepr::api::rest::events::get_event;
+    use xzepr::api::rest::timeline::get_receiver_timeline;
     let api_state = to_api_state(&state);
-    get_event(State(api_state), path).await.into_response()
+    get_receiver_timeline(State(api_state), create_dev_user(), path, query)
+        .await
+        .into_response()
 }
 
-async fn create_event_receiver_wrapper(
+async fn get_receiver_pii_report_wrapper(
     State(state): State<AppState>,
-    body: Bytes,
+    path: Path<String>,
 ) -> axum::response::Response {
-    use xzepr::api::rest::events::create_event_receiver;
+    use xzepr::api::rest::pii_report::get_receiver_pii_report;
     let api_state = to_api_state(&state);
-    let json_result = serde_json::from_slice(&body);
-    match json_result {
-        Ok(json) => {
-            let user = create_dev_user();
-            create_event_receiver(State(api_state), user, Json(json))
-        }
+    get_receiver_pii_report(State(api_state), create_dev_user(), path)
         .await
-        .into_response(),
-        Err(e) => (
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": format!("Invalid JSON: {}", e)})),
-        )
-            .into_response(),
-    }
+        .into_response()
 }
 
-async fn list_event_receivers_wrapper(
+async fn get_group_completeness_wrapper(
     State(state): State<AppState>,
-    query: Query<xzepr::api::rest::dtos::EventReceiverQueryParams>,
+    path: Path<String>,
+    query: Query<xzepr::api::rest::GroupCompletenessQueryParams>,
 ) -> axum::response::Response {
-    use xzepr::api::rest::events::list_event_receivers;
+    use xzepr::api::rest::completeness::get_group_completeness;
     let api_state = to_api_state(&state);
-    list_event_receivers(State(api_state), query)
+    get_group_completeness(State(api_state), create_dev_user(), path, query)
         .await
         .into_response()
 }
 
-async fn get_event_receiver_wrapper(
+async fn get_receiver_description_html_wrapper(
     State(state): State<AppState>,
     path: Path<String>,
 ) -> axum::response::Response {
-    use xzepr::api::rest::events::get_event_receiver;
+    use xzepr::api::rest::descriptions::get_receiver_description_html;
+    let api_state = to_api_state(&state);
+    get_receiver_description_html(State(api_state), path)
+        .await
+        .into_response()
+}
+
+async fn record_receiver_heartbeat_wrapper(
+    State(state): State<AppState>,
+    path: Path<String>,
+    body: Bytes,
+) -> axum::response::Response {
+    use xzepr::api::rest::heartbeat::record_receiver_heartbeat;
     let api_state = to_api_state(&state);
-    get_event_receiver(State(api_state), path)
+    record_receiver_heartbeat(State(api_state), create_dev_user(), None, path, body)
         .await
         .into_response()
 }
@@ -600,7 +2432,27 @@ async fn update_event_receiver_wrapper(
     let api_state = to_api_state(&state);
     let json_result = serde_json::from_slice(&body);
     match json_result {
-        Ok(json) => update_event_receiver(State(api_state), path, Json(json))
+        Ok(json) => update_event_receiver(State(api_state), create_dev_user(), path, Json(json))
+            .await
+            .into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": format!("Invalid JSON: {}", e)})),
+        )
+            .into_response(),
+    }
+}
+
+async fn clone_event_receiver_wrapper(
+    State(state): State<AppState>,
+    path: Path<String>,
+    body: Bytes,
+) -> axum::response::Response {
+    use xzepr::api::rest::events::clone_event_receiver;
+    let api_state = to_api_state(&state);
+    let json_result = serde_json::from_slice(&body);
+    match json_result {
+        Ok(json) => clone_event_receiver(State(api_state), create_dev_user(), path, Json(json))
             .await
             .into_response(),
         Err(e) => (
@@ -614,10 +2466,26 @@ async fn update_event_receiver_wrapper(
 async fn delete_event_receiver_wrapper(
     State(state): State<AppState>,
     path: Path<String>,
+    query: Query<xzepr::api::rest::dtos::DeleteEventReceiverQueryParams>,
 ) -> axum::response::Response {
     use xzepr::api::rest::events::delete_event_receiver;
     let api_state = to_api_state(&state);
-    delete_event_receiver(State(api_state), path)
+    delete_event_receiver(State(api_state), create_dev_user(), path, query)
+        .await
+        .into_response()
+}
+
+async fn list_event_receiver_groups_wrapper(
+    State(state): State<AppState>,
+    query: Query<xzepr::api::rest::dtos::EventReceiverGroupQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::events::list_event_receiver_groups;
+    use xzepr::api::rest::preferences::RequestPreferences;
+    let api_state = to_api_state(&state);
+    let user = create_dev_user();
+    let preferences =
+        RequestPreferences::load(&api_state.user_preferences_handler, Some(&user)).await;
+    list_event_receiver_groups(State(api_state), preferences, query)
         .await
         .into_response()
 }
@@ -647,10 +2515,22 @@ async fn create_event_receiver_group_wrapper(
 async fn get_event_receiver_group_wrapper(
     State(state): State<AppState>,
     path: Path<String>,
+    query: Query<xzepr::api::rest::dtos::HistoricalQueryParams>,
 ) -> axum::response::Response {
     use xzepr::api::rest::events::get_event_receiver_group;
     let api_state = to_api_state(&state);
-    get_event_receiver_group(State(api_state), path)
+    get_event_receiver_group(State(api_state), path, query)
+        .await
+        .into_response()
+}
+
+async fn get_group_description_html_wrapper(
+    State(state): State<AppState>,
+    path: Path<String>,
+) -> axum::response::Response {
+    use xzepr::api::rest::descriptions::get_group_description_html;
+    let api_state = to_api_state(&state);
+    get_group_description_html(State(api_state), path)
         .await
         .into_response()
 }
@@ -664,9 +2544,11 @@ async fn update_event_receiver_group_wrapper(
     let api_state = to_api_state(&state);
     let json_result = serde_json::from_slice(&body);
     match json_result {
-        Ok(json) => update_event_receiver_group(State(api_state), path, Json(json))
-            .await
-            .into_response(),
+        Ok(json) => {
+            update_event_receiver_group(State(api_state), create_dev_user(), path, Json(json))
+                .await
+                .into_response()
+        }
         Err(e) => (
             StatusCode::BAD_REQUEST,
             Json(json!({"error": format!("Invalid JSON: {}", e)})),
@@ -675,13 +2557,35 @@ async fn update_event_receiver_group_wrapper(
     }
 }
 
+async fn list_event_receiver_group_changes_wrapper(
+    State(state): State<AppState>,
+    query: Query<xzepr::api::rest::dtos::ChangesQueryParams>,
+) -> axum::response::Response {
+    use xzepr::api::rest::changes::list_event_receiver_group_changes;
+    let api_state = to_api_state(&state);
+    list_event_receiver_group_changes(State(api_state), query)
+        .await
+        .into_response()
+}
+
 async fn delete_event_receiver_group_wrapper(
     State(state): State<AppState>,
     path: Path<String>,
 ) -> axum::response::Response {
     use xzepr::api::rest::events::delete_event_receiver_group;
     let api_state = to_api_state(&state);
-    delete_event_receiver_group(State(api_state), path)
+    delete_event_receiver_group(State(api_state), create_dev_user(), path)
+        .await
+        .into_response()
+}
+
+async fn set_default_schema_group_wrapper(
+    State(state): State<AppState>,
+    path: Path<(String, String)>,
+) -> axum::response::Response {
+    use xzepr::api::rest::events::set_default_schema_group;
+    let api_state = to_api_state(&state);
+    set_default_schema_group(State(api_state), create_dev_user(), path)
         .await
         .into_response()
 }
@@ -947,10 +2851,14 @@ impl EventRepository for MockEventRepository {
 
     async fn find_by_criteria(
         &self,
-        _criteria: FindEventCriteria,
+        criteria: FindEventCriteria,
     ) -> xzepr::error::Result<Vec<Event>> {
-        // Simplified implementation
-        self.list(1000, 0).await
+        // Simplified implementation; only the origin scope is applied
+        let events = self.list(1000, 0).await?;
+        Ok(events
+            .into_iter()
+            .filter(|e| criteria.origin.is_none_or(|origin| e.origin() == origin))
+            .collect())
     }
 
     async fn find_by_owner(&self, owner_id: UserId) -> xzepr::error::Result<Vec<Event>> {
@@ -992,9 +2900,35 @@ impl EventRepository for MockEventRepository {
     }
 }
 
+#[async_trait]
+impl EventFeedRepository for MockEventRepository {
+    async fn find_events_after(
+        &self,
+        after: Option<EventId>,
+        receiver_id: Option<EventReceiverId>,
+        limit: usize,
+    ) -> xzepr::error::Result<Vec<Event>> {
+        let events = self.events.lock().unwrap();
+        let mut events: Vec<Event> = events
+            .values()
+            .filter(|e| e.origin() == EventOrigin::User)
+            .filter(|e| after.is_none_or(|after| e.id().as_ulid() > after.as_ulid()))
+            .filter(|e| receiver_id.is_none_or(|id| e.event_receiver_id() == id))
+            .cloned()
+            .collect();
+        events.sort_by_key(|e| e.id().as_ulid());
+        events.truncate(limit);
+        Ok(events)
+    }
+}
+
+/// Deleted ids with their deletion time, consumed by the change feed
+type Tombstones<I> = Arc<Mutex<Vec<(I, DateTime<Utc>)>>>;
+
 /// Mock event receiver repository
 pub struct MockEventReceiverRepository {
     receivers: Arc<Mutex<HashMap<EventReceiverId, EventReceiver>>>,
+    tombstones: Tombstones<EventReceiverId>,
 }
 
 impl Default for MockEventReceiverRepository {
@@ -1007,6 +2941,7 @@ impl MockEventReceiverRepository {
     pub fn new() -> Self {
         Self {
             receivers: Arc::new(Mutex::new(HashMap::new())),
+            tombstones: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -1091,7 +3026,9 @@ impl EventReceiverRepository for MockEventReceiverRepository {
 
     async fn delete(&self, id: EventReceiverId) -> xzepr::error::Result<()> {
         let mut receivers = self.receivers.lock().unwrap();
-        receivers.remove(&id);
+        if receivers.remove(&id).is_some() {
+            self.tombstones.lock().unwrap().push((id, Utc::now()));
+        }
         Ok(())
     }
 
@@ -1162,6 +3099,7 @@ impl EventReceiverRepository for MockEventReceiverRepository {
 /// Mock event receiver group repository
 pub struct MockEventReceiverGroupRepository {
     groups: Arc<Mutex<HashMap<EventReceiverGroupId, EventReceiverGroup>>>,
+    tombstones: Tombstones<EventReceiverGroupId>,
 }
 
 impl Default for MockEventReceiverGroupRepository {
@@ -1174,6 +3112,7 @@ impl MockEventReceiverGroupRepository {
     pub fn new() -> Self {
         Self {
             groups: Arc::new(Mutex::new(HashMap::new())),
+            tombstones: Arc::new(Mutex::new(Vec::new())),
         }
     }
 }
@@ -1278,7 +3217,9 @@ impl EventReceiverGroupRepository for MockEventReceiverGroupRepository {
 
     async fn delete(&self, id: EventReceiverGroupId) -> xzepr::error::Result<()> {
         let mut groups = self.groups.lock().unwrap();
-        groups.remove(&id);
+        if groups.remove(&id).is_some() {
+            self.tombstones.lock().unwrap().push((id, Utc::now()));
+        }
         Ok(())
     }
 
@@ -1335,6 +3276,21 @@ impl EventReceiverGroupRepository for MockEventReceiverGroupRepository {
         Ok(Vec::new())
     }
 
+    async fn find_default_schema_group(
+        &self,
+        _receiver_id: EventReceiverId,
+    ) -> xzepr::error::Result<Option<EventReceiverGroupId>> {
+        Ok(None)
+    }
+
+    async fn set_default_schema_group(
+        &self,
+        _receiver_id: EventReceiverId,
+        _group_id: EventReceiverGroupId,
+    ) -> xzepr::error::Result<()> {
+        Ok(())
+    }
+
     async fn find_by_criteria(
         &self,
         _criteria: FindEventReceiverGroupCriteria,
@@ -1405,15 +3361,44 @@ impl EventReceiverGroupRepository for MockEventReceiverGroupRepository {
         Ok(vec![])
     }
 
+    async fn get_group_memberships(
+        &self,
+        _group_id: EventReceiverGroupId,
+    ) -> xzepr::error::Result<
+        Vec<xzepr::domain::repositories::event_receiver_group_repo::GroupMembership>,
+    > {
+        Ok(vec![])
+    }
+
     async fn add_member(
         &self,
         _group_id: EventReceiverGroupId,
         _user_id: UserId,
         _added_by: UserId,
+        _expires_at: Option<DateTime<Utc>>,
+    ) -> xzepr::error::Result<()> {
+        Ok(())
+    }
+
+    async fn set_member_expiry(
+        &self,
+        _group_id: EventReceiverGroupId,
+        _user_id: UserId,
+        _expires_at: Option<DateTime<Utc>>,
     ) -> xzepr::error::Result<()> {
         Ok(())
     }
 
+    async fn remove_expired_members(
+        &self,
+        _now: DateTime<Utc>,
+        _limit: usize,
+    ) -> xzepr::error::Result<
+        Vec<xzepr::domain::repositories::event_receiver_group_repo::ExpiredMembership>,
+    > {
+        Ok(vec![])
+    }
+
     async fn remove_member(
         &self,
         _group_id: EventReceiverGroupId,
@@ -1429,3 +3414,109 @@ impl EventReceiverGroupRepository for MockEventReceiverGroupRepository {
         Ok(vec![])
     }
 }
+
+#[async_trait]
+impl EventReceiverChangeFeedRepository for MockEventReceiverRepository {
+    async fn receiver_changes_since(
+        &self,
+        since: &ChangeCursor,
+        limit: usize,
+    ) -> xzepr::error::Result<ChangeSet<EventReceiver, EventReceiverId>> {
+        let receivers = self.receivers.lock().unwrap();
+        let tombstones = self.tombstones.lock().unwrap();
+
+        let upserts = receivers.values().map(|r| {
+            (
+                ChangeCursor::new(r.updated_at(), r.id().to_string()),
+                Change::Upserted(r.clone()),
+            )
+        });
+        let deletions = tombstones
+            .iter()
+            .map(|(id, at)| (ChangeCursor::new(*at, id.to_string()), Change::Deleted(*id)));
+
+        Ok(ChangeSet::from_changes(
+            since,
+            limit,
+            upserts.chain(deletions),
+        ))
+    }
+}
+
+#[async_trait]
+impl EventReceiverGroupChangeFeedRepository for MockEventReceiverGroupRepository {
+    async fn group_changes_since(
+        &self,
+        since: &ChangeCursor,
+        limit: usize,
+    ) -> xzepr::error::Result<ChangeSet<EventReceiverGroup, EventReceiverGroupId>> {
+        let groups = self.groups.lock().unwrap();
+        let tombstones = self.tombstones.lock().unwrap();
+
+        let upserts = groups.values().map(|g| {
+            (
+                ChangeCursor::new(g.updated_at(), g.id().to_string()),
+                Change::Upserted(g.clone()),
+            )
+        });
+        let deletions = tombstones
+            .iter()
+            .map(|(id, at)| (ChangeCursor::new(*at, id.to_string()), Change::Deleted(*id)));
+
+        Ok(ChangeSet::from_changes(
+            since,
+            limit,
+            upserts.chain(deletions),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+    use xzepr::api::graphql::{QUERY_TOO_LARGE, TOO_MANY_ALIASES};
+
+    /// The GraphQL routes with a stub endpoint standing in for the schema
+    fn graphql_app() -> Router {
+        let graphql = GraphQLConfig {
+            max_request_bytes: 1024,
+            max_aliases_per_field: 2,
+            ..GraphQLConfig::default()
+        };
+        let stub = || async { Json(json!({ "data": "executed" })) };
+        graphql_routes(&graphql, post(stub), get(stub), get(stub))
+    }
+
+    /// Posts `body` to `/graphql`, returning the error code or the data
+    async fn post_graphql(body: String) -> serde_json::Value {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/graphql")
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let response = graphql_app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        match json.get("errors") {
+            Some(errors) => errors[0]["extensions"]["code"].clone(),
+            None => json["data"].clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graphql_endpoint_applies_query_limits() {
+        let small = json!({ "query": "{ a b }" }).to_string();
+        assert_eq!(post_graphql(small).await, "executed");
+
+        let oversized = json!({ "query": format!("{{ a }} #{}", "x".repeat(2048)) });
+        assert_eq!(post_graphql(oversized.to_string()).await, QUERY_TOO_LARGE);
+
+        let aliased = json!({ "query": "{ a: f b: f c: f }" }).to_string();
+        assert_eq!(post_graphql(aliased).await, TOO_MANY_ALIASES);
+    }
+}
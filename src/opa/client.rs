@@ -10,6 +10,8 @@
 use super::cache::{AuthorizationCache, CacheKey};
 use super::circuit_breaker::{CircuitBreaker, CircuitBreakerError};
 use super::types::{AuthorizationDecision, OpaConfig, OpaError, OpaInput, OpaRequest, OpaResponse};
+use crate::infrastructure::deadline;
+use crate::infrastructure::http_client::{HttpClientError, HttpClientFactory};
 use chrono::Duration;
 use reqwest::Client;
 use std::sync::Arc;
@@ -98,10 +100,26 @@ impl OpaClient {
     /// let client = OpaClient::new(config);
     /// ```
     pub fn new(config: OpaConfig) -> Self {
-        let http_client = Client::builder()
+        Self::with_http_client_factory(config, &HttpClientFactory::default())
+            .expect("Failed to build HTTP client")
+    }
+
+    /// Creates a new OPA client using the shared outbound HTTP settings
+    ///
+    /// The proxy and CA bundle come from `factory`; the request timeout is
+    /// taken from `config.timeout_seconds`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be built
+    pub fn with_http_client_factory(
+        config: OpaConfig,
+        factory: &HttpClientFactory,
+    ) -> Result<Self, HttpClientError> {
+        let http_client = factory
+            .builder()
             .timeout(StdDuration::from_secs(config.timeout_seconds))
-            .build()
-            .expect("Failed to build HTTP client");
+            .build()?;
 
         let cache = Arc::new(AuthorizationCache::new(Duration::seconds(
             config.cache_ttl_seconds as i64,
@@ -109,12 +127,12 @@ impl OpaClient {
 
         let circuit_breaker = Arc::new(CircuitBreaker::new(5, StdDuration::from_secs(30)));
 
-        Self {
+        Ok(Self {
             http_client,
             config,
             cache,
             circuit_breaker,
-        }
+        })
     }
 
     /// Evaluates a policy with caching
@@ -229,10 +247,12 @@ impl OpaClient {
 
         let url = format!("{}{}", self.config.url, self.config.policy_path);
 
+        let timeout = StdDuration::from_secs(self.config.timeout_seconds);
         let response = self
             .http_client
             .post(&url)
             .json(&request)
+            .timeout(deadline::capped_timeout(timeout))
             .send()
             .await
             .map_err(|e| OpaError::RequestFailed(e.to_string()))?;
@@ -254,6 +274,41 @@ impl OpaClient {
             .ok_or_else(|| OpaError::EvaluationError("No result in OPA response".to_string()))
     }
 
+    /// Probes the OPA server health endpoint
+    ///
+    /// Used at startup to wait for OPA before serving traffic.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OpaError::RequestFailed` if OPA cannot be reached,
+    /// `OpaError::InvalidResponse` for server errors, and
+    /// `OpaError::ConfigurationError` for client errors such as a wrong URL
+    pub async fn health_check(&self) -> Result<(), OpaError> {
+        let url = format!("{}/health", self.config.url.trim_end_matches('/'));
+
+        let response = self
+            .http_client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| OpaError::RequestFailed(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status.is_client_error() {
+            Err(OpaError::ConfigurationError(format!(
+                "OPA health check at {} returned status: {}",
+                url, status
+            )))
+        } else {
+            Err(OpaError::InvalidResponse(format!(
+                "OPA health check returned status: {}",
+                status
+            )))
+        }
+    }
+
     /// Evaluates a policy with circuit breaker protection
     ///
     /// Uses the circuit breaker to prevent cascading failures when OPA is unavailable.
@@ -0,0 +1,172 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/api_key_rotation_tests.rs
+
+//! Integration tests for rotating API keys stored in PostgreSQL and
+//! tracking their usage
+//!
+//! These tests start PostgreSQL with testcontainers and require a running
+//! Docker daemon, so they are ignored by default:
+//!
+//! ```bash
+//! cargo test --test api_key_rotation_tests -- --ignored
+//! ```
+
+mod common;
+
+use chrono::{Duration as ChronoDuration, Utc};
+use common::start_postgres;
+use std::sync::Arc;
+use xzepr::auth::api_key::{
+    ApiKeyExpiryNotifier, ApiKeyRepository, ApiKeyRequest, ApiKeySecret, UserRepository,
+};
+use xzepr::auth::api_key_usage::{
+    ApiKeyOutcome, ApiKeyUsageRepository, ApiKeyUsageTracker, MAX_RECENT_FAILURES,
+};
+use xzepr::domain::entities::user::User;
+use xzepr::error::AuthError;
+use xzepr::infrastructure::database::PostgresApiKeyUsageRepository;
+use xzepr::{ApiKeyService, PostgresApiKeyRepository, PostgresUserRepository};
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_rotated_key_accepts_both_secrets_during_grace() {
+    let (_postgres, pool) = start_postgres().await;
+
+    let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
+    let user = User::new_oidc(
+        "producer".to_string(),
+        "producer@example.com".to_string(),
+        "subject-1".to_string(),
+    );
+    user_repo.save(&user).await.unwrap();
+
+    let api_key_repo = Arc::new(PostgresApiKeyRepository::new(pool));
+    let service = ApiKeyService::new(user_repo, api_key_repo.clone());
+
+    let now = Utc::now();
+    let (old_secret, api_key) = service
+        .generate_api_key(
+            *user.id(),
+            "ci".to_string(),
+            Some(now + ChronoDuration::days(3)),
+        )
+        .await
+        .unwrap();
+    let (new_secret, _) = service
+        .rotate_key(api_key.id, Some(ChronoDuration::hours(1)))
+        .await
+        .unwrap();
+
+    service.verify_api_key(&old_secret).await.unwrap();
+    let stored = service.get_key(api_key.id).await.unwrap();
+    assert_eq!(stored.last_used_with, Some(ApiKeySecret::Previous));
+
+    service.verify_api_key(&new_secret).await.unwrap();
+    let stored = service.get_key(api_key.id).await.unwrap();
+    assert_eq!(stored.last_used_with, Some(ApiKeySecret::Current));
+
+    let after_grace = now + ChronoDuration::hours(2);
+    assert!(matches!(
+        service.verify_api_key_at(&old_secret, after_grace).await,
+        Err(AuthError::InvalidApiKey)
+    ));
+
+    // The expiry warning is stored, so a second run the same day is quiet
+    let notifier = ApiKeyExpiryNotifier::new(api_key_repo, ChronoDuration::days(7));
+    assert_eq!(notifier.run_once(now).await.unwrap(), vec![api_key.id]);
+    assert!(notifier.run_once(now).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_key_usage_accumulates_and_keeps_latest_failures() {
+    let (_postgres, pool) = start_postgres().await;
+
+    let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
+    let user = User::new_oidc(
+        "producer".to_string(),
+        "producer@example.com".to_string(),
+        "subject-1".to_string(),
+    );
+    user_repo.save(&user).await.unwrap();
+
+    let api_key_repo = Arc::new(PostgresApiKeyRepository::new(pool.clone()));
+    let usage_repo = Arc::new(PostgresApiKeyUsageRepository::new(pool.clone()));
+    let tracker = ApiKeyUsageTracker::new(api_key_repo.clone(), usage_repo.clone());
+    let service =
+        ApiKeyService::new(user_repo, api_key_repo.clone()).with_usage_tracker(tracker.clone());
+    let (secret, api_key) = service
+        .generate_api_key(*user.id(), "ci".to_string(), None)
+        .await
+        .unwrap();
+    let (_, deleted) = service
+        .generate_api_key(*user.id(), "gone".to_string(), None)
+        .await
+        .unwrap();
+
+    let request = ApiKeyRequest {
+        route: "/api/v1/events".to_string(),
+        ip: Some("192.0.2.10".to_string()),
+    };
+    service
+        .authenticate_request(&secret, &request)
+        .await
+        .unwrap();
+    let now = Utc::now();
+    for flush in 0..2 {
+        tracker.record_outcome(
+            api_key.id,
+            ApiKeyOutcome::Success,
+            None,
+            &request.route,
+            now,
+        );
+        for i in 0..15 {
+            tracker.record_outcome(
+                api_key.id,
+                ApiKeyOutcome::AuthzDenied,
+                Some(&format!("denied-{}-{}", flush, i)),
+                "/api/v1/receivers",
+                now + ChronoDuration::milliseconds(flush * 100 + i),
+            );
+        }
+        tracker.flush().await.unwrap();
+    }
+
+    let stored = api_key_repo.find_by_id(api_key.id).await.unwrap().unwrap();
+    assert_eq!(stored.last_used_ip.as_deref(), Some("192.0.2.10"));
+    assert_eq!(stored.last_used_with, Some(ApiKeySecret::Current));
+
+    let days = usage_repo
+        .find_daily_usage(api_key.id, now.date_naive())
+        .await
+        .unwrap();
+    assert_eq!(days.len(), 1);
+    assert_eq!(days[0].successes, 2);
+    assert_eq!(days[0].authz_denials, 30);
+
+    let failures = usage_repo
+        .find_recent_failures(api_key.id, 100)
+        .await
+        .unwrap();
+    assert_eq!(failures.len(), MAX_RECENT_FAILURES);
+    assert_eq!(failures[0].reason, "denied-1-14");
+
+    // Usage of a key deleted before the flush is dropped, not retried
+    sqlx::query("DELETE FROM api_keys WHERE id = $1")
+        .bind(deleted.id.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    tracker.record_outcome(
+        deleted.id,
+        ApiKeyOutcome::Success,
+        None,
+        &request.route,
+        now,
+    );
+    assert_eq!(tracker.flush().await.unwrap(), 1);
+    assert_eq!(tracker.flush().await.unwrap(), 0);
+}
@@ -0,0 +1,151 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/audit_chain_tests.rs
+
+//! Integration tests for the hash-chained audit record store
+//!
+//! These tests start PostgreSQL with testcontainers and require a running
+//! Docker daemon, so they are ignored by default:
+//!
+//! ```bash
+//! cargo test --test audit_chain_tests -- --ignored
+//! ```
+
+mod common;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use common::start_postgres;
+use std::sync::Arc;
+use xzepr::application::handlers::{AuditChainHandler, AuditRetentionHandler};
+use xzepr::domain::entities::audit_chain::AuditChainFault;
+use xzepr::domain::repositories::audit_record_repo::AuditRecordRepository;
+use xzepr::infrastructure::database::PostgresAuditRecordRepository;
+
+fn day(d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 5, d).unwrap()
+}
+
+fn at(d: u32, minute: u32) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&day(d).and_hms_opt(10, minute, 0).unwrap())
+}
+
+/// Appends `per_day` records to each day, concurrently within a day
+async fn seed(records: &Arc<PostgresAuditRecordRepository>, days: &[u32], per_day: u32) {
+    for &d in days {
+        let writers: Vec<_> = (0..per_day)
+            .map(|n| {
+                let records = records.clone();
+                tokio::spawn(async move {
+                    records
+                        .append(at(d, n), format!(r#"{{"action":"login","n":{}}}"#, n))
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_concurrent_appends_form_one_verifiable_chain_per_day() {
+    let (_container, pool) = start_postgres().await;
+    let records = Arc::new(PostgresAuditRecordRepository::new(pool.clone()));
+    seed(&records, &[1, 2], 20).await;
+
+    let positions: Vec<i64> = sqlx::query_scalar(
+        "SELECT position FROM audit_records WHERE chain_day = $1 ORDER BY position",
+    )
+    .bind(day(1))
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(positions, (1..=20).collect::<Vec<i64>>());
+
+    let report = AuditChainHandler::new(records)
+        .with_page_size(7)
+        .verify(day(1), day(2))
+        .await
+        .unwrap();
+    assert!(report.is_intact());
+    assert_eq!(report.records_checked, 40);
+    assert_eq!(report.days_checked, 2);
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_records_cannot_be_updated() {
+    let (_container, pool) = start_postgres().await;
+    let records = Arc::new(PostgresAuditRecordRepository::new(pool.clone()));
+    seed(&records, &[1], 1).await;
+
+    let result = sqlx::query("UPDATE audit_records SET content = '{}'")
+        .execute(&pool)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_mutated_record_is_reported_with_its_position() {
+    let (_container, pool) = start_postgres().await;
+    let records = Arc::new(PostgresAuditRecordRepository::new(pool.clone()));
+    seed(&records, &[1, 2], 10).await;
+
+    // Tamper the way only a superuser could, around the append-only trigger
+    sqlx::query("ALTER TABLE audit_records DISABLE TRIGGER audit_records_append_only")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"UPDATE audit_records SET content = '{"action":"logout"}'
+           WHERE chain_day = $1 AND position = 6"#,
+    )
+    .bind(day(2))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let report = AuditChainHandler::new(records)
+        .verify(day(1), day(2))
+        .await
+        .unwrap();
+
+    let broken = report.first_broken.unwrap();
+    assert_eq!((broken.chain_day, broken.position), (day(2), 6));
+    assert_eq!(broken.fault, AuditChainFault::ContentMismatch);
+    assert_eq!(report.records_checked, 16);
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_purged_chain_still_verifies_through_anchors() {
+    let (_container, pool) = start_postgres().await;
+    let records = Arc::new(PostgresAuditRecordRepository::new(pool.clone()));
+    seed(&records, &[1, 2, 3], 10).await;
+
+    // Expire day 1 and part of day 2 in two passes
+    let retention = AuditRetentionHandler::new(records.clone())
+        .with_retention_days(1)
+        .with_batch_size(8);
+    let now = at(4, 0);
+    let first = retention.run_once(now).await.unwrap();
+    let second = retention.run_once(now).await.unwrap();
+    assert_eq!((first.records, first.anchors), (8, 1));
+    assert_eq!((second.records, second.anchors), (8, 2));
+
+    let handler = AuditChainHandler::new(records.clone());
+    let report = handler.verify(day(1), day(3)).await.unwrap();
+    assert!(report.is_intact());
+    assert_eq!(report.records_checked, 14);
+
+    // A purged day keeps extending its chain from the anchor
+    records.append(at(1, 59), "{}".to_string()).await.unwrap();
+    let report = handler.verify(day(1), day(1)).await.unwrap();
+    assert!(report.is_intact());
+    assert_eq!(report.records_checked, 1);
+}
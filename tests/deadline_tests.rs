@@ -0,0 +1,92 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/deadline_tests.rs
+
+//! Integration tests for stopping database work at the request deadline
+//!
+//! They start PostgreSQL with testcontainers and require a running Docker
+//! daemon, so they are ignored by default. Set `XZEPR_TEST_DATABASE_URL` to
+//! run them against an existing server instead; each test creates a
+//! database of its own there:
+//!
+//! ```bash
+//! cargo test --test deadline_tests -- --ignored
+//! ```
+
+mod common;
+
+use common::start_postgres;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use xzepr::domain::repositories::event_repo::EventRepository;
+use xzepr::domain::value_objects::EventId;
+use xzepr::error::{Error, InfrastructureError};
+use xzepr::infrastructure::database::PostgresEventRepository;
+use xzepr::infrastructure::deadline::{guard, with_deadline, DeadlineSource, RequestDeadline};
+use xzepr::infrastructure::DeadlineEventRepository;
+
+fn deadline_in(millis: u64) -> Option<RequestDeadline> {
+    Some(RequestDeadline::after(
+        Duration::from_millis(millis),
+        DeadlineSource::Client,
+    ))
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_slow_query_is_cancelled_at_the_deadline() {
+    let (_container, pool) = start_postgres().await;
+    let started = Instant::now();
+
+    let result = with_deadline(
+        deadline_in(200),
+        guard(
+            "test.pg_sleep",
+            sqlx::query("SELECT pg_sleep(5)").execute(&pool),
+        ),
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert!(started.elapsed() < Duration::from_secs(3));
+
+    // The pool is still usable afterwards
+    let one: i32 = sqlx::query_scalar("SELECT 1")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(one, 1);
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_repository_stops_waiting_for_a_locked_table() {
+    let (_container, pool) = start_postgres().await;
+    let events = DeadlineEventRepository::new(Arc::new(PostgresEventRepository::new(pool.clone())));
+
+    // Another transaction holds the events table until rolled back
+    let mut lock = pool.begin().await.unwrap();
+    sqlx::query("LOCK TABLE events IN ACCESS EXCLUSIVE MODE")
+        .execute(&mut *lock)
+        .await
+        .unwrap();
+
+    let started = Instant::now();
+    let result = with_deadline(deadline_in(300), events.find_by_id(EventId::new())).await;
+
+    match result {
+        Err(Error::Infrastructure(InfrastructureError::DeadlineExceeded { operation })) => {
+            assert_eq!(operation, "events.find_by_id")
+        }
+        other => panic!("expected the deadline to be exceeded, got {:?}", other),
+    }
+    assert!(started.elapsed() < Duration::from_secs(3));
+
+    // Once the lock is gone, a request with time left gets its answer
+    lock.rollback().await.unwrap();
+    let found = with_deadline(deadline_in(5_000), events.find_by_id(EventId::new()))
+        .await
+        .unwrap();
+    assert!(found.is_none());
+}
@@ -0,0 +1,132 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/default_schema_group_tests.rs
+
+//! Integration tests for a receiver's default schema group
+//!
+//! These tests start PostgreSQL with testcontainers and require a running
+//! Docker daemon, so they are ignored by default:
+//!
+//! ```bash
+//! cargo test --test default_schema_group_tests -- --ignored
+//! ```
+
+mod common;
+
+use common::start_postgres;
+use serde_json::json;
+use sqlx::PgPool;
+use xzepr::domain::entities::event_receiver::EventReceiver;
+use xzepr::domain::entities::event_receiver_group::EventReceiverGroup;
+use xzepr::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use xzepr::domain::value_objects::EventReceiverGroupId;
+use xzepr::error::{DomainError, Error};
+use xzepr::fixtures::{GroupFixture, ReceiverFixture};
+use xzepr::infrastructure::database::{
+    PostgresEventReceiverGroupRepository, PostgresEventReceiverRepository,
+};
+
+async fn save_receiver(pool: &PgPool) -> EventReceiver {
+    ReceiverFixture::new()
+        .schema(json!({}))
+        .persist(&PostgresEventReceiverRepository::new(pool.clone()))
+        .await
+}
+
+async fn save_group(
+    repo: &PostgresEventReceiverGroupRepository,
+    name: &str,
+    receiver: &EventReceiver,
+) -> EventReceiverGroup {
+    GroupFixture::new()
+        .name(name)
+        .receiver(receiver)
+        .default_schema(json!({"required": [name]}))
+        .persist(repo)
+        .await
+}
+
+async fn default_rows(pool: &PgPool, receiver: &EventReceiver) -> Vec<String> {
+    sqlx::query_scalar(
+        "SELECT group_id FROM event_receiver_group_receivers WHERE receiver_id = $1 AND is_default",
+    )
+    .bind(receiver.id().to_string())
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_setting_a_default_replaces_the_previous_one() {
+    let (_container, pool) = start_postgres().await;
+    let repo = PostgresEventReceiverGroupRepository::new(pool.clone());
+    let receiver = save_receiver(&pool).await;
+    let builds = save_group(&repo, "builds", &receiver).await;
+    let deploys = save_group(&repo, "deploys", &receiver).await;
+
+    repo.set_default_schema_group(receiver.id(), builds.id())
+        .await
+        .unwrap();
+    repo.set_default_schema_group(receiver.id(), deploys.id())
+        .await
+        .unwrap();
+    assert_eq!(
+        default_rows(&pool, &receiver).await,
+        [deploys.id().to_string()]
+    );
+    assert_eq!(
+        repo.find_default_schema_group(receiver.id()).await.unwrap(),
+        Some(deploys.id())
+    );
+
+    // Saving the group again keeps the flag on memberships it still has
+    repo.update(&deploys).await.unwrap();
+    assert_eq!(
+        repo.find_default_schema_group(receiver.id()).await.unwrap(),
+        Some(deploys.id())
+    );
+
+    // A failed swap rolls back, leaving the previous default in place
+    let err = repo
+        .set_default_schema_group(receiver.id(), EventReceiverGroupId::new())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::Domain(DomainError::NotFound { .. })),
+        "{err:?}"
+    );
+    assert_eq!(
+        default_rows(&pool, &receiver).await,
+        [deploys.id().to_string()]
+    );
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_index_rejects_a_second_default() {
+    let (_container, pool) = start_postgres().await;
+    let repo = PostgresEventReceiverGroupRepository::new(pool.clone());
+    let receiver = save_receiver(&pool).await;
+    let builds = save_group(&repo, "builds", &receiver).await;
+    save_group(&repo, "deploys", &receiver).await;
+
+    repo.set_default_schema_group(receiver.id(), builds.id())
+        .await
+        .unwrap();
+
+    // Writes bypassing the repository cannot add a second default
+    let err = sqlx::query(
+        "UPDATE event_receiver_group_receivers SET is_default = TRUE WHERE receiver_id = $1",
+    )
+    .bind(receiver.id().to_string())
+    .execute(&pool)
+    .await
+    .unwrap_err();
+    let code = err
+        .as_database_error()
+        .and_then(|e| e.code())
+        .map(|code| code.into_owned());
+    assert_eq!(code.as_deref(), Some("23505"));
+}
@@ -0,0 +1,224 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integration tests for embedding XZepr in a host router
+//!
+//! These tests verify that:
+//! 1. The XZepr router works when nested under a host prefix
+//! 2. Authentication is enforced on the nested API routes
+//! 3. Stored events reach domain event subscribers of the handle
+//! 4. Host jobs run through the shared job runner and stop on shutdown
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+use xzepr::application::domain_events::DomainEvent;
+use xzepr::auth::jwt::JwtService;
+use xzepr::infrastructure::{Job, JobReport, JobSchedule};
+use xzepr::{Role, Settings, UserId, XzeprBuilder};
+
+const TEST_SECRET: &str = "embedded-test-secret-key-do-not-use-in-production";
+
+/// Counts its runs
+struct CountingJob {
+    runs: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Job for CountingJob {
+    fn name(&self) -> &str {
+        "host_counter"
+    }
+
+    fn schedule(&self) -> JobSchedule {
+        JobSchedule::Every(Duration::from_secs(3600))
+    }
+
+    async fn run(&self) -> xzepr::Result<JobReport> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        Ok(JobReport::default())
+    }
+}
+
+fn test_settings() -> Settings {
+    let mut settings = Settings::new().expect("Failed to load settings");
+    settings.auth.jwt.algorithm = "HS256".to_string();
+    settings.auth.jwt.secret_key = Some(TEST_SECRET.to_string().into());
+    settings.graphql.playground_enabled = true;
+    settings
+}
+
+/// Mints an admin token accepted by the embedded router
+fn admin_token(settings: &Settings) -> String {
+    let service = JwtService::from_config(settings.auth.jwt.to_jwt_config().unwrap()).unwrap();
+    let permissions = Role::Admin
+        .permissions()
+        .iter()
+        .map(|permission| format!("{:?}", permission))
+        .collect();
+    service
+        .generate_access_token(
+            UserId::new().to_string(),
+            vec![Role::Admin.to_string()],
+            permissions,
+        )
+        .unwrap()
+}
+
+async fn send(
+    app: &Router,
+    method: Method,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, String) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(token) = token {
+        request = request.header("authorization", format!("Bearer {}", token));
+    }
+    let request = match body {
+        Some(body) => request
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+        None => request.body(Body::empty()).unwrap(),
+    };
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[tokio::test]
+async fn test_nested_router_end_to_end() {
+    let settings = test_settings();
+    let token = admin_token(&settings);
+    let runs = Arc::new(AtomicUsize::new(0));
+    let (xzepr, handle) = XzeprBuilder::new(settings)
+        .with_job(Arc::new(CountingJob { runs: runs.clone() }))
+        .build()
+        .await
+        .expect("Failed to build embedded router");
+    let mut domain_events = handle.domain_events().subscribe();
+
+    let app = Router::new()
+        .route("/host", get(|| async { "host" }))
+        .nest("/xzepr", xzepr);
+
+    // Host and embedded public routes are both served
+    let (status, body) = send(&app, Method::GET, "/host", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "host");
+    let (status, _) = send(&app, Method::GET, "/xzepr/health", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The API requires a token under the prefix too
+    let (status, _) = send(&app, Method::GET, "/xzepr/api/v1/receivers", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, body) = send(
+        &app,
+        Method::POST,
+        "/xzepr/api/v1/receivers",
+        Some(&token),
+        Some(serde_json::json!({
+            "name": "embedded-receiver",
+            "type": "build",
+            "version": "1.0.0",
+            "description": "Receiver created through the embedded router",
+            "schema": {"type": "object"}
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let receiver_id = serde_json::from_str::<serde_json::Value>(&body).unwrap()["data"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let (status, body) = send(
+        &app,
+        Method::POST,
+        "/xzepr/api/v1/events",
+        Some(&token),
+        Some(serde_json::json!({
+            "name": "build.finished",
+            "version": "1.0.0",
+            "release": "1.0.0",
+            "platform_id": "linux",
+            "package": "embedded",
+            "description": "Event created through the embedded router",
+            "payload": {"ok": true},
+            "success": true,
+            "event_receiver_id": receiver_id
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    // The handle sees what the router stored
+    let created = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let DomainEvent::EventCreated { event } = &*domain_events.recv().await.unwrap() {
+                return event.name().to_string();
+            }
+        }
+    })
+    .await
+    .expect("EventCreated was not published");
+    assert_eq!(created, "build.finished");
+
+    // Internal links follow the mount point
+    let (status, body) = send(&app, Method::GET, "/xzepr/graphql/playground", None, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("/xzepr/graphql"));
+
+    // Host jobs run through the shared runner and the admin API
+    let (status, body) = send(
+        &app,
+        Method::POST,
+        "/xzepr/api/v1/admin/jobs/host_counter/run",
+        Some(&token),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        handle.jobs().status("host_counter").unwrap().success_count,
+        1
+    );
+
+    tokio::time::timeout(Duration::from_secs(5), handle.shutdown())
+        .await
+        .expect("Shutdown did not finish");
+}
+
+#[tokio::test]
+async fn test_disabled_subsystems_are_not_served() {
+    let (xzepr, handle) = XzeprBuilder::new(test_settings())
+        .with_graphql(false)
+        .with_jobs(false)
+        .build()
+        .await
+        .expect("Failed to build embedded router");
+    let app = Router::new().nest("/xzepr", xzepr);
+
+    let (status, _) = send(&app, Method::GET, "/xzepr/graphql/playground", None, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send(&app, Method::GET, "/xzepr/api/v1/receivers", None, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    handle.shutdown().await;
+}
@@ -0,0 +1,200 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/group_topic_fanout_tests.rs
+
+//! Integration tests for fanning events out to dedicated group topics
+//!
+//! These tests start Redpanda and PostgreSQL with testcontainers and require
+//! a running Docker daemon, so they are ignored by default:
+//!
+//! ```bash
+//! cargo test --test group_topic_fanout_tests -- --ignored
+//! ```
+
+mod common;
+
+use common::start_postgres;
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::{ClientConfig, Message};
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+use xzepr::application::handlers::{
+    EventHandler, EventReceiverGroupHandler, EventReceiverHandler, GroupTopicFanout,
+};
+use xzepr::domain::entities::event::CreateEventParams;
+use xzepr::domain::value_objects::{EventId, UserId};
+use xzepr::infrastructure::database::{
+    PostgresEventReceiverGroupRepository, PostgresEventReceiverRepository, PostgresEventRepository,
+    PostgresGroupTopicOutboxRepository,
+};
+use xzepr::infrastructure::messaging::producer::KafkaEventPublisher;
+use xzepr::infrastructure::messaging::{KafkaTopicProvisioner, TopicManager};
+
+/// Host port the broker advertises; fixed so it is known before startup
+const REDPANDA_HOST_PORT: u16 = 29093;
+
+const MAIN_TOPIC: &str = "xzepr.test.events";
+const BUILDS_TOPIC: &str = "xzepr.test.builds";
+const RELEASES_TOPIC: &str = "xzepr.test.releases";
+
+async fn start_redpanda() -> ContainerAsync<GenericImage> {
+    let advertise = format!("PLAINTEXT://127.0.0.1:{}", REDPANDA_HOST_PORT);
+    GenericImage::new("docker.redpanda.com/redpandadata/redpanda", "v24.2.4")
+        .with_exposed_port(9092.tcp())
+        .with_wait_for(WaitFor::message_on_stderr("Successfully started Redpanda!"))
+        .with_mapped_port(REDPANDA_HOST_PORT, 9092.tcp())
+        .with_cmd([
+            "redpanda",
+            "start",
+            "--overprovisioned",
+            "--smp",
+            "1",
+            "--memory",
+            "512M",
+            "--reserve-memory",
+            "0M",
+            "--node-id",
+            "0",
+            "--check=false",
+            "--kafka-addr",
+            "PLAINTEXT://0.0.0.0:9092",
+            "--advertise-kafka-addr",
+            advertise.as_str(),
+            "--set",
+            "redpanda.auto_create_topics_enabled=false",
+        ])
+        .start()
+        .await
+        .expect("failed to start Redpanda")
+}
+
+/// Returns the ids of the events published to `topic`, polling from the
+/// start until `expected` of them arrived or a few seconds passed
+fn consume_event_ids(brokers: &str, topic: &str, expected: usize) -> HashSet<String> {
+    let consumer: BaseConsumer = ClientConfig::new()
+        .set("bootstrap.servers", brokers)
+        .set("group.id", format!("fanout-test-{}", topic))
+        .set("auto.offset.reset", "earliest")
+        .set("enable.auto.commit", "false")
+        .create()
+        .unwrap();
+    consumer.subscribe(&[topic]).unwrap();
+
+    let mut ids = HashSet::new();
+    let deadline = Instant::now() + Duration::from_secs(15);
+    while Instant::now() < deadline && ids.len() < expected.max(1) {
+        if let Some(Ok(message)) = consumer.poll(Duration::from_millis(500)) {
+            let payload: serde_json::Value =
+                serde_json::from_slice(message.payload().unwrap_or_default()).unwrap();
+            ids.insert(payload["id"].as_str().unwrap().to_string());
+        }
+    }
+    ids
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run Redpanda and PostgreSQL containers"]
+async fn test_events_fan_out_to_enabled_group_topics() {
+    let _redpanda = start_redpanda().await;
+    let (_postgres, pool) = start_postgres().await;
+    let brokers = format!("127.0.0.1:{}", REDPANDA_HOST_PORT);
+
+    let provisioner = Arc::new(KafkaTopicProvisioner::new(
+        TopicManager::new(&brokers).unwrap(),
+        1,
+        1,
+    ));
+    TopicManager::new(&brokers)
+        .unwrap()
+        .ensure_topic_exists(MAIN_TOPIC, 1, 1)
+        .await
+        .unwrap();
+
+    let event_repo = Arc::new(PostgresEventRepository::new(pool.clone()));
+    let receiver_repo = Arc::new(PostgresEventReceiverRepository::new(pool.clone()));
+    let group_repo = Arc::new(PostgresEventReceiverGroupRepository::new(pool.clone()));
+    let publisher = Arc::new(KafkaEventPublisher::new(&brokers, MAIN_TOPIC).unwrap());
+
+    let receiver_handler = EventReceiverHandler::new(receiver_repo.clone());
+    let group_handler = EventReceiverGroupHandler::new(group_repo.clone(), receiver_repo.clone())
+        .with_topic_provisioner(provisioner);
+    let fanout = GroupTopicFanout::new(group_repo.clone(), event_repo.clone(), publisher.clone())
+        .with_outbox(Arc::new(PostgresGroupTopicOutboxRepository::new(pool)));
+    let event_handler = EventHandler::with_publisher(event_repo, receiver_repo, publisher)
+        .with_group_fanout(fanout);
+
+    let owner = UserId::new();
+    let receiver_id = receiver_handler
+        .create_event_receiver(
+            "builds".to_string(),
+            "ci".to_string(),
+            "1.0.0".to_string(),
+            "Build results".to_string(),
+            json!({}),
+            owner,
+        )
+        .await
+        .unwrap();
+
+    // Creating the groups provisions their topics, since the broker does
+    // not create topics on first use
+    let mut group_ids = Vec::new();
+    for (name, topic) in [("builds", BUILDS_TOPIC), ("releases", RELEASES_TOPIC)] {
+        let group_id = group_handler
+            .create_event_receiver_group(
+                name.to_string(),
+                "ci".to_string(),
+                "1.0.0".to_string(),
+                format!("{} group", name),
+                true,
+                vec![receiver_id],
+                Vec::new(),
+                None,
+                Some(topic.to_string()),
+                owner,
+            )
+            .await
+            .unwrap();
+        group_ids.push(group_id);
+    }
+
+    let create_event = |name: &str| {
+        event_handler.create_event(CreateEventParams {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            release: "1.0.0".to_string(),
+            platform_id: "linux".to_string(),
+            package: "xzepr".to_string(),
+            description: "Fan-out test".to_string(),
+            payload: json!({"status": "passed"}),
+            success: true,
+            receiver_id,
+            owner_id: owner,
+        })
+    };
+    let event_id = |id: Option<EventId>| id.unwrap().to_string();
+
+    let first = event_id(create_event("build.finished").await.unwrap().event_id());
+    assert!(consume_event_ids(&brokers, MAIN_TOPIC, 1).contains(&first));
+    assert!(consume_event_ids(&brokers, BUILDS_TOPIC, 1).contains(&first));
+    assert!(consume_event_ids(&brokers, RELEASES_TOPIC, 1).contains(&first));
+
+    // A disabled group no longer receives events
+    group_handler
+        .disable_event_receiver_group(group_ids[1])
+        .await
+        .unwrap();
+    let second = event_id(create_event("build.finished").await.unwrap().event_id());
+
+    assert!(consume_event_ids(&brokers, MAIN_TOPIC, 2).contains(&second));
+    assert!(consume_event_ids(&brokers, BUILDS_TOPIC, 2).contains(&second));
+    let releases = consume_event_ids(&brokers, RELEASES_TOPIC, 2);
+    assert!(releases.contains(&first));
+    assert!(!releases.contains(&second));
+}
@@ -0,0 +1,306 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/id_integrity_tests.rs
+
+//! Integration tests for canonical ULID ids and the references between them
+//!
+//! The repair tests seed data the way it could have been written before
+//! ids were checked, by running only the migrations before
+//! [`ULID_MIGRATION`], and then run the rest.
+//!
+//! They start PostgreSQL with testcontainers and require a running Docker
+//! daemon, so they are ignored by default. Set `XZEPR_TEST_DATABASE_URL` to
+//! run them against an existing server instead; each test creates a
+//! database of its own there:
+//!
+//! ```bash
+//! cargo test --test id_integrity_tests -- --ignored
+//! ```
+
+mod common;
+
+use common::start_empty_postgres;
+use serde_json::json;
+use sqlx::migrate::Migrator;
+use sqlx::PgPool;
+use std::borrow::Cow;
+use xzepr::domain::entities::user_preferences::UserPreferences;
+use xzepr::domain::repositories::event_repo::EventRepository;
+use xzepr::domain::repositories::user_preferences_repo::UserPreferencesRepository;
+use xzepr::domain::value_objects::EventReceiverId;
+use xzepr::error::{DomainError, Error};
+use xzepr::fixtures::EventFixture;
+use xzepr::infrastructure::database::{PostgresEventRepository, PostgresUserPreferencesRepository};
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Version of the migration enforcing canonical ULID ids
+const ULID_MIGRATION: i64 = 20250626000001;
+
+const RECEIVER: &str = "01JC2X3Y4Z5A6B7C8D9E0F1G2J";
+const GROUP: &str = "01JC2X3Y4Z5A6B7C8D9E0F1G2K";
+const EVENT: &str = "01JC2X3Y4Z5A6B7C8D9E0F1G2M";
+const OWNER: &str = "01JC2X3Y4Z5A6B7C8D9E0F1G2H";
+
+/// Runs the migrations before ids were checked
+async fn migrate_before_ulid_checks(pool: &PgPool) {
+    let earlier = Migrator {
+        migrations: Cow::Owned(
+            MIGRATOR
+                .iter()
+                .filter(|m| m.version < ULID_MIGRATION)
+                .cloned()
+                .collect(),
+        ),
+        ..Migrator::DEFAULT
+    };
+    earlier.run(pool).await.unwrap();
+}
+
+async fn execute(pool: &PgPool, sql: &str) {
+    sqlx::query(sql)
+        .execute(pool)
+        .await
+        .unwrap_or_else(|e| panic!("failed to execute {}: {}", sql, e));
+}
+
+async fn insert_receiver(pool: &PgPool, id: &str, owner_id: &str) {
+    sqlx::query(
+        "INSERT INTO event_receivers (id, name, receiver_type, version, fingerprint, owner_id) \
+         VALUES ($1, 'receiver-' || $1, 'ci', '1.0.0', md5($1), $2)",
+    )
+    .bind(id)
+    .bind(owner_id)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn insert_event(pool: &PgPool, id: &str, receiver_id: &str) {
+    sqlx::query(
+        "INSERT INTO events (id, name, version, event_receiver_id, owner_id) \
+         VALUES ($1, 'build.finished', '1.0.0', $2, $3)",
+    )
+    .bind(id)
+    .bind(receiver_id)
+    .bind(OWNER)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn unvalidated_constraints(pool: &PgPool) -> Vec<String> {
+    sqlx::query_scalar(
+        "SELECT conname::text FROM pg_constraint \
+         WHERE connamespace = 'public'::regnamespace AND NOT convalidated \
+         ORDER BY conname",
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+fn sql_state(err: &sqlx::Error) -> Option<String> {
+    err.as_database_error()
+        .and_then(|e| e.code())
+        .map(|code| code.into_owned())
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_malformed_ids_fail_loudly() {
+    let (_container, pool) = start_empty_postgres().await;
+    MIGRATOR.run(&pool).await.unwrap();
+    sqlx::query(
+        "INSERT INTO users (id, username, email, auth_provider_type) \
+         VALUES ($1, 'alice', 'alice@example.com', 'local')",
+    )
+    .bind(OWNER)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // Repositories reject what is not a ULID and store the canonical form
+    // of what is
+    let preferences_repo = PostgresUserPreferencesRepository::new(pool.clone());
+    let mut preferences = UserPreferences::new();
+    preferences
+        .merge(&json!({"default_page_size": 25}))
+        .unwrap();
+    let err = preferences_repo
+        .save_preferences("alice", &preferences)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::Domain(DomainError::ValidationError { ref field, .. }) if field == "user_id"),
+        "{err:?}"
+    );
+    preferences_repo
+        .save_preferences(&OWNER.to_lowercase(), &preferences)
+        .await
+        .unwrap();
+    let stored: Vec<String> = sqlx::query_scalar("SELECT user_id FROM user_preferences")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, vec![OWNER.to_string()]);
+    let found = preferences_repo.find_preferences(OWNER).await.unwrap();
+    assert_eq!(found.default_page_size(), Some(25));
+
+    let event_repo = PostgresEventRepository::new(pool.clone());
+    let orphan = EventFixture::new()
+        .receiver_id(EventReceiverId::new())
+        .build();
+    assert!(event_repo.save(&orphan).await.is_err());
+
+    // Writes bypassing the repositories are checked too
+    let err = sqlx::query(
+        "INSERT INTO event_receivers (id, name, receiver_type, version, fingerprint, owner_id) \
+         VALUES ($1, 'lowercase', 'ci', '1.0.0', 'f', $2)",
+    )
+    .bind(RECEIVER.to_lowercase())
+    .bind(OWNER)
+    .execute(&pool)
+    .await
+    .unwrap_err();
+    assert_eq!(sql_state(&err).as_deref(), Some("23514"));
+
+    insert_receiver(&pool, RECEIVER, OWNER).await;
+    let err = sqlx::query(
+        "INSERT INTO events (id, name, version, event_receiver_id, owner_id) \
+         VALUES ($1, 'build.finished', '1.0.0', $2, $3)",
+    )
+    .bind(EVENT.to_lowercase())
+    .bind(RECEIVER)
+    .bind(OWNER)
+    .execute(&pool)
+    .await
+    .unwrap_err();
+    assert_eq!(sql_state(&err).as_deref(), Some("23514"));
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_repair_uppercases_lowercase_ids() {
+    let (_container, pool) = start_empty_postgres().await;
+    migrate_before_ulid_checks(&pool).await;
+    insert_receiver(&pool, &RECEIVER.to_lowercase(), OWNER).await;
+    execute(
+        &pool,
+        &format!(
+            "INSERT INTO event_receiver_groups (id, name, group_type, version, owner_id) \
+             VALUES ('{GROUP}', 'group', 'release', '1.0.0', '{OWNER}')"
+        ),
+    )
+    .await;
+    execute(
+        &pool,
+        &format!(
+            "INSERT INTO event_receiver_group_receivers (group_id, receiver_id) \
+             VALUES ('{GROUP}', '{}')",
+            RECEIVER.to_lowercase()
+        ),
+    )
+    .await;
+    insert_event(&pool, &EVENT.to_lowercase(), &RECEIVER.to_lowercase()).await;
+
+    MIGRATOR.run(&pool).await.unwrap();
+
+    let receivers: Vec<String> = sqlx::query_scalar("SELECT id FROM event_receivers")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(receivers, vec![RECEIVER.to_string()]);
+    let members: Vec<String> =
+        sqlx::query_scalar("SELECT receiver_id FROM event_receiver_group_receivers")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(members, vec![RECEIVER.to_string()]);
+    let events: Vec<(String, String)> = sqlx::query_as("SELECT id, event_receiver_id FROM events")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(events, vec![(EVENT.to_string(), RECEIVER.to_string())]);
+
+    let quarantined: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM id_quarantine")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(quarantined, 0);
+    assert!(unvalidated_constraints(&pool).await.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_repair_quarantines_orphans_without_deleting_them() {
+    let (_container, pool) = start_empty_postgres().await;
+    migrate_before_ulid_checks(&pool).await;
+    let missing = "01JC2X3Y4Z5A6B7C8D9E0F1G9Z";
+    let deleted = "01JC2X3Y4Z5A6B7C8D9E0F1G9Y";
+    insert_receiver(&pool, RECEIVER, "SYSTEM").await;
+    insert_event(&pool, EVENT, missing).await;
+    insert_event(&pool, "01JC2X3Y4Z5A6B7C8D9E0F1G2N", deleted).await;
+    execute(
+        &pool,
+        &format!("INSERT INTO event_receiver_tombstones (id) VALUES ('{deleted}')"),
+    )
+    .await;
+    execute(
+        &pool,
+        &format!(
+            "INSERT INTO event_receiver_heartbeats (event_receiver_id, last_heartbeat_at) \
+             VALUES ('{missing}', NOW())"
+        ),
+    )
+    .await;
+
+    MIGRATOR.run(&pool).await.unwrap();
+
+    let quarantined: Vec<(String, String, String, String)> = sqlx::query_as(
+        "SELECT table_name, column_name, value, reason FROM id_quarantine \
+         ORDER BY table_name, column_name",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    let expected = [
+        (
+            "event_receiver_heartbeats",
+            "event_receiver_id",
+            missing,
+            "orphan",
+        ),
+        ("event_receivers", "owner_id", "SYSTEM", "malformed"),
+        ("events", "event_receiver_id", missing, "orphan"),
+    ]
+    .map(|(table, column, value, reason)| {
+        (
+            table.to_string(),
+            column.to_string(),
+            value.to_string(),
+            reason.to_string(),
+        )
+    });
+    assert_eq!(quarantined, expected);
+
+    // Nothing is deleted, and the violated constraints wait for review
+    let events: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM events")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(events, 2);
+    let heartbeats: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM event_receiver_heartbeats")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(heartbeats, 1);
+    assert_eq!(
+        unvalidated_constraints(&pool).await,
+        vec![
+            "event_receiver_heartbeats_event_receiver_id_fkey",
+            "event_receivers_owner_id_ulid",
+        ]
+    );
+}
@@ -42,7 +42,7 @@ fn create_scram_sha256_config() -> KafkaAuthConfig {
         sasl_config: Some(SaslConfig {
             mechanism: SaslMechanism::ScramSha256,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         }),
         ssl_config: None,
     }
@@ -55,7 +55,7 @@ fn create_scram_sha512_config() -> KafkaAuthConfig {
         sasl_config: Some(SaslConfig {
             mechanism: SaslMechanism::ScramSha512,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         }),
         ssl_config: None,
     }
@@ -68,7 +68,7 @@ fn create_sasl_plain_config() -> KafkaAuthConfig {
         sasl_config: Some(SaslConfig {
             mechanism: SaslMechanism::Plain,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         }),
         ssl_config: None,
     }
@@ -81,7 +81,7 @@ fn create_sasl_ssl_config() -> KafkaAuthConfig {
         sasl_config: Some(SaslConfig {
             mechanism: SaslMechanism::ScramSha256,
             username: "test-user".to_string(),
-            password: "test-password".to_string(),
+            password: "test-password".to_string().into(),
         }),
         ssl_config: Some(SslConfig {
             ca_location: Some("/path/to/ca-cert".to_string()),
@@ -126,7 +126,7 @@ fn test_kafka_auth_config_serialization_scram_sha256() {
     let sasl = deserialized.sasl_config.unwrap();
     assert_eq!(sasl.mechanism, SaslMechanism::ScramSha256);
     assert_eq!(sasl.username, "test-user");
-    assert_eq!(sasl.password, "test-password");
+    assert_eq!(sasl.password.expose(), "test-password");
 }
 
 #[test]
@@ -151,7 +151,7 @@ fn test_kafka_auth_config_serialization_scram_sha512() {
     let sasl = deserialized.sasl_config.unwrap();
     assert_eq!(sasl.mechanism, SaslMechanism::ScramSha512);
     assert_eq!(sasl.username, "test-user");
-    assert_eq!(sasl.password, "test-password");
+    assert_eq!(sasl.password.expose(), "test-password");
 }
 
 #[test]
@@ -176,7 +176,7 @@ fn test_kafka_auth_config_serialization_sasl_plain() {
     let sasl = deserialized.sasl_config.unwrap();
     assert_eq!(sasl.mechanism, SaslMechanism::Plain);
     assert_eq!(sasl.username, "test-user");
-    assert_eq!(sasl.password, "test-password");
+    assert_eq!(sasl.password.expose(), "test-password");
 }
 
 #[test]
@@ -285,7 +285,7 @@ fn test_kafka_auth_config_from_env_scram_sha256() {
     let sasl = config.sasl_config.unwrap();
     assert_eq!(sasl.mechanism, SaslMechanism::ScramSha256);
     assert_eq!(sasl.username, "env-user");
-    assert_eq!(sasl.password, "env-password");
+    assert_eq!(sasl.password.expose(), "env-password");
 
     // Restore original values
     std::env::remove_var("KAFKA_SECURITY_PROTOCOL");
@@ -331,7 +331,7 @@ fn test_kafka_auth_config_from_env_scram_sha512() {
     let sasl = config.sasl_config.unwrap();
     assert_eq!(sasl.mechanism, SaslMechanism::ScramSha512);
     assert_eq!(sasl.username, "env-user");
-    assert_eq!(sasl.password, "env-password");
+    assert_eq!(sasl.password.expose(), "env-password");
 
     // Restore original values
     std::env::remove_var("KAFKA_SECURITY_PROTOCOL");
@@ -597,7 +597,7 @@ fn test_producer_connection_fails_with_invalid_credentials() {
         sasl_config: Some(SaslConfig {
             mechanism: SaslMechanism::ScramSha256,
             username: "invalid-user".to_string(),
-            password: "invalid-password".to_string(),
+            password: "invalid-password".to_string().into(),
         }),
         ssl_config: None,
     };
@@ -619,7 +619,7 @@ fn test_auth_config_validation_missing_sasl_credentials() {
         sasl_config: Some(SaslConfig {
             mechanism: SaslMechanism::ScramSha256,
             username: "".to_string(),
-            password: "".to_string(),
+            password: "".to_string().into(),
         }),
         ssl_config: None,
     };
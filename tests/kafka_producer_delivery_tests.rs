@@ -0,0 +1,110 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/kafka_producer_delivery_tests.rs
+
+//! Integration tests for Kafka producer delivery error reporting
+//!
+//! These tests start a Redpanda broker with testcontainers and require a
+//! running Docker daemon, so they are ignored by default:
+//!
+//! ```bash
+//! cargo test --test kafka_producer_delivery_tests -- --ignored
+//! ```
+
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{GenericImage, ImageExt};
+use xzepr::domain::entities::event::{CreateEventParams, Event};
+use xzepr::domain::value_objects::{EventReceiverId, UserId};
+use xzepr::error::{Error, InfrastructureError};
+use xzepr::infrastructure::messaging::cloudevents::CloudEventMessage;
+use xzepr::infrastructure::messaging::config::KafkaProducerConfig;
+use xzepr::infrastructure::messaging::producer::KafkaEventPublisher;
+
+/// Host port the broker advertises; fixed so it is known before startup
+const REDPANDA_HOST_PORT: u16 = 29092;
+
+fn test_message() -> CloudEventMessage {
+    let event = Event::new(CreateEventParams {
+        name: "delivery.test".to_string(),
+        version: "1.0.0".to_string(),
+        release: "1.0.0".to_string(),
+        platform_id: "test".to_string(),
+        package: "test".to_string(),
+        description: "Delivery error test".to_string(),
+        payload: serde_json::json!({"key": "value"}),
+        success: true,
+        receiver_id: EventReceiverId::new(),
+        owner_id: UserId::new(),
+    })
+    .unwrap();
+
+    CloudEventMessage::from_event(&event)
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a Redpanda container"]
+async fn test_publish_to_missing_topic_is_fatal_delivery_error() {
+    let advertise = format!("PLAINTEXT://127.0.0.1:{}", REDPANDA_HOST_PORT);
+    let _redpanda = GenericImage::new("docker.redpanda.com/redpandadata/redpanda", "v24.2.4")
+        .with_exposed_port(9092.tcp())
+        .with_wait_for(WaitFor::message_on_stderr("Successfully started Redpanda!"))
+        .with_mapped_port(REDPANDA_HOST_PORT, 9092.tcp())
+        .with_cmd([
+            "redpanda",
+            "start",
+            "--overprovisioned",
+            "--smp",
+            "1",
+            "--memory",
+            "512M",
+            "--reserve-memory",
+            "0M",
+            "--node-id",
+            "0",
+            "--check=false",
+            "--kafka-addr",
+            "PLAINTEXT://0.0.0.0:9092",
+            "--advertise-kafka-addr",
+            advertise.as_str(),
+            "--set",
+            "redpanda.auto_create_topics_enabled=false",
+        ])
+        .start()
+        .await
+        .expect("failed to start Redpanda");
+
+    // Outlast librdkafka's topic metadata propagation window (30s) so the
+    // failure is reported as an unknown topic rather than a timeout
+    let producer_config = KafkaProducerConfig {
+        message_timeout_ms: 60000,
+        ..Default::default()
+    };
+    let publisher = KafkaEventPublisher::with_config(
+        &format!("127.0.0.1:{}", REDPANDA_HOST_PORT),
+        "xzepr.test.does-not-exist",
+        None,
+        &producer_config,
+    )
+    .unwrap();
+
+    let err = publisher
+        .publish_message(&test_message())
+        .await
+        .expect_err("publishing to a missing topic must fail");
+
+    match err {
+        Error::Infrastructure(InfrastructureError::KafkaDeliveryError {
+            code, retriable, ..
+        }) => {
+            assert!(
+                code == "UnknownTopic" || code == "UnknownTopicOrPartition",
+                "unexpected code {}",
+                code
+            );
+            assert!(!retriable);
+        }
+        other => panic!("expected a delivery error, got {:?}", other),
+    }
+}
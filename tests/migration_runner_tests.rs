@@ -0,0 +1,175 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/migration_runner_tests.rs
+
+//! Integration tests for the migration runner
+//!
+//! The tests run a small set of migrations of their own, the second of
+//! which always fails, rather than the embedded ones.
+//!
+//! They start PostgreSQL with testcontainers and require a running Docker
+//! daemon, so they are ignored by default. Set `XZEPR_TEST_DATABASE_URL` to
+//! run them against an existing server instead; each test creates a
+//! database of its own there:
+//!
+//! ```bash
+//! cargo test --test migration_runner_tests -- --ignored
+//! ```
+
+mod common;
+
+use common::start_empty_postgres;
+use serde_json::Value;
+use sqlx::migrate::{Migration, MigrationType, Migrator};
+use sqlx::PgPool;
+use std::borrow::Cow;
+use std::path::PathBuf;
+use ulid::Ulid;
+use xzepr::infrastructure::migrations::MigrationOutcome;
+use xzepr::infrastructure::startup::DependencyError;
+use xzepr::infrastructure::MigrationRunner;
+
+fn migration(version: i64, description: &str, sql: &str) -> Migration {
+    Migration::new(
+        version,
+        Cow::Owned(description.to_string()),
+        MigrationType::Simple,
+        Cow::Owned(sql.to_string()),
+        false,
+    )
+}
+
+/// Three migrations; the second divides by zero
+fn failing_migrator() -> Migrator {
+    Migrator {
+        migrations: Cow::Owned(vec![
+            migration(1, "create widgets", "CREATE TABLE widgets (id INTEGER);"),
+            migration(
+                2,
+                "backfill widgets",
+                "INSERT INTO widgets (id) VALUES (1);\nSELECT 1 / 0;",
+            ),
+            migration(
+                3,
+                "index widgets",
+                "CREATE INDEX idx_widgets_id ON widgets (id);",
+            ),
+        ]),
+        ..Migrator::DEFAULT
+    }
+}
+
+fn report_path() -> PathBuf {
+    std::env::temp_dir()
+        .join(format!("xzepr-migrations-{}", Ulid::new()))
+        .join("report.json")
+}
+
+async fn table_exists(pool: &PgPool, table: &str) -> bool {
+    sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+        .bind(table)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_failed_migration_writes_structured_report() {
+    let (_container, pool) = start_empty_postgres().await;
+    let migrator = failing_migrator();
+    let path = report_path();
+    let runner = MigrationRunner::new(&migrator).with_report_path(Some(path.clone()));
+
+    let err = runner.run(&pool).await.unwrap_err();
+    let DependencyError::Fatal(message) = err else {
+        panic!("expected a fatal error, got {:?}", err);
+    };
+    assert!(
+        message.contains("migration 2 (backfill widgets) failed"),
+        "{}",
+        message
+    );
+    assert!(message.contains("schema version 1"), "{}", message);
+
+    let report: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(report["outcome"], "failed");
+    assert_eq!(report["schema_version_before"], Value::Null);
+    assert_eq!(report["schema_version"], 1);
+    assert_eq!(report["applied"][0]["version"], 1);
+    assert_eq!(report["applied"].as_array().unwrap().len(), 1);
+    assert_eq!(report["failed_migration"]["version"], 2);
+    assert_eq!(
+        report["failed_migration"]["description"],
+        "backfill widgets"
+    );
+    assert_eq!(report["failed_migration"]["safe_to_rerun"], true);
+    assert!(report["failed_migration"]["error"]
+        .as_str()
+        .unwrap()
+        .contains("division by zero"));
+    let pending: Vec<i64> = report["pending"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["version"].as_i64().unwrap())
+        .collect();
+    assert_eq!(pending, vec![2, 3]);
+
+    // The failed migration ran in a transaction and left nothing behind
+    let widgets: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM widgets")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(widgets, 0);
+    let status = runner.status(&pool).await.unwrap();
+    assert_eq!(status.schema_version, Some(1));
+    assert!(!status.is_current());
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_status_lists_applied_and_pending_migrations() {
+    let (_container, pool) = start_empty_postgres().await;
+    let migrator = failing_migrator();
+    let runner = MigrationRunner::new(&migrator);
+    runner.run(&pool).await.unwrap_err();
+
+    let status = runner.status(&pool).await.unwrap();
+
+    assert_eq!(
+        status.to_string(),
+        "Schema version: 1 (1 applied, 2 pending)\n\
+         applied  1               create widgets\n\
+         pending  2               backfill widgets\n\
+         pending  3               index widgets\n"
+    );
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_dry_run_applies_nothing() {
+    let (_container, pool) = start_empty_postgres().await;
+    let migrator = failing_migrator();
+    let path = report_path();
+    let runner = MigrationRunner::new(&migrator).with_report_path(Some(path.clone()));
+
+    let report = runner.dry_run(&pool).await.unwrap();
+
+    assert_eq!(report.outcome, MigrationOutcome::DryRun);
+    assert_eq!(report.schema_version, None);
+    assert!(report.applied.is_empty());
+    let pending: Vec<i64> = report.pending.iter().map(|m| m.version).collect();
+    assert_eq!(pending, vec![1, 2, 3]);
+    assert!(!table_exists(&pool, "_sqlx_migrations").await);
+    assert!(!table_exists(&pool, "widgets").await);
+
+    let written: Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(written["outcome"], "dry_run");
+    assert_eq!(written["pending"].as_array().unwrap().len(), 3);
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,204 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/payload_interning_tests.rs
+
+//! Integration tests for sharing large event payloads by content hash
+//!
+//! These tests start PostgreSQL with testcontainers and require a running
+//! Docker daemon, so they are ignored by default:
+//!
+//! ```bash
+//! cargo test --test payload_interning_tests -- --ignored
+//! ```
+
+mod common;
+
+use common::start_postgres;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::sync::Arc;
+use xzepr::application::handlers::EventPayloadCollector;
+use xzepr::domain::entities::event::{CreateEventParams, DatabaseEventFields, Event};
+use xzepr::domain::repositories::event_payload_repo::EventPayloadRepository;
+use xzepr::domain::repositories::event_repo::{EventRepository, FindEventCriteria};
+use xzepr::domain::value_objects::{EventReceiverId, UserId};
+use xzepr::fixtures::ReceiverFixture;
+use xzepr::infrastructure::database::{PostgresEventReceiverRepository, PostgresEventRepository};
+
+/// Payloads above this many bytes are interned
+const THRESHOLD: usize = 1024;
+
+/// Saves a receiver for the events to belong to
+async fn save_receiver(pool: &PgPool) -> EventReceiverId {
+    ReceiverFixture::new()
+        .persist(&PostgresEventReceiverRepository::new(pool.clone()))
+        .await
+        .id()
+}
+
+fn event(receiver_id: EventReceiverId, payload: Value) -> Event {
+    Event::new(CreateEventParams {
+        name: "release.sbom".to_string(),
+        version: "1.0.0".to_string(),
+        release: "2025.05".to_string(),
+        platform_id: "linux".to_string(),
+        package: "xzepr".to_string(),
+        description: "SBOM attached to a build".to_string(),
+        payload,
+        success: true,
+        receiver_id,
+        owner_id: UserId::new(),
+    })
+    .unwrap()
+}
+
+/// A payload well above the threshold
+fn sbom() -> Value {
+    let components: Vec<Value> = (0..50)
+        .map(|i| json!({"name": format!("component-{}", i), "version": "1.0.0", "licenses": ["Apache-2.0"]}))
+        .collect();
+    json!({"bomFormat": "CycloneDX", "specVersion": "1.5", "components": components})
+}
+
+/// Returns the stored payloads as (ref_count, size_bytes)
+async fn stored_payloads(pool: &PgPool) -> Vec<(i64, i64)> {
+    sqlx::query_as("SELECT ref_count, size_bytes FROM event_payloads ORDER BY hash")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_identical_large_payloads_share_one_blob_until_collected() {
+    let (_postgres, pool) = start_postgres().await;
+    let repo =
+        Arc::new(PostgresEventRepository::new(pool.clone()).with_intern_threshold(THRESHOLD));
+    let collector = EventPayloadCollector::new(repo.clone());
+    let receiver_id = save_receiver(&pool).await;
+
+    // Key order differs, the canonical payload does not
+    let payload = sbom();
+    let reordered: Value = serde_json::from_str(&format!(
+        r#"{{"components": {}, "specVersion": "1.5", "bomFormat": "CycloneDX"}}"#,
+        payload["components"]
+    ))
+    .unwrap();
+    let first = event(receiver_id, payload.clone());
+    let second = event(receiver_id, reordered);
+    let small = event(receiver_id, json!({"status": "passed"}));
+    for e in [&first, &second, &small] {
+        repo.save(e).await.unwrap();
+    }
+
+    let blobs = stored_payloads(&pool).await;
+    assert_eq!(blobs.len(), 1);
+    assert_eq!(blobs[0].0, 2);
+    let size = blobs[0].1 as u64;
+    assert!(size > THRESHOLD as u64);
+    let stats = repo.payload_stats().await.unwrap();
+    assert_eq!(stats.stored_bytes, size);
+    assert_eq!(stats.saved_bytes, size);
+
+    // Only the hash is kept on the event rows, and reads reassemble them
+    let inline: Vec<Option<Value>> =
+        sqlx::query_scalar("SELECT payload FROM events WHERE payload_hash IS NOT NULL")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(inline, vec![None, None]);
+    let found = repo.find_by_id(first.id()).await.unwrap().unwrap();
+    assert_eq!(found.payload(), &payload);
+    let listed = repo
+        .find_by_criteria(FindEventCriteria::new().with_event_receiver_id(receiver_id))
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 3);
+    for e in &listed {
+        let expected = if e.id() == small.id() {
+            small.payload()
+        } else {
+            &payload
+        };
+        assert_eq!(e.payload(), expected);
+    }
+
+    // Deleting one event keeps the blob for the other
+    repo.delete(first.id()).await.unwrap();
+    assert_eq!(collector.run_once().await.unwrap().0.payloads, 0);
+    assert_eq!(stored_payloads(&pool).await, vec![(1, size as i64)]);
+    assert_eq!(
+        repo.find_by_id(second.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .payload(),
+        &payload
+    );
+
+    // Deleting the last reference leaves it for the collector
+    repo.delete(second.id()).await.unwrap();
+    assert_eq!(stored_payloads(&pool).await, vec![(0, size as i64)]);
+    let (collected, stats) = collector.run_once().await.unwrap();
+    assert_eq!(collected.payloads, 1);
+    assert_eq!(collected.bytes, size);
+    assert_eq!(stats.payloads, 0);
+    assert!(stored_payloads(&pool).await.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_interned_payload_round_trips_unicode_and_numbers() {
+    let (_postgres, pool) = start_postgres().await;
+    let repo = PostgresEventRepository::new(pool.clone()).with_intern_threshold(64);
+    let receiver_id = save_receiver(&pool).await;
+
+    let payload = json!({
+        "name": "Grüße, 世界 🚀",
+        "escaped": "tab\tquote\"backslash\\",
+        "integer": 9007199254740993u64,
+        "negative": -42,
+        "float": 0.1,
+        "whole_float": 1.0,
+        "exponent": 1.5e300,
+        "nested": [null, true, {"é": []}],
+        "severity": "HIGH"
+    });
+    let interned = event(receiver_id, payload.clone());
+    repo.save(&interned).await.unwrap();
+    assert_eq!(stored_payloads(&pool).await.len(), 1);
+
+    let found = repo.find_by_id(interned.id()).await.unwrap().unwrap();
+    assert_eq!(found.payload(), &payload);
+    assert!(found.payload()["whole_float"].is_f64());
+    assert_eq!(found.payload()["integer"].as_u64(), Some(9007199254740993));
+
+    // Saving over the event with a small payload releases the shared one
+    let updated = Event::from_database(DatabaseEventFields {
+        id: found.id(),
+        name: found.name().to_string(),
+        version: found.version().to_string(),
+        release: found.release().to_string(),
+        platform_id: found.platform_id().to_string(),
+        package: found.package().to_string(),
+        description: found.description().to_string(),
+        payload: json!({"severity": "low"}),
+        success: found.success(),
+        event_receiver_id: found.event_receiver_id(),
+        owner_id: found.owner_id(),
+        origin: found.origin(),
+        resource_version: found.resource_version() + 1,
+        created_at: found.created_at(),
+    });
+    repo.save(&updated).await.unwrap();
+    assert_eq!(stored_payloads(&pool).await[0].0, 0);
+    assert_eq!(
+        repo.find_by_id(interned.id())
+            .await
+            .unwrap()
+            .unwrap()
+            .payload(),
+        &json!({"severity": "low"})
+    );
+}
@@ -0,0 +1,335 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/query_plan_tests.rs
+
+//! Query plan regression tests for the PostgreSQL repositories
+//!
+//! The tests seed a dataset large enough for the planner to prefer indexes,
+//! run `EXPLAIN (FORMAT JSON)` for each entry of [`catalog`], and fail when
+//! a plan scans one of the [`LARGE_TABLES`] sequentially. Each entry mirrors
+//! the SQL of a repository method; when a repository query is added or its
+//! SQL changes, add or update its entry.
+//!
+//! They start PostgreSQL with testcontainers and require a running Docker
+//! daemon, so they are ignored by default. Set `XZEPR_TEST_DATABASE_URL` to
+//! run them against an existing, empty database instead:
+//!
+//! ```bash
+//! cargo test --test query_plan_tests -- --ignored
+//! ```
+
+mod common;
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use common::start_postgres;
+use serde_json::Value;
+use sqlx::postgres::PgArguments;
+use sqlx::query::Query;
+use sqlx::{PgPool, Postgres, Row};
+
+/// Tables that grow with usage and must never be scanned sequentially
+const LARGE_TABLES: &[&str] = &[
+    "events",
+    "event_receivers",
+    "event_receiver_groups",
+    "event_receiver_group_receivers",
+    "event_receiver_group_members",
+];
+
+/// Columns the event queries select
+const EVENT_COLUMNS: &str = "id, event_receiver_id, name, version, release, \
+     platform_id, package, description, payload, success, \
+     (SELECT p.body FROM event_payloads p WHERE p.hash = payload_hash) AS interned_payload, \
+     created_at, origin";
+
+/// Columns the group queries select
+const GROUP_COLUMNS: &str = "g.id, g.name, g.group_type, g.version, g.description, g.enabled, \
+     g.default_schema, g.dedicated_topic, g.owner_id, g.resource_version, g.created_at, \
+     g.updated_at";
+
+const RECEIVERS: i64 = 5_000;
+const GROUPS: i64 = 1_000;
+const MEMBERS: i64 = 20_000;
+const EVENTS: i64 = 200_000;
+const PLATFORMS: i64 = 50;
+
+/// Parameter bound to a catalog query
+enum Bind {
+    Text(String),
+    TextArray(Vec<String>),
+    Bool(bool),
+    Time(DateTime<Utc>),
+    NullTime,
+    Int(i64),
+}
+
+/// A repository query whose plan is checked
+struct PlanCase {
+    /// Repository method the SQL comes from
+    name: &'static str,
+    sql: String,
+    binds: Vec<Bind>,
+}
+
+impl PlanCase {
+    fn new(name: &'static str, sql: impl Into<String>, binds: Vec<Bind>) -> Self {
+        Self {
+            name,
+            sql: sql.into(),
+            binds,
+        }
+    }
+}
+
+/// Seeded ULID of the `n`th resource, told apart by a Crockford letter
+fn seeded_id(prefix: char, n: i64) -> String {
+    format!("0{}{:0>24}", prefix, n)
+}
+
+/// Representative repository queries, bound to values of the seeded data
+fn catalog() -> Vec<PlanCase> {
+    let receiver = || Bind::Text(seeded_id('R', 42));
+    let now = Utc::now();
+
+    vec![
+        PlanCase::new(
+            "PostgresEventRepository::find_by_receiver_id",
+            format!(
+                "SELECT {EVENT_COLUMNS} FROM events \
+                 WHERE event_receiver_id = $1 ORDER BY created_at DESC"
+            ),
+            vec![receiver()],
+        ),
+        PlanCase::new(
+            "PostgresEventRepository::find_latest_by_receiver_id",
+            format!(
+                "SELECT {EVENT_COLUMNS} FROM events \
+                 WHERE event_receiver_id = $1 ORDER BY created_at DESC LIMIT 1"
+            ),
+            vec![receiver()],
+        ),
+        PlanCase::new(
+            "PostgresEventRepository::find_by_success",
+            format!(
+                "SELECT {EVENT_COLUMNS} FROM events \
+                 WHERE success = $1 ORDER BY created_at DESC"
+            ),
+            vec![Bind::Bool(false)],
+        ),
+        PlanCase::new(
+            "PostgresEventRepository::find_by_platform_id",
+            format!(
+                "SELECT {EVENT_COLUMNS} FROM events \
+                 WHERE platform_id = $1 ORDER BY created_at DESC"
+            ),
+            vec![Bind::Text("platform-7".to_string())],
+        ),
+        PlanCase::new(
+            "PostgresEventRepository::find_by_time_range",
+            format!(
+                "SELECT {EVENT_COLUMNS} FROM events \
+                 WHERE created_at >= $1 AND created_at <= $2 ORDER BY created_at DESC"
+            ),
+            vec![Bind::Time(now - ChronoDuration::days(1)), Bind::Time(now)],
+        ),
+        PlanCase::new(
+            "PostgresEventRepository::find_by_criteria (success, receiver)",
+            format!(
+                "SELECT {EVENT_COLUMNS} FROM events WHERE 1=1 \
+                 AND success = $1 AND event_receiver_id = $2 \
+                 ORDER BY created_at DESC, id DESC LIMIT $3 OFFSET $4"
+            ),
+            vec![Bind::Bool(true), receiver(), Bind::Int(50), Bind::Int(0)],
+        ),
+        PlanCase::new(
+            "PostgresEventRepository::count_by_receiver_since",
+            "SELECT COUNT(*) as count FROM events \
+             WHERE event_receiver_id = $1 AND created_at >= $2 AND origin = $3",
+            vec![
+                receiver(),
+                Bind::Time(now - ChronoDuration::days(7)),
+                Bind::Text("user".to_string()),
+            ],
+        ),
+        PlanCase::new(
+            "PostgresEventRepository::find_release_outcomes",
+            "SELECT event_receiver_id, BOOL_OR(success) AS succeeded, \
+             BOOL_OR(NOT success) AS failed FROM events \
+             WHERE event_receiver_id = ANY($1) AND release = $2 AND origin = $3 \
+             AND ($4::timestamptz IS NULL OR created_at >= $4) \
+             GROUP BY event_receiver_id",
+            vec![
+                Bind::TextArray((40..45).map(|n| seeded_id('R', n)).collect()),
+                Bind::Text("1.42".to_string()),
+                Bind::Text("user".to_string()),
+                Bind::NullTime,
+            ],
+        ),
+        PlanCase::new(
+            "PostgresEventReceiverGroupRepository::find_by_event_receiver_id",
+            format!(
+                "SELECT {GROUP_COLUMNS} FROM event_receiver_groups g \
+                 INNER JOIN event_receiver_group_receivers gr ON g.id = gr.group_id \
+                 WHERE gr.receiver_id = $1 ORDER BY g.created_at DESC"
+            ),
+            vec![receiver()],
+        ),
+        PlanCase::new(
+            "PostgresEventReceiverGroupRepository::find_by_criteria (receiver)",
+            format!(
+                "SELECT {GROUP_COLUMNS} FROM event_receiver_groups g \
+                 INNER JOIN event_receiver_group_receivers gr ON g.id = gr.group_id \
+                 WHERE gr.receiver_id = $1 ORDER BY g.created_at DESC, g.id DESC \
+                 LIMIT $2 OFFSET $3"
+            ),
+            vec![receiver(), Bind::Int(50), Bind::Int(0)],
+        ),
+        PlanCase::new(
+            "PostgresEventReceiverGroupRepository::find_groups_for_user",
+            format!(
+                "SELECT {GROUP_COLUMNS} FROM event_receiver_groups g \
+                 INNER JOIN event_receiver_group_members m ON g.id = m.group_id \
+                 WHERE m.user_id = $1 AND (m.expires_at IS NULL OR m.expires_at > NOW()) \
+                 ORDER BY g.created_at DESC"
+            ),
+            vec![Bind::Text(seeded_id('M', 4242))],
+        ),
+        PlanCase::new(
+            "PostgresEventReceiverGroupRepository::is_member",
+            "SELECT EXISTS(SELECT 1 FROM event_receiver_group_members \
+             WHERE group_id = $1 AND user_id = $2 \
+             AND (expires_at IS NULL OR expires_at > NOW())) as is_member",
+            vec![
+                Bind::Text(seeded_id('G', 242)),
+                Bind::Text(seeded_id('M', 4242)),
+            ],
+        ),
+    ]
+}
+
+/// Seeds receivers, groups, members, and events, then refreshes statistics
+///
+/// Failed events are rare and platforms, releases, and receivers spread
+/// evenly, the way production data skews.
+async fn seed(pool: &PgPool) {
+    let statements = [
+        format!(
+            "INSERT INTO event_receivers (id, name, receiver_type, version, fingerprint, owner_id, created_at) \
+             SELECT '0R' || lpad(n::text, 24, '0'), 'receiver-' || n, 'ci', '1.0.0', \
+                    md5('receiver-' || n), '0M' || lpad((n % 500)::text, 24, '0'), \
+                    NOW() - n * INTERVAL '1 minute' \
+             FROM generate_series(1, {RECEIVERS}) n"
+        ),
+        format!(
+            "INSERT INTO event_receiver_groups (id, name, group_type, version, owner_id, created_at) \
+             SELECT '0G' || lpad(n::text, 24, '0'), 'group-' || n, 'release', '1.0.0', \
+                    '0M' || lpad((n % 500)::text, 24, '0'), NOW() - n * INTERVAL '1 minute' \
+             FROM generate_series(1, {GROUPS}) n"
+        ),
+        format!(
+            "INSERT INTO event_receiver_group_receivers (group_id, receiver_id) \
+             SELECT '0G' || lpad((n % {GROUPS} + 1)::text, 24, '0'), '0R' || lpad(n::text, 24, '0') \
+             FROM generate_series(1, {RECEIVERS}) n"
+        ),
+        format!(
+            "INSERT INTO event_receiver_group_members (group_id, user_id, added_by) \
+             SELECT '0G' || lpad((n % {GROUPS} + 1)::text, 24, '0'), '0M' || lpad(n::text, 24, '0'), \
+                    '0A' || lpad(n::text, 24, '0') \
+             FROM generate_series(1, {MEMBERS}) n"
+        ),
+        format!(
+            "INSERT INTO events (id, name, version, release, platform_id, package, success, \
+                                 event_receiver_id, owner_id, created_at) \
+             SELECT '0E' || lpad(n::text, 24, '0'), 'build.finished', '1.0.0', \
+                    '1.' || (n % 100), 'platform-' || (n % {PLATFORMS}), 'xzepr', n % 20 <> 0, \
+                    '0R' || lpad((n % {RECEIVERS} + 1)::text, 24, '0'), \
+                    '0M' || lpad((n % 500)::text, 24, '0'), NOW() - n * INTERVAL '150 seconds' \
+             FROM generate_series(1, {EVENTS}) n"
+        ),
+        "ANALYZE".to_string(),
+    ];
+    for statement in statements {
+        sqlx::query(&statement)
+            .execute(pool)
+            .await
+            .unwrap_or_else(|e| panic!("failed to seed with {}: {}", statement, e));
+    }
+}
+
+fn bind_all<'q>(
+    mut query: Query<'q, Postgres, PgArguments>,
+    binds: &'q [Bind],
+) -> Query<'q, Postgres, PgArguments> {
+    for bind in binds {
+        query = match bind {
+            Bind::Text(value) => query.bind(value),
+            Bind::TextArray(values) => query.bind(values),
+            Bind::Bool(value) => query.bind(value),
+            Bind::Time(value) => query.bind(value),
+            Bind::NullTime => query.bind(None::<DateTime<Utc>>),
+            Bind::Int(value) => query.bind(value),
+        };
+    }
+    query
+}
+
+/// Returns the `EXPLAIN (FORMAT JSON)` plan of `case`
+async fn explain(pool: &PgPool, case: &PlanCase) -> Value {
+    let sql = format!("EXPLAIN (FORMAT JSON) {}", case.sql);
+    let row = bind_all(sqlx::query(&sql), &case.binds)
+        .fetch_one(pool)
+        .await
+        .unwrap_or_else(|e| panic!("failed to explain {}: {}", case.name, e));
+    let plan: Value = row.get(0);
+    plan[0]["Plan"].clone()
+}
+
+/// Collects the large tables a plan node or its children scan sequentially
+fn sequential_scans(node: &Value, scans: &mut Vec<String>) {
+    if node["Node Type"] == "Seq Scan" {
+        if let Some(table) = node["Relation Name"].as_str() {
+            if LARGE_TABLES.contains(&table) {
+                scans.push(table.to_string());
+            }
+        }
+    }
+    if let Some(children) = node["Plans"].as_array() {
+        for child in children {
+            sequential_scans(child, scans);
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_repository_queries_avoid_sequential_scans() {
+    let (_container, pool) = start_postgres().await;
+    seed(&pool).await;
+
+    // The check must catch a query no index serves
+    let unindexed = PlanCase::new(
+        "unindexed description filter",
+        format!("SELECT {EVENT_COLUMNS} FROM events WHERE description = $1"),
+        vec![Bind::Text("nothing".to_string())],
+    );
+    let mut scans = Vec::new();
+    sequential_scans(&explain(&pool, &unindexed).await, &mut scans);
+    assert_eq!(scans, vec!["events".to_string()]);
+
+    let mut failures = Vec::new();
+    for case in catalog() {
+        let plan = explain(&pool, &case).await;
+        let mut scans = Vec::new();
+        sequential_scans(&plan, &mut scans);
+        if !scans.is_empty() {
+            failures.push(format!(
+                "{} scans {} sequentially:\n{}",
+                case.name,
+                scans.join(", "),
+                serde_json::to_string_pretty(&plan).unwrap()
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
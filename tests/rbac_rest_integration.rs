@@ -36,7 +36,11 @@ fn create_test_jwt_service() -> JwtService {
         algorithm: Algorithm::HS256,
         private_key_path: None,
         public_key_path: None,
-        secret_key: Some("test-secret-key-for-testing-only-do-not-use-in-production".to_string()),
+        secret_key: Some(
+            "test-secret-key-for-testing-only-do-not-use-in-production"
+                .to_string()
+                .into(),
+        ),
         enable_token_rotation: false,
         leeway_seconds: 5,
     };
@@ -0,0 +1,196 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/search_tests.rs
+
+//! Integration tests for full-text search
+//!
+//! These tests start PostgreSQL with testcontainers and require a running
+//! Docker daemon, so they are ignored by default:
+//!
+//! ```bash
+//! cargo test --test search_tests -- --ignored
+//! ```
+
+mod common;
+
+use common::start_postgres;
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use xzepr::application::handlers::SearchHandler;
+use xzepr::domain::entities::event_receiver::EventReceiver;
+use xzepr::domain::entities::search::{SearchResourceType, SearchResults};
+use xzepr::domain::repositories::event_receiver_group_repo::EventReceiverGroupRepository;
+use xzepr::domain::repositories::search_repo::SearchViewer;
+use xzepr::domain::value_objects::UserId;
+use xzepr::fixtures::{GroupFixture, ReceiverFixture};
+use xzepr::infrastructure::database::{
+    PostgresEventReceiverGroupRepository, PostgresEventReceiverRepository, PostgresSearchRepository,
+};
+
+async fn save_receiver(
+    pool: &PgPool,
+    name: &str,
+    description: &str,
+    owner_id: UserId,
+) -> EventReceiver {
+    ReceiverFixture::new()
+        .name(name)
+        .description(description)
+        .schema(json!({}))
+        .owner(owner_id)
+        .persist(&PostgresEventReceiverRepository::new(pool.clone()))
+        .await
+}
+
+fn names(results: &SearchResults) -> Vec<&str> {
+    results.hits.iter().map(|hit| hit.name.as_str()).collect()
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_exact_name_ranks_first_with_highlighted_snippets() {
+    let (_container, pool) = start_postgres().await;
+    let owner = UserId::new();
+    save_receiver(
+        &pool,
+        "deploy-notifier",
+        "Posts every deploy, deploy rollback, and deploy approval",
+        owner,
+    )
+    .await;
+    save_receiver(&pool, "deploy", "Receives release builds", owner).await;
+    save_receiver(&pool, "nightly", "Starts a deploy every night", owner).await;
+    save_receiver(&pool, "lint", "Reports style findings", owner).await;
+
+    let handler = SearchHandler::new(Arc::new(PostgresSearchRepository::new(pool)));
+    let results = handler
+        .search(
+            "deploy",
+            &[SearchResourceType::Receiver],
+            &SearchViewer::admin(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(names(&results)[0], "deploy");
+    assert!(results.hits[0].exact_name);
+    assert_eq!(results.hits.len(), 3);
+    assert_eq!(results.counts.receivers, 3);
+
+    let nightly = results.hits.iter().find(|h| h.name == "nightly").unwrap();
+    assert!(
+        nightly.snippet.contains("<mark>deploy</mark>"),
+        "snippet {:?} does not highlight the match",
+        nightly.snippet
+    );
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_results_only_include_readable_resources() {
+    let (_container, pool) = start_postgres().await;
+    let owner = UserId::new();
+    let outsider = UserId::new();
+    let receiver = save_receiver(&pool, "billing-sync", "Syncs invoices", owner).await;
+
+    let handler = SearchHandler::new(Arc::new(PostgresSearchRepository::new(pool.clone())));
+    let search = |viewer: SearchViewer| {
+        let handler = handler.clone();
+        async move {
+            handler
+                .search("invoices", &[], &viewer, None)
+                .await
+                .unwrap()
+        }
+    };
+
+    let hidden = search(SearchViewer::new(outsider)).await;
+    assert!(hidden.hits.is_empty());
+    assert_eq!(hidden.counts.receivers, 0);
+
+    assert_eq!(
+        names(&search(SearchViewer::new(owner)).await),
+        ["billing-sync"]
+    );
+    assert_eq!(
+        names(
+            &search(SearchViewer::new(outsider).with_unrestricted(SearchResourceType::Receiver))
+                .await
+        ),
+        ["billing-sync"]
+    );
+
+    // Membership in a group holding the receiver makes it readable
+    let groups = PostgresEventReceiverGroupRepository::new(pool);
+    let group = GroupFixture::new()
+        .name("finance")
+        .group_type("team")
+        .description("Finance integrations")
+        .receiver(&receiver)
+        .owner(owner)
+        .persist(&groups)
+        .await;
+    groups
+        .add_member(group.id(), outsider, owner, None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        names(&search(SearchViewer::new(outsider)).await),
+        ["billing-sync"]
+    );
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_stop_word_queries_are_rejected() {
+    let (_container, pool) = start_postgres().await;
+    let handler = SearchHandler::new(Arc::new(PostgresSearchRepository::new(pool)));
+
+    let result = handler
+        .search("the and of", &[], &SearchViewer::admin(), None)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_search_uses_the_gin_index() {
+    let (_container, pool) = start_postgres().await;
+    sqlx::query(
+        r#"
+        INSERT INTO event_receivers (id, name, receiver_type, version, description,
+                                     fingerprint, owner_id)
+        SELECT lpad(n::text, 26, '0'), 'receiver-' || n, 'webhook', '1.0.0',
+               'Forwards build number ' || n || ' to the dashboard',
+               md5(n::text), $1
+        FROM generate_series(1, 20000) AS n
+        "#,
+    )
+    .bind(UserId::new().to_string())
+    .execute(&pool)
+    .await
+    .unwrap();
+    save_receiver(&pool, "jira-bridge", "Opens tickets", UserId::new()).await;
+    sqlx::query("ANALYZE event_receivers")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let plan: Vec<String> = sqlx::query_scalar(
+        "EXPLAIN SELECT id FROM event_receivers \
+         WHERE search_vector @@ to_tsquery('english', 'jira:*')",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert!(
+        plan.iter()
+            .any(|line| line.contains("idx_event_receivers_search")),
+        "search does not use the index:\n{}",
+        plan.join("\n")
+    );
+}
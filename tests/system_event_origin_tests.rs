@@ -0,0 +1,132 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/system_event_origin_tests.rs
+
+//! Integration tests for keeping system events out of user lists and stats
+//!
+//! These tests start PostgreSQL with testcontainers and require a running
+//! Docker daemon, so they are ignored by default:
+//!
+//! ```bash
+//! cargo test --test system_event_origin_tests -- --ignored
+//! ```
+
+mod common;
+
+use chrono::{Duration as ChronoDuration, Utc};
+use common::start_postgres;
+use serde_json::json;
+use std::sync::Arc;
+use xzepr::application::handlers::system_events::{GROUP_CREATED_EVENT, RECEIVER_CREATED_EVENT};
+use xzepr::application::handlers::{
+    EventHandler, EventReceiverGroupHandler, EventReceiverHandler, EventStatsHandler,
+    SystemEventFactory,
+};
+use xzepr::domain::entities::event::{CreateEventParams, EventOrigin};
+use xzepr::domain::repositories::event_repo::FindEventCriteria;
+use xzepr::domain::value_objects::UserId;
+use xzepr::infrastructure::database::{
+    PostgresEventReceiverGroupRepository, PostgresEventReceiverRepository, PostgresEventRepository,
+    PostgresEventRollupRepository,
+};
+
+#[tokio::test]
+#[ignore = "Requires Docker to run a PostgreSQL container"]
+async fn test_system_events_are_kept_out_of_user_queries() {
+    let (_postgres, pool) = start_postgres().await;
+
+    let event_repo = Arc::new(PostgresEventRepository::new(pool.clone()));
+    let receiver_repo = Arc::new(PostgresEventReceiverRepository::new(pool.clone()));
+    let group_repo = Arc::new(PostgresEventReceiverGroupRepository::new(pool.clone()));
+    let system_events = SystemEventFactory::new().with_store(event_repo.clone());
+
+    let receiver_handler = EventReceiverHandler::new(receiver_repo.clone())
+        .with_system_event_factory(system_events.clone());
+    let group_handler = EventReceiverGroupHandler::new(group_repo, receiver_repo.clone())
+        .with_system_event_factory(system_events);
+    let event_handler = EventHandler::new(event_repo.clone(), receiver_repo);
+    let stats = EventStatsHandler::new(
+        event_repo,
+        Arc::new(PostgresEventRollupRepository::new(pool)),
+    );
+
+    let owner = UserId::new();
+    let receiver_id = receiver_handler
+        .create_event_receiver(
+            "builds".to_string(),
+            "ci".to_string(),
+            "1.0.0".to_string(),
+            "Build results".to_string(),
+            json!({}),
+            owner,
+        )
+        .await
+        .unwrap();
+
+    event_handler
+        .create_event(CreateEventParams {
+            name: "build.finished".to_string(),
+            version: "1.0.0".to_string(),
+            release: "1.0.0".to_string(),
+            platform_id: "linux".to_string(),
+            package: "xzepr".to_string(),
+            description: "Build finished".to_string(),
+            payload: json!({"status": "passed"}),
+            success: true,
+            receiver_id,
+            owner_id: owner,
+        })
+        .await
+        .unwrap();
+
+    // The group event targets its first member, the receiver above
+    group_handler
+        .create_event_receiver_group(
+            "builds".to_string(),
+            "ci".to_string(),
+            "1.0.0".to_string(),
+            "Build group".to_string(),
+            true,
+            vec![receiver_id],
+            Vec::new(),
+            None,
+            None,
+            owner,
+        )
+        .await
+        .unwrap();
+
+    // Stats only count the user event, from raw events and the rollup alike
+    let now = Utc::now();
+    let counts = stats
+        .receiver_counts(
+            now - ChronoDuration::days(1),
+            now + ChronoDuration::hours(1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(counts.len(), 1);
+    assert_eq!(counts[0].receiver_id, receiver_id);
+    assert_eq!(counts[0].successful, 1);
+    assert_eq!(counts[0].failed, 0);
+
+    let user_events = event_handler
+        .find_by_criteria(FindEventCriteria::new().with_origin(EventOrigin::User))
+        .await
+        .unwrap();
+    assert_eq!(user_events.len(), 1);
+    assert_eq!(user_events[0].name(), "build.finished");
+
+    // The admin filter surfaces the system events, with their origin intact
+    let system_events = event_handler
+        .find_by_criteria(FindEventCriteria::new().with_origin(EventOrigin::System))
+        .await
+        .unwrap();
+    let mut names: Vec<&str> = system_events.iter().map(|e| e.name()).collect();
+    names.sort_unstable();
+    assert_eq!(names, vec![RECEIVER_CREATED_EVENT, GROUP_CREATED_EVENT]);
+    assert!(system_events
+        .iter()
+        .all(|e| e.origin() == EventOrigin::System));
+}
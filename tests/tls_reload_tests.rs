@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2025 Brett Smith <xbcsmith@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+// tests/tls_reload_tests.rs
+
+//! Integration tests for reloading the HTTPS certificate of a running server
+
+use axum::routing::get;
+use axum::Router;
+use std::fs::File;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName};
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+use xzepr::infrastructure::{TlsConfigBuilder, TlsReloader};
+
+/// Writes a new self-signed pair for `localhost` into `dir`, moving the
+/// modification time forward by `generation` seconds so every write is
+/// seen as a change
+fn write_pair(dir: &Path, generation: u64) -> CertificateDer<'static> {
+    let pair = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    for (name, contents) in [
+        ("cert.pem", pair.cert.pem()),
+        ("key.pem", pair.key_pair.serialize_pem()),
+    ] {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(generation))
+            .unwrap();
+    }
+    pair.cert.der().clone()
+}
+
+/// Completes a TLS handshake with `addr`, trusting only `trusted`
+async fn handshake(addr: SocketAddr, trusted: &CertificateDer<'static>) -> std::io::Result<()> {
+    let mut roots = RootCertStore::empty();
+    roots.add(trusted.clone()).unwrap();
+    let config = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    let stream = TcpStream::connect(addr).await?;
+    TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn test_reload_serves_regenerated_certificate() {
+    let _ = tokio_rustls::rustls::crypto::ring::default_provider().install_default();
+    let dir = std::env::temp_dir().join(format!("xzepr-tls-reload-{}", ulid::Ulid::new()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let first = write_pair(&dir, 0);
+
+    let builder = TlsConfigBuilder::new(dir.join("cert.pem"), dir.join("key.pem"));
+    let tls_config = builder.build_rustls_config().unwrap();
+    let mut reloader = TlsReloader::new(builder, tls_config.clone());
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = Router::new().route("/", get(|| async { "ok" }));
+    let server = tokio::spawn(async move {
+        axum_server::from_tcp_rustls(listener, tls_config)
+            .serve(app.into_make_service())
+            .await
+    });
+
+    handshake(addr, &first).await.unwrap();
+
+    let second = write_pair(&dir, 10);
+    assert!(reloader.run_once().unwrap());
+
+    handshake(addr, &second).await.unwrap();
+    assert!(handshake(addr, &first).await.is_err());
+
+    // A broken replacement leaves the renewed certificate in service
+    std::fs::write(dir.join("key.pem"), "not a key").unwrap();
+    File::options()
+        .write(true)
+        .open(dir.join("key.pem"))
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(20))
+        .unwrap();
+    assert!(reloader.run_once().is_err());
+    handshake(addr, &second).await.unwrap();
+
+    server.abort();
+    let _ = std::fs::remove_dir_all(&dir);
+}